@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aquatic_udp_protocol::PeerId;
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::stream::FuturesUnordered;
+use torrust_tracker::core::services::tracker_factory;
+use torrust_tracker::core::Tracker;
+use torrust_tracker_configuration::AnnounceBatching;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_test_helpers::configuration;
+
+const NUMBER_OF_TORRENTS: u8 = 8;
+
+fn unbatched_tracker() -> Arc<Tracker> {
+    Arc::new(tracker_factory(&configuration::ephemeral_with_no_services()))
+}
+
+fn batched_tracker() -> Arc<Tracker> {
+    let mut config = configuration::ephemeral_with_no_services();
+
+    config.core.announce_batching = Some(AnnounceBatching::default());
+
+    Arc::new(tracker_factory(&config))
+}
+
+// Announce peers for a few torrents in parallel (depending on the set worker threads)
+async fn announce_in_parallel(runtime: &tokio::runtime::Runtime, tracker: Arc<Tracker>, samples: u64) -> Duration {
+    let handles = FuturesUnordered::new();
+
+    let start = Instant::now();
+
+    for sample in 0..samples {
+        let tracker_clone = tracker.clone();
+
+        let handle = runtime.spawn(async move {
+            #[allow(clippy::cast_possible_truncation)]
+            let torrent = (sample % u64::from(NUMBER_OF_TORRENTS)) as u8;
+            let info_hash = InfoHash::from_bytes(&[torrent; 20]);
+
+            let mut peer_id = [b'0'; 20];
+            peer_id[12..].copy_from_slice(&sample.to_be_bytes());
+
+            let peer = PeerBuilder::default().with_peer_id(&PeerId(peer_id)).build();
+
+            tracker_clone.upsert_peer_and_get_stats(&info_hash, &peer);
+        });
+
+        handles.push(handle);
+    }
+
+    // Await all tasks
+    futures::future::join_all(handles).await;
+
+    // Apply the pending updates (if any)
+    tracker.flush_announce_batches();
+
+    start.elapsed()
+}
+
+fn announce(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().worker_threads(4).build().unwrap();
+
+    let mut group = c.benchmark_group("announce_in_parallel");
+
+    group.warm_up_time(Duration::from_millis(500));
+    group.measurement_time(Duration::from_secs(1));
+
+    let tracker = unbatched_tracker();
+
+    group.bench_function("Unbatched", |b| {
+        b.to_async(&rt)
+            .iter_custom(|iters| announce_in_parallel(&rt, tracker.clone(), iters));
+    });
+
+    let tracker = batched_tracker();
+
+    group.bench_function("Batched", |b| {
+        b.to_async(&rt)
+            .iter_custom(|iters| announce_in_parallel(&rt, tracker.clone(), iters));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, announce);
+criterion_main!(benches);
@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_policies (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        info_hash VARCHAR(40) NOT NULL UNIQUE,
+        policy VARCHAR(16) NOT NULL
+    );
\ No newline at end of file
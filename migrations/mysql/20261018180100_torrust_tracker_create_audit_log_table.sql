@@ -0,0 +1,10 @@
+CREATE TABLE
+    IF NOT EXISTS audit_log (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        time BIGINT NOT NULL,
+        token_label VARCHAR(255) NOT NULL,
+        source_ip VARCHAR(45),
+        method VARCHAR(16) NOT NULL,
+        path TEXT NOT NULL,
+        status SMALLINT UNSIGNED NOT NULL
+    );
\ No newline at end of file
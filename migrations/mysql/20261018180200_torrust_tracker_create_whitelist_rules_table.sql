@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS whitelist_rules (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        pattern VARCHAR(41) NOT NULL,
+        valid_until BIGINT,
+        comment TEXT
+    );
\ No newline at end of file
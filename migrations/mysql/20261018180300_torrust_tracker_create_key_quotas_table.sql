@@ -0,0 +1,9 @@
+CREATE TABLE
+    IF NOT EXISTS `key_quotas` (
+        `id` INT NOT NULL AUTO_INCREMENT,
+        `key` VARCHAR(32) NOT NULL,
+        `max_announces_per_day` INT UNSIGNED,
+        `max_peers_per_day` INT UNSIGNED,
+        PRIMARY KEY (`id`),
+        UNIQUE (`key`)
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_static_peers (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        info_hash VARCHAR(40) NOT NULL,
+        peer_addr VARCHAR(64) NOT NULL,
+        UNIQUE (info_hash, peer_addr)
+    );
\ No newline at end of file
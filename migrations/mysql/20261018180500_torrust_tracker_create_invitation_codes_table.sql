@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS `invitation_codes` (
+        `id` INT NOT NULL AUTO_INCREMENT,
+        `code` VARCHAR(32) NOT NULL,
+        PRIMARY KEY (`id`),
+        UNIQUE (`code`)
+    );
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `keys` ADD COLUMN `bound_to` VARCHAR(49);
\ No newline at end of file
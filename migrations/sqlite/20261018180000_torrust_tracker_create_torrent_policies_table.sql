@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_policies (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        info_hash TEXT NOT NULL UNIQUE,
+        policy TEXT NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,10 @@
+CREATE TABLE
+    IF NOT EXISTS audit_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        time INTEGER NOT NULL,
+        token_label TEXT NOT NULL,
+        source_ip TEXT,
+        method TEXT NOT NULL,
+        path TEXT NOT NULL,
+        status INTEGER NOT NULL
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS whitelist_rules (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        pattern TEXT NOT NULL,
+        valid_until INTEGER,
+        comment TEXT
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS key_quotas (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL UNIQUE,
+        max_announces_per_day INTEGER,
+        max_peers_per_day INTEGER
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_static_peers (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        info_hash TEXT NOT NULL,
+        peer_addr TEXT NOT NULL,
+        UNIQUE (info_hash, peer_addr)
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS invitation_codes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        code TEXT NOT NULL UNIQUE
+    );
\ No newline at end of file
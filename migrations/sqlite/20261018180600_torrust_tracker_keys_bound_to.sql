@@ -0,0 +1 @@
+ALTER TABLE keys ADD COLUMN bound_to TEXT;
\ No newline at end of file
@@ -3,25 +3,45 @@
 //! This module contains the configuration data structures for the
 //! Torrust Tracker, which is a `BitTorrent` tracker server.
 //!
-//! The current version for configuration is [`v2`].
+//! The current version for configuration is [`v2`]. The configuration files
+//! in the version `1` format can be converted with the [`migration`] module.
+pub mod migration;
+pub mod paths;
 pub mod v2_0_0;
 pub mod validator;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::env;
+use std::net::{AddrParseError, IpAddr, SocketAddr, ToSocketAddrs};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
+use std::{env, fmt};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use derive_more::{Constructor, Display};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{serde_as, DeserializeFromStr, SerializeDisplay};
 use thiserror::Error;
 use torrust_tracker_located_error::{DynError, LocatedError};
 
 /// The maximum number of returned peers for a torrent.
 pub const TORRENT_PEERS_LIMIT: usize = 74;
 
+/// The default maximum number of torrents that can be scraped at once.
+///
+/// The [BEP 15. UDP Tracker Protocol for `BitTorrent`](https://www.bittorrent.org/beps/bep_0015.html)
+/// defines this limit:
+///
+/// "Up to about 74 torrents can be scraped at once. A full scrape can't be done
+/// with this protocol."
+///
+/// The [BEP 48. Tracker Protocol Extension: Scrape](https://www.bittorrent.org/beps/bep_0048.html)
+/// does not specifically mention this limit, but the limit is being used for
+/// both the UDP and HTTP trackers since it's applied at the domain level.
+pub const MAX_SCRAPE_TORRENTS: u16 = 74;
+
 /// Default timeout for sending and receiving packets. And waiting for sockets
 /// to be readable and writable.
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
@@ -36,21 +56,66 @@ const ENV_VAR_CONFIG_TOML: &str = "TORRUST_TRACKER_CONFIG_TOML";
 pub const ENV_VAR_CONFIG_TOML_PATH: &str = "TORRUST_TRACKER_CONFIG_TOML_PATH";
 
 pub type Configuration = v2_0_0::Configuration;
+pub type ConfigurationBuilder = v2_0_0::builder::Builder;
+pub type ConfigurationBuildError = v2_0_0::builder::BuildError;
 pub type Core = v2_0_0::core::Core;
+pub type AnnounceBatching = v2_0_0::core::AnnounceBatching;
+pub type AuthenticatedAnnouncePolicy = v2_0_0::core::AuthenticatedAnnouncePolicy;
+pub type AnnounceExport = v2_0_0::core::AnnounceExport;
+pub type AnnounceJournal = v2_0_0::core::AnnounceJournal;
+pub type JournalFsync = v2_0_0::core::JournalFsync;
+pub type AnnounceResponsePadding = v2_0_0::core::AnnounceResponsePadding;
+pub type AsyncAnnounce = v2_0_0::core::AsyncAnnounce;
+pub type AuthorizationCache = v2_0_0::core::AuthorizationCache;
+pub type ConsistencyCheck = v2_0_0::core::ConsistencyCheck;
+pub type DatabaseAuthorization = v2_0_0::core::DatabaseAuthorization;
+pub type DatabaseBackup = v2_0_0::core::DatabaseBackup;
+pub type DeadLetterQueue = v2_0_0::core::DeadLetterQueue;
+pub type EventWebhook = v2_0_0::core::EventWebhook;
+pub type FailureReasons = v2_0_0::core::FailureReasons;
+pub type FaultInjection = v2_0_0::core::FaultInjection;
+pub type FileDescriptors = v2_0_0::core::FileDescriptors;
+pub type Maintenance = v2_0_0::core::Maintenance;
+pub type NegativeCache = v2_0_0::core::NegativeCache;
+pub type PeerReachability = v2_0_0::core::PeerReachability;
+pub type Preflight = v2_0_0::core::Preflight;
+pub type ReadSnapshot = v2_0_0::core::ReadSnapshot;
+pub type RequestDeadline = v2_0_0::core::RequestDeadline;
+pub type ServiceManifest = v2_0_0::core::ServiceManifest;
+pub type Slo = v2_0_0::core::Slo;
+pub type SlowRequests = v2_0_0::core::SlowRequests;
+pub type Statistics = v2_0_0::core::Statistics;
+pub type SwarmChanges = v2_0_0::core::SwarmChanges;
 pub type HealthCheckApi = v2_0_0::health_check_api::HealthCheckApi;
+pub type Seed = v2_0_0::seed::Seed;
+pub type SeedKey = v2_0_0::seed::SeedKey;
+pub type SeedTorrent = v2_0_0::seed::SeedTorrent;
 pub type HttpApi = v2_0_0::tracker_api::HttpApi;
+pub type ApiAuth = v2_0_0::tracker_api::ApiAuth;
+pub type Registration = v2_0_0::tracker_api::Registration;
+pub type ApiPermission = v2_0_0::tracker_api::ApiPermission;
 pub type HttpTracker = v2_0_0::http_tracker::HttpTracker;
+pub type KeyPathFormat = v2_0_0::http_tracker::KeyPathFormat;
+pub type Health = v2_0_0::http_tracker::Health;
+pub type HealthCheck = v2_0_0::http_tracker::HealthCheck;
+pub type RequestLimits = v2_0_0::http_tracker::RequestLimits;
 pub type UdpTracker = v2_0_0::udp_tracker::UdpTracker;
+pub type UdpRuntime = v2_0_0::udp_tracker::UdpRuntime;
+pub type VirtualTracker = v2_0_0::virtual_tracker::VirtualTracker;
 pub type Database = v2_0_0::database::Database;
 pub type Driver = v2_0_0::database::Driver;
 pub type Threshold = v2_0_0::logging::Threshold;
+pub type LogDeduplication = v2_0_0::logging::LogDeduplication;
 
 pub type AccessTokens = HashMap<String, String>;
 
+/// The placeholder for the authentication key in the API `announce_urls`.
+pub const ANNOUNCE_URL_KEY_PLACEHOLDER: &str = v2_0_0::tracker_api::ANNOUNCE_URL_KEY_PLACEHOLDER;
+
 pub const LATEST_VERSION: &str = "2.0.0";
 
 /// Info about the configuration specification.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Display, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Display, Clone)]
 #[display("Metadata(app: {app}, purpose: {purpose}, schema_version: {schema_version})")]
 pub struct Metadata {
     /// The application this configuration is valid for.
@@ -91,20 +156,20 @@ impl Metadata {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Display, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Display, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub enum App {
     TorrustTracker,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Display, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Display, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum Purpose {
     Configuration,
 }
 
 /// The configuration version.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Display, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Display, Clone)]
 #[serde(rename_all = "lowercase")]
 pub struct Version {
     #[serde(default = "Version::default_semver")]
@@ -137,7 +202,7 @@ impl Version {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Constructor)]
 pub struct TrackerPolicy {
     // Cleanup job configuration
     /// Maximum time in seconds that a peer can be inactive before being
@@ -158,6 +223,34 @@ pub struct TrackerPolicy {
     /// enabled.
     #[serde(default = "TrackerPolicy::default_remove_peerless_torrents")]
     pub remove_peerless_torrents: bool,
+
+    /// Maximum time in seconds that a torrent can go without announces before
+    /// being considered a stale torrent. If set, the clean up torrent job
+    /// removes stale torrents even if they still have peers or completed
+    /// downloads. It's disabled by default.
+    #[serde(default = "TrackerPolicy::default_max_torrent_inactivity")]
+    pub max_torrent_inactivity: Option<u32>,
+
+    /// Maximum number of peers kept in the swarm of a torrent. When the limit
+    /// is reached, the `peer_eviction_policy` decides what happens with the
+    /// peers announcing for the first time. There is no limit by default.
+    #[serde(default = "TrackerPolicy::default_max_peers_per_torrent")]
+    pub max_peers_per_torrent: Option<u32>,
+
+    /// What to do when a new peer announces to a torrent that already has
+    /// `max_peers_per_torrent` peers. Peers already in the swarm can always
+    /// update their announce.
+    #[serde(default = "TrackerPolicy::default_peer_eviction_policy")]
+    pub peer_eviction_policy: PeerEvictionPolicy,
+
+    /// If enabled, a client announcing the same peer ID from an IPv4 and an
+    /// IPv6 address to the same torrent is merged into one peer with two
+    /// addresses. It's counted only once in the torrent metrics, so dual-stack
+    /// seeders are not counted twice, but both addresses are still returned
+    /// to the other peers. It's disabled by default, and the latest announce
+    /// replaces the previous address of the peer.
+    #[serde(default = "TrackerPolicy::default_merge_dual_stack_peers")]
+    pub merge_dual_stack_peers: bool,
 }
 
 impl Default for TrackerPolicy {
@@ -166,6 +259,10 @@ impl Default for TrackerPolicy {
             max_peer_timeout: Self::default_max_peer_timeout(),
             persistent_torrent_completed_stat: Self::default_persistent_torrent_completed_stat(),
             remove_peerless_torrents: Self::default_remove_peerless_torrents(),
+            max_torrent_inactivity: Self::default_max_torrent_inactivity(),
+            max_peers_per_torrent: Self::default_max_peers_per_torrent(),
+            peer_eviction_policy: Self::default_peer_eviction_policy(),
+            merge_dual_stack_peers: Self::default_merge_dual_stack_peers(),
         }
     }
 }
@@ -182,6 +279,37 @@ impl TrackerPolicy {
     fn default_remove_peerless_torrents() -> bool {
         true
     }
+
+    fn default_max_torrent_inactivity() -> Option<u32> {
+        None
+    }
+
+    fn default_max_peers_per_torrent() -> Option<u32> {
+        None
+    }
+
+    fn default_peer_eviction_policy() -> PeerEvictionPolicy {
+        PeerEvictionPolicy::RejectNew
+    }
+
+    fn default_merge_dual_stack_peers() -> bool {
+        false
+    }
+}
+
+/// The policy applied when a new peer announces to a torrent whose swarm is
+/// already full. See [`TrackerPolicy::max_peers_per_torrent`].
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerEvictionPolicy {
+    /// The new peer is not added to the swarm.
+    RejectNew,
+    /// The peer with the oldest announce is removed to make room for the new
+    /// one.
+    EvictOldest,
+    /// A random leecher is removed to make room for the new peer. Seeders are
+    /// never evicted, so the new peer is rejected when there are no leechers.
+    EvictRandomLeecher,
 }
 
 /// Information required for loading config
@@ -189,6 +317,7 @@ impl TrackerPolicy {
 pub struct Info {
     config_toml: Option<String>,
     config_toml_path: String,
+    path_variables: paths::Variables,
 }
 
 impl Info {
@@ -196,7 +325,8 @@ impl Info {
     ///
     /// # Errors
     ///
-    /// Will return `Err` if unable to obtain a configuration.
+    /// Will return `Err` if unable to obtain a configuration, or the layout
+    /// of the [path variables](paths) is unknown.
     ///
     #[allow(clippy::needless_pass_by_value)]
     pub fn new(default_config_toml_path: String) -> Result<Self, Error> {
@@ -221,12 +351,37 @@ impl Info {
         Ok(Self {
             config_toml,
             config_toml_path,
+            path_variables: paths::Variables::from_env()?,
         })
     }
+
+    /// It sets the path of the config file, overriding the one in the
+    /// `TORRUST_TRACKER_CONFIG_TOML_PATH` environment variable.
+    #[must_use]
+    pub fn with_config_toml_path(mut self, config_toml_path: String) -> Self {
+        self.config_toml_path = config_toml_path;
+        self
+    }
+
+    /// It sets the layout of the [path variables](paths), overriding the one
+    /// in the `TORRUST_TRACKER_PATHS_LAYOUT` environment variable.
+    #[must_use]
+    pub fn with_paths_layout(mut self, layout: paths::Layout) -> Self {
+        self.path_variables = self.path_variables.with_layout(layout);
+        self
+    }
+
+    /// It sets the value of a [path variable](paths), overriding the one in
+    /// its environment variable.
+    #[must_use]
+    pub fn with_path_variable(mut self, variable: paths::Variable, value: String) -> Self {
+        self.path_variables = self.path_variables.with(variable, value);
+        self
+    }
 }
 
 /// Announce policy
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
 pub struct AnnouncePolicy {
     /// Interval in seconds that the client should wait between sending regular
     /// announce requests to the tracker.
@@ -306,6 +461,15 @@ pub enum Error {
 
     #[error("Missing mandatory configuration option. Option path: {path}")]
     MissingMandatoryOption { path: String },
+
+    #[error("Unable to resolve the bind address {bind_address}: {source}")]
+    UnresolvedBindAddress { bind_address: String, source: ResolveError },
+
+    #[error("Unknown path variable in the configuration: {name}")]
+    UnknownPathVariable { name: String },
+
+    #[error("Unknown paths layout: {layout}. It must be `local` or `fhs`")]
+    UnknownPathsLayout { layout: String },
 }
 
 impl From<figment::Error> for Error {
@@ -318,14 +482,16 @@ impl From<figment::Error> for Error {
 }
 
 #[serde_as]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Default)]
 pub struct TslConfig {
     /// Path to the SSL certificate file.
     #[serde(default = "TslConfig::default_ssl_cert_path")]
+    #[schemars(with = "String")]
     pub ssl_cert_path: Utf8PathBuf,
 
     /// Path to the SSL key file.
     #[serde(default = "TslConfig::default_ssl_key_path")]
+    #[schemars(with = "String")]
     pub ssl_key_path: Utf8PathBuf,
 }
 
@@ -340,3 +506,495 @@ impl TslConfig {
         Utf8PathBuf::new()
     }
 }
+
+/// The address a service binds to. It's shared by all the services.
+///
+/// It can be:
+///
+/// - A TCP or UDP socket address like `0.0.0.0:7070`.
+/// - A hostname and a port like `tracker.local:7070`. The hostname is resolved
+///   when the configuration is loaded.
+/// - A network interface name and a port with the `iface:` prefix like
+///   `iface:eth0:7070`. It's resolved to the first IPv4 address of the
+///   interface (or the first IPv6 address if it does not have any) when the
+///   configuration is loaded.
+/// - A Unix domain socket path with the `unix:` prefix like
+///   `unix:/var/run/torrust/api.sock`. Only for the HTTP services (tracker
+///   REST API and HTTP trackers).
+#[derive(SerializeDisplay, DeserializeFromStr, PartialEq, Eq, Debug, Clone)]
+pub enum BindAddress {
+    Socket(SocketAddr),
+    Host { host: String, port: u16 },
+    Interface { name: String, port: u16 },
+    Unix(Utf8PathBuf),
+}
+
+impl BindAddress {
+    /// Prefix for Unix domain socket paths.
+    pub const UNIX_PREFIX: &'static str = "unix:";
+
+    /// Prefix for network interface names.
+    pub const INTERFACE_PREFIX: &'static str = "iface:";
+
+    /// It returns the socket address, if it's a socket address or it has been
+    /// [resolved](BindAddress::resolve).
+    #[must_use]
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        match self {
+            BindAddress::Socket(socket_addr) => Some(*socket_addr),
+            BindAddress::Host { .. } | BindAddress::Interface { .. } | BindAddress::Unix(_) => None,
+        }
+    }
+
+    /// It returns the Unix domain socket path, if it's a Unix domain socket
+    /// address.
+    #[must_use]
+    pub fn unix_socket_path(&self) -> Option<&Utf8Path> {
+        match self {
+            BindAddress::Unix(path) => Some(path),
+            BindAddress::Socket(_) | BindAddress::Host { .. } | BindAddress::Interface { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_unix(&self) -> bool {
+        matches!(self, BindAddress::Unix(_))
+    }
+
+    /// It resolves the hostname or the network interface name to a socket
+    /// address. Socket addresses and Unix domain socket paths are returned as
+    /// they are.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the hostname can't be resolved, or the network
+    /// interface does not exist or it does not have any IP address.
+    pub fn resolve(&self) -> Result<BindAddress, ResolveError> {
+        match self {
+            BindAddress::Socket(_) | BindAddress::Unix(_) => Ok(self.clone()),
+            BindAddress::Host { host, port } => (host.as_str(), *port)
+                .to_socket_addrs()
+                .map_err(|e| ResolveError::UnresolvedHost { reason: e.to_string() })?
+                .next()
+                .map(BindAddress::Socket)
+                .ok_or(ResolveError::HostWithoutAddresses),
+            BindAddress::Interface { name, port } => {
+                let addresses: Vec<IpAddr> = local_ip_address::list_afinet_netifas()
+                    .map_err(|e| ResolveError::UnavailableInterfaces { reason: e.to_string() })?
+                    .into_iter()
+                    .filter(|(interface, _)| interface == name)
+                    .map(|(_, ip)| ip)
+                    .collect();
+
+                addresses
+                    .iter()
+                    .find(|ip| ip.is_ipv4())
+                    .or(addresses.first())
+                    .map(|ip| BindAddress::Socket(SocketAddr::new(*ip, *port)))
+                    .ok_or_else(|| ResolveError::UnknownInterface { name: name.clone() })
+            }
+        }
+    }
+}
+
+impl JsonSchema for BindAddress {
+    fn schema_name() -> Cow<'static, str> {
+        "BindAddress".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A socket address like `0.0.0.0:7070`, a hostname and a port, a network interface name and a port with the `iface:` prefix, or a Unix domain socket path with the `unix:` prefix (only for the HTTP services).",
+            "examples": ["0.0.0.0:7070", "tracker.local:7070", "iface:eth0:7070", "unix:/var/run/torrust/api.sock"]
+        })
+    }
+}
+
+impl From<SocketAddr> for BindAddress {
+    fn from(socket_addr: SocketAddr) -> Self {
+        BindAddress::Socket(socket_addr)
+    }
+}
+
+impl fmt::Display for BindAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindAddress::Socket(socket_addr) => write!(f, "{socket_addr}"),
+            BindAddress::Host { host, port } => write!(f, "{host}:{port}"),
+            BindAddress::Interface { name, port } => write!(f, "{}{name}:{port}", Self::INTERFACE_PREFIX),
+            BindAddress::Unix(path) => write!(f, "{}{path}", Self::UNIX_PREFIX),
+        }
+    }
+}
+
+/// One or more [`BindAddress`]es. It's used by the UDP and HTTP trackers.
+///
+/// In the configuration, it's a single address like `0.0.0.0:6969` or a list
+/// of them like `["0.0.0.0:6969", "[::]:6969"]`. One listener is started for
+/// each address, but they are reported as a single service.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct BindAddresses {
+    first: BindAddress,
+    others: Vec<BindAddress>,
+}
+
+impl BindAddresses {
+    #[must_use]
+    pub fn new(first: BindAddress, others: Vec<BindAddress>) -> Self {
+        Self { first, others }
+    }
+
+    /// The first address. It identifies the service, for example, in the
+    /// logs.
+    #[must_use]
+    pub fn first(&self) -> &BindAddress {
+        &self.first
+    }
+
+    #[must_use]
+    pub fn first_mut(&mut self) -> &mut BindAddress {
+        &mut self.first
+    }
+
+    /// Whether there is only one address.
+    #[must_use]
+    pub fn is_single(&self) -> bool {
+        self.others.is_empty()
+    }
+
+    /// Whether any of the addresses is a Unix domain socket path.
+    #[must_use]
+    pub fn is_unix(&self) -> bool {
+        self.iter().any(BindAddress::is_unix)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BindAddress> {
+        std::iter::once(&self.first).chain(&self.others)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut BindAddress> {
+        std::iter::once(&mut self.first).chain(&mut self.others)
+    }
+}
+
+impl From<BindAddress> for BindAddresses {
+    fn from(bind_address: BindAddress) -> Self {
+        Self {
+            first: bind_address,
+            others: Vec::new(),
+        }
+    }
+}
+
+impl From<SocketAddr> for BindAddresses {
+    fn from(socket_addr: SocketAddr) -> Self {
+        BindAddress::Socket(socket_addr).into()
+    }
+}
+
+impl FromStr for BindAddresses {
+    type Err = BindAddressError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value.parse::<BindAddress>().map(BindAddresses::from)
+    }
+}
+
+impl fmt::Display for BindAddresses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.first)?;
+
+        for bind_address in &self.others {
+            write!(f, ", {bind_address}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for BindAddresses {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_single() {
+            self.first.serialize(serializer)
+        } else {
+            serializer.collect_seq(self.iter())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BindAddresses {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = BindAddresses;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a bind address or a non-empty list of bind addresses")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse::<BindAddress>().map(BindAddresses::from).map_err(E::custom)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let first = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+
+                let mut others = Vec::new();
+
+                while let Some(bind_address) = seq.next_element()? {
+                    others.push(bind_address);
+                }
+
+                Ok(BindAddresses { first, others })
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl JsonSchema for BindAddresses {
+    fn schema_name() -> Cow<'static, str> {
+        "BindAddresses".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let bind_address = generator.subschema_for::<BindAddress>();
+
+        json_schema!({
+            "anyOf": [
+                bind_address,
+                { "type": "array", "items": bind_address, "minItems": 1 }
+            ]
+        })
+    }
+}
+
+/// Errors that can occur when parsing a [`BindAddress`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BindAddressError {
+    #[error("invalid socket address: {0}")]
+    InvalidSocketAddress(#[from] AddrParseError),
+
+    #[error("missing Unix domain socket path after the `unix:` prefix")]
+    MissingUnixSocketPath,
+
+    #[error("missing network interface name after the `iface:` prefix")]
+    MissingInterfaceName,
+
+    #[error("missing port after the network interface name, for example `iface:eth0:6969`")]
+    MissingInterfacePort,
+
+    #[error("invalid port: {0}")]
+    InvalidPort(String),
+}
+
+/// Errors that can occur when resolving a [`BindAddress`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    #[error("unable to resolve the hostname: {reason}")]
+    UnresolvedHost { reason: String },
+
+    #[error("the hostname does not resolve to any address")]
+    HostWithoutAddresses,
+
+    #[error("unable to list the network interfaces: {reason}")]
+    UnavailableInterfaces { reason: String },
+
+    #[error("there is no network interface named `{name}` with an IP address")]
+    UnknownInterface { name: String },
+}
+
+impl FromStr for BindAddress {
+    type Err = BindAddressError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = value.strip_prefix(Self::UNIX_PREFIX) {
+            if path.is_empty() {
+                return Err(BindAddressError::MissingUnixSocketPath);
+            }
+
+            return Ok(BindAddress::Unix(Utf8PathBuf::from(path)));
+        }
+
+        if let Some(interface) = value.strip_prefix(Self::INTERFACE_PREFIX) {
+            let (name, port) = interface.rsplit_once(':').ok_or(BindAddressError::MissingInterfacePort)?;
+
+            if name.is_empty() {
+                return Err(BindAddressError::MissingInterfaceName);
+            }
+
+            return Ok(BindAddress::Interface {
+                name: name.to_string(),
+                port: parse_port(port)?,
+            });
+        }
+
+        match value.parse::<SocketAddr>() {
+            Ok(socket_addr) => Ok(BindAddress::Socket(socket_addr)),
+            Err(error) => match value.rsplit_once(':') {
+                Some((host, port)) if is_hostname(host) => Ok(BindAddress::Host {
+                    host: host.to_string(),
+                    port: parse_port(port)?,
+                }),
+                _ => Err(error.into()),
+            },
+        }
+    }
+}
+
+fn parse_port(port: &str) -> Result<u16, BindAddressError> {
+    port.parse().map_err(|_| BindAddressError::InvalidPort(port.to_string()))
+}
+
+/// A hostname has letters, digits, hyphens and dots, and at least one letter
+/// so that malformed IP addresses are not taken as hostnames.
+fn is_hostname(host: &str) -> bool {
+    host.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.') && host.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+
+    use camino::Utf8PathBuf;
+
+    use crate::{BindAddress, BindAddressError, BindAddresses, ResolveError};
+
+    #[test]
+    fn a_bind_address_should_be_parsed_from_a_socket_address() {
+        assert_eq!(
+            BindAddress::from_str("0.0.0.0:7070"),
+            Ok(BindAddress::Socket("0.0.0.0:7070".parse::<SocketAddr>().unwrap()))
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_be_parsed_from_a_unix_domain_socket_path() {
+        assert_eq!(
+            BindAddress::from_str("unix:/var/run/torrust/api.sock"),
+            Ok(BindAddress::Unix(Utf8PathBuf::from("/var/run/torrust/api.sock")))
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_fail_when_the_unix_domain_socket_path_is_empty() {
+        assert_eq!(BindAddress::from_str("unix:"), Err(BindAddressError::MissingUnixSocketPath));
+    }
+
+    #[test]
+    fn a_bind_address_should_be_parsed_from_a_hostname_and_a_port() {
+        assert_eq!(
+            BindAddress::from_str("localhost:7070"),
+            Ok(BindAddress::Host {
+                host: "localhost".to_string(),
+                port: 7070
+            })
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_be_parsed_from_a_network_interface_name_and_a_port() {
+        assert_eq!(
+            BindAddress::from_str("iface:eth0:6969"),
+            Ok(BindAddress::Interface {
+                name: "eth0".to_string(),
+                port: 6969
+            })
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_fail_when_the_port_is_not_valid() {
+        assert_eq!(
+            BindAddress::from_str("localhost:http"),
+            Err(BindAddressError::InvalidPort("http".to_string()))
+        );
+        assert_eq!(
+            BindAddress::from_str("iface:eth0:70000"),
+            Err(BindAddressError::InvalidPort("70000".to_string()))
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_fail_when_the_network_interface_name_is_empty() {
+        assert_eq!(
+            BindAddress::from_str("iface::6969"),
+            Err(BindAddressError::MissingInterfaceName)
+        );
+    }
+
+    #[test]
+    fn a_malformed_ip_address_should_not_be_taken_as_a_hostname() {
+        assert!(matches!(
+            BindAddress::from_str("0.0.0.300:6969"),
+            Err(BindAddressError::InvalidSocketAddress(_))
+        ));
+    }
+
+    #[test]
+    fn a_bind_address_should_resolve_a_hostname() {
+        let bind_address = BindAddress::from_str("localhost:7070").unwrap().resolve().unwrap();
+
+        assert!(bind_address
+            .socket_addr()
+            .is_some_and(|socket_addr| socket_addr.ip().is_loopback()));
+    }
+
+    #[test]
+    fn a_bind_address_should_fail_resolving_an_unknown_network_interface() {
+        assert_eq!(
+            BindAddress::from_str("iface:missing0:6969").unwrap().resolve(),
+            Err(ResolveError::UnknownInterface {
+                name: "missing0".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn a_bind_address_should_be_displayed_in_the_same_format_it_is_parsed() {
+        for value in [
+            "127.0.0.1:1212",
+            "[::1]:1212",
+            "localhost:1212",
+            "iface:eth0:1212",
+            "unix:/var/run/torrust/api.sock",
+        ] {
+            assert_eq!(BindAddress::from_str(value).unwrap().to_string(), value);
+        }
+    }
+
+    #[test]
+    fn bind_addresses_should_be_serialized_as_a_single_address_when_there_is_only_one() {
+        let bind_addresses = BindAddresses::from_str("0.0.0.0:6969").unwrap();
+
+        assert_eq!(serde_json::to_string(&bind_addresses).unwrap(), r#""0.0.0.0:6969""#);
+        assert_eq!(
+            serde_json::from_str::<BindAddresses>(r#""0.0.0.0:6969""#).unwrap(),
+            bind_addresses
+        );
+    }
+
+    #[test]
+    fn bind_addresses_should_be_serialized_as_a_list_when_there_are_more_than_one() {
+        let bind_addresses = BindAddresses::new(
+            BindAddress::from_str("0.0.0.0:6969").unwrap(),
+            vec![BindAddress::from_str("[::]:6969").unwrap()],
+        );
+
+        let json = r#"["0.0.0.0:6969","[::]:6969"]"#;
+
+        assert_eq!(serde_json::to_string(&bind_addresses).unwrap(), json);
+        assert_eq!(serde_json::from_str::<BindAddresses>(json).unwrap(), bind_addresses);
+    }
+
+    #[test]
+    fn bind_addresses_should_not_be_deserialized_from_an_empty_list() {
+        assert!(serde_json::from_str::<BindAddresses>("[]").is_err());
+    }
+}
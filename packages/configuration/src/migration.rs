@@ -0,0 +1,500 @@
+//! Migration of the configuration files from the version `1` format.
+//!
+//! The version `1` configuration was a flat TOML file, with most of the core
+//! options at the top level:
+//!
+//! ```toml
+//! log_level = "info"
+//! mode = "public"
+//! db_driver = "Sqlite3"
+//! db_path = "./storage/tracker/lib/database/sqlite3.db"
+//! announce_interval = 120
+//! min_announce_interval = 120
+//! on_reverse_proxy = false
+//! external_ip = "0.0.0.0"
+//! tracker_usage_statistics = true
+//! persistent_torrent_completed_stat = false
+//! max_peer_timeout = 900
+//! inactive_peer_cleanup_interval = 600
+//! remove_peerless_torrents = true
+//!
+//! [[udp_trackers]]
+//! enabled = true
+//! bind_address = "0.0.0.0:6969"
+//!
+//! [[http_trackers]]
+//! enabled = true
+//! bind_address = "0.0.0.0:7070"
+//! ssl_enabled = false
+//! ssl_cert_path = ""
+//! ssl_key_path = ""
+//!
+//! [http_api]
+//! enabled = true
+//! bind_address = "127.0.0.1:1212"
+//! ssl_enabled = false
+//! ssl_cert_path = ""
+//! ssl_key_path = ""
+//!
+//! [http_api.access_tokens]
+//! admin = "MyAccessToken"
+//!
+//! [health_check_api]
+//! bind_address = "127.0.0.1:1313"
+//! ```
+//!
+//! [`from_v1`] converts it into the current nested format, only with the
+//! options set in the version `1` file (the rest keep their default values),
+//! and validates the result. For example, `mode = "private_listed"` becomes
+//! `private = true` and `listed = true` in the `[core]` section, and
+//! `db_driver` and `db_path` become the `[core.database]` section.
+//!
+//! Some options can't be migrated. The disabled services are removed, since
+//! the current format has no `enabled` option (a service is enabled when its
+//! section is present), and the unknown options are dropped. The
+//! [`Migration`] includes a warning for each of them.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use figment::providers::{Format, Serialized, Toml};
+use figment::Figment;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::validator::{SemanticValidationError, Validator};
+use crate::Configuration;
+
+/// Errors that can occur migrating a configuration file.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid version 1 configuration: {source}")]
+    InvalidV1Configuration { source: toml::de::Error },
+
+    #[error("Invalid migrated configuration: {source}")]
+    InvalidMigratedConfiguration { source: crate::Error },
+
+    #[error("The migrated configuration is not valid: {source}")]
+    SemanticValidation { source: SemanticValidationError },
+}
+
+/// The result of migrating a version `1` configuration file.
+#[derive(Debug)]
+pub struct Migration {
+    /// The migrated configuration in TOML format.
+    pub toml: String,
+    /// The migrated configuration, with the default values for the options
+    /// that were not in the version `1` file.
+    pub configuration: Configuration,
+    /// The options that could not be migrated.
+    pub warnings: Vec<String>,
+}
+
+/// The tracker mode in the version `1` configuration.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    #[default]
+    Public,
+    Listed,
+    Private,
+    PrivateListed,
+}
+
+#[derive(Deserialize, Debug)]
+struct V1 {
+    log_level: Option<String>,
+    #[serde(default)]
+    mode: Mode,
+    db_driver: Option<String>,
+    db_path: Option<String>,
+    announce_interval: Option<u32>,
+    min_announce_interval: Option<u32>,
+    on_reverse_proxy: Option<bool>,
+    external_ip: Option<String>,
+    tracker_usage_statistics: Option<bool>,
+    persistent_torrent_completed_stat: Option<bool>,
+    max_peer_timeout: Option<u32>,
+    inactive_peer_cleanup_interval: Option<i64>,
+    remove_peerless_torrents: Option<bool>,
+    #[serde(default)]
+    udp_trackers: Vec<V1UdpTracker>,
+    #[serde(default)]
+    http_trackers: Vec<V1HttpService>,
+    http_api: Option<V1HttpService>,
+    health_check_api: Option<V1HealthCheckApi>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct V1UdpTracker {
+    #[serde(default)]
+    enabled: bool,
+    bind_address: String,
+}
+
+/// A version `1` HTTP tracker or HTTP API section.
+#[derive(Deserialize, Debug)]
+struct V1HttpService {
+    #[serde(default)]
+    enabled: bool,
+    bind_address: String,
+    #[serde(default)]
+    ssl_enabled: bool,
+    ssl_cert_path: Option<String>,
+    ssl_key_path: Option<String>,
+    #[serde(default)]
+    access_tokens: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct V1HealthCheckApi {
+    bind_address: String,
+}
+
+/// It converts a version `1` configuration file into the current format.
+///
+/// # Errors
+///
+/// Will return an error if the version `1` configuration can't be parsed, or
+/// the migrated configuration is not valid.
+pub fn from_v1(v1_toml: &str) -> Result<Migration, Error> {
+    let v1: V1 = toml::from_str(v1_toml).map_err(|source| Error::InvalidV1Configuration { source })?;
+
+    let mut warnings: Vec<String> = v1
+        .unknown
+        .keys()
+        .map(|option| format!("The unknown option `{option}` is not migrated"))
+        .collect();
+
+    let mut toml = String::new();
+
+    section(&mut toml, "[metadata]");
+    option(&mut toml, "app", Some("torrust-tracker"));
+    option(&mut toml, "purpose", Some("configuration"));
+    option(&mut toml, "schema_version", Some("2.0.0"));
+
+    section(&mut toml, "[logging]");
+    option(
+        &mut toml,
+        "threshold",
+        Some(v1.log_level.as_deref().unwrap_or("info").to_lowercase()),
+    );
+
+    let (private, listed) = match v1.mode {
+        Mode::Public => (false, false),
+        Mode::Listed => (false, true),
+        Mode::Private => (true, false),
+        Mode::PrivateListed => (true, true),
+    };
+
+    section(&mut toml, "[core]");
+    option(&mut toml, "inactive_peer_cleanup_interval", v1.inactive_peer_cleanup_interval);
+    option(&mut toml, "listed", Some(listed));
+    option(&mut toml, "private", Some(private));
+    option(&mut toml, "tracker_usage_statistics", v1.tracker_usage_statistics);
+
+    if v1.announce_interval.is_some() || v1.min_announce_interval.is_some() {
+        section(&mut toml, "[core.announce_policy]");
+        option(&mut toml, "interval", v1.announce_interval);
+        option(&mut toml, "interval_min", v1.min_announce_interval);
+    }
+
+    if v1.db_driver.is_some() || v1.db_path.is_some() {
+        section(&mut toml, "[core.database]");
+        option(&mut toml, "driver", v1.db_driver.map(|driver| driver.to_lowercase()));
+        option(&mut toml, "path", v1.db_path);
+    }
+
+    if v1.external_ip.is_some() || v1.on_reverse_proxy.is_some() {
+        section(&mut toml, "[core.net]");
+        option(&mut toml, "external_ip", v1.external_ip);
+        option(&mut toml, "on_reverse_proxy", v1.on_reverse_proxy);
+    }
+
+    if v1.max_peer_timeout.is_some() || v1.persistent_torrent_completed_stat.is_some() || v1.remove_peerless_torrents.is_some() {
+        section(&mut toml, "[core.tracker_policy]");
+        option(&mut toml, "max_peer_timeout", v1.max_peer_timeout);
+        option(
+            &mut toml,
+            "persistent_torrent_completed_stat",
+            v1.persistent_torrent_completed_stat,
+        );
+        option(&mut toml, "remove_peerless_torrents", v1.remove_peerless_torrents);
+    }
+
+    for udp_tracker in v1.udp_trackers {
+        if !udp_tracker.enabled {
+            warnings.push(format!(
+                "The disabled UDP tracker on {} is not migrated",
+                udp_tracker.bind_address
+            ));
+            continue;
+        }
+
+        section(&mut toml, "[[udp_trackers]]");
+        option(&mut toml, "bind_address", Some(udp_tracker.bind_address));
+    }
+
+    for http_tracker in v1.http_trackers {
+        if !http_tracker.enabled {
+            warnings.push(format!(
+                "The disabled HTTP tracker on {} is not migrated",
+                http_tracker.bind_address
+            ));
+            continue;
+        }
+
+        section(&mut toml, "[[http_trackers]]");
+        option(&mut toml, "bind_address", Some(http_tracker.bind_address.clone()));
+        tsl_config(&mut toml, "[http_trackers.tsl_config]", &http_tracker);
+    }
+
+    if let Some(http_api) = v1.http_api {
+        if http_api.enabled {
+            section(&mut toml, "[http_api]");
+            option(&mut toml, "bind_address", Some(http_api.bind_address.clone()));
+
+            if !http_api.access_tokens.is_empty() {
+                section(&mut toml, "[http_api.access_tokens]");
+
+                for (user, token) in &http_api.access_tokens {
+                    option(&mut toml, user, Some(token.as_str()));
+                }
+            }
+
+            tsl_config(&mut toml, "[http_api.tsl_config]", &http_api);
+        } else {
+            warnings.push(format!("The disabled HTTP API on {} is not migrated", http_api.bind_address));
+        }
+    }
+
+    if let Some(health_check_api) = v1.health_check_api {
+        section(&mut toml, "[health_check_api]");
+        option(&mut toml, "bind_address", Some(health_check_api.bind_address));
+    }
+
+    let configuration = validate(&toml)?;
+
+    Ok(Migration {
+        toml,
+        configuration,
+        warnings,
+    })
+}
+
+/// It returns the changes from the `old` text to the `new` one, line by line.
+/// Removed lines start with `-`, added lines with `+` and the unchanged ones
+/// with a space.
+#[must_use]
+pub fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            let _ = writeln!(output, " {}", old[i]);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            let _ = writeln!(output, "-{}", old[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(output, "+{}", new[j]);
+            j += 1;
+        }
+    }
+
+    output
+}
+
+/// It loads the migrated configuration like the tracker does, filling the
+/// missing options with the default values, and validates it.
+fn validate(toml: &str) -> Result<Configuration, Error> {
+    let configuration: Configuration = Figment::from(Toml::string(toml))
+        .join(Serialized::defaults(Configuration::default()))
+        .extract()
+        .map_err(|err| Error::InvalidMigratedConfiguration { source: err.into() })?;
+
+    configuration
+        .validate()
+        .map_err(|source| Error::SemanticValidation { source })?;
+
+    Ok(configuration)
+}
+
+fn section(toml: &mut String, header: &str) {
+    if !toml.is_empty() {
+        toml.push('\n');
+    }
+
+    toml.push_str(header);
+    toml.push('\n');
+}
+
+fn option<T: Into<toml::Value>>(toml: &mut String, key: &str, value: Option<T>) {
+    if let Some(value) = value {
+        let _ = writeln!(toml, "{} = {}", toml_key(key), value.into());
+    }
+}
+
+/// It quotes the key unless it's a valid bare key, like the names of the
+/// access tokens with spaces or dots.
+fn toml_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        key.to_string()
+    } else {
+        toml::Value::from(key).to_string()
+    }
+}
+
+fn tsl_config(toml: &mut String, header: &str, service: &V1HttpService) {
+    if service.ssl_enabled {
+        section(toml, header);
+        option(toml, "ssl_cert_path", service.ssl_cert_path.as_deref());
+        option(toml, "ssl_key_path", service.ssl_key_path.as_deref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, from_v1, Error};
+    use crate::v2_0_0::database::Driver;
+
+    const V1_TOML: &str = r#"
+log_level = "debug"
+mode = "private_listed"
+db_driver = "Sqlite3"
+db_path = "./storage/tracker/lib/database/sqlite3.db"
+announce_interval = 120
+min_announce_interval = 60
+max_peer_timeout = 900
+
+[[udp_trackers]]
+enabled = true
+bind_address = "0.0.0.0:6969"
+
+[[http_trackers]]
+enabled = false
+bind_address = "0.0.0.0:7070"
+
+[http_api]
+enabled = true
+bind_address = "127.0.0.1:1212"
+
+[http_api.access_tokens]
+admin = "MyAccessToken"
+"#;
+
+    #[test]
+    fn it_should_migrate_a_version_1_configuration_into_the_nested_format() {
+        let migration = from_v1(V1_TOML).unwrap();
+
+        assert_eq!(
+            migration.toml,
+            r#"[metadata]
+app = "torrust-tracker"
+purpose = "configuration"
+schema_version = "2.0.0"
+
+[logging]
+threshold = "debug"
+
+[core]
+listed = true
+private = true
+
+[core.announce_policy]
+interval = 120
+interval_min = 60
+
+[core.database]
+driver = "sqlite3"
+path = "./storage/tracker/lib/database/sqlite3.db"
+
+[core.tracker_policy]
+max_peer_timeout = 900
+
+[[udp_trackers]]
+bind_address = "0.0.0.0:6969"
+
+[http_api]
+bind_address = "127.0.0.1:1212"
+
+[http_api.access_tokens]
+admin = "MyAccessToken"
+"#
+        );
+
+        assert!(migration.configuration.core.private);
+        assert_eq!(migration.configuration.core.database.driver, Driver::Sqlite3);
+        assert_eq!(migration.configuration.core.announce_policy.interval_min, 60);
+    }
+
+    #[test]
+    fn it_should_quote_the_access_token_names_that_are_not_bare_keys() {
+        let v1_toml = V1_TOML.replace("admin = ", r#""the \"main\" admin.v1" = "#);
+
+        let migration = from_v1(&v1_toml).unwrap();
+
+        assert_eq!(
+            migration
+                .configuration
+                .http_api
+                .unwrap()
+                .access_tokens
+                .get("the \"main\" admin.v1"),
+            Some(&"MyAccessToken".to_string())
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_the_options_that_are_not_migrated() {
+        let migration = from_v1(&format!("unknown_option = 1\n{V1_TOML}")).unwrap();
+
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "The unknown option `unknown_option` is not migrated".to_string(),
+                "The disabled HTTP tracker on 0.0.0.0:7070 is not migrated".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_fail_when_the_migrated_configuration_is_not_valid() {
+        assert!(matches!(
+            from_v1("log_level = \"verbose\""),
+            Err(Error::InvalidMigratedConfiguration { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_fail_when_the_version_1_configuration_can_not_be_parsed() {
+        assert!(matches!(
+            from_v1("mode = \"open\""),
+            Err(Error::InvalidV1Configuration { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_print_the_changes_line_by_line() {
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), " a\n-b\n+x\n c\n");
+    }
+}
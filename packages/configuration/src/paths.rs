@@ -0,0 +1,261 @@
+//! Path templates in the configuration.
+//!
+//! The values in the TOML configuration can contain variables for the base
+//! directories, so packaged installs don't need to rewrite every path:
+//!
+//! ```toml
+//! [core.database]
+//! driver = "sqlite3"
+//! path = "${DATA_DIR}/database/sqlite3.db"
+//!
+//! [http_api.tsl_config]
+//! ssl_cert_path = "${CONFIG_DIR}/tls/localhost.crt"
+//! ssl_key_path = "${CONFIG_DIR}/tls/localhost.key"
+//! ```
+//!
+//! The variables are:
+//!
+//! Variable        | `local` layout          | `fhs` layout
+//! ----------------|-------------------------|-----------------------------
+//! `${DATA_DIR}`   | `./storage/tracker/lib` | `/var/lib/torrust-tracker`
+//! `${CONFIG_DIR}` | `./storage/tracker/etc` | `/etc/torrust-tracker`
+//! `${LOG_DIR}`    | `./storage/tracker/log` | `/var/log/torrust-tracker`
+//!
+//! The `local` layout is the default one. The layout is set with the
+//! `TORRUST_TRACKER_PATHS_LAYOUT` environment variable, and each directory can
+//! be overridden with the `TORRUST_TRACKER_DATA_DIR`,
+//! `TORRUST_TRACKER_CONFIG_DIR` and `TORRUST_TRACKER_LOG_DIR` environment
+//! variables. The tracker command line options have priority over the
+//! environment variables.
+//!
+//! The variables are resolved in the TOML configuration when it's loaded,
+//! before the `TORRUST_TRACKER_CONFIG_OVERRIDE_*` environment variables are
+//! applied. Use `$${` to write a literal `${`. Unknown variables are an error.
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// The layout of the base directories used by default for the path variables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Directories relative to the working directory, like in the repository.
+    #[default]
+    Local,
+    /// Directories following the Filesystem Hierarchy Standard, for the deb and
+    /// rpm packages.
+    Fhs,
+}
+
+impl FromStr for Layout {
+    type Err = Error;
+
+    fn from_str(layout: &str) -> Result<Self, Self::Err> {
+        match layout {
+            "local" => Ok(Self::Local),
+            "fhs" => Ok(Self::Fhs),
+            _ => Err(Error::UnknownPathsLayout {
+                layout: layout.to_string(),
+            }),
+        }
+    }
+}
+
+/// A variable that can be used in the paths of the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variable {
+    DataDir,
+    ConfigDir,
+    LogDir,
+}
+
+impl Variable {
+    const ALL: [Variable; 3] = [Variable::DataDir, Variable::ConfigDir, Variable::LogDir];
+
+    /// The name of the variable in the templates.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variable::DataDir => "DATA_DIR",
+            Variable::ConfigDir => "CONFIG_DIR",
+            Variable::LogDir => "LOG_DIR",
+        }
+    }
+
+    /// The environment variable overriding the default value of the variable.
+    #[must_use]
+    pub fn env_var(&self) -> String {
+        format!("TORRUST_TRACKER_{}", self.name())
+    }
+
+    fn default_value(self, layout: Layout) -> &'static str {
+        match (layout, self) {
+            (Layout::Local, Variable::DataDir) => "./storage/tracker/lib",
+            (Layout::Local, Variable::ConfigDir) => "./storage/tracker/etc",
+            (Layout::Local, Variable::LogDir) => "./storage/tracker/log",
+            (Layout::Fhs, Variable::DataDir) => "/var/lib/torrust-tracker",
+            (Layout::Fhs, Variable::ConfigDir) => "/etc/torrust-tracker",
+            (Layout::Fhs, Variable::LogDir) => "/var/log/torrust-tracker",
+        }
+    }
+}
+
+/// The environment variable with the layout of the base directories.
+pub const ENV_VAR_PATHS_LAYOUT: &str = "TORRUST_TRACKER_PATHS_LAYOUT";
+
+/// The values of the path variables.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Variables {
+    layout: Layout,
+    values: HashMap<Variable, String>,
+}
+
+impl Variables {
+    /// It reads the layout and the directories from the environment
+    /// variables.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the layout is unknown.
+    pub fn from_env() -> Result<Self, Error> {
+        let mut variables = Self::default();
+
+        if let Ok(layout) = env::var(ENV_VAR_PATHS_LAYOUT) {
+            variables.layout = layout.parse()?;
+        }
+
+        for variable in Variable::ALL {
+            if let Ok(value) = env::var(variable.env_var()) {
+                variables.values.insert(variable, value);
+            }
+        }
+
+        Ok(variables)
+    }
+
+    /// It sets the layout used for the variables without a value.
+    #[must_use]
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// It sets the value of a variable.
+    #[must_use]
+    pub fn with(mut self, variable: Variable, value: String) -> Self {
+        self.values.insert(variable, value);
+        self
+    }
+
+    /// The value of a variable, without trailing slashes.
+    #[must_use]
+    pub fn get(&self, variable: Variable) -> &str {
+        let value = self
+            .values
+            .get(&variable)
+            .map_or_else(|| variable.default_value(self.layout), String::as_str);
+
+        match value.trim_end_matches('/') {
+            "" => value,
+            trimmed => trimmed,
+        }
+    }
+
+    /// It replaces the variables in the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a variable is unknown or not closed.
+    pub fn expand(&self, config: &str) -> Result<String, Error> {
+        let mut expanded = String::with_capacity(config.len());
+        let mut rest = config;
+
+        while let Some(start) = rest.find("${") {
+            if rest[..start].ends_with('$') {
+                expanded.push_str(&rest[..start - 1]);
+                expanded.push_str("${");
+                rest = &rest[start + 2..];
+                continue;
+            }
+
+            expanded.push_str(&rest[..start]);
+
+            let Some(end) = rest[start..].find('}') else {
+                return Err(Error::UnknownPathVariable {
+                    name: rest[start..].lines().next().unwrap_or_default().to_string(),
+                });
+            };
+
+            let name = &rest[start + 2..start + end];
+
+            let variable = Variable::ALL
+                .into_iter()
+                .find(|variable| variable.name() == name)
+                .ok_or_else(|| Error::UnknownPathVariable { name: name.to_string() })?;
+
+            expanded.push_str(self.get(variable));
+
+            rest = &rest[start + end + 1..];
+        }
+
+        expanded.push_str(rest);
+
+        Ok(expanded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Layout, Variable, Variables};
+    use crate::Error;
+
+    #[test]
+    fn it_should_replace_the_variables_with_the_directories_of_the_layout() {
+        let variables = Variables::default().with_layout(Layout::Fhs);
+
+        assert_eq!(
+            variables.expand(r#"path = "${DATA_DIR}/database/sqlite3.db""#).unwrap(),
+            r#"path = "/var/lib/torrust-tracker/database/sqlite3.db""#
+        );
+        assert_eq!(
+            Variables::default().expand(r#"path = "${CONFIG_DIR}/tls/cert.pem""#).unwrap(),
+            r#"path = "./storage/tracker/etc/tls/cert.pem""#
+        );
+    }
+
+    #[test]
+    fn it_should_give_priority_to_the_values_over_the_layout() {
+        let variables = Variables::default()
+            .with_layout(Layout::Fhs)
+            .with(Variable::DataDir, "/srv/tracker/".to_string());
+
+        assert_eq!(
+            variables.expand("${DATA_DIR}/db ${LOG_DIR}").unwrap(),
+            "/srv/tracker/db /var/log/torrust-tracker"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_escaped_templates() {
+        assert_eq!(Variables::default().expand("$${DATA_DIR}").unwrap(), "${DATA_DIR}");
+    }
+
+    #[test]
+    fn it_should_fail_with_an_unknown_variable() {
+        assert!(matches!(
+            Variables::default().expand("${HOME}/db"),
+            Err(Error::UnknownPathVariable { name }) if name == "HOME"
+        ));
+        assert!(matches!(
+            Variables::default().expand("${DATA_DIR"),
+            Err(Error::UnknownPathVariable { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_parse_the_layout() {
+        assert_eq!("fhs".parse::<Layout>().unwrap(), Layout::Fhs);
+        assert!("opt".parse::<Layout>().is_err());
+    }
+}
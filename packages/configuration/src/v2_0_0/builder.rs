@@ -0,0 +1,347 @@
+//! A builder to create a [`Configuration`] programmatically, without having to
+//! fill in the nested sections field by field.
+//!
+//! ```rust
+//! use torrust_tracker_configuration::Configuration;
+//!
+//! let configuration = Configuration::builder()
+//!     .with_udp("0.0.0.0:6969")
+//!     .with_http("0.0.0.0:7070")
+//!     .with_api("127.0.0.1:1212")
+//!     .with_api_token("admin", "MyAccessToken")
+//!     .private(false)
+//!     .build()
+//!     .expect("the configuration should be valid");
+//!
+//! assert_eq!(configuration.udp_trackers.unwrap()[0].bind_address.to_string(), "0.0.0.0:6969");
+//! ```
+//!
+//! Bind addresses are parsed and the configuration is validated when it's
+//! built. The first error is returned by [`Builder::build`].
+use thiserror::Error;
+
+use super::core::Core;
+use super::http_tracker::HttpTracker;
+use super::tracker_api::HttpApi;
+use super::udp_tracker::UdpTracker;
+use super::Configuration;
+use crate::validator::{SemanticValidationError, Validator};
+use crate::{BindAddress, BindAddressError, Driver, Threshold};
+
+/// Errors that can occur building a [`Configuration`].
+#[derive(Error, Debug)]
+pub enum BuildError {
+    #[error("Invalid bind address for the {service}: {address}: {source}")]
+    InvalidBindAddress {
+        service: &'static str,
+        address: String,
+        source: BindAddressError,
+    },
+
+    #[error("The API must be enabled with `with_api` before adding access tokens.")]
+    ApiTokenWithoutApi,
+
+    #[error("Invalid configuration: {source}")]
+    Invalid {
+        #[from]
+        source: SemanticValidationError,
+    },
+
+    #[error(transparent)]
+    UnresolvedBindAddress {
+        #[from]
+        source: crate::Error,
+    },
+}
+
+/// A builder for [`Configuration`]. It starts from the default configuration
+/// without any service.
+#[derive(Debug, Default)]
+pub struct Builder {
+    configuration: Configuration,
+    error: Option<BuildError>,
+}
+
+impl Configuration {
+    /// It returns a [`Builder`] to create a configuration programmatically.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl Builder {
+    /// It adds a UDP tracker bound to the `bind_address`.
+    #[must_use]
+    pub fn with_udp(self, bind_address: &str) -> Self {
+        self.with_bind_address("UDP tracker", bind_address, |configuration, bind_address| {
+            configuration.udp_trackers.get_or_insert_with(Vec::new).push(UdpTracker {
+                bind_address: bind_address.into(),
+                ..Default::default()
+            });
+        })
+    }
+
+    /// It adds a UDP tracker with all its settings.
+    #[must_use]
+    pub fn with_udp_tracker(mut self, udp_tracker: UdpTracker) -> Self {
+        self.configuration.udp_trackers.get_or_insert_with(Vec::new).push(udp_tracker);
+        self
+    }
+
+    /// It adds an HTTP tracker bound to the `bind_address`. It can be a Unix
+    /// domain socket path with the `unix:` prefix.
+    #[must_use]
+    pub fn with_http(self, bind_address: &str) -> Self {
+        self.with_bind_address("HTTP tracker", bind_address, |configuration, bind_address| {
+            configuration.http_trackers.get_or_insert_with(Vec::new).push(HttpTracker {
+                bind_address: bind_address.into(),
+                ..Default::default()
+            });
+        })
+    }
+
+    /// It adds an HTTP tracker with all its settings.
+    #[must_use]
+    pub fn with_http_tracker(mut self, http_tracker: HttpTracker) -> Self {
+        self.configuration
+            .http_trackers
+            .get_or_insert_with(Vec::new)
+            .push(http_tracker);
+        self
+    }
+
+    /// It enables the HTTP API bound to the `bind_address`, without access
+    /// tokens. It can be a Unix domain socket path with the `unix:` prefix.
+    #[must_use]
+    pub fn with_api(self, bind_address: &str) -> Self {
+        self.with_bind_address("API", bind_address, |configuration, bind_address| {
+            configuration.http_api = Some(HttpApi {
+                bind_address,
+                ..Default::default()
+            });
+        })
+    }
+
+    /// It adds an access token to the HTTP API.
+    #[must_use]
+    pub fn with_api_token(self, label: &str, token: &str) -> Self {
+        self.with_http_api(|http_api| http_api.add_token(label, token))
+    }
+
+    /// It adds an admin access token to the HTTP API.
+    #[must_use]
+    pub fn with_api_admin_token(self, label: &str, token: &str) -> Self {
+        self.with_http_api(|http_api| http_api.add_admin_token(label, token))
+    }
+
+    /// It binds the Health Check API to the `bind_address`.
+    #[must_use]
+    pub fn with_health_check_api(self, bind_address: &str) -> Self {
+        self.with_bind_address("Health Check API", bind_address, |configuration, bind_address| {
+            configuration.health_check_api.bind_address = bind_address;
+        })
+    }
+
+    /// It replaces the core configuration.
+    #[must_use]
+    pub fn with_core(mut self, core: Core) -> Self {
+        self.configuration.core = core;
+        self
+    }
+
+    /// It sets the database driver and path.
+    #[must_use]
+    pub fn with_database(mut self, driver: Driver, path: &str) -> Self {
+        self.configuration.core.database.driver = driver;
+        self.configuration.core.database.path = path.to_string();
+        self
+    }
+
+    /// It sets the logging threshold.
+    #[must_use]
+    pub fn with_log_threshold(mut self, threshold: Threshold) -> Self {
+        self.configuration.logging.threshold = threshold;
+        self
+    }
+
+    /// It sets whether the tracker runs in private mode.
+    #[must_use]
+    pub fn private(mut self, private: bool) -> Self {
+        self.configuration.core.private = private;
+        self
+    }
+
+    /// It sets whether the tracker runs in hybrid mode.
+    #[must_use]
+    pub fn hybrid(mut self, hybrid: bool) -> Self {
+        self.configuration.core.hybrid = hybrid;
+        self
+    }
+
+    /// It sets whether the tracker runs in listed mode.
+    #[must_use]
+    pub fn listed(mut self, listed: bool) -> Self {
+        self.configuration.core.listed = listed;
+        self
+    }
+
+    /// It sets whether the tracker is behind a reverse proxy.
+    #[must_use]
+    pub fn on_reverse_proxy(mut self, on_reverse_proxy: bool) -> Self {
+        self.configuration.core.net.on_reverse_proxy = on_reverse_proxy;
+        self
+    }
+
+    /// It returns the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Will return the first error found while building: an invalid bind
+    /// address, an access token without the API, a semantic validation error,
+    /// or a bind address that can't be resolved.
+    pub fn build(mut self) -> Result<Configuration, BuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        self.configuration.validate()?;
+
+        self.configuration.resolve_bind_addresses()?;
+
+        Ok(self.configuration)
+    }
+
+    fn with_bind_address(
+        mut self,
+        service: &'static str,
+        bind_address: &str,
+        apply: impl FnOnce(&mut Configuration, BindAddress),
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match bind_address.parse::<BindAddress>() {
+            Ok(value) => apply(&mut self.configuration, value),
+            Err(source) => {
+                self.error = Some(BuildError::InvalidBindAddress {
+                    service,
+                    address: bind_address.to_string(),
+                    source,
+                });
+            }
+        }
+
+        self
+    }
+
+    fn with_http_api(mut self, apply: impl FnOnce(&mut HttpApi)) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match &mut self.configuration.http_api {
+            Some(http_api) => apply(http_api),
+            None => self.error = Some(BuildError::ApiTokenWithoutApi),
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildError;
+    use crate::validator::SemanticValidationError;
+    use crate::{BindAddress, Configuration, Driver};
+
+    #[test]
+    fn it_should_build_a_configuration_with_the_given_services() {
+        let configuration = Configuration::builder()
+            .with_udp("0.0.0.0:6969")
+            .with_udp("[::]:6969")
+            .with_http("0.0.0.0:7070")
+            .with_api("127.0.0.1:1212")
+            .with_api_token("admin", "MyAccessToken")
+            .with_health_check_api("127.0.0.1:1313")
+            .with_database(Driver::None, "")
+            .private(true)
+            .build()
+            .unwrap();
+
+        let udp_trackers = configuration.udp_trackers.unwrap();
+        let http_api = configuration.http_api.unwrap();
+
+        assert_eq!(
+            udp_trackers
+                .iter()
+                .map(|udp_tracker| udp_tracker.bind_address.first().clone())
+                .collect::<Vec<_>>(),
+            vec![
+                BindAddress::Socket("0.0.0.0:6969".parse().unwrap()),
+                BindAddress::Socket("[::]:6969".parse().unwrap())
+            ]
+        );
+        assert_eq!(
+            configuration.http_trackers.unwrap()[0].bind_address,
+            BindAddress::Socket("0.0.0.0:7070".parse().unwrap()).into()
+        );
+        assert_eq!(http_api.bind_address, BindAddress::Socket("127.0.0.1:1212".parse().unwrap()));
+        assert_eq!(http_api.access_tokens.get("admin"), Some(&"MyAccessToken".to_string()));
+        assert_eq!(
+            configuration.health_check_api.bind_address,
+            BindAddress::Socket("127.0.0.1:1313".parse().unwrap())
+        );
+        assert_eq!(configuration.core.database.driver, Driver::None);
+        assert!(configuration.core.private);
+    }
+
+    #[test]
+    fn it_should_build_a_configuration_without_services_by_default() {
+        let configuration = Configuration::builder().build().unwrap();
+
+        assert_eq!(configuration, Configuration::default());
+    }
+
+    #[test]
+    fn it_should_fail_building_with_an_invalid_bind_address() {
+        let result = Configuration::builder().with_udp("0.0.0.0:port").build();
+
+        assert!(matches!(
+            result,
+            Err(BuildError::InvalidBindAddress {
+                service: "UDP tracker",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn it_should_fail_building_with_a_bind_address_that_cannot_be_resolved() {
+        let result = Configuration::builder().with_udp("iface:missing0:6969").build();
+
+        assert!(matches!(result, Err(BuildError::UnresolvedBindAddress { .. })));
+    }
+
+    #[test]
+    fn it_should_fail_building_with_api_tokens_when_the_api_is_not_enabled() {
+        let result = Configuration::builder().with_api_token("admin", "MyAccessToken").build();
+
+        assert!(matches!(result, Err(BuildError::ApiTokenWithoutApi)));
+    }
+
+    #[test]
+    fn it_should_validate_the_configuration() {
+        let result = Configuration::builder()
+            .with_http("unix:/var/run/torrust/http_tracker.sock")
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(BuildError::Invalid {
+                source: SemanticValidationError::UnixSocketWithoutReverseProxy { .. }
+            })
+        ));
+    }
+}
@@ -1,24 +1,189 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8PathBuf;
 use derive_more::{Constructor, Display};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::network::Network;
-use crate::v2_0_0::database::Database;
+use crate::v2_0_0::database::{Database, Driver};
 use crate::validator::{SemanticValidationError, Validator};
-use crate::{AnnouncePolicy, TrackerPolicy};
+use crate::{AnnouncePolicy, TrackerPolicy, MAX_SCRAPE_TORRENTS, TORRENT_PEERS_LIMIT};
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct Core {
+    /// Optional micro-batching of the `announce` writes to the torrents
+    /// repository. It's disabled by default.
+    ///
+    /// When enabled, peer updates are queued per shard and applied in small
+    /// batches, so the peer lists and swarm metadata returned to clients can
+    /// be slightly stale (up to `max_delay_ms` milliseconds).
+    #[serde(default = "Core::default_announce_batching")]
+    pub announce_batching: Option<AnnounceBatching>,
+
+    /// Optional export of one structured record per `announce` request for
+    /// offline analytics. It's disabled by default.
+    ///
+    /// Records are appended to a local file in NDJSON format (one json object
+    /// per line).
+    #[serde(default = "Core::default_announce_export")]
+    pub announce_export: Option<AnnounceExport>,
+
+    /// Optional write-ahead journal of the `announce` requests. It's disabled
+    /// by default.
+    ///
+    /// When enabled, a compact record of every peer update is appended to a
+    /// fixed-size ring file. On restart, the updates from the last
+    /// `replay_window_secs` seconds are replayed, so the swarms are warm right
+    /// away after a crash. It's lighter than persisting all the peers.
+    #[serde(default = "Core::default_announce_journal")]
+    pub announce_journal: Option<AnnounceJournal>,
+
     /// Announce policy configuration.
     #[serde(default = "Core::default_announce_policy")]
     pub announce_policy: AnnouncePolicy,
 
+    /// Optional padding of the HTTP tracker `announce` responses. It's
+    /// disabled by default.
+    ///
+    /// When enabled, the responses smaller than `min_size` bytes get an extra
+    /// `padding` key, which clients ignore, so they are at least `min_size`
+    /// bytes long. Some carrier-grade NATs and middleboxes drop tiny responses.
+    #[serde(default = "Core::default_announce_response_padding")]
+    pub announce_response_padding: Option<AnnounceResponsePadding>,
+
+    /// Optional queue for the asynchronous `announce` updates. It's disabled
+    /// by default.
+    ///
+    /// The HTTP and UDP trackers with `async_announce` enabled send the
+    /// `announce` response computed from the current peer list right away,
+    /// and the peer update is applied afterwards by a background task. The
+    /// announcing peer might not be in the swarm yet when other peers announce
+    /// right after it.
+    #[serde(default = "Core::default_async_announce")]
+    pub async_announce: Option<AsyncAnnounce>,
+
+    /// Optional announce policy for the peers that authenticate with a key.
+    /// Peers that do not provide a valid key use the `announce_policy`.
+    ///
+    /// It allows, for example, shorter intervals and more peers in the
+    /// response for authenticated peers of a listed tracker. In private mode
+    /// all the peers are authenticated.
+    #[serde(default = "Core::default_authenticated_announce_policy")]
+    pub authenticated_announce_policy: Option<AuthenticatedAnnouncePolicy>,
+
+    /// Optional cache of the positive authorization decisions for `announce`
+    /// requests with a key. It's disabled by default.
+    ///
+    /// When enabled, once a key is verified and the torrent is authorized,
+    /// the decision for that key and torrent is reused for `ttl_ms`
+    /// milliseconds, skipping the key verification and the whitelist lookup.
+    /// Decisions are invalidated when the key is revoked or the torrent is
+    /// removed from the whitelist.
+    #[serde(default = "Core::default_authorization_cache")]
+    pub authorization_cache: Option<AuthorizationCache>,
+
+    /// Optional periodic check of the internal invariants of the torrent
+    /// repository. It's disabled by default.
+    ///
+    /// It's a debugging tool to catch concurrency bugs in the repository
+    /// implementations. When enabled, the aggregate metrics, the swarm
+    /// metadata and the peer timeouts are recomputed every `interval_secs`
+    /// seconds and the inconsistencies found are logged and exposed in the
+    /// API.
+    #[serde(default = "Core::default_consistency_check")]
+    pub consistency_check: Option<ConsistencyCheck>,
+
     /// Database configuration.
     #[serde(default = "Core::default_database")]
     pub database: Database,
 
+    /// Optional lookups in the database of the torrents that are not in the
+    /// in-memory whitelist. It's disabled by default.
+    ///
+    /// It's useful when the whitelist is updated directly in a database
+    /// shared by several trackers. When enabled, a listed tracker looks up
+    /// the torrents it does not know in the database before rejecting them.
+    /// The lookups run in a dedicated pool of `workers` threads with a bounded
+    /// queue, and concurrent lookups of the same torrent are coalesced into
+    /// one query. When the queue is full, the requests are rejected with a
+    /// retryable error.
+    #[serde(default = "Core::default_database_authorization")]
+    pub database_authorization: Option<DatabaseAuthorization>,
+
+    /// Optional periodic backup of the database. It's disabled by default.
+    ///
+    /// It's only supported for the `sqlite3` driver. Backups are taken
+    /// online, while the tracker is running.
+    #[serde(default = "Core::default_database_backup")]
+    pub database_backup: Option<DatabaseBackup>,
+
+    /// Optional dead-letter queue for the failed database writes. It's
+    /// disabled by default.
+    ///
+    /// When enabled, the writes that fail (the torrent `completed` counters
+    /// and the new authentication keys) are appended to a bounded queue kept
+    /// in a local file, and retried with an exponential backoff. Otherwise,
+    /// failed writes of the `completed` counters are discarded.
+    #[serde(default = "Core::default_dead_letter_queue")]
+    pub dead_letter_queue: Option<DeadLetterQueue>,
+
+    /// Optional webhook notified of the domain events of the tracker. It's
+    /// disabled by default.
+    ///
+    /// When enabled, the tracker sends a `POST` request with a json body to
+    /// the webhook every time a torrent entry is created or removed, a
+    /// torrent is removed from the whitelist, or a key is created or revoked.
+    /// The `announce` requests are not notified.
+    #[serde(default = "Core::default_event_webhook")]
+    pub event_webhook: Option<EventWebhook>,
+
+    /// Optional templates of the failure reasons sent to the clients. They
+    /// are disabled by default.
+    ///
+    /// When enabled, the human-readable message of the errors with a code in
+    /// the tracker error taxonomy (for example, `E03` for a torrent that is
+    /// not whitelisted) is replaced with the template for the code in the
+    /// selected `language`, so operators can customize or translate it.
+    #[serde(default = "Core::default_failure_reasons")]
+    pub failure_reasons: Option<FailureReasons>,
+
+    /// Optional fault injection, for resilience testing. It's disabled by
+    /// default, and it's only applied when the tracker is built with the
+    /// `fault-injection` feature.
+    ///
+    /// When enabled, the tracker randomly delays requests, fails database
+    /// writes and delays the writes to the torrents repository, with the
+    /// configured probabilities. Never enable it in production.
+    #[serde(default = "Core::default_fault_injection")]
+    pub fault_injection: Option<FaultInjection>,
+
+    /// Optional admission control based on the usage of file descriptors.
+    /// It's disabled by default.
+    ///
+    /// When enabled, the number of open file descriptors is sampled every
+    /// `refresh_interval_ms` milliseconds. When the usage gets close to the
+    /// process limit (`ulimit -n`), new HTTP connections are rejected with a
+    /// `503 Service Unavailable` response and the health check reports a
+    /// warning. The UDP trackers keep running, since they don't open a file
+    /// descriptor per request.
+    #[serde(default = "Core::default_file_descriptors")]
+    pub file_descriptors: Option<FileDescriptors>,
+
+    /// When `true` clients can use the tracker without a key, like in public
+    /// mode, but the keys they provide are verified, like in private mode.
+    /// Peers with a valid key are authenticated: they get the
+    /// `authenticated_announce_policy` and their usage is counted in the key
+    /// stats. It allows migrating a public tracker to private gradually.
+    ///
+    /// It can't be enabled together with `private`.
+    #[serde(default = "Core::default_hybrid")]
+    pub hybrid: bool,
+
     /// Interval in seconds that the cleanup job will run to remove inactive
-    /// peers from the torrent peer list.
+    /// peers from the torrent peer list. `0` disables the cleanup job. It can
+    /// be changed at runtime using the API.
     #[serde(default = "Core::default_inactive_peer_cleanup_interval")]
     pub inactive_peer_cleanup_interval: u64,
 
@@ -26,10 +191,60 @@ pub struct Core {
     #[serde(default = "Core::default_listed")]
     pub listed: bool,
 
+    /// Maintenance mode configuration.
+    ///
+    /// While the tracker is in maintenance mode, `announce` requests are
+    /// rejected asking clients to retry later. `scrape` requests and the API
+    /// keep working. Maintenance mode can also be toggled using the API.
+    #[serde(default = "Core::default_maintenance")]
+    pub maintenance: Maintenance,
+
+    /// Maximum number of torrents that can be requested in a single `scrape`
+    /// request. It's applied to both the UDP and HTTP trackers.
+    ///
+    /// The [BEP 15](https://www.bittorrent.org/beps/bep_0015.html) states that
+    /// "up to about 74 torrents can be scraped at once". Requests exceeding
+    /// the limit are rejected with an error response.
+    ///
+    /// UDP requests with more than 255 torrents are always rejected, but HTTP
+    /// requests are only limited by the URI length, so the limit can be raised
+    /// above 255 for the HTTP tracker.
+    #[serde(default = "Core::default_max_scrape_torrents")]
+    pub max_scrape_torrents: u16,
+
+    /// Optional cache of the infohashes recently rejected by the UDP tracker.
+    /// It's disabled by default.
+    ///
+    /// When the tracker is flooded with `announce` and `scrape` requests for
+    /// torrents that are not whitelisted or do not exist, the rejected
+    /// infohashes are remembered for `ttl_ms` milliseconds, so the following
+    /// requests for them skip the authorization and the torrents repository
+    /// lookups.
+    #[serde(default = "Core::default_negative_cache")]
+    pub negative_cache: Option<NegativeCache>,
+
     /// Network configuration.
     #[serde(default = "Core::default_network")]
     pub net: Network,
 
+    /// Optional verification of the addresses announced by peers. It's
+    /// disabled by default.
+    ///
+    /// When enabled, a sample of the newly announced peers is probed in the
+    /// background to check whether the announced port accepts connections.
+    /// Unreachable peers are returned last in the `announce` peer lists.
+    #[serde(default = "Core::default_peer_reachability")]
+    pub peer_reachability: Option<PeerReachability>,
+
+    /// Optional settings for the preflight checks run at startup.
+    ///
+    /// The checks are always run and they never stop the tracker. They only
+    /// report warnings, like a storage directory that is not writable or a
+    /// TLS certificate about to expire. When not set, the checks use the
+    /// default settings and the clock is not compared with an NTP server.
+    #[serde(default = "Core::default_preflight")]
+    pub preflight: Option<Preflight>,
+
     /// When `true` clients require a key to connect and use the tracker.
     #[serde(default = "Core::default_private")]
     pub private: bool,
@@ -38,6 +253,76 @@ pub struct Core {
     #[serde(default = "Core::default_private_mode")]
     pub private_mode: Option<PrivateMode>,
 
+    /// Optional read snapshot of the swarm metadata. It's disabled by
+    /// default.
+    ///
+    /// When enabled, a background job periodically publishes an immutable
+    /// snapshot of the swarm metadata of all torrents. `scrape` requests and
+    /// the torrents metrics read the snapshot instead of the live torrents
+    /// repository, so they do not contend with the `announce` writes. The
+    /// trade-off is that the returned data can be stale, at most by
+    /// `refresh_interval_ms` milliseconds.
+    #[serde(default = "Core::default_read_snapshot")]
+    pub read_snapshot: Option<ReadSnapshot>,
+
+    /// Optional deadline for each `announce` and `scrape` request. It's
+    /// disabled by default.
+    ///
+    /// When enabled, at most `max_concurrent_requests` requests work in the
+    /// torrents repository or the database at the same time. The requests
+    /// that are not admitted within `budget_ms` milliseconds get a best-effort
+    /// response, without peers and with zeroed counters, instead of waiting.
+    /// The deadline-exceeded counters in the statistics show when the tracker
+    /// is saturated.
+    #[serde(default = "Core::default_request_deadline")]
+    pub request_deadline: Option<RequestDeadline>,
+
+    /// Optional manifest listing the services the tracker is running. It's
+    /// disabled by default.
+    ///
+    /// When enabled, the manifest is written once all the services have
+    /// started. It includes the actual bound addresses, so orchestration
+    /// scripts can discover the ephemeral ports assigned when port `0` is
+    /// configured. The same information is always logged on startup.
+    #[serde(default = "Core::default_service_manifest")]
+    pub service_manifest: Option<ServiceManifest>,
+
+    /// Optional service level objectives for the `announce` requests. They
+    /// are disabled by default.
+    ///
+    /// When enabled, the response time and the errors of the `announce`
+    /// requests are tracked over a sliding window. The health check reports
+    /// the tracker as `Degraded` while an objective is not met, and a webhook
+    /// can be notified when it starts and stops being met.
+    #[serde(default = "Core::default_slo")]
+    pub slo: Option<Slo>,
+
+    /// Optional logging of the slow requests. It's disabled by default.
+    ///
+    /// When enabled, the `announce` and `scrape` requests (HTTP and UDP) and
+    /// the API requests exceeding their threshold are logged with a warning
+    /// that includes the infohashes, the client and the time spent in every
+    /// phase of the request. Only the total time is measured for the API
+    /// requests.
+    #[serde(default = "Core::default_slow_requests")]
+    pub slow_requests: Option<SlowRequests>,
+
+    /// The statistics collected when `tracker_usage_statistics` is enabled.
+    ///
+    /// Performance-sensitive deployments can collect only the statistics
+    /// they need, for example, only the UDP tracker counters.
+    #[serde(default = "Core::default_statistics")]
+    pub statistics: Statistics,
+
+    /// Optional log of the swarm changes. It's disabled by default.
+    ///
+    /// When enabled, the tracker assigns a monotonic cursor to every change
+    /// in the swarm metadata of a torrent, so API clients (like the Index)
+    /// can get only the torrents that changed since their last sync instead
+    /// of scraping all of them again.
+    #[serde(default = "Core::default_swarm_changes")]
+    pub swarm_changes: Option<SwarmChanges>,
+
     /// Tracker policy configuration.
     #[serde(default = "Core::default_tracker_policy")]
     pub tracker_policy: TrackerPolicy,
@@ -54,13 +339,41 @@ pub struct Core {
 impl Default for Core {
     fn default() -> Self {
         Self {
+            announce_batching: Self::default_announce_batching(),
+            announce_export: Self::default_announce_export(),
+            announce_journal: Self::default_announce_journal(),
             announce_policy: Self::default_announce_policy(),
+            announce_response_padding: Self::default_announce_response_padding(),
+            async_announce: Self::default_async_announce(),
+            authenticated_announce_policy: Self::default_authenticated_announce_policy(),
+            authorization_cache: Self::default_authorization_cache(),
+            consistency_check: Self::default_consistency_check(),
             database: Self::default_database(),
+            database_authorization: Self::default_database_authorization(),
+            database_backup: Self::default_database_backup(),
+            dead_letter_queue: Self::default_dead_letter_queue(),
+            event_webhook: Self::default_event_webhook(),
+            failure_reasons: Self::default_failure_reasons(),
+            fault_injection: Self::default_fault_injection(),
+            file_descriptors: Self::default_file_descriptors(),
+            hybrid: Self::default_hybrid(),
             inactive_peer_cleanup_interval: Self::default_inactive_peer_cleanup_interval(),
             listed: Self::default_listed(),
+            maintenance: Self::default_maintenance(),
+            max_scrape_torrents: Self::default_max_scrape_torrents(),
+            negative_cache: Self::default_negative_cache(),
             net: Self::default_network(),
+            peer_reachability: Self::default_peer_reachability(),
+            preflight: Self::default_preflight(),
             private: Self::default_private(),
             private_mode: Self::default_private_mode(),
+            read_snapshot: Self::default_read_snapshot(),
+            request_deadline: Self::default_request_deadline(),
+            service_manifest: Self::default_service_manifest(),
+            slo: Self::default_slo(),
+            slow_requests: Self::default_slow_requests(),
+            statistics: Self::default_statistics(),
+            swarm_changes: Self::default_swarm_changes(),
             tracker_policy: Self::default_tracker_policy(),
             tracker_usage_statistics: Self::default_tracker_usage_statistics(),
         }
@@ -68,14 +381,78 @@ impl Default for Core {
 }
 
 impl Core {
+    fn default_announce_batching() -> Option<AnnounceBatching> {
+        None
+    }
+
+    fn default_announce_export() -> Option<AnnounceExport> {
+        None
+    }
+
+    fn default_announce_journal() -> Option<AnnounceJournal> {
+        None
+    }
+
     fn default_announce_policy() -> AnnouncePolicy {
         AnnouncePolicy::default()
     }
 
+    fn default_announce_response_padding() -> Option<AnnounceResponsePadding> {
+        None
+    }
+
+    fn default_async_announce() -> Option<AsyncAnnounce> {
+        None
+    }
+
+    fn default_authenticated_announce_policy() -> Option<AuthenticatedAnnouncePolicy> {
+        None
+    }
+
+    fn default_authorization_cache() -> Option<AuthorizationCache> {
+        None
+    }
+
+    fn default_consistency_check() -> Option<ConsistencyCheck> {
+        None
+    }
+
     fn default_database() -> Database {
         Database::default()
     }
 
+    fn default_database_authorization() -> Option<DatabaseAuthorization> {
+        None
+    }
+
+    fn default_database_backup() -> Option<DatabaseBackup> {
+        None
+    }
+
+    fn default_dead_letter_queue() -> Option<DeadLetterQueue> {
+        None
+    }
+
+    fn default_event_webhook() -> Option<EventWebhook> {
+        None
+    }
+
+    fn default_failure_reasons() -> Option<FailureReasons> {
+        None
+    }
+
+    fn default_fault_injection() -> Option<FaultInjection> {
+        None
+    }
+
+    fn default_file_descriptors() -> Option<FileDescriptors> {
+        None
+    }
+
+    fn default_hybrid() -> bool {
+        false
+    }
+
     fn default_inactive_peer_cleanup_interval() -> u64 {
         600
     }
@@ -84,10 +461,30 @@ impl Core {
         false
     }
 
+    fn default_maintenance() -> Maintenance {
+        Maintenance::default()
+    }
+
+    fn default_max_scrape_torrents() -> u16 {
+        MAX_SCRAPE_TORRENTS
+    }
+
+    fn default_negative_cache() -> Option<NegativeCache> {
+        None
+    }
+
     fn default_network() -> Network {
         Network::default()
     }
 
+    fn default_peer_reachability() -> Option<PeerReachability> {
+        None
+    }
+
+    fn default_preflight() -> Option<Preflight> {
+        None
+    }
+
     fn default_private() -> bool {
         false
     }
@@ -100,6 +497,34 @@ impl Core {
         }
     }
 
+    fn default_read_snapshot() -> Option<ReadSnapshot> {
+        None
+    }
+
+    fn default_request_deadline() -> Option<RequestDeadline> {
+        None
+    }
+
+    fn default_service_manifest() -> Option<ServiceManifest> {
+        None
+    }
+
+    fn default_slo() -> Option<Slo> {
+        None
+    }
+
+    fn default_slow_requests() -> Option<SlowRequests> {
+        None
+    }
+
+    fn default_statistics() -> Statistics {
+        Statistics::default()
+    }
+
+    fn default_swarm_changes() -> Option<SwarmChanges> {
+        None
+    }
+
     fn default_tracker_policy() -> TrackerPolicy {
         TrackerPolicy::default()
     }
@@ -109,7 +534,10 @@ impl Core {
 }
 
 /// Configuration specific when the tracker is running in private mode.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Constructor, Display)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor, Display)]
+#[display(
+    "PrivateMode(check_keys_expiration: {check_keys_expiration}, inactive_peer_cleanup_interval: {inactive_peer_cleanup_interval:?}, max_peer_timeout: {max_peer_timeout:?})"
+)]
 pub struct PrivateMode {
     /// A flag to disable expiration date for peer keys.
     ///
@@ -117,12 +545,26 @@ pub struct PrivateMode {
     /// ignored. The key will be accepted even if it has expired.
     #[serde(default = "PrivateMode::default_check_keys_expiration")]
     pub check_keys_expiration: bool,
+
+    /// Interval in seconds that the cleanup job will run to remove inactive
+    /// peers in private mode. It overrides the `inactive_peer_cleanup_interval`
+    /// option when it's set.
+    #[serde(default = "PrivateMode::default_inactive_peer_cleanup_interval")]
+    pub inactive_peer_cleanup_interval: Option<u64>,
+
+    /// Maximum time in seconds that a peer can be inactive in private mode.
+    /// Private trackers usually keep the peers for longer. It overrides the
+    /// `tracker_policy.max_peer_timeout` option when it's set.
+    #[serde(default = "PrivateMode::default_max_peer_timeout")]
+    pub max_peer_timeout: Option<u32>,
 }
 
 impl Default for PrivateMode {
     fn default() -> Self {
         Self {
             check_keys_expiration: Self::default_check_keys_expiration(),
+            inactive_peer_cleanup_interval: Self::default_inactive_peer_cleanup_interval(),
+            max_peer_timeout: Self::default_max_peer_timeout(),
         }
     }
 }
@@ -131,12 +573,1304 @@ impl PrivateMode {
     fn default_check_keys_expiration() -> bool {
         true
     }
+
+    fn default_inactive_peer_cleanup_interval() -> Option<u64> {
+        None
+    }
+
+    fn default_max_peer_timeout() -> Option<u32> {
+        None
+    }
 }
 
-impl Validator for Core {
-    fn validate(&self) -> Result<(), SemanticValidationError> {
-        if self.private_mode.is_some() && !self.private {
-            return Err(SemanticValidationError::UselessPrivateModeSection);
+/// Configuration for the maintenance mode.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct Maintenance {
+    /// Whether the tracker starts in maintenance mode.
+    #[serde(default = "Maintenance::default_enabled")]
+    pub enabled: bool,
+
+    /// Time in minutes clients are asked to wait before retrying an
+    /// `announce` while the tracker is in maintenance mode. It's sent in the
+    /// `retry in` field of the HTTP tracker failure responses
+    /// ([BEP 31](https://www.bittorrent.org/beps/bep_0031.html)).
+    #[serde(default = "Maintenance::default_retry_in")]
+    pub retry_in: u32,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            retry_in: Self::default_retry_in(),
+        }
+    }
+}
+
+impl Maintenance {
+    fn default_enabled() -> bool {
+        false
+    }
+
+    fn default_retry_in() -> u32 {
+        10
+    }
+}
+
+/// Configuration for the `announce` micro-batching.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct AnnounceBatching {
+    /// Number of independent queues. Announces are assigned to a shard
+    /// depending on the torrent info-hash.
+    #[serde(default = "AnnounceBatching::default_shards")]
+    pub shards: usize,
+
+    /// Number of queued updates that triggers applying a shard batch.
+    #[serde(default = "AnnounceBatching::default_max_batch_size")]
+    pub max_batch_size: usize,
+
+    /// Maximum time in milliseconds an update can wait in the queue before
+    /// being applied.
+    #[serde(default = "AnnounceBatching::default_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+impl Default for AnnounceBatching {
+    fn default() -> Self {
+        Self {
+            shards: Self::default_shards(),
+            max_batch_size: Self::default_max_batch_size(),
+            max_delay_ms: Self::default_max_delay_ms(),
+        }
+    }
+}
+
+impl AnnounceBatching {
+    fn default_shards() -> usize {
+        16
+    }
+
+    fn default_max_batch_size() -> usize {
+        32
+    }
+
+    fn default_max_delay_ms() -> u64 {
+        5
+    }
+}
+
+/// Configuration for the asynchronous `announce` updates.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct AsyncAnnounce {
+    /// Maximum number of pending peer updates. When the queue is full, the
+    /// updates are applied synchronously, before sending the response.
+    #[serde(default = "AsyncAnnounce::default_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+impl Default for AsyncAnnounce {
+    fn default() -> Self {
+        Self {
+            queue_capacity: Self::default_queue_capacity(),
+        }
+    }
+}
+
+impl AsyncAnnounce {
+    fn default_queue_capacity() -> usize {
+        10_000
+    }
+}
+
+/// Announce policy for the peers that authenticate with a key.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct AuthenticatedAnnouncePolicy {
+    /// Interval in seconds that the client should wait between sending
+    /// regular announce requests to the tracker.
+    #[serde(default = "AuthenticatedAnnouncePolicy::default_interval")]
+    pub interval: u32,
+
+    /// Minimum announce interval. Clients must not reannounce more frequently
+    /// than this.
+    #[serde(default = "AuthenticatedAnnouncePolicy::default_interval_min")]
+    pub interval_min: u32,
+
+    /// Number of peers in the response when the client does not ask for a
+    /// specific number of peers (`numwant`).
+    #[serde(default = "AuthenticatedAnnouncePolicy::default_max_peers")]
+    pub max_peers: u32,
+}
+
+impl Default for AuthenticatedAnnouncePolicy {
+    fn default() -> Self {
+        Self {
+            interval: Self::default_interval(),
+            interval_min: Self::default_interval_min(),
+            max_peers: Self::default_max_peers(),
+        }
+    }
+}
+
+impl AuthenticatedAnnouncePolicy {
+    /// The policy in the announce responses.
+    #[must_use]
+    pub fn announce_policy(&self) -> AnnouncePolicy {
+        AnnouncePolicy::new(self.interval, self.interval_min)
+    }
+
+    fn default_interval() -> u32 {
+        AnnouncePolicy::default().interval
+    }
+
+    fn default_interval_min() -> u32 {
+        AnnouncePolicy::default().interval_min
+    }
+
+    fn default_max_peers() -> u32 {
+        u32::try_from(TORRENT_PEERS_LIMIT).expect("it should fit in a u32")
+    }
+}
+
+/// Configuration for the cache of authorization decisions.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct AuthorizationCache {
+    /// Time in milliseconds a positive decision is reused.
+    #[serde(default = "AuthorizationCache::default_ttl_ms")]
+    pub ttl_ms: u64,
+
+    /// Maximum number of cached decisions. New decisions are not cached when
+    /// the cache is full of unexpired ones.
+    #[serde(default = "AuthorizationCache::default_capacity")]
+    pub capacity: usize,
+}
+
+impl Default for AuthorizationCache {
+    fn default() -> Self {
+        Self {
+            ttl_ms: Self::default_ttl_ms(),
+            capacity: Self::default_capacity(),
+        }
+    }
+}
+
+impl AuthorizationCache {
+    fn default_ttl_ms() -> u64 {
+        5000
+    }
+
+    fn default_capacity() -> usize {
+        100_000
+    }
+}
+
+/// Configuration for the consistency check of the torrent repository.
+///
+/// ```toml
+/// [core.consistency_check]
+/// interval_secs = 60
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct ConsistencyCheck {
+    /// Interval in seconds between checks.
+    #[serde(default = "ConsistencyCheck::default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for ConsistencyCheck {
+    fn default() -> Self {
+        Self {
+            interval_secs: Self::default_interval_secs(),
+        }
+    }
+}
+
+impl ConsistencyCheck {
+    fn default_interval_secs() -> u64 {
+        60
+    }
+}
+
+/// Configuration for the cache of rejected infohashes.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct NegativeCache {
+    /// Time in milliseconds a rejection is reused.
+    #[serde(default = "NegativeCache::default_ttl_ms")]
+    pub ttl_ms: u64,
+
+    /// Maximum number of cached rejections. New rejections are not cached
+    /// when the cache is full of unexpired ones.
+    #[serde(default = "NegativeCache::default_capacity")]
+    pub capacity: usize,
+}
+
+impl Default for NegativeCache {
+    fn default() -> Self {
+        Self {
+            ttl_ms: Self::default_ttl_ms(),
+            capacity: Self::default_capacity(),
+        }
+    }
+}
+
+impl NegativeCache {
+    fn default_ttl_ms() -> u64 {
+        10_000
+    }
+
+    fn default_capacity() -> usize {
+        100_000
+    }
+}
+
+/// Configuration for the admission control based on the usage of file
+/// descriptors.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct FileDescriptors {
+    /// Percentage of the file descriptors limit from which new HTTP
+    /// connections are rejected. It must be between 1 and 100.
+    #[serde(default = "FileDescriptors::default_max_usage_percent")]
+    pub max_usage_percent: u8,
+
+    /// Time in milliseconds between two samples of the open file descriptors.
+    #[serde(default = "FileDescriptors::default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+
+    /// Minimum recommended file descriptors limit. A warning is logged at
+    /// startup when the process limit is lower. Use `0` to disable the check.
+    #[serde(default = "FileDescriptors::default_min_limit")]
+    pub min_limit: u64,
+}
+
+impl Default for FileDescriptors {
+    fn default() -> Self {
+        Self {
+            max_usage_percent: Self::default_max_usage_percent(),
+            refresh_interval_ms: Self::default_refresh_interval_ms(),
+            min_limit: Self::default_min_limit(),
+        }
+    }
+}
+
+impl FileDescriptors {
+    fn default_max_usage_percent() -> u8 {
+        90
+    }
+
+    fn default_refresh_interval_ms() -> u64 {
+        1000
+    }
+
+    fn default_min_limit() -> u64 {
+        0
+    }
+}
+
+/// Configuration for the `announce` data export.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct AnnounceExport {
+    /// Path of the NDJSON file the records are appended to. It's created if
+    /// it does not exist.
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+
+    /// Only the announces from one out of every `sample_one_in` peers are
+    /// exported. Peers are sampled by their hash, so all the announces from a
+    /// sampled peer are exported.
+    #[serde(default = "AnnounceExport::default_sample_one_in")]
+    pub sample_one_in: u32,
+
+    /// Secret salt the peer hashes are calculated with, so they can't be
+    /// reversed by hashing every peer ID and IP address. When it's not set, a
+    /// random salt is generated on every start, and the hashes of the same
+    /// peer can't be correlated across restarts.
+    #[serde(default = "AnnounceExport::default_salt")]
+    pub salt: Option<String>,
+}
+
+impl AnnounceExport {
+    fn default_sample_one_in() -> u32 {
+        1
+    }
+
+    fn default_salt() -> Option<String> {
+        None
+    }
+}
+
+/// Configuration for the write-ahead journal of the `announce` requests.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct AnnounceJournal {
+    /// Path of the ring file the records are written to. It's created if it
+    /// does not exist.
+    #[serde(default = "AnnounceJournal::default_path")]
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+
+    /// Maximum size of the ring file in bytes. The oldest records are
+    /// overwritten when it's full. It must be at least 1024 bytes.
+    #[serde(default = "AnnounceJournal::default_max_size_bytes")]
+    pub max_size_bytes: u64,
+
+    /// Only the records from the last `replay_window_secs` seconds are
+    /// replayed on restart. It should not be longer than the peer timeout
+    /// (`tracker_policy.max_peer_timeout`), since older peers would be
+    /// removed by the next cleanup anyway.
+    #[serde(default = "AnnounceJournal::default_replay_window_secs")]
+    pub replay_window_secs: u64,
+
+    /// When the records are flushed to the disk.
+    #[serde(default = "AnnounceJournal::default_fsync")]
+    pub fsync: JournalFsync,
+
+    /// Time in milliseconds between two flushes with the `interval` fsync
+    /// policy.
+    #[serde(default = "AnnounceJournal::default_fsync_interval_ms")]
+    pub fsync_interval_ms: u64,
+}
+
+impl Default for AnnounceJournal {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            max_size_bytes: Self::default_max_size_bytes(),
+            replay_window_secs: Self::default_replay_window_secs(),
+            fsync: Self::default_fsync(),
+            fsync_interval_ms: Self::default_fsync_interval_ms(),
+        }
+    }
+}
+
+impl AnnounceJournal {
+    fn default_path() -> Utf8PathBuf {
+        Utf8PathBuf::from("./storage/tracker/lib/database/announce_journal.bin")
+    }
+
+    fn default_max_size_bytes() -> u64 {
+        64 * 1024 * 1024
+    }
+
+    fn default_replay_window_secs() -> u64 {
+        900
+    }
+
+    fn default_fsync() -> JournalFsync {
+        JournalFsync::Interval
+    }
+
+    fn default_fsync_interval_ms() -> u64 {
+        1000
+    }
+}
+
+/// When the records of the `announce` journal are flushed to the disk.
+///
+/// The records are written to the file right away in all cases, so they
+/// survive a crash of the tracker process. The policy only matters when the
+/// whole system crashes.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalFsync {
+    /// After every record. It's the safest and the slowest policy.
+    Always,
+    /// Every `fsync_interval_ms` milliseconds.
+    Interval,
+    /// Never. The operating system decides when to flush the records.
+    Never,
+}
+
+/// Configuration for the database lookups of the whitelist.
+///
+/// ```toml
+/// [core.database_authorization]
+/// workers = 4
+/// queue_capacity = 1024
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct DatabaseAuthorization {
+    /// Number of threads querying the database.
+    #[serde(default = "DatabaseAuthorization::default_workers")]
+    pub workers: usize,
+
+    /// Maximum number of pending lookups. The lookups coalesced with a
+    /// pending one do not take a place in the queue.
+    #[serde(default = "DatabaseAuthorization::default_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+impl Default for DatabaseAuthorization {
+    fn default() -> Self {
+        Self {
+            workers: Self::default_workers(),
+            queue_capacity: Self::default_queue_capacity(),
+        }
+    }
+}
+
+impl DatabaseAuthorization {
+    fn default_workers() -> usize {
+        4
+    }
+
+    fn default_queue_capacity() -> usize {
+        1024
+    }
+}
+
+/// Configuration for the database backups.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct DatabaseBackup {
+    /// Directory where the backup files are written. It's created if it does
+    /// not exist.
+    #[schemars(with = "String")]
+    pub directory: Utf8PathBuf,
+
+    /// Interval in seconds between backups.
+    #[serde(default = "DatabaseBackup::default_interval")]
+    pub interval: u64,
+
+    /// Number of backup files to keep. The oldest ones are removed after each
+    /// backup.
+    #[serde(default = "DatabaseBackup::default_retention")]
+    pub retention: usize,
+}
+
+impl DatabaseBackup {
+    fn default_interval() -> u64 {
+        86_400
+    }
+
+    fn default_retention() -> usize {
+        7
+    }
+}
+
+/// Configuration for the dead-letter queue of failed database writes.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct DeadLetterQueue {
+    /// Path of the NDJSON file where the pending writes are kept, so they
+    /// survive restarts. It's created if it does not exist.
+    #[serde(default = "DeadLetterQueue::default_path")]
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+
+    /// Maximum number of pending writes. The oldest write is discarded when a
+    /// new one is queued and the queue is full.
+    #[serde(default = "DeadLetterQueue::default_capacity")]
+    pub capacity: usize,
+
+    /// Time in milliseconds to wait before the first retry. It's doubled
+    /// after each failed retry, up to `max_backoff_ms`.
+    #[serde(default = "DeadLetterQueue::default_min_backoff_ms")]
+    pub min_backoff_ms: u64,
+
+    /// Maximum time in milliseconds between retries.
+    #[serde(default = "DeadLetterQueue::default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            capacity: Self::default_capacity(),
+            min_backoff_ms: Self::default_min_backoff_ms(),
+            max_backoff_ms: Self::default_max_backoff_ms(),
+        }
+    }
+}
+
+impl DeadLetterQueue {
+    fn default_path() -> Utf8PathBuf {
+        Utf8PathBuf::from("./storage/tracker/lib/database/dead_letters.ndjson")
+    }
+
+    fn default_capacity() -> usize {
+        10_000
+    }
+
+    fn default_min_backoff_ms() -> u64 {
+        1000
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        60_000
+    }
+}
+
+/// Configuration for the templates of the failure reasons.
+///
+/// The templates are grouped by language and indexed by the error code. They
+/// can contain the `{code}` and `{message}` placeholders, which are replaced
+/// with the error code and the default message. Errors without a template in
+/// the selected language keep the default message.
+///
+/// ```toml
+/// [core.failure_reasons]
+/// language = "es"
+///
+/// [core.failure_reasons.templates.en]
+/// E03 = "torrent not registered, visit https://example.com/rules"
+///
+/// [core.failure_reasons.templates.es]
+/// E03 = "torrent no registrado, visita https://example.com/rules"
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct FailureReasons {
+    /// The language of the templates used. It must be one of the languages
+    /// in the `templates` table.
+    #[serde(default = "FailureReasons::default_language")]
+    pub language: String,
+
+    /// The templates by language and error code.
+    #[serde(default)]
+    pub templates: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Default for FailureReasons {
+    fn default() -> Self {
+        Self {
+            language: Self::default_language(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+impl FailureReasons {
+    fn default_language() -> String {
+        "en".to_string()
+    }
+}
+
+/// Configuration for the fault injection.
+///
+/// The probabilities are percentages. For example, to delay 5% of the
+/// requests by 2 seconds and fail 1% of the database writes:
+///
+/// ```toml
+/// [core.fault_injection]
+/// latency_percent = 5
+/// latency_ms = 2000
+/// db_write_failure_percent = 1
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct FaultInjection {
+    /// Percentage of the UDP and HTTP tracker requests delayed by
+    /// `latency_ms` milliseconds.
+    #[serde(default = "FaultInjection::default_latency_percent")]
+    pub latency_percent: u8,
+
+    /// Artificial latency in milliseconds of the delayed requests.
+    #[serde(default = "FaultInjection::default_latency_ms")]
+    pub latency_ms: u64,
+
+    /// Percentage of the database writes that fail without writing anything.
+    #[serde(default = "FaultInjection::default_db_write_failure_percent")]
+    pub db_write_failure_percent: u8,
+
+    /// Percentage of the writes to the torrents repository delayed by
+    /// `lock_delay_ms` milliseconds, like when the lock is contended.
+    #[serde(default = "FaultInjection::default_lock_delay_percent")]
+    pub lock_delay_percent: u8,
+
+    /// Delay in milliseconds of the delayed repository writes.
+    #[serde(default = "FaultInjection::default_lock_delay_ms")]
+    pub lock_delay_ms: u64,
+}
+
+impl Default for FaultInjection {
+    fn default() -> Self {
+        Self {
+            latency_percent: Self::default_latency_percent(),
+            latency_ms: Self::default_latency_ms(),
+            db_write_failure_percent: Self::default_db_write_failure_percent(),
+            lock_delay_percent: Self::default_lock_delay_percent(),
+            lock_delay_ms: Self::default_lock_delay_ms(),
+        }
+    }
+}
+
+impl FaultInjection {
+    fn default_latency_percent() -> u8 {
+        0
+    }
+
+    fn default_latency_ms() -> u64 {
+        1000
+    }
+
+    fn default_db_write_failure_percent() -> u8 {
+        0
+    }
+
+    fn default_lock_delay_percent() -> u8 {
+        0
+    }
+
+    fn default_lock_delay_ms() -> u64 {
+        100
+    }
+}
+
+/// Configuration for the padding of the `announce` responses.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct AnnounceResponsePadding {
+    /// Minimum size in bytes of the `announce` responses.
+    #[serde(default = "AnnounceResponsePadding::default_min_size")]
+    pub min_size: usize,
+}
+
+impl Default for AnnounceResponsePadding {
+    fn default() -> Self {
+        Self {
+            min_size: Self::default_min_size(),
+        }
+    }
+}
+
+impl AnnounceResponsePadding {
+    fn default_min_size() -> usize {
+        256
+    }
+}
+
+/// Configuration for the read snapshot of the swarm metadata.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct ReadSnapshot {
+    /// Interval in milliseconds between snapshot refreshes.
+    #[serde(default = "ReadSnapshot::default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+}
+
+impl Default for ReadSnapshot {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: Self::default_refresh_interval_ms(),
+        }
+    }
+}
+
+impl ReadSnapshot {
+    fn default_refresh_interval_ms() -> u64 {
+        1000
+    }
+}
+
+/// Configuration for the deadline of the `announce` and `scrape` requests.
+///
+/// ```toml
+/// [core.request_deadline]
+/// budget_ms = 100
+/// max_concurrent_requests = 64
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RequestDeadline {
+    /// Maximum time in milliseconds a request waits to be admitted before a
+    /// best-effort response is returned.
+    #[serde(default = "RequestDeadline::default_budget_ms")]
+    pub budget_ms: u64,
+
+    /// Maximum number of requests working in the torrents repository or the
+    /// database at the same time.
+    #[serde(default = "RequestDeadline::default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for RequestDeadline {
+    fn default() -> Self {
+        Self {
+            budget_ms: Self::default_budget_ms(),
+            max_concurrent_requests: Self::default_max_concurrent_requests(),
+        }
+    }
+}
+
+impl RequestDeadline {
+    fn default_budget_ms() -> u64 {
+        100
+    }
+
+    fn default_max_concurrent_requests() -> usize {
+        64
+    }
+}
+
+/// Configuration for the manifest of the running services.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct ServiceManifest {
+    /// Path of the json file the manifest is written to. It's overwritten
+    /// every time the tracker starts.
+    #[serde(default = "ServiceManifest::default_path")]
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+}
+
+impl Default for ServiceManifest {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+        }
+    }
+}
+
+impl ServiceManifest {
+    fn default_path() -> Utf8PathBuf {
+        Utf8PathBuf::from("./storage/tracker/lib/services.json")
+    }
+}
+
+/// Configuration for the service level objectives of the `announce`
+/// requests.
+///
+/// For example, to require a p99 response time below 50 milliseconds and an
+/// error rate of at most 1%:
+///
+/// ```toml
+/// [core.slo]
+/// latency_percentile = 99
+/// max_latency_ms = 50
+/// max_error_rate_percent = 1
+/// webhook_url = "https://alerts.example.com/tracker"
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct Slo {
+    /// Length in seconds of the sliding window the objectives are evaluated
+    /// over.
+    #[serde(default = "Slo::default_window_secs")]
+    pub window_secs: u64,
+
+    /// Minimum number of `announce` requests in the window to evaluate the
+    /// objectives, so a few slow requests on an idle tracker don't degrade it.
+    #[serde(default = "Slo::default_min_requests")]
+    pub min_requests: u64,
+
+    /// The percentile of the response times that must be below
+    /// `max_latency_ms`. It must be between 1 and 100.
+    #[serde(default = "Slo::default_latency_percentile")]
+    pub latency_percentile: u8,
+
+    /// Maximum response time in milliseconds for the `latency_percentile`.
+    #[serde(default = "Slo::default_max_latency_ms")]
+    pub max_latency_ms: u64,
+
+    /// Maximum percentage of `announce` requests answered with an error. It
+    /// must not be greater than 100.
+    #[serde(default = "Slo::default_max_error_rate_percent")]
+    pub max_error_rate_percent: u8,
+
+    /// Optional URL the tracker sends a `POST` request with a json body to
+    /// when the objectives start or stop being met.
+    #[serde(default = "Slo::default_webhook_url")]
+    pub webhook_url: Option<String>,
+}
+
+impl Default for Slo {
+    fn default() -> Self {
+        Self {
+            window_secs: Self::default_window_secs(),
+            min_requests: Self::default_min_requests(),
+            latency_percentile: Self::default_latency_percentile(),
+            max_latency_ms: Self::default_max_latency_ms(),
+            max_error_rate_percent: Self::default_max_error_rate_percent(),
+            webhook_url: Self::default_webhook_url(),
+        }
+    }
+}
+
+impl Slo {
+    fn default_window_secs() -> u64 {
+        60
+    }
+
+    fn default_min_requests() -> u64 {
+        100
+    }
+
+    fn default_latency_percentile() -> u8 {
+        99
+    }
+
+    fn default_max_latency_ms() -> u64 {
+        50
+    }
+
+    fn default_max_error_rate_percent() -> u8 {
+        1
+    }
+
+    fn default_webhook_url() -> Option<String> {
+        None
+    }
+}
+
+/// Configuration for the logging of the slow requests.
+///
+/// ```toml
+/// [core.slow_requests]
+/// announce_threshold_ms = 500
+/// scrape_threshold_ms = 500
+/// api_threshold_ms = 1000
+/// ```
+///
+/// A threshold of `0` logs all the requests of that kind.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct SlowRequests {
+    /// Minimum duration in milliseconds of the logged `announce` requests.
+    #[serde(default = "SlowRequests::default_announce_threshold_ms")]
+    pub announce_threshold_ms: u64,
+
+    /// Minimum duration in milliseconds of the logged `scrape` requests.
+    #[serde(default = "SlowRequests::default_scrape_threshold_ms")]
+    pub scrape_threshold_ms: u64,
+
+    /// Minimum duration in milliseconds of the logged API requests.
+    #[serde(default = "SlowRequests::default_api_threshold_ms")]
+    pub api_threshold_ms: u64,
+}
+
+impl Default for SlowRequests {
+    fn default() -> Self {
+        Self {
+            announce_threshold_ms: Self::default_announce_threshold_ms(),
+            scrape_threshold_ms: Self::default_scrape_threshold_ms(),
+            api_threshold_ms: Self::default_api_threshold_ms(),
+        }
+    }
+}
+
+impl SlowRequests {
+    fn default_announce_threshold_ms() -> u64 {
+        500
+    }
+
+    fn default_scrape_threshold_ms() -> u64 {
+        500
+    }
+
+    fn default_api_threshold_ms() -> u64 {
+        1000
+    }
+}
+
+/// Configuration for the statistics collected by the tracker. They are all
+/// collected by default.
+///
+/// For example, to collect only the UDP tracker counters:
+///
+/// ```toml
+/// [core.statistics]
+/// http = false
+/// histograms = false
+/// connection_ids = false
+/// ```
+///
+/// The statistics are not collected at all when `tracker_usage_statistics`
+/// is disabled.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct Statistics {
+    /// Collect the HTTP tracker counters.
+    #[serde(default = "Statistics::default_http")]
+    pub http: bool,
+
+    /// Collect the UDP tracker counters.
+    #[serde(default = "Statistics::default_udp")]
+    pub udp: bool,
+
+    /// Collect the histograms, like the size buckets of the HTTP `announce`
+    /// responses. Only the aggregate counters are collected when disabled.
+    #[serde(default = "Statistics::default_histograms")]
+    pub histograms: bool,
+
+    /// Track the UDP connection IDs to count the ones used, unused and
+    /// expired. It keeps up to 500,000 connection IDs in memory.
+    #[serde(default = "Statistics::default_connection_ids")]
+    pub connection_ids: bool,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            http: Self::default_http(),
+            udp: Self::default_udp(),
+            histograms: Self::default_histograms(),
+            connection_ids: Self::default_connection_ids(),
+        }
+    }
+}
+
+impl Statistics {
+    fn default_http() -> bool {
+        true
+    }
+
+    fn default_udp() -> bool {
+        true
+    }
+
+    fn default_histograms() -> bool {
+        true
+    }
+
+    fn default_connection_ids() -> bool {
+        true
+    }
+}
+
+/// Configuration for the webhook notified of the domain events of the
+/// tracker.
+///
+/// ```toml
+/// [core.event_webhook]
+/// url = "https://events.example.com/tracker"
+/// ```
+///
+/// The json body of the `POST` requests has the type of the event and its
+/// data, for example:
+///
+/// ```json
+/// {
+///   "type": "torrent_removed",
+///   "info_hash": "9c38422213e30bff212b30c360d26f9a02136422"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct EventWebhook {
+    /// The URL the events are sent to.
+    pub url: String,
+}
+
+/// Configuration for the log of the swarm changes.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct SwarmChanges {
+    /// Maximum number of torrents kept in the log. When it's full, the
+    /// torrents that changed longer ago are forgotten, and the clients with
+    /// an older cursor have to sync all the torrents again.
+    #[serde(default = "SwarmChanges::default_capacity")]
+    pub capacity: usize,
+}
+
+impl Default for SwarmChanges {
+    fn default() -> Self {
+        Self {
+            capacity: Self::default_capacity(),
+        }
+    }
+}
+
+impl SwarmChanges {
+    fn default_capacity() -> usize {
+        1_000_000
+    }
+}
+
+/// Configuration for the peer reachability verification.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy, Constructor)]
+pub struct PeerReachability {
+    /// Only one out of every `sample_one_in` newly announced peers is probed.
+    #[serde(default = "PeerReachability::default_sample_one_in")]
+    pub sample_one_in: u32,
+
+    /// Maximum number of probes started per second.
+    #[serde(default = "PeerReachability::default_max_probes_per_second")]
+    pub max_probes_per_second: u32,
+
+    /// Time in milliseconds to wait for the peer to answer the probe.
+    #[serde(default = "PeerReachability::default_probe_timeout_ms")]
+    pub probe_timeout_ms: u64,
+
+    /// Maximum number of peers waiting to be probed. Samples are discarded
+    /// when the queue is full.
+    #[serde(default = "PeerReachability::default_queue_capacity")]
+    pub queue_capacity: usize,
+
+    /// Maximum number of probe results kept. New results are discarded when
+    /// the limit is reached, until the results of the removed peers are
+    /// dropped.
+    #[serde(default = "PeerReachability::default_max_results")]
+    pub max_results: usize,
+}
+
+impl Default for PeerReachability {
+    fn default() -> Self {
+        Self {
+            sample_one_in: Self::default_sample_one_in(),
+            max_probes_per_second: Self::default_max_probes_per_second(),
+            probe_timeout_ms: Self::default_probe_timeout_ms(),
+            queue_capacity: Self::default_queue_capacity(),
+            max_results: Self::default_max_results(),
+        }
+    }
+}
+
+impl PeerReachability {
+    fn default_sample_one_in() -> u32 {
+        10
+    }
+
+    fn default_max_probes_per_second() -> u32 {
+        10
+    }
+
+    fn default_probe_timeout_ms() -> u64 {
+        2000
+    }
+
+    fn default_queue_capacity() -> usize {
+        1000
+    }
+
+    fn default_max_results() -> usize {
+        100_000
+    }
+}
+
+/// Configuration for the preflight checks run at startup.
+///
+/// For example, to compare the clock with an NTP server and to warn about
+/// TLS certificates expiring in the next 30 days:
+///
+/// ```toml
+/// [core.preflight]
+/// certificate_expiry_days = 30
+/// ntp_server = "pool.ntp.org:123"
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct Preflight {
+    /// A warning is reported for the TLS certificates expiring in less than
+    /// `certificate_expiry_days` days.
+    #[serde(default = "Preflight::default_certificate_expiry_days")]
+    pub certificate_expiry_days: u32,
+
+    /// Optional NTP server (`host:port`) the clock is compared with. The
+    /// clock skew is not checked when it's not set.
+    #[serde(default = "Preflight::default_ntp_server")]
+    pub ntp_server: Option<String>,
+
+    /// Maximum difference in seconds between the clock and the NTP server.
+    #[serde(default = "Preflight::default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+
+    /// Minimum recommended open file descriptors limit (`ulimit -n`).
+    #[serde(default = "Preflight::default_min_file_descriptors")]
+    pub min_file_descriptors: u64,
+}
+
+impl Default for Preflight {
+    fn default() -> Self {
+        Self {
+            certificate_expiry_days: Self::default_certificate_expiry_days(),
+            ntp_server: Self::default_ntp_server(),
+            max_clock_skew_secs: Self::default_max_clock_skew_secs(),
+            min_file_descriptors: Self::default_min_file_descriptors(),
+        }
+    }
+}
+
+impl Preflight {
+    fn default_certificate_expiry_days() -> u32 {
+        14
+    }
+
+    fn default_ntp_server() -> Option<String> {
+        None
+    }
+
+    fn default_max_clock_skew_secs() -> u64 {
+        5
+    }
+
+    fn default_min_file_descriptors() -> u64 {
+        1024
+    }
+}
+
+impl Validator for Core {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if self.private_mode.is_some() && !self.private {
+            return Err(SemanticValidationError::UselessPrivateModeSection);
+        }
+
+        if self.hybrid && self.private {
+            return Err(SemanticValidationError::HybridPrivateTracker);
+        }
+
+        if self.maintenance.retry_in == 0 {
+            return Err(SemanticValidationError::ZeroMaintenanceRetryIn);
+        }
+
+        if self.max_scrape_torrents == 0 {
+            return Err(SemanticValidationError::ZeroMaxScrapeTorrents);
+        }
+
+        if let Some(announce_batching) = self.announce_batching {
+            if announce_batching.shards == 0 || announce_batching.max_batch_size == 0 || announce_batching.max_delay_ms == 0 {
+                return Err(SemanticValidationError::InvalidAnnounceBatching);
+            }
+        }
+
+        if let Some(announce_response_padding) = self.announce_response_padding {
+            if announce_response_padding.min_size == 0 {
+                return Err(SemanticValidationError::InvalidAnnounceResponsePadding);
+            }
+        }
+
+        if let Some(async_announce) = self.async_announce {
+            if async_announce.queue_capacity == 0 {
+                return Err(SemanticValidationError::InvalidAsyncAnnounce);
+            }
+        }
+
+        if let Some(authenticated_announce_policy) = &self.authenticated_announce_policy {
+            if authenticated_announce_policy.interval_min > authenticated_announce_policy.interval
+                || authenticated_announce_policy.max_peers == 0
+            {
+                return Err(SemanticValidationError::InvalidAuthenticatedAnnouncePolicy);
+            }
+        }
+
+        if let Some(authorization_cache) = self.authorization_cache {
+            if authorization_cache.ttl_ms == 0 || authorization_cache.capacity == 0 {
+                return Err(SemanticValidationError::InvalidAuthorizationCache);
+            }
+        }
+
+        if let Some(consistency_check) = self.consistency_check {
+            if consistency_check.interval_secs == 0 {
+                return Err(SemanticValidationError::InvalidConsistencyCheck);
+            }
+        }
+
+        if let Some(negative_cache) = self.negative_cache {
+            if negative_cache.ttl_ms == 0 || negative_cache.capacity == 0 {
+                return Err(SemanticValidationError::InvalidNegativeCache);
+            }
+        }
+
+        if let Some(file_descriptors) = self.file_descriptors {
+            if !(1..=100).contains(&file_descriptors.max_usage_percent) || file_descriptors.refresh_interval_ms == 0 {
+                return Err(SemanticValidationError::InvalidFileDescriptors);
+            }
+        }
+
+        if let Some(announce_export) = &self.announce_export {
+            if announce_export.sample_one_in == 0
+                || announce_export.path.as_str().is_empty()
+                || announce_export.salt.as_ref().is_some_and(String::is_empty)
+            {
+                return Err(SemanticValidationError::InvalidAnnounceExport);
+            }
+        }
+
+        if let Some(announce_journal) = &self.announce_journal {
+            if announce_journal.path.as_str().is_empty()
+                || announce_journal.max_size_bytes < 1024
+                || announce_journal.replay_window_secs == 0
+                || (announce_journal.fsync == JournalFsync::Interval && announce_journal.fsync_interval_ms == 0)
+            {
+                return Err(SemanticValidationError::InvalidAnnounceJournal);
+            }
+        }
+
+        if let Some(database_authorization) = self.database_authorization {
+            if self.database.driver == Driver::None {
+                return Err(SemanticValidationError::DatabaseAuthorizationWithoutDatabase);
+            }
+
+            if database_authorization.workers == 0 || database_authorization.queue_capacity == 0 {
+                return Err(SemanticValidationError::InvalidDatabaseAuthorization);
+            }
+        }
+
+        if let Some(database_backup) = &self.database_backup {
+            if self.database.driver != Driver::Sqlite3 {
+                return Err(SemanticValidationError::DatabaseBackupNotSupported);
+            }
+
+            if database_backup.interval == 0 || database_backup.retention == 0 || database_backup.directory.as_str().is_empty() {
+                return Err(SemanticValidationError::InvalidDatabaseBackup);
+            }
+        }
+
+        if let Some(dead_letter_queue) = &self.dead_letter_queue {
+            if self.database.driver == Driver::None {
+                return Err(SemanticValidationError::DeadLetterQueueWithoutDatabase);
+            }
+
+            if dead_letter_queue.path.as_str().is_empty()
+                || dead_letter_queue.capacity == 0
+                || dead_letter_queue.min_backoff_ms == 0
+                || dead_letter_queue.max_backoff_ms < dead_letter_queue.min_backoff_ms
+            {
+                return Err(SemanticValidationError::InvalidDeadLetterQueue);
+            }
+        }
+
+        if let Some(failure_reasons) = &self.failure_reasons {
+            let Some(templates) = failure_reasons.templates.get(&failure_reasons.language) else {
+                return Err(SemanticValidationError::InvalidFailureReasons);
+            };
+
+            if templates
+                .iter()
+                .any(|(code, template)| code.is_empty() || template.is_empty())
+            {
+                return Err(SemanticValidationError::InvalidFailureReasons);
+            }
+        }
+
+        if let Some(fault_injection) = self.fault_injection {
+            if fault_injection.latency_percent > 100
+                || fault_injection.db_write_failure_percent > 100
+                || fault_injection.lock_delay_percent > 100
+            {
+                return Err(SemanticValidationError::InvalidFaultInjection);
+            }
+        }
+
+        if let Some(read_snapshot) = self.read_snapshot {
+            if read_snapshot.refresh_interval_ms == 0 {
+                return Err(SemanticValidationError::InvalidReadSnapshot);
+            }
+        }
+
+        if let Some(request_deadline) = self.request_deadline {
+            if request_deadline.budget_ms == 0 || request_deadline.max_concurrent_requests == 0 {
+                return Err(SemanticValidationError::InvalidRequestDeadline);
+            }
+        }
+
+        if let Some(service_manifest) = &self.service_manifest {
+            if service_manifest.path.as_str().is_empty() {
+                return Err(SemanticValidationError::InvalidServiceManifest);
+            }
+        }
+
+        if let Some(slo) = &self.slo {
+            if slo.window_secs == 0 || !(1..=100).contains(&slo.latency_percentile) || slo.max_error_rate_percent > 100 {
+                return Err(SemanticValidationError::InvalidSlo);
+            }
+        }
+
+        if let Some(swarm_changes) = self.swarm_changes {
+            if swarm_changes.capacity == 0 {
+                return Err(SemanticValidationError::InvalidSwarmChanges);
+            }
+        }
+
+        if let Some(preflight) = &self.preflight {
+            if preflight.max_clock_skew_secs == 0 || preflight.ntp_server.as_ref().is_some_and(String::is_empty) {
+                return Err(SemanticValidationError::InvalidPreflight);
+            }
+        }
+
+        if let Some(peer_reachability) = self.peer_reachability {
+            if peer_reachability.sample_one_in == 0
+                || peer_reachability.max_probes_per_second == 0
+                || peer_reachability.probe_timeout_ms == 0
+                || peer_reachability.queue_capacity == 0
+                || peer_reachability.max_results == 0
+            {
+                return Err(SemanticValidationError::InvalidPeerReachability);
+            }
         }
 
         Ok(())
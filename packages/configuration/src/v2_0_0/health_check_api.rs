@@ -1,18 +1,24 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
+use crate::BindAddress;
+
 /// Configuration for the Health Check API.
 #[serde_as]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct HealthCheckApi {
     /// The address the API will bind to.
     /// The format is `ip:port`, for example `127.0.0.1:1313`. If you want to
     /// listen to all interfaces, use `0.0.0.0`. If you want the operating
     /// system to choose a random port, use port `0`.
+    ///
+    /// It can also be a hostname like `localhost:1313` or a network interface
+    /// name like `iface:lo:1313`, resolved when the configuration is loaded.
     #[serde(default = "HealthCheckApi::default_bind_address")]
-    pub bind_address: SocketAddr,
+    pub bind_address: BindAddress,
 }
 
 impl Default for HealthCheckApi {
@@ -24,7 +30,7 @@ impl Default for HealthCheckApi {
 }
 
 impl HealthCheckApi {
-    fn default_bind_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1313)
+    fn default_bind_address() -> BindAddress {
+        BindAddress::Socket(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1313))
     }
 }
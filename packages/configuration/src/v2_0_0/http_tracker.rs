@@ -1,41 +1,290 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::TslConfig;
+use crate::validator::{validate_bind_device, SemanticValidationError, Validator};
+use crate::{BindAddress, BindAddresses, TslConfig};
+
+/// Paths already used by the HTTP tracker.
+const RESERVED_PATHS: [&str; 3] = ["/announce", "/scrape", "/health_check"];
 
 /// Configuration for each HTTP tracker.
 #[serde_as]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct HttpTracker {
     /// The address the tracker will bind to.
     /// The format is `ip:port`, for example `0.0.0.0:6969`. If you want to
     /// listen to all interfaces, use `0.0.0.0`. If you want the operating
     /// system to choose a random port, use port `0`.
+    ///
+    /// It can also be a hostname like `tracker.local:7070` or a network
+    /// interface name like `iface:eth0:7070`, resolved when the configuration
+    /// is loaded.
+    ///
+    /// Or a Unix domain socket path with the `unix:` prefix, for
+    /// example `unix:/var/run/torrust/http_tracker.sock`. TLS is not supported on Unix
+    /// domain sockets.
+    ///
+    /// Or a list of addresses the tracker binds to with the same settings,
+    /// for example `["0.0.0.0:7070", "[::]:7070"]` to listen on IPv6 too. A
+    /// Unix domain socket can't be in a list.
     #[serde(default = "HttpTracker::default_bind_address")]
-    pub bind_address: SocketAddr,
+    pub bind_address: BindAddresses,
+
+    /// Optional network interface the service is bound to, for example,
+    /// `eth1`. It sets the `SO_BINDTODEVICE` socket option, so multi-homed
+    /// servers can pin the tracker traffic to a specific NIC regardless of
+    /// the routing tables. It's only supported on Linux, and it might require
+    /// the `CAP_NET_RAW` capability.
+    #[serde(default = "HttpTracker::default_bind_device")]
+    pub bind_device: Option<String>,
 
     /// TSL config.
     #[serde(default = "HttpTracker::default_tsl_config")]
     pub tsl_config: Option<TslConfig>,
+
+    /// Whether the tracker expects a `HAProxy` PROXY protocol (v1 or v2)
+    /// header at the beginning of every connection. It's needed to get the
+    /// real client IP when the tracker is behind a TCP load balancer that
+    /// cannot inject HTTP headers. Connections without a valid header are
+    /// rejected. It's only supported on TCP socket addresses.
+    #[serde(default = "HttpTracker::default_proxy_protocol")]
+    pub proxy_protocol: bool,
+
+    /// Where the authentication key goes in the `announce` and `scrape` URL
+    /// paths of private trackers. It can be `suffix` (`/announce/<key>`),
+    /// `prefix` (`/<key>/announce`) or `both`.
+    #[serde(default = "HttpTracker::default_key_path_format")]
+    pub key_path_format: KeyPathFormat,
+
+    /// The health endpoint for load balancers. Unlike the health check API,
+    /// it's served by the tracker itself, so it checks the actual service
+    /// the load balancer routes requests to.
+    #[serde(default = "HttpTracker::default_health")]
+    pub health: Health,
+
+    /// The limits of the size of the requests. Announce URLs with hundreds of
+    /// parameters could exhaust the memory of the tracker.
+    #[serde(default = "HttpTracker::default_request_limits")]
+    pub request_limits: RequestLimits,
+
+    /// Whether the `announce` response is sent before applying the peer
+    /// update, which is applied asynchronously afterwards. It lowers the
+    /// response latency, but the announcing peer is not in the swarm until the
+    /// update is applied. It requires the `core.async_announce` queue.
+    #[serde(default = "HttpTracker::default_async_announce")]
+    pub async_announce: bool,
+
+    /// The name of the [virtual tracker](crate::VirtualTracker) served on
+    /// this port. When it's not set, it serves the main tracker. The requests
+    /// for the `hosts` of other virtual trackers are served by them.
+    #[serde(default = "HttpTracker::default_tracker")]
+    pub tracker: Option<String>,
 }
 
 impl Default for HttpTracker {
     fn default() -> Self {
         Self {
             bind_address: Self::default_bind_address(),
+            bind_device: Self::default_bind_device(),
             tsl_config: Self::default_tsl_config(),
+            proxy_protocol: Self::default_proxy_protocol(),
+            key_path_format: Self::default_key_path_format(),
+            health: Self::default_health(),
+            request_limits: Self::default_request_limits(),
+            async_announce: Self::default_async_announce(),
+            tracker: Self::default_tracker(),
         }
     }
 }
 
 impl HttpTracker {
-    fn default_bind_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)
+    fn default_bind_address() -> BindAddresses {
+        BindAddress::Socket(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)).into()
+    }
+
+    fn default_bind_device() -> Option<String> {
+        None
     }
 
     fn default_tsl_config() -> Option<TslConfig> {
         None
     }
+
+    fn default_proxy_protocol() -> bool {
+        false
+    }
+
+    fn default_key_path_format() -> KeyPathFormat {
+        KeyPathFormat::Suffix
+    }
+
+    fn default_health() -> Health {
+        Health::default()
+    }
+
+    fn default_request_limits() -> RequestLimits {
+        RequestLimits::default()
+    }
+
+    fn default_async_announce() -> bool {
+        false
+    }
+
+    fn default_tracker() -> Option<String> {
+        None
+    }
+}
+
+/// Configuration for the health endpoint of an HTTP tracker.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct Health {
+    /// The URL path of the endpoint. It must start with `/`.
+    #[serde(default = "Health::default_path")]
+    pub path: String,
+
+    /// The checks the endpoint runs. The endpoint returns a `200` response
+    /// only if all of them pass.
+    #[serde(default = "Health::default_checks")]
+    pub checks: Vec<HealthCheck>,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            checks: Self::default_checks(),
+        }
+    }
+}
+
+impl Health {
+    fn default_path() -> String {
+        String::from("/health")
+    }
+
+    fn default_checks() -> Vec<HealthCheck> {
+        vec![HealthCheck::Core, HealthCheck::Repository, HealthCheck::Database]
+    }
+}
+
+/// The limits of the size of the HTTP tracker requests.
+///
+/// The requests exceeding them are rejected with a bencoded failure response
+/// and they are counted in the tracker statistics.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RequestLimits {
+    /// Maximum length in bytes of the request URI, including the query
+    /// string. Longer requests get a `414 URI Too Long` response.
+    ///
+    /// A `scrape` request with 74 infohashes is about 5 KiB long, so this
+    /// limit should be raised when `core.max_scrape_torrents` is raised.
+    #[serde(default = "RequestLimits::default_max_uri_bytes")]
+    pub max_uri_bytes: usize,
+
+    /// Maximum size in bytes of all the request headers, names and values.
+    /// Larger requests get a `431 Request Header Fields Too Large` response.
+    #[serde(default = "RequestLimits::default_max_header_bytes")]
+    pub max_header_bytes: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_uri_bytes: Self::default_max_uri_bytes(),
+            max_header_bytes: Self::default_max_header_bytes(),
+        }
+    }
+}
+
+impl RequestLimits {
+    fn default_max_uri_bytes() -> usize {
+        8192
+    }
+
+    fn default_max_header_bytes() -> usize {
+        16_384
+    }
+}
+
+/// The checks the health endpoint of an HTTP tracker can run.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Hash, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthCheck {
+    /// The core tracker accepts `announce` requests. It fails while the
+    /// tracker is in maintenance mode.
+    Core,
+    /// The in-memory torrents repository answers queries.
+    Repository,
+    /// The database answers queries.
+    Database,
+}
+
+/// The supported formats for the authentication key in the URL path.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Hash, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPathFormat {
+    /// The key goes after the action: `/announce/<key>` and `/scrape/<key>`.
+    Suffix,
+    /// The key goes before the action: `/<key>/announce` and `/<key>/scrape`.
+    /// It's the format used by some other private tracker software.
+    Prefix,
+    /// Both formats are accepted.
+    Both,
+}
+
+impl KeyPathFormat {
+    /// Whether the `/announce/<key>` format is accepted.
+    #[must_use]
+    pub fn accepts_suffix(&self) -> bool {
+        matches!(self, Self::Suffix | Self::Both)
+    }
+
+    /// Whether the `/<key>/announce` format is accepted.
+    #[must_use]
+    pub fn accepts_prefix(&self) -> bool {
+        matches!(self, Self::Prefix | Self::Both)
+    }
+}
+
+impl Validator for HttpTracker {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if self.bind_address.is_unix() && self.tsl_config.is_some() {
+            return Err(SemanticValidationError::TlsOnUnixSocket {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if self.bind_address.is_unix() && !self.bind_address.is_single() {
+            return Err(SemanticValidationError::UnixSocketWithOtherBindAddresses {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if self.bind_address.is_unix() && self.proxy_protocol {
+            return Err(SemanticValidationError::ProxyProtocolOnUnixSocket {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if let Some(bind_device) = &self.bind_device {
+            validate_bind_device(bind_device, self.bind_address.first())?;
+        }
+
+        if !self.health.path.starts_with('/') || RESERVED_PATHS.contains(&self.health.path.as_str()) {
+            return Err(SemanticValidationError::InvalidHealthPath {
+                path: self.health.path.clone(),
+            });
+        }
+
+        if self.request_limits.max_uri_bytes == 0 || self.request_limits.max_header_bytes == 0 {
+            return Err(SemanticValidationError::InvalidHttpRequestLimits {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        Ok(())
+    }
 }
@@ -1,18 +1,29 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::validator::{SemanticValidationError, Validator};
+
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct Logging {
     /// Logging level. Possible values are: `Off`, `Error`, `Warn`, `Info`,
     /// `Debug` and `Trace`. Default is `Info`.
     #[serde(default = "Logging::default_threshold")]
     pub threshold: Threshold,
+
+    /// Deduplication of the repeated warnings and errors, if any. When it's
+    /// enabled, an event identical to one logged in the last `window_secs`
+    /// seconds is not logged. A summary with the number of suppressed
+    /// occurrences is logged when the window ends.
+    #[serde(default = "Logging::default_deduplication")]
+    pub deduplication: Option<LogDeduplication>,
 }
 
 impl Default for Logging {
     fn default() -> Self {
         Self {
             threshold: Self::default_threshold(),
+            deduplication: Self::default_deduplication(),
         }
     }
 }
@@ -21,9 +32,47 @@ impl Logging {
     fn default_threshold() -> Threshold {
         Threshold::Info
     }
+
+    fn default_deduplication() -> Option<LogDeduplication> {
+        None
+    }
+}
+
+impl Validator for Logging {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if let Some(deduplication) = self.deduplication {
+            if deduplication.window_secs == 0 {
+                return Err(SemanticValidationError::InvalidLogDeduplication);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Configuration for the deduplication of the repeated warnings and errors.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct LogDeduplication {
+    /// Time in seconds the repeated events are collapsed into one summary.
+    #[serde(default = "LogDeduplication::default_window_secs")]
+    pub window_secs: u64,
+}
+
+impl Default for LogDeduplication {
+    fn default() -> Self {
+        Self {
+            window_secs: Self::default_window_secs(),
+        }
+    }
+}
+
+impl LogDeduplication {
+    fn default_window_secs() -> u64 {
+        60
+    }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum Threshold {
     /// A threshold lower than all security levels.
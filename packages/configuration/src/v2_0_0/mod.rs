@@ -18,6 +18,9 @@
 //! When you run the tracker without providing the configuration via a file or
 //! env var, the default configuration is used.
 //!
+//! To create a configuration programmatically, for example in tests, use
+//! the [`builder`] returned by [`Configuration::builder`].
+//!
 //! # Table of contents
 //!
 //! - [Sections](#sections)
@@ -44,6 +47,7 @@
 //! - [`HTTP Tracker configuration`](crate::v2::http_tracker::HttpTracker)
 //! - [`UDP Tracker configuration`](crate::v2::udp_tracker::UdpTracker)
 //! - [`Health Check API configuration`](crate::v2::health_check_api::HealthCheckApi)
+//! - [`Seed configuration`](crate::v2::seed::Seed)
 //!
 //! ## Port binding
 //!
@@ -51,6 +55,13 @@
 //! port `0`. For example, if you want to bind to a random port on all
 //! interfaces, use `0.0.0.0:0`. The OS will choose a random free port.
 //!
+//! The bind address of every service can also be a hostname like
+//! `tracker.local:6969`, or a network interface name with the `iface:` prefix
+//! like `iface:eth0:6969`. They are resolved to a socket address when the
+//! configuration is loaded, and the tracker does not start if they can't be
+//! resolved. Refer to [`BindAddress`](crate::BindAddress) for more
+//! information.
+//!
 //! ## TSL support
 //!
 //! For the API and HTTP tracker you can enable TSL by setting `ssl_enabled` to
@@ -199,8 +210,10 @@
 //! threshold = "info"
 //!
 //! [core]
+//! hybrid = false
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
+//! max_scrape_torrents = 74
 //! private = false
 //! tracker_usage_statistics = true
 //!
@@ -212,14 +225,28 @@
 //! driver = "sqlite3"
 //! path = "./storage/tracker/lib/database/sqlite3.db"
 //!
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//!
 //! [core.net]
+//! exclude_ipv6_peers_for_ipv4_clients = false
 //! external_ip = "0.0.0.0"
 //! on_reverse_proxy = false
+//! trusted_proxies = []
+//!
+//! [core.statistics]
+//! http = true
+//! udp = true
+//! histograms = true
+//! connection_ids = true
 //!
 //! [core.tracker_policy]
 //! max_peer_timeout = 900
 //! persistent_torrent_completed_stat = false
 //! remove_peerless_torrents = true
+//! peer_eviction_policy = "reject_new"
+//! merge_dual_stack_peers = false
 //!
 //! [http_api]
 //! bind_address = "127.0.0.1:1212"
@@ -229,28 +256,36 @@
 //! [health_check_api]
 //! bind_address = "127.0.0.1:1313"
 //!```
+pub mod builder;
 pub mod core;
 pub mod database;
 pub mod health_check_api;
 pub mod http_tracker;
 pub mod logging;
 pub mod network;
+pub mod seed;
 pub mod tracker_api;
 pub mod udp_tracker;
+pub mod virtual_tracker;
 
+use std::collections::HashSet;
 use std::fs;
 use std::net::IpAddr;
 
 use figment::providers::{Env, Format, Serialized, Toml};
 use figment::Figment;
 use logging::Logging;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use self::core::Core;
+use self::database::Driver;
 use self::health_check_api::HealthCheckApi;
 use self::http_tracker::HttpTracker;
+use self::seed::Seed;
 use self::tracker_api::HttpApi;
 use self::udp_tracker::UdpTracker;
+use self::virtual_tracker::VirtualTracker;
 use crate::validator::{SemanticValidationError, Validator};
 use crate::{Error, Info, Metadata, Version};
 
@@ -264,7 +299,7 @@ const CONFIG_OVERRIDE_PREFIX: &str = "TORRUST_TRACKER_CONFIG_OVERRIDE_";
 const CONFIG_OVERRIDE_SEPARATOR: &str = "__";
 
 /// Core configuration for the tracker.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Default, Clone)]
 pub struct Configuration {
     /// Configuration metadata.
     pub metadata: Metadata,
@@ -290,6 +325,14 @@ pub struct Configuration {
 
     /// The Health Check API configuration.
     pub health_check_api: HealthCheckApi,
+
+    /// The initial state to pre-populate in the database.
+    pub seed: Option<Seed>,
+
+    /// Other trackers served by the same process, each one with its own
+    /// core configuration. The UDP and HTTP trackers are bound to them by
+    /// name.
+    pub virtual_trackers: Option<Vec<VirtualTracker>>,
 }
 
 impl Configuration {
@@ -318,18 +361,24 @@ impl Configuration {
     ///
     /// Configuration provided via env var has priority over config file path.
     ///
+    /// The [path variables](crate::paths) in the TOML configuration, like
+    /// `${DATA_DIR}`, are replaced before the env var overrides are applied.
+    ///
     /// # Errors
     ///
     /// Will return `Err` if the environment variable does not exist or has a bad configuration.
     pub fn load(info: &Info) -> Result<Configuration, Error> {
         // Load configuration provided by the user, prioritizing env vars
-        let figment = if let Some(config_toml) = &info.config_toml {
-            Figment::from(Toml::string(config_toml)).merge(Env::prefixed(CONFIG_OVERRIDE_PREFIX).split(CONFIG_OVERRIDE_SEPARATOR))
+        let toml = if let Some(config_toml) = &info.config_toml {
+            Toml::string(&info.path_variables.expand(config_toml)?)
+        } else if let Ok(config_toml) = fs::read_to_string(&info.config_toml_path) {
+            Toml::string(&info.path_variables.expand(&config_toml)?)
         } else {
-            Figment::from(Toml::file(&info.config_toml_path))
-                .merge(Env::prefixed(CONFIG_OVERRIDE_PREFIX).split(CONFIG_OVERRIDE_SEPARATOR))
+            Toml::file(&info.config_toml_path)
         };
 
+        let figment = Figment::from(toml).merge(Env::prefixed(CONFIG_OVERRIDE_PREFIX).split(CONFIG_OVERRIDE_SEPARATOR));
+
         // Make sure user has provided the mandatory options.
         Self::check_mandatory_options(&figment)?;
 
@@ -337,7 +386,7 @@ impl Configuration {
         let figment = figment.join(Serialized::defaults(Configuration::default()));
 
         // Build final configuration.
-        let config: Configuration = figment.extract()?;
+        let mut config: Configuration = figment.extract()?;
 
         // Make sure the provided schema version matches this version.
         if config.metadata.schema_version != Version::new(VERSION_2_0_0) {
@@ -346,9 +395,46 @@ impl Configuration {
             });
         }
 
+        config.resolve_bind_addresses()?;
+
         Ok(config)
     }
 
+    /// It resolves the hostnames and the network interface names in the bind
+    /// addresses of all the services to socket addresses.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if any of the bind addresses can't be resolved.
+    pub fn resolve_bind_addresses(&mut self) -> Result<(), Error> {
+        let bind_addresses = self
+            .http_api
+            .iter_mut()
+            .map(|http_api| &mut http_api.bind_address)
+            .chain(
+                self.udp_trackers
+                    .iter_mut()
+                    .flatten()
+                    .flat_map(|udp_tracker| udp_tracker.bind_address.iter_mut()),
+            )
+            .chain(
+                self.http_trackers
+                    .iter_mut()
+                    .flatten()
+                    .flat_map(|http_tracker| http_tracker.bind_address.iter_mut()),
+            )
+            .chain(std::iter::once(&mut self.health_check_api.bind_address));
+
+        for bind_address in bind_addresses {
+            *bind_address = bind_address.resolve().map_err(|source| Error::UnresolvedBindAddress {
+                bind_address: bind_address.to_string(),
+                source,
+            })?;
+        }
+
+        Ok(())
+    }
+
     /// Some configuration options are mandatory. The tracker will panic if
     /// the user doesn't provide an explicit value for them from one of the
     /// configuration sources: TOML or ENV VARS.
@@ -409,11 +495,27 @@ impl Configuration {
         serde_json::to_string_pretty(self).expect("Could not encode JSON value")
     }
 
+    /// Generates the [JSON Schema](https://json-schema.org/) of the
+    /// configuration, so editors and configuration management tools can
+    /// validate the configuration for this version of the tracker.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if it can't be converted to JSON.
+    #[must_use]
+    pub fn json_schema() -> String {
+        serde_json::to_string_pretty(&schemars::schema_for!(Configuration)).expect("Could not encode JSON value")
+    }
+
     /// Masks secrets in the configuration.
     #[must_use]
     pub fn mask_secrets(mut self) -> Self {
         self.core.database.mask_secrets();
 
+        for virtual_tracker in self.virtual_trackers.iter_mut().flatten() {
+            virtual_tracker.core.database.mask_secrets();
+        }
+
         if let Some(ref mut api) = self.http_api {
             api.mask_secrets();
         }
@@ -424,17 +526,132 @@ impl Configuration {
 
 impl Validator for Configuration {
     fn validate(&self) -> Result<(), SemanticValidationError> {
-        self.core.validate()
+        self.logging.validate()?;
+
+        self.core.validate()?;
+
+        if let Some(http_api) = &self.http_api {
+            http_api.validate()?;
+        }
+
+        if self.health_check_api.bind_address.is_unix() {
+            return Err(SemanticValidationError::UnixSocketNotSupported {
+                service: "Health Check API",
+                bind_address: self.health_check_api.bind_address.to_string(),
+            });
+        }
+
+        self.validate_virtual_trackers()?;
+
+        for udp_tracker in self.udp_trackers.iter().flatten() {
+            udp_tracker.validate()?;
+
+            let core = self.core_of(udp_tracker.tracker.as_deref())?;
+
+            if udp_tracker.async_announce && core.async_announce.is_none() {
+                return Err(SemanticValidationError::AsyncAnnounceWithoutQueue {
+                    bind_address: udp_tracker.bind_address.to_string(),
+                });
+            }
+        }
+
+        for http_tracker in self.http_trackers.iter().flatten() {
+            http_tracker.validate()?;
+
+            let core = self.core_of(http_tracker.tracker.as_deref())?;
+
+            if http_tracker.async_announce && core.async_announce.is_none() {
+                return Err(SemanticValidationError::AsyncAnnounceWithoutQueue {
+                    bind_address: http_tracker.bind_address.to_string(),
+                });
+            }
+
+            // Clients connected through a Unix domain socket do not have an
+            // IP address, so it has to be taken from the proxy headers.
+            if http_tracker.bind_address.is_unix() && !core.net.on_reverse_proxy {
+                return Err(SemanticValidationError::UnixSocketWithoutReverseProxy {
+                    bind_address: http_tracker.bind_address.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Configuration {
+    /// The core configuration of the tracker with the given name, or the main
+    /// one when there is no name.
+    fn core_of(&self, tracker: Option<&str>) -> Result<&Core, SemanticValidationError> {
+        match tracker {
+            None => Ok(&self.core),
+            Some(name) => self
+                .virtual_trackers
+                .iter()
+                .flatten()
+                .find(|virtual_tracker| virtual_tracker.name == name)
+                .map(|virtual_tracker| &virtual_tracker.core)
+                .ok_or_else(|| SemanticValidationError::UnknownVirtualTracker { name: name.to_owned() }),
+        }
+    }
+
+    fn validate_virtual_trackers(&self) -> Result<(), SemanticValidationError> {
+        let mut names = HashSet::new();
+        let mut hosts = HashSet::new();
+        let mut databases = HashSet::new();
+
+        if self.core.database.driver != Driver::None {
+            databases.insert(&self.core.database.path);
+        }
+
+        for virtual_tracker in self.virtual_trackers.iter().flatten() {
+            virtual_tracker.core.validate()?;
+
+            if virtual_tracker.name.is_empty() || !names.insert(&virtual_tracker.name) {
+                return Err(SemanticValidationError::InvalidVirtualTrackerName {
+                    name: virtual_tracker.name.clone(),
+                });
+            }
+
+            for host in &virtual_tracker.hosts {
+                if !hosts.insert(host.to_lowercase()) {
+                    return Err(SemanticValidationError::DuplicateVirtualTrackerHost { host: host.clone() });
+                }
+            }
+
+            // Each tracker loads its keys and whitelist from its own database.
+            if virtual_tracker.core.database.driver != Driver::None && !databases.insert(&virtual_tracker.core.database.path) {
+                return Err(SemanticValidationError::SharedVirtualTrackerDatabase {
+                    name: virtual_tracker.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::result_large_err)]
 mod tests {
 
-    use std::net::{IpAddr, Ipv4Addr};
-
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use crate::paths::Layout;
+    use crate::v2_0_0::core::{ConsistencyCheck, Core, FailureReasons, Preflight, PrivateMode, RequestDeadline, SlowRequests};
+    use crate::v2_0_0::database::Driver;
+    use crate::v2_0_0::http_tracker::{Health, HealthCheck, HttpTracker, KeyPathFormat, RequestLimits};
+    use crate::v2_0_0::logging::LogDeduplication;
+    use crate::v2_0_0::seed::{Seed, SeedKey, SeedTorrent};
+    use crate::v2_0_0::tracker_api::Registration;
+    use crate::v2_0_0::udp_tracker::{UdpRuntime, UdpTracker};
+    use crate::v2_0_0::virtual_tracker::VirtualTracker;
     use crate::v2_0_0::Configuration;
-    use crate::Info;
+    use crate::validator::{SemanticValidationError, Validator};
+    use crate::{
+        AnnouncePolicy, AsyncAnnounce, AuthenticatedAnnouncePolicy, BindAddress, BindAddresses, DatabaseAuthorization,
+        DatabaseBackup, DeadLetterQueue, Error, Info,
+    };
 
     #[cfg(test)]
     fn default_config_toml() -> String {
@@ -447,8 +664,10 @@ mod tests {
                                 threshold = "info"
 
                                 [core]
+                                hybrid = false
                                 inactive_peer_cleanup_interval = 600
                                 listed = false
+                                max_scrape_torrents = 74
                                 private = false
                                 tracker_usage_statistics = true
 
@@ -460,14 +679,28 @@ mod tests {
                                 driver = "sqlite3"
                                 path = "./storage/tracker/lib/database/sqlite3.db"
 
+                                [core.maintenance]
+                                enabled = false
+                                retry_in = 10
+
                                 [core.net]
+                                exclude_ipv6_peers_for_ipv4_clients = false
                                 external_ip = "0.0.0.0"
                                 on_reverse_proxy = false
+                                trusted_proxies = []
+
+                                [core.statistics]
+                                http = true
+                                udp = true
+                                histograms = true
+                                connection_ids = true
 
                                 [core.tracker_policy]
                                 max_peer_timeout = 900
                                 persistent_torrent_completed_stat = false
                                 remove_peerless_torrents = true
+                                peer_eviction_policy = "reject_new"
+                                merge_dual_stack_peers = false
 
                                 [health_check_api]
                                 bind_address = "127.0.0.1:1313"
@@ -498,6 +731,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn configuration_should_have_a_json_schema_with_all_the_sections() {
+        let schema: serde_json::Value = serde_json::from_str(&Configuration::json_schema()).unwrap();
+
+        let sections: Vec<&String> = schema["properties"].as_object().unwrap().keys().collect();
+
+        assert_eq!(
+            sections,
+            vec![
+                "metadata",
+                "logging",
+                "core",
+                "udp_trackers",
+                "http_trackers",
+                "http_api",
+                "health_check_api",
+                "seed",
+                "virtual_trackers"
+            ]
+        );
+        assert_eq!(schema["$defs"]["BindAddress"]["type"], "string");
+    }
+
     #[test]
     fn configuration_should_be_saved_in_a_toml_config_file() {
         use std::{env, fs};
@@ -544,6 +800,7 @@ mod tests {
             let info = Info {
                 config_toml: None,
                 config_toml_path: "tracker.toml".to_string(),
+                ..Default::default()
             };
 
             let configuration = Configuration::load(&info).expect("Could not load configuration from file");
@@ -554,6 +811,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn configuration_should_replace_the_path_variables_in_the_toml_file() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "tracker.toml",
+                r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [core.database]
+                path = "${DATA_DIR}/database/sqlite3.db"
+            "#,
+            )?;
+
+            let info = Info {
+                config_toml: None,
+                config_toml_path: "tracker.toml".to_string(),
+                ..Default::default()
+            }
+            .with_paths_layout(Layout::Fhs);
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.core.database.path,
+                "/var/lib/torrust-tracker/database/sqlite3.db".to_string()
+            );
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn configuration_should_use_the_default_values_when_only_the_mandatory_options_are_provided_by_the_user_via_toml_content() {
         figment::Jail::expect_with(|_jail| {
@@ -573,6 +869,7 @@ mod tests {
             let info = Info {
                 config_toml: Some(config_toml),
                 config_toml_path: String::new(),
+                ..Default::default()
             };
 
             let configuration = Configuration::load(&info).expect("Could not load configuration from file");
@@ -605,6 +902,7 @@ mod tests {
             let info = Info {
                 config_toml: Some(config_toml),
                 config_toml_path: String::new(),
+                ..Default::default()
             };
 
             let configuration = Configuration::load(&info).expect("Could not load configuration from file");
@@ -639,6 +937,7 @@ mod tests {
             let info = Info {
                 config_toml: None,
                 config_toml_path: "tracker.toml".to_string(),
+                ..Default::default()
             };
 
             let configuration = Configuration::load(&info).expect("Could not load configuration from file");
@@ -657,6 +956,7 @@ mod tests {
             let info = Info {
                 config_toml: Some(default_config_toml()),
                 config_toml_path: String::new(),
+                ..Default::default()
             };
 
             let configuration = Configuration::load(&info).expect("Could not load configuration from file");
@@ -669,4 +969,1168 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn configuration_should_allow_binding_the_tracker_api_to_a_unix_domain_socket() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [http_api]
+                bind_address = "unix:/var/run/torrust/api.sock"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.http_api.unwrap().bind_address,
+                BindAddress::Unix("/var/run/torrust/api.sock".into())
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_enabling_the_self_registration_in_the_tracker_api() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = true
+
+                [http_api]
+                bind_address = "127.0.0.1:1212"
+
+                [http_api.registration]
+                key_seconds_valid = 2592000
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.http_api.unwrap().registration,
+                Some(Registration {
+                    key_seconds_valid: Some(2_592_000)
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_http_trackers_on_unix_domain_sockets_when_the_tracker_is_not_on_a_reverse_proxy() {
+        let mut configuration = Configuration {
+            http_trackers: Some(vec![HttpTracker {
+                bind_address: BindAddress::Unix("/var/run/torrust/http_tracker.sock".into()).into(),
+                bind_device: None,
+                tsl_config: None,
+                proxy_protocol: false,
+                key_path_format: KeyPathFormat::Suffix,
+                health: Health::default(),
+                request_limits: RequestLimits::default(),
+                async_announce: false,
+                tracker: None,
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::UnixSocketWithoutReverseProxy { .. })
+        ));
+
+        configuration.core.net.on_reverse_proxy = true;
+
+        assert!(configuration.validate().is_ok());
+    }
+
+    #[test]
+    fn configuration_should_allow_binding_a_udp_tracker_to_more_than_one_address() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = ["0.0.0.0:6969", "[::]:6969"]
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.udp_trackers.unwrap()[0].bind_addresses(),
+                vec![
+                    "0.0.0.0:6969".parse::<SocketAddr>().unwrap(),
+                    "[::]:6969".parse::<SocketAddr>().unwrap()
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_resolve_the_hostnames_in_the_bind_addresses_when_it_is_loaded() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "localhost:6969"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            let bind_address = configuration.udp_trackers.unwrap()[0]
+                .bind_address
+                .first()
+                .socket_addr()
+                .unwrap();
+
+            assert!(bind_address.ip().is_loopback());
+            assert_eq!(bind_address.port(), 6969);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_fail_loading_when_a_bind_address_cannot_be_resolved() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "iface:missing0:6969"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            assert!(matches!(Configuration::load(&info), Err(Error::UnresolvedBindAddress { .. })));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_binding_a_udp_tracker_to_a_unix_domain_socket() {
+        let configuration = Configuration {
+            udp_trackers: Some(vec![UdpTracker {
+                bind_address: BindAddress::Unix("/var/run/torrust/udp_tracker.sock".into()).into(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::UnixSocketNotSupported {
+                service: "UDP tracker",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_a_dual_stack_udp_tracker_socket() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "[::]:6969"
+                ipv6_only = false
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.udp_trackers.unwrap()[0].ipv6_only, Some(false));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_marking_the_udp_tracker_responses_with_a_dscp_value() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "0.0.0.0:6969"
+                dscp = 46
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.udp_trackers.unwrap()[0].dscp, Some(46));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_binding_the_trackers_to_a_network_interface() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "0.0.0.0:6969"
+                bind_device = "eth1"
+
+                [[http_trackers]]
+                bind_address = "0.0.0.0:7070"
+                bind_device = "eth1"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.udp_trackers.unwrap()[0].bind_device, Some("eth1".to_string()));
+            assert_eq!(configuration.http_trackers.unwrap()[0].bind_device, Some("eth1".to_string()));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_an_invalid_network_interface_name() {
+        for bind_device in [String::new(), "a".repeat(16)] {
+            let configuration = Configuration {
+                udp_trackers: Some(vec![UdpTracker {
+                    bind_device: Some(bind_device),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            };
+
+            assert!(matches!(
+                configuration.validate(),
+                Err(SemanticValidationError::InvalidBindDevice { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn configuration_should_not_allow_binding_a_unix_domain_socket_to_a_network_interface() {
+        let configuration = Configuration {
+            http_trackers: Some(vec![HttpTracker {
+                bind_address: BindAddress::Unix("/tmp/tracker.sock".into()).into(),
+                bind_device: Some("eth1".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::BindDeviceNotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_udp_tracker_dscp_value_greater_than_63() {
+        let configuration = Configuration {
+            udp_trackers: Some(vec![UdpTracker {
+                dscp: Some(64),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidUdpDscp { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_a_dedicated_runtime_for_udp_trackers() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "0.0.0.0:6969"
+
+                [udp_trackers.runtime]
+                worker_threads = 2
+                core_ids = [2, 3]
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.udp_trackers.unwrap()[0].runtime,
+                Some(UdpRuntime {
+                    worker_threads: 2,
+                    core_ids: vec![2, 3]
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_udp_tracker_runtime_without_worker_threads() {
+        let configuration = Configuration {
+            udp_trackers: Some(vec![UdpTracker {
+                runtime: Some(UdpRuntime {
+                    worker_threads: 0,
+                    core_ids: vec![],
+                }),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidUdpRuntime { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_async_announces_without_the_core_queue() {
+        let mut configuration = Configuration {
+            udp_trackers: Some(vec![UdpTracker {
+                async_announce: true,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::AsyncAnnounceWithoutQueue { .. })
+        ));
+
+        configuration.core.async_announce = Some(AsyncAnnounce::default());
+
+        assert!(configuration.validate().is_ok());
+    }
+
+    #[test]
+    fn configuration_should_not_allow_binding_an_http_tracker_to_a_unix_domain_socket_and_other_addresses() {
+        let mut configuration = Configuration {
+            http_trackers: Some(vec![HttpTracker {
+                bind_address: BindAddresses::new(
+                    BindAddress::Unix("/var/run/torrust/http_tracker.sock".into()),
+                    vec!["127.0.0.1:7070".parse::<SocketAddr>().unwrap().into()],
+                ),
+                bind_device: None,
+                tsl_config: None,
+                proxy_protocol: false,
+                key_path_format: KeyPathFormat::Suffix,
+                health: Health::default(),
+                request_limits: RequestLimits::default(),
+                async_announce: false,
+                tracker: None,
+            }]),
+            ..Default::default()
+        };
+
+        configuration.core.net.on_reverse_proxy = true;
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::UnixSocketWithOtherBindAddresses { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_a_different_announce_policy_for_authenticated_peers() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = true
+                private = false
+
+                [core.authenticated_announce_policy]
+                interval = 300
+                max_peers = 200
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            let policy = configuration.core.authenticated_announce_policy.unwrap();
+
+            assert_eq!(policy.interval, 300);
+            assert_eq!(policy.interval_min, AnnouncePolicy::default().interval_min);
+            assert_eq!(policy.max_peers, 200);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_an_authenticated_announce_policy_with_a_minimum_interval_greater_than_the_interval() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.authenticated_announce_policy = Some(AuthenticatedAnnouncePolicy {
+            interval: 60,
+            interval_min: 120,
+            max_peers: 200,
+        });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidAuthenticatedAnnouncePolicy)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_deduplicating_the_repeated_log_events() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [logging.deduplication]
+
+                [core]
+                listed = true
+                private = false
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.logging.deduplication, Some(LogDeduplication::default()));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_customizing_the_failure_reasons_in_several_languages() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = true
+                private = false
+
+                [core.failure_reasons]
+                language = "es"
+
+                [core.failure_reasons.templates.en]
+                E03 = "torrent not registered, visit https://example.com/rules"
+
+                [core.failure_reasons.templates.es]
+                E03 = "torrent no registrado, visita https://example.com/rules"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            let failure_reasons = configuration.core.failure_reasons.unwrap();
+
+            assert_eq!(failure_reasons.language, "es");
+            assert_eq!(
+                failure_reasons.templates["es"]["E03"],
+                "torrent no registrado, visita https://example.com/rules"
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_use_the_default_slow_request_thresholds_that_are_not_overridden() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [core.slow_requests]
+                announce_threshold_ms = 100
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.core.slow_requests,
+                Some(SlowRequests {
+                    announce_threshold_ms: 100,
+                    scrape_threshold_ms: 500,
+                    api_threshold_ms: 1000,
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_failure_reasons_language_without_templates() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.failure_reasons = Some(FailureReasons {
+            language: "es".to_string(),
+            ..FailureReasons::default()
+        });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidFailureReasons)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_overriding_the_peer_cleanup_options_in_private_mode() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "tracker.toml",
+                r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = true
+
+                [core.private_mode]
+                inactive_peer_cleanup_interval = 1800
+                max_peer_timeout = 3600
+            "#,
+            )?;
+
+            let info = Info {
+                config_toml: None,
+                config_toml_path: "tracker.toml".to_string(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.core.private_mode,
+                Some(PrivateMode {
+                    check_keys_expiration: true,
+                    inactive_peer_cleanup_interval: Some(1800),
+                    max_peer_timeout: Some(3600),
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_log_deduplication_window_of_zero_seconds() {
+        let mut configuration = Configuration::default();
+
+        configuration.logging.deduplication = Some(LogDeduplication { window_secs: 0 });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidLogDeduplication)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_request_deadline_budget_of_zero_milliseconds() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.request_deadline = Some(RequestDeadline {
+            budget_ms: 0,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidRequestDeadline)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_request_deadline_without_concurrent_requests() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.request_deadline = Some(RequestDeadline {
+            max_concurrent_requests: 0,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidRequestDeadline)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_a_consistency_check_interval_of_zero_seconds() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.consistency_check = Some(ConsistencyCheck { interval_secs: 0 });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidConsistencyCheck)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_an_empty_preflight_ntp_server() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.preflight = Some(Preflight {
+            ntp_server: Some(String::new()),
+            ..Preflight::default()
+        });
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidPreflight)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_the_hybrid_mode_in_a_private_tracker() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.hybrid = true;
+
+        assert!(configuration.validate().is_ok());
+
+        configuration.core.private = true;
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::HybridPrivateTracker)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_only_allow_database_backups_for_the_sqlite_driver() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.database_backup = Some(DatabaseBackup {
+            directory: "./storage/tracker/lib/database/backups".into(),
+            interval: 86_400,
+            retention: 7,
+        });
+
+        assert!(configuration.validate().is_ok());
+
+        configuration.core.database.driver = Driver::MySQL;
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::DatabaseBackupNotSupported)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_running_the_tracker_without_a_database() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [core.database]
+                driver = "None"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.core.database.driver, Driver::None);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_the_dead_letter_queue_without_a_database() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.database.driver = Driver::None;
+        configuration.core.dead_letter_queue = Some(DeadLetterQueue::default());
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::DeadLetterQueueWithoutDatabase)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_the_database_authorization_without_a_database() {
+        let mut configuration = Configuration::default();
+
+        configuration.core.database.driver = Driver::None;
+        configuration.core.database_authorization = Some(DatabaseAuthorization::default());
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::DatabaseAuthorizationWithoutDatabase)
+        ));
+    }
+
+    #[test]
+    fn configuration_should_write_the_service_manifest_to_the_storage_dir_by_default() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [core.service_manifest]
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.core.service_manifest.unwrap().path,
+                "./storage/tracker/lib/services.json"
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_seeding_the_initial_state() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = true
+                private = true
+
+                [seed]
+                whitelist = ["9c38422213e30bff212b30c360d26f9a02136422"]
+
+                [[seed.keys]]
+                key = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"
+
+                [[seed.keys]]
+                key = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6"
+                valid_until = 1893456000
+
+                [[seed.torrents]]
+                info_hash = "9c38422213e30bff212b30c360d26f9a02136422"
+                policy = "seed-only"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.seed,
+                Some(Seed {
+                    whitelist: vec!["9c38422213e30bff212b30c360d26f9a02136422".to_string()],
+                    keys: vec![
+                        SeedKey {
+                            key: "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".to_string(),
+                            valid_until: None,
+                        },
+                        SeedKey {
+                            key: "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".to_string(),
+                            valid_until: Some(1_893_456_000),
+                        },
+                    ],
+                    torrents: vec![SeedTorrent {
+                        info_hash: "9c38422213e30bff212b30c360d26f9a02136422".to_string(),
+                        policy: "seed-only".to_string(),
+                    }],
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_changing_the_path_and_checks_of_the_http_tracker_health_endpoint() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[http_trackers]]
+                bind_address = "0.0.0.0:7070"
+
+                [http_trackers.health]
+                path = "/lb/status"
+                checks = ["core", "database"]
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.http_trackers.unwrap()[0].health,
+                Health {
+                    path: "/lb/status".to_string(),
+                    checks: vec![HealthCheck::Core, HealthCheck::Database],
+                }
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_an_http_tracker_health_path_that_is_already_used_by_the_tracker() {
+        let configuration = Configuration {
+            http_trackers: Some(vec![HttpTracker {
+                health: Health {
+                    path: "/announce".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidHealthPath { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_http_tracker_request_limits_of_zero_bytes() {
+        let configuration = Configuration {
+            http_trackers: Some(vec![HttpTracker {
+                request_limits: RequestLimits {
+                    max_uri_bytes: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidHttpRequestLimits { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_allow_virtual_trackers_with_their_own_core_configuration() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[virtual_trackers]]
+                name = "private"
+                hosts = ["private.tracker.example.com"]
+
+                [virtual_trackers.core]
+                private = true
+
+                [virtual_trackers.core.database]
+                path = "./storage/tracker/lib/database/private.sqlite3.db"
+
+                [[http_trackers]]
+                bind_address = "0.0.0.0:7071"
+                tracker = "private"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+                ..Default::default()
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            let virtual_tracker = &configuration.virtual_trackers.as_ref().unwrap()[0];
+
+            assert_eq!(virtual_tracker.name, "private");
+            assert_eq!(virtual_tracker.hosts, vec!["private.tracker.example.com".to_string()]);
+            assert!(virtual_tracker.core.private);
+            assert_eq!(virtual_tracker.core.database.driver, Driver::Sqlite3);
+            assert_eq!(
+                configuration.http_trackers.as_ref().unwrap()[0].tracker,
+                Some("private".to_string())
+            );
+            assert!(configuration.validate().is_ok());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_not_allow_services_bound_to_an_unknown_virtual_tracker() {
+        let configuration = Configuration {
+            udp_trackers: Some(vec![UdpTracker {
+                tracker: Some("unknown".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::UnknownVirtualTracker { .. })
+        ));
+    }
+
+    #[test]
+    fn configuration_should_not_allow_virtual_trackers_sharing_the_database() {
+        let mut configuration = Configuration {
+            virtual_trackers: Some(vec![VirtualTracker {
+                name: "other".to_string(),
+                hosts: Vec::new(),
+                core: Core::default(),
+            }]),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::SharedVirtualTrackerDatabase { .. })
+        ));
+
+        configuration.virtual_trackers.as_mut().unwrap()[0].core.database.path = "./other.sqlite3.db".to_string();
+
+        assert!(configuration.validate().is_ok());
+    }
 }
@@ -1,10 +1,18 @@
 use std::net::{IpAddr, Ipv4Addr};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct Network {
+    /// Whether to exclude the IPv6 peers from the peer lists returned to the
+    /// clients announcing from an IPv4 address. Non-compact HTTP responses
+    /// mix both address families in the same list, and IPv4-only clients
+    /// can't connect to the IPv6 peers.
+    #[serde(default = "Network::default_exclude_ipv6_peers_for_ipv4_clients")]
+    pub exclude_ipv6_peers_for_ipv4_clients: bool,
+
     /// The external IP address of the tracker. If the client is using a
     /// loopback IP address, this IP address will be used instead. If the peer
     /// is using a loopback IP address, the tracker assumes that the peer is
@@ -18,13 +26,27 @@ pub struct Network {
     /// sent from the proxy will be used to get the client's IP address.
     #[serde(default = "Network::default_on_reverse_proxy")]
     pub on_reverse_proxy: bool,
+
+    /// The IP addresses of the proxies the tracker trusts. When the tracker
+    /// is behind a reverse proxy, the peer addresses taken from the
+    /// `X-Forwarded-For` header are only probed by the peer reachability
+    /// verification when the request comes from one of these proxies.
+    /// Requests received on a Unix domain socket always come from a local
+    /// proxy, so they are trusted.
+    ///
+    /// The services with the `proxy_protocol` option enabled only accept
+    /// PROXY protocol headers from these proxies and from loopback addresses.
+    #[serde(default = "Network::default_trusted_proxies")]
+    pub trusted_proxies: Vec<IpAddr>,
 }
 
 impl Default for Network {
     fn default() -> Self {
         Self {
+            exclude_ipv6_peers_for_ipv4_clients: Self::default_exclude_ipv6_peers_for_ipv4_clients(),
             external_ip: Self::default_external_ip(),
             on_reverse_proxy: Self::default_on_reverse_proxy(),
+            trusted_proxies: Self::default_trusted_proxies(),
         }
     }
 }
@@ -38,4 +60,12 @@ impl Network {
     fn default_on_reverse_proxy() -> bool {
         false
     }
+
+    fn default_exclude_ipv6_peers_for_ipv4_clients() -> bool {
+        false
+    }
+
+    fn default_trusted_proxies() -> Vec<IpAddr> {
+        Vec::new()
+    }
 }
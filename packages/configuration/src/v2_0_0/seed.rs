@@ -0,0 +1,79 @@
+//! Configuration for the initial state of the tracker.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Data the tracker pre-populates in the database when it starts, so
+/// reproducible deployments converge to a known state without calling the API
+/// after starting the tracker.
+///
+/// The seed is applied on every start, but it only adds the missing entries.
+/// Entries that already exist are never modified, so changes made with the
+/// API are kept.
+///
+/// ```toml
+/// [seed]
+/// whitelist = ["9c38422213e30bff212b30c360d26f9a02136422"]
+///
+/// [[seed.keys]]
+/// key = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"
+///
+/// [[seed.torrents]]
+/// info_hash = "9c38422213e30bff212b30c360d26f9a02136422"
+/// policy = "seed-only"
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Seed {
+    /// The info-hashes to add to the whitelist, in hex or base32.
+    #[serde(default = "Seed::default_whitelist")]
+    pub whitelist: Vec<String>,
+
+    /// The authentication keys to add.
+    #[serde(default = "Seed::default_keys")]
+    pub keys: Vec<SeedKey>,
+
+    /// The torrents to add with an announce policy.
+    #[serde(default = "Seed::default_torrents")]
+    pub torrents: Vec<SeedTorrent>,
+}
+
+impl Seed {
+    fn default_whitelist() -> Vec<String> {
+        Vec::new()
+    }
+
+    fn default_keys() -> Vec<SeedKey> {
+        Vec::new()
+    }
+
+    fn default_torrents() -> Vec<SeedTorrent> {
+        Vec::new()
+    }
+}
+
+/// An authentication key to pre-populate.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct SeedKey {
+    /// The 32-char key.
+    pub key: String,
+
+    /// Unix timestamp, in seconds, after which the key is no longer valid.
+    /// If `None` the key is permanent.
+    #[serde(default = "SeedKey::default_valid_until")]
+    pub valid_until: Option<u64>,
+}
+
+impl SeedKey {
+    fn default_valid_until() -> Option<u64> {
+        None
+    }
+}
+
+/// A torrent to pre-populate with an announce policy.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct SeedTorrent {
+    /// The info-hash of the torrent, in hex or base32.
+    pub info_hash: String,
+
+    /// The announce policy: `seed-only` or `frozen`.
+    pub policy: String,
+}
@@ -1,23 +1,38 @@
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use camino::Utf8PathBuf;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use url::Url;
 
-use crate::TslConfig;
+use crate::validator::{SemanticValidationError, Validator};
+use crate::{BindAddress, TslConfig};
 
 pub type AccessTokens = HashMap<String, String>;
 
+/// The placeholder for the authentication key in the public announce URLs.
+pub const ANNOUNCE_URL_KEY_PLACEHOLDER: &str = "{key}";
+
 /// Configuration for the HTTP API.
 #[serde_as]
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct HttpApi {
     /// The address the tracker will bind to.
     /// The format is `ip:port`, for example `0.0.0.0:6969`. If you want to
     /// listen to all interfaces, use `0.0.0.0`. If you want the operating
     /// system to choose a random port, use port `0`.
+    ///
+    /// It can also be a hostname like `localhost:1212` or a network interface
+    /// name like `iface:lo:1212`, resolved when the configuration
+    /// is loaded.
+    ///
+    /// Or a Unix domain socket path with the `unix:` prefix, for
+    /// example `unix:/var/run/torrust/api.sock`. TLS is not supported on Unix
+    /// domain sockets.
     #[serde(default = "HttpApi::default_bind_address")]
-    pub bind_address: SocketAddr,
+    pub bind_address: BindAddress,
 
     /// TSL config. Only used if `ssl_enabled` is true.
     #[serde(default = "HttpApi::default_tsl_config")]
@@ -25,10 +40,58 @@ pub struct HttpApi {
 
     /// Access tokens for the HTTP API. The key is a label identifying the
     /// token and the value is the token itself. The token is used to
-    /// authenticate the user. All tokens are valid for all endpoints and have
-    /// all permissions.
+    /// authenticate the user. All tokens are valid for all endpoints except
+    /// the administrative ones, which require an admin access token.
     #[serde(default = "HttpApi::default_access_tokens")]
     pub access_tokens: AccessTokens,
+
+    /// Admin access tokens for the HTTP API. Like `access_tokens`, but they
+    /// are also valid for the administrative endpoints, for example, the one
+    /// to force-expire all the authentication keys.
+    #[serde(default = "HttpApi::default_access_tokens")]
+    pub admin_access_tokens: AccessTokens,
+
+    /// Optional URL the tracker will `POST` a JSON notification to when an
+    /// incident response action is executed, for example, when all the
+    /// authentication keys are force-expired.
+    #[serde(default = "HttpApi::default_incident_webhook_url")]
+    #[schemars(with = "Option<String>")]
+    pub incident_webhook_url: Option<Url>,
+
+    /// Optional path of a file where the audit log entries are appended, one
+    /// JSON object per line, in addition to the `audit_log` database table.
+    #[serde(default = "HttpApi::default_audit_log_path")]
+    #[schemars(with = "Option<String>")]
+    pub audit_log_path: Option<Utf8PathBuf>,
+
+    /// Optional salt to hash the peer IDs returned by the torrent detail and
+    /// the events endpoints. Peer IDs can identify users across torrents. When it's set,
+    /// the API returns the salted hash of the peer IDs instead of the raw
+    /// ones. The raw peer IDs are still used internally to manage the swarms.
+    #[serde(default = "HttpApi::default_peer_id_hash_salt")]
+    pub peer_id_hash_salt: Option<String>,
+
+    /// Optional external authentication provider. When it's set, the API
+    /// also accepts bearer tokens (`Authorization: Bearer <token>`) issued by
+    /// an OIDC provider, in addition to the static `access_tokens`.
+    #[serde(default = "HttpApi::default_auth")]
+    pub auth: Option<ApiAuth>,
+
+    /// Optional self-registration of new users. When it's set, anyone with a
+    /// valid invitation code, minted by the admins, can get an authentication
+    /// key from the public `POST /api/v1/register` endpoint.
+    #[serde(default = "HttpApi::default_registration")]
+    pub registration: Option<Registration>,
+
+    /// The public announce URLs of the tracker, used to build the magnet
+    /// links returned by the `GET /api/v1/torrent/:info_hash/magnet`
+    /// endpoint. They are the URLs the clients use to reach the tracker, for
+    /// example, `udp://tracker.example.com:6969/announce`. The `{key}`
+    /// placeholder is replaced with the authentication key of the user on
+    /// private trackers, for example,
+    /// `https://tracker.example.com/announce/{key}`.
+    #[serde(default = "HttpApi::default_announce_urls")]
+    pub announce_urls: Vec<String>,
 }
 
 impl Default for HttpApi {
@@ -37,13 +100,20 @@ impl Default for HttpApi {
             bind_address: Self::default_bind_address(),
             tsl_config: Self::default_tsl_config(),
             access_tokens: Self::default_access_tokens(),
+            admin_access_tokens: Self::default_access_tokens(),
+            incident_webhook_url: Self::default_incident_webhook_url(),
+            audit_log_path: Self::default_audit_log_path(),
+            peer_id_hash_salt: Self::default_peer_id_hash_salt(),
+            auth: Self::default_auth(),
+            registration: Self::default_registration(),
+            announce_urls: Self::default_announce_urls(),
         }
     }
 }
 
 impl HttpApi {
-    fn default_bind_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1212)
+    fn default_bind_address() -> BindAddress {
+        BindAddress::Socket(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 1212))
     }
 
     #[allow(clippy::unnecessary_wraps)]
@@ -55,20 +125,242 @@ impl HttpApi {
         [].iter().cloned().collect()
     }
 
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_incident_webhook_url() -> Option<Url> {
+        None
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_audit_log_path() -> Option<Utf8PathBuf> {
+        None
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_peer_id_hash_salt() -> Option<String> {
+        None
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_auth() -> Option<ApiAuth> {
+        None
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_registration() -> Option<Registration> {
+        None
+    }
+
+    fn default_announce_urls() -> Vec<String> {
+        Vec::new()
+    }
+
     pub fn add_token(&mut self, key: &str, token: &str) {
         self.access_tokens.insert(key.to_string(), token.to_string());
     }
 
+    pub fn add_admin_token(&mut self, key: &str, token: &str) {
+        self.admin_access_tokens.insert(key.to_string(), token.to_string());
+    }
+
     pub fn mask_secrets(&mut self) {
-        for token in self.access_tokens.values_mut() {
+        for token in self.access_tokens.values_mut().chain(self.admin_access_tokens.values_mut()) {
             *token = "***".to_string();
         }
+
+        if let Some(salt) = &mut self.peer_id_hash_salt {
+            *salt = "***".to_string();
+        }
+
+        if let Some(secret) = self.auth.as_mut().and_then(|auth| auth.client_secret.as_mut()) {
+            *secret = "***".to_string();
+        }
+    }
+}
+
+impl Validator for HttpApi {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if self.bind_address.is_unix() && self.tsl_config.is_some() {
+            return Err(SemanticValidationError::TlsOnUnixSocket {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if let Some(auth) = &self.auth {
+            auth.validate()?;
+        }
+
+        for announce_url in &self.announce_urls {
+            let is_valid = Url::parse(&announce_url.replace(ANNOUNCE_URL_KEY_PLACEHOLDER, "key"))
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https" | "udp"));
+
+            if !is_valid {
+                return Err(SemanticValidationError::InvalidAnnounceUrl {
+                    url: announce_url.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Permission granted to the bearer tokens with a given scope.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiPermission {
+    /// Like the `access_tokens`: all the endpoints except the administrative
+    /// ones.
+    Access,
+    /// Like the `admin_access_tokens`: all the endpoints.
+    Admin,
+}
+
+/// External authentication provider for the HTTP API.
+///
+/// Bearer tokens can be validated in two ways, only one of them can be
+/// configured:
+///
+/// - `jwks_path`: the tokens are JWTs signed by one of the keys in a static
+///   JWKS (JSON Web Key Set) file.
+/// - `introspection_url`: the tokens are sent to an OAuth 2.0 token
+///   introspection endpoint (RFC 7662).
+///
+/// The scopes of the token (the `scope` or `scp` claim) are mapped to a
+/// permission with the `scopes` table. Tokens without any of the configured
+/// scopes are rejected.
+///
+/// ```toml
+/// [http_api.auth]
+/// jwks_path = "./storage/tracker/etc/jwks.json"
+/// issuer = "https://auth.example.com"
+/// audience = "torrust-tracker"
+///
+/// [http_api.auth.scopes]
+/// "tracker:read" = "access"
+/// "tracker:admin" = "admin"
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct ApiAuth {
+    /// Path of a static JWKS file with the keys to verify the tokens.
+    #[serde(default = "ApiAuth::default_jwks_path")]
+    #[schemars(with = "Option<String>")]
+    pub jwks_path: Option<Utf8PathBuf>,
+
+    /// URL of the token introspection endpoint.
+    #[serde(default = "ApiAuth::default_introspection_url")]
+    #[schemars(with = "Option<String>")]
+    pub introspection_url: Option<Url>,
+
+    /// Client ID used to authenticate to the introspection endpoint with
+    /// HTTP basic authentication.
+    #[serde(default = "ApiAuth::default_client_credential")]
+    pub client_id: Option<String>,
+
+    /// Client secret used to authenticate to the introspection endpoint.
+    #[serde(default = "ApiAuth::default_client_credential")]
+    pub client_secret: Option<String>,
+
+    /// Expected issuer (`iss` claim) of the tokens. It's not checked if it's
+    /// not set.
+    #[serde(default = "ApiAuth::default_issuer")]
+    pub issuer: Option<String>,
+
+    /// Expected audience (`aud` claim) of the tokens. It's not checked if
+    /// it's not set.
+    #[serde(default = "ApiAuth::default_audience")]
+    pub audience: Option<String>,
+
+    /// Permission granted by each scope. When a token has more than one of
+    /// the scopes, it gets the highest permission.
+    #[serde(default = "ApiAuth::default_scopes")]
+    pub scopes: HashMap<String, ApiPermission>,
+
+    /// Seconds the result of the introspection of an active token is reused,
+    /// instead of sending the token to the introspection endpoint again. It's
+    /// never reused after the token expires. `0` disables the cache.
+    #[serde(default = "ApiAuth::default_introspection_cache_ttl")]
+    pub introspection_cache_ttl: u32,
+}
+
+impl Default for ApiAuth {
+    fn default() -> Self {
+        Self {
+            jwks_path: Self::default_jwks_path(),
+            introspection_url: Self::default_introspection_url(),
+            client_id: Self::default_client_credential(),
+            client_secret: Self::default_client_credential(),
+            issuer: Self::default_issuer(),
+            audience: Self::default_audience(),
+            scopes: Self::default_scopes(),
+            introspection_cache_ttl: Self::default_introspection_cache_ttl(),
+        }
+    }
+}
+
+impl ApiAuth {
+    fn default_jwks_path() -> Option<Utf8PathBuf> {
+        None
+    }
+
+    fn default_introspection_url() -> Option<Url> {
+        None
+    }
+
+    fn default_client_credential() -> Option<String> {
+        None
+    }
+
+    fn default_issuer() -> Option<String> {
+        None
+    }
+
+    fn default_audience() -> Option<String> {
+        None
+    }
+
+    fn default_scopes() -> HashMap<String, ApiPermission> {
+        HashMap::new()
+    }
+
+    fn default_introspection_cache_ttl() -> u32 {
+        30
+    }
+}
+
+impl Validator for ApiAuth {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if self.jwks_path.is_some() == self.introspection_url.is_some() || self.scopes.is_empty() {
+            return Err(SemanticValidationError::InvalidApiAuth);
+        }
+
+        Ok(())
+    }
+}
+
+/// Self-registration of new users with single-use invitation codes.
+///
+/// ```toml
+/// [http_api.registration]
+/// key_seconds_valid = 2592000
+/// ```
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Registration {
+    /// How long the authentication keys issued to the new users are valid,
+    /// in seconds. The keys are permanent if it's not set.
+    #[serde(default = "Registration::default_key_seconds_valid")]
+    pub key_seconds_valid: Option<u64>,
+}
+
+impl Registration {
+    fn default_key_seconds_valid() -> Option<u64> {
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::v2_0_0::tracker_api::HttpApi;
+    use crate::v2_0_0::tracker_api::{ApiAuth, ApiPermission, HttpApi};
+    use crate::validator::{SemanticValidationError, Validator};
 
     #[test]
     fn default_http_api_configuration_should_not_contains_any_token() {
@@ -85,4 +377,100 @@ mod tests {
 
         assert!(configuration.access_tokens.values().any(|t| t == "MyAccessToken"));
     }
+
+    #[test]
+    fn http_api_configuration_should_allow_adding_admin_tokens() {
+        let mut configuration = HttpApi::default();
+
+        configuration.add_admin_token("incident-response", "MyAdminAccessToken");
+
+        assert!(configuration.admin_access_tokens.values().any(|t| t == "MyAdminAccessToken"));
+        assert!(configuration.access_tokens.is_empty());
+    }
+
+    #[test]
+    fn http_api_configuration_should_mask_admin_tokens() {
+        let mut configuration = HttpApi::default();
+
+        configuration.add_token("admin", "MyAccessToken");
+        configuration.add_admin_token("incident-response", "MyAdminAccessToken");
+
+        configuration.mask_secrets();
+
+        assert!(configuration
+            .access_tokens
+            .values()
+            .chain(configuration.admin_access_tokens.values())
+            .all(|t| t == "***"));
+    }
+
+    #[test]
+    fn http_api_configuration_should_mask_the_peer_id_hash_salt() {
+        let mut configuration = HttpApi {
+            peer_id_hash_salt: Some("MyPeerIdHashSalt".to_string()),
+            ..Default::default()
+        };
+
+        configuration.mask_secrets();
+
+        assert_eq!(configuration.peer_id_hash_salt, Some("***".to_string()));
+    }
+
+    #[test]
+    fn http_api_configuration_should_mask_the_auth_client_secret() {
+        let mut configuration = HttpApi {
+            auth: Some(ApiAuth {
+                client_secret: Some("MyClientSecret".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        configuration.mask_secrets();
+
+        assert_eq!(configuration.auth.unwrap().client_secret, Some("***".to_string()));
+    }
+
+    #[test]
+    fn http_api_auth_should_have_exactly_one_way_to_validate_the_tokens() {
+        let scopes = [("tracker:read".to_string(), ApiPermission::Access)].into_iter().collect();
+
+        let auth = ApiAuth {
+            jwks_path: Some("jwks.json".into()),
+            scopes,
+            ..Default::default()
+        };
+
+        assert!(auth.validate().is_ok());
+
+        let auth = ApiAuth {
+            introspection_url: Some("https://auth.example.com/introspect".parse().unwrap()),
+            ..auth
+        };
+
+        assert!(matches!(auth.validate(), Err(SemanticValidationError::InvalidApiAuth)));
+    }
+
+    #[test]
+    fn http_api_announce_urls_should_be_http_https_or_udp_urls() {
+        let configuration = HttpApi {
+            announce_urls: vec![
+                "udp://tracker.example.com:6969/announce".to_string(),
+                "https://tracker.example.com/announce/{key}".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        assert!(configuration.validate().is_ok());
+
+        let configuration = HttpApi {
+            announce_urls: vec!["wss://tracker.example.com/announce".to_string()],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            configuration.validate(),
+            Err(SemanticValidationError::InvalidAnnounceUrl { .. })
+        ));
+    }
 }
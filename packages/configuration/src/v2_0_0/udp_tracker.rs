@@ -1,26 +1,212 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+use crate::validator::{validate_bind_device, SemanticValidationError, Validator};
+use crate::{BindAddress, BindAddresses};
+
+/// The highest DSCP value. It's a 6-bit field.
+const MAX_DSCP: u8 = 63;
+
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
 pub struct UdpTracker {
     /// The address the tracker will bind to.
     /// The format is `ip:port`, for example `0.0.0.0:6969`. If you want to
     /// listen to all interfaces, use `0.0.0.0`. If you want the operating
     /// system to choose a random port, use port `0`.
+    ///
+    /// It can also be a hostname like `tracker.local:6969` or a network
+    /// interface name like `iface:eth0:6969`, resolved when the configuration
+    /// is loaded.
+    ///
+    /// Or a list of addresses the tracker binds to with the same settings,
+    /// for example `["0.0.0.0:6969", "[::]:6969"]` to listen on IPv6 too.
     #[serde(default = "UdpTracker::default_bind_address")]
-    pub bind_address: SocketAddr,
+    pub bind_address: BindAddresses,
+
+    /// Optional network interface the service is bound to, for example,
+    /// `eth1`. It sets the `SO_BINDTODEVICE` socket option, so multi-homed
+    /// servers can pin the tracker traffic to a specific NIC regardless of
+    /// the routing tables. It's only supported on Linux, and it might require
+    /// the `CAP_NET_RAW` capability.
+    #[serde(default = "UdpTracker::default_bind_device")]
+    pub bind_device: Option<String>,
+
+    /// Whether every datagram is expected to be prefixed with a `HAProxy`
+    /// PROXY protocol v2 header, for load balancers supporting PROXY over
+    /// UDP. The header contains the real client address. Responses are sent
+    /// back to the load balancer. Datagrams without a valid header are
+    /// discarded.
+    #[serde(default = "UdpTracker::default_proxy_protocol")]
+    pub proxy_protocol: bool,
+
+    /// The value of the `IPV6_V6ONLY` option for IPv6 sockets. Set it to
+    /// `false` to serve both IPv4 and IPv6 peers from a single socket bound
+    /// to `[::]`, or to `true` to only serve IPv6 peers. When it's not set,
+    /// the operating system default is used. It's ignored for IPv4 bind
+    /// addresses.
+    #[serde(default = "UdpTracker::default_ipv6_only")]
+    pub ipv6_only: Option<bool>,
+
+    /// Optional DSCP (Differentiated Services Code Point) value, from `0` to
+    /// `63`, to mark the outgoing responses so that the network can apply
+    /// QoS policies to them, for example, `46` for Expedited Forwarding. It
+    /// sets the upper six bits of the IPv4 TOS byte. IPv6 peers are only
+    /// marked when they are served from a dual-stack socket as IPv4-mapped
+    /// addresses.
+    #[serde(default = "UdpTracker::default_dscp")]
+    pub dscp: Option<u8>,
+
+    /// Optional dedicated runtime for the tracker. When it's not set, the
+    /// tracker runs on the main runtime, shared with the HTTP trackers and
+    /// the API.
+    #[serde(default = "UdpTracker::default_runtime")]
+    pub runtime: Option<UdpRuntime>,
+
+    /// Whether the `announce` response is sent before applying the peer
+    /// update, which is applied asynchronously afterwards. It lowers the
+    /// response latency, but the announcing peer is not in the swarm until the
+    /// update is applied. It requires the `core.async_announce` queue.
+    #[serde(default = "UdpTracker::default_async_announce")]
+    pub async_announce: bool,
+
+    /// The name of the [virtual tracker](crate::VirtualTracker) served on
+    /// this port. When it's not set, it serves the main tracker.
+    #[serde(default = "UdpTracker::default_tracker")]
+    pub tracker: Option<String>,
 }
 impl Default for UdpTracker {
     fn default() -> Self {
         Self {
             bind_address: Self::default_bind_address(),
+            bind_device: Self::default_bind_device(),
+            proxy_protocol: Self::default_proxy_protocol(),
+            ipv6_only: Self::default_ipv6_only(),
+            dscp: Self::default_dscp(),
+            runtime: Self::default_runtime(),
+            async_announce: Self::default_async_announce(),
+            tracker: Self::default_tracker(),
         }
     }
 }
 
 impl UdpTracker {
-    fn default_bind_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 6969)
+    /// All the socket addresses the tracker binds to.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the bind addresses have not been resolved. See
+    /// [`BindAddress::resolve`].
+    #[must_use]
+    pub fn bind_addresses(&self) -> Vec<SocketAddr> {
+        self.bind_address
+            .iter()
+            .map(|bind_address| {
+                bind_address
+                    .socket_addr()
+                    .expect("the UDP tracker bind address should be resolved")
+            })
+            .collect()
+    }
+
+    fn default_bind_address() -> BindAddresses {
+        BindAddress::Socket(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 6969)).into()
+    }
+
+    fn default_bind_device() -> Option<String> {
+        None
+    }
+
+    fn default_proxy_protocol() -> bool {
+        false
+    }
+
+    fn default_ipv6_only() -> Option<bool> {
+        None
+    }
+
+    fn default_dscp() -> Option<u8> {
+        None
+    }
+
+    fn default_runtime() -> Option<UdpRuntime> {
+        None
+    }
+
+    fn default_async_announce() -> bool {
+        false
+    }
+
+    fn default_tracker() -> Option<String> {
+        None
+    }
+}
+
+impl Validator for UdpTracker {
+    fn validate(&self) -> Result<(), SemanticValidationError> {
+        if self.bind_address.is_unix() {
+            return Err(SemanticValidationError::UnixSocketNotSupported {
+                service: "UDP tracker",
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if let Some(bind_device) = &self.bind_device {
+            validate_bind_device(bind_device, self.bind_address.first())?;
+        }
+
+        if self.dscp.is_some_and(|dscp| dscp > MAX_DSCP) {
+            return Err(SemanticValidationError::InvalidUdpDscp {
+                bind_address: self.bind_address.to_string(),
+            });
+        }
+
+        if let Some(runtime) = &self.runtime {
+            if runtime.worker_threads == 0 {
+                return Err(SemanticValidationError::InvalidUdpRuntime {
+                    bind_address: self.bind_address.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Configuration of a dedicated runtime for a UDP tracker.
+///
+/// It isolates the latency-sensitive UDP processing from the work of the HTTP
+/// trackers and the API, which run on the main runtime.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct UdpRuntime {
+    /// Number of worker threads of the runtime.
+    #[serde(default = "UdpRuntime::default_worker_threads")]
+    pub worker_threads: usize,
+
+    /// Optional CPU cores the worker threads are pinned to. The cores are
+    /// assigned to the threads in order, starting again from the first core
+    /// when there are more threads than cores. When it's empty, the threads
+    /// are not pinned.
+    #[serde(default = "UdpRuntime::default_core_ids")]
+    pub core_ids: Vec<usize>,
+}
+
+impl Default for UdpRuntime {
+    fn default() -> Self {
+        Self {
+            worker_threads: Self::default_worker_threads(),
+            core_ids: Self::default_core_ids(),
+        }
+    }
+}
+
+impl UdpRuntime {
+    fn default_worker_threads() -> usize {
+        1
+    }
+
+    fn default_core_ids() -> Vec<usize> {
+        Vec::new()
     }
 }
@@ -0,0 +1,58 @@
+//! Configuration for the virtual trackers.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::core::Core;
+
+/// A named tracker served by the same process as the main tracker, with its
+/// own core configuration: mode, whitelist, keys, policies and database.
+///
+/// The UDP and HTTP trackers are bound to a virtual tracker with their
+/// `tracker` option, so each port serves only one tracker. Besides, the HTTP
+/// requests whose `Host` matches one of the `hosts` are served by the virtual
+/// tracker on every HTTP tracker.
+///
+/// ```toml
+/// [[virtual_trackers]]
+/// name = "private"
+/// hosts = ["private.tracker.example.com"]
+///
+/// [virtual_trackers.core]
+/// private = true
+///
+/// [virtual_trackers.core.database]
+/// path = "./storage/tracker/lib/database/private.sqlite3.db"
+///
+/// [[http_trackers]]
+/// bind_address = "0.0.0.0:7071"
+/// tracker = "private"
+/// ```
+///
+/// The tracker API serves the keys, events, whitelist, torrents, policies,
+/// static peers, peer cleanup and stats of a virtual tracker under
+/// `/api/v1/trackers/{name}`, for example `/api/v1/trackers/private/stats`.
+/// The rest of the API and the health check API only serve the main tracker.
+#[derive(Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+pub struct VirtualTracker {
+    /// The name the services use to refer to the tracker.
+    pub name: String,
+
+    /// The hostnames, without the port, of the HTTP requests served by this
+    /// tracker on every HTTP tracker. They are case-insensitive.
+    #[serde(default = "VirtualTracker::default_hosts")]
+    pub hosts: Vec<String>,
+
+    /// The core configuration of the tracker. It must use its own database.
+    #[serde(default = "VirtualTracker::default_core")]
+    pub core: Core,
+}
+
+impl VirtualTracker {
+    fn default_hosts() -> Vec<String> {
+        Vec::new()
+    }
+
+    fn default_core() -> Core {
+        Core::default()
+    }
+}
@@ -4,11 +4,185 @@
 //! combinations can be incompatible.
 use thiserror::Error;
 
+use crate::BindAddress;
+
 /// Errors that can occur validating the configuration.
 #[derive(Error, Debug)]
 pub enum SemanticValidationError {
     #[error("Private mode section in configuration can only be included when the tracker is running in private mode.")]
     UselessPrivateModeSection,
+
+    #[error("The tracker can't run in hybrid mode (`hybrid`) and private mode (`private`) at the same time.")]
+    HybridPrivateTracker,
+
+    #[error("The maximum number of torrents in a scrape request (`max_scrape_torrents`) must be greater than zero.")]
+    ZeroMaxScrapeTorrents,
+
+    #[error("The maintenance `retry_in` option must be greater than zero.")]
+    ZeroMaintenanceRetryIn,
+
+    #[error("Announce batching `shards`, `max_batch_size` and `max_delay_ms` options must be greater than zero.")]
+    InvalidAnnounceBatching,
+
+    #[error("Announce journal `path` must not be empty, `max_size_bytes` must be at least 1024, `replay_window_secs` must be greater than zero, and `fsync_interval_ms` must be greater than zero with the `interval` fsync policy.")]
+    InvalidAnnounceJournal,
+
+    #[error("Async announce `queue_capacity` must be greater than zero.")]
+    InvalidAsyncAnnounce,
+
+    #[error("The tracker `async_announce` option requires the `core.async_announce` queue: {bind_address}")]
+    AsyncAnnounceWithoutQueue { bind_address: String },
+
+    #[error("Announce response padding `min_size` must be greater than zero.")]
+    InvalidAnnounceResponsePadding,
+
+    #[error("Authenticated announce policy `interval_min` must not be greater than `interval`, and `max_peers` must be greater than zero.")]
+    InvalidAuthenticatedAnnouncePolicy,
+
+    #[error("Authorization cache `ttl_ms` and `capacity` options must be greater than zero.")]
+    InvalidAuthorizationCache,
+
+    #[error("Consistency check `interval_secs` must be greater than zero.")]
+    InvalidConsistencyCheck,
+
+    #[error("Negative cache `ttl_ms` and `capacity` options must be greater than zero.")]
+    InvalidNegativeCache,
+
+    #[error("Log deduplication `window_secs` must be greater than zero.")]
+    InvalidLogDeduplication,
+
+    #[error(
+        "File descriptors `max_usage_percent` must be between 1 and 100 and `refresh_interval_ms` must be greater than zero."
+    )]
+    InvalidFileDescriptors,
+
+    #[error("Announce export `path` and `salt` must not be empty and `sample_one_in` must be greater than zero.")]
+    InvalidAnnounceExport,
+
+    #[error("Database backups are only supported for the `sqlite3` database driver.")]
+    DatabaseBackupNotSupported,
+
+    #[error("Database authorization `workers` and `queue_capacity` options must be greater than zero.")]
+    InvalidDatabaseAuthorization,
+
+    #[error("The database authorization can't be enabled when the tracker runs without a database (`none` driver).")]
+    DatabaseAuthorizationWithoutDatabase,
+
+    #[error("Database backup `directory` must not be empty, and `interval` and `retention` must be greater than zero.")]
+    InvalidDatabaseBackup,
+
+    #[error("Dead-letter queue `path` must not be empty, `capacity` and `min_backoff_ms` must be greater than zero, and `max_backoff_ms` must not be lower than `min_backoff_ms`.")]
+    InvalidDeadLetterQueue,
+
+    #[error("The dead-letter queue can't be enabled when the tracker runs without a database (`none` driver).")]
+    DeadLetterQueueWithoutDatabase,
+
+    #[error("Failure reasons `language` must be one of the languages in the `templates` table, and the codes and templates must not be empty.")]
+    InvalidFailureReasons,
+
+    #[error("Fault injection `latency_percent`, `db_write_failure_percent` and `lock_delay_percent` options must not be greater than 100.")]
+    InvalidFaultInjection,
+
+    #[error("Peer reachability `sample_one_in`, `max_probes_per_second`, `probe_timeout_ms`, `queue_capacity` and `max_results` options must be greater than zero.")]
+    InvalidPeerReachability,
+
+    #[error("Read snapshot `refresh_interval_ms` must be greater than zero.")]
+    InvalidReadSnapshot,
+
+    #[error("Request deadline `budget_ms` and `max_concurrent_requests` must be greater than zero.")]
+    InvalidRequestDeadline,
+
+    #[error("Preflight `max_clock_skew_secs` must be greater than zero and `ntp_server` must not be empty.")]
+    InvalidPreflight,
+
+    #[error("Service manifest `path` must not be empty.")]
+    InvalidServiceManifest,
+
+    #[error("SLO `window_secs` must be greater than zero, `latency_percentile` must be between 1 and 100, and `max_error_rate_percent` must not be greater than 100.")]
+    InvalidSlo,
+
+    #[error("Swarm changes `capacity` must be greater than zero.")]
+    InvalidSwarmChanges,
+
+    #[error("The HTTP tracker health `path` must start with `/` and must not be an existing tracker path: {path}")]
+    InvalidHealthPath { path: String },
+
+    #[error("The HTTP tracker request limits `max_uri_bytes` and `max_header_bytes` must be greater than zero: {bind_address}")]
+    InvalidHttpRequestLimits { bind_address: String },
+
+    #[error("TLS is not supported on Unix domain sockets: {bind_address}")]
+    TlsOnUnixSocket { bind_address: String },
+
+    #[error("HTTP trackers bound to a Unix domain socket can only be used when the tracker is on a reverse proxy (`on_reverse_proxy`): {bind_address}")]
+    UnixSocketWithoutReverseProxy { bind_address: String },
+
+    #[error("The PROXY protocol is not supported on Unix domain sockets: {bind_address}")]
+    ProxyProtocolOnUnixSocket { bind_address: String },
+
+    #[error("API `auth` must have either a `jwks_path` or an `introspection_url`, but not both, and at least one scope.")]
+    InvalidApiAuth,
+
+    #[error("API `announce_urls` must be valid `http`, `https` or `udp` URLs: {url}")]
+    InvalidAnnounceUrl { url: String },
+
+    #[error("The UDP tracker runtime `worker_threads` must be greater than zero: {bind_address}")]
+    InvalidUdpRuntime { bind_address: String },
+
+    #[error("The UDP tracker `dscp` must not be greater than 63: {bind_address}")]
+    InvalidUdpDscp { bind_address: String },
+
+    #[error("The {service} can't be bound to a Unix domain socket: {bind_address}")]
+    UnixSocketNotSupported { service: &'static str, bind_address: String },
+
+    #[error("The `bind_device` must be a network interface name with 1 to 15 chars: {bind_address}")]
+    InvalidBindDevice { bind_address: String },
+
+    #[error(
+        "Binding to a network interface (`bind_device`) is only supported on Linux and TCP/UDP socket addresses: {bind_address}"
+    )]
+    BindDeviceNotSupported { bind_address: String },
+
+    #[error("A Unix domain socket can't be bound together with other addresses: {bind_address}")]
+    UnixSocketWithOtherBindAddresses { bind_address: String },
+
+    #[error("There is no virtual tracker with the name: {name}")]
+    UnknownVirtualTracker { name: String },
+
+    #[error("Virtual tracker names must not be empty and must be unique: {name}")]
+    InvalidVirtualTrackerName { name: String },
+
+    #[error("A host can only be served by one virtual tracker: {host}")]
+    DuplicateVirtualTrackerHost { host: String },
+
+    #[error("Virtual trackers must not share the database with other trackers: {name}")]
+    SharedVirtualTrackerDatabase { name: String },
+}
+
+/// The maximum length of a network interface name (`IFNAMSIZ` without the
+/// trailing null byte).
+const MAX_BIND_DEVICE_LENGTH: usize = 15;
+
+/// It validates the network interface a service is bound to with the
+/// `SO_BINDTODEVICE` socket option, which is only available on Linux.
+///
+/// # Errors
+///
+/// Will return an error if the interface name is not valid, or if the option
+/// is not supported on this platform or for Unix domain sockets.
+pub fn validate_bind_device(bind_device: &str, bind_address: &BindAddress) -> Result<(), SemanticValidationError> {
+    if bind_device.is_empty() || bind_device.len() > MAX_BIND_DEVICE_LENGTH {
+        return Err(SemanticValidationError::InvalidBindDevice {
+            bind_address: bind_address.to_string(),
+        });
+    }
+
+    if bind_address.is_unix() || !cfg!(any(target_os = "android", target_os = "fuchsia", target_os = "linux")) {
+        return Err(SemanticValidationError::BindDeviceNotSupported {
+            bind_address: bind_address.to_string(),
+        });
+    }
+
+    Ok(())
 }
 
 pub trait Validator {
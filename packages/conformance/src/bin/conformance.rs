@@ -0,0 +1,53 @@
+//! Command line interface to run the conformance suite against trackers.
+//!
+//! ```text
+//! cargo run -p torrust-tracker-conformance -- http://127.0.0.1:7070/announce udp://127.0.0.1:6969
+//! ```
+//!
+//! It prints a report for each tracker URL and exits with a non-zero status
+//! when any assertion fails.
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+use url::Url;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The tracker URLs: HTTP announce URLs or UDP URLs.
+    #[arg(required = true)]
+    urls: Vec<Url>,
+
+    /// The timeout for each request, in seconds.
+    #[arg(long, default_value = "5")]
+    timeout: u64,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let mut passed = true;
+
+    for url in &args.urls {
+        match torrust_tracker_conformance::run(url, Duration::from_secs(args.timeout)).await {
+            Ok(report) => {
+                println!("{report}\n");
+
+                passed &= report.passed();
+            }
+            Err(err) => {
+                eprintln!("{url}\n  {err}\n");
+
+                passed = false;
+            }
+        }
+    }
+
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,431 @@
+//! The HTTP tracker assertions: [BEP 3](https://www.bittorrent.org/beps/bep_0003.html),
+//! [BEP 7](https://www.bittorrent.org/beps/bep_0007.html),
+//! [BEP 23](https://www.bittorrent.org/beps/bep_0023.html) and
+//! [BEP 48](https://www.bittorrent.org/beps/bep_0048.html).
+use std::collections::HashMap;
+use std::time::Duration;
+
+use percent_encoding::{percent_encode, NON_ALPHANUMERIC};
+use serde_bencode::value::Value;
+use url::{Host, Url};
+
+use crate::report::{Assertion, Bep, Outcome};
+use crate::swarm::{random_info_hash, Peer};
+use crate::Error;
+
+/// A decoded bencoded dictionary.
+pub type Dictionary = HashMap<Vec<u8>, Value>;
+
+const COMPACT_IPV4_PEER_LEN: usize = 6;
+const COMPACT_IPV6_PEER_LEN: usize = 18;
+
+/// The parameters of an announce request.
+#[derive(Debug, Clone, Copy)]
+pub struct Announce<'a> {
+    pub info_hash: &'a [u8; 20],
+    pub peer: &'a Peer,
+    pub left: u64,
+    pub event: Option<&'static str>,
+    pub compact: bool,
+}
+
+impl Announce<'_> {
+    fn query(&self) -> String {
+        let mut query = format!(
+            "info_hash={}&peer_id={}&port={}&uploaded=0&downloaded=0&left={}&compact={}",
+            percent_encode(self.info_hash, NON_ALPHANUMERIC),
+            percent_encode(&self.peer.peer_id, NON_ALPHANUMERIC),
+            self.peer.port,
+            self.left,
+            u8::from(self.compact)
+        );
+
+        if let Some(event) = self.event {
+            query.push_str(&format!("&event={event}"));
+        }
+
+        query
+    }
+}
+
+/// A minimal HTTP tracker client. It keeps the query of the announce URL (for
+/// example, a passkey) in all the requests.
+pub struct Client {
+    http: reqwest::Client,
+    announce_url: Url,
+}
+
+impl Client {
+    /// # Errors
+    ///
+    /// Will return an error if the HTTP client can't be built.
+    pub fn new(announce_url: Url, timeout: Duration) -> Result<Self, Error> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|err| Error::Http { err })?;
+
+        Ok(Self { http, announce_url })
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails or the response is not a
+    /// bencoded dictionary.
+    pub async fn announce(&self, announce: &Announce<'_>) -> Result<Dictionary, Error> {
+        self.get(&self.announce_url, &announce.query()).await
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails or the response is not a
+    /// bencoded dictionary.
+    pub async fn announce_with_query(&self, query: &str) -> Result<Dictionary, Error> {
+        self.get(&self.announce_url, query).await
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails or the response is not a
+    /// bencoded dictionary.
+    pub async fn scrape(&self, scrape_url: &Url, info_hash: &[u8; 20]) -> Result<Dictionary, Error> {
+        self.get(
+            scrape_url,
+            &format!("info_hash={}", percent_encode(info_hash, NON_ALPHANUMERIC)),
+        )
+        .await
+    }
+
+    async fn get(&self, url: &Url, query: &str) -> Result<Dictionary, Error> {
+        let separator = if url.query().is_some() { '&' } else { '?' };
+
+        let response = self
+            .http
+            .get(format!("{url}{separator}{query}"))
+            .send()
+            .await
+            .map_err(|err| Error::Http { err })?;
+
+        let bytes = response.bytes().await.map_err(|err| Error::Http { err })?;
+
+        match serde_bencode::from_bytes(&bytes).map_err(|err| Error::Bencode { err })? {
+            Value::Dict(dictionary) => Ok(dictionary),
+            _ => Err(Error::NotADictionary),
+        }
+    }
+}
+
+/// The scrape URL for an announce URL, following the
+/// [BEP 48](https://www.bittorrent.org/beps/bep_0048.html) convention: the
+/// last path segment must start with `announce`, which is replaced by
+/// `scrape`. Trackers with other announce URLs do not support scrape.
+#[must_use]
+pub fn scrape_url(announce_url: &Url) -> Option<Url> {
+    let path = announce_url.path();
+    let (parent, last) = path.rsplit_once('/')?;
+    let rest = last.strip_prefix("announce")?;
+
+    let mut scrape_url = announce_url.clone();
+    scrape_url.set_path(&format!("{parent}/scrape{rest}"));
+
+    Some(scrape_url)
+}
+
+/// It runs the HTTP assertions against the tracker `announce_url`.
+///
+/// # Errors
+///
+/// Will return an error if the HTTP client can't be built.
+pub async fn run(announce_url: &Url, timeout: Duration) -> Result<Vec<Assertion>, Error> {
+    let client = Client::new(announce_url.clone(), timeout)?;
+
+    let info_hash = random_info_hash();
+    let first = Peer::random();
+    let second = Peer::random();
+
+    let leecher = |peer, event, compact| Announce {
+        info_hash: &info_hash,
+        peer,
+        left: 1000,
+        event,
+        compact,
+    };
+
+    let mut assertions = vec![];
+
+    let response = client.announce(&leecher(&first, Some("started"), true)).await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep3,
+        "the announce response has an interval",
+        with(response, has_interval),
+    ));
+
+    let response = client.announce(&leecher(&second, Some("started"), false)).await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep3,
+        "the announce response lists the other peers of the swarm",
+        with(response, |response| {
+            contains(&peer_ports(response, b"peers", COMPACT_IPV4_PEER_LEN)?, &first)
+        }),
+    ));
+
+    let response = client
+        .announce(&leecher(&second, None, true))
+        .await
+        .map_err(|err| err.to_string());
+
+    assertions.push(Assertion::new(
+        Bep::Bep23,
+        "the compact announce response has 6 bytes per peer",
+        with(response.clone(), |response| match response.get(b"peers".as_slice()) {
+            Some(Value::Bytes(_)) => contains(&peer_ports(response, b"peers", COMPACT_IPV4_PEER_LEN)?, &first),
+            _ => Err("the peers are not a byte string".to_string()),
+        }),
+    ));
+
+    assertions.push(Assertion::new(
+        Bep::Bep7,
+        "the compact announce response lists the IPv6 peers in peers6",
+        match response {
+            Ok(response) => ipv6_peers(&response, announce_url, &first),
+            Err(reason) => Outcome::Failed { reason },
+        },
+    ));
+
+    let response = client
+        .announce_with_query(&format!("peer_id={}&port={}", "-TC0001-000000000000", first.port))
+        .await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep3,
+        "an announce without info_hash fails with a failure reason",
+        with(response, |response| match response.get(b"failure reason".as_slice()) {
+            Some(Value::Bytes(_)) => Ok(()),
+            _ => Err("the response has no failure reason".to_string()),
+        }),
+    ));
+
+    match scrape_url(announce_url) {
+        Some(scrape_url) => {
+            let response = client.scrape(&scrape_url, &info_hash).await;
+
+            assertions.push(Assertion::new(
+                Bep::Bep48,
+                "the scrape response has the statistics of the torrent",
+                with(response, |response| {
+                    let incomplete = scrape_statistic(response, &info_hash, b"incomplete")?;
+
+                    if incomplete >= 1 {
+                        Ok(())
+                    } else {
+                        Err(format!("expected at least 1 leecher, got {incomplete}"))
+                    }
+                }),
+            ));
+
+            let completed = Announce {
+                left: 0,
+                ..leecher(&first, Some("completed"), true)
+            };
+
+            let response = match client.announce(&completed).await {
+                Ok(_) => client.scrape(&scrape_url, &info_hash).await,
+                Err(err) => Err(err),
+            };
+
+            assertions.push(Assertion::new(
+                Bep::Bep48,
+                "the scrape response counts the completed downloads",
+                with(response, |response| {
+                    let downloaded = scrape_statistic(response, &info_hash, b"downloaded")?;
+                    let complete = scrape_statistic(response, &info_hash, b"complete")?;
+
+                    if downloaded >= 1 && complete >= 1 {
+                        Ok(())
+                    } else {
+                        Err(format!(
+                            "expected at least 1 download and 1 seeder, got {downloaded} and {complete}"
+                        ))
+                    }
+                }),
+            ));
+        }
+        None => {
+            for behavior in [
+                "the scrape response has the statistics of the torrent",
+                "the scrape response counts the completed downloads",
+            ] {
+                assertions.push(Assertion::new(
+                    Bep::Bep48,
+                    behavior,
+                    Outcome::Skipped {
+                        reason: "the announce URL has no scrape convention".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    let response = match client.announce(&leecher(&first, Some("stopped"), true)).await {
+        Ok(_) => client.announce(&leecher(&second, None, true)).await,
+        Err(err) => Err(err),
+    };
+
+    assertions.push(Assertion::new(
+        Bep::Bep3,
+        "the stopped peers are removed from the swarm",
+        with(response, |response| {
+            if peer_ports(response, b"peers", COMPACT_IPV4_PEER_LEN)?.contains(&first.port) {
+                Err("the stopped peer is still listed".to_string())
+            } else {
+                Ok(())
+            }
+        }),
+    ));
+
+    drop(client.announce(&leecher(&second, Some("stopped"), true)).await);
+
+    Ok(assertions)
+}
+
+/// It runs the `check` on the response. When the check fails, the failure
+/// reason of the tracker, if any, is added to the reason.
+fn with<E: ToString>(response: Result<Dictionary, E>, check: impl FnOnce(&Dictionary) -> Result<(), String>) -> Outcome {
+    match response {
+        Ok(response) => check(&response)
+            .map_err(|reason| match response.get(b"failure reason".as_slice()) {
+                Some(Value::Bytes(failure)) => format!("{reason} (the tracker failed: {})", String::from_utf8_lossy(failure)),
+                _ => reason,
+            })
+            .into(),
+        Err(err) => Outcome::Failed { reason: err.to_string() },
+    }
+}
+
+fn has_interval(response: &Dictionary) -> Result<(), String> {
+    match response.get(b"interval".as_slice()) {
+        Some(Value::Int(interval)) if *interval > 0 => Ok(()),
+        Some(Value::Int(interval)) => Err(format!("the interval is not positive: {interval}")),
+        _ => Err("the response has no interval".to_string()),
+    }
+}
+
+fn contains(ports: &[u16], peer: &Peer) -> Result<(), String> {
+    if ports.contains(&peer.port) {
+        Ok(())
+    } else {
+        Err(format!("the peer with port {} is not listed", peer.port))
+    }
+}
+
+/// The ports of the peers in the `key` list. It accepts both the dictionary
+/// model from BEP 3 and the compact model from BEP 23, because trackers can
+/// ignore `compact=0`.
+fn peer_ports(response: &Dictionary, key: &[u8], compact_len: usize) -> Result<Vec<u16>, String> {
+    let name = String::from_utf8_lossy(key);
+
+    match response.get(key) {
+        Some(Value::Bytes(bytes)) => compact_peer_ports(bytes, compact_len)
+            .ok_or_else(|| format!("the {name} length {} is not a multiple of {compact_len}", bytes.len())),
+        Some(Value::List(peers)) => peers
+            .iter()
+            .map(|peer| match peer {
+                Value::Dict(peer) => match (peer.get(b"ip".as_slice()), peer.get(b"port".as_slice())) {
+                    (Some(Value::Bytes(_)), Some(Value::Int(port))) => {
+                        u16::try_from(*port).map_err(|_| format!("invalid peer port {port}"))
+                    }
+                    _ => Err(format!("the {name} dictionaries must have an ip and a port")),
+                },
+                _ => Err(format!("the {name} list must contain dictionaries")),
+            })
+            .collect(),
+        Some(_) => Err(format!("the {name} are neither a list nor a byte string")),
+        None => Err(format!("the response has no {name}")),
+    }
+}
+
+fn compact_peer_ports(bytes: &[u8], compact_len: usize) -> Option<Vec<u16>> {
+    if bytes.len() % compact_len != 0 {
+        return None;
+    }
+
+    Some(
+        bytes
+            .chunks(compact_len)
+            .map(|peer| u16::from_be_bytes([peer[compact_len - 2], peer[compact_len - 1]]))
+            .collect(),
+    )
+}
+
+/// IPv6 peers can only be announced when the tracker is reached over IPv6.
+/// Otherwise, it only checks the `peers6` format, if present.
+fn ipv6_peers(response: &Dictionary, announce_url: &Url, peer: &Peer) -> Outcome {
+    let over_ipv6 = matches!(announce_url.host(), Some(Host::Ipv6(_)));
+
+    match (response.get(b"peers6".as_slice()), over_ipv6) {
+        (Some(_), true) => peer_ports(response, b"peers6", COMPACT_IPV6_PEER_LEN)
+            .and_then(|ports| contains(&ports, peer))
+            .into(),
+        (Some(_), false) => peer_ports(response, b"peers6", COMPACT_IPV6_PEER_LEN).map(|_| ()).into(),
+        (None, true) => Outcome::Failed {
+            reason: "the response has no peers6".to_string(),
+        },
+        (None, false) => Outcome::Skipped {
+            reason: "the tracker is not reached over IPv6".to_string(),
+        },
+    }
+}
+
+fn scrape_statistic(response: &Dictionary, info_hash: &[u8; 20], key: &[u8]) -> Result<i64, String> {
+    let Some(Value::Dict(files)) = response.get(b"files".as_slice()) else {
+        return Err("the response has no files".to_string());
+    };
+
+    let Some(Value::Dict(file)) = files.get(info_hash.as_slice()) else {
+        return Err("the torrent is not in the files".to_string());
+    };
+
+    match file.get(key) {
+        Some(Value::Int(value)) => Ok(*value),
+        _ => Err(format!("the torrent has no {}", String::from_utf8_lossy(key))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{compact_peer_ports, scrape_url};
+
+    #[test]
+    fn it_should_build_the_scrape_url_replacing_the_announce_segment() {
+        let url = Url::parse("http://tracker.com:7070/announce").unwrap();
+
+        assert_eq!(scrape_url(&url).unwrap().as_str(), "http://tracker.com:7070/scrape");
+    }
+
+    #[test]
+    fn it_should_keep_the_suffix_and_the_query_in_the_scrape_url() {
+        let url = Url::parse("http://tracker.com/x/announce.php?passkey=abc").unwrap();
+
+        assert_eq!(
+            scrape_url(&url).unwrap().as_str(),
+            "http://tracker.com/x/scrape.php?passkey=abc"
+        );
+    }
+
+    #[test]
+    fn it_should_not_build_a_scrape_url_when_the_last_segment_is_not_an_announce() {
+        let url = Url::parse("http://tracker.com/a").unwrap();
+
+        assert_eq!(scrape_url(&url), None);
+    }
+
+    #[test]
+    fn it_should_read_the_ports_of_the_compact_peers() {
+        assert_eq!(compact_peer_ports(&[127, 0, 0, 1, 0x1f, 0x90], 6), Some(vec![8080]));
+        assert_eq!(compact_peer_ports(&[127, 0, 0, 1, 0x1f], 6), None);
+    }
+}
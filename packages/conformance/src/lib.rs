@@ -0,0 +1,92 @@
+//! A protocol conformance test suite for `BitTorrent` trackers.
+//!
+//! It runs a matrix of spec-level assertions against any tracker URL, ours or
+//! a third party one:
+//!
+//! - [BEP 3](https://www.bittorrent.org/beps/bep_0003.html): the HTTP
+//!   announce.
+//! - [BEP 7](https://www.bittorrent.org/beps/bep_0007.html): IPv6 peers.
+//! - [BEP 15](https://www.bittorrent.org/beps/bep_0015.html): the UDP
+//!   protocol.
+//! - [BEP 23](https://www.bittorrent.org/beps/bep_0023.html): compact peer
+//!   lists.
+//! - [BEP 48](https://www.bittorrent.org/beps/bep_0048.html): the HTTP scrape.
+//!
+//! The HTTP assertions run for `http` and `https` URLs, which must be the
+//! announce URL of the tracker (for example `http://127.0.0.1:7070/announce`).
+//! The UDP assertions run for `udp` URLs (for example `udp://127.0.0.1:6969`).
+//!
+//! ```text
+//! let report = torrust_tracker_conformance::run(&url, Duration::from_secs(5)).await?;
+//!
+//! assert!(report.passed(), "{report}");
+//! ```
+//!
+//! Every run uses new random info-hashes and peer IDs, so it does not
+//! interfere with the real swarms of the tracker. The peers it announces are
+//! stopped at the end of the run.
+//!
+//! Some assertions can't be verified from every network. For example, the
+//! IPv6 peers can only be checked when the tracker is reached over IPv6. Those
+//! assertions are [`Skipped`](report::Outcome::Skipped) instead of failed.
+use std::time::Duration;
+
+use url::Url;
+
+pub mod http;
+pub mod report;
+pub mod swarm;
+pub mod udp;
+
+use report::Report;
+
+/// Errors reaching the tracker or decoding its responses.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unsupported scheme \"{scheme}\", expected http, https or udp")]
+    UnsupportedScheme { scheme: String },
+
+    #[error("the URL {url} has no host or port")]
+    InvalidUrl { url: Url },
+
+    #[error("the HTTP request failed: {err}")]
+    Http { err: reqwest::Error },
+
+    #[error("the response is not valid bencode: {err}")]
+    Bencode { err: serde_bencode::Error },
+
+    #[error("the response is not a bencoded dictionary")]
+    NotADictionary,
+
+    #[error("the UDP socket failed: {err}")]
+    Io { err: std::io::Error },
+
+    #[error("no response within {timeout:?}")]
+    Timeout { timeout: Duration },
+
+    #[error("unexpected response: {response}")]
+    UnexpectedResponse { response: String },
+}
+
+/// It runs the assertions for the tracker `url` protocol.
+///
+/// # Errors
+///
+/// Will return an error if the `url` scheme is not supported or the `url` has
+/// no host. Failing requests are reported as failed assertions.
+pub async fn run(url: &Url, timeout: Duration) -> Result<Report, Error> {
+    let assertions = match url.scheme() {
+        "http" | "https" => http::run(url, timeout).await?,
+        "udp" => udp::run(url, timeout).await?,
+        scheme => {
+            return Err(Error::UnsupportedScheme {
+                scheme: scheme.to_string(),
+            })
+        }
+    };
+
+    Ok(Report {
+        url: url.clone(),
+        assertions,
+    })
+}
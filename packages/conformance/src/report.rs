@@ -0,0 +1,175 @@
+//! The results of a conformance run.
+use std::fmt;
+
+use url::Url;
+
+/// The BEPs covered by the suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bep {
+    /// The `BitTorrent` protocol: the HTTP announce.
+    Bep3,
+    /// IPv6 tracker extension.
+    Bep7,
+    /// UDP tracker protocol.
+    Bep15,
+    /// Compact peer lists.
+    Bep23,
+    /// Tracker scrape.
+    Bep48,
+}
+
+impl fmt::Display for Bep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = match self {
+            Bep::Bep3 => 3,
+            Bep::Bep7 => 7,
+            Bep::Bep15 => 15,
+            Bep::Bep23 => 23,
+            Bep::Bep48 => 48,
+        };
+
+        f.pad(&format!("BEP {number}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed {
+        reason: String,
+    },
+    /// The behavior can't be verified against this tracker. For example,
+    /// IPv6 peers when the tracker is reached over IPv4.
+    Skipped {
+        reason: String,
+    },
+}
+
+impl From<Result<(), String>> for Outcome {
+    fn from(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Outcome::Passed,
+            Err(reason) => Outcome::Failed { reason },
+        }
+    }
+}
+
+/// A spec-level behavior and whether the tracker conforms to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertion {
+    pub bep: Bep,
+    pub behavior: &'static str,
+    pub outcome: Outcome,
+}
+
+impl Assertion {
+    #[must_use]
+    pub fn new(bep: Bep, behavior: &'static str, outcome: impl Into<Outcome>) -> Self {
+        Self {
+            bep,
+            behavior,
+            outcome: outcome.into(),
+        }
+    }
+}
+
+/// The assertions run against one tracker URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub url: Url,
+    pub assertions: Vec<Assertion>,
+}
+
+impl Report {
+    /// Whether no assertion failed. Skipped assertions do not count as
+    /// failures.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        !self
+            .assertions
+            .iter()
+            .any(|assertion| matches!(assertion.outcome, Outcome::Failed { .. }))
+    }
+
+    fn count(&self, f: impl Fn(&Outcome) -> bool) -> usize {
+        self.assertions.iter().filter(|assertion| f(&assertion.outcome)).count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.url)?;
+
+        for assertion in &self.assertions {
+            match &assertion.outcome {
+                Outcome::Passed => writeln!(f, "  PASS  {:<6}  {}", assertion.bep, assertion.behavior)?,
+                Outcome::Failed { reason } => writeln!(f, "  FAIL  {:<6}  {}: {reason}", assertion.bep, assertion.behavior)?,
+                Outcome::Skipped { reason } => writeln!(f, "  SKIP  {:<6}  {}: {reason}", assertion.bep, assertion.behavior)?,
+            }
+        }
+
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.count(|outcome| *outcome == Outcome::Passed),
+            self.count(|outcome| matches!(outcome, Outcome::Failed { .. })),
+            self.count(|outcome| matches!(outcome, Outcome::Skipped { .. })),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{Assertion, Bep, Outcome, Report};
+
+    fn report(outcomes: Vec<Outcome>) -> Report {
+        Report {
+            url: Url::parse("udp://127.0.0.1:6969").unwrap(),
+            assertions: outcomes
+                .into_iter()
+                .map(|outcome| Assertion::new(Bep::Bep15, "behavior", outcome))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_should_pass_when_no_assertion_failed() {
+        let report = report(vec![
+            Outcome::Passed,
+            Outcome::Skipped {
+                reason: "not verifiable".to_string(),
+            },
+        ]);
+
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn it_should_not_pass_when_any_assertion_failed() {
+        let report = report(vec![
+            Outcome::Passed,
+            Outcome::Failed {
+                reason: "wrong".to_string(),
+            },
+        ]);
+
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn it_should_summarize_the_outcomes() {
+        let report = report(vec![
+            Outcome::Passed,
+            Outcome::Failed {
+                reason: "wrong".to_string(),
+            },
+        ]);
+
+        assert_eq!(
+            report.to_string(),
+            "udp://127.0.0.1:6969\n  PASS  BEP 15  behavior\n  FAIL  BEP 15  behavior: wrong\n1 passed, 1 failed, 0 skipped"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Random torrents and peers, so that a run does not interfere with the real
+//! swarms of the tracker.
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+/// A peer announced by the suite. It's identified in the peer lists by its
+/// port, because the tracker sees the public IP of the machine running the
+/// suite, which the suite doesn't know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Peer {
+    pub peer_id: [u8; 20],
+    pub port: u16,
+}
+
+impl Peer {
+    #[must_use]
+    pub fn random() -> Self {
+        let mut peer_id = *b"-TC0001-000000000000";
+
+        for (byte, random) in peer_id[8..].iter_mut().zip(rand::thread_rng().sample_iter(Alphanumeric)) {
+            *byte = random;
+        }
+
+        Self {
+            peer_id,
+            port: rand::thread_rng().gen_range(1024..=u16::MAX),
+        }
+    }
+}
+
+#[must_use]
+pub fn random_info_hash() -> [u8; 20] {
+    rand::random()
+}
@@ -0,0 +1,274 @@
+//! The UDP tracker assertions: [BEP 15](https://www.bittorrent.org/beps/bep_0015.html).
+use std::io::Cursor;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use aquatic_udp_protocol::common::InfoHash;
+use aquatic_udp_protocol::{
+    AnnounceActionPlaceholder, AnnounceEvent, AnnounceRequest, ConnectRequest, ConnectionId, NumberOfBytes, NumberOfPeers,
+    PeerId, PeerKey, Port, Request, Response, ScrapeRequest, TransactionId,
+};
+use tokio::net::UdpSocket;
+use url::Url;
+
+use crate::report::{Assertion, Bep, Outcome};
+use crate::swarm::{random_info_hash, Peer};
+use crate::Error;
+
+const MAX_PACKET_SIZE: usize = 1496;
+
+/// A minimal UDP tracker client.
+pub struct Client {
+    socket: UdpSocket,
+    remote_addr: SocketAddr,
+    timeout: Duration,
+}
+
+impl Client {
+    /// # Errors
+    ///
+    /// Will return an error if the socket can't be bound or connected to the
+    /// `remote_addr`.
+    pub async fn new(remote_addr: SocketAddr, timeout: Duration) -> Result<Self, Error> {
+        let local_addr = if remote_addr.is_ipv4() {
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))
+        } else {
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))
+        };
+
+        let socket = UdpSocket::bind(local_addr).await.map_err(|err| Error::Io { err })?;
+
+        socket.connect(remote_addr).await.map_err(|err| Error::Io { err })?;
+
+        Ok(Self {
+            socket,
+            remote_addr,
+            timeout,
+        })
+    }
+
+    /// It sends the `request` and waits for the response.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the request can't be sent, or the response is
+    /// not received within the timeout or can't be parsed.
+    pub async fn send(&self, request: Request) -> Result<Response, Error> {
+        let mut writer = Cursor::new(Vec::with_capacity(200));
+
+        request.write_bytes(&mut writer).map_err(|err| Error::Io { err })?;
+
+        self.socket.send(writer.get_ref()).await.map_err(|err| Error::Io { err })?;
+
+        let mut buffer = [0u8; MAX_PACKET_SIZE];
+
+        let size = tokio::time::timeout(self.timeout, self.socket.recv(&mut buffer))
+            .await
+            .map_err(|_| Error::Timeout { timeout: self.timeout })?
+            .map_err(|err| Error::Io { err })?;
+
+        Response::parse_bytes(&buffer[..size], self.remote_addr.is_ipv4()).map_err(|err| Error::Io { err })
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails or the response is not a
+    /// connect response with the same transaction ID.
+    pub async fn connect(&self) -> Result<ConnectionId, Error> {
+        let transaction_id = TransactionId::new(rand::random());
+
+        match self.send(ConnectRequest { transaction_id }.into()).await? {
+            Response::Connect(response) if response.transaction_id == transaction_id => Ok(response.connection_id),
+            response => Err(unexpected(&response)),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails.
+    pub async fn announce(
+        &self,
+        connection_id: ConnectionId,
+        info_hash: &[u8; 20],
+        peer: &Peer,
+        event: AnnounceEvent,
+    ) -> Result<Response, Error> {
+        let request = AnnounceRequest {
+            connection_id,
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: TransactionId::new(rand::random()),
+            info_hash: InfoHash(*info_hash),
+            peer_id: PeerId(peer.peer_id),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: event.into(),
+            ip_address: Ipv4Addr::UNSPECIFIED.into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(50i32.into()),
+            port: Port::new(peer.port.try_into().expect("the peer port should not be zero")),
+        };
+
+        self.send(request.into()).await
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the request fails.
+    pub async fn scrape(&self, connection_id: ConnectionId, info_hash: &[u8; 20]) -> Result<Response, Error> {
+        let request = ScrapeRequest {
+            connection_id,
+            transaction_id: TransactionId::new(rand::random()),
+            info_hashes: vec![InfoHash(*info_hash)],
+        };
+
+        self.send(request.into()).await
+    }
+}
+
+/// It runs the UDP assertions against the tracker `url`.
+///
+/// # Errors
+///
+/// Will return an error if the `url` has no host and port, or they can't be
+/// resolved.
+pub async fn run(url: &Url, timeout: Duration) -> Result<Vec<Assertion>, Error> {
+    let remote_addr = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => tokio::net::lookup_host((host.trim_start_matches('[').trim_end_matches(']'), port))
+            .await
+            .map_err(|err| Error::Io { err })?
+            .next(),
+        _ => None,
+    }
+    .ok_or_else(|| Error::InvalidUrl { url: url.clone() })?;
+
+    let info_hash = random_info_hash();
+    let first = Peer::random();
+    let second = Peer::random();
+
+    let mut assertions = vec![];
+
+    let first_client = Client::new(remote_addr, timeout).await?;
+    let first_connection = first_client.connect().await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep15,
+        "the connect response echoes the transaction ID",
+        first_connection.as_ref().map(|_| ()).map_err(ToString::to_string),
+    ));
+
+    let Ok(first_connection) = first_connection else {
+        return Ok(assertions);
+    };
+
+    let response = first_client
+        .announce(first_connection, &info_hash, &first, AnnounceEvent::Started)
+        .await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep15,
+        "the announce response has an interval",
+        announce_peer_ports(response).map(|_| ()),
+    ));
+
+    let second_client = Client::new(remote_addr, timeout).await?;
+
+    let response = match second_client.connect().await {
+        Ok(connection_id) => {
+            let response = second_client
+                .announce(connection_id, &info_hash, &second, AnnounceEvent::Started)
+                .await;
+
+            drop(
+                second_client
+                    .announce(connection_id, &info_hash, &second, AnnounceEvent::Stopped)
+                    .await,
+            );
+
+            response
+        }
+        Err(err) => Err(err),
+    };
+
+    assertions.push(Assertion::new(
+        Bep::Bep15,
+        "the announce response lists the other peers of the swarm",
+        announce_peer_ports(response).and_then(|ports| {
+            if ports.contains(&first.port) {
+                Ok(())
+            } else {
+                Err(format!("the peer with port {} is not listed", first.port))
+            }
+        }),
+    ));
+
+    let response = first_client.scrape(first_connection, &info_hash).await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep15,
+        "the scrape response has the statistics of the torrent",
+        match response {
+            Ok(Response::Scrape(response)) => match response.torrent_stats.as_slice() {
+                [statistics] if i32::from(statistics.seeders.0) >= 1 => Ok(()),
+                [statistics] => Err(format!("expected at least 1 seeder, got {}", i32::from(statistics.seeders.0))),
+                statistics => Err(format!("expected the statistics of 1 torrent, got {}", statistics.len())),
+            },
+            Ok(response) => Err(unexpected(&response).to_string()),
+            Err(err) => Err(err.to_string()),
+        },
+    ));
+
+    let unknown_connection = ConnectionId::new(rand::random());
+
+    let response = first_client
+        .announce(unknown_connection, &info_hash, &first, AnnounceEvent::Started)
+        .await;
+
+    assertions.push(Assertion::new(
+        Bep::Bep15,
+        "an announce with an unknown connection ID is rejected",
+        match response {
+            Ok(Response::Error(_)) | Err(Error::Timeout { .. }) => Outcome::Passed,
+            Ok(response) => Outcome::Failed {
+                reason: unexpected(&response).to_string(),
+            },
+            Err(err) => Outcome::Failed { reason: err.to_string() },
+        },
+    ));
+
+    drop(
+        first_client
+            .announce(first_connection, &info_hash, &first, AnnounceEvent::Stopped)
+            .await,
+    );
+
+    Ok(assertions)
+}
+
+fn unexpected(response: &Response) -> Error {
+    Error::UnexpectedResponse {
+        response: format!("{response:?}"),
+    }
+}
+
+/// The ports of the peers in an announce response with a positive interval.
+fn announce_peer_ports(response: Result<Response, Error>) -> Result<Vec<u16>, String> {
+    let (interval, ports): (i32, Vec<u16>) = match response.map_err(|err| err.to_string())? {
+        Response::AnnounceIpv4(response) => (
+            response.fixed.announce_interval.0.into(),
+            response.peers.iter().map(|peer| peer.port.0.get()).collect(),
+        ),
+        Response::AnnounceIpv6(response) => (
+            response.fixed.announce_interval.0.into(),
+            response.peers.iter().map(|peer| peer.port.0.get()).collect(),
+        ),
+        Response::Error(response) => return Err(format!("the tracker failed: {}", response.message)),
+        response => return Err(unexpected(&response).to_string()),
+    };
+
+    if interval > 0 {
+        Ok(ports)
+    } else {
+        Err(format!("the interval is not positive: {interval}"))
+    }
+}
@@ -0,0 +1,38 @@
+//! Runs the conformance suite against a locally spawned tracker.
+//!
+//! ```text
+//! cargo test -p torrust-tracker-conformance
+//! ```
+use std::time::Duration;
+
+use torrust_tracker::testing::configuration;
+use torrust_tracker::testing::environment::{http, udp};
+use url::Url;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn the_http_tracker_should_conform_to_the_http_tracker_beps() {
+    let env = http::Started::new(&configuration::ephemeral_public().into()).await;
+
+    let url = Url::parse(&format!("http://{}/announce", env.bind_address())).unwrap();
+
+    let report = torrust_tracker_conformance::run(&url, TIMEOUT).await.unwrap();
+
+    env.stop().await;
+
+    assert!(report.passed(), "{report}");
+}
+
+#[tokio::test]
+async fn the_udp_tracker_should_conform_to_the_udp_tracker_bep() {
+    let env = udp::Started::new(&configuration::ephemeral_public().into()).await;
+
+    let url = Url::parse(&format!("udp://{}", env.bind_address())).unwrap();
+
+    let report = torrust_tracker_conformance::run(&url, TIMEOUT).await.unwrap();
+
+    env.stop().await;
+
+    assert!(report.passed(), "{report}");
+}
@@ -1,18 +1,39 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
-use std::ops::{Deref, DerefMut};
-use std::panic::Location;
-
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use core::panic::Location;
+#[cfg(feature = "std")]
+use std::hash::{DefaultHasher, Hasher};
+
+#[cfg(feature = "std")]
 use thiserror::Error;
+#[cfg(feature = "std")]
 use zerocopy::FromBytes;
 
 /// `BitTorrent` Info Hash v1
+///
+/// The canonical text representation is the lowercase hex string. When parsed
+/// from text, it also accepts uppercase hex and the base32 representation
+/// (RFC 4648, case-insensitive) used in some magnet links.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct InfoHash {
-    data: aquatic_udp_protocol::InfoHash,
+    data: [u8; INFO_HASH_BYTES_LEN],
 }
 
 pub const INFO_HASH_BYTES_LEN: usize = 20;
 
+/// Length of the hex representation of an `InfoHash`.
+pub const INFO_HASH_HEX_LEN: usize = 40;
+
+/// Length of the base32 representation of an `InfoHash`. 20 bytes fit in 32
+/// base32 digits, so it never has padding.
+pub const INFO_HASH_BASE32_LEN: usize = 32;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
 impl InfoHash {
     /// Create a new `InfoHash` from a byte slice.
     ///
@@ -21,7 +42,7 @@ impl InfoHash {
     /// Will panic if byte slice does not contains the exact amount of bytes need for the `InfoHash`.
     #[must_use]
     pub fn from_bytes(bytes: &[u8]) -> Self {
-        let data = aquatic_udp_protocol::InfoHash::read_from(bytes).expect("it should have the exact amount of bytes");
+        let data = bytes.try_into().expect("it should have the exact amount of bytes");
 
         Self { data }
     }
@@ -29,7 +50,7 @@ impl InfoHash {
     /// Returns the `InfoHash` internal byte array.
     #[must_use]
     pub fn bytes(&self) -> [u8; 20] {
-        self.0
+        self.data
     }
 
     /// Returns the `InfoHash` as a hex string.
@@ -37,79 +58,146 @@ impl InfoHash {
     pub fn to_hex_string(&self) -> String {
         self.to_string()
     }
+
+    /// Returns the `InfoHash` as an uppercase base32 string.
+    #[must_use]
+    pub fn to_base32_string(&self) -> String {
+        // One extra byte, so every 5-bit digit can be read from a 2-byte window.
+        let mut bytes = [0u8; INFO_HASH_BYTES_LEN + 1];
+        bytes[..INFO_HASH_BYTES_LEN].copy_from_slice(&self.data);
+
+        (0..INFO_HASH_BASE32_LEN)
+            .map(|digit| {
+                let offset = digit * 5;
+                let window = u16::from_be_bytes([bytes[offset / 8], bytes[offset / 8 + 1]]);
+                char::from(BASE32_ALPHABET[usize::from((window >> (11 - offset % 8)) & 0x1f)])
+            })
+            .collect()
+    }
+
+    /// Parses the hex representation of an `InfoHash`, in any case.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the string is not 40 hex digits long.
+    pub fn from_hex(s: &str) -> Result<Self, binascii::ConvertError> {
+        if s.len() != INFO_HASH_HEX_LEN {
+            return Err(binascii::ConvertError::InvalidInputLength);
+        }
+
+        let mut info_hash = Self::default();
+        binascii::hex2bin(s.as_bytes(), &mut info_hash.data)?;
+        Ok(info_hash)
+    }
+
+    /// Parses the base32 representation of an `InfoHash`, in any case.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the string is not 32 base32 digits long.
+    pub fn from_base32(s: &str) -> Result<Self, binascii::ConvertError> {
+        if s.len() != INFO_HASH_BASE32_LEN {
+            return Err(binascii::ConvertError::InvalidInputLength);
+        }
+
+        let mut bytes = [0u8; INFO_HASH_BYTES_LEN];
+
+        // Every 8 digits are 40 bits, that is, 5 bytes.
+        for (block, digits) in s.as_bytes().chunks(8).enumerate() {
+            let mut bits = 0u64;
+
+            for digit in digits {
+                let value = match digit {
+                    b'A'..=b'Z' => digit - b'A',
+                    b'a'..=b'z' => digit - b'a',
+                    b'2'..=b'7' => digit - b'2' + 26,
+                    _ => return Err(binascii::ConvertError::InvalidInput),
+                };
+                bits = (bits << 5) | u64::from(value);
+            }
+
+            bytes[block * 5..block * 5 + 5].copy_from_slice(&bits.to_be_bytes()[3..]);
+        }
+
+        Ok(Self::from(bytes))
+    }
 }
 
 impl Default for InfoHash {
     fn default() -> Self {
         Self {
-            data: aquatic_udp_protocol::InfoHash(Default::default()),
+            data: [0; INFO_HASH_BYTES_LEN],
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<aquatic_udp_protocol::InfoHash> for InfoHash {
-    fn from(data: aquatic_udp_protocol::InfoHash) -> Self {
-        Self { data }
+    fn from(info_hash: aquatic_udp_protocol::InfoHash) -> Self {
+        Self { data: info_hash.0 }
     }
 }
 
+#[cfg(feature = "std")]
 impl Deref for InfoHash {
     type Target = aquatic_udp_protocol::InfoHash;
 
     fn deref(&self) -> &Self::Target {
-        &self.data
+        aquatic_udp_protocol::InfoHash::ref_from(&self.data).expect("it should have the exact amount of bytes")
     }
 }
 
+#[cfg(feature = "std")]
 impl DerefMut for InfoHash {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.data
+        aquatic_udp_protocol::InfoHash::mut_from(&mut self.data).expect("it should have the exact amount of bytes")
     }
 }
 
 impl Ord for InfoHash {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.cmp(&other.0)
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
     }
 }
 
 impl PartialOrd<InfoHash> for InfoHash {
-    fn partial_cmp(&self, other: &InfoHash) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &InfoHash) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl std::fmt::Display for InfoHash {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InfoHash {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut chars = [0u8; 40];
-        binascii::bin2hex(&self.0, &mut chars).expect("failed to hexlify");
-        write!(f, "{}", std::str::from_utf8(&chars).unwrap())
+        binascii::bin2hex(&self.data, &mut chars).expect("failed to hexlify");
+        write!(f, "{}", core::str::from_utf8(&chars).unwrap())
     }
 }
 
-impl std::str::FromStr for InfoHash {
+impl core::str::FromStr for InfoHash {
     type Err = binascii::ConvertError;
 
+    /// It accepts the hex and the base32 representations, in any case.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut i = Self::default();
-        if s.len() != 40 {
-            return Err(binascii::ConvertError::InvalidInputLength);
+        match s.len() {
+            INFO_HASH_HEX_LEN => Self::from_hex(s),
+            INFO_HASH_BASE32_LEN => Self::from_base32(s),
+            _ => Err(binascii::ConvertError::InvalidInputLength),
         }
-        binascii::hex2bin(s.as_bytes(), &mut i.0)?;
-        Ok(i)
     }
 }
 
-impl std::convert::From<&[u8]> for InfoHash {
+impl core::convert::From<&[u8]> for InfoHash {
     fn from(data: &[u8]) -> InfoHash {
         assert_eq!(data.len(), 20);
         let mut ret = Self::default();
-        ret.0.clone_from_slice(data);
+        ret.data.clone_from_slice(data);
         ret
     }
 }
 
 /// for testing
+#[cfg(feature = "std")]
 impl std::convert::From<&DefaultHasher> for InfoHash {
     fn from(data: &DefaultHasher) -> InfoHash {
         let n = data.finish().to_le_bytes();
@@ -117,28 +205,26 @@ impl std::convert::From<&DefaultHasher> for InfoHash {
             n[0], n[1], n[2], n[3], n[4], n[5], n[6], n[7], n[0], n[1], n[2], n[3], n[4], n[5], n[6], n[7], n[0], n[1], n[2],
             n[3],
         ];
-        let data = aquatic_udp_protocol::InfoHash(bytes);
-        Self { data }
+        Self { data: bytes }
     }
 }
 
-impl std::convert::From<&i32> for InfoHash {
+impl core::convert::From<&i32> for InfoHash {
     fn from(n: &i32) -> InfoHash {
         let n = n.to_le_bytes();
         let bytes = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, n[0], n[1], n[2], n[3]];
-        let data = aquatic_udp_protocol::InfoHash(bytes);
-        Self { data }
+        Self { data: bytes }
     }
 }
 
-impl std::convert::From<[u8; 20]> for InfoHash {
+impl core::convert::From<[u8; 20]> for InfoHash {
     fn from(bytes: [u8; 20]) -> Self {
-        let data = aquatic_udp_protocol::InfoHash(bytes);
-        Self { data }
+        Self { data: bytes }
     }
 }
 
 /// Errors that can occur when converting from a `Vec<u8>` to an `InfoHash`.
+#[cfg(feature = "std")]
 #[derive(Error, Debug)]
 pub enum ConversionError {
     /// Not enough bytes for infohash. An infohash is 20 bytes.
@@ -155,6 +241,7 @@ pub enum ConversionError {
     },
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Vec<u8>> for InfoHash {
     type Error = ConversionError;
 
@@ -178,8 +265,8 @@ impl TryFrom<Vec<u8>> for InfoHash {
 impl serde::ser::Serialize for InfoHash {
     fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut buffer = [0u8; 40];
-        let bytes_out = binascii::bin2hex(&self.0, &mut buffer).ok().unwrap();
-        let str_out = std::str::from_utf8(bytes_out).unwrap();
+        let bytes_out = binascii::bin2hex(&self.data, &mut buffer).ok().unwrap();
+        let str_out = core::str::from_utf8(bytes_out).unwrap();
         serializer.serialize_str(str_out)
     }
 }
@@ -195,26 +282,168 @@ struct InfoHashVisitor;
 impl<'v> serde::de::Visitor<'v> for InfoHashVisitor {
     type Value = InfoHash;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(formatter, "a 40 character long hash")
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(formatter, "a 40 character long hex or a 32 character long base32 hash")
     }
 
     fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
-        if v.len() != 40 {
-            return Err(serde::de::Error::invalid_value(
+        match v.len() {
+            INFO_HASH_HEX_LEN => InfoHash::from_hex(v)
+                .map_err(|_| serde::de::Error::invalid_value(serde::de::Unexpected::Str(v), &"a hexadecimal string")),
+            INFO_HASH_BASE32_LEN => InfoHash::from_base32(v)
+                .map_err(|_| serde::de::Error::invalid_value(serde::de::Unexpected::Str(v), &"a base32 string")),
+            _ => Err(serde::de::Error::invalid_value(
                 serde::de::Unexpected::Str(v),
-                &"a 40 character long string",
-            ));
+                &"a 40 or 32 character long string",
+            )),
         }
+    }
+}
 
-        let mut res = InfoHash::default();
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
 
-        if binascii::hex2bin(v.as_bytes(), &mut res.0).is_err() {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Str(v),
-                &"a hexadecimal string",
-            ));
-        };
-        Ok(res)
+    use super::{InfoHash, INFO_HASH_BASE32_LEN, INFO_HASH_HEX_LEN};
+
+    const HEX: &str = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d";
+    const BASE32: &str = "TYBBPUH2OHEHGMWNRP45X2V4WLBM6PCN";
+
+    /// A small deterministic generator (xorshift), so the fuzz tests are
+    /// reproducible.
+    struct Generator(u64);
+
+    impl Generator {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn bytes(&mut self) -> [u8; 20] {
+            let mut bytes = [0u8; 20];
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next().to_le_bytes()[..chunk.len()]);
+            }
+            bytes
+        }
+
+        fn string(&mut self, alphabet: &[u8], len: usize) -> String {
+            (0..len)
+                .map(|_| char::from(alphabet[usize::try_from(self.next() % alphabet.len() as u64).unwrap()]))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn it_should_be_parsed_from_lowercase_and_uppercase_hex() {
+        let info_hash = InfoHash::from_str(HEX).unwrap();
+
+        assert_eq!(InfoHash::from_str(&HEX.to_uppercase()).unwrap(), info_hash);
+        assert_eq!(info_hash.to_string(), HEX);
+    }
+
+    #[test]
+    fn it_should_be_parsed_from_uppercase_and_lowercase_base32() {
+        let info_hash = InfoHash::from_str(HEX).unwrap();
+
+        assert_eq!(InfoHash::from_str(BASE32).unwrap(), info_hash);
+        assert_eq!(InfoHash::from_str(&BASE32.to_lowercase()).unwrap(), info_hash);
+        assert_eq!(info_hash.to_base32_string(), BASE32);
+    }
+
+    #[test]
+    fn it_should_be_deserialized_from_hex_and_base32() {
+        let info_hash = InfoHash::from_str(HEX).unwrap();
+
+        assert_eq!(serde_json::from_str::<InfoHash>(&format!("\"{BASE32}\"")).unwrap(), info_hash);
+        assert_eq!(
+            serde_json::from_str::<InfoHash>(&format!("\"{}\"", HEX.to_uppercase())).unwrap(),
+            info_hash
+        );
+        assert!(serde_json::from_str::<InfoHash>("\"9e0217d0\"").is_err());
+    }
+
+    #[test]
+    fn it_should_not_be_parsed_from_invalid_strings() {
+        let invalid = [
+            "",
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4",
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d0",
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4g",
+            // Base32 excludes the digits 0, 1, 8 and 9.
+            "TYBBPUH2OHEHGMWNRP45X2V4WLBM6PC0",
+            "TYBBPUH2OHEHGMWNRP45X2V4WLBM6PC=",
+            "ÑYBBPUH2OHEHGMWNRP45X2V4WLBM6PC",
+        ];
+
+        for s in invalid {
+            assert!(InfoHash::from_str(s).is_err(), "{s:?} should not be a valid info-hash");
+        }
+    }
+
+    #[test]
+    fn fuzz_all_the_text_representations_should_round_trip() {
+        let mut generator = Generator(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..10_000 {
+            let info_hash = InfoHash::from(generator.bytes());
+
+            let hex = info_hash.to_hex_string();
+            let base32 = info_hash.to_base32_string();
+
+            assert_eq!(hex.len(), INFO_HASH_HEX_LEN);
+            assert_eq!(base32.len(), INFO_HASH_BASE32_LEN);
+
+            for text in [hex.clone(), hex.to_uppercase(), base32.clone(), base32.to_lowercase()] {
+                assert_eq!(
+                    InfoHash::from_str(&text).unwrap(),
+                    info_hash,
+                    "{text} should be parsed as {hex}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_parsing_arbitrary_strings_should_only_accept_canonicalizable_ones() {
+        let mut generator = Generator(0x9e37_79b9_7f4a_7c15);
+
+        let alphabets: [&[u8]; 3] = [
+            b"0123456789abcdefABCDEF",
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz234567",
+            b"0123456789abcdefABCDEFghijklmnopqrstuvwxyzGHIJKLMNOPQRSTUVWXYZ=%+/ -",
+        ];
+        let lens = [INFO_HASH_HEX_LEN, INFO_HASH_BASE32_LEN, 0, 20, 33, 39, 41];
+
+        let mut accepted = 0;
+
+        for _ in 0..10_000 {
+            let alphabet = alphabets[usize::try_from(generator.next() % 3).unwrap()];
+            let len = lens[usize::try_from(generator.next() % 7).unwrap()];
+
+            let text = generator.string(alphabet, len);
+
+            if let Ok(info_hash) = InfoHash::from_str(&text) {
+                let canonical = if len == INFO_HASH_HEX_LEN {
+                    info_hash.to_hex_string()
+                } else {
+                    info_hash.to_base32_string()
+                };
+
+                assert!(canonical.eq_ignore_ascii_case(&text), "{text} was parsed as {canonical}");
+
+                accepted += 1;
+            } else {
+                assert!(
+                    !(len == INFO_HASH_HEX_LEN && alphabet == alphabets[0]
+                        || len == INFO_HASH_BASE32_LEN && alphabet == alphabets[1]),
+                    "{text} should be a valid info-hash"
+                );
+            }
+        }
+
+        assert!(accepted > 0);
     }
 }
@@ -4,8 +4,26 @@
 //! which is a `BitTorrent` tracker server. These structures are used not only
 //! by the tracker server crate, but also by other crates in the Torrust
 //! ecosystem.
-use std::collections::BTreeMap;
-use std::time::Duration;
+//!
+//! # Features
+//!
+//! - `std` (enabled by default): it adds the types and conversions that
+//!   depend on the standard library, like the [`Peer`](peer::Peer) and the
+//!   conversions from and to the [`aquatic_udp_protocol`](https://docs.rs/aquatic_udp_protocol)
+//!   types.
+//!
+//! Without the `std` feature the crate is `no_std` (it only requires `alloc`).
+//! Lightweight tools, like WASM dashboards or embedded clients, can use it to
+//! reuse the same [`InfoHash`](info_hash::InfoHash), peer [`Id`](peer::Id),
+//! [`AnnounceEventSer`](peer::AnnounceEventSer), [`SwarmMetadata`](swarm_metadata::SwarmMetadata),
+//! [`TorrentsMetrics`](torrent_metrics::TorrentsMetrics) and [`Pagination`](pagination::Pagination)
+//! types, with the same serialization, the tracker uses.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
 
 use info_hash::InfoHash;
 
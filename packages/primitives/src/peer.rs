@@ -19,17 +19,36 @@
 //!     downloaded: NumberOfBytes::new(0),
 //!     left: NumberOfBytes::new(0),
 //!     event: AnnounceEvent::Started,
+//!     partial_seed: false,
+//!     corrupt: NumberOfBytes::new(0),
+//!     redundant: NumberOfBytes::new(0),
 //! };
 //! ```
-
+//!
+//! Without the `std` feature, only the peer [`Id`] and the [`AnnounceEventSer`]
+//! types are available.
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use core::panic::Location;
+#[cfg(feature = "std")]
 use std::net::{IpAddr, SocketAddr};
-use std::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
 use std::sync::Arc;
 
+#[cfg(feature = "std")]
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
 use serde::Serialize;
+#[cfg(feature = "std")]
+use thiserror::Error;
+#[cfg(feature = "std")]
 use zerocopy::FromBytes as _;
 
+#[cfg(feature = "std")]
 use crate::DurationSinceUnixEpoch;
 
 /// Peer struct used by the core `Tracker`.
@@ -53,8 +72,12 @@ use crate::DurationSinceUnixEpoch;
 ///     downloaded: NumberOfBytes::new(0),
 ///     left: NumberOfBytes::new(0),
 ///     event: AnnounceEvent::Started,
+///     partial_seed: false,
+///     corrupt: NumberOfBytes::new(0),
+///     redundant: NumberOfBytes::new(0),
 /// };
 /// ```
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Serialize, Copy, PartialEq, Eq, Hash)]
 pub struct Peer {
     /// ID used by the downloader peer
@@ -77,18 +100,35 @@ pub struct Peer {
     /// This is an optional key which maps to started, completed, or stopped (or empty, which is the same as not being present).
     #[serde(serialize_with = "ser_announce_event")]
     pub event: AnnounceEvent,
+    /// Whether the peer is a partial seed: it has all the pieces it wants but
+    /// not the whole torrent. Partial seeds are reported with the `paused`
+    /// event. See [BEP 21. Extension for partial seeds](https://www.bittorrent.org/beps/bep_0021.html).
+    pub partial_seed: bool,
+    /// The total amount of bytes this peer downloaded that failed the hash
+    /// check. Reported by some clients with the non-standard `corrupt` param.
+    /// It's zero when the client does not report it.
+    #[serde(serialize_with = "ser_number_of_bytes")]
+    pub corrupt: NumberOfBytes,
+    /// The total amount of bytes this peer downloaded more than once, for
+    /// example, from several peers in end-game mode. Reported by some clients
+    /// with the non-standard `redundant` param. It's zero when the client does
+    /// not report it.
+    #[serde(serialize_with = "ser_number_of_bytes")]
+    pub redundant: NumberOfBytes,
 }
 
 /// Serializes a `DurationSinceUnixEpoch` as a Unix timestamp in milliseconds.
 /// # Errors
 ///
 /// Will return `serde::Serializer::Error` if unable to serialize the `unix_time_value`.
+#[cfg(feature = "std")]
 pub fn ser_unix_time_value<S: serde::Serializer>(unix_time_value: &DurationSinceUnixEpoch, ser: S) -> Result<S::Ok, S::Error> {
     #[allow(clippy::cast_possible_truncation)]
     ser.serialize_u64(unix_time_value.as_millis() as u64)
 }
 
-#[derive(Serialize)]
+/// The announce events, as they are serialized.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnnounceEventSer {
     Started,
     Stopped,
@@ -101,6 +141,7 @@ pub enum AnnounceEventSer {
 /// # Errors
 ///
 /// If will return an error if the internal serializer was to fail.
+#[cfg(feature = "std")]
 pub fn ser_announce_event<S: serde::Serializer>(announce_event: &AnnounceEvent, ser: S) -> Result<S::Ok, S::Error> {
     let event_ser = match announce_event {
         AnnounceEvent::Started => AnnounceEventSer::Started,
@@ -117,6 +158,7 @@ pub fn ser_announce_event<S: serde::Serializer>(announce_event: &AnnounceEvent,
 /// # Errors
 ///
 /// If will return an error if the internal serializer was to fail.
+#[cfg(feature = "std")]
 pub fn ser_number_of_bytes<S: serde::Serializer>(number_of_bytes: &NumberOfBytes, ser: S) -> Result<S::Ok, S::Error> {
     ser.serialize_i64(number_of_bytes.0.get())
 }
@@ -126,23 +168,27 @@ pub fn ser_number_of_bytes<S: serde::Serializer>(number_of_bytes: &NumberOfBytes
 /// # Errors
 ///
 /// If will return an error if the internal serializer was to fail.
+#[cfg(feature = "std")]
 pub fn ser_peer_id<S: serde::Serializer>(peer_id: &PeerId, ser: S) -> Result<S::Ok, S::Error> {
-    let id = Id { data: *peer_id };
+    let id = Id::from(*peer_id);
     ser.serialize_some(&id)
 }
 
+#[cfg(feature = "std")]
 impl Ord for Peer {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.peer_id.cmp(&other.peer_id)
     }
 }
 
+#[cfg(feature = "std")]
 impl PartialOrd for Peer {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.peer_id.cmp(&other.peer_id))
+        Some(self.cmp(other))
     }
 }
 
+#[cfg(feature = "std")]
 pub trait ReadInfo {
     fn is_seeder(&self) -> bool;
     fn get_event(&self) -> AnnounceEvent;
@@ -151,6 +197,7 @@ pub trait ReadInfo {
     fn get_address(&self) -> SocketAddr;
 }
 
+#[cfg(feature = "std")]
 impl ReadInfo for Peer {
     fn is_seeder(&self) -> bool {
         self.left.0.get() <= 0 && self.event != AnnounceEvent::Stopped
@@ -173,6 +220,7 @@ impl ReadInfo for Peer {
     }
 }
 
+#[cfg(feature = "std")]
 impl ReadInfo for Arc<Peer> {
     fn is_seeder(&self) -> bool {
         self.left.0.get() <= 0 && self.event != AnnounceEvent::Stopped
@@ -195,12 +243,19 @@ impl ReadInfo for Arc<Peer> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Peer {
     #[must_use]
     pub fn is_seeder(&self) -> bool {
         self.left.0.get() <= 0 && self.event != AnnounceEvent::Stopped
     }
 
+    /// Partial seeds are leechers that are not downloading anymore.
+    #[must_use]
+    pub fn is_partial_seed(&self) -> bool {
+        self.partial_seed && !self.is_seeder()
+    }
+
     pub fn ip(&mut self) -> IpAddr {
         self.peer_addr.ip()
     }
@@ -210,13 +265,10 @@ impl Peer {
     }
 }
 
-use std::panic::Location;
-
-use thiserror::Error;
-
 /// Error returned when trying to convert an invalid peer id from another type.
 ///
 /// Usually because the source format does not contain 20 bytes.
+#[cfg(feature = "std")]
 #[derive(Error, Debug)]
 pub enum IdConversionError {
     #[error("not enough bytes for peer id: {message} {location}")]
@@ -232,26 +284,35 @@ pub enum IdConversionError {
 }
 
 pub struct Id {
-    data: PeerId,
+    data: [u8; PEER_ID_BYTES_LEN],
+}
+
+impl From<[u8; PEER_ID_BYTES_LEN]> for Id {
+    fn from(bytes: [u8; PEER_ID_BYTES_LEN]) -> Self {
+        Self { data: bytes }
+    }
 }
 
+#[cfg(feature = "std")]
 impl From<PeerId> for Id {
     fn from(id: PeerId) -> Self {
-        Self { data: id }
+        Self { data: id.0 }
     }
 }
 
+#[cfg(feature = "std")]
 impl Deref for Id {
     type Target = PeerId;
 
     fn deref(&self) -> &Self::Target {
-        &self.data
+        PeerId::ref_from(&self.data).expect("it should have the exact amount of bytes")
     }
 }
 
+#[cfg(feature = "std")]
 impl DerefMut for Id {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.data
+        PeerId::mut_from(&mut self.data).expect("it should have the exact amount of bytes")
     }
 }
 
@@ -268,11 +329,11 @@ impl Id {
             number[8], number[9], number[10], number[11], number[12], number[13], number[14], number[15],
         ];
 
-        let data = PeerId(bytes);
-        Id { data }
+        Id { data: bytes }
     }
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Vec<u8>> for Id {
     type Error = IdConversionError;
 
@@ -291,12 +352,12 @@ impl TryFrom<Vec<u8>> for Id {
         }
 
         let data = PeerId::read_from(&bytes).expect("it should have the correct amount of bytes");
-        Ok(Self { data })
+        Ok(Self::from(data))
     }
 }
 
-impl std::fmt::Display for Id {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Id {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self.to_hex_string() {
             Some(hex) => write!(f, "{hex}"),
             None => write!(f, ""),
@@ -335,24 +396,25 @@ impl Id {
     ///
     /// It will panic if the `binascii::bin2hex` from a too-small output buffer.
     pub fn to_hex_string(&self) -> Option<String> {
-        let buff_size = self.0.len() * 2;
-        let mut tmp: Vec<u8> = vec![0; buff_size];
+        let mut tmp = [0u8; PEER_ID_BYTES_LEN * 2];
 
-        binascii::bin2hex(&self.0, &mut tmp).unwrap();
+        binascii::bin2hex(&self.data, &mut tmp).unwrap();
 
-        match std::str::from_utf8(&tmp) {
+        match core::str::from_utf8(&tmp) {
             Ok(hex) => Some(format!("0x{hex}")),
             Err(_) => None,
         }
     }
 
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn get_client_name(&self) -> Option<String> {
-        let peer_id = tdyne_peer_id::PeerId::from(self.0);
+        let peer_id = tdyne_peer_id::PeerId::from(self.data);
         tdyne_peer_id_registry::parse(peer_id).ok().map(|parsed| parsed.client)
     }
 }
 
+#[cfg(feature = "std")]
 impl Serialize for Id {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -373,8 +435,10 @@ impl Serialize for Id {
 }
 
 /// Marker Trait for Peer Vectors
+#[cfg(feature = "std")]
 pub trait Encoding: From<Peer> + PartialEq {}
 
+#[cfg(feature = "std")]
 impl<P: Encoding> FromIterator<Peer> for Vec<P> {
     fn from_iter<T: IntoIterator<Item = Peer>>(iter: T) -> Self {
         let mut peers: Vec<P> = vec![];
@@ -387,6 +451,7 @@ impl<P: Encoding> FromIterator<Peer> for Vec<P> {
     }
 }
 
+#[cfg(feature = "std")]
 pub mod fixture {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
@@ -420,6 +485,9 @@ pub mod fixture {
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(0),
                 event: AnnounceEvent::Completed,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
             };
 
             Self { peer }
@@ -436,6 +504,9 @@ pub mod fixture {
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(10),
                 event: AnnounceEvent::Started,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
             };
 
             Self { peer }
@@ -469,6 +540,27 @@ pub mod fixture {
             self
         }
 
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn as_partial_seed(mut self) -> Self {
+            self.peer.partial_seed = true;
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_corrupt_bytes(mut self, corrupt: i64) -> Self {
+            self.peer.corrupt = NumberOfBytes::new(corrupt);
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_redundant_bytes(mut self, redundant: i64) -> Self {
+            self.peer.redundant = NumberOfBytes::new(redundant);
+            self
+        }
+
         #[allow(dead_code)]
         #[must_use]
         pub fn last_updated_on(mut self, updated: DurationSinceUnixEpoch) -> Self {
@@ -499,14 +591,18 @@ pub mod fixture {
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(0),
                 event: AnnounceEvent::Started,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
             }
         }
     }
 
     impl Default for Id {
         fn default() -> Self {
-            let data = PeerId(*b"-qB00000000000000000");
-            Self { data }
+            Self {
+                data: *b"-qB00000000000000000",
+            }
         }
     }
 }
@@ -514,8 +610,6 @@ pub mod fixture {
 #[cfg(test)]
 pub mod test {
     mod torrent_peer_id {
-        use aquatic_udp_protocol::PeerId;
-
         use crate::peer;
 
         #[test]
@@ -532,31 +626,23 @@ pub mod test {
 
         #[test]
         fn should_be_converted_to_hex_string() {
-            let id = peer::Id {
-                data: PeerId(*b"-qB00000000000000000"),
-            };
+            let id = peer::Id::from(*b"-qB00000000000000000");
             assert_eq!(id.to_hex_string().unwrap(), "0x2d71423030303030303030303030303030303030");
 
-            let id = peer::Id {
-                data: PeerId([
-                    0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150,
-                ]),
-            };
+            let id = peer::Id::from([
+                0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150,
+            ]);
             assert_eq!(id.to_hex_string().unwrap(), "0x009f9296009f9296009f9296009f9296009f9296");
         }
 
         #[test]
         fn should_be_converted_into_string_type_using_the_hex_string_format() {
-            let id = peer::Id {
-                data: PeerId(*b"-qB00000000000000000"),
-            };
+            let id = peer::Id::from(*b"-qB00000000000000000");
             assert_eq!(id.to_string(), "0x2d71423030303030303030303030303030303030");
 
-            let id = peer::Id {
-                data: PeerId([
-                    0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150,
-                ]),
-            };
+            let id = peer::Id::from([
+                0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150, 0, 159, 146, 150,
+            ]);
             assert_eq!(id.to_string(), "0x009f9296009f9296009f9296009f9296009f9296");
         }
     }
@@ -12,6 +12,10 @@ pub struct SwarmMetadata {
     pub complete: u32, //seeders
     /// (i.e `leechers`): The number of active peers that have not completed downloading (leechers)
     pub incomplete: u32,
+    /// The number of active peers that have not completed downloading, but
+    /// have all the pieces they want (partial seeds). They are also counted
+    /// in `incomplete`. See [BEP 21. Extension for partial seeds](https://www.bittorrent.org/beps/bep_0021.html).
+    pub partial_seeds: u32,
 }
 
 impl SwarmMetadata {
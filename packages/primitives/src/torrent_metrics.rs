@@ -1,4 +1,4 @@
-use std::ops::AddAssign;
+use core::ops::AddAssign;
 
 /// Structure that holds general `Tracker` torrents metrics.
 ///
@@ -1,8 +1,8 @@
 //! Tracker configuration factories for testing.
 use std::env;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
 
-use torrust_tracker_configuration::{Configuration, HttpApi, HttpTracker, Threshold, UdpTracker};
+use torrust_tracker_configuration::{Configuration, Driver, Threshold};
 
 use crate::random;
 
@@ -20,49 +20,30 @@ use crate::random;
 ///
 /// # Panics
 ///
-/// Will panic if it can't convert the temp file path to string
+/// Will panic if it can't convert the temp file path to string, or the
+/// configuration is not valid.
 #[must_use]
 pub fn ephemeral() -> Configuration {
     // todo: disable services that are not needed.
     // For example: a test for the UDP tracker should disable the API and HTTP tracker.
 
-    let mut config = Configuration::default();
-
-    config.logging.threshold = Threshold::Off; // It should always be off here, the tests manage their own logging.
-
-    // Ephemeral socket address for API
-    let api_port = 0u16;
-    let mut http_api = HttpApi {
-        bind_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), api_port),
-        ..Default::default()
-    };
-    http_api.add_token("admin", "MyAccessToken");
-    config.http_api = Some(http_api);
-
-    // Ephemeral socket address for Health Check API
-    let health_check_api_port = 0u16;
-    config.health_check_api.bind_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), health_check_api_port);
-
-    // Ephemeral socket address for UDP tracker
-    let udp_port = 0u16;
-    config.udp_trackers = Some(vec![UdpTracker {
-        bind_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), udp_port),
-    }]);
-
-    // Ephemeral socket address for HTTP tracker
-    let http_port = 0u16;
-    config.http_trackers = Some(vec![HttpTracker {
-        bind_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), http_port),
-        tsl_config: None,
-    }]);
-
     // Ephemeral sqlite database
     let temp_directory = env::temp_dir();
     let random_db_id = random::string(16);
     let temp_file = temp_directory.join(format!("data_{random_db_id}.db"));
-    temp_file.to_str().unwrap().clone_into(&mut config.core.database.path);
 
-    config
+    // Port 0 is used for ephemeral socket addresses
+    Configuration::builder()
+        .with_log_threshold(Threshold::Off) // It should always be off here, the tests manage their own logging.
+        .with_api("127.0.0.1:0")
+        .with_api_token("admin", "MyAccessToken")
+        .with_api_admin_token("incident-response", "MyAdminAccessToken")
+        .with_health_check_api("127.0.0.1:0")
+        .with_udp("127.0.0.1:0")
+        .with_http("127.0.0.1:0")
+        .with_database(Driver::Sqlite3, temp_file.to_str().unwrap())
+        .build()
+        .expect("the ephemeral configuration should be valid")
 }
 
 /// Ephemeral configuration with reverse proxy enabled.
@@ -85,6 +66,23 @@ pub fn ephemeral_without_reverse_proxy() -> Configuration {
     cfg
 }
 
+/// Ephemeral configuration with the PROXY protocol enabled for the UDP and
+/// HTTP trackers.
+#[must_use]
+pub fn ephemeral_with_proxy_protocol() -> Configuration {
+    let mut cfg = ephemeral();
+
+    if let Some(ref mut http_trackers) = cfg.http_trackers {
+        http_trackers[0].proxy_protocol = true;
+    }
+
+    if let Some(ref mut udp_trackers) = cfg.udp_trackers {
+        udp_trackers[0].proxy_protocol = true;
+    }
+
+    cfg
+}
+
 /// Ephemeral configuration with `public` mode.
 #[must_use]
 pub fn ephemeral_public() -> Configuration {
@@ -105,6 +103,16 @@ pub fn ephemeral_private() -> Configuration {
     cfg
 }
 
+/// Ephemeral configuration with `hybrid` mode.
+#[must_use]
+pub fn ephemeral_hybrid() -> Configuration {
+    let mut cfg = ephemeral();
+
+    cfg.core.hybrid = true;
+
+    cfg
+}
+
 /// Ephemeral configuration with `listed` mode.
 #[must_use]
 pub fn ephemeral_listed() -> Configuration {
@@ -145,15 +153,15 @@ pub fn ephemeral_ipv6() -> Configuration {
     let ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0)), 0);
 
     if let Some(ref mut http_api) = cfg.http_api {
-        http_api.bind_address.clone_from(&ipv6);
+        http_api.bind_address = ipv6.into();
     };
 
     if let Some(ref mut http_trackers) = cfg.http_trackers {
-        http_trackers[0].bind_address.clone_from(&ipv6);
+        http_trackers[0].bind_address = ipv6.into();
     }
 
     if let Some(ref mut udp_trackers) = cfg.udp_trackers {
-        udp_trackers[0].bind_address.clone_from(&ipv6);
+        udp_trackers[0].bind_address = ipv6.into();
     }
 
     cfg
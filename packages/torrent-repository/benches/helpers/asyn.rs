@@ -5,7 +5,7 @@ use futures::stream::FuturesUnordered;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_torrent_repository::repository::RepositoryAsync;
 
-use super::utils::{generate_unique_info_hashes, DEFAULT_PEER};
+use super::utils::{generate_unique_info_hashes, generate_unique_peers, BATCH_SIZE, DEFAULT_PEER, DEFAULT_POLICY};
 
 pub async fn add_one_torrent<V, T>(samples: u64) -> Duration
 where
@@ -18,7 +18,33 @@ where
 
         let info_hash = InfoHash::default();
 
-        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+        torrent_repository
+            .upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+            .await;
+
+        torrent_repository.get_swarm_metadata(&info_hash).await;
+    }
+
+    start.elapsed()
+}
+
+// Add a batch of peers to one torrent, acquiring the torrent entry lock once
+pub async fn upsert_a_batch_of_peers<V, T>(samples: u64) -> Duration
+where
+    V: RepositoryAsync<T> + Default,
+{
+    let peers = generate_unique_peers(BATCH_SIZE);
+
+    let start = Instant::now();
+
+    for _ in 0..samples {
+        let torrent_repository = V::default();
+
+        let info_hash = InfoHash::default();
+
+        torrent_repository
+            .upsert_peers_batch(&info_hash, &peers, &DEFAULT_POLICY)
+            .await;
 
         torrent_repository.get_swarm_metadata(&info_hash).await;
     }
@@ -37,7 +63,9 @@ where
     let handles = FuturesUnordered::new();
 
     // Add the torrent/peer to the torrent repository
-    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+    torrent_repository
+        .upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+        .await;
 
     torrent_repository.get_swarm_metadata(&info_hash).await;
 
@@ -47,7 +75,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+                .await;
 
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
@@ -87,7 +117,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+                .await;
 
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
@@ -123,7 +155,9 @@ where
 
     // Add the torrents/peers to the torrent repository
     for info_hash in &info_hashes {
-        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER).await;
+        torrent_repository
+            .upsert_peer(info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+            .await;
         torrent_repository.get_swarm_metadata(info_hash).await;
     }
 
@@ -133,7 +167,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY)
+                .await;
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
             if let Some(sleep_time) = sleep {
@@ -1,11 +1,13 @@
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::stream::FuturesUnordered;
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::PersistentTorrents;
 use torrust_tracker_torrent_repository::repository::Repository;
 
-use super::utils::{generate_unique_info_hashes, DEFAULT_PEER};
+use super::utils::{generate_unique_info_hashes, generate_unique_peers, BATCH_SIZE, DEFAULT_PEER, DEFAULT_POLICY};
 
 // Simply add one torrent
 #[must_use]
@@ -20,7 +22,30 @@ where
 
         let info_hash = InfoHash::default();
 
-        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER);
+        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
+
+        torrent_repository.get_swarm_metadata(&info_hash);
+    }
+
+    start.elapsed()
+}
+
+// Add a batch of peers to one torrent, acquiring the torrent entry lock once
+#[must_use]
+pub fn upsert_a_batch_of_peers<V, T>(samples: u64) -> Duration
+where
+    V: Repository<T> + Default,
+{
+    let peers = generate_unique_peers(BATCH_SIZE);
+
+    let start = Instant::now();
+
+    for _ in 0..samples {
+        let torrent_repository = V::default();
+
+        let info_hash = InfoHash::default();
+
+        torrent_repository.upsert_peers_batch(&info_hash, &peers, &DEFAULT_POLICY);
 
         torrent_repository.get_swarm_metadata(&info_hash);
     }
@@ -39,7 +64,7 @@ where
     let handles = FuturesUnordered::new();
 
     // Add the torrent/peer to the torrent repository
-    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER);
+    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
 
     torrent_repository.get_swarm_metadata(&info_hash);
 
@@ -49,7 +74,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
 
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
@@ -89,7 +114,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
 
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
@@ -125,7 +150,7 @@ where
 
     // Add the torrents/peers to the torrent repository
     for info_hash in &info_hashes {
-        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER);
+        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
         torrent_repository.get_swarm_metadata(info_hash);
     }
 
@@ -135,7 +160,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &DEFAULT_POLICY);
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
             if let Some(sleep_time) = sleep {
@@ -153,3 +178,42 @@ where
 
     start.elapsed()
 }
+
+// Import ten thousand persisted torrents in parallel shards (depending on the available parallelism),
+// like the tracker does at startup. The repository is created for the number of torrents to import.
+pub async fn import_persistent_torrents_in_parallel<V, T, F>(
+    runtime: &tokio::runtime::Runtime,
+    samples: u64,
+    new_repository: F,
+) -> Duration
+where
+    V: Repository<T>,
+    Arc<V>: Clone + Send + Sync + 'static,
+    F: Fn(usize) -> V,
+{
+    let torrents: usize = samples.try_into().expect("it should fit in a usize");
+    let torrent_repository = Arc::new(new_repository(torrents));
+    let shard_size = (torrents / std::thread::available_parallelism().map_or(1, NonZeroUsize::get)).max(1);
+    let shards: Vec<PersistentTorrents> = generate_unique_info_hashes(torrents)
+        .chunks(shard_size)
+        .map(|info_hashes| info_hashes.iter().map(|info_hash| (*info_hash, 1)).collect())
+        .collect();
+    let handles = FuturesUnordered::new();
+
+    let start = Instant::now();
+
+    for shard in shards {
+        let torrent_repository_clone = torrent_repository.clone();
+
+        let handle = runtime.spawn(async move {
+            torrent_repository_clone.import_persistent(&shard);
+        });
+
+        handles.push(handle);
+    }
+
+    // Await all tasks
+    futures::future::join_all(handles).await;
+
+    start.elapsed()
+}
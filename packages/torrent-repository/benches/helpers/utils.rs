@@ -2,11 +2,15 @@ use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+use torrust_tracker_configuration::{PeerEvictionPolicy, TrackerPolicy};
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer::Peer;
 use torrust_tracker_primitives::DurationSinceUnixEpoch;
 use zerocopy::I64;
 
+/// The number of peers in a batch upsert.
+pub const BATCH_SIZE: u16 = 64;
+
 pub const DEFAULT_PEER: Peer = Peer {
     peer_id: PeerId([0; 20]),
     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -15,6 +19,19 @@ pub const DEFAULT_PEER: Peer = Peer {
     downloaded: NumberOfBytes(I64::ZERO),
     left: NumberOfBytes(I64::ZERO),
     event: AnnounceEvent::Started,
+    partial_seed: false,
+    corrupt: NumberOfBytes(I64::ZERO),
+    redundant: NumberOfBytes(I64::ZERO),
+};
+
+pub const DEFAULT_POLICY: TrackerPolicy = TrackerPolicy {
+    max_peer_timeout: 900,
+    persistent_torrent_completed_stat: false,
+    remove_peerless_torrents: true,
+    max_torrent_inactivity: None,
+    max_peers_per_torrent: None,
+    peer_eviction_policy: PeerEvictionPolicy::RejectNew,
+    merge_dual_stack_peers: false,
 };
 
 #[must_use]
@@ -39,3 +56,21 @@ pub fn generate_unique_info_hashes(size: usize) -> Vec<InfoHash> {
 
     result.into_iter().collect()
 }
+
+/// Peers with unique IDs and socket addresses, to upsert them in the same
+/// torrent.
+#[must_use]
+pub fn generate_unique_peers(size: u16) -> Vec<Peer> {
+    (0..size)
+        .map(|i| {
+            let mut peer_id = [0u8; 20];
+            peer_id[..2].copy_from_slice(&i.to_be_bytes());
+
+            Peer {
+                peer_id: PeerId(peer_id),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), i),
+                ..DEFAULT_PEER
+            }
+        })
+        .collect()
+}
@@ -5,8 +5,8 @@ mod helpers;
 use criterion::{criterion_group, criterion_main, Criterion};
 use torrust_tracker_torrent_repository::{
     TorrentsDashMapMutexStd, TorrentsRwLockStd, TorrentsRwLockStdMutexStd, TorrentsRwLockStdMutexTokio, TorrentsRwLockTokio,
-    TorrentsRwLockTokioMutexStd, TorrentsRwLockTokioMutexTokio, TorrentsSkipMapMutexParkingLot, TorrentsSkipMapMutexStd,
-    TorrentsSkipMapRwLockParkingLot,
+    TorrentsRwLockTokioMutexStd, TorrentsRwLockTokioMutexTokio, TorrentsShardedSkipMapMutexStd, TorrentsSkipMapMutexParkingLot,
+    TorrentsSkipMapMutexStd, TorrentsSkipMapRwLockParkingLot,
 };
 
 use crate::helpers::{asyn, sync};
@@ -50,6 +50,10 @@ fn add_one_torrent(c: &mut Criterion) {
         b.iter_custom(sync::add_one_torrent::<TorrentsSkipMapMutexStd, _>);
     });
 
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.iter_custom(sync::add_one_torrent::<TorrentsShardedSkipMapMutexStd, _>);
+    });
+
     group.bench_function("SkipMapMutexParkingLot", |b| {
         b.iter_custom(sync::add_one_torrent::<TorrentsSkipMapMutexParkingLot, _>);
     });
@@ -65,6 +69,65 @@ fn add_one_torrent(c: &mut Criterion) {
     group.finish();
 }
 
+fn upsert_a_batch_of_peers(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().worker_threads(4).build().unwrap();
+
+    let mut group = c.benchmark_group("upsert_a_batch_of_peers");
+
+    group.warm_up_time(Duration::from_millis(500));
+    group.measurement_time(Duration::from_millis(1000));
+
+    group.bench_function("RwLockStd", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsRwLockStd, _>);
+    });
+
+    group.bench_function("RwLockStdMutexStd", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsRwLockStdMutexStd, _>);
+    });
+
+    group.bench_function("RwLockStdMutexTokio", |b| {
+        b.to_async(&rt)
+            .iter_custom(asyn::upsert_a_batch_of_peers::<TorrentsRwLockStdMutexTokio, _>);
+    });
+
+    group.bench_function("RwLockTokio", |b| {
+        b.to_async(&rt)
+            .iter_custom(asyn::upsert_a_batch_of_peers::<TorrentsRwLockTokio, _>);
+    });
+
+    group.bench_function("RwLockTokioMutexStd", |b| {
+        b.to_async(&rt)
+            .iter_custom(asyn::upsert_a_batch_of_peers::<TorrentsRwLockTokioMutexStd, _>);
+    });
+
+    group.bench_function("RwLockTokioMutexTokio", |b| {
+        b.to_async(&rt)
+            .iter_custom(asyn::upsert_a_batch_of_peers::<TorrentsRwLockTokioMutexTokio, _>);
+    });
+
+    group.bench_function("SkipMapMutexStd", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsSkipMapMutexStd, _>);
+    });
+
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsShardedSkipMapMutexStd, _>);
+    });
+
+    group.bench_function("SkipMapMutexParkingLot", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsSkipMapMutexParkingLot, _>);
+    });
+
+    group.bench_function("SkipMapRwLockParkingLot", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsSkipMapRwLockParkingLot, _>);
+    });
+
+    group.bench_function("DashMapMutexStd", |b| {
+        b.iter_custom(sync::upsert_a_batch_of_peers::<TorrentsDashMapMutexStd, _>);
+    });
+
+    group.finish();
+}
+
 fn add_multiple_torrents_in_parallel(c: &mut Criterion) {
     let rt = tokio::runtime::Builder::new_multi_thread().worker_threads(4).build().unwrap();
 
@@ -111,6 +174,11 @@ fn add_multiple_torrents_in_parallel(c: &mut Criterion) {
             .iter_custom(|iters| sync::add_multiple_torrents_in_parallel::<TorrentsSkipMapMutexStd, _>(&rt, iters, None));
     });
 
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.to_async(&rt)
+            .iter_custom(|iters| sync::add_multiple_torrents_in_parallel::<TorrentsShardedSkipMapMutexStd, _>(&rt, iters, None));
+    });
+
     group.bench_function("SkipMapMutexParkingLot", |b| {
         b.to_async(&rt)
             .iter_custom(|iters| sync::add_multiple_torrents_in_parallel::<TorrentsSkipMapMutexParkingLot, _>(&rt, iters, None));
@@ -175,6 +243,11 @@ fn update_one_torrent_in_parallel(c: &mut Criterion) {
             .iter_custom(|iters| sync::update_one_torrent_in_parallel::<TorrentsSkipMapMutexStd, _>(&rt, iters, None));
     });
 
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.to_async(&rt)
+            .iter_custom(|iters| sync::update_one_torrent_in_parallel::<TorrentsShardedSkipMapMutexStd, _>(&rt, iters, None));
+    });
+
     group.bench_function("SkipMapMutexParkingLot", |b| {
         b.to_async(&rt)
             .iter_custom(|iters| sync::update_one_torrent_in_parallel::<TorrentsSkipMapMutexParkingLot, _>(&rt, iters, None));
@@ -240,6 +313,12 @@ fn update_multiple_torrents_in_parallel(c: &mut Criterion) {
             .iter_custom(|iters| sync::update_multiple_torrents_in_parallel::<TorrentsSkipMapMutexStd, _>(&rt, iters, None));
     });
 
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.to_async(&rt).iter_custom(|iters| {
+            sync::update_multiple_torrents_in_parallel::<TorrentsShardedSkipMapMutexStd, _>(&rt, iters, None)
+        });
+    });
+
     group.bench_function("SkipMapMutexParkingLot", |b| {
         b.to_async(&rt).iter_custom(|iters| {
             sync::update_multiple_torrents_in_parallel::<TorrentsSkipMapMutexParkingLot, _>(&rt, iters, None)
@@ -260,11 +339,42 @@ fn update_multiple_torrents_in_parallel(c: &mut Criterion) {
     group.finish();
 }
 
+fn import_persistent_torrents_in_parallel(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().worker_threads(4).build().unwrap();
+
+    let mut group = c.benchmark_group("import_persistent_torrents_in_parallel");
+
+    group.warm_up_time(Duration::from_millis(500));
+    group.measurement_time(Duration::from_millis(1000));
+
+    group.bench_function("SkipMapMutexStd", |b| {
+        b.to_async(&rt).iter_custom(|iters| {
+            sync::import_persistent_torrents_in_parallel(&rt, iters, |_| TorrentsSkipMapMutexStd::default())
+        });
+    });
+
+    group.bench_function("ShardedSkipMapMutexStd", |b| {
+        b.to_async(&rt).iter_custom(|iters| {
+            sync::import_persistent_torrents_in_parallel(&rt, iters, TorrentsShardedSkipMapMutexStd::with_expected_torrents)
+        });
+    });
+
+    group.bench_function("DashMapMutexStd", |b| {
+        b.to_async(&rt).iter_custom(|iters| {
+            sync::import_persistent_torrents_in_parallel(&rt, iters, |_| TorrentsDashMapMutexStd::default())
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     add_one_torrent,
+    upsert_a_batch_of_peers,
     add_multiple_torrents_in_parallel,
     update_one_torrent_in_parallel,
-    update_multiple_torrents_in_parallel
+    update_multiple_torrents_in_parallel,
+    import_persistent_torrents_in_parallel
 );
 criterion_main!(benches);
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -33,6 +34,10 @@ pub trait Entry {
     /// Get all swarm peers, optionally limiting the result.
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
 
+    /// It returns the peer with the given peer ID, including the static
+    /// peers, without copying the peer list.
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
+
     /// It returns the list of peers for a given peer client, optionally limiting the
     /// result.
     ///
@@ -44,10 +49,37 @@ pub trait Entry {
     ///
     /// The number of peers that have complete downloading is synchronously updated when peers are updated.
     /// That's the total torrent downloads counter.
-    fn upsert_peer(&mut self, peer: &peer::Peer) -> bool;
+    ///
+    /// New peers are only added when there is room for them in the swarm,
+    /// according to the `max_peers_per_torrent` and `peer_eviction_policy`
+    /// options in the [`TrackerPolicy`].
+    fn upsert_peer(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool;
+
+    /// It updates a batch of peers, in order, and returns true if the number of
+    /// complete downloads have increased.
+    ///
+    /// The entry lock is acquired only once for the whole batch.
+    fn upsert_peers_batch(&mut self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool;
 
     /// It removes peer from the swarm that have not been updated for more than `current_cutoff` seconds
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch);
+
+    /// It returns the time of the last announce for the torrent. For torrents
+    /// imported from the database it's the time they were imported, until
+    /// the first announce.
+    fn get_last_announce_at(&self) -> DurationSinceUnixEpoch;
+
+    /// It replaces the static peers of the torrent.
+    ///
+    /// Static peers are registered by the tracker operator, for example, an
+    /// organization-run seedbox. They are kept apart from the swarm: they
+    /// never expire, they are always included in the peer lists, before the
+    /// swarm peers, and they are counted as seeders.
+    fn set_static_peers(&mut self, peers: &[peer::Peer]);
+
+    /// Returns True if the torrent has not been announced since
+    /// `current_cutoff` and it has no static peers.
+    fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> bool;
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -57,9 +89,14 @@ pub trait EntrySync {
     fn peers_is_empty(&self) -> bool;
     fn get_peers_len(&self) -> usize;
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
-    fn upsert_peer(&self, peer: &peer::Peer) -> bool;
+    fn upsert_peer(&self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool;
+    fn upsert_peers_batch(&self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool;
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch);
+    fn get_last_announce_at(&self) -> DurationSinceUnixEpoch;
+    fn set_static_peers(&self, peers: &[peer::Peer]);
+    fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> bool;
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -69,13 +106,18 @@ pub trait EntryAsync {
     fn peers_is_empty(&self) -> impl std::future::Future<Output = bool> + Send;
     fn get_peers_len(&self) -> impl std::future::Future<Output = usize> + Send;
     fn get_peers(&self, limit: Option<usize>) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
+    fn get_peer(&self, peer_id: &PeerId) -> impl std::future::Future<Output = Option<Arc<peer::Peer>>> + Send;
     fn get_peers_for_client(
         &self,
         client: &SocketAddr,
         limit: Option<usize>,
     ) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
-    fn upsert_peer(self, peer: &peer::Peer) -> impl std::future::Future<Output = bool> + Send;
+    fn upsert_peer(self, peer: &peer::Peer, policy: &TrackerPolicy) -> impl std::future::Future<Output = bool> + Send;
+    fn upsert_peers_batch(self, peers: &[peer::Peer], policy: &TrackerPolicy) -> impl std::future::Future<Output = bool> + Send;
     fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
+    fn get_last_announce_at(&self) -> impl std::future::Future<Output = DurationSinceUnixEpoch> + Send;
+    fn set_static_peers(self, peers: &[peer::Peer]) -> impl std::future::Future<Output = ()> + Send;
+    fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> impl std::future::Future<Output = bool> + Send;
 }
 
 /// A data structure containing all the information about a torrent in the tracker.
@@ -89,4 +131,8 @@ pub struct Torrent {
     pub(crate) swarm: PeerList,
     /// The number of peers that have ever completed downloading the torrent associated to this entry
     pub(crate) downloaded: u32,
+    /// The time of the last announce for the torrent associated to this entry
+    pub(crate) last_announce_at: DurationSinceUnixEpoch,
+    /// The peers registered by the tracker operator, which never expire
+    pub(crate) static_peers: Vec<Arc<peer::Peer>>,
 }
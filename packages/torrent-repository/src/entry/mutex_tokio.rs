@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -29,17 +30,37 @@ impl EntryAsync for EntryMutexTokio {
         self.lock().await.get_peers(limit)
     }
 
+    async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.lock().await.get_peer(peer_id)
+    }
+
     async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.lock().await.get_peers_for_client(client, limit)
     }
 
-    async fn upsert_peer(self, peer: &peer::Peer) -> bool {
-        self.lock().await.upsert_peer(peer)
+    async fn upsert_peer(self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool {
+        self.lock().await.upsert_peer(peer, policy)
+    }
+
+    async fn upsert_peers_batch(self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool {
+        self.lock().await.upsert_peers_batch(peers, policy)
     }
 
     async fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) {
         self.lock().await.remove_inactive_peers(current_cutoff);
     }
+
+    async fn get_last_announce_at(&self) -> DurationSinceUnixEpoch {
+        self.lock().await.get_last_announce_at()
+    }
+
+    async fn set_static_peers(self, peers: &[peer::Peer]) {
+        self.lock().await.set_static_peers(peers);
+    }
+
+    async fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> bool {
+        self.lock().await.is_stale(current_cutoff)
+    }
 }
 
 impl From<EntrySingle> for EntryMutexTokio {
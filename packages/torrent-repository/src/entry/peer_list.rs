@@ -1,8 +1,15 @@
 //! A peer list.
+//!
+//! The peers are identified by their peer Id. When the dual-stack peers are
+//! merged (see [`PeerList::upsert_dual_stack`]), a peer announcing from an IPv4
+//! and an IPv6 address is one logical peer with two addresses: it's counted
+//! once in the swarm metadata, but both addresses are returned to the other
+//! peers.
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 use aquatic_udp_protocol::PeerId;
+use rand::seq::IteratorRandom;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 // code-review: the current implementation uses the peer Id as the ``BTreeMap``
@@ -12,7 +19,11 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PeerList {
+    /// The latest announce of every peer.
     peers: std::collections::BTreeMap<PeerId, Arc<peer::Peer>>,
+    /// The latest announce of the merged dual-stack peers from the IP family
+    /// of their other address. It's only used when the peers are merged.
+    other_family: std::collections::BTreeMap<PeerId, Arc<peer::Peer>>,
 }
 
 impl PeerList {
@@ -30,11 +41,56 @@ impl PeerList {
         self.peers.insert(value.peer_id, value)
     }
 
+    /// It updates a peer merging its IPv4 and IPv6 addresses. The peer keeps
+    /// the latest announce, and the address from the other IP family, if any,
+    /// is kept as a second address of the same peer.
+    ///
+    /// It returns the previous announce of the peer from any IP family.
+    pub fn upsert_dual_stack(&mut self, value: Arc<peer::Peer>) -> Option<Arc<peer::Peer>> {
+        let previous = self.peers.insert(value.peer_id, value.clone());
+
+        if let Some(previous) = &previous {
+            if previous.peer_addr.is_ipv4() != value.peer_addr.is_ipv4() {
+                self.other_family.insert(value.peer_id, previous.clone());
+            }
+        }
+
+        previous
+    }
+
+    /// It removes the peer with all its addresses.
     pub fn remove(&mut self, key: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.other_family.remove(key);
         self.peers.remove(key)
     }
 
+    /// It removes only the address of a merged dual-stack peer in the IP
+    /// family of the given announce. The peer is kept with its other address,
+    /// if any.
+    pub fn remove_dual_stack(&mut self, value: &peer::Peer) -> Option<Arc<peer::Peer>> {
+        if self
+            .other_family
+            .get(&value.peer_id)
+            .is_some_and(|other| other.peer_addr.is_ipv4() == value.peer_addr.is_ipv4())
+        {
+            return self.other_family.remove(&value.peer_id);
+        }
+
+        let removed = self.peers.remove(&value.peer_id);
+
+        if let Some(other) = self.other_family.remove(&value.peer_id) {
+            self.peers.insert(value.peer_id, other);
+        }
+
+        removed
+    }
+
     pub fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
+        self.other_family
+            .retain(|_, peer| peer::ReadInfo::get_updated(peer) > current_cutoff);
+
+        // The other address is always older than the latest announce, so it
+        // has already been removed if the peer is inactive.
         self.peers
             .retain(|_, peer| peer::ReadInfo::get_updated(peer) > current_cutoff);
     }
@@ -44,11 +100,15 @@ impl PeerList {
         self.peers.get(peer_id)
     }
 
+    /// It returns all the addresses of the peers, including the second
+    /// address of the merged dual-stack peers.
     #[must_use]
     pub fn get_all(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
+        let peers = self.peers.values().chain(self.other_family.values());
+
         match limit {
-            Some(limit) => self.peers.values().take(limit).cloned().collect(),
-            None => self.peers.values().cloned().collect(),
+            Some(limit) => peers.take(limit).cloned().collect(),
+            None => peers.cloned().collect(),
         }
     }
 
@@ -60,23 +120,58 @@ impl PeerList {
         (seeders, leechers)
     }
 
+    /// It returns the number of leechers that are partial seeds.
+    #[must_use]
+    pub fn partial_seeds(&self) -> usize {
+        self.peers.values().filter(|peer| peer.is_partial_seed()).count()
+    }
+
+    /// It returns the Id of the peer with the oldest announce.
+    #[must_use]
+    pub fn oldest(&self) -> Option<PeerId> {
+        self.peers
+            .values()
+            .min_by_key(|peer| peer::ReadInfo::get_updated(peer.as_ref()))
+            .map(|peer| peer.peer_id)
+    }
+
+    /// It returns the Id of a leecher chosen at random.
+    #[must_use]
+    pub fn random_leecher(&self) -> Option<PeerId> {
+        self.peers
+            .values()
+            .filter(|peer| !peer.is_seeder())
+            .choose(&mut rand::thread_rng())
+            .map(|peer| peer.peer_id)
+    }
+
+    /// It returns the addresses of the peers, except the client ones. The
+    /// other address of a merged dual-stack client is excluded too.
     #[must_use]
     pub fn get_peers_excluding_addr(&self, peer_addr: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        match limit {
-            Some(limit) => self
-                .peers
+        let client_id = if self.other_family.is_empty() {
+            None
+        } else {
+            self.peers
                 .values()
+                .chain(self.other_family.values())
+                .find(|peer| peer::ReadInfo::get_address(peer.as_ref()) == *peer_addr)
+                .map(|peer| peer.peer_id)
+        };
+
+        let peers = self.peers.values().chain(self.other_family.values());
+
+        match limit {
+            Some(limit) => peers
                 // Take peers which are not the client peer
-                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr)
+                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr && Some(peer.peer_id) != client_id)
                 // Limit the number of peers on the result
                 .take(limit)
                 .cloned()
                 .collect(),
-            None => self
-                .peers
-                .values()
+            None => peers
                 // Take peers which are not the client peer
-                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr)
+                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr && Some(peer.peer_id) != client_id)
                 .cloned()
                 .collect(),
         }
@@ -87,10 +182,11 @@ impl PeerList {
 mod tests {
 
     mod it_should {
-        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
         use std::sync::Arc;
 
         use aquatic_udp_protocol::PeerId;
+        use torrust_tracker_primitives::peer;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_primitives::DurationSinceUnixEpoch;
 
@@ -238,6 +334,24 @@ mod tests {
             assert_eq!(leechers, 1);
         }
 
+        #[test]
+        fn return_the_number_of_partial_seeds_in_the_list() {
+            let mut peer_list = PeerList::default();
+
+            let seeder = PeerBuilder::seeder().as_partial_seed().build();
+            let leecher = PeerBuilder::leecher().build();
+            let partial_seed = PeerBuilder::leecher()
+                .with_peer_id(&PeerId(*b"-qB00000000000000003"))
+                .as_partial_seed()
+                .build();
+
+            peer_list.upsert(seeder.into());
+            peer_list.upsert(leecher.into());
+            peer_list.upsert(partial_seed.into());
+
+            assert_eq!(peer_list.partial_seeds(), 1);
+        }
+
         #[test]
         fn remove_inactive_peers() {
             let mut peer_list = PeerList::default();
@@ -282,5 +396,98 @@ mod tests {
 
             assert_eq!(peer_list.len(), 2);
         }
+
+        #[test]
+        fn return_the_peer_with_the_oldest_announce() {
+            let mut peer_list = PeerList::default();
+
+            let peer1 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                .last_updated_on(DurationSinceUnixEpoch::new(2, 0))
+                .build();
+            peer_list.upsert(peer1.into());
+
+            let peer2 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .last_updated_on(DurationSinceUnixEpoch::new(1, 0))
+                .build();
+            peer_list.upsert(peer2.into());
+
+            assert_eq!(peer_list.oldest(), Some(peer2.peer_id));
+        }
+
+        fn a_dual_stack_peer() -> (peer::Peer, peer::Peer) {
+            let ipv4 = PeerBuilder::seeder()
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 6969))
+                .build();
+            let ipv6 = PeerBuilder::seeder()
+                .with_peer_addr(&SocketAddr::new(
+                    IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+                    6969,
+                ))
+                .build();
+
+            (ipv4, ipv6)
+        }
+
+        #[test]
+        fn merge_the_ipv4_and_ipv6_addresses_of_a_dual_stack_peer_into_one_peer() {
+            let mut peer_list = PeerList::default();
+
+            let (ipv4, ipv6) = a_dual_stack_peer();
+
+            peer_list.upsert_dual_stack(ipv4.into());
+            peer_list.upsert_dual_stack(ipv6.into());
+
+            assert_eq!(peer_list.len(), 1);
+            assert_eq!(peer_list.seeders_and_leechers(), (1, 0));
+            assert_eq!(peer_list.get_all(None), [Arc::new(ipv6), Arc::new(ipv4)]);
+        }
+
+        #[test]
+        fn not_return_any_address_of_a_dual_stack_client_to_itself() {
+            let mut peer_list = PeerList::default();
+
+            let (ipv4, ipv6) = a_dual_stack_peer();
+            peer_list.upsert_dual_stack(ipv4.into());
+            peer_list.upsert_dual_stack(ipv6.into());
+
+            let other = PeerBuilder::leecher().build();
+            peer_list.upsert_dual_stack(other.into());
+
+            assert_eq!(peer_list.get_peers_excluding_addr(&ipv4.peer_addr, None), [Arc::new(other)]);
+        }
+
+        #[test]
+        fn keep_the_other_address_of_a_dual_stack_peer_when_one_address_is_removed() {
+            let mut peer_list = PeerList::default();
+
+            let (ipv4, ipv6) = a_dual_stack_peer();
+            peer_list.upsert_dual_stack(ipv4.into());
+            peer_list.upsert_dual_stack(ipv6.into());
+
+            peer_list.remove_dual_stack(&ipv6);
+
+            assert_eq!(peer_list.get_all(None), [Arc::new(ipv4)]);
+
+            peer_list.remove_dual_stack(&ipv4);
+
+            assert!(peer_list.is_empty());
+        }
+
+        #[test]
+        fn return_a_random_leecher_only_when_there_are_leechers() {
+            let mut peer_list = PeerList::default();
+
+            let seeder = PeerBuilder::seeder().build();
+            peer_list.upsert(seeder.into());
+
+            assert_eq!(peer_list.random_leecher(), None);
+
+            let leecher = PeerBuilder::leecher().build();
+            peer_list.upsert(leecher.into());
+
+            assert_eq!(peer_list.random_leecher(), Some(leecher.peer_id));
+        }
     }
 }
@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -29,17 +30,37 @@ impl EntrySync for EntryRwLockParkingLot {
         self.read().get_peers(limit)
     }
 
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.read().get_peer(peer_id)
+    }
+
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         self.read().get_peers_for_client(client, limit)
     }
 
-    fn upsert_peer(&self, peer: &peer::Peer) -> bool {
-        self.write().upsert_peer(peer)
+    fn upsert_peer(&self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool {
+        self.write().upsert_peer(peer, policy)
+    }
+
+    fn upsert_peers_batch(&self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool {
+        self.write().upsert_peers_batch(peers, policy)
     }
 
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) {
         self.write().remove_inactive_peers(current_cutoff);
     }
+
+    fn get_last_announce_at(&self) -> DurationSinceUnixEpoch {
+        self.read().get_last_announce_at()
+    }
+
+    fn set_static_peers(&self, peers: &[peer::Peer]) {
+        self.write().set_static_peers(peers);
+    }
+
+    fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> bool {
+        self.read().is_stale(current_cutoff)
+    }
 }
 
 impl From<EntrySingle> for EntryRwLockParkingLot {
@@ -1,8 +1,8 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use aquatic_udp_protocol::AnnounceEvent;
-use torrust_tracker_configuration::TrackerPolicy;
+use aquatic_udp_protocol::{AnnounceEvent, PeerId};
+use torrust_tracker_configuration::{PeerEvictionPolicy, TrackerPolicy};
 use torrust_tracker_primitives::peer::{self};
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::DurationSinceUnixEpoch;
@@ -17,12 +17,17 @@ impl Entry for EntrySingle {
 
         SwarmMetadata {
             downloaded: self.downloaded,
-            complete: seeders as u32,
+            complete: (seeders + self.static_peers.len()) as u32,
             incomplete: leechers as u32,
+            partial_seeds: self.swarm.partial_seeds() as u32,
         }
     }
 
     fn meets_retaining_policy(&self, policy: &TrackerPolicy) -> bool {
+        if !self.static_peers.is_empty() {
+            return true;
+        }
+
         if policy.persistent_torrent_completed_stat && self.downloaded > 0 {
             return true;
         }
@@ -43,22 +48,50 @@ impl Entry for EntrySingle {
     }
 
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        self.swarm.get_all(limit)
+        if self.static_peers.is_empty() {
+            return self.swarm.get_all(limit);
+        }
+
+        let swarm_peers = self.swarm.get_all(limit.map(|limit| limit + self.static_peers.len()));
+
+        self.with_static_peers(None, swarm_peers, limit)
+    }
+
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.swarm
+            .get(peer_id)
+            .or_else(|| self.static_peers.iter().find(|peer| peer.peer_id == *peer_id))
+            .cloned()
     }
 
     fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        self.swarm.get_peers_excluding_addr(client, limit)
+        if self.static_peers.is_empty() {
+            return self.swarm.get_peers_excluding_addr(client, limit);
+        }
+
+        let swarm_peers = self
+            .swarm
+            .get_peers_excluding_addr(client, limit.map(|limit| limit + self.static_peers.len()));
+
+        self.with_static_peers(Some(client), swarm_peers, limit)
     }
 
-    fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
+    fn upsert_peer(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool {
         let mut downloaded_stats_updated: bool = false;
 
+        self.last_announce_at = peer.updated;
+
         match peer::ReadInfo::get_event(peer) {
+            AnnounceEvent::Stopped if policy.merge_dual_stack_peers => {
+                drop(self.swarm.remove_dual_stack(peer));
+            }
             AnnounceEvent::Stopped => {
                 drop(self.swarm.remove(&peer::ReadInfo::get_id(peer)));
             }
+            // The swarm is full and the new peer is rejected.
+            _ if !self.make_room_for(peer, policy) => {}
             AnnounceEvent::Completed => {
-                let previous = self.swarm.upsert(Arc::new(*peer));
+                let previous = self.upsert(peer, policy);
                 // Don't count if peer was not previously known and not already completed.
                 if previous.is_some_and(|p| p.event != AnnounceEvent::Completed) {
                     self.downloaded += 1;
@@ -66,14 +99,99 @@ impl Entry for EntrySingle {
                 }
             }
             _ => {
-                drop(self.swarm.upsert(Arc::new(*peer)));
+                drop(self.upsert(peer, policy));
             }
         }
 
         downloaded_stats_updated
     }
 
+    fn upsert_peers_batch(&mut self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool {
+        let mut downloaded_stats_updated = false;
+
+        for peer in peers {
+            downloaded_stats_updated |= self.upsert_peer(peer, policy);
+        }
+
+        downloaded_stats_updated
+    }
+
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
         self.swarm.remove_inactive_peers(current_cutoff);
     }
+
+    fn get_last_announce_at(&self) -> DurationSinceUnixEpoch {
+        self.last_announce_at
+    }
+
+    fn set_static_peers(&mut self, peers: &[peer::Peer]) {
+        self.static_peers = peers.iter().map(|peer| Arc::new(*peer)).collect();
+    }
+
+    fn is_stale(&self, current_cutoff: DurationSinceUnixEpoch) -> bool {
+        self.static_peers.is_empty() && self.last_announce_at < current_cutoff
+    }
+}
+
+impl EntrySingle {
+    /// It puts the static peers before the swarm peers, skipping the client
+    /// and the swarm peers with the address of a static peer.
+    fn with_static_peers(
+        &self,
+        client: Option<&SocketAddr>,
+        swarm_peers: Vec<Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>> {
+        let is_static = |peer: &Arc<peer::Peer>| {
+            self.static_peers
+                .iter()
+                .any(|static_peer| static_peer.peer_addr == peer.peer_addr)
+        };
+
+        self.static_peers
+            .iter()
+            .filter(|peer| Some(&peer.peer_addr) != client)
+            .cloned()
+            .chain(swarm_peers.into_iter().filter(|peer| !is_static(peer)))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// It updates the peer in the swarm, merging its IPv4 and IPv6 addresses
+    /// when the policy is to merge the dual-stack peers.
+    fn upsert(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> Option<Arc<peer::Peer>> {
+        if policy.merge_dual_stack_peers {
+            self.swarm.upsert_dual_stack(Arc::new(*peer))
+        } else {
+            self.swarm.upsert(Arc::new(*peer))
+        }
+    }
+
+    /// It checks if the peer fits in the swarm, evicting another peer
+    /// according to the [`PeerEvictionPolicy`] when the swarm is full.
+    ///
+    /// It returns false if the peer has to be rejected.
+    fn make_room_for(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool {
+        let Some(max_peers) = policy.max_peers_per_torrent else {
+            return true;
+        };
+
+        if self.swarm.get(&peer.peer_id).is_some() || self.swarm.len() < max_peers as usize {
+            return true;
+        }
+
+        let evicted = match policy.peer_eviction_policy {
+            PeerEvictionPolicy::RejectNew => None,
+            PeerEvictionPolicy::EvictOldest => self.swarm.oldest(),
+            PeerEvictionPolicy::EvictRandomLeecher => self.swarm.random_leecher(),
+        };
+
+        match evicted {
+            Some(peer_id) => {
+                drop(self.swarm.remove(&peer_id));
+                true
+            }
+            None => false,
+        }
+    }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use repository::dash_map_mutex_std::XacrimonDashMap;
 use repository::rw_lock_std::RwLockStd;
 use repository::rw_lock_tokio::RwLockTokio;
+use repository::sharded_skip_map_mutex_std::ShardedCrossbeamSkipList;
 use repository::skip_map_mutex_std::CrossbeamSkipList;
 use torrust_tracker_clock::clock;
 
@@ -30,6 +31,8 @@ pub type TorrentsSkipMapMutexStd = CrossbeamSkipList<EntryMutexStd>;
 pub type TorrentsSkipMapMutexParkingLot = CrossbeamSkipList<EntryMutexParkingLot>;
 pub type TorrentsSkipMapRwLockParkingLot = CrossbeamSkipList<EntryRwLockParkingLot>;
 
+pub type TorrentsShardedSkipMapMutexStd = ShardedCrossbeamSkipList<EntryMutexStd>;
+
 pub type TorrentsDashMapMutexStd = XacrimonDashMap<EntryMutexStd>;
 
 /// This code needs to be copied into each crate.
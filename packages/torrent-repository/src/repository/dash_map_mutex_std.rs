@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use dashmap::DashMap;
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -11,7 +12,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::Repository;
 use crate::entry::peer_list::PeerList;
 use crate::entry::{Entry, EntrySync};
-use crate::{EntryMutexStd, EntrySingle};
+use crate::{CurrentClock, EntryMutexStd, EntrySingle};
 
 #[derive(Default, Debug)]
 pub struct XacrimonDashMap<T> {
@@ -23,13 +24,35 @@ where
     EntryMutexStd: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         if let Some(entry) = self.torrents.get(info_hash) {
-            entry.upsert_peer(peer);
+            entry.upsert_peer(peer, policy);
         } else {
             let _unused = self.torrents.insert(*info_hash, Arc::default());
             if let Some(entry) = self.torrents.get(info_hash) {
-                entry.upsert_peer(peer);
+                entry.upsert_peer(peer, policy);
+            }
+        }
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        if let Some(entry) = self.torrents.get(info_hash) {
+            entry.upsert_peers_batch(peers, policy);
+        } else {
+            let _unused = self.torrents.insert(*info_hash, Arc::default());
+            if let Some(entry) = self.torrents.get(info_hash) {
+                entry.upsert_peers_batch(peers, policy);
+            }
+        }
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        if let Some(entry) = self.torrents.get(info_hash) {
+            entry.set_static_peers(peers);
+        } else {
+            let _unused = self.torrents.insert(*info_hash, Arc::default());
+            if let Some(entry) = self.torrents.get(info_hash) {
+                entry.set_static_peers(peers);
             }
         }
     }
@@ -84,6 +107,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -102,7 +127,35 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
-        self.torrents.retain(|_, entry| entry.meets_retaining_policy(policy));
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        let mut removed = Vec::new();
+
+        self.torrents.retain(|info_hash, entry| {
+            let retain = entry.meets_retaining_policy(policy);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
+    }
+
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        let mut removed = Vec::new();
+
+        self.torrents.retain(|info_hash, entry| {
+            let retain = !entry.is_stale(current_cutoff);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
     }
 }
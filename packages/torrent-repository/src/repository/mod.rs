@@ -12,6 +12,7 @@ pub mod rw_lock_std_mutex_tokio;
 pub mod rw_lock_tokio;
 pub mod rw_lock_tokio_mutex_std;
 pub mod rw_lock_tokio_mutex_tokio;
+pub mod sharded_skip_map_mutex_std;
 pub mod skip_map_mutex_std;
 
 use std::fmt::Debug;
@@ -23,8 +24,11 @@ pub trait Repository<T>: Debug + Default + Sized + 'static {
     fn import_persistent(&self, persistent_torrents: &PersistentTorrents);
     fn remove(&self, key: &InfoHash) -> Option<T>;
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch);
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy);
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer);
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash>;
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash>;
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy);
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy);
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]);
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata>;
 }
 
@@ -36,7 +40,23 @@ pub trait RepositoryAsync<T>: Debug + Default + Sized + 'static {
     fn import_persistent(&self, persistent_torrents: &PersistentTorrents) -> impl std::future::Future<Output = ()> + Send;
     fn remove(&self, key: &InfoHash) -> impl std::future::Future<Output = Option<T>> + Send;
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> impl std::future::Future<Output = ()> + Send;
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) -> impl std::future::Future<Output = ()> + Send;
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> impl std::future::Future<Output = Vec<InfoHash>> + Send;
+    fn remove_stale_torrents(
+        &self,
+        current_cutoff: DurationSinceUnixEpoch,
+    ) -> impl std::future::Future<Output = Vec<InfoHash>> + Send;
+    fn upsert_peer(
+        &self,
+        info_hash: &InfoHash,
+        peer: &peer::Peer,
+        policy: &TrackerPolicy,
+    ) -> impl std::future::Future<Output = ()> + Send;
+    fn upsert_peers_batch(
+        &self,
+        info_hash: &InfoHash,
+        peers: &[peer::Peer],
+        policy: &TrackerPolicy,
+    ) -> impl std::future::Future<Output = ()> + Send;
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) -> impl std::future::Future<Output = ()> + Send;
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> impl std::future::Future<Output = Option<SwarmMetadata>> + Send;
 }
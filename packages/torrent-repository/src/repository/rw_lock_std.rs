@@ -1,3 +1,4 @@
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -8,7 +9,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::Repository;
 use crate::entry::peer_list::PeerList;
 use crate::entry::Entry;
-use crate::{EntrySingle, TorrentsRwLockStd};
+use crate::{CurrentClock, EntrySingle, TorrentsRwLockStd};
 
 #[derive(Default, Debug)]
 pub struct RwLockStd<T> {
@@ -46,12 +47,28 @@ impl Repository<EntrySingle> for TorrentsRwLockStd
 where
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let mut db = self.get_torrents_mut();
 
         let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
 
-        entry.upsert_peer(peer);
+        entry.upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let mut db = self.get_torrents_mut();
+
+        let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
+
+        entry.upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let mut db = self.get_torrents_mut();
+
+        let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
+
+        entry.set_static_peers(peers);
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -103,6 +120,8 @@ where
             let entry = EntrySingle {
                 swarm: PeerList::default(),
                 downloaded: *downloaded,
+                last_announce_at: CurrentClock::now(),
+                static_peers: Vec::new(),
             };
 
             torrents.insert(*info_hash, entry);
@@ -123,9 +142,39 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        let mut db = self.get_torrents_mut();
+
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = e.meets_retaining_policy(policy);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
+    }
+
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
         let mut db = self.get_torrents_mut();
 
-        db.retain(|_, e| e.meets_retaining_policy(policy));
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = !e.is_stale(current_cutoff);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
     }
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -10,7 +11,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::Repository;
 use crate::entry::peer_list::PeerList;
 use crate::entry::{Entry, EntrySync};
-use crate::{EntryMutexStd, EntrySingle, TorrentsRwLockStdMutexStd};
+use crate::{CurrentClock, EntryMutexStd, EntrySingle, TorrentsRwLockStdMutexStd};
 
 impl TorrentsRwLockStdMutexStd {
     fn get_torrents<'a>(&'a self) -> std::sync::RwLockReadGuard<'a, std::collections::BTreeMap<InfoHash, EntryMutexStd>>
@@ -33,7 +34,7 @@ where
     EntryMutexStd: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let maybe_entry = self.get_torrents().get(info_hash).cloned();
 
         let entry = if let Some(entry) = maybe_entry {
@@ -44,7 +45,35 @@ where
             entry.clone()
         };
 
-        entry.upsert_peer(peer);
+        entry.upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let maybe_entry = self.get_torrents().get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut();
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let maybe_entry = self.get_torrents().get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut();
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.set_static_peers(peers);
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -99,6 +128,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -121,9 +152,39 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
         let mut db = self.get_torrents_mut();
 
-        db.retain(|_, e| e.lock().expect("it should lock entry").meets_retaining_policy(policy));
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = e.lock().expect("it should lock entry").meets_retaining_policy(policy);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
+    }
+
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        let mut db = self.get_torrents_mut();
+
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = !e.lock().expect("it should lock entry").is_stale(current_cutoff);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
     }
 }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use futures::future::join_all;
 use futures::{Future, FutureExt};
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -14,7 +15,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::RepositoryAsync;
 use crate::entry::peer_list::PeerList;
 use crate::entry::{Entry, EntryAsync};
-use crate::{EntryMutexTokio, EntrySingle, TorrentsRwLockStdMutexTokio};
+use crate::{CurrentClock, EntryMutexTokio, EntrySingle, TorrentsRwLockStdMutexTokio};
 
 impl TorrentsRwLockStdMutexTokio {
     fn get_torrents<'a>(&'a self) -> std::sync::RwLockReadGuard<'a, std::collections::BTreeMap<InfoHash, EntryMutexTokio>>
@@ -37,7 +38,7 @@ where
     EntryMutexTokio: EntryAsync,
     EntrySingle: Entry,
 {
-    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let maybe_entry = self.get_torrents().get(info_hash).cloned();
 
         let entry = if let Some(entry) = maybe_entry {
@@ -48,7 +49,35 @@ where
             entry.clone()
         };
 
-        entry.upsert_peer(peer).await;
+        entry.upsert_peer(peer, policy).await;
+    }
+
+    async fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let maybe_entry = self.get_torrents().get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut();
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.upsert_peers_batch(peers, policy).await;
+    }
+
+    async fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let maybe_entry = self.get_torrents().get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut();
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.set_static_peers(peers).await;
     }
 
     async fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -108,6 +137,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -134,7 +165,7 @@ where
         join_all(handles).await;
     }
 
-    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
         let handles: Vec<Pin<Box<dyn Future<Output = Option<InfoHash>> + Send>>>;
 
         {
@@ -154,8 +185,32 @@ where
 
         let mut db = self.get_torrents_mut();
 
-        for remove in not_good.into_iter().flatten() {
-            drop(db.remove(&remove));
+        not_good
+            .into_iter()
+            .flatten()
+            .filter(|info_hash| db.remove(info_hash).is_some())
+            .collect()
+    }
+
+    async fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        let handles: Vec<Pin<Box<dyn Future<Output = Option<InfoHash>> + Send>>>;
+
+        {
+            let db = self.get_torrents();
+
+            handles = zip(db.keys().copied(), db.values().cloned())
+                .map(|(infohash, torrent)| async move { torrent.is_stale(current_cutoff).await.then_some(infohash) }.boxed())
+                .collect::<Vec<_>>();
         }
+
+        let stale = join_all(handles).await;
+
+        let mut db = self.get_torrents_mut();
+
+        stale
+            .into_iter()
+            .flatten()
+            .filter(|info_hash| db.remove(info_hash).is_some())
+            .collect()
     }
 }
@@ -1,3 +1,4 @@
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -8,7 +9,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::RepositoryAsync;
 use crate::entry::peer_list::PeerList;
 use crate::entry::Entry;
-use crate::{EntrySingle, TorrentsRwLockTokio};
+use crate::{CurrentClock, EntrySingle, TorrentsRwLockTokio};
 
 #[derive(Default, Debug)]
 pub struct RwLockTokio<T> {
@@ -50,12 +51,28 @@ impl RepositoryAsync<EntrySingle> for TorrentsRwLockTokio
 where
     EntrySingle: Entry,
 {
-    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let mut db = self.get_torrents_mut().await;
 
         let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
 
-        entry.upsert_peer(peer);
+        entry.upsert_peer(peer, policy);
+    }
+
+    async fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let mut db = self.get_torrents_mut().await;
+
+        let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
+
+        entry.upsert_peers_batch(peers, policy);
+    }
+
+    async fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let mut db = self.get_torrents_mut().await;
+
+        let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
+
+        entry.set_static_peers(peers);
     }
 
     async fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -107,6 +124,8 @@ where
             let entry = EntrySingle {
                 swarm: PeerList::default(),
                 downloaded: *completed,
+                last_announce_at: CurrentClock::now(),
+                static_peers: Vec::new(),
             };
 
             torrents.insert(*info_hash, entry);
@@ -127,9 +146,39 @@ where
         }
     }
 
-    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        let mut db = self.get_torrents_mut().await;
+
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = e.meets_retaining_policy(policy);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
+    }
+
+    async fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
         let mut db = self.get_torrents_mut().await;
 
-        db.retain(|_, e| e.meets_retaining_policy(policy));
+        let mut removed = Vec::new();
+
+        db.retain(|info_hash, e| {
+            let retain = !e.is_stale(current_cutoff);
+
+            if !retain {
+                removed.push(*info_hash);
+            }
+
+            retain
+        });
+
+        removed
     }
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -10,7 +11,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::RepositoryAsync;
 use crate::entry::peer_list::PeerList;
 use crate::entry::{Entry, EntryAsync};
-use crate::{EntryMutexTokio, EntrySingle, TorrentsRwLockTokioMutexTokio};
+use crate::{CurrentClock, EntryMutexTokio, EntrySingle, TorrentsRwLockTokioMutexTokio};
 
 impl TorrentsRwLockTokioMutexTokio {
     async fn get_torrents<'a>(&'a self) -> tokio::sync::RwLockReadGuard<'a, std::collections::BTreeMap<InfoHash, EntryMutexTokio>>
@@ -35,7 +36,7 @@ where
     EntryMutexTokio: EntryAsync,
     EntrySingle: Entry,
 {
-    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let maybe_entry = self.get_torrents().await.get(info_hash).cloned();
 
         let entry = if let Some(entry) = maybe_entry {
@@ -46,7 +47,35 @@ where
             entry.clone()
         };
 
-        entry.upsert_peer(peer).await;
+        entry.upsert_peer(peer, policy).await;
+    }
+
+    async fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let maybe_entry = self.get_torrents().await.get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut().await;
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.upsert_peers_batch(peers, policy).await;
+    }
+
+    async fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let maybe_entry = self.get_torrents().await.get(info_hash).cloned();
+
+        let entry = if let Some(entry) = maybe_entry {
+            entry
+        } else {
+            let mut db = self.get_torrents_mut().await;
+            let entry = db.entry(*info_hash).or_insert(Arc::default());
+            entry.clone()
+        };
+
+        entry.set_static_peers(peers).await;
     }
 
     async fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -102,6 +131,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -124,7 +155,7 @@ where
         }
     }
 
-    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
         let mut db = self.get_torrents_mut().await;
 
         let mut not_good = Vec::<InfoHash>::default();
@@ -135,8 +166,23 @@ where
             }
         }
 
-        for remove in not_good {
-            drop(db.remove(&remove));
+        not_good
+            .into_iter()
+            .filter(|info_hash| db.remove(info_hash).is_some())
+            .collect()
+    }
+
+    async fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        let mut db = self.get_torrents_mut().await;
+
+        let mut stale = Vec::<InfoHash>::default();
+
+        for (&infohash, torrent) in db.iter() {
+            if torrent.is_stale(current_cutoff).await {
+                stale.push(infohash);
+            }
         }
+
+        stale.into_iter().filter(|info_hash| db.remove(info_hash).is_some()).collect()
     }
 }
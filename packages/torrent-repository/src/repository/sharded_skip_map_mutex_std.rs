@@ -0,0 +1,201 @@
+//! A torrents repository split into shards of [`SkipMap`].
+//!
+//! The torrents are assigned to the shards by the leading byte of their
+//! infohash, so every shard holds a consecutive range of infohashes and
+//! iterating the shards in order returns the torrents ordered by infohash,
+//! like the single [`SkipMap`] repository.
+//!
+//! The number of shards is chosen when the repository is created, from the
+//! number of torrents it's expected to hold, for example the number of
+//! persisted torrents imported at startup. The shards are only initialized
+//! when the first torrent is inserted into them.
+use std::sync::{Arc, OnceLock};
+
+use crossbeam_skiplist::map::Entry as SkipMapEntry;
+use crossbeam_skiplist::SkipMap;
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::TrackerPolicy;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrents};
+
+use super::skip_map_mutex_std::remove_entries;
+use super::Repository;
+use crate::entry::peer_list::PeerList;
+use crate::entry::{Entry, EntrySync};
+use crate::{CurrentClock, EntryMutexStd, EntrySingle};
+
+/// Number of expected torrents for each shard.
+pub const TORRENTS_PER_SHARD: usize = 65_536;
+
+/// Maximum number of shards. The shards are selected by the leading byte of
+/// the infohash.
+pub const MAX_SHARDS: usize = 256;
+
+#[derive(Debug)]
+pub struct ShardedCrossbeamSkipList<T> {
+    shards: Box<[OnceLock<SkipMap<InfoHash, T>>]>,
+}
+
+impl<T> Default for ShardedCrossbeamSkipList<T> {
+    fn default() -> Self {
+        Self::with_expected_torrents(0)
+    }
+}
+
+impl<T> ShardedCrossbeamSkipList<T> {
+    /// It creates a repository with one shard for every [`TORRENTS_PER_SHARD`]
+    /// expected torrents, rounded up to a power of two, up to [`MAX_SHARDS`].
+    /// None of the shards is initialized.
+    #[must_use]
+    pub fn with_expected_torrents(torrents: usize) -> Self {
+        let shards = (torrents.saturating_add(TORRENTS_PER_SHARD - 1) / TORRENTS_PER_SHARD)
+            .next_power_of_two()
+            .min(MAX_SHARDS);
+
+        Self {
+            shards: (0..shards).map(|_| OnceLock::new()).collect(),
+        }
+    }
+
+    /// The number of shards.
+    #[must_use]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The number of shards that have been initialized.
+    #[must_use]
+    pub fn initialized_shards(&self) -> usize {
+        self.shards.iter().filter(|shard| shard.get().is_some()).count()
+    }
+
+    /// It inserts a torrent, replacing the previous one with the same
+    /// infohash.
+    pub fn insert(&self, info_hash: InfoHash, torrent: T)
+    where
+        T: Send + 'static,
+    {
+        self.shard_or_init(&info_hash).insert(info_hash, torrent);
+    }
+
+    fn shard_index(&self, info_hash: &InfoHash) -> usize {
+        usize::from(info_hash.bytes()[0]) * self.shards.len() / MAX_SHARDS
+    }
+
+    fn shard(&self, info_hash: &InfoHash) -> Option<&SkipMap<InfoHash, T>> {
+        self.shards[self.shard_index(info_hash)].get()
+    }
+
+    fn shard_or_init(&self, info_hash: &InfoHash) -> &SkipMap<InfoHash, T> {
+        self.shards[self.shard_index(info_hash)].get_or_init(SkipMap::new)
+    }
+
+    /// It iterates the torrents of all the initialized shards, ordered by
+    /// infohash.
+    fn iter(&self) -> impl Iterator<Item = SkipMapEntry<'_, InfoHash, T>> {
+        self.shards.iter().filter_map(OnceLock::get).flat_map(SkipMap::iter)
+    }
+}
+
+impl Repository<EntryMutexStd> for ShardedCrossbeamSkipList<EntryMutexStd>
+where
+    EntryMutexStd: EntrySync,
+    EntrySingle: Entry,
+{
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
+        let entry = self.shard_or_init(info_hash).get_or_insert(*info_hash, Arc::default());
+        entry.value().upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let entry = self.shard_or_init(info_hash).get_or_insert(*info_hash, Arc::default());
+        entry.value().upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let entry = self.shard_or_init(info_hash).get_or_insert(*info_hash, Arc::default());
+        entry.value().set_static_peers(peers);
+    }
+
+    fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
+        self.shard(info_hash)?
+            .get(info_hash)
+            .map(|entry| entry.value().get_swarm_metadata())
+    }
+
+    fn get(&self, key: &InfoHash) -> Option<EntryMutexStd> {
+        let maybe_entry = self.shard(key)?.get(key);
+        maybe_entry.map(|entry| entry.value().clone())
+    }
+
+    fn get_metrics(&self) -> TorrentsMetrics {
+        let mut metrics = TorrentsMetrics::default();
+
+        for entry in self.iter() {
+            let stats = entry.value().lock().expect("it should get a lock").get_swarm_metadata();
+            metrics.complete += u64::from(stats.complete);
+            metrics.downloaded += u64::from(stats.downloaded);
+            metrics.incomplete += u64::from(stats.incomplete);
+            metrics.torrents += 1;
+        }
+
+        metrics
+    }
+
+    fn get_paginated(&self, pagination: Option<&Pagination>) -> Vec<(InfoHash, EntryMutexStd)> {
+        match pagination {
+            Some(pagination) => self
+                .iter()
+                .skip(pagination.offset as usize)
+                .take(pagination.limit as usize)
+                .map(|entry| (*entry.key(), entry.value().clone()))
+                .collect(),
+            None => self.iter().map(|entry| (*entry.key(), entry.value().clone())).collect(),
+        }
+    }
+
+    fn import_persistent(&self, persistent_torrents: &PersistentTorrents) {
+        for (info_hash, completed) in persistent_torrents {
+            let shard = self.shard_or_init(info_hash);
+
+            if shard.contains_key(info_hash) {
+                continue;
+            }
+
+            let entry = EntryMutexStd::new(
+                EntrySingle {
+                    swarm: PeerList::default(),
+                    downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
+                }
+                .into(),
+            );
+
+            // Since SkipMap is lock-free the torrent could have been inserted
+            // after checking if it exists.
+            shard.get_or_insert(*info_hash, entry);
+        }
+    }
+
+    fn remove(&self, key: &InfoHash) -> Option<EntryMutexStd> {
+        self.shard(key)?.remove(key).map(|entry| entry.value().clone())
+    }
+
+    fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) {
+        for entry in self.iter() {
+            entry.value().remove_inactive_peers(current_cutoff);
+        }
+    }
+
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        remove_entries(self.iter(), |torrent| !torrent.meets_retaining_policy(policy))
+    }
+
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        remove_entries(self.iter(), |torrent| torrent.is_stale(current_cutoff))
+    }
+}
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
+use crossbeam_skiplist::map::Entry as SkipMapEntry;
 use crossbeam_skiplist::SkipMap;
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
@@ -11,21 +13,44 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use super::Repository;
 use crate::entry::peer_list::PeerList;
 use crate::entry::{Entry, EntrySync};
-use crate::{EntryMutexParkingLot, EntryMutexStd, EntryRwLockParkingLot, EntrySingle};
+use crate::{CurrentClock, EntryMutexParkingLot, EntryMutexStd, EntryRwLockParkingLot, EntrySingle};
 
 #[derive(Default, Debug)]
 pub struct CrossbeamSkipList<T> {
     pub torrents: SkipMap<InfoHash, T>,
 }
 
+/// It removes the entries matching the predicate and returns their infohashes.
+pub(super) fn remove_entries<'a, T: Send + 'static>(
+    entries: impl Iterator<Item = SkipMapEntry<'a, InfoHash, T>>,
+    predicate: impl Fn(&T) -> bool,
+) -> Vec<InfoHash> {
+    entries
+        .filter(|entry| predicate(entry.value()))
+        // It's `false` if the entry was removed by another thread first.
+        .filter(SkipMapEntry::remove)
+        .map(|entry| *entry.key())
+        .collect()
+}
+
 impl Repository<EntryMutexStd> for CrossbeamSkipList<EntryMutexStd>
 where
     EntryMutexStd: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().set_static_peers(peers);
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -78,6 +103,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -98,14 +125,12 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
-        for entry in &self.torrents {
-            if entry.value().meets_retaining_policy(policy) {
-                continue;
-            }
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| !torrent.meets_retaining_policy(policy))
+    }
 
-            entry.remove();
-        }
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| torrent.is_stale(current_cutoff))
     }
 }
 
@@ -114,9 +139,19 @@ where
     EntryRwLockParkingLot: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().set_static_peers(peers);
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -169,6 +204,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -189,14 +226,12 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
-        for entry in &self.torrents {
-            if entry.value().meets_retaining_policy(policy) {
-                continue;
-            }
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| !torrent.meets_retaining_policy(policy))
+    }
 
-            entry.remove();
-        }
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| torrent.is_stale(current_cutoff))
     }
 }
 
@@ -205,9 +240,19 @@ where
     EntryMutexParkingLot: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().upsert_peer(peer, policy);
+    }
+
+    fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peers_batch(peers, policy);
+    }
+
+    fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
+        entry.value().set_static_peers(peers);
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -260,6 +305,8 @@ where
                 EntrySingle {
                     swarm: PeerList::default(),
                     downloaded: *completed,
+                    last_announce_at: CurrentClock::now(),
+                    static_peers: Vec::new(),
                 }
                 .into(),
             );
@@ -280,13 +327,11 @@ where
         }
     }
 
-    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
-        for entry in &self.torrents {
-            if entry.value().meets_retaining_policy(policy) {
-                continue;
-            }
+    fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| !torrent.meets_retaining_policy(policy))
+    }
 
-            entry.remove();
-        }
+    fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        remove_entries(self.torrents.iter(), |torrent| torrent.is_stale(current_cutoff))
     }
 }
@@ -7,8 +7,8 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 use torrust_tracker_torrent_repository::repository::{Repository as _, RepositoryAsync as _};
 use torrust_tracker_torrent_repository::{
     EntrySingle, TorrentsDashMapMutexStd, TorrentsRwLockStd, TorrentsRwLockStdMutexStd, TorrentsRwLockStdMutexTokio,
-    TorrentsRwLockTokio, TorrentsRwLockTokioMutexStd, TorrentsRwLockTokioMutexTokio, TorrentsSkipMapMutexParkingLot,
-    TorrentsSkipMapMutexStd, TorrentsSkipMapRwLockParkingLot,
+    TorrentsRwLockTokio, TorrentsRwLockTokioMutexStd, TorrentsRwLockTokioMutexTokio, TorrentsShardedSkipMapMutexStd,
+    TorrentsSkipMapMutexParkingLot, TorrentsSkipMapMutexStd, TorrentsSkipMapRwLockParkingLot,
 };
 
 #[derive(Debug)]
@@ -20,24 +20,58 @@ pub(crate) enum Repo {
     RwLockTokioMutexStd(TorrentsRwLockTokioMutexStd),
     RwLockTokioMutexTokio(TorrentsRwLockTokioMutexTokio),
     SkipMapMutexStd(TorrentsSkipMapMutexStd),
+    ShardedSkipMapMutexStd(TorrentsShardedSkipMapMutexStd),
     SkipMapMutexParkingLot(TorrentsSkipMapMutexParkingLot),
     SkipMapRwLockParkingLot(TorrentsSkipMapRwLockParkingLot),
     DashMapMutexStd(TorrentsDashMapMutexStd),
 }
 
 impl Repo {
-    pub(crate) async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    pub(crate) async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) {
         match self {
-            Repo::RwLockStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::RwLockStdMutexStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::RwLockStdMutexTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokioMutexStd(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokioMutexTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::SkipMapMutexStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::SkipMapMutexParkingLot(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::SkipMapRwLockParkingLot(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::DashMapMutexStd(repo) => repo.upsert_peer(info_hash, peer),
+            Repo::RwLockStd(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::RwLockStdMutexStd(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::RwLockStdMutexTokio(repo) => repo.upsert_peer(info_hash, peer, policy).await,
+            Repo::RwLockTokio(repo) => repo.upsert_peer(info_hash, peer, policy).await,
+            Repo::RwLockTokioMutexStd(repo) => repo.upsert_peer(info_hash, peer, policy).await,
+            Repo::RwLockTokioMutexTokio(repo) => repo.upsert_peer(info_hash, peer, policy).await,
+            Repo::SkipMapMutexStd(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::SkipMapMutexParkingLot(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::SkipMapRwLockParkingLot(repo) => repo.upsert_peer(info_hash, peer, policy),
+            Repo::DashMapMutexStd(repo) => repo.upsert_peer(info_hash, peer, policy),
+        }
+    }
+
+    pub(crate) async fn upsert_peers_batch(&self, info_hash: &InfoHash, peers: &[peer::Peer], policy: &TrackerPolicy) {
+        match self {
+            Repo::RwLockStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::RwLockStdMutexStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::RwLockStdMutexTokio(repo) => repo.upsert_peers_batch(info_hash, peers, policy).await,
+            Repo::RwLockTokio(repo) => repo.upsert_peers_batch(info_hash, peers, policy).await,
+            Repo::RwLockTokioMutexStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy).await,
+            Repo::RwLockTokioMutexTokio(repo) => repo.upsert_peers_batch(info_hash, peers, policy).await,
+            Repo::SkipMapMutexStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::SkipMapMutexParkingLot(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::SkipMapRwLockParkingLot(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+            Repo::DashMapMutexStd(repo) => repo.upsert_peers_batch(info_hash, peers, policy),
+        }
+    }
+
+    pub(crate) async fn set_static_peers(&self, info_hash: &InfoHash, peers: &[peer::Peer]) {
+        match self {
+            Repo::RwLockStd(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::RwLockStdMutexStd(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::RwLockStdMutexTokio(repo) => repo.set_static_peers(info_hash, peers).await,
+            Repo::RwLockTokio(repo) => repo.set_static_peers(info_hash, peers).await,
+            Repo::RwLockTokioMutexStd(repo) => repo.set_static_peers(info_hash, peers).await,
+            Repo::RwLockTokioMutexTokio(repo) => repo.set_static_peers(info_hash, peers).await,
+            Repo::SkipMapMutexStd(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::SkipMapMutexParkingLot(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::SkipMapRwLockParkingLot(repo) => repo.set_static_peers(info_hash, peers),
+            Repo::DashMapMutexStd(repo) => repo.set_static_peers(info_hash, peers),
         }
     }
 
@@ -50,6 +84,7 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => repo.get_swarm_metadata(info_hash).await,
             Repo::RwLockTokioMutexTokio(repo) => repo.get_swarm_metadata(info_hash).await,
             Repo::SkipMapMutexStd(repo) => repo.get_swarm_metadata(info_hash),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.get_swarm_metadata(info_hash),
             Repo::SkipMapMutexParkingLot(repo) => repo.get_swarm_metadata(info_hash),
             Repo::SkipMapRwLockParkingLot(repo) => repo.get_swarm_metadata(info_hash),
             Repo::DashMapMutexStd(repo) => repo.get_swarm_metadata(info_hash),
@@ -65,6 +100,7 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => Some(repo.get(key).await?.lock().unwrap().clone()),
             Repo::RwLockTokioMutexTokio(repo) => Some(repo.get(key).await?.lock().await.clone()),
             Repo::SkipMapMutexStd(repo) => Some(repo.get(key)?.lock().unwrap().clone()),
+            Repo::ShardedSkipMapMutexStd(repo) => Some(repo.get(key)?.lock().unwrap().clone()),
             Repo::SkipMapMutexParkingLot(repo) => Some(repo.get(key)?.lock().clone()),
             Repo::SkipMapRwLockParkingLot(repo) => Some(repo.get(key)?.read().clone()),
             Repo::DashMapMutexStd(repo) => Some(repo.get(key)?.lock().unwrap().clone()),
@@ -80,6 +116,7 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => repo.get_metrics().await,
             Repo::RwLockTokioMutexTokio(repo) => repo.get_metrics().await,
             Repo::SkipMapMutexStd(repo) => repo.get_metrics(),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.get_metrics(),
             Repo::SkipMapMutexParkingLot(repo) => repo.get_metrics(),
             Repo::SkipMapRwLockParkingLot(repo) => repo.get_metrics(),
             Repo::DashMapMutexStd(repo) => repo.get_metrics(),
@@ -122,6 +159,11 @@ impl Repo {
                 .iter()
                 .map(|(i, t)| (*i, t.lock().expect("it should get a lock").clone()))
                 .collect(),
+            Repo::ShardedSkipMapMutexStd(repo) => repo
+                .get_paginated(pagination)
+                .iter()
+                .map(|(i, t)| (*i, t.lock().expect("it should get a lock").clone()))
+                .collect(),
             Repo::SkipMapMutexParkingLot(repo) => repo
                 .get_paginated(pagination)
                 .iter()
@@ -149,6 +191,7 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => repo.import_persistent(persistent_torrents).await,
             Repo::RwLockTokioMutexTokio(repo) => repo.import_persistent(persistent_torrents).await,
             Repo::SkipMapMutexStd(repo) => repo.import_persistent(persistent_torrents),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.import_persistent(persistent_torrents),
             Repo::SkipMapMutexParkingLot(repo) => repo.import_persistent(persistent_torrents),
             Repo::SkipMapRwLockParkingLot(repo) => repo.import_persistent(persistent_torrents),
             Repo::DashMapMutexStd(repo) => repo.import_persistent(persistent_torrents),
@@ -164,6 +207,7 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => Some(repo.remove(key).await?.lock().unwrap().clone()),
             Repo::RwLockTokioMutexTokio(repo) => Some(repo.remove(key).await?.lock().await.clone()),
             Repo::SkipMapMutexStd(repo) => Some(repo.remove(key)?.lock().unwrap().clone()),
+            Repo::ShardedSkipMapMutexStd(repo) => Some(repo.remove(key)?.lock().unwrap().clone()),
             Repo::SkipMapMutexParkingLot(repo) => Some(repo.remove(key)?.lock().clone()),
             Repo::SkipMapRwLockParkingLot(repo) => Some(repo.remove(key)?.write().clone()),
             Repo::DashMapMutexStd(repo) => Some(repo.remove(key)?.lock().unwrap().clone()),
@@ -179,13 +223,14 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => repo.remove_inactive_peers(current_cutoff).await,
             Repo::RwLockTokioMutexTokio(repo) => repo.remove_inactive_peers(current_cutoff).await,
             Repo::SkipMapMutexStd(repo) => repo.remove_inactive_peers(current_cutoff),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.remove_inactive_peers(current_cutoff),
             Repo::SkipMapMutexParkingLot(repo) => repo.remove_inactive_peers(current_cutoff),
             Repo::SkipMapRwLockParkingLot(repo) => repo.remove_inactive_peers(current_cutoff),
             Repo::DashMapMutexStd(repo) => repo.remove_inactive_peers(current_cutoff),
         }
     }
 
-    pub(crate) async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) {
+    pub(crate) async fn remove_peerless_torrents(&self, policy: &TrackerPolicy) -> Vec<InfoHash> {
         match self {
             Repo::RwLockStd(repo) => repo.remove_peerless_torrents(policy),
             Repo::RwLockStdMutexStd(repo) => repo.remove_peerless_torrents(policy),
@@ -194,12 +239,29 @@ impl Repo {
             Repo::RwLockTokioMutexStd(repo) => repo.remove_peerless_torrents(policy).await,
             Repo::RwLockTokioMutexTokio(repo) => repo.remove_peerless_torrents(policy).await,
             Repo::SkipMapMutexStd(repo) => repo.remove_peerless_torrents(policy),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.remove_peerless_torrents(policy),
             Repo::SkipMapMutexParkingLot(repo) => repo.remove_peerless_torrents(policy),
             Repo::SkipMapRwLockParkingLot(repo) => repo.remove_peerless_torrents(policy),
             Repo::DashMapMutexStd(repo) => repo.remove_peerless_torrents(policy),
         }
     }
 
+    pub(crate) async fn remove_stale_torrents(&self, current_cutoff: DurationSinceUnixEpoch) -> Vec<InfoHash> {
+        match self {
+            Repo::RwLockStd(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::RwLockStdMutexStd(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::RwLockStdMutexTokio(repo) => repo.remove_stale_torrents(current_cutoff).await,
+            Repo::RwLockTokio(repo) => repo.remove_stale_torrents(current_cutoff).await,
+            Repo::RwLockTokioMutexStd(repo) => repo.remove_stale_torrents(current_cutoff).await,
+            Repo::RwLockTokioMutexTokio(repo) => repo.remove_stale_torrents(current_cutoff).await,
+            Repo::SkipMapMutexStd(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::ShardedSkipMapMutexStd(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::SkipMapMutexParkingLot(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::SkipMapRwLockParkingLot(repo) => repo.remove_stale_torrents(current_cutoff),
+            Repo::DashMapMutexStd(repo) => repo.remove_stale_torrents(current_cutoff),
+        }
+    }
+
     pub(crate) async fn insert(&self, info_hash: &InfoHash, torrent: EntrySingle) -> Option<EntrySingle> {
         match self {
             Repo::RwLockStd(repo) => {
@@ -223,6 +285,9 @@ impl Repo {
             Repo::SkipMapMutexStd(repo) => {
                 repo.torrents.insert(*info_hash, torrent.into());
             }
+            Repo::ShardedSkipMapMutexStd(repo) => {
+                repo.insert(*info_hash, torrent.into());
+            }
             Repo::SkipMapMutexParkingLot(repo) => {
                 repo.torrents.insert(*info_hash, torrent.into());
             }
@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -69,6 +70,16 @@ impl Torrent {
         }
     }
 
+    pub(crate) async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        match self {
+            Torrent::Single(entry) => entry.get_peer(peer_id),
+            Torrent::MutexStd(entry) => entry.get_peer(peer_id),
+            Torrent::MutexTokio(entry) => entry.clone().get_peer(peer_id).await,
+            Torrent::MutexParkingLot(entry) => entry.get_peer(peer_id),
+            Torrent::RwLockParkingLot(entry) => entry.get_peer(peer_id),
+        }
+    }
+
     pub(crate) async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         match self {
             Torrent::Single(entry) => entry.get_peers_for_client(client, limit),
@@ -79,13 +90,23 @@ impl Torrent {
         }
     }
 
-    pub(crate) async fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
+    pub(crate) async fn upsert_peer(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> bool {
+        match self {
+            Torrent::Single(entry) => entry.upsert_peer(peer, policy),
+            Torrent::MutexStd(entry) => entry.upsert_peer(peer, policy),
+            Torrent::MutexTokio(entry) => entry.clone().upsert_peer(peer, policy).await,
+            Torrent::MutexParkingLot(entry) => entry.upsert_peer(peer, policy),
+            Torrent::RwLockParkingLot(entry) => entry.upsert_peer(peer, policy),
+        }
+    }
+
+    pub(crate) async fn upsert_peers_batch(&mut self, peers: &[peer::Peer], policy: &TrackerPolicy) -> bool {
         match self {
-            Torrent::Single(entry) => entry.upsert_peer(peer),
-            Torrent::MutexStd(entry) => entry.upsert_peer(peer),
-            Torrent::MutexTokio(entry) => entry.clone().upsert_peer(peer).await,
-            Torrent::MutexParkingLot(entry) => entry.upsert_peer(peer),
-            Torrent::RwLockParkingLot(entry) => entry.upsert_peer(peer),
+            Torrent::Single(entry) => entry.upsert_peers_batch(peers, policy),
+            Torrent::MutexStd(entry) => entry.upsert_peers_batch(peers, policy),
+            Torrent::MutexTokio(entry) => entry.clone().upsert_peers_batch(peers, policy).await,
+            Torrent::MutexParkingLot(entry) => entry.upsert_peers_batch(peers, policy),
+            Torrent::RwLockParkingLot(entry) => entry.upsert_peers_batch(peers, policy),
         }
     }
 
@@ -98,4 +119,24 @@ impl Torrent {
             Torrent::RwLockParkingLot(entry) => entry.remove_inactive_peers(current_cutoff),
         }
     }
+
+    pub(crate) async fn get_last_announce_at(&self) -> DurationSinceUnixEpoch {
+        match self {
+            Torrent::Single(entry) => entry.get_last_announce_at(),
+            Torrent::MutexStd(entry) => entry.get_last_announce_at(),
+            Torrent::MutexTokio(entry) => entry.get_last_announce_at().await,
+            Torrent::MutexParkingLot(entry) => entry.get_last_announce_at(),
+            Torrent::RwLockParkingLot(entry) => entry.get_last_announce_at(),
+        }
+    }
+
+    pub(crate) async fn set_static_peers(&mut self, peers: &[peer::Peer]) {
+        match self {
+            Torrent::Single(entry) => entry.set_static_peers(peers),
+            Torrent::MutexStd(entry) => entry.set_static_peers(peers),
+            Torrent::MutexTokio(entry) => entry.clone().set_static_peers(peers).await,
+            Torrent::MutexParkingLot(entry) => entry.set_static_peers(peers),
+            Torrent::RwLockParkingLot(entry) => entry.set_static_peers(peers),
+        }
+    }
 }
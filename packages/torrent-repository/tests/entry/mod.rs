@@ -1,12 +1,13 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::ops::Sub;
+use std::sync::Arc;
 use std::time::Duration;
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
 use rstest::{fixture, rstest};
 use torrust_tracker_clock::clock::stopped::Stopped as _;
 use torrust_tracker_clock::clock::{self, Time as _};
-use torrust_tracker_configuration::{TrackerPolicy, TORRENT_PEERS_LIMIT};
+use torrust_tracker_configuration::{PeerEvictionPolicy, TrackerPolicy, TORRENT_PEERS_LIMIT};
 use torrust_tracker_primitives::peer;
 use torrust_tracker_primitives::peer::Peer;
 use torrust_tracker_torrent_repository::{
@@ -43,22 +44,22 @@ fn rw_lock_parking_lot() -> Torrent {
 
 #[fixture]
 fn policy_none() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, false)
+    TrackerPolicy::new(0, false, false, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, false)
+    TrackerPolicy::new(0, true, false, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_remove() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, true)
+    TrackerPolicy::new(0, false, true, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_remove_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, true)
+    TrackerPolicy::new(0, true, true, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 pub enum Makes {
@@ -74,34 +75,34 @@ async fn make(torrent: &mut Torrent, makes: &Makes) -> Vec<Peer> {
         Makes::Empty => vec![],
         Makes::Started => {
             let peer = a_started_peer(1);
-            torrent.upsert_peer(&peer).await;
+            torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
             vec![peer]
         }
         Makes::Completed => {
             let peer = a_completed_peer(2);
-            torrent.upsert_peer(&peer).await;
+            torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
             vec![peer]
         }
         Makes::Downloaded => {
             let mut peer = a_started_peer(3);
-            torrent.upsert_peer(&peer).await;
+            torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
             peer.event = AnnounceEvent::Completed;
             peer.left = NumberOfBytes::new(0);
-            torrent.upsert_peer(&peer).await;
+            torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
             vec![peer]
         }
         Makes::Three => {
             let peer_1 = a_started_peer(1);
-            torrent.upsert_peer(&peer_1).await;
+            torrent.upsert_peer(&peer_1, &TrackerPolicy::default()).await;
 
             let peer_2 = a_completed_peer(2);
-            torrent.upsert_peer(&peer_2).await;
+            torrent.upsert_peer(&peer_2, &TrackerPolicy::default()).await;
 
             let mut peer_3 = a_started_peer(3);
-            torrent.upsert_peer(&peer_3).await;
+            torrent.upsert_peer(&peer_3, &TrackerPolicy::default()).await;
             peer_3.event = AnnounceEvent::Completed;
             peer_3.left = NumberOfBytes::new(0);
-            torrent.upsert_peer(&peer_3).await;
+            torrent.upsert_peer(&peer_3, &TrackerPolicy::default()).await;
             vec![peer_1, peer_2, peer_3]
         }
     }
@@ -179,6 +180,24 @@ async fn it_should_get_peers_for_torrent_entry(
     }
 }
 
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_get_a_peer_by_its_peer_id(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    for peer in &peers {
+        assert_eq!(torrent.get_peer(&peer.peer_id).await, Some(Arc::new(*peer)));
+    }
+
+    assert_eq!(torrent.get_peer(&peer::Id::new(99)).await, None);
+}
+
 #[rstest]
 #[case::empty(&Makes::Empty)]
 #[case::started(&Makes::Started)]
@@ -191,7 +210,7 @@ async fn it_should_update_a_peer(#[values(single(), mutex_std(), mutex_tokio())]
 
     // Make and insert a new peer.
     let mut peer = a_started_peer(-1);
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
 
     // Get the Inserted Peer by Id.
     let peers = torrent.get_peers(None).await;
@@ -204,7 +223,7 @@ async fn it_should_update_a_peer(#[values(single(), mutex_std(), mutex_tokio())]
 
     // Announce "Completed" torrent download event.
     peer.event = AnnounceEvent::Completed;
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
 
     // Get the Updated Peer by Id.
     let peers = torrent.get_peers(None).await;
@@ -233,7 +252,7 @@ async fn it_should_remove_a_peer_upon_stopped_announcement(
 
     let mut peer = a_started_peer(-1);
 
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
 
     // The started peer should be inserted.
     let peers = torrent.get_peers(None).await;
@@ -246,7 +265,7 @@ async fn it_should_remove_a_peer_upon_stopped_announcement(
 
     // Change peer to "Stopped" and insert.
     peer.event = AnnounceEvent::Stopped;
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
 
     // It should be removed now.
     let peers = torrent.get_peers(None).await;
@@ -279,7 +298,7 @@ async fn it_should_handle_a_peer_completed_announcement_and_update_the_downloade
     // Announce "Completed" torrent download event.
     peer.event = AnnounceEvent::Completed;
 
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
     let stats = torrent.get_stats().await;
 
     if is_already_completed {
@@ -289,6 +308,22 @@ async fn it_should_handle_a_peer_completed_announcement_and_update_the_downloade
     }
 }
 
+#[rstest]
+#[tokio::test]
+async fn it_should_update_a_batch_of_peers_in_order_and_update_the_downloaded_statistic(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let batch = [a_started_peer(1), a_completed_peer(1), a_started_peer(2)];
+
+    assert!(torrent.upsert_peers_batch(&batch, &TrackerPolicy::default()).await);
+
+    let stats = torrent.get_stats().await;
+
+    assert_eq!(stats.complete, 1);
+    assert_eq!(stats.incomplete, 1);
+    assert_eq!(stats.downloaded, 1);
+}
+
 #[rstest]
 #[case::started(&Makes::Started)]
 #[case::completed(&Makes::Completed)]
@@ -309,7 +344,7 @@ async fn it_should_update_a_peer_as_a_seeder(
 
     // Set Bytes Left to Zero
     peer.left = NumberOfBytes::new(0);
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
     let stats = torrent.get_stats().await;
 
     if is_already_non_left {
@@ -341,7 +376,7 @@ async fn it_should_update_a_peer_as_incomplete(
 
     // Set Bytes Left to no Zero
     peer.left = NumberOfBytes::new(1);
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
     let stats = torrent.get_stats().await;
 
     if completed_already {
@@ -378,7 +413,7 @@ async fn it_should_get_peers_excluding_the_client_socket(
 
     // set the address to the socket.
     peer.peer_addr = socket;
-    torrent.upsert_peer(&peer).await; // Add peer
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await; // Add peer
 
     // It should not include the peer that has the same socket.
     assert!(!torrent.get_peers_for_client(&socket, None).await.contains(&peer.into()));
@@ -401,7 +436,7 @@ async fn it_should_limit_the_number_of_peers_returned(
     for peer_number in 1..=74 + 1 {
         let mut peer = a_started_peer(1);
         peer.peer_id = *peer::Id::new(peer_number);
-        torrent.upsert_peer(&peer).await;
+        torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
     }
 
     let peers = torrent.get_peers(Some(TORRENT_PEERS_LIMIT)).await;
@@ -432,7 +467,7 @@ async fn it_should_remove_inactive_peers_beyond_cutoff(
 
     peer.updated = now.sub(EXPIRE);
 
-    torrent.upsert_peer(&peer).await;
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
 
     assert_eq!(torrent.get_peers_len().await, peers.len() + 1);
 
@@ -441,3 +476,181 @@ async fn it_should_remove_inactive_peers_beyond_cutoff(
 
     assert_eq!(torrent.get_peers_len().await, peers.len());
 }
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_update_the_last_announce_time_when_a_peer_is_updated(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    make(&mut torrent, makes).await;
+
+    let mut peer = a_started_peer(-1);
+
+    peer.updated = clock::Working::now();
+
+    torrent.upsert_peer(&peer, &TrackerPolicy::default()).await;
+
+    assert_eq!(torrent.get_last_announce_at().await, peer.updated);
+}
+
+fn policy_with_max_peers(max_peers_per_torrent: u32, peer_eviction_policy: PeerEvictionPolicy) -> TrackerPolicy {
+    TrackerPolicy::new(
+        0,
+        false,
+        false,
+        None,
+        Some(max_peers_per_torrent),
+        peer_eviction_policy,
+        false,
+    )
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_reject_a_new_peer_when_the_swarm_is_full_and_the_policy_is_to_reject_new_peers(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let peers = make(&mut torrent, &Makes::Three).await;
+    let policy = policy_with_max_peers(3, PeerEvictionPolicy::RejectNew);
+
+    let new_peer = a_started_peer(4);
+    torrent.upsert_peer(&new_peer, &policy).await;
+
+    let peer_ids: Vec<_> = torrent.get_peers(None).await.iter().map(|peer| peer.peer_id).collect();
+
+    assert_eq!(peer_ids, peers.iter().map(|peer| peer.peer_id).collect::<Vec<_>>());
+
+    // Peers already in the swarm can still announce.
+    let mut peer = peers[0];
+    peer.left = NumberOfBytes::new(0);
+    torrent.upsert_peer(&peer, &policy).await;
+
+    assert_eq!(torrent.get_stats().await.complete, 3);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_evict_the_peer_with_the_oldest_announce_when_the_swarm_is_full_and_the_policy_is_to_evict_the_oldest(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let policy = policy_with_max_peers(3, PeerEvictionPolicy::EvictOldest);
+
+    for (id, updated) in [(1, 20), (2, 10), (3, 30)] {
+        let mut peer = a_started_peer(id);
+        peer.updated = Duration::from_secs(updated);
+        torrent.upsert_peer(&peer, &policy).await;
+    }
+
+    let new_peer = a_started_peer(4);
+    torrent.upsert_peer(&new_peer, &policy).await;
+
+    let peer_ids: Vec<_> = torrent.get_peers(None).await.iter().map(|peer| peer.peer_id).collect();
+
+    assert_eq!(peer_ids.len(), 3);
+    assert!(peer_ids.contains(&new_peer.peer_id));
+    assert!(!peer_ids.contains(&a_started_peer(2).peer_id));
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_evict_a_leecher_when_the_swarm_is_full_and_the_policy_is_to_evict_a_random_leecher(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    // The first peer is the only leecher.
+    let peers = make(&mut torrent, &Makes::Three).await;
+    let policy = policy_with_max_peers(3, PeerEvictionPolicy::EvictRandomLeecher);
+
+    let new_peer = a_started_peer(4);
+    torrent.upsert_peer(&new_peer, &policy).await;
+
+    let peer_ids: Vec<_> = torrent.get_peers(None).await.iter().map(|peer| peer.peer_id).collect();
+
+    assert_eq!(peer_ids.len(), 3);
+    assert!(peer_ids.contains(&new_peer.peer_id));
+    assert!(!peer_ids.contains(&peers[0].peer_id));
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_reject_a_new_peer_when_the_swarm_is_full_of_seeders_and_the_policy_is_to_evict_a_random_leecher(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let policy = policy_with_max_peers(2, PeerEvictionPolicy::EvictRandomLeecher);
+
+    torrent.upsert_peer(&a_completed_peer(1), &policy).await;
+    torrent.upsert_peer(&a_completed_peer(2), &policy).await;
+
+    torrent.upsert_peer(&a_started_peer(3), &policy).await;
+
+    let stats = torrent.get_stats().await;
+
+    assert_eq!(stats.complete, 2);
+    assert_eq!(stats.incomplete, 0);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_count_a_dual_stack_peer_only_once_when_the_policy_is_to_merge_the_dual_stack_peers(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let policy = TrackerPolicy {
+        merge_dual_stack_peers: true,
+        ..Default::default()
+    };
+
+    let mut ipv4 = a_completed_peer(1);
+    ipv4.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+    let mut ipv6 = a_completed_peer(1);
+    ipv6.peer_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080);
+
+    torrent.upsert_peer(&ipv4, &policy).await;
+    torrent.upsert_peer(&ipv6, &policy).await;
+
+    assert_eq!(torrent.get_stats().await.complete, 1);
+    assert_eq!(torrent.get_peers(None).await.len(), 2);
+
+    // The peer leaves on one address only.
+    ipv6.event = AnnounceEvent::Stopped;
+    torrent.upsert_peer(&ipv6, &policy).await;
+
+    assert_eq!(torrent.get_stats().await.complete, 1);
+    assert_eq!(torrent.get_peers(None).await, [Arc::new(ipv4)]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_return_the_static_peers_before_the_swarm_peers_and_count_them_as_seeders(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let swarm_peer = a_started_peer(1);
+    torrent.upsert_peer(&swarm_peer, &TrackerPolicy::default()).await;
+
+    let mut static_peer = a_completed_peer(2);
+    static_peer.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 6881);
+    torrent.set_static_peers(&[static_peer]).await;
+
+    assert_eq!(torrent.get_peers(None).await, [Arc::new(static_peer), Arc::new(swarm_peer)]);
+    assert_eq!(torrent.get_peers(Some(1)).await, [Arc::new(static_peer)]);
+    assert_eq!(
+        torrent.get_peers_for_client(&static_peer.peer_addr, None).await,
+        [Arc::new(swarm_peer)]
+    );
+    assert_eq!(torrent.get_stats().await.complete, 1);
+    assert_eq!(torrent.get_stats().await.incomplete, 1);
+
+    // The static peers are kept when the swarm peers leave.
+    torrent
+        .remove_inactive_peers(swarm_peer.updated + Duration::from_secs(1))
+        .await;
+
+    assert!(torrent.peers_is_empty().await);
+    assert!(torrent.meets_retaining_policy(&policy_remove()).await);
+    assert_eq!(torrent.get_peers(None).await, [Arc::new(static_peer)]);
+    assert_eq!(torrent.get_peer(&static_peer.peer_id).await, Some(Arc::new(static_peer)));
+}
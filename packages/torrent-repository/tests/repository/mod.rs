@@ -3,7 +3,7 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
 use rstest::{fixture, rstest};
-use torrust_tracker_configuration::TrackerPolicy;
+use torrust_tracker_configuration::{PeerEvictionPolicy, TrackerPolicy};
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
@@ -12,6 +12,7 @@ use torrust_tracker_torrent_repository::entry::Entry as _;
 use torrust_tracker_torrent_repository::repository::dash_map_mutex_std::XacrimonDashMap;
 use torrust_tracker_torrent_repository::repository::rw_lock_std::RwLockStd;
 use torrust_tracker_torrent_repository::repository::rw_lock_tokio::RwLockTokio;
+use torrust_tracker_torrent_repository::repository::sharded_skip_map_mutex_std::ShardedCrossbeamSkipList;
 use torrust_tracker_torrent_repository::repository::skip_map_mutex_std::CrossbeamSkipList;
 use torrust_tracker_torrent_repository::EntrySingle;
 
@@ -53,6 +54,11 @@ fn skip_list_mutex_std() -> Repo {
     Repo::SkipMapMutexStd(CrossbeamSkipList::default())
 }
 
+#[fixture]
+fn sharded_skip_list_mutex_std() -> Repo {
+    Repo::ShardedSkipMapMutexStd(ShardedCrossbeamSkipList::with_expected_torrents(1_000_000))
+}
+
 #[fixture]
 fn skip_list_mutex_parking_lot() -> Repo {
     Repo::SkipMapMutexParkingLot(CrossbeamSkipList::default())
@@ -83,14 +89,14 @@ fn default() -> Entries {
 #[fixture]
 fn started() -> Entries {
     let mut torrent = EntrySingle::default();
-    torrent.upsert_peer(&a_started_peer(1));
+    torrent.upsert_peer(&a_started_peer(1), &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
 #[fixture]
 fn completed() -> Entries {
     let mut torrent = EntrySingle::default();
-    torrent.upsert_peer(&a_completed_peer(2));
+    torrent.upsert_peer(&a_completed_peer(2), &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
@@ -98,10 +104,10 @@ fn completed() -> Entries {
 fn downloaded() -> Entries {
     let mut torrent = EntrySingle::default();
     let mut peer = a_started_peer(3);
-    torrent.upsert_peer(&peer);
+    torrent.upsert_peer(&peer, &TrackerPolicy::default());
     peer.event = AnnounceEvent::Completed;
     peer.left = NumberOfBytes::new(0);
-    torrent.upsert_peer(&peer);
+    torrent.upsert_peer(&peer, &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
@@ -109,21 +115,21 @@ fn downloaded() -> Entries {
 fn three() -> Entries {
     let mut started = EntrySingle::default();
     let started_h = &mut DefaultHasher::default();
-    started.upsert_peer(&a_started_peer(1));
+    started.upsert_peer(&a_started_peer(1), &TrackerPolicy::default());
     started.hash(started_h);
 
     let mut completed = EntrySingle::default();
     let completed_h = &mut DefaultHasher::default();
-    completed.upsert_peer(&a_completed_peer(2));
+    completed.upsert_peer(&a_completed_peer(2), &TrackerPolicy::default());
     completed.hash(completed_h);
 
     let mut downloaded = EntrySingle::default();
     let downloaded_h = &mut DefaultHasher::default();
     let mut downloaded_peer = a_started_peer(3);
-    downloaded.upsert_peer(&downloaded_peer);
+    downloaded.upsert_peer(&downloaded_peer, &TrackerPolicy::default());
     downloaded_peer.event = AnnounceEvent::Completed;
     downloaded_peer.left = NumberOfBytes::new(0);
-    downloaded.upsert_peer(&downloaded_peer);
+    downloaded.upsert_peer(&downloaded_peer, &TrackerPolicy::default());
     downloaded.hash(downloaded_h);
 
     vec![
@@ -139,7 +145,7 @@ fn many_out_of_order() -> Entries {
 
     for i in 0..408 {
         let mut entry = EntrySingle::default();
-        entry.upsert_peer(&a_started_peer(i));
+        entry.upsert_peer(&a_started_peer(i), &TrackerPolicy::default());
 
         entries.insert((InfoHash::from(&i), entry));
     }
@@ -154,7 +160,7 @@ fn many_hashed_in_order() -> Entries {
 
     for i in 0..408 {
         let mut entry = EntrySingle::default();
-        entry.upsert_peer(&a_started_peer(i));
+        entry.upsert_peer(&a_started_peer(i), &TrackerPolicy::default());
 
         let hash: &mut DefaultHasher = &mut DefaultHasher::default();
         hash.write_i32(i);
@@ -220,22 +226,22 @@ fn paginated_limit_one_offset_one() -> Pagination {
 
 #[fixture]
 fn policy_none() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, false)
+    TrackerPolicy::new(0, false, false, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, false)
+    TrackerPolicy::new(0, true, false, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_remove() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, true)
+    TrackerPolicy::new(0, false, true, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[fixture]
 fn policy_remove_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, true)
+    TrackerPolicy::new(0, true, true, None, None, PeerEvictionPolicy::RejectNew, false)
 }
 
 #[rstest]
@@ -257,6 +263,7 @@ async fn it_should_get_a_torrent_entry(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -292,6 +299,7 @@ async fn it_should_get_paginated_entries_in_a_stable_or_sorted_order(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot()
     )]
@@ -336,6 +344,7 @@ async fn it_should_get_paginated(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot()
     )]
@@ -364,12 +373,10 @@ async fn it_should_get_paginated(
         }
 
         // it should return the only the second entry if both the limit and the offset are one.
-        Pagination { limit: 1, offset: 1 } => {
-            if info_hashes.len() > 1 {
-                let page = repo.get_paginated(Some(&paginated)).await;
-                assert_eq!(page.len(), 1);
-                assert_eq!(page[0].0, info_hashes[1]);
-            }
+        Pagination { limit: 1, offset: 1 } if info_hashes.len() > 1 => {
+            let page = repo.get_paginated(Some(&paginated)).await;
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].0, info_hashes[1]);
         }
         // the other cases are not yet tested.
         _ => {}
@@ -395,6 +402,7 @@ async fn it_should_get_metrics(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -439,6 +447,7 @@ async fn it_should_import_persistent_torrents(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -461,6 +470,62 @@ async fn it_should_import_persistent_torrents(
     }
 }
 
+#[rstest]
+#[case::empty(empty())]
+#[case::default(default())]
+#[case::started(started())]
+#[case::completed(completed())]
+#[case::downloaded(downloaded())]
+#[case::three(three())]
+#[case::out_of_order(many_out_of_order())]
+#[case::in_order(many_hashed_in_order())]
+#[tokio::test]
+async fn it_should_upsert_a_batch_of_peers_in_order(
+    #[values(
+        standard(),
+        standard_mutex(),
+        standard_tokio(),
+        tokio_std(),
+        tokio_mutex(),
+        tokio_tokio(),
+        skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
+        skip_list_mutex_parking_lot(),
+        skip_list_rw_lock_parking_lot(),
+        dash_map_std()
+    )]
+    repo: Repo,
+    #[case] entries: Entries,
+) {
+    make(&repo, &entries).await;
+
+    let info_hash: InfoHash;
+
+    // Generate a new infohash.
+    {
+        let hash = &mut DefaultHasher::default();
+        hash.write_u8(254);
+        info_hash = InfoHash::from(&hash.clone());
+    }
+
+    repo.upsert_peers_batch(
+        &info_hash,
+        &[a_started_peer(1), a_completed_peer(1), a_started_peer(2)],
+        &TrackerPolicy::default(),
+    )
+    .await;
+
+    assert_eq!(
+        repo.get_swarm_metadata(&info_hash).await,
+        Some(SwarmMetadata {
+            downloaded: 1,
+            complete: 1,
+            incomplete: 1,
+            partial_seeds: 0
+        })
+    );
+}
+
 #[rstest]
 #[case::empty(empty())]
 #[case::default(default())]
@@ -480,6 +545,7 @@ async fn it_should_remove_an_entry(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -519,6 +585,7 @@ async fn it_should_remove_inactive_peers(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -562,21 +629,22 @@ async fn it_should_remove_inactive_peers(
     // Insert the infohash and peer into the repository
     // and verify there is an extra torrent entry.
     {
-        repo.upsert_peer(&info_hash, &peer).await;
+        repo.upsert_peer(&info_hash, &peer, &TrackerPolicy::default()).await;
         assert_eq!(repo.get_metrics().await.torrents, entries.len() as u64 + 1);
     }
 
     // Insert the infohash and peer into the repository
     // and verify the swarm metadata was updated.
     {
-        repo.upsert_peer(&info_hash, &peer).await;
+        repo.upsert_peer(&info_hash, &peer, &TrackerPolicy::default()).await;
         let stats = repo.get_swarm_metadata(&info_hash).await;
         assert_eq!(
             stats,
             Some(SwarmMetadata {
                 downloaded: 0,
                 complete: 1,
-                incomplete: 0
+                incomplete: 0,
+                partial_seeds: 0
             })
         );
     }
@@ -619,6 +687,7 @@ async fn it_should_remove_peerless_torrents(
         tokio_mutex(),
         tokio_tokio(),
         skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
         skip_list_mutex_parking_lot(),
         skip_list_rw_lock_parking_lot(),
         dash_map_std()
@@ -629,11 +698,178 @@ async fn it_should_remove_peerless_torrents(
 ) {
     make(&repo, &entries).await;
 
-    repo.remove_peerless_torrents(&policy).await;
+    let mut removed = repo.remove_peerless_torrents(&policy).await;
 
     let torrents = repo.get_paginated(None).await;
 
     for (_, entry) in torrents {
         assert!(entry.meets_retaining_policy(&policy));
     }
+
+    // It returns the removed torrents.
+    let mut expected = entries
+        .iter()
+        .filter(|(_, entry)| !entry.meets_retaining_policy(&policy))
+        .map(|(info_hash, _)| *info_hash)
+        .collect::<Vec<_>>();
+
+    removed.sort();
+    expected.sort();
+
+    assert_eq!(removed, expected);
+}
+
+#[rstest]
+#[case::empty(empty())]
+#[case::default(default())]
+#[case::started(started())]
+#[case::completed(completed())]
+#[case::downloaded(downloaded())]
+#[case::three(three())]
+#[case::out_of_order(many_out_of_order())]
+#[case::in_order(many_hashed_in_order())]
+#[tokio::test]
+async fn it_should_remove_stale_torrents(
+    #[values(
+        standard(),
+        standard_mutex(),
+        standard_tokio(),
+        tokio_std(),
+        tokio_mutex(),
+        tokio_tokio(),
+        skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
+        skip_list_mutex_parking_lot(),
+        skip_list_rw_lock_parking_lot(),
+        dash_map_std()
+    )]
+    repo: Repo,
+    #[case] entries: Entries,
+) {
+    use std::time::Duration;
+
+    make(&repo, &entries).await;
+
+    // All the torrents announced before the cutoff are stale.
+    let current_cutoff = entries
+        .iter()
+        .map(|(_, entry)| entry.get_last_announce_at())
+        .max()
+        .unwrap_or_default()
+        + Duration::from_secs(1);
+
+    // A torrent announced at the cutoff is not stale.
+    let info_hash: InfoHash;
+    {
+        let hash = &mut DefaultHasher::default();
+        hash.write_u8(255);
+        info_hash = InfoHash::from(&hash.clone());
+
+        let mut peer = a_started_peer(-1);
+        peer.updated = current_cutoff;
+
+        repo.upsert_peer(&info_hash, &peer, &TrackerPolicy::default()).await;
+    }
+
+    let mut removed = repo.remove_stale_torrents(current_cutoff).await;
+
+    let torrents = repo.get_paginated(None).await;
+
+    assert_eq!(
+        torrents.iter().map(|(info_hash, _)| *info_hash).collect::<Vec<_>>(),
+        vec![info_hash]
+    );
+
+    // It returns the removed torrents.
+    let mut expected = entries.iter().map(|(info_hash, _)| *info_hash).collect::<Vec<_>>();
+
+    removed.sort();
+    expected.sort();
+
+    assert_eq!(removed, expected);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_not_remove_the_stale_torrents_with_static_peers(
+    #[values(
+        standard(),
+        standard_mutex(),
+        standard_tokio(),
+        tokio_std(),
+        tokio_mutex(),
+        tokio_tokio(),
+        skip_list_mutex_std(),
+        sharded_skip_list_mutex_std(),
+        skip_list_mutex_parking_lot(),
+        skip_list_rw_lock_parking_lot(),
+        dash_map_std()
+    )]
+    repo: Repo,
+) {
+    use std::time::Duration;
+
+    let info_hash = InfoHash::from(&DefaultHasher::default());
+
+    let peer = a_completed_peer(1);
+
+    repo.set_static_peers(&info_hash, &[peer]).await;
+
+    // The torrent has never been announced, but it has static peers.
+    assert!(repo
+        .remove_stale_torrents(peer.updated + Duration::from_secs(1))
+        .await
+        .is_empty());
+
+    assert_eq!(
+        repo.get_swarm_metadata(&info_hash).await,
+        Some(SwarmMetadata {
+            downloaded: 0,
+            complete: 1,
+            incomplete: 0,
+            partial_seeds: 0
+        })
+    );
+}
+
+#[test]
+fn the_sharded_repository_should_have_a_shard_for_every_group_of_expected_torrents() {
+    use torrust_tracker_torrent_repository::repository::sharded_skip_map_mutex_std::{MAX_SHARDS, TORRENTS_PER_SHARD};
+    use torrust_tracker_torrent_repository::TorrentsShardedSkipMapMutexStd;
+
+    assert_eq!(TorrentsShardedSkipMapMutexStd::default().shards(), 1);
+    assert_eq!(
+        TorrentsShardedSkipMapMutexStd::with_expected_torrents(3 * TORRENTS_PER_SHARD).shards(),
+        4
+    );
+    assert_eq!(
+        TorrentsShardedSkipMapMutexStd::with_expected_torrents(usize::MAX).shards(),
+        MAX_SHARDS
+    );
+}
+
+#[test]
+fn the_sharded_repository_should_only_initialize_the_shards_with_torrents() {
+    use torrust_tracker_torrent_repository::repository::Repository as _;
+    use torrust_tracker_torrent_repository::TorrentsShardedSkipMapMutexStd;
+
+    let repo = TorrentsShardedSkipMapMutexStd::with_expected_torrents(1_000_000);
+
+    assert_eq!(repo.initialized_shards(), 0);
+
+    repo.import_persistent(&BTreeMap::from([
+        (InfoHash::from([0; 20]), 1),
+        (InfoHash::from([1; 20]), 2),
+        (InfoHash::from([255; 20]), 3),
+    ]));
+
+    assert_eq!(repo.shards(), 16);
+    assert_eq!(repo.initialized_shards(), 2);
+    assert_eq!(
+        repo.get_paginated(None)
+            .iter()
+            .map(|(info_hash, _)| *info_hash)
+            .collect::<Vec<_>>(),
+        vec![InfoHash::from([0; 20]), InfoHash::from([1; 20]), InfoHash::from([255; 20])]
+    );
 }
@@ -0,0 +1,354 @@
+//! The tracker API client.
+use std::time::Duration;
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use url::Url;
+
+use crate::error::Error;
+use crate::resources::{AuthKey, ErrorResponse, Stats, Torrent, TorrentListItem};
+
+/// Default timeout of the requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How the client authenticates to the tracker API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// No authentication. Only the public endpoints can be used.
+    None,
+    /// One of the `access_tokens` in the tracker configuration. It's sent in
+    /// the `token` query param.
+    Token(String),
+    /// A bearer token issued by the external authentication provider of the
+    /// tracker. It's sent in the `Authorization` header.
+    Bearer(String),
+}
+
+/// How many times the failed requests are retried, and how long the client
+/// waits before retrying. The wait doubles after each retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retries {
+    /// Maximum number of retries of a request.
+    pub max_retries: u32,
+    /// Wait before the first retry.
+    pub backoff: Duration,
+}
+
+impl Retries {
+    /// The requests are not retried.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for Retries {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+/// A client for the tracker API `v1`.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: Url,
+    auth: Auth,
+    retries: Retries,
+}
+
+impl Client {
+    /// It creates a client for the API at the base URL, for example
+    /// `http://127.0.0.1:1212/api/`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the HTTP client can't be initialized, for example, when
+    /// the TLS backend can't be loaded.
+    #[must_use]
+    pub fn new(mut base_url: Url, auth: Auth) -> Self {
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+
+        Self {
+            http: http_client(DEFAULT_TIMEOUT),
+            base_url,
+            auth,
+            retries: Retries::default(),
+        }
+    }
+
+    /// It sets the timeout of the requests. It's 10 seconds by default.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the HTTP client can't be initialized.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.http = http_client(timeout);
+        self
+    }
+
+    /// It sets the [`Retries`] of the failed requests.
+    #[must_use]
+    pub fn with_retries(mut self, retries: Retries) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// It gets the tracker statistics.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn get_stats(&self) -> Result<Stats, Error> {
+        json(self.send(Method::GET, "v1/stats", &[], None).await?).await
+    }
+
+    /// It generates a new random authentication key, valid for the given
+    /// number of seconds.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn generate_key(&self, seconds_valid: u64) -> Result<AuthKey, Error> {
+        json(self.send(Method::POST, &format!("v1/key/{seconds_valid}"), &[], None).await?).await
+    }
+
+    /// It adds an authentication key. A random key is generated when `key` is
+    /// `None`, and the key is permanent when `seconds_valid` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails, for example, when the
+    /// key is not valid.
+    pub async fn add_key(&self, key: Option<&str>, seconds_valid: Option<u64>) -> Result<AuthKey, Error> {
+        let body = json!({ "key": key, "seconds_valid": seconds_valid });
+
+        json(self.send(Method::POST, "v1/keys", &[], Some(&body)).await?).await
+    }
+
+    /// It deletes an authentication key.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn delete_key(&self, key: &str) -> Result<(), Error> {
+        self.send(Method::DELETE, &format!("v1/key/{key}"), &[], None).await?;
+        Ok(())
+    }
+
+    /// It reloads the authentication keys from the database.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn reload_keys(&self) -> Result<(), Error> {
+        self.send(Method::GET, "v1/keys/reload", &[], None).await?;
+        Ok(())
+    }
+
+    /// It gets the info-hashes of the whitelisted torrents.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn get_whitelist(&self) -> Result<Vec<String>, Error> {
+        json(self.send(Method::GET, "v1/whitelist", &[], None).await?).await
+    }
+
+    /// It adds a torrent to the whitelist.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails, for example, when the
+    /// info-hash is not valid.
+    pub async fn whitelist_torrent(&self, info_hash: &str) -> Result<(), Error> {
+        self.send(Method::POST, &format!("v1/whitelist/{info_hash}"), &[], None)
+            .await?;
+        Ok(())
+    }
+
+    /// It removes a torrent from the whitelist.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn remove_torrent_from_whitelist(&self, info_hash: &str) -> Result<(), Error> {
+        self.send(Method::DELETE, &format!("v1/whitelist/{info_hash}"), &[], None)
+            .await?;
+        Ok(())
+    }
+
+    /// It reloads the whitelist from the database.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn reload_whitelist(&self) -> Result<(), Error> {
+        self.send(Method::GET, "v1/whitelist/reload", &[], None).await?;
+        Ok(())
+    }
+
+    /// It gets a torrent with its peers, or `None` if the tracker does not
+    /// know the torrent.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails, for example, when the
+    /// info-hash is not valid.
+    pub async fn get_torrent(&self, info_hash: &str) -> Result<Option<Torrent>, Error> {
+        match self.send(Method::GET, &format!("v1/torrent/{info_hash}"), &[], None).await {
+            Ok(response) => Ok(Some(json(response).await?)),
+            Err(Error::Api { status: 404, .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// It gets a page of the torrents. The tracker returns the first page
+    /// with its default limit when the `offset` and `limit` are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails.
+    pub async fn get_torrents(&self, offset: Option<u32>, limit: Option<u32>) -> Result<Vec<TorrentListItem>, Error> {
+        let mut query = vec![];
+
+        if let Some(offset) = offset {
+            query.push(("offset", offset.to_string()));
+        }
+
+        if let Some(limit) = limit {
+            query.push(("limit", limit.to_string()));
+        }
+
+        json(self.send(Method::GET, "v1/torrents", &query, None).await?).await
+    }
+
+    /// It gets the torrents with the given info-hashes. The unknown torrents
+    /// are not included.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Error`] if the request fails, for example, when an
+    /// info-hash is not valid.
+    pub async fn get_torrents_by_info_hash(&self, info_hashes: &[&str]) -> Result<Vec<TorrentListItem>, Error> {
+        let query: Vec<(&str, String)> = info_hashes
+            .iter()
+            .map(|info_hash| ("info_hash", (*info_hash).to_string()))
+            .collect();
+
+        json(self.send(Method::GET, "v1/torrents", &query, None).await?).await
+    }
+
+    /// It sends the request, retrying it when it can, and returns the
+    /// successful response.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response, Error> {
+        let idempotent = method == Method::GET || method == Method::DELETE;
+
+        let mut backoff = self.retries.backoff;
+
+        for _ in 0..self.retries.max_retries {
+            let result = self.request(method.clone(), path, query, body).send().await;
+
+            let retry = match &result {
+                Err(err) => err.is_connect() || (idempotent && err.is_timeout()),
+                Ok(response) => idempotent && is_temporarily_unavailable(response.status()),
+            };
+
+            if !retry {
+                return error_for_status(result?).await;
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+
+        error_for_status(self.request(method, path, query, body).send().await?).await
+    }
+
+    fn request(&self, method: Method, path: &str, query: &[(&str, String)], body: Option<&serde_json::Value>) -> RequestBuilder {
+        let url = self.base_url.join(path).expect("the API paths should be valid URLs");
+
+        let mut request = self.http.request(method, url).query(query);
+
+        request = match &self.auth {
+            Auth::None => request,
+            Auth::Token(token) => request.query(&[("token", token)]),
+            Auth::Bearer(token) => request.bearer_auth(token),
+        };
+
+        match body {
+            Some(body) => request.json(body),
+            None => request,
+        }
+    }
+}
+
+/// It parses the resource in the body of a successful response.
+async fn json<T: DeserializeOwned>(response: Response) -> Result<T, Error> {
+    let status = response.status().as_u16();
+    let body = response.text().await?;
+
+    serde_json::from_str(&body).map_err(|_| Error::UnexpectedResponse { status, body })
+}
+
+fn http_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("it should build the HTTP client")
+}
+
+fn is_temporarily_unavailable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// It returns the response if it's successful, or the API error otherwise.
+async fn error_for_status(response: Response) -> Result<Response, Error> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status().as_u16();
+    let body = response.text().await?;
+
+    match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(response) => Err(Error::Api { status, response }),
+        Err(_) => Err(Error::UnexpectedResponse { status, body }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{Auth, Client};
+
+    #[test]
+    fn it_should_add_the_trailing_slash_to_the_base_url() {
+        let client = Client::new(Url::parse("http://127.0.0.1:1212/api").unwrap(), Auth::None);
+
+        assert_eq!(
+            client.base_url.join("v1/stats").unwrap().as_str(),
+            "http://127.0.0.1:1212/api/v1/stats"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Errors returned by the API [`Client`](crate::Client).
+use thiserror::Error;
+
+use crate::resources::ErrorResponse;
+
+/// An error calling the tracker API.
+#[derive(Error, Debug)]
+pub enum Error {
+    /// The request could not be sent or the response could not be read. For
+    /// example, when the tracker can't be reached.
+    #[error("the request to the tracker API failed: {source}")]
+    Request { source: reqwest::Error },
+
+    /// The tracker API returned an error response.
+    #[error("the tracker API returned the error {status}: {}", response.message)]
+    Api { status: u16, response: ErrorResponse },
+
+    /// The tracker API returned a response that is not a valid resource or
+    /// error. For example, when the base URL is not the tracker API.
+    #[error("unexpected response from the tracker API with status {status}: {body}")]
+    UnexpectedResponse { status: u16, body: String },
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(source: reqwest::Error) -> Self {
+        Self::Request { source }
+    }
+}
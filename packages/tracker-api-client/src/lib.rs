@@ -0,0 +1,43 @@
+//! A typed client for the Torrust Tracker REST API.
+//!
+//! It covers the endpoints used by the applications integrated with the
+//! tracker, like the [Torrust Index](https://github.com/torrust/torrust-index):
+//!
+//! - Statistics: [`Client::get_stats`].
+//! - Authentication keys: [`Client::generate_key`], [`Client::add_key`],
+//!   [`Client::delete_key`] and [`Client::reload_keys`].
+//! - Whitelist: [`Client::get_whitelist`], [`Client::whitelist_torrent`],
+//!   [`Client::remove_torrent_from_whitelist`] and
+//!   [`Client::reload_whitelist`].
+//! - Torrents: [`Client::get_torrent`], [`Client::get_torrents`] and
+//!   [`Client::get_torrents_by_info_hash`].
+//!
+//! ```text
+//! let client = Client::new(Url::parse("http://127.0.0.1:1212/api/")?, Auth::Token("MyAccessToken".to_string()));
+//!
+//! let stats = client.get_stats().await?;
+//!
+//! client.whitelist_torrent("9c38422213e30bff212b30c360d26f9a02136422").await?;
+//! ```
+//!
+//! The base URL is the prefix of the API, without the version. The client
+//! authenticates with an access token in the `token` query param, or with a
+//! bearer token issued by the external authentication provider of the
+//! tracker. See [`Auth`].
+//!
+//! The errors returned by the API are parsed into [`Error::Api`], with the
+//! [`ErrorCode`](resources::ErrorCode) and the message of the tracker.
+//!
+//! # Retries
+//!
+//! The requests that fail because the tracker can't be reached are retried,
+//! since they were not handled. The reads (`GET`) and the deletions
+//! (`DELETE`), which are idempotent, are also retried when they time out or
+//! the tracker is temporarily unavailable (`502`, `503` and `504`). See
+//! [`Retries`].
+pub mod client;
+pub mod error;
+pub mod resources;
+
+pub use client::{Auth, Client, Retries};
+pub use error::Error;
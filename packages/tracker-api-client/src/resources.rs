@@ -0,0 +1,173 @@
+//! Resources of the tracker API.
+//!
+//! They only include the attributes the clients usually need. The unknown
+//! attributes are ignored, so the client keeps working when the tracker adds
+//! new ones.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The tracker statistics.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// Total number of torrents.
+    pub torrents: u64,
+    /// Total number of seeders for all torrents.
+    pub seeders: u64,
+    /// Total number of times any torrent has been downloaded.
+    pub completed: u64,
+    /// Total number of leechers for all torrents.
+    pub leechers: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv4
+    /// peers.
+    pub tcp4_announces_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
+    pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv6
+    /// peers.
+    pub tcp6_announces_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
+    pub tcp6_scrapes_handled: u64,
+    /// Total number of UDP `announce` requests from IPv4 peers.
+    pub udp4_announces_handled: u64,
+    /// Total number of UDP `scrape` requests from IPv4 peers.
+    pub udp4_scrapes_handled: u64,
+    /// Total number of UDP `announce` requests from IPv6 peers.
+    pub udp6_announces_handled: u64,
+    /// Total number of UDP `scrape` requests from IPv6 peers.
+    pub udp6_scrapes_handled: u64,
+    /// All the other metrics, by name.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Number>,
+}
+
+/// An authentication key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthKey {
+    /// The authentication key.
+    pub key: String,
+    /// The ISO 8601 time when the key expires. `None` for permanent keys.
+    pub expiry_time: Option<String>,
+}
+
+/// A torrent, with its peers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Torrent {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+    /// Active peers with a full copy of the torrent.
+    pub seeders: u64,
+    /// Peers that have ever completed the download.
+    pub completed: u64,
+    /// Active peers that are downloading the torrent.
+    pub leechers: u64,
+    /// The torrent's peers.
+    #[serde(default)]
+    pub peers: Vec<Peer>,
+}
+
+/// A torrent in a torrent list. It does not include the peers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TorrentListItem {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+    /// Active peers with a full copy of the torrent.
+    pub seeders: u64,
+    /// Peers that have ever completed the download.
+    pub completed: u64,
+    /// Active peers that are downloading the torrent.
+    pub leechers: u64,
+}
+
+/// A peer of a torrent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Peer {
+    /// The peer's ID.
+    pub peer_id: PeerId,
+    /// The peer's socket address. For example: `192.168.1.88:17548`.
+    pub peer_addr: String,
+    /// Time in milliseconds since the last `announce` of the peer.
+    pub updated_milliseconds_ago: u128,
+    /// The peer's uploaded bytes.
+    pub uploaded: i64,
+    /// The peer's downloaded bytes.
+    pub downloaded: i64,
+    /// The peer's left bytes (pending to download).
+    pub left: i64,
+    /// The peer's last event: `started`, `stopped`, `completed` or `none`.
+    pub event: String,
+}
+
+/// The ID of a peer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeerId {
+    /// The peer's ID in hex format. For example:
+    /// `0x2d7142343431302d2a64465a3844484944704579`.
+    pub id: Option<String>,
+    /// The peer's client name. For example: `qBittorrent`.
+    pub client: Option<String>,
+}
+
+/// The body of the API error responses.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    /// The kind of error.
+    pub code: ErrorCode,
+    /// The description of the error.
+    pub message: String,
+}
+
+/// The kind of error in an [`ErrorResponse`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidParam,
+    InvalidBody,
+    Unauthorized,
+    InvalidToken,
+    Forbidden,
+    NotFound,
+    Timeout,
+    NotEnabled,
+    PersistenceDisabled,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    ValidationFailed,
+    InternalError,
+    NotSupported,
+    DatabaseUnavailable,
+    /// An error code added to the tracker after this version of the client.
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorCode, ErrorResponse, Stats};
+
+    #[test]
+    fn the_stats_should_keep_the_metrics_without_attribute_by_name() {
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "torrents": 1, "seeders": 2, "completed": 3, "leechers": 4,
+                "tcp4_announces_handled": 0, "tcp4_scrapes_handled": 0,
+                "tcp6_announces_handled": 0, "tcp6_scrapes_handled": 0,
+                "udp4_announces_handled": 0, "udp4_scrapes_handled": 0,
+                "udp6_announces_handled": 0, "udp6_scrapes_handled": 0,
+                "udp4_expired_connection_id_ratio": 0.25
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(stats.torrents, 1);
+        assert_eq!(stats.other["udp4_expired_connection_id_ratio"].as_f64(), Some(0.25));
+    }
+
+    #[test]
+    fn the_unknown_error_codes_should_be_accepted() {
+        let response: ErrorResponse =
+            serde_json::from_str(r#"{"code": "new_error", "message": "error", "details": []}"#).unwrap();
+
+        assert_eq!(response.code, ErrorCode::Unknown);
+    }
+}
@@ -0,0 +1,128 @@
+//! Runs the API client against a locally spawned tracker.
+//!
+//! ```text
+//! cargo test -p torrust-tracker-api-client
+//! ```
+use std::str::FromStr;
+use std::time::Duration;
+
+use torrust_tracker::testing::configuration;
+use torrust_tracker::testing::environment::api::Started;
+use torrust_tracker_api_client::resources::ErrorCode;
+use torrust_tracker_api_client::{Auth, Client, Error, Retries};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use url::Url;
+
+const INFO_HASH: &str = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d";
+
+fn client_for(env: &Started, auth: Auth) -> Client {
+    Client::new(Url::parse(&format!("http://{}/api/", env.bind_address())).unwrap(), auth)
+}
+
+fn admin_client(env: &Started) -> Client {
+    client_for(env, Auth::Token(env.get_connection_info().api_token.unwrap()))
+}
+
+#[tokio::test]
+async fn it_should_get_the_tracker_statistics() {
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(&InfoHash::from_str(INFO_HASH).unwrap(), &PeerBuilder::default().into());
+
+    let stats = admin_client(&env).get_stats().await.unwrap();
+
+    env.stop().await;
+
+    assert_eq!(stats.torrents, 1);
+    assert_eq!(stats.seeders, 1);
+    assert!(stats.other.contains_key("udp4_connections_handled"));
+}
+
+#[tokio::test]
+async fn it_should_manage_the_authentication_keys() {
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let client = admin_client(&env);
+
+    let generated = client.generate_key(60).await.unwrap();
+    let added = client.add_key(Some("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"), None).await.unwrap();
+
+    client.delete_key(&generated.key).await.unwrap();
+    client.reload_keys().await.unwrap();
+
+    env.stop().await;
+
+    assert!(generated.expiry_time.is_some());
+    assert_eq!(added.key, "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ");
+    assert_eq!(added.expiry_time, None);
+}
+
+#[tokio::test]
+async fn it_should_manage_the_whitelist() {
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let client = admin_client(&env);
+
+    client.whitelist_torrent(INFO_HASH).await.unwrap();
+    let whitelist = client.get_whitelist().await.unwrap();
+
+    client.remove_torrent_from_whitelist(INFO_HASH).await.unwrap();
+    client.reload_whitelist().await.unwrap();
+    let reloaded_whitelist = client.get_whitelist().await.unwrap();
+
+    env.stop().await;
+
+    assert_eq!(whitelist, vec![INFO_HASH.to_string()]);
+    assert!(reloaded_whitelist.is_empty());
+}
+
+#[tokio::test]
+async fn it_should_get_the_torrents() {
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(&InfoHash::from_str(INFO_HASH).unwrap(), &PeerBuilder::default().into());
+
+    let client = admin_client(&env);
+
+    let torrent = client.get_torrent(INFO_HASH).await.unwrap().unwrap();
+    let unknown_torrent = client.get_torrent("0b3aea4adc213ce32295be85d3883a63bca25446").await.unwrap();
+    let torrents = client.get_torrents(Some(0), Some(10)).await.unwrap();
+    let torrents_by_info_hash = client.get_torrents_by_info_hash(&[INFO_HASH]).await.unwrap();
+
+    env.stop().await;
+
+    assert_eq!(torrent.seeders, 1);
+    assert_eq!(torrent.peers.len(), 1);
+    assert_eq!(unknown_torrent, None);
+    assert_eq!(torrents.len(), 1);
+    assert_eq!(torrents, torrents_by_info_hash);
+}
+
+#[tokio::test]
+async fn it_should_return_the_api_errors() {
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let result = client_for(&env, Auth::Token("invalid token".to_string())).get_stats().await;
+
+    env.stop().await;
+
+    assert!(matches!(
+        result,
+        Err(Error::Api { status: 401, response }) if response.code == ErrorCode::InvalidToken
+    ));
+}
+
+#[tokio::test]
+async fn it_should_fail_after_retrying_when_the_tracker_can_not_be_reached() {
+    let env = Started::new(&configuration::ephemeral().into()).await;
+    let client = admin_client(&env).with_retries(Retries {
+        max_retries: 2,
+        backoff: Duration::from_millis(1),
+    });
+    env.stop().await;
+
+    let result = client.get_stats().await;
+
+    assert!(matches!(result, Err(Error::Request { .. })));
+}
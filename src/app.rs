@@ -8,37 +8,74 @@
 //!
 //! The application is responsible for:
 //!
+//! - Seeding the database with the initial state declared in the configuration.
 //! - Loading data from the database when it's needed.
 //! - Starting some jobs depending on the configuration.
-//!
-//! Jobs executed always:
-//!
-//! - Health Check API
+//! - Building the [virtual trackers](crate::core::virtual_trackers), which
+//!   load their own data and start their own core jobs. The UDP and HTTP
+//!   trackers bound to them serve them instead of the main tracker.
 //!
 //! Optional jobs:
 //!
+//! - Health Check API: it's always started when the tracker is built with the `web` feature, which is enabled by `http` and `api`.
 //! - Torrent cleaner: it removes inactive peers and (optionally) peerless torrents.
-//! - UDP trackers: the user can enable multiple UDP tracker on several ports.
-//! - HTTP trackers: the user can enable multiple HTTP tracker on several ports.
-//! - Tracker REST API: the tracker API can be enabled/disabled.
+//! - Announce batching flusher: it applies the pending `announce` updates (only when micro-batching is enabled).
+//! - Asynchronous announce applier: it applies the queued peer updates of the asynchronous `announce` requests (only when the queue is enabled).
+//! - Peer reachability prober: it probes a sample of the announced peers (only when the verification is enabled).
+//! - Read snapshot refresher: it publishes the swarm metadata snapshot for `scrape` requests (only when the read snapshot is enabled).
+//! - Consistency checker: it verifies the invariants of the torrents repository (only when the consistency check is enabled).
+//! - SLO evaluator: it notifies when the `announce` requests start and stop meeting the service level objectives (only when they are enabled).
+//! - Dead-letter replayer: it retries the failed database writes (only when the dead-letter queue is enabled).
+//! - File descriptors sampler: it samples the open file descriptors (only when the admission control is enabled).
+//! - UDP trackers: the user can enable multiple UDP tracker on several ports (only when built with the `udp` feature).
+//! - HTTP trackers: the user can enable multiple HTTP tracker on several ports (only when built with the `http` feature).
+//! - Tracker REST API: the tracker API can be enabled/disabled (only when built with the `api` feature).
+//!
+//! Once all the services have started, the application logs the
+//! [`Manifest`](crate::servers::manifest::Manifest) of the bound services and,
+//! when enabled, writes it to a json file. In [development mode](crate::bootstrap::dev),
+//! the manifest also lists the configured ports that were taken.
 use std::sync::Arc;
 
 use tokio::task::JoinHandle;
-use torrust_tracker_configuration::Configuration;
+use torrust_tracker_configuration::{Configuration, Core};
 use tracing::instrument;
 
-use crate::bootstrap::jobs::{health_check_api, http_tracker, torrent_cleanup, tracker_apis, udp_tracker};
+#[cfg(feature = "web")]
+use crate::bootstrap::jobs::health_check_api;
+#[cfg(feature = "http")]
+use crate::bootstrap::jobs::http_tracker;
+#[cfg(feature = "api")]
+use crate::bootstrap::jobs::tracker_apis;
+#[cfg(feature = "udp")]
+use crate::bootstrap::jobs::udp_tracker;
+use crate::bootstrap::jobs::{
+    announce_batching, announce_export, announce_journal, async_announce, consistency_check, database_backup, dead_letter_replay,
+    event_webhook, file_descriptors, peer_reachability, read_snapshot, slo, swarm_changes, torrent_cleanup,
+    unwhitelisted_torrents,
+};
+use crate::bootstrap::preflight;
+use crate::core;
+use crate::core::virtual_trackers::VirtualTrackers;
+use crate::servers::manifest::{Manifest, PortFallback};
 use crate::servers::registar::Registar;
-use crate::{core, servers};
 
 /// # Panics
 ///
 /// Will panic if:
 ///
+/// - Can't apply the seed.
 /// - Can't retrieve tracker keys from database.
 /// - Can't load whitelist from database.
-#[instrument(skip(config, tracker))]
-pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<JoinHandle<()>> {
+/// - Can't load torrents from database.
+/// - Can't write the service manifest.
+#[instrument(skip(config, tracker, preflight))]
+pub async fn start(
+    config: &Configuration,
+    tracker: Arc<core::Tracker>,
+    preflight: Arc<preflight::Report>,
+    port_fallbacks: Vec<PortFallback>,
+) -> Vec<JoinHandle<()>> {
     if config.http_api.is_none()
         && (config.udp_trackers.is_none() || config.udp_trackers.as_ref().map_or(true, std::vec::Vec::is_empty))
         && (config.http_trackers.is_none() || config.http_trackers.as_ref().map_or(true, std::vec::Vec::is_empty))
@@ -50,25 +87,89 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
 
     let registar = Registar::default();
 
-    // Load peer keys
-    if tracker.is_private() {
-        tracker
-            .load_keys_from_database()
-            .await
-            .expect("Could not retrieve keys from database.");
+    // Warn about the fault injection, it must never be enabled in production
+    #[cfg(feature = "fault-injection")]
+    if config.core.fault_injection.is_some() {
+        tracing::warn!("Fault injection is enabled. The tracker delays requests and fails database writes on purpose");
     }
 
-    // Load whitelisted torrents
-    if tracker.is_listed() {
-        tracker
-            .load_whitelist_from_database()
-            .await
-            .expect("Could not load whitelist from database.");
+    #[cfg(not(feature = "fault-injection"))]
+    if config.core.fault_injection.is_some() {
+        tracing::warn!("Fault injection is not applied. The tracker was built without the `fault-injection` feature");
+    }
+
+    // Check the open file descriptors limit
+    if let Some(monitor) = tracker.file_descriptors() {
+        monitor.check_limit();
+    }
+
+    // Seed the database
+    if let Some(seed) = &config.seed {
+        let applied = tracker.apply_seed(seed).expect("Could not apply the seed.");
+
+        tracing::info!(
+            whitelisted = applied.whitelisted,
+            keys = applied.keys,
+            torrents = applied.torrents,
+            "Seeded the database"
+        );
+    }
+
+    load_from_database(&tracker).await;
+
+    // Build the virtual trackers and load their data
+    let virtual_trackers = VirtualTrackers::new(config.virtual_trackers.as_deref().unwrap_or_default());
+
+    for (name, virtual_tracker) in virtual_trackers.iter() {
+        tracing::info!(name = %name, "Starting virtual tracker");
+
+        load_from_database(virtual_tracker).await;
+    }
+
+    // Start Health Check API. It's started before the import of the persisted
+    // torrents, so it can report that the tracker is warming up.
+    #[cfg(feature = "web")]
+    let health_check_api_address = {
+        let (address, job) = health_check_api::start_job(
+            &config.health_check_api,
+            registar.entries(),
+            tracker.warm_up(),
+            tracker.file_descriptors(),
+            tracker.slo(),
+            preflight,
+        )
+        .await;
+        jobs.push(job);
+        Some(address)
+    };
+
+    #[cfg(not(feature = "web"))]
+    let health_check_api_address = {
+        drop(preflight);
+        tracing::info!("The Health Check API is not started. The tracker was built without the `web` feature");
+        None
+    };
+
+    // Load the persisted number of completed downloads and replay the announce journal
+    load_torrents_from_database(&config.core, &tracker).await;
+
+    for virtual_tracker_config in config.virtual_trackers.iter().flatten() {
+        load_torrents_from_database(
+            &virtual_tracker_config.core,
+            &virtual_tracker(&virtual_trackers, &virtual_tracker_config.name),
+        )
+        .await;
     }
 
     // Start the UDP blocks
+    #[cfg(feature = "udp")]
     if let Some(udp_trackers) = &config.udp_trackers {
         for udp_tracker_config in udp_trackers {
+            let tracker = match &udp_tracker_config.tracker {
+                Some(name) => virtual_tracker(&virtual_trackers, name),
+                None => tracker.clone(),
+            };
+
             if tracker.is_private() {
                 tracing::warn!(
                     "Could not start UDP tracker on: {} while in private mode. UDP is not safe for private trackers!",
@@ -82,14 +183,30 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         tracing::info!("No UDP blocks in configuration");
     }
 
+    #[cfg(not(feature = "udp"))]
+    if config
+        .udp_trackers
+        .as_ref()
+        .is_some_and(|udp_trackers| !udp_trackers.is_empty())
+    {
+        tracing::warn!("Could not start the UDP trackers. The tracker was built without the `udp` feature");
+    }
+
     // Start the HTTP blocks
+    #[cfg(feature = "http")]
     if let Some(http_trackers) = &config.http_trackers {
         for http_tracker_config in http_trackers {
+            let tracker = match &http_tracker_config.tracker {
+                Some(name) => virtual_tracker(&virtual_trackers, name),
+                None => tracker.clone(),
+            };
+
             if let Some(job) = http_tracker::start_job(
                 http_tracker_config,
-                tracker.clone(),
+                tracker,
+                virtual_trackers.hosts(),
                 registar.give_form(),
-                servers::http::Version::V1,
+                crate::servers::http::Version::V1,
             )
             .await
             {
@@ -100,13 +217,24 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         tracing::info!("No HTTP blocks in configuration");
     }
 
+    #[cfg(not(feature = "http"))]
+    if config
+        .http_trackers
+        .as_ref()
+        .is_some_and(|http_trackers| !http_trackers.is_empty())
+    {
+        tracing::warn!("Could not start the HTTP trackers. The tracker was built without the `http` feature");
+    }
+
     // Start HTTP API
+    #[cfg(feature = "api")]
     if let Some(http_api_config) = &config.http_api {
         if let Some(job) = tracker_apis::start_job(
             http_api_config,
             tracker.clone(),
+            virtual_trackers.clone(),
             registar.give_form(),
-            servers::apis::Version::V1,
+            crate::servers::apis::Version::V1,
         )
         .await
         {
@@ -116,13 +244,183 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         tracing::info!("No API block in configuration");
     }
 
-    // Start runners to remove torrents without peers, every interval
-    if config.core.inactive_peer_cleanup_interval > 0 {
-        jobs.push(torrent_cleanup::start_job(&config.core, &tracker));
+    #[cfg(not(feature = "api"))]
+    if config.http_api.is_some() {
+        tracing::warn!("Could not start the tracker API. The tracker was built without the `api` feature");
     }
 
-    // Start Health Check API
-    jobs.push(health_check_api::start_job(&config.health_check_api, registar.entries()).await);
+    start_core_jobs(&config.core, &tracker, &mut jobs);
+
+    for virtual_tracker_config in config.virtual_trackers.iter().flatten() {
+        start_core_jobs(
+            &virtual_tracker_config.core,
+            &virtual_tracker(&virtual_trackers, &virtual_tracker_config.name),
+            &mut jobs,
+        );
+    }
+
+    // Publish the actual addresses of the started services
+    registar.wait_for_registrations().await;
+
+    let manifest = Manifest::new(&registar.entries(), health_check_api_address)
+        .await
+        .with_port_fallbacks(port_fallbacks);
+
+    manifest.log();
+
+    if let Some(service_manifest) = &config.core.service_manifest {
+        manifest
+            .write(&service_manifest.path)
+            .await
+            .unwrap_or_else(|err| panic!("it should write the service manifest: {}, {err}", service_manifest.path));
+    }
 
     jobs
 }
+
+/// It loads the keys, the whitelist, the torrent policies and the static peers
+/// of the tracker.
+///
+/// # Panics
+///
+/// Will panic if the data can't be loaded from the database.
+async fn load_from_database(tracker: &Arc<core::Tracker>) {
+    // Load peer keys
+    if tracker.verifies_keys() {
+        tracker
+            .load_keys_from_database()
+            .await
+            .expect("Could not retrieve keys from database.");
+    }
+
+    // Load whitelisted torrents
+    if tracker.is_listed() {
+        tracker
+            .load_whitelist_from_database()
+            .await
+            .expect("Could not load whitelist from database.");
+    }
+
+    // Load torrent policies
+    tracker
+        .load_torrent_policies_from_database()
+        .await
+        .expect("Could not load torrent policies from database.");
+
+    // Load static peers
+    tracker
+        .load_static_peers_from_database()
+        .await
+        .expect("Could not load static peers from database.");
+}
+
+/// It loads the persisted number of completed downloads, when it's enabled,
+/// and then replays the peers in the `announce` journal, when it's enabled.
+///
+/// # Panics
+///
+/// Will panic if the torrents can't be loaded from the database.
+async fn load_torrents_from_database(core_config: &Core, tracker: &Arc<core::Tracker>) {
+    if core_config.tracker_policy.persistent_torrent_completed_stat {
+        let importer = tracker.clone();
+
+        tokio::task::spawn_blocking(move || importer.load_torrents_from_database())
+            .await
+            .expect("it should be able to join the import of the persisted torrents")
+            .expect("Could not load torrents from database.");
+    }
+
+    if core_config.announce_journal.is_some() {
+        let replayer = tracker.clone();
+
+        tokio::task::spawn_blocking(move || replayer.replay_announce_journal())
+            .await
+            .expect("it should be able to join the replay of the announce journal");
+    }
+}
+
+/// It starts the jobs enabled in the core configuration of the tracker.
+fn start_core_jobs(core_config: &Core, tracker: &Arc<core::Tracker>, jobs: &mut Vec<JoinHandle<()>>) {
+    // Start runner to apply the pending announce updates, every `max_delay_ms`
+    if let Some(announce_batching) = &core_config.announce_batching {
+        jobs.push(announce_batching::start_job(announce_batching, tracker));
+    }
+
+    // Start runner to apply the queued peer updates of the asynchronous announces
+    if core_config.async_announce.is_some() {
+        jobs.push(async_announce::start_job(tracker));
+    }
+
+    // Start runner to append the sampled announces to the export file
+    if let Some(announce_export) = &core_config.announce_export {
+        jobs.push(announce_export::start_job(announce_export, tracker));
+    }
+
+    // Start runner to flush the peer updates journal to the disk
+    if let Some(announce_journal) = &core_config.announce_journal {
+        jobs.push(announce_journal::start_job(announce_journal, tracker));
+    }
+
+    // Start runner to probe the sampled peer addresses, at most `max_probes_per_second`
+    if let Some(peer_reachability) = &core_config.peer_reachability {
+        jobs.push(peer_reachability::start_job(peer_reachability, tracker));
+    }
+
+    // Start runner to publish the read snapshot, every `refresh_interval_ms`
+    if let Some(read_snapshot) = &core_config.read_snapshot {
+        jobs.push(read_snapshot::start_job(read_snapshot, tracker));
+    }
+
+    // Start runner to verify the torrents repository, every `interval_secs`
+    if let Some(consistency_check) = &core_config.consistency_check {
+        jobs.push(consistency_check::start_job(consistency_check, tracker));
+    }
+
+    // Start runner to evaluate the service level objectives, every second
+    if core_config.slo.is_some() {
+        jobs.push(slo::start_job(tracker));
+    }
+
+    // Start runner to remove the torrents removed from the whitelist, published in the event bus
+    if core_config.listed {
+        jobs.push(unwhitelisted_torrents::start_job(tracker));
+    }
+
+    // Start runner to record the swarm changes published in the event bus
+    if core_config.swarm_changes.is_some() {
+        jobs.push(swarm_changes::start_job(tracker));
+    }
+
+    // Start runner to notify the events published in the event bus to the webhook
+    if let Some(event_webhook) = &core_config.event_webhook {
+        jobs.push(event_webhook::start_job(event_webhook, tracker));
+    }
+
+    // Start runner to back up the database, every `interval`
+    if let Some(database_backup) = &core_config.database_backup {
+        jobs.push(database_backup::start_job(database_backup, tracker));
+    }
+
+    // Start runner to retry the failed database writes, with exponential backoff
+    if let Some(dead_letter_queue) = &core_config.dead_letter_queue {
+        jobs.push(dead_letter_replay::start_job(dead_letter_queue, tracker));
+    }
+
+    // Start runner to sample the open file descriptors, every `refresh_interval_ms`
+    if let Some(file_descriptors) = &core_config.file_descriptors {
+        jobs.push(file_descriptors::start_job(file_descriptors, tracker));
+    }
+
+    // Start runners to remove torrents without peers, every interval. It's
+    // always started because the interval can be changed at runtime.
+    jobs.push(torrent_cleanup::start_job(tracker));
+}
+
+/// The virtual tracker with the given name. The names are checked when the
+/// configuration is validated.
+fn virtual_tracker(virtual_trackers: &VirtualTrackers, name: &str) -> Arc<core::Tracker> {
+    virtual_trackers
+        .get(name)
+        .unwrap_or_else(|| panic!("there should be a virtual tracker with the name: {name}"))
+        .clone()
+}
@@ -0,0 +1,6 @@
+//! Program to export and import the authentication keys.
+use torrust_tracker::console::keys;
+
+fn main() -> anyhow::Result<()> {
+    keys::run()
+}
@@ -0,0 +1,13 @@
+//! Program to verify that the tracker persistent data survives a restart.
+use std::process;
+
+use torrust_tracker::console::clients::persistence::app;
+
+#[tokio::main]
+async fn main() {
+    let report = app::run().await.expect("it should run the persistence verification");
+
+    if !report.passed {
+        process::exit(1);
+    }
+}
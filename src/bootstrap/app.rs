@@ -11,6 +11,7 @@
 //! 2. Initialize static variables.
 //! 3. Initialize logging.
 //! 4. Initialize the domain tracker.
+//! 5. Run the [preflight checks](crate::bootstrap::preflight).
 use std::sync::Arc;
 
 use torrust_tracker_clock::static_time;
@@ -19,6 +20,7 @@ use torrust_tracker_configuration::Configuration;
 use tracing::instrument;
 
 use super::config::initialize_configuration;
+use super::preflight;
 use crate::bootstrap;
 use crate::core::services::tracker_factory;
 use crate::core::Tracker;
@@ -31,9 +33,22 @@ use crate::shared::crypto::ephemeral_instance_keys;
 /// Setup can file if the configuration is invalid.
 #[must_use]
 #[instrument(skip())]
-pub fn setup() -> (Configuration, Arc<Tracker>) {
-    let configuration = initialize_configuration();
+pub fn setup() -> (Configuration, Arc<Tracker>, Arc<preflight::Report>) {
+    setup_with(initialize_configuration())
+}
 
+/// It builds the main domain [`Tracker`] struct from an already loaded
+/// configuration and runs the preflight checks.
+///
+/// The preflight warnings are logged in a single report, which is also
+/// returned so it can be exposed in the health check API.
+///
+/// # Panics
+///
+/// Setup can fail if the configuration is invalid.
+#[must_use]
+#[instrument(skip(configuration))]
+pub fn setup_with(configuration: Configuration) -> (Configuration, Arc<Tracker>, Arc<preflight::Report>) {
     if let Err(e) = configuration.validate() {
         panic!("Configuration error: {e}");
     }
@@ -42,7 +57,15 @@ pub fn setup() -> (Configuration, Arc<Tracker>) {
 
     tracing::info!("Configuration:\n{}", configuration.clone().mask_secrets().to_json());
 
-    (configuration, tracker)
+    let report = preflight::run(&configuration, &tracker);
+
+    if report.is_empty() {
+        tracing::info!("Preflight checks passed");
+    } else {
+        tracing::warn!("{report}");
+    }
+
+    (configuration, tracker, Arc::new(report))
 }
 
 /// It initializes the application with the given configuration.
@@ -2,6 +2,7 @@
 //!
 //! All environment variables are prefixed with `TORRUST_TRACKER_`.
 
+use torrust_tracker_configuration::paths::{Layout, Variable};
 use torrust_tracker_configuration::{Configuration, Info};
 
 pub const DEFAULT_PATH_CONFIG: &str = "./share/default/config/tracker.development.sqlite3.toml";
@@ -23,7 +24,49 @@ pub const DEFAULT_PATH_CONFIG: &str = "./share/default/config/tracker.developmen
 /// `./tracker.toml` file or the env var `TORRUST_TRACKER_CONFIG_TOML`.
 #[must_use]
 pub fn initialize_configuration() -> Configuration {
-    let info = Info::new(DEFAULT_PATH_CONFIG.to_string()).expect("info to load configuration is not valid");
+    initialize_configuration_from(None, &PathOptions::default())
+}
+
+/// The options for the [path variables](torrust_tracker_configuration::paths)
+/// in the configuration. They have priority over the environment variables.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PathOptions {
+    pub layout: Option<Layout>,
+    pub data_dir: Option<String>,
+    pub config_dir: Option<String>,
+    pub log_dir: Option<String>,
+}
+
+/// It loads the application configuration like [`initialize_configuration`],
+/// but from the given config file, when there is one, instead of the file in
+/// the `TORRUST_TRACKER_CONFIG_TOML_PATH` environment variable, and with
+/// the given path options.
+///
+/// # Panics
+///
+/// Will panic if it can't load the configuration.
+#[must_use]
+pub fn initialize_configuration_from(config_toml_path: Option<String>, paths: &PathOptions) -> Configuration {
+    let mut info = Info::new(DEFAULT_PATH_CONFIG.to_string()).expect("info to load configuration is not valid");
+
+    if let Some(config_toml_path) = config_toml_path {
+        info = info.with_config_toml_path(config_toml_path);
+    }
+
+    if let Some(layout) = paths.layout {
+        info = info.with_paths_layout(layout);
+    }
+
+    for (variable, value) in [
+        (Variable::DataDir, &paths.data_dir),
+        (Variable::ConfigDir, &paths.config_dir),
+        (Variable::LogDir, &paths.log_dir),
+    ] {
+        if let Some(value) = value {
+            info = info.with_path_variable(variable, value.clone());
+        }
+    }
+
     Configuration::load(&info).expect("error loading configuration from sources")
 }
 
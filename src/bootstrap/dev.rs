@@ -0,0 +1,159 @@
+//! Development mode.
+//!
+//! When the tracker is started with the `--dev` flag (`torrust-tracker run
+//! --dev`), a configured port that is already in use does not prevent the
+//! service from starting. The service is bound to an ephemeral port assigned
+//! by the operating system instead, and both the configured and the actual
+//! addresses are published in the [`Manifest`](crate::servers::manifest::Manifest).
+//!
+//! It makes it possible to run several instances locally, or in parallel CI
+//! jobs, without editing the configuration. It should not be used in
+//! production, where a taken port is a configuration error.
+//!
+//! > **NOTICE**: the ports are probed before starting the services, so
+//! > another process can still take a port in between.
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, UdpSocket};
+
+use torrust_tracker_configuration::{BindAddress, Configuration};
+
+use crate::servers::manifest::PortFallback;
+use crate::servers::registar::ServiceKind;
+
+/// It replaces the configured ports that are already in use with port `0`, so
+/// the services are bound to ephemeral ports. It returns the configured
+/// addresses that were replaced.
+///
+/// Services bound to port `0` or to a Unix domain socket are not changed.
+#[must_use]
+pub fn fall_back_to_ephemeral_ports(config: &mut Configuration) -> Vec<PortFallback> {
+    let mut fallbacks = Vec::new();
+
+    for udp_tracker in config.udp_trackers.iter_mut().flatten() {
+        for bind_address in udp_tracker.bind_address.iter_mut() {
+            fall_back_bind_address(ServiceKind::UdpTracker, bind_address, is_udp_port_taken, &mut fallbacks);
+        }
+    }
+
+    for http_tracker in config.http_trackers.iter_mut().flatten() {
+        for bind_address in http_tracker.bind_address.iter_mut() {
+            fall_back_bind_address(ServiceKind::HttpTracker, bind_address, is_tcp_port_taken, &mut fallbacks);
+        }
+    }
+
+    if let Some(http_api) = &mut config.http_api {
+        fall_back_bind_address(
+            ServiceKind::HttpApi,
+            &mut http_api.bind_address,
+            is_tcp_port_taken,
+            &mut fallbacks,
+        );
+    }
+
+    #[cfg(feature = "web")]
+    fall_back_bind_address(
+        ServiceKind::HealthCheckApi,
+        &mut config.health_check_api.bind_address,
+        is_tcp_port_taken,
+        &mut fallbacks,
+    );
+
+    fallbacks
+}
+
+fn fall_back_bind_address(
+    service: ServiceKind,
+    bind_address: &mut BindAddress,
+    is_taken: fn(&SocketAddr) -> bool,
+    fallbacks: &mut Vec<PortFallback>,
+) {
+    if let Some(mut address) = bind_address.socket_addr() {
+        fall_back(service, &mut address, is_taken, fallbacks);
+
+        *bind_address = BindAddress::Socket(address);
+    }
+}
+
+fn fall_back(
+    service: ServiceKind,
+    address: &mut SocketAddr,
+    is_taken: fn(&SocketAddr) -> bool,
+    fallbacks: &mut Vec<PortFallback>,
+) {
+    if address.port() == 0 || !is_taken(address) {
+        return;
+    }
+
+    tracing::warn!(?service, configured = %address, "Configured port is in use, falling back to an ephemeral port");
+
+    fallbacks.push(PortFallback::new(service, *address));
+
+    address.set_port(0);
+}
+
+fn is_tcp_port_taken(address: &SocketAddr) -> bool {
+    matches!(TcpListener::bind(address), Err(err) if err.kind() == ErrorKind::AddrInUse)
+}
+
+fn is_udp_port_taken(address: &SocketAddr) -> bool {
+    matches!(UdpSocket::bind(address), Err(err) if err.kind() == ErrorKind::AddrInUse)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener, UdpSocket};
+
+    use torrust_tracker_configuration::BindAddress;
+    use torrust_tracker_test_helpers::configuration;
+
+    use super::fall_back_to_ephemeral_ports;
+    use crate::servers::manifest::PortFallback;
+    use crate::servers::registar::ServiceKind;
+
+    fn socket_addr(bind_address: &BindAddress) -> SocketAddr {
+        bind_address.socket_addr().unwrap()
+    }
+
+    #[test]
+    fn it_should_not_change_the_ephemeral_ports() {
+        let mut config = configuration::ephemeral();
+        let expected = config.clone();
+
+        assert!(fall_back_to_ephemeral_ports(&mut config).is_empty());
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn it_should_fall_back_to_an_ephemeral_port_when_the_configured_tcp_port_is_taken() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken = listener.local_addr().unwrap();
+
+        let mut config = configuration::ephemeral();
+        config.http_trackers.as_mut().unwrap()[0].bind_address = BindAddress::Socket(taken).into();
+
+        let fallbacks = fall_back_to_ephemeral_ports(&mut config);
+
+        assert_eq!(fallbacks, vec![PortFallback::new(ServiceKind::HttpTracker, taken)]);
+        assert_eq!(
+            socket_addr(config.http_trackers.unwrap()[0].bind_address.first()),
+            SocketAddr::new(taken.ip(), 0)
+        );
+    }
+
+    #[test]
+    fn it_should_fall_back_to_an_ephemeral_port_when_the_configured_udp_port_is_taken() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let taken = socket.local_addr().unwrap();
+
+        let mut config = configuration::ephemeral();
+        config.udp_trackers.as_mut().unwrap()[0].bind_address = BindAddress::Socket(taken).into();
+
+        let fallbacks = fall_back_to_ephemeral_ports(&mut config);
+
+        assert_eq!(fallbacks, vec![PortFallback::new(ServiceKind::UdpTracker, taken)]);
+        assert_eq!(
+            socket_addr(config.udp_trackers.unwrap()[0].bind_address.first()),
+            SocketAddr::new(taken.ip(), 0)
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Job that periodically applies the pending `announce` updates.
+//!
+//! It's only started when the `announce` micro-batching is enabled. Shard
+//! batches are applied as soon as they are full, but this job guarantees
+//! that an update does not stay in the queue for more than `max_delay_ms`
+//! milliseconds.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `announce_batching` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::AnnounceBatching;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for flushing the pending `announce` updates.
+///
+/// The flushing task is executed on a `max_delay_ms` interval.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &AnnounceBatching, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.max_delay_ms;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping announce batching job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.flush_announce_batches();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
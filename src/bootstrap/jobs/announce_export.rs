@@ -0,0 +1,92 @@
+//! Job that exports the `announce` data to a NDJSON file.
+//!
+//! It's only started when the announce export is enabled. The job subscribes
+//! to the tracker domain events and appends one json line per exported
+//! `announce` to the configured file.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `announce_export` options.
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::AnnounceExport;
+use tracing::instrument;
+
+use torrust_tracker_clock::clock::Time;
+
+use crate::core;
+use crate::core::announce_export::Exporter;
+use crate::CurrentClock;
+
+/// It starts a job for exporting the `announce` data.
+///
+/// The file is flushed every time the job catches up with the published
+/// events. The peers that have not announced for longer than the peer timeout
+/// are forgotten at the same time.
+///
+/// # Panics
+///
+/// Will panic if the export file can't be opened.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &AnnounceExport, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let mut receiver = tracker.subscribe();
+    let mut exporter = Exporter::new(config);
+    let path = config.path.clone();
+    let tracker = tracker.clone();
+
+    tokio::spawn(async move {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap_or_else(|err| panic!("it should open the announce export file: {path}, {err}"));
+
+        let mut writer = BufWriter::new(file);
+
+        tracing::info!("Exporting announces to: {path}");
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping announce export job..");
+                    break;
+                }
+                event = receiver.recv() => {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!("Announce export is falling behind, {skipped} events were not exported");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
+
+                    if let Some(record) = exporter.handle(&event) {
+                        let line = format!("{}\n", record.to_json_line());
+
+                        if let Err(err) = writer.write_all(line.as_bytes()).await {
+                            tracing::error!("Failed to write to the announce export file: {path}, {err}");
+                        }
+                    }
+
+                    if receiver.is_empty() {
+                        if let Err(err) = writer.flush().await {
+                            tracing::error!("Failed to flush the announce export file: {path}, {err}");
+                        }
+
+                        let max_peer_timeout = Duration::from_secs(u64::from(tracker.get_peer_cleanup().max_peer_timeout));
+                        exporter.remove_inactive_peers(CurrentClock::now_sub(&max_peer_timeout).unwrap_or_default());
+                    }
+                }
+            }
+        }
+
+        drop(writer.flush().await);
+    })
+}
@@ -0,0 +1,59 @@
+//! Job that flushes the `announce` journal to the disk.
+//!
+//! It's only started when the announce journal is enabled. The records are
+//! appended by the tracker in the `announce` path. With the `interval` fsync
+//! policy, the job flushes them to the disk every `fsync_interval_ms`
+//! milliseconds. It always flushes the journal when the tracker stops.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `announce_journal` options.
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::{AnnounceJournal, JournalFsync};
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for flushing the `announce` journal.
+///
+/// The file is flushed from a blocking thread, so it's not flushed from the
+/// async runtime threads.
+///
+/// # Panics
+///
+/// Will panic if the announce journal is not enabled in the tracker.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &AnnounceJournal, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let journal = tracker
+        .announce_journal()
+        .expect("it should have the announce journal enabled");
+    let fsync_interval = match config.fsync {
+        JournalFsync::Interval => Some(Duration::from_millis(config.fsync_interval_ms)),
+        JournalFsync::Always | JournalFsync::Never => None,
+    };
+
+    tokio::spawn(async move {
+        // The period is not used without the `interval` fsync policy
+        let mut interval = tokio::time::interval(fsync_interval.unwrap_or(Duration::from_secs(3600)));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping announce journal job..");
+                    break;
+                }
+                _ = interval.tick(), if fsync_interval.is_some() => {
+                    let journal = journal.clone();
+
+                    drop(tokio::task::spawn_blocking(move || journal.sync()).await);
+                }
+            }
+        }
+
+        drop(tokio::task::spawn_blocking(move || journal.sync()).await);
+    })
+}
@@ -0,0 +1,47 @@
+//! Job that applies the queued peer updates of the asynchronous `announce`
+//! requests.
+//!
+//! It's only started when the asynchronous `announce` queue is enabled. The
+//! updates are applied in arrival order, as soon as they are queued.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `async_announce` options.
+
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for applying the queued peer updates.
+///
+/// # Panics
+///
+/// Will panic if the asynchronous `announce` queue is not enabled.
+#[must_use]
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let queued = tracker
+        .async_announce_queued()
+        .expect("the asynchronous announce queue should be enabled");
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping asynchronous announce job..");
+                    break;
+                }
+                () = queued.notified() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.apply_async_announces();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,47 @@
+//! Job that periodically verifies the invariants of the torrents repository.
+//!
+//! It's only started when the consistency check is enabled. The first check
+//! runs as soon as the job starts, and then every `interval_secs` seconds.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `consistency_check` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::ConsistencyCheck;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for checking the consistency of the torrents repository.
+///
+/// The checking task is executed on an `interval_secs` interval.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &ConsistencyCheck, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.interval_secs;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping consistency check job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.check_consistency();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
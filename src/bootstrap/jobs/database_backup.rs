@@ -0,0 +1,49 @@
+//! Job that runs a task on intervals to back up the database.
+//!
+//! It writes a new backup file every `interval` seconds and removes the oldest
+//! ones, keeping only the newest `retention` backups.
+//!
+//! Refer to the [`backup`](crate::core::backup) module for more information.
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::DatabaseBackup;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for backing up the database.
+///
+/// The backup task is executed on the `interval` in the `database_backup`
+/// configuration. The first backup is written after the first interval.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &DatabaseBackup, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.interval;
+
+    tokio::spawn(async move {
+        let interval = std::time::Duration::from_secs(interval);
+        let mut interval = tokio::time::interval(interval);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping database backup job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        match tracker.backup_database().await {
+                            Ok(file) => tracing::info!("Database backed up to: {file}"),
+                            Err(err) => tracing::error!("Failed to back up the database: {err}"),
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
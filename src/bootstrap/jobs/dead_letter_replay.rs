@@ -0,0 +1,57 @@
+//! Job that retries the failed database writes in the dead-letter queue.
+//!
+//! It waits `min_backoff_ms` milliseconds between retries while they succeed
+//! (or there is nothing to retry). When some writes fail again, the wait is
+//! doubled after each retry, up to `max_backoff_ms` milliseconds.
+//!
+//! Refer to the [`dead_letter`](crate::core::dead_letter) module for more
+//! information.
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::DeadLetterQueue;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for retrying the failed database writes.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &DeadLetterQueue, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let min_backoff = Duration::from_millis(config.min_backoff_ms);
+    let max_backoff = Duration::from_millis(config.max_backoff_ms);
+
+    tokio::spawn(async move {
+        let mut backoff = min_backoff;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping dead-letter replay job..");
+                    break;
+                }
+                () = tokio::time::sleep(backoff) => {
+                    let Some(tracker) = weak_tracker.upgrade() else {
+                        break;
+                    };
+
+                    backoff = match tracker.replay_dead_letters().await {
+                        Some(replay) if replay.failed > 0 => {
+                            tracing::warn!(replayed = replay.replayed, failed = replay.failed, "Failed to replay database writes");
+                            (backoff * 2).min(max_backoff)
+                        }
+                        Some(replay) => {
+                            if replay.replayed > 0 {
+                                tracing::info!(replayed = replay.replayed, "Replayed database writes");
+                            }
+                            min_backoff
+                        }
+                        None => break,
+                    };
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,83 @@
+//! Job that notifies the domain events of the tracker to a webhook.
+//!
+//! It's only started when the event webhook is enabled. The job subscribes
+//! to the tracker domain events and sends a `POST` request with each event to
+//! the webhook, in the same order they were published. The peers announcing
+//! themselves are not notified.
+//!
+//! The webhook receives a json body like this:
+//!
+//! ```json
+//! {
+//!   "type": "key_revoked",
+//!   "key": "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"
+//! }
+//! ```
+//!
+//! The event types are: `torrent_created`, `torrent_removed`,
+//! `torrent_unwhitelisted`, `key_created` and `key_revoked`. Failed requests
+//! are only logged, they are not retried.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `event_webhook` options.
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::EventWebhook;
+use tracing::instrument;
+
+use crate::core;
+use crate::core::events::Event;
+
+/// Time to wait for the webhook to answer.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// It starts a job for notifying the domain events to the webhook.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &EventWebhook, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let mut receiver = tracker.subscribe();
+    let webhook_url = config.url.clone();
+    let client = reqwest::Client::new();
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping event webhook job..");
+                    break;
+                }
+                event = receiver.recv() => {
+                    let event = match event {
+                        Ok(Event::PeerAnnounced { .. }) => continue,
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!(webhook_url, "Event webhook is falling behind, {skipped} events were not notified");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
+
+                    notify(&client, &webhook_url, &event).await;
+                }
+            }
+        }
+    })
+}
+
+/// It sends the event to the webhook. Failures are only logged.
+async fn notify(client: &reqwest::Client, webhook_url: &str, event: &Event) {
+    let result = client
+        .post(webhook_url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(event)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+
+    if let Err(err) = result {
+        tracing::error!(webhook_url, %err, "Could not notify the event webhook");
+    }
+}
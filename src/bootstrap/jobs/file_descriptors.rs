@@ -0,0 +1,50 @@
+//! Job that periodically samples the open file descriptors.
+//!
+//! It's only started when the admission control based on the usage of file
+//! descriptors is enabled. The HTTP trackers reject new connections while the
+//! last sample is close to the process limit.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `file_descriptors` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::FileDescriptors;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for sampling the open file descriptors.
+///
+/// The sampling task is executed on a `refresh_interval_ms` interval.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &FileDescriptors, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.refresh_interval_ms;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping file descriptors job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        if let Some(monitor) = tracker.file_descriptors() {
+                            monitor.refresh();
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -3,7 +3,7 @@
 //! The [`health_check_api::start_job`](crate::bootstrap::jobs::health_check_api::start_job)
 //! function starts the Health Check REST API.
 //!
-//! The [`health_check_api::start_job`](crate::bootstrap::jobs::health_check_api::start_job)  
+//! The [`health_check_api::start_job`](crate::bootstrap::jobs::health_check_api::start_job)\
 //! function spawns a new asynchronous task, that tasks is the "**launcher**".
 //! The "**launcher**" starts the actual server and sends a message back
 //! to the main application.
@@ -14,12 +14,19 @@
 //! Refer to the [configuration documentation](https://docs.rs/torrust-tracker-configuration)
 //! for the API configuration options.
 
+use std::net::SocketAddr;
+use std::sync::Arc;
+
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 use torrust_tracker_configuration::HealthCheckApi;
 use tracing::instrument;
 
 use super::Started;
+use crate::bootstrap::preflight;
+use crate::core::file_descriptors::Monitor;
+use crate::core::slo;
+use crate::core::torrent::import::WarmUp;
 use crate::servers::health_check_api::{server, HEALTH_CHECK_API_LOG_TARGET};
 use crate::servers::logging::STARTED_ON;
 use crate::servers::registar::ServiceRegistry;
@@ -32,13 +39,31 @@ use crate::servers::signals::Halted;
 /// This task will send a message to the main application process to notify
 /// that the API server was successfully started.
 ///
+/// The `warm_up` state is used to report that the tracker is still importing
+/// the persisted torrents, the `file_descriptors` usage to warn that the
+/// HTTP trackers are rejecting connections, and the `slo` monitor to report
+/// that the service level objectives are not met. The `preflight` warnings
+/// found at startup are included in all the reports.
+///
+/// It returns the address the server is bound to, along with the job.
+///
 /// # Panics
 ///
 /// It would panic if unable to send the  `ApiServerJobStarted` notice.
 #[allow(clippy::async_yields_async)]
-#[instrument(skip(config, register))]
-pub async fn start_job(config: &HealthCheckApi, register: ServiceRegistry) -> JoinHandle<()> {
-    let bind_addr = config.bind_address;
+#[instrument(skip(config, register, warm_up, file_descriptors, slo, preflight))]
+pub async fn start_job(
+    config: &HealthCheckApi,
+    register: ServiceRegistry,
+    warm_up: Arc<WarmUp>,
+    file_descriptors: Option<Arc<Monitor>>,
+    slo: Option<Arc<slo::Monitor>>,
+    preflight: Arc<preflight::Report>,
+) -> (SocketAddr, JoinHandle<()>) {
+    let bind_addr = config
+        .bind_address
+        .socket_addr()
+        .expect("the Health Check API bind address should be resolved when the configuration is loaded");
 
     let (tx_start, rx_start) = oneshot::channel::<Started>();
     let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
@@ -49,7 +74,16 @@ pub async fn start_job(config: &HealthCheckApi, register: ServiceRegistry) -> Jo
     let join_handle = tokio::spawn(async move {
         tracing::info!(target: HEALTH_CHECK_API_LOG_TARGET, "Starting on: {protocol}://{}", bind_addr);
 
-        let handle = server::start(bind_addr, tx_start, rx_halt, register);
+        let handle = server::start(
+            bind_addr,
+            tx_start,
+            rx_halt,
+            register,
+            warm_up,
+            file_descriptors,
+            slo,
+            preflight,
+        );
 
         if let Ok(()) = handle.await {
             tracing::info!(target: HEALTH_CHECK_API_LOG_TARGET, "Stopped server running on: {protocol}://{}", bind_addr);
@@ -57,17 +91,22 @@ pub async fn start_job(config: &HealthCheckApi, register: ServiceRegistry) -> Jo
     });
 
     // Wait until the server sends the started message
-    match rx_start.await {
-        Ok(msg) => tracing::info!(target: HEALTH_CHECK_API_LOG_TARGET, "{STARTED_ON}: {protocol}://{}", msg.address),
+    let address = match rx_start.await {
+        Ok(msg) => {
+            tracing::info!(target: HEALTH_CHECK_API_LOG_TARGET, "{STARTED_ON}: {protocol}://{}", msg.address);
+            msg.address
+        }
         Err(e) => panic!("the Health Check API server was dropped: {e}"),
-    }
+    };
 
     // Wait until the server finishes
-    tokio::spawn(async move {
+    let job = tokio::spawn(async move {
         assert!(!tx_halt.is_closed(), "Halt channel for Health Check API should be open");
 
         join_handle
             .await
             .expect("it should be able to join to the Health Check API server task");
-    })
+    });
+
+    (address, job)
 }
@@ -10,81 +10,188 @@
 //!
 //! The "**launcher**" is an intermediary thread that decouples the HTTP servers from the process that handles it. The HTTP could be used independently in the future.
 //! In that case it would not need to notify a parent process.
+//!
+//! When the HTTP tracker is bound to a Unix domain socket, there is no "**launcher**".
+//! The socket is bound before spawning the task that runs the server, and the service is registered for the health check
+//! with its socket path. See [`unix_socket`].
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum_server::tls_rustls::RustlsConfig;
+use camino::Utf8Path;
 use tokio::task::JoinHandle;
-use torrust_tracker_configuration::HttpTracker;
+use torrust_tracker_configuration::{BindAddress, HttpTracker};
 use tracing::instrument;
 
 use super::make_rust_tls;
 use crate::core;
-use crate::servers::http::server::{HttpServer, Launcher};
-use crate::servers::http::Version;
-use crate::servers::registar::ServiceRegistrationForm;
+use crate::core::virtual_trackers::VirtualHosts;
+use crate::servers::custom_axum_server::RustlsConfig;
+use crate::servers::http::server::{check_unix_socket_fn, HttpServer, Launcher};
+use crate::servers::http::v1::routes::router;
+use crate::servers::http::{Version, HTTP_TRACKER_LOG_TARGET};
+use crate::servers::registar::{ServiceKind, ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::signals::Halted;
+use crate::servers::unix_socket::{self, UNIX_SOCKET_CLIENT_ADDR};
 
 /// It starts a new HTTP server with the provided configuration and version.
 ///
+/// The requests are served by the given tracker, except the ones routed to a
+/// virtual tracker by their `Host`.
+///
 /// Right now there is only one version but in the future we could support more than one HTTP tracker version at the same time.
 /// This feature allows supporting breaking changes on `BitTorrent` BEPs.
 ///
 /// # Panics
 ///
-/// It would panic if the `config::HttpTracker` struct would contain inappropriate values.
+/// It would panic if the `config::HttpTracker` struct would contain inappropriate values, or if unable to bind to the
+/// Unix domain socket.
 ///
-#[instrument(skip(config, tracker, form))]
+#[instrument(skip(config, tracker, virtual_hosts, form))]
 pub async fn start_job(
     config: &HttpTracker,
     tracker: Arc<core::Tracker>,
+    virtual_hosts: VirtualHosts,
     form: ServiceRegistrationForm,
     version: Version,
 ) -> Option<JoinHandle<()>> {
-    let socket = config.bind_address;
+    // A Unix domain socket can't be bound together with other addresses.
+    if let BindAddress::Unix(path) = config.bind_address.first() {
+        return match version {
+            Version::V1 => Some(
+                start_v1_on_unix_socket(path, config, tracker, &virtual_hosts, form)
+                    .unwrap_or_else(|err| panic!("it should be able to bind the http tracker to unix:{path}: {err}")),
+            ),
+        };
+    }
+
+    let sockets = config
+        .bind_address
+        .iter()
+        .map(|bind_address| {
+            bind_address
+                .socket_addr()
+                .expect("the HTTP tracker bind address should be resolved when the configuration is loaded")
+        })
+        .collect();
 
     let tls = make_rust_tls(&config.tsl_config)
         .await
         .map(|tls| tls.expect("it should have a valid http tracker tls configuration"));
 
     match version {
-        Version::V1 => Some(start_v1(socket, tls, tracker.clone(), form).await),
+        Version::V1 => Some(start_v1(sockets, tls, config, tracker.clone(), virtual_hosts, form).await),
     }
 }
 
+#[instrument(skip(config, tracker, virtual_hosts, form))]
+fn start_v1_on_unix_socket(
+    path: &Utf8Path,
+    config: &HttpTracker,
+    tracker: Arc<core::Tracker>,
+    virtual_hosts: &VirtualHosts,
+    form: ServiceRegistrationForm,
+) -> std::io::Result<JoinHandle<()>> {
+    let server = unix_socket::Server::bind(path)?;
+
+    let router = router(
+        tracker,
+        virtual_hosts,
+        UNIX_SOCKET_CLIENT_ADDR,
+        config.key_path_format,
+        &config.health,
+        core::AnnounceMode::new(config.async_announce),
+        config.request_limits,
+    );
+
+    form.send(ServiceRegistration::unix_socket(
+        server.path().to_path_buf(),
+        ServiceKind::HttpTracker,
+        check_unix_socket_fn,
+    ))
+    .expect("it should be able to send service registration");
+
+    Ok(tokio::spawn(async move {
+        // The server only stops on the global shutdown signal.
+        let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
+
+        server.serve(router, rx_halt, HTTP_TRACKER_LOG_TARGET).await;
+
+        drop(tx_halt);
+    }))
+}
+
 #[allow(clippy::async_yields_async)]
-#[instrument(skip(socket, tls, tracker, form))]
+#[instrument(skip(sockets, tls, config, tracker, virtual_hosts, form))]
 async fn start_v1(
-    socket: SocketAddr,
+    sockets: Vec<SocketAddr>,
     tls: Option<RustlsConfig>,
+    config: &HttpTracker,
     tracker: Arc<core::Tracker>,
+    virtual_hosts: VirtualHosts,
     form: ServiceRegistrationForm,
 ) -> JoinHandle<()> {
-    let server = HttpServer::new(Launcher::new(socket, tls))
-        .start(tracker, form)
+    let mut servers = Vec::new();
+    let mut registrations = Vec::new();
+
+    // One server for each address, all registered as a single service.
+    for bind_to in sockets {
+        let (tx_registration, rx_registration) = tokio::sync::oneshot::channel::<ServiceRegistration>();
+
+        let server = HttpServer::new(Launcher::new(
+            bind_to,
+            tls.clone(),
+            config.proxy_protocol,
+            config.key_path_format,
+            config.health.clone(),
+            core::AnnounceMode::new(config.async_announce),
+            virtual_hosts.clone(),
+            config.request_limits,
+            config.bind_device.clone(),
+        ))
+        .start(tracker.clone(), tx_registration)
         .await
         .expect("it should be able to start to the http tracker");
 
-    tokio::spawn(async move {
-        assert!(
-            !server.state.halt_task.is_closed(),
-            "Halt channel for HTTP tracker should be open"
+        registrations.push(
+            rx_registration
+                .await
+                .expect("it should receive the service registration from the http tracker"),
         );
-        server
-            .state
-            .task
-            .await
-            .expect("it should be able to join to the http tracker task");
+
+        servers.push(server);
+    }
+
+    form.send(ServiceRegistration::group(registrations))
+        .expect("it should be able to send service registration");
+
+    tokio::spawn(async move {
+        let tasks = servers.into_iter().map(|server| async move {
+            assert!(
+                !server.state.halt_task.is_closed(),
+                "Halt channel for HTTP tracker should be open"
+            );
+            server
+                .state
+                .task
+                .await
+                .expect("it should be able to join to the http tracker task");
+        });
+
+        futures::future::join_all(tasks).await;
     })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::net::SocketAddr;
     use std::sync::Arc;
 
+    use torrust_tracker_configuration::BindAddresses;
     use torrust_tracker_test_helpers::configuration::ephemeral_public;
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::http_tracker::start_job;
+    use crate::core::virtual_trackers::VirtualHosts;
     use crate::servers::http::Version;
     use crate::servers::registar::Registar;
 
@@ -96,8 +203,35 @@ mod tests {
         let tracker = initialize_with_configuration(&cfg);
         let version = Version::V1;
 
-        start_job(config, tracker, Registar::default().give_form(), version)
+        start_job(
+            config,
+            tracker,
+            VirtualHosts::default(),
+            Registar::default().give_form(),
+            version,
+        )
+        .await
+        .expect("it should be able to join to the http tracker start-job");
+    }
+
+    #[tokio::test]
+    async fn it_should_register_an_http_tracker_bound_to_more_than_one_address_as_a_single_service() {
+        let cfg = Arc::new(ephemeral_public());
+        let mut config = cfg.http_trackers.clone().expect("missing HTTP tracker configuration")[0].clone();
+        config.bind_address = BindAddresses::new(
+            config.bind_address.first().clone(),
+            vec!["127.0.0.1:0".parse::<SocketAddr>().unwrap().into()],
+        );
+        let tracker = initialize_with_configuration(&cfg);
+        let registar = Registar::default();
+
+        start_job(&config, tracker, VirtualHosts::default(), registar.give_form(), Version::V1)
             .await
             .expect("it should be able to join to the http tracker start-job");
+
+        // The registration is inserted by a background task.
+        tokio::task::yield_now().await;
+
+        assert_eq!(registar.entries().lock().await.len(), 1);
     }
 }
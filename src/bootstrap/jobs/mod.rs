@@ -6,11 +6,29 @@
 //! 2. Launch all the application services as concurrent jobs.
 //!
 //! This modules contains all the functions needed to start those jobs.
+pub mod announce_batching;
+pub mod announce_export;
+pub mod announce_journal;
+pub mod async_announce;
+pub mod consistency_check;
+pub mod database_backup;
+pub mod dead_letter_replay;
+pub mod event_webhook;
+pub mod file_descriptors;
+#[cfg(feature = "web")]
 pub mod health_check_api;
+#[cfg(feature = "http")]
 pub mod http_tracker;
+pub mod peer_reachability;
+pub mod read_snapshot;
+pub mod slo;
+pub mod swarm_changes;
 pub mod torrent_cleanup;
+#[cfg(feature = "api")]
 pub mod tracker_apis;
+#[cfg(feature = "udp")]
 pub mod udp_tracker;
+pub mod unwhitelisted_torrents;
 
 /// This is the message that the "launcher" spawned task sends to the main
 /// application process to notify the service was successfully started.
@@ -20,6 +38,7 @@ pub struct Started {
     pub address: std::net::SocketAddr,
 }
 
+#[cfg(feature = "web")]
 #[instrument(skip(opt_tsl_config))]
 pub async fn make_rust_tls(opt_tsl_config: &Option<TslConfig>) -> Option<Result<RustlsConfig, Error>> {
     match opt_tsl_config {
@@ -36,19 +55,25 @@ pub async fn make_rust_tls(opt_tsl_config: &Option<TslConfig>) -> Option<Result<
             tracing::info!("Using https: cert path: {cert}.");
             tracing::info!("Using https: key path: {key}.");
 
-            Some(
+            #[cfg(feature = "tls")]
+            return Some(
                 RustlsConfig::from_pem_file(cert, key)
                     .await
                     .map_err(|err| Error::BadTlsConfig {
                         source: (Arc::new(err) as DynError).into(),
                     }),
-            )
+            );
+
+            #[cfg(not(feature = "tls"))]
+            return Some(Err(Error::TlsNotCompiled {
+                location: Location::caller(),
+            }));
         }
         None => None,
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "web"))]
 mod tests {
 
     use camino::Utf8PathBuf;
@@ -84,14 +109,21 @@ mod tests {
 }
 
 use std::panic::Location;
+#[cfg(all(feature = "tls", feature = "web"))]
 use std::sync::Arc;
 
-use axum_server::tls_rustls::RustlsConfig;
 use thiserror::Error;
+#[cfg(feature = "web")]
 use torrust_tracker_configuration::TslConfig;
-use torrust_tracker_located_error::{DynError, LocatedError};
+#[cfg(all(feature = "tls", feature = "web"))]
+use torrust_tracker_located_error::DynError;
+use torrust_tracker_located_error::LocatedError;
+#[cfg(feature = "web")]
 use tracing::instrument;
 
+#[cfg(feature = "web")]
+use crate::servers::custom_axum_server::RustlsConfig;
+
 /// Error returned by the Bootstrap Process.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -99,6 +131,10 @@ pub enum Error {
     #[error("tls config missing")]
     MissingTlsConfig { location: &'static Location<'static> },
 
+    /// Enabled tls but the tracker was built without the `tls` feature.
+    #[error("tls config provided but the tracker was built without the `tls` feature, {location}")]
+    TlsNotCompiled { location: &'static Location<'static> },
+
     /// Unable to parse tls Config.
     #[error("bad tls config: {source}")]
     BadTlsConfig {
@@ -0,0 +1,60 @@
+//! Job that probes the sampled peer addresses.
+//!
+//! It's only started when the peer reachability verification is enabled. The
+//! `Tracker` only queues the sampled peers while handling the `announce`
+//! requests, this job consumes the queue starting at most
+//! `max_probes_per_second` probes per second.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `peer_reachability` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::PeerReachability;
+use tracing::instrument;
+
+use crate::core;
+use crate::core::reachability;
+
+/// It starts a job for probing the sampled peer addresses.
+///
+/// Every probe runs in its own task, so that slow peers do not delay the
+/// following probes.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &PeerReachability, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let period = Duration::from_secs(1) / config.max_probes_per_second;
+    let timeout = Duration::from_millis(config.probe_timeout_ms);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping peer reachability job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let Some(tracker) = weak_tracker.upgrade() else {
+                        break;
+                    };
+
+                    if let Some(probe) = tracker.next_reachability_probe() {
+                        tokio::spawn(async move {
+                            let reachability = reachability::probe(&probe.peer_addr, timeout).await;
+
+                            tracing::debug!(peer_addr = %probe.peer_addr, ?reachability, "peer probed");
+
+                            tracker.record_reachability(&probe, reachability);
+                        });
+                    }
+                }
+            }
+        }
+    })
+}
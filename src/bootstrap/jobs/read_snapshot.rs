@@ -0,0 +1,48 @@
+//! Job that periodically publishes the read snapshot of the swarm metadata.
+//!
+//! It's only started when the read snapshot is enabled. The first snapshot is
+//! published as soon as the job starts, and then every `refresh_interval_ms`
+//! milliseconds.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `read_snapshot` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::ReadSnapshot;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for refreshing the read snapshot.
+///
+/// The refreshing task is executed on a `refresh_interval_ms` interval.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &ReadSnapshot, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.refresh_interval_ms;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping read snapshot job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.refresh_read_snapshot();
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,119 @@
+//! Job that periodically evaluates the service level objectives.
+//!
+//! It's only started when the service level objectives are enabled. It logs
+//! when the `announce` requests start and stop meeting the objectives, and
+//! notifies the webhook, if there is one.
+//!
+//! The webhook receives a `POST` request with a json body like this:
+//!
+//! ```json
+//! {
+//!   "status": "degraded",
+//!   "message": "announce error rate is above 1% (3 of 120 requests)",
+//!   "evaluation": {
+//!     "requests": 120,
+//!     "errors": 3,
+//!     "slow": 0,
+//!     "latency_met": true,
+//!     "error_rate_met": false,
+//!     "latency_percentile": 99,
+//!     "max_latency_ms": 50,
+//!     "max_error_rate_percent": 1
+//!   }
+//! }
+//! ```
+//!
+//! The `status` is `ok` when the objectives are met again.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `slo` options.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::core;
+use crate::core::slo::Evaluation;
+
+/// Time between two evaluations of the objectives.
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time to wait for the webhook to answer.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// It starts a job for evaluating the service level objectives, every second.
+#[must_use]
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EVALUATION_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let mut degraded = false;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping SLO job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let Some(tracker) = weak_tracker.upgrade() else {
+                        break;
+                    };
+
+                    let Some(monitor) = tracker.slo() else {
+                        break;
+                    };
+
+                    let evaluation = monitor.evaluate();
+
+                    if evaluation.is_degraded() == degraded {
+                        continue;
+                    }
+
+                    degraded = evaluation.is_degraded();
+
+                    if degraded {
+                        tracing::warn!(%evaluation, "The service level objectives are not met");
+                    } else {
+                        tracing::info!(%evaluation, "The service level objectives are met again");
+                    }
+
+                    if let Some(webhook_url) = monitor.webhook_url() {
+                        let webhook_url = webhook_url.to_owned();
+
+                        // The next evaluations don't wait for the webhook.
+                        tokio::spawn(async move { notify(&webhook_url, &evaluation).await });
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// It sends the evaluation to the webhook. Failures are only logged.
+async fn notify(webhook_url: &str, evaluation: &Evaluation) {
+    let body = json!({
+        "status": if evaluation.is_degraded() { "degraded" } else { "ok" },
+        "message": evaluation.to_string(),
+        "evaluation": evaluation,
+    });
+
+    let result = reqwest::Client::new()
+        .post(webhook_url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&body)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+
+    if let Err(err) = result {
+        tracing::error!(webhook_url, %err, "Could not notify the SLO webhook");
+    }
+}
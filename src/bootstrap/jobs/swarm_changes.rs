@@ -0,0 +1,58 @@
+//! Job that maintains the log of the swarm changes.
+//!
+//! It's only started when the swarm changes log is enabled. The job subscribes
+//! to the tracker domain events and records a change for the torrent every
+//! time a peer announces itself, or the torrent entry is created or removed.
+//!
+//! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about the
+//! `swarm_changes` options.
+use std::sync::Arc;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::core;
+use crate::core::events::Event;
+
+/// It starts a job for recording the swarm changes.
+#[must_use]
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let mut receiver = tracker.subscribe();
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping swarm changes job..");
+                    break;
+                }
+                event = receiver.recv() => {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!("Swarm changes log is falling behind, {skipped} events were not recorded");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
+
+                    let info_hash = match event {
+                        Event::PeerAnnounced { info_hash, .. }
+                        | Event::TorrentCreated { info_hash }
+                        | Event::TorrentRemoved { info_hash } => info_hash,
+                        Event::TorrentUnwhitelisted { .. } | Event::KeyCreated { .. } | Event::KeyRevoked { .. } => continue,
+                    };
+
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        tracker.record_swarm_change(&info_hash);
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -1,47 +1,66 @@
 //! Job that runs a task on intervals to clean up torrents.
 //!
-//! It removes inactive peers and (optionally) peerless torrents.
+//! It removes inactive peers and (optionally) peerless and stale torrents.
 //!
 //! **Inactive peers** are peers that have not been updated for more than `max_peer_timeout` seconds.
-//! `max_peer_timeout` is a customizable core tracker option.
+//! `max_peer_timeout` is a customizable core tracker option that can be changed at runtime.
 //!
 //! If the core tracker configuration option `remove_peerless_torrents` is true, the cleanup job will also
 //! remove **peerless torrents** which are torrents with an empty peer list.
 //!
+//! If the core tracker configuration option `max_torrent_inactivity` is set, the cleanup job will also
+//! remove **stale torrents** which are torrents without announces for more than `max_torrent_inactivity`
+//! seconds, regardless of their peers.
+//!
 //! Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about those options.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
 use tokio::task::JoinHandle;
-use torrust_tracker_configuration::Core;
 use tracing::instrument;
 
 use crate::core;
 
 /// It starts a jobs for cleaning up the torrent data in the tracker.
 ///
-/// The cleaning task is executed on an `inactive_peer_cleanup_interval`.
+/// The cleaning task is executed on an `inactive_peer_cleanup_interval`. The
+/// interval is taken from the tracker [peer cleanup settings](crate::core::peer_cleanup),
+/// so it can be changed at runtime. When it changes, the next cleanup runs
+/// one new interval later. The job is idle while the interval is `0`.
 ///
 /// Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about that option.
 #[must_use]
-#[instrument(skip(config, tracker))]
-pub fn start_job(config: &Core, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
     let weak_tracker = std::sync::Arc::downgrade(tracker);
-    let interval = config.inactive_peer_cleanup_interval;
+    let mut settings = tracker.subscribe_to_peer_cleanup();
 
     tokio::spawn(async move {
-        let interval = std::time::Duration::from_secs(interval);
-        let mut interval = tokio::time::interval(interval);
-        interval.tick().await;
-
         loop {
+            let interval = settings.borrow_and_update().inactive_peer_cleanup_interval;
+
+            let next_cleanup = async {
+                if interval == 0 {
+                    std::future::pending::<()>().await;
+                } else {
+                    tokio::time::sleep(Duration::from_secs(interval)).await;
+                }
+            };
+
             tokio::select! {
                 _ = tokio::signal::ctrl_c() => {
                     tracing::info!("Stopping torrent cleanup job..");
                     break;
                 }
-                _ = interval.tick() => {
+                changed = settings.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    tracing::info!("Torrent cleanup interval changed to {}s", settings.borrow().inactive_peer_cleanup_interval);
+                }
+                () = next_cleanup => {
                     if let Some(tracker) = weak_tracker.upgrade() {
                         let start_time = Utc::now().time();
                         tracing::info!("Cleaning up torrents..");
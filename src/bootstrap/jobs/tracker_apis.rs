@@ -7,7 +7,7 @@
 //! > versions. API consumers can choose which version to use. The API version is
 //! > part of the URL, for example: `http://localhost:1212/api/v1/stats`.
 //!
-//! The [`tracker_apis::start_job`](crate::bootstrap::jobs::tracker_apis::start_job)  
+//! The [`tracker_apis::start_job`](crate::bootstrap::jobs::tracker_apis::start_job)\
 //! function spawns a new asynchronous task, that tasks is the "**launcher**".
 //! The "**launcher**" starts the actual server and sends a message back
 //! to the main application. The main application waits until receives
@@ -18,21 +18,31 @@
 //! from the process that handles it. The API could be used independently
 //! in the future. In that case it would not need to notify a parent process.
 //!
+//! When the API is bound to a Unix domain socket, there is no "**launcher**".
+//! The socket is bound before spawning the task that runs the server, and the
+//! service is registered for the health check with its socket path. See
+//! [`unix_socket`].
+//!
 //! Refer to the [configuration documentation](https://docs.rs/torrust-tracker-configuration)
 //! for the API configuration options.
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum_server::tls_rustls::RustlsConfig;
+use camino::Utf8Path;
 use tokio::task::JoinHandle;
-use torrust_tracker_configuration::{AccessTokens, HttpApi};
+use torrust_tracker_configuration::{BindAddress, HttpApi};
 use tracing::instrument;
 
 use super::make_rust_tls;
 use crate::core;
-use crate::servers::apis::server::{ApiServer, Launcher};
-use crate::servers::apis::Version;
-use crate::servers::registar::ServiceRegistrationForm;
+use crate::core::virtual_trackers::VirtualTrackers;
+use crate::servers::apis::routes::router;
+use crate::servers::apis::server::{check_unix_socket_fn, ApiServer, Launcher};
+use crate::servers::apis::{Version, API_LOG_TARGET};
+use crate::servers::custom_axum_server::RustlsConfig;
+use crate::servers::registar::{ServiceKind, ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::signals::Halted;
+use crate::servers::unix_socket;
 
 /// This is the message that the "launcher" spawned task sends to the main
 /// application process to notify the API server was successfully started.
@@ -51,40 +61,91 @@ pub struct ApiServerJobStarted();
 ///
 /// # Panics
 ///
-/// It would panic if unable to send the  `ApiServerJobStarted` notice.
-///
+/// It would panic if unable to send the  `ApiServerJobStarted` notice, or
+/// if unable to bind to the Unix domain socket.
 ///
-#[instrument(skip(config, tracker, form))]
+#[instrument(skip(config, tracker, virtual_trackers, form))]
 pub async fn start_job(
     config: &HttpApi,
     tracker: Arc<core::Tracker>,
+    virtual_trackers: VirtualTrackers,
     form: ServiceRegistrationForm,
     version: Version,
 ) -> Option<JoinHandle<()>> {
-    let bind_to = config.bind_address;
+    let bind_to = match &config.bind_address {
+        BindAddress::Unix(path) => {
+            return match version {
+                Version::V1 => Some(
+                    start_v1_on_unix_socket(path, tracker, &virtual_trackers, form, Arc::new(config.clone()))
+                        .unwrap_or_else(|err| panic!("it should be able to bind the tracker api to unix:{path}: {err}")),
+                ),
+            };
+        }
+        bind_address => bind_address
+            .socket_addr()
+            .expect("the API bind address should be resolved when the configuration is loaded"),
+    };
 
     let tls = make_rust_tls(&config.tsl_config)
         .await
         .map(|tls| tls.expect("it should have a valid tracker api tls configuration"));
 
-    let access_tokens = Arc::new(config.access_tokens.clone());
-
     match version {
-        Version::V1 => Some(start_v1(bind_to, tls, tracker.clone(), form, access_tokens).await),
+        Version::V1 => Some(
+            start_v1(
+                bind_to,
+                tls,
+                tracker.clone(),
+                virtual_trackers,
+                form,
+                Arc::new(config.clone()),
+            )
+            .await,
+        ),
     }
 }
 
+#[instrument(skip(tracker, virtual_trackers, form, config))]
+fn start_v1_on_unix_socket(
+    path: &Utf8Path,
+    tracker: Arc<core::Tracker>,
+    virtual_trackers: &VirtualTrackers,
+    form: ServiceRegistrationForm,
+    config: Arc<HttpApi>,
+) -> std::io::Result<JoinHandle<()>> {
+    let server = unix_socket::Server::bind(path)?;
+
+    let router = router(tracker, config, virtual_trackers);
+
+    form.send(ServiceRegistration::unix_socket(
+        server.path().to_path_buf(),
+        ServiceKind::HttpApi,
+        check_unix_socket_fn,
+    ))
+    .expect("it should be able to send service registration");
+
+    Ok(tokio::spawn(async move {
+        // The server only stops on the global shutdown signal.
+        let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
+
+        server.serve(router, rx_halt, API_LOG_TARGET).await;
+
+        drop(tx_halt);
+    }))
+}
+
 #[allow(clippy::async_yields_async)]
-#[instrument(skip(socket, tls, tracker, form, access_tokens))]
+#[instrument(skip(socket, tls, tracker, virtual_trackers, form, config))]
 async fn start_v1(
     socket: SocketAddr,
     tls: Option<RustlsConfig>,
     tracker: Arc<core::Tracker>,
+    virtual_trackers: VirtualTrackers,
     form: ServiceRegistrationForm,
-    access_tokens: Arc<AccessTokens>,
+    config: Arc<HttpApi>,
 ) -> JoinHandle<()> {
-    let server = ApiServer::new(Launcher::new(socket, tls))
-        .start(tracker, form, access_tokens)
+    let server = ApiServer::new(Launcher::new(socket, tls, virtual_trackers))
+        .start(tracker, form, config)
         .await
         .expect("it should be able to start to the tracker api");
 
@@ -102,6 +163,7 @@ mod tests {
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::tracker_apis::start_job;
+    use crate::core::virtual_trackers::VirtualTrackers;
     use crate::servers::apis::Version;
     use crate::servers::registar::Registar;
 
@@ -112,8 +174,14 @@ mod tests {
         let tracker = initialize_with_configuration(&cfg);
         let version = Version::V1;
 
-        start_job(config, tracker, Registar::default().give_form(), version)
-            .await
-            .expect("it should be able to join to the tracker api start-job");
+        start_job(
+            config,
+            tracker,
+            VirtualTrackers::default(),
+            Registar::default().give_form(),
+            version,
+        )
+        .await
+        .expect("it should be able to join to the tracker api start-job");
     }
 }
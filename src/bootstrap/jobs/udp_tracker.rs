@@ -12,15 +12,22 @@ use tokio::task::JoinHandle;
 use torrust_tracker_configuration::UdpTracker;
 use tracing::instrument;
 
+use crate::bootstrap::runtimes;
 use crate::core;
-use crate::servers::registar::ServiceRegistrationForm;
+use crate::servers::registar::{ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::udp::server::bound_socket::SocketOptions;
 use crate::servers::udp::server::spawner::Spawner;
 use crate::servers::udp::server::Server;
 use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
 
 /// It starts a new UDP server with the provided configuration.
 ///
-/// It spawns a new asynchronous task for the new UDP server.
+/// It spawns a new asynchronous task for the new UDP server. When the tracker
+/// is bound to more than one address, one server is started for each address,
+/// and they are registered as a single service for the health check.
+///
+/// When the tracker has a dedicated `runtime`, the servers are started on it
+/// instead of the main runtime.
 ///
 /// # Panics
 ///
@@ -31,28 +38,64 @@ use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
 #[allow(clippy::async_yields_async)]
 #[instrument(skip(config, tracker, form))]
 pub async fn start_job(config: &UdpTracker, tracker: Arc<core::Tracker>, form: ServiceRegistrationForm) -> JoinHandle<()> {
-    let bind_to = config.bind_address;
+    let mut servers = Vec::new();
+    let mut registrations = Vec::new();
+
+    let runtime = config
+        .runtime
+        .as_ref()
+        .map(|runtime| runtimes::start(&format!("udp-{}", config.bind_address.first()), runtime));
+
+    for bind_to in config.bind_addresses() {
+        let (tx_registration, rx_registration) = tokio::sync::oneshot::channel::<ServiceRegistration>();
 
-    let server = Server::new(Spawner::new(bind_to))
-        .start(tracker, form)
-        .await
+        let server = Server::new(Spawner::new(
+            bind_to,
+            config.proxy_protocol,
+            SocketOptions::new(config.ipv6_only, config.dscp, config.bind_device.clone()),
+            core::AnnounceMode::new(config.async_announce),
+        ));
+
+        let server = match &runtime {
+            Some(runtime) => runtime
+                .spawn(server.start(tracker.clone(), tx_registration))
+                .await
+                .expect("it should be able to join the udp tracker start task"),
+            None => server.start(tracker.clone(), tx_registration).await,
+        }
         .expect("it should be able to start the udp tracker");
 
+        registrations.push(
+            rx_registration
+                .await
+                .expect("it should receive the service registration from the udp tracker"),
+        );
+
+        servers.push(server);
+    }
+
+    form.send(ServiceRegistration::group(registrations))
+        .expect("it should be able to send service registration");
+
     tokio::spawn(async move {
-        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Wait for launcher (UDP service) to finish ...");
-        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Is halt channel closed before waiting?: {}", server.state.halt_task.is_closed());
+        let tasks = servers.into_iter().map(|server| async move {
+            tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Wait for launcher (UDP service) to finish ...");
+            tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Is halt channel closed before waiting?: {}", server.state.halt_task.is_closed());
 
-        assert!(
-            !server.state.halt_task.is_closed(),
-            "Halt channel for UDP tracker should be open"
-        );
+            assert!(
+                !server.state.halt_task.is_closed(),
+                "Halt channel for UDP tracker should be open"
+            );
+
+            server
+                .state
+                .task
+                .await
+                .expect("it should be able to join to the udp tracker task");
 
-        server
-            .state
-            .task
-            .await
-            .expect("it should be able to join to the udp tracker task");
+            tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Is halt channel closed after finishing the server?: {}", server.state.halt_task.is_closed());
+        });
 
-        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, "Is halt channel closed after finishing the server?: {}", server.state.halt_task.is_closed());
+        futures::future::join_all(tasks).await;
     })
 }
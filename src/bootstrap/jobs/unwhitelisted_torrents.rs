@@ -0,0 +1,57 @@
+//! Job that removes the torrents removed from the whitelist from the
+//! torrents repository.
+//!
+//! It's only started when the tracker is listed. The job subscribes to the
+//! tracker domain events and, every time a torrent is removed from the
+//! whitelist, it drops the torrent entry with its peers, so the removed
+//! content stops being served. The torrent is kept if it's allowed again in
+//! the meantime, for example, by a whitelist rule.
+use std::sync::Arc;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::core;
+use crate::core::events::Event;
+
+/// It starts a job for removing the torrents removed from the whitelist.
+#[must_use]
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let mut receiver = tracker.subscribe();
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping unwhitelisted torrents job..");
+                    break;
+                }
+                event = receiver.recv() => {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!("Unwhitelisted torrents job is falling behind, {skipped} events were skipped");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
+
+                    let Event::TorrentUnwhitelisted { info_hash } = event else {
+                        continue;
+                    };
+
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        if tracker.remove_unwhitelisted_torrent(&info_hash).await {
+                            tracing::info!(%info_hash, "Removed the torrent removed from the whitelist");
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,291 @@
+//! Deduplication of the repeated warnings and errors.
+//!
+//! During an outage, like when the database is down, the tracker logs the
+//! same error for every request, and the flood of identical events hides the
+//! other events. When the `logging.deduplication` section is in the
+//! configuration, only the first occurrence of a warning or an error is
+//! logged. The identical events are suppressed for `window_secs` seconds, and
+//! then a summary with the number of suppressed occurrences is logged:
+//!
+//! ```text
+//! ERROR LOG DEDUPLICATION: Suppressed 1234 repeated events in the last 60 seconds: torrust_tracker::core: Could not save the torrent
+//! ```
+//!
+//! ```toml
+//! [logging.deduplication]
+//! window_secs = 60
+//! ```
+//!
+//! Two events are identical when they have the same level, target and
+//! fields. The `info`, `debug` and `trace` events are never suppressed.
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+
+/// The target of the summaries. They are never suppressed.
+pub const TARGET: &str = "LOG DEDUPLICATION";
+
+/// How often the expired windows are checked to log their summaries.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A tracing layer that suppresses the repeated warnings and errors.
+pub struct Layer {
+    deduplicator: Arc<Deduplicator>,
+}
+
+impl Layer {
+    /// It creates the layer and starts the thread that logs the summaries.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the thread can't be spawned.
+    #[must_use]
+    pub fn start(window: Duration) -> Self {
+        let deduplicator = Arc::new(Deduplicator::new(window));
+
+        let flushed = deduplicator.clone();
+
+        std::thread::Builder::new()
+            .name("log-deduplication".to_owned())
+            .spawn(move || loop {
+                std::thread::sleep(FLUSH_INTERVAL);
+
+                for summary in flushed.flush(Instant::now()) {
+                    summary.log(window);
+                }
+            })
+            .expect("it should spawn the log deduplication thread");
+
+        Self { deduplicator }
+    }
+}
+
+impl<S: Subscriber> tracing_subscriber::Layer<S> for Layer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let metadata = event.metadata();
+
+        if *metadata.level() > Level::WARN || metadata.target() == TARGET {
+            return true;
+        }
+
+        let mut fields = FieldsVisitor::default();
+        event.record(&mut fields);
+
+        self.deduplicator.check(
+            RepeatedEvent {
+                level: *metadata.level(),
+                target: metadata.target().to_owned(),
+                fields: fields.0,
+            },
+            Instant::now(),
+        )
+    }
+}
+
+/// The identity of an event: two events are identical when all of them are
+/// equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RepeatedEvent {
+    pub level: Level,
+    pub target: String,
+    pub fields: String,
+}
+
+/// The occurrences of an event that were suppressed in a window.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub event: RepeatedEvent,
+    pub suppressed: u64,
+}
+
+impl Summary {
+    fn log(&self, window: Duration) {
+        let message = format!(
+            "Suppressed {} repeated events in the last {} seconds: {}: {}",
+            self.suppressed,
+            window.as_secs(),
+            self.event.target,
+            self.event.fields
+        );
+
+        if self.event.level == Level::ERROR {
+            tracing::error!(target: TARGET, "{message}");
+        } else {
+            tracing::warn!(target: TARGET, "{message}");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Occurrences {
+    first_at: Instant,
+    suppressed: u64,
+}
+
+/// It keeps the events logged in the current window of each event.
+#[derive(Debug)]
+pub struct Deduplicator {
+    window: Duration,
+    events: Mutex<HashMap<RepeatedEvent, Occurrences>>,
+}
+
+impl Deduplicator {
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            events: Mutex::default(),
+        }
+    }
+
+    /// It returns `true` when the event has to be logged, because there is
+    /// not an identical event in the current window. Otherwise, it counts the
+    /// suppressed occurrence.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the lock is poisoned.
+    pub fn check(&self, event: RepeatedEvent, now: Instant) -> bool {
+        let mut events = self.events.lock().expect("it should lock the log events");
+
+        if let Some(occurrences) = events.get_mut(&event) {
+            occurrences.suppressed += 1;
+            return false;
+        }
+
+        events.insert(
+            event,
+            Occurrences {
+                first_at: now,
+                suppressed: 0,
+            },
+        );
+
+        true
+    }
+
+    /// It ends the expired windows. It returns the summaries of the events
+    /// with suppressed occurrences.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the lock is poisoned.
+    pub fn flush(&self, now: Instant) -> Vec<Summary> {
+        let mut events = self.events.lock().expect("it should lock the log events");
+
+        let mut summaries = vec![];
+
+        events.retain(|event, occurrences| {
+            if now.duration_since(occurrences.first_at) < self.window {
+                return true;
+            }
+
+            if occurrences.suppressed > 0 {
+                summaries.push(Summary {
+                    event: event.clone(),
+                    suppressed: occurrences.suppressed,
+                });
+            }
+
+            false
+        });
+
+        summaries
+    }
+}
+
+/// It writes all the fields of an event, starting with the message.
+#[derive(Default)]
+struct FieldsVisitor(String);
+
+impl Visit for FieldsVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+
+        if field.name() == "message" {
+            let _ = write!(self.0, "{value:?}");
+        } else {
+            let _ = write!(self.0, "{}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use tracing::Level;
+
+    use super::{Deduplicator, RepeatedEvent, Summary};
+
+    fn database_error() -> RepeatedEvent {
+        RepeatedEvent {
+            level: Level::ERROR,
+            target: "torrust_tracker::core".to_owned(),
+            fields: "Could not save the torrent".to_owned(),
+        }
+    }
+
+    #[test]
+    fn it_should_only_log_the_first_occurrence_of_an_event_in_the_window() {
+        let deduplicator = Deduplicator::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(deduplicator.check(database_error(), now));
+        assert!(!deduplicator.check(database_error(), now + Duration::from_secs(1)));
+        assert!(!deduplicator.check(database_error(), now + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn it_should_not_suppress_different_events() {
+        let deduplicator = Deduplicator::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        let warning = RepeatedEvent {
+            level: Level::WARN,
+            ..database_error()
+        };
+
+        assert!(deduplicator.check(database_error(), now));
+        assert!(deduplicator.check(warning, now));
+    }
+
+    #[test]
+    fn it_should_summarize_the_suppressed_occurrences_when_the_window_ends() {
+        let deduplicator = Deduplicator::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        deduplicator.check(database_error(), now);
+        deduplicator.check(database_error(), now);
+        deduplicator.check(database_error(), now);
+
+        assert!(deduplicator.flush(now + Duration::from_secs(59)).is_empty());
+        assert_eq!(
+            deduplicator.flush(now + Duration::from_secs(60)),
+            vec![Summary {
+                event: database_error(),
+                suppressed: 2
+            }]
+        );
+
+        // A new window starts with the next occurrence.
+        assert!(deduplicator.check(database_error(), now + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn it_should_not_summarize_the_events_without_suppressed_occurrences() {
+        let deduplicator = Deduplicator::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        deduplicator.check(database_error(), now);
+
+        assert!(deduplicator.flush(now + Duration::from_secs(60)).is_empty());
+    }
+}
@@ -10,11 +10,19 @@
 //! - `Debug`
 //! - `Trace`
 //!
+//! The repeated warnings and errors can be collapsed into periodic summaries.
+//! See the [`deduplication`] module.
+//!
 //! Refer to the [configuration crate documentation](https://docs.rs/torrust-tracker-configuration) to know how to change log settings.
+pub mod deduplication;
+
 use std::sync::Once;
+use std::time::Duration;
 
 use torrust_tracker_configuration::{Configuration, Threshold};
 use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
 
 static INIT: Once = Once::new();
 
@@ -28,7 +36,12 @@ pub fn setup(cfg: &Configuration) {
     }
 
     INIT.call_once(|| {
-        tracing_stdout_init(tracing_level, &TraceStyle::Default);
+        let deduplication = cfg
+            .logging
+            .deduplication
+            .map(|deduplication| deduplication::Layer::start(Duration::from_secs(deduplication.window_secs)));
+
+        tracing_stdout_init(tracing_level, &TraceStyle::Default, deduplication);
     });
 }
 
@@ -43,14 +56,19 @@ fn map_to_tracing_level_filter(threshold: &Threshold) -> LevelFilter {
     }
 }
 
-fn tracing_stdout_init(filter: LevelFilter, style: &TraceStyle) {
+fn tracing_stdout_init(filter: LevelFilter, style: &TraceStyle, deduplication: Option<deduplication::Layer>) {
     let builder = tracing_subscriber::fmt().with_max_level(filter).with_ansi(true);
 
     let () = match style {
-        TraceStyle::Default => builder.init(),
-        TraceStyle::Pretty(display_filename) => builder.pretty().with_file(*display_filename).init(),
-        TraceStyle::Compact => builder.compact().init(),
-        TraceStyle::Json => builder.json().init(),
+        TraceStyle::Default => builder.finish().with(deduplication).init(),
+        TraceStyle::Pretty(display_filename) => builder
+            .pretty()
+            .with_file(*display_filename)
+            .finish()
+            .with(deduplication)
+            .init(),
+        TraceStyle::Compact => builder.compact().finish().with(deduplication).init(),
+        TraceStyle::Json => builder.json().finish().with(deduplication).init(),
     };
 
     tracing::info!("Logging initialized");
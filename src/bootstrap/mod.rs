@@ -7,5 +7,8 @@
 //! For example, you can have more than one UDP and HTTP tracker, each server is executed like a independent job.
 pub mod app;
 pub mod config;
+pub mod dev;
 pub mod jobs;
 pub mod logging;
+pub mod preflight;
+pub mod runtimes;
@@ -0,0 +1,166 @@
+//! Dedicated runtimes.
+//!
+//! By default, all the services run on the main tokio runtime. The UDP
+//! trackers can run on their own runtime instead, so that the latency-sensitive
+//! UDP processing is isolated from the work of the HTTP trackers and the API.
+//!
+//! Each dedicated runtime is a multi-thread runtime driven by its own OS
+//! thread. Its worker threads can be pinned to CPU cores.
+//!
+//! The runtimes are registered when they are started, so that the API can
+//! expose the scheduler metrics of every runtime with [`metrics`].
+//!
+//! Refer to the [`UdpRuntime`] configuration for more information.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+use tokio::runtime::{Builder, Handle};
+use torrust_tracker_configuration::UdpRuntime;
+
+/// The name of the main runtime in the [`metrics`].
+pub const MAIN_RUNTIME: &str = "main";
+
+lazy_static! {
+    static ref RUNTIMES: Mutex<Vec<(String, Handle)>> = Mutex::new(Vec::new());
+}
+
+/// Scheduler metrics of a runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metrics {
+    pub name: String,
+    /// Number of worker threads.
+    pub workers: usize,
+    /// Number of tasks currently alive (spawned and not finished).
+    pub alive_tasks: usize,
+}
+
+impl Metrics {
+    fn of(name: &str, handle: &Handle) -> Self {
+        let metrics = handle.metrics();
+
+        Self {
+            name: name.to_string(),
+            workers: metrics.num_workers(),
+            alive_tasks: metrics.num_alive_tasks(),
+        }
+    }
+}
+
+/// It starts a new dedicated runtime and returns its handle.
+///
+/// The runtime lives until the end of the process.
+///
+/// # Panics
+///
+/// It will panic if the runtime can't be built.
+#[must_use]
+pub fn start(name: &str, config: &UdpRuntime) -> Handle {
+    let core_ids = pinned_cores(&config.core_ids);
+    let next_core = Arc::new(AtomicUsize::new(0));
+
+    let runtime = Builder::new_multi_thread()
+        .worker_threads(config.worker_threads)
+        .thread_name(name)
+        .on_thread_start(move || {
+            if core_ids.is_empty() {
+                return;
+            }
+
+            let core_id = core_ids[next_core.fetch_add(1, Ordering::Relaxed) % core_ids.len()];
+
+            if !core_affinity::set_for_current(core_id) {
+                tracing::warn!(core = core_id.id, "Unable to pin a runtime thread to the CPU core");
+            }
+        })
+        .enable_all()
+        .build()
+        .expect("it should be able to build the runtime");
+
+    let handle = runtime.handle().clone();
+
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || runtime.block_on(std::future::pending::<()>()))
+        .expect("it should be able to spawn the runtime thread");
+
+    RUNTIMES
+        .lock()
+        .expect("it should get a lock on the runtimes")
+        .push((name.to_string(), handle.clone()));
+
+    tracing::info!(name, workers = config.worker_threads, cores = ?config.core_ids, "Dedicated runtime started");
+
+    handle
+}
+
+/// It returns the scheduler metrics of the main runtime, followed by the
+/// metrics of the dedicated runtimes in the order they were started.
+///
+/// # Panics
+///
+/// It will panic if it's not called from a tokio runtime.
+#[must_use]
+pub fn metrics() -> Vec<Metrics> {
+    let mut metrics = vec![Metrics::of(MAIN_RUNTIME, &Handle::current())];
+
+    metrics.extend(
+        RUNTIMES
+            .lock()
+            .expect("it should get a lock on the runtimes")
+            .iter()
+            .map(|(name, handle)| Metrics::of(name, handle)),
+    );
+
+    metrics
+}
+
+/// It returns the available CPU cores among the configured ones.
+fn pinned_cores(ids: &[usize]) -> Vec<core_affinity::CoreId> {
+    let available = core_affinity::get_core_ids().unwrap_or_default();
+
+    ids.iter()
+        .filter_map(|id| {
+            let core = available.iter().find(|core| core.id == *id).copied();
+
+            if core.is_none() {
+                tracing::warn!(
+                    core = id,
+                    "The CPU core is not available, the runtime threads won't be pinned to it"
+                );
+            }
+
+            core
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::UdpRuntime;
+
+    use super::{metrics, start, MAIN_RUNTIME};
+
+    #[tokio::test]
+    async fn it_should_expose_the_metrics_of_the_main_runtime_and_the_dedicated_ones() {
+        let handle = start(
+            "test-runtime",
+            &UdpRuntime {
+                worker_threads: 2,
+                core_ids: vec![0],
+            },
+        );
+
+        let task = handle.spawn(std::future::pending::<()>());
+
+        let metrics = metrics();
+        let main = metrics.first().unwrap();
+        let dedicated = metrics.iter().find(|metrics| metrics.name == "test-runtime").unwrap();
+
+        assert_eq!(main.name, MAIN_RUNTIME);
+        assert_eq!(dedicated.workers, 2);
+        assert_eq!(dedicated.alive_tasks, 1);
+
+        task.abort();
+    }
+}
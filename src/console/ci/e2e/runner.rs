@@ -77,7 +77,7 @@ pub fn run() -> anyhow::Result<()> {
     // Besides, if we don't use port 0 we should get the port numbers from the tracker configuration.
     // We could not use docker, but the intention was to create E2E tests including containerization.
     let options = RunOptions {
-        env_vars: vec![("TORRUST_TRACKER_CONFIG_TOML".to_string(), tracker_config.to_string())],
+        env_vars: vec![("TORRUST_TRACKER_CONFIG_TOML".to_string(), tracker_config.clone())],
         ports: vec![
             "6969:6969/udp".to_string(),
             "7070:7070/tcp".to_string(),
@@ -137,7 +137,7 @@ fn load_tracker_configuration(args: &Args) -> anyhow::Result<String> {
 }
 
 fn load_config_from_file(path: &PathBuf) -> anyhow::Result<String> {
-    let config = std::fs::read_to_string(path).with_context(|| format!("CSan't read config file {path:?}"))?;
+    let config = std::fs::read_to_string(path).with_context(|| format!("CSan't read config file {}", path.display()))?;
 
     Ok(config)
 }
@@ -0,0 +1,419 @@
+//! The tracker command line interface.
+//!
+//! ```text
+//! torrust-tracker [--config <PATH>] [--log-level <LEVEL>] [COMMAND]
+//! ```
+//!
+//! Commands:
+//!
+//! - `run`: it runs the tracker. It's the default command, so `torrust-tracker`
+//!   and `torrust-tracker --dev` still run the tracker. Use `--dev` for the
+//!   [development mode](crate::bootstrap::dev).
+//! - `check`: it checks running trackers. See the [tracker checker](crate::console::clients::checker).
+//! - `admin keys`: it exports and imports the authentication keys. See [`keys`](crate::console::keys).
+//! - `client http` and `client udp`: they make requests to HTTP and UDP trackers.
+//!   See the [clients](crate::console::clients).
+//! - `config schema`, `config show` and `config validate`: they print the
+//!   JSON schema of the configuration, print the loaded configuration with the
+//!   secrets masked, and validate it.
+//! - `config migrate --from <PATH>`: it converts a configuration file in the
+//!   version `1` format into the current one, validates it and prints the
+//!   changes. It's a dry run, nothing is written. See the
+//!   [`migration`](torrust_tracker_configuration::migration) module.
+//!
+//! The global `--config` option is the path of the tracker configuration file.
+//! It has priority over the `TORRUST_TRACKER_CONFIG_TOML_PATH` environment
+//! variable. The `--log-level` option overrides the `logging.threshold`
+//! option. Both are only used by the commands that load the tracker
+//! configuration, like the global `--paths-layout`, `--data-dir`,
+//! `--config-dir` and `--log-dir` options for the
+//! [path variables](torrust_tracker_configuration::paths) in the
+//! configuration. For example, the deb and rpm packages run:
+//!
+//! ```text
+//! torrust-tracker --paths-layout fhs --config /etc/torrust-tracker/tracker.toml
+//! ```
+//!
+//! `torrust-tracker --version --features` prints the version and the
+//! [features](crate#build-a-minimal-binary) the tracker was built with.
+//!
+//! The separate binaries, like `tracker_checker` or `keys`, are still built
+//! for the scripts and containers using them.
+use anyhow::{anyhow, Context, Result};
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
+use torrust_tracker_configuration::migration;
+use torrust_tracker_configuration::paths::Layout;
+use torrust_tracker_configuration::validator::Validator;
+use torrust_tracker_configuration::{Configuration, Threshold};
+
+use crate::bootstrap::config::{initialize_configuration_from, PathOptions};
+use crate::console::clients::{checker, http, udp};
+use crate::console::keys;
+use crate::{app, bootstrap};
+
+/// The features the tracker can be built with, and whether this build has
+/// them.
+const FEATURES: [(&str, bool); 8] = [
+    ("api", cfg!(feature = "api")),
+    ("fault-injection", cfg!(feature = "fault-injection")),
+    ("http", cfg!(feature = "http")),
+    ("mysql", cfg!(feature = "mysql")),
+    ("sqlite", cfg!(feature = "sqlite")),
+    ("testing", cfg!(feature = "testing")),
+    ("tls", cfg!(feature = "tls")),
+    ("udp", cfg!(feature = "udp")),
+];
+
+#[derive(Parser, Debug)]
+#[command(author, about, long_about = None, disable_version_flag = true, args_conflicts_with_subcommands = true)]
+struct Cli {
+    /// Path to the tracker configuration file. It has priority over the
+    /// `TORRUST_TRACKER_CONFIG_TOML_PATH` environment variable.
+    #[arg(long, global = true)]
+    config: Option<String>,
+
+    /// The logging threshold: `off`, `error`, `warn`, `info`, `debug` or
+    /// `trace`. It overrides the `logging.threshold` option.
+    #[arg(long, global = true, value_parser = parse_threshold)]
+    log_level: Option<Threshold>,
+
+    #[command(flatten)]
+    paths: PathArgs,
+
+    /// Print the version.
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    /// Print the features the tracker was built with, along with the version.
+    #[arg(long, requires = "version")]
+    features: bool,
+
+    #[command(flatten)]
+    run: RunArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Args, Debug)]
+struct PathArgs {
+    /// The layout of the directories used by default in the path variables of
+    /// the configuration: `local` or `fhs`.
+    #[arg(long, global = true, value_parser = parse_layout)]
+    paths_layout: Option<Layout>,
+
+    /// The value of `${DATA_DIR}` in the configuration.
+    #[arg(long, global = true)]
+    data_dir: Option<String>,
+
+    /// The value of `${CONFIG_DIR}` in the configuration.
+    #[arg(long, global = true)]
+    config_dir: Option<String>,
+
+    /// The value of `${LOG_DIR}` in the configuration.
+    #[arg(long, global = true)]
+    log_dir: Option<String>,
+}
+
+impl From<PathArgs> for PathOptions {
+    fn from(args: PathArgs) -> Self {
+        Self {
+            layout: args.paths_layout,
+            data_dir: args.data_dir,
+            config_dir: args.config_dir,
+            log_dir: args.log_dir,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    /// Fall back to ephemeral ports when the configured ones are taken, for
+    /// local development and CI.
+    #[arg(long)]
+    dev: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the tracker. It's the default command.
+    Run(RunArgs),
+    /// Check running trackers.
+    Check(checker::app::Args),
+    /// Administration tasks on the tracker database.
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+    /// Make requests to trackers.
+    Client {
+        #[command(subcommand)]
+        command: ClientCommand,
+    },
+    /// Tools for the tracker configuration.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AdminCommand {
+    /// Export and import the authentication keys.
+    Keys(keys::Args),
+}
+
+#[derive(Subcommand, Debug)]
+enum ClientCommand {
+    /// Make requests to HTTP trackers.
+    Http(http::app::Args),
+    /// Make requests to UDP trackers.
+    Udp(udp::app::Args),
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print the JSON schema of the configuration, for editors and
+    /// configuration management tools.
+    Schema,
+    /// Print the loaded configuration, with the secrets masked.
+    Show,
+    /// Check the loaded configuration is valid.
+    Validate,
+    /// Convert a version 1 configuration file into the current format and
+    /// print the changes, without writing anything.
+    Migrate {
+        /// The version 1 configuration file.
+        #[arg(long)]
+        from: Utf8PathBuf,
+    },
+}
+
+/// It runs the command in the command line arguments.
+///
+/// # Errors
+///
+/// Will return an error if the command fails.
+///
+/// # Panics
+///
+/// Will panic if the tracker configuration can't be loaded, or the tracker
+/// can't be started.
+pub async fn run() -> Result<()> {
+    let Cli {
+        config,
+        log_level,
+        paths,
+        version,
+        features,
+        run,
+        command,
+    } = Cli::parse();
+
+    let paths = PathOptions::from(paths);
+
+    if version {
+        print_version(features);
+        return Ok(());
+    }
+
+    match command.unwrap_or(Command::Run(run)) {
+        Command::Run(args) => run_tracker(load_configuration(config, log_level, &paths), &args).await,
+        Command::Check(args) => checker::app::run_with(args).await.map(|_| ()),
+        Command::Admin {
+            command: AdminCommand::Keys(args),
+        } => keys::run_with(args, &load_configuration(config, log_level, &paths)),
+        Command::Client {
+            command: ClientCommand::Http(args),
+        } => http::app::run_with(args).await,
+        Command::Client {
+            command: ClientCommand::Udp(args),
+        } => udp::app::run_with(args).await,
+        Command::Config { command } => run_config_command(&command, config, log_level, &paths),
+    }
+}
+
+fn print_version(features: bool) {
+    println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+
+    if features {
+        let enabled: Vec<&str> = FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(feature, _)| *feature)
+            .collect();
+
+        println!("features: {}", enabled.join(", "));
+    }
+}
+
+fn load_configuration(config_toml_path: Option<String>, log_level: Option<Threshold>, paths: &PathOptions) -> Configuration {
+    let mut configuration = initialize_configuration_from(config_toml_path, paths);
+
+    if let Some(threshold) = log_level {
+        configuration.logging.threshold = threshold;
+    }
+
+    configuration
+}
+
+fn parse_threshold(threshold: &str) -> Result<Threshold, String> {
+    serde_json::from_value(serde_json::Value::String(threshold.to_lowercase()))
+        .map_err(|_| format!("invalid log level: {threshold}"))
+}
+
+fn parse_layout(layout: &str) -> Result<Layout, String> {
+    layout.parse().map_err(|err| format!("{err}"))
+}
+
+async fn run_tracker(configuration: Configuration, args: &RunArgs) -> Result<()> {
+    let (mut config, tracker, preflight) = bootstrap::app::setup_with(configuration);
+
+    let port_fallbacks = if args.dev {
+        bootstrap::dev::fall_back_to_ephemeral_ports(&mut config)
+    } else {
+        Vec::new()
+    };
+
+    let jobs = app::start(&config, tracker, preflight, port_fallbacks).await;
+
+    // handle the signals
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("Torrust shutting down ...");
+
+            // Await for all jobs to shutdown
+            futures::future::join_all(jobs).await;
+            tracing::info!("Torrust successfully shutdown.");
+        }
+    }
+
+    Ok(())
+}
+
+fn run_config_command(
+    command: &ConfigCommand,
+    config_toml_path: Option<String>,
+    log_level: Option<Threshold>,
+    paths: &PathOptions,
+) -> Result<()> {
+    match command {
+        ConfigCommand::Schema => println!("{}", Configuration::json_schema()),
+        ConfigCommand::Show => println!(
+            "{}",
+            load_configuration(config_toml_path, log_level, paths)
+                .mask_secrets()
+                .to_json()
+        ),
+        ConfigCommand::Validate => {
+            load_configuration(config_toml_path, log_level, paths)
+                .validate()
+                .map_err(|err| anyhow!("invalid configuration: {err}"))?;
+
+            println!("The configuration is valid");
+        }
+        ConfigCommand::Migrate { from } => {
+            let v1_toml = std::fs::read_to_string(from).with_context(|| format!("could not read {from}"))?;
+
+            let migration = migration::from_v1(&v1_toml).map_err(|err| anyhow!("could not migrate {from}: {err}"))?;
+
+            println!("--- {from} (version 1)");
+            println!("+++ {from} (version 2)");
+            print!("{}", migration::diff(&v1_toml, &migration.toml));
+
+            for warning in &migration.warnings {
+                eprintln!("warning: {warning}");
+            }
+
+            println!("The migrated configuration is valid. Nothing was written");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+    use torrust_tracker_configuration::paths::Layout;
+    use torrust_tracker_configuration::Threshold;
+
+    use super::{AdminCommand, Cli, Command, ConfigCommand};
+
+    #[test]
+    fn it_should_have_a_valid_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn it_should_run_the_tracker_when_there_is_no_command() {
+        let cli = Cli::try_parse_from(["torrust-tracker", "--dev"]).unwrap();
+
+        assert!(cli.command.is_none());
+        assert!(cli.run.dev);
+    }
+
+    #[test]
+    fn it_should_accept_the_global_options_after_the_command() {
+        let cli = Cli::try_parse_from([
+            "torrust-tracker",
+            "config",
+            "validate",
+            "--config",
+            "tracker.toml",
+            "--log-level",
+            "DEBUG",
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                command: ConfigCommand::Validate
+            })
+        ));
+        assert_eq!(cli.config, Some("tracker.toml".to_string()));
+        assert_eq!(cli.log_level, Some(Threshold::Debug));
+    }
+
+    #[test]
+    fn it_should_accept_the_path_options() {
+        let cli = Cli::try_parse_from(["torrust-tracker", "--paths-layout", "fhs", "--data-dir", "/srv/tracker"]).unwrap();
+
+        assert_eq!(cli.paths.paths_layout, Some(Layout::Fhs));
+        assert_eq!(cli.paths.data_dir, Some("/srv/tracker".to_string()));
+        assert!(Cli::try_parse_from(["torrust-tracker", "--paths-layout", "opt"]).is_err());
+    }
+
+    #[test]
+    fn it_should_require_the_file_to_migrate() {
+        let cli = Cli::try_parse_from(["torrust-tracker", "config", "migrate", "--from", "tracker-v1.toml"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                command: ConfigCommand::Migrate { .. }
+            })
+        ));
+        assert!(Cli::try_parse_from(["torrust-tracker", "config", "migrate"]).is_err());
+    }
+
+    #[test]
+    fn it_should_embed_the_console_apps() {
+        let cli = Cli::try_parse_from(["torrust-tracker", "admin", "keys", "export", "--file", "keys.json"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Admin {
+                command: AdminCommand::Keys(_)
+            })
+        ));
+    }
+
+    #[test]
+    fn it_should_only_print_the_features_with_the_version() {
+        assert!(Cli::try_parse_from(["torrust-tracker", "--features"]).is_err());
+        assert!(Cli::try_parse_from(["torrust-tracker", "--version", "--features"]).is_ok());
+    }
+}
@@ -56,12 +56,22 @@
 //!   ]
 //! }
 //! ```
+//!
+//! The checks can be sent through a proxy with the `--proxy` option (or the
+//! `TORRUST_CHECKER_PROXY` env var). HTTP trackers and health checks accept
+//! HTTP and SOCKS5 proxies, while UDP trackers require a SOCKS5 proxy
+//! supporting the `UDP ASSOCIATE` command:
+//!
+//! ```text
+//! cargo run --bin tracker_checker -- --proxy socks5://127.0.0.1:1080 --config-path "./share/default/config/tracker_checker.json"
+//! ```
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use tracing::level_filters::LevelFilter;
+use url::Url;
 
 use super::config::Configuration;
 use super::console::Console;
@@ -70,7 +80,7 @@ use crate::console::clients::checker::config::parse_from_json;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
-struct Args {
+pub struct Args {
     /// Path to the JSON configuration file.
     #[clap(short, long, env = "TORRUST_CHECKER_CONFIG_PATH")]
     config_path: Option<PathBuf>,
@@ -78,15 +88,28 @@ struct Args {
     /// Direct configuration content in JSON.
     #[clap(env = "TORRUST_CHECKER_CONFIG", hide_env_values = true)]
     config_content: Option<String>,
+
+    /// Proxy to send the checks through, for example: `socks5://127.0.0.1:1080`.
+    #[clap(long, env = "TORRUST_CHECKER_PROXY")]
+    proxy: Option<Url>,
 }
 
 /// # Errors
 ///
 /// Will return an error if the configuration was not provided.
 pub async fn run() -> Result<Vec<CheckResult>> {
+    run_with(Args::parse()).await
+}
+
+/// It runs the checks with the already parsed arguments.
+///
+/// # Errors
+///
+/// Will return an error if the configuration was not provided.
+pub async fn run_with(args: Args) -> Result<Vec<CheckResult>> {
     tracing_stdout_init(LevelFilter::INFO);
 
-    let args = Args::parse();
+    let proxy = args.proxy.clone();
 
     let config = setup_config(args)?;
 
@@ -95,6 +118,7 @@ pub async fn run() -> Result<Vec<CheckResult>> {
     let service = Service {
         config: Arc::new(config),
         console: console_printer,
+        proxy,
     };
 
     service.run_checks().await.context("it should run the check tasks")
@@ -114,7 +138,7 @@ fn setup_config(args: Args) -> Result<Configuration> {
 }
 
 fn load_config_from_file(path: &PathBuf) -> Result<Configuration> {
-    let file_content = std::fs::read_to_string(path).with_context(|| format!("can't read config file {path:?}"))?;
+    let file_content = std::fs::read_to_string(path).with_context(|| format!("can't read config file {}", path.display()))?;
 
     parse_from_json(&file_content).context("invalid config format")
 }
@@ -2,8 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
-use hyper::StatusCode;
-use reqwest::{Client as HttpClient, Response};
+use reqwest::{Client as HttpClient, Proxy, Response, StatusCode};
 use serde::Serialize;
 use thiserror::Error;
 use url::Url;
@@ -31,13 +30,13 @@ pub struct Checks {
     result: Result<String, Error>,
 }
 
-pub async fn run(health_checks: Vec<Url>, timeout: Duration) -> Vec<Result<Checks, Checks>> {
+pub async fn run(health_checks: Vec<Url>, timeout: Duration, proxy: Option<Url>) -> Vec<Result<Checks, Checks>> {
     let mut results = Vec::default();
 
     tracing::debug!("Health checks ...");
 
     for url in health_checks {
-        let result = match run_health_check(url.clone(), timeout).await {
+        let result = match run_health_check(url.clone(), timeout, proxy.as_ref()).await {
             Ok(response) => Ok(response.status().to_string()),
             Err(err) => Err(err),
         };
@@ -54,11 +53,14 @@ pub async fn run(health_checks: Vec<Url>, timeout: Duration) -> Vec<Result<Check
     results
 }
 
-async fn run_health_check(url: Url, timeout: Duration) -> Result<Response, Error> {
-    let client = HttpClient::builder()
-        .timeout(timeout)
-        .build()
-        .map_err(|e| Error::ClientBuildingError { err: e.into() })?;
+async fn run_health_check(url: Url, timeout: Duration, proxy: Option<&Url>) -> Result<Response, Error> {
+    let mut builder = HttpClient::builder().timeout(timeout);
+
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(Proxy::all(proxy.clone()).map_err(|e| Error::ClientBuildingError { err: e.into() })?);
+    }
+
+    let client = builder.build().map_err(|e| Error::ClientBuildingError { err: e.into() })?;
 
     let response = client
         .get(url.clone())
@@ -22,7 +22,7 @@ pub enum Check {
     Scrape,
 }
 
-pub async fn run(http_trackers: Vec<Url>, timeout: Duration) -> Vec<Result<Checks, Checks>> {
+pub async fn run(http_trackers: Vec<Url>, timeout: Duration, proxy: Option<Url>) -> Vec<Result<Checks, Checks>> {
     let mut results = Vec::default();
 
     tracing::debug!("HTTP trackers ...");
@@ -38,14 +38,14 @@ pub async fn run(http_trackers: Vec<Url>, timeout: Duration) -> Vec<Result<Check
 
         // Announce
         {
-            let check = check_http_announce(&base_url, timeout).await.map(|_| ());
+            let check = check_http_announce(&base_url, timeout, proxy.as_ref()).await.map(|_| ());
 
             checks.results.push((Check::Announce, check));
         }
 
         // Scrape
         {
-            let check = check_http_scrape(&base_url, timeout).await.map(|_| ());
+            let check = check_http_scrape(&base_url, timeout, proxy.as_ref()).await.map(|_| ());
 
             checks.results.push((Check::Scrape, check));
         }
@@ -60,11 +60,11 @@ pub async fn run(http_trackers: Vec<Url>, timeout: Duration) -> Vec<Result<Check
     results
 }
 
-async fn check_http_announce(url: &Url, timeout: Duration) -> Result<Announce, Error> {
+async fn check_http_announce(url: &Url, timeout: Duration, proxy: Option<&Url>) -> Result<Announce, Error> {
     let info_hash_str = "9c38422213e30bff212b30c360d26f9a02136422".to_string(); // # DevSkim: ignore DS173237
     let info_hash = InfoHash::from_str(&info_hash_str).expect("a valid info-hash is required");
 
-    let client = Client::new(url.clone(), timeout).map_err(|err| Error::HttpClientError { err })?;
+    let client = client(url, timeout, proxy)?;
 
     let response = client
         .announce(
@@ -85,11 +85,11 @@ async fn check_http_announce(url: &Url, timeout: Duration) -> Result<Announce, E
     Ok(response)
 }
 
-async fn check_http_scrape(url: &Url, timeout: Duration) -> Result<scrape::Response, Error> {
+async fn check_http_scrape(url: &Url, timeout: Duration, proxy: Option<&Url>) -> Result<scrape::Response, Error> {
     let info_hashes: Vec<String> = vec!["9c38422213e30bff212b30c360d26f9a02136422".to_string()]; // # DevSkim: ignore DS173237
     let query = requests::scrape::Query::try_from(info_hashes).expect("a valid array of info-hashes is required");
 
-    let client = Client::new(url.clone(), timeout).map_err(|err| Error::HttpClientError { err })?;
+    let client = client(url, timeout, proxy)?;
 
     let response = client.scrape(&query).await.map_err(|err| Error::HttpClientError { err })?;
 
@@ -102,3 +102,11 @@ async fn check_http_scrape(url: &Url, timeout: Duration) -> Result<scrape::Respo
 
     Ok(response)
 }
+
+fn client(url: &Url, timeout: Duration, proxy: Option<&Url>) -> Result<Client, Error> {
+    match proxy {
+        Some(proxy) => Client::proxied(url.clone(), timeout, proxy),
+        None => Client::new(url.clone(), timeout),
+    }
+    .map_err(|err| Error::HttpClientError { err })
+}
@@ -24,7 +24,7 @@ pub enum Check {
 }
 
 #[allow(clippy::missing_panics_doc)]
-pub async fn run(udp_trackers: Vec<Url>, timeout: Duration) -> Vec<Result<Checks, Checks>> {
+pub async fn run(udp_trackers: Vec<Url>, timeout: Duration, proxy: Option<Url>) -> Vec<Result<Checks, Checks>> {
     let mut results = Vec::default();
 
     tracing::debug!("UDP trackers ...");
@@ -42,7 +42,7 @@ pub async fn run(udp_trackers: Vec<Url>, timeout: Duration) -> Vec<Result<Checks
         tracing::debug!("UDP tracker: {:?}", remote_url);
 
         // Setup
-        let client = match Client::new(remote_addr, timeout).await {
+        let client = match Client::new(remote_addr, timeout, proxy.as_ref()).await {
             Ok(client) => {
                 checks.results.push((Check::Setup, Ok(())));
                 client
@@ -117,8 +117,8 @@ mod tests {
         let socket_addr = resolve_socket_addr(&Url::parse("udp://localhost:8080").unwrap());
 
         assert!(
-            socket_addr == SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080)
-                || socket_addr == SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), 8080)
+            socket_addr == SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080)
+                || socket_addr == SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080)
         );
     }
 
@@ -127,8 +127,8 @@ mod tests {
         let socket_addr = resolve_socket_addr(&Url::parse("udp://localhost:8080").unwrap());
 
         assert!(
-            socket_addr == SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080)
-                || socket_addr == SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), 8080)
+            socket_addr == SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080)
+                || socket_addr == SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080)
         );
     }
 }
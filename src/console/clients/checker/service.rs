@@ -4,6 +4,7 @@ use futures::FutureExt as _;
 use serde::Serialize;
 use tokio::task::{JoinError, JoinSet};
 use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+use url::Url;
 
 use super::checks::{health, http, udp};
 use super::config::Configuration;
@@ -13,6 +14,8 @@ use crate::console::clients::checker::printer::Printer;
 pub struct Service {
     pub(crate) config: Arc<Configuration>,
     pub(crate) console: Console,
+    /// The proxy the checks are sent through, if any.
+    pub(crate) proxy: Option<Url>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -38,14 +41,15 @@ impl Service {
 
         let mut checks = JoinSet::new();
         checks.spawn(
-            udp::run(self.config.udp_trackers.clone(), DEFAULT_TIMEOUT).map(|mut f| f.drain(..).map(CheckResult::Udp).collect()),
+            udp::run(self.config.udp_trackers.clone(), DEFAULT_TIMEOUT, self.proxy.clone())
+                .map(|mut f| f.drain(..).map(CheckResult::Udp).collect()),
         );
         checks.spawn(
-            http::run(self.config.http_trackers.clone(), DEFAULT_TIMEOUT)
+            http::run(self.config.http_trackers.clone(), DEFAULT_TIMEOUT, self.proxy.clone())
                 .map(|mut f| f.drain(..).map(CheckResult::Http).collect()),
         );
         checks.spawn(
-            health::run(self.config.health_checks.clone(), DEFAULT_TIMEOUT)
+            health::run(self.config.health_checks.clone(), DEFAULT_TIMEOUT, self.proxy.clone())
                 .map(|mut f| f.drain(..).map(CheckResult::Health).collect()),
         );
 
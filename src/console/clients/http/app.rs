@@ -13,6 +13,13 @@
 //! ```text
 //! cargo run --bin http_tracker_client scrape http://127.0.0.1:7070 9c38422213e30bff212b30c360d26f9a02136422 | jq
 //! ```
+//!
+//! The requests can be sent through a proxy with the `--proxy` option (or the
+//! `TORRUST_CLIENT_PROXY` env var). HTTP and SOCKS5 proxies are supported:
+//!
+//! ```text
+//! cargo run --bin http_tracker_client -- --proxy socks5://127.0.0.1:1080 announce http://127.0.0.1:7070 9c38422213e30bff212b30c360d26f9a02136422 | jq
+//! ```
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -29,7 +36,11 @@ use crate::shared::bit_torrent::tracker::http::client::{requests, Client};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
+pub struct Args {
+    /// Proxy to send the requests through, for example: `socks5://127.0.0.1:1080`.
+    #[arg(long, global = true, env = "TORRUST_CLIENT_PROXY")]
+    proxy: Option<Url>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -44,29 +55,36 @@ enum Command {
 ///
 /// Will return an error if the command fails.
 pub async fn run() -> anyhow::Result<()> {
-    let args = Args::parse();
+    run_with(Args::parse()).await
+}
 
+/// It runs the client with the already parsed arguments.
+///
+/// # Errors
+///
+/// Will return an error if the command fails.
+pub async fn run_with(args: Args) -> anyhow::Result<()> {
     match args.command {
         Command::Announce { tracker_url, info_hash } => {
-            announce_command(tracker_url, info_hash, DEFAULT_TIMEOUT).await?;
+            announce_command(tracker_url, info_hash, DEFAULT_TIMEOUT, args.proxy.as_ref()).await?;
         }
         Command::Scrape {
             tracker_url,
             info_hashes,
         } => {
-            scrape_command(&tracker_url, &info_hashes, DEFAULT_TIMEOUT).await?;
+            scrape_command(&tracker_url, &info_hashes, DEFAULT_TIMEOUT, args.proxy.as_ref()).await?;
         }
     }
 
     Ok(())
 }
 
-async fn announce_command(tracker_url: String, info_hash: String, timeout: Duration) -> anyhow::Result<()> {
+async fn announce_command(tracker_url: String, info_hash: String, timeout: Duration, proxy: Option<&Url>) -> anyhow::Result<()> {
     let base_url = Url::parse(&tracker_url).context("failed to parse HTTP tracker base URL")?;
     let info_hash =
         InfoHash::from_str(&info_hash).expect("Invalid infohash. Example infohash: `9c38422213e30bff212b30c360d26f9a02136422`");
 
-    let response = Client::new(base_url, timeout)?
+    let response = client(base_url, timeout, proxy)?
         .announce(&QueryBuilder::with_default_values().with_info_hash(&info_hash).query())
         .await?;
 
@@ -82,12 +100,12 @@ async fn announce_command(tracker_url: String, info_hash: String, timeout: Durat
     Ok(())
 }
 
-async fn scrape_command(tracker_url: &str, info_hashes: &[String], timeout: Duration) -> anyhow::Result<()> {
+async fn scrape_command(tracker_url: &str, info_hashes: &[String], timeout: Duration, proxy: Option<&Url>) -> anyhow::Result<()> {
     let base_url = Url::parse(tracker_url).context("failed to parse HTTP tracker base URL")?;
 
     let query = requests::scrape::Query::try_from(info_hashes).context("failed to parse infohashes")?;
 
-    let response = Client::new(base_url, timeout)?.scrape(&query).await?;
+    let response = client(base_url, timeout, proxy)?.scrape(&query).await?;
 
     let body = response.bytes().await?;
 
@@ -100,3 +118,12 @@ async fn scrape_command(tracker_url: &str, info_hashes: &[String], timeout: Dura
 
     Ok(())
 }
+
+fn client(base_url: Url, timeout: Duration, proxy: Option<&Url>) -> anyhow::Result<Client> {
+    let client = match proxy {
+        Some(proxy) => Client::proxied(base_url, timeout, proxy)?,
+        None => Client::new(base_url, timeout)?,
+    };
+
+    Ok(client)
+}
@@ -18,13 +18,13 @@ pub enum Error {
     ResponseError { err: Arc<reqwest::Error> },
     #[error("Failed to deserialize the bencoded response data with the error: \"{err:?}\"")]
     ParseBencodeError {
-        data: hyper::body::Bytes,
+        data: bytes::Bytes,
         err: Arc<serde_bencode::Error>,
     },
 
     #[error("Failed to deserialize the bencoded response data with the error: \"{err:?}\"")]
     BencodeParseError {
-        data: hyper::body::Bytes,
+        data: bytes::Bytes,
         err: Arc<BencodeParseError>,
     },
 }
@@ -1,4 +1,5 @@
 //! Console clients.
 pub mod checker;
 pub mod http;
+pub mod persistence;
 pub mod udp;
@@ -0,0 +1,97 @@
+//! Minimal client for the tracker API endpoints used by the persistence
+//! verification.
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::{Method, Response, Url};
+use serde::Deserialize;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use crate::core::auth::Key;
+
+/// Tracker API client authenticated with an access token.
+#[allow(clippy::struct_field_names)]
+pub struct Client {
+    base_url: Url,
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct GeneratedKey {
+    key: String,
+}
+
+impl Client {
+    /// # Errors
+    ///
+    /// Will return an error if the HTTP client can't be built.
+    pub fn new(base_url: Url, token: String, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("failed to build the API client")?;
+
+        Ok(Self { base_url, token, client })
+    }
+
+    /// It generates a new authentication key valid for `seconds_valid`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the API request fails or returns an invalid key.
+    pub async fn generate_key(&self, seconds_valid: u64) -> Result<Key> {
+        let response = self.request(Method::POST, &format!("key/{seconds_valid}")).await?;
+
+        let generated_key: GeneratedKey = response.json().await.context("failed to parse the generated key")?;
+
+        Key::from_str(&generated_key.key).context("the API generated an invalid key")
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the API request fails.
+    pub async fn delete_key(&self, key: &Key) -> Result<()> {
+        self.request(Method::DELETE, &format!("key/{key}")).await.map(|_| ())
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the API request fails.
+    pub async fn whitelist(&self, info_hash: &InfoHash) -> Result<()> {
+        self.request(Method::POST, &format!("whitelist/{info_hash}"))
+            .await
+            .map(|_| ())
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the API request fails.
+    pub async fn remove_from_whitelist(&self, info_hash: &InfoHash) -> Result<()> {
+        self.request(Method::DELETE, &format!("whitelist/{info_hash}"))
+            .await
+            .map(|_| ())
+    }
+
+    async fn request(&self, method: Method, path: &str) -> Result<Response> {
+        let url = self
+            .base_url
+            .join(&format!("api/v1/{path}"))
+            .with_context(|| format!("invalid API path: {path}"))?;
+
+        let response = self
+            .client
+            .request(method.clone(), url)
+            .query(&[("token", &self.token)])
+            .send()
+            .await
+            .with_context(|| format!("failed to send the API request: {method} {path}"))?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("the API request {method} {path} failed with status {}", response.status());
+        }
+
+        Ok(response)
+    }
+}
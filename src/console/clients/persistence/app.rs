@@ -0,0 +1,326 @@
+//! Program to verify that the tracker persistent data survives a restart.
+//!
+//! Run providing the HTTP tracker, the tracker API and the command that
+//! restarts the tracker:
+//!
+//! ```text
+//! cargo run --bin verify_persistence -- \
+//!   --tracker-url http://127.0.0.1:7070 \
+//!   --api-url http://127.0.0.1:1212 \
+//!   --api-token MyAccessToken \
+//!   --restart-command "docker restart tracker"
+//! ```
+//!
+//! The restart command is run with `sh -c` and it should return once the
+//! tracker has been restarted, or at least stopped. Without a restart command,
+//! the program waits for the operator to restart the tracker by hand.
+//!
+//! The report is printed to the standard output and the program exits with an
+//! error if any check fails:
+//!
+//! ```json
+//! {
+//!   "info_hash": "4f8a8b0c1e6d3a1f2b0e9c8d7a6b5c4d3e2f1a0b",
+//!   "completed_peers": 3,
+//!   "checks": [
+//!     {
+//!       "name": "auth_key",
+//!       "status": {
+//!         "code": "ok"
+//!       }
+//!     },
+//!     {
+//!       "name": "completed_downloads",
+//!       "status": {
+//!         "code": "failed",
+//!         "message": "expected 3 completed downloads, got 0. Is `persistent_torrent_completed_stat` enabled?"
+//!       }
+//!     }
+//!   ],
+//!   "passed": false
+//! }
+//! ```
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use aquatic_udp_protocol::PeerId;
+use clap::Parser;
+use tokio::time::{sleep, Instant};
+use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use tracing::level_filters::LevelFilter;
+use url::Url;
+
+use super::api;
+use super::report::{Check, Name, Report, Status};
+use crate::core::auth::{self, Key};
+use crate::shared::bit_torrent::tracker::http::client::requests::announce::{Event, Query, QueryBuilder};
+use crate::shared::bit_torrent::tracker::http::client::requests::scrape;
+use crate::shared::bit_torrent::tracker::http::client::responses::error::Error;
+use crate::shared::bit_torrent::tracker::http::client::responses::scrape::Response;
+use crate::shared::bit_torrent::tracker::http::client::Client;
+
+/// The lifetime of the key generated for the verification.
+const KEY_LIFETIME_SECS: u64 = 3600;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The HTTP tracker URL, for example: `http://127.0.0.1:7070`.
+    #[clap(long, env = "TORRUST_VERIFY_TRACKER_URL")]
+    tracker_url: Url,
+
+    /// The tracker API URL, for example: `http://127.0.0.1:1212`.
+    #[clap(long, env = "TORRUST_VERIFY_API_URL")]
+    api_url: Url,
+
+    /// The tracker API access token.
+    #[clap(long, env = "TORRUST_VERIFY_API_TOKEN", hide_env_values = true)]
+    api_token: String,
+
+    /// The command that restarts (or signals) the tracker, for example:
+    /// `docker restart tracker`.
+    #[clap(long, env = "TORRUST_VERIFY_RESTART_COMMAND")]
+    restart_command: Option<String>,
+
+    /// The number of synthetic peers that complete the download.
+    #[clap(long, default_value_t = 3)]
+    peers: u32,
+
+    /// The seconds to wait for the tracker to count the completed downloads,
+    /// and to answer again after the restart.
+    #[clap(long, default_value_t = 60)]
+    timeout: u64,
+}
+
+/// # Errors
+///
+/// Will return an error if the verification can't be run, for example, when
+/// the tracker is not reachable or it rejects the synthetic peers before the
+/// restart.
+///
+/// # Panics
+///
+/// Will panic if the report can't be serialized.
+pub async fn run() -> Result<Report> {
+    tracing_stderr_init(LevelFilter::INFO);
+
+    let args = Args::parse();
+
+    let report = verify(&args).await?;
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("the report should be serializable")
+    );
+
+    Ok(report)
+}
+
+fn tracing_stderr_init(filter: LevelFilter) {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(filter)
+        .init();
+    tracing::debug!("Logging initialized");
+}
+
+async fn verify(args: &Args) -> Result<Report> {
+    let api = api::Client::new(args.api_url.clone(), args.api_token.clone(), DEFAULT_TIMEOUT)?;
+
+    let info_hash = InfoHash::from(rand::random::<[u8; 20]>());
+
+    let key = api.generate_key(KEY_LIFETIME_SECS).await?;
+
+    api.whitelist(&info_hash).await?;
+
+    let report = run_scenario(args, &info_hash, &key).await;
+
+    if let Err(err) = api.delete_key(&key).await {
+        tracing::warn!("Could not remove the key {key}: {err:#}");
+    }
+
+    if let Err(err) = api.remove_from_whitelist(&info_hash).await {
+        tracing::warn!("Could not remove the torrent {info_hash} from the whitelist: {err:#}");
+    }
+
+    report
+}
+
+async fn run_scenario(args: &Args, info_hash: &InfoHash, key: &Key) -> Result<Report> {
+    let timeout = Duration::from_secs(args.timeout);
+
+    let tracker = Client::authenticated(args.tracker_url.clone(), DEFAULT_TIMEOUT, key.clone())?;
+
+    tracing::info!("Announcing {} completed peers for the torrent {info_hash} ...", args.peers);
+
+    for index in 0..args.peers {
+        complete_download(&tracker, info_hash, index).await?;
+    }
+
+    let expected = i64::from(args.peers);
+
+    wait_for_downloaded(&tracker, info_hash, expected, timeout).await?;
+
+    // The key can only be verified if the tracker rejects unknown keys
+    let unknown_key = Client::authenticated(args.tracker_url.clone(), DEFAULT_TIMEOUT, auth::generate_key(None).key)?;
+
+    let keys_required = announce(&unknown_key, &peer(info_hash, args.peers)).await.is_err();
+
+    restart(args.restart_command.clone()).await?;
+
+    wait_for_tracker(&tracker, info_hash, timeout).await?;
+
+    let auth_key = if keys_required {
+        match announce(&tracker, &peer(info_hash, 0)).await {
+            Ok(()) => Status::Ok,
+            Err(err) => Status::Failed(format!("{err:#}")),
+        }
+    } else {
+        Status::Skipped("the tracker accepts unknown keys, it does not run in private mode".to_string())
+    };
+
+    let downloaded = downloaded(&tracker, info_hash).await?;
+
+    let completed_downloads = if downloaded == expected {
+        Status::Ok
+    } else {
+        Status::Failed(format!(
+            "expected {expected} completed downloads, got {downloaded}. Is `persistent_torrent_completed_stat` enabled?"
+        ))
+    };
+
+    Ok(Report::new(
+        info_hash.to_string(),
+        args.peers,
+        vec![
+            Check {
+                name: Name::AuthKey,
+                status: auth_key,
+            },
+            Check {
+                name: Name::CompletedDownloads,
+                status: completed_downloads,
+            },
+        ],
+    ))
+}
+
+/// A synthetic seeder. It does not send any event, so it does not change the
+/// number of completed downloads.
+fn peer(info_hash: &InfoHash, index: u32) -> Query {
+    let peer_id = format!("-VP0001-{index:012}");
+
+    let mut query = QueryBuilder::with_default_values()
+        .with_info_hash(info_hash)
+        .with_peer_id(&PeerId(
+            peer_id.as_bytes().try_into().expect("the peer id should have 20 bytes"),
+        ))
+        .query();
+
+    query.event = None;
+
+    query
+}
+
+/// The peer joins the swarm as a leecher and then completes the download.
+/// The tracker does not count the completed downloads of unknown peers.
+async fn complete_download(client: &Client, info_hash: &InfoHash, index: u32) -> Result<()> {
+    let mut leecher = peer(info_hash, index);
+    leecher.left = 1;
+
+    announce(client, &leecher).await?;
+
+    let mut completed = peer(info_hash, index);
+    completed.event = Some(Event::Completed);
+
+    announce(client, &completed).await
+}
+
+/// It announces the peer, failing if the tracker rejects it.
+async fn announce(client: &Client, query: &Query) -> Result<()> {
+    let response = client.announce(query).await.context("failed to announce the peer")?;
+
+    let body = response.bytes().await.context("failed to read the announce response")?;
+
+    if let Ok(error) = serde_bencode::from_bytes::<Error>(&body) {
+        anyhow::bail!("the tracker rejected the announce: {}", error.failure_reason);
+    }
+
+    Ok(())
+}
+
+/// It returns the number of completed downloads of the torrent.
+async fn downloaded(client: &Client, info_hash: &InfoHash) -> Result<i64> {
+    let query = scrape::QueryBuilder::default().with_one_info_hash(info_hash).query();
+
+    let response = client.scrape(&query).await.context("failed to scrape the torrent")?;
+
+    let body = response.bytes().await.context("failed to read the scrape response")?;
+
+    let response = Response::try_from_bencoded(&body).map_err(|err| anyhow::anyhow!("invalid scrape response: {err:?}"))?;
+
+    Ok(response.files.get(&info_hash.0).map_or(0, |file| file.downloaded))
+}
+
+/// It waits until the tracker counts all the completed downloads, since the
+/// announces could be applied in batches.
+async fn wait_for_downloaded(client: &Client, info_hash: &InfoHash, expected: i64, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let downloaded = downloaded(client, info_hash).await?;
+
+        if downloaded >= expected {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            anyhow::bail!("the tracker only counted {downloaded} of the {expected} completed downloads before the restart");
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// It waits until the tracker answers again after the restart.
+async fn wait_for_tracker(client: &Client, info_hash: &InfoHash, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match downloaded(client, info_hash).await {
+            Ok(_) => return Ok(()),
+            Err(err) if Instant::now() >= deadline => return Err(err.context("the tracker did not answer after the restart")),
+            Err(_) => sleep(POLL_INTERVAL).await,
+        }
+    }
+}
+
+async fn restart(command: Option<String>) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+        if let Some(command) = command {
+            tracing::info!("Restarting the tracker: {command}");
+
+            let status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(&command)
+                .status()
+                .with_context(|| format!("failed to run the restart command: {command}"))?;
+
+            if !status.success() {
+                anyhow::bail!("the restart command failed with {status}");
+            }
+        } else {
+            eprintln!("Restart the tracker and press Enter to continue ...");
+
+            std::io::stdin()
+                .read_line(&mut String::new())
+                .context("failed to wait for the restart")?;
+        }
+
+        Ok(())
+    })
+    .await?
+}
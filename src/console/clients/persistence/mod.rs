@@ -0,0 +1,23 @@
+//! Verification of the tracker persistent data across restarts.
+//!
+//! The [`app`] runs this scenario against a running tracker:
+//!
+//! 1. It generates a random torrent, whitelists it and generates an
+//!    authentication key, using the tracker API.
+//! 2. It announces some synthetic peers that have completed the download,
+//!    using the HTTP tracker and the generated key.
+//! 3. It restarts (or signals) the tracker, and waits until the HTTP tracker
+//!    answers again.
+//! 4. It verifies that the key is still valid and that the number of completed
+//!    downloads of the torrent did not change.
+//! 5. It removes the key and the whitelisted torrent.
+//!
+//! The result of each check is printed in the [`Report`](report::Report).
+//!
+//! > **NOTICE**: the number of completed downloads is only persisted when the
+//! > `persistent_torrent_completed_stat` option is enabled, and the keys are
+//! > only verified when the tracker runs in private mode. Otherwise, the key
+//! > check is skipped.
+pub mod api;
+pub mod app;
+pub mod report;
@@ -0,0 +1,142 @@
+//! Summary of the persistence verification.
+use serde::Serialize;
+
+/// The result of the persistence verification.
+///
+/// Sample report (JSON):
+///
+/// ```json
+/// {
+///   "info_hash": "9c38422213e30bff212b30c360d26f9a02136422",
+///   "completed_peers": 3,
+///   "checks": [
+///     {
+///       "name": "auth_key",
+///       "status": {
+///         "code": "skipped",
+///         "message": "the tracker accepts unknown keys, it does not run in private mode"
+///       }
+///     },
+///     {
+///       "name": "completed_downloads",
+///       "status": {
+///         "code": "ok"
+///       }
+///     }
+///   ],
+///   "passed": true
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// The torrent used to announce the synthetic peers.
+    pub info_hash: String,
+    /// The number of synthetic peers that completed the download.
+    pub completed_peers: u32,
+    pub checks: Vec<Check>,
+    /// `true` when none of the checks failed.
+    pub passed: bool,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(info_hash: String, completed_peers: u32, checks: Vec<Check>) -> Self {
+        let passed = !checks.iter().any(|check| matches!(check.status, Status::Failed(_)));
+
+        Self {
+            info_hash,
+            completed_peers,
+            checks,
+            passed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub name: Name,
+    pub status: Status,
+}
+
+/// The persistent data verified after the restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Name {
+    /// The authentication key generated before the restart.
+    AuthKey,
+    /// The number of completed downloads of the torrent.
+    CompletedDownloads,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Failed(String),
+    /// The check does not apply to the tracker configuration.
+    Skipped(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Check, Name, Report, Status};
+
+    fn check(name: Name, status: Status) -> Check {
+        Check { name, status }
+    }
+
+    #[test]
+    fn it_should_pass_when_no_check_failed() {
+        let report = Report::new(
+            "9c38422213e30bff212b30c360d26f9a02136422".to_string(),
+            3,
+            vec![
+                check(Name::AuthKey, Status::Skipped("not private".to_string())),
+                check(Name::CompletedDownloads, Status::Ok),
+            ],
+        );
+
+        assert!(report.passed);
+    }
+
+    #[test]
+    fn it_should_fail_when_any_check_failed() {
+        let report = Report::new(
+            "9c38422213e30bff212b30c360d26f9a02136422".to_string(),
+            3,
+            vec![
+                check(Name::AuthKey, Status::Ok),
+                check(Name::CompletedDownloads, Status::Failed("expected 3, got 0".to_string())),
+            ],
+        );
+
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn it_should_be_serialized_with_the_status_code_and_message() {
+        let report = Report::new(
+            "9c38422213e30bff212b30c360d26f9a02136422".to_string(),
+            3,
+            vec![
+                check(Name::AuthKey, Status::Failed("key not found".to_string())),
+                check(Name::CompletedDownloads, Status::Ok),
+            ],
+        );
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "info_hash": "9c38422213e30bff212b30c360d26f9a02136422",
+                "completed_peers": 3,
+                "checks": [
+                    { "name": "auth_key", "status": { "code": "failed", "message": "key not found" } },
+                    { "name": "completed_downloads", "status": { "code": "ok" } }
+                ],
+                "passed": false
+            })
+        );
+    }
+}
@@ -8,6 +8,7 @@ use aquatic_udp_protocol::{
     PeerId, PeerKey, Port, Response, ScrapeRequest, TransactionId,
 };
 use torrust_tracker_primitives::info_hash::InfoHash as TorrustInfoHash;
+use url::Url;
 
 use super::Error;
 use crate::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;
@@ -23,12 +24,15 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// It will error if unable to bind and connect to the udp remote address.
+    /// It will error if unable to bind and connect to the udp remote address,
+    /// or to open the association with the SOCKS5 `proxy` when one is given.
     ///
-    pub async fn new(remote_addr: SocketAddr, timeout: Duration) -> Result<Self, Error> {
-        let client = UdpTrackerClient::new(remote_addr, timeout)
-            .await
-            .map_err(|err| Error::UnableToBindAndConnect { remote_addr, err })?;
+    pub async fn new(remote_addr: SocketAddr, timeout: Duration, proxy: Option<&Url>) -> Result<Self, Error> {
+        let client = match proxy {
+            Some(proxy) => UdpTrackerClient::proxied(remote_addr, proxy, timeout).await,
+            None => UdpTrackerClient::new(remote_addr, timeout).await,
+        }
+        .map_err(|err| Error::UnableToBindAndConnect { remote_addr, err })?;
 
         Ok(Self { client })
     }
@@ -116,7 +120,7 @@ impl Client {
             bytes_uploaded: NumberOfBytes(0i64.into()),
             bytes_left: NumberOfBytes(0i64.into()),
             event: AnnounceEvent::Started.into(),
-            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            ip_address: Ipv4Addr::UNSPECIFIED.into(),
             key: PeerKey::new(0i32),
             peers_wanted: NumberOfPeers(1i32.into()),
             port: Port::new(port),
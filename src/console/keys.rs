@@ -0,0 +1,121 @@
+//! Program to export and import the authentication keys.
+//!
+//! It uses the database in the tracker configuration, which is loaded like
+//! the tracker does, from the `TORRUST_TRACKER_CONFIG_TOML` or the
+//! `TORRUST_TRACKER_CONFIG_TOML_PATH` environment variables.
+//!
+//! Export all the keys, with their expiration time and quota, to a file:
+//!
+//! ```text
+//! cargo run --bin keys -- export --file keys.json
+//! ```
+//!
+//! And import them, for example, into a database using another driver:
+//!
+//! ```text
+//! TORRUST_TRACKER_CONFIG_TOML_PATH="./share/default/config/tracker.container.mysql.toml" \
+//!   cargo run --bin keys -- import --file keys.json
+//! ```
+//!
+//! The export is encrypted when a passphrase is provided with the
+//! `TORRUST_KEYS_PASSPHRASE` environment variable (or the `--passphrase`
+//! option). The same passphrase is required to import it.
+//!
+//! The tracker does not need to be stopped, but the imported keys are only
+//! loaded into memory when the tracker starts or the keys are reloaded.
+//!
+//! Refer to [`key_export`](crate::core::key_export) for the file format.
+use std::fs;
+
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
+use torrust_tracker_configuration::Configuration;
+
+use crate::bootstrap::config::initialize_configuration;
+use crate::core::databases::driver::{self, Driver};
+use crate::core::key_export;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    /// The passphrase to encrypt the export, or to decrypt the import.
+    #[clap(long, global = true, env = "TORRUST_KEYS_PASSPHRASE", hide_env_values = true)]
+    passphrase: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Export all the keys in the database to a file.
+    Export {
+        /// The file the keys are written to. It's overwritten if it exists.
+        #[clap(long)]
+        file: Utf8PathBuf,
+    },
+    /// Import the keys in a file that are missing in the database.
+    Import {
+        /// The file with the exported keys.
+        #[clap(long)]
+        file: Utf8PathBuf,
+    },
+}
+
+/// # Errors
+///
+/// Will return an error if the database can't be opened, or the keys can't be
+/// exported or imported.
+///
+/// # Panics
+///
+/// Will panic if the configuration can't be loaded or the exported keys
+/// can't be serialized.
+pub fn run() -> Result<()> {
+    run_with(Args::parse(), &initialize_configuration())
+}
+
+/// It runs the command with the already parsed arguments, on the database of
+/// the given tracker configuration.
+///
+/// # Errors
+///
+/// Will return an error if the database can't be opened, or the keys can't be
+/// exported or imported.
+///
+/// # Panics
+///
+/// Will panic if the exported keys can't be serialized.
+pub fn run_with(args: Args, config: &Configuration) -> Result<()> {
+    let database = driver::build(&Driver::from(&config.core.database.driver), &config.core.database.path)
+        .context("can't open the database")?;
+
+    let passphrase = args.passphrase.as_deref();
+
+    match args.command {
+        Command::Export { file } => {
+            let export = key_export::export(database.as_ref(), passphrase)?;
+
+            let contents = serde_json::to_string_pretty(&export).expect("the keys file should be serializable");
+
+            fs::write(&file, contents).with_context(|| format!("can't write the keys file: {file}"))?;
+
+            println!("Exported the keys to {file}");
+        }
+        Command::Import { file } => {
+            let contents = fs::read_to_string(&file).with_context(|| format!("can't read the keys file: {file}"))?;
+
+            let import = serde_json::from_str(&contents).with_context(|| format!("invalid keys file: {file}"))?;
+
+            let imported = key_export::import(database.as_ref(), &import, passphrase)?;
+
+            println!(
+                "Imported {} keys from {file}, skipped {} keys already in the database",
+                imported.keys, imported.skipped
+            );
+        }
+    }
+
+    Ok(())
+}
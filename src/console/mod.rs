@@ -1,4 +1,7 @@
 //! Console apps.
+#[cfg(all(feature = "http", feature = "udp"))]
 pub mod ci;
+pub mod cli;
 pub mod clients;
+pub mod keys;
 pub mod profiling;
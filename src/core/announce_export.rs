@@ -0,0 +1,391 @@
+//! Export of the `announce` data for analytics pipelines.
+//!
+//! When the export is enabled, the tracker writes one structured record per
+//! `announce` request to a local append-only file in
+//! [NDJSON](https://github.com/ndjson/ndjson-spec) format, so operators can
+//! run offline analytics without instrumenting the tracker externally:
+//!
+//! ```json
+//! {"timestamp":1669397478934,"info_hash":"3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0","peer_hash":"c1b6a9e0...","event":"started","uploaded":0,"downloaded":0,"left":1000,"uploaded_delta":0,"downloaded_delta":0}
+//! ```
+//!
+//! The records are built from the [`PeerAnnounced`](crate::core::events::Event::PeerAnnounced)
+//! domain events, so the export does not add any work to the `announce` hot
+//! path.
+//!
+//! Peers are not identified by their IP address. The `peer_hash` is the SHA-1
+//! hash of a secret salt, the peer ID and the peer IP address. The salt
+//! prevents reversing the hash by hashing every peer ID and IP address. The
+//! hash is only stable across restarts when the salt is configured, otherwise
+//! a random salt is generated on every start.
+//!
+//! The `uploaded_delta` and `downloaded_delta` fields contain the bytes
+//! transferred since the previous exported announce from the same peer (or
+//! since the client started, for the first one).
+//!
+//! Sampling is done per peer: only one out of every `sample_one_in` peers is
+//! exported, but all the announces from a sampled peer are exported.
+//!
+//! > **NOTICE**: only the file sink is supported. Records can be shipped to a
+//! > message broker (for example, a Kafka topic) by tailing the file with the
+//! > broker's file connector.
+//!
+//! Refer to the [`AnnounceExport`] configuration for more information.
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::net::IpAddr;
+
+use aquatic_udp_protocol::{AnnounceEvent, PeerId};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use torrust_tracker_configuration::AnnounceExport;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+use super::events::Event;
+
+/// A record for one `announce` request.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Time of the announce in milliseconds since the Unix epoch.
+    pub timestamp: u128,
+    pub info_hash: String,
+    /// Hex-encoded SHA-1 hash of the salt, the peer ID and the peer IP
+    /// address.
+    pub peer_hash: String,
+    /// `started`, `stopped`, `completed` or `none`.
+    pub event: &'static str,
+    pub uploaded: i64,
+    pub downloaded: i64,
+    pub left: i64,
+    /// Bytes uploaded since the previous exported announce from the peer.
+    pub uploaded_delta: i64,
+    /// Bytes downloaded since the previous exported announce from the peer.
+    pub downloaded_delta: i64,
+}
+
+impl Record {
+    /// It returns the record as a single json line, without the trailing
+    /// new line.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the record can't be serialized, which should never
+    /// happen.
+    #[must_use]
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("it should serialize the announce record")
+    }
+}
+
+/// It builds the export records from the domain events.
+///
+/// It keeps the last uploaded and downloaded totals for every sampled peer in
+/// order to calculate the deltas. Peers are forgotten when they stop, when
+/// their torrent is removed from the repository or when they have not
+/// announced for longer than the peer timeout (see
+/// [`remove_inactive_peers`](Exporter::remove_inactive_peers)).
+#[derive(Debug)]
+pub struct Exporter {
+    sample_one_in: u64,
+    salt: Vec<u8>,
+    totals: HashMap<InfoHash, HashMap<PeerId, Totals>>,
+}
+
+/// Last uploaded and downloaded totals of a peer, and the time of its last
+/// announce.
+#[derive(Debug, Clone, Copy)]
+struct Totals {
+    uploaded: i64,
+    downloaded: i64,
+    updated: DurationSinceUnixEpoch,
+}
+
+impl Exporter {
+    #[must_use]
+    pub fn new(config: &AnnounceExport) -> Self {
+        let salt = match &config.salt {
+            Some(salt) => salt.as_bytes().to_vec(),
+            None => rand::random::<[u8; 32]>().to_vec(),
+        };
+
+        Self {
+            sample_one_in: u64::from(config.sample_one_in.max(1)),
+            salt,
+            totals: HashMap::new(),
+        }
+    }
+
+    /// It returns the record for the event, if the event is an announce from
+    /// a sampled peer.
+    pub fn handle(&mut self, event: &Event) -> Option<Record> {
+        match event {
+            Event::PeerAnnounced { info_hash, peer } => self.record(info_hash, peer),
+            Event::TorrentRemoved { info_hash } => {
+                self.totals.remove(info_hash);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// It forgets the peers that have not announced since the `cutoff`.
+    ///
+    /// The tracker removes inactive peers without a domain event, so they
+    /// have to be pruned here too, otherwise the totals would grow without
+    /// bound.
+    pub fn remove_inactive_peers(&mut self, cutoff: DurationSinceUnixEpoch) {
+        self.totals.retain(|_, peers| {
+            peers.retain(|_, totals| totals.updated >= cutoff);
+            !peers.is_empty()
+        });
+    }
+
+    /// It returns the number of peers whose totals are being tracked.
+    #[must_use]
+    pub fn tracked_peers(&self) -> usize {
+        self.totals.values().map(HashMap::len).sum()
+    }
+
+    fn record(&mut self, info_hash: &InfoHash, peer: &peer::Peer) -> Option<Record> {
+        let peer_hash = peer_hash(&self.salt, &peer.peer_id, &peer.peer_addr.ip());
+
+        if u64::from_be_bytes(peer_hash[..8].try_into().expect("it should be a 20-byte hash")) % self.sample_one_in != 0 {
+            return None;
+        }
+
+        let uploaded = peer.uploaded.0.get();
+        let downloaded = peer.downloaded.0.get();
+
+        let peers = self.totals.entry(*info_hash).or_default();
+
+        let previous = if peer.event == AnnounceEvent::Stopped {
+            peers.remove(&peer.peer_id)
+        } else {
+            peers.insert(
+                peer.peer_id,
+                Totals {
+                    uploaded,
+                    downloaded,
+                    updated: peer.updated,
+                },
+            )
+        };
+
+        let (previous_uploaded, previous_downloaded) =
+            previous.map_or((0, 0), |previous| (previous.uploaded, previous.downloaded));
+
+        if peers.is_empty() {
+            self.totals.remove(info_hash);
+        }
+
+        Some(Record {
+            timestamp: peer.updated.as_millis(),
+            info_hash: info_hash.to_hex_string(),
+            peer_hash: to_hex(&peer_hash),
+            event: event_name(peer.event),
+            uploaded,
+            downloaded,
+            left: peer.left.0.get(),
+            uploaded_delta: delta(uploaded, previous_uploaded),
+            downloaded_delta: delta(downloaded, previous_downloaded),
+        })
+    }
+}
+
+/// Clients reset their counters when they restart, so a total lower than
+/// the previous one is a delta from zero.
+fn delta(current: i64, previous: i64) -> i64 {
+    if current >= previous {
+        current - previous
+    } else {
+        current
+    }
+}
+
+fn peer_hash(salt: &[u8], peer_id: &PeerId, ip: &IpAddr) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+
+    hasher.update(salt);
+    hasher.update(peer_id.0);
+
+    match ip {
+        IpAddr::V4(ip) => hasher.update(ip.octets()),
+        IpAddr::V6(ip) => hasher.update(ip.octets()),
+    }
+
+    hasher.finalize().into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+        write!(hex, "{byte:02x}").expect("it should write to a string");
+        hex
+    })
+}
+
+fn event_name(event: AnnounceEvent) -> &'static str {
+    match event {
+        AnnounceEvent::Started => "started",
+        AnnounceEvent::Stopped => "stopped",
+        AnnounceEvent::Completed => "completed",
+        AnnounceEvent::None => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+    use camino::Utf8PathBuf;
+    use torrust_tracker_configuration::AnnounceExport;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+    use torrust_tracker_primitives::peer::Peer;
+
+    use super::Exporter;
+    use crate::core::events::Event;
+
+    fn exporter(sample_one_in: u32) -> Exporter {
+        exporter_with_salt(sample_one_in, Some("salt"))
+    }
+
+    fn exporter_with_salt(sample_one_in: u32, salt: Option<&str>) -> Exporter {
+        Exporter::new(&AnnounceExport {
+            path: Utf8PathBuf::from("announces.ndjson"),
+            sample_one_in,
+            salt: salt.map(ToString::to_string),
+        })
+    }
+
+    fn sample_info_hash() -> InfoHash {
+        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+    }
+
+    fn announced(peer: Peer) -> Event {
+        Event::PeerAnnounced {
+            info_hash: sample_info_hash(),
+            peer,
+        }
+    }
+
+    #[test]
+    fn it_should_build_a_record_for_every_announce() {
+        let mut exporter = exporter(1);
+
+        let mut peer = PeerBuilder::default().with_bytes_pending_to_download(1000).build();
+        peer.event = AnnounceEvent::Started;
+
+        let record = exporter.handle(&announced(peer)).unwrap();
+
+        assert_eq!(record.info_hash, sample_info_hash().to_hex_string());
+        assert_eq!(record.event, "started");
+        assert_eq!(record.left, 1000);
+        assert_eq!(record.peer_hash.len(), 40);
+    }
+
+    #[test]
+    fn it_should_calculate_the_transferred_bytes_since_the_previous_announce() {
+        let mut exporter = exporter(1);
+
+        let mut peer = PeerBuilder::default().build();
+
+        peer.uploaded = NumberOfBytes::new(100);
+        peer.downloaded = NumberOfBytes::new(200);
+        drop(exporter.handle(&announced(peer)));
+
+        peer.uploaded = NumberOfBytes::new(150);
+        peer.downloaded = NumberOfBytes::new(500);
+        let record = exporter.handle(&announced(peer)).unwrap();
+
+        assert_eq!((record.uploaded_delta, record.downloaded_delta), (50, 300));
+    }
+
+    #[test]
+    fn it_should_forget_the_peers_when_they_stop() {
+        let mut exporter = exporter(1);
+
+        drop(exporter.handle(&announced(PeerBuilder::default().build())));
+
+        assert_eq!(exporter.tracked_peers(), 1);
+
+        let mut stopped_peer = PeerBuilder::default().build();
+        stopped_peer.event = AnnounceEvent::Stopped;
+        drop(exporter.handle(&announced(stopped_peer)));
+
+        assert_eq!(exporter.tracked_peers(), 0);
+    }
+
+    #[test]
+    fn it_should_forget_the_peers_of_removed_torrents() {
+        let mut exporter = exporter(1);
+
+        drop(exporter.handle(&announced(PeerBuilder::default().build())));
+
+        assert!(exporter
+            .handle(&Event::TorrentRemoved {
+                info_hash: sample_info_hash()
+            })
+            .is_none());
+
+        assert_eq!(exporter.tracked_peers(), 0);
+    }
+
+    #[test]
+    fn it_should_forget_the_peers_that_have_not_announced_since_the_cutoff() {
+        let mut exporter = exporter(1);
+
+        let mut inactive_peer = PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000001")).build();
+        inactive_peer.updated = Duration::from_secs(100);
+        drop(exporter.handle(&announced(inactive_peer)));
+
+        let mut active_peer = PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000002")).build();
+        active_peer.updated = Duration::from_secs(200);
+        drop(exporter.handle(&announced(active_peer)));
+
+        exporter.remove_inactive_peers(Duration::from_secs(150));
+
+        assert_eq!(exporter.tracked_peers(), 1);
+
+        exporter.remove_inactive_peers(Duration::from_secs(250));
+
+        assert_eq!(exporter.tracked_peers(), 0);
+    }
+
+    #[test]
+    fn it_should_salt_the_peer_hash() {
+        let peer = PeerBuilder::default().build();
+
+        let hash = |salt: Option<&str>| exporter_with_salt(1, salt).handle(&announced(peer)).unwrap().peer_hash;
+
+        assert_eq!(hash(Some("salt")), hash(Some("salt")));
+        assert_ne!(hash(Some("salt")), hash(Some("pepper")));
+        assert_ne!(hash(None), hash(None));
+    }
+
+    #[test]
+    fn it_should_export_all_the_announces_from_the_sampled_peers_only() {
+        let mut exporter = exporter(4);
+
+        let peers: Vec<Peer> = (0..64u8)
+            .map(|i| {
+                let mut peer_id = *b"-qB00000000000000000";
+                peer_id[19] = i;
+                PeerBuilder::default().with_peer_id(&PeerId(peer_id)).build()
+            })
+            .collect();
+
+        let sampled: Vec<bool> = peers
+            .iter()
+            .map(|peer| exporter.handle(&announced(*peer)).is_some())
+            .collect();
+
+        assert!(sampled.iter().any(|sampled| *sampled));
+        assert!(sampled.iter().any(|sampled| !*sampled));
+
+        for (peer, was_sampled) in peers.iter().zip(sampled) {
+            assert_eq!(exporter.handle(&announced(*peer)).is_some(), was_sampled);
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//! Write-ahead journal of the `announce` requests.
+//!
+//! The peers are only kept in memory, so all the swarms are empty after a
+//! restart until the peers announce again. When the journal is enabled, a
+//! compact record of every peer update is appended to a local ring file:
+//!
+//! ```toml
+//! [core.announce_journal]
+//! path = "./storage/tracker/lib/database/announce_journal.bin"
+//! max_size_bytes = 67108864
+//! replay_window_secs = 900
+//! fsync = "interval"
+//! fsync_interval_ms = 1000
+//! ```
+//!
+//! On startup, the records from the last `replay_window_secs` seconds are
+//! replayed into the torrents repository, so the swarms are warm right away
+//! after a crash. It complements the persistence of the torrent `completed`
+//! counters: the replayed `completed` events do not increase the counters
+//! again.
+//!
+//! The record is appended in the `announce` path, before the swarm is updated
+//! and the response is sent, so no accepted `announce` is missing from the
+//! journal. With the `interval` fsync policy, the records are flushed to the
+//! disk by the [journal job](crate::bootstrap::jobs::announce_journal), so the
+//! records from the last interval can be lost if the whole system crashes.
+//!
+//! The ring file has a fixed size. Each record has a fixed size, a sequence
+//! number and a checksum, so the oldest records are overwritten in place when
+//! the file is full, and the records partially written during a crash are
+//! discarded on replay.
+//!
+//! Refer to the [`AnnounceJournal`] configuration for more information.
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+use camino::Utf8PathBuf;
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::{AnnounceJournal, JournalFsync};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+use crate::CurrentClock;
+
+/// Size in bytes of a record in the ring file.
+pub const RECORD_SIZE: usize = 104;
+
+/// Size of the record without the trailing checksum.
+const CHECKSUMMED_SIZE: usize = RECORD_SIZE - 4;
+
+/// A decoded record: its sequence number, the torrent and the peer.
+type Record = (u64, InfoHash, peer::Peer);
+
+/// Counters of the journal since the tracker started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Maximum number of records in the ring file.
+    pub capacity: u64,
+    /// Records written to the ring file.
+    pub appended: u64,
+    /// Records that could not be written to the ring file.
+    pub failed: u64,
+    /// The replay of the records on startup.
+    pub replay: Replay,
+}
+
+/// The result of replaying the journal on startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Replay {
+    /// Records applied to the torrents repository.
+    pub replayed: u64,
+    /// Records older than the replay window.
+    pub expired: u64,
+    /// Slots of the ring file with an invalid checksum, usually records
+    /// partially written during a crash.
+    pub corrupted: u64,
+    /// Time in milliseconds spent reading and applying the records.
+    pub duration_ms: u64,
+}
+
+/// The write side of the ring file.
+#[derive(Debug)]
+struct Writer {
+    file: Option<File>,
+    /// The sequence number of the next record. Sequence numbers start at `1`.
+    next_sequence: u64,
+    /// Whether there are records written since the last flush to the disk.
+    unsynced: bool,
+}
+
+/// The journal of the peer updates.
+#[derive(Debug)]
+pub struct Journal {
+    path: Utf8PathBuf,
+    capacity: u64,
+    fsync: JournalFsync,
+    writer: Mutex<Writer>,
+    /// The records to replay, until they are taken on startup.
+    pending: Mutex<Vec<(InfoHash, peer::Peer)>>,
+    appended: AtomicU64,
+    failed: AtomicU64,
+    replay: Mutex<Replay>,
+}
+
+impl Journal {
+    /// It opens the ring file, creating it if it does not exist, and reads the
+    /// records to replay.
+    #[must_use]
+    pub fn new(config: &AnnounceJournal) -> Self {
+        let started_reading = Instant::now();
+
+        let capacity = (config.max_size_bytes / RECORD_SIZE as u64).max(1);
+
+        let file = open(&config.path)
+            .map_err(|err| tracing::error!(path = %config.path, %err, "Failed to open the announce journal file"))
+            .ok();
+
+        let (records, corrupted) = match &file {
+            Some(file) => read_records(file).unwrap_or_else(|err| {
+                tracing::error!(path = %config.path, %err, "Failed to read the announce journal file");
+                (Vec::new(), 0)
+            }),
+            None => (Vec::new(), 0),
+        };
+
+        let next_sequence = records.iter().map(|(sequence, _, _)| sequence + 1).max().unwrap_or(1);
+
+        let oldest = CurrentClock::now().saturating_sub(Duration::from_secs(config.replay_window_secs));
+
+        let total = records.len();
+
+        let pending: Vec<(InfoHash, peer::Peer)> = records
+            .into_iter()
+            .filter(|(_, _, peer)| peer.updated >= oldest)
+            .map(|(_, info_hash, peer)| (info_hash, peer))
+            .collect();
+
+        Self {
+            path: config.path.clone(),
+            capacity,
+            fsync: config.fsync,
+            writer: Mutex::new(Writer {
+                file,
+                next_sequence,
+                unsynced: false,
+            }),
+            replay: Mutex::new(Replay {
+                expired: (total - pending.len()) as u64,
+                corrupted,
+                duration_ms: elapsed_ms(started_reading),
+                ..Default::default()
+            }),
+            pending: Mutex::new(pending),
+            appended: AtomicU64::default(),
+            failed: AtomicU64::default(),
+        }
+    }
+
+    /// It returns the records to replay, oldest first. They are only
+    /// returned once.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the journal lock is poisoned.
+    #[must_use]
+    pub fn take_pending(&self) -> Vec<(InfoHash, peer::Peer)> {
+        std::mem::take(&mut *self.pending.lock().expect("it should get the announce journal lock"))
+    }
+
+    /// It records the result of replaying the records returned by
+    /// [`Journal::take_pending`], which started at `started`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the journal lock is poisoned.
+    pub fn finish_replay(&self, replayed: usize, started: Instant) {
+        let mut replay = self.replay.lock().expect("it should get the announce journal lock");
+
+        replay.replayed = replayed as u64;
+        // It includes the time spent reading the file
+        replay.duration_ms += elapsed_ms(started);
+
+        tracing::info!(
+            path = %self.path,
+            replayed = replay.replayed,
+            expired = replay.expired,
+            corrupted = replay.corrupted,
+            duration_ms = replay.duration_ms,
+            "Replayed the announce journal"
+        );
+    }
+
+    /// It appends the peer update to the ring file, overwriting the oldest
+    /// record when it's full.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the journal lock is poisoned.
+    pub fn append(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+        let mut writer = self.writer.lock().expect("it should get the announce journal lock");
+
+        let sequence = writer.next_sequence;
+
+        let Some(file) = &mut writer.file else {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+
+        let offset = (sequence % self.capacity) * RECORD_SIZE as u64;
+
+        let result = file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| file.write_all(&encode(sequence, info_hash, peer)))
+            .and_then(|()| match self.fsync {
+                JournalFsync::Always => file.sync_data(),
+                JournalFsync::Interval | JournalFsync::Never => Ok(()),
+            });
+
+        match result {
+            Ok(()) => {
+                writer.next_sequence += 1;
+                writer.unsynced = self.fsync == JournalFsync::Interval;
+                self.appended.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                tracing::error!(path = %self.path, %err, "Failed to write to the announce journal file");
+                self.failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// It flushes the records written since the last flush to the disk.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the journal lock is poisoned.
+    pub fn sync(&self) {
+        let mut writer = self.writer.lock().expect("it should get the announce journal lock");
+
+        if !writer.unsynced {
+            return;
+        }
+
+        if let Some(file) = &writer.file {
+            if let Err(err) = file.sync_data() {
+                tracing::error!(path = %self.path, %err, "Failed to flush the announce journal file");
+                return;
+            }
+        }
+
+        writer.unsynced = false;
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the journal lock is poisoned.
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            capacity: self.capacity,
+            appended: self.appended.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            replay: *self.replay.lock().expect("it should get the announce journal lock"),
+        }
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+fn open(path: &Utf8PathBuf) -> io::Result<File> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// It reads the valid records in the ring file, oldest first. It also returns
+/// the number of corrupted slots.
+fn read_records(mut file: &File) -> io::Result<(Vec<Record>, u64)> {
+    let mut content = Vec::new();
+
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_end(&mut content)?;
+
+    let mut records = Vec::new();
+    let mut corrupted = 0;
+
+    for slot in content.chunks_exact(RECORD_SIZE) {
+        // Slots never written
+        if slot.iter().all(|byte| *byte == 0) {
+            continue;
+        }
+
+        match decode(slot) {
+            Some(record) => records.push(record),
+            None => corrupted += 1,
+        }
+    }
+
+    records.sort_unstable_by_key(|(sequence, _, _)| *sequence);
+
+    Ok((records, corrupted))
+}
+
+/// It encodes a record. All the integers are big-endian:
+///
+/// ```text
+/// sequence (8) | updated in ms (8) | info-hash (20) | peer ID (20) | IPv6 or IPv4-mapped IP (16) | port (2)
+/// | uploaded (8) | downloaded (8) | left (8) | event (1) | partial seed (1) | checksum (4)
+/// ```
+fn encode(sequence: u64, info_hash: &InfoHash, peer: &peer::Peer) -> [u8; RECORD_SIZE] {
+    let ip = match peer.peer_addr.ip() {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    };
+
+    let event = match peer.event {
+        AnnounceEvent::Started => 1,
+        AnnounceEvent::Stopped => 2,
+        AnnounceEvent::Completed => 3,
+        AnnounceEvent::None => 0,
+    };
+
+    let mut record = [0; RECORD_SIZE];
+
+    record[0..8].copy_from_slice(&sequence.to_be_bytes());
+    record[8..16].copy_from_slice(&u64::try_from(peer.updated.as_millis()).unwrap_or(u64::MAX).to_be_bytes());
+    record[16..36].copy_from_slice(&info_hash.bytes());
+    record[36..56].copy_from_slice(&peer.peer_id.0);
+    record[56..72].copy_from_slice(&ip.octets());
+    record[72..74].copy_from_slice(&peer.peer_addr.port().to_be_bytes());
+    record[74..82].copy_from_slice(&peer.uploaded.0.get().to_be_bytes());
+    record[82..90].copy_from_slice(&peer.downloaded.0.get().to_be_bytes());
+    record[90..98].copy_from_slice(&peer.left.0.get().to_be_bytes());
+    record[98] = event;
+    record[99] = u8::from(peer.partial_seed);
+
+    let checksum = checksum(&record[..CHECKSUMMED_SIZE]);
+    record[CHECKSUMMED_SIZE..].copy_from_slice(&checksum.to_be_bytes());
+
+    record
+}
+
+/// It decodes a record, or returns `None` if the checksum does not match.
+fn decode(record: &[u8]) -> Option<Record> {
+    let (data, expected) = record.split_at(CHECKSUMMED_SIZE);
+
+    if checksum(data).to_be_bytes() != expected {
+        return None;
+    }
+
+    let u64_at = |offset: usize| u64::from_be_bytes(data[offset..offset + 8].try_into().expect("it should be 8 bytes"));
+    let i64_at = |offset: usize| i64::from_be_bytes(data[offset..offset + 8].try_into().expect("it should be 8 bytes"));
+
+    let ip = Ipv6Addr::from(<[u8; 16]>::try_from(&data[56..72]).expect("it should be 16 bytes"));
+    let ip = match ip.to_ipv4_mapped() {
+        Some(ip) => IpAddr::V4(ip),
+        None => IpAddr::V6(ip),
+    };
+
+    let event = match data[98] {
+        1 => AnnounceEvent::Started,
+        2 => AnnounceEvent::Stopped,
+        // The completed downloads were already counted.
+        _ => AnnounceEvent::None,
+    };
+
+    let peer = peer::Peer {
+        peer_id: PeerId(data[36..56].try_into().expect("it should be 20 bytes")),
+        peer_addr: SocketAddr::new(ip, u16::from_be_bytes([data[72], data[73]])),
+        updated: DurationSinceUnixEpoch::from_millis(u64_at(8)),
+        uploaded: NumberOfBytes::new(i64_at(74)),
+        downloaded: NumberOfBytes::new(i64_at(82)),
+        left: NumberOfBytes::new(i64_at(90)),
+        event,
+        partial_seed: data[99] == 1,
+        // The extended client stats are not journaled. They are updated on
+        // the next announce of the peer.
+        corrupt: NumberOfBytes::new(0),
+        redundant: NumberOfBytes::new(0),
+    };
+
+    Some((u64_at(0), InfoHash::from_bytes(&data[16..36]), peer))
+}
+
+/// FNV-1a hash of the record.
+fn checksum(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(0x811c_9dc5, |hash, byte| (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{AnnounceEvent, PeerId};
+    use camino::Utf8PathBuf;
+    use torrust_tracker_clock::clock::stopped::Stopped as _;
+    use torrust_tracker_clock::clock::{self};
+    use torrust_tracker_configuration::{AnnounceJournal, JournalFsync};
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+    use torrust_tracker_primitives::DurationSinceUnixEpoch;
+    use torrust_tracker_test_helpers::random;
+
+    use super::{decode, encode, Journal, RECORD_SIZE};
+
+    fn config(path: &Utf8PathBuf, max_records: u64) -> AnnounceJournal {
+        AnnounceJournal {
+            path: path.clone(),
+            max_size_bytes: max_records * RECORD_SIZE as u64,
+            fsync: JournalFsync::Always,
+            ..Default::default()
+        }
+    }
+
+    fn temp_path() -> Utf8PathBuf {
+        Utf8PathBuf::from_path_buf(env::temp_dir().join(format!("announce_journal_{}.bin", random::string(16)))).unwrap()
+    }
+
+    fn peer(id: u8, updated_secs: u64) -> torrust_tracker_primitives::peer::Peer {
+        PeerBuilder::default()
+            .with_peer_id(&PeerId([id; 20]))
+            .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, id)), 8080))
+            .last_updated_on(DurationSinceUnixEpoch::from_secs(updated_secs))
+            .build()
+    }
+
+    #[test]
+    fn it_should_decode_the_encoded_records() {
+        let info_hash = InfoHash::from([1; 20]);
+        let peer = peer(1, 1_000);
+
+        assert_eq!(decode(&encode(7, &info_hash, &peer)), Some((7, info_hash, peer)));
+    }
+
+    #[test]
+    fn it_should_not_decode_the_corrupted_records() {
+        let mut record = encode(7, &InfoHash::from([1; 20]), &peer(1, 1_000));
+
+        record[20] ^= 0xff;
+
+        assert_eq!(decode(&record), None);
+    }
+
+    #[test]
+    fn it_should_not_replay_the_completed_events() {
+        let mut peer = peer(1, 1_000);
+        peer.event = AnnounceEvent::Completed;
+
+        let (_, _, decoded) = decode(&encode(7, &InfoHash::from([1; 20]), &peer)).unwrap();
+
+        assert_eq!(decoded.event, AnnounceEvent::None);
+    }
+
+    #[test]
+    fn it_should_replay_the_records_written_before_the_restart() {
+        clock::Stopped::local_set(&Duration::from_secs(1_000));
+
+        let path = temp_path();
+
+        let journal = Journal::new(&config(&path, 10));
+        journal.append(&InfoHash::from([1; 20]), &peer(1, 1_000));
+        journal.append(&InfoHash::from([2; 20]), &peer(2, 1_000));
+        drop(journal);
+
+        let journal = Journal::new(&config(&path, 10));
+
+        assert_eq!(
+            journal.take_pending(),
+            vec![
+                (InfoHash::from([1; 20]), peer(1, 1_000)),
+                (InfoHash::from([2; 20]), peer(2, 1_000))
+            ]
+        );
+        assert!(journal.take_pending().is_empty());
+    }
+
+    #[test]
+    fn it_should_overwrite_the_oldest_records_when_the_ring_file_is_full() {
+        clock::Stopped::local_set(&Duration::from_secs(1_000));
+
+        let path = temp_path();
+
+        let journal = Journal::new(&config(&path, 2));
+        for id in 1..=3 {
+            journal.append(&InfoHash::from([id; 20]), &peer(id, 1_000));
+        }
+        drop(journal);
+
+        let journal = Journal::new(&config(&path, 2));
+
+        assert_eq!(
+            journal.take_pending(),
+            vec![
+                (InfoHash::from([2; 20]), peer(2, 1_000)),
+                (InfoHash::from([3; 20]), peer(3, 1_000))
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_not_replay_the_records_older_than_the_replay_window() {
+        clock::Stopped::local_set(&Duration::from_secs(10_000));
+
+        let path = temp_path();
+
+        let journal = Journal::new(&config(&path, 10));
+        journal.append(&InfoHash::from([1; 20]), &peer(1, 1_000));
+        journal.append(&InfoHash::from([2; 20]), &peer(2, 9_500));
+        drop(journal);
+
+        let journal = Journal::new(&config(&path, 10));
+
+        assert_eq!(journal.take_pending(), vec![(InfoHash::from([2; 20]), peer(2, 9_500))]);
+        assert_eq!(journal.metrics().replay.expired, 1);
+    }
+}
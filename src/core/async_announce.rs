@@ -0,0 +1,184 @@
+//! Asynchronous `announce` updates.
+//!
+//! By default, the peer update of an `announce` request is applied to the
+//! torrents repository before building the response, so the response always
+//! includes the update (strict read-after-write). The HTTP and UDP trackers
+//! with `async_announce` enabled build the response from the current swarm
+//! instead, and queue the update. A background job applies the queued updates
+//! in arrival order.
+//!
+//! It removes the write (and the torrent entry lock) from the latency of the
+//! response. The trade-off is that the swarm is stale until the update is
+//! applied: the announcing peer is not returned to other peers, and the swarm
+//! metadata in the response does not include it yet.
+//!
+//! The queue is bounded. When it's full, the pending updates and then the new
+//! one are applied synchronously, so they are never lost and an older update
+//! of the same peer can't be applied after a newer one.
+//!
+//! Refer to the [`AsyncAnnounce`] configuration for more information.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use tokio::sync::{mpsc, Notify};
+use torrust_tracker_configuration::AsyncAnnounce;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+/// A queued peer update.
+#[derive(Debug)]
+pub struct Update {
+    pub info_hash: InfoHash,
+    pub peer: peer::Peer,
+    queued_at: Instant,
+}
+
+/// The state of the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metrics {
+    /// Number of pending updates.
+    pub queue_depth: usize,
+    /// Maximum number of pending updates.
+    pub queue_capacity: usize,
+    /// Time in milliseconds the last applied update waited in the queue.
+    pub staleness_ms: u64,
+    /// Total number of updates applied synchronously because the queue was
+    /// full.
+    pub overflows: u64,
+}
+
+/// Bounded queue of the pending peer updates.
+#[derive(Debug)]
+pub struct Queue {
+    sender: mpsc::Sender<Update>,
+    /// The receiving end. It's locked while the pending updates are applied,
+    /// so they are applied in arrival order.
+    receiver: Mutex<mpsc::Receiver<Update>>,
+    /// It's notified when an update is queued.
+    queued: Arc<Notify>,
+    staleness_ms: AtomicU64,
+    overflows: AtomicU64,
+}
+
+impl Queue {
+    #[must_use]
+    pub fn new(config: &AsyncAnnounce) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_capacity.max(1));
+
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+            queued: Arc::default(),
+            staleness_ms: AtomicU64::default(),
+            overflows: AtomicU64::default(),
+        }
+    }
+
+    /// It queues a peer update. It returns `false` when the queue is full, so
+    /// the caller has to apply the update with [`Queue::apply_pending`].
+    #[must_use]
+    pub fn enqueue(&self, info_hash: &InfoHash, peer: &peer::Peer) -> bool {
+        let update = Update {
+            info_hash: *info_hash,
+            peer: *peer,
+            queued_at: Instant::now(),
+        };
+
+        if self.sender.try_send(update).is_ok() {
+            self.queued.notify_one();
+            true
+        } else {
+            self.overflows.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+    }
+
+    /// It returns the notifier the job that applies the updates waits on. It's
+    /// notified when an update is queued.
+    #[must_use]
+    pub fn queued(&self) -> Arc<Notify> {
+        self.queued.clone()
+    }
+
+    /// It applies the pending updates in arrival order with `apply`, and then
+    /// runs `and_then` before any other queued update can be applied.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the receiver lock is poisoned.
+    pub fn apply_pending<T>(&self, mut apply: impl FnMut(&Update), and_then: impl FnOnce() -> T) -> T {
+        let mut receiver = self.receiver.lock().expect("it should get the receiver lock");
+
+        while let Ok(update) = receiver.try_recv() {
+            apply(&update);
+            self.applied(&update);
+        }
+
+        and_then()
+    }
+
+    /// It records that the update has been applied.
+    fn applied(&self, update: &Update) {
+        let staleness_ms = u64::try_from(update.queued_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        self.staleness_ms.store(staleness_ms, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            queue_depth: self.sender.max_capacity() - self.sender.capacity(),
+            queue_capacity: self.sender.max_capacity(),
+            staleness_ms: self.staleness_ms.load(Ordering::Relaxed),
+            overflows: self.overflows.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use torrust_tracker_configuration::AsyncAnnounce;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+
+    use super::Queue;
+
+    #[test]
+    fn it_should_not_queue_more_updates_than_its_capacity() {
+        let queue = Queue::new(&AsyncAnnounce { queue_capacity: 1 });
+        let peer = PeerBuilder::default().build();
+
+        assert!(queue.enqueue(&InfoHash::from([1; 20]), &peer));
+        assert!(!queue.enqueue(&InfoHash::from([2; 20]), &peer));
+
+        let metrics = queue.metrics();
+
+        assert_eq!(metrics.queue_depth, 1);
+        assert_eq!(metrics.overflows, 1);
+    }
+
+    #[test]
+    fn it_should_apply_the_pending_updates_in_arrival_order_before_the_given_one() {
+        let queue = Queue::new(&AsyncAnnounce::default());
+        let peer = PeerBuilder::default().build();
+
+        assert!(queue.enqueue(&InfoHash::from([1; 20]), &peer));
+        assert!(queue.enqueue(&InfoHash::from([2; 20]), &peer));
+
+        let applied = RefCell::new(vec![]);
+
+        queue.apply_pending(
+            |update| applied.borrow_mut().push(update.info_hash),
+            || applied.borrow_mut().push(InfoHash::from([3; 20])),
+        );
+
+        assert_eq!(
+            applied.into_inner(),
+            vec![InfoHash::from([1; 20]), InfoHash::from([2; 20]), InfoHash::from([3; 20])]
+        );
+        assert_eq!(queue.metrics().queue_depth, 0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Audit log of the administrative actions.
+//!
+//! Every state-changing request to the tracker API (creating and deleting
+//! keys, changing the whitelist or the torrent policies, reloading data from
+//! the database, etc.) is recorded with the label of the token used, the
+//! source IP and the time of the request.
+//!
+//! The entries are always stored in the `audit_log` database table. They can
+//! also be appended to a local file, one JSON object per line:
+//!
+//! ```toml
+//! [http_api]
+//! audit_log_path = "./storage/tracker/log/audit.ndjson"
+//! ```
+//!
+//! The audit log is append-only. There is no way to change or remove entries
+//! through the tracker.
+use std::io::Write;
+use std::net::IpAddr;
+
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+/// A recorded administrative action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Time of the request.
+    pub time: DurationSinceUnixEpoch,
+    /// The label of the access token used in the request.
+    pub token_label: String,
+    /// The IP of the client, if it's known. It's not known for requests
+    /// received through a Unix domain socket.
+    pub source_ip: Option<IpAddr>,
+    /// The HTTP method of the request.
+    pub method: String,
+    /// The request path, without the query, so that the token is not logged.
+    pub path: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+/// It appends the entry to the audit log file, creating it if it does not
+/// exist.
+///
+/// # Errors
+///
+/// Will return an error if the file cannot be opened or written.
+///
+/// # Panics
+///
+/// Will panic if the entry can't be serialized to json.
+pub fn append_to_file(path: &Utf8Path, entry: &Entry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(entry).expect("it should serialize the audit log entry to json");
+    line.push('\n');
+
+    // A single write, so that concurrent entries are not interleaved.
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    use camino::Utf8PathBuf;
+    use torrust_tracker_test_helpers::random;
+
+    use super::{append_to_file, Entry};
+
+    fn entry(path: &str) -> Entry {
+        Entry {
+            time: Duration::from_secs(1_729_069_200),
+            token_label: "admin".to_string(),
+            source_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            method: "POST".to_string(),
+            path: path.to_string(),
+            status: 200,
+        }
+    }
+
+    #[test]
+    fn it_should_append_the_entries_to_the_audit_log_file() {
+        let path = Utf8PathBuf::from_path_buf(
+            env::temp_dir()
+                .join(format!("audit_log_{}", random::string(16)))
+                .join("audit.ndjson"),
+        )
+        .unwrap();
+
+        append_to_file(&path, &entry("/api/v1/keys")).unwrap();
+        append_to_file(&path, &entry("/api/v1/keys/reload")).unwrap();
+
+        let entries: Vec<Entry> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries, vec![entry("/api/v1/keys"), entry("/api/v1/keys/reload")]);
+    }
+}
@@ -2,10 +2,16 @@
 //!
 //! This module contains functions to handle tracker keys.
 //! Tracker keys are tokens used to authenticate the tracker clients when the tracker runs
-//! in `private` or `private_listed` modes.
+//! in `private` or `private_listed` modes. In `hybrid` mode keys are optional,
+//! but they are verified when provided.
 //!
 //! There are services to [`generate_key`]  and [`verify_key_expiration`]  authentication keys.
 //!
+//! A key can optionally be bound to an IP address or an IP range in CIDR
+//! notation (for example `203.0.113.0/24`). Bound keys are only accepted from
+//! addresses in that range (see [`verify_key_binding`]), so a leaked announce
+//! URL can't be reused from somewhere else.
+//!
 //! Authentication keys are used only by [`HTTP`](crate::servers::http) trackers. All keys have an expiration time, that means
 //! they are only valid during a period of time. After that time the expiring key will no longer be valid.
 //!
@@ -36,8 +42,10 @@
 //! assert!(auth::verify_key_expiration(&expiring_key).is_ok());
 //! ```
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::panic::Location;
 use std::str::FromStr;
+#[cfg(feature = "sqlite")]
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -45,10 +53,13 @@ use derive_more::Display;
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 use thiserror::Error;
 use torrust_tracker_clock::clock::Time;
 use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
-use torrust_tracker_located_error::{DynError, LocatedError};
+#[cfg(feature = "sqlite")]
+use torrust_tracker_located_error::DynError;
+use torrust_tracker_located_error::LocatedError;
 use torrust_tracker_primitives::DurationSinceUnixEpoch;
 
 use crate::shared::bit_torrent::common::AUTH_KEY_LENGTH;
@@ -85,6 +96,7 @@ pub fn generate_key(lifetime: Option<Duration>) -> PeerKey {
         PeerKey {
             key: random_id.parse::<Key>().unwrap(),
             valid_until: Some(CurrentClock::now_add(&lifetime).unwrap()),
+            bound_to: None,
         }
     } else {
         tracing::debug!("Generated key: {}, permanent", random_id);
@@ -92,6 +104,7 @@ pub fn generate_key(lifetime: Option<Duration>) -> PeerKey {
         PeerKey {
             key: random_id.parse::<Key>().unwrap(),
             valid_until: None,
+            bound_to: None,
         }
     }
 }
@@ -122,6 +135,23 @@ pub fn verify_key_expiration(auth_key: &PeerKey) -> Result<(), Error> {
     }
 }
 
+/// It verifies a [`PeerKey`] is used from an address it's bound to. Keys not
+/// bound to any address (`None`) can be used from any address.
+///
+/// # Errors
+///
+/// Will return `Error::KeyBoundToAnotherAddress` if the `ip` is not in the
+/// IP range the key is bound to.
+pub fn verify_key_binding(auth_key: &PeerKey, ip: &IpAddr) -> Result<(), Error> {
+    match auth_key.bound_to {
+        Some(bound_to) if !bound_to.contains(ip) => Err(Error::KeyBoundToAnotherAddress {
+            ip: *ip,
+            location: Location::caller(),
+        }),
+        _ => Ok(()),
+    }
+}
+
 /// An authentication key which can potentially have an expiration time.
 /// After that time is will automatically become invalid.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -132,6 +162,11 @@ pub struct PeerKey {
     /// Timestamp, the key will be no longer valid after this timestamp.
     /// If `None` the keys will not expire (permanent key).
     pub valid_until: Option<DurationSinceUnixEpoch>,
+
+    /// The IP address or IP range the key can only be used from.
+    /// If `None` the key can be used from any address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound_to: Option<IpRange>,
 }
 
 impl std::fmt::Display for PeerKey {
@@ -194,6 +229,127 @@ impl Key {
     }
 }
 
+/// An IP address or an IP range in CIDR notation. For example: `203.0.113.7`,
+/// `203.0.113.0/24` or `2001:db8::/32`.
+///
+/// The address is stored without the host bits, so `203.0.113.7/24` is the
+/// same range as `203.0.113.0/24`. IPv4-mapped IPv6 addresses are matched
+/// against IPv4 ranges.
+///
+/// ```rust,no_run
+/// use std::net::IpAddr;
+/// use torrust_tracker::core::auth::IpRange;
+///
+/// let range = "203.0.113.0/24".parse::<IpRange>().unwrap();
+///
+/// assert!(range.contains(&"203.0.113.7".parse::<IpAddr>().unwrap()));
+/// assert!(!range.contains(&"198.51.100.7".parse::<IpAddr>().unwrap()));
+/// ```
+#[derive(SerializeDisplay, DeserializeFromStr, Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// It returns the range for a single IP address.
+    #[must_use]
+    pub fn single(ip: IpAddr) -> Self {
+        Self {
+            network: ip,
+            prefix_len: max_prefix_len(&ip),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return an error if the prefix length is greater than the number
+    /// of bits of the address: 32 for IPv4 and 128 for IPv6.
+    pub fn new(ip: IpAddr, prefix_len: u8) -> Result<Self, ParseIpRangeError> {
+        if prefix_len > max_prefix_len(&ip) {
+            return Err(ParseIpRangeError::InvalidPrefixLength);
+        }
+
+        let network = match ip {
+            IpAddr::V4(ip) => IpAddr::V4(Ipv4Addr::from(u32::from(ip) & ipv4_mask(prefix_len))),
+            IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & ipv6_mask(prefix_len))),
+        };
+
+        Ok(Self { network, prefix_len })
+    }
+
+    /// Whether the IP address is in the range.
+    #[must_use]
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(*ip, IpAddr::V4),
+            IpAddr::V4(_) => *ip,
+        };
+
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => u32::from(ip) & ipv4_mask(self.prefix_len) == u32::from(network),
+            (IpAddr::V6(network), IpAddr::V6(ip)) => u128::from(ip) & ipv6_mask(self.prefix_len) == u128::from(network),
+            _ => false,
+        }
+    }
+}
+
+fn max_prefix_len(ip: &IpAddr) -> u8 {
+    match ip {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn ipv4_mask(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix_len)).unwrap_or(0)
+}
+
+fn ipv6_mask(prefix_len: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix_len)).unwrap_or(0)
+}
+
+impl std::fmt::Display for IpRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.prefix_len == max_prefix_len(&self.network) {
+            write!(f, "{}", self.network)
+        } else {
+            write!(f, "{}/{}", self.network, self.prefix_len)
+        }
+    }
+}
+
+/// Error returned when an [`IpRange`] cannot be parsed from a string.
+#[derive(Debug, Error)]
+pub enum ParseIpRangeError {
+    #[error("Invalid IP address. It must be an IPv4 or IPv6 address")]
+    InvalidAddress,
+    #[error("Invalid prefix length. It can't be greater than 32 for IPv4 or 128 for IPv6")]
+    InvalidPrefixLength,
+}
+
+impl FromStr for IpRange {
+    type Err = ParseIpRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ip, prefix_len) = match s.split_once('/') {
+            Some((ip, prefix_len)) => (ip, Some(prefix_len)),
+            None => (s, None),
+        };
+
+        let ip = ip.parse::<IpAddr>().map_err(|_| ParseIpRangeError::InvalidAddress)?;
+
+        match prefix_len {
+            Some(prefix_len) => {
+                let prefix_len = prefix_len.parse::<u8>().map_err(|_| ParseIpRangeError::InvalidPrefixLength)?;
+
+                IpRange::new(ip, prefix_len)
+            }
+            None => Ok(IpRange::single(ip)),
+        }
+    }
+}
+
 /// Error returned when a key cannot be parsed from a string.
 ///
 /// ```rust,no_run
@@ -242,8 +398,14 @@ pub enum Error {
     },
     #[error("Key has expired, {location}")]
     KeyExpired { location: &'static Location<'static> },
+    #[error("Key can't be used from {ip}, {location}")]
+    KeyBoundToAnotherAddress {
+        ip: IpAddr,
+        location: &'static Location<'static>,
+    },
 }
 
+#[cfg(feature = "sqlite")]
 impl From<r2d2_sqlite::rusqlite::Error> for Error {
     fn from(e: r2d2_sqlite::rusqlite::Error) -> Self {
         Error::KeyVerificationError {
@@ -343,4 +505,91 @@ mod tests {
             assert!(auth::verify_key_expiration(&expiring_key).is_err());
         }
     }
+
+    mod ip_range {
+        use std::net::IpAddr;
+
+        use crate::core::auth::IpRange;
+
+        fn ip(ip: &str) -> IpAddr {
+            ip.parse().unwrap()
+        }
+
+        #[test]
+        fn should_be_parsed_from_a_single_ip_address() {
+            let range = "203.0.113.7".parse::<IpRange>().unwrap();
+
+            assert!(range.contains(&ip("203.0.113.7")));
+            assert!(!range.contains(&ip("203.0.113.8")));
+            assert_eq!(range.to_string(), "203.0.113.7");
+        }
+
+        #[test]
+        fn should_be_parsed_from_an_ipv4_cidr() {
+            let range = "203.0.113.7/24".parse::<IpRange>().unwrap();
+
+            assert!(range.contains(&ip("203.0.113.0")));
+            assert!(range.contains(&ip("203.0.113.255")));
+            assert!(!range.contains(&ip("203.0.114.1")));
+            assert_eq!(range.to_string(), "203.0.113.0/24");
+        }
+
+        #[test]
+        fn should_be_parsed_from_an_ipv6_cidr() {
+            let range = "2001:db8::/32".parse::<IpRange>().unwrap();
+
+            assert!(range.contains(&ip("2001:db8:1::1")));
+            assert!(!range.contains(&ip("2001:db9::1")));
+            assert!(!range.contains(&ip("203.0.113.7")));
+        }
+
+        #[test]
+        fn should_match_ipv4_mapped_ipv6_addresses_against_ipv4_ranges() {
+            let range = "203.0.113.0/24".parse::<IpRange>().unwrap();
+
+            assert!(range.contains(&ip("::ffff:203.0.113.7")));
+        }
+
+        #[test]
+        fn should_contain_any_address_of_the_same_family_with_a_zero_prefix_length() {
+            let range = "0.0.0.0/0".parse::<IpRange>().unwrap();
+
+            assert!(range.contains(&ip("198.51.100.7")));
+        }
+
+        #[test]
+        fn should_fail_parsing_an_invalid_address_or_prefix_length() {
+            assert!("203.0.113".parse::<IpRange>().is_err());
+            assert!("203.0.113.0/33".parse::<IpRange>().is_err());
+            assert!("2001:db8::/129".parse::<IpRange>().is_err());
+            assert!("203.0.113.0/".parse::<IpRange>().is_err());
+        }
+    }
+
+    mod key_binding {
+        use std::net::IpAddr;
+
+        use crate::core::auth;
+
+        #[test]
+        fn should_accept_keys_not_bound_to_any_address() {
+            let key = auth::generate_permanent_key();
+
+            assert!(auth::verify_key_binding(&key, &"198.51.100.7".parse::<IpAddr>().unwrap()).is_ok());
+        }
+
+        #[test]
+        fn should_only_accept_bound_keys_from_addresses_in_the_range() {
+            let key = auth::PeerKey {
+                bound_to: Some("203.0.113.0/24".parse().unwrap()),
+                ..auth::generate_permanent_key()
+            };
+
+            assert!(auth::verify_key_binding(&key, &"203.0.113.7".parse::<IpAddr>().unwrap()).is_ok());
+            assert!(matches!(
+                auth::verify_key_binding(&key, &"198.51.100.7".parse::<IpAddr>().unwrap()),
+                Err(auth::Error::KeyBoundToAnotherAddress { .. })
+            ));
+        }
+    }
 }
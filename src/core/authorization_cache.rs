@@ -0,0 +1,223 @@
+//! Cache of positive authorization decisions for `announce` requests.
+//!
+//! In `private_listed` mode every `announce` request verifies the peer key and
+//! looks up the torrent in the whitelist, taking the read lock of both lists.
+//! When the cache is enabled, the decision for a key and torrent that passed
+//! both checks is reused for `ttl_ms` milliseconds, so repeated announces from
+//! the same client skip the locks.
+//!
+//! Only positive decisions are cached. A cached decision is removed when:
+//!
+//! - It expires, either after `ttl_ms` or when the key expires.
+//! - The key is revoked or the keys are reloaded from the database.
+//! - The torrent is removed from the whitelist or the whitelist is reloaded.
+//!
+//! Refer to the [`AuthorizationCache`] configuration for more information.
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::AuthorizationCache;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use super::auth::Key;
+use crate::CurrentClock;
+
+/// Authorized keys and torrents, with the time until the decision is valid.
+#[derive(Debug)]
+pub struct Cache {
+    decisions: RwLock<HashMap<(Key, InfoHash), DurationSinceUnixEpoch>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new(config: &AuthorizationCache) -> Self {
+        Self {
+            decisions: RwLock::default(),
+            ttl: Duration::from_millis(config.ttl_ms),
+            capacity: config.capacity,
+        }
+    }
+
+    /// It returns `true` if the key was authorized for the torrent recently.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    #[must_use]
+    pub fn is_authorized(&self, key: &Key, info_hash: &InfoHash) -> bool {
+        self.decisions
+            .read()
+            .expect("it should get the authorization cache lock")
+            .get(&(key.clone(), *info_hash))
+            .is_some_and(|valid_until| *valid_until > CurrentClock::now())
+    }
+
+    /// It caches the authorization of the key for the torrent. The decision
+    /// is valid for the cache TTL, but never beyond the key `valid_until`.
+    ///
+    /// The decision is not cached if the cache is full of unexpired ones.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn insert(&self, key: &Key, info_hash: &InfoHash, key_valid_until: Option<DurationSinceUnixEpoch>) {
+        let now = CurrentClock::now();
+
+        let Some(valid_until) = now.checked_add(self.ttl) else {
+            return;
+        };
+
+        let valid_until = key_valid_until.map_or(valid_until, |key_valid_until| key_valid_until.min(valid_until));
+
+        let mut decisions = self.decisions.write().expect("it should get the authorization cache lock");
+
+        if decisions.len() >= self.capacity {
+            decisions.retain(|_, valid_until| *valid_until > now);
+        }
+
+        if decisions.len() < self.capacity {
+            decisions.insert((key.clone(), *info_hash), valid_until);
+        }
+    }
+
+    /// It removes the decisions for the key.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn revoke_key(&self, key: &Key) {
+        self.decisions
+            .write()
+            .expect("it should get the authorization cache lock")
+            .retain(|(cached_key, _), _| cached_key != key);
+    }
+
+    /// It removes the decisions for the torrent.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn revoke_info_hash(&self, info_hash: &InfoHash) {
+        self.decisions
+            .write()
+            .expect("it should get the authorization cache lock")
+            .retain(|(_, cached_info_hash), _| cached_info_hash != info_hash);
+    }
+
+    /// It removes all the decisions.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn clear(&self) {
+        self.decisions
+            .write()
+            .expect("it should get the authorization cache lock")
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_clock::clock::stopped::Stopped as _;
+    use torrust_tracker_clock::clock::{self, Time};
+    use torrust_tracker_configuration::AuthorizationCache;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::Cache;
+    use crate::core::auth::Key;
+    use crate::CurrentClock;
+
+    fn key() -> Key {
+        "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse().unwrap()
+    }
+
+    fn info_hash(byte: u8) -> InfoHash {
+        InfoHash::from([byte; 20])
+    }
+
+    fn cache(ttl_ms: u64, capacity: usize) -> Cache {
+        Cache::new(&AuthorizationCache::new(ttl_ms, capacity))
+    }
+
+    #[test]
+    fn it_should_reuse_a_decision_until_the_ttl_expires() {
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cache = cache(1000, 10);
+
+        cache.insert(&key(), &info_hash(1), None);
+
+        assert!(cache.is_authorized(&key(), &info_hash(1)));
+        assert!(!cache.is_authorized(&key(), &info_hash(2)));
+
+        clock::Stopped::local_add(&Duration::from_secs(1)).unwrap();
+
+        assert!(!cache.is_authorized(&key(), &info_hash(1)));
+    }
+
+    #[test]
+    fn it_should_not_reuse_a_decision_after_the_key_expires() {
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cache = cache(1000, 10);
+
+        cache.insert(
+            &key(),
+            &info_hash(1),
+            Some(CurrentClock::now_add(&Duration::from_millis(10)).unwrap()),
+        );
+
+        clock::Stopped::local_add(&Duration::from_millis(10)).unwrap();
+
+        assert!(!cache.is_authorized(&key(), &info_hash(1)));
+    }
+
+    #[test]
+    fn it_should_remove_the_decisions_for_a_revoked_key() {
+        let cache = cache(1000, 10);
+
+        cache.insert(&key(), &info_hash(1), None);
+        cache.revoke_key(&key());
+
+        assert!(!cache.is_authorized(&key(), &info_hash(1)));
+    }
+
+    #[test]
+    fn it_should_remove_the_decisions_for_a_torrent_removed_from_the_whitelist() {
+        let cache = cache(1000, 10);
+
+        cache.insert(&key(), &info_hash(1), None);
+        cache.insert(&key(), &info_hash(2), None);
+        cache.revoke_info_hash(&info_hash(1));
+
+        assert!(!cache.is_authorized(&key(), &info_hash(1)));
+        assert!(cache.is_authorized(&key(), &info_hash(2)));
+    }
+
+    #[test]
+    fn it_should_not_cache_more_decisions_than_the_capacity() {
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cache = cache(1000, 1);
+
+        cache.insert(&key(), &info_hash(1), None);
+        cache.insert(&key(), &info_hash(2), None);
+
+        assert!(!cache.is_authorized(&key(), &info_hash(2)));
+
+        // Expired decisions are evicted to make room for new ones.
+        clock::Stopped::local_add(&Duration::from_secs(1)).unwrap();
+
+        cache.insert(&key(), &info_hash(2), None);
+
+        assert!(cache.is_authorized(&key(), &info_hash(2)));
+    }
+}
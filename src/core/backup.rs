@@ -0,0 +1,261 @@
+//! Online backups of the `SQLite` database.
+//!
+//! Small deployments usually run the tracker with the `SQLite` driver and
+//! keep the authentication keys, the whitelist and the torrent policies only
+//! in the database file. When the backups are enabled, the tracker writes a
+//! consistent copy of the database into a directory on intervals:
+//!
+//! ```toml
+//! [core.database_backup]
+//! directory = "./storage/tracker/lib/database/backups"
+//! interval = 86400
+//! retention = 7
+//! ```
+//!
+//! Backups are taken with `VACUUM INTO`, so they can run while the tracker is
+//! serving requests. Each backup is a new file named after the time it was
+//! taken (for example, `sqlite3-20241016T090000.000000Z.db`). After each
+//! backup only the newest `retention` files are kept.
+//!
+//! A backup can also be triggered with the tracker API, which also reports the
+//! status of the last backup.
+//!
+//! Refer to the [`DatabaseBackup`] configuration for more information.
+use std::panic::Location;
+use std::sync::Arc;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use torrust_tracker_configuration::DatabaseBackup;
+use torrust_tracker_located_error::{DynError, LocatedError};
+
+use super::databases::{self, Database};
+
+const FILE_PREFIX: &str = "sqlite3-";
+const FILE_SUFFIX: &str = ".db";
+
+/// Errors returned when a backup can't be taken.
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("Database backups are not enabled, {location}")]
+    NotEnabled { location: &'static Location<'static> },
+
+    #[error("Failed to manage the backup files in {directory}: {source}")]
+    Directory {
+        directory: Utf8PathBuf,
+        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Failed to write the database backup: {source}")]
+    Database { source: databases::error::Error },
+}
+
+/// The result of the last backup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    /// Time when the backup finished.
+    pub finished_at: DateTime<Utc>,
+    /// The backup file, when the backup succeeded.
+    pub file: Option<Utf8PathBuf>,
+    /// The error message, when the backup failed.
+    pub error: Option<String>,
+}
+
+/// It takes the backups and keeps the status of the last one.
+pub struct Backups {
+    config: DatabaseBackup,
+    last: std::sync::RwLock<Option<Status>>,
+    /// Only one backup runs at the same time, whether it was started by the
+    /// job or by the API.
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Backups {
+    #[must_use]
+    pub fn new(config: &DatabaseBackup) -> Self {
+        Self {
+            config: config.clone(),
+            last: std::sync::RwLock::default(),
+            running: tokio::sync::Mutex::default(),
+        }
+    }
+
+    /// It returns the status of the last backup, if any.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the lock for the status is poisoned.
+    #[must_use]
+    pub fn last(&self) -> Option<Status> {
+        self.last
+            .read()
+            .expect("it should get the lock for the last backup status")
+            .clone()
+    }
+
+    /// It writes a new backup and removes the oldest ones. It returns the
+    /// path of the new backup file.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the backup directory can't be created or read,
+    /// or if the database backup fails.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the backup task can't be joined or if the lock for the
+    /// status is poisoned.
+    pub async fn run(&self, database: &Arc<Box<dyn Database>>) -> Result<Utf8PathBuf, Error> {
+        let _running = self.running.lock().await;
+
+        let directory = self.config.directory.clone();
+        let retention = self.config.retention;
+        let database = database.clone();
+
+        let result = tokio::task::spawn_blocking(move || backup(&database, &directory, retention))
+            .await
+            .expect("it should join the backup task");
+
+        let status = Status {
+            finished_at: Utc::now(),
+            file: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+
+        *self.last.write().expect("it should get the lock for the last backup status") = Some(status);
+
+        result
+    }
+}
+
+fn backup(database: &Arc<Box<dyn Database>>, directory: &Utf8Path, retention: usize) -> Result<Utf8PathBuf, Error> {
+    std::fs::create_dir_all(directory).map_err(|err| directory_error(directory, err))?;
+
+    let file = directory.join(file_name(&Utc::now()));
+
+    database.backup(file.as_str()).map_err(|source| Error::Database { source })?;
+
+    remove_old_backups(directory, retention).map_err(|err| directory_error(directory, err))?;
+
+    Ok(file)
+}
+
+/// The file names sort in chronological order.
+fn file_name(time: &DateTime<Utc>) -> String {
+    format!("{FILE_PREFIX}{}{FILE_SUFFIX}", time.format("%Y%m%dT%H%M%S%.6fZ"))
+}
+
+/// It removes the oldest backup files in the directory, keeping the newest
+/// `retention` ones. Other files in the directory are ignored.
+fn remove_old_backups(directory: &Utf8Path, retention: usize) -> Result<(), std::io::Error> {
+    let mut files = vec![];
+
+    for entry in directory.read_dir_utf8()? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        if name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX) {
+            files.push(entry.into_path());
+        }
+    }
+
+    files.sort();
+
+    let excess = files.len().saturating_sub(retention);
+
+    for file in &files[..excess] {
+        std::fs::remove_file(file)?;
+    }
+
+    Ok(())
+}
+
+#[track_caller]
+fn directory_error(directory: &Utf8Path, err: std::io::Error) -> Error {
+    Error::Directory {
+        directory: directory.to_owned(),
+        source: (Arc::new(err) as DynError).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use camino::Utf8PathBuf;
+    use chrono::{TimeZone, Utc};
+    use torrust_tracker_configuration::DatabaseBackup;
+    use torrust_tracker_test_helpers::{configuration, random};
+
+    use super::file_name;
+    use crate::core::services::tracker_factory;
+    use crate::core::Tracker;
+
+    fn temp_backup_directory() -> Utf8PathBuf {
+        let directory = env::temp_dir().join(format!("backups_{}", random::string(16)));
+        Utf8PathBuf::from_path_buf(directory).unwrap()
+    }
+
+    fn tracker_with_database_backups(directory: &Utf8PathBuf, retention: usize) -> Tracker {
+        let mut configuration = configuration::ephemeral();
+        configuration.core.database_backup = Some(DatabaseBackup {
+            directory: directory.clone(),
+            interval: 60,
+            retention,
+        });
+        tracker_factory(&configuration)
+    }
+
+    fn backup_files(directory: &Utf8PathBuf) -> Vec<Utf8PathBuf> {
+        let mut files: Vec<Utf8PathBuf> = directory
+            .read_dir_utf8()
+            .unwrap()
+            .map(|entry| entry.unwrap().into_path())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn backup_file_names_should_sort_in_chronological_order() {
+        let earlier = Utc.with_ymd_and_hms(2024, 10, 16, 9, 59, 59).unwrap();
+        let later = Utc.with_ymd_and_hms(2024, 10, 16, 10, 0, 0).unwrap();
+
+        assert_eq!(file_name(&earlier), "sqlite3-20241016T095959.000000Z.db");
+        assert!(file_name(&earlier) < file_name(&later));
+    }
+
+    #[tokio::test]
+    async fn it_should_write_a_copy_of_the_database_into_the_backup_directory() {
+        let directory = temp_backup_directory();
+        let tracker = tracker_with_database_backups(&directory, 7);
+
+        let file = tracker.backup_database().await.unwrap();
+
+        assert_eq!(backup_files(&directory), vec![file.clone()]);
+
+        let last = tracker.get_last_database_backup().unwrap();
+        assert_eq!(last.file, Some(file));
+        assert_eq!(last.error, None);
+    }
+
+    #[tokio::test]
+    async fn it_should_only_keep_the_newest_backups() {
+        let directory = temp_backup_directory();
+        let tracker = tracker_with_database_backups(&directory, 2);
+
+        tracker.backup_database().await.unwrap();
+        let second = tracker.backup_database().await.unwrap();
+        let third = tracker.backup_database().await.unwrap();
+
+        assert_eq!(backup_files(&directory), vec![second, third]);
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_the_backups_are_not_enabled() {
+        let tracker = tracker_factory(&configuration::ephemeral());
+
+        assert!(tracker.backup_database().await.is_err());
+        assert_eq!(tracker.get_last_database_backup(), None);
+    }
+}
@@ -0,0 +1,251 @@
+//! Lookups in the database of the torrents that are not in the in-memory
+//! whitelist.
+//!
+//! The whitelist is loaded in memory when the tracker starts, and it's kept
+//! up to date with the changes made through the tracker API. When the
+//! database authorization is enabled, listed trackers also look up the
+//! torrents they do not know in the database before rejecting them, so the
+//! torrents whitelisted directly in a shared database are accepted without
+//! reloading the whitelist. The torrents found are added to the in-memory
+//! whitelist.
+//!
+//! A flood of `announce` requests for unknown torrents would turn into a
+//! flood of database queries, so the lookups are protected with:
+//!
+//! - A dedicated pool of `workers` threads. The async runtime is never
+//!   blocked, and the database never gets more than `workers` concurrent
+//!   queries.
+//! - A bounded queue of pending lookups. When it's full, the lookup fails
+//!   immediately and the request is rejected with a retryable error.
+//! - Request coalescing. Concurrent lookups of the same torrent wait for the
+//!   same query.
+//!
+//! Refer to the [`DatabaseAuthorization`] configuration for more information.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+use tokio::sync::oneshot;
+use torrust_tracker_configuration::DatabaseAuthorization;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::databases::{self, Database};
+
+/// Error returned when a torrent can't be looked up in the database.
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("there are too many pending whitelist lookups in the database")]
+    QueueFull,
+
+    #[error("the whitelist lookup workers have stopped")]
+    WorkersStopped,
+
+    #[error("the whitelist lookup in the database failed: {source}")]
+    Database { source: databases::error::Error },
+}
+
+/// The waiters of the lookups in progress, by torrent.
+type Waiters = HashMap<InfoHash, Vec<oneshot::Sender<Result<bool, Error>>>>;
+
+/// The counters of the lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metrics {
+    /// Number of pending lookups.
+    pub queue_depth: usize,
+    /// Maximum number of pending lookups.
+    pub queue_capacity: usize,
+    /// Total number of lookups.
+    pub lookups: u64,
+    /// Total number of lookups that waited for the query of a concurrent
+    /// lookup of the same torrent.
+    pub coalesced: u64,
+    /// Total number of queries to the database.
+    pub queries: u64,
+    /// Total number of lookups that failed because the queue was full.
+    pub overflows: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    waiters: Mutex<Waiters>,
+    queue_depth: AtomicUsize,
+    lookups: AtomicU64,
+    coalesced: AtomicU64,
+    queries: AtomicU64,
+    overflows: AtomicU64,
+}
+
+impl State {
+    /// It sends the result of the query to all the waiters of the torrent.
+    fn resolve(&self, info_hash: &InfoHash, result: &Result<bool, Error>) {
+        let waiters = self
+            .waiters
+            .lock()
+            .expect("it should get the waiters lock")
+            .remove(info_hash)
+            .unwrap_or_default();
+
+        for waiter in waiters {
+            // The request may have been cancelled.
+            drop(waiter.send(result.clone()));
+        }
+    }
+}
+
+/// The pool of threads looking up the torrents in the database.
+#[derive(Debug)]
+pub struct Pool {
+    sender: mpsc::SyncSender<InfoHash>,
+    queue_capacity: usize,
+    state: Arc<State>,
+}
+
+impl Pool {
+    /// It starts the worker threads. They stop when the pool is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the threads can't be spawned.
+    #[must_use]
+    pub fn new(config: &DatabaseAuthorization, database: &Arc<Box<dyn Database>>) -> Self {
+        let queue_capacity = config.queue_capacity.max(1);
+        let (sender, receiver) = mpsc::sync_channel::<InfoHash>(queue_capacity);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let state = Arc::new(State::default());
+
+        for worker in 0..config.workers.max(1) {
+            let receiver = receiver.clone();
+            let database = database.clone();
+            let state = state.clone();
+
+            std::thread::Builder::new()
+                .name(format!("whitelist-lookup-{worker}"))
+                .spawn(move || loop {
+                    let next = receiver.lock().expect("it should get the receiver lock").recv();
+
+                    let Ok(info_hash) = next else {
+                        // The pool was dropped.
+                        break;
+                    };
+
+                    state.queue_depth.fetch_sub(1, Ordering::Relaxed);
+                    state.queries.fetch_add(1, Ordering::Relaxed);
+
+                    let result = database
+                        .is_info_hash_whitelisted(info_hash)
+                        .map_err(|source| Error::Database { source });
+
+                    state.resolve(&info_hash, &result);
+                })
+                .expect("it should spawn the whitelist lookup thread");
+        }
+
+        Self {
+            sender,
+            queue_capacity,
+            state,
+        }
+    }
+
+    /// It returns `true` if the torrent is whitelisted in the database. The
+    /// concurrent lookups of the same torrent wait for the same query.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the queue is full or the query fails.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the waiters lock is poisoned.
+    pub async fn is_whitelisted(&self, info_hash: &InfoHash) -> Result<bool, Error> {
+        let (waiter, result) = oneshot::channel();
+
+        self.state.lookups.fetch_add(1, Ordering::Relaxed);
+
+        {
+            let mut waiters = self.state.waiters.lock().expect("it should get the waiters lock");
+
+            if let Some(pending) = waiters.get_mut(info_hash) {
+                pending.push(waiter);
+                self.state.coalesced.fetch_add(1, Ordering::Relaxed);
+            } else {
+                // The depth is increased before sending, so the worker never
+                // decreases it below zero.
+                self.state.queue_depth.fetch_add(1, Ordering::Relaxed);
+
+                if self.sender.try_send(*info_hash).is_err() {
+                    self.state.queue_depth.fetch_sub(1, Ordering::Relaxed);
+                    self.state.overflows.fetch_add(1, Ordering::Relaxed);
+                    return Err(Error::QueueFull);
+                }
+
+                waiters.insert(*info_hash, vec![waiter]);
+            }
+        }
+
+        result.await.unwrap_or(Err(Error::WorkersStopped))
+    }
+
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            queue_depth: self.state.queue_depth.load(Ordering::Relaxed),
+            queue_capacity: self.queue_capacity,
+            lookups: self.state.lookups.load(Ordering::Relaxed),
+            coalesced: self.state.coalesced.load(Ordering::Relaxed),
+            queries: self.state.queries.load(Ordering::Relaxed),
+            overflows: self.state.overflows.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::sync::Arc;
+
+    use torrust_tracker_configuration::DatabaseAuthorization;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_test_helpers::random;
+
+    use super::Pool;
+    use crate::core::databases::driver::{self, Driver};
+    use crate::core::databases::Database;
+
+    fn database() -> Arc<Box<dyn Database>> {
+        let database_path = env::temp_dir().join(format!("whitelist_lookups_{}.db", random::string(16)));
+        Arc::new(driver::build(&Driver::Sqlite3, database_path.to_str().unwrap()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn it_should_look_up_the_torrent_in_the_database_whitelist() {
+        let database = database();
+        database.add_info_hash_to_whitelist(InfoHash::from([1; 20])).unwrap();
+
+        let pool = Pool::new(&DatabaseAuthorization::default(), &database);
+
+        assert!(pool.is_whitelisted(&InfoHash::from([1; 20])).await.unwrap());
+        assert!(!pool.is_whitelisted(&InfoHash::from([2; 20])).await.unwrap());
+        assert_eq!(pool.metrics().queries, 2);
+    }
+
+    #[tokio::test]
+    async fn it_should_coalesce_the_concurrent_lookups_of_the_same_torrent() {
+        let database = database();
+        database.add_info_hash_to_whitelist(InfoHash::from([1; 20])).unwrap();
+
+        let pool = Pool::new(&DatabaseAuthorization::default(), &database);
+
+        let info_hash = InfoHash::from([1; 20]);
+
+        let results = futures::future::join_all((0..10).map(|_| pool.is_whitelisted(&info_hash))).await;
+
+        assert!(results.into_iter().all(|result| result.unwrap()));
+
+        let metrics = pool.metrics();
+
+        assert_eq!(metrics.lookups, 10);
+        assert_eq!(metrics.coalesced + metrics.queries, 10);
+        assert_eq!(metrics.queue_depth, 0);
+    }
+}
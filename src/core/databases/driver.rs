@@ -2,10 +2,17 @@
 //!
 //! See [`databases::driver::build`](crate::core::databases::driver::build)
 //! function for more information.
+#[cfg(not(all(feature = "mysql", feature = "sqlite")))]
+use std::panic::Location;
+
 use serde::{Deserialize, Serialize};
+use torrust_tracker_configuration::v2_0_0::database;
 
 use super::error::Error;
+#[cfg(feature = "mysql")]
 use super::mysql::Mysql;
+use super::none::NoDatabase;
+#[cfg(feature = "sqlite")]
 use super::sqlite::Sqlite;
 use super::{Builder, Database};
 
@@ -23,6 +30,18 @@ pub enum Driver {
     Sqlite3,
     /// The `MySQL` database driver.
     MySQL,
+    /// No database. The tracker runs in memory only.
+    None,
+}
+
+impl From<&database::Driver> for Driver {
+    fn from(driver: &database::Driver) -> Self {
+        match driver {
+            database::Driver::Sqlite3 => Driver::Sqlite3,
+            database::Driver::MySQL => Driver::MySQL,
+            database::Driver::None => Driver::None,
+        }
+    }
 }
 
 /// It builds a new database driver.
@@ -49,6 +68,8 @@ pub enum Driver {
 /// let database = databases::driver::build(&db_driver, &db_path);
 /// ```
 ///
+/// The `None` driver does not use any database and the `db_path` is ignored.
+///
 /// Refer to the [configuration documentation](https://docs.rs/torrust-tracker-configuration)
 /// for more information about the database configuration.
 ///
@@ -56,15 +77,30 @@ pub enum Driver {
 ///
 /// # Errors
 ///
-/// This function will return an error if unable to connect to the database.
+/// This function will return an error if unable to connect to the database,
+/// or if the driver was left out of the build (see the `mysql` and `sqlite`
+/// features).
 ///
 /// # Panics
 ///
 /// This function will panic if unable to create database tables.
 pub fn build(driver: &Driver, db_path: &str) -> Result<Box<dyn Database>, Error> {
     let database = match driver {
+        #[cfg(feature = "sqlite")]
         Driver::Sqlite3 => Builder::<Sqlite>::build(db_path),
+        #[cfg(feature = "mysql")]
         Driver::MySQL => Builder::<Mysql>::build(db_path),
+        Driver::None => Builder::<NoDatabase>::build(db_path),
+        #[cfg(not(feature = "sqlite"))]
+        Driver::Sqlite3 => Err(Error::DriverNotCompiled {
+            location: Location::caller(),
+            driver: Driver::Sqlite3,
+        }),
+        #[cfg(not(feature = "mysql"))]
+        Driver::MySQL => Err(Error::DriverNotCompiled {
+            location: Location::caller(),
+            driver: Driver::MySQL,
+        }),
     }?;
 
     database.create_database_tables().expect("Could not create database tables.");
@@ -2,12 +2,17 @@
 //!
 //! This module contains the [Database errors](crate::core::databases::error::Error).
 use std::panic::Location;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
 use std::sync::Arc;
 
+#[cfg(feature = "mysql")]
 use r2d2_mysql::mysql::UrlError;
-use torrust_tracker_located_error::{DynError, Located, LocatedError};
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use torrust_tracker_located_error::DynError;
+use torrust_tracker_located_error::{Located, LocatedError};
 
 use super::driver::Driver;
+use crate::core::torrent::policy::ParseTorrentPolicyError;
 
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
@@ -40,7 +45,38 @@ pub enum Error {
         driver: Driver,
     },
 
+    /// A stored torrent policy is not a valid policy
+    #[error("The {driver} database contains an invalid torrent policy: {source}")]
+    InvalidTorrentPolicy {
+        source: LocatedError<'static, ParseTorrentPolicyError>,
+        driver: Driver,
+    },
+
+    /// The driver does not support online backups
+    #[error("Backups are not supported for the {driver} database, {location}")]
+    BackupNotSupported {
+        location: &'static Location<'static>,
+        driver: Driver,
+    },
+
+    /// The driver was left out of the build
+    #[error("The {driver} database driver was not built in this tracker, {location}")]
+    DriverNotCompiled {
+        location: &'static Location<'static>,
+        driver: Driver,
+    },
+
+    /// The tracker runs without a database
+    #[error("Persistence is disabled, the tracker runs without a database, {location}")]
+    PersistenceDisabled { location: &'static Location<'static> },
+
+    /// The write was failed on purpose by the fault injection
+    #[cfg(feature = "fault-injection")]
+    #[error("The write was failed by the fault injection, {location}")]
+    InjectedFault { location: &'static Location<'static> },
+
     /// Unable to connect to the database
+    #[cfg(feature = "mysql")]
     #[error("Failed to connect to {driver} database: {source}")]
     ConnectionError {
         source: LocatedError<'static, UrlError>,
@@ -55,6 +91,7 @@ pub enum Error {
     },
 }
 
+#[cfg(feature = "sqlite")]
 impl From<r2d2_sqlite::rusqlite::Error> for Error {
     #[track_caller]
     fn from(err: r2d2_sqlite::rusqlite::Error) -> Self {
@@ -71,6 +108,7 @@ impl From<r2d2_sqlite::rusqlite::Error> for Error {
     }
 }
 
+#[cfg(feature = "mysql")]
 impl From<r2d2_mysql::mysql::Error> for Error {
     #[track_caller]
     fn from(err: r2d2_mysql::mysql::Error) -> Self {
@@ -82,6 +120,7 @@ impl From<r2d2_mysql::mysql::Error> for Error {
     }
 }
 
+#[cfg(feature = "mysql")]
 impl From<UrlError> for Error {
     #[track_caller]
     fn from(err: UrlError) -> Self {
@@ -92,6 +131,17 @@ impl From<UrlError> for Error {
     }
 }
 
+impl From<(ParseTorrentPolicyError, Driver)> for Error {
+    #[track_caller]
+    fn from(e: (ParseTorrentPolicyError, Driver)) -> Self {
+        let (err, driver) = e;
+        Self::InvalidTorrentPolicy {
+            source: Located(err).into(),
+            driver,
+        }
+    }
+}
+
 impl From<(r2d2::Error, Driver)> for Error {
     #[track_caller]
     fn from(e: (r2d2::Error, Driver)) -> Self {
@@ -0,0 +1,261 @@
+//! A database driver that fails writes on purpose.
+//!
+//! It wraps the configured driver when the [fault injection](crate::core::faults)
+//! is enabled. The writes fail with an
+//! [`InjectedFault`](crate::core::databases::error::Error::InjectedFault)
+//! error, with the `db_write_failure_percent` probability, without reaching
+//! the wrapped driver. The reads are always delegated.
+use std::net::SocketAddr;
+use std::panic::Location;
+use std::sync::Arc;
+
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
+use torrust_tracker_primitives::PersistentTorrents;
+
+use super::none::NoDatabase;
+use super::{Database, Error};
+use crate::core::audit;
+use crate::core::auth::{self, Key};
+use crate::core::faults::Injector;
+use crate::core::invitation;
+use crate::core::key_quota::Quota;
+use crate::core::torrent::policy::TorrentPolicy;
+use crate::core::whitelist_rules::Rule;
+
+pub struct FaultyDatabase {
+    database: Box<dyn Database>,
+    injector: Arc<Injector>,
+}
+
+impl FaultyDatabase {
+    /// It wraps a database driver.
+    #[must_use]
+    pub fn wrap(database: Box<dyn Database>, injector: Arc<Injector>) -> Self {
+        Self { database, injector }
+    }
+
+    /// It returns an error when the write has to fail.
+    #[track_caller]
+    fn check_write(&self) -> Result<(), Error> {
+        if self.injector.fail_write() {
+            return Err(Error::InjectedFault {
+                location: Location::caller(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Database for FaultyDatabase {
+    /// It wraps the `None` database driver, without faults. Use
+    /// [`FaultyDatabase::wrap`] to wrap other drivers.
+    ///
+    /// Refer to [`databases::Database::new`](crate::core::databases::Database::new).
+    fn new(db_path: &str) -> Result<FaultyDatabase, Error> {
+        Ok(Self::wrap(
+            Box::new(NoDatabase::new(db_path)?),
+            Arc::new(Injector::new(&torrust_tracker_configuration::FaultInjection::default())),
+        ))
+    }
+
+    fn create_database_tables(&self) -> Result<(), Error> {
+        self.database.create_database_tables()
+    }
+
+    fn drop_database_tables(&self) -> Result<(), Error> {
+        self.database.drop_database_tables()
+    }
+
+    fn ping(&self) -> Result<(), Error> {
+        self.database.ping()
+    }
+
+    fn backup(&self, to_path: &str) -> Result<(), Error> {
+        self.database.backup(to_path)
+    }
+
+    fn table_columns(&self, table: &str) -> Result<Vec<String>, Error> {
+        self.database.table_columns(table)
+    }
+
+    fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error> {
+        self.database.load_persistent_torrents()
+    }
+
+    fn load_persistent_torrents_batch(&self, after: Option<&InfoHash>, limit: u32) -> Result<PersistentTorrents, Error> {
+        self.database.load_persistent_torrents_batch(after, limit)
+    }
+
+    fn count_persistent_torrents(&self) -> Result<u64, Error> {
+        self.database.count_persistent_torrents()
+    }
+
+    fn save_persistent_torrent(&self, info_hash: &InfoHash, downloaded: u32) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.save_persistent_torrent(info_hash, downloaded)
+    }
+
+    fn load_whitelist(&self) -> Result<Vec<InfoHash>, Error> {
+        self.database.load_whitelist()
+    }
+
+    fn get_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<Option<InfoHash>, Error> {
+        self.database.get_info_hash_from_whitelist(info_hash)
+    }
+
+    fn add_info_hash_to_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.add_info_hash_to_whitelist(info_hash)
+    }
+
+    fn remove_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_info_hash_from_whitelist(info_hash)
+    }
+
+    fn load_whitelist_rules(&self) -> Result<Vec<(u64, Rule)>, Error> {
+        self.database.load_whitelist_rules()
+    }
+
+    fn add_whitelist_rule(&self, rule: &Rule) -> Result<u64, Error> {
+        self.check_write()?;
+        self.database.add_whitelist_rule(rule)
+    }
+
+    fn remove_whitelist_rule(&self, id: u64) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_whitelist_rule(id)
+    }
+
+    fn load_torrent_policies(&self) -> Result<Vec<(InfoHash, TorrentPolicy)>, Error> {
+        self.database.load_torrent_policies()
+    }
+
+    fn save_torrent_policy(&self, info_hash: &InfoHash, policy: TorrentPolicy) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.save_torrent_policy(info_hash, policy)
+    }
+
+    fn remove_torrent_policy(&self, info_hash: &InfoHash) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_torrent_policy(info_hash)
+    }
+
+    fn load_static_peers(&self) -> Result<Vec<(InfoHash, SocketAddr)>, Error> {
+        self.database.load_static_peers()
+    }
+
+    fn add_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.add_static_peer(info_hash, peer_addr)
+    }
+
+    fn remove_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_static_peer(info_hash, peer_addr)
+    }
+
+    fn load_keys(&self) -> Result<Vec<auth::PeerKey>, Error> {
+        self.database.load_keys()
+    }
+
+    fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
+        self.database.get_key_from_keys(key)
+    }
+
+    fn add_key_to_keys(&self, auth_key: &auth::PeerKey) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.add_key_to_keys(auth_key)
+    }
+
+    fn remove_key_from_keys(&self, key: &Key) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_key_from_keys(key)
+    }
+
+    fn remove_all_keys(&self) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_all_keys()
+    }
+
+    fn load_key_quotas(&self) -> Result<Vec<(Key, Quota)>, Error> {
+        self.database.load_key_quotas()
+    }
+
+    fn save_key_quota(&self, key: &Key, quota: &Quota) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.save_key_quota(key, quota)
+    }
+
+    fn remove_key_quota(&self, key: &Key) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_key_quota(key)
+    }
+
+    fn load_invitation_codes(&self) -> Result<Vec<invitation::Code>, Error> {
+        self.database.load_invitation_codes()
+    }
+
+    fn add_invitation_code(&self, code: &invitation::Code) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.add_invitation_code(code)
+    }
+
+    fn remove_invitation_code(&self, code: &invitation::Code) -> Result<usize, Error> {
+        self.check_write()?;
+        self.database.remove_invitation_code(code)
+    }
+
+    fn add_audit_log_entry(&self, entry: &audit::Entry) -> Result<(), Error> {
+        self.check_write()?;
+        self.database.add_audit_log_entry(entry)
+    }
+
+    fn load_audit_log(&self, pagination: &Pagination) -> Result<Vec<audit::Entry>, Error> {
+        self.database.load_audit_log(pagination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use torrust_tracker_configuration::FaultInjection;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::FaultyDatabase;
+    use crate::core::databases::error::Error;
+    use crate::core::databases::none::NoDatabase;
+    use crate::core::databases::Database;
+    use crate::core::faults::Injector;
+
+    fn faulty_database(db_write_failure_percent: u8) -> FaultyDatabase {
+        FaultyDatabase::wrap(
+            Box::new(NoDatabase),
+            Arc::new(Injector::new(&FaultInjection {
+                db_write_failure_percent,
+                ..Default::default()
+            })),
+        )
+    }
+
+    #[test]
+    fn it_should_fail_the_writes_with_the_injected_fault() {
+        let database = faulty_database(100);
+
+        assert!(matches!(
+            database.save_persistent_torrent(&InfoHash::default(), 1),
+            Err(Error::InjectedFault { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_delegate_the_reads_and_the_writes_without_faults() {
+        let database = faulty_database(0);
+
+        assert!(database.save_persistent_torrent(&InfoHash::default(), 1).is_ok());
+        assert!(database.load_persistent_torrents().unwrap().is_empty());
+    }
+}
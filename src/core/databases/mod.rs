@@ -4,8 +4,13 @@
 //!
 //! There are two implementations of the trait (two drivers):
 //!
-//! - [`Mysql`](crate::core::databases::mysql::Mysql)
-//! - [`Sqlite`](crate::core::databases::sqlite::Sqlite)
+//! - [`Mysql`](crate::core::databases::mysql::Mysql), built with the `mysql` feature.
+//! - [`Sqlite`](crate::core::databases::sqlite::Sqlite), built with the `sqlite` feature.
+//!
+//! And the [`NoDatabase`](crate::core::databases::none::NoDatabase) driver,
+//! for trackers that run in memory only. The persistent objects are not
+//! stored, and the API endpoints that need them fail with a "persistence
+//! disabled" error.
 //!
 //! > **NOTICE**: There are no database migrations. If there are any changes,
 //! > we will implemented them or provide a script to migrate to the new schema.
@@ -14,7 +19,13 @@
 //!
 //! - [Torrent metrics](#torrent-metrics)
 //! - [Torrent whitelist](torrent-whitelist)
+//! - [Whitelist rules](whitelist-rules)
+//! - [Torrent policies](torrent-policies)
+//! - [Static peers](static-peers)
 //! - [Authentication keys](authentication-keys)
+//! - [Key quotas](key-quotas)
+//! - [Invitation codes](invitation-codes)
+//! - [Audit log](audit-log)
 //!
 //! # Torrent metrics
 //!
@@ -34,6 +45,31 @@
 //! `id`          | 1                                        | Autoincrement id
 //! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
 //!
+//! # Whitelist rules
+//!
+//! Field         | Sample data                              | Description
+//! ---|---|---
+//! `id`          | 1                                        | Autoincrement id
+//! `pattern`     | `c1277613*`                              | The [`Pattern`](crate::core::whitelist_rules::Pattern) of the allowed torrents
+//! `valid_until` | 1672419840                               | Timestamp for the expiring date, if any
+//! `comment`     | `Community releases`                     | Optional description of the rule
+//!
+//! # Torrent policies
+//!
+//! Field         | Sample data                              | Description
+//! ---|---|---
+//! `id`          | 1                                        | Autoincrement id
+//! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
+//! `policy`      | `seed-only`                              | The [`TorrentPolicy`](crate::core::torrent::policy::TorrentPolicy)
+//!
+//! # Static peers
+//!
+//! Field         | Sample data                              | Description
+//! ---|---|---
+//! `id`          | 1                                        | Autoincrement id
+//! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
+//! `peer_addr`   | `126.0.0.1:8080`                         | The IP and port of the [static peer](crate::core::torrent::static_peers)
+//!
 //! # Authentication keys
 //!
 //! Field         | Sample data                      | Description                  
@@ -43,18 +79,85 @@
 //! `valid_until` | 1672419840                       | Timestamp for the expiring date  
 //!
 //! > **NOTICE**: All keys must have an expiration date.
+//!
+//! # Key quotas
+//!
+//! Field                   | Sample data                      | Description
+//! ---|---|---
+//! `id`                    | 1                                | Autoincrement id
+//! `key`                   | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Token
+//! `max_announces_per_day` | 1000                             | The daily limit of `announce` requests, if any
+//! `max_peers_per_day`     | 50000                            | The daily limit of peers returned, if any
+//!
+//! See the [`key_quota`](crate::core::key_quota) module for more information.
+//!
+//! # Invitation codes
+//!
+//! Field         | Sample data                      | Description
+//! ---|---|---
+//! `id`          | 1                                | Autoincrement id
+//! `code`        | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Single-use invitation code
+//!
+//! See the [`invitation`](crate::core::invitation) module for more information.
+//!
+//! # Audit log
+//!
+//! Field         | Sample data                      | Description
+//! ---|---|---
+//! `id`          | 1                                | Autoincrement id
+//! `time`        | 1729069200                       | Timestamp of the request
+//! `token_label` | `admin`                          | The label of the API token used
+//! `source_ip`   | `127.0.0.1`                      | The client IP, if it's known
+//! `method`      | `POST`                           | The HTTP method of the request
+//! `path`        | `/api/v1/whitelist/c1277613db1d28709b034a017ab2cae4be07ae10` | The request path
+//! `status`      | 200                              | The HTTP status code of the response
+//!
+//! See the [`audit`](crate::core::audit) module for more information.
 pub mod driver;
 pub mod error;
+#[cfg(feature = "fault-injection")]
+pub mod faulty;
+#[cfg(feature = "mysql")]
 pub mod mysql;
+pub mod none;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
 
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use self::error::Error;
+use crate::core::audit;
 use crate::core::auth::{self, Key};
+use crate::core::invitation;
+use crate::core::key_quota::Quota;
+use crate::core::torrent::policy::TorrentPolicy;
+use crate::core::whitelist_rules::Rule;
+
+/// The tables and the columns the tracker expects.
+///
+/// Since the migrations are not applied automatically, the tables created by
+/// older versions of the tracker can lack some columns until the migrations in
+/// the `migrations` folder are run. The columns are compared with
+/// [`Database::table_columns`] to detect them.
+pub const SCHEMA: &[(&str, &[&str])] = &[
+    ("whitelist", &["id", "info_hash"]),
+    ("whitelist_rules", &["id", "pattern", "valid_until", "comment"]),
+    ("torrents", &["id", "info_hash", "completed"]),
+    ("torrent_policies", &["id", "info_hash", "policy"]),
+    ("torrent_static_peers", &["id", "info_hash", "peer_addr"]),
+    ("keys", &["id", "key", "valid_until", "bound_to"]),
+    ("key_quotas", &["id", "key", "max_announces_per_day", "max_peers_per_day"]),
+    ("invitation_codes", &["id", "code"]),
+    (
+        "audit_log",
+        &["id", "time", "token_label", "source_ip", "method", "path", "status"],
+    ),
+];
 
 struct Builder<T>
 where
@@ -109,6 +212,36 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to drop tables.
     fn drop_database_tables(&self) -> Result<(), Error>;
 
+    /// It checks that the database answers queries.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to run the query.
+    fn ping(&self) -> Result<(), Error>;
+
+    /// It writes a consistent copy of the whole database into a new file,
+    /// while the database is in use.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the driver does not support backups or if unable
+    /// to write the copy. The file must not exist.
+    fn backup(&self, to_path: &str) -> Result<(), Error>;
+
+    /// It returns the names of the columns of a table. The list is empty if
+    /// the table does not exist.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to run the query.
+    fn table_columns(&self, table: &str) -> Result<Vec<String>, Error>;
+
     // Torrent Metrics
 
     /// It loads the torrent metrics data from the database.
@@ -126,6 +259,31 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to load.
     fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error>;
 
+    /// It loads a batch of the torrent metrics data from the database, the
+    /// first `limit` torrents whose infohash is greater than `after`.
+    ///
+    /// It's used to stream the torrents, without loading all of them at once.
+    /// The batch is empty when there are no more torrents.
+    ///
+    /// # Context: Torrent Metrics
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_persistent_torrents_batch(&self, after: Option<&InfoHash>, limit: u32) -> Result<PersistentTorrents, Error>;
+
+    /// It returns the number of torrents with persisted metrics data.
+    ///
+    /// It's used to pre-size the torrents repository and to report the
+    /// progress of the import at startup.
+    ///
+    /// # Context: Torrent Metrics
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to count.
+    fn count_persistent_torrents(&self) -> Result<u64, Error>;
+
     /// It saves the torrent metrics data into the database.
     ///
     /// # Context: Torrent Metrics
@@ -186,6 +344,96 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to save.
     fn remove_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error>;
 
+    // Whitelist rules
+
+    /// It loads the whitelist rules from the database, with their ids.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_whitelist_rules(&self) -> Result<Vec<(u64, Rule)>, Error>;
+
+    /// It adds a whitelist rule. It returns the id of the new rule.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_whitelist_rule(&self, rule: &Rule) -> Result<u64, Error>;
+
+    /// It removes a whitelist rule. It returns the number of removed rules.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_whitelist_rule(&self, id: u64) -> Result<usize, Error>;
+
+    // Torrent policies
+
+    /// It loads the torrent policies from the database.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_torrent_policies(&self) -> Result<Vec<(InfoHash, TorrentPolicy)>, Error>;
+
+    /// It sets the policy for a torrent, replacing the previous one.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_torrent_policy(&self, info_hash: &InfoHash, policy: TorrentPolicy) -> Result<(), Error>;
+
+    /// It removes the policy for a torrent. It returns the number of removed
+    /// policies.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_torrent_policy(&self, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    // Static peers
+
+    /// It loads the static peers of all the torrents from the database.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_static_peers(&self) -> Result<Vec<(InfoHash, SocketAddr)>, Error>;
+
+    /// It adds a static peer to a torrent. Adding an existing static peer
+    /// does nothing.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), Error>;
+
+    /// It removes a static peer from a torrent. It returns the number of
+    /// removed peers.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<usize, Error>;
+
     // Authentication keys
 
     /// It loads the expiring authentication keys from the database.
@@ -226,4 +474,94 @@ pub trait Database: Sync + Send {
     ///
     /// Will return `Err` if unable to load.
     fn remove_key_from_keys(&self, key: &Key) -> Result<usize, Error>;
+
+    /// It removes all the expiring authentication keys from the database. It
+    /// returns the number of removed keys.
+    ///
+    /// # Context: Authentication Keys
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_all_keys(&self) -> Result<usize, Error>;
+
+    // Key quotas
+
+    /// It loads the quotas of the keys from the database.
+    ///
+    /// # Context: Authentication Keys
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_key_quotas(&self) -> Result<Vec<(Key, Quota)>, Error>;
+
+    /// It sets the quota for a key, replacing the previous one.
+    ///
+    /// # Context: Authentication Keys
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_key_quota(&self, key: &Key, quota: &Quota) -> Result<(), Error>;
+
+    /// It removes the quota for a key. It returns the number of removed
+    /// quotas.
+    ///
+    /// # Context: Authentication Keys
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_key_quota(&self, key: &Key) -> Result<usize, Error>;
+
+    // Invitation codes
+
+    /// It loads the unused invitation codes from the database.
+    ///
+    /// # Context: Invitation Codes
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_invitation_codes(&self) -> Result<Vec<invitation::Code>, Error>;
+
+    /// It adds an invitation code.
+    ///
+    /// # Context: Invitation Codes
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_invitation_code(&self, code: &invitation::Code) -> Result<(), Error>;
+
+    /// It removes an invitation code. It returns the number of removed codes,
+    /// so that a code can only be used once even if it's used concurrently.
+    ///
+    /// # Context: Invitation Codes
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_invitation_code(&self, code: &invitation::Code) -> Result<usize, Error>;
+
+    // Audit log
+
+    /// It appends an entry to the audit log.
+    ///
+    /// # Context: Audit Log
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_audit_log_entry(&self, entry: &audit::Entry) -> Result<(), Error>;
+
+    /// It loads a page of the audit log, newest entries first.
+    ///
+    /// # Context: Audit Log
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_audit_log(&self, pagination: &Pagination) -> Result<Vec<audit::Entry>, Error>;
 }
@@ -1,4 +1,6 @@
 //! The `MySQL` database driver.
+use std::net::SocketAddr;
+use std::panic::Location;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -7,11 +9,17 @@ use r2d2_mysql::mysql::prelude::Queryable;
 use r2d2_mysql::mysql::{params, Opts, OptsBuilder};
 use r2d2_mysql::MySqlConnectionManager;
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use super::driver::Driver;
 use super::{Database, Error};
-use crate::core::auth::{self, Key};
+use crate::core::audit;
+use crate::core::auth::{self, IpRange, Key};
+use crate::core::invitation::{self, INVITATION_CODE_LENGTH};
+use crate::core::key_quota::Quota;
+use crate::core::torrent::policy::TorrentPolicy;
+use crate::core::whitelist_rules::Rule;
 use crate::shared::bit_torrent::common::AUTH_KEY_LENGTH;
 
 const DRIVER: Driver = Driver::MySQL;
@@ -46,6 +54,15 @@ impl Database for Mysql {
         );"
         .to_string();
 
+        let create_whitelist_rules_table = "
+        CREATE TABLE IF NOT EXISTS whitelist_rules (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            pattern VARCHAR(41) NOT NULL,
+            valid_until BIGINT,
+            comment TEXT
+        );"
+        .to_string();
+
         let create_torrents_table = "
         CREATE TABLE IF NOT EXISTS torrents (
             id integer PRIMARY KEY AUTO_INCREMENT,
@@ -54,25 +71,84 @@ impl Database for Mysql {
         );"
         .to_string();
 
+        let create_torrent_policies_table = "
+        CREATE TABLE IF NOT EXISTS torrent_policies (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            info_hash VARCHAR(40) NOT NULL UNIQUE,
+            policy VARCHAR(16) NOT NULL
+        );"
+        .to_string();
+
+        let create_torrent_static_peers_table = "
+        CREATE TABLE IF NOT EXISTS torrent_static_peers (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            info_hash VARCHAR(40) NOT NULL,
+            peer_addr VARCHAR(64) NOT NULL,
+            UNIQUE (info_hash, peer_addr)
+        );"
+        .to_string();
+
         let create_keys_table = format!(
             "
         CREATE TABLE IF NOT EXISTS `keys` (
           `id` INT NOT NULL AUTO_INCREMENT,
           `key` VARCHAR({}) NOT NULL,
           `valid_until` INT(10),
+          `bound_to` VARCHAR(49),
           PRIMARY KEY (`id`),
           UNIQUE (`key`)
         );",
             i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
         );
 
+        let create_key_quotas_table = format!(
+            "
+        CREATE TABLE IF NOT EXISTS `key_quotas` (
+          `id` INT NOT NULL AUTO_INCREMENT,
+          `key` VARCHAR({}) NOT NULL,
+          `max_announces_per_day` INT UNSIGNED,
+          `max_peers_per_day` INT UNSIGNED,
+          PRIMARY KEY (`id`),
+          UNIQUE (`key`)
+        );",
+            i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
+        );
+
+        let create_invitation_codes_table = format!(
+            "
+        CREATE TABLE IF NOT EXISTS `invitation_codes` (
+          `id` INT NOT NULL AUTO_INCREMENT,
+          `code` VARCHAR({}) NOT NULL,
+          PRIMARY KEY (`id`),
+          UNIQUE (`code`)
+        );",
+            i8::try_from(INVITATION_CODE_LENGTH).expect("invitation code length should fit within a i8!")
+        );
+
+        let create_audit_log_table = "
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id integer PRIMARY KEY AUTO_INCREMENT,
+            time BIGINT NOT NULL,
+            token_label VARCHAR(255) NOT NULL,
+            source_ip VARCHAR(45),
+            method VARCHAR(16) NOT NULL,
+            path TEXT NOT NULL,
+            status SMALLINT UNSIGNED NOT NULL
+        );"
+        .to_string();
+
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
-        conn.query_drop(&create_torrents_table)
-            .expect("Could not create torrents table.");
-        conn.query_drop(&create_keys_table).expect("Could not create keys table.");
-        conn.query_drop(&create_whitelist_table)
-            .expect("Could not create whitelist table.");
+        conn.query_drop(&create_torrents_table)?;
+        conn.query_drop(&create_keys_table)?;
+
+        conn.query_drop(&create_whitelist_table)?;
+        conn.query_drop(&create_whitelist_rules_table)?;
+        conn.query_drop(&create_torrent_policies_table)?;
+        conn.query_drop(&create_torrent_static_peers_table)?;
+        conn.query_drop(&create_key_quotas_table)?;
+        conn.query_drop(&create_invitation_codes_table)?;
+        conn.query_drop(&create_audit_log_table)?;
 
         Ok(())
     }
@@ -83,6 +159,10 @@ impl Database for Mysql {
         DROP TABLE `whitelist`;"
             .to_string();
 
+        let drop_whitelist_rules_table = "
+        DROP TABLE `whitelist_rules`;"
+            .to_string();
+
         let drop_torrents_table = "
         DROP TABLE `torrents`;"
             .to_string();
@@ -91,17 +171,80 @@ impl Database for Mysql {
             DROP TABLE `keys`;"
             .to_string();
 
+        let drop_torrent_policies_table = "
+        DROP TABLE `torrent_policies`;"
+            .to_string();
+
+        let drop_torrent_static_peers_table = "
+        DROP TABLE `torrent_static_peers`;"
+            .to_string();
+
+        let drop_key_quotas_table = "
+        DROP TABLE `key_quotas`;"
+            .to_string();
+
+        let drop_invitation_codes_table = "
+        DROP TABLE `invitation_codes`;"
+            .to_string();
+
+        let drop_audit_log_table = "
+        DROP TABLE `audit_log`;"
+            .to_string();
+
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.query_drop(&drop_whitelist_table)
             .expect("Could not drop `whitelist` table.");
+        conn.query_drop(&drop_whitelist_rules_table)
+            .expect("Could not drop `whitelist_rules` table.");
         conn.query_drop(&drop_torrents_table)
             .expect("Could not drop `torrents` table.");
         conn.query_drop(&drop_keys_table).expect("Could not drop `keys` table.");
+        conn.query_drop(&drop_torrent_policies_table)
+            .expect("Could not drop `torrent_policies` table.");
+        conn.query_drop(&drop_torrent_static_peers_table)
+            .expect("Could not drop `torrent_static_peers` table.");
+        conn.query_drop(&drop_key_quotas_table)
+            .expect("Could not drop `key_quotas` table.");
+        conn.query_drop(&drop_invitation_codes_table)
+            .expect("Could not drop `invitation_codes` table.");
+        conn.query_drop(&drop_audit_log_table)
+            .expect("Could not drop `audit_log` table.");
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::ping`](crate::core::databases::Database::ping).
+    fn ping(&self) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.query_drop("SELECT 1")?;
 
         Ok(())
     }
 
+    /// Refer to [`databases::Database::backup`](crate::core::databases::Database::backup).
+    ///
+    /// Backups are not supported for `MySQL`. Use the `MySQL` tools instead.
+    fn backup(&self, _to_path: &str) -> Result<(), Error> {
+        Err(Error::BackupNotSupported {
+            location: Location::caller(),
+            driver: DRIVER,
+        })
+    }
+
+    /// Refer to [`databases::Database::table_columns`](crate::core::databases::Database::table_columns).
+    fn table_columns(&self, table: &str) -> Result<Vec<String>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let columns = conn.exec::<String, _, _>(
+            "SELECT COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = :table",
+            params! { table },
+        )?;
+
+        Ok(columns)
+    }
+
     /// Refer to [`databases::Database::load_persistent_torrents`](crate::core::databases::Database::load_persistent_torrents).
     fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error> {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -117,21 +260,41 @@ impl Database for Mysql {
         Ok(torrents.iter().copied().collect())
     }
 
+    /// Refer to [`databases::Database::load_persistent_torrents_batch`](crate::core::databases::Database::load_persistent_torrents_batch).
+    fn load_persistent_torrents_batch(&self, after: Option<&InfoHash>, limit: u32) -> Result<PersistentTorrents, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let torrents = conn.exec_map(
+            "SELECT info_hash, completed FROM torrents WHERE info_hash > :after ORDER BY info_hash LIMIT :limit",
+            params! { "after" => after.map(InfoHash::to_hex_string).unwrap_or_default(), limit },
+            |(info_hash_string, completed): (String, u32)| {
+                let info_hash = InfoHash::from_str(&info_hash_string).unwrap();
+                (info_hash, completed)
+            },
+        )?;
+
+        Ok(torrents.iter().copied().collect())
+    }
+
+    /// Refer to [`databases::Database::count_persistent_torrents`](crate::core::databases::Database::count_persistent_torrents).
+    fn count_persistent_torrents(&self) -> Result<u64, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let torrents = conn.query_first::<u64, _>("SELECT COUNT(*) FROM torrents")?;
+
+        Ok(torrents.unwrap_or_default())
+    }
+
     /// Refer to [`databases::Database::load_keys`](crate::core::databases::Database::load_keys).
     fn load_keys(&self) -> Result<Vec<auth::PeerKey>, Error> {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         let keys = conn.query_map(
-            "SELECT `key`, valid_until FROM `keys`",
-            |(key, valid_until): (String, Option<i64>)| match valid_until {
-                Some(valid_until) => auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: Some(Duration::from_secs(valid_until.unsigned_abs())),
-                },
-                None => auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: None,
-                },
+            "SELECT `key`, valid_until, bound_to FROM `keys`",
+            |(key, valid_until, bound_to): (String, Option<i64>, Option<String>)| auth::PeerKey {
+                key: key.parse::<Key>().unwrap(),
+                valid_until: valid_until.map(|valid_until| Duration::from_secs(valid_until.unsigned_abs())),
+                bound_to: bound_to.map(|bound_to| bound_to.parse::<IpRange>().unwrap()),
             },
         )?;
 
@@ -201,26 +364,164 @@ impl Database for Mysql {
         Ok(1)
     }
 
+    /// Refer to [`databases::Database::load_whitelist_rules`](crate::core::databases::Database::load_whitelist_rules).
+    fn load_whitelist_rules(&self) -> Result<Vec<(u64, Rule)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let rules = conn.query_map(
+            "SELECT id, pattern, valid_until, comment FROM whitelist_rules",
+            |(id, pattern, valid_until, comment): (u64, String, Option<u64>, Option<String>)| {
+                (
+                    id,
+                    Rule {
+                        pattern: pattern.parse().unwrap(),
+                        valid_until: valid_until.map(Duration::from_secs),
+                        comment,
+                    },
+                )
+            },
+        )?;
+
+        Ok(rules)
+    }
+
+    /// Refer to [`databases::Database::add_whitelist_rule`](crate::core::databases::Database::add_whitelist_rule).
+    fn add_whitelist_rule(&self, rule: &Rule) -> Result<u64, Error> {
+        const COMMAND: &str =
+            "INSERT INTO whitelist_rules (pattern, valid_until, comment) VALUES (:pattern, :valid_until, :comment)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let pattern = rule.pattern.to_string();
+        let valid_until = rule.valid_until.map(|valid_until| valid_until.as_secs());
+
+        conn.exec_drop(
+            COMMAND,
+            params! {
+                pattern,
+                valid_until,
+                "comment" => &rule.comment
+            },
+        )?;
+
+        Ok(conn.last_insert_id())
+    }
+
+    /// Refer to [`databases::Database::remove_whitelist_rule`](crate::core::databases::Database::remove_whitelist_rule).
+    fn remove_whitelist_rule(&self, id: u64) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop("DELETE FROM whitelist_rules WHERE id = :id", params! { id })?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("affected rows should fit in usize"))
+    }
+
+    /// Refer to [`databases::Database::load_torrent_policies`](crate::core::databases::Database::load_torrent_policies).
+    fn load_torrent_policies(&self) -> Result<Vec<(InfoHash, TorrentPolicy)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let policies = conn.query_map(
+            "SELECT info_hash, policy FROM torrent_policies",
+            |(info_hash, policy): (String, String)| (info_hash, policy),
+        )?;
+
+        policies
+            .into_iter()
+            .map(|(info_hash, policy)| {
+                Ok((
+                    InfoHash::from_str(&info_hash).unwrap(),
+                    TorrentPolicy::from_str(&policy).map_err(|e| (e, DRIVER))?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Refer to [`databases::Database::save_torrent_policy`](crate::core::databases::Database::save_torrent_policy).
+    fn save_torrent_policy(&self, info_hash: &InfoHash, policy: TorrentPolicy) -> Result<(), Error> {
+        const COMMAND : &str = "INSERT INTO torrent_policies (info_hash, policy) VALUES (:info_hash_str, :policy_str) ON DUPLICATE KEY UPDATE policy = VALUES(policy)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash_str = info_hash.to_string();
+        let policy_str = policy.to_string();
+
+        Ok(conn.exec_drop(COMMAND, params! { info_hash_str, policy_str })?)
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_policy`](crate::core::databases::Database::remove_torrent_policy).
+    fn remove_torrent_policy(&self, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash = info_hash.to_string();
+
+        conn.exec_drop(
+            "DELETE FROM torrent_policies WHERE info_hash = :info_hash",
+            params! { info_hash },
+        )?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("affected rows should fit in usize"))
+    }
+
+    /// Refer to [`databases::Database::load_static_peers`](crate::core::databases::Database::load_static_peers).
+    fn load_static_peers(&self) -> Result<Vec<(InfoHash, SocketAddr)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let static_peers = conn.query_map(
+            "SELECT info_hash, peer_addr FROM torrent_static_peers",
+            |(info_hash, peer_addr): (String, String)| {
+                (
+                    InfoHash::from_str(&info_hash).unwrap(),
+                    SocketAddr::from_str(&peer_addr).unwrap(),
+                )
+            },
+        )?;
+
+        Ok(static_peers)
+    }
+
+    /// Refer to [`databases::Database::add_static_peer`](crate::core::databases::Database::add_static_peer).
+    fn add_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), Error> {
+        const COMMAND: &str =
+            "INSERT IGNORE INTO torrent_static_peers (info_hash, peer_addr) VALUES (:info_hash_str, :peer_addr_str)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash_str = info_hash.to_string();
+        let peer_addr_str = peer_addr.to_string();
+
+        Ok(conn.exec_drop(COMMAND, params! { info_hash_str, peer_addr_str })?)
+    }
+
+    /// Refer to [`databases::Database::remove_static_peer`](crate::core::databases::Database::remove_static_peer).
+    fn remove_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash = info_hash.to_string();
+        let peer_addr = peer_addr.to_string();
+
+        conn.exec_drop(
+            "DELETE FROM torrent_static_peers WHERE info_hash = :info_hash AND peer_addr = :peer_addr",
+            params! { info_hash, peer_addr },
+        )?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("affected rows should fit in usize"))
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
-        let query = conn.exec_first::<(String, Option<i64>), _, _>(
-            "SELECT `key`, valid_until FROM `keys` WHERE `key` = :key",
+        let query = conn.exec_first::<(String, Option<i64>, Option<String>), _, _>(
+            "SELECT `key`, valid_until, bound_to FROM `keys` WHERE `key` = :key",
             params! { "key" => key.to_string() },
         );
 
         let key = query?;
 
-        Ok(key.map(|(key, opt_valid_until)| match opt_valid_until {
-            Some(valid_until) => auth::PeerKey {
-                key: key.parse::<Key>().unwrap(),
-                valid_until: Some(Duration::from_secs(valid_until.unsigned_abs())),
-            },
-            None => auth::PeerKey {
-                key: key.parse::<Key>().unwrap(),
-                valid_until: None,
-            },
+        Ok(key.map(|(key, opt_valid_until, opt_bound_to)| auth::PeerKey {
+            key: key.parse::<Key>().unwrap(),
+            valid_until: opt_valid_until.map(|valid_until| Duration::from_secs(valid_until.unsigned_abs())),
+            bound_to: opt_bound_to.map(|bound_to| bound_to.parse::<IpRange>().unwrap()),
         }))
     }
 
@@ -233,10 +534,11 @@ impl Database for Mysql {
             Some(valid_until) => valid_until.as_secs().to_string(),
             None => todo!(),
         };
+        let bound_to = auth_key.bound_to.map(|bound_to| bound_to.to_string());
 
         conn.exec_drop(
-            "INSERT INTO `keys` (`key`, valid_until) VALUES (:key, :valid_until)",
-            params! { key, valid_until },
+            "INSERT INTO `keys` (`key`, valid_until, bound_to) VALUES (:key, :valid_until, :bound_to)",
+            params! { key, valid_until, bound_to },
         )?;
 
         Ok(1)
@@ -250,4 +552,137 @@ impl Database for Mysql {
 
         Ok(1)
     }
+
+    /// Refer to [`databases::Database::remove_all_keys`](crate::core::databases::Database::remove_all_keys).
+    fn remove_all_keys(&self) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.query_drop("DELETE FROM `keys`")?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("it should fit in a usize"))
+    }
+
+    /// Refer to [`databases::Database::load_key_quotas`](crate::core::databases::Database::load_key_quotas).
+    fn load_key_quotas(&self) -> Result<Vec<(Key, Quota)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let quotas = conn.query_map(
+            "SELECT `key`, max_announces_per_day, max_peers_per_day FROM `key_quotas`",
+            |(key, max_announces_per_day, max_peers_per_day): (String, Option<u32>, Option<u32>)| {
+                (
+                    key.parse::<Key>().unwrap(),
+                    Quota {
+                        max_announces_per_day,
+                        max_peers_per_day,
+                    },
+                )
+            },
+        )?;
+
+        Ok(quotas)
+    }
+
+    /// Refer to [`databases::Database::save_key_quota`](crate::core::databases::Database::save_key_quota).
+    fn save_key_quota(&self, key: &Key, quota: &Quota) -> Result<(), Error> {
+        const COMMAND : &str = "INSERT INTO `key_quotas` (`key`, max_announces_per_day, max_peers_per_day) VALUES (:key, :max_announces_per_day, :max_peers_per_day) ON DUPLICATE KEY UPDATE max_announces_per_day = VALUES(max_announces_per_day), max_peers_per_day = VALUES(max_peers_per_day)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let key = key.to_string();
+
+        Ok(conn.exec_drop(
+            COMMAND,
+            params! {
+                key,
+                "max_announces_per_day" => quota.max_announces_per_day,
+                "max_peers_per_day" => quota.max_peers_per_day
+            },
+        )?)
+    }
+
+    /// Refer to [`databases::Database::remove_key_quota`](crate::core::databases::Database::remove_key_quota).
+    fn remove_key_quota(&self, key: &Key) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let key = key.to_string();
+
+        conn.exec_drop("DELETE FROM `key_quotas` WHERE `key` = :key", params! { key })?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("affected rows should fit in usize"))
+    }
+
+    /// Refer to [`databases::Database::load_invitation_codes`](crate::core::databases::Database::load_invitation_codes).
+    fn load_invitation_codes(&self) -> Result<Vec<invitation::Code>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let codes = conn.query_map("SELECT `code` FROM `invitation_codes`", |code: String| {
+            invitation::Code::from_str(&code).unwrap()
+        })?;
+
+        Ok(codes)
+    }
+
+    /// Refer to [`databases::Database::add_invitation_code`](crate::core::databases::Database::add_invitation_code).
+    fn add_invitation_code(&self, code: &invitation::Code) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let code = code.to_string();
+
+        Ok(conn.exec_drop("INSERT INTO `invitation_codes` (`code`) VALUES (:code)", params! { code })?)
+    }
+
+    /// Refer to [`databases::Database::remove_invitation_code`](crate::core::databases::Database::remove_invitation_code).
+    fn remove_invitation_code(&self, code: &invitation::Code) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let code = code.to_string();
+
+        conn.exec_drop("DELETE FROM `invitation_codes` WHERE `code` = :code", params! { code })?;
+
+        Ok(usize::try_from(conn.affected_rows()).expect("affected rows should fit in usize"))
+    }
+
+    /// Refer to [`databases::Database::add_audit_log_entry`](crate::core::databases::Database::add_audit_log_entry).
+    fn add_audit_log_entry(&self, entry: &audit::Entry) -> Result<(), Error> {
+        const COMMAND : &str = "INSERT INTO audit_log (time, token_label, source_ip, method, path, status) VALUES (:time, :token_label, :source_ip, :method, :path, :status)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let time = entry.time.as_secs();
+        let source_ip = entry.source_ip.map(|ip| ip.to_string());
+
+        Ok(conn.exec_drop(
+            COMMAND,
+            params! {
+                time,
+                "token_label" => &entry.token_label,
+                source_ip,
+                "method" => &entry.method,
+                "path" => &entry.path,
+                "status" => entry.status
+            },
+        )?)
+    }
+
+    /// Refer to [`databases::Database::load_audit_log`](crate::core::databases::Database::load_audit_log).
+    fn load_audit_log(&self, pagination: &Pagination) -> Result<Vec<audit::Entry>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let entries = conn.exec_map(
+            "SELECT time, token_label, source_ip, method, path, status FROM audit_log ORDER BY id DESC LIMIT :limit OFFSET :offset",
+            params! { "limit" => pagination.limit, "offset" => pagination.offset },
+            |(time, token_label, source_ip, method, path, status): (u64, String, Option<String>, String, String, u16)| {
+                audit::Entry {
+                    time: Duration::from_secs(time),
+                    token_label,
+                    source_ip: source_ip.and_then(|ip| ip.parse().ok()),
+                    method,
+                    path,
+                    status,
+                }
+            },
+        )?;
+
+        Ok(entries)
+    }
 }
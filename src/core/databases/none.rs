@@ -0,0 +1,222 @@
+//! The `None` database driver.
+//!
+//! It's used when the tracker runs in memory only, without any database. It
+//! does not store anything:
+//!
+//! - The data loaded at startup is always empty.
+//! - The number of completed downloads are only kept in memory and there is
+//!   no audit log, so saving them does nothing.
+//! - All the other writes, and reading the audit log, fail with a
+//!   [`PersistenceDisabled`](crate::core::databases::error::Error::PersistenceDisabled)
+//!   error.
+use std::net::SocketAddr;
+use std::panic::Location;
+
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
+use torrust_tracker_primitives::PersistentTorrents;
+
+use super::{Database, Error};
+use crate::core::audit;
+use crate::core::auth::{self, Key};
+use crate::core::invitation;
+use crate::core::key_quota::Quota;
+use crate::core::torrent::policy::TorrentPolicy;
+use crate::core::whitelist_rules::Rule;
+
+pub struct NoDatabase;
+
+#[track_caller]
+fn persistence_disabled() -> Error {
+    Error::PersistenceDisabled {
+        location: Location::caller(),
+    }
+}
+
+impl Database for NoDatabase {
+    /// It instantiates the `None` database driver. The `db_path` is ignored.
+    ///
+    /// Refer to [`databases::Database::new`](crate::core::databases::Database::new).
+    fn new(_db_path: &str) -> Result<NoDatabase, Error> {
+        Ok(NoDatabase)
+    }
+
+    /// Refer to [`databases::Database::create_database_tables`](crate::core::databases::Database::create_database_tables).
+    fn create_database_tables(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::drop_database_tables`](crate::core::databases::Database::drop_database_tables).
+    fn drop_database_tables(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::ping`](crate::core::databases::Database::ping).
+    fn ping(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::backup`](crate::core::databases::Database::backup).
+    fn backup(&self, _to_path: &str) -> Result<(), Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::table_columns`](crate::core::databases::Database::table_columns).
+    fn table_columns(&self, _table: &str) -> Result<Vec<String>, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_persistent_torrents`](crate::core::databases::Database::load_persistent_torrents).
+    fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error> {
+        Ok(PersistentTorrents::default())
+    }
+
+    /// Refer to [`databases::Database::load_persistent_torrents_batch`](crate::core::databases::Database::load_persistent_torrents_batch).
+    fn load_persistent_torrents_batch(&self, _after: Option<&InfoHash>, _limit: u32) -> Result<PersistentTorrents, Error> {
+        Ok(PersistentTorrents::default())
+    }
+
+    /// Refer to [`databases::Database::count_persistent_torrents`](crate::core::databases::Database::count_persistent_torrents).
+    fn count_persistent_torrents(&self) -> Result<u64, Error> {
+        Ok(0)
+    }
+
+    /// The counters are kept in memory only.
+    ///
+    /// Refer to [`databases::Database::save_persistent_torrent`](crate::core::databases::Database::save_persistent_torrent).
+    fn save_persistent_torrent(&self, _info_hash: &InfoHash, _downloaded: u32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_whitelist`](crate::core::databases::Database::load_whitelist).
+    fn load_whitelist(&self) -> Result<Vec<InfoHash>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_whitelist`](crate::core::databases::Database::get_info_hash_from_whitelist).
+    fn get_info_hash_from_whitelist(&self, _info_hash: InfoHash) -> Result<Option<InfoHash>, Error> {
+        Ok(None)
+    }
+
+    /// Refer to [`databases::Database::add_info_hash_to_whitelist`](crate::core::databases::Database::add_info_hash_to_whitelist).
+    fn add_info_hash_to_whitelist(&self, _info_hash: InfoHash) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_info_hash_from_whitelist`](crate::core::databases::Database::remove_info_hash_from_whitelist).
+    fn remove_info_hash_from_whitelist(&self, _info_hash: InfoHash) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_whitelist_rules`](crate::core::databases::Database::load_whitelist_rules).
+    fn load_whitelist_rules(&self) -> Result<Vec<(u64, Rule)>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::add_whitelist_rule`](crate::core::databases::Database::add_whitelist_rule).
+    fn add_whitelist_rule(&self, _rule: &Rule) -> Result<u64, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_whitelist_rule`](crate::core::databases::Database::remove_whitelist_rule).
+    fn remove_whitelist_rule(&self, _id: u64) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_torrent_policies`](crate::core::databases::Database::load_torrent_policies).
+    fn load_torrent_policies(&self) -> Result<Vec<(InfoHash, TorrentPolicy)>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::save_torrent_policy`](crate::core::databases::Database::save_torrent_policy).
+    fn save_torrent_policy(&self, _info_hash: &InfoHash, _policy: TorrentPolicy) -> Result<(), Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_policy`](crate::core::databases::Database::remove_torrent_policy).
+    fn remove_torrent_policy(&self, _info_hash: &InfoHash) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_static_peers`](crate::core::databases::Database::load_static_peers).
+    fn load_static_peers(&self) -> Result<Vec<(InfoHash, SocketAddr)>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::add_static_peer`](crate::core::databases::Database::add_static_peer).
+    fn add_static_peer(&self, _info_hash: &InfoHash, _peer_addr: &SocketAddr) -> Result<(), Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_static_peer`](crate::core::databases::Database::remove_static_peer).
+    fn remove_static_peer(&self, _info_hash: &InfoHash, _peer_addr: &SocketAddr) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_keys`](crate::core::databases::Database::load_keys).
+    fn load_keys(&self) -> Result<Vec<auth::PeerKey>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
+    fn get_key_from_keys(&self, _key: &Key) -> Result<Option<auth::PeerKey>, Error> {
+        Ok(None)
+    }
+
+    /// Refer to [`databases::Database::add_key_to_keys`](crate::core::databases::Database::add_key_to_keys).
+    fn add_key_to_keys(&self, _auth_key: &auth::PeerKey) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_key_from_keys`](crate::core::databases::Database::remove_key_from_keys).
+    fn remove_key_from_keys(&self, _key: &Key) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_all_keys`](crate::core::databases::Database::remove_all_keys).
+    fn remove_all_keys(&self) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_key_quotas`](crate::core::databases::Database::load_key_quotas).
+    fn load_key_quotas(&self) -> Result<Vec<(Key, Quota)>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::save_key_quota`](crate::core::databases::Database::save_key_quota).
+    fn save_key_quota(&self, _key: &Key, _quota: &Quota) -> Result<(), Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_key_quota`](crate::core::databases::Database::remove_key_quota).
+    fn remove_key_quota(&self, _key: &Key) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::load_invitation_codes`](crate::core::databases::Database::load_invitation_codes).
+    fn load_invitation_codes(&self) -> Result<Vec<invitation::Code>, Error> {
+        Ok(vec![])
+    }
+
+    /// Refer to [`databases::Database::add_invitation_code`](crate::core::databases::Database::add_invitation_code).
+    fn add_invitation_code(&self, _code: &invitation::Code) -> Result<(), Error> {
+        Err(persistence_disabled())
+    }
+
+    /// Refer to [`databases::Database::remove_invitation_code`](crate::core::databases::Database::remove_invitation_code).
+    fn remove_invitation_code(&self, _code: &invitation::Code) -> Result<usize, Error> {
+        Err(persistence_disabled())
+    }
+
+    /// The audit log is not kept.
+    ///
+    /// Refer to [`databases::Database::add_audit_log_entry`](crate::core::databases::Database::add_audit_log_entry).
+    fn add_audit_log_entry(&self, _entry: &audit::Entry) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_audit_log`](crate::core::databases::Database::load_audit_log).
+    fn load_audit_log(&self, _pagination: &Pagination) -> Result<Vec<audit::Entry>, Error> {
+        Err(persistence_disabled())
+    }
+}
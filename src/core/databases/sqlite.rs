@@ -1,17 +1,23 @@
 //! The `SQLite3` database driver.
+use std::net::SocketAddr;
 use std::panic::Location;
 use std::str::FromStr;
 
 use r2d2::Pool;
 use r2d2_sqlite::rusqlite::params;
-use r2d2_sqlite::rusqlite::types::Null;
 use r2d2_sqlite::SqliteConnectionManager;
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::{DurationSinceUnixEpoch, PersistentTorrents};
 
 use super::driver::Driver;
 use super::{Database, Error};
-use crate::core::auth::{self, Key};
+use crate::core::audit;
+use crate::core::auth::{self, IpRange, Key};
+use crate::core::invitation;
+use crate::core::key_quota::Quota;
+use crate::core::torrent::policy::TorrentPolicy;
+use crate::core::whitelist_rules::Rule;
 
 const DRIVER: Driver = Driver::Sqlite3;
 
@@ -41,6 +47,15 @@ impl Database for Sqlite {
         );"
         .to_string();
 
+        let create_whitelist_rules_table = "
+        CREATE TABLE IF NOT EXISTS whitelist_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pattern TEXT NOT NULL,
+            valid_until INTEGER,
+            comment TEXT
+        );"
+        .to_string();
+
         let create_torrents_table = "
         CREATE TABLE IF NOT EXISTS torrents (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -49,19 +64,72 @@ impl Database for Sqlite {
         );"
         .to_string();
 
+        let create_torrent_policies_table = "
+        CREATE TABLE IF NOT EXISTS torrent_policies (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            info_hash TEXT NOT NULL UNIQUE,
+            policy TEXT NOT NULL
+        );"
+        .to_string();
+
+        let create_torrent_static_peers_table = "
+        CREATE TABLE IF NOT EXISTS torrent_static_peers (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            info_hash TEXT NOT NULL,
+            peer_addr TEXT NOT NULL,
+            UNIQUE (info_hash, peer_addr)
+        );"
+        .to_string();
+
         let create_keys_table = "
         CREATE TABLE IF NOT EXISTS keys (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             key TEXT NOT NULL UNIQUE,
-            valid_until INTEGER
+            valid_until INTEGER,
+            bound_to TEXT
          );"
         .to_string();
 
+        let create_key_quotas_table = "
+        CREATE TABLE IF NOT EXISTS key_quotas (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL UNIQUE,
+            max_announces_per_day INTEGER,
+            max_peers_per_day INTEGER
+        );"
+        .to_string();
+
+        let create_invitation_codes_table = "
+        CREATE TABLE IF NOT EXISTS invitation_codes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            code TEXT NOT NULL UNIQUE
+        );"
+        .to_string();
+
+        let create_audit_log_table = "
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            time INTEGER NOT NULL,
+            token_label TEXT NOT NULL,
+            source_ip TEXT,
+            method TEXT NOT NULL,
+            path TEXT NOT NULL,
+            status INTEGER NOT NULL
+        );"
+        .to_string();
+
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.execute(&create_whitelist_table, [])?;
+        conn.execute(&create_whitelist_rules_table, [])?;
         conn.execute(&create_keys_table, [])?;
+
         conn.execute(&create_torrents_table, [])?;
+        conn.execute(&create_torrent_policies_table, [])?;
+        conn.execute(&create_torrent_static_peers_table, [])?;
+        conn.execute(&create_key_quotas_table, [])?;
+        conn.execute(&create_invitation_codes_table, [])?;
+        conn.execute(&create_audit_log_table, [])?;
 
         Ok(())
     }
@@ -72,6 +140,10 @@ impl Database for Sqlite {
         DROP TABLE whitelist;"
             .to_string();
 
+        let drop_whitelist_rules_table = "
+        DROP TABLE whitelist_rules;"
+            .to_string();
+
         let drop_torrents_table = "
         DROP TABLE torrents;"
             .to_string();
@@ -80,15 +152,70 @@ impl Database for Sqlite {
         DROP TABLE keys;"
             .to_string();
 
+        let drop_torrent_policies_table = "
+        DROP TABLE torrent_policies;"
+            .to_string();
+
+        let drop_torrent_static_peers_table = "
+        DROP TABLE torrent_static_peers;"
+            .to_string();
+
+        let drop_key_quotas_table = "
+        DROP TABLE key_quotas;"
+            .to_string();
+
+        let drop_invitation_codes_table = "
+        DROP TABLE invitation_codes;"
+            .to_string();
+
+        let drop_audit_log_table = "
+        DROP TABLE audit_log;"
+            .to_string();
+
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.execute(&drop_whitelist_table, [])
+            .and_then(|_| conn.execute(&drop_whitelist_rules_table, []))
             .and_then(|_| conn.execute(&drop_torrents_table, []))
-            .and_then(|_| conn.execute(&drop_keys_table, []))?;
+            .and_then(|_| conn.execute(&drop_keys_table, []))
+            .and_then(|_| conn.execute(&drop_torrent_policies_table, []))
+            .and_then(|_| conn.execute(&drop_torrent_static_peers_table, []))
+            .and_then(|_| conn.execute(&drop_key_quotas_table, []))
+            .and_then(|_| conn.execute(&drop_invitation_codes_table, []))
+            .and_then(|_| conn.execute(&drop_audit_log_table, []))?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::ping`](crate::core::databases::Database::ping).
+    fn ping(&self) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.query_row("SELECT 1", [], |_| Ok(()))?;
 
         Ok(())
     }
 
+    /// Refer to [`databases::Database::backup`](crate::core::databases::Database::backup).
+    fn backup(&self, to_path: &str) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute("VACUUM INTO ?", [to_path])?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::table_columns`](crate::core::databases::Database::table_columns).
+    fn table_columns(&self, table: &str) -> Result<Vec<String>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?)")?;
+
+        let column_iter = stmt.query_map([table], |row| row.get::<_, String>(0))?;
+
+        Ok(column_iter.filter_map(std::result::Result::ok).collect())
+    }
+
     /// Refer to [`databases::Database::load_persistent_torrents`](crate::core::databases::Database::load_persistent_torrents).
     fn load_persistent_torrents(&self) -> Result<PersistentTorrents, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -105,26 +232,50 @@ impl Database for Sqlite {
         Ok(torrent_iter.filter_map(std::result::Result::ok).collect())
     }
 
+    /// Refer to [`databases::Database::load_persistent_torrents_batch`](crate::core::databases::Database::load_persistent_torrents_batch).
+    fn load_persistent_torrents_batch(&self, after: Option<&InfoHash>, limit: u32) -> Result<PersistentTorrents, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt =
+            conn.prepare("SELECT info_hash, completed FROM torrents WHERE info_hash > ?1 ORDER BY info_hash LIMIT ?2")?;
+
+        let after = after.map(InfoHash::to_hex_string).unwrap_or_default();
+
+        let torrent_iter = stmt.query_map(params![after, limit], |row| {
+            let info_hash_string: String = row.get(0)?;
+            let info_hash = InfoHash::from_str(&info_hash_string).unwrap();
+            let completed: u32 = row.get(1)?;
+            Ok((info_hash, completed))
+        })?;
+
+        Ok(torrent_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::count_persistent_torrents`](crate::core::databases::Database::count_persistent_torrents).
+    fn count_persistent_torrents(&self) -> Result<u64, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let torrents: i64 = conn.query_row("SELECT COUNT(*) FROM torrents", [], |row| row.get(0))?;
+
+        Ok(torrents.unsigned_abs())
+    }
+
     /// Refer to [`databases::Database::load_keys`](crate::core::databases::Database::load_keys).
     fn load_keys(&self) -> Result<Vec<auth::PeerKey>, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
-        let mut stmt = conn.prepare("SELECT key, valid_until FROM keys")?;
+        let mut stmt = conn.prepare("SELECT key, valid_until, bound_to FROM keys")?;
 
         let keys_iter = stmt.query_map([], |row| {
             let key: String = row.get(0)?;
             let opt_valid_until: Option<i64> = row.get(1)?;
+            let opt_bound_to: Option<String> = row.get(2)?;
 
-            match opt_valid_until {
-                Some(valid_until) => Ok(auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: Some(DurationSinceUnixEpoch::from_secs(valid_until.unsigned_abs())),
-                }),
-                None => Ok(auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: None,
-                }),
-            }
+            Ok(auth::PeerKey {
+                key: key.parse::<Key>().unwrap(),
+                valid_until: opt_valid_until.map(|valid_until| DurationSinceUnixEpoch::from_secs(valid_until.unsigned_abs())),
+                bound_to: opt_bound_to.map(|bound_to| bound_to.parse::<IpRange>().unwrap()),
+            })
         })?;
 
         let keys: Vec<auth::PeerKey> = keys_iter.filter_map(std::result::Result::ok).collect();
@@ -215,11 +366,162 @@ impl Database for Sqlite {
         }
     }
 
+    /// Refer to [`databases::Database::load_whitelist_rules`](crate::core::databases::Database::load_whitelist_rules).
+    fn load_whitelist_rules(&self) -> Result<Vec<(u64, Rule)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT id, pattern, valid_until, comment FROM whitelist_rules")?;
+
+        let rule_iter = stmt.query_map([], |row| {
+            let id: u64 = row.get(0)?;
+            let pattern: String = row.get(1)?;
+            let valid_until: Option<u64> = row.get(2)?;
+
+            Ok((
+                id,
+                Rule {
+                    pattern: pattern.parse().unwrap(),
+                    valid_until: valid_until.map(DurationSinceUnixEpoch::from_secs),
+                    comment: row.get(3)?,
+                },
+            ))
+        })?;
+
+        Ok(rule_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_whitelist_rule`](crate::core::databases::Database::add_whitelist_rule).
+    fn add_whitelist_rule(&self, rule: &Rule) -> Result<u64, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO whitelist_rules (pattern, valid_until, comment) VALUES (?1, ?2, ?3)",
+            params![
+                rule.pattern.to_string(),
+                rule.valid_until.map(|valid_until| valid_until.as_secs()),
+                rule.comment
+            ],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(u64::try_from(conn.last_insert_rowid()).expect("the row id should be positive"))
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_whitelist_rule`](crate::core::databases::Database::remove_whitelist_rule).
+    fn remove_whitelist_rule(&self, id: u64) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM whitelist_rules WHERE id = ?", [id])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::load_torrent_policies`](crate::core::databases::Database::load_torrent_policies).
+    fn load_torrent_policies(&self) -> Result<Vec<(InfoHash, TorrentPolicy)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash, policy FROM torrent_policies")?;
+
+        let policy_iter = stmt.query_map([], |row| {
+            let info_hash: String = row.get(0)?;
+            let policy: String = row.get(1)?;
+
+            Ok((info_hash, policy))
+        })?;
+
+        policy_iter
+            .filter_map(std::result::Result::ok)
+            .map(|(info_hash, policy)| {
+                Ok((
+                    InfoHash::from_str(&info_hash).unwrap(),
+                    TorrentPolicy::from_str(&policy).map_err(|e| (e, DRIVER))?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Refer to [`databases::Database::save_torrent_policy`](crate::core::databases::Database::save_torrent_policy).
+    fn save_torrent_policy(&self, info_hash: &InfoHash, policy: TorrentPolicy) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO torrent_policies (info_hash, policy) VALUES (?1, ?2) ON CONFLICT(info_hash) DO UPDATE SET policy = ?2",
+            [info_hash.to_string(), policy.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_policy`](crate::core::databases::Database::remove_torrent_policy).
+    fn remove_torrent_policy(&self, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM torrent_policies WHERE info_hash = ?", [info_hash.to_string()])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::load_static_peers`](crate::core::databases::Database::load_static_peers).
+    fn load_static_peers(&self) -> Result<Vec<(InfoHash, SocketAddr)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash, peer_addr FROM torrent_static_peers")?;
+
+        let static_peer_iter = stmt.query_map([], |row| {
+            let info_hash: String = row.get(0)?;
+            let peer_addr: String = row.get(1)?;
+
+            Ok((
+                InfoHash::from_str(&info_hash).unwrap(),
+                SocketAddr::from_str(&peer_addr).unwrap(),
+            ))
+        })?;
+
+        Ok(static_peer_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_static_peer`](crate::core::databases::Database::add_static_peer).
+    fn add_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute(
+            "INSERT INTO torrent_static_peers (info_hash, peer_addr) VALUES (?1, ?2) ON CONFLICT(info_hash, peer_addr) DO NOTHING",
+            [info_hash.to_string(), peer_addr.to_string()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::remove_static_peer`](crate::core::databases::Database::remove_static_peer).
+    fn remove_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM torrent_static_peers WHERE info_hash = ?1 AND peer_addr = ?2",
+            [info_hash.to_string(), peer_addr.to_string()],
+        )?;
+
+        Ok(deleted)
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
-        let mut stmt = conn.prepare("SELECT key, valid_until FROM keys WHERE key = ?")?;
+        let mut stmt = conn.prepare("SELECT key, valid_until, bound_to FROM keys WHERE key = ?")?;
 
         let mut rows = stmt.query([key.to_string()])?;
 
@@ -228,16 +530,12 @@ impl Database for Sqlite {
         Ok(key.map(|f| {
             let valid_until: Option<i64> = f.get(1).unwrap();
             let key: String = f.get(0).unwrap();
+            let bound_to: Option<String> = f.get(2).unwrap();
 
-            match valid_until {
-                Some(valid_until) => auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: Some(DurationSinceUnixEpoch::from_secs(valid_until.unsigned_abs())),
-                },
-                None => auth::PeerKey {
-                    key: key.parse::<Key>().unwrap(),
-                    valid_until: None,
-                },
+            auth::PeerKey {
+                key: key.parse::<Key>().unwrap(),
+                valid_until: valid_until.map(|valid_until| DurationSinceUnixEpoch::from_secs(valid_until.unsigned_abs())),
+                bound_to: bound_to.map(|bound_to| bound_to.parse::<IpRange>().unwrap()),
             }
         }))
     }
@@ -246,16 +544,14 @@ impl Database for Sqlite {
     fn add_key_to_keys(&self, auth_key: &auth::PeerKey) -> Result<usize, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
-        let insert = match auth_key.valid_until {
-            Some(valid_until) => conn.execute(
-                "INSERT INTO keys (key, valid_until) VALUES (?1, ?2)",
-                [auth_key.key.to_string(), valid_until.as_secs().to_string()],
-            )?,
-            None => conn.execute(
-                "INSERT INTO keys (key, valid_until) VALUES (?1, ?2)",
-                params![auth_key.key.to_string(), Null],
-            )?,
-        };
+        let insert = conn.execute(
+            "INSERT INTO keys (key, valid_until, bound_to) VALUES (?1, ?2, ?3)",
+            params![
+                auth_key.key.to_string(),
+                auth_key.valid_until.map(|valid_until| valid_until.as_secs().to_string()),
+                auth_key.bound_to.map(|bound_to| bound_to.to_string()),
+            ],
+        )?;
 
         if insert == 0 {
             Err(Error::InsertFailed {
@@ -284,4 +580,147 @@ impl Database for Sqlite {
             })
         }
     }
+
+    /// Refer to [`databases::Database::remove_all_keys`](crate::core::databases::Database::remove_all_keys).
+    fn remove_all_keys(&self) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM keys", [])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::load_key_quotas`](crate::core::databases::Database::load_key_quotas).
+    fn load_key_quotas(&self) -> Result<Vec<(Key, Quota)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key, max_announces_per_day, max_peers_per_day FROM key_quotas")?;
+
+        let quota_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+
+            Ok((
+                Key::from_str(&key).unwrap(),
+                Quota {
+                    max_announces_per_day: row.get(1)?,
+                    max_peers_per_day: row.get(2)?,
+                },
+            ))
+        })?;
+
+        Ok(quota_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::save_key_quota`](crate::core::databases::Database::save_key_quota).
+    fn save_key_quota(&self, key: &Key, quota: &Quota) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO key_quotas (key, max_announces_per_day, max_peers_per_day) VALUES (?1, ?2, ?3) \
+             ON CONFLICT(key) DO UPDATE SET max_announces_per_day = ?2, max_peers_per_day = ?3",
+            params![key.to_string(), quota.max_announces_per_day, quota.max_peers_per_day],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_key_quota`](crate::core::databases::Database::remove_key_quota).
+    fn remove_key_quota(&self, key: &Key) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM key_quotas WHERE key = ?", [key.to_string()])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::load_invitation_codes`](crate::core::databases::Database::load_invitation_codes).
+    fn load_invitation_codes(&self) -> Result<Vec<invitation::Code>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT code FROM invitation_codes")?;
+
+        let code_iter = stmt.query_map([], |row| {
+            let code: String = row.get(0)?;
+
+            Ok(invitation::Code::from_str(&code).unwrap())
+        })?;
+
+        Ok(code_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_invitation_code`](crate::core::databases::Database::add_invitation_code).
+    fn add_invitation_code(&self, code: &invitation::Code) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute("INSERT INTO invitation_codes (code) VALUES (?)", [code.to_string()])?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::remove_invitation_code`](crate::core::databases::Database::remove_invitation_code).
+    fn remove_invitation_code(&self, code: &invitation::Code) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM invitation_codes WHERE code = ?", [code.to_string()])?;
+
+        Ok(deleted)
+    }
+
+    /// Refer to [`databases::Database::add_audit_log_entry`](crate::core::databases::Database::add_audit_log_entry).
+    fn add_audit_log_entry(&self, entry: &audit::Entry) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO audit_log (time, token_label, source_ip, method, path, status) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.time.as_secs(),
+                entry.token_label,
+                entry.source_ip.map(|ip| ip.to_string()),
+                entry.method,
+                entry.path,
+                entry.status
+            ],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::load_audit_log`](crate::core::databases::Database::load_audit_log).
+    fn load_audit_log(&self, pagination: &Pagination) -> Result<Vec<audit::Entry>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT time, token_label, source_ip, method, path, status FROM audit_log ORDER BY id DESC LIMIT ?1 OFFSET ?2",
+        )?;
+
+        let entry_iter = stmt.query_map([pagination.limit, pagination.offset], |row| {
+            let time: u64 = row.get(0)?;
+            let source_ip: Option<String> = row.get(2)?;
+
+            Ok(audit::Entry {
+                time: DurationSinceUnixEpoch::from_secs(time),
+                token_label: row.get(1)?,
+                source_ip: source_ip.and_then(|ip| ip.parse().ok()),
+                method: row.get(3)?,
+                path: row.get(4)?,
+                status: row.get(5)?,
+            })
+        })?;
+
+        Ok(entry_iter.filter_map(std::result::Result::ok).collect())
+    }
 }
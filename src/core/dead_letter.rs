@@ -0,0 +1,651 @@
+//! Dead-letter queue for the failed database writes.
+//!
+//! Some writes are not allowed to fail the request that triggers them. For
+//! example, the torrent `completed` counter is persisted while handling an
+//! `announce` request, and its result is ignored. When the dead-letter queue
+//! is enabled, the failed writes are kept instead of being discarded:
+//!
+//! ```toml
+//! [core.dead_letter_queue]
+//! path = "./storage/tracker/lib/database/dead_letters.ndjson"
+//! capacity = 10000
+//! min_backoff_ms = 1000
+//! max_backoff_ms = 60000
+//! ```
+//!
+//! The queued writes are:
+//!
+//! - The torrent `completed` counters. Only the last counter of each torrent
+//!   is kept, because it replaces the previous ones.
+//! - The new authentication keys. The key is still added to the tracker, so
+//!   peers can use it while the database is unavailable.
+//!
+//! The queue is bounded. When it's full, the oldest write is discarded. The
+//! queued writes are appended to a local file, so the pending writes survive
+//! restarts. The file is only rewritten when the queue is replayed or drained,
+//! or when it has grown to twice the capacity of the queue.
+//!
+//! A background job retries the pending writes with an exponential backoff,
+//! from `min_backoff_ms` up to `max_backoff_ms` milliseconds. The queue can
+//! also be inspected, replayed and drained with the tracker API.
+//!
+//! Refer to the [`DeadLetterQueue`] configuration for more information.
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::DeadLetterQueue;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use super::auth::PeerKey;
+use super::databases::{self, Database};
+use crate::CurrentClock;
+
+/// A database write that can be queued.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "write", rename_all = "snake_case")]
+pub enum Write {
+    /// The number of completed downloads of a torrent.
+    TorrentCompleted { info_hash: InfoHash, completed: u32 },
+    /// A new authentication key.
+    AuthKey { peer_key: PeerKey },
+}
+
+impl Write {
+    fn apply(&self, database: &dyn Database) -> Result<(), databases::error::Error> {
+        match self {
+            Write::TorrentCompleted { info_hash, completed } => database.save_persistent_torrent(info_hash, *completed),
+            Write::AuthKey { peer_key } => {
+                // A previous attempt could have been stored despite the error.
+                if database.get_key_from_keys(&peer_key.key)?.is_none() {
+                    database.add_key_to_keys(peer_key)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A failed write waiting to be retried.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Letter {
+    pub write: Write,
+    /// Time of the last failure.
+    pub failed_at: DurationSinceUnixEpoch,
+    /// Number of failed attempts, including the original write.
+    pub attempts: u32,
+    /// The error of the last attempt.
+    pub last_error: String,
+}
+
+/// Counters of the dead-letter queue since the tracker started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Writes waiting to be retried.
+    pub pending: usize,
+    /// Failed writes added to the queue.
+    pub queued: u64,
+    /// Writes that succeeded when they were retried.
+    pub replayed: u64,
+    /// Writes discarded because the queue was full or it was drained.
+    pub dropped: u64,
+}
+
+/// The result of retrying the pending writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Replay {
+    pub replayed: usize,
+    pub failed: usize,
+}
+
+/// The pending letters, oldest first.
+#[derive(Debug, Default)]
+struct Letters {
+    /// The letters by the order they were queued.
+    by_sequence: BTreeMap<u64, Letter>,
+    /// The sequence of the pending `completed` counter of each torrent.
+    completed_by_info_hash: HashMap<InfoHash, u64>,
+    next_sequence: u64,
+    /// Number of letters in the queue file, including the ones that have been
+    /// replaced or discarded since the file was rewritten.
+    logged: usize,
+}
+
+impl Letters {
+    fn len(&self) -> usize {
+        self.by_sequence.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.by_sequence.is_empty()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Letter> {
+        self.by_sequence.values()
+    }
+
+    fn push_back(&mut self, letter: Letter) {
+        let sequence = self.next_sequence;
+
+        self.next_sequence += 1;
+
+        self.insert(sequence, letter);
+    }
+
+    fn insert(&mut self, sequence: u64, letter: Letter) {
+        if let Write::TorrentCompleted { info_hash, .. } = &letter.write {
+            self.completed_by_info_hash.insert(*info_hash, sequence);
+        }
+
+        self.by_sequence.insert(sequence, letter);
+    }
+
+    fn pop_front(&mut self) -> Option<Letter> {
+        let (sequence, letter) = self.by_sequence.pop_first()?;
+
+        self.unindex(sequence, &letter);
+
+        Some(letter)
+    }
+
+    fn unindex(&mut self, sequence: u64, letter: &Letter) {
+        if let Write::TorrentCompleted { info_hash, .. } = &letter.write {
+            if self.completed_by_info_hash.get(info_hash) == Some(&sequence) {
+                self.completed_by_info_hash.remove(info_hash);
+            }
+        }
+    }
+
+    /// The sequence of the pending write replaced by the given one, if any.
+    fn replaced_by(&self, write: &Write) -> Option<u64> {
+        match write {
+            Write::TorrentCompleted { info_hash, .. } => self.completed_by_info_hash.get(info_hash).copied(),
+            Write::AuthKey { .. } => None,
+        }
+    }
+
+    /// It removes the pending write replaced by the new one, if any. It
+    /// returns the number of attempts of the removed write.
+    fn remove_replaced(&mut self, write: &Write) -> u32 {
+        let Some(sequence) = self.replaced_by(write) else {
+            return 0;
+        };
+
+        let Some(letter) = self.by_sequence.remove(&sequence) else {
+            return 0;
+        };
+
+        self.unindex(sequence, &letter);
+
+        letter.attempts
+    }
+
+    /// It removes the letters that don't match the predicate. It returns the
+    /// number of removed letters.
+    fn retain(&mut self, predicate: impl Fn(&Letter) -> bool) -> usize {
+        let pending = self.len();
+
+        self.by_sequence.retain(|_, letter| predicate(letter));
+        self.completed_by_info_hash
+            .retain(|_, sequence| self.by_sequence.contains_key(sequence));
+
+        pending - self.len()
+    }
+
+    /// It removes all the letters, keeping their sequence.
+    fn take(&mut self) -> BTreeMap<u64, Letter> {
+        self.completed_by_info_hash.clear();
+
+        std::mem::take(&mut self.by_sequence)
+    }
+}
+
+/// The queue of failed writes.
+#[derive(Debug)]
+pub struct Queue {
+    path: Utf8PathBuf,
+    capacity: usize,
+    letters: Mutex<Letters>,
+    queued: AtomicU64,
+    replayed: AtomicU64,
+    dropped: AtomicU64,
+    /// Only one replay runs at the same time, whether it was started by the
+    /// job or by the API.
+    replaying: tokio::sync::Mutex<()>,
+}
+
+impl Queue {
+    /// It creates the queue with the writes left in the queue file, if any.
+    #[must_use]
+    pub fn new(config: &DeadLetterQueue) -> Self {
+        let mut letters = Letters::default();
+
+        let loaded = load(&config.path);
+
+        letters.logged = loaded.len();
+
+        for letter in loaded {
+            letters.remove_replaced(&letter.write);
+
+            if letters.len() >= config.capacity {
+                letters.pop_front();
+            }
+
+            letters.push_back(letter);
+        }
+
+        if !letters.is_empty() {
+            tracing::warn!(path = %config.path, pending = letters.len(), "Loaded pending database writes");
+        }
+
+        Self {
+            path: config.path.clone(),
+            capacity: config.capacity,
+            letters: Mutex::new(letters),
+            queued: AtomicU64::default(),
+            replayed: AtomicU64::default(),
+            dropped: AtomicU64::default(),
+            replaying: tokio::sync::Mutex::default(),
+        }
+    }
+
+    /// It queues a failed write. A pending write replaced by the new one is
+    /// removed, and the oldest one is discarded when the queue is full.
+    ///
+    /// The write is appended to the queue file.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    pub fn push(&self, write: Write, error: &databases::error::Error) {
+        tracing::warn!(?write, %error, "Queued failed database write");
+
+        let mut letters = self.letters.lock().expect("it should get the dead-letter queue lock");
+
+        let attempts = letters.remove_replaced(&write) + 1;
+
+        if letters.len() >= self.capacity {
+            letters.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let letter = Letter {
+            write,
+            failed_at: CurrentClock::now(),
+            attempts,
+            last_error: error.to_string(),
+        };
+
+        self.queued.fetch_add(1, Ordering::Relaxed);
+
+        if letters.logged >= self.capacity.saturating_mul(2) {
+            letters.push_back(letter);
+            self.save(&mut letters);
+        } else {
+            self.append(&mut letters, &letter);
+            letters.push_back(letter);
+        }
+    }
+
+    /// It returns the pending writes, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    #[must_use]
+    pub fn letters(&self) -> Vec<Letter> {
+        self.letters
+            .lock()
+            .expect("it should get the dead-letter queue lock")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            pending: self.letters.lock().expect("it should get the dead-letter queue lock").len(),
+            queued: self.queued.load(Ordering::Relaxed),
+            replayed: self.replayed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// It retries all the pending writes. The ones that fail again are kept
+    /// in the queue.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the replay task can't be joined or if the queue lock is
+    /// poisoned.
+    pub async fn replay(&self, database: &Arc<Box<dyn Database>>) -> Replay {
+        let _replaying = self.replaying.lock().await;
+
+        let pending = self.letters.lock().expect("it should get the dead-letter queue lock").take();
+
+        if pending.is_empty() {
+            return Replay::default();
+        }
+
+        let database = database.clone();
+
+        let (failed, replayed) = tokio::task::spawn_blocking(move || {
+            let mut failed = Vec::new();
+            let mut replayed = 0;
+
+            for (sequence, mut letter) in pending {
+                match letter.write.apply(database.as_ref().as_ref()) {
+                    Ok(()) => replayed += 1,
+                    Err(error) => {
+                        letter.failed_at = CurrentClock::now();
+                        letter.attempts += 1;
+                        letter.last_error = error.to_string();
+                        failed.push((sequence, letter));
+                    }
+                }
+            }
+
+            (failed, replayed)
+        })
+        .await
+        .expect("it should join the dead-letter replay task");
+
+        self.replayed.fetch_add(replayed as u64, Ordering::Relaxed);
+
+        let mut letters = self.letters.lock().expect("it should get the dead-letter queue lock");
+
+        let mut result = Replay { replayed, failed: 0 };
+
+        // Writes queued during the replay are newer than the failed ones, so
+        // the failed ones keep their place before them.
+        for (sequence, letter) in failed {
+            if letters.replaced_by(&letter.write).is_none() {
+                letters.insert(sequence, letter);
+                result.failed += 1;
+            }
+        }
+
+        while letters.len() > self.capacity {
+            letters.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.save(&mut letters);
+
+        result
+    }
+
+    /// It discards all the pending writes. It returns the number of discarded
+    /// writes.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    pub fn drain(&self) -> usize {
+        let mut letters = self.letters.lock().expect("it should get the dead-letter queue lock");
+
+        let drained = letters.take().len();
+
+        self.dropped.fetch_add(drained as u64, Ordering::Relaxed);
+
+        self.save(&mut letters);
+
+        drained
+    }
+
+    /// It discards the pending authentication key writes, so that they are
+    /// not stored after the keys have been revoked. It returns the number of
+    /// discarded writes.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    pub fn discard_auth_keys(&self) -> usize {
+        let mut letters = self.letters.lock().expect("it should get the dead-letter queue lock");
+
+        let discarded = letters.retain(|letter| !matches!(letter.write, Write::AuthKey { .. }));
+
+        self.dropped.fetch_add(discarded as u64, Ordering::Relaxed);
+
+        self.save(&mut letters);
+
+        discarded
+    }
+
+    fn append(&self, letters: &mut Letters, letter: &Letter) {
+        match append(&self.path, letter) {
+            Ok(()) => letters.logged += 1,
+            Err(err) => tracing::error!(path = %self.path, %err, "Failed to append to the dead-letter queue file"),
+        }
+    }
+
+    fn save(&self, letters: &mut Letters) {
+        match save(&self.path, letters) {
+            Ok(()) => letters.logged = letters.len(),
+            Err(err) => tracing::error!(path = %self.path, %err, "Failed to write the dead-letter queue file"),
+        }
+    }
+}
+
+/// It returns the letters in the queue file, in the order they were queued.
+/// A letter is replaced by the newer letters of the same write.
+fn load(path: &Utf8Path) -> Vec<Letter> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(letter) => Some(letter),
+            Err(err) => {
+                tracing::error!(%path, %err, "Discarded invalid line in the dead-letter queue file");
+                None
+            }
+        })
+        .collect()
+}
+
+fn line(letter: &Letter) -> String {
+    let mut line = serde_json::to_string(letter).expect("it should serialize the dead letter to json");
+    line.push('\n');
+    line
+}
+
+fn create_parent_dir(path: &Utf8Path) -> std::io::Result<()> {
+    match path.parent().filter(|parent| !parent.as_str().is_empty()) {
+        Some(parent) => std::fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/// It appends a letter to the queue file.
+fn append(path: &Utf8Path, letter: &Letter) -> std::io::Result<()> {
+    create_parent_dir(path)?;
+
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line(letter).as_bytes())
+}
+
+/// It replaces the queue file, writing a temporary file first so the queue
+/// file is never left half written.
+fn save(path: &Utf8Path, letters: &Letters) -> std::io::Result<()> {
+    create_parent_dir(path)?;
+
+    let content: String = letters.iter().map(line).collect();
+
+    let temporary = Utf8PathBuf::from(format!("{path}.tmp"));
+
+    std::fs::write(&temporary, content)?;
+    std::fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::panic::Location;
+    use std::sync::Arc;
+
+    use camino::Utf8PathBuf;
+    use torrust_tracker_configuration::DeadLetterQueue;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_test_helpers::random;
+
+    use super::{Queue, Write};
+    use crate::core::auth;
+    use crate::core::databases::driver::{self, Driver};
+    use crate::core::databases::error::Error;
+
+    fn config(capacity: usize) -> DeadLetterQueue {
+        let path = env::temp_dir()
+            .join(format!("dead_letters_{}", random::string(16)))
+            .join("dead_letters.ndjson");
+
+        DeadLetterQueue {
+            path: Utf8PathBuf::from_path_buf(path).unwrap(),
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    fn error() -> Error {
+        Error::InsertFailed {
+            location: Location::caller(),
+            driver: Driver::Sqlite3,
+        }
+    }
+
+    fn torrent_completed(byte: u8, completed: u32) -> Write {
+        Write::TorrentCompleted {
+            info_hash: InfoHash::from([byte; 20]),
+            completed,
+        }
+    }
+
+    #[test]
+    fn it_should_only_keep_the_last_completed_counter_of_a_torrent() {
+        let queue = Queue::new(&config(10));
+
+        queue.push(torrent_completed(1, 1), &error());
+        queue.push(torrent_completed(1, 2), &error());
+
+        let letters = queue.letters();
+
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].write, torrent_completed(1, 2));
+        assert_eq!(letters[0].attempts, 2);
+    }
+
+    #[test]
+    fn it_should_discard_the_oldest_write_when_the_queue_is_full() {
+        let queue = Queue::new(&config(1));
+
+        queue.push(torrent_completed(1, 1), &error());
+        queue.push(torrent_completed(2, 1), &error());
+
+        assert_eq!(queue.letters()[0].write, torrent_completed(2, 1));
+        assert_eq!(queue.metrics().dropped, 1);
+        assert_eq!(queue.metrics().queued, 2);
+    }
+
+    #[test]
+    fn it_should_load_the_pending_writes_from_the_queue_file() {
+        let config = config(10);
+
+        Queue::new(&config).push(torrent_completed(1, 1), &error());
+
+        assert_eq!(Queue::new(&config).letters()[0].write, torrent_completed(1, 1));
+    }
+
+    #[test]
+    fn it_should_rewrite_the_queue_file_when_it_has_grown_to_twice_the_capacity() {
+        let config = config(2);
+        let queue = Queue::new(&config);
+
+        for completed in 1..=5 {
+            queue.push(torrent_completed(1, completed), &error());
+        }
+
+        let lines = std::fs::read_to_string(&config.path).unwrap().lines().count();
+
+        assert!(lines <= 4);
+
+        let letters = Queue::new(&config).letters();
+
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].write, torrent_completed(1, 5));
+        assert_eq!(letters[0].attempts, 5);
+    }
+
+    #[test]
+    fn it_should_drain_the_pending_writes() {
+        let config = config(10);
+        let queue = Queue::new(&config);
+
+        queue.push(torrent_completed(1, 1), &error());
+
+        assert_eq!(queue.drain(), 1);
+        assert!(queue.letters().is_empty());
+        assert!(Queue::new(&config).letters().is_empty());
+    }
+
+    #[test]
+    fn it_should_discard_the_pending_authentication_key_writes() {
+        let config = config(10);
+        let queue = Queue::new(&config);
+
+        queue.push(torrent_completed(1, 1), &error());
+        queue.push(
+            Write::AuthKey {
+                peer_key: auth::generate_permanent_key(),
+            },
+            &error(),
+        );
+
+        assert_eq!(queue.discard_auth_keys(), 1);
+        assert_eq!(queue.letters()[0].write, torrent_completed(1, 1));
+        assert_eq!(Queue::new(&config).letters().len(), 1);
+        assert_eq!(queue.metrics().dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_replay_the_pending_writes_and_keep_the_ones_that_fail_again() {
+        let database_path = env::temp_dir().join(format!("dead_letters_{}.db", random::string(16)));
+        let database = Arc::new(driver::build(&Driver::Sqlite3, database_path.to_str().unwrap()).unwrap());
+
+        let queue = Queue::new(&config(10));
+
+        queue.push(torrent_completed(1, 3), &error());
+
+        database.drop_database_tables().unwrap();
+
+        let replay = queue.replay(&database).await;
+
+        assert_eq!((replay.replayed, replay.failed), (0, 1));
+        assert_eq!(queue.letters()[0].attempts, 2);
+
+        database.create_database_tables().unwrap();
+
+        let replay = queue.replay(&database).await;
+
+        assert_eq!((replay.replayed, replay.failed), (1, 0));
+        assert_eq!(queue.metrics().pending, 0);
+        assert_eq!(
+            database.load_persistent_torrents().unwrap().get(&InfoHash::from([1; 20])),
+            Some(&3)
+        );
+    }
+}
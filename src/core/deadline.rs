@@ -0,0 +1,177 @@
+//! Deadline for the `announce` and `scrape` requests.
+//!
+//! When the torrents repository or the database are slow, like when the
+//! repository lock is contended or the database is overloaded, the requests
+//! pile up waiting for them. When the `core.request_deadline` section is in
+//! the configuration, only `max_concurrent_requests` requests work in the
+//! repository or the database at the same time, and the other `announce` and
+//! `scrape` requests wait to be admitted for up to `budget_ms` milliseconds:
+//!
+//! ```toml
+//! [core.request_deadline]
+//! budget_ms = 100
+//! max_concurrent_requests = 64
+//! ```
+//!
+//! The requests not admitted within the budget get a best-effort response
+//! instead of waiting:
+//!
+//! - `announce`: no peers, with the swarm metadata of the
+//!   [read snapshot](crate::core::torrent::snapshot) when it's enabled, or
+//!   zeroed otherwise.
+//! - `scrape`: the swarm metadata of the read snapshot when it's enabled, or
+//!   zeroed otherwise.
+//!
+//! A warning is logged and the deadline-exceeded counters of the
+//! [statistics](crate::core::statistics) are increased, so operators can see
+//! when the tracker is saturated.
+//!
+//! > **NOTICE**: the requests that are not admitted don't do any work, for
+//! > example, the peer of an `announce` request is not added to the swarm.
+//! > Once admitted, an `announce` request is handled to completion, even if
+//! > it takes longer than the budget.
+use std::future::Future;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+use torrust_tracker_configuration::RequestDeadline;
+
+/// The request exceeded its deadline.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("the request deadline was exceeded")]
+pub struct Exceeded;
+
+/// Admission control for the repository and database work of the requests.
+///
+/// The requests that are not admitted get a best-effort response, not an
+/// error. So the errors of the `announce` and `scrape` handlers only come from
+/// the checks before the repository, like the authentication or the
+/// whitelist, and their time is accounted to the
+/// [`Authorization`](crate::core::slow_requests::Phase::Authorization) phase.
+#[derive(Debug)]
+pub struct Admission {
+    semaphore: Semaphore,
+}
+
+impl Admission {
+    #[must_use]
+    pub fn new(config: &RequestDeadline) -> Self {
+        Self {
+            semaphore: Semaphore::new(config.max_concurrent_requests.min(Semaphore::MAX_PERMITS)),
+        }
+    }
+}
+
+/// The point in time a request has to be handled by. Requests without a
+/// deadline can take as long as needed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// It starts the deadline of a request, when it's configured.
+    #[must_use]
+    pub fn start(config: Option<&RequestDeadline>) -> Self {
+        Self {
+            at: config.map(|config| Instant::now() + Duration::from_millis(config.budget_ms)),
+        }
+    }
+
+    /// It waits for the future until the deadline.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Exceeded`] error if the future is not ready before
+    /// the deadline.
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, Exceeded> {
+        match self.at {
+            Some(at) => tokio::time::timeout_at(at, future).await.map_err(|_| Exceeded),
+            None => Ok(future.await),
+        }
+    }
+
+    /// It waits until the request is admitted to work in the repository or
+    /// the database. The returned permit has to be held while working.
+    /// Without admission control, the request is admitted right away.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`Exceeded`] error if the request is not admitted
+    /// before the deadline, or the deadline was already exceeded.
+    pub async fn admit<'a>(&self, admission: Option<&'a Admission>) -> Result<Option<SemaphorePermit<'a>>, Exceeded> {
+        if self.at.is_some_and(|at| Instant::now() >= at) {
+            return Err(Exceeded);
+        }
+
+        match admission {
+            Some(admission) => self
+                .run(admission.semaphore.acquire())
+                .await?
+                .map(Some)
+                .map_err(|_closed| Exceeded),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_configuration::RequestDeadline;
+
+    use super::{Admission, Deadline, Exceeded};
+
+    fn config_of(budget_ms: u64) -> RequestDeadline {
+        RequestDeadline {
+            budget_ms,
+            max_concurrent_requests: 1,
+        }
+    }
+
+    fn deadline_of(budget_ms: u64) -> Deadline {
+        Deadline::start(Some(&config_of(budget_ms)))
+    }
+
+    #[tokio::test]
+    async fn it_should_return_the_output_of_the_futures_ready_before_the_deadline() {
+        assert_eq!(deadline_of(1000).run(async { 1 }).await, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_the_future_is_not_ready_before_the_deadline() {
+        let slow = tokio::time::sleep(Duration::from_secs(10));
+
+        assert_eq!(deadline_of(10).run(slow).await, Err(Exceeded));
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_when_the_request_is_not_admitted_before_the_deadline() {
+        let admission = Admission::new(&config_of(10));
+
+        let _permit = deadline_of(10).admit(Some(&admission)).await.unwrap();
+
+        assert!(matches!(deadline_of(10).admit(Some(&admission)).await, Err(Exceeded)));
+    }
+
+    #[tokio::test]
+    async fn it_should_not_admit_the_requests_after_the_deadline() {
+        let admission = Admission::new(&config_of(10));
+        let deadline = deadline_of(10);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(matches!(deadline.admit(Some(&admission)).await, Err(Exceeded)));
+    }
+
+    #[tokio::test]
+    async fn it_should_never_fail_without_a_deadline() {
+        let deadline = Deadline::start(None);
+
+        assert_eq!(deadline.run(async { 1 }).await, Ok(1));
+        assert!(matches!(deadline.admit(None).await, Ok(None)));
+    }
+}
@@ -1,17 +1,27 @@
 //! Error returned by the core `Tracker`.
 //!
-//! Error | Context | Description
-//! ---|---|---
-//! `PeerKeyNotValid` | Authentication | The supplied key is not valid. It may not be registered or expired.
-//! `PeerNotAuthenticated` | Authentication | The peer did not provide the authentication key.
-//! `TorrentNotWhitelisted` | Authorization | The action cannot be perform on a not-whitelisted torrent (it only applies for trackers running in `listed` or `private_listed` modes).
+//! Error | Code | Context | Description
+//! ---|---|---|---
+//! `PeerKeyNotValid` | `E01` | Authentication | The supplied key is not valid. It may not be registered or expired.
+//! `PeerNotAuthenticated` | `E02` | Authentication | The peer did not provide the authentication key.
+//! `KeyQuotaExceeded` | `E08` | Authentication | The key has reached its daily quota.
+//! `TorrentNotWhitelisted` | `E03` | Authorization | The action cannot be perform on a not-whitelisted torrent (it only applies for trackers running in `listed` or `private_listed` modes).
+//! `TorrentRemovedFromWhitelist` | `E04` | Authorization | The action cannot be perform on a torrent that was removed from the whitelist (it only applies for trackers running in `listed` or `private_listed` modes).
+//! `TorrentSeedOnly` | `E05` | Authorization | Leechers cannot announce a torrent with the `seed-only` policy.
+//! `TorrentFrozen` | `E06` | Authorization | New peers cannot announce a torrent with the `frozen` policy.
+//! `WhitelistLookupUnavailable` | `E09` | Authorization | The torrent could not be looked up in the database whitelist, for example because there are too many pending lookups (it only applies when the database authorization is enabled).
+//! `TrackerInMaintenance` | `E07` | Maintenance | The tracker does not accept `announce` requests while it's in maintenance mode.
+//!
+//! The codes are part of the unified error taxonomy shared with the
+//! [`udp`](crate::servers::udp::error) server errors. They are stable: new
+//! errors get a new code and existing codes are never reused.
 //!
 use std::panic::Location;
 
 use torrust_tracker_located_error::LocatedError;
 use torrust_tracker_primitives::info_hash::InfoHash;
 
-use super::auth::ParseKeyError;
+use super::auth::{ParseIpRangeError, ParseKeyError};
 use super::databases;
 
 /// Authentication or authorization error returned by the core `Tracker`
@@ -27,12 +37,69 @@ pub enum Error {
     #[error("The peer is not authenticated, {location}")]
     PeerNotAuthenticated { location: &'static Location<'static> },
 
+    #[error("The key: {key}, has reached its daily quota, retry in {retry_in} minutes, {location}")]
+    KeyQuotaExceeded {
+        key: super::auth::Key,
+        retry_in: u32,
+        location: &'static Location<'static>,
+    },
+
     // Authorization errors
     #[error("The torrent: {info_hash}, is not whitelisted, {location}")]
     TorrentNotWhitelisted {
         info_hash: InfoHash,
         location: &'static Location<'static>,
     },
+
+    #[error("The torrent: {info_hash}, was removed from the whitelist, {location}")]
+    TorrentRemovedFromWhitelist {
+        info_hash: InfoHash,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The torrent: {info_hash}, only accepts seeders, {location}")]
+    TorrentSeedOnly {
+        info_hash: InfoHash,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The torrent: {info_hash}, does not accept new peers, {location}")]
+    TorrentFrozen {
+        info_hash: InfoHash,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The torrent: {info_hash}, could not be looked up in the whitelist, retry later: {source}")]
+    WhitelistLookupUnavailable {
+        info_hash: InfoHash,
+        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
+    },
+
+    // Maintenance errors
+    #[error("The tracker is in maintenance mode, retry in {retry_in} minutes, {location}")]
+    TrackerInMaintenance {
+        retry_in: u32,
+        location: &'static Location<'static>,
+    },
+}
+
+impl Error {
+    /// The short machine-readable code of the error in the unified error
+    /// taxonomy. See the [module](self) documentation.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::PeerKeyNotValid { .. } => "E01",
+            Error::PeerNotAuthenticated { .. } => "E02",
+            Error::TorrentNotWhitelisted { .. } => "E03",
+            Error::TorrentRemovedFromWhitelist { .. } => "E04",
+            Error::TorrentSeedOnly { .. } => "E05",
+            Error::TorrentFrozen { .. } => "E06",
+            Error::TrackerInMaintenance { .. } => "E07",
+            Error::KeyQuotaExceeded { .. } => "E08",
+            Error::WhitelistLookupUnavailable { .. } => "E09",
+        }
+    }
 }
 
 /// Errors related to peers keys.
@@ -48,8 +115,26 @@ pub enum PeerKeyError {
         source: LocatedError<'static, ParseKeyError>,
     },
 
+    #[error("Invalid key binding: {bound_to}")]
+    InvalidBinding {
+        bound_to: String,
+        source: LocatedError<'static, ParseIpRangeError>,
+    },
+
     #[error("Can't persist key: {source}")]
     DatabaseError {
         source: LocatedError<'static, databases::error::Error>,
     },
 }
+
+/// Errors related to the self-registration of new users with invitation codes.
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum RegistrationError {
+    #[error("The invitation code is not valid or it was already used, {location}")]
+    InvitationCodeNotValid { location: &'static Location<'static> },
+
+    #[error("Can't register: {source}")]
+    DatabaseError {
+        source: LocatedError<'static, databases::error::Error>,
+    },
+}
@@ -0,0 +1,175 @@
+//! Internal domain event bus.
+//!
+//! The core [`Tracker`](crate::core::Tracker) publishes domain events to an
+//! in-process bus every time something relevant happens in the tracker:
+//!
+//! - A peer announced itself.
+//! - A new torrent entry was created in the torrents repository.
+//! - A torrent entry was removed from the torrents repository.
+//! - A torrent was removed from the whitelist.
+//! - An authentication key was created.
+//! - An authentication key was revoked.
+//!
+//! Subsystems that need to react to those changes subscribe to the bus instead
+//! of being called directly from the `announce` hot path:
+//!
+//! - The [statistics](crate::core::statistics) count the torrents created and
+//!   removed.
+//! - The [event webhook](crate::bootstrap::jobs::event_webhook) notifies the
+//!   events, except the announces, to an external service.
+//! - The [events API](crate::servers::apis::v1::context::events) streams the
+//!   events as server-sent events, so other services, like replicas, can
+//!   follow the tracker.
+//! - The [swarm changes](crate::bootstrap::jobs::swarm_changes), the
+//!   [removal of unwhitelisted torrents](crate::bootstrap::jobs::unwhitelisted_torrents)
+//!   and the [announce export](crate::bootstrap::jobs::announce_export) jobs.
+//!
+//! The bus is a [`tokio::sync::broadcast`] channel. Every subscriber gets its
+//! own copy of each event published after it subscribed. Publishing never
+//! blocks: if there are no subscribers the event is discarded, and if a
+//! subscriber falls behind more than [`CHANNEL_CAPACITY`] events, it will
+//! receive a [`RecvError::Lagged`](tokio::sync::broadcast::error::RecvError::Lagged)
+//! error with the number of skipped events.
+//!
+//! ```text
+//! let mut receiver = tracker.subscribe();
+//!
+//! while let Ok(event) = receiver.recv().await {
+//!     match event {
+//!         Event::PeerAnnounced { info_hash, peer } => { /* ... */ }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+use serde::Serialize;
+use tokio::sync::broadcast;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+use super::auth::Key;
+
+/// Maximum number of events kept in the channel for slow subscribers.
+pub const CHANNEL_CAPACITY: usize = 65_536;
+
+/// A domain event published by the core `Tracker`.
+///
+/// It's serialized as a json object with the `type` of the event in snake
+/// case and its fields, for example `{"type":"torrent_created","info_hash":"..."}`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A peer announced itself for a torrent. The peer contains the data
+    /// stored by the tracker, that is, after assigning the peer IP.
+    PeerAnnounced { info_hash: InfoHash, peer: peer::Peer },
+    /// A torrent entry was added to the torrents repository.
+    TorrentCreated { info_hash: InfoHash },
+    /// A torrent entry was removed from the torrents repository.
+    TorrentRemoved { info_hash: InfoHash },
+    /// A torrent was removed from the whitelist of a listed tracker.
+    TorrentUnwhitelisted { info_hash: InfoHash },
+    /// A new authentication key was added to the tracker.
+    KeyCreated { key: Key },
+    /// An authentication key was removed from the tracker.
+    KeyRevoked { key: Key },
+}
+
+/// The in-process event bus.
+#[derive(Debug)]
+pub struct Bus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self::new(CHANNEL_CAPACITY)
+    }
+}
+
+impl Bus {
+    /// # Panics
+    ///
+    /// Will panic if the `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _receiver) = broadcast::channel(capacity);
+
+        Self { sender }
+    }
+
+    /// It returns a new receiver that will get all the events published
+    /// from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+
+    /// It returns `true` if there is at least one active subscriber.
+    #[must_use]
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// It publishes an event to all the current subscribers.
+    ///
+    /// The event is discarded if there are no subscribers.
+    pub fn publish(&self, event: Event) {
+        if self.has_subscribers() {
+            drop(self.sender.send(event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use crate::core::events::{Bus, Event};
+
+    fn sample_info_hash() -> InfoHash {
+        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_deliver_published_events_to_all_subscribers() {
+        let bus = Bus::default();
+
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+
+        bus.publish(Event::TorrentCreated {
+            info_hash: sample_info_hash(),
+        });
+
+        let expected = Event::TorrentCreated {
+            info_hash: sample_info_hash(),
+        };
+
+        assert_eq!(first.recv().await.unwrap(), expected);
+        assert_eq!(second.recv().await.unwrap(), expected);
+    }
+
+    #[test]
+    fn it_should_discard_events_when_there_are_no_subscribers() {
+        let bus = Bus::default();
+
+        bus.publish(Event::TorrentRemoved {
+            info_hash: sample_info_hash(),
+        });
+
+        assert!(!bus.has_subscribers());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_deliver_events_published_before_subscribing() {
+        let bus = Bus::default();
+        let _keep_channel_open = bus.subscribe();
+
+        bus.publish(Event::TorrentCreated {
+            info_hash: sample_info_hash(),
+        });
+
+        let mut late_subscriber = bus.subscribe();
+
+        assert!(late_subscriber.try_recv().is_err());
+    }
+}
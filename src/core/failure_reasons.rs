@@ -0,0 +1,96 @@
+//! Templates of the failure reasons sent to the clients.
+//!
+//! The HTTP and UDP trackers send a human-readable message to the clients
+//! when a request fails. Operators can replace the message of the errors with
+//! a code in the unified error taxonomy (see the [`core`](crate::core::error)
+//! and [`udp`](crate::servers::udp::error) errors) with their own templates,
+//! for example to point the users to the rules of the tracker, or to
+//! translate the messages.
+//!
+//! The templates are grouped by language in the configuration, and only the
+//! ones in the selected language are used. They can contain these
+//! placeholders:
+//!
+//! - `{code}`: the error code, for example `E03`.
+//! - `{message}`: the default message of the error.
+//!
+//! The UDP tracker keeps the code at the beginning of the message, so the
+//! clients can still parse it. The HTTP tracker only applies the templates
+//! to the errors returned by the domain tracker, since they are the only
+//! ones with a code.
+//!
+//! Refer to the [`FailureReasons`] configuration for more information.
+use std::collections::BTreeMap;
+
+use torrust_tracker_configuration::FailureReasons;
+
+/// The templates of the failure reasons in the selected language, indexed by
+/// the error code.
+#[derive(Debug, Default)]
+pub struct Templates {
+    templates: BTreeMap<String, String>,
+}
+
+impl Templates {
+    #[must_use]
+    pub fn new(config: &FailureReasons) -> Self {
+        Self {
+            templates: config.templates.get(&config.language).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// It returns the failure reason for the error with the `code`, or
+    /// `None` if there is no template for it.
+    #[must_use]
+    pub fn render(&self, code: &str, message: &str) -> Option<String> {
+        self.templates
+            .get(code)
+            .map(|template| template.replace("{code}", code).replace("{message}", message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use torrust_tracker_configuration::FailureReasons;
+
+    use super::Templates;
+
+    fn templates(language: &str) -> Templates {
+        Templates::new(&FailureReasons {
+            language: language.to_string(),
+            templates: BTreeMap::from([
+                (
+                    "en".to_string(),
+                    BTreeMap::from([("E03".to_string(), "torrent not registered ({code}): {message}".to_string())]),
+                ),
+                (
+                    "es".to_string(),
+                    BTreeMap::from([("E03".to_string(), "torrent no registrado".to_string())]),
+                ),
+            ]),
+        })
+    }
+
+    #[test]
+    fn it_should_render_the_template_of_the_error_code_in_the_selected_language() {
+        assert_eq!(
+            templates("es").render("E03", "The torrent is not whitelisted"),
+            Some("torrent no registrado".to_string())
+        );
+    }
+
+    #[test]
+    fn it_should_replace_the_code_and_message_placeholders() {
+        assert_eq!(
+            templates("en").render("E03", "The torrent is not whitelisted"),
+            Some("torrent not registered (E03): The torrent is not whitelisted".to_string())
+        );
+    }
+
+    #[test]
+    fn it_should_not_render_the_errors_without_a_template() {
+        assert_eq!(templates("en").render("E07", "The tracker is in maintenance mode"), None);
+    }
+}
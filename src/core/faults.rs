@@ -0,0 +1,95 @@
+//! Fault injection for resilience testing.
+//!
+//! It's only built with the `fault-injection` feature. When the
+//! `core.fault_injection` section is in the configuration, the tracker
+//! randomly:
+//!
+//! - Delays the UDP and HTTP tracker requests by `latency_ms` milliseconds,
+//!   to validate the client and proxy timeouts.
+//! - Fails the database writes, without writing anything, to validate the
+//!   retries and the [dead-letter queue](crate::core::dead_letter). See the
+//!   [`FaultyDatabase`](crate::core::databases::faulty::FaultyDatabase).
+//! - Delays the writes to the torrents repository by `lock_delay_ms`
+//!   milliseconds, like when the lock is contended.
+//!
+//! Each fault happens with the configured probability:
+//!
+//! ```toml
+//! [core.fault_injection]
+//! latency_percent = 5
+//! latency_ms = 2000
+//! db_write_failure_percent = 1
+//! lock_delay_percent = 10
+//! lock_delay_ms = 100
+//! ```
+//!
+//! > **WARNING**: the repository delays block the thread handling the
+//! > request. Never enable the fault injection in production.
+use std::time::Duration;
+
+use rand::Rng;
+use torrust_tracker_configuration::FaultInjection;
+
+/// It decides randomly when a fault is injected.
+#[derive(Debug)]
+pub struct Injector {
+    config: FaultInjection,
+}
+
+impl Injector {
+    #[must_use]
+    pub fn new(config: &FaultInjection) -> Self {
+        Self { config: *config }
+    }
+
+    /// It delays the request `latency_ms` milliseconds, with a
+    /// `latency_percent` probability.
+    pub async fn delay_request(&self) {
+        if happens(self.config.latency_percent) {
+            tokio::time::sleep(Duration::from_millis(self.config.latency_ms)).await;
+        }
+    }
+
+    /// It blocks the current thread `lock_delay_ms` milliseconds, with a
+    /// `lock_delay_percent` probability.
+    pub fn delay_lock(&self) {
+        if happens(self.config.lock_delay_percent) {
+            std::thread::sleep(Duration::from_millis(self.config.lock_delay_ms));
+        }
+    }
+
+    /// It returns `true`, with a `db_write_failure_percent` probability, when
+    /// the database write has to fail.
+    #[must_use]
+    pub fn fail_write(&self) -> bool {
+        happens(self.config.db_write_failure_percent)
+    }
+}
+
+fn happens(percent: u8) -> bool {
+    percent > 0 && rand::thread_rng().gen_range(0..100) < percent
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::FaultInjection;
+
+    use super::Injector;
+
+    #[test]
+    fn it_should_never_inject_a_fault_with_a_zero_probability() {
+        let injector = Injector::new(&FaultInjection::default());
+
+        assert!((0..1000).all(|_| !injector.fail_write()));
+    }
+
+    #[test]
+    fn it_should_always_inject_a_fault_with_a_full_probability() {
+        let injector = Injector::new(&FaultInjection {
+            db_write_failure_percent: 100,
+            ..Default::default()
+        });
+
+        assert!((0..1000).all(|_| injector.fail_write()));
+    }
+}
@@ -0,0 +1,190 @@
+//! Usage of the file descriptors of the tracker process.
+//!
+//! Every HTTP connection takes a file descriptor. When the process reaches its
+//! limit (`ulimit -n`), accepting new connections fails with `EMFILE` ("Too
+//! many open files"), and so does opening any other file, like the database.
+//! The clients that already have a connection keep waiting while the server
+//! retries to accept the new ones.
+//!
+//! When the admission control is enabled, the [`Monitor`] samples the number
+//! of open file descriptors every `refresh_interval_ms` milliseconds, and the
+//! HTTP trackers reject new connections with a `503 Service Unavailable`
+//! response while the usage is above `max_usage_percent` of the limit. The
+//! health check API reports a warning in the meantime.
+//!
+//! The UDP trackers are not affected, since they use a single socket for all
+//! the requests.
+//!
+//! The open file descriptors and the limit are read from the `/proc`
+//! filesystem, so the admission control only works on Linux. On other
+//! platforms the limit is unknown and connections are never rejected.
+//!
+//! Refer to the [`FileDescriptors`] configuration for more information.
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use torrust_tracker_configuration::FileDescriptors;
+
+/// The number of open file descriptors and the process limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub open: u64,
+    /// `None` if the limit is unknown or unlimited.
+    pub limit: Option<u64>,
+}
+
+/// It keeps the last sample of the open file descriptors.
+#[derive(Debug)]
+pub struct Monitor {
+    max_usage_percent: u8,
+    min_limit: u64,
+    limit: Option<u64>,
+    open: AtomicU64,
+    rejected_connections: AtomicU64,
+}
+
+impl Monitor {
+    #[must_use]
+    pub fn new(config: &FileDescriptors) -> Self {
+        Self {
+            max_usage_percent: config.max_usage_percent,
+            min_limit: config.min_limit,
+            limit: read_limit(Path::new("/proc/self/limits")),
+            open: AtomicU64::new(count_open(Path::new("/proc/self/fd")).unwrap_or_default()),
+            rejected_connections: AtomicU64::default(),
+        }
+    }
+
+    /// It samples the number of open file descriptors again.
+    pub fn refresh(&self) {
+        if let Some(open) = count_open(Path::new("/proc/self/fd")) {
+            self.open.store(open, Ordering::Relaxed);
+        }
+    }
+
+    /// It returns the last sample of the open file descriptors.
+    #[must_use]
+    pub fn usage(&self) -> Usage {
+        Usage {
+            open: self.open.load(Ordering::Relaxed),
+            limit: self.limit,
+        }
+    }
+
+    /// It returns `true` when the usage is above `max_usage_percent` of the
+    /// limit, so new connections should be rejected.
+    #[must_use]
+    pub fn is_near_the_limit(&self) -> bool {
+        let Usage { open, limit } = self.usage();
+
+        limit.is_some_and(|limit| u128::from(open) * 100 >= u128::from(limit) * u128::from(self.max_usage_percent))
+    }
+
+    /// It counts a connection rejected because of the usage.
+    pub fn increase_rejected_connections(&self) {
+        self.rejected_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Total number of connections rejected because of the usage.
+    #[must_use]
+    pub fn rejected_connections(&self) -> u64 {
+        self.rejected_connections.load(Ordering::Relaxed)
+    }
+
+    /// It logs a warning when the process limit is lower than the configured
+    /// `min_limit`.
+    pub fn check_limit(&self) {
+        if let Some(limit) = self.limit.filter(|limit| *limit < self.min_limit) {
+            tracing::warn!(
+                limit,
+                min_limit = self.min_limit,
+                "The open file descriptors limit is too low, increase it with `ulimit -n`"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+impl Monitor {
+    /// A monitor with the given usage, instead of the usage of the process.
+    pub(crate) fn with_usage(max_usage_percent: u8, open: u64, limit: Option<u64>) -> Self {
+        Self {
+            max_usage_percent,
+            min_limit: 0,
+            limit,
+            open: AtomicU64::new(open),
+            rejected_connections: AtomicU64::default(),
+        }
+    }
+
+    /// It replaces the last sample of the open file descriptors.
+    pub(crate) fn set_open(&self, open: u64) {
+        self.open.store(open, Ordering::Relaxed);
+    }
+}
+
+/// It returns the soft limit of open files of the tracker process, or `None`
+/// if it's unknown or unlimited.
+#[must_use]
+pub fn process_limit() -> Option<u64> {
+    read_limit(Path::new("/proc/self/limits"))
+}
+
+/// It reads the soft limit of open files from a `/proc/<pid>/limits` file.
+fn read_limit(path: &Path) -> Option<u64> {
+    parse_limit(&std::fs::read_to_string(path).ok()?)
+}
+
+fn parse_limit(limits: &str) -> Option<u64> {
+    let line = limits.lines().find(|line| line.starts_with("Max open files"))?;
+
+    line.trim_start_matches("Max open files")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// It counts the entries in a `/proc/<pid>/fd` directory.
+fn count_open(path: &Path) -> Option<u64> {
+    let entries = std::fs::read_dir(path).ok()?;
+
+    u64::try_from(entries.count()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_limit, Monitor};
+
+    const LIMITS: &str = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            1024                 524288               files
+Max locked memory         8388608              8388608              bytes
+";
+
+    fn monitor(open: u64, limit: Option<u64>) -> Monitor {
+        Monitor::with_usage(90, open, limit)
+    }
+
+    #[test]
+    fn it_should_parse_the_soft_limit_of_open_files() {
+        assert_eq!(parse_limit(LIMITS), Some(1024));
+    }
+
+    #[test]
+    fn it_should_not_have_a_limit_when_it_is_unlimited() {
+        assert_eq!(parse_limit(&LIMITS.replace("1024 ", "unlimited")), None);
+    }
+
+    #[test]
+    fn it_should_be_near_the_limit_when_the_usage_is_above_the_max_usage_percent() {
+        assert!(!monitor(899, Some(1000)).is_near_the_limit());
+        assert!(monitor(900, Some(1000)).is_near_the_limit());
+    }
+
+    #[test]
+    fn it_should_never_be_near_the_limit_when_the_limit_is_unknown() {
+        assert!(!monitor(u64::MAX, None).is_near_the_limit());
+    }
+}
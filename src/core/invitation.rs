@@ -0,0 +1,111 @@
+//! Invitation codes for the self-registration of new users.
+//!
+//! Small communities running a private tracker without a full
+//! [Torrust Index](https://github.com/torrust/torrust-index) can let the
+//! tracker distribute the authentication keys:
+//!
+//! 1. The admins mint invitation codes with the tracker API and share them
+//!    with the new users.
+//! 2. The new users exchange a code for an authentication key with the public
+//!    `POST /api/v1/register` endpoint.
+//!
+//! The codes are single-use: the code is removed from the database when it's
+//! exchanged for a key. The self-registration is disabled by default:
+//!
+//! ```toml
+//! [http_api.registration]
+//! key_seconds_valid = 2592000
+//! ```
+//!
+//! The keys issued to the new users are permanent if `key_seconds_valid` is
+//! not set.
+use std::str::FromStr;
+
+use derive_more::Display;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The length of the invitation codes.
+pub const INVITATION_CODE_LENGTH: usize = 32;
+
+/// A single-use invitation code.
+///
+/// - It contains only ascii alphanumeric chars: lower and uppercase letters and
+///   numbers.
+/// - It's a 32-char string.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Display, Hash)]
+pub struct Code(String);
+
+impl Code {
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Error returned when an invitation code cannot be parsed from a string.
+#[derive(Debug, Error)]
+pub enum ParseCodeError {
+    #[error("Invalid invitation code length. It must have 32 chars")]
+    InvalidLength,
+    #[error("Invalid chars for invitation code. It can only contain alphanumeric chars (0-9, a-z, A-Z)")]
+    InvalidChars,
+}
+
+impl FromStr for Code {
+    type Err = ParseCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != INVITATION_CODE_LENGTH {
+            return Err(ParseCodeError::InvalidLength);
+        }
+
+        if !s.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ParseCodeError::InvalidChars);
+        }
+
+        Ok(Self(s.to_owned()))
+    }
+}
+
+/// It generates a new random invitation [`Code`].
+#[must_use]
+pub fn generate_code() -> Code {
+    let code: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(INVITATION_CODE_LENGTH)
+        .map(char::from)
+        .collect();
+
+    Code(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{generate_code, Code, ParseCodeError};
+
+    #[test]
+    fn it_should_generate_valid_codes() {
+        let code = generate_code();
+
+        assert!(Code::from_str(code.value()).is_ok());
+        assert_ne!(generate_code(), code);
+    }
+
+    #[test]
+    fn it_should_not_parse_codes_with_an_invalid_length() {
+        assert!(matches!(Code::from_str("abc"), Err(ParseCodeError::InvalidLength)));
+    }
+
+    #[test]
+    fn it_should_not_parse_codes_with_non_alphanumeric_chars() {
+        assert!(matches!(
+            Code::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nr-"),
+            Err(ParseCodeError::InvalidChars)
+        ));
+    }
+}
@@ -0,0 +1,507 @@
+//! Export and import of the authentication keys.
+//!
+//! The keys, with their expiration time, their quota and the IP range they are
+//! bound to, can be dumped to a JSON file and restored later into any database
+//! driver. It can be used to migrate the keys from one database driver to
+//! another, or to restore them after the database has been lost or corrupted.
+//!
+//! An exported file looks like this:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "keys": [
+//!     {
+//!       "key": "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ",
+//!       "valid_until": 1729070400,
+//!       "quota": {
+//!         "max_announces_per_day": 1000,
+//!         "max_peers_per_day": null
+//!       },
+//!       "bound_to": "203.0.113.0/24"
+//!     },
+//!     {
+//!       "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!       "valid_until": null,
+//!       "quota": null
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The export can be encrypted with a passphrase. The keys are encrypted with
+//! `ChaCha20-Poly1305`, using a key derived from the passphrase with
+//! `PBKDF2-HMAC-SHA256`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "encryption": {
+//!     "algorithm": "chacha20-poly1305",
+//!     "kdf": "pbkdf2-hmac-sha256",
+//!     "iterations": 600000,
+//!     "salt": "3q2+7w6MXxPRlb+Pr3vNbA==",
+//!     "nonce": "Vg5oE3Jd3oZ7KZ9x"
+//!   },
+//!   "ciphertext": "..."
+//! }
+//! ```
+//!
+//! Importing is idempotent: only the keys missing in the database are added,
+//! together with their quota. The existing keys are never modified.
+//!
+//! The `keys` console app exports and imports the keys of the database in the
+//! tracker configuration. Refer to [`console::keys`](crate::console::keys).
+use std::num::NonZeroU32;
+use std::panic::Location;
+use std::str::FromStr;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{digest, pbkdf2};
+use serde::{Deserialize, Serialize};
+use torrust_tracker_located_error::{Located, LocatedError};
+
+use super::auth::{IpRange, Key, ParseKeyError, PeerKey};
+use super::databases::{self, Database};
+use super::key_quota::Quota;
+
+/// The version of the file format.
+pub const VERSION: u32 = 1;
+
+const ALGORITHM: &str = "chacha20-poly1305";
+const KDF: &str = "pbkdf2-hmac-sha256";
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = digest::SHA256_OUTPUT_LEN;
+
+/// Errors returned when the keys can't be exported or imported.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Unsupported keys file version: {version}, expected {VERSION}, {location}")]
+    UnsupportedVersion {
+        version: u32,
+        location: &'static Location<'static>,
+    },
+
+    #[error("Unsupported keys file encryption: {algorithm} with {kdf}, {location}")]
+    UnsupportedEncryption {
+        algorithm: String,
+        kdf: String,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The keys file is encrypted, a passphrase is required, {location}")]
+    PassphraseRequired { location: &'static Location<'static> },
+
+    #[error("Can't decrypt the keys file, wrong passphrase or corrupted file, {location}")]
+    Decryption { location: &'static Location<'static> },
+
+    #[error("Can't encrypt the keys file, {location}")]
+    Encryption { location: &'static Location<'static> },
+
+    #[error("Invalid keys file: {message}, {location}")]
+    InvalidFile {
+        message: String,
+        location: &'static Location<'static>,
+    },
+
+    #[error("Invalid key in the keys file: {key}, {source}")]
+    InvalidKey {
+        key: String,
+        source: LocatedError<'static, ParseKeyError>,
+    },
+
+    #[error("Can't read or write the keys in the database: {source}")]
+    DatabaseError {
+        source: LocatedError<'static, databases::error::Error>,
+    },
+}
+
+impl From<databases::error::Error> for Error {
+    #[track_caller]
+    fn from(err: databases::error::Error) -> Self {
+        Self::DatabaseError {
+            source: Located(err).into(),
+        }
+    }
+}
+
+/// One exported key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExportedKey {
+    /// The key. For example: `YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`.
+    pub key: String,
+    /// Unix timestamp, in seconds, when the key expires. `None` for permanent
+    /// keys.
+    pub valid_until: Option<u64>,
+    /// The daily quota of the key, if it has one.
+    pub quota: Option<Quota>,
+    /// The IP address or IP range the key is bound to, if it's bound to one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound_to: Option<IpRange>,
+}
+
+/// The parameters used to encrypt the keys.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Encryption {
+    pub algorithm: String,
+    pub kdf: String,
+    pub iterations: u32,
+    /// Base64 encoded salt for the key derivation.
+    pub salt: String,
+    /// Base64 encoded nonce.
+    pub nonce: String,
+}
+
+/// The contents of a keys file, in plain text or encrypted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Contents {
+    Plain {
+        keys: Vec<ExportedKey>,
+    },
+    Encrypted {
+        encryption: Encryption,
+        /// Base64 encoded encrypted JSON array of keys, with the
+        /// authentication tag.
+        ciphertext: String,
+    },
+}
+
+/// A keys file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    pub version: u32,
+    #[serde(flatten)]
+    pub contents: Contents,
+}
+
+/// The number of keys imported and the ones skipped because they were
+/// already in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Imported {
+    pub keys: usize,
+    pub skipped: usize,
+}
+
+/// It exports all the keys in the database, with their quotas. The export is
+/// encrypted when a passphrase is provided.
+///
+/// # Errors
+///
+/// Will return an error if the keys can't be loaded from the database or
+/// they can't be encrypted.
+pub fn export(database: &dyn Database, passphrase: Option<&str>) -> Result<File, Error> {
+    let quotas = database.load_key_quotas()?;
+
+    let keys = database
+        .load_keys()?
+        .into_iter()
+        .map(|peer_key| ExportedKey {
+            key: peer_key.key.to_string(),
+            valid_until: peer_key.valid_until.map(|valid_until| valid_until.as_secs()),
+            quota: quotas.iter().find(|(key, _)| *key == peer_key.key).map(|(_, quota)| *quota),
+            bound_to: peer_key.bound_to,
+        })
+        .collect::<Vec<_>>();
+
+    let contents = match passphrase {
+        Some(passphrase) => encrypt(&keys, passphrase)?,
+        None => Contents::Plain { keys },
+    };
+
+    Ok(File {
+        version: VERSION,
+        contents,
+    })
+}
+
+/// It adds the keys in the file that are missing in the database, with their
+/// quotas. The passphrase is only required for encrypted files.
+///
+/// The whole file is parsed before writing anything, so an invalid key does
+/// not leave a partially imported file.
+///
+/// # Errors
+///
+/// Will return an error if the file can't be decrypted, it contains an
+/// invalid key, or the database can't be read or written.
+pub fn import(database: &dyn Database, file: &File, passphrase: Option<&str>) -> Result<Imported, Error> {
+    if file.version != VERSION {
+        return Err(Error::UnsupportedVersion {
+            version: file.version,
+            location: Location::caller(),
+        });
+    }
+
+    let exported_keys = match &file.contents {
+        Contents::Plain { keys } => keys.clone(),
+        Contents::Encrypted { encryption, ciphertext } => {
+            let passphrase = passphrase.ok_or(Error::PassphraseRequired {
+                location: Location::caller(),
+            })?;
+
+            decrypt(encryption, ciphertext, passphrase)?
+        }
+    };
+
+    let keys = exported_keys
+        .iter()
+        .map(|exported_key| {
+            let peer_key = PeerKey {
+                key: Key::from_str(&exported_key.key).map_err(|err| Error::InvalidKey {
+                    key: exported_key.key.clone(),
+                    source: Located(err).into(),
+                })?,
+                valid_until: exported_key.valid_until.map(Duration::from_secs),
+                bound_to: exported_key.bound_to,
+            };
+
+            Ok((peer_key, exported_key.quota))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut imported = Imported::default();
+
+    for (peer_key, quota) in keys {
+        if database.get_key_from_keys(&peer_key.key)?.is_some() {
+            imported.skipped += 1;
+            continue;
+        }
+
+        database.add_key_to_keys(&peer_key)?;
+
+        if let Some(quota) = quota {
+            database.save_key_quota(&peer_key.key, &quota)?;
+        }
+
+        imported.keys += 1;
+    }
+
+    Ok(imported)
+}
+
+fn encrypt(keys: &[ExportedKey], passphrase: &str) -> Result<Contents, Error> {
+    let rng = SystemRandom::new();
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+
+    rng.fill(&mut salt).map_err(|_| Error::Encryption {
+        location: Location::caller(),
+    })?;
+    rng.fill(&mut nonce).map_err(|_| Error::Encryption {
+        location: Location::caller(),
+    })?;
+
+    let mut in_out = serde_json::to_vec(keys).expect("the keys should be serializable");
+
+    derive_key(passphrase, &salt, KDF_ITERATIONS)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out)
+        .map_err(|_| Error::Encryption {
+            location: Location::caller(),
+        })?;
+
+    Ok(Contents::Encrypted {
+        encryption: Encryption {
+            algorithm: ALGORITHM.to_string(),
+            kdf: KDF.to_string(),
+            iterations: KDF_ITERATIONS,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+        },
+        ciphertext: BASE64.encode(in_out),
+    })
+}
+
+fn decrypt(encryption: &Encryption, ciphertext: &str, passphrase: &str) -> Result<Vec<ExportedKey>, Error> {
+    if encryption.algorithm != ALGORITHM || encryption.kdf != KDF {
+        return Err(Error::UnsupportedEncryption {
+            algorithm: encryption.algorithm.clone(),
+            kdf: encryption.kdf.clone(),
+            location: Location::caller(),
+        });
+    }
+
+    let salt = decode_base64("salt", &encryption.salt)?;
+    let nonce = decode_base64("nonce", &encryption.nonce)?;
+    let mut in_out = decode_base64("ciphertext", ciphertext)?;
+
+    let nonce = Nonce::try_assume_unique_for_key(&nonce).map_err(|_| Error::InvalidFile {
+        message: format!("the nonce should be {NONCE_LEN} bytes long"),
+        location: Location::caller(),
+    })?;
+
+    let plaintext = derive_key(passphrase, &salt, encryption.iterations)
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| Error::Decryption {
+            location: Location::caller(),
+        })?;
+
+    serde_json::from_slice(plaintext).map_err(|err| Error::InvalidFile {
+        message: err.to_string(),
+        location: Location::caller(),
+    })
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> LessSafeKey {
+    let iterations = NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN);
+
+    let mut key = [0u8; KEY_LEN];
+
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key);
+
+    LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &key).expect("the key should have the algorithm key length"))
+}
+
+#[track_caller]
+fn decode_base64(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    BASE64.decode(value).map_err(|err| Error::InvalidFile {
+        message: format!("invalid base64 {field}: {err}"),
+        location: Location::caller(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use torrust_tracker_test_helpers::random;
+
+    use super::{export, import, Contents, Error, Imported};
+    use crate::core::auth::{Key, PeerKey};
+    use crate::core::databases::driver::{self, Driver};
+    use crate::core::databases::Database;
+    use crate::core::key_quota::Quota;
+
+    const KEY: &str = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ";
+    const PERMANENT_KEY: &str = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6";
+
+    fn database() -> Box<dyn Database> {
+        let path = env::temp_dir().join(format!("key_export_{}.db", random::string(16)));
+        driver::build(&Driver::Sqlite3, path.to_str().unwrap()).unwrap()
+    }
+
+    fn quota() -> Quota {
+        Quota {
+            max_announces_per_day: Some(1000),
+            max_peers_per_day: None,
+        }
+    }
+
+    fn database_with_keys() -> Box<dyn Database> {
+        let database = database();
+
+        let key = Key::from_str(KEY).unwrap();
+
+        database
+            .add_key_to_keys(&PeerKey {
+                key: key.clone(),
+                valid_until: Some(Duration::from_secs(1_729_070_400)),
+                bound_to: Some("203.0.113.0/24".parse().unwrap()),
+            })
+            .unwrap();
+        database.save_key_quota(&key, &quota()).unwrap();
+
+        database
+            .add_key_to_keys(&PeerKey {
+                key: Key::from_str(PERMANENT_KEY).unwrap(),
+                valid_until: None,
+                bound_to: None,
+            })
+            .unwrap();
+
+        database
+    }
+
+    fn sorted_keys(database: &dyn Database) -> Vec<PeerKey> {
+        let mut keys = database.load_keys().unwrap();
+        keys.sort_by_key(|peer_key| peer_key.key.to_string());
+        keys
+    }
+
+    #[test]
+    fn it_should_restore_the_exported_keys_with_their_quotas() {
+        let source = database_with_keys();
+        let target = database();
+
+        let file = export(source.as_ref(), None).unwrap();
+
+        assert_eq!(
+            import(target.as_ref(), &file, None).unwrap(),
+            Imported { keys: 2, skipped: 0 }
+        );
+        assert_eq!(sorted_keys(target.as_ref()), sorted_keys(source.as_ref()));
+        assert_eq!(
+            target.load_key_quotas().unwrap(),
+            vec![(Key::from_str(KEY).unwrap(), quota())]
+        );
+    }
+
+    #[test]
+    fn it_should_skip_the_keys_already_in_the_database() {
+        let database = database_with_keys();
+
+        let file = export(database.as_ref(), None).unwrap();
+
+        assert_eq!(
+            import(database.as_ref(), &file, None).unwrap(),
+            Imported { keys: 0, skipped: 2 }
+        );
+    }
+
+    #[test]
+    fn it_should_restore_an_encrypted_export_with_the_passphrase() {
+        let source = database_with_keys();
+        let target = database();
+
+        let file = export(source.as_ref(), Some("secret")).unwrap();
+
+        assert!(matches!(file.contents, Contents::Encrypted { .. }));
+        assert!(!serde_json::to_string(&file).unwrap().contains(KEY));
+
+        import(target.as_ref(), &file, Some("secret")).unwrap();
+
+        assert_eq!(sorted_keys(target.as_ref()), sorted_keys(source.as_ref()));
+    }
+
+    #[test]
+    fn it_should_fail_importing_an_encrypted_export_with_a_wrong_or_missing_passphrase() {
+        let source = database_with_keys();
+        let target = database();
+
+        let file = export(source.as_ref(), Some("secret")).unwrap();
+
+        assert!(matches!(
+            import(target.as_ref(), &file, Some("wrong")),
+            Err(Error::Decryption { .. })
+        ));
+        assert!(matches!(
+            import(target.as_ref(), &file, None),
+            Err(Error::PassphraseRequired { .. })
+        ));
+        assert!(target.load_keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_not_import_anything_when_the_file_contains_an_invalid_key() {
+        let database = database();
+
+        let file = serde_json::from_str(&format!(
+            r#"{{"version": 1, "keys": [{{"key": "{KEY}", "valid_until": null, "quota": null}}, {{"key": "invalid", "valid_until": null, "quota": null}}]}}"#
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            import(database.as_ref(), &file, None),
+            Err(Error::InvalidKey { .. })
+        ));
+        assert!(database.load_keys().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+//! Daily usage quotas for the authentication keys.
+//!
+//! In private mode, a key can have a [`Quota`] that limits how much it can
+//! use the tracker per day:
+//!
+//! Limit | Description
+//! ---|---
+//! `max_announces_per_day` | The number of `announce` requests made with the key.
+//! `max_peers_per_day` | The number of peers returned in the `announce` responses for the key.
+//!
+//! The [`Usage`] of every key is counted, with or without a quota, so that it
+//! can be checked through the API. The counters are reset at midnight (UTC).
+//!
+//! When a limit is reached the `announce` requests with the key fail, and the
+//! failure tells the client to retry when the counters are reset. The last
+//! request before reaching the peers limit gets only the remaining peers.
+//!
+//! The quotas are persisted in the database. They are not removed with the
+//! keys, so that re-uploading a key keeps its quota.
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use super::auth::Key;
+
+const SECONDS_IN_A_DAY: u64 = 86_400;
+
+/// The daily limits for a key. `None` means there is no limit.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quota {
+    /// The maximum number of `announce` requests per day.
+    pub max_announces_per_day: Option<u32>,
+    /// The maximum number of peers returned in the `announce` responses per
+    /// day.
+    pub max_peers_per_day: Option<u32>,
+}
+
+/// What a key has consumed today.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    /// The number of `announce` requests.
+    pub announces: u32,
+    /// The number of peers returned in the `announce` responses.
+    pub peers: u32,
+}
+
+/// Error returned when the key has reached one of the limits of its quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exhausted {
+    /// Time until the usage counters are reset.
+    pub reset_in: Duration,
+}
+
+/// The quotas and the daily usage of the keys.
+#[derive(Debug, Default)]
+pub struct Quotas {
+    quotas: RwLock<HashMap<Key, Quota>>,
+    /// The usage of each key, with the day it belongs to.
+    usage: RwLock<HashMap<Key, (u64, Usage)>>,
+}
+
+impl Quotas {
+    /// It sets the quota for the key, replacing the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas lock is poisoned.
+    pub fn set(&self, key: &Key, quota: Quota) {
+        self.quotas
+            .write()
+            .expect("it should get the key quotas lock")
+            .insert(key.clone(), quota);
+    }
+
+    /// It removes the quota for the key. It returns the removed quota.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas lock is poisoned.
+    pub fn remove(&self, key: &Key) -> Option<Quota> {
+        self.quotas.write().expect("it should get the key quotas lock").remove(key)
+    }
+
+    /// It replaces all the quotas. The usage counters are kept.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas lock is poisoned.
+    pub fn replace_all(&self, quotas: impl IntoIterator<Item = (Key, Quota)>) {
+        let mut current = self.quotas.write().expect("it should get the key quotas lock");
+
+        current.clear();
+        current.extend(quotas);
+    }
+
+    /// It returns the quota for the key, if any.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas lock is poisoned.
+    #[must_use]
+    pub fn get(&self, key: &Key) -> Option<Quota> {
+        self.quotas
+            .read()
+            .expect("it should get the key quotas lock")
+            .get(key)
+            .copied()
+    }
+
+    /// It returns what the key has consumed on the day of `now`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the usage lock is poisoned.
+    #[must_use]
+    pub fn usage(&self, key: &Key, now: DurationSinceUnixEpoch) -> Usage {
+        self.usage
+            .read()
+            .expect("it should get the key usage lock")
+            .get(key)
+            .filter(|(day, _)| *day == day_of(now))
+            .map(|(_, usage)| *usage)
+            .unwrap_or_default()
+    }
+
+    /// It returns `true` if the key has reached any of the limits of its quota
+    /// on the day of `now`, without counting a request.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas or the usage locks are poisoned.
+    #[must_use]
+    pub fn is_exhausted(&self, key: &Key, now: DurationSinceUnixEpoch) -> bool {
+        let quota = self.get(key).unwrap_or_default();
+        let usage = self.usage(key, now);
+
+        quota.max_announces_per_day.is_some_and(|max| usage.announces >= max)
+            || quota.max_peers_per_day.is_some_and(|max| usage.peers >= max)
+    }
+
+    /// It counts an `announce` request made with the key. It returns how many
+    /// peers the key can still receive today, or `None` if there is no limit.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error, without counting the request, if the key has
+    /// reached any of the limits of its quota.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the quotas or the usage locks are poisoned.
+    pub fn consume_announce(&self, key: &Key, now: DurationSinceUnixEpoch) -> Result<Option<u32>, Exhausted> {
+        let quota = self.get(key).unwrap_or_default();
+
+        let mut usage = self.usage.write().expect("it should get the key usage lock");
+        let usage = today_usage(&mut usage, key, now);
+
+        let announces_exhausted = quota.max_announces_per_day.is_some_and(|max| usage.announces >= max);
+        let peers_exhausted = quota.max_peers_per_day.is_some_and(|max| usage.peers >= max);
+
+        if announces_exhausted || peers_exhausted {
+            return Err(Exhausted {
+                reset_in: time_until_reset(now),
+            });
+        }
+
+        usage.announces = usage.announces.saturating_add(1);
+
+        Ok(quota.max_peers_per_day.map(|max| max - usage.peers))
+    }
+
+    /// It counts the peers returned in an `announce` response for the key.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the usage lock is poisoned.
+    pub fn consume_peers(&self, key: &Key, peers: u32, now: DurationSinceUnixEpoch) {
+        let mut usage = self.usage.write().expect("it should get the key usage lock");
+        let usage = today_usage(&mut usage, key, now);
+
+        usage.peers = usage.peers.saturating_add(peers);
+    }
+}
+
+/// It returns the usage of the key for the day of `now`, resetting it if it
+/// belongs to a previous day.
+fn today_usage<'a>(usage: &'a mut HashMap<Key, (u64, Usage)>, key: &Key, now: DurationSinceUnixEpoch) -> &'a mut Usage {
+    let today = day_of(now);
+
+    let (day, usage) = usage.entry(key.clone()).or_insert((today, Usage::default()));
+
+    if *day != today {
+        *day = today;
+        *usage = Usage::default();
+    }
+
+    usage
+}
+
+fn day_of(time: DurationSinceUnixEpoch) -> u64 {
+    time.as_secs() / SECONDS_IN_A_DAY
+}
+
+fn time_until_reset(now: DurationSinceUnixEpoch) -> Duration {
+    Duration::from_secs((day_of(now) + 1) * SECONDS_IN_A_DAY).saturating_sub(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Exhausted, Quota, Quotas, Usage};
+    use crate::core::auth::Key;
+
+    fn key() -> Key {
+        "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse().unwrap()
+    }
+
+    /// 2024-10-16 09:00:00 UTC
+    fn morning() -> Duration {
+        Duration::from_secs(1_729_069_200)
+    }
+
+    #[test]
+    fn it_should_count_the_usage_of_keys_without_a_quota() {
+        let quotas = Quotas::default();
+
+        assert_eq!(quotas.consume_announce(&key(), morning()), Ok(None));
+        quotas.consume_peers(&key(), 50, morning());
+
+        assert_eq!(quotas.usage(&key(), morning()), Usage { announces: 1, peers: 50 });
+    }
+
+    #[test]
+    fn it_should_reject_announces_once_the_daily_announces_are_exhausted() {
+        let quotas = Quotas::default();
+        quotas.set(
+            &key(),
+            Quota {
+                max_announces_per_day: Some(1),
+                max_peers_per_day: None,
+            },
+        );
+
+        assert!(quotas.consume_announce(&key(), morning()).is_ok());
+
+        assert_eq!(
+            quotas.consume_announce(&key(), morning()),
+            Err(Exhausted {
+                reset_in: Duration::from_secs(15 * 3600)
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_tell_whether_the_quota_is_exhausted_without_counting_a_request() {
+        let quotas = Quotas::default();
+        quotas.set(
+            &key(),
+            Quota {
+                max_announces_per_day: Some(1),
+                max_peers_per_day: None,
+            },
+        );
+
+        assert!(!quotas.is_exhausted(&key(), morning()));
+
+        assert!(quotas.consume_announce(&key(), morning()).is_ok());
+
+        assert!(quotas.is_exhausted(&key(), morning()));
+        assert_eq!(quotas.usage(&key(), morning()).announces, 1);
+    }
+
+    #[test]
+    fn it_should_return_the_peers_the_key_can_still_receive() {
+        let quotas = Quotas::default();
+        quotas.set(
+            &key(),
+            Quota {
+                max_announces_per_day: None,
+                max_peers_per_day: Some(100),
+            },
+        );
+
+        assert_eq!(quotas.consume_announce(&key(), morning()), Ok(Some(100)));
+        quotas.consume_peers(&key(), 70, morning());
+
+        assert_eq!(quotas.consume_announce(&key(), morning()), Ok(Some(30)));
+        quotas.consume_peers(&key(), 30, morning());
+
+        assert!(quotas.consume_announce(&key(), morning()).is_err());
+    }
+
+    #[test]
+    fn it_should_reset_the_usage_the_next_day() {
+        let quotas = Quotas::default();
+        quotas.set(
+            &key(),
+            Quota {
+                max_announces_per_day: Some(1),
+                max_peers_per_day: None,
+            },
+        );
+        assert!(quotas.consume_announce(&key(), morning()).is_ok());
+
+        let next_day = morning() + Duration::from_secs(86_400);
+
+        assert_eq!(quotas.usage(&key(), next_day), Usage::default());
+        assert!(quotas.consume_announce(&key(), next_day).is_ok());
+    }
+}
@@ -28,6 +28,7 @@
 //! - [Services](#services)
 //! - [Authentication](#authentication)
 //! - [Statistics](#statistics)
+//! - [Events](#events)
 //! - [Persistence](#persistence)
 //!
 //! # Tracker
@@ -38,6 +39,7 @@
 //! - **Authentication**: it handles authentication keys which are used by HTTP trackers.
 //! - **Authorization**: it handles the permission to perform requests.
 //! - **Whitelist**: when the tracker runs in `listed` or `private_listed` mode all operations are restricted to whitelisted torrents.
+//!   The torrents removed from the whitelist are dropped from the repository, and their requests are rejected with a specific failure reason.
 //! - **Statistics**: it keeps and serves the tracker statistics.
 //!
 //! Refer to [torrust-tracker-configuration](https://docs.rs/torrust-tracker-configuration) crate docs to get more information about the tracker settings.
@@ -75,6 +77,9 @@
 //!     downloaded: NumberOfBytes::new(0),
 //!     left: NumberOfBytes::new(0),
 //!     event: AnnounceEvent::Completed,
+//!     partial_seed: false,
+//!     corrupt: NumberOfBytes::new(0),
+//!     redundant: NumberOfBytes::new(0),
 //! };
 //!
 //! let peer_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
@@ -147,6 +152,7 @@
 //!     pub complete: u32,   // The number of active peers that have completed downloading (seeders)
 //!     pub downloaded: u32, // The number of peers that have ever completed downloading
 //!     pub incomplete: u32, // The number of active peers that have not completed downloading (leechers)
+//!     pub partial_seeds: u32, // The number of leechers that have all the pieces they want (BEP 21 partial seeds)
 //! }
 //! ```
 //!
@@ -230,6 +236,7 @@
 //!     pub complete: u32,   // The number of active peers that have completed downloading (seeders)
 //!     pub downloaded: u32, // The number of peers that have ever completed downloading
 //!     pub incomplete: u32, // The number of active peers that have not completed downloading (leechers)
+//!     pub partial_seeds: u32, // The number of leechers that have all the pieces they want (BEP 21 partial seeds)
 //! }
 //!
 //! ```
@@ -308,6 +315,10 @@
 //!
 //! Refer to [`peer`] module for more information about peers.
 //!
+//! Optionally, the `Tracker` can verify that the announced peer addresses are
+//! reachable. Peers known to be unreachable are returned last in the peer
+//! lists. Refer to [`reachability`] module for more information.
+//!
 //! # Configuration
 //!
 //! You can control the behavior of this module with the module settings:
@@ -317,8 +328,10 @@
 //! threshold = "debug"
 //!
 //! [core]
+//! hybrid = false
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
+//! max_scrape_torrents = 74
 //! private = false
 //! tracker_usage_statistics = true
 //!
@@ -330,14 +343,21 @@
 //! driver = "sqlite3"
 //! path = "./storage/tracker/lib/database/sqlite3.db"
 //!
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//!
 //! [core.net]
 //! on_reverse_proxy = false
 //! external_ip = "2.137.87.41"
+//! exclude_ipv6_peers_for_ipv4_clients = false
+//! trusted_proxies = []
 //!
 //! [core.tracker_policy]
 //! max_peer_timeout = 900
 //! persistent_torrent_completed_stat = false
 //! remove_peerless_torrents = true
+//! peer_eviction_policy = "reject_new"
 //! ```
 //!
 //! Refer to the [`configuration` module documentation](https://docs.rs/torrust-tracker-configuration) to get more information about all options.
@@ -375,6 +395,20 @@
 //! - [`core`](crate::core) module.
 //! - [`http`](crate::servers::http) module.
 //!
+//! Under high request rates, the positive authorization decisions for each
+//! key and torrent can be cached for a short time. Refer to the
+//! [`authorization_cache`] module for more information.
+//!
+//! Keys can have a daily quota of `announce` requests and returned peers.
+//! Refer to the [`key_quota`] module for more information.
+//!
+//! In `hybrid` mode keys are optional. Peers can announce without a key, like
+//! in public mode, but the keys they provide are verified. Peers with a valid
+//! key are authenticated: they get the `authenticated_announce_policy` (for
+//! example, longer peer lists) and their usage is counted in the key stats.
+//! Peers with an invalid key are handled as anonymous peers. It allows
+//! migrating a public tracker to private gradually.
+//!
 //! # Statistics
 //!
 //! The `Tracker` keeps metrics for some events:
@@ -387,11 +421,16 @@
 //!     pub tcp4_connections_handled: u64,
 //!     pub tcp4_announces_handled: u64,
 //!     pub tcp4_scrapes_handled: u64,
+//!     pub tcp4_scrapes_exceeding_limit: u64,
 //!
 //!     // UDP tracker
 //!     pub udp4_connections_handled: u64,
 //!     pub udp4_announces_handled: u64,
 //!     pub udp4_scrapes_handled: u64,
+//!     pub udp4_scrapes_exceeding_limit: u64,
+//!     pub udp4_connection_ids_used: u64,
+//!     pub udp4_connection_ids_unused: u64,
+//!     pub udp4_announces_with_expired_connection_id: u64,
 //!
 //!     // IP version 6
 //!
@@ -399,11 +438,57 @@
 //!     pub tcp6_connections_handled: u64,
 //!     pub tcp6_announces_handled: u64,
 //!     pub tcp6_scrapes_handled: u64,
+//!     pub tcp6_scrapes_exceeding_limit: u64,
+//!
+//!     // HTTP tracker announce responses
+//!     pub tcp_announce_responses_under_128_bytes: u64,
+//!     pub tcp_announce_responses_under_512_bytes: u64,
+//!     pub tcp_announce_responses_under_2048_bytes: u64,
+//!     pub tcp_announce_responses_over_2048_bytes: u64,
+//!     pub tcp_announce_responses_padded: u64,
+//!
+//!     // HTTP tracker announces with unknown events
+//!     pub tcp_announces_with_unknown_event: u64,
+//!
+//!     // HTTP tracker duplicate infohashes in scrapes
+//!     pub tcp_scrape_duplicate_info_hashes: u64,
+//!
+//!     // HTTP tracker requests rejected for their size
+//!     pub tcp_requests_with_uri_too_long: u64,
+//!     pub tcp_requests_with_headers_too_large: u64,
+//!
+//!     // HTTP tracker requests that exceeded the request deadline
+//!     pub tcp_announces_with_deadline_exceeded: u64,
+//!     pub tcp_scrapes_with_deadline_exceeded: u64,
+//!
+//!     // HTTP tracker announces rejected because the key is bound to another address
+//!     pub tcp_announces_with_key_bound_to_another_address: u64,
 //!
 //!     // UDP tracker
 //!     pub udp6_connections_handled: u64,
 //!     pub udp6_announces_handled: u64,
 //!     pub udp6_scrapes_handled: u64,
+//!     pub udp6_scrapes_exceeding_limit: u64,
+//!     pub udp6_connection_ids_used: u64,
+//!     pub udp6_connection_ids_unused: u64,
+//!     pub udp6_announces_with_expired_connection_id: u64,
+//!
+//!     // UDP tracker announces with unknown events
+//!     pub udp_announces_with_unknown_event: u64,
+//!
+//!     // UDP tracker duplicate infohashes in scrapes
+//!     pub udp_scrape_duplicate_info_hashes: u64,
+//!
+//!     // UDP tracker requests for recently rejected infohashes
+//!     pub udp_negative_cache_hits: u64,
+//!
+//!     // UDP tracker requests that exceeded the request deadline
+//!     pub udp_announces_with_deadline_exceeded: u64,
+//!     pub udp_scrapes_with_deadline_exceeded: u64,
+//!
+//!     // Torrent entries added to and removed from the torrents repository
+//!     pub torrents_created: u64,
+//!     pub torrents_removed: u64,
 //! }
 //! ```
 //!
@@ -425,8 +510,26 @@
 //! tracker.send_stats_event(statistics::Event::Tcp4Announce).await
 //! ```
 //!
+//! The statistics can be collected selectively with the `[core.statistics]`
+//! section of the configuration, for example, only for the UDP tracker. The
+//! events that are not collected are discarded before being sent.
+//!
 //! Refer to [`statistics`] module for more information about statistics.
 //!
+//! # Events
+//!
+//! The `Tracker` publishes domain events (a peer announced, a torrent was
+//! created or removed, a torrent was removed from the whitelist, a key was
+//! created or revoked) to an internal event bus.
+//! Other subsystems can subscribe to the bus to react to those changes without
+//! adding more work to the `announce` hot path.
+//!
+//! ```text
+//! let mut receiver = tracker.subscribe();
+//! ```
+//!
+//! Refer to [`events`] module for more information about events.
+//!
 //! # Persistence
 //!
 //! Right now the `Tracker` is responsible for storing and load data into and
@@ -439,32 +542,73 @@
 //! - Torrent metrics
 //!
 //! Refer to [`databases`] module for more information about persistence.
+//!
+//! The `SQLite` database can be backed up on intervals. Refer to the [`backup`]
+//! module for more information.
+//!
+//! The writes that fail can be kept in a dead-letter queue and retried later.
+//! Refer to the [`dead_letter`] module for more information.
+//!
+//! The peers are not persisted, but the `announce` requests can be written to
+//! a journal and replayed on restart. Refer to the [`announce_journal`] module
+//! for more information.
+//!
+//! The initial whitelist, keys and torrent policies can be declared in the
+//! configuration. Refer to the [`seed`] module for more information.
+pub mod announce_export;
+pub mod announce_journal;
+pub mod async_announce;
+pub mod audit;
 pub mod auth;
+pub mod authorization_cache;
+pub mod backup;
+pub mod database_authorization;
 pub mod databases;
+pub mod dead_letter;
+pub mod deadline;
 pub mod error;
+pub mod events;
+pub mod failure_reasons;
+#[cfg(feature = "fault-injection")]
+pub mod faults;
+pub mod file_descriptors;
+pub mod invitation;
+pub mod key_export;
+pub mod key_quota;
+pub mod negative_cache;
+pub mod peer_cleanup;
+pub mod reachability;
+pub mod seed;
 pub mod services;
+pub mod slo;
+pub mod slow_requests;
 pub mod statistics;
 pub mod torrent;
+pub mod virtual_trackers;
+pub mod whitelist_rules;
 
 pub mod peer_tests;
 
 use std::cmp::max;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use auth::PeerKey;
+use aquatic_udp_protocol::AnnounceEvent;
+use auth::{IpRange, PeerKey};
+use camino::Utf8PathBuf;
 use databases::driver::Driver;
 use derive_more::Constructor;
-use error::PeerKeyError;
+use error::{PeerKeyError, RegistrationError};
 use tokio::sync::mpsc::error::SendError;
 use torrust_tracker_clock::clock::Time;
-use torrust_tracker_configuration::v2_0_0::database;
 use torrust_tracker_configuration::{AnnouncePolicy, Core, TORRENT_PEERS_LIMIT};
-use torrust_tracker_located_error::Located;
+use torrust_tracker_located_error::{DynError, Located};
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -473,7 +617,9 @@ use torrust_tracker_torrent_repository::repository::Repository;
 
 use self::auth::Key;
 use self::error::Error;
+use self::torrent::policy::TorrentPolicy;
 use self::torrent::Torrents;
+use self::whitelist_rules::Rule;
 use crate::core::databases::Database;
 use crate::CurrentClock;
 
@@ -500,6 +646,38 @@ pub struct Tracker {
     /// The list of allowed torrents. Only for listed trackers.
     whitelist: tokio::sync::RwLock<std::collections::HashSet<InfoHash>>,
 
+    /// The rules to allow torrents that are not in the whitelist, by id.
+    /// Only for listed trackers.
+    whitelist_rules: tokio::sync::RwLock<std::collections::BTreeMap<u64, Rule>>,
+
+    /// The torrents removed from the whitelist, and not added again. Only for
+    /// listed trackers.
+    removed_from_whitelist: tokio::sync::RwLock<std::collections::HashSet<InfoHash>>,
+
+    /// The daily quotas and usage of the keys. Only for private trackers.
+    key_quotas: key_quota::Quotas,
+
+    /// Recent positive authorization decisions, when the cache is enabled.
+    authorization_cache: Option<authorization_cache::Cache>,
+
+    /// Recently rejected infohashes, when the negative cache is enabled.
+    negative_cache: Option<negative_cache::Cache>,
+
+    /// The workers looking up in the database the torrents that are not in
+    /// the in-memory whitelist, when the database authorization is enabled.
+    /// Only for listed trackers.
+    database_authorization: Option<database_authorization::Pool>,
+
+    /// The templates of the failure reasons sent to the clients, when they
+    /// are enabled.
+    failure_reasons: Option<failure_reasons::Templates>,
+
+    /// The announce policies for specific torrents.
+    policies: tokio::sync::RwLock<std::collections::HashMap<InfoHash, TorrentPolicy>>,
+
+    /// The static seed peers for specific torrents.
+    static_peers: tokio::sync::RwLock<std::collections::HashMap<InfoHash, std::collections::BTreeSet<SocketAddr>>>,
+
     /// The in-memory torrents repository.
     torrents: Arc<Torrents>,
 
@@ -508,6 +686,69 @@ pub struct Tracker {
 
     /// The in-memory stats repo.
     stats_repository: statistics::Repo,
+
+    /// Bus where the domain events are published.
+    event_bus: events::Bus,
+
+    /// Queues for the `announce` writes when micro-batching is enabled.
+    announce_batcher: Option<torrent::batching::Batcher>,
+
+    /// Queue for the peer updates of the asynchronous `announce` requests,
+    /// when it's enabled.
+    async_announce: Option<async_announce::Queue>,
+
+    /// Snapshot of the swarm metadata for reads, when it's enabled.
+    read_snapshot: Option<torrent::snapshot::Snapshot>,
+
+    /// Admission control for the `announce` and `scrape` requests, when the
+    /// request deadline is enabled.
+    request_admission: Option<deadline::Admission>,
+
+    /// The last consistency check of the torrents repository, when it's
+    /// enabled.
+    consistency_checker: Option<torrent::consistency::Checker>,
+
+    /// Log of the swarm changes, when it's enabled.
+    swarm_changes: Option<torrent::changes::Changes>,
+
+    /// Peer reachability verifier, when the verification is enabled.
+    reachability_verifier: Option<reachability::Verifier>,
+
+    /// Whether the tracker is in maintenance mode. It can be toggled at
+    /// runtime.
+    maintenance: AtomicBool,
+
+    /// The settings of the inactive peer cleanup. They can be changed at
+    /// runtime and the cleanup job is notified.
+    peer_cleanup: tokio::sync::watch::Sender<peer_cleanup::Settings>,
+
+    /// Database backups, when they are enabled.
+    database_backups: Option<backup::Backups>,
+
+    /// Failed database writes waiting to be retried, when the dead-letter
+    /// queue is enabled.
+    dead_letters: Option<dead_letter::Queue>,
+
+    /// Journal of the peer updates, when it's enabled.
+    announce_journal: Option<Arc<announce_journal::Journal>>,
+
+    /// The state of the import of the persisted torrents at startup.
+    warm_up: Arc<torrent::import::WarmUp>,
+
+    /// Usage of the file descriptors, when the admission control is
+    /// enabled.
+    file_descriptors: Option<Arc<file_descriptors::Monitor>>,
+
+    /// Outcome of the recent `announce` requests, when the service level
+    /// objectives are enabled.
+    slo: Option<Arc<slo::Monitor>>,
+
+    /// Logger of the slow requests, when it's enabled.
+    slow_requests: Option<slow_requests::Logger>,
+
+    /// The injected faults, when the fault injection is enabled.
+    #[cfg(feature = "fault-injection")]
+    faults: Option<Arc<faults::Injector>>,
 }
 
 /// Structure that holds the data returned by the `announce` request.
@@ -542,6 +783,14 @@ impl PeersWanted {
         Self::Only { amount }
     }
 
+    /// It limits the amount of peers wanted to `max`.
+    #[must_use]
+    pub fn at_most(&self, max: u32) -> Self {
+        Self::Only {
+            amount: self.limit().min(Self::only(max).limit()),
+        }
+    }
+
     fn limit(&self) -> usize {
         match self {
             PeersWanted::All => TORRENT_PEERS_LIMIT,
@@ -550,6 +799,41 @@ impl PeersWanted {
     }
 }
 
+/// Whether the peer announcing authenticated with a key or not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Requester {
+    /// The peer did not provide a valid key.
+    #[default]
+    Anonymous,
+    /// The peer provided a valid key.
+    Authenticated,
+}
+
+/// When the peer update of an `announce` request is applied.
+///
+/// Refer to the [`async_announce`] module for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AnnounceMode {
+    /// The update is applied before building the response.
+    #[default]
+    Synchronous,
+    /// The response is built from the current swarm and the update is applied
+    /// afterwards, when the queue is enabled.
+    Asynchronous,
+}
+
+impl AnnounceMode {
+    /// It returns the mode for a service with the `async_announce` option.
+    #[must_use]
+    pub fn new(async_announce: bool) -> Self {
+        if async_announce {
+            Self::Asynchronous
+        } else {
+            Self::Synchronous
+        }
+    }
+}
+
 impl From<i32> for PeersWanted {
     fn from(value: i32) -> Self {
         if value > 0 {
@@ -564,6 +848,11 @@ impl From<i32> for PeersWanted {
 }
 
 /// Structure that holds the data returned by the `scrape` request.
+///
+/// There is only one file for each torrent, even if the infohash was
+/// requested more than once. The files are not ordered: the responses that
+/// keep the request order, like the UDP tracker one, have to follow the
+/// requested infohashes.
 #[derive(Debug, PartialEq, Default)]
 pub struct ScrapeData {
     /// A map of infohashes and swarm metadata for each torrent.
@@ -599,6 +888,19 @@ impl ScrapeData {
     pub fn add_file_with_zeroed_metadata(&mut self, info_hash: &InfoHash) {
         self.files.insert(*info_hash, SwarmMetadata::zeroed());
     }
+
+    /// Whether the torrent is already in the `ScrapeData`.
+    #[must_use]
+    pub fn contains(&self, info_hash: &InfoHash) -> bool {
+        self.files.contains_key(info_hash)
+    }
+
+    /// Number of infohashes requested more than once, not counting the first
+    /// occurrence.
+    #[must_use]
+    pub fn duplicates(&self, info_hashes: &[InfoHash]) -> u64 {
+        (info_hashes.len().saturating_sub(self.files.len())) as u64
+    }
 }
 
 /// This type contains the info needed to add a new tracker key.
@@ -613,6 +915,10 @@ pub struct AddKeyRequest {
 
     /// How long the key will be valid in seconds. Use `None` for permanent keys.
     pub opt_seconds_valid: Option<u64>,
+
+    /// The IP address or IP range in CIDR notation the key can only be used
+    /// from. Use `None` for keys that can be used from any address.
+    pub opt_bound_to: Option<String>,
 }
 
 impl Tracker {
@@ -626,24 +932,143 @@ impl Tracker {
         stats_event_sender: Option<Box<dyn statistics::EventSender>>,
         stats_repository: statistics::Repo,
     ) -> Result<Tracker, databases::error::Error> {
-        let driver = match config.database.driver {
-            database::Driver::Sqlite3 => Driver::Sqlite3,
-            database::Driver::MySQL => Driver::MySQL,
+        let driver = Driver::from(&config.database.driver);
+
+        let database = databases::driver::build(&driver, &config.database.path)?;
+
+        #[cfg(feature = "fault-injection")]
+        let faults = config
+            .fault_injection
+            .as_ref()
+            .map(|config| Arc::new(faults::Injector::new(config)));
+
+        #[cfg(feature = "fault-injection")]
+        let database: Box<dyn Database> = match &faults {
+            Some(injector) => Box::new(databases::faulty::FaultyDatabase::wrap(database, injector.clone())),
+            None => database,
+        };
+
+        let database = Arc::new(database);
+
+        // The persisted torrents are counted to report the progress of the import.
+        let expected_torrents = if config.tracker_policy.persistent_torrent_completed_stat {
+            count_persistent_torrents(&**database)
+        } else {
+            0
         };
 
-        let database = Arc::new(databases::driver::build(&driver, &config.database.path)?);
+        let database_authorization = config
+            .database_authorization
+            .as_ref()
+            .map(|config| database_authorization::Pool::new(config, &database));
 
         Ok(Tracker {
             config: config.clone(),
             keys: tokio::sync::RwLock::new(std::collections::HashMap::new()),
             whitelist: tokio::sync::RwLock::new(std::collections::HashSet::new()),
+            whitelist_rules: tokio::sync::RwLock::new(std::collections::BTreeMap::new()),
+            removed_from_whitelist: tokio::sync::RwLock::new(std::collections::HashSet::new()),
+            key_quotas: key_quota::Quotas::default(),
+            authorization_cache: config.authorization_cache.as_ref().map(authorization_cache::Cache::new),
+            negative_cache: config.negative_cache.as_ref().map(negative_cache::Cache::new),
+            database_authorization,
+            failure_reasons: config.failure_reasons.as_ref().map(failure_reasons::Templates::new),
+            policies: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+            static_peers: tokio::sync::RwLock::new(std::collections::HashMap::new()),
             torrents: Arc::default(),
             stats_event_sender,
             stats_repository,
             database,
+            event_bus: events::Bus::default(),
+            announce_batcher: config.announce_batching.as_ref().map(torrent::batching::Batcher::new),
+            async_announce: config.async_announce.as_ref().map(async_announce::Queue::new),
+            read_snapshot: config.read_snapshot.map(|_| torrent::snapshot::Snapshot::default()),
+            request_admission: config.request_deadline.as_ref().map(deadline::Admission::new),
+            consistency_checker: config.consistency_check.map(|_| torrent::consistency::Checker::default()),
+            swarm_changes: config.swarm_changes.as_ref().map(torrent::changes::Changes::new),
+            reachability_verifier: config.peer_reachability.as_ref().map(reachability::Verifier::new),
+            maintenance: AtomicBool::new(config.maintenance.enabled),
+            peer_cleanup: tokio::sync::watch::Sender::new(peer_cleanup::Settings::from_config(config)),
+            database_backups: config.database_backup.as_ref().map(backup::Backups::new),
+            dead_letters: config.dead_letter_queue.as_ref().map(dead_letter::Queue::new),
+            announce_journal: config
+                .announce_journal
+                .as_ref()
+                .map(|config| Arc::new(announce_journal::Journal::new(config))),
+            warm_up: Arc::new(torrent::import::WarmUp::new(expected_torrents)),
+            file_descriptors: config
+                .file_descriptors
+                .as_ref()
+                .map(|config| Arc::new(file_descriptors::Monitor::new(config))),
+            slo: config.slo.as_ref().map(|config| Arc::new(slo::Monitor::new(config))),
+            slow_requests: config.slow_requests.as_ref().map(slow_requests::Logger::new),
+            #[cfg(feature = "fault-injection")]
+            faults,
         })
     }
 
+    /// It returns the state of the import of the persisted torrents. It's
+    /// shared with the health check API.
+    #[must_use]
+    pub fn warm_up(&self) -> Arc<torrent::import::WarmUp> {
+        self.warm_up.clone()
+    }
+
+    /// It returns the usage of the file descriptors, when the admission
+    /// control is enabled. It's shared with the HTTP trackers and the health
+    /// check API.
+    #[must_use]
+    pub fn file_descriptors(&self) -> Option<Arc<file_descriptors::Monitor>> {
+        self.file_descriptors.clone()
+    }
+
+    /// It returns the journal of the peer updates, when it's enabled. It's
+    /// shared with the job flushing the records to the disk.
+    #[must_use]
+    pub fn announce_journal(&self) -> Option<Arc<announce_journal::Journal>> {
+        self.announce_journal.clone()
+    }
+
+    /// It returns the counters of the journal of the peer updates, or `None`
+    /// if the journal is not enabled.
+    #[must_use]
+    pub fn get_announce_journal_metrics(&self) -> Option<announce_journal::Metrics> {
+        self.announce_journal.as_deref().map(announce_journal::Journal::metrics)
+    }
+
+    /// It returns the outcome of the recent `announce` requests, when the
+    /// service level objectives are enabled. It's shared with the health
+    /// check API.
+    #[must_use]
+    pub fn slo(&self) -> Option<Arc<slo::Monitor>> {
+        self.slo.clone()
+    }
+
+    /// It logs the request if it's slower than the threshold of its kind,
+    /// when the logging of the slow requests is enabled. The `request`
+    /// context is only built for the slow requests.
+    pub fn log_slow_request(
+        &self,
+        kind: slow_requests::Kind,
+        timer: &slow_requests::Timer,
+        request: impl FnOnce() -> slow_requests::Request,
+    ) {
+        if let Some(logger) = &self.slow_requests {
+            logger.log(kind, timer, request);
+        }
+    }
+
+    /// It delays the UDP or HTTP tracker request, when the
+    /// [fault injection](crate::core::faults) is enabled and the fault
+    /// happens. It does nothing otherwise.
+    #[cfg_attr(not(feature = "fault-injection"), allow(clippy::unused_async))]
+    pub async fn inject_request_latency(&self) {
+        #[cfg(feature = "fault-injection")]
+        if let Some(faults) = &self.faults {
+            faults.delay_request().await;
+        }
+    }
+
     /// Returns `true` is the tracker is in public mode.
     pub fn is_public(&self) -> bool {
         !self.config.private
@@ -654,11 +1079,22 @@ impl Tracker {
         self.config.private
     }
 
+    /// Returns `true` is the tracker is in hybrid mode.
+    pub fn is_hybrid(&self) -> bool {
+        self.config.hybrid
+    }
+
     /// Returns `true` is the tracker is in whitelisted mode.
     pub fn is_listed(&self) -> bool {
         self.config.listed
     }
 
+    /// Returns `true` if the tracker verifies the keys provided by the peers.
+    /// Keys are required in private mode and optional in hybrid mode.
+    pub fn verifies_keys(&self) -> bool {
+        self.is_private() || self.is_hybrid()
+    }
+
     /// Returns `true` if the tracker requires authentication.
     pub fn requires_authentication(&self) -> bool {
         self.is_private()
@@ -669,15 +1105,183 @@ impl Tracker {
         self.config.net.on_reverse_proxy
     }
 
+    /// Returns `true` if the IP address is one of the trusted proxies.
+    pub fn is_trusted_proxy(&self, ip: &IpAddr) -> bool {
+        self.config.net.trusted_proxies.contains(ip)
+    }
+
+    /// Returns the IP addresses of the trusted proxies.
+    pub fn get_trusted_proxies(&self) -> &[IpAddr] {
+        &self.config.net.trusted_proxies
+    }
+
     pub fn get_announce_policy(&self) -> AnnouncePolicy {
         self.config.announce_policy
     }
 
+    /// Returns the minimum size of the HTTP `announce` responses, when they
+    /// are padded.
+    pub fn get_announce_response_min_size(&self) -> Option<usize> {
+        self.config.announce_response_padding.map(|padding| padding.min_size)
+    }
+
     pub fn get_maybe_external_ip(&self) -> Option<IpAddr> {
         self.config.net.external_ip
     }
 
-    /// It handles an announce request.
+    /// Returns the maximum number of torrents that can be scraped at once.
+    pub fn get_max_scrape_torrents(&self) -> u16 {
+        self.config.max_scrape_torrents
+    }
+
+    /// Returns `true` if the tracker is in maintenance mode.
+    pub fn is_in_maintenance(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    /// It enables or disables the maintenance mode.
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the current settings of the inactive peer cleanup.
+    #[must_use]
+    pub fn get_peer_cleanup(&self) -> peer_cleanup::Settings {
+        *self.peer_cleanup.borrow()
+    }
+
+    /// It changes the settings of the inactive peer cleanup. The cleanup job
+    /// uses the new settings without restarting.
+    pub fn set_peer_cleanup(&self, settings: peer_cleanup::Settings) {
+        self.peer_cleanup.send_replace(settings);
+    }
+
+    /// It returns a receiver notified when the settings of the inactive peer
+    /// cleanup change.
+    #[must_use]
+    pub fn subscribe_to_peer_cleanup(&self) -> tokio::sync::watch::Receiver<peer_cleanup::Settings> {
+        self.peer_cleanup.subscribe()
+    }
+
+    /// Returns the time in minutes clients should wait before retrying an
+    /// `announce` while the tracker is in maintenance mode.
+    pub fn get_maintenance_retry_in(&self) -> u32 {
+        self.config.maintenance.retry_in
+    }
+
+    /// It checks whether the tracker accepts `announce` requests, which are
+    /// rejected while the tracker is in maintenance mode.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the tracker is in maintenance mode.
+    pub fn check_maintenance(&self) -> Result<(), Error> {
+        if self.is_in_maintenance() {
+            return Err(Error::TrackerInMaintenance {
+                retry_in: self.get_maintenance_retry_in(),
+                location: Location::caller(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// It returns the failure reason sent to the clients for the error with
+    /// the `code`, or `None` if the templates are not enabled or there is no
+    /// template for the code. The `message` is the default message of the
+    /// error.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn render_failure_reason(&self, code: &str, message: &str) -> Option<String> {
+        self.failure_reasons
+            .as_ref()
+            .and_then(|templates| templates.render(code, message))
+    }
+
+    /// Returns `true` if the database backups are enabled.
+    pub fn is_database_backup_enabled(&self) -> bool {
+        self.database_backups.is_some()
+    }
+
+    /// It writes a new database backup. It returns the path of the backup
+    /// file.
+    ///
+    /// # Context: Persistence
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the backups are not enabled or if the backup
+    /// fails.
+    pub async fn backup_database(&self) -> Result<Utf8PathBuf, backup::Error> {
+        let Some(backups) = &self.database_backups else {
+            return Err(backup::Error::NotEnabled {
+                location: Location::caller(),
+            });
+        };
+
+        backups.run(&self.database).await
+    }
+
+    /// It returns the status of the last database backup, if any.
+    pub fn get_last_database_backup(&self) -> Option<backup::Status> {
+        self.database_backups.as_ref().and_then(backup::Backups::last)
+    }
+
+    /// Returns `true` if the dead-letter queue for failed database writes is
+    /// enabled.
+    pub fn is_dead_letter_queue_enabled(&self) -> bool {
+        self.dead_letters.is_some()
+    }
+
+    /// It returns the failed database writes waiting to be retried.
+    ///
+    /// # Context: Persistence
+    pub fn get_dead_letters(&self) -> Vec<dead_letter::Letter> {
+        self.dead_letters
+            .as_ref()
+            .map(dead_letter::Queue::letters)
+            .unwrap_or_default()
+    }
+
+    /// It returns the dead-letter queue metrics, if the queue is enabled.
+    ///
+    /// # Context: Persistence
+    pub fn get_dead_letter_metrics(&self) -> Option<dead_letter::Metrics> {
+        self.dead_letters.as_ref().map(dead_letter::Queue::metrics)
+    }
+
+    /// It retries the failed database writes. It returns `None` if the
+    /// dead-letter queue is not enabled.
+    ///
+    /// # Context: Persistence
+    pub async fn replay_dead_letters(&self) -> Option<dead_letter::Replay> {
+        match &self.dead_letters {
+            Some(dead_letters) => Some(dead_letters.replay(&self.database).await),
+            None => None,
+        }
+    }
+
+    /// It discards the failed database writes. It returns `None` if the
+    /// dead-letter queue is not enabled.
+    ///
+    /// # Context: Persistence
+    pub fn drain_dead_letters(&self) -> Option<usize> {
+        self.dead_letters.as_ref().map(dead_letter::Queue::drain)
+    }
+
+    /// It returns the announce policy for the requester.
+    ///
+    /// Authenticated requesters get the `authenticated_announce_policy`, when
+    /// it's configured.
+    pub fn get_announce_policy_for(&self, requester: &Requester) -> AnnouncePolicy {
+        match (requester, &self.config.authenticated_announce_policy) {
+            (Requester::Authenticated, Some(policy)) => policy.announce_policy(),
+            _ => self.get_announce_policy(),
+        }
+    }
+
+    /// It handles an announce request from an anonymous requester.
     ///
     /// # Context: Tracker
     ///
@@ -688,6 +1292,54 @@ impl Tracker {
         peer: &mut peer::Peer,
         remote_client_ip: &IpAddr,
         peers_wanted: &PeersWanted,
+    ) -> AnnounceData {
+        self.announce_as(&Requester::Anonymous, info_hash, peer, remote_client_ip, peers_wanted)
+    }
+
+    /// It handles an announce request.
+    ///
+    /// The announce policy and the number of peers in the response depend on
+    /// the requester. See [`Tracker::get_announce_policy_for`].
+    ///
+    /// # Context: Tracker
+    ///
+    /// BEP 03: [The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html).
+    pub fn announce_as(
+        &self,
+        requester: &Requester,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
+    ) -> AnnounceData {
+        self.announce_with_mode(
+            requester,
+            info_hash,
+            peer,
+            remote_client_ip,
+            peers_wanted,
+            AnnounceMode::Synchronous,
+        )
+    }
+
+    /// It handles an announce request in the given [`AnnounceMode`].
+    ///
+    /// In the asynchronous mode, the response is built from the current swarm
+    /// and the peer update is queued. The update is applied synchronously when
+    /// the queue is not enabled or it's full. When it's full, the pending
+    /// updates are applied first, so they can't overwrite the newer one.
+    ///
+    /// # Context: Tracker
+    ///
+    /// BEP 03: [The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html).
+    pub fn announce_with_mode(
+        &self,
+        requester: &Requester,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
+        mode: AnnounceMode,
     ) -> AnnounceData {
         // code-review: maybe instead of mutating the peer we could just return
         // a tuple with the new peer and the announce data: (Peer, AnnounceData).
@@ -708,28 +1360,132 @@ impl Tracker {
         peer.change_ip(&assign_ip_address_to_peer(remote_client_ip, self.config.net.external_ip));
         tracing::debug!("After: {peer:?}");
 
-        let stats = self.upsert_peer_and_get_stats(info_hash, peer);
+        if let Some(journal) = &self.announce_journal {
+            journal.append(info_hash, peer);
+        }
+
+        let stats = match (mode, &self.async_announce) {
+            (AnnounceMode::Asynchronous, Some(queue)) if queue.enqueue(info_hash, peer) => self.get_swarm_metadata(info_hash),
+            (AnnounceMode::Asynchronous, Some(queue)) => queue.apply_pending(
+                |update| self.apply_async_announce(update),
+                || self.upsert_peer_and_get_stats(info_hash, peer),
+            ),
+            _ => self.upsert_peer_and_get_stats(info_hash, peer),
+        };
+
+        self.event_bus.publish(events::Event::PeerAnnounced {
+            info_hash: *info_hash,
+            peer: *peer,
+        });
+
+        if let Some(verifier) = &self.reachability_verifier {
+            if peer.event == AnnounceEvent::Stopped {
+                verifier.remove(info_hash, &peer.peer_addr);
+            }
+        }
+
+        let limit = match (requester, &self.config.authenticated_announce_policy, peers_wanted) {
+            (Requester::Authenticated, Some(policy), PeersWanted::All) => {
+                usize::try_from(policy.max_peers).unwrap_or(TORRENT_PEERS_LIMIT)
+            }
+            _ => peers_wanted.limit(),
+        };
 
-        let peers = self.get_peers_for(info_hash, peer, peers_wanted.limit());
+        let peers = self.get_peers_for(info_hash, peer, limit);
 
         AnnounceData {
             peers,
             stats,
-            policy: self.get_announce_policy(),
+            policy: self.get_announce_policy_for(requester),
+        }
+    }
+
+    /// It handles an announce request within the
+    /// [request deadline](crate::core::deadline), when it's configured. The
+    /// peer is updated like in [`Tracker::announce_with_mode`].
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`deadline::Exceeded`] error if the request is not
+    /// admitted before the deadline. The peer is not added to the swarm in
+    /// that case.
+    pub async fn announce_within_deadline(
+        &self,
+        requester: &Requester,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
+        mode: AnnounceMode,
+    ) -> Result<AnnounceData, deadline::Exceeded> {
+        let _permit = self.start_request_deadline().admit(self.request_admission.as_ref()).await?;
+
+        Ok(self.announce_with_mode(requester, info_hash, peer, remote_client_ip, peers_wanted, mode))
+    }
+
+    /// It returns the best-effort response for the `announce` requests that
+    /// exceeded the [request deadline](crate::core::deadline): no peers, and
+    /// the swarm metadata of the read snapshot, when it's enabled.
+    #[must_use]
+    pub fn best_effort_announce_data(&self, info_hash: &InfoHash, requester: &Requester) -> AnnounceData {
+        AnnounceData {
+            peers: vec![],
+            stats: self.best_effort_swarm_metadata(info_hash),
+            policy: self.get_announce_policy_for(requester),
+        }
+    }
+
+    /// It returns the notifier the job that applies the queued updates of the
+    /// asynchronous `announce` requests waits on. It returns `None` if the
+    /// queue is not enabled.
+    #[must_use]
+    pub fn async_announce_queued(&self) -> Option<Arc<tokio::sync::Notify>> {
+        self.async_announce.as_ref().map(async_announce::Queue::queued)
+    }
+
+    /// It applies the queued peer updates of the asynchronous `announce`
+    /// requests, in arrival order.
+    pub fn apply_async_announces(&self) {
+        if let Some(queue) = &self.async_announce {
+            queue.apply_pending(|update| self.apply_async_announce(update), || ());
         }
     }
 
+    fn apply_async_announce(&self, update: &async_announce::Update) {
+        let _stats = self.upsert_peer_and_get_stats(&update.info_hash, &update.peer);
+    }
+
+    /// It returns the asynchronous `announce` queue metrics, if the queue is
+    /// enabled.
+    pub fn get_async_announce_metrics(&self) -> Option<async_announce::Metrics> {
+        self.async_announce.as_ref().map(async_announce::Queue::metrics)
+    }
+
     /// It handles a scrape request.
     ///
     /// # Context: Tracker
     ///
     /// BEP 48: [Tracker Protocol Extension: Scrape](https://www.bittorrent.org/beps/bep_0048.html).
+    ///
+    /// When the read snapshot is enabled, the swarm metadata is taken from the
+    /// last published snapshot.
+    ///
+    /// Duplicate infohashes are only looked up once.
     pub async fn scrape(&self, info_hashes: &Vec<InfoHash>) -> ScrapeData {
         let mut scrape_data = ScrapeData::empty();
 
+        let snapshot = self.read_snapshot.as_ref().map(torrent::snapshot::Snapshot::load);
+
         for info_hash in info_hashes {
+            if scrape_data.contains(info_hash) {
+                continue;
+            }
+
             let swarm_metadata = match self.authorize(info_hash).await {
-                Ok(()) => self.get_swarm_metadata(info_hash),
+                Ok(()) => match &snapshot {
+                    Some(snapshot) => snapshot.get_swarm_metadata(info_hash),
+                    None => self.get_swarm_metadata(info_hash),
+                },
                 Err(_) => SwarmMetadata::zeroed(),
             };
             scrape_data.add_file(info_hash, swarm_metadata);
@@ -738,10 +1494,84 @@ impl Tracker {
         scrape_data
     }
 
-    /// It returns the data for a `scrape` response.
-    fn get_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
-        match self.torrents.get(info_hash) {
-            Some(torrent_entry) => torrent_entry.get_swarm_metadata(),
+    /// It returns the swarm metadata of one torrent for a `scrape` request,
+    /// or the reason why the torrent was rejected. Unlike [`Tracker::scrape`],
+    /// it tells apart the torrents that are not whitelisted or do not exist,
+    /// so the rejection can be cached.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the torrent is not authorized or it's not in
+    /// the torrents repository.
+    pub async fn scrape_torrent(&self, info_hash: &InfoHash) -> Result<SwarmMetadata, negative_cache::Rejection> {
+        if self.authorize(info_hash).await.is_err() {
+            return Err(negative_cache::Rejection::NotAuthorized);
+        }
+
+        let maybe_swarm_metadata = match &self.read_snapshot {
+            Some(snapshot) => snapshot.load().find_swarm_metadata(info_hash),
+            None => self.torrents.get_swarm_metadata(info_hash),
+        };
+
+        maybe_swarm_metadata.ok_or(negative_cache::Rejection::NotFound)
+    }
+
+    /// It handles a scrape request within the
+    /// [request deadline](crate::core::deadline), when it's configured.
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`deadline::Exceeded`] error if the request is not
+    /// admitted, or not handled, before the deadline.
+    pub async fn scrape_within_deadline(&self, info_hashes: &Vec<InfoHash>) -> Result<ScrapeData, deadline::Exceeded> {
+        let deadline = self.start_request_deadline();
+
+        let _permit = deadline.admit(self.request_admission.as_ref()).await?;
+
+        deadline.run(self.scrape(info_hashes)).await
+    }
+
+    /// It returns the best-effort response for the `scrape` requests that
+    /// exceeded the [request deadline](crate::core::deadline). The swarm
+    /// metadata is taken from the read snapshot, when it's enabled and the
+    /// tracker is not listed. Otherwise, it's zeroed, since the torrents could
+    /// not be authorized.
+    #[must_use]
+    pub fn best_effort_scrape_data(&self, info_hashes: &Vec<InfoHash>) -> ScrapeData {
+        if self.is_listed() {
+            return ScrapeData::zeroed(info_hashes);
+        }
+
+        let mut scrape_data = ScrapeData::empty();
+
+        for info_hash in info_hashes {
+            scrape_data.add_file(info_hash, self.best_effort_swarm_metadata(info_hash));
+        }
+
+        scrape_data
+    }
+
+    /// It starts the deadline of an `announce` or `scrape` request.
+    #[must_use]
+    pub fn start_request_deadline(&self) -> deadline::Deadline {
+        deadline::Deadline::start(self.config.request_deadline.as_ref())
+    }
+
+    /// It returns the swarm metadata of the read snapshot, when it's enabled,
+    /// without waiting for the torrents repository. Otherwise, it's zeroed.
+    fn best_effort_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
+        match &self.read_snapshot {
+            Some(snapshot) => snapshot.load().get_swarm_metadata(info_hash),
+            None => SwarmMetadata::zeroed(),
+        }
+    }
+
+    /// It returns the data for a `scrape` response.
+    fn get_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
+        match self.torrents.get(info_hash) {
+            Some(torrent_entry) => torrent_entry.get_swarm_metadata(),
             None => SwarmMetadata::default(),
         }
     }
@@ -755,11 +1585,104 @@ impl Tracker {
     ///
     /// Will return a `database::Error` if unable to load the list of `persistent_torrents` from the database.
     pub fn load_torrents_from_database(&self) -> Result<(), databases::error::Error> {
-        let persistent_torrents = self.database.load_persistent_torrents()?;
+        self.import_torrents_from_database(torrent::import::BATCH_SIZE)
+    }
+
+    /// It loads the torrents from database in batches of `batch_size`
+    /// torrents. Every batch is imported in parallel shards, one per available
+    /// CPU, while the next batch is loaded from the database.
+    ///
+    /// The tracker is warming up until the import finishes. The persisted
+    /// torrents are counted first, so the progress of the import can be
+    /// reported.
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load a batch of `persistent_torrents` from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a thread importing a shard of torrents panics.
+    pub fn import_torrents_from_database(&self, batch_size: u32) -> Result<(), databases::error::Error> {
+        let shards = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
 
-        self.torrents.import_persistent(&persistent_torrents);
+        self.warm_up.start();
 
-        Ok(())
+        let result = std::thread::scope(|scope| {
+            let mut after: Option<InfoHash> = None;
+            let mut importing: Vec<std::thread::ScopedJoinHandle<'_, ()>> = vec![];
+
+            loop {
+                let batch = self.database.load_persistent_torrents_batch(after.as_ref(), batch_size);
+
+                for shard in importing.drain(..) {
+                    shard.join().expect("it should import the shard of torrents");
+                }
+
+                let batch = batch?;
+
+                tracing::info!(
+                    imported = self.warm_up.imported_torrents(),
+                    expected = self.warm_up.expected_torrents(),
+                    "Importing the persisted torrents"
+                );
+
+                let Some(last) = batch.keys().next_back().copied() else {
+                    return Ok(());
+                };
+
+                let is_last_batch = batch.len() < batch_size as usize;
+
+                after = Some(last);
+
+                for shard in torrent::import::split_into_shards(batch, shards) {
+                    importing.push(scope.spawn(move || {
+                        self.torrents.import_persistent(&shard);
+                        self.warm_up.add_imported_torrents(shard.len());
+                    }));
+                }
+
+                if is_last_batch {
+                    for shard in importing.drain(..) {
+                        shard.join().expect("it should import the shard of torrents");
+                    }
+
+                    return Ok(());
+                }
+            }
+        });
+
+        self.warm_up.finish();
+
+        tracing::info!(imported = self.warm_up.imported_torrents(), "Persisted torrents imported");
+
+        result
+    }
+
+    /// It replays the peer updates in the `announce` journal, when it's
+    /// enabled, to rebuild the swarms after a restart. It returns the number
+    /// of replayed updates.
+    ///
+    /// The replayed updates do not publish domain events and do not persist
+    /// the torrent stats, since they were already handled before the restart.
+    ///
+    /// # Context: Tracker
+    pub fn replay_announce_journal(&self) -> usize {
+        let Some(journal) = &self.announce_journal else {
+            return 0;
+        };
+
+        let started = std::time::Instant::now();
+
+        let pending = journal.take_pending();
+
+        for (info_hash, peer) in &pending {
+            self.torrents.upsert_peer(info_hash, peer, &self.config.tracker_policy);
+        }
+
+        journal.finish_replay(pending.len(), started);
+
+        pending.len()
     }
 
     /// # Context: Tracker
@@ -767,10 +1690,30 @@ impl Tracker {
     /// Get torrent peers for a given torrent and client.
     ///
     /// It filters out the client making the request.
+    ///
+    /// When the peer reachability verification is enabled, peers known to be
+    /// unreachable are returned after the other ones.
     fn get_peers_for(&self, info_hash: &InfoHash, peer: &peer::Peer, limit: usize) -> Vec<Arc<peer::Peer>> {
-        match self.torrents.get(info_hash) {
-            None => vec![],
-            Some(entry) => entry.get_peers_for_client(&peer.peer_addr, Some(max(limit, TORRENT_PEERS_LIMIT))),
+        let Some(entry) = self.torrents.get(info_hash) else {
+            return vec![];
+        };
+
+        let limit = max(limit, TORRENT_PEERS_LIMIT);
+
+        let only_ipv4 = self.config.net.exclude_ipv6_peers_for_ipv4_clients && is_ipv4(&peer.peer_addr.ip());
+
+        if self.reachability_verifier.is_none() && !only_ipv4 {
+            return entry.get_peers_for_client(&peer.peer_addr, Some(limit));
+        }
+
+        let peers = entry
+            .get_peers_for_client(&peer.peer_addr, None)
+            .into_iter()
+            .filter(|peer| !only_ipv4 || is_ipv4(&peer.peer_addr.ip()));
+
+        match &self.reachability_verifier {
+            Some(verifier) => verifier.reachable_first(info_hash, peers, limit),
+            None => peers.take(limit).collect(),
         }
     }
 
@@ -789,13 +1732,35 @@ impl Tracker {
     /// needed for a `announce` request response.
     ///
     /// # Context: Tracker
+    ///
+    /// When `announce` micro-batching is enabled, the update is queued and the
+    /// returned stats might not include it yet.
     pub fn upsert_peer_and_get_stats(&self, info_hash: &InfoHash, peer: &peer::Peer) -> SwarmMetadata {
-        let swarm_metadata_before = match self.torrents.get_swarm_metadata(info_hash) {
-            Some(swarm_metadata) => swarm_metadata,
-            None => SwarmMetadata::zeroed(),
-        };
+        if let Some(batcher) = &self.announce_batcher {
+            if let Some(batch) = batcher.enqueue(info_hash, peer) {
+                self.apply_announce_batch(batch);
+            }
 
-        self.torrents.upsert_peer(info_hash, peer);
+            return self.get_swarm_metadata(info_hash);
+        }
+
+        let maybe_swarm_metadata_before = self.torrents.get_swarm_metadata(info_hash);
+
+        #[cfg(feature = "fault-injection")]
+        if let Some(faults) = &self.faults {
+            faults.delay_lock();
+        }
+
+        self.torrents.upsert_peer(info_hash, peer, &self.config.tracker_policy);
+
+        let swarm_metadata_before = if let Some(swarm_metadata) = maybe_swarm_metadata_before {
+            swarm_metadata
+        } else {
+            self.event_bus
+                .publish(events::Event::TorrentCreated { info_hash: *info_hash });
+            self.forget_rejection(info_hash);
+            SwarmMetadata::zeroed()
+        };
 
         let swarm_metadata_after = match self.torrents.get_swarm_metadata(info_hash) {
             Some(swarm_metadata) => swarm_metadata,
@@ -809,6 +1774,85 @@ impl Tracker {
         swarm_metadata_after
     }
 
+    /// It publishes a new snapshot of the swarm metadata of all torrents when
+    /// the read snapshot is enabled. It does nothing otherwise.
+    ///
+    /// The health of the torrents is computed comparing their swarms with the
+    /// ones in the previous snapshot.
+    ///
+    /// # Context: Tracker
+    pub fn refresh_read_snapshot(&self) {
+        if let Some(snapshot) = &self.read_snapshot {
+            let data: torrent::snapshot::Data = self
+                .torrents
+                .get_paginated(None)
+                .iter()
+                .map(|(info_hash, entry)| (*info_hash, entry.get_swarm_metadata()))
+                .collect();
+
+            snapshot.publish(data.with_health(&snapshot.load()));
+        }
+    }
+
+    /// It records a change in the swarm of the torrent when the swarm changes
+    /// log is enabled. It does nothing otherwise.
+    ///
+    /// # Context: Tracker
+    pub fn record_swarm_change(&self, info_hash: &InfoHash) {
+        if let Some(swarm_changes) = &self.swarm_changes {
+            swarm_changes.record(info_hash);
+        }
+    }
+
+    /// It returns at most `limit` torrents whose swarm changed after the
+    /// `since` cursor, or `None` if the swarm changes log is not enabled.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_swarm_changes(&self, since: u64, limit: usize) -> Option<torrent::changes::Page> {
+        self.swarm_changes
+            .as_ref()
+            .map(|swarm_changes| swarm_changes.since(since, limit))
+    }
+
+    /// It applies all the pending `announce` updates when micro-batching is
+    /// enabled. It does nothing otherwise.
+    ///
+    /// # Context: Tracker
+    pub fn flush_announce_batches(&self) {
+        if let Some(batcher) = &self.announce_batcher {
+            self.apply_announce_batch(batcher.drain());
+        }
+    }
+
+    /// It applies a batch of peer updates, acquiring each torrent entry lock
+    /// only once per batch.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a torrent entry lock is poisoned.
+    fn apply_announce_batch(&self, batch: Vec<torrent::batching::Update>) {
+        for (info_hash, peers) in torrent::batching::group_by_torrent(batch) {
+            let maybe_swarm_metadata_before = self.torrents.get_swarm_metadata(&info_hash);
+
+            self.torrents
+                .upsert_peers_batch(&info_hash, &peers, &self.config.tracker_policy);
+
+            if maybe_swarm_metadata_before.is_none() {
+                self.event_bus.publish(events::Event::TorrentCreated { info_hash });
+                self.forget_rejection(&info_hash);
+            }
+
+            let swarm_metadata_after = self.torrents.get_swarm_metadata(&info_hash).unwrap_or_default();
+
+            if maybe_swarm_metadata_before.unwrap_or_default() != swarm_metadata_after {
+                self.persist_stats(&info_hash, &swarm_metadata_after);
+            }
+        }
+    }
+
     /// It stores the torrents stats into the database (if persistency is enabled).
     ///
     /// # Context: Tracker
@@ -817,45 +1861,300 @@ impl Tracker {
             let completed = swarm_metadata.downloaded;
             let info_hash = *info_hash;
 
-            drop(self.database.save_persistent_torrent(&info_hash, completed));
+            if let Err(error) = self.database.save_persistent_torrent(&info_hash, completed) {
+                if let Some(dead_letters) = &self.dead_letters {
+                    dead_letters.push(dead_letter::Write::TorrentCompleted { info_hash, completed }, &error);
+                } else {
+                    tracing::debug!(%info_hash, %error, "Discarded failed write of the torrent completed counter");
+                }
+            }
+        }
+    }
+
+    /// It checks that the torrents repository answers queries. It reads the
+    /// swarm metadata of the first torrent, if any, so it also takes the lock
+    /// of one torrent entry.
+    ///
+    /// # Context: Tracker
+    pub fn check_torrents_repository(&self) {
+        if let Some((_, entry)) = self.torrents.get_paginated(Some(&Pagination::new(0, 1))).first() {
+            let _ = entry.get_swarm_metadata();
+        }
+    }
+
+    /// It checks that the database answers queries.
+    ///
+    /// # Context: Persistence
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to run the query.
+    pub fn ping_database(&self) -> Result<(), databases::error::Error> {
+        self.database.ping()
+    }
+
+    /// It returns the database tables that lack some of the columns the
+    /// tracker expects, along with the missing columns. They are tables
+    /// created by an older version of the tracker.
+    ///
+    /// See [`databases::SCHEMA`].
+    ///
+    /// # Context: Persistence
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to get the columns of a table.
+    pub fn outdated_database_tables(&self) -> Result<Vec<(String, Vec<String>)>, databases::error::Error> {
+        let mut outdated = vec![];
+
+        for (table, expected_columns) in databases::SCHEMA {
+            let columns = self.database.table_columns(table)?;
+
+            let missing: Vec<String> = expected_columns
+                .iter()
+                .filter(|column| !columns.iter().any(|existing| existing == *column))
+                .map(ToString::to_string)
+                .collect();
+
+            if !missing.is_empty() {
+                outdated.push(((*table).to_string(), missing));
+            }
         }
+
+        Ok(outdated)
     }
 
     /// It calculates and returns the general `Tracker`
     /// [`TorrentsMetrics`]
     ///
+    /// When the read snapshot is enabled, the metrics are taken from the last
+    /// published snapshot.
+    ///
     /// # Context: Tracker
     ///
     /// # Panics
     /// Panics if unable to get the torrent metrics.
     pub fn get_torrents_metrics(&self) -> TorrentsMetrics {
-        self.torrents.get_metrics()
+        match &self.read_snapshot {
+            Some(snapshot) => snapshot.load().get_metrics(),
+            None => self.torrents.get_metrics(),
+        }
+    }
+
+    /// It returns the distribution of the swarm sizes computed with the last
+    /// published read snapshot, or `None` if the read snapshot is not enabled.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_swarm_sizes(&self) -> Option<torrent::snapshot::SwarmSizes> {
+        self.read_snapshot.as_ref().map(|snapshot| snapshot.load().get_swarm_sizes())
+    }
+
+    /// It returns the health of the torrent computed with the last published
+    /// read snapshot, or `None` if the read snapshot is not enabled or the
+    /// torrent was not in it.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_torrent_health(&self, info_hash: &InfoHash) -> Option<torrent::snapshot::Health> {
+        self.read_snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.load().get_health(info_hash))
+    }
+
+    /// It returns the distribution of the health scores computed with the
+    /// last published read snapshot, or `None` if the read snapshot is not
+    /// enabled.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_health_scores(&self) -> Option<torrent::snapshot::HealthScores> {
+        self.read_snapshot
+            .as_ref()
+            .map(|snapshot| snapshot.load().get_health_scores())
+    }
+
+    /// It verifies the invariants of the torrents repository when the
+    /// consistency check is enabled. It does nothing otherwise.
+    ///
+    /// Peers older than the peer timeout plus the cleanup interval are
+    /// reported as stale, since the last cleanup should have removed them.
+    /// Every inconsistency found is logged as a warning.
+    ///
+    /// # Context: Tracker
+    pub fn check_consistency(&self) {
+        if let Some(checker) = &self.consistency_checker {
+            let settings = self.get_peer_cleanup();
+
+            let grace = Duration::from_secs(u64::from(settings.max_peer_timeout) + settings.inactive_peer_cleanup_interval);
+            let stale_cutoff = CurrentClock::now_sub(&grace).unwrap_or_default();
+
+            let report = torrent::consistency::check(self.torrents.as_ref(), stale_cutoff, CurrentClock::now());
+
+            for inconsistency in &report.inconsistencies {
+                if let Some(info_hash) = &inconsistency.info_hash {
+                    tracing::warn!(
+                        kind = %inconsistency.kind,
+                        %info_hash,
+                        "Torrents repository inconsistency: {}",
+                        inconsistency.message
+                    );
+                } else {
+                    tracing::warn!(
+                        kind = %inconsistency.kind,
+                        "Torrents repository inconsistency: {}",
+                        inconsistency.message
+                    );
+                }
+            }
+
+            checker.record(report);
+        }
+    }
+
+    /// It returns the counters of the consistency checks of the torrents
+    /// repository, or `None` if the consistency check is not enabled.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_consistency_check_metrics(&self) -> Option<torrent::consistency::Metrics> {
+        self.consistency_checker.as_ref().map(torrent::consistency::Checker::metrics)
+    }
+
+    /// It returns the counters of the lookups in the database whitelist, or
+    /// `None` if the database authorization is not enabled.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_database_authorization_metrics(&self) -> Option<database_authorization::Metrics> {
+        self.database_authorization
+            .as_ref()
+            .map(database_authorization::Pool::metrics)
+    }
+
+    /// It returns the report of the last consistency check of the torrents
+    /// repository, or `None` if the consistency check is not enabled or it
+    /// has not run yet.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_last_consistency_report(&self) -> Option<Arc<torrent::consistency::Report>> {
+        self.consistency_checker
+            .as_ref()
+            .and_then(torrent::consistency::Checker::last_report)
     }
 
-    /// Remove inactive peers and (optionally) peerless torrents.
+    /// Remove inactive peers and (optionally) peerless and stale torrents.
     ///
     /// # Context: Tracker
     pub fn cleanup_torrents(&self) {
-        let current_cutoff = CurrentClock::now_sub(&Duration::from_secs(u64::from(self.config.tracker_policy.max_peer_timeout)))
-            .unwrap_or_default();
+        let max_peer_timeout = self.get_peer_cleanup().max_peer_timeout;
+
+        let current_cutoff = CurrentClock::now_sub(&Duration::from_secs(u64::from(max_peer_timeout))).unwrap_or_default();
 
         self.torrents.remove_inactive_peers(current_cutoff);
 
         if self.config.tracker_policy.remove_peerless_torrents {
-            self.torrents.remove_peerless_torrents(&self.config.tracker_policy);
+            for info_hash in self.torrents.remove_peerless_torrents(&self.config.tracker_policy) {
+                self.event_bus.publish(events::Event::TorrentRemoved { info_hash });
+            }
+        }
+
+        if let Some(max_torrent_inactivity) = self.config.tracker_policy.max_torrent_inactivity {
+            let stale_cutoff = CurrentClock::now_sub(&Duration::from_secs(u64::from(max_torrent_inactivity))).unwrap_or_default();
+
+            for info_hash in self.torrents.remove_stale_torrents(stale_cutoff) {
+                self.event_bus.publish(events::Event::TorrentRemoved { info_hash });
+            }
+        }
+
+        if let Some(verifier) = &self.reachability_verifier {
+            verifier.remove_older_than(current_cutoff);
+            self.remove_reachability_of_removed_peers(verifier);
+        }
+    }
+
+    /// It removes the probe results of the peers that are no longer in their
+    /// swarms, including the ones of the removed torrents.
+    fn remove_reachability_of_removed_peers(&self, verifier: &reachability::Verifier) {
+        let swarms = verifier
+            .probed_torrents()
+            .into_iter()
+            .filter_map(|info_hash| {
+                let entry = self.torrents.get(&info_hash)?;
+                let peer_addrs = entry.get_peers(None).iter().map(|peer| peer.peer_addr).collect();
+                Some((info_hash, peer_addrs))
+            })
+            .collect();
+
+        verifier.retain_swarm_peers(&swarms);
+    }
+
+    /// It samples the announced peer address to be probed, if the peer
+    /// reachability verification is enabled.
+    ///
+    /// The servers only sample the addresses they can trust: when the address
+    /// comes from a proxy header, the proxy must be one of the trusted ones.
+    ///
+    /// # Context: Reachability
+    pub fn sample_peer_reachability(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) {
+        if let Some(verifier) = &self.reachability_verifier {
+            verifier.sample(info_hash, peer_addr);
+        }
+    }
+
+    /// It removes the next peer address waiting to be probed, if the peer
+    /// reachability verification is enabled.
+    ///
+    /// # Context: Reachability
+    #[must_use]
+    pub fn next_reachability_probe(&self) -> Option<reachability::Probe> {
+        self.reachability_verifier
+            .as_ref()
+            .and_then(reachability::Verifier::next_probe)
+    }
+
+    /// It stores the result of a peer reachability probe.
+    ///
+    /// # Context: Reachability
+    pub fn record_reachability(&self, probe: &reachability::Probe, reachability: reachability::Reachability) {
+        if let Some(verifier) = &self.reachability_verifier {
+            verifier.record(probe, reachability, CurrentClock::now());
         }
     }
 
+    /// It returns the ratio of reachable peers over the probed peers for a
+    /// torrent. It's `None` when the verification is disabled or no peer has
+    /// been probed yet.
+    ///
+    /// # Context: Reachability
+    #[must_use]
+    pub fn get_reachability_ratio(&self, info_hash: &InfoHash) -> Option<f64> {
+        self.reachability_verifier
+            .as_ref()
+            .and_then(|verifier| verifier.get_ratio(info_hash))
+    }
+
+    /// It returns a new receiver for the domain [`events`] published by the
+    /// tracker.
+    ///
+    /// # Context: Events
+    #[must_use]
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<events::Event> {
+        self.event_bus.subscribe()
+    }
+
     /// It authenticates the peer `key` against the `Tracker` authentication
     /// key list.
     ///
     /// # Errors
     ///
     /// Will return an error if the the authentication key cannot be verified.
+    /// Keys are only verified in private and hybrid modes.
     ///
     /// # Context: Authentication
     pub async fn authenticate(&self, key: &Key) -> Result<(), auth::Error> {
-        if self.is_private() {
+        if self.verifies_keys() {
             self.verify_auth_key(key).await
         } else {
             Ok(())
@@ -876,64 +2175,58 @@ impl Tracker {
     pub async fn add_peer_key(&self, add_key_req: AddKeyRequest) -> Result<auth::PeerKey, PeerKeyError> {
         // code-review: all methods related to keys should be moved to a new independent "keys" service.
 
-        match add_key_req.opt_key {
+        let bound_to = match add_key_req.opt_bound_to {
+            Some(bound_to) => match bound_to.parse::<IpRange>() {
+                Ok(ip_range) => Some(ip_range),
+                Err(err) => {
+                    return Err(PeerKeyError::InvalidBinding {
+                        bound_to,
+                        source: Located(err).into(),
+                    })
+                }
+            },
+            None => None,
+        };
+
+        let auth_key = match add_key_req.opt_key {
             // Upload pre-generated key
             Some(pre_existing_key) => {
-                if let Some(seconds_valid) = add_key_req.opt_seconds_valid {
+                let valid_until = match add_key_req.opt_seconds_valid {
                     // Expiring key
-                    let Some(valid_until) = CurrentClock::now_add(&Duration::from_secs(seconds_valid)) else {
-                        return Err(PeerKeyError::DurationOverflow { seconds_valid });
-                    };
-
-                    let key = pre_existing_key.parse::<Key>();
+                    Some(seconds_valid) => match CurrentClock::now_add(&Duration::from_secs(seconds_valid)) {
+                        Some(valid_until) => Some(valid_until),
+                        None => return Err(PeerKeyError::DurationOverflow { seconds_valid }),
+                    },
+                    // Permanent key
+                    None => None,
+                };
 
-                    match key {
-                        Ok(key) => match self.add_auth_key(key, Some(valid_until)).await {
-                            Ok(auth_key) => Ok(auth_key),
-                            Err(err) => Err(PeerKeyError::DatabaseError {
-                                source: Located(err).into(),
-                            }),
-                        },
-                        Err(err) => Err(PeerKeyError::InvalidKey {
+                match pre_existing_key.parse::<Key>() {
+                    Ok(key) => PeerKey {
+                        key,
+                        valid_until,
+                        bound_to,
+                    },
+                    Err(err) => {
+                        return Err(PeerKeyError::InvalidKey {
                             key: pre_existing_key,
                             source: Located(err).into(),
-                        }),
-                    }
-                } else {
-                    // Permanent key
-                    let key = pre_existing_key.parse::<Key>();
-
-                    match key {
-                        Ok(key) => match self.add_permanent_auth_key(key).await {
-                            Ok(auth_key) => Ok(auth_key),
-                            Err(err) => Err(PeerKeyError::DatabaseError {
-                                source: Located(err).into(),
-                            }),
-                        },
-                        Err(err) => Err(PeerKeyError::InvalidKey {
-                            key: pre_existing_key,
-                            source: Located(err).into(),
-                        }),
+                        })
                     }
                 }
             }
-            // Generate a new random key
-            None => match add_key_req.opt_seconds_valid {
-                // Expiring key
-                Some(seconds_valid) => match self.generate_auth_key(Some(Duration::from_secs(seconds_valid))).await {
-                    Ok(auth_key) => Ok(auth_key),
-                    Err(err) => Err(PeerKeyError::DatabaseError {
-                        source: Located(err).into(),
-                    }),
-                },
-                // Permanent key
-                None => match self.generate_permanent_auth_key().await {
-                    Ok(auth_key) => Ok(auth_key),
-                    Err(err) => Err(PeerKeyError::DatabaseError {
-                        source: Located(err).into(),
-                    }),
-                },
+            // Generate a new random key, expiring or permanent
+            None => PeerKey {
+                bound_to,
+                ..auth::generate_key(add_key_req.opt_seconds_valid.map(Duration::from_secs))
             },
+        };
+
+        match self.insert_auth_key(auth_key).await {
+            Ok(auth_key) => Ok(auth_key),
+            Err(err) => Err(PeerKeyError::DatabaseError {
+                source: Located(err).into(),
+            }),
         }
     }
 
@@ -965,11 +2258,7 @@ impl Tracker {
     /// * `lifetime` - The duration in seconds for the new key. The key will be
     ///   no longer valid after `lifetime` seconds.
     pub async fn generate_auth_key(&self, lifetime: Option<Duration>) -> Result<auth::PeerKey, databases::error::Error> {
-        let auth_key = auth::generate_key(lifetime);
-
-        self.database.add_key_to_keys(&auth_key)?;
-        self.keys.write().await.insert(auth_key.key.clone(), auth_key.clone());
-        Ok(auth_key)
+        self.insert_auth_key(auth::generate_key(lifetime)).await
     }
 
     /// It adds a pre-generated permanent authentication key.
@@ -1011,16 +2300,63 @@ impl Tracker {
         key: Key,
         valid_until: Option<DurationSinceUnixEpoch>,
     ) -> Result<auth::PeerKey, databases::error::Error> {
-        let auth_key = PeerKey { key, valid_until };
+        self.insert_auth_key(PeerKey {
+            key,
+            valid_until,
+            bound_to: None,
+        })
+        .await
+    }
 
+    /// It stores a new authentication key in the database and in memory.
+    ///
+    /// # Context: Authentication
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the `auth_key` to the
+    /// database. For example, if the key already exist.
+    async fn insert_auth_key(&self, auth_key: PeerKey) -> Result<auth::PeerKey, databases::error::Error> {
         // code-review: should we return a friendly error instead of the DB
         // constrain error when the key already exist? For now, it's returning
         // the specif error for each DB driver when a UNIQUE constrain fails.
-        self.database.add_key_to_keys(&auth_key)?;
+        self.persist_auth_key(&auth_key).await?;
         self.keys.write().await.insert(auth_key.key.clone(), auth_key.clone());
+        self.event_bus.publish(events::Event::KeyCreated {
+            key: auth_key.key.clone(),
+        });
         Ok(auth_key)
     }
 
+    /// It stores a new authentication key in the database. When the write
+    /// fails and the dead-letter queue is enabled, the write is queued to be
+    /// retried, unless the key already exists.
+    ///
+    /// # Context: Authentication
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the `auth_key` to the
+    /// database and the write can't be queued.
+    async fn persist_auth_key(&self, auth_key: &PeerKey) -> Result<(), databases::error::Error> {
+        let Err(error) = self.database.add_key_to_keys(auth_key) else {
+            return Ok(());
+        };
+
+        match &self.dead_letters {
+            Some(dead_letters) if !self.keys.read().await.contains_key(&auth_key.key) => {
+                dead_letters.push(
+                    dead_letter::Write::AuthKey {
+                        peer_key: auth_key.clone(),
+                    },
+                    &error,
+                );
+                Ok(())
+            }
+            _ => Err(error),
+        }
+    }
+
     /// It removes an authentication key.
     ///
     /// # Context: Authentication    
@@ -1031,17 +2367,79 @@ impl Tracker {
     pub async fn remove_auth_key(&self, key: &Key) -> Result<(), databases::error::Error> {
         self.database.remove_key_from_keys(key)?;
         self.keys.write().await.remove(key);
+        if let Some(cache) = &self.authorization_cache {
+            cache.revoke_key(key);
+        }
+        self.event_bus.publish(events::Event::KeyRevoked { key: key.clone() });
         Ok(())
     }
 
-    /// It verifies an authentication key.
+    /// It removes all the authentication keys, from memory and from the
+    /// database. Pending writes of new keys in the dead-letter queue are
+    /// discarded too. It returns the number of keys removed from memory.
+    ///
+    /// It's meant for incident response, when the keys are suspected to be
+    /// compromised.
+    ///
+    /// # Context: Authentication
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the keys from the database.
+    pub async fn remove_all_auth_keys(&self) -> Result<usize, databases::error::Error> {
+        let mut keys = self.keys.write().await;
+
+        self.database.remove_all_keys()?;
+
+        if let Some(dead_letters) = &self.dead_letters {
+            dead_letters.discard_auth_keys();
+        }
+
+        let removed: Vec<Key> = keys.drain().map(|(key, _)| key).collect();
+
+        drop(keys);
+
+        if let Some(cache) = &self.authorization_cache {
+            cache.clear();
+        }
+
+        for key in &removed {
+            self.event_bus.publish(events::Event::KeyRevoked { key: key.clone() });
+        }
+
+        Ok(removed.len())
+    }
+
+    /// It verifies an authentication key is used from an address it's bound
+    /// to. Keys are only verified in private and hybrid modes. Unknown keys are
+    /// not verified, they are rejected by the authentication.
+    ///
+    /// # Context: Authentication
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the key is bound to an IP range that does not
+    /// contain the `ip`.
+    pub async fn verify_key_binding(&self, key: &Key, ip: &IpAddr) -> Result<(), auth::Error> {
+        if !self.verifies_keys() {
+            return Ok(());
+        }
+
+        match self.keys.read().await.get(key) {
+            Some(auth_key) => auth::verify_key_binding(auth_key, ip),
+            None => Ok(()),
+        }
+    }
+
+    /// It verifies an authentication key. The key is not counted against its
+    /// daily quota.
     ///
     /// # Context: Authentication
     ///
     /// # Errors
     ///
     /// Will return a `key::Error` if unable to get any `auth_key`.
-    async fn verify_auth_key(&self, key: &Key) -> Result<(), auth::Error> {
+    pub async fn verify_auth_key(&self, key: &Key) -> Result<(), auth::Error> {
         match self.keys.read().await.get(key) {
             None => Err(auth::Error::UnableToReadKey {
                 location: Location::caller(),
@@ -1072,10 +2470,17 @@ impl Tracker {
     /// Will return a `database::Error` if unable to `load_keys` from the database.
     pub async fn load_keys_from_database(&self) -> Result<(), databases::error::Error> {
         let keys_from_database = self.database.load_keys()?;
+        let key_quotas_from_database = self.database.load_key_quotas()?;
         let mut keys = self.keys.write().await;
 
+        self.key_quotas.replace_all(key_quotas_from_database);
+
         keys.clear();
 
+        if let Some(cache) = &self.authorization_cache {
+            cache.clear();
+        }
+
         for key in keys_from_database {
             keys.insert(key.key.clone(), key);
         }
@@ -1083,1138 +2488,3680 @@ impl Tracker {
         Ok(())
     }
 
-    /// Right now, there is only authorization when the `Tracker` runs in
-    /// `listed` or `private_listed` modes.
+    /// It sets the daily quota for a key, replacing the previous one.
     ///
-    /// # Context: Authorization
+    /// # Context: Authentication
     ///
     /// # Errors
     ///
-    /// Will return an error if the tracker is running in `listed` mode
-    /// and the infohash is not whitelisted.
-    pub async fn authorize(&self, info_hash: &InfoHash) -> Result<(), Error> {
-        if !self.is_listed() {
-            return Ok(());
-        }
-
-        if self.is_info_hash_whitelisted(info_hash).await {
-            return Ok(());
-        }
-
-        Err(Error::TorrentNotWhitelisted {
-            info_hash: *info_hash,
-            location: Location::caller(),
-        })
+    /// Will return a `database::Error` if unable to persist the quota.
+    pub fn set_key_quota(&self, key: &Key, quota: key_quota::Quota) -> Result<(), databases::error::Error> {
+        self.database.save_key_quota(key, &quota)?;
+        self.key_quotas.set(key, quota);
+        Ok(())
     }
 
-    /// It adds a torrent to the whitelist.
-    /// Adding torrents is not relevant to public trackers.
+    /// It removes the daily quota for a key.
     ///
-    /// # Context: Whitelist
+    /// # Context: Authentication
     ///
     /// # Errors
     ///
-    /// Will return a `database::Error` if unable to add the `info_hash` into the whitelist database.
-    pub async fn add_torrent_to_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
-        self.add_torrent_to_database_whitelist(info_hash)?;
-        self.add_torrent_to_memory_whitelist(info_hash).await;
+    /// Will return a `database::Error` if unable to remove the persisted quota.
+    pub fn remove_key_quota(&self, key: &Key) -> Result<(), databases::error::Error> {
+        self.database.remove_key_quota(key)?;
+        self.key_quotas.remove(key);
         Ok(())
     }
 
-    /// It adds a torrent to the whitelist if it has not been whitelisted previously
-    fn add_torrent_to_database_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
-        let is_whitelisted = self.database.is_info_hash_whitelisted(*info_hash)?;
+    /// It returns the daily quota for a key, if any.
+    ///
+    /// # Context: Authentication
+    pub fn get_key_quota(&self, key: &Key) -> Option<key_quota::Quota> {
+        self.key_quotas.get(key)
+    }
 
-        if is_whitelisted {
-            return Ok(());
+    /// It returns an authentication key, or `None` if it's not registered.
+    ///
+    /// # Context: Authentication
+    pub async fn get_peer_key(&self, key: &Key) -> Option<auth::PeerKey> {
+        self.keys.read().await.get(key).cloned()
+    }
+
+    /// It returns `true` if the key has reached its daily quota. The key is
+    /// not counted against it.
+    ///
+    /// # Context: Authentication
+    pub fn is_key_quota_exhausted(&self, key: &Key) -> bool {
+        self.key_quotas.is_exhausted(key, CurrentClock::now())
+    }
+
+    /// It returns what a key has consumed today.
+    ///
+    /// # Context: Authentication
+    pub fn get_key_usage(&self, key: &Key) -> key_quota::Usage {
+        self.key_quotas.usage(key, CurrentClock::now())
+    }
+
+    /// It counts an `announce` request made with the key against its daily
+    /// quota. It returns how many peers the key can still receive today, or
+    /// `None` if there is no limit.
+    ///
+    /// Usage is only counted in private and hybrid modes, where the keys are
+    /// verified.
+    ///
+    /// # Context: Authentication
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the key has reached its daily quota.
+    pub fn consume_key_announce(&self, key: &Key) -> Result<Option<u32>, Error> {
+        if !self.verifies_keys() {
+            return Ok(None);
         }
 
-        self.database.add_info_hash_to_whitelist(*info_hash)?;
+        self.key_quotas
+            .consume_announce(key, CurrentClock::now())
+            .map_err(|exhausted| Error::KeyQuotaExceeded {
+                key: key.clone(),
+                retry_in: u32::try_from((exhausted.reset_in.as_secs() + 59) / 60).unwrap_or(u32::MAX),
+                location: Location::caller(),
+            })
+    }
 
-        Ok(())
+    /// It counts the peers returned in an `announce` response for the key.
+    ///
+    /// # Context: Authentication
+    pub fn consume_key_peers(&self, key: &Key, peers: usize) {
+        if !self.verifies_keys() {
+            return;
+        }
+
+        self.key_quotas
+            .consume_peers(key, u32::try_from(peers).unwrap_or(u32::MAX), CurrentClock::now());
     }
 
-    pub async fn add_torrent_to_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
-        self.whitelist.write().await.insert(*info_hash)
+    /// It mints a new single-use invitation code.
+    ///
+    /// # Context: Invitation Codes
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to store the code.
+    pub fn generate_invitation_code(&self) -> Result<invitation::Code, databases::error::Error> {
+        let code = invitation::generate_code();
+
+        self.database.add_invitation_code(&code)?;
+
+        Ok(code)
     }
 
-    /// It removes a torrent from the whitelist.
-    /// Removing torrents is not relevant to public trackers.
+    /// It returns the invitation codes that have not been used yet.
     ///
-    /// # Context: Whitelist
+    /// # Context: Invitation Codes
     ///
     /// # Errors
     ///
-    /// Will return a `database::Error` if unable to remove the `info_hash` from the whitelist database.
-    pub async fn remove_torrent_from_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
-        self.remove_torrent_from_database_whitelist(info_hash)?;
-        self.remove_torrent_from_memory_whitelist(info_hash).await;
-        Ok(())
+    /// Will return a `database::Error` if unable to load the codes.
+    pub fn get_invitation_codes(&self) -> Result<Vec<invitation::Code>, databases::error::Error> {
+        self.database.load_invitation_codes()
     }
 
-    /// It removes a torrent from the whitelist in the database.
+    /// It revokes an invitation code that has not been used yet.
     ///
-    /// # Context: Whitelist
+    /// # Context: Invitation Codes
     ///
     /// # Errors
     ///
-    /// Will return a `database::Error` if unable to remove the `info_hash` from the whitelist database.
-    pub fn remove_torrent_from_database_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
-        let is_whitelisted = self.database.is_info_hash_whitelisted(*info_hash)?;
+    /// Will return a `database::Error` if unable to remove the code.
+    pub fn remove_invitation_code(&self, code: &invitation::Code) -> Result<(), databases::error::Error> {
+        self.database.remove_invitation_code(code)?;
+        Ok(())
+    }
 
-        if !is_whitelisted {
-            return Ok(());
+    /// It exchanges a single-use invitation code for a new authentication key.
+    ///
+    /// The code is removed before issuing the key, so that it can't be used
+    /// twice by concurrent requests. It's restored if the key can't be
+    /// issued.
+    ///
+    /// # Context: Invitation Codes
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the code is not valid or it was already used,
+    /// or if unable to issue the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - How long the key is valid. `None` for permanent keys.
+    pub async fn register_with_invitation_code(
+        &self,
+        code: &invitation::Code,
+        lifetime: Option<Duration>,
+    ) -> Result<auth::PeerKey, RegistrationError> {
+        let removed = self
+            .database
+            .remove_invitation_code(code)
+            .map_err(|err| RegistrationError::DatabaseError {
+                source: Located(err).into(),
+            })?;
+
+        if removed == 0 {
+            return Err(RegistrationError::InvitationCodeNotValid {
+                location: Location::caller(),
+            });
         }
 
-        self.database.remove_info_hash_from_whitelist(*info_hash)?;
+        match self.generate_auth_key(lifetime).await {
+            Ok(auth_key) => Ok(auth_key),
+            Err(err) => {
+                if let Err(restore_err) = self.database.add_invitation_code(code) {
+                    tracing::error!("Failed to restore the invitation code after a failed registration: {restore_err}");
+                }
 
-        Ok(())
+                Err(RegistrationError::DatabaseError {
+                    source: Located(err).into(),
+                })
+            }
+        }
     }
 
-    /// It removes a torrent from the whitelist in memory.
+    /// It records an administrative action in the audit log.
     ///
-    /// # Context: Whitelist
-    pub async fn remove_torrent_from_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
-        self.whitelist.write().await.remove(info_hash)
+    /// # Context: Audit Log
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to store the entry.
+    pub fn add_audit_log_entry(&self, entry: &audit::Entry) -> Result<(), databases::error::Error> {
+        self.database.add_audit_log_entry(entry)
     }
 
-    /// It checks if a torrent is whitelisted.
+    /// It returns a page of the audit log, newest entries first.
     ///
-    /// # Context: Whitelist
-    pub async fn is_info_hash_whitelisted(&self, info_hash: &InfoHash) -> bool {
-        self.whitelist.read().await.contains(info_hash)
+    /// # Context: Audit Log
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the entries.
+    pub fn get_audit_log(&self, pagination: &Pagination) -> Result<Vec<audit::Entry>, databases::error::Error> {
+        self.database.load_audit_log(pagination)
     }
 
-    /// It loads the whitelist from the database.
+    /// Right now, there is only authorization when the `Tracker` runs in
+    /// `listed` or `private_listed` modes.
     ///
-    /// # Context: Whitelist
+    /// # Context: Authorization
     ///
     /// # Errors
     ///
-    /// Will return a `database::Error` if unable to load the list whitelisted `info_hash`s from the database.
-    pub async fn load_whitelist_from_database(&self) -> Result<(), databases::error::Error> {
-        let whitelisted_torrents_from_database = self.database.load_whitelist()?;
-        let mut whitelist = self.whitelist.write().await;
+    /// Will return an error if the tracker is running in `listed` mode
+    /// and the infohash is not whitelisted nor allowed by a whitelist rule,
+    /// or if it can't be looked up in the database whitelist when the
+    /// database authorization is enabled.
+    pub async fn authorize(&self, info_hash: &InfoHash) -> Result<(), Error> {
+        if !self.is_listed() {
+            return Ok(());
+        }
 
-        whitelist.clear();
+        if self.is_info_hash_whitelisted(info_hash).await {
+            return Ok(());
+        }
 
-        for info_hash in whitelisted_torrents_from_database {
-            let _: bool = whitelist.insert(info_hash);
+        if self.is_info_hash_allowed_by_whitelist_rules(info_hash).await {
+            return Ok(());
         }
 
-        Ok(())
+        if let Some(pool) = &self.database_authorization {
+            match pool.is_whitelisted(info_hash).await {
+                Ok(true) => {
+                    self.add_torrent_to_memory_whitelist(info_hash).await;
+                    return Ok(());
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    return Err(Error::WhitelistLookupUnavailable {
+                        info_hash: *info_hash,
+                        source: (Arc::new(err) as DynError).into(),
+                    })
+                }
+            }
+        }
+
+        if self.is_info_hash_removed_from_whitelist(info_hash).await {
+            return Err(Error::TorrentRemovedFromWhitelist {
+                info_hash: *info_hash,
+                location: Location::caller(),
+            });
+        }
+
+        Err(Error::TorrentNotWhitelisted {
+            info_hash: *info_hash,
+            location: Location::caller(),
+        })
     }
 
-    /// It return the `Tracker` [`statistics::Metrics`].
+    /// It returns `true` if the `key` was recently authenticated and
+    /// authorized for the torrent, so both checks can be skipped. It's always
+    /// `false` when the authorization cache is disabled.
     ///
-    /// # Context: Statistics
-    pub async fn get_stats(&self) -> tokio::sync::RwLockReadGuard<'_, statistics::Metrics> {
-        self.stats_repository.get_stats().await
+    /// # Context: Authorization
+    pub fn is_authorization_cached(&self, key: &Key, info_hash: &InfoHash) -> bool {
+        self.authorization_cache
+            .as_ref()
+            .is_some_and(|cache| cache.is_authorized(key, info_hash))
     }
 
-    /// It allows to send a statistic events which eventually will be used to update [`statistics::Metrics`].
+    /// It returns the reason why the torrent was rejected recently, if the
+    /// rejection is still cached. It's always `None` when the negative cache
+    /// is disabled.
     ///
-    /// # Context: Statistics
-    pub async fn send_stats_event(&self, event: statistics::Event) -> Option<Result<(), SendError<statistics::Event>>> {
-        match &self.stats_event_sender {
-            None => None,
-            Some(stats_event_sender) => stats_event_sender.send_event(event).await,
-        }
+    /// # Context: Authorization
+    pub fn get_cached_rejection(&self, info_hash: &InfoHash) -> Option<negative_cache::Rejection> {
+        self.negative_cache.as_ref().and_then(|cache| cache.get(info_hash))
     }
 
-    /// It drops the database tables.
+    /// It caches the rejection of the torrent, if the negative cache is
+    /// enabled.
     ///
-    /// # Errors
-    ///
-    /// Will return `Err` if unable to drop tables.
-    pub fn drop_database_tables(&self) -> Result<(), databases::error::Error> {
-        // todo: this is only used for testing. WE have to pass the database
-        // reference directly to the tests instead of via the tracker.
-        self.database.drop_database_tables()
+    /// # Context: Authorization
+    pub fn cache_rejection(&self, info_hash: &InfoHash, rejection: negative_cache::Rejection) {
+        if let Some(cache) = &self.negative_cache {
+            cache.insert(info_hash, rejection);
+        }
     }
-}
 
-#[must_use]
-fn assign_ip_address_to_peer(remote_client_ip: &IpAddr, tracker_external_ip: Option<IpAddr>) -> IpAddr {
-    if let Some(host_ip) = tracker_external_ip.filter(|_| remote_client_ip.is_loopback()) {
-        host_ip
-    } else {
-        *remote_client_ip
+    /// It removes the cached rejection of a torrent that is now whitelisted
+    /// or known.
+    fn forget_rejection(&self, info_hash: &InfoHash) {
+        if let Some(cache) = &self.negative_cache {
+            cache.remove(info_hash);
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// It caches the decision for a `key` that was authenticated and
+    /// authorized for the torrent, if the authorization cache is enabled.
+    ///
+    /// # Context: Authorization
+    pub async fn cache_authorization(&self, key: &Key, info_hash: &InfoHash) {
+        let Some(cache) = &self.authorization_cache else {
+            return;
+        };
 
-    mod the_tracker {
+        let key_valid_until = if self.config.private_mode.map_or(true, |mode| mode.check_keys_expiration) {
+            self.keys.read().await.get(key).and_then(|peer_key| peer_key.valid_until)
+        } else {
+            None
+        };
 
-        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-        use std::str::FromStr;
-        use std::sync::Arc;
+        // The decision can't outlive the whitelist rules that allowed the torrent.
+        let valid_until = match (key_valid_until, self.whitelist_rules_valid_until(info_hash).await) {
+            (Some(key_valid_until), Some(rules_valid_until)) => Some(key_valid_until.min(rules_valid_until)),
+            (key_valid_until, rules_valid_until) => key_valid_until.or(rules_valid_until),
+        };
 
-        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
-        use torrust_tracker_configuration::TORRENT_PEERS_LIMIT;
-        use torrust_tracker_primitives::info_hash::InfoHash;
-        use torrust_tracker_primitives::DurationSinceUnixEpoch;
-        use torrust_tracker_test_helpers::configuration;
+        cache.insert(key, info_hash, valid_until);
+    }
 
-        use crate::core::peer::Peer;
-        use crate::core::services::tracker_factory;
-        use crate::core::{TorrentsMetrics, Tracker};
-        use crate::shared::bit_torrent::info_hash::fixture::gen_seeded_infohash;
+    /// It returns until when the torrent is allowed only because of expiring
+    /// whitelist rules. It's `None` if the torrent does not depend on them.
+    async fn whitelist_rules_valid_until(&self, info_hash: &InfoHash) -> Option<DurationSinceUnixEpoch> {
+        if !self.is_listed() || self.is_info_hash_whitelisted(info_hash).await {
+            return None;
+        }
 
-        fn public_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_public())
+        let now = CurrentClock::now();
+        let whitelist_rules = self.whitelist_rules.read().await;
+        let mut allowing_rules = whitelist_rules.values().filter(|rule| rule.allows(info_hash, now));
+
+        // The torrent is allowed until the last of the allowing rules expires.
+        // Without allowing rules, it's not allowed at all.
+        allowing_rules.try_fold(DurationSinceUnixEpoch::ZERO, |valid_until, rule| {
+            rule.valid_until.map(|rule_valid_until| valid_until.max(rule_valid_until))
+        })
+    }
+
+    /// It checks the announce policy of the torrent for the peer announcing
+    /// itself:
+    ///
+    /// - `seed-only` torrents reject leechers.
+    /// - `frozen` torrents reject peers that are not in the swarm yet.
+    ///
+    /// Peers leaving the swarm (`stopped` event) are always allowed.
+    ///
+    /// # Context: Authorization
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the torrent policy does not allow the peer to
+    /// announce itself.
+    pub async fn authorize_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) -> Result<(), Error> {
+        let Some(policy) = self.get_torrent_policy(info_hash).await else {
+            return Ok(());
+        };
+
+        if peer.event == AnnounceEvent::Stopped {
+            return Ok(());
         }
 
-        fn private_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_private())
+        match policy {
+            TorrentPolicy::SeedOnly if !peer.is_seeder() => Err(Error::TorrentSeedOnly {
+                info_hash: *info_hash,
+                location: Location::caller(),
+            }),
+            TorrentPolicy::Frozen if !self.is_peer_in_swarm(info_hash, peer) => Err(Error::TorrentFrozen {
+                info_hash: *info_hash,
+                location: Location::caller(),
+            }),
+            TorrentPolicy::SeedOnly | TorrentPolicy::Frozen => Ok(()),
         }
+    }
 
-        fn whitelisted_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_listed())
+    fn is_peer_in_swarm(&self, info_hash: &InfoHash, peer: &peer::Peer) -> bool {
+        self.torrents
+            .get(info_hash)
+            .is_some_and(|entry| entry.get_peer(&peer.peer_id).is_some())
+    }
+
+    /// It sets the announce policy for a torrent.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to persist the policy.
+    pub async fn set_torrent_policy(&self, info_hash: &InfoHash, policy: TorrentPolicy) -> Result<(), databases::error::Error> {
+        self.database.save_torrent_policy(info_hash, policy)?;
+        self.policies.write().await.insert(*info_hash, policy);
+        Ok(())
+    }
+
+    /// It removes the announce policy for a torrent.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the persisted policy.
+    pub async fn remove_torrent_policy(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        self.database.remove_torrent_policy(info_hash)?;
+        self.policies.write().await.remove(info_hash);
+        Ok(())
+    }
+
+    /// It returns the announce policy for a torrent, if any.
+    ///
+    /// # Context: Torrent Policies
+    pub async fn get_torrent_policy(&self, info_hash: &InfoHash) -> Option<TorrentPolicy> {
+        self.policies.read().await.get(info_hash).copied()
+    }
+
+    /// It returns all the torrents with an announce policy.
+    ///
+    /// # Context: Torrent Policies
+    pub async fn get_torrent_policies(&self) -> Vec<(InfoHash, TorrentPolicy)> {
+        let mut policies: Vec<(InfoHash, TorrentPolicy)> = self
+            .policies
+            .read()
+            .await
+            .iter()
+            .map(|(info_hash, policy)| (*info_hash, *policy))
+            .collect();
+
+        policies.sort_by_key(|(info_hash, _)| *info_hash);
+
+        policies
+    }
+
+    /// It loads the torrent policies from the database.
+    ///
+    /// # Context: Torrent Policies
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the torrent policies from the database.
+    pub async fn load_torrent_policies_from_database(&self) -> Result<(), databases::error::Error> {
+        let policies_from_database = self.database.load_torrent_policies()?;
+        let mut policies = self.policies.write().await;
+
+        policies.clear();
+        policies.extend(policies_from_database);
+
+        Ok(())
+    }
+
+    /// It adds a static seed peer to a torrent. The peer is included in the
+    /// `announce` responses until it's removed.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to persist the static peer.
+    pub async fn add_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), databases::error::Error> {
+        self.database.add_static_peer(info_hash, peer_addr)?;
+
+        let mut static_peers = self.static_peers.write().await;
+
+        let peer_addrs = static_peers.entry(*info_hash).or_default();
+        peer_addrs.insert(*peer_addr);
+
+        self.set_static_peers_in_swarm(info_hash, peer_addrs);
+
+        Ok(())
+    }
+
+    /// It removes a static seed peer from a torrent.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the persisted static peer.
+    pub async fn remove_static_peer(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Result<(), databases::error::Error> {
+        self.database.remove_static_peer(info_hash, peer_addr)?;
+
+        let mut static_peers = self.static_peers.write().await;
+
+        if let Some(peer_addrs) = static_peers.get_mut(info_hash) {
+            peer_addrs.remove(peer_addr);
+
+            self.set_static_peers_in_swarm(info_hash, peer_addrs);
+
+            if peer_addrs.is_empty() {
+                static_peers.remove(info_hash);
+            }
         }
 
-        pub fn tracker_persisting_torrents_in_database() -> Tracker {
-            let mut configuration = configuration::ephemeral();
-            configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
-            tracker_factory(&configuration)
+        Ok(())
+    }
+
+    /// It returns all the static seed peers, sorted by infohash.
+    ///
+    /// # Context: Static Peers
+    pub async fn get_static_peers(&self) -> Vec<(InfoHash, Vec<SocketAddr>)> {
+        let mut static_peers: Vec<(InfoHash, Vec<SocketAddr>)> = self
+            .static_peers
+            .read()
+            .await
+            .iter()
+            .map(|(info_hash, peer_addrs)| (*info_hash, peer_addrs.iter().copied().collect()))
+            .collect();
+
+        static_peers.sort_by_key(|(info_hash, _)| *info_hash);
+
+        static_peers
+    }
+
+    /// It loads the static seed peers from the database and adds them to the
+    /// swarms.
+    ///
+    /// # Context: Static Peers
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the static peers from the database.
+    pub async fn load_static_peers_from_database(&self) -> Result<(), databases::error::Error> {
+        let static_peers_from_database = self.database.load_static_peers()?;
+        let mut static_peers = self.static_peers.write().await;
+
+        for (info_hash, _) in static_peers.drain() {
+            self.torrents.set_static_peers(&info_hash, &[]);
         }
 
-        fn sample_info_hash() -> InfoHash {
-            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+        for (info_hash, peer_addr) in static_peers_from_database {
+            static_peers.entry(info_hash).or_default().insert(peer_addr);
         }
 
-        // The client peer IP
-        fn peer_ip() -> IpAddr {
-            IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap())
+        for (info_hash, peer_addrs) in static_peers.iter() {
+            self.set_static_peers_in_swarm(info_hash, peer_addrs);
         }
 
-        /// Sample peer whose state is not relevant for the tests
-        fn sample_peer() -> Peer {
-            complete_peer()
+        Ok(())
+    }
+
+    fn set_static_peers_in_swarm(&self, info_hash: &InfoHash, peer_addrs: &std::collections::BTreeSet<SocketAddr>) {
+        let peers: Vec<peer::Peer> = peer_addrs.iter().map(torrent::static_peers::to_peer).collect();
+
+        self.torrents.set_static_peers(info_hash, &peers);
+    }
+
+    /// It adds a torrent to the whitelist.
+    /// Adding torrents is not relevant to public trackers.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the `info_hash` into the whitelist database.
+    pub async fn add_torrent_to_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        self.add_torrent_to_database_whitelist(info_hash)?;
+        self.add_torrent_to_memory_whitelist(info_hash).await;
+        Ok(())
+    }
+
+    /// It adds a torrent to the whitelist if it has not been whitelisted previously
+    fn add_torrent_to_database_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        let is_whitelisted = self.database.is_info_hash_whitelisted(*info_hash)?;
+
+        if is_whitelisted {
+            return Ok(());
         }
 
-        /// Sample peer when for tests that need more than one peer
-        fn sample_peer_1() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000001"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0),
-                event: AnnounceEvent::Completed,
-            }
+        self.database.add_info_hash_to_whitelist(*info_hash)?;
+
+        Ok(())
+    }
+
+    pub async fn add_torrent_to_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
+        let added = self.whitelist.write().await.insert(*info_hash);
+
+        self.removed_from_whitelist.write().await.remove(info_hash);
+
+        self.forget_rejection(info_hash);
+
+        added
+    }
+
+    /// It removes a torrent from the whitelist.
+    /// Removing torrents is not relevant to public trackers.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the `info_hash` from the whitelist database.
+    pub async fn remove_torrent_from_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        self.remove_torrent_from_database_whitelist(info_hash)?;
+        self.remove_torrent_from_memory_whitelist(info_hash).await;
+        Ok(())
+    }
+
+    /// It removes a torrent from the whitelist in the database.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the `info_hash` from the whitelist database.
+    pub fn remove_torrent_from_database_whitelist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        let is_whitelisted = self.database.is_info_hash_whitelisted(*info_hash)?;
+
+        if !is_whitelisted {
+            return Ok(());
         }
 
-        /// Sample peer when for tests that need more than one peer
-        fn sample_peer_2() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000002"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8082),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0),
-                event: AnnounceEvent::Completed,
-            }
+        self.database.remove_info_hash_from_whitelist(*info_hash)?;
+
+        Ok(())
+    }
+
+    /// It removes a torrent from the whitelist in memory.
+    ///
+    /// When the tracker is listed, the torrent is rejected with a specific
+    /// [`TorrentRemovedFromWhitelist`](Error::TorrentRemovedFromWhitelist)
+    /// error from now on, and an [`events::Event::TorrentUnwhitelisted`] event
+    /// is published, so the torrent entry is removed from the repository.
+    ///
+    /// # Context: Whitelist
+    pub async fn remove_torrent_from_memory_whitelist(&self, info_hash: &InfoHash) -> bool {
+        let removed = self.whitelist.write().await.remove(info_hash);
+
+        if let Some(cache) = &self.authorization_cache {
+            cache.revoke_info_hash(info_hash);
         }
 
-        fn seeder() -> Peer {
-            complete_peer()
+        if removed && self.is_listed() {
+            self.removed_from_whitelist.write().await.insert(*info_hash);
+
+            self.event_bus
+                .publish(events::Event::TorrentUnwhitelisted { info_hash: *info_hash });
         }
 
-        fn leecher() -> Peer {
-            incomplete_peer()
+        removed
+    }
+
+    /// It checks if a torrent was removed from the whitelist, and not added
+    /// again.
+    ///
+    /// # Context: Whitelist
+    pub async fn is_info_hash_removed_from_whitelist(&self, info_hash: &InfoHash) -> bool {
+        self.removed_from_whitelist.read().await.contains(info_hash)
+    }
+
+    /// It rejects the `scrape` requests for torrents that were removed from
+    /// the whitelist. The other torrents that are not whitelisted are scraped
+    /// with zeroed metadata.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`TorrentRemovedFromWhitelist`](Error::TorrentRemovedFromWhitelist)
+    /// error with the first torrent that was removed from the whitelist and is
+    /// not allowed again.
+    pub async fn check_scrape_of_removed_torrents(&self, info_hashes: &[InfoHash]) -> Result<(), Error> {
+        if !self.is_listed() {
+            return Ok(());
         }
 
-        fn started_peer() -> Peer {
-            incomplete_peer()
+        for info_hash in info_hashes {
+            if self.is_info_hash_removed_from_whitelist(info_hash).await {
+                self.authorize(info_hash).await?;
+            }
         }
 
-        fn completed_peer() -> Peer {
-            complete_peer()
+        Ok(())
+    }
+
+    /// It removes the entry of a torrent removed from the whitelist from the
+    /// torrents repository, unless it's allowed again.
+    ///
+    /// # Context: Whitelist
+    pub async fn remove_unwhitelisted_torrent(&self, info_hash: &InfoHash) -> bool {
+        if self.authorize(info_hash).await.is_ok() {
+            return false;
         }
 
-        /// A peer that counts as `complete` is swarm metadata
-        /// IMPORTANT!: it only counts if the it has been announce at least once before
-        /// announcing the `AnnounceEvent::Completed` event.
-        fn complete_peer() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000000"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0), // No bytes left to download
-                event: AnnounceEvent::Completed,
+        let removed = self.torrents.remove(info_hash).is_some();
+
+        if removed {
+            self.event_bus
+                .publish(events::Event::TorrentRemoved { info_hash: *info_hash });
+        }
+
+        removed
+    }
+
+    /// It checks if a torrent is whitelisted.
+    ///
+    /// # Context: Whitelist
+    pub async fn is_info_hash_whitelisted(&self, info_hash: &InfoHash) -> bool {
+        self.whitelist.read().await.contains(info_hash)
+    }
+
+    /// It checks if each one of the torrents is whitelisted, taking the
+    /// whitelist lock only once. The results are in the same order as the
+    /// infohashes.
+    ///
+    /// # Context: Whitelist
+    pub async fn are_info_hashes_whitelisted(&self, info_hashes: &[InfoHash]) -> Vec<bool> {
+        let whitelist = self.whitelist.read().await;
+
+        info_hashes.iter().map(|info_hash| whitelist.contains(info_hash)).collect()
+    }
+
+    /// It checks if a torrent is allowed by any of the whitelist rules that
+    /// have not expired.
+    ///
+    /// # Context: Whitelist
+    pub async fn is_info_hash_allowed_by_whitelist_rules(&self, info_hash: &InfoHash) -> bool {
+        let now = CurrentClock::now();
+
+        self.whitelist_rules
+            .read()
+            .await
+            .values()
+            .any(|rule| rule.allows(info_hash, now))
+    }
+
+    /// It adds a whitelist rule. It returns the id of the new rule.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to persist the rule.
+    pub async fn add_whitelist_rule(&self, rule: Rule) -> Result<u64, databases::error::Error> {
+        let id = self.database.add_whitelist_rule(&rule)?;
+        self.whitelist_rules.write().await.insert(id, rule);
+
+        // We don't know which rejected torrents the rule allows.
+        if let Some(cache) = &self.negative_cache {
+            cache.clear_not_authorized();
+        }
+
+        Ok(id)
+    }
+
+    /// It removes a whitelist rule. It returns the removed rule, if it
+    /// existed.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the persisted rule.
+    pub async fn remove_whitelist_rule(&self, id: u64) -> Result<Option<Rule>, databases::error::Error> {
+        self.database.remove_whitelist_rule(id)?;
+
+        let removed = self.whitelist_rules.write().await.remove(&id);
+
+        // We don't know which cached decisions depended on the rule.
+        if let (Some(_), Some(cache)) = (&removed, &self.authorization_cache) {
+            cache.clear();
+        }
+
+        Ok(removed)
+    }
+
+    /// It returns the whitelisted torrents, sorted by infohash.
+    ///
+    /// # Context: Whitelist
+    pub async fn get_whitelist(&self) -> Vec<InfoHash> {
+        let mut whitelist: Vec<InfoHash> = self.whitelist.read().await.iter().copied().collect();
+
+        whitelist.sort();
+
+        whitelist
+    }
+
+    /// It returns the whitelist rules, including the expired ones, sorted by
+    /// id.
+    ///
+    /// # Context: Whitelist
+    pub async fn get_whitelist_rules(&self) -> Vec<(u64, Rule)> {
+        self.whitelist_rules
+            .read()
+            .await
+            .iter()
+            .map(|(id, rule)| (*id, rule.clone()))
+            .collect()
+    }
+
+    /// It loads the whitelist and the whitelist rules from the database.
+    ///
+    /// # Context: Whitelist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the list whitelisted `info_hash`s
+    /// or the whitelist rules from the database.
+    pub async fn load_whitelist_from_database(&self) -> Result<(), databases::error::Error> {
+        let whitelisted_torrents_from_database = self.database.load_whitelist()?;
+        let whitelist_rules_from_database = self.database.load_whitelist_rules()?;
+        let mut whitelist = self.whitelist.write().await;
+        let mut whitelist_rules = self.whitelist_rules.write().await;
+
+        whitelist.clear();
+        whitelist_rules.clear();
+        whitelist_rules.extend(whitelist_rules_from_database);
+
+        if let Some(cache) = &self.authorization_cache {
+            cache.clear();
+        }
+
+        if let Some(cache) = &self.negative_cache {
+            cache.clear_not_authorized();
+        }
+
+        for info_hash in whitelisted_torrents_from_database {
+            let _: bool = whitelist.insert(info_hash);
+        }
+
+        self.removed_from_whitelist
+            .write()
+            .await
+            .retain(|info_hash| !whitelist.contains(info_hash));
+
+        Ok(())
+    }
+
+    /// It return the `Tracker` [`statistics::Metrics`].
+    ///
+    /// # Context: Statistics
+    pub async fn get_stats(&self) -> tokio::sync::RwLockReadGuard<'_, statistics::Metrics> {
+        self.stats_repository.get_stats().await
+    }
+
+    /// It allows to send a statistic events which eventually will be used to update [`statistics::Metrics`].
+    ///
+    /// # Context: Statistics
+    pub async fn send_stats_event(&self, event: statistics::Event) -> Option<Result<(), SendError<statistics::Event>>> {
+        match &self.stats_event_sender {
+            None => None,
+            Some(stats_event_sender) => stats_event_sender.send_event(event).await,
+        }
+    }
+
+    /// It adds the missing entries of the declarative seed to the database.
+    /// It does not load them into memory.
+    ///
+    /// # Context: Persistence
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the seed contains an invalid value or the
+    /// database can't be read or written.
+    pub fn apply_seed(&self, seed: &torrust_tracker_configuration::Seed) -> Result<seed::Applied, seed::Error> {
+        seed::apply(&**self.database, seed)
+    }
+
+    /// It drops the database tables.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to drop tables.
+    pub fn drop_database_tables(&self) -> Result<(), databases::error::Error> {
+        // todo: this is only used for testing. WE have to pass the database
+        // reference directly to the tests instead of via the tracker.
+        self.database.drop_database_tables()
+    }
+}
+
+/// IPv4-mapped IPv6 addresses (from dual-stack sockets) are IPv4 addresses.
+fn is_ipv4(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(_) => true,
+        IpAddr::V6(ip) => ip.to_ipv4_mapped().is_some(),
+    }
+}
+
+/// The number of persisted torrents, or `0` if they can't be counted. The
+/// count is only used to report the progress of the import, so a failure is
+/// not fatal.
+fn count_persistent_torrents(database: &dyn Database) -> u64 {
+    database.count_persistent_torrents().unwrap_or_else(|err| {
+        tracing::warn!(%err, "Failed to count the persisted torrents");
+        0
+    })
+}
+
+#[must_use]
+fn assign_ip_address_to_peer(remote_client_ip: &IpAddr, tracker_external_ip: Option<IpAddr>) -> IpAddr {
+    if let Some(host_ip) = tracker_external_ip.filter(|_| remote_client_ip.is_loopback()) {
+        host_ip
+    } else {
+        *remote_client_ip
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_tracker {
+
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use std::str::FromStr;
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+        use torrust_tracker_configuration::TORRENT_PEERS_LIMIT;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::DurationSinceUnixEpoch;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::peer::Peer;
+        use crate::core::services::tracker_factory;
+        use crate::core::{TorrentsMetrics, Tracker};
+        use crate::shared::bit_torrent::info_hash::fixture::gen_seeded_infohash;
+
+        fn public_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_public())
+        }
+
+        fn private_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_private())
+        }
+
+        fn hybrid_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_hybrid())
+        }
+
+        fn whitelisted_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_listed())
+        }
+
+        pub fn tracker_persisting_torrents_in_database() -> Tracker {
+            let mut configuration = configuration::ephemeral();
+            configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
+            tracker_factory(&configuration)
+        }
+
+        fn sample_info_hash() -> InfoHash {
+            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+        }
+
+        // The client peer IP
+        fn peer_ip() -> IpAddr {
+            IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap())
+        }
+
+        /// Sample peer whose state is not relevant for the tests
+        fn sample_peer() -> Peer {
+            complete_peer()
+        }
+
+        /// Sample peer when for tests that need more than one peer
+        fn sample_peer_1() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000001"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0),
+                event: AnnounceEvent::Completed,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
+            }
+        }
+
+        /// Sample peer when for tests that need more than one peer
+        fn sample_peer_2() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000002"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8082),
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0),
+                event: AnnounceEvent::Completed,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
+            }
+        }
+
+        fn seeder() -> Peer {
+            complete_peer()
+        }
+
+        fn leecher() -> Peer {
+            incomplete_peer()
+        }
+
+        fn started_peer() -> Peer {
+            incomplete_peer()
+        }
+
+        fn completed_peer() -> Peer {
+            complete_peer()
+        }
+
+        /// A peer that counts as `complete` is swarm metadata
+        /// IMPORTANT!: it only counts if the it has been announce at least once before
+        /// announcing the `AnnounceEvent::Completed` event.
+        fn complete_peer() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000000"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0), // No bytes left to download
+                event: AnnounceEvent::Completed,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
+            }
+        }
+
+        /// A peer that counts as `incomplete` is swarm metadata
+        fn incomplete_peer() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000000"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(1000), // Still bytes to download
+                event: AnnounceEvent::Started,
+                partial_seed: false,
+                corrupt: NumberOfBytes::new(0),
+                redundant: NumberOfBytes::new(0),
+            }
+        }
+
+        #[tokio::test]
+        async fn should_collect_torrent_metrics() {
+            let tracker = public_tracker();
+
+            let torrents_metrics = tracker.get_torrents_metrics();
+
+            assert_eq!(
+                torrents_metrics,
+                TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 0,
+                    torrents: 0
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_peers_for_a_given_torrent() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+            let peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+            let peers = tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers, vec![Arc::new(peer)]);
+        }
+
+        /// It generates a peer id from a number where the number is the last
+        /// part of the peer ID. For example, for `12` it returns
+        /// `-qB00000000000000012`.
+        fn numeric_peer_id(two_digits_value: i32) -> PeerId {
+            // Format idx as a string with leading zeros, ensuring it has exactly 2 digits
+            let idx_str = format!("{two_digits_value:02}");
+
+            // Create the base part of the peer ID.
+            let base = b"-qB00000000000000000";
+
+            // Concatenate the base with idx bytes, ensuring the total length is 20 bytes.
+            let mut peer_id_bytes = [0u8; 20];
+            peer_id_bytes[..base.len()].copy_from_slice(base);
+            peer_id_bytes[base.len() - idx_str.len()..].copy_from_slice(idx_str.as_bytes());
+
+            PeerId(peer_id_bytes)
+        }
+
+        #[tokio::test]
+        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+
+            for idx in 1..=75 {
+                let peer = Peer {
+                    peer_id: numeric_peer_id(idx),
+                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                    uploaded: NumberOfBytes::new(0),
+                    downloaded: NumberOfBytes::new(0),
+                    left: NumberOfBytes::new(0), // No bytes left to download
+                    event: AnnounceEvent::Completed,
+                    partial_seed: false,
+                    corrupt: NumberOfBytes::new(0),
+                    redundant: NumberOfBytes::new(0),
+                };
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            }
+
+            let peers = tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers.len(), 74);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_peers_for_a_given_torrent_excluding_a_given_peer() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+            let peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+            let peers = tracker.get_peers_for(&info_hash, &peer, TORRENT_PEERS_LIMIT);
+
+            assert_eq!(peers, vec![]);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent_when_it_filters_out_a_given_peer() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+
+            let excluded_peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &excluded_peer);
+
+            // Add 74 peers
+            for idx in 2..=75 {
+                let peer = Peer {
+                    peer_id: numeric_peer_id(idx),
+                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                    uploaded: NumberOfBytes::new(0),
+                    downloaded: NumberOfBytes::new(0),
+                    left: NumberOfBytes::new(0), // No bytes left to download
+                    event: AnnounceEvent::Completed,
+                    partial_seed: false,
+                    corrupt: NumberOfBytes::new(0),
+                    redundant: NumberOfBytes::new(0),
+                };
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            }
+
+            let peers = tracker.get_peers_for(&info_hash, &excluded_peer, TORRENT_PEERS_LIMIT);
+
+            assert_eq!(peers.len(), 74);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_torrent_metrics() {
+            let tracker = public_tracker();
+
+            tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher());
+
+            let torrent_metrics = tracker.get_torrents_metrics();
+
+            assert_eq!(
+                torrent_metrics,
+                TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 1,
+                    torrents: 1,
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_get_many_the_torrent_metrics() {
+            let tracker = public_tracker();
+
+            let start_time = std::time::Instant::now();
+            for i in 0..1_000_000 {
+                tracker.upsert_peer_and_get_stats(&gen_seeded_infohash(&i), &leecher());
+            }
+            let result_a = start_time.elapsed();
+
+            let start_time = std::time::Instant::now();
+            let torrent_metrics = tracker.get_torrents_metrics();
+            let result_b = start_time.elapsed();
+
+            assert_eq!(
+                (torrent_metrics),
+                (TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 1_000_000,
+                    torrents: 1_000_000,
+                }),
+                "{result_a:?} {result_b:?}"
+            );
+        }
+
+        mod for_all_config_modes {
+
+            mod handling_an_announce_request {
+
+                use std::sync::Arc;
+
+                use crate::core::tests::the_tracker::{
+                    peer_ip, public_tracker, sample_info_hash, sample_peer, sample_peer_1, sample_peer_2,
+                };
+                use crate::core::PeersWanted;
+
+                mod should_assign_the_ip_to_the_peer {
+
+                    use std::net::{IpAddr, Ipv4Addr};
+
+                    use crate::core::assign_ip_address_to_peer;
+
+                    #[test]
+                    fn using_the_source_ip_instead_of_the_ip_in_the_announce_request() {
+                        let remote_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+
+                        let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                        assert_eq!(peer_ip, remote_ip);
+                    }
+
+                    mod and_when_the_client_ip_is_a_ipv4_loopback_ip {
+
+                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+                        use std::str::FromStr;
+
+                        use crate::core::assign_ip_address_to_peer;
+
+                        #[test]
+                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                            assert_eq!(peer_ip, remote_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_tracker_ip_in_tracker_configuration_if_it_is_defined() {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv6_ip(
+                        ) {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let tracker_external_ip =
+                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+                    }
+
+                    mod and_when_client_ip_is_a_ipv6_loopback_ip {
+
+                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+                        use std::str::FromStr;
+
+                        use crate::core::assign_ip_address_to_peer;
+
+                        #[test]
+                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                            assert_eq!(peer_ip, remote_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_tracker_configuration_if_it_is_defined() {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let tracker_external_ip =
+                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv4_ip(
+                        ) {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+                    }
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_announce_data_with_an_empty_peer_list_when_it_is_the_first_announced_peer() {
+                    let tracker = public_tracker();
+
+                    let mut peer = sample_peer();
+
+                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert_eq!(announce_data.peers, vec![]);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_announce_data_with_the_previously_announced_peers() {
+                    let tracker = public_tracker();
+
+                    let mut previously_announced_peer = sample_peer_1();
+                    tracker.announce(
+                        &sample_info_hash(),
+                        &mut previously_announced_peer,
+                        &peer_ip(),
+                        &PeersWanted::All,
+                    );
+
+                    let mut peer = sample_peer_2();
+                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert_eq!(announce_data.peers, vec![Arc::new(previously_announced_peer)]);
+                }
+
+                mod it_should_update_the_swarm_stats_for_the_torrent {
+
+                    use crate::core::tests::the_tracker::{
+                        completed_peer, leecher, peer_ip, public_tracker, sample_info_hash, seeder, started_peer,
+                    };
+                    use crate::core::PeersWanted;
+
+                    #[tokio::test]
+                    async fn when_the_peer_is_a_seeder() {
+                        let tracker = public_tracker();
+
+                        let mut peer = seeder();
+
+                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.complete, 1);
+                    }
+
+                    #[tokio::test]
+                    async fn when_the_peer_is_a_leecher() {
+                        let tracker = public_tracker();
+
+                        let mut peer = leecher();
+
+                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.incomplete, 1);
+                    }
+
+                    #[tokio::test]
+                    async fn when_the_peer_is_a_partial_seed() {
+                        let tracker = public_tracker();
+
+                        let mut peer = leecher();
+                        peer.partial_seed = true;
+
+                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                        // Partial seeds are also leechers
+                        assert_eq!(announce_data.stats.incomplete, 1);
+                        assert_eq!(announce_data.stats.partial_seeds, 1);
+                    }
+
+                    #[tokio::test]
+                    async fn when_a_previously_announced_started_peer_has_completed_downloading() {
+                        let tracker = public_tracker();
+
+                        // We have to announce with "started" event because peer does not count if peer was not previously known
+                        let mut started_peer = started_peer();
+                        tracker.announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All);
+
+                        let mut completed_peer = completed_peer();
+                        let announce_data =
+                            tracker.announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.downloaded, 1);
+                    }
+                }
+            }
+
+            mod handling_a_scrape_request {
+
+                use std::net::{IpAddr, Ipv4Addr};
+
+                use torrust_tracker_primitives::info_hash::InfoHash;
+
+                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
+                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
+
+                #[tokio::test]
+                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
+                ) {
+                    let tracker = public_tracker();
+
+                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
+
+                    let scrape_data = tracker.scrape(&info_hashes).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_only_one_file_for_a_duplicate_info_hash() {
+                    let tracker = public_tracker();
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+                    let info_hashes = vec![info_hash, info_hash];
+
+                    let scrape_data = tracker.scrape(&info_hashes).await;
+
+                    assert_eq!(scrape_data.files.len(), 1);
+                    assert_eq!(scrape_data.duplicates(&info_hashes), 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
+                    let tracker = public_tracker();
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    // Announce a "complete" peer for the torrent
+                    let mut complete_peer = complete_peer();
+                    tracker.announce(
+                        &info_hash,
+                        &mut complete_peer,
+                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
+                        &PeersWanted::All,
+                    );
+
+                    // Announce an "incomplete" peer for the torrent
+                    let mut incomplete_peer = incomplete_peer();
+                    tracker.announce(
+                        &info_hash,
+                        &mut incomplete_peer,
+                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
+                        &PeersWanted::All,
+                    );
+
+                    // Scrape
+                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+
+                    // The expected swarm metadata for the file
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file(
+                        &info_hash,
+                        SwarmMetadata {
+                            complete: 0, // the "complete" peer does not count because it was not previously known
+                            downloaded: 0,
+                            incomplete: 1, // the "incomplete" peer we have just announced
+                            partial_seeds: 0,
+                        },
+                    );
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_allow_scraping_for_multiple_torrents() {
+                    let tracker = public_tracker();
+
+                    let info_hashes = vec![
+                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
+                    ];
+
+                    let scrape_data = tracker.scrape(&info_hashes).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+            }
+        }
+
+        mod configured_as_whitelisted {
+
+            mod handling_authorization {
+                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+
+                #[tokio::test]
+                async fn it_should_authorize_the_announce_and_scrape_actions_on_whitelisted_torrents() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
+
+                    let result = tracker.add_torrent_to_whitelist(&info_hash).await;
+                    assert!(result.is_ok());
+
+                    let result = tracker.authorize(&info_hash).await;
+                    assert!(result.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_the_announce_and_scrape_actions_on_not_whitelisted_torrents() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
+
+                    let result = tracker.authorize(&info_hash).await;
+                    assert!(result.is_err());
+                }
+            }
+
+            mod handling_the_torrent_whitelist {
+                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+
+                #[tokio::test]
+                async fn it_should_add_a_torrent_to_the_whitelist() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
+
+                    tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+                    assert!(tracker.is_info_hash_whitelisted(&info_hash).await);
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_a_torrent_from_the_whitelist() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = sample_info_hash();
+
+                    tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+                    tracker.remove_torrent_from_whitelist(&info_hash).await.unwrap();
+
+                    assert!(!tracker.is_info_hash_whitelisted(&info_hash).await);
+                }
+
+                #[tokio::test]
+                async fn it_should_check_whether_many_torrents_are_whitelisted_at_once() {
+                    let tracker = whitelisted_tracker();
+
+                    let whitelisted = sample_info_hash();
+                    let not_whitelisted = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse().unwrap();
+
+                    tracker.add_torrent_to_whitelist(&whitelisted).await.unwrap();
+
+                    assert_eq!(
+                        tracker
+                            .are_info_hashes_whitelisted(&[not_whitelisted, whitelisted, whitelisted])
+                            .await,
+                        vec![false, true, true]
+                    );
+                }
+
+                mod removing_torrents {
+                    use crate::core::error::Error;
+                    use crate::core::events::Event;
+                    use crate::core::tests::the_tracker::{peer_ip, sample_info_hash, seeder, whitelisted_tracker};
+                    use crate::core::PeersWanted;
+
+                    #[tokio::test]
+                    async fn it_should_reject_the_torrents_removed_from_the_whitelist_with_a_specific_error() {
+                        let tracker = whitelisted_tracker();
+
+                        tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                        tracker.remove_torrent_from_whitelist(&sample_info_hash()).await.unwrap();
+
+                        assert!(matches!(
+                            tracker.authorize(&sample_info_hash()).await,
+                            Err(Error::TorrentRemovedFromWhitelist { .. })
+                        ));
+                        assert!(matches!(
+                            tracker.check_scrape_of_removed_torrents(&[sample_info_hash()]).await,
+                            Err(Error::TorrentRemovedFromWhitelist { .. })
+                        ));
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_allow_the_torrents_added_again_to_the_whitelist() {
+                        let tracker = whitelisted_tracker();
+
+                        tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                        tracker.remove_torrent_from_whitelist(&sample_info_hash()).await.unwrap();
+                        tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+
+                        assert!(tracker.authorize(&sample_info_hash()).await.is_ok());
+                        assert!(tracker.check_scrape_of_removed_torrents(&[sample_info_hash()]).await.is_ok());
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_publish_an_event_to_remove_the_torrent_entry_from_the_repository() {
+                        let tracker = whitelisted_tracker();
+                        let mut receiver = tracker.subscribe();
+
+                        tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                        tracker.announce(&sample_info_hash(), &mut seeder(), &peer_ip(), &PeersWanted::All);
+                        tracker.remove_torrent_from_whitelist(&sample_info_hash()).await.unwrap();
+
+                        let unwhitelisted = Event::TorrentUnwhitelisted {
+                            info_hash: sample_info_hash(),
+                        };
+                        let mut events = std::iter::from_fn(|| receiver.try_recv().ok());
+                        assert!(events.any(|event| event == unwhitelisted));
+
+                        assert!(tracker.remove_unwhitelisted_torrent(&sample_info_hash()).await);
+                        assert!(tracker.get_torrent_peers(&sample_info_hash()).is_empty());
+                    }
+                }
+
+                mod persistence {
+                    use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+
+                    #[tokio::test]
+                    async fn it_should_load_the_whitelist_from_the_database() {
+                        let tracker = whitelisted_tracker();
+
+                        let info_hash = sample_info_hash();
+
+                        tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+
+                        // Remove torrent from the in-memory whitelist
+                        tracker.whitelist.write().await.remove(&info_hash);
+                        assert!(!tracker.is_info_hash_whitelisted(&info_hash).await);
+
+                        tracker.load_whitelist_from_database().await.unwrap();
+
+                        assert!(tracker.is_info_hash_whitelisted(&info_hash).await);
+                    }
+                }
+            }
+
+            mod handling_the_whitelist_rules {
+                use std::time::Duration;
+
+                use torrust_tracker_clock::clock::stopped::Stopped as _;
+                use torrust_tracker_clock::clock::{self, Time};
+
+                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+                use crate::core::whitelist_rules::{Pattern, Rule};
+                use crate::CurrentClock;
+
+                fn rule(pattern: Pattern, valid_until: Option<Duration>) -> Rule {
+                    Rule {
+                        pattern,
+                        valid_until,
+                        comment: Some("Community releases".to_string()),
+                    }
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_the_torrents_allowed_by_an_allow_all_rule() {
+                    let tracker = whitelisted_tracker();
+
+                    tracker.add_whitelist_rule(rule(Pattern::Any, None)).await.unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_the_torrents_matching_a_prefix_rule() {
+                    let tracker = whitelisted_tracker();
+
+                    tracker
+                        .add_whitelist_rule(rule(Pattern::Prefix("3b2455".to_string()), None))
+                        .await
+                        .unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_ok());
+                    assert!(tracker
+                        .authorize(&"99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse().unwrap())
+                        .await
+                        .is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_the_torrents_allowed_only_by_expired_rules() {
+                    clock::Stopped::local_set(&Duration::from_secs(1000));
+
+                    let tracker = whitelisted_tracker();
+
+                    tracker
+                        .add_whitelist_rule(rule(Pattern::Any, CurrentClock::now_add(&Duration::from_secs(60))))
+                        .await
+                        .unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_ok());
+
+                    clock::Stopped::local_add(&Duration::from_secs(60)).unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_a_whitelist_rule() {
+                    let tracker = whitelisted_tracker();
+
+                    let id = tracker.add_whitelist_rule(rule(Pattern::Any, None)).await.unwrap();
+
+                    let removed = tracker.remove_whitelist_rule(id).await.unwrap();
+
+                    assert_eq!(removed, Some(rule(Pattern::Any, None)));
+                    assert!(tracker.get_whitelist_rules().await.is_empty());
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_load_the_whitelist_rules_from_the_database() {
+                    let tracker = whitelisted_tracker();
+
+                    let id = tracker.add_whitelist_rule(rule(Pattern::Any, None)).await.unwrap();
+
+                    // Remove the rule from memory only
+                    tracker.whitelist_rules.write().await.clear();
+
+                    tracker.load_whitelist_from_database().await.unwrap();
+
+                    assert_eq!(tracker.get_whitelist_rules().await, vec![(id, rule(Pattern::Any, None))]);
+                }
+            }
+
+            mod handling_the_database_authorization {
+                use torrust_tracker_configuration::DatabaseAuthorization;
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::error::Error;
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+                use crate::core::Tracker;
+
+                fn whitelisted_tracker_with_database_authorization() -> Tracker {
+                    let mut configuration = configuration::ephemeral_listed();
+                    configuration.core.database_authorization = Some(DatabaseAuthorization::default());
+                    tracker_factory(&configuration)
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_the_torrents_whitelisted_only_in_the_database() {
+                    let tracker = whitelisted_tracker_with_database_authorization();
+
+                    // Add the torrent to the database only
+                    tracker.database.add_info_hash_to_whitelist(sample_info_hash()).unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash()).await.is_ok());
+                    assert!(tracker.is_info_hash_whitelisted(&sample_info_hash()).await);
+                }
+
+                #[tokio::test]
+                async fn it_should_not_look_up_again_the_torrents_found_in_the_database() {
+                    let tracker = whitelisted_tracker_with_database_authorization();
+
+                    tracker.database.add_info_hash_to_whitelist(sample_info_hash()).unwrap();
+
+                    tracker.authorize(&sample_info_hash()).await.unwrap();
+                    tracker.authorize(&sample_info_hash()).await.unwrap();
+
+                    assert_eq!(tracker.get_database_authorization_metrics().unwrap().queries, 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_the_torrents_that_are_not_in_the_database() {
+                    let tracker = whitelisted_tracker_with_database_authorization();
+
+                    assert!(matches!(
+                        tracker.authorize(&sample_info_hash()).await,
+                        Err(Error::TorrentNotWhitelisted { .. })
+                    ));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_have_metrics_when_the_database_authorization_is_disabled() {
+                    let tracker = whitelisted_tracker();
+
+                    assert!(tracker.get_database_authorization_metrics().is_none());
+                }
+            }
+
+            mod handling_an_announce_request {}
+
+            mod handling_an_scrape_request {
+
+                use torrust_tracker_primitives::info_hash::InfoHash;
+                use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+
+                use crate::core::tests::the_tracker::{
+                    complete_peer, incomplete_peer, peer_ip, sample_info_hash, whitelisted_tracker,
+                };
+                use crate::core::{PeersWanted, ScrapeData};
+
+                #[test]
+                fn it_should_be_able_to_build_a_zeroed_scrape_data_for_a_list_of_info_hashes() {
+                    // Zeroed scrape data is used when the authentication for the scrape request fails.
+
+                    let sample_info_hash = sample_info_hash();
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file_with_zeroed_metadata(&sample_info_hash);
+
+                    assert_eq!(ScrapeData::zeroed(&vec![sample_info_hash]), expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_zeroed_swarm_metadata_for_the_requested_file_if_it_is_not_whitelisted() {
+                    let tracker = whitelisted_tracker();
+
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    let mut peer = incomplete_peer();
+                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    // Announce twice to force non zeroed swarm metadata
+                    let mut peer = complete_peer();
+                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+
+                    // The expected zeroed swarm metadata for the file
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file(&info_hash, SwarmMetadata::zeroed());
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+            }
+        }
+
+        mod configured_as_private {
+
+            mod handling_authentication {
+                use std::str::FromStr;
+                use std::time::Duration;
+
+                use crate::core::auth::{self};
+                use crate::core::tests::the_tracker::private_tracker;
+
+                #[tokio::test]
+                async fn it_should_fail_authenticating_a_peer_when_it_uses_an_unregistered_key() {
+                    let tracker = private_tracker();
+
+                    let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+                    let result = tracker.authenticate(&unregistered_key).await;
+
+                    assert!(result.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_fail_verifying_an_unregistered_authentication_key() {
+                    let tracker = private_tracker();
+
+                    let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+                    assert!(tracker.verify_auth_key(&unregistered_key).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_an_authentication_key() {
+                    let tracker = private_tracker();
+
+                    let expiring_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                    let result = tracker.remove_auth_key(&expiring_key.key()).await;
+
+                    assert!(result.is_ok());
+                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_all_the_authentication_keys_from_memory_and_from_the_database() {
+                    let tracker = private_tracker();
+
+                    let expiring_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+                    let permanent_key = tracker.generate_auth_key(None).await.unwrap();
+
+                    let removed = tracker.remove_all_auth_keys().await.unwrap();
+
+                    assert_eq!(removed, 2);
+                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_err());
+                    assert!(tracker.verify_auth_key(&permanent_key.key()).await.is_err());
+
+                    tracker.load_keys_from_database().await.unwrap();
+
+                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_load_authentication_keys_from_the_database() {
+                    let tracker = private_tracker();
+
+                    let expiring_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                    // Remove the newly generated key in memory
+                    tracker.keys.write().await.remove(&expiring_key.key());
+
+                    let result = tracker.load_keys_from_database().await;
+
+                    assert!(result.is_ok());
+                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_ok());
+                }
+
+                mod with_expiring_and {
+
+                    mod randomly_generated_keys {
+                        use std::time::Duration;
+
+                        use torrust_tracker_clock::clock::Time;
+                        use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+
+                        use crate::core::auth::Key;
+                        use crate::core::tests::the_tracker::private_tracker;
+                        use crate::CurrentClock;
+
+                        #[tokio::test]
+                        async fn it_should_generate_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                            assert_eq!(
+                                peer_key.valid_until,
+                                Some(CurrentClock::now_add(&Duration::from_secs(100)).unwrap())
+                            );
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_authenticate_a_peer_with_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                            let result = tracker.authenticate(&peer_key.key()).await;
+
+                            assert!(result.is_ok());
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
+                            let mut tracker = private_tracker();
+
+                            tracker.config.private_mode = Some(PrivateMode {
+                                check_keys_expiration: false,
+                                ..Default::default()
+                            });
+
+                            let past_timestamp = Duration::ZERO;
+
+                            let peer_key = tracker
+                                .add_auth_key(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap(), Some(past_timestamp))
+                                .await
+                                .unwrap();
+
+                            assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
+                        }
+                    }
+
+                    mod pre_generated_keys {
+                        use std::time::Duration;
+
+                        use torrust_tracker_clock::clock::Time;
+                        use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+
+                        use crate::core::auth::Key;
+                        use crate::core::tests::the_tracker::private_tracker;
+                        use crate::core::AddKeyRequest;
+                        use crate::CurrentClock;
+
+                        #[tokio::test]
+                        async fn it_should_add_a_pre_generated_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker
+                                .add_peer_key(AddKeyRequest {
+                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
+                                    opt_seconds_valid: Some(100),
+                                    opt_bound_to: None,
+                                })
+                                .await
+                                .unwrap();
+
+                            assert_eq!(
+                                peer_key.valid_until,
+                                Some(CurrentClock::now_add(&Duration::from_secs(100)).unwrap())
+                            );
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_authenticate_a_peer_with_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker
+                                .add_peer_key(AddKeyRequest {
+                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
+                                    opt_seconds_valid: Some(100),
+                                    opt_bound_to: None,
+                                })
+                                .await
+                                .unwrap();
+
+                            let result = tracker.authenticate(&peer_key.key()).await;
+
+                            assert!(result.is_ok());
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
+                            let mut tracker = private_tracker();
+
+                            tracker.config.private_mode = Some(PrivateMode {
+                                check_keys_expiration: false,
+                                ..Default::default()
+                            });
+
+                            let peer_key = tracker
+                                .add_peer_key(AddKeyRequest {
+                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
+                                    opt_seconds_valid: Some(0),
+                                    opt_bound_to: None,
+                                })
+                                .await
+                                .unwrap();
+
+                            assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
+                        }
+                    }
+                }
+
+                mod with_permanent_and {
+
+                    mod randomly_generated_keys {
+                        use crate::core::tests::the_tracker::private_tracker;
+
+                        #[tokio::test]
+                        async fn it_should_generate_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+
+                            assert_eq!(peer_key.valid_until, None);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_authenticate_a_peer_with_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+
+                            let result = tracker.authenticate(&peer_key.key()).await;
+
+                            assert!(result.is_ok());
+                        }
+                    }
+
+                    mod pre_generated_keys {
+                        use crate::core::auth::Key;
+                        use crate::core::tests::the_tracker::private_tracker;
+                        use crate::core::AddKeyRequest;
+
+                        #[tokio::test]
+                        async fn it_should_add_a_pre_generated_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker
+                                .add_peer_key(AddKeyRequest {
+                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
+                                    opt_seconds_valid: None,
+                                    opt_bound_to: None,
+                                })
+                                .await
+                                .unwrap();
+
+                            assert_eq!(peer_key.valid_until, None);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_authenticate_a_peer_with_the_key() {
+                            let tracker = private_tracker();
+
+                            let peer_key = tracker
+                                .add_peer_key(AddKeyRequest {
+                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
+                                    opt_seconds_valid: None,
+                                    opt_bound_to: None,
+                                })
+                                .await
+                                .unwrap();
+
+                            let result = tracker.authenticate(&peer_key.key()).await;
+
+                            assert!(result.is_ok());
+                        }
+                    }
+                }
+
+                mod with_keys_bound_to_an_ip_range {
+                    use std::net::IpAddr;
+
+                    use crate::core::auth::{self, IpRange};
+                    use crate::core::error::PeerKeyError;
+                    use crate::core::tests::the_tracker::private_tracker;
+                    use crate::core::AddKeyRequest;
+
+                    fn bound_key_request() -> AddKeyRequest {
+                        AddKeyRequest {
+                            opt_key: None,
+                            opt_seconds_valid: None,
+                            opt_bound_to: Some("203.0.113.0/24".to_string()),
+                        }
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_generate_a_key_bound_to_the_ip_range() {
+                        let tracker = private_tracker();
+
+                        let peer_key = tracker.add_peer_key(bound_key_request()).await.unwrap();
+
+                        assert_eq!(peer_key.bound_to, Some("203.0.113.0/24".parse::<IpRange>().unwrap()));
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_accept_the_key_from_an_address_in_the_range() {
+                        let tracker = private_tracker();
+
+                        let peer_key = tracker.add_peer_key(bound_key_request()).await.unwrap();
+
+                        let result = tracker
+                            .verify_key_binding(&peer_key.key(), &"203.0.113.7".parse::<IpAddr>().unwrap())
+                            .await;
+
+                        assert!(result.is_ok());
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_reject_the_key_from_an_address_out_of_the_range() {
+                        let tracker = private_tracker();
+
+                        let peer_key = tracker.add_peer_key(bound_key_request()).await.unwrap();
+
+                        let result = tracker
+                            .verify_key_binding(&peer_key.key(), &"198.51.100.7".parse::<IpAddr>().unwrap())
+                            .await;
+
+                        assert!(matches!(result, Err(auth::Error::KeyBoundToAnotherAddress { .. })));
+                    }
+
+                    #[tokio::test]
+                    async fn it_should_not_add_a_key_with_an_invalid_binding() {
+                        let tracker = private_tracker();
+
+                        let result = tracker
+                            .add_peer_key(AddKeyRequest {
+                                opt_bound_to: Some("203.0.113.0/33".to_string()),
+                                ..bound_key_request()
+                            })
+                            .await;
+
+                        assert!(matches!(result, Err(PeerKeyError::InvalidBinding { .. })));
+                    }
+                }
+            }
+
+            mod handling_an_announce_request {}
+
+            mod handling_an_scrape_request {}
+        }
+
+        mod configured_as_hybrid {
+
+            mod handling_authentication {
+                use std::str::FromStr;
+
+                use crate::core::auth::{self};
+                use crate::core::key_quota::Usage;
+                use crate::core::tests::the_tracker::hybrid_tracker;
+
+                #[tokio::test]
+                async fn it_should_verify_the_keys_provided_by_the_peers() {
+                    let tracker = hybrid_tracker();
+
+                    assert!(!tracker.is_private());
+                    assert!(tracker.verifies_keys());
+                }
+
+                #[tokio::test]
+                async fn it_should_fail_authenticating_a_peer_when_it_uses_an_unregistered_key() {
+                    let tracker = hybrid_tracker();
+
+                    let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+                    assert!(tracker.authenticate(&unregistered_key).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_authenticate_a_peer_with_a_registered_key() {
+                    let tracker = hybrid_tracker();
+
+                    let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+
+                    assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_count_the_usage_of_the_keys() {
+                    let tracker = hybrid_tracker();
+
+                    let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+
+                    tracker.consume_key_announce(&peer_key.key()).unwrap();
+                    tracker.consume_key_peers(&peer_key.key(), 10);
+
+                    assert_eq!(tracker.get_key_usage(&peer_key.key()), Usage { announces: 1, peers: 10 });
+                }
+            }
+        }
+
+        mod configured_as_private_and_whitelisted {
+
+            mod handling_an_announce_request {}
+
+            mod handling_an_scrape_request {}
+        }
+
+        mod handling_torrent_persistence {
+
+            use std::env;
+
+            use aquatic_udp_protocol::AnnounceEvent;
+            use camino::Utf8PathBuf;
+            use torrust_tracker_clock::clock::stopped::Stopped as _;
+            use torrust_tracker_clock::clock::{self};
+            use torrust_tracker_configuration::{AnnounceJournal, Driver};
+            use torrust_tracker_primitives::info_hash::InfoHash;
+            use torrust_tracker_test_helpers::{configuration, random};
+            use torrust_tracker_torrent_repository::entry::EntrySync;
+            use torrust_tracker_torrent_repository::repository::Repository;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{
+                peer_ip, sample_info_hash, sample_peer, tracker_persisting_torrents_in_database,
+            };
+            use crate::core::{databases, PeersWanted};
+
+            #[tokio::test]
+            async fn it_should_persist_the_number_of_completed_peers_for_all_torrents_into_the_database() {
+                let tracker = tracker_persisting_torrents_in_database();
+
+                let info_hash = sample_info_hash();
+
+                let mut peer = sample_peer();
+
+                peer.event = AnnounceEvent::Started;
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                assert_eq!(swarm_stats.downloaded, 0);
+
+                peer.event = AnnounceEvent::Completed;
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                assert_eq!(swarm_stats.downloaded, 1);
+
+                // Remove the newly updated torrent from memory
+                tracker.torrents.remove(&info_hash);
+
+                tracker.load_torrents_from_database().unwrap();
+
+                let torrent_entry = tracker.torrents.get(&info_hash).expect("it should be able to get entry");
+
+                // It persists the number of completed peers.
+                assert_eq!(torrent_entry.get_swarm_metadata().downloaded, 1);
+
+                // It does not persist the peers
+                assert!(torrent_entry.peers_is_empty());
+            }
+
+            #[tokio::test]
+            async fn it_should_not_report_outdated_tables_in_a_database_created_by_the_current_version() {
+                let tracker = tracker_persisting_torrents_in_database();
+
+                assert!(tracker.outdated_database_tables().unwrap().is_empty());
+            }
+
+            #[tokio::test]
+            async fn it_should_report_the_columns_missing_from_the_database_tables() {
+                let tracker = tracker_persisting_torrents_in_database();
+
+                tracker.database.drop_database_tables().unwrap();
+
+                let outdated = tracker.outdated_database_tables().unwrap();
+
+                assert_eq!(outdated.len(), databases::SCHEMA.len());
+                assert_eq!(
+                    outdated[0],
+                    ("whitelist".to_string(), vec!["id".to_string(), "info_hash".to_string()])
+                );
+            }
+
+            #[tokio::test]
+            async fn it_should_keep_the_number_of_completed_peers_in_memory_when_the_tracker_runs_without_a_database() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
+                configuration.core.database.driver = Driver::None;
+                let tracker = tracker_factory(&configuration);
+
+                let info_hash = sample_info_hash();
+
+                let mut peer = sample_peer();
+
+                peer.event = AnnounceEvent::Started;
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+                peer.event = AnnounceEvent::Completed;
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                assert_eq!(swarm_stats.downloaded, 1);
+
+                assert!(tracker.database.load_persistent_torrents().unwrap().is_empty());
+            }
+
+            #[tokio::test]
+            async fn it_should_import_the_persisted_torrents_in_batches() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
+
+                let tracker = tracker_factory(&configuration);
+
+                for i in 0..5u8 {
+                    tracker
+                        .database
+                        .save_persistent_torrent(&InfoHash::from([i; 20]), u32::from(i))
+                        .unwrap();
+                }
+
+                // The persisted torrents are counted when the tracker is created.
+                let tracker = tracker_factory(&configuration);
+
+                tracker.import_torrents_from_database(2).unwrap();
+
+                for i in 0..5u8 {
+                    let torrent_entry = tracker
+                        .torrents
+                        .get(&InfoHash::from([i; 20]))
+                        .expect("it should import the torrent");
+
+                    assert_eq!(torrent_entry.get_swarm_metadata().downloaded, u32::from(i));
+                }
+
+                assert!(!tracker.warm_up().is_in_progress());
+                assert_eq!(tracker.warm_up().imported_torrents(), 5);
+                assert_eq!(tracker.warm_up().expected_torrents(), 5);
+                assert_eq!(tracker.warm_up().progress(), Some(100));
+            }
+
+            #[tokio::test]
+            async fn it_should_rebuild_the_swarms_from_the_announce_journal_after_a_restart() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.announce_journal = Some(AnnounceJournal {
+                    path: Utf8PathBuf::from_path_buf(
+                        env::temp_dir().join(format!("announce_journal_{}.bin", random::string(16))),
+                    )
+                    .unwrap(),
+                    ..Default::default()
+                });
+
+                let info_hash = sample_info_hash();
+                let mut peer = sample_peer();
+
+                clock::Stopped::local_set(&peer.updated);
+
+                let tracker = tracker_factory(&configuration);
+                tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+                drop(tracker);
+
+                let tracker = tracker_factory(&configuration);
+
+                assert_eq!(tracker.replay_announce_journal(), 1);
+
+                let torrent_entry = tracker.torrents.get(&info_hash).expect("it should replay the torrent");
+
+                assert_eq!(torrent_entry.get_peers_len(), 1);
+
+                // The completed download was already counted before the restart
+                assert_eq!(torrent_entry.get_swarm_metadata().downloaded, 0);
+            }
+        }
+
+        mod handling_the_audit_log {
+
+            use std::net::{IpAddr, Ipv4Addr};
+            use std::time::Duration;
+
+            use torrust_tracker_primitives::pagination::Pagination;
+
+            use crate::core::audit;
+            use crate::core::tests::the_tracker::public_tracker;
+
+            fn entry(path: &str) -> audit::Entry {
+                audit::Entry {
+                    time: Duration::from_secs(1_729_069_200),
+                    token_label: "admin".to_string(),
+                    source_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+                    method: "POST".to_string(),
+                    path: path.to_string(),
+                    status: 200,
+                }
+            }
+
+            #[tokio::test]
+            async fn it_should_return_the_audit_log_newest_entries_first() {
+                let tracker = public_tracker();
+
+                tracker.add_audit_log_entry(&entry("/api/v1/keys")).unwrap();
+                tracker.add_audit_log_entry(&entry("/api/v1/keys/reload")).unwrap();
+
+                assert_eq!(
+                    tracker.get_audit_log(&Pagination::default()).unwrap(),
+                    vec![entry("/api/v1/keys/reload"), entry("/api/v1/keys")]
+                );
+            }
+
+            #[tokio::test]
+            async fn it_should_paginate_the_audit_log() {
+                let tracker = public_tracker();
+
+                tracker.add_audit_log_entry(&entry("/api/v1/keys")).unwrap();
+                tracker.add_audit_log_entry(&entry("/api/v1/keys/reload")).unwrap();
+                tracker.add_audit_log_entry(&entry("/api/v1/whitelist/reload")).unwrap();
+
+                assert_eq!(
+                    tracker.get_audit_log(&Pagination::new(1, 1)).unwrap(),
+                    vec![entry("/api/v1/keys/reload")]
+                );
+            }
+        }
+
+        mod handling_domain_events {
+
+            use std::collections::BTreeMap;
+            use std::time::Duration;
+
+            use torrust_tracker_torrent_repository::repository::Repository;
+
+            use crate::core::events::Event;
+            use crate::core::tests::the_tracker::{peer_ip, private_tracker, public_tracker, sample_info_hash, sample_peer};
+            use crate::core::PeersWanted;
+
+            #[tokio::test]
+            async fn it_should_publish_the_torrent_created_and_peer_announced_events_when_the_first_peer_announces() {
+                let tracker = public_tracker();
+                let mut receiver = tracker.subscribe();
+
+                let mut peer = sample_peer();
+
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                assert_eq!(
+                    receiver.try_recv().unwrap(),
+                    Event::TorrentCreated {
+                        info_hash: sample_info_hash()
+                    }
+                );
+                assert_eq!(
+                    receiver.try_recv().unwrap(),
+                    Event::PeerAnnounced {
+                        info_hash: sample_info_hash(),
+                        peer
+                    }
+                );
+                assert!(receiver.try_recv().is_err());
+            }
+
+            #[tokio::test]
+            async fn it_should_not_publish_the_torrent_created_event_when_the_torrent_already_exists() {
+                let tracker = public_tracker();
+
+                let mut peer = sample_peer();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                let mut receiver = tracker.subscribe();
+
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                assert!(matches!(receiver.try_recv().unwrap(), Event::PeerAnnounced { .. }));
+                assert!(receiver.try_recv().is_err());
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_the_torrent_removed_event_when_a_peerless_torrent_is_cleaned_up() {
+                let tracker = public_tracker();
+
+                tracker.torrents.import_persistent(&BTreeMap::from([(sample_info_hash(), 0)]));
+
+                let mut receiver = tracker.subscribe();
+
+                tracker.cleanup_torrents();
+
+                assert_eq!(
+                    receiver.try_recv().unwrap(),
+                    Event::TorrentRemoved {
+                        info_hash: sample_info_hash()
+                    }
+                );
+                assert!(tracker.torrents.get(&sample_info_hash()).is_none());
+            }
+
+            #[tokio::test]
+            async fn it_should_publish_the_key_created_and_key_revoked_events() {
+                let tracker = private_tracker();
+                let mut receiver = tracker.subscribe();
+
+                let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                tracker.remove_auth_key(&peer_key.key()).await.unwrap();
+
+                assert_eq!(receiver.try_recv().unwrap(), Event::KeyCreated { key: peer_key.key() });
+                assert_eq!(receiver.try_recv().unwrap(), Event::KeyRevoked { key: peer_key.key() });
+            }
+        }
+
+        mod handling_announce_batching {
+
+            use torrust_tracker_configuration::AnnounceBatching;
+            use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer_1, sample_peer_2};
+            use crate::core::Tracker;
+
+            fn tracker_with_announce_batching(max_batch_size: usize) -> Tracker {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.announce_batching = Some(AnnounceBatching {
+                    shards: 1,
+                    max_batch_size,
+                    max_delay_ms: 5,
+                });
+                tracker_factory(&configuration)
+            }
+
+            #[tokio::test]
+            async fn it_should_not_apply_the_announce_updates_until_the_batches_are_flushed() {
+                let tracker = tracker_with_announce_batching(10);
+
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+
+                assert_eq!(swarm_stats, SwarmMetadata::zeroed());
+
+                tracker.flush_announce_batches();
+
+                assert_eq!(tracker.get_swarm_metadata(&sample_info_hash()).complete, 1);
             }
-        }
 
-        /// A peer that counts as `incomplete` is swarm metadata
-        fn incomplete_peer() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000000"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(1000), // Still bytes to download
-                event: AnnounceEvent::Started,
+            #[tokio::test]
+            async fn it_should_apply_the_announce_updates_when_the_batch_is_full() {
+                let tracker = tracker_with_announce_batching(2);
+
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_2());
+
+                assert_eq!(swarm_stats.complete, 2);
             }
         }
 
-        #[tokio::test]
-        async fn should_collect_torrent_metrics() {
-            let tracker = public_tracker();
+        mod handling_async_announces {
 
-            let torrents_metrics = tracker.get_torrents_metrics();
+            use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_configuration::AsyncAnnounce;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::peer::Peer;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{peer_ip, sample_info_hash, sample_peer_1, sample_peer_2};
+            use crate::core::{AnnounceData, AnnounceMode, PeersWanted, Requester, Tracker};
+
+            fn tracker_with_async_announces(queue_capacity: usize) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.async_announce = Some(AsyncAnnounce { queue_capacity });
+                tracker_factory(&configuration)
+            }
 
-            assert_eq!(
-                torrents_metrics,
-                TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 0,
-                    torrents: 0
-                }
-            );
-        }
+            fn announce(tracker: &Tracker, peer: &mut Peer, mode: AnnounceMode) -> AnnounceData {
+                tracker.announce_with_mode(
+                    &Requester::Anonymous,
+                    &sample_info_hash(),
+                    peer,
+                    &peer_ip(),
+                    &PeersWanted::All,
+                    mode,
+                )
+            }
 
-        #[tokio::test]
-        async fn it_should_return_the_peers_for_a_given_torrent() {
-            let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_build_the_response_before_applying_the_queued_update() {
+                let tracker = tracker_with_async_announces(10);
 
-            let info_hash = sample_info_hash();
-            let peer = sample_peer();
+                let announce_data = announce(&tracker, &mut sample_peer_1(), AnnounceMode::Asynchronous);
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                assert_eq!(announce_data.stats.complete, 0);
+                assert_eq!(tracker.get_async_announce_metrics().unwrap().queue_depth, 1);
 
-            let peers = tracker.get_torrent_peers(&info_hash);
+                tracker.apply_async_announces();
 
-            assert_eq!(peers, vec![Arc::new(peer)]);
-        }
+                assert_eq!(tracker.get_swarm_metadata(&sample_info_hash()).complete, 1);
+            }
 
-        /// It generates a peer id from a number where the number is the last
-        /// part of the peer ID. For example, for `12` it returns
-        /// `-qB00000000000000012`.
-        fn numeric_peer_id(two_digits_value: i32) -> PeerId {
-            // Format idx as a string with leading zeros, ensuring it has exactly 2 digits
-            let idx_str = format!("{two_digits_value:02}");
+            #[tokio::test]
+            async fn it_should_apply_the_pending_updates_and_the_update_synchronously_when_the_queue_is_full() {
+                let tracker = tracker_with_async_announces(1);
 
-            // Create the base part of the peer ID.
-            let base = b"-qB00000000000000000";
+                let _announce_data = announce(&tracker, &mut sample_peer_1(), AnnounceMode::Asynchronous);
+                let announce_data = announce(&tracker, &mut sample_peer_2(), AnnounceMode::Asynchronous);
 
-            // Concatenate the base with idx bytes, ensuring the total length is 20 bytes.
-            let mut peer_id_bytes = [0u8; 20];
-            peer_id_bytes[..base.len()].copy_from_slice(base);
-            peer_id_bytes[base.len() - idx_str.len()..].copy_from_slice(idx_str.as_bytes());
+                assert_eq!(announce_data.stats.complete, 2);
+                assert_eq!(tracker.get_async_announce_metrics().unwrap().overflows, 1);
+                assert_eq!(tracker.get_async_announce_metrics().unwrap().queue_depth, 0);
+            }
 
-            PeerId(peer_id_bytes)
-        }
+            #[tokio::test]
+            async fn it_should_not_apply_a_queued_update_after_a_newer_one_of_the_same_peer() {
+                let tracker = tracker_with_async_announces(1);
 
-        #[tokio::test]
-        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent() {
-            let tracker = public_tracker();
+                let mut started_peer = sample_peer_1();
+                started_peer.event = AnnounceEvent::Started;
 
-            let info_hash = sample_info_hash();
+                let mut stopped_peer = sample_peer_1();
+                stopped_peer.event = AnnounceEvent::Stopped;
 
-            for idx in 1..=75 {
-                let peer = Peer {
-                    peer_id: numeric_peer_id(idx),
-                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
-                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                    uploaded: NumberOfBytes::new(0),
-                    downloaded: NumberOfBytes::new(0),
-                    left: NumberOfBytes::new(0), // No bytes left to download
-                    event: AnnounceEvent::Completed,
-                };
+                let _announce_data = announce(&tracker, &mut started_peer, AnnounceMode::Asynchronous);
+                let _announce_data = announce(&tracker, &mut stopped_peer, AnnounceMode::Asynchronous);
 
-                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                tracker.apply_async_announces();
+
+                assert_eq!(tracker.get_swarm_metadata(&sample_info_hash()).complete, 0);
+                assert_eq!(tracker.get_swarm_metadata(&sample_info_hash()).incomplete, 0);
             }
 
-            let peers = tracker.get_torrent_peers(&info_hash);
+            #[tokio::test]
+            async fn it_should_apply_the_update_synchronously_in_the_synchronous_mode() {
+                let tracker = tracker_with_async_announces(10);
 
-            assert_eq!(peers.len(), 74);
+                let announce_data = announce(&tracker, &mut sample_peer_1(), AnnounceMode::Synchronous);
+
+                assert_eq!(announce_data.stats.complete, 1);
+                assert_eq!(tracker.get_async_announce_metrics().unwrap().queue_depth, 0);
+            }
         }
 
-        #[tokio::test]
-        async fn it_should_return_the_peers_for_a_given_torrent_excluding_a_given_peer() {
-            let tracker = public_tracker();
+        mod handling_stale_torrents {
 
-            let info_hash = sample_info_hash();
-            let peer = sample_peer();
+            use std::time::Duration;
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            use torrust_tracker_clock::clock;
+            use torrust_tracker_clock::clock::stopped::Stopped as _;
+            use torrust_tracker_primitives::info_hash::InfoHash;
+            use torrust_tracker_test_helpers::configuration;
+            use torrust_tracker_torrent_repository::repository::Repository;
 
-            let peers = tracker.get_peers_for(&info_hash, &peer, TORRENT_PEERS_LIMIT);
+            use crate::core::peer::Peer;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer};
+            use crate::core::Tracker;
 
-            assert_eq!(peers, vec![]);
-        }
+            fn tracker_with_max_torrent_inactivity(max_torrent_inactivity: Option<u32>) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.tracker_policy.remove_peerless_torrents = false;
+                configuration.core.tracker_policy.max_torrent_inactivity = max_torrent_inactivity;
+                tracker_factory(&configuration)
+            }
 
-        #[tokio::test]
-        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent_when_it_filters_out_a_given_peer() {
-            let tracker = public_tracker();
+            fn peer_announced_at(secs: u64) -> Peer {
+                Peer {
+                    updated: Duration::from_secs(secs),
+                    ..sample_peer()
+                }
+            }
 
-            let info_hash = sample_info_hash();
+            #[tokio::test]
+            async fn it_should_remove_the_torrents_without_announces_for_more_than_the_max_torrent_inactivity() {
+                clock::Stopped::local_set(&Duration::from_secs(1000));
 
-            let excluded_peer = sample_peer();
+                let tracker = tracker_with_max_torrent_inactivity(Some(60));
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &excluded_peer);
+                let active_info_hash = InfoHash::from([1; 20]);
 
-            // Add 74 peers
-            for idx in 2..=75 {
-                let peer = Peer {
-                    peer_id: numeric_peer_id(idx),
-                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
-                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                    uploaded: NumberOfBytes::new(0),
-                    downloaded: NumberOfBytes::new(0),
-                    left: NumberOfBytes::new(0), // No bytes left to download
-                    event: AnnounceEvent::Completed,
-                };
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer_announced_at(100));
+                tracker.upsert_peer_and_get_stats(&active_info_hash, &peer_announced_at(990));
 
-                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                tracker.cleanup_torrents();
+
+                clock::Stopped::local_reset();
+
+                assert!(tracker.torrents.get(&sample_info_hash()).is_none());
+                assert!(tracker.torrents.get(&active_info_hash).is_some());
             }
 
-            let peers = tracker.get_peers_for(&info_hash, &excluded_peer, TORRENT_PEERS_LIMIT);
+            #[tokio::test]
+            async fn it_should_not_remove_stale_torrents_when_the_max_torrent_inactivity_is_not_set() {
+                clock::Stopped::local_set(&Duration::from_secs(1000));
 
-            assert_eq!(peers.len(), 74);
-        }
+                let tracker = tracker_with_max_torrent_inactivity(None);
 
-        #[tokio::test]
-        async fn it_should_return_the_torrent_metrics() {
-            let tracker = public_tracker();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &peer_announced_at(100));
 
-            tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher());
+                tracker.cleanup_torrents();
 
-            let torrent_metrics = tracker.get_torrents_metrics();
+                clock::Stopped::local_reset();
 
-            assert_eq!(
-                torrent_metrics,
-                TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 1,
-                    torrents: 1,
-                }
-            );
+                assert!(tracker.torrents.get(&sample_info_hash()).is_some());
+            }
         }
 
-        #[tokio::test]
-        async fn it_should_get_many_the_torrent_metrics() {
-            let tracker = public_tracker();
+        mod handling_the_read_snapshot {
 
-            let start_time = std::time::Instant::now();
-            for i in 0..1_000_000 {
-                tracker.upsert_peer_and_get_stats(&gen_seeded_infohash(&i), &leecher());
+            use torrust_tracker_configuration::ReadSnapshot;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer_1};
+            use crate::core::Tracker;
+
+            fn tracker_with_read_snapshot() -> Tracker {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.read_snapshot = Some(ReadSnapshot::default());
+                tracker_factory(&configuration)
             }
-            let result_a = start_time.elapsed();
 
-            let start_time = std::time::Instant::now();
-            let torrent_metrics = tracker.get_torrents_metrics();
-            let result_b = start_time.elapsed();
+            #[tokio::test]
+            async fn it_should_scrape_the_swarm_metadata_from_the_last_published_snapshot() {
+                let tracker = tracker_with_read_snapshot();
 
-            assert_eq!(
-                (torrent_metrics),
-                (TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 1_000_000,
-                    torrents: 1_000_000,
-                }),
-                "{result_a:?} {result_b:?}"
-            );
-        }
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-        mod for_all_config_modes {
+                let scrape_data = tracker.scrape(&vec![sample_info_hash()]).await;
 
-            mod handling_an_announce_request {
+                assert_eq!(scrape_data.files[&sample_info_hash()].complete, 0);
 
-                use std::sync::Arc;
+                tracker.refresh_read_snapshot();
 
-                use crate::core::tests::the_tracker::{
-                    peer_ip, public_tracker, sample_info_hash, sample_peer, sample_peer_1, sample_peer_2,
-                };
-                use crate::core::PeersWanted;
+                let scrape_data = tracker.scrape(&vec![sample_info_hash()]).await;
 
-                mod should_assign_the_ip_to_the_peer {
+                assert_eq!(scrape_data.files[&sample_info_hash()].complete, 1);
+            }
 
-                    use std::net::{IpAddr, Ipv4Addr};
+            #[tokio::test]
+            async fn it_should_return_the_torrents_metrics_from_the_last_published_snapshot() {
+                let tracker = tracker_with_read_snapshot();
 
-                    use crate::core::assign_ip_address_to_peer;
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-                    #[test]
-                    fn using_the_source_ip_instead_of_the_ip_in_the_announce_request() {
-                        let remote_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+                assert_eq!(tracker.get_torrents_metrics().torrents, 0);
 
-                        let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+                tracker.refresh_read_snapshot();
 
-                        assert_eq!(peer_ip, remote_ip);
-                    }
+                assert_eq!(tracker.get_torrents_metrics().torrents, 1);
+            }
 
-                    mod and_when_the_client_ip_is_a_ipv4_loopback_ip {
+            #[tokio::test]
+            async fn it_should_return_the_swarm_sizes_from_the_last_published_snapshot() {
+                let tracker = tracker_with_read_snapshot();
 
-                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-                        use std::str::FromStr;
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-                        use crate::core::assign_ip_address_to_peer;
+                tracker.refresh_read_snapshot();
 
-                        #[test]
-                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                let buckets = tracker.get_swarm_sizes().unwrap().buckets();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+                assert_eq!(buckets[1].torrents, 1);
+            }
 
-                            assert_eq!(peer_ip, remote_ip);
-                        }
+            #[tokio::test]
+            async fn it_should_return_the_torrent_health_from_the_last_published_snapshot() {
+                let tracker = tracker_with_read_snapshot();
 
-                        #[test]
-                        fn it_should_use_the_external_tracker_ip_in_tracker_configuration_if_it_is_defined() {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+                assert_eq!(tracker.get_torrent_health(&sample_info_hash()), None);
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                tracker.refresh_read_snapshot();
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
+                assert_eq!(tracker.get_torrent_health(&sample_info_hash()).unwrap().score, 92);
+                assert_eq!(tracker.get_health_scores().unwrap().buckets()[4].torrents, 1);
+            }
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv6_ip(
-                        ) {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+            #[tokio::test]
+            async fn it_should_keep_returning_the_live_swarm_metadata_in_the_announce_responses() {
+                let tracker = tracker_with_read_snapshot();
 
-                            let tracker_external_ip =
-                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+                let swarm_stats = tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                assert_eq!(swarm_stats.complete, 1);
+            }
+        }
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
-                    }
+        mod handling_the_consistency_check {
 
-                    mod and_when_client_ip_is_a_ipv6_loopback_ip {
+            use torrust_tracker_configuration::ConsistencyCheck;
+            use torrust_tracker_test_helpers::configuration;
 
-                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-                        use std::str::FromStr;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer_1};
+            use crate::core::Tracker;
 
-                        use crate::core::assign_ip_address_to_peer;
+            fn tracker_with_consistency_check() -> Tracker {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.consistency_check = Some(ConsistencyCheck::default());
+                tracker_factory(&configuration)
+            }
 
-                        #[test]
-                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+            #[tokio::test]
+            async fn it_should_not_have_a_report_before_the_first_check() {
+                let tracker = tracker_with_consistency_check();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+                assert!(tracker.get_last_consistency_report().is_none());
+                assert_eq!(tracker.get_consistency_check_metrics().unwrap().checks, 0);
+            }
 
-                            assert_eq!(peer_ip, remote_ip);
-                        }
+            #[tokio::test]
+            async fn it_should_not_find_inconsistencies_in_the_torrents_repository() {
+                let tracker = tracker_with_consistency_check();
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_tracker_configuration_if_it_is_defined() {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
 
-                            let tracker_external_ip =
-                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+                tracker.check_consistency();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                let report = tracker.get_last_consistency_report().unwrap();
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
+                assert_eq!(report.torrents, 1);
+                assert!(report.inconsistencies.is_empty());
+                assert_eq!(tracker.get_consistency_check_metrics().unwrap().checks, 1);
+            }
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv4_ip(
-                        ) {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+            #[tokio::test]
+            async fn it_should_not_check_the_consistency_when_it_is_not_enabled() {
+                let tracker = tracker_factory(&configuration::ephemeral());
 
-                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+                tracker.check_consistency();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                assert!(tracker.get_consistency_check_metrics().is_none());
+                assert!(tracker.get_last_consistency_report().is_none());
+            }
+        }
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
-                    }
-                }
+        mod handling_the_key_quotas {
+
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::key_quota::{Quota, Usage};
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{private_tracker, public_tracker};
+            use crate::core::PeersWanted;
+
+            #[tokio::test]
+            async fn it_should_reject_the_announces_of_a_key_that_has_reached_its_daily_quota() {
+                let tracker = private_tracker();
+
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                tracker
+                    .set_key_quota(
+                        &peer_key.key(),
+                        Quota {
+                            max_announces_per_day: Some(1),
+                            max_peers_per_day: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert!(tracker.consume_key_announce(&peer_key.key()).is_ok());
+                assert!(tracker.consume_key_announce(&peer_key.key()).is_err());
+            }
+
+            #[tokio::test]
+            async fn it_should_return_the_number_of_peers_a_key_can_still_receive_today() {
+                let tracker = private_tracker();
+
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                tracker
+                    .set_key_quota(
+                        &peer_key.key(),
+                        Quota {
+                            max_announces_per_day: None,
+                            max_peers_per_day: Some(100),
+                        },
+                    )
+                    .unwrap();
+                tracker.consume_key_peers(&peer_key.key(), 40);
 
-                #[tokio::test]
-                async fn it_should_return_the_announce_data_with_an_empty_peer_list_when_it_is_the_first_announced_peer() {
-                    let tracker = public_tracker();
+                assert_eq!(tracker.consume_key_announce(&peer_key.key()).unwrap(), Some(60));
+                assert_eq!(tracker.get_key_usage(&peer_key.key()), Usage { announces: 1, peers: 40 });
+            }
 
-                    let mut peer = sample_peer();
+            #[tokio::test]
+            async fn it_should_not_count_the_usage_of_keys_in_public_mode() {
+                let tracker = public_tracker();
 
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
 
-                    assert_eq!(announce_data.peers, vec![]);
-                }
+                assert_eq!(tracker.consume_key_announce(&peer_key.key()).unwrap(), None);
+                assert_eq!(tracker.get_key_usage(&peer_key.key()), Usage::default());
+            }
 
-                #[tokio::test]
-                async fn it_should_return_the_announce_data_with_the_previously_announced_peers() {
-                    let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_load_the_key_quotas_from_the_database() {
+                let tracker = tracker_factory(&configuration::ephemeral_private());
 
-                    let mut previously_announced_peer = sample_peer_1();
-                    tracker.announce(
-                        &sample_info_hash(),
-                        &mut previously_announced_peer,
-                        &peer_ip(),
-                        &PeersWanted::All,
-                    );
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                let quota = Quota {
+                    max_announces_per_day: Some(1),
+                    max_peers_per_day: Some(2),
+                };
+                tracker.set_key_quota(&peer_key.key(), quota).unwrap();
 
-                    let mut peer = sample_peer_2();
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                // Remove the quota from memory only
+                tracker.key_quotas.remove(&peer_key.key());
 
-                    assert_eq!(announce_data.peers, vec![Arc::new(previously_announced_peer)]);
-                }
+                tracker.load_keys_from_database().await.unwrap();
 
-                mod it_should_update_the_swarm_stats_for_the_torrent {
+                assert_eq!(tracker.get_key_quota(&peer_key.key()), Some(quota));
+            }
 
-                    use crate::core::tests::the_tracker::{
-                        completed_peer, leecher, peer_ip, public_tracker, sample_info_hash, seeder, started_peer,
-                    };
-                    use crate::core::PeersWanted;
+            #[test]
+            fn the_peers_wanted_should_be_limited_to_the_remaining_peers() {
+                assert_eq!(PeersWanted::All.at_most(10), PeersWanted::Only { amount: 10 });
+                assert_eq!(PeersWanted::only(5).at_most(10), PeersWanted::Only { amount: 5 });
+            }
+        }
 
-                    #[tokio::test]
-                    async fn when_the_peer_is_a_seeder() {
-                        let tracker = public_tracker();
+        mod handling_the_authorization_cache {
 
-                        let mut peer = seeder();
+            use torrust_tracker_configuration::AuthorizationCache;
+            use torrust_tracker_test_helpers::configuration;
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::sample_info_hash;
+            use crate::core::whitelist_rules::{Pattern, Rule};
+            use crate::core::Tracker;
 
-                        assert_eq!(announce_data.stats.complete, 1);
-                    }
+            fn private_listed_tracker_with_authorization_cache() -> Tracker {
+                let mut configuration = configuration::ephemeral_private_and_listed();
+                configuration.core.authorization_cache = Some(AuthorizationCache::default());
+                tracker_factory(&configuration)
+            }
 
-                    #[tokio::test]
-                    async fn when_the_peer_is_a_leecher() {
-                        let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_not_cache_authorizations_when_the_cache_is_disabled() {
+                let tracker = tracker_factory(&configuration::ephemeral_private_and_listed());
 
-                        let mut peer = leecher();
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                tracker.cache_authorization(&peer_key.key(), &sample_info_hash()).await;
 
-                        assert_eq!(announce_data.stats.incomplete, 1);
-                    }
+                assert!(!tracker.is_authorization_cached(&peer_key.key(), &sample_info_hash()));
+            }
 
-                    #[tokio::test]
-                    async fn when_a_previously_announced_started_peer_has_completed_downloading() {
-                        let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_cache_the_authorization_of_a_key_for_a_torrent() {
+                let tracker = private_listed_tracker_with_authorization_cache();
 
-                        // We have to announce with "started" event because peer does not count if peer was not previously known
-                        let mut started_peer = started_peer();
-                        tracker.announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All);
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
 
-                        let mut completed_peer = completed_peer();
-                        let announce_data =
-                            tracker.announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All);
+                tracker.cache_authorization(&peer_key.key(), &sample_info_hash()).await;
 
-                        assert_eq!(announce_data.stats.downloaded, 1);
-                    }
-                }
+                assert!(tracker.is_authorization_cached(&peer_key.key(), &sample_info_hash()));
             }
 
-            mod handling_a_scrape_request {
+            #[tokio::test]
+            async fn it_should_invalidate_the_cached_authorizations_of_a_revoked_key() {
+                let tracker = private_listed_tracker_with_authorization_cache();
 
-                use std::net::{IpAddr, Ipv4Addr};
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                tracker.cache_authorization(&peer_key.key(), &sample_info_hash()).await;
 
-                use torrust_tracker_primitives::info_hash::InfoHash;
+                tracker.remove_auth_key(&peer_key.key()).await.unwrap();
 
-                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
-                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
+                assert!(!tracker.is_authorization_cached(&peer_key.key(), &sample_info_hash()));
+            }
 
-                #[tokio::test]
-                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
-                ) {
-                    let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_invalidate_the_cached_authorizations_of_a_torrent_removed_from_the_whitelist() {
+                let tracker = private_listed_tracker_with_authorization_cache();
 
-                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                tracker.cache_authorization(&peer_key.key(), &sample_info_hash()).await;
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+                tracker.remove_torrent_from_whitelist(&sample_info_hash()).await.unwrap();
 
-                    let mut expected_scrape_data = ScrapeData::empty();
+                assert!(!tracker.is_authorization_cached(&peer_key.key(), &sample_info_hash()));
+            }
 
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+            #[tokio::test]
+            async fn it_should_invalidate_the_cached_authorizations_when_a_whitelist_rule_is_removed() {
+                let tracker = private_listed_tracker_with_authorization_cache();
+
+                let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                let id = tracker
+                    .add_whitelist_rule(Rule {
+                        pattern: Pattern::Any,
+                        valid_until: None,
+                        comment: None,
+                    })
+                    .await
+                    .unwrap();
+                tracker.cache_authorization(&peer_key.key(), &sample_info_hash()).await;
+
+                tracker.remove_whitelist_rule(id).await.unwrap();
+
+                assert!(!tracker.is_authorization_cached(&peer_key.key(), &sample_info_hash()));
+            }
+        }
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+        mod handling_peer_reachability {
 
-                #[tokio::test]
-                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
-                    let tracker = public_tracker();
+            use std::sync::Arc;
 
-                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+            use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_configuration::PeerReachability;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::reachability::{Probe, Reachability};
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{peer_ip, sample_info_hash, sample_peer, sample_peer_1, sample_peer_2};
+            use crate::core::{PeersWanted, Tracker};
+
+            fn tracker_verifying_every_peer() -> Tracker {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.peer_reachability = Some(PeerReachability {
+                    sample_one_in: 1,
+                    ..Default::default()
+                });
+                tracker_factory(&configuration)
+            }
 
-                    // Announce a "complete" peer for the torrent
-                    let mut complete_peer = complete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut complete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
-                        &PeersWanted::All,
-                    );
+            #[tokio::test]
+            async fn it_should_queue_the_sampled_peers_to_be_probed() {
+                let tracker = tracker_verifying_every_peer();
 
-                    // Announce an "incomplete" peer for the torrent
-                    let mut incomplete_peer = incomplete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut incomplete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
-                        &PeersWanted::All,
-                    );
+                let mut peer = sample_peer();
 
-                    // Scrape
-                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    // The expected swarm metadata for the file
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file(
-                        &info_hash,
-                        SwarmMetadata {
-                            complete: 0, // the "complete" peer does not count because it was not previously known
-                            downloaded: 0,
-                            incomplete: 1, // the "incomplete" peer we have just announced
-                        },
-                    );
+                tracker.sample_peer_reachability(&sample_info_hash(), &peer.peer_addr);
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+                assert_eq!(
+                    tracker.next_reachability_probe(),
+                    Some(Probe {
+                        info_hash: sample_info_hash(),
+                        peer_addr: peer.peer_addr
+                    })
+                );
+            }
 
-                #[tokio::test]
-                async fn it_should_allow_scraping_for_multiple_torrents() {
-                    let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_return_the_unreachable_peers_after_the_other_ones() {
+                let tracker = tracker_verifying_every_peer();
 
-                    let info_hashes = vec![
-                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
-                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
-                    ];
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_2());
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+                tracker.record_reachability(
+                    &Probe {
+                        info_hash: sample_info_hash(),
+                        peer_addr: sample_peer_1().peer_addr,
+                    },
+                    Reachability::Unreachable,
+                );
 
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+                let peers = tracker.get_peers_for(&sample_info_hash(), &sample_peer(), 74);
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+                assert_eq!(peers, vec![Arc::new(sample_peer_2()), Arc::new(sample_peer_1())]);
             }
-        }
 
-        mod configured_as_whitelisted {
+            #[tokio::test]
+            async fn it_should_drop_the_reachability_of_a_peer_that_leaves_the_swarm() {
+                let tracker = tracker_verifying_every_peer();
 
-            mod handling_authorization {
-                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
+                let mut peer = sample_peer();
 
-                #[tokio::test]
-                async fn it_should_authorize_the_announce_and_scrape_actions_on_whitelisted_torrents() {
-                    let tracker = whitelisted_tracker();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    let info_hash = sample_info_hash();
+                tracker.record_reachability(
+                    &Probe {
+                        info_hash: sample_info_hash(),
+                        peer_addr: peer.peer_addr,
+                    },
+                    Reachability::Reachable,
+                );
 
-                    let result = tracker.add_torrent_to_whitelist(&info_hash).await;
-                    assert!(result.is_ok());
+                peer.event = AnnounceEvent::Stopped;
 
-                    let result = tracker.authorize(&info_hash).await;
-                    assert!(result.is_ok());
-                }
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                #[tokio::test]
-                async fn it_should_not_authorize_the_announce_and_scrape_actions_on_not_whitelisted_torrents() {
-                    let tracker = whitelisted_tracker();
+                assert_eq!(tracker.get_reachability_ratio(&sample_info_hash()), None);
+            }
 
-                    let info_hash = sample_info_hash();
+            #[tokio::test]
+            async fn it_should_return_the_reachability_ratio_for_a_torrent() {
+                let tracker = tracker_verifying_every_peer();
+
+                for (peer, reachability) in [
+                    (sample_peer_1(), Reachability::Reachable),
+                    (sample_peer_2(), Reachability::Unreachable),
+                ] {
+                    let probe = Probe {
+                        info_hash: sample_info_hash(),
+                        peer_addr: peer.peer_addr,
+                    };
 
-                    let result = tracker.authorize(&info_hash).await;
-                    assert!(result.is_err());
+                    tracker.record_reachability(&probe, reachability);
                 }
-            }
 
-            mod handling_the_torrent_whitelist {
-                use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
-
-                #[tokio::test]
-                async fn it_should_add_a_torrent_to_the_whitelist() {
-                    let tracker = whitelisted_tracker();
+                assert_eq!(tracker.get_reachability_ratio(&sample_info_hash()), Some(0.5));
+            }
 
-                    let info_hash = sample_info_hash();
+            #[tokio::test]
+            async fn it_should_not_return_a_reachability_ratio_when_the_verification_is_disabled() {
+                let tracker = tracker_factory(&configuration::ephemeral());
 
-                    tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+                assert_eq!(tracker.get_reachability_ratio(&sample_info_hash()), None);
+            }
+        }
 
-                    assert!(tracker.is_info_hash_whitelisted(&info_hash).await);
-                }
+        mod handling_mixed_address_families {
 
-                #[tokio::test]
-                async fn it_should_remove_a_torrent_from_the_whitelist() {
-                    let tracker = whitelisted_tracker();
+            use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+            use std::sync::Arc;
 
-                    let info_hash = sample_info_hash();
+            use aquatic_udp_protocol::PeerId;
+            use torrust_tracker_test_helpers::configuration;
 
-                    tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+            use crate::core::peer::Peer;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer, sample_peer_1};
+            use crate::core::Tracker;
 
-                    tracker.remove_torrent_from_whitelist(&info_hash).await.unwrap();
+            fn tracker_excluding_ipv6_peers_for_ipv4_clients() -> Tracker {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.net.exclude_ipv6_peers_for_ipv4_clients = true;
+                tracker_factory(&configuration)
+            }
 
-                    assert!(!tracker.is_info_hash_whitelisted(&info_hash).await);
+            fn ipv6_peer() -> Peer {
+                Peer {
+                    peer_id: PeerId(*b"-qB00000000000000006"),
+                    peer_addr: SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 8086),
+                    ..sample_peer_1()
                 }
+            }
 
-                mod persistence {
-                    use crate::core::tests::the_tracker::{sample_info_hash, whitelisted_tracker};
-
-                    #[tokio::test]
-                    async fn it_should_load_the_whitelist_from_the_database() {
-                        let tracker = whitelisted_tracker();
+            #[tokio::test]
+            async fn it_should_return_the_peers_of_both_address_families_by_default() {
+                let tracker = tracker_factory(&configuration::ephemeral());
 
-                        let info_hash = sample_info_hash();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &ipv6_peer());
 
-                        tracker.add_torrent_to_whitelist(&info_hash).await.unwrap();
+                let peers = tracker.get_peers_for(&sample_info_hash(), &sample_peer(), 74);
 
-                        // Remove torrent from the in-memory whitelist
-                        tracker.whitelist.write().await.remove(&info_hash);
-                        assert!(!tracker.is_info_hash_whitelisted(&info_hash).await);
+                assert_eq!(peers.len(), 2);
+            }
 
-                        tracker.load_whitelist_from_database().await.unwrap();
+            #[tokio::test]
+            async fn it_should_exclude_the_ipv6_peers_for_ipv4_clients_when_configured() {
+                let tracker = tracker_excluding_ipv6_peers_for_ipv4_clients();
 
-                        assert!(tracker.is_info_hash_whitelisted(&info_hash).await);
-                    }
-                }
-            }
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &ipv6_peer());
 
-            mod handling_an_announce_request {}
+                let peers = tracker.get_peers_for(&sample_info_hash(), &sample_peer(), 74);
 
-            mod handling_an_scrape_request {
+                assert_eq!(peers, vec![Arc::new(sample_peer_1())]);
+            }
 
-                use torrust_tracker_primitives::info_hash::InfoHash;
-                use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+            #[tokio::test]
+            async fn it_should_not_exclude_any_peer_for_ipv6_clients() {
+                let tracker = tracker_excluding_ipv6_peers_for_ipv4_clients();
 
-                use crate::core::tests::the_tracker::{
-                    complete_peer, incomplete_peer, peer_ip, sample_info_hash, whitelisted_tracker,
+                let ipv6_client = Peer {
+                    peer_id: PeerId(*b"-qB00000000000000007"),
+                    peer_addr: SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)), 8087),
+                    ..sample_peer_1()
                 };
-                use crate::core::{PeersWanted, ScrapeData};
-
-                #[test]
-                fn it_should_be_able_to_build_a_zeroed_scrape_data_for_a_list_of_info_hashes() {
-                    // Zeroed scrape data is used when the authentication for the scrape request fails.
 
-                    let sample_info_hash = sample_info_hash();
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &sample_peer_1());
+                tracker.upsert_peer_and_get_stats(&sample_info_hash(), &ipv6_peer());
 
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file_with_zeroed_metadata(&sample_info_hash);
+                let peers = tracker.get_peers_for(&sample_info_hash(), &ipv6_client, 74);
 
-                    assert_eq!(ScrapeData::zeroed(&vec![sample_info_hash]), expected_scrape_data);
-                }
+                assert_eq!(peers.len(), 2);
+            }
+        }
 
-                #[tokio::test]
-                async fn it_should_return_the_zeroed_swarm_metadata_for_the_requested_file_if_it_is_not_whitelisted() {
-                    let tracker = whitelisted_tracker();
+        mod handling_torrent_policies {
 
-                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+            use aquatic_udp_protocol::AnnounceEvent;
 
-                    let mut peer = incomplete_peer();
-                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+            use super::{leecher, peer_ip, public_tracker, sample_info_hash, sample_peer_1, sample_peer_2, seeder};
+            use crate::core::error::Error;
+            use crate::core::torrent::policy::TorrentPolicy;
+            use crate::core::PeersWanted;
 
-                    // Announce twice to force non zeroed swarm metadata
-                    let mut peer = complete_peer();
-                    tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
+            #[tokio::test]
+            async fn it_should_allow_any_peer_to_announce_a_torrent_without_a_policy() {
+                let tracker = public_tracker();
 
-                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                assert!(tracker.authorize_peer(&sample_info_hash(), &leecher()).await.is_ok());
+            }
 
-                    // The expected zeroed swarm metadata for the file
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file(&info_hash, SwarmMetadata::zeroed());
+            #[tokio::test]
+            async fn it_should_reject_leechers_announcing_a_seed_only_torrent() {
+                let tracker = public_tracker();
+
+                tracker
+                    .set_torrent_policy(&sample_info_hash(), TorrentPolicy::SeedOnly)
+                    .await
+                    .unwrap();
+
+                assert!(matches!(
+                    tracker.authorize_peer(&sample_info_hash(), &leecher()).await,
+                    Err(Error::TorrentSeedOnly { .. })
+                ));
+                assert!(tracker.authorize_peer(&sample_info_hash(), &seeder()).await.is_ok());
+            }
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+            #[tokio::test]
+            async fn it_should_only_allow_peers_already_in_the_swarm_to_announce_a_frozen_torrent() {
+                let tracker = public_tracker();
+
+                let mut peer_in_swarm = sample_peer_1();
+                tracker.announce(&sample_info_hash(), &mut peer_in_swarm, &peer_ip(), &PeersWanted::All);
+
+                tracker
+                    .set_torrent_policy(&sample_info_hash(), TorrentPolicy::Frozen)
+                    .await
+                    .unwrap();
+
+                assert!(tracker.authorize_peer(&sample_info_hash(), &peer_in_swarm).await.is_ok());
+                assert!(matches!(
+                    tracker.authorize_peer(&sample_info_hash(), &sample_peer_2()).await,
+                    Err(Error::TorrentFrozen { .. })
+                ));
             }
-        }
 
-        mod configured_as_private {
+            #[tokio::test]
+            async fn it_should_always_allow_peers_to_leave_the_swarm() {
+                let tracker = public_tracker();
 
-            mod handling_authentication {
-                use std::str::FromStr;
-                use std::time::Duration;
+                tracker
+                    .set_torrent_policy(&sample_info_hash(), TorrentPolicy::SeedOnly)
+                    .await
+                    .unwrap();
 
-                use crate::core::auth::{self};
-                use crate::core::tests::the_tracker::private_tracker;
+                let mut stopped_leecher = leecher();
+                stopped_leecher.event = AnnounceEvent::Stopped;
+
+                assert!(tracker.authorize_peer(&sample_info_hash(), &stopped_leecher).await.is_ok());
+            }
 
-                #[tokio::test]
-                async fn it_should_fail_authenticating_a_peer_when_it_uses_an_unregistered_key() {
-                    let tracker = private_tracker();
+            #[tokio::test]
+            async fn it_should_remove_the_policy_of_a_torrent() {
+                let tracker = public_tracker();
 
-                    let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+                tracker
+                    .set_torrent_policy(&sample_info_hash(), TorrentPolicy::Frozen)
+                    .await
+                    .unwrap();
 
-                    let result = tracker.authenticate(&unregistered_key).await;
+                tracker.remove_torrent_policy(&sample_info_hash()).await.unwrap();
 
-                    assert!(result.is_err());
-                }
+                assert_eq!(tracker.get_torrent_policy(&sample_info_hash()).await, None);
+            }
 
-                #[tokio::test]
-                async fn it_should_fail_verifying_an_unregistered_authentication_key() {
-                    let tracker = private_tracker();
+            #[tokio::test]
+            async fn it_should_load_the_torrent_policies_from_the_database() {
+                let tracker = public_tracker();
 
-                    let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+                tracker
+                    .set_torrent_policy(&sample_info_hash(), TorrentPolicy::SeedOnly)
+                    .await
+                    .unwrap();
 
-                    assert!(tracker.verify_auth_key(&unregistered_key).await.is_err());
-                }
+                tracker.policies.write().await.clear();
 
-                #[tokio::test]
-                async fn it_should_remove_an_authentication_key() {
-                    let tracker = private_tracker();
+                tracker.load_torrent_policies_from_database().await.unwrap();
 
-                    let expiring_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+                assert_eq!(
+                    tracker.get_torrent_policies().await,
+                    vec![(sample_info_hash(), TorrentPolicy::SeedOnly)]
+                );
+            }
+        }
 
-                    let result = tracker.remove_auth_key(&expiring_key.key()).await;
+        mod handling_static_peers {
 
-                    assert!(result.is_ok());
-                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_err());
-                }
+            use std::net::SocketAddr;
+            use std::sync::Arc;
 
-                #[tokio::test]
-                async fn it_should_load_authentication_keys_from_the_database() {
-                    let tracker = private_tracker();
+            use super::{leecher, peer_ip, public_tracker, sample_info_hash};
+            use crate::core::torrent::static_peers;
+            use crate::core::PeersWanted;
 
-                    let expiring_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+            fn static_peer_addr() -> SocketAddr {
+                "126.0.0.100:8080".parse().unwrap()
+            }
 
-                    // Remove the newly generated key in memory
-                    tracker.keys.write().await.remove(&expiring_key.key());
+            #[tokio::test]
+            async fn it_should_return_the_static_peers_in_the_announce_response() {
+                let tracker = public_tracker();
 
-                    let result = tracker.load_keys_from_database().await;
+                tracker
+                    .add_static_peer(&sample_info_hash(), &static_peer_addr())
+                    .await
+                    .unwrap();
 
-                    assert!(result.is_ok());
-                    assert!(tracker.verify_auth_key(&expiring_key.key()).await.is_ok());
-                }
+                let announce_data = tracker.announce(&sample_info_hash(), &mut leecher(), &peer_ip(), &PeersWanted::All);
 
-                mod with_expiring_and {
+                assert_eq!(
+                    announce_data.peers,
+                    vec![Arc::new(static_peers::to_peer(&static_peer_addr()))]
+                );
+                assert_eq!(announce_data.stats.complete, 1);
+            }
 
-                    mod randomly_generated_keys {
-                        use std::time::Duration;
+            #[tokio::test]
+            async fn it_should_not_return_the_static_peers_after_removing_them() {
+                let tracker = public_tracker();
+
+                tracker
+                    .add_static_peer(&sample_info_hash(), &static_peer_addr())
+                    .await
+                    .unwrap();
+                tracker
+                    .remove_static_peer(&sample_info_hash(), &static_peer_addr())
+                    .await
+                    .unwrap();
+
+                let announce_data = tracker.announce(&sample_info_hash(), &mut leecher(), &peer_ip(), &PeersWanted::All);
+
+                assert!(announce_data.peers.is_empty());
+                assert!(tracker.get_static_peers().await.is_empty());
+            }
 
-                        use torrust_tracker_clock::clock::Time;
-                        use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+            #[tokio::test]
+            async fn it_should_load_the_static_peers_from_the_database() {
+                let tracker = public_tracker();
 
-                        use crate::core::auth::Key;
-                        use crate::core::tests::the_tracker::private_tracker;
-                        use crate::CurrentClock;
+                tracker
+                    .add_static_peer(&sample_info_hash(), &static_peer_addr())
+                    .await
+                    .unwrap();
 
-                        #[tokio::test]
-                        async fn it_should_generate_the_key() {
-                            let tracker = private_tracker();
+                tracker.static_peers.write().await.clear();
 
-                            let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+                tracker.load_static_peers_from_database().await.unwrap();
 
-                            assert_eq!(
-                                peer_key.valid_until,
-                                Some(CurrentClock::now_add(&Duration::from_secs(100)).unwrap())
-                            );
-                        }
+                assert_eq!(
+                    tracker.get_static_peers().await,
+                    vec![(sample_info_hash(), vec![static_peer_addr()])]
+                );
+            }
+        }
 
-                        #[tokio::test]
-                        async fn it_should_authenticate_a_peer_with_the_key() {
-                            let tracker = private_tracker();
+        mod handling_invitation_codes {
 
-                            let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+            use std::time::Duration;
 
-                            let result = tracker.authenticate(&peer_key.key()).await;
+            use super::private_tracker;
+            use crate::core::error::RegistrationError;
+            use crate::core::invitation;
 
-                            assert!(result.is_ok());
-                        }
+            #[tokio::test]
+            async fn it_should_issue_an_authentication_key_in_exchange_for_an_invitation_code() {
+                let tracker = private_tracker();
 
-                        #[tokio::test]
-                        async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
-                            let mut tracker = private_tracker();
+                let code = tracker.generate_invitation_code().unwrap();
 
-                            tracker.config.private_mode = Some(PrivateMode {
-                                check_keys_expiration: false,
-                            });
+                let peer_key = tracker
+                    .register_with_invitation_code(&code, Some(Duration::from_secs(100)))
+                    .await
+                    .unwrap();
 
-                            let past_timestamp = Duration::ZERO;
+                assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
+                assert!(peer_key.valid_until.is_some());
+            }
 
-                            let peer_key = tracker
-                                .add_auth_key(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap(), Some(past_timestamp))
-                                .await
-                                .unwrap();
+            #[tokio::test]
+            async fn it_should_not_accept_an_invitation_code_twice() {
+                let tracker = private_tracker();
 
-                            assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
-                        }
-                    }
+                let code = tracker.generate_invitation_code().unwrap();
 
-                    mod pre_generated_keys {
-                        use std::time::Duration;
+                tracker.register_with_invitation_code(&code, None).await.unwrap();
 
-                        use torrust_tracker_clock::clock::Time;
-                        use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+                let result = tracker.register_with_invitation_code(&code, None).await;
 
-                        use crate::core::auth::Key;
-                        use crate::core::tests::the_tracker::private_tracker;
-                        use crate::core::AddKeyRequest;
-                        use crate::CurrentClock;
+                assert!(matches!(result, Err(RegistrationError::InvitationCodeNotValid { .. })));
+                assert!(tracker.get_invitation_codes().unwrap().is_empty());
+            }
 
-                        #[tokio::test]
-                        async fn it_should_add_a_pre_generated_key() {
-                            let tracker = private_tracker();
+            #[tokio::test]
+            async fn it_should_not_accept_an_unknown_invitation_code() {
+                let tracker = private_tracker();
 
-                            let peer_key = tracker
-                                .add_peer_key(AddKeyRequest {
-                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
-                                    opt_seconds_valid: Some(100),
-                                })
-                                .await
-                                .unwrap();
+                let result = tracker
+                    .register_with_invitation_code(&invitation::generate_code(), None)
+                    .await;
 
-                            assert_eq!(
-                                peer_key.valid_until,
-                                Some(CurrentClock::now_add(&Duration::from_secs(100)).unwrap())
-                            );
-                        }
+                assert!(matches!(result, Err(RegistrationError::InvitationCodeNotValid { .. })));
+            }
 
-                        #[tokio::test]
-                        async fn it_should_authenticate_a_peer_with_the_key() {
-                            let tracker = private_tracker();
+            #[tokio::test]
+            async fn it_should_not_accept_a_revoked_invitation_code() {
+                let tracker = private_tracker();
 
-                            let peer_key = tracker
-                                .add_peer_key(AddKeyRequest {
-                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
-                                    opt_seconds_valid: Some(100),
-                                })
-                                .await
-                                .unwrap();
+                let code = tracker.generate_invitation_code().unwrap();
 
-                            let result = tracker.authenticate(&peer_key.key()).await;
+                assert_eq!(tracker.get_invitation_codes().unwrap(), vec![code.clone()]);
 
-                            assert!(result.is_ok());
-                        }
+                tracker.remove_invitation_code(&code).unwrap();
 
-                        #[tokio::test]
-                        async fn it_should_accept_an_expired_key_when_checking_expiration_is_disabled_in_configuration() {
-                            let mut tracker = private_tracker();
+                let result = tracker.register_with_invitation_code(&code, None).await;
 
-                            tracker.config.private_mode = Some(PrivateMode {
-                                check_keys_expiration: false,
-                            });
+                assert!(matches!(result, Err(RegistrationError::InvitationCodeNotValid { .. })));
+            }
+        }
 
-                            let peer_key = tracker
-                                .add_peer_key(AddKeyRequest {
-                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
-                                    opt_seconds_valid: Some(0),
-                                })
-                                .await
-                                .unwrap();
+        mod handling_the_request_deadline {
 
-                            assert!(tracker.authenticate(&peer_key.key()).await.is_ok());
-                        }
-                    }
-                }
+            use std::sync::Arc;
 
-                mod with_permanent_and {
+            use torrust_tracker_configuration::RequestDeadline;
+            use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+            use torrust_tracker_test_helpers::configuration;
 
-                    mod randomly_generated_keys {
-                        use crate::core::tests::the_tracker::private_tracker;
+            use super::{leecher, peer_ip, sample_info_hash, sample_peer_1};
+            use crate::core::services::tracker_factory;
+            use crate::core::{AnnounceMode, PeersWanted, Requester, Tracker};
 
-                        #[tokio::test]
-                        async fn it_should_generate_the_key() {
-                            let tracker = private_tracker();
+            fn tracker_with_a_request_deadline() -> Arc<Tracker> {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.request_deadline = Some(RequestDeadline {
+                    budget_ms: 10_000,
+                    ..Default::default()
+                });
+                Arc::new(tracker_factory(&configuration))
+            }
 
-                            let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+            #[tokio::test]
+            async fn it_should_handle_the_announce_requests_within_the_deadline() {
+                let tracker = tracker_with_a_request_deadline();
 
-                            assert_eq!(peer_key.valid_until, None);
-                        }
+                tracker.announce(&sample_info_hash(), &mut sample_peer_1(), &peer_ip(), &PeersWanted::All);
 
-                        #[tokio::test]
-                        async fn it_should_authenticate_a_peer_with_the_key() {
-                            let tracker = private_tracker();
+                let mut peer = leecher();
 
-                            let peer_key = tracker.generate_permanent_auth_key().await.unwrap();
+                let announce_data = tracker
+                    .announce_within_deadline(
+                        &Requester::Anonymous,
+                        &sample_info_hash(),
+                        &mut peer,
+                        &peer_ip(),
+                        &PeersWanted::All,
+                        AnnounceMode::Synchronous,
+                    )
+                    .await
+                    .unwrap();
+
+                assert_eq!(announce_data.peers.len(), 1);
+                assert_eq!(announce_data.stats.incomplete, 1);
+                assert_eq!(peer.peer_addr.ip(), peer_ip());
+            }
 
-                            let result = tracker.authenticate(&peer_key.key()).await;
+            #[tokio::test]
+            async fn it_should_return_a_best_effort_announce_response_without_peers_and_with_zeroed_metadata() {
+                let tracker = tracker_with_a_request_deadline();
 
-                            assert!(result.is_ok());
-                        }
-                    }
+                tracker.announce(&sample_info_hash(), &mut sample_peer_1(), &peer_ip(), &PeersWanted::All);
 
-                    mod pre_generated_keys {
-                        use crate::core::auth::Key;
-                        use crate::core::tests::the_tracker::private_tracker;
-                        use crate::core::AddKeyRequest;
+                let announce_data = tracker.best_effort_announce_data(&sample_info_hash(), &Requester::Anonymous);
 
-                        #[tokio::test]
-                        async fn it_should_add_a_pre_generated_key() {
-                            let tracker = private_tracker();
+                assert!(announce_data.peers.is_empty());
+                assert_eq!(announce_data.stats, SwarmMetadata::zeroed());
+                assert_eq!(announce_data.policy, tracker.get_announce_policy());
+            }
+        }
 
-                            let peer_key = tracker
-                                .add_peer_key(AddKeyRequest {
-                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
-                                    opt_seconds_valid: None,
-                                })
-                                .await
-                                .unwrap();
+        mod handling_the_authenticated_announce_policy {
 
-                            assert_eq!(peer_key.valid_until, None);
-                        }
+            use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-                        #[tokio::test]
-                        async fn it_should_authenticate_a_peer_with_the_key() {
-                            let tracker = private_tracker();
+            use aquatic_udp_protocol::PeerId;
+            use torrust_tracker_configuration::{AnnouncePolicy, AuthenticatedAnnouncePolicy};
+            use torrust_tracker_test_helpers::configuration;
 
-                            let peer_key = tracker
-                                .add_peer_key(AddKeyRequest {
-                                    opt_key: Some(Key::new("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap().to_string()),
-                                    opt_seconds_valid: None,
-                                })
-                                .await
-                                .unwrap();
+            use super::{leecher, peer_ip, sample_info_hash, seeder};
+            use crate::core::services::tracker_factory;
+            use crate::core::{PeersWanted, Requester, Tracker};
 
-                            let result = tracker.authenticate(&peer_key.key()).await;
+            fn tracker_with_an_authenticated_announce_policy(max_peers: u32) -> Tracker {
+                let mut configuration = configuration::ephemeral_listed();
+                configuration.core.authenticated_announce_policy = Some(AuthenticatedAnnouncePolicy {
+                    interval: 60,
+                    interval_min: 30,
+                    max_peers,
+                });
+                tracker_factory(&configuration)
+            }
 
-                            assert!(result.is_ok());
-                        }
-                    }
+            fn announce_seeders(tracker: &Tracker, number_of_seeders: u8) {
+                for i in 0..number_of_seeders {
+                    let mut peer = seeder();
+                    peer.peer_id = PeerId(*format!("-qB1000000000000{i:04}").as_bytes().first_chunk().unwrap());
+                    peer.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 1, i)), 8080);
+                    let ip = peer.peer_addr.ip();
+                    tracker.announce(&sample_info_hash(), &mut peer, &ip, &PeersWanted::All);
                 }
             }
 
-            mod handling_an_announce_request {}
+            #[tokio::test]
+            async fn it_should_return_the_authenticated_announce_policy_to_authenticated_requesters() {
+                let tracker = tracker_with_an_authenticated_announce_policy(100);
+
+                let announce_data = tracker.announce_as(
+                    &Requester::Authenticated,
+                    &sample_info_hash(),
+                    &mut leecher(),
+                    &peer_ip(),
+                    &PeersWanted::All,
+                );
+
+                assert_eq!(announce_data.policy, AnnouncePolicy::new(60, 30));
+            }
 
-            mod handling_an_scrape_request {}
+            #[tokio::test]
+            async fn it_should_return_the_default_announce_policy_to_anonymous_requesters() {
+                let tracker = tracker_with_an_authenticated_announce_policy(100);
+
+                let announce_data = tracker.announce_as(
+                    &Requester::Anonymous,
+                    &sample_info_hash(),
+                    &mut leecher(),
+                    &peer_ip(),
+                    &PeersWanted::All,
+                );
+
+                assert_eq!(announce_data.policy, tracker.get_announce_policy());
+            }
+
+            #[tokio::test]
+            async fn it_should_return_up_to_the_authenticated_max_peers_to_authenticated_requesters() {
+                let tracker = tracker_with_an_authenticated_announce_policy(100);
+
+                announce_seeders(&tracker, 90);
+
+                let authenticated = tracker.announce_as(
+                    &Requester::Authenticated,
+                    &sample_info_hash(),
+                    &mut leecher(),
+                    &peer_ip(),
+                    &PeersWanted::All,
+                );
+                let anonymous = tracker.announce_as(
+                    &Requester::Anonymous,
+                    &sample_info_hash(),
+                    &mut leecher(),
+                    &peer_ip(),
+                    &PeersWanted::All,
+                );
+
+                assert_eq!(authenticated.peers.len(), 90);
+                assert_eq!(anonymous.peers.len(), 74);
+            }
+
+            #[tokio::test]
+            async fn it_should_use_the_default_announce_policy_for_authenticated_requesters_when_there_is_no_authenticated_policy(
+            ) {
+                let tracker = tracker_factory(&configuration::ephemeral_listed());
+
+                assert_eq!(
+                    tracker.get_announce_policy_for(&Requester::Authenticated),
+                    tracker.get_announce_policy()
+                );
+            }
         }
 
-        mod configured_as_private_and_whitelisted {
+        mod handling_maintenance_mode {
 
-            mod handling_an_announce_request {}
+            use torrust_tracker_test_helpers::configuration;
 
-            mod handling_an_scrape_request {}
-        }
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
 
-        mod handling_torrent_persistence {
+            #[tokio::test]
+            async fn it_should_accept_announces_when_it_is_not_in_maintenance_mode() {
+                let tracker = tracker_factory(&configuration::ephemeral());
 
-            use aquatic_udp_protocol::AnnounceEvent;
-            use torrust_tracker_torrent_repository::entry::EntrySync;
-            use torrust_tracker_torrent_repository::repository::Repository;
+                assert!(!tracker.is_in_maintenance());
+                assert!(tracker.check_maintenance().is_ok());
+            }
 
-            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer, tracker_persisting_torrents_in_database};
+            #[tokio::test]
+            async fn it_should_reject_announces_asking_to_retry_later_while_it_is_in_maintenance_mode() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.maintenance.retry_in = 30;
+                let tracker = tracker_factory(&configuration);
+
+                tracker.set_maintenance(true);
+
+                assert!(matches!(
+                    tracker.check_maintenance(),
+                    Err(Error::TrackerInMaintenance { retry_in: 30, .. })
+                ));
+            }
 
             #[tokio::test]
-            async fn it_should_persist_the_number_of_completed_peers_for_all_torrents_into_the_database() {
-                let tracker = tracker_persisting_torrents_in_database();
+            async fn it_should_start_in_maintenance_mode_if_it_is_enabled_in_the_configuration() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.maintenance.enabled = true;
+                let tracker = tracker_factory(&configuration);
 
-                let info_hash = sample_info_hash();
+                assert!(tracker.is_in_maintenance());
 
-                let mut peer = sample_peer();
+                tracker.set_maintenance(false);
 
-                peer.event = AnnounceEvent::Started;
-                let swarm_stats = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
-                assert_eq!(swarm_stats.downloaded, 0);
+                assert!(tracker.check_maintenance().is_ok());
+            }
+        }
 
-                peer.event = AnnounceEvent::Completed;
-                let swarm_stats = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
-                assert_eq!(swarm_stats.downloaded, 1);
+        mod handling_the_peer_cleanup {
 
-                // Remove the newly updated torrent from memory
-                tracker.torrents.remove(&info_hash);
+            use torrust_tracker_test_helpers::configuration;
 
-                tracker.load_torrents_from_database().unwrap();
+            use crate::core::peer_cleanup::Settings;
+            use crate::core::services::tracker_factory;
 
-                let torrent_entry = tracker.torrents.get(&info_hash).expect("it should be able to get entry");
+            #[tokio::test]
+            async fn it_should_start_with_the_peer_cleanup_settings_in_the_configuration() {
+                let mut configuration = configuration::ephemeral();
+                configuration.core.inactive_peer_cleanup_interval = 60;
+                configuration.core.tracker_policy.max_peer_timeout = 120;
+                let tracker = tracker_factory(&configuration);
+
+                assert_eq!(
+                    tracker.get_peer_cleanup(),
+                    Settings {
+                        inactive_peer_cleanup_interval: 60,
+                        max_peer_timeout: 120,
+                    }
+                );
+            }
 
-                // It persists the number of completed peers.
-                assert_eq!(torrent_entry.get_swarm_metadata().downloaded, 1);
+            #[tokio::test]
+            async fn it_should_notify_the_subscribers_when_the_peer_cleanup_settings_change() {
+                let tracker = tracker_factory(&configuration::ephemeral());
 
-                // It does not persist the peers
-                assert!(torrent_entry.peers_is_empty());
+                let mut receiver = tracker.subscribe_to_peer_cleanup();
+
+                let settings = Settings {
+                    inactive_peer_cleanup_interval: 10,
+                    max_peer_timeout: 20,
+                };
+
+                tracker.set_peer_cleanup(settings);
+
+                assert!(receiver.has_changed().unwrap());
+                assert_eq!(*receiver.borrow_and_update(), settings);
+                assert_eq!(tracker.get_peer_cleanup(), settings);
             }
         }
     }
@@ -0,0 +1,193 @@
+//! Cache of the infohashes recently rejected by the UDP tracker.
+//!
+//! When the tracker is under attack, it can be flooded with `announce` and
+//! `scrape` requests for torrents that are not whitelisted or do not exist.
+//! Every one of those requests takes the whitelist locks and looks up the
+//! torrents repository only to reject the infohash again. When the cache is
+//! enabled, the rejection is reused for `ttl_ms` milliseconds.
+//!
+//! There are two kinds of rejections:
+//!
+//! - [`Rejection::NotAuthorized`]: the torrent is not whitelisted. Both
+//!   `announce` and `scrape` requests for the torrent are short-circuited.
+//! - [`Rejection::NotFound`]: the torrent is not in the torrents repository.
+//!   Only `scrape` requests are short-circuited, since an `announce` request
+//!   creates the torrent.
+//!
+//! A cached rejection is removed when:
+//!
+//! - It expires after `ttl_ms`.
+//! - The torrent is added to the whitelist, a whitelist rule is added or the
+//!   whitelist is reloaded.
+//! - The torrent is added to the torrents repository.
+//!
+//! Refer to the [`NegativeCache`] configuration for more information.
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::NegativeCache;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use crate::CurrentClock;
+
+/// Why the infohash was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The torrent is not whitelisted.
+    NotAuthorized,
+    /// The torrent is not in the torrents repository.
+    NotFound,
+}
+
+/// Rejected infohashes, with the time until the rejection is valid.
+#[derive(Debug)]
+pub struct Cache {
+    rejections: RwLock<HashMap<InfoHash, (Rejection, DurationSinceUnixEpoch)>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl Cache {
+    #[must_use]
+    pub fn new(config: &NegativeCache) -> Self {
+        Self {
+            rejections: RwLock::default(),
+            ttl: Duration::from_millis(config.ttl_ms),
+            capacity: config.capacity,
+        }
+    }
+
+    /// It returns the rejection of the infohash, if it was rejected recently.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    #[must_use]
+    pub fn get(&self, info_hash: &InfoHash) -> Option<Rejection> {
+        self.rejections
+            .read()
+            .expect("it should get the negative cache lock")
+            .get(info_hash)
+            .filter(|(_, valid_until)| *valid_until > CurrentClock::now())
+            .map(|(rejection, _)| *rejection)
+    }
+
+    /// It caches the rejection of the infohash for the cache TTL.
+    ///
+    /// The rejection is not cached if the cache is full of unexpired ones.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn insert(&self, info_hash: &InfoHash, rejection: Rejection) {
+        let now = CurrentClock::now();
+
+        let Some(valid_until) = now.checked_add(self.ttl) else {
+            return;
+        };
+
+        let mut rejections = self.rejections.write().expect("it should get the negative cache lock");
+
+        if rejections.len() >= self.capacity {
+            rejections.retain(|_, (_, valid_until)| *valid_until > now);
+        }
+
+        if rejections.len() < self.capacity {
+            rejections.insert(*info_hash, (rejection, valid_until));
+        }
+    }
+
+    /// It removes the rejection of the infohash.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn remove(&self, info_hash: &InfoHash) {
+        self.rejections
+            .write()
+            .expect("it should get the negative cache lock")
+            .remove(info_hash);
+    }
+
+    /// It removes all the [`Rejection::NotAuthorized`] rejections.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the cache lock is poisoned.
+    pub fn clear_not_authorized(&self) {
+        self.rejections
+            .write()
+            .expect("it should get the negative cache lock")
+            .retain(|_, (rejection, _)| *rejection != Rejection::NotAuthorized);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_clock::clock::stopped::Stopped as _;
+    use torrust_tracker_clock::clock::{self};
+    use torrust_tracker_configuration::NegativeCache;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::{Cache, Rejection};
+
+    fn info_hash(byte: u8) -> InfoHash {
+        InfoHash::from([byte; 20])
+    }
+
+    fn cache(ttl_ms: u64, capacity: usize) -> Cache {
+        Cache::new(&NegativeCache::new(ttl_ms, capacity))
+    }
+
+    #[test]
+    fn it_should_reuse_a_rejection_until_the_ttl_expires() {
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cache = cache(1000, 10);
+
+        cache.insert(&info_hash(1), Rejection::NotAuthorized);
+
+        assert_eq!(cache.get(&info_hash(1)), Some(Rejection::NotAuthorized));
+        assert_eq!(cache.get(&info_hash(2)), None);
+
+        clock::Stopped::local_add(&Duration::from_secs(1)).unwrap();
+
+        assert_eq!(cache.get(&info_hash(1)), None);
+    }
+
+    #[test]
+    fn it_should_only_clear_the_not_authorized_rejections() {
+        let cache = cache(1000, 10);
+
+        cache.insert(&info_hash(1), Rejection::NotAuthorized);
+        cache.insert(&info_hash(2), Rejection::NotFound);
+        cache.clear_not_authorized();
+
+        assert_eq!(cache.get(&info_hash(1)), None);
+        assert_eq!(cache.get(&info_hash(2)), Some(Rejection::NotFound));
+    }
+
+    #[test]
+    fn it_should_not_cache_more_rejections_than_the_capacity() {
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cache = cache(1000, 1);
+
+        cache.insert(&info_hash(1), Rejection::NotFound);
+        cache.insert(&info_hash(2), Rejection::NotFound);
+
+        assert_eq!(cache.get(&info_hash(2)), None);
+
+        // Expired rejections are evicted to make room for new ones.
+        clock::Stopped::local_add(&Duration::from_secs(1)).unwrap();
+
+        cache.insert(&info_hash(2), Rejection::NotFound);
+
+        assert_eq!(cache.get(&info_hash(2)), Some(Rejection::NotFound));
+    }
+}
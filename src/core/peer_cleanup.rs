@@ -0,0 +1,118 @@
+//! Settings of the inactive peer cleanup.
+//!
+//! The [`torrent_cleanup`](crate::bootstrap::jobs::torrent_cleanup) job
+//! removes the peers that have not announced for more than
+//! `max_peer_timeout` seconds, every `inactive_peer_cleanup_interval`
+//! seconds. In private mode, the values in the `core.private_mode` section
+//! override the general ones, since private trackers usually keep the peers
+//! for longer:
+//!
+//! ```toml
+//! [core]
+//! inactive_peer_cleanup_interval = 600
+//! private = true
+//!
+//! [core.tracker_policy]
+//! max_peer_timeout = 900
+//!
+//! [core.private_mode]
+//! inactive_peer_cleanup_interval = 1800
+//! max_peer_timeout = 3600
+//! ```
+//!
+//! Both settings can be changed at runtime using the
+//! [API](crate::servers::apis::v1::context::peer_cleanup), without
+//! restarting the tracker. The cleanup job picks up the new interval
+//! immediately.
+//!
+//! > **NOTICE**: the settings changed using the API are not persisted. The
+//! > tracker starts with the settings in the configuration after restarting.
+use torrust_tracker_configuration::Core;
+
+/// The settings of the inactive peer cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Interval in seconds the cleanup job runs at. `0` disables the job.
+    pub inactive_peer_cleanup_interval: u64,
+    /// Maximum time in seconds that a peer can be inactive before being
+    /// removed.
+    pub max_peer_timeout: u32,
+}
+
+impl Settings {
+    /// It returns the settings for the tracker mode in the configuration.
+    #[must_use]
+    pub fn from_config(config: &Core) -> Self {
+        let private_mode = if config.private { config.private_mode } else { None };
+
+        Self {
+            inactive_peer_cleanup_interval: private_mode
+                .and_then(|mode| mode.inactive_peer_cleanup_interval)
+                .unwrap_or(config.inactive_peer_cleanup_interval),
+            max_peer_timeout: private_mode
+                .and_then(|mode| mode.max_peer_timeout)
+                .unwrap_or(config.tracker_policy.max_peer_timeout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::v2_0_0::core::PrivateMode;
+    use torrust_tracker_test_helpers::configuration;
+
+    use super::Settings;
+
+    fn private_mode_overriding_the_settings() -> PrivateMode {
+        PrivateMode {
+            check_keys_expiration: true,
+            inactive_peer_cleanup_interval: Some(1800),
+            max_peer_timeout: Some(3600),
+        }
+    }
+
+    #[test]
+    fn it_should_use_the_general_settings_in_public_mode() {
+        let mut configuration = configuration::ephemeral_public();
+
+        configuration.core.private_mode = Some(private_mode_overriding_the_settings());
+
+        assert_eq!(
+            Settings::from_config(&configuration.core),
+            Settings {
+                inactive_peer_cleanup_interval: configuration.core.inactive_peer_cleanup_interval,
+                max_peer_timeout: configuration.core.tracker_policy.max_peer_timeout,
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_use_the_private_mode_settings_in_private_mode() {
+        let mut configuration = configuration::ephemeral_private();
+
+        configuration.core.private_mode = Some(private_mode_overriding_the_settings());
+
+        assert_eq!(
+            Settings::from_config(&configuration.core),
+            Settings {
+                inactive_peer_cleanup_interval: 1800,
+                max_peer_timeout: 3600,
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_fall_back_to_the_general_settings_when_the_private_mode_does_not_override_them() {
+        let mut configuration = configuration::ephemeral_private();
+
+        configuration.core.private_mode = Some(PrivateMode::default());
+
+        assert_eq!(
+            Settings::from_config(&configuration.core),
+            Settings {
+                inactive_peer_cleanup_interval: configuration.core.inactive_peer_cleanup_interval,
+                max_peer_timeout: configuration.core.tracker_policy.max_peer_timeout,
+            }
+        );
+    }
+}
@@ -21,6 +21,9 @@ fn it_should_be_serializable() {
         downloaded: NumberOfBytes::new(0),
         left: NumberOfBytes::new(0),
         event: AnnounceEvent::Started,
+        partial_seed: false,
+        corrupt: NumberOfBytes::new(0),
+        redundant: NumberOfBytes::new(0),
     };
 
     let raw_json = serde_json::to_string(&torrent_peer).unwrap();
@@ -36,7 +39,10 @@ fn it_should_be_serializable() {
                 "uploaded":0,
                 "downloaded":0,
                 "left":0,
-                "event":"Started"
+                "event":"Started",
+                "partial_seed":false,
+                "corrupt":0,
+                "redundant":0
             }
         "#;
 
@@ -0,0 +1,652 @@
+//! Peer reachability verification.
+//!
+//! Peers announce the port where they accept incoming connections, but some of
+//! them are behind a NAT or a firewall and nobody can connect to them. When the
+//! verification is enabled, the tracker probes a sample of the newly announced
+//! peers to check whether the announced address is reachable:
+//!
+//! 1. It tries to open a TCP connection (TCP SYN) to the announced port.
+//! 2. If that fails, it sends a [uTP](https://www.bittorrent.org/beps/bep_0029.html)
+//!    `ST_SYN` packet to the same port and waits for the `ST_STATE` answer.
+//!
+//! Unreachable peers are not removed from the swarm, they are only returned
+//! after the reachable (or not verified yet) ones in the `announce` responses.
+//!
+//! Probes are never sent from the `announce` path. Sampled peers are queued in
+//! a bounded queue and a background job consumes the queue with a strict rate
+//! limit (`max_probes_per_second`). Samples are discarded when the queue is
+//! full.
+//!
+//! The results are kept up to `max_results`. The result of a peer is dropped
+//! when the peer leaves the swarm, or when it gets older than the peer
+//! timeout.
+//!
+//! When the tracker is behind a reverse proxy, the peer address comes from the
+//! `X-Forwarded-For` header. It's only probed when the request comes from one
+//! of the `trusted_proxies`, otherwise anybody could make the tracker probe
+//! arbitrary addresses.
+//!
+//! Refer to the [`PeerReachability`] configuration for more information.
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use tokio::net::{TcpStream, UdpSocket};
+use torrust_tracker_configuration::PeerReachability;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+/// uTP packet type `ST_STATE` (see BEP 29).
+const UTP_ST_STATE: u8 = 2;
+
+/// uTP packet type `ST_SYN` (see BEP 29).
+const UTP_ST_SYN: u8 = 4;
+
+/// uTP protocol version (see BEP 29).
+const UTP_VERSION: u8 = 1;
+
+/// The result of probing a peer address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reachability {
+    Reachable,
+    Unreachable,
+}
+
+/// The probe results for each peer address of a torrent, with the time of
+/// the probe.
+type TorrentResults = HashMap<SocketAddr, (Reachability, DurationSinceUnixEpoch)>;
+
+/// The probe results of all the torrents.
+#[derive(Debug, Default)]
+struct Results {
+    torrents: HashMap<InfoHash, TorrentResults>,
+    len: usize,
+}
+
+impl Results {
+    fn get(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Option<Reachability> {
+        self.torrents
+            .get(info_hash)
+            .and_then(|peers| peers.get(peer_addr))
+            .map(|(reachability, _)| *reachability)
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&InfoHash, &SocketAddr, DurationSinceUnixEpoch) -> bool) {
+        let mut len = 0;
+
+        self.torrents.retain(|info_hash, peers| {
+            peers.retain(|peer_addr, (_, probed_at)| keep(info_hash, peer_addr, *probed_at));
+            len += peers.len();
+            !peers.is_empty()
+        });
+
+        self.len = len;
+    }
+}
+
+/// A peer address waiting to be probed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Probe {
+    pub info_hash: InfoHash,
+    pub peer_addr: SocketAddr,
+}
+
+/// It samples the announced peers, keeps the queue of pending probes and the
+/// probe results.
+#[derive(Debug)]
+pub struct Verifier {
+    config: PeerReachability,
+    announces: AtomicU64,
+    queue: Mutex<VecDeque<Probe>>,
+    results: RwLock<Results>,
+}
+
+impl Verifier {
+    #[must_use]
+    pub fn new(config: &PeerReachability) -> Self {
+        Self {
+            config: *config,
+            announces: AtomicU64::new(0),
+            queue: Mutex::new(VecDeque::with_capacity(config.queue_capacity)),
+            results: RwLock::default(),
+        }
+    }
+
+    /// It queues the peer address to be probed if the announce is sampled, the
+    /// address has not been probed yet and there is room for its result.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue or results locks are poisoned.
+    pub fn sample(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) {
+        let announce = self.announces.fetch_add(1, Ordering::Relaxed);
+
+        if announce % u64::from(self.config.sample_one_in.max(1)) != 0 {
+            return;
+        }
+
+        {
+            let results = self.results.read().expect("it should get the results lock");
+
+            if results.len >= self.config.max_results || results.get(info_hash, peer_addr).is_some() {
+                return;
+            }
+        }
+
+        let mut queue = self.queue.lock().expect("it should get the queue lock");
+
+        if queue.len() < self.config.queue_capacity {
+            queue.push_back(Probe {
+                info_hash: *info_hash,
+                peer_addr: *peer_addr,
+            });
+        }
+    }
+
+    /// It removes the next pending probe from the queue.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the queue lock is poisoned.
+    #[must_use]
+    pub fn next_probe(&self) -> Option<Probe> {
+        self.queue.lock().expect("it should get the queue lock").pop_front()
+    }
+
+    /// It stores the result of a probe. The result of a new peer address is
+    /// discarded when there are already `max_results` results.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    pub fn record(&self, probe: &Probe, reachability: Reachability, now: DurationSinceUnixEpoch) {
+        let mut results = self.results.write().expect("it should get the results lock");
+
+        let is_full = results.len >= self.config.max_results;
+
+        let peers = results.torrents.entry(probe.info_hash).or_default();
+
+        match peers.get_mut(&probe.peer_addr) {
+            Some(result) => *result = (reachability, now),
+            None if is_full => {}
+            None => {
+                peers.insert(probe.peer_addr, (reachability, now));
+                results.len += 1;
+            }
+        }
+    }
+
+    /// It removes the result of the peer address, for example, when the peer
+    /// leaves the swarm.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    pub fn remove(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) {
+        let mut results = self.results.write().expect("it should get the results lock");
+
+        let Some(peers) = results.torrents.get_mut(info_hash) else {
+            return;
+        };
+
+        if peers.remove(peer_addr).is_some() {
+            if peers.is_empty() {
+                results.torrents.remove(info_hash);
+            }
+
+            results.len -= 1;
+        }
+    }
+
+    /// It returns the result of the last probe to the peer address, if any.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    #[must_use]
+    pub fn get(&self, info_hash: &InfoHash, peer_addr: &SocketAddr) -> Option<Reachability> {
+        self.results
+            .read()
+            .expect("it should get the results lock")
+            .get(info_hash, peer_addr)
+    }
+
+    /// It returns up to `limit` peers, the ones known to be unreachable after
+    /// the other ones.
+    ///
+    /// The peers are consumed in a single pass that stops as soon as there
+    /// are `limit` peers not known to be unreachable.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    #[must_use]
+    pub fn reachable_first(
+        &self,
+        info_hash: &InfoHash,
+        peers: impl IntoIterator<Item = Arc<peer::Peer>>,
+        limit: usize,
+    ) -> Vec<Arc<peer::Peer>> {
+        let mut listed = Vec::new();
+        let mut unreachable = Vec::new();
+
+        {
+            let results = self.results.read().expect("it should get the results lock");
+
+            let probed = results.torrents.get(info_hash);
+
+            for peer in peers {
+                if listed.len() == limit {
+                    break;
+                }
+
+                let is_unreachable = probed
+                    .and_then(|probed| probed.get(&peer.peer_addr))
+                    .is_some_and(|(reachability, _)| *reachability == Reachability::Unreachable);
+
+                if !is_unreachable {
+                    listed.push(peer);
+                } else if unreachable.len() < limit {
+                    unreachable.push(peer);
+                }
+            }
+        }
+
+        let missing = limit - listed.len();
+
+        listed.extend(unreachable.into_iter().take(missing));
+
+        listed
+    }
+
+    /// It returns the ratio of reachable peers over the probed peers for a
+    /// torrent, or `None` if no peer has been probed yet.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    #[must_use]
+    pub fn get_ratio(&self, info_hash: &InfoHash) -> Option<f64> {
+        let results = self.results.read().expect("it should get the results lock");
+
+        let peers = results.torrents.get(info_hash)?;
+
+        if peers.is_empty() {
+            return None;
+        }
+
+        let reachable = peers
+            .values()
+            .filter(|(reachability, _)| *reachability == Reachability::Reachable)
+            .count();
+
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = reachable as f64 / peers.len() as f64;
+
+        Some(ratio)
+    }
+
+    /// It removes the results older than the `cutoff`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    pub fn remove_older_than(&self, cutoff: DurationSinceUnixEpoch) {
+        self.results
+            .write()
+            .expect("it should get the results lock")
+            .retain(|_, _, probed_at| probed_at > cutoff);
+    }
+
+    /// It returns the torrents with probe results.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    #[must_use]
+    pub fn probed_torrents(&self) -> Vec<InfoHash> {
+        self.results
+            .read()
+            .expect("it should get the results lock")
+            .torrents
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// It removes the results of the peers that are no longer in the swarms.
+    /// The torrents missing in `swarms` have been removed.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    pub fn retain_swarm_peers(&self, swarms: &HashMap<InfoHash, HashSet<SocketAddr>>) {
+        self.results
+            .write()
+            .expect("it should get the results lock")
+            .retain(|info_hash, peer_addr, _| swarms.get(info_hash).is_some_and(|swarm| swarm.contains(peer_addr)));
+    }
+
+    /// The number of probe results kept.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the results lock is poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.read().expect("it should get the results lock").len
+    }
+
+    /// It returns `true` if there are no probe results.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// It checks if the peer address accepts TCP or uTP connections.
+pub async fn probe(peer_addr: &SocketAddr, timeout: Duration) -> Reachability {
+    if probe_tcp(peer_addr, timeout).await || probe_utp(peer_addr, timeout).await {
+        Reachability::Reachable
+    } else {
+        Reachability::Unreachable
+    }
+}
+
+async fn probe_tcp(peer_addr: &SocketAddr, timeout: Duration) -> bool {
+    matches!(tokio::time::timeout(timeout, TcpStream::connect(peer_addr)).await, Ok(Ok(_)))
+}
+
+async fn probe_utp(peer_addr: &SocketAddr, timeout: Duration) -> bool {
+    let local_addr = match peer_addr {
+        SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+        SocketAddr::V6(_) => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+    };
+
+    let Ok(socket) = UdpSocket::bind(local_addr).await else {
+        return false;
+    };
+
+    if socket.send_to(&utp_syn_packet(), peer_addr).await.is_err() {
+        return false;
+    }
+
+    let mut buffer = [0u8; 64];
+
+    let answer = tokio::time::timeout(timeout, async {
+        loop {
+            match socket.recv_from(&mut buffer).await {
+                Ok((len, from)) if from == *peer_addr => return len >= 20 && buffer[0] >> 4 == UTP_ST_STATE,
+                Ok(_) => {}
+                Err(_) => return false,
+            }
+        }
+    })
+    .await;
+
+    answer.unwrap_or(false)
+}
+
+/// It builds the 20-byte uTP `ST_SYN` packet used to open a connection.
+fn utp_syn_packet() -> [u8; 20] {
+    let connection_id: u16 = rand::random();
+    let seq_nr: u16 = 1;
+    let wnd_size: u32 = 0xf000;
+
+    let mut packet = [0u8; 20];
+
+    packet[0] = (UTP_ST_SYN << 4) | UTP_VERSION;
+    packet[2..4].copy_from_slice(&connection_id.to_be_bytes());
+    packet[12..16].copy_from_slice(&wnd_size.to_be_bytes());
+    packet[16..18].copy_from_slice(&seq_nr.to_be_bytes());
+
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::net::{TcpListener, UdpSocket};
+    use torrust_tracker_configuration::PeerReachability;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+    use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+    use super::{probe, utp_syn_packet, Probe, Reachability, Verifier, UTP_ST_STATE, UTP_VERSION};
+
+    fn verifier_probing_every_peer() -> Verifier {
+        Verifier::new(&PeerReachability {
+            sample_one_in: 1,
+            ..Default::default()
+        })
+    }
+
+    fn sample_peer_addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([126, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn it_should_queue_only_one_out_of_every_n_announced_peers() {
+        let verifier = Verifier::new(&PeerReachability {
+            sample_one_in: 2,
+            ..Default::default()
+        });
+
+        for port in 1..=4 {
+            verifier.sample(&InfoHash::default(), &sample_peer_addr(port));
+        }
+
+        assert_eq!(verifier.next_probe().unwrap().peer_addr, sample_peer_addr(1));
+        assert_eq!(verifier.next_probe().unwrap().peer_addr, sample_peer_addr(3));
+        assert!(verifier.next_probe().is_none());
+    }
+
+    #[test]
+    fn it_should_discard_samples_when_the_queue_is_full() {
+        let verifier = Verifier::new(&PeerReachability {
+            sample_one_in: 1,
+            queue_capacity: 1,
+            ..Default::default()
+        });
+
+        verifier.sample(&InfoHash::default(), &sample_peer_addr(1));
+        verifier.sample(&InfoHash::default(), &sample_peer_addr(2));
+
+        assert_eq!(verifier.next_probe().unwrap().peer_addr, sample_peer_addr(1));
+        assert!(verifier.next_probe().is_none());
+    }
+
+    #[test]
+    fn it_should_not_queue_peers_that_have_already_been_probed() {
+        let verifier = verifier_probing_every_peer();
+
+        let probe = Probe {
+            info_hash: InfoHash::default(),
+            peer_addr: sample_peer_addr(1),
+        };
+
+        verifier.record(&probe, Reachability::Reachable, DurationSinceUnixEpoch::ZERO);
+
+        verifier.sample(&probe.info_hash, &probe.peer_addr);
+
+        assert!(verifier.next_probe().is_none());
+    }
+
+    #[test]
+    fn it_should_calculate_the_reachability_ratio_for_a_torrent() {
+        let verifier = verifier_probing_every_peer();
+
+        assert_eq!(verifier.get_ratio(&InfoHash::default()), None);
+
+        for (port, reachability) in [
+            (1, Reachability::Reachable),
+            (2, Reachability::Unreachable),
+            (3, Reachability::Unreachable),
+            (4, Reachability::Reachable),
+        ] {
+            let probe = Probe {
+                info_hash: InfoHash::default(),
+                peer_addr: sample_peer_addr(port),
+            };
+
+            verifier.record(&probe, reachability, DurationSinceUnixEpoch::ZERO);
+        }
+
+        assert_eq!(verifier.get_ratio(&InfoHash::default()), Some(0.5));
+    }
+
+    #[test]
+    fn it_should_remove_the_results_older_than_a_cutoff() {
+        let verifier = verifier_probing_every_peer();
+
+        let probe = Probe {
+            info_hash: InfoHash::default(),
+            peer_addr: sample_peer_addr(1),
+        };
+
+        verifier.record(&probe, Reachability::Reachable, DurationSinceUnixEpoch::from_secs(10));
+
+        verifier.remove_older_than(DurationSinceUnixEpoch::from_secs(20));
+
+        assert_eq!(verifier.get(&probe.info_hash, &probe.peer_addr), None);
+        assert_eq!(verifier.get_ratio(&probe.info_hash), None);
+    }
+
+    #[test]
+    fn it_should_discard_the_results_of_new_peers_when_the_limit_is_reached() {
+        let verifier = Verifier::new(&PeerReachability {
+            sample_one_in: 1,
+            max_results: 1,
+            ..Default::default()
+        });
+
+        let first = Probe {
+            info_hash: InfoHash::default(),
+            peer_addr: sample_peer_addr(1),
+        };
+        let second = Probe {
+            info_hash: InfoHash::default(),
+            peer_addr: sample_peer_addr(2),
+        };
+
+        verifier.record(&first, Reachability::Reachable, DurationSinceUnixEpoch::ZERO);
+        verifier.record(&second, Reachability::Reachable, DurationSinceUnixEpoch::ZERO);
+        verifier.record(&first, Reachability::Unreachable, DurationSinceUnixEpoch::ZERO);
+
+        assert_eq!(verifier.len(), 1);
+        assert_eq!(
+            verifier.get(&first.info_hash, &first.peer_addr),
+            Some(Reachability::Unreachable)
+        );
+        assert_eq!(verifier.get(&second.info_hash, &second.peer_addr), None);
+
+        verifier.sample(&second.info_hash, &second.peer_addr);
+
+        assert!(verifier.next_probe().is_none());
+    }
+
+    #[test]
+    fn it_should_remove_the_results_of_the_peers_that_are_no_longer_in_the_swarms() {
+        let verifier = verifier_probing_every_peer();
+
+        let removed_torrent = InfoHash::from([1u8; 20]);
+
+        for (info_hash, port) in [(InfoHash::default(), 1), (InfoHash::default(), 2), (removed_torrent, 3)] {
+            let probe = Probe {
+                info_hash,
+                peer_addr: sample_peer_addr(port),
+            };
+
+            verifier.record(&probe, Reachability::Reachable, DurationSinceUnixEpoch::ZERO);
+        }
+
+        let swarms = HashMap::from([(InfoHash::default(), HashSet::from([sample_peer_addr(1)]))]);
+
+        verifier.retain_swarm_peers(&swarms);
+
+        assert_eq!(verifier.len(), 1);
+        assert_eq!(verifier.probed_torrents(), vec![InfoHash::default()]);
+        assert!(verifier.get(&InfoHash::default(), &sample_peer_addr(1)).is_some());
+    }
+
+    #[test]
+    fn it_should_return_the_unreachable_peers_last_and_stop_at_the_limit() {
+        let verifier = verifier_probing_every_peer();
+
+        let peers: Vec<Arc<peer::Peer>> = (1..=4)
+            .map(|port| Arc::new(PeerBuilder::default().with_peer_addr(&sample_peer_addr(port)).build()))
+            .collect();
+
+        for port in [1, 2] {
+            let probe = Probe {
+                info_hash: InfoHash::default(),
+                peer_addr: sample_peer_addr(port),
+            };
+
+            verifier.record(&probe, Reachability::Unreachable, DurationSinceUnixEpoch::ZERO);
+        }
+
+        let addrs = |peers: Vec<Arc<peer::Peer>>| peers.iter().map(|peer| peer.peer_addr.port()).collect::<Vec<_>>();
+
+        assert_eq!(
+            addrs(verifier.reachable_first(&InfoHash::default(), peers.clone(), 3)),
+            vec![3, 4, 1]
+        );
+        assert_eq!(addrs(verifier.reachable_first(&InfoHash::default(), peers, 1)), vec![3]);
+    }
+
+    #[test]
+    fn it_should_build_a_valid_utp_syn_packet() {
+        let packet = utp_syn_packet();
+
+        assert_eq!(packet[0], 0x41);
+        assert_eq!(packet[0] & 0x0f, UTP_VERSION);
+    }
+
+    #[tokio::test]
+    async fn a_peer_accepting_tcp_connections_should_be_reachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        let reachability = probe(&listener.local_addr().unwrap(), Duration::from_secs(1)).await;
+
+        assert_eq!(reachability, Reachability::Reachable);
+    }
+
+    #[tokio::test]
+    async fn a_peer_answering_the_utp_handshake_should_be_reachable() {
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut buffer = [0u8; 64];
+            let (_, from) = peer.recv_from(&mut buffer).await.unwrap();
+
+            let mut state = [0u8; 20];
+            state[0] = (UTP_ST_STATE << 4) | UTP_VERSION;
+
+            peer.send_to(&state, from).await.unwrap();
+        });
+
+        let reachability = probe(&peer_addr, Duration::from_secs(1)).await;
+
+        assert_eq!(reachability, Reachability::Reachable);
+    }
+
+    #[tokio::test]
+    async fn a_peer_not_accepting_connections_should_be_unreachable() {
+        // Reserve a port and release it so that nobody is listening on it.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer_addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let reachability = probe(&peer_addr, Duration::from_millis(200)).await;
+
+        assert_eq!(reachability, Reachability::Unreachable);
+    }
+}
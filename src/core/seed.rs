@@ -0,0 +1,272 @@
+//! Declarative initial state of the tracker.
+//!
+//! Reproducible deployments (Docker, Nix, ...) can declare the whitelist, the
+//! authentication keys and the torrent policies the tracker should start with
+//! in the `[seed]` configuration section, instead of calling the API after the
+//! tracker starts:
+//!
+//! ```toml
+//! [seed]
+//! whitelist = ["9c38422213e30bff212b30c360d26f9a02136422"]
+//!
+//! [[seed.keys]]
+//! key = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"
+//!
+//! [[seed.torrents]]
+//! info_hash = "9c38422213e30bff212b30c360d26f9a02136422"
+//! policy = "seed-only"
+//! ```
+//!
+//! The seed is written to the database when the tracker starts, before the
+//! keys, the whitelist and the policies are loaded into memory. It's
+//! idempotent: only the missing entries are added, and the existing ones are
+//! never modified.
+//!
+//! The whole seed is parsed before writing anything, so a typo does not leave
+//! a partially seeded database.
+//!
+//! Refer to the [`Seed`] configuration for more information.
+use std::panic::Location;
+use std::str::FromStr;
+use std::time::Duration;
+
+use torrust_tracker_configuration::Seed;
+use torrust_tracker_located_error::{Located, LocatedError};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::auth::{Key, ParseKeyError, PeerKey};
+use super::databases::{self, Database};
+use super::torrent::policy::{ParseTorrentPolicyError, TorrentPolicy};
+
+/// Error returned when the seed can't be applied.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Invalid info-hash in the seed: {info_hash}, {location}")]
+    InvalidInfoHash {
+        info_hash: String,
+        location: &'static Location<'static>,
+    },
+
+    #[error("Invalid key in the seed: {key}, {source}")]
+    InvalidKey {
+        key: String,
+        source: LocatedError<'static, ParseKeyError>,
+    },
+
+    #[error("Invalid torrent policy in the seed: {source}")]
+    InvalidPolicy {
+        source: LocatedError<'static, ParseTorrentPolicyError>,
+    },
+
+    #[error("Can't write the seed to the database: {source}")]
+    DatabaseError {
+        source: LocatedError<'static, databases::error::Error>,
+    },
+}
+
+impl From<databases::error::Error> for Error {
+    #[track_caller]
+    fn from(err: databases::error::Error) -> Self {
+        Self::DatabaseError {
+            source: Located(err).into(),
+        }
+    }
+}
+
+/// The number of entries added by the seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Applied {
+    pub whitelisted: usize,
+    pub keys: usize,
+    pub torrents: usize,
+}
+
+/// It adds the missing entries of the seed to the database.
+///
+/// # Errors
+///
+/// Will return an error if the seed contains an invalid value or the database
+/// can't be read or written.
+pub fn apply(database: &dyn Database, seed: &Seed) -> Result<Applied, Error> {
+    let whitelist = seed
+        .whitelist
+        .iter()
+        .map(|info_hash| parse_info_hash(info_hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let keys = seed
+        .keys
+        .iter()
+        .map(|seed_key| {
+            Ok(PeerKey {
+                key: Key::from_str(&seed_key.key).map_err(|err| Error::InvalidKey {
+                    key: seed_key.key.clone(),
+                    source: Located(err).into(),
+                })?,
+                valid_until: seed_key.valid_until.map(Duration::from_secs),
+                bound_to: None,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let torrents = seed
+        .torrents
+        .iter()
+        .map(|torrent| {
+            Ok((
+                parse_info_hash(&torrent.info_hash)?,
+                TorrentPolicy::from_str(&torrent.policy).map_err(|err| Error::InvalidPolicy {
+                    source: Located(err).into(),
+                })?,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut applied = Applied::default();
+
+    for info_hash in whitelist {
+        if !database.is_info_hash_whitelisted(info_hash)? {
+            database.add_info_hash_to_whitelist(info_hash)?;
+            applied.whitelisted += 1;
+        }
+    }
+
+    for peer_key in keys {
+        if database.get_key_from_keys(&peer_key.key)?.is_none() {
+            database.add_key_to_keys(&peer_key)?;
+            applied.keys += 1;
+        }
+    }
+
+    let policies = database.load_torrent_policies()?;
+
+    for (info_hash, policy) in torrents {
+        if !policies.iter().any(|(existing, _)| *existing == info_hash) {
+            database.save_torrent_policy(&info_hash, policy)?;
+            applied.torrents += 1;
+        }
+    }
+
+    Ok(applied)
+}
+
+#[track_caller]
+fn parse_info_hash(info_hash: &str) -> Result<InfoHash, Error> {
+    InfoHash::from_str(info_hash).map_err(|_| Error::InvalidInfoHash {
+        info_hash: info_hash.to_owned(),
+        location: Location::caller(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use torrust_tracker_configuration::{Seed, SeedKey, SeedTorrent};
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_test_helpers::random;
+
+    use super::{apply, Applied, Error};
+    use crate::core::auth::Key;
+    use crate::core::databases::driver::{self, Driver};
+    use crate::core::databases::Database;
+    use crate::core::torrent::policy::TorrentPolicy;
+
+    const INFO_HASH: &str = "9c38422213e30bff212b30c360d26f9a02136422";
+    const KEY: &str = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ";
+
+    fn database() -> Box<dyn Database> {
+        let path = env::temp_dir().join(format!("seed_{}.db", random::string(16)));
+        driver::build(&Driver::Sqlite3, path.to_str().unwrap()).unwrap()
+    }
+
+    fn seed() -> Seed {
+        Seed {
+            whitelist: vec![INFO_HASH.to_string()],
+            keys: vec![SeedKey {
+                key: KEY.to_string(),
+                valid_until: Some(60),
+            }],
+            torrents: vec![SeedTorrent {
+                info_hash: INFO_HASH.to_string(),
+                policy: "seed-only".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn it_should_add_the_seed_to_an_empty_database() {
+        let database = database();
+
+        let applied = apply(database.as_ref(), &seed()).unwrap();
+
+        let info_hash = InfoHash::from_str(INFO_HASH).unwrap();
+
+        assert_eq!(
+            applied,
+            Applied {
+                whitelisted: 1,
+                keys: 1,
+                torrents: 1
+            }
+        );
+        assert!(database.is_info_hash_whitelisted(info_hash).unwrap());
+        assert_eq!(
+            database
+                .get_key_from_keys(&Key::from_str(KEY).unwrap())
+                .unwrap()
+                .unwrap()
+                .valid_until,
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            database.load_torrent_policies().unwrap(),
+            vec![(info_hash, TorrentPolicy::SeedOnly)]
+        );
+    }
+
+    #[test]
+    fn it_should_be_idempotent() {
+        let database = database();
+
+        apply(database.as_ref(), &seed()).unwrap();
+
+        let applied = apply(database.as_ref(), &seed()).unwrap();
+
+        assert_eq!(applied, Applied::default());
+        assert_eq!(database.load_whitelist().unwrap().len(), 1);
+        assert_eq!(database.load_keys().unwrap().len(), 1);
+        assert_eq!(database.load_torrent_policies().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_not_modify_the_existing_entries() {
+        let database = database();
+
+        let info_hash = InfoHash::from_str(INFO_HASH).unwrap();
+
+        database.save_torrent_policy(&info_hash, TorrentPolicy::Frozen).unwrap();
+
+        let applied = apply(database.as_ref(), &seed()).unwrap();
+
+        assert_eq!(applied.torrents, 0);
+        assert_eq!(
+            database.load_torrent_policies().unwrap(),
+            vec![(info_hash, TorrentPolicy::Frozen)]
+        );
+    }
+
+    #[test]
+    fn it_should_not_write_anything_when_the_seed_contains_an_invalid_value() {
+        let database = database();
+
+        let mut seed = seed();
+        seed.torrents[0].policy = "paused".to_string();
+
+        assert!(matches!(apply(database.as_ref(), &seed), Err(Error::InvalidPolicy { .. })));
+        assert!(database.load_whitelist().unwrap().is_empty());
+        assert!(database.load_keys().unwrap().is_empty());
+    }
+}
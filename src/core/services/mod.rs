@@ -3,13 +3,13 @@
 //! There are two types of service:
 //!
 //! - [Core tracker services](crate::core::services::torrent): related to the tracker main functionalities like getting info about torrents.
+//!   They are only used by the tracker API, so they are built with the `api` feature.
 //! - [Services for statistics](crate::core::services::statistics): related to tracker metrics. Aggregate data about the tracker server.
 pub mod statistics;
+#[cfg(feature = "api")]
 pub mod torrent;
 
-use std::sync::Arc;
-
-use torrust_tracker_configuration::Configuration;
+use torrust_tracker_configuration::{Configuration, Core};
 
 use crate::core::Tracker;
 
@@ -20,14 +20,34 @@ use crate::core::Tracker;
 /// Will panic if tracker cannot be instantiated.
 #[must_use]
 pub fn tracker_factory(config: &Configuration) -> Tracker {
+    core_tracker_factory(&config.core)
+}
+
+/// It returns a new tracker for the given core configuration, building its
+/// dependencies. It's used for the [virtual trackers](crate::core::virtual_trackers).
+///
+/// # Panics
+///
+/// Will panic if tracker cannot be instantiated.
+#[must_use]
+pub fn core_tracker_factory(core: &Core) -> Tracker {
     // Initialize statistics
-    let (stats_event_sender, stats_repository) = statistics::setup::factory(config.core.tracker_usage_statistics);
+    let (stats_event_sender, stats_repository) = statistics::setup::factory(core.tracker_usage_statistics, core.statistics);
+
+    let domain_events_stats_repository = core.tracker_usage_statistics.then(|| stats_repository.clone());
 
     // Initialize Torrust tracker
-    match Tracker::new(&Arc::new(config).core, stats_event_sender, stats_repository) {
+    let tracker = match Tracker::new(core, stats_event_sender, stats_repository) {
         Ok(tracker) => tracker,
         Err(error) => {
             panic!("{}", error)
         }
+    };
+
+    // The statistics subscribe to the domain events of the tracker
+    if let Some(stats_repository) = domain_events_stats_repository {
+        crate::core::statistics::run_domain_event_listener(tracker.subscribe(), stats_repository);
     }
+
+    tracker
 }
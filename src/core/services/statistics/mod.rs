@@ -13,7 +13,7 @@
 //! - An statistics [`Repo`](crate::core::statistics::Repo)
 //!
 //! ```text
-//! let (stats_event_sender, stats_repository) = factory(tracker_usage_statistics);
+//! let (stats_event_sender, stats_repository) = factory(tracker_usage_statistics, statistics);
 //! ```
 //!
 //! The statistics repository is responsible for storing the metrics in memory.
@@ -70,15 +70,44 @@ pub async fn get_metrics(tracker: Arc<Tracker>) -> TrackerMetrics {
             tcp4_connections_handled: stats.tcp4_connections_handled,
             tcp4_announces_handled: stats.tcp4_announces_handled,
             tcp4_scrapes_handled: stats.tcp4_scrapes_handled,
+            tcp4_scrapes_exceeding_limit: stats.tcp4_scrapes_exceeding_limit,
             tcp6_connections_handled: stats.tcp6_connections_handled,
             tcp6_announces_handled: stats.tcp6_announces_handled,
             tcp6_scrapes_handled: stats.tcp6_scrapes_handled,
+            tcp6_scrapes_exceeding_limit: stats.tcp6_scrapes_exceeding_limit,
+            tcp_announce_responses_under_128_bytes: stats.tcp_announce_responses_under_128_bytes,
+            tcp_announce_responses_under_512_bytes: stats.tcp_announce_responses_under_512_bytes,
+            tcp_announce_responses_under_2048_bytes: stats.tcp_announce_responses_under_2048_bytes,
+            tcp_announce_responses_over_2048_bytes: stats.tcp_announce_responses_over_2048_bytes,
+            tcp_announce_responses_padded: stats.tcp_announce_responses_padded,
+            tcp_announces_with_unknown_event: stats.tcp_announces_with_unknown_event,
+            tcp_scrape_duplicate_info_hashes: stats.tcp_scrape_duplicate_info_hashes,
+            tcp_requests_with_uri_too_long: stats.tcp_requests_with_uri_too_long,
+            tcp_requests_with_headers_too_large: stats.tcp_requests_with_headers_too_large,
+            tcp_announces_with_deadline_exceeded: stats.tcp_announces_with_deadline_exceeded,
+            tcp_scrapes_with_deadline_exceeded: stats.tcp_scrapes_with_deadline_exceeded,
+            tcp_announces_with_key_bound_to_another_address: stats.tcp_announces_with_key_bound_to_another_address,
             udp4_connections_handled: stats.udp4_connections_handled,
             udp4_announces_handled: stats.udp4_announces_handled,
             udp4_scrapes_handled: stats.udp4_scrapes_handled,
+            udp4_scrapes_exceeding_limit: stats.udp4_scrapes_exceeding_limit,
+            udp4_connection_ids_used: stats.udp4_connection_ids_used,
+            udp4_connection_ids_unused: stats.udp4_connection_ids_unused,
+            udp4_announces_with_expired_connection_id: stats.udp4_announces_with_expired_connection_id,
             udp6_connections_handled: stats.udp6_connections_handled,
             udp6_announces_handled: stats.udp6_announces_handled,
             udp6_scrapes_handled: stats.udp6_scrapes_handled,
+            udp6_scrapes_exceeding_limit: stats.udp6_scrapes_exceeding_limit,
+            udp6_connection_ids_used: stats.udp6_connection_ids_used,
+            udp6_connection_ids_unused: stats.udp6_connection_ids_unused,
+            udp6_announces_with_expired_connection_id: stats.udp6_announces_with_expired_connection_id,
+            udp_announces_with_unknown_event: stats.udp_announces_with_unknown_event,
+            udp_scrape_duplicate_info_hashes: stats.udp_scrape_duplicate_info_hashes,
+            udp_negative_cache_hits: stats.udp_negative_cache_hits,
+            udp_announces_with_deadline_exceeded: stats.udp_announces_with_deadline_exceeded,
+            udp_scrapes_with_deadline_exceeded: stats.udp_scrapes_with_deadline_exceeded,
+            torrents_created: stats.torrents_created,
+            torrents_removed: stats.torrents_removed,
         },
     }
 }
@@ -1,6 +1,8 @@
 //! Setup for the tracker statistics.
 //!
 //! The [`factory`] function builds the structs needed for handling the tracker metrics.
+use torrust_tracker_configuration::Statistics;
+
 use crate::core::statistics;
 
 /// It builds the structs needed for handling the tracker metrics.
@@ -12,14 +14,19 @@ use crate::core::statistics;
 ///
 /// When the input argument `tracker_usage_statistics`is false the setup does not run the event listeners, consequently the statistics
 /// events are sent are received but not dispatched to the handler.
+///
+/// Otherwise, only the events collected with the `statistics` configuration are sent.
 #[must_use]
-pub fn factory(tracker_usage_statistics: bool) -> (Option<Box<dyn statistics::EventSender>>, statistics::Repo) {
+pub fn factory(
+    tracker_usage_statistics: bool,
+    statistics: Statistics,
+) -> (Option<Box<dyn statistics::EventSender>>, statistics::Repo) {
     let mut stats_event_sender = None;
 
     let mut stats_tracker = statistics::Keeper::new();
 
     if tracker_usage_statistics {
-        stats_event_sender = Some(stats_tracker.run_event_listener());
+        stats_event_sender = Some(stats_tracker.run_event_listener(statistics));
     }
 
     (stats_event_sender, stats_tracker.repository)
@@ -27,13 +34,15 @@ pub fn factory(tracker_usage_statistics: bool) -> (Option<Box<dyn statistics::Ev
 
 #[cfg(test)]
 mod test {
+    use torrust_tracker_configuration::Statistics;
+
     use super::factory;
 
     #[tokio::test]
     async fn should_not_send_any_event_when_statistics_are_disabled() {
         let tracker_usage_statistics = false;
 
-        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics);
+        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics, Statistics::default());
 
         assert!(stats_event_sender.is_none());
     }
@@ -42,7 +51,7 @@ mod test {
     async fn should_send_events_when_statistics_are_enabled() {
         let tracker_usage_statistics = true;
 
-        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics);
+        let (stats_event_sender, _stats_repository) = factory(tracker_usage_statistics, Statistics::default());
 
         assert!(stats_event_sender.is_some());
     }
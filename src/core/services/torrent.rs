@@ -4,15 +4,21 @@
 //!
 //! - [`get_torrent_info`]: it returns all the data about one torrent.
 //! - [`get_torrents`]: it returns data about some torrent in bulk excluding the peer list.
+//! - [`get_stale_torrents`]: it returns data about the torrents without announces for some time.
 use std::sync::Arc;
+use std::time::Duration;
 
+use aquatic_udp_protocol::NumberOfBytes;
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
-use torrust_tracker_primitives::peer;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 use torrust_tracker_torrent_repository::entry::EntrySync;
 use torrust_tracker_torrent_repository::repository::Repository;
 
+use crate::core::torrent::snapshot::Health;
 use crate::core::Tracker;
+use crate::CurrentClock;
 
 /// It contains all the information the tracker has about a torrent
 #[derive(Debug, PartialEq)]
@@ -25,8 +31,20 @@ pub struct Info {
     pub completed: u64,
     /// The total number of leechers for this torrent. Peers that actively downloading this torrent
     pub leechers: u64,
+    /// The number of leechers that have all the pieces they want (partial seeds). See BEP 21
+    pub partial_seeds: u64,
     /// The swarm: the list of peers that are actively trying to download or serving this torrent
     pub peers: Option<Vec<peer::Peer>>,
+    /// The total number of bytes that failed the hash check, as reported by the peers with the `corrupt` announce param
+    pub corrupt: u64,
+    /// The total number of bytes downloaded more than once, as reported by the peers with the `redundant` announce param
+    pub redundant: u64,
+    /// The number of peers that reported corrupt bytes. Many of them could indicate a poisoned swarm
+    pub peers_with_corrupt_data: u64,
+    /// The ratio of reachable peers over the probed peers. Only when the peer reachability verification is enabled
+    pub reachability_ratio: Option<f64>,
+    /// The health of the swarm. Only when the read snapshot is enabled and the torrent was in the last snapshot
+    pub health: Option<Health>,
 }
 
 /// It contains only part of the information the tracker has about a torrent
@@ -42,6 +60,24 @@ pub struct BasicInfo {
     pub completed: u64,
     /// The total number of leechers for this torrent. Peers that actively downloading this torrent
     pub leechers: u64,
+    /// The number of leechers that have all the pieces they want (partial seeds). See BEP 21
+    pub partial_seeds: u64,
+}
+
+/// It contains the information the tracker has about a stale torrent, that's
+/// a torrent without announces for some time.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StaleInfo {
+    /// The infohash of the torrent this data is related to
+    pub info_hash: InfoHash,
+    /// The total number of seeders for this torrent. Peer that actively serving a full copy of the torrent data
+    pub seeders: u64,
+    /// The total number of peers that have ever complete downloading this torrent
+    pub completed: u64,
+    /// The total number of leechers for this torrent. Peers that actively downloading this torrent
+    pub leechers: u64,
+    /// The time of the last announce for this torrent
+    pub last_announce_at: DurationSinceUnixEpoch,
 }
 
 /// It returns all the information the tracker has about one torrent in a [Info] struct.
@@ -54,14 +90,26 @@ pub async fn get_torrent_info(tracker: Arc<Tracker>, info_hash: &InfoHash) -> Op
 
     let peers = torrent_entry.get_peers(None);
 
-    let peers = Some(peers.iter().map(|peer| (**peer)).collect());
+    let bytes = |number_of_bytes: NumberOfBytes| u64::try_from(number_of_bytes.0.get()).unwrap_or_default();
+
+    let corrupt = peers.iter().map(|peer| bytes(peer.corrupt)).sum();
+    let redundant = peers.iter().map(|peer| bytes(peer.redundant)).sum();
+    let peers_with_corrupt_data = peers.iter().filter(|peer| bytes(peer.corrupt) > 0).count() as u64;
+
+    let peers = Some(peers.iter().map(|peer| **peer).collect());
 
     Some(Info {
         info_hash: *info_hash,
         seeders: u64::from(stats.complete),
         completed: u64::from(stats.downloaded),
         leechers: u64::from(stats.incomplete),
+        partial_seeds: u64::from(stats.partial_seeds),
         peers,
+        corrupt,
+        redundant,
+        peers_with_corrupt_data,
+        reachability_ratio: tracker.get_reachability_ratio(info_hash),
+        health: tracker.get_torrent_health(info_hash),
     })
 }
 
@@ -77,6 +125,7 @@ pub async fn get_torrents_page(tracker: Arc<Tracker>, pagination: Option<&Pagina
             seeders: u64::from(stats.complete),
             completed: u64::from(stats.downloaded),
             leechers: u64::from(stats.incomplete),
+            partial_seeds: u64::from(stats.partial_seeds),
         });
     }
 
@@ -94,6 +143,7 @@ pub async fn get_torrents(tracker: Arc<Tracker>, info_hashes: &[InfoHash]) -> Ve
                 seeders: u64::from(stats.complete),
                 completed: u64::from(stats.downloaded),
                 leechers: u64::from(stats.incomplete),
+                partial_seeds: u64::from(stats.partial_seeds),
             });
         }
     }
@@ -101,6 +151,33 @@ pub async fn get_torrents(tracker: Arc<Tracker>, info_hashes: &[InfoHash]) -> Ve
     basic_infos
 }
 
+/// It returns the torrents without announces for more than `inactive_for` in a [`StaleInfo`] struct.
+pub async fn get_stale_torrents(tracker: Arc<Tracker>, inactive_for: Duration) -> Vec<StaleInfo> {
+    let stale_cutoff = CurrentClock::now_sub(&inactive_for).unwrap_or_default();
+
+    let mut stale_infos: Vec<StaleInfo> = vec![];
+
+    for (info_hash, torrent_entry) in tracker.torrents.get_paginated(None) {
+        let last_announce_at = torrent_entry.get_last_announce_at();
+
+        if last_announce_at >= stale_cutoff {
+            continue;
+        }
+
+        let stats = torrent_entry.get_swarm_metadata();
+
+        stale_infos.push(StaleInfo {
+            info_hash,
+            seeders: u64::from(stats.complete),
+            completed: u64::from(stats.downloaded),
+            leechers: u64::from(stats.incomplete),
+            last_announce_at,
+        });
+    }
+
+    stale_infos
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -117,6 +194,9 @@ mod tests {
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            partial_seed: false,
+            corrupt: NumberOfBytes::new(0),
+            redundant: NumberOfBytes::new(0),
         }
     }
 
@@ -125,8 +205,10 @@ mod tests {
         use std::str::FromStr;
         use std::sync::Arc;
 
+        use aquatic_udp_protocol::PeerId;
         use torrust_tracker_configuration::Configuration;
         use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
 
         use crate::core::services::torrent::tests::sample_peer;
@@ -167,10 +249,46 @@ mod tests {
                     seeders: 1,
                     completed: 0,
                     leechers: 0,
+                    partial_seeds: 0,
                     peers: Some(vec![sample_peer()]),
+                    corrupt: 0,
+                    redundant: 0,
+                    peers_with_corrupt_data: 0,
+                    reachability_ratio: None,
+                    health: None,
                 }
             );
         }
+
+        #[tokio::test]
+        async fn should_aggregate_the_corrupt_and_redundant_bytes_reported_by_the_peers() {
+            let tracker = Arc::new(tracker_factory(&tracker_configuration()));
+
+            let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+            let peers = [
+                PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_corrupt_bytes(100)
+                    .with_redundant_bytes(10)
+                    .build(),
+                PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                    .with_corrupt_bytes(200)
+                    .build(),
+                PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000003")).build(),
+            ];
+
+            for peer in &peers {
+                tracker.upsert_peer_and_get_stats(&info_hash, peer);
+            }
+
+            let torrent_info = get_torrent_info(tracker.clone(), &info_hash).await.unwrap();
+
+            assert_eq!(torrent_info.corrupt, 300);
+            assert_eq!(torrent_info.redundant, 10);
+            assert_eq!(torrent_info.peers_with_corrupt_data, 2);
+        }
     }
 
     mod searching_for_torrents {
@@ -217,6 +335,7 @@ mod tests {
                     seeders: 1,
                     completed: 0,
                     leechers: 0,
+                    partial_seeds: 0,
                 }]
             );
         }
@@ -266,6 +385,7 @@ mod tests {
                     seeders: 1,
                     completed: 0,
                     leechers: 0,
+                    partial_seeds: 0,
                 }]
             );
         }
@@ -292,15 +412,69 @@ mod tests {
                         seeders: 1,
                         completed: 0,
                         leechers: 0,
+                        partial_seeds: 0,
                     },
                     BasicInfo {
                         info_hash: InfoHash::from_str(&hash1).unwrap(),
                         seeders: 1,
                         completed: 0,
                         leechers: 0,
+                        partial_seeds: 0,
                     }
                 ]
             );
         }
     }
+
+    mod searching_for_stale_torrents {
+
+        use std::str::FromStr;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use torrust_tracker_clock::clock;
+        use torrust_tracker_clock::clock::stopped::Stopped as _;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_primitives::peer;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::services::torrent::tests::sample_peer;
+        use crate::core::services::torrent::{get_stale_torrents, StaleInfo};
+        use crate::core::services::tracker_factory;
+
+        fn peer_announced_at(secs: u64) -> peer::Peer {
+            peer::Peer {
+                updated: Duration::from_secs(secs),
+                ..sample_peer()
+            }
+        }
+
+        #[tokio::test]
+        async fn should_return_the_torrents_without_announces_for_more_than_the_given_time() {
+            clock::Stopped::local_set(&Duration::from_secs(1000));
+
+            let tracker = Arc::new(tracker_factory(&configuration::ephemeral()));
+
+            let stale_info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+            tracker.upsert_peer_and_get_stats(&stale_info_hash, &peer_announced_at(100));
+
+            let active_info_hash = InfoHash::from_str("03840548643af2a7b63a9f5cbca348bc7150ca3a").unwrap();
+            tracker.upsert_peer_and_get_stats(&active_info_hash, &peer_announced_at(990));
+
+            let torrents = get_stale_torrents(tracker.clone(), Duration::from_secs(60)).await;
+
+            clock::Stopped::local_reset();
+
+            assert_eq!(
+                torrents,
+                vec![StaleInfo {
+                    info_hash: stale_info_hash,
+                    seeders: 1,
+                    completed: 0,
+                    leechers: 0,
+                    last_announce_at: Duration::from_secs(100),
+                }]
+            );
+        }
+    }
 }
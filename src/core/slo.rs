@@ -0,0 +1,267 @@
+//! Service level objectives (SLO) of the `announce` requests.
+//!
+//! When they are enabled, the UDP and HTTP trackers record the response time
+//! of every `announce` request, and whether it was answered with an error, in
+//! the [`Monitor`]. The requests are counted in one-second buckets, and only
+//! the buckets in the last `window_secs` seconds are evaluated.
+//!
+//! The latency objective "p99 below 50 milliseconds" is met when at most 1%
+//! of the requests in the window took 50 milliseconds or more. So only the
+//! number of slow requests is kept, instead of all the response times.
+//!
+//! The health check API reports the tracker as `Degraded` while an objective
+//! is not met, and the [SLO job](crate::bootstrap::jobs::slo) notifies the
+//! webhook when it starts and stops being met.
+//!
+//! Refer to the [`Slo`] configuration for more information.
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::Slo;
+
+use crate::CurrentClock;
+
+/// The `announce` requests in one second.
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    /// Seconds since the Unix epoch.
+    second: u64,
+    requests: u64,
+    errors: u64,
+    slow: u64,
+}
+
+/// It keeps the outcome of the `announce` requests in the sliding window.
+#[derive(Debug)]
+pub struct Monitor {
+    config: Slo,
+    buckets: Mutex<VecDeque<Bucket>>,
+}
+
+impl Monitor {
+    #[must_use]
+    pub fn new(config: &Slo) -> Self {
+        Self {
+            config: config.clone(),
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// The webhook notified when the objectives start or stop being met.
+    #[must_use]
+    pub fn webhook_url(&self) -> Option<&str> {
+        self.config.webhook_url.as_deref()
+    }
+
+    /// It records the response time of an `announce` request, and whether it
+    /// was answered with an error.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the buckets lock is poisoned.
+    pub fn record(&self, latency: Duration, error: bool) {
+        let now = CurrentClock::now().as_secs();
+
+        let mut buckets = self.buckets.lock().expect("it should lock the SLO buckets");
+
+        if buckets.back().map_or(true, |bucket| bucket.second != now) {
+            buckets.push_back(Bucket {
+                second: now,
+                ..Default::default()
+            });
+        }
+
+        let bucket = buckets.back_mut().expect("it should have a bucket for the current second");
+
+        bucket.requests += 1;
+
+        if error {
+            bucket.errors += 1;
+        }
+
+        if latency >= Duration::from_millis(self.config.max_latency_ms) {
+            bucket.slow += 1;
+        }
+
+        Self::forget_old_buckets(&mut buckets, now, self.config.window_secs);
+    }
+
+    /// It evaluates the objectives over the requests in the window.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the buckets lock is poisoned.
+    #[must_use]
+    pub fn evaluate(&self) -> Evaluation {
+        let now = CurrentClock::now().as_secs();
+
+        let mut buckets = self.buckets.lock().expect("it should lock the SLO buckets");
+
+        Self::forget_old_buckets(&mut buckets, now, self.config.window_secs);
+
+        let (requests, errors, slow) = buckets.iter().fold((0, 0, 0), |(requests, errors, slow), bucket| {
+            (requests + bucket.requests, errors + bucket.errors, slow + bucket.slow)
+        });
+
+        let evaluated = requests > 0 && requests >= self.config.min_requests;
+
+        Evaluation {
+            requests,
+            errors,
+            slow,
+            latency_met: !evaluated
+                || u128::from(slow) * 100 <= u128::from(requests) * u128::from(100 - self.config.latency_percentile),
+            error_rate_met: !evaluated
+                || u128::from(errors) * 100 <= u128::from(requests) * u128::from(self.config.max_error_rate_percent),
+            latency_percentile: self.config.latency_percentile,
+            max_latency_ms: self.config.max_latency_ms,
+            max_error_rate_percent: self.config.max_error_rate_percent,
+        }
+    }
+
+    fn forget_old_buckets(buckets: &mut VecDeque<Bucket>, now: u64, window_secs: u64) {
+        while buckets.front().is_some_and(|bucket| bucket.second + window_secs <= now) {
+            buckets.pop_front();
+        }
+    }
+}
+
+/// The result of evaluating the objectives over the window.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Number of `announce` requests in the window.
+    pub requests: u64,
+    /// Number of them answered with an error.
+    pub errors: u64,
+    /// Number of them that took `max_latency_ms` or more.
+    pub slow: u64,
+    pub latency_met: bool,
+    pub error_rate_met: bool,
+    pub latency_percentile: u8,
+    pub max_latency_ms: u64,
+    pub max_error_rate_percent: u8,
+}
+
+impl Evaluation {
+    /// `true` when an objective is not met.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        !self.latency_met || !self.error_rate_met
+    }
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut violations = Vec::new();
+
+        if !self.latency_met {
+            violations.push(format!(
+                "p{} announce response time is not below {} ms ({} of {} requests)",
+                self.latency_percentile, self.max_latency_ms, self.slow, self.requests
+            ));
+        }
+
+        if !self.error_rate_met {
+            violations.push(format!(
+                "announce error rate is above {}% ({} of {} requests)",
+                self.max_error_rate_percent, self.errors, self.requests
+            ));
+        }
+
+        if violations.is_empty() {
+            write!(f, "service level objectives met ({} requests)", self.requests)
+        } else {
+            write!(f, "{}", violations.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_clock::clock::stopped::Stopped as _;
+    use torrust_tracker_clock::clock::{self};
+    use torrust_tracker_configuration::Slo;
+
+    use super::Monitor;
+
+    fn monitor() -> Monitor {
+        clock::Stopped::local_set(&Duration::from_secs(1_000));
+
+        Monitor::new(&Slo {
+            min_requests: 10,
+            ..Default::default()
+        })
+    }
+
+    fn record(monitor: &Monitor, requests: usize, latency_ms: u64, error: bool) {
+        for _ in 0..requests {
+            monitor.record(Duration::from_millis(latency_ms), error);
+        }
+    }
+
+    #[test]
+    fn it_should_meet_the_objectives_when_the_requests_are_fast_and_successful() {
+        let monitor = monitor();
+
+        record(&monitor, 100, 10, false);
+
+        assert!(!monitor.evaluate().is_degraded());
+    }
+
+    #[test]
+    fn it_should_be_degraded_when_the_latency_percentile_is_not_below_the_maximum() {
+        let monitor = monitor();
+
+        record(&monitor, 98, 10, false);
+        record(&monitor, 2, 50, false);
+
+        let evaluation = monitor.evaluate();
+
+        assert!(evaluation.is_degraded());
+        assert!(!evaluation.latency_met);
+        assert_eq!(evaluation.slow, 2);
+    }
+
+    #[test]
+    fn it_should_be_degraded_when_the_error_rate_is_above_the_maximum() {
+        let monitor = monitor();
+
+        record(&monitor, 98, 10, false);
+        record(&monitor, 2, 10, true);
+
+        let evaluation = monitor.evaluate();
+
+        assert!(evaluation.is_degraded());
+        assert!(!evaluation.error_rate_met);
+        assert!(evaluation.latency_met);
+    }
+
+    #[test]
+    fn it_should_not_evaluate_the_objectives_with_fewer_requests_than_the_minimum() {
+        let monitor = monitor();
+
+        record(&monitor, 9, 100, true);
+
+        assert!(!monitor.evaluate().is_degraded());
+    }
+
+    #[test]
+    fn it_should_forget_the_requests_outside_the_window() {
+        let monitor = monitor();
+
+        record(&monitor, 100, 100, true);
+
+        clock::Stopped::local_add(&Duration::from_secs(60)).unwrap();
+
+        let evaluation = monitor.evaluate();
+
+        assert_eq!(evaluation.requests, 0);
+        assert!(!evaluation.is_degraded());
+    }
+}
@@ -0,0 +1,239 @@
+//! Logging of the slow requests.
+//!
+//! When it's enabled, the HTTP and UDP trackers measure the time spent in
+//! every phase of the `announce` and `scrape` requests:
+//!
+//! - `parse`: parsing the request.
+//! - `authorization`: the maintenance, authentication and authorization
+//!   checks.
+//! - `repository`: reading and updating the torrents repository.
+//! - `serialize`: building the response.
+//!
+//! The requests exceeding the threshold of their kind are logged with a
+//! warning, together with the infohashes and the client IP, so operators can
+//! find out where the time goes without enabling the full tracing. The API
+//! requests are logged too, but only their total time is measured.
+//!
+//! Measuring the phases only takes a few clock reads per request. The context
+//! of the request is only built for the slow ones.
+//!
+//! Refer to the [`SlowRequests`] configuration for more information.
+use std::fmt;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use torrust_tracker_configuration::SlowRequests;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+/// The kinds of requests. Each one has its own threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Announce,
+    Scrape,
+    Api,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Announce => write!(f, "announce"),
+            Kind::Scrape => write!(f, "scrape"),
+            Kind::Api => write!(f, "api"),
+        }
+    }
+}
+
+/// The phases of the `announce` and `scrape` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Parsing the request.
+    Parse,
+    /// The maintenance, authentication and authorization checks.
+    Authorization,
+    /// Reading and updating the torrents repository.
+    Repository,
+    /// Building the response.
+    Serialize,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Parse, Phase::Authorization, Phase::Repository, Phase::Serialize];
+
+    fn index(self) -> usize {
+        match self {
+            Phase::Parse => 0,
+            Phase::Authorization => 1,
+            Phase::Repository => 2,
+            Phase::Serialize => 3,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Authorization => write!(f, "authorization"),
+            Phase::Repository => write!(f, "repository"),
+            Phase::Serialize => write!(f, "serialize"),
+        }
+    }
+}
+
+/// It measures the total time of a request and the time spent in each phase.
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    started: Instant,
+    last_lap: Instant,
+    phases: [Option<Duration>; 4],
+}
+
+impl Timer {
+    #[must_use]
+    pub fn start() -> Self {
+        let now = Instant::now();
+
+        Self {
+            started: now,
+            last_lap: now,
+            phases: [None; 4],
+        }
+    }
+
+    /// It ends a phase. The time since the previous phase ended, or since the
+    /// timer started, is added to the `phase`.
+    pub fn lap(&mut self, phase: Phase) {
+        let now = Instant::now();
+        let spent = now.duration_since(self.last_lap);
+
+        self.phases[phase.index()] = Some(self.phases[phase.index()].unwrap_or_default() + spent);
+        self.last_lap = now;
+    }
+
+    /// The time spent in a phase, or `None` if it was not measured.
+    #[must_use]
+    pub fn phase(&self, phase: Phase) -> Option<Duration> {
+        self.phases[phase.index()]
+    }
+
+    /// The time since the timer started.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// The context of a slow request in the log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Request {
+    /// The requested torrents.
+    pub info_hashes: Vec<InfoHash>,
+    /// The IP address of the client.
+    pub client_ip: Option<IpAddr>,
+    /// The method and path of the API requests.
+    pub target: Option<String>,
+}
+
+/// It logs the requests exceeding the threshold of their kind.
+#[derive(Debug)]
+pub struct Logger {
+    config: SlowRequests,
+}
+
+impl Logger {
+    #[must_use]
+    pub fn new(config: &SlowRequests) -> Self {
+        Self { config: *config }
+    }
+
+    #[must_use]
+    pub fn threshold(&self, kind: Kind) -> Duration {
+        Duration::from_millis(match kind {
+            Kind::Announce => self.config.announce_threshold_ms,
+            Kind::Scrape => self.config.scrape_threshold_ms,
+            Kind::Api => self.config.api_threshold_ms,
+        })
+    }
+
+    /// It logs the request if it's slower than the threshold of its kind. The
+    /// context of the request is only built for the slow ones. It returns
+    /// `true` if the request was logged.
+    pub fn log(&self, kind: Kind, timer: &Timer, request: impl FnOnce() -> Request) -> bool {
+        let elapsed = timer.elapsed();
+
+        if elapsed < self.threshold(kind) {
+            return false;
+        }
+
+        let request = request();
+
+        let info_hashes = request
+            .info_hashes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let phases = Phase::ALL
+            .into_iter()
+            .filter_map(|phase| timer.phase(phase).map(|spent| format!("{phase}={spent:?}")))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        tracing::warn!(
+            %kind,
+            elapsed = ?elapsed,
+            %phases,
+            %info_hashes,
+            client_ip = ?request.client_ip,
+            target = ?request.target,
+            "slow request"
+        );
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_configuration::SlowRequests;
+
+    use super::{Kind, Logger, Phase, Request, Timer};
+
+    #[test]
+    fn it_should_add_the_time_since_the_previous_lap_to_the_phase() {
+        let mut timer = Timer::start();
+
+        std::thread::sleep(Duration::from_millis(5));
+        timer.lap(Phase::Parse);
+        timer.lap(Phase::Repository);
+
+        assert!(timer.phase(Phase::Parse).unwrap() >= Duration::from_millis(5));
+        assert!(timer.phase(Phase::Repository).unwrap() < Duration::from_millis(5));
+        assert_eq!(timer.phase(Phase::Authorization), None);
+        assert!(timer.elapsed() >= timer.phase(Phase::Parse).unwrap());
+    }
+
+    #[test]
+    fn it_should_log_the_requests_exceeding_the_threshold_of_their_kind() {
+        let logger = Logger::new(&SlowRequests {
+            announce_threshold_ms: 0,
+            scrape_threshold_ms: 60_000,
+            api_threshold_ms: 60_000,
+        });
+
+        let timer = Timer::start();
+
+        assert!(logger.log(Kind::Announce, &timer, Request::default));
+        assert!(!logger.log(Kind::Scrape, &timer, Request::default));
+    }
+
+    #[test]
+    fn it_should_not_build_the_context_of_the_fast_requests() {
+        let logger = Logger::new(&SlowRequests::default());
+
+        assert!(!logger.log(Kind::Api, &Timer::start(), || panic!("the context should not be built")));
+    }
+}
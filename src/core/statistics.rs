@@ -17,17 +17,75 @@
 //! The [`statistics::Keeper`](crate::core::statistics::Keeper) listens to new events and uses the [`statistics::Repo`](crate::core::statistics::Repo) to upgrade and store metrics.
 //!
 //! See the [`statistics::Event`](crate::core::statistics::Event) enum to check which events are available.
-use std::sync::Arc;
+//!
+//! ## UDP connection IDs
+//!
+//! The UDP tracker sends the connection IDs it issues, uses and rejects, so
+//! the [`statistics::Repo`](crate::core::statistics::Repo) can count:
+//!
+//! - The connection IDs that were used in at least one `announce` request.
+//! - The connection IDs that expired without being used in any `announce`
+//!   request. A high number of unused connection IDs is a signal of a denial
+//!   of service attack with spoofed `connect` requests.
+//! - The `announce` requests rejected because their connection ID had
+//!   expired. They usually come from misconfigured clients that do not
+//!   reconnect.
+//!
+//! ## HTTP `announce` response sizes
+//!
+//! The HTTP tracker sends the size of every `announce` response, before it's
+//! padded, so the [`statistics::Repo`](crate::core::statistics::Repo) can
+//! count the responses in size buckets: less than 128 bytes, from 128 to 511
+//! bytes, from 512 to 2047 bytes, and 2048 bytes or more. It also counts the
+//! responses that were padded. See the `announce_response_padding` option in
+//! the [configuration](https://docs.rs/torrust-tracker-configuration).
+//!
+//! The issued connection IDs are kept in memory until
+//! [`EXPIRED_CONNECTION_ID_RETENTION`] after they expire, up to
+//! [`MAX_TRACKED_CONNECTION_IDS`]. The connection IDs issued while the limit is
+//! reached are not counted.
+//!
+//! ## Torrents repository
+//!
+//! The torrent entries added to and removed from the torrents repository are
+//! counted from the [domain events](crate::core::events) of the tracker, with
+//! [`run_domain_event_listener`], instead of sending statistics events from
+//! the tracker.
+//!
+//! ## Selective statistics
+//!
+//! The [`Statistics`] configuration enables the statistics for each protocol,
+//! the histograms and the UDP connection IDs separately. The
+//! [`statistics::Sender`](crate::core::statistics::Sender) discards the events
+//! that are not collected, so they are not even sent to the listener. See
+//! [`Event::is_collected`].
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 #[cfg(test)]
 use mockall::{automock, predicate::str};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc::error::SendError;
-use tokio::sync::{mpsc, RwLock, RwLockReadGuard};
+use tokio::sync::{broadcast, mpsc, RwLock, RwLockReadGuard};
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::Statistics;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+use crate::core::events;
+use crate::CurrentClock;
 
 const CHANNEL_BUFFER_SIZE: usize = 65_535;
 
+/// Time an expired UDP connection ID is remembered, so that the `announce`
+/// requests using it are counted as requests with an expired connection ID.
+pub const EXPIRED_CONNECTION_ID_RETENTION: Duration = Duration::from_secs(600);
+
+/// Maximum number of UDP connection IDs kept in memory.
+pub const MAX_TRACKED_CONNECTION_IDS: usize = 500_000;
+
 /// An statistics event. It is used to collect tracker metrics.
 ///
 /// - `Tcp` prefix means the event was triggered by the HTTP tracker
@@ -36,20 +94,106 @@ const CHANNEL_BUFFER_SIZE: usize = 65_535;
 /// - Finally the event suffix is the type of request: `announce`, `scrape` or `connection`
 ///
 /// > NOTE: HTTP trackers do not use `connection` requests.
+///
+/// The `ConnectionId` events contain the UDP connection ID. The events for
+/// used and rejected connection IDs do not have the IP version prefix because
+/// the IP version is known since the connection ID was issued.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Event {
     // code-review: consider one single event for request type with data: Event::Announce { scheme: HTTPorUDP, ip_version: V4orV6 }
     // Attributes are enums too.
     Tcp4Announce,
     Tcp4Scrape,
+    Tcp4ScrapeLimitExceeded,
     Tcp6Announce,
     Tcp6Scrape,
+    Tcp6ScrapeLimitExceeded,
+    TcpAnnounceResponse {
+        size: usize,
+        padded: bool,
+    },
+    TcpAnnounceWithUnknownEvent,
+    TcpScrapeDuplicateInfoHashes {
+        count: u64,
+    },
+    TcpUriTooLong,
+    TcpHeadersTooLarge,
+    TcpAnnounceDeadlineExceeded,
+    TcpScrapeDeadlineExceeded,
+    TcpAnnounceKeyBindingRejected,
     Udp4Connect,
     Udp4Announce,
     Udp4Scrape,
+    Udp4ScrapeLimitExceeded,
+    Udp4ConnectionIdIssued {
+        connection_id: i64,
+        valid_until: DurationSinceUnixEpoch,
+    },
     Udp6Connect,
     Udp6Announce,
     Udp6Scrape,
+    Udp6ScrapeLimitExceeded,
+    Udp6ConnectionIdIssued {
+        connection_id: i64,
+        valid_until: DurationSinceUnixEpoch,
+    },
+    UdpConnectionIdUsed {
+        connection_id: i64,
+    },
+    UdpConnectionIdRejected {
+        connection_id: i64,
+    },
+    UdpAnnounceWithUnknownEvent,
+    UdpScrapeDuplicateInfoHashes {
+        count: u64,
+    },
+    UdpNegativeCacheHit,
+    UdpAnnounceDeadlineExceeded,
+    UdpScrapeDeadlineExceeded,
+    TorrentCreated,
+    TorrentRemoved,
+}
+
+impl Event {
+    /// Whether the event is collected with the given [`Statistics`]
+    /// configuration.
+    #[must_use]
+    pub fn is_collected(&self, config: &Statistics) -> bool {
+        match self {
+            Event::TcpAnnounceResponse { .. } => config.http && config.histograms,
+            Event::Tcp4Announce
+            | Event::Tcp4Scrape
+            | Event::Tcp4ScrapeLimitExceeded
+            | Event::Tcp6Announce
+            | Event::Tcp6Scrape
+            | Event::Tcp6ScrapeLimitExceeded
+            | Event::TcpAnnounceWithUnknownEvent
+            | Event::TcpScrapeDuplicateInfoHashes { .. }
+            | Event::TcpUriTooLong
+            | Event::TcpHeadersTooLarge
+            | Event::TcpAnnounceDeadlineExceeded
+            | Event::TcpScrapeDeadlineExceeded
+            | Event::TcpAnnounceKeyBindingRejected => config.http,
+            Event::Udp4ConnectionIdIssued { .. }
+            | Event::Udp6ConnectionIdIssued { .. }
+            | Event::UdpConnectionIdUsed { .. }
+            | Event::UdpConnectionIdRejected { .. } => config.udp && config.connection_ids,
+            Event::Udp4Connect
+            | Event::Udp4Announce
+            | Event::Udp4Scrape
+            | Event::Udp4ScrapeLimitExceeded
+            | Event::Udp6Connect
+            | Event::Udp6Announce
+            | Event::Udp6Scrape
+            | Event::Udp6ScrapeLimitExceeded
+            | Event::UdpAnnounceWithUnknownEvent
+            | Event::UdpScrapeDuplicateInfoHashes { .. }
+            | Event::UdpNegativeCacheHit
+            | Event::UdpAnnounceDeadlineExceeded
+            | Event::UdpScrapeDeadlineExceeded => config.udp,
+            Event::TorrentCreated | Event::TorrentRemoved => true,
+        }
+    }
 }
 
 /// Metrics collected by the tracker.
@@ -70,24 +214,111 @@ pub struct Metrics {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub tcp4_scrapes_exceeding_limit: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub tcp6_scrapes_exceeding_limit: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses smaller than
+    /// 128 bytes, before padding.
+    pub tcp_announce_responses_under_128_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses from 128 to
+    /// 511 bytes, before padding.
+    pub tcp_announce_responses_under_512_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses from 512 to
+    /// 2047 bytes, before padding.
+    pub tcp_announce_responses_under_2048_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses of 2048 bytes
+    /// or more.
+    pub tcp_announce_responses_over_2048_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses that were
+    /// padded to the minimum size.
+    pub tcp_announce_responses_padded: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests with an unknown
+    /// `event` value. They are handled as regular announces.
+    pub tcp_announces_with_unknown_event: u64,
+    /// Total number of duplicate infohashes in TCP (HTTP tracker) `scrape`
+    /// requests. Each torrent is only looked up once per request.
+    pub tcp_scrape_duplicate_info_hashes: u64,
+    /// Total number of TCP (HTTP tracker) requests rejected because their
+    /// URI was longer than the configured maximum.
+    pub tcp_requests_with_uri_too_long: u64,
+    /// Total number of TCP (HTTP tracker) requests rejected because their
+    /// headers were larger than the configured maximum.
+    pub tcp_requests_with_headers_too_large: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests that exceeded
+    /// the request deadline. They got a best-effort response.
+    pub tcp_announces_with_deadline_exceeded: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests that exceeded
+    /// the request deadline. They got a best-effort response.
+    pub tcp_scrapes_with_deadline_exceeded: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests rejected
+    /// because the key is bound to an IP or IP range that does not contain
+    /// the peer address.
+    pub tcp_announces_with_key_bound_to_another_address: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
     pub udp4_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers.
     pub udp4_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub udp4_scrapes_exceeding_limit: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv4 peers
+    /// that were used in at least one `announce` request.
+    pub udp4_connection_ids_used: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv4 peers
+    /// that expired without being used in any `announce` request.
+    pub udp4_connection_ids_unused: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers
+    /// rejected because their connection ID had expired.
+    pub udp4_announces_with_expired_connection_id: u64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
     pub udp6_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers.
     pub udp6_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub udp6_scrapes_exceeding_limit: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv6 peers
+    /// that were used in at least one `announce` request.
+    pub udp6_connection_ids_used: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv6 peers
+    /// that expired without being used in any `announce` request.
+    pub udp6_connection_ids_unused: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers
+    /// rejected because their connection ID had expired.
+    pub udp6_announces_with_expired_connection_id: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests with an unknown
+    /// `event` value. They are handled as regular announces.
+    pub udp_announces_with_unknown_event: u64,
+    /// Total number of duplicate infohashes in UDP (UDP tracker) `scrape`
+    /// requests. Each torrent is only looked up once per request.
+    pub udp_scrape_duplicate_info_hashes: u64,
+    /// Total number of infohashes in UDP (UDP tracker) `announce` and
+    /// `scrape` requests rejected from the negative cache, without looking
+    /// them up again.
+    pub udp_negative_cache_hits: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests that exceeded
+    /// the request deadline. They got a best-effort response.
+    pub udp_announces_with_deadline_exceeded: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests that exceeded
+    /// the request deadline. They got a best-effort response.
+    pub udp_scrapes_with_deadline_exceeded: u64,
+    /// Total number of torrent entries added to the torrents repository.
+    pub torrents_created: u64,
+    /// Total number of torrent entries removed from the torrents repository.
+    pub torrents_removed: u64,
 }
 
 /// The service responsible for keeping tracker metrics (listening to statistics events and handle them).
@@ -114,19 +345,21 @@ impl Keeper {
     pub fn new_active_instance() -> (Box<dyn EventSender>, Repo) {
         let mut stats_tracker = Self::new();
 
-        let stats_event_sender = stats_tracker.run_event_listener();
+        let stats_event_sender = stats_tracker.run_event_listener(Statistics::default());
 
         (stats_event_sender, stats_tracker.repository)
     }
 
-    pub fn run_event_listener(&mut self) -> Box<dyn EventSender> {
+    /// It runs the listener and returns the sender for the events. Only the
+    /// events collected with the given [`Statistics`] configuration are sent.
+    pub fn run_event_listener(&mut self, config: Statistics) -> Box<dyn EventSender> {
         let (sender, receiver) = mpsc::channel::<Event>(CHANNEL_BUFFER_SIZE);
 
         let stats_repository = self.repository.clone();
 
         tokio::spawn(async move { event_listener(receiver, stats_repository).await });
 
-        Box::new(Sender { sender })
+        Box::new(Sender { sender, config })
     }
 }
 
@@ -136,6 +369,31 @@ async fn event_listener(mut receiver: mpsc::Receiver<Event>, stats_repository: R
     }
 }
 
+/// It runs a listener for the [domain events](crate::core::events) of a
+/// tracker that counts the torrent entries created and removed. It stops when
+/// the tracker is dropped.
+pub fn run_domain_event_listener(receiver: broadcast::Receiver<events::Event>, stats_repository: Repo) {
+    tokio::spawn(async move { domain_event_listener(receiver, stats_repository).await });
+}
+
+async fn domain_event_listener(mut receiver: broadcast::Receiver<events::Event>, stats_repository: Repo) {
+    loop {
+        let event = match receiver.recv().await {
+            Ok(events::Event::TorrentCreated { .. }) => Event::TorrentCreated,
+            Ok(events::Event::TorrentRemoved { .. }) => Event::TorrentRemoved,
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!("The statistics are falling behind, {skipped} domain events were not counted");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        event_handler(event, &stats_repository).await;
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 async fn event_handler(event: Event, stats_repository: &Repo) {
     match event {
         // TCP4
@@ -147,6 +405,9 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
             stats_repository.increase_tcp4_scrapes().await;
             stats_repository.increase_tcp4_connections().await;
         }
+        Event::Tcp4ScrapeLimitExceeded => {
+            stats_repository.increase_tcp4_scrapes_exceeding_limit().await;
+        }
 
         // TCP6
         Event::Tcp6Announce => {
@@ -157,6 +418,72 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
             stats_repository.increase_tcp6_scrapes().await;
             stats_repository.increase_tcp6_connections().await;
         }
+        Event::Tcp6ScrapeLimitExceeded => {
+            stats_repository.increase_tcp6_scrapes_exceeding_limit().await;
+        }
+
+        // TCP announce responses
+        Event::TcpAnnounceResponse { size, padded } => {
+            stats_repository.record_tcp_announce_response(size, padded).await;
+        }
+
+        // Announces with unknown events
+        Event::TcpAnnounceWithUnknownEvent => {
+            stats_repository.increase_tcp_announces_with_unknown_event().await;
+        }
+        Event::UdpAnnounceWithUnknownEvent => {
+            stats_repository.increase_udp_announces_with_unknown_event().await;
+        }
+
+        // Duplicate infohashes in scrapes
+        Event::TcpScrapeDuplicateInfoHashes { count } => {
+            stats_repository.increase_tcp_scrape_duplicate_info_hashes(count).await;
+        }
+        Event::UdpScrapeDuplicateInfoHashes { count } => {
+            stats_repository.increase_udp_scrape_duplicate_info_hashes(count).await;
+        }
+
+        // Oversized requests
+        Event::TcpUriTooLong => {
+            stats_repository.increase_tcp_requests_with_uri_too_long().await;
+        }
+        Event::TcpHeadersTooLarge => {
+            stats_repository.increase_tcp_requests_with_headers_too_large().await;
+        }
+
+        // Negative cache
+        Event::UdpNegativeCacheHit => {
+            stats_repository.increase_udp_negative_cache_hits().await;
+        }
+
+        // Request deadline
+        Event::TcpAnnounceDeadlineExceeded => {
+            stats_repository.increase_tcp_announces_with_deadline_exceeded().await;
+        }
+        Event::TcpScrapeDeadlineExceeded => {
+            stats_repository.increase_tcp_scrapes_with_deadline_exceeded().await;
+        }
+        Event::UdpAnnounceDeadlineExceeded => {
+            stats_repository.increase_udp_announces_with_deadline_exceeded().await;
+        }
+        Event::UdpScrapeDeadlineExceeded => {
+            stats_repository.increase_udp_scrapes_with_deadline_exceeded().await;
+        }
+
+        // Torrents repository
+        Event::TorrentCreated => {
+            stats_repository.increase_torrents_created().await;
+        }
+        Event::TorrentRemoved => {
+            stats_repository.increase_torrents_removed().await;
+        }
+
+        // Key binding
+        Event::TcpAnnounceKeyBindingRejected => {
+            stats_repository
+                .increase_tcp_announces_with_key_bound_to_another_address()
+                .await;
+        }
 
         // UDP4
         Event::Udp4Connect => {
@@ -168,6 +495,17 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
         Event::Udp4Scrape => {
             stats_repository.increase_udp4_scrapes().await;
         }
+        Event::Udp4ScrapeLimitExceeded => {
+            stats_repository.increase_udp4_scrapes_exceeding_limit().await;
+        }
+        Event::Udp4ConnectionIdIssued {
+            connection_id,
+            valid_until,
+        } => {
+            stats_repository
+                .issue_udp_connection_id(connection_id, IpVersion::V4, valid_until)
+                .await;
+        }
 
         // UDP6
         Event::Udp6Connect => {
@@ -179,6 +517,25 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
         Event::Udp6Scrape => {
             stats_repository.increase_udp6_scrapes().await;
         }
+        Event::Udp6ScrapeLimitExceeded => {
+            stats_repository.increase_udp6_scrapes_exceeding_limit().await;
+        }
+        Event::Udp6ConnectionIdIssued {
+            connection_id,
+            valid_until,
+        } => {
+            stats_repository
+                .issue_udp_connection_id(connection_id, IpVersion::V6, valid_until)
+                .await;
+        }
+
+        // UDP connection IDs
+        Event::UdpConnectionIdUsed { connection_id } => {
+            stats_repository.use_udp_connection_id(connection_id).await;
+        }
+        Event::UdpConnectionIdRejected { connection_id } => {
+            stats_repository.reject_udp_connection_id(connection_id).await;
+        }
     }
 
     tracing::debug!("stats: {:?}", stats_repository.get_stats().await);
@@ -194,13 +551,53 @@ pub trait EventSender: Sync + Send {
 ///
 /// It uses a channel sender to send the statistic events. The channel is created by a
 /// [`statistics::Keeper`](crate::core::statistics::Keeper)
+///
+/// The events that are not collected with its [`Statistics`] configuration
+/// are discarded, and `None` is returned for them.
 pub struct Sender {
     sender: mpsc::Sender<Event>,
+    config: Statistics,
 }
 
 impl EventSender for Sender {
     fn send_event(&self, event: Event) -> BoxFuture<'_, Option<Result<(), SendError<Event>>>> {
-        async move { Some(self.sender.send(event).await) }.boxed()
+        async move {
+            if !event.is_collected(&self.config) {
+                return None;
+            }
+
+            Some(self.sender.send(event).await)
+        }
+        .boxed()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IpVersion {
+    V4,
+    V6,
+}
+
+#[derive(Debug)]
+struct ConnectionId {
+    ip_version: IpVersion,
+    valid_until: DurationSinceUnixEpoch,
+    used: bool,
+}
+
+/// The UDP connection IDs issued recently, sorted by expiration time.
+#[derive(Debug, Default)]
+struct ConnectionIds {
+    ids: HashMap<i64, ConnectionId>,
+    /// Connection IDs in the order they expire. The first `expired` ones
+    /// have already expired.
+    expirations: VecDeque<(DurationSinceUnixEpoch, i64)>,
+    expired: usize,
+}
+
+impl ConnectionIds {
+    fn is_current(&self, connection_id: i64, valid_until: DurationSinceUnixEpoch) -> bool {
+        self.ids.get(&connection_id).is_some_and(|id| id.valid_until == valid_until)
     }
 }
 
@@ -208,6 +605,7 @@ impl EventSender for Sender {
 #[derive(Clone)]
 pub struct Repo {
     pub stats: Arc<RwLock<Metrics>>,
+    connection_ids: Arc<Mutex<ConnectionIds>>,
 }
 
 impl Default for Repo {
@@ -221,6 +619,7 @@ impl Repo {
     pub fn new() -> Self {
         Self {
             stats: Arc::new(RwLock::new(Metrics::default())),
+            connection_ids: Arc::default(),
         }
     }
 
@@ -246,6 +645,12 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp4_scrapes_exceeding_limit(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp4_scrapes_exceeding_limit += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_tcp6_announces(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.tcp6_announces_handled += 1;
@@ -264,6 +669,12 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp6_scrapes_exceeding_limit(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp6_scrapes_exceeding_limit += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp4_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp4_connections_handled += 1;
@@ -282,6 +693,12 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_udp4_scrapes_exceeding_limit(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_scrapes_exceeding_limit += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp6_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp6_connections_handled += 1;
@@ -299,12 +716,221 @@ impl Repo {
         stats_lock.udp6_scrapes_handled += 1;
         drop(stats_lock);
     }
+
+    pub async fn increase_udp6_scrapes_exceeding_limit(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_scrapes_exceeding_limit += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_announces_with_unknown_event(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_announces_with_unknown_event += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp_announces_with_unknown_event(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp_announces_with_unknown_event += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_scrape_duplicate_info_hashes(&self, count: u64) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_scrape_duplicate_info_hashes += count;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp_scrape_duplicate_info_hashes(&self, count: u64) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp_scrape_duplicate_info_hashes += count;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_requests_with_uri_too_long(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_requests_with_uri_too_long += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_requests_with_headers_too_large(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_requests_with_headers_too_large += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp_negative_cache_hits(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp_negative_cache_hits += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_announces_with_deadline_exceeded(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_announces_with_deadline_exceeded += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_scrapes_with_deadline_exceeded(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_scrapes_with_deadline_exceeded += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_tcp_announces_with_key_bound_to_another_address(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp_announces_with_key_bound_to_another_address += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp_announces_with_deadline_exceeded(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp_announces_with_deadline_exceeded += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp_scrapes_with_deadline_exceeded(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp_scrapes_with_deadline_exceeded += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_torrents_created(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.torrents_created += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_torrents_removed(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.torrents_removed += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn record_tcp_announce_response(&self, size: usize, padded: bool) {
+        let mut stats_lock = self.stats.write().await;
+        match size {
+            0..=127 => stats_lock.tcp_announce_responses_under_128_bytes += 1,
+            128..=511 => stats_lock.tcp_announce_responses_under_512_bytes += 1,
+            512..=2047 => stats_lock.tcp_announce_responses_under_2048_bytes += 1,
+            _ => stats_lock.tcp_announce_responses_over_2048_bytes += 1,
+        }
+        if padded {
+            stats_lock.tcp_announce_responses_padded += 1;
+        }
+        drop(stats_lock);
+    }
+
+    async fn issue_udp_connection_id(&self, connection_id: i64, ip_version: IpVersion, valid_until: DurationSinceUnixEpoch) {
+        let mut stats_lock = self.stats.write().await;
+        let mut connection_ids = self.lock_connection_ids();
+
+        Self::expire_udp_connection_ids(&mut stats_lock, &mut connection_ids);
+
+        // The same connection ID is issued again when the peer connects twice
+        // in the same time extent.
+        if connection_ids.ids.contains_key(&connection_id) || connection_ids.ids.len() >= MAX_TRACKED_CONNECTION_IDS {
+            return;
+        }
+
+        connection_ids.ids.insert(
+            connection_id,
+            ConnectionId {
+                ip_version,
+                valid_until,
+                used: false,
+            },
+        );
+        connection_ids.expirations.push_back((valid_until, connection_id));
+    }
+
+    async fn use_udp_connection_id(&self, connection_id: i64) {
+        let mut stats_lock = self.stats.write().await;
+        let mut connection_ids = self.lock_connection_ids();
+
+        Self::expire_udp_connection_ids(&mut stats_lock, &mut connection_ids);
+
+        if let Some(id) = connection_ids.ids.get_mut(&connection_id) {
+            if !id.used {
+                id.used = true;
+
+                match id.ip_version {
+                    IpVersion::V4 => stats_lock.udp4_connection_ids_used += 1,
+                    IpVersion::V6 => stats_lock.udp6_connection_ids_used += 1,
+                }
+            }
+        }
+    }
+
+    async fn reject_udp_connection_id(&self, connection_id: i64) {
+        let mut stats_lock = self.stats.write().await;
+        let mut connection_ids = self.lock_connection_ids();
+
+        Self::expire_udp_connection_ids(&mut stats_lock, &mut connection_ids);
+
+        // Unknown connection IDs are not counted: they could be forged.
+        if let Some(id) = connection_ids.ids.get(&connection_id) {
+            if id.valid_until <= CurrentClock::now() {
+                match id.ip_version {
+                    IpVersion::V4 => stats_lock.udp4_announces_with_expired_connection_id += 1,
+                    IpVersion::V6 => stats_lock.udp6_announces_with_expired_connection_id += 1,
+                }
+            }
+        }
+    }
+
+    /// It counts the connection IDs that expired without being used, and it
+    /// forgets the ones expired more than [`EXPIRED_CONNECTION_ID_RETENTION`]
+    /// ago.
+    fn expire_udp_connection_ids(stats: &mut Metrics, connection_ids: &mut ConnectionIds) {
+        let now = CurrentClock::now();
+
+        while let Some(&(valid_until, connection_id)) = connection_ids.expirations.get(connection_ids.expired) {
+            if valid_until > now {
+                break;
+            }
+
+            connection_ids.expired += 1;
+
+            if !connection_ids.is_current(connection_id, valid_until) {
+                continue;
+            }
+
+            let id = &connection_ids.ids[&connection_id];
+
+            if !id.used {
+                match id.ip_version {
+                    IpVersion::V4 => stats.udp4_connection_ids_unused += 1,
+                    IpVersion::V6 => stats.udp6_connection_ids_unused += 1,
+                }
+            }
+        }
+
+        while let Some(&(valid_until, connection_id)) = connection_ids.expirations.front() {
+            if valid_until.saturating_add(EXPIRED_CONNECTION_ID_RETENTION) > now {
+                break;
+            }
+
+            connection_ids.expirations.pop_front();
+            connection_ids.expired = connection_ids.expired.saturating_sub(1);
+
+            if connection_ids.is_current(connection_id, valid_until) {
+                connection_ids.ids.remove(&connection_id);
+            }
+        }
+    }
+
+    fn lock_connection_ids(&self) -> std::sync::MutexGuard<'_, ConnectionIds> {
+        self.connection_ids.lock().expect("it should get the connection IDs lock")
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     mod stats_tracker {
+        use torrust_tracker_configuration::Statistics;
+
         use crate::core::statistics::{Event, Keeper, Metrics};
 
         #[tokio::test]
@@ -320,12 +946,30 @@ mod tests {
         async fn should_create_an_event_sender_to_send_statistical_events() {
             let mut stats_tracker = Keeper::new();
 
-            let event_sender = stats_tracker.run_event_listener();
+            let event_sender = stats_tracker.run_event_listener(Statistics::default());
 
             let result = event_sender.send_event(Event::Udp4Connect).await;
 
             assert!(result.is_some());
         }
+
+        #[tokio::test]
+        async fn should_discard_the_events_that_are_not_collected() {
+            let mut stats_tracker = Keeper::new();
+
+            let event_sender = stats_tracker.run_event_listener(Statistics {
+                http: false,
+                histograms: false,
+                ..Default::default()
+            });
+
+            assert!(event_sender.send_event(Event::Tcp4Announce).await.is_none());
+            assert!(event_sender
+                .send_event(Event::TcpAnnounceResponse { size: 64, padded: false })
+                .await
+                .is_none());
+            assert!(event_sender.send_event(Event::Udp4Connect).await.is_some());
+        }
     }
 
     mod event_handler {
@@ -419,6 +1063,157 @@ mod tests {
             assert_eq!(stats.tcp6_connections_handled, 1);
         }
 
+        #[tokio::test]
+        async fn should_increase_the_tcp4_scrapes_exceeding_limit_counter_when_it_receives_a_tcp4_scrape_limit_exceeded_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp4ScrapeLimitExceeded, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp4_scrapes_exceeding_limit, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp6_scrapes_exceeding_limit_counter_when_it_receives_a_tcp6_scrape_limit_exceeded_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Tcp6ScrapeLimitExceeded, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp6_scrapes_exceeding_limit, 1);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_tcp_announce_responses_by_size_when_it_receives_a_tcp_announce_response_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpAnnounceResponse { size: 90, padded: true }, &stats_repository).await;
+            event_handler(
+                Event::TcpAnnounceResponse {
+                    size: 128,
+                    padded: false,
+                },
+                &stats_repository,
+            )
+            .await;
+            event_handler(
+                Event::TcpAnnounceResponse {
+                    size: 2048,
+                    padded: false,
+                },
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_announce_responses_under_128_bytes, 1);
+            assert_eq!(stats.tcp_announce_responses_under_512_bytes, 1);
+            assert_eq!(stats.tcp_announce_responses_under_2048_bytes, 0);
+            assert_eq!(stats.tcp_announce_responses_over_2048_bytes, 1);
+            assert_eq!(stats.tcp_announce_responses_padded, 1);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_announces_with_unknown_events_by_protocol() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpAnnounceWithUnknownEvent, &stats_repository).await;
+            event_handler(Event::UdpAnnounceWithUnknownEvent, &stats_repository).await;
+            event_handler(Event::UdpAnnounceWithUnknownEvent, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_announces_with_unknown_event, 1);
+            assert_eq!(stats.udp_announces_with_unknown_event, 2);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_duplicate_info_hashes_in_scrapes_by_protocol() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpScrapeDuplicateInfoHashes { count: 2 }, &stats_repository).await;
+            event_handler(Event::UdpScrapeDuplicateInfoHashes { count: 1 }, &stats_repository).await;
+            event_handler(Event::UdpScrapeDuplicateInfoHashes { count: 3 }, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_scrape_duplicate_info_hashes, 2);
+            assert_eq!(stats.udp_scrape_duplicate_info_hashes, 4);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_oversized_tcp_requests() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpUriTooLong, &stats_repository).await;
+            event_handler(Event::TcpHeadersTooLarge, &stats_repository).await;
+            event_handler(Event::TcpHeadersTooLarge, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_requests_with_uri_too_long, 1);
+            assert_eq!(stats.tcp_requests_with_headers_too_large, 2);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_requests_that_exceeded_the_deadline() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpAnnounceDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::TcpScrapeDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::TcpScrapeDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::UdpAnnounceDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::UdpAnnounceDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::UdpAnnounceDeadlineExceeded, &stats_repository).await;
+            event_handler(Event::UdpScrapeDeadlineExceeded, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_announces_with_deadline_exceeded, 1);
+            assert_eq!(stats.tcp_scrapes_with_deadline_exceeded, 2);
+            assert_eq!(stats.udp_announces_with_deadline_exceeded, 3);
+            assert_eq!(stats.udp_scrapes_with_deadline_exceeded, 1);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_torrents_created_and_removed_in_the_torrents_repository() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TorrentCreated, &stats_repository).await;
+            event_handler(Event::TorrentCreated, &stats_repository).await;
+            event_handler(Event::TorrentRemoved, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.torrents_created, 2);
+            assert_eq!(stats.torrents_removed, 1);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_announces_rejected_because_the_key_is_bound_to_another_address() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::TcpAnnounceKeyBindingRejected, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp_announces_with_key_bound_to_another_address, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp_negative_cache_hits_counter_when_it_receives_a_udp_negative_cache_hit_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::UdpNegativeCacheHit, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp_negative_cache_hits, 1);
+        }
+
         #[tokio::test]
         async fn should_increase_the_udp4_connections_counter_when_it_receives_a_udp4_connect_event() {
             let stats_repository = Repo::new();
@@ -452,6 +1247,17 @@ mod tests {
             assert_eq!(stats.udp4_scrapes_handled, 1);
         }
 
+        #[tokio::test]
+        async fn should_increase_the_udp4_scrapes_exceeding_limit_counter_when_it_receives_a_udp4_scrape_limit_exceeded_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp4ScrapeLimitExceeded, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_scrapes_exceeding_limit, 1);
+        }
+
         #[tokio::test]
         async fn should_increase_the_udp6_connections_counter_when_it_receives_a_udp6_connect_event() {
             let stats_repository = Repo::new();
@@ -484,5 +1290,150 @@ mod tests {
 
             assert_eq!(stats.udp6_scrapes_handled, 1);
         }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_scrapes_exceeding_limit_counter_when_it_receives_a_udp6_scrape_limit_exceeded_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(Event::Udp6ScrapeLimitExceeded, &stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_scrapes_exceeding_limit, 1);
+        }
+    }
+
+    mod udp_connection_ids {
+        use std::time::Duration;
+
+        use torrust_tracker_clock::clock::stopped::Stopped as _;
+        use torrust_tracker_clock::clock::{self};
+
+        use crate::core::statistics::{event_handler, Event, Repo, EXPIRED_CONNECTION_ID_RETENTION};
+
+        const CONNECTION_ID: i64 = 42;
+        const LIFETIME: Duration = Duration::from_secs(120);
+
+        async fn issue_connection_id(stats_repository: &Repo) {
+            event_handler(
+                Event::Udp4ConnectionIdIssued {
+                    connection_id: CONNECTION_ID,
+                    valid_until: LIFETIME,
+                },
+                stats_repository,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn should_count_the_connection_ids_used_in_announce_requests_only_once() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let stats_repository = Repo::new();
+
+            issue_connection_id(&stats_repository).await;
+
+            event_handler(
+                Event::UdpConnectionIdUsed {
+                    connection_id: CONNECTION_ID,
+                },
+                &stats_repository,
+            )
+            .await;
+            event_handler(
+                Event::UdpConnectionIdUsed {
+                    connection_id: CONNECTION_ID,
+                },
+                &stats_repository,
+            )
+            .await;
+
+            clock::Stopped::local_set(&LIFETIME);
+
+            issue_connection_id(&stats_repository).await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_connection_ids_used, 1);
+            assert_eq!(stats.udp4_connection_ids_unused, 0);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_connection_ids_that_expired_without_being_used() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let stats_repository = Repo::new();
+
+            issue_connection_id(&stats_repository).await;
+
+            clock::Stopped::local_set(&LIFETIME);
+
+            event_handler(
+                Event::Udp6ConnectionIdIssued {
+                    connection_id: CONNECTION_ID + 1,
+                    valid_until: LIFETIME * 2,
+                },
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_connection_ids_unused, 1);
+            assert_eq!(stats.udp6_connection_ids_unused, 0);
+        }
+
+        #[tokio::test]
+        async fn should_count_the_announce_requests_with_an_expired_connection_id() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let stats_repository = Repo::new();
+
+            issue_connection_id(&stats_repository).await;
+
+            clock::Stopped::local_set(&LIFETIME);
+
+            event_handler(
+                Event::UdpConnectionIdRejected {
+                    connection_id: CONNECTION_ID,
+                },
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_announces_with_expired_connection_id, 1);
+        }
+
+        #[tokio::test]
+        async fn should_not_count_the_announce_requests_with_an_unknown_connection_id_as_expired() {
+            clock::Stopped::local_set_to_unix_epoch();
+
+            let stats_repository = Repo::new();
+
+            issue_connection_id(&stats_repository).await;
+
+            clock::Stopped::local_set(&(LIFETIME + EXPIRED_CONNECTION_ID_RETENTION));
+
+            event_handler(
+                Event::UdpConnectionIdRejected {
+                    connection_id: CONNECTION_ID,
+                },
+                &stats_repository,
+            )
+            .await;
+            event_handler(
+                Event::UdpConnectionIdRejected {
+                    connection_id: CONNECTION_ID + 1,
+                },
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_announces_with_expired_connection_id, 0);
+        }
     }
 }
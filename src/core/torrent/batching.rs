@@ -0,0 +1,194 @@
+//! Micro-batching of `announce` writes to the torrents repository.
+//!
+//! Under extreme load, the cost of acquiring the torrent entry lock for every
+//! single `announce` request can be significant. When batching is enabled, the
+//! peer updates are queued in one of several shards (selected by the torrent
+//! info-hash) and applied in small batches. Updates for the same torrent in a
+//! batch are applied while holding the torrent entry lock only once.
+//!
+//! A batch is applied when:
+//!
+//! - The shard queue reaches the `max_batch_size`.
+//! - The periodic flush job runs (every `max_delay_ms` milliseconds).
+//!
+//! The trade-off is that reads (peer lists and swarm metadata) can be slightly
+//! stale, at most by `max_delay_ms` milliseconds.
+//!
+//! Refer to the [`AnnounceBatching`] configuration for more information.
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Mutex;
+
+use torrust_tracker_configuration::AnnounceBatching;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+/// A pending peer update.
+pub type Update = (InfoHash, peer::Peer);
+
+/// Sharded queues of pending peer updates.
+#[derive(Debug)]
+pub struct Batcher {
+    shards: Vec<Mutex<Vec<Update>>>,
+    max_batch_size: usize,
+}
+
+impl Batcher {
+    #[must_use]
+    pub fn new(config: &AnnounceBatching) -> Self {
+        let shards = (0..config.shards.max(1))
+            .map(|_| Mutex::new(Vec::with_capacity(config.max_batch_size)))
+            .collect();
+
+        Self {
+            shards,
+            max_batch_size: config.max_batch_size.max(1),
+        }
+    }
+
+    /// It queues a peer update. It returns the whole shard batch when the
+    /// shard queue is full, so that the caller can apply it.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the shard lock is poisoned.
+    #[must_use]
+    pub fn enqueue(&self, info_hash: &InfoHash, peer: &peer::Peer) -> Option<Vec<Update>> {
+        let mut queue = self.shards[self.shard_for(info_hash)]
+            .lock()
+            .expect("it should get the shard lock");
+
+        queue.push((*info_hash, *peer));
+
+        if queue.len() >= self.max_batch_size {
+            Some(std::mem::replace(&mut *queue, Vec::with_capacity(self.max_batch_size)))
+        } else {
+            None
+        }
+    }
+
+    /// It removes and returns all the pending updates in all shards.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a shard lock is poisoned.
+    #[must_use]
+    pub fn drain(&self) -> Vec<Update> {
+        let mut updates = vec![];
+
+        for shard in &self.shards {
+            updates.append(&mut shard.lock().expect("it should get the shard lock"));
+        }
+
+        updates
+    }
+
+    /// It returns the number of pending updates in all shards.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a shard lock is poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().expect("it should get the shard lock").len())
+            .sum()
+    }
+
+    /// It returns `true` if there are no pending updates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn shard_for(&self, info_hash: &InfoHash) -> usize {
+        let mut hasher = DefaultHasher::new();
+        info_hash.hash(&mut hasher);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let shard = (hasher.finish() % self.shards.len() as u64) as usize;
+
+        shard
+    }
+}
+
+/// It groups the updates by torrent keeping the arrival order of the updates
+/// for each torrent.
+#[must_use]
+pub fn group_by_torrent(updates: Vec<Update>) -> Vec<(InfoHash, Vec<peer::Peer>)> {
+    let mut groups: Vec<(InfoHash, Vec<peer::Peer>)> = vec![];
+    let mut positions: HashMap<InfoHash, usize> = HashMap::new();
+
+    for (info_hash, peer) in updates {
+        if let Some(&position) = positions.get(&info_hash) {
+            groups[position].1.push(peer);
+        } else {
+            positions.insert(info_hash, groups.len());
+            groups.push((info_hash, vec![peer]));
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::AnnounceBatching;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+
+    use super::{group_by_torrent, Batcher};
+
+    fn batching_config(shards: usize, max_batch_size: usize) -> AnnounceBatching {
+        AnnounceBatching {
+            shards,
+            max_batch_size,
+            max_delay_ms: 5,
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_batch_when_the_shard_queue_is_full() {
+        let batcher = Batcher::new(&batching_config(1, 2));
+
+        let info_hash = InfoHash::default();
+        let peer = PeerBuilder::default().build();
+
+        assert!(batcher.enqueue(&info_hash, &peer).is_none());
+
+        let batch = batcher.enqueue(&info_hash, &peer).unwrap();
+
+        assert_eq!(batch.len(), 2);
+        assert!(batcher.is_empty());
+    }
+
+    #[test]
+    fn it_should_drain_all_the_pending_updates_from_all_the_shards() {
+        let batcher = Batcher::new(&batching_config(4, 100));
+
+        let peer = PeerBuilder::default().build();
+
+        for i in 0..10u8 {
+            assert!(batcher.enqueue(&InfoHash::from_bytes(&[i; 20]), &peer).is_none());
+        }
+
+        assert_eq!(batcher.len(), 10);
+        assert_eq!(batcher.drain().len(), 10);
+        assert!(batcher.is_empty());
+    }
+
+    #[test]
+    fn it_should_group_the_updates_by_torrent_keeping_the_arrival_order() {
+        let info_hash_1 = InfoHash::from_bytes(&[1; 20]);
+        let info_hash_2 = InfoHash::from_bytes(&[2; 20]);
+
+        let first = PeerBuilder::default().with_bytes_pending_to_download(1).build();
+        let second = PeerBuilder::default().with_bytes_pending_to_download(2).build();
+        let third = PeerBuilder::default().with_bytes_pending_to_download(3).build();
+
+        let groups = group_by_torrent(vec![(info_hash_1, first), (info_hash_2, second), (info_hash_1, third)]);
+
+        assert_eq!(groups, vec![(info_hash_1, vec![first, third]), (info_hash_2, vec![second])]);
+    }
+}
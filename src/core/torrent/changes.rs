@@ -0,0 +1,210 @@
+//! Log of the swarm changes for incremental syncing.
+//!
+//! Clients that keep a copy of the swarm metadata (like the Index, which shows
+//! the number of seeders and leechers of every torrent) would have to scrape
+//! all the torrents again to keep it up to date. When the log is enabled, the
+//! tracker assigns a monotonic cursor to every change in the swarm of a
+//! torrent, so those clients only have to get the torrents that changed since
+//! the last cursor they saw.
+//!
+//! The log is maintained from the domain [`events`](crate::core::events): a
+//! peer announced itself, or a torrent entry was created or removed. Only the
+//! last change of each torrent is kept, so the log size is bounded by the
+//! number of torrents, and by the configured `capacity`.
+//!
+//! The cursor is not persisted. When the tracker restarts, or when a client
+//! cursor is older than the oldest change the log still has, the client has
+//! to sync all the torrents again. That's reported with the `reset` flag in
+//! the returned [`Page`].
+//!
+//! Refer to the [`SwarmChanges`] configuration for more information.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use torrust_tracker_configuration::SwarmChanges;
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+/// A page of torrents that changed since a cursor.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Page {
+    /// The cursor to use to get the next page of changes.
+    pub cursor: u64,
+    /// The torrents that changed, in the order they changed.
+    pub info_hashes: Vec<InfoHash>,
+    /// `true` if the changes since the requested cursor are not in the log
+    /// anymore. The client has to sync all the torrents again, and then
+    /// continue from the returned `cursor`.
+    pub reset: bool,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    /// Cursor of the last change.
+    cursor: u64,
+    /// Cursor of the last change that was removed from the log because it
+    /// was full.
+    forgotten: u64,
+    by_info_hash: HashMap<InfoHash, u64>,
+    by_cursor: BTreeMap<u64, InfoHash>,
+}
+
+/// The log of the swarm changes.
+#[derive(Debug)]
+pub struct Changes {
+    capacity: usize,
+    log: Mutex<Log>,
+}
+
+impl Changes {
+    #[must_use]
+    pub fn new(config: &SwarmChanges) -> Self {
+        Self {
+            capacity: config.capacity,
+            log: Mutex::default(),
+        }
+    }
+
+    /// It records a change in the swarm of the torrent, replacing its
+    /// previous change.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the log lock is poisoned.
+    pub fn record(&self, info_hash: &InfoHash) {
+        let mut log = self.log.lock().expect("it should get the swarm changes lock");
+
+        log.cursor += 1;
+        let cursor = log.cursor;
+
+        if let Some(previous) = log.by_info_hash.insert(*info_hash, cursor) {
+            log.by_cursor.remove(&previous);
+        }
+
+        log.by_cursor.insert(cursor, *info_hash);
+
+        while log.by_cursor.len() > self.capacity {
+            if let Some((oldest, info_hash)) = log.by_cursor.pop_first() {
+                log.by_info_hash.remove(&info_hash);
+                log.forgotten = oldest;
+            }
+        }
+    }
+
+    /// It returns at most `limit` torrents that changed after the `since`
+    /// cursor.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the log lock is poisoned.
+    #[must_use]
+    pub fn since(&self, since: u64, limit: usize) -> Page {
+        let log = self.log.lock().expect("it should get the swarm changes lock");
+
+        // The cursor is older than the log or it's from a previous run
+        if since < log.forgotten || since > log.cursor {
+            return Page {
+                cursor: log.cursor,
+                info_hashes: vec![],
+                reset: true,
+            };
+        }
+
+        let changes: Vec<(u64, InfoHash)> = log
+            .by_cursor
+            .range(since + 1..)
+            .take(limit)
+            .map(|(cursor, info_hash)| (*cursor, *info_hash))
+            .collect();
+
+        Page {
+            cursor: changes.last().map_or(since, |(cursor, _)| *cursor),
+            info_hashes: changes.into_iter().map(|(_, info_hash)| info_hash).collect(),
+            reset: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::SwarmChanges;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::Changes;
+
+    fn changes_with_capacity(capacity: usize) -> Changes {
+        Changes::new(&SwarmChanges { capacity })
+    }
+
+    #[test]
+    fn it_should_return_the_torrents_that_changed_since_a_cursor() {
+        let changes = changes_with_capacity(10);
+
+        changes.record(&InfoHash::from([1; 20]));
+        changes.record(&InfoHash::from([2; 20]));
+        changes.record(&InfoHash::from([3; 20]));
+
+        let page = changes.since(1, 10);
+
+        assert_eq!(page.info_hashes, vec![InfoHash::from([2; 20]), InfoHash::from([3; 20])]);
+        assert_eq!(page.cursor, 3);
+        assert!(!page.reset);
+    }
+
+    #[test]
+    fn it_should_only_keep_the_last_change_of_each_torrent() {
+        let changes = changes_with_capacity(10);
+
+        changes.record(&InfoHash::from([1; 20]));
+        changes.record(&InfoHash::from([2; 20]));
+        changes.record(&InfoHash::from([1; 20]));
+
+        let page = changes.since(0, 10);
+
+        assert_eq!(page.info_hashes, vec![InfoHash::from([2; 20]), InfoHash::from([1; 20])]);
+        assert_eq!(page.cursor, 3);
+    }
+
+    #[test]
+    fn it_should_return_the_changes_in_pages() {
+        let changes = changes_with_capacity(10);
+
+        changes.record(&InfoHash::from([1; 20]));
+        changes.record(&InfoHash::from([2; 20]));
+
+        let first_page = changes.since(0, 1);
+        let second_page = changes.since(first_page.cursor, 1);
+        let last_page = changes.since(second_page.cursor, 1);
+
+        assert_eq!(first_page.info_hashes, vec![InfoHash::from([1; 20])]);
+        assert_eq!(second_page.info_hashes, vec![InfoHash::from([2; 20])]);
+        assert!(last_page.info_hashes.is_empty());
+        assert_eq!(last_page.cursor, 2);
+    }
+
+    #[test]
+    fn it_should_ask_for_a_full_sync_when_the_changes_since_the_cursor_were_forgotten() {
+        let changes = changes_with_capacity(1);
+
+        changes.record(&InfoHash::from([1; 20]));
+        changes.record(&InfoHash::from([2; 20]));
+
+        let page = changes.since(0, 10);
+
+        assert!(page.reset);
+        assert_eq!(page.cursor, 2);
+
+        assert_eq!(changes.since(1, 10).info_hashes, vec![InfoHash::from([2; 20])]);
+    }
+
+    #[test]
+    fn it_should_ask_for_a_full_sync_when_the_cursor_is_from_a_previous_run() {
+        let changes = changes_with_capacity(10);
+
+        changes.record(&InfoHash::from([1; 20]));
+
+        let page = changes.since(100, 10);
+
+        assert!(page.reset);
+        assert_eq!(page.cursor, 1);
+    }
+}
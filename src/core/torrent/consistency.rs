@@ -0,0 +1,349 @@
+//! Consistency check of the in-memory torrents repository.
+//!
+//! It's a debugging tool to catch concurrency bugs in the repository
+//! implementations early. When it's enabled, a background job periodically
+//! goes through all the torrents and verifies these invariants:
+//!
+//! - The aggregate metrics of the repository match the ones recomputed from
+//!   the swarm metadata of every torrent ([`Kind::AggregateMetrics`]).
+//! - The number of torrents in the aggregate metrics matches the number of
+//!   entries, and every entry can be found by its infohash
+//!   ([`Kind::EntryCount`]).
+//! - The number of peers of every torrent matches the number of distinct peer
+//!   IDs in the swarm ([`Kind::PeerCount`]).
+//! - The seeders and leechers in the swarm metadata match the ones recomputed
+//!   from the peers ([`Kind::SwarmMetadata`]). Torrents with static peers are
+//!   not verified, because the swarm peers sharing an address with a static
+//!   peer are hidden.
+//! - There are no peers older than the peer timeout plus the cleanup
+//!   interval, that is, peers that should have been removed by the last
+//!   cleanup ([`Kind::StalePeers`]).
+//!
+//! The repository is not locked as a whole while it's verified, so
+//! concurrent `announce` requests can cause transient inconsistencies between
+//! the aggregate metrics and the recomputed ones. Inconsistencies that show up
+//! in consecutive reports are the ones worth investigating.
+//!
+//! Refer to the [`ConsistencyCheck`](torrust_tracker_configuration::ConsistencyCheck)
+//! configuration for more information.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use aquatic_udp_protocol::PeerId;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+use torrust_tracker_torrent_repository::entry::EntrySync;
+use torrust_tracker_torrent_repository::repository::Repository;
+
+use super::static_peers::STATIC_PEER_ID;
+
+/// The invariant that is not satisfied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    AggregateMetrics,
+    EntryCount,
+    PeerCount,
+    SwarmMetadata,
+    StalePeers,
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Kind::AggregateMetrics => "aggregate_metrics",
+            Kind::EntryCount => "entry_count",
+            Kind::PeerCount => "peer_count",
+            Kind::SwarmMetadata => "swarm_metadata",
+            Kind::StalePeers => "stale_peers",
+        };
+
+        write!(f, "{kind}")
+    }
+}
+
+/// An invariant of the repository that is not satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    pub kind: Kind,
+    /// The torrent with the inconsistency, or `None` if it's about the whole
+    /// repository.
+    pub info_hash: Option<InfoHash>,
+    pub message: String,
+}
+
+impl Inconsistency {
+    fn new(kind: Kind, info_hash: Option<InfoHash>, message: String) -> Self {
+        Self {
+            kind,
+            info_hash,
+            message,
+        }
+    }
+}
+
+/// The result of a consistency check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// When the check was run.
+    pub checked_at: DurationSinceUnixEpoch,
+    /// Number of torrents verified.
+    pub torrents: u64,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+/// It verifies the invariants of the repository.
+///
+/// Peers last updated before the `stale_cutoff` are reported as stale.
+#[must_use]
+pub fn check<R, E>(torrents: &R, stale_cutoff: DurationSinceUnixEpoch, now: DurationSinceUnixEpoch) -> Report
+where
+    R: Repository<E>,
+    E: EntrySync,
+{
+    let metrics = torrents.get_metrics();
+    let entries = torrents.get_paginated(None);
+
+    let mut inconsistencies = vec![];
+    let mut recomputed = TorrentsMetrics::default();
+
+    for (info_hash, entry) in &entries {
+        if torrents.get(info_hash).is_none() {
+            inconsistencies.push(Inconsistency::new(
+                Kind::EntryCount,
+                Some(*info_hash),
+                "the torrent is listed but it can't be found by its infohash".to_string(),
+            ));
+        }
+
+        let swarm_metadata = entry.get_swarm_metadata();
+
+        recomputed.complete += u64::from(swarm_metadata.complete);
+        recomputed.downloaded += u64::from(swarm_metadata.downloaded);
+        recomputed.incomplete += u64::from(swarm_metadata.incomplete);
+        recomputed.torrents += 1;
+
+        let peers = entry.get_peers(None);
+
+        let has_static_peers = peers.iter().any(|peer| peer.peer_id == STATIC_PEER_ID);
+
+        // The latest announce of every peer. The second address of the merged
+        // dual-stack peers is always older.
+        let mut latest: HashMap<PeerId, Arc<peer::Peer>> = HashMap::new();
+
+        for peer in peers.iter().filter(|peer| peer.peer_id != STATIC_PEER_ID) {
+            latest
+                .entry(peer.peer_id)
+                .and_modify(|current| {
+                    if peer.updated > current.updated {
+                        *current = peer.clone();
+                    }
+                })
+                .or_insert_with(|| peer.clone());
+        }
+
+        let peers_len = entry.get_peers_len();
+
+        if latest.len() != peers_len {
+            inconsistencies.push(Inconsistency::new(
+                Kind::PeerCount,
+                Some(*info_hash),
+                format!("the torrent has {peers_len} peers but {} distinct peer IDs", latest.len()),
+            ));
+        }
+
+        if !has_static_peers {
+            let seeders = latest.values().filter(|peer| peer.is_seeder()).count();
+            let leechers = latest.len() - seeders;
+
+            if u64::from(swarm_metadata.complete) != seeders as u64 || u64::from(swarm_metadata.incomplete) != leechers as u64 {
+                inconsistencies.push(Inconsistency::new(
+                    Kind::SwarmMetadata,
+                    Some(*info_hash),
+                    format!(
+                        "the swarm metadata has {} seeders and {} leechers but the peers are {seeders} seeders and {leechers} leechers",
+                        swarm_metadata.complete, swarm_metadata.incomplete
+                    ),
+                ));
+            }
+        }
+
+        let stale_peers = peers
+            .iter()
+            .filter(|peer| peer.peer_id != STATIC_PEER_ID && peer.updated < stale_cutoff)
+            .count();
+
+        if stale_peers > 0 {
+            inconsistencies.push(Inconsistency::new(
+                Kind::StalePeers,
+                Some(*info_hash),
+                format!("the torrent has {stale_peers} peers that should have been removed by the cleanup"),
+            ));
+        }
+    }
+
+    if metrics.torrents != entries.len() as u64 {
+        inconsistencies.push(Inconsistency::new(
+            Kind::EntryCount,
+            None,
+            format!(
+                "the aggregate metrics have {} torrents but there are {} entries",
+                metrics.torrents,
+                entries.len()
+            ),
+        ));
+    }
+
+    if metrics.complete != recomputed.complete
+        || metrics.downloaded != recomputed.downloaded
+        || metrics.incomplete != recomputed.incomplete
+    {
+        inconsistencies.push(Inconsistency::new(
+            Kind::AggregateMetrics,
+            None,
+            format!("the aggregate metrics are {metrics:?} but the recomputed ones are {recomputed:?}"),
+        ));
+    }
+
+    Report {
+        checked_at: now,
+        torrents: entries.len() as u64,
+        inconsistencies,
+    }
+}
+
+/// Counters of the consistency checks run since the tracker started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Number of checks run.
+    pub checks: u64,
+    /// Number of inconsistencies found in all the checks.
+    pub inconsistencies: u64,
+}
+
+/// It keeps the last [`Report`] and the counters of all the checks.
+#[derive(Debug, Default)]
+pub struct Checker {
+    last_report: RwLock<Option<Arc<Report>>>,
+    checks: AtomicU64,
+    inconsistencies: AtomicU64,
+}
+
+impl Checker {
+    /// It replaces the last report.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the report lock is poisoned.
+    pub fn record(&self, report: Report) {
+        self.checks.fetch_add(1, Ordering::Relaxed);
+        self.inconsistencies
+            .fetch_add(report.inconsistencies.len() as u64, Ordering::Relaxed);
+
+        *self.last_report.write().expect("it should get the report lock") = Some(Arc::new(report));
+    }
+
+    /// It returns the last report, or `None` if no check has been run yet.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the report lock is poisoned.
+    #[must_use]
+    pub fn last_report(&self) -> Option<Arc<Report>> {
+        self.last_report.read().expect("it should get the report lock").clone()
+    }
+
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            checks: self.checks.load(Ordering::Relaxed),
+            inconsistencies: self.inconsistencies.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+    use torrust_tracker_configuration::TrackerPolicy;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+    use torrust_tracker_torrent_repository::repository::Repository;
+
+    use super::{check, Checker, Kind, Report};
+    use crate::core::torrent::Torrents;
+
+    fn peer(id: u8, updated_secs: u64, left: i64) -> peer::Peer {
+        peer::Peer {
+            peer_id: PeerId([id; 20]),
+            peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, id)), 8080),
+            updated: DurationSinceUnixEpoch::from_secs(updated_secs),
+            uploaded: NumberOfBytes::new(0),
+            downloaded: NumberOfBytes::new(0),
+            left: NumberOfBytes::new(left),
+            event: AnnounceEvent::Started,
+            partial_seed: false,
+            corrupt: NumberOfBytes::new(0),
+            redundant: NumberOfBytes::new(0),
+        }
+    }
+
+    fn now() -> DurationSinceUnixEpoch {
+        DurationSinceUnixEpoch::from_secs(1000)
+    }
+
+    #[test]
+    fn it_should_not_find_inconsistencies_in_a_consistent_repository() {
+        let torrents = Torrents::default();
+
+        torrents.upsert_peer(&InfoHash::from([1; 20]), &peer(1, 900, 0), &TrackerPolicy::default());
+        torrents.upsert_peer(&InfoHash::from([1; 20]), &peer(2, 900, 100), &TrackerPolicy::default());
+        torrents.upsert_peer(&InfoHash::from([2; 20]), &peer(3, 900, 100), &TrackerPolicy::default());
+
+        let report = check(&torrents, DurationSinceUnixEpoch::from_secs(500), now());
+
+        assert_eq!(
+            report,
+            Report {
+                checked_at: now(),
+                torrents: 2,
+                inconsistencies: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_report_the_peers_that_should_have_been_removed_by_the_cleanup() {
+        let torrents = Torrents::default();
+
+        torrents.upsert_peer(&InfoHash::from([1; 20]), &peer(1, 100, 0), &TrackerPolicy::default());
+        torrents.upsert_peer(&InfoHash::from([1; 20]), &peer(2, 900, 0), &TrackerPolicy::default());
+
+        let report = check(&torrents, DurationSinceUnixEpoch::from_secs(500), now());
+
+        assert_eq!(report.inconsistencies.len(), 1);
+        assert_eq!(report.inconsistencies[0].kind, Kind::StalePeers);
+        assert_eq!(report.inconsistencies[0].info_hash, Some(InfoHash::from([1; 20])));
+    }
+
+    #[test]
+    fn it_should_keep_the_last_report_and_count_the_inconsistencies_of_all_the_checks() {
+        let torrents = Torrents::default();
+
+        torrents.upsert_peer(&InfoHash::from([1; 20]), &peer(1, 100, 0), &TrackerPolicy::default());
+
+        let checker = Checker::default();
+
+        assert!(checker.last_report().is_none());
+
+        checker.record(check(&torrents, DurationSinceUnixEpoch::from_secs(500), now()));
+        checker.record(check(&torrents, DurationSinceUnixEpoch::from_secs(50), now()));
+
+        assert_eq!(checker.last_report().unwrap().inconsistencies, vec![]);
+        assert_eq!(checker.metrics().checks, 2);
+        assert_eq!(checker.metrics().inconsistencies, 1);
+    }
+}
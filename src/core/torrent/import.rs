@@ -0,0 +1,157 @@
+//! Import of the persisted torrents at startup.
+//!
+//! When the `persistent_torrent_completed_stat` policy is enabled, the tracker
+//! loads the number of completed downloads of every torrent from the database
+//! before it starts the trackers. With millions of torrents that can take a
+//! while, so the torrents are streamed from the database in batches of
+//! [`BATCH_SIZE`] and each batch is imported into the repository in parallel
+//! shards, one per available CPU, while the next batch is loaded.
+//!
+//! The tracker is "warming up" while the import is in progress. The
+//! [`WarmUp`] state is exposed in the health check, and the progress is logged.
+//! The persisted torrents are counted once, when the tracker is created. The
+//! count is used to pre-size the torrents repository, see
+//! [`Torrents`](crate::core::torrent::Torrents), and the progress is reported
+//! against it.
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use torrust_tracker_primitives::PersistentTorrents;
+
+/// Number of torrents loaded from the database at once.
+pub const BATCH_SIZE: u32 = 50_000;
+
+/// The state of the import of the persisted torrents.
+#[derive(Debug, Default)]
+pub struct WarmUp {
+    in_progress: AtomicBool,
+    imported_torrents: AtomicU64,
+    expected_torrents: AtomicU64,
+}
+
+impl WarmUp {
+    /// It creates the state of an import that has not started yet. The
+    /// `expected_torrents` is `0` when the number of persisted torrents is
+    /// unknown.
+    #[must_use]
+    pub fn new(expected_torrents: u64) -> Self {
+        Self {
+            expected_torrents: AtomicU64::new(expected_torrents),
+            ..Default::default()
+        }
+    }
+
+    /// It marks the import as started.
+    pub fn start(&self) {
+        self.imported_torrents.store(0, Ordering::Relaxed);
+        self.in_progress.store(true, Ordering::Release);
+    }
+
+    /// It marks the import as finished, successfully or not.
+    pub fn finish(&self) {
+        self.in_progress.store(false, Ordering::Release);
+    }
+
+    /// It adds the number of torrents imported in a shard.
+    pub fn add_imported_torrents(&self, torrents: usize) {
+        self.imported_torrents.fetch_add(torrents as u64, Ordering::Relaxed);
+    }
+
+    /// It returns `true` if the persisted torrents are still being imported.
+    #[must_use]
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress.load(Ordering::Acquire)
+    }
+
+    /// The number of torrents imported so far.
+    #[must_use]
+    pub fn imported_torrents(&self) -> u64 {
+        self.imported_torrents.load(Ordering::Relaxed)
+    }
+
+    /// The number of persisted torrents counted when the tracker was created,
+    /// or `0` if they could not be counted.
+    #[must_use]
+    pub fn expected_torrents(&self) -> u64 {
+        self.expected_torrents.load(Ordering::Relaxed)
+    }
+
+    /// The percentage of the expected torrents imported so far, or `None` if
+    /// the number of expected torrents is unknown.
+    #[must_use]
+    pub fn progress(&self) -> Option<u8> {
+        let expected = self.expected_torrents();
+
+        if expected == 0 {
+            return None;
+        }
+
+        let progress = (self.imported_torrents().saturating_mul(100) / expected).min(100);
+
+        Some(u8::try_from(progress).unwrap_or(100))
+    }
+}
+
+/// It splits a batch of torrents into at most `shards` batches of consecutive
+/// infohashes.
+#[must_use]
+pub fn split_into_shards(batch: PersistentTorrents, shards: usize) -> Vec<PersistentTorrents> {
+    let shards = shards.max(1);
+    let shard_size = (batch.len() / shards + usize::from(batch.len() % shards != 0)).max(1);
+
+    let mut torrents = batch.into_iter();
+    let mut result = vec![];
+
+    loop {
+        let shard: PersistentTorrents = torrents.by_ref().take(shard_size).collect();
+
+        if shard.is_empty() {
+            return result;
+        }
+
+        result.push(shard);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::PersistentTorrents;
+
+    use super::{split_into_shards, WarmUp};
+
+    fn batch(torrents: u8) -> PersistentTorrents {
+        (0..torrents).map(|i| (InfoHash::from([i; 20]), u32::from(i))).collect()
+    }
+
+    #[test]
+    fn it_should_split_a_batch_into_shards_of_consecutive_infohashes() {
+        let shards = split_into_shards(batch(5), 2);
+
+        assert_eq!(
+            shards,
+            vec![batch(3), (3..5).map(|i| (InfoHash::from([i; 20]), u32::from(i))).collect()]
+        );
+    }
+
+    #[test]
+    fn it_should_report_the_progress_against_the_expected_torrents() {
+        let warm_up = WarmUp::new(4);
+
+        warm_up.start();
+        warm_up.add_imported_torrents(1);
+
+        assert_eq!(warm_up.progress(), Some(25));
+
+        let warm_up = WarmUp::new(0);
+
+        warm_up.start();
+
+        assert_eq!(warm_up.progress(), None);
+    }
+
+    #[test]
+    fn it_should_not_return_empty_shards() {
+        assert_eq!(split_into_shards(batch(2), 4).len(), 2);
+        assert!(split_into_shards(batch(0), 4).is_empty());
+    }
+}
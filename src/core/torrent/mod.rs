@@ -25,6 +25,14 @@
 //! - The number of peers that have NOT completed downloading the torrent and are still active, that means they are actively participating in the network.
 //!   Peer that don not have a full copy of the torrent data are called "leechers".
 //!
+pub mod batching;
+pub mod changes;
+pub mod consistency;
+pub mod import;
+pub mod policy;
+pub mod snapshot;
+pub mod static_peers;
+
 use torrust_tracker_torrent_repository::TorrentsSkipMapMutexStd;
 
 pub type Torrents = TorrentsSkipMapMutexStd; // Currently Used
@@ -0,0 +1,73 @@
+//! Per-torrent announce policies.
+//!
+//! Operators can mark specific torrents with a policy to retire them
+//! gracefully:
+//!
+//! Policy | Description
+//! ---|---
+//! `seed-only` | Only seeders can announce. `announce` requests from leechers are rejected.
+//! `frozen` | No new peers are accepted. Only peers already in the swarm can keep announcing.
+//!
+//! The policies are persisted in the database and enforced in the `announce`
+//! authorization step. They do not affect `scrape` requests.
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// An announce policy for a torrent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum TorrentPolicy {
+    /// `announce` requests from leechers are rejected.
+    SeedOnly,
+    /// `announce` requests from peers that are not in the swarm yet are
+    /// rejected.
+    Frozen,
+}
+
+impl fmt::Display for TorrentPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TorrentPolicy::SeedOnly => write!(f, "seed-only"),
+            TorrentPolicy::Frozen => write!(f, "frozen"),
+        }
+    }
+}
+
+/// Error returned when a string is not a valid [`TorrentPolicy`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid torrent policy: {0}")]
+pub struct ParseTorrentPolicyError(pub String);
+
+impl FromStr for TorrentPolicy {
+    type Err = ParseTorrentPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "seed-only" => Ok(TorrentPolicy::SeedOnly),
+            "frozen" => Ok(TorrentPolicy::Frozen),
+            _ => Err(ParseTorrentPolicyError(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::TorrentPolicy;
+
+    #[test]
+    fn it_should_be_parsed_from_its_string_representation() {
+        for policy in [TorrentPolicy::SeedOnly, TorrentPolicy::Frozen] {
+            assert_eq!(TorrentPolicy::from_str(&policy.to_string()), Ok(policy));
+        }
+    }
+
+    #[test]
+    fn it_should_fail_parsing_an_unknown_policy() {
+        assert!(TorrentPolicy::from_str("leech-only").is_err());
+    }
+}
@@ -0,0 +1,495 @@
+//! Read snapshot of the swarm metadata for scrape-heavy workloads.
+//!
+//! Every `scrape` request and every torrents metrics query takes the lock of
+//! the torrent entries it reads, competing with the `announce` requests that
+//! write to them. When the read snapshot is enabled, a background job
+//! periodically builds an immutable copy of the swarm metadata of all torrents
+//! and publishes it, replacing the previous one. Reads only clone the pointer
+//! to the current snapshot, while the `announce` requests keep writing to the
+//! live torrents repository.
+//!
+//! The trade-off is that `scrape` responses and metrics can be stale, at most
+//! by `refresh_interval_ms` milliseconds.
+//!
+//! Since the job already goes through all the torrents, it also computes the
+//! distribution of the swarm sizes ([`SwarmSizes`]), so operators can know the
+//! shape of the workload without scanning the torrents on every request.
+//!
+//! It also computes a [`Health`] score (from `0` to `100`) for every torrent,
+//! comparing its swarm with the one in the previous snapshot, and the
+//! distribution of the scores ([`HealthScores`]), so index sites can badge
+//! the torrents with data the tracker already has. The score combines:
+//!
+//! - The availability: the ratio of seeders to leechers, up to `1.0` when
+//!   there are at least as many seeders as leechers (60 points).
+//! - The churn: the change in the number of peers since the previous
+//!   snapshot, over the size of the swarm. A stable swarm gets the 25 points.
+//! - The availability trend: whether the number of seeders is rising, stable
+//!   or falling (15, 7 or 0 points).
+//!
+//! Torrents without seeders always score `0`. Torrents that were not in the
+//! previous snapshot have no churn and a stable trend.
+//!
+//! Refer to the [`ReadSnapshot`](torrust_tracker_configuration::ReadSnapshot)
+//! configuration for more information.
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+
+/// Inclusive upper bounds of the swarm size buckets. The last bucket, after
+/// them, has no upper bound.
+pub const SWARM_SIZE_BUCKETS: [u32; 5] = [0, 10, 100, 1_000, 10_000];
+
+/// Inclusive upper bounds of the health score buckets.
+pub const HEALTH_SCORE_BUCKETS: [u32; 5] = [19, 39, 59, 79, 100];
+
+/// Histogram of the number of peers (seeders and leechers) per torrent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SwarmSizes {
+    torrents: [u64; SWARM_SIZE_BUCKETS.len() + 1],
+}
+
+/// A bucket of the [`SwarmSizes`] or the [`HealthScores`] histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    /// Minimum number of peers (or health score), inclusive.
+    pub min: u32,
+    /// Maximum number of peers (or health score), inclusive. `None` for the
+    /// last bucket of the swarm sizes.
+    pub max: Option<u32>,
+    /// Number of torrents whose swarm size (or health score) is in the bucket.
+    pub torrents: u64,
+}
+
+impl SwarmSizes {
+    fn add(&mut self, peers: u32) {
+        let bucket = SWARM_SIZE_BUCKETS
+            .iter()
+            .position(|max| peers <= *max)
+            .unwrap_or(SWARM_SIZE_BUCKETS.len());
+
+        self.torrents[bucket] += 1;
+    }
+
+    /// It returns the buckets in ascending order of swarm size.
+    #[must_use]
+    pub fn buckets(&self) -> Vec<Bucket> {
+        self.torrents
+            .iter()
+            .enumerate()
+            .map(|(index, torrents)| Bucket {
+                min: if index == 0 { 0 } else { SWARM_SIZE_BUCKETS[index - 1] + 1 },
+                max: SWARM_SIZE_BUCKETS.get(index).copied(),
+                torrents: *torrents,
+            })
+            .collect()
+    }
+}
+
+/// Whether the number of seeders of a torrent is rising, stable or falling
+/// since the previous snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Rising,
+    Stable,
+    Falling,
+}
+
+/// The health of the swarm of a torrent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    /// The health score, from `0` (dead) to `100` (healthy).
+    pub score: u8,
+    /// The ratio of seeders to leechers, from `0.0` to `1.0`. It's `1.0`
+    /// when there are at least as many seeders as leechers.
+    pub availability: f64,
+    /// The change in the number of peers since the previous snapshot, over
+    /// the size of the swarm, from `0.0` to `1.0`.
+    pub churn: f64,
+    /// The trend of the number of seeders since the previous snapshot.
+    pub trend: Trend,
+}
+
+impl Health {
+    /// It computes the health of a swarm, given the swarm in the previous
+    /// snapshot, if the torrent was in it.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn new(swarm_metadata: &SwarmMetadata, previous: Option<&SwarmMetadata>) -> Self {
+        let seeders = f64::from(swarm_metadata.complete);
+        let leechers = f64::from(swarm_metadata.incomplete);
+
+        let availability = if swarm_metadata.incomplete == 0 {
+            if swarm_metadata.complete == 0 {
+                0.0
+            } else {
+                1.0
+            }
+        } else {
+            (seeders / leechers).min(1.0)
+        };
+
+        let (churn, trend) = match previous {
+            Some(previous) => {
+                let peers = swarm_metadata.complete.saturating_add(swarm_metadata.incomplete);
+                let previous_peers = previous.complete.saturating_add(previous.incomplete);
+                let changed = swarm_metadata
+                    .complete
+                    .abs_diff(previous.complete)
+                    .saturating_add(swarm_metadata.incomplete.abs_diff(previous.incomplete));
+
+                let churn = match peers.max(previous_peers) {
+                    0 => 0.0,
+                    size => (f64::from(changed) / f64::from(size)).min(1.0),
+                };
+
+                let trend = match swarm_metadata.complete.cmp(&previous.complete) {
+                    std::cmp::Ordering::Greater => Trend::Rising,
+                    std::cmp::Ordering::Equal => Trend::Stable,
+                    std::cmp::Ordering::Less => Trend::Falling,
+                };
+
+                (churn, trend)
+            }
+            None => (0.0, Trend::Stable),
+        };
+
+        let score = if swarm_metadata.complete == 0 {
+            0
+        } else {
+            let trend_points = match trend {
+                Trend::Rising => 15.0,
+                Trend::Stable => 7.0,
+                Trend::Falling => 0.0,
+            };
+
+            (60.0 * availability + 25.0 * (1.0 - churn) + trend_points).round() as u8
+        };
+
+        Self {
+            score,
+            availability,
+            churn,
+            trend,
+        }
+    }
+}
+
+/// Histogram of the health scores of the torrents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HealthScores {
+    torrents: [u64; HEALTH_SCORE_BUCKETS.len()],
+}
+
+impl HealthScores {
+    fn add(&mut self, score: u8) {
+        let bucket = HEALTH_SCORE_BUCKETS
+            .iter()
+            .position(|max| u32::from(score) <= *max)
+            .unwrap_or(HEALTH_SCORE_BUCKETS.len() - 1);
+
+        self.torrents[bucket] += 1;
+    }
+
+    /// It returns the buckets in ascending order of health score.
+    #[must_use]
+    pub fn buckets(&self) -> Vec<Bucket> {
+        self.torrents
+            .iter()
+            .enumerate()
+            .map(|(index, torrents)| Bucket {
+                min: if index == 0 { 0 } else { HEALTH_SCORE_BUCKETS[index - 1] + 1 },
+                max: Some(HEALTH_SCORE_BUCKETS[index]),
+                torrents: *torrents,
+            })
+            .collect()
+    }
+}
+
+/// An immutable copy of the swarm metadata of all torrents.
+#[derive(Debug, Default)]
+pub struct Data {
+    swarms: HashMap<InfoHash, SwarmMetadata>,
+    metrics: TorrentsMetrics,
+    swarm_sizes: SwarmSizes,
+    health: HashMap<InfoHash, Health>,
+    health_scores: HealthScores,
+}
+
+impl Data {
+    /// It returns the swarm metadata of the torrent, or zeroed metadata if
+    /// the torrent was not in the snapshot.
+    #[must_use]
+    pub fn get_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
+        self.swarms.get(info_hash).copied().unwrap_or_default()
+    }
+
+    /// It returns the swarm metadata of the torrent, or `None` if the torrent
+    /// was not in the snapshot.
+    #[must_use]
+    pub fn find_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
+        self.swarms.get(info_hash).copied()
+    }
+
+    #[must_use]
+    pub fn get_metrics(&self) -> TorrentsMetrics {
+        self.metrics
+    }
+
+    #[must_use]
+    pub fn get_swarm_sizes(&self) -> SwarmSizes {
+        self.swarm_sizes
+    }
+
+    /// It returns the health of the torrent, or `None` if the torrent was not
+    /// in the snapshot.
+    #[must_use]
+    pub fn get_health(&self, info_hash: &InfoHash) -> Option<Health> {
+        self.health.get(info_hash).copied()
+    }
+
+    #[must_use]
+    pub fn get_health_scores(&self) -> HealthScores {
+        self.health_scores
+    }
+
+    /// It computes the health of all the torrents, comparing their swarms
+    /// with the ones in the `previous` snapshot.
+    #[must_use]
+    pub fn with_health(mut self, previous: &Data) -> Self {
+        let mut health_scores = HealthScores::default();
+
+        self.health = self
+            .swarms
+            .iter()
+            .map(|(info_hash, swarm_metadata)| {
+                let health = Health::new(swarm_metadata, previous.swarms.get(info_hash));
+                health_scores.add(health.score);
+                (*info_hash, health)
+            })
+            .collect();
+
+        self.health_scores = health_scores;
+
+        self
+    }
+}
+
+impl FromIterator<(InfoHash, SwarmMetadata)> for Data {
+    fn from_iter<T: IntoIterator<Item = (InfoHash, SwarmMetadata)>>(iter: T) -> Self {
+        let mut metrics = TorrentsMetrics::default();
+        let mut swarm_sizes = SwarmSizes::default();
+
+        let swarms = iter
+            .into_iter()
+            .inspect(|(_, swarm_metadata)| {
+                metrics.complete += u64::from(swarm_metadata.complete);
+                metrics.downloaded += u64::from(swarm_metadata.downloaded);
+                metrics.incomplete += u64::from(swarm_metadata.incomplete);
+                metrics.torrents += 1;
+                swarm_sizes.add(swarm_metadata.complete.saturating_add(swarm_metadata.incomplete));
+            })
+            .collect();
+
+        Self {
+            swarms,
+            metrics,
+            swarm_sizes,
+            health: HashMap::new(),
+            health_scores: HealthScores::default(),
+        }
+    }
+}
+
+/// The last published [`Data`] snapshot.
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    current: RwLock<Arc<Data>>,
+}
+
+impl Snapshot {
+    /// It returns the current snapshot. It stays valid even when a new one is
+    /// published.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the snapshot lock is poisoned.
+    #[must_use]
+    pub fn load(&self) -> Arc<Data> {
+        self.current.read().expect("it should get the snapshot lock").clone()
+    }
+
+    /// It replaces the current snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the snapshot lock is poisoned.
+    pub fn publish(&self, data: Data) {
+        *self.current.write().expect("it should get the snapshot lock") = Arc::new(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_primitives::info_hash::InfoHash;
+    use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+    use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+
+    use super::{Bucket, Data, Health, Snapshot, Trend};
+
+    fn swarm_metadata(complete: u32, incomplete: u32, downloaded: u32) -> SwarmMetadata {
+        SwarmMetadata {
+            downloaded,
+            complete,
+            incomplete,
+            partial_seeds: 0,
+        }
+    }
+
+    #[test]
+    fn it_should_aggregate_the_metrics_of_all_the_torrents() {
+        let data: Data = vec![
+            (InfoHash::from([1; 20]), swarm_metadata(1, 2, 3)),
+            (InfoHash::from([2; 20]), swarm_metadata(4, 5, 6)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            data.get_metrics(),
+            TorrentsMetrics {
+                complete: 5,
+                downloaded: 9,
+                incomplete: 7,
+                torrents: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_count_the_torrents_by_swarm_size() {
+        let data: Data = vec![
+            (InfoHash::from([1; 20]), swarm_metadata(0, 0, 3)),
+            (InfoHash::from([2; 20]), swarm_metadata(4, 6, 0)),
+            (InfoHash::from([3; 20]), swarm_metadata(5, 6, 0)),
+            (InfoHash::from([4; 20]), swarm_metadata(20_000, 0, 0)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            data.get_swarm_sizes().buckets(),
+            vec![
+                Bucket {
+                    min: 0,
+                    max: Some(0),
+                    torrents: 1
+                },
+                Bucket {
+                    min: 1,
+                    max: Some(10),
+                    torrents: 1
+                },
+                Bucket {
+                    min: 11,
+                    max: Some(100),
+                    torrents: 1
+                },
+                Bucket {
+                    min: 101,
+                    max: Some(1_000),
+                    torrents: 0
+                },
+                Bucket {
+                    min: 1_001,
+                    max: Some(10_000),
+                    torrents: 0
+                },
+                Bucket {
+                    min: 10_001,
+                    max: None,
+                    torrents: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_torrent_without_seeders_should_have_a_zero_health_score() {
+        let health = Health::new(&swarm_metadata(0, 5, 0), None);
+
+        assert_eq!(health.score, 0);
+    }
+
+    #[test]
+    fn a_new_torrent_with_more_seeders_than_leechers_should_have_no_churn_and_a_stable_trend() {
+        assert_eq!(
+            Health::new(&swarm_metadata(10, 5, 0), None),
+            Health {
+                score: 92,
+                availability: 1.0,
+                churn: 0.0,
+                trend: Trend::Stable,
+            }
+        );
+    }
+
+    #[test]
+    fn the_health_should_compare_the_swarm_with_the_one_in_the_previous_snapshot() {
+        assert_eq!(
+            Health::new(&swarm_metadata(2, 4, 0), Some(&swarm_metadata(4, 4, 0))),
+            Health {
+                score: 49,
+                availability: 0.5,
+                churn: 0.25,
+                trend: Trend::Falling,
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_count_the_torrents_by_health_score() {
+        let previous = Data::default();
+
+        let data = vec![
+            (InfoHash::from([1; 20]), swarm_metadata(0, 3, 0)),
+            (InfoHash::from([2; 20]), swarm_metadata(10, 5, 0)),
+        ]
+        .into_iter()
+        .collect::<Data>()
+        .with_health(&previous);
+
+        assert_eq!(
+            data.get_health_scores()
+                .buckets()
+                .iter()
+                .map(|bucket| bucket.torrents)
+                .collect::<Vec<_>>(),
+            vec![1, 0, 0, 0, 1]
+        );
+        assert_eq!(data.get_health(&InfoHash::from([2; 20])).unwrap().score, 92);
+        assert_eq!(data.get_health(&InfoHash::from([3; 20])), None);
+    }
+
+    #[test]
+    fn it_should_return_zeroed_swarm_metadata_for_torrents_not_in_the_snapshot() {
+        let data = Data::default();
+
+        assert_eq!(data.get_swarm_metadata(&InfoHash::from([1; 20])), SwarmMetadata::zeroed());
+    }
+
+    #[test]
+    fn a_loaded_snapshot_should_not_change_when_a_new_one_is_published() {
+        let snapshot = Snapshot::default();
+
+        let before = snapshot.load();
+
+        snapshot.publish(vec![(InfoHash::from([1; 20]), swarm_metadata(1, 0, 0))].into_iter().collect());
+
+        assert_eq!(before.get_metrics().torrents, 0);
+        assert_eq!(snapshot.load().get_metrics().torrents, 1);
+    }
+}
@@ -0,0 +1,55 @@
+//! Static seed peers.
+//!
+//! Operators can register permanent peers for a torrent, like a web seed
+//! or an origin server, to bootstrap new swarms. The static peers:
+//!
+//! - Are always included in the `announce` responses, before the swarm peers.
+//! - Are counted as seeders.
+//! - Never expire. The torrent is not removed while it has static peers.
+//!
+//! They are persisted in the database and managed through the API. They are
+//! stored apart from the peers that announce themselves, so an `announce`
+//! request from the same address does not replace them.
+use std::net::SocketAddr;
+
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+
+/// The peer ID of all the static peers.
+pub const STATIC_PEER_ID: PeerId = PeerId(*b"-TT0000-staticpeer00");
+
+/// It builds the peer returned to the clients for a static peer address.
+///
+/// The static peers are seeders that never announce themselves.
+#[must_use]
+pub fn to_peer(peer_addr: &SocketAddr) -> peer::Peer {
+    peer::Peer {
+        peer_id: STATIC_PEER_ID,
+        peer_addr: *peer_addr,
+        updated: DurationSinceUnixEpoch::ZERO,
+        uploaded: NumberOfBytes::new(0),
+        downloaded: NumberOfBytes::new(0),
+        left: NumberOfBytes::new(0),
+        event: AnnounceEvent::None,
+        partial_seed: false,
+        corrupt: NumberOfBytes::new(0),
+        redundant: NumberOfBytes::new(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::to_peer;
+
+    #[test]
+    fn it_should_build_a_seeder_with_the_static_peer_address() {
+        let peer_addr: SocketAddr = "126.0.0.1:8080".parse().unwrap();
+
+        let peer = to_peer(&peer_addr);
+
+        assert_eq!(peer.peer_addr, peer_addr);
+        assert!(peer.is_seeder());
+    }
+}
@@ -0,0 +1,151 @@
+//! Trackers served by the same process as the main tracker.
+//!
+//! Each [virtual tracker](torrust_tracker_configuration::VirtualTracker) is a
+//! separate [`Tracker`] with its own core configuration, so it has its own
+//! mode, whitelist, keys, torrents and database. The services are bound to a
+//! virtual tracker by name, and the HTTP trackers also route the requests to
+//! a virtual tracker by their `Host`. The tracker API serves each virtual
+//! tracker under `/api/v1/trackers/{name}`.
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use torrust_tracker_configuration::VirtualTracker;
+
+use super::services::core_tracker_factory;
+use super::Tracker;
+
+/// The virtual trackers, by name.
+#[derive(Default, Clone)]
+pub struct VirtualTrackers {
+    trackers: HashMap<String, Arc<Tracker>>,
+    hosts: VirtualHosts,
+}
+
+impl VirtualTrackers {
+    /// It builds a tracker for each virtual tracker configuration.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a tracker cannot be instantiated.
+    #[must_use]
+    pub fn new(config: &[VirtualTracker]) -> Self {
+        let mut trackers = HashMap::new();
+        let mut hosts = HashMap::new();
+
+        for virtual_tracker in config {
+            let tracker = Arc::new(core_tracker_factory(&virtual_tracker.core));
+
+            for host in &virtual_tracker.hosts {
+                hosts.insert(host.to_lowercase(), tracker.clone());
+            }
+
+            trackers.insert(virtual_tracker.name.clone(), tracker);
+        }
+
+        Self {
+            trackers,
+            hosts: VirtualHosts(Arc::new(hosts)),
+        }
+    }
+
+    /// The tracker with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Arc<Tracker>> {
+        self.trackers.get(name)
+    }
+
+    /// The virtual trackers with their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Arc<Tracker>)> {
+        self.trackers.iter()
+    }
+
+    /// The trackers routed by the `Host` of the HTTP requests.
+    #[must_use]
+    pub fn hosts(&self) -> VirtualHosts {
+        self.hosts.clone()
+    }
+}
+
+/// The trackers serving the HTTP requests for some hosts, instead of the
+/// tracker bound to the HTTP tracker.
+#[derive(Default, Clone)]
+pub struct VirtualHosts(Arc<HashMap<String, Arc<Tracker>>>);
+
+impl VirtualHosts {
+    /// The tracker serving the given `Host`. The port is ignored and the
+    /// hostname is case-insensitive.
+    #[must_use]
+    pub fn get(&self, host: &str) -> Option<&Arc<Tracker>> {
+        self.0.get(&hostname(host).to_lowercase())
+    }
+
+    /// The trackers with the hosts they serve.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Arc<Tracker>)> {
+        self.0.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for VirtualTrackers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.trackers.keys()).finish()
+    }
+}
+
+impl fmt::Debug for VirtualHosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// It removes the port from a `Host` header value.
+fn hostname(host: &str) -> &str {
+    match host.rsplit_once(':') {
+        // IPv6 addresses without port, like `[::1]`
+        Some((name, port)) if !port.ends_with(']') => name,
+        _ => host,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_configuration::VirtualTracker;
+    use torrust_tracker_test_helpers::configuration;
+
+    use super::VirtualTrackers;
+
+    fn private_virtual_tracker() -> VirtualTracker {
+        VirtualTracker {
+            name: "private".to_string(),
+            hosts: vec!["Private.Tracker.Example.com".to_string()],
+            core: configuration::ephemeral_private().core,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_build_a_separate_tracker_for_each_virtual_tracker() {
+        let virtual_trackers = VirtualTrackers::new(&[private_virtual_tracker()]);
+
+        let tracker = virtual_trackers.get("private").unwrap();
+
+        assert!(tracker.is_private());
+        assert!(virtual_trackers.get("other").is_none());
+    }
+
+    #[tokio::test]
+    async fn it_should_route_the_hosts_ignoring_the_port_and_the_case() {
+        let virtual_trackers = VirtualTrackers::new(&[private_virtual_tracker()]);
+
+        let hosts = virtual_trackers.hosts();
+
+        assert!(hosts.get("private.tracker.example.com").is_some());
+        assert!(hosts.get("PRIVATE.tracker.example.com:7070").is_some());
+        assert!(hosts.get("public.tracker.example.com").is_none());
+        assert!(hosts.get("[::1]").is_none());
+    }
+}
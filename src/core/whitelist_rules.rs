@@ -0,0 +1,208 @@
+//! Whitelist rules.
+//!
+//! Besides the exact infohashes in the whitelist, listed trackers can allow
+//! torrents with rules. A rule has a pattern, an optional expiration date and
+//! an optional comment.
+//!
+//! Pattern | Description
+//! ---|---
+//! `*` | Allows all the torrents. Useful for temporary "allow all" windows.
+//! `c1277613*` | Allows the torrents whose infohash starts with the hexadecimal prefix.
+//!
+//! A torrent is allowed when it's in the whitelist or when it matches any of
+//! the rules that have not expired yet. Expired rules are kept until they are
+//! removed, but they do not allow any torrent.
+//!
+//! The rules are persisted in the database and loaded with the whitelist.
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+/// The number of hexadecimal characters of a `BitTorrent` infohash V1.
+const INFO_HASH_HEX_LENGTH: usize = 40;
+
+/// The torrents allowed by a whitelist [`Rule`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// All the torrents.
+    Any,
+    /// The torrents whose infohash starts with the lowercase hexadecimal
+    /// prefix.
+    Prefix(String),
+}
+
+impl Pattern {
+    /// It returns `true` if the pattern matches the infohash.
+    #[must_use]
+    pub fn matches(&self, info_hash: &InfoHash) -> bool {
+        match self {
+            Pattern::Any => true,
+            Pattern::Prefix(prefix) => info_hash.to_hex_string().starts_with(prefix.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Any => write!(f, "*"),
+            Pattern::Prefix(prefix) => write!(f, "{prefix}*"),
+        }
+    }
+}
+
+/// Error returned when a string is not a valid whitelist [`Pattern`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid whitelist rule pattern: {0}")]
+pub struct ParsePatternError(pub String);
+
+impl FromStr for Pattern {
+    type Err = ParsePatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(prefix) = s.strip_suffix('*') else {
+            return Err(ParsePatternError(s.to_owned()));
+        };
+
+        if prefix.len() >= INFO_HASH_HEX_LENGTH || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParsePatternError(s.to_owned()));
+        }
+
+        if prefix.is_empty() {
+            Ok(Pattern::Any)
+        } else {
+            Ok(Pattern::Prefix(prefix.to_ascii_lowercase()))
+        }
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A whitelist rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The torrents allowed by the rule.
+    pub pattern: Pattern,
+    /// The rule does not allow any torrent after this time. `None` for rules
+    /// that never expire.
+    pub valid_until: Option<DurationSinceUnixEpoch>,
+    /// A free text to describe the rule, e.g. why it was added.
+    pub comment: Option<String>,
+}
+
+impl Rule {
+    /// It returns `true` if the rule has not expired at the given time.
+    #[must_use]
+    pub fn is_active(&self, now: DurationSinceUnixEpoch) -> bool {
+        self.valid_until.map_or(true, |valid_until| valid_until > now)
+    }
+
+    /// It returns `true` if the rule has not expired at the given time and it
+    /// matches the infohash.
+    #[must_use]
+    pub fn allows(&self, info_hash: &InfoHash, now: DurationSinceUnixEpoch) -> bool {
+        self.is_active(now) && self.pattern.matches(info_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_whitelist_rule_pattern {
+        use std::str::FromStr;
+
+        use torrust_tracker_primitives::info_hash::InfoHash;
+
+        use crate::core::whitelist_rules::Pattern;
+
+        fn info_hash() -> InfoHash {
+            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse().unwrap()
+        }
+
+        #[test]
+        fn it_should_be_parsed_from_its_string_representation() {
+            for pattern in [Pattern::Any, Pattern::Prefix("3b2455".to_string())] {
+                assert_eq!(Pattern::from_str(&pattern.to_string()), Ok(pattern));
+            }
+        }
+
+        #[test]
+        fn it_should_normalize_the_prefix_to_lowercase() {
+            assert_eq!(Pattern::from_str("3B2455*"), Ok(Pattern::Prefix("3b2455".to_string())));
+        }
+
+        #[test]
+        fn it_should_fail_parsing_invalid_patterns() {
+            for pattern in [
+                "",
+                "3b2455",
+                "3b24*55*",
+                "not-hex*",
+                "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0*",
+            ] {
+                assert!(Pattern::from_str(pattern).is_err(), "{pattern}");
+            }
+        }
+
+        #[test]
+        fn the_any_pattern_should_match_all_torrents() {
+            assert!(Pattern::Any.matches(&info_hash()));
+        }
+
+        #[test]
+        fn a_prefix_pattern_should_only_match_the_torrents_with_the_prefix() {
+            assert!(Pattern::Prefix("3b2455".to_string()).matches(&info_hash()));
+            assert!(!Pattern::Prefix("3b2456".to_string()).matches(&info_hash()));
+        }
+    }
+
+    mod the_whitelist_rule {
+        use std::time::Duration;
+
+        use torrust_tracker_primitives::info_hash::InfoHash;
+
+        use crate::core::whitelist_rules::{Pattern, Rule};
+
+        fn info_hash() -> InfoHash {
+            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse().unwrap()
+        }
+
+        #[test]
+        fn it_should_allow_the_matching_torrents_before_it_expires() {
+            let rule = Rule {
+                pattern: Pattern::Any,
+                valid_until: Some(Duration::from_secs(100)),
+                comment: None,
+            };
+
+            assert!(rule.allows(&info_hash(), Duration::from_secs(99)));
+            assert!(!rule.allows(&info_hash(), Duration::from_secs(100)));
+        }
+
+        #[test]
+        fn it_should_never_expire_without_an_expiration_date() {
+            let rule = Rule {
+                pattern: Pattern::Any,
+                valid_until: None,
+                comment: None,
+            };
+
+            assert!(rule.allows(&info_hash(), Duration::MAX));
+        }
+    }
+}
@@ -30,6 +30,7 @@
 //!     - [Minimum requirements](#minimum-requirements)
 //!     - [Prerequisites](#prerequisites)
 //!     - [Install from sources](#install-from-sources)
+//!     - [Build a minimal binary](#build-a-minimal-binary)
 //!     - [Run with docker](#run-with-docker)
 //! - [Configuration](#configuration)
 //! - [Usage](#usage)
@@ -88,7 +89,7 @@
 //!
 //! The tracker has some system dependencies:
 //!
-//! Since we are using the `openssl` crate with the [vendored feature](https://docs.rs/openssl/latest/openssl/#vendored),
+//! Since the `MySQL` database driver uses the `openssl` crate with the [vendored feature](https://docs.rs/openssl/latest/openssl/#vendored),
 //! enabled, you will need to install the following dependencies:
 //!
 //! ```text
@@ -152,6 +153,67 @@
 //! cargo run
 //! ```
 //!
+//! Running the tracker is the default command. The other tools, like the
+//! tracker checker or the configuration validator, are subcommands too:
+//!
+//! ```text
+//! cargo run -- --help
+//! cargo run -- config validate --config ./share/default/config/tracker.development.sqlite3.toml
+//! ```
+//!
+//! Refer to the [command line interface](crate::console::cli) for all the
+//! commands.
+//!
+//! ## Build a minimal binary
+//!
+//! All the services and database drivers are built by default. Each one can be
+//! left out with these Cargo features:
+//!
+//! Feature | Includes
+//! ---|---
+//! `udp`   | The [`UDP`](crate::servers::udp) tracker
+//! `http`  | The [`HTTP`](crate::servers::http) tracker
+//! `api`   | The tracker [`API`](crate::servers::apis)
+//! `tls`   | HTTPS for the HTTP tracker and the tracker API
+//! `web`   | The [Health Check API](crate::servers::health_check_api) and the web servers shared by the HTTP tracker and the tracker API. It's enabled by `http` and `api`
+//! `sqlite`| The `SQLite3` database driver
+//! `mysql` | The `MySQL` database driver
+//!
+//! For example, to build a UDP-only tracker using `SQLite3`, which compiles
+//! much faster on constrained hosts:
+//!
+//! ```text
+//! cargo build --release --no-default-features --features udp,sqlite
+//! ```
+//!
+//! The UDP-only build does not include the Health Check API, so it does not
+//! depend on the web framework. Add the `web` feature to include it. The
+//! tracker logs a warning for every service in the configuration that was
+//! left out of the build, and it fails to start if the configured database
+//! driver was left out.
+//!
+//! The `fault-injection` feature is not built by default. It applies the
+//! `core.fault_injection` options, which inject latency, database write
+//! failures and repository delays for resilience testing.
+//!
+//! The tracker can also run without any database, with the `none` driver:
+//!
+//! ```toml
+//! [core.database]
+//! driver = "none"
+//! ```
+//!
+//! Nothing is persisted: the number of completed downloads is only kept in
+//! memory, and the API endpoints that need the database (keys, whitelist,
+//! torrent policies, static peers, key quotas and the audit log) fail with a
+//! `persistence_disabled` error. It's meant for public trackers that don't
+//! need to write to the filesystem, and it can be built without any of the
+//! database drivers:
+//!
+//! ```text
+//! cargo build --release --no-default-features --features udp
+//! ```
+//!
 //! ## Run with docker
 //!
 //! You can run the tracker with a pre-built docker image. Please refer to the
@@ -171,8 +233,10 @@
 //! threshold = "info"
 //!
 //! [core]
+//! hybrid = false
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
+//! max_scrape_torrents = 74
 //! private = false
 //! tracker_usage_statistics = true
 //!
@@ -184,14 +248,28 @@
 //! driver = "sqlite3"
 //! path = "./storage/tracker/lib/database/sqlite3.db"
 //!
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//!
 //! [core.net]
+//! exclude_ipv6_peers_for_ipv4_clients = false
 //! external_ip = "0.0.0.0"
 //! on_reverse_proxy = false
+//! trusted_proxies = []
+//!
+//! [core.statistics]
+//! http = true
+//! udp = true
+//! histograms = true
+//! connection_ids = true
 //!
 //! [core.tracker_policy]
 //! max_peer_timeout = 900
 //! persistent_torrent_completed_stat = false
 //! remove_peerless_torrents = true
+//! peer_eviction_policy = "reject_new"
+//! merge_dual_stack_peers = false
 //!
 //! [health_check_api]
 //! bind_address = "127.0.0.1:1313"
@@ -224,6 +302,26 @@
 //! > IMPORTANT: Every time you change the configuration you need to restart the
 //! > service.
 //!
+//! The [JSON Schema](https://json-schema.org/) of the configuration can be
+//! used to validate it in editors and configuration management tools. You
+//! can print the schema for the version of the tracker you are running with:
+//!
+//! ```text
+//! ./target/release/torrust-tracker --print-config-schema > tracker.schema.json
+//! ```
+//!
+//! It's also served by the tracker API, at `GET /api/v1/configuration/schema`.
+//!
+//! For local development, or parallel CI runs, you can start the tracker in
+//! [development mode](crate::bootstrap::dev). The services whose configured
+//! port is already in use are bound to an ephemeral port instead of failing:
+//!
+//! ```text
+//! ./target/release/torrust-tracker --dev
+//! ```
+//!
+//! The actual addresses are logged and written to the service manifest.
+//!
 //! # Usage
 //!
 //! Running the tracker with the default configuration and enabling the UDP and
@@ -268,15 +366,48 @@
 //!     "tcp4_connections_handled": 0,
 //!     "tcp4_announces_handled": 0,
 //!     "tcp4_scrapes_handled": 0,
+//!     "tcp4_scrapes_exceeding_limit": 0,
 //!     "tcp6_connections_handled": 0,
 //!     "tcp6_announces_handled": 0,
 //!     "tcp6_scrapes_handled": 0,
+//!     "tcp6_scrapes_exceeding_limit": 0,
+//!     "tcp_announce_responses_under_128_bytes": 0,
+//!     "tcp_announce_responses_under_512_bytes": 0,
+//!     "tcp_announce_responses_under_2048_bytes": 0,
+//!     "tcp_announce_responses_over_2048_bytes": 0,
+//!     "tcp_announce_responses_padded": 0,
+//!     "tcp_announces_with_unknown_event": 0,
+//!     "tcp_scrape_duplicate_info_hashes": 0,
+//!     "tcp_requests_with_uri_too_long": 0,
+//!     "tcp_requests_with_headers_too_large": 0,
+//!     "tcp_announces_with_deadline_exceeded": 0,
+//!     "tcp_scrapes_with_deadline_exceeded": 0,
+//!     "tcp_announces_with_key_bound_to_another_address": 0,
 //!     "udp4_connections_handled": 0,
 //!     "udp4_announces_handled": 0,
 //!     "udp4_scrapes_handled": 0,
+//!     "udp4_scrapes_exceeding_limit": 0,
+//!     "udp4_connection_ids_used": 0,
+//!     "udp4_connection_ids_unused": 0,
+//!     "udp4_announces_with_expired_connection_id": 0,
+//!     "udp4_connect_to_announce_conversion_rate": 0.0,
+//!     "udp4_expired_connection_id_ratio": 0.0,
 //!     "udp6_connections_handled": 0,
 //!     "udp6_announces_handled": 0,
-//!     "udp6_scrapes_handled": 0
+//!     "udp6_scrapes_handled": 0,
+//!     "udp6_scrapes_exceeding_limit": 0,
+//!     "udp6_connection_ids_used": 0,
+//!     "udp6_connection_ids_unused": 0,
+//!     "udp6_announces_with_expired_connection_id": 0,
+//!     "udp6_connect_to_announce_conversion_rate": 0.0,
+//!     "udp6_expired_connection_id_ratio": 0.0,
+//!     "udp_announces_with_unknown_event": 0,
+//!     "udp_scrape_duplicate_info_hashes": 0,
+//!     "udp_negative_cache_hits": 0,
+//!     "udp_announces_with_deadline_exceeded": 0,
+//!     "udp_scrapes_with_deadline_exceeded": 0,
+//!     "torrents_created": 0,
+//!     "torrents_removed": 0
 //! }
 //! ```
 //!
@@ -294,6 +425,10 @@
 //! - Announce: <http://127.0.0.1:7070/announce/key>
 //! - Scrape: <http://127.0.0.1:7070/scrape/key>
 //!
+//! The key can also go before the action (`/key/announce` and `/key/scrape`),
+//! which is the format used by some other private trackers, if the HTTP tracker
+//! is configured with `key_path_format = "prefix"` (or `"both"`).
+//!
 //! In order to use the HTTP tracker you need to enable at least one server in the configuration:
 //!
 //! ```toml
@@ -419,7 +554,7 @@
 //! The core tracker handles:
 //!
 //! - Authentication with keys
-//! - Authorization using a torrent whitelist
+//! - Authorization using a torrent whitelist and per-torrent announce policies
 //! - Statistics
 //! - Persistence
 //!
@@ -427,9 +562,13 @@
 //!
 //! ## Tracker API
 //!
-//! The tracker exposes a REST API. The API has four resource groups:
+//! The tracker exposes a REST API. The API has these resource groups:
 //!
 //! - Authentication keys: to handle the keys for the HTTP tracker
+//! - Database backups: to back up the `SQLite` database and get the status of the last backup
+//! - Maintenance: to enable or disable the maintenance mode
+//! - Policies: to set `seed-only` or `frozen` policies for specific torrents
+//! - Static peers: to add permanent seed peers to specific torrents
 //! - Statistics: to get the tracker metrics like requests counters
 //! - Torrents: to get peers for a torrent
 //! - Whitelist: to handle the torrent whitelist when the tracker runs on `listed` or `private_listed` mode
@@ -487,6 +626,10 @@
 //!
 //! In addition to the production code documentation you can find a lot of
 //! examples on the integration and unit tests.
+//!
+//! The helpers used by the integration tests (ephemeral configurations,
+//! running servers and typed clients) are also available to other crates in
+//! the `testing` module when the `testing` feature is enabled.
 
 use torrust_tracker_clock::{clock, time_extent};
 
@@ -497,6 +640,9 @@ pub mod core;
 pub mod servers;
 pub mod shared;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[macro_use]
 extern crate lazy_static;
 
@@ -0,0 +1,172 @@
+//! Admission control for the HTTP trackers.
+//!
+//! The [`AdmissionAcceptor`] wraps the acceptor of the HTTP trackers. While
+//! the usage of file descriptors is close to the process limit, new
+//! connections are answered with a `503 Service Unavailable` response and
+//! closed, instead of being served. The connections already accepted are not
+//! affected.
+//!
+//! For HTTPS connections, the response can't be sent before the TLS
+//! handshake, so the connections are just closed.
+//!
+//! Refer to the [`file_descriptors`](crate::core::file_descriptors) module
+//! for more information.
+use std::io;
+use std::sync::Arc;
+
+use axum_server::accept::Accept;
+use futures::future::BoxFuture;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+use crate::core::file_descriptors::Monitor;
+
+const SERVICE_UNAVAILABLE: &[u8] =
+    b"HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nRetry-After: 1\r\nContent-Length: 0\r\n\r\n";
+
+#[derive(Clone)]
+pub struct AdmissionAcceptor<A> {
+    inner: A,
+    monitor: Option<Arc<Monitor>>,
+    reply: bool,
+}
+
+impl<A> AdmissionAcceptor<A> {
+    /// It answers the rejected connections with a `503 Service Unavailable`
+    /// response.
+    #[must_use]
+    pub fn new(inner: A, monitor: Option<Arc<Monitor>>) -> Self {
+        Self {
+            inner,
+            monitor,
+            reply: true,
+        }
+    }
+
+    /// It closes the rejected connections without a response. It's used for
+    /// HTTPS connections.
+    #[must_use]
+    pub fn closing(inner: A, monitor: Option<Arc<Monitor>>) -> Self {
+        Self {
+            inner,
+            monitor,
+            reply: false,
+        }
+    }
+}
+
+impl<A, S> Accept<TcpStream, S> for AdmissionAcceptor<A>
+where
+    A: Accept<TcpStream, S> + Clone + Send + Sync + 'static,
+    A::Future: Send,
+    S: Send + 'static,
+{
+    type Stream = A::Stream;
+    type Service = A::Service;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, mut stream: TcpStream, service: S) -> Self::Future {
+        let Some(monitor) = self.monitor.as_ref().filter(|monitor| monitor.is_near_the_limit()) else {
+            return Box::pin(self.inner.accept(stream, service));
+        };
+
+        monitor.increase_rejected_connections();
+
+        let reply = self.reply;
+
+        Box::pin(async move {
+            if reply {
+                stream.write_all(SERVICE_UNAVAILABLE).await?;
+                stream.shutdown().await?;
+            }
+
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "connection rejected, the file descriptors usage is close to the limit",
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::Arc;
+
+    use axum_server::accept::{Accept, DefaultAcceptor};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::{AdmissionAcceptor, SERVICE_UNAVAILABLE};
+    use crate::core::file_descriptors::Monitor;
+
+    /// Every accepted connection takes a slot, out of two.
+    fn monitor() -> Arc<Monitor> {
+        Arc::new(Monitor::with_usage(100, 0, Some(2)))
+    }
+
+    /// It opens a connection and returns the client side and the result of
+    /// accepting the server side.
+    async fn connect(
+        listener: &TcpListener,
+        acceptor: &AdmissionAcceptor<DefaultAcceptor>,
+    ) -> (TcpStream, io::Result<TcpStream>) {
+        let client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+
+        (client, acceptor.accept(server, ()).await.map(|(stream, ())| stream))
+    }
+
+    async fn read_to_end(mut client: TcpStream) -> Vec<u8> {
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn it_should_answer_the_connections_with_a_503_response_while_the_limit_is_reached() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let monitor = monitor();
+        let acceptor = AdmissionAcceptor::new(DefaultAcceptor, Some(monitor.clone()));
+
+        let (_first_client, first) = connect(&listener, &acceptor).await;
+        monitor.set_open(1);
+        let (_second_client, second) = connect(&listener, &acceptor).await;
+        monitor.set_open(2);
+
+        assert!(first.is_ok() && second.is_ok());
+
+        let (client, rejected) = connect(&listener, &acceptor).await;
+
+        assert!(rejected.is_err());
+        assert_eq!(read_to_end(client).await, SERVICE_UNAVAILABLE);
+        assert_eq!(monitor.rejected_connections(), 1);
+
+        drop(first);
+        monitor.set_open(1);
+
+        let (_client, admitted) = connect(&listener, &acceptor).await;
+
+        assert!(admitted.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_close_the_connections_without_a_response_when_it_is_closing() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let monitor = monitor();
+        let acceptor = AdmissionAcceptor::closing(DefaultAcceptor, Some(monitor.clone()));
+
+        monitor.set_open(2);
+
+        let (client, rejected) = connect(&listener, &acceptor).await;
+
+        assert!(rejected.is_err());
+        assert!(read_to_end(client).await.is_empty());
+
+        monitor.set_open(1);
+
+        let (_client, admitted) = connect(&listener, &acceptor).await;
+
+        assert!(admitted.is_ok());
+    }
+}
@@ -71,15 +71,48 @@
 //!   "tcp4_connections_handled": 0,
 //!   "tcp4_announces_handled": 0,
 //!   "tcp4_scrapes_handled": 0,
+//!   "tcp4_scrapes_exceeding_limit": 0,
 //!   "tcp6_connections_handled": 0,
 //!   "tcp6_announces_handled": 0,
 //!   "tcp6_scrapes_handled": 0,
+//!   "tcp6_scrapes_exceeding_limit": 0,
+//!   "tcp_announce_responses_under_128_bytes": 0,
+//!   "tcp_announce_responses_under_512_bytes": 0,
+//!   "tcp_announce_responses_under_2048_bytes": 0,
+//!   "tcp_announce_responses_over_2048_bytes": 0,
+//!   "tcp_announce_responses_padded": 0,
+//!   "tcp_announces_with_unknown_event": 0,
+//!   "tcp_scrape_duplicate_info_hashes": 0,
+//!   "tcp_requests_with_uri_too_long": 0,
+//!   "tcp_requests_with_headers_too_large": 0,
+//!   "tcp_announces_with_deadline_exceeded": 0,
+//!   "tcp_scrapes_with_deadline_exceeded": 0,
+//!   "tcp_announces_with_key_bound_to_another_address": 0,
 //!   "udp4_connections_handled": 0,
 //!   "udp4_announces_handled": 0,
 //!   "udp4_scrapes_handled": 0,
+//!   "udp4_scrapes_exceeding_limit": 0,
+//!   "udp4_connection_ids_used": 0,
+//!   "udp4_connection_ids_unused": 0,
+//!   "udp4_announces_with_expired_connection_id": 0,
+//!   "udp4_connect_to_announce_conversion_rate": 0.0,
+//!   "udp4_expired_connection_id_ratio": 0.0,
 //!   "udp6_connections_handled": 0,
 //!   "udp6_announces_handled": 0,
-//!   "udp6_scrapes_handled": 0
+//!   "udp6_scrapes_handled": 0,
+//!   "udp6_scrapes_exceeding_limit": 0,
+//!   "udp6_connection_ids_used": 0,
+//!   "udp6_connection_ids_unused": 0,
+//!   "udp6_announces_with_expired_connection_id": 0,
+//!   "udp6_connect_to_announce_conversion_rate": 0.0,
+//!   "udp6_expired_connection_id_ratio": 0.0,
+//!   "udp_announces_with_unknown_event": 0,
+//!   "udp_scrape_duplicate_info_hashes": 0,
+//!   "udp_negative_cache_hits": 0,
+//!   "udp_announces_with_deadline_exceeded": 0,
+//!   "udp_scrapes_with_deadline_exceeded": 0,
+//!   "torrents_created": 0,
+//!   "torrents_removed": 0
 //! }
 //! ```
 //!
@@ -97,7 +130,19 @@
 //! ```
 //!
 //! The token label is used to identify the token. All tokens have full access
-//! to the API.
+//! to the API, except for the administrative endpoints, like the one to
+//! [force-expire all the authentication keys](crate::servers::apis::v1::context::auth_key#force-expire-all-authentication-keys).
+//! They require an admin token:
+//!
+//! ```toml
+//! [http_api.admin_access_tokens]
+//! incident-response = "MyAdminAccessToken"
+//! ```
+//!
+//! Administrative actions are logged with the `AUDIT` log target. Every
+//! state-changing request is also recorded in the [audit log](crate::core::audit),
+//! which can be retrieved with the
+//! [audit log endpoint](crate::servers::apis::v1::context::audit_log).
 //!
 //! Refer to [`torrust-tracker-configuration`](torrust_tracker_configuration)
 //! for more information about the API configuration and to the
@@ -161,6 +206,9 @@ use serde::{Deserialize, Serialize};
 
 pub const API_LOG_TARGET: &str = "API";
 
+/// Log target for the administrative actions executed through the API.
+pub const AUDIT_LOG_TARGET: &str = "AUDIT";
+
 /// The info hash URL path parameter.
 ///
 /// Some API endpoints require an info hash as a path parameter.
@@ -170,6 +218,10 @@ pub const API_LOG_TARGET: &str = "API";
 /// The info hash represents teh value collected from the URL path parameter.
 /// It does not include validation as this is done by the API endpoint handler,
 /// in order to provide a more specific error message.
+///
+/// Handlers accept the hex (in any case) and the base32 representations, for
+/// example `9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d` or
+/// `TYBBPUH2OHEHGMWNRP45X2V4WLBM6PCN`.
 #[derive(Deserialize)]
 pub struct InfoHashParam(pub String);
 
@@ -1,20 +1,23 @@
 //! API routes.
 //!
-//! It loads all the API routes for all API versions and adds the authentication
-//! middleware to them.
+//! It loads all the API routes for all API versions and adds the authentication,
+//! audit and slow requests middlewares to them.
 //!
 //! All the API routes have the `/api` prefix and the version number as the
 //! first path segment. For example: `/api/v1/torrents`.
+//!
+//! The routes for the [virtual trackers](crate::core::virtual_trackers) are
+//! nested under the tracker name. For example: `/api/v1/trackers/private/torrents`.
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::error_handling::HandleErrorLayer;
 use axum::http::HeaderName;
-use axum::response::Response;
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{middleware, BoxError, Router};
-use hyper::{Request, StatusCode};
-use torrust_tracker_configuration::{AccessTokens, DEFAULT_TIMEOUT};
+use hyper::Request;
+use torrust_tracker_configuration::{HttpApi, DEFAULT_TIMEOUT};
 use tower::timeout::TimeoutLayer;
 use tower::ServiceBuilder;
 use tower_http::compression::CompressionLayer;
@@ -25,25 +28,55 @@ use tracing::{instrument, Level, Span};
 
 use super::v1;
 use super::v1::context::health_check::handlers::health_check_handler;
+use super::v1::context::invitation;
 use super::v1::middlewares::auth::State;
+use super::v1::middlewares::bearer::Provider;
+use super::v1::responses::{not_found_response, ErrorCode, ErrorResponse};
+use crate::core::virtual_trackers::VirtualTrackers;
 use crate::core::Tracker;
 use crate::servers::apis::API_LOG_TARGET;
 
 /// Add all API routes to the router.
 #[allow(clippy::needless_pass_by_value)]
-#[instrument(skip(tracker, access_tokens))]
-pub fn router(tracker: Arc<Tracker>, access_tokens: Arc<AccessTokens>) -> Router {
+#[instrument(skip(tracker, config, virtual_trackers))]
+pub fn router(tracker: Arc<Tracker>, config: Arc<HttpApi>, virtual_trackers: &VirtualTrackers) -> Router {
     let router = Router::new();
 
     let api_url_prefix = "/api";
 
-    let router = v1::routes::add(api_url_prefix, router, tracker.clone());
+    let router = v1::routes::add(api_url_prefix, router, tracker.clone(), &config);
 
-    let state = State { access_tokens };
+    let router = virtual_trackers.iter().fold(router, |router, (name, virtual_tracker)| {
+        v1::routes::add_virtual_tracker(api_url_prefix, name, router, virtual_tracker.clone(), &config)
+    });
 
-    router
+    let audit_state = v1::middlewares::audit::State {
+        tracker: tracker.clone(),
+        audit_log_path: config.audit_log_path.clone(),
+    };
+
+    let state = State {
+        access_tokens: Arc::new(config.access_tokens.clone()),
+        admin_access_tokens: Arc::new(config.admin_access_tokens.clone()),
+        provider: config.auth.as_ref().map(|auth| Arc::new(Provider::new(auth))),
+    };
+
+    let router = router
+        // The audit layer runs after the authentication, so it knows the token label.
+        .layer(middleware::from_fn_with_state(audit_state, v1::middlewares::audit::audit))
         .layer(middleware::from_fn_with_state(state, v1::middlewares::auth::auth))
+        // The slow requests layer is the outermost one, so it includes the time of the other middlewares.
+        .layer(middleware::from_fn_with_state(
+            tracker.clone(),
+            v1::middlewares::slow_requests::slow_requests,
+        ));
+
+    // Public routes: they don't require an access token and they are not audited.
+    let router = invitation::routes::add_public(&format!("{api_url_prefix}/v1"), router, tracker, config.registration.clone());
+
+    router
         .route(&format!("{api_url_prefix}/health_check"), get(health_check_handler))
+        .fallback(|| async { not_found_response("not found") })
         .layer(CompressionLayer::new())
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .layer(PropagateHeaderLayer::new(HeaderName::from_static("x-request-id")))
@@ -82,7 +115,9 @@ pub fn router(tracker: Arc<Tracker>, access_tokens: Arc<AccessTokens>) -> Router
             ServiceBuilder::new()
                 // this middleware goes above `TimeoutLayer` because it will receive
                 // errors returned by `TimeoutLayer`
-                .layer(HandleErrorLayer::new(|_: BoxError| async { StatusCode::REQUEST_TIMEOUT }))
+                .layer(HandleErrorLayer::new(|_: BoxError| async {
+                    ErrorResponse::new(ErrorCode::Timeout, "request timed out").into_response()
+                }))
                 .layer(TimeoutLayer::new(DEFAULT_TIMEOUT)),
         )
 }
@@ -26,24 +26,26 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum_server::tls_rustls::RustlsConfig;
 use axum_server::Handle;
+use camino::Utf8Path;
 use derive_more::derive::Display;
 use derive_more::Constructor;
 use futures::future::BoxFuture;
 use thiserror::Error;
 use tokio::sync::oneshot::{Receiver, Sender};
-use torrust_tracker_configuration::AccessTokens;
+use torrust_tracker_configuration::HttpApi;
 use tracing::{instrument, Level};
 
 use super::routes::router;
 use crate::bootstrap::jobs::Started;
+use crate::core::virtual_trackers::VirtualTrackers;
 use crate::core::Tracker;
 use crate::servers::apis::API_LOG_TARGET;
-use crate::servers::custom_axum_server::{self, TimeoutAcceptor};
+use crate::servers::custom_axum_server::{self, RustlsConfig, TimeoutAcceptor};
 use crate::servers::logging::STARTED_ON;
-use crate::servers::registar::{ServiceHealthCheckJob, ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::registar::{Binding, ServiceHealthCheckJob, ServiceKind, ServiceRegistration, ServiceRegistrationForm};
 use crate::servers::signals::{graceful_shutdown, Halted};
+use crate::servers::unix_socket;
 
 /// Errors that can occur when starting or stopping the API server.
 #[derive(Debug, Error)]
@@ -122,22 +124,24 @@ impl ApiServer<Stopped> {
     /// # Panics
     ///
     /// It would panic if the bound socket address cannot be sent back to this starter.
-    #[instrument(skip(self, tracker, form, access_tokens), err, ret(Display, level = Level::INFO))]
+    #[instrument(skip(self, tracker, form, config), err, ret(Display, level = Level::INFO))]
     pub async fn start(
         self,
         tracker: Arc<Tracker>,
         form: ServiceRegistrationForm,
-        access_tokens: Arc<AccessTokens>,
+        config: Arc<HttpApi>,
     ) -> Result<ApiServer<Running>, Error> {
         let (tx_start, rx_start) = tokio::sync::oneshot::channel::<Started>();
         let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
 
         let launcher = self.state.launcher;
 
+        let tls = launcher.tls.is_some();
+
         let task = tokio::spawn(async move {
             tracing::debug!(target: API_LOG_TARGET, "Starting with launcher in spawned task ...");
 
-            let _task = launcher.start(tracker, access_tokens, tx_start, rx_halt).await;
+            let _task = launcher.start(tracker, config, tx_start, rx_halt).await;
 
             tracing::debug!(target: API_LOG_TARGET, "Started with launcher in spawned task");
 
@@ -146,7 +150,7 @@ impl ApiServer<Stopped> {
 
         let api_server = match rx_start.await {
             Ok(started) => {
-                form.send(ServiceRegistration::new(started.address, check_fn))
+                form.send(ServiceRegistration::new(started.address, ServiceKind::HttpApi, tls, check_fn))
                     .expect("it should be able to send service registration");
 
                 ApiServer {
@@ -207,19 +211,41 @@ pub fn check_fn(binding: &SocketAddr) -> ServiceHealthCheckJob {
     ServiceHealthCheckJob::new(*binding, info, job)
 }
 
+/// Checks the Health by connecting to the API service endpoint bound to a Unix
+/// domain socket.
+///
+/// # Errors
+///
+/// This function will return an error if unable to connect.
+/// Or if the request returns an error.
+#[must_use]
+pub fn check_unix_socket_fn(path: &Utf8Path) -> ServiceHealthCheckJob {
+    let path = path.to_path_buf();
+
+    let info = format!("checking api health check at: unix:{path}/api/health_check");
+
+    let job = {
+        let path = path.clone();
+        tokio::spawn(async move { unix_socket::get_status(&path, "/api/health_check").await })
+    };
+
+    ServiceHealthCheckJob::new(Binding::Unix(path), info, job)
+}
+
 /// A struct responsible for starting the API server.
 #[derive(Constructor, Debug)]
 pub struct Launcher {
     bind_to: SocketAddr,
     tls: Option<RustlsConfig>,
+    virtual_trackers: VirtualTrackers,
 }
 
 impl std::fmt::Display for Launcher {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.tls.is_some() {
-            write!(f, "(with socket): {}, using TLS", self.bind_to,)
+            write!(f, "(with socket): {}, using TLS", self.bind_to)
         } else {
-            write!(f, "(with socket): {}, without TLS", self.bind_to,)
+            write!(f, "(with socket): {}, without TLS", self.bind_to)
         }
     }
 }
@@ -235,15 +261,15 @@ impl Launcher {
     ///
     /// Will panic if unable to bind to the socket, or unable to get the address of the bound socket.
     /// Will also panic if unable to send message regarding the bound socket address.
-    #[instrument(skip(self, tracker, access_tokens, tx_start, rx_halt))]
+    #[instrument(skip(self, tracker, config, tx_start, rx_halt))]
     pub fn start(
         &self,
         tracker: Arc<Tracker>,
-        access_tokens: Arc<AccessTokens>,
+        config: Arc<HttpApi>,
         tx_start: Sender<Started>,
         rx_halt: Receiver<Halted>,
     ) -> BoxFuture<'static, ()> {
-        let router = router(tracker, access_tokens);
+        let router = router(tracker, config, &self.virtual_trackers);
         let socket = std::net::TcpListener::bind(self.bind_to).expect("Could not bind tcp_listener to address.");
         let address = socket.local_addr().expect("Could not get local_addr from tcp_listener.");
 
@@ -262,6 +288,7 @@ impl Launcher {
 
         let running = Box::pin(async {
             match tls {
+                #[cfg(feature = "tls")]
                 Some(tls) => custom_axum_server::from_tcp_rustls_with_timeouts(socket, tls)
                     .handle(handle)
                     // The TimeoutAcceptor is commented because TSL does not work with it.
@@ -270,6 +297,8 @@ impl Launcher {
                     .serve(router.into_make_service_with_connect_info::<std::net::SocketAddr>())
                     .await
                     .expect("Axum server for tracker API crashed."),
+                #[cfg(not(feature = "tls"))]
+                Some(tls) => match tls {},
                 None => custom_axum_server::from_tcp_with_timeouts(socket)
                     .handle(handle)
                     .acceptor(TimeoutAcceptor)
@@ -297,6 +326,7 @@ mod tests {
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::make_rust_tls;
+    use crate::core::virtual_trackers::VirtualTrackers;
     use crate::servers::apis::server::{ApiServer, Launcher};
     use crate::servers::registar::Registar;
 
@@ -307,20 +337,21 @@ mod tests {
 
         let tracker = initialize_with_configuration(&cfg);
 
-        let bind_to = config.bind_address;
+        let bind_to = config
+            .bind_address
+            .socket_addr()
+            .expect("it should be bound to a TCP socket address");
 
         let tls = make_rust_tls(&config.tsl_config)
             .await
             .map(|tls| tls.expect("tls config failed"));
 
-        let access_tokens = Arc::new(config.access_tokens.clone());
-
-        let stopped = ApiServer::new(Launcher::new(bind_to, tls));
+        let stopped = ApiServer::new(Launcher::new(bind_to, tls, VirtualTrackers::default()));
 
         let register = &Registar::default();
 
         let started = stopped
-            .start(tracker, register.give_form(), access_tokens)
+            .start(tracker, register.give_form(), Arc::new(config.clone()))
             .await
             .expect("it should start the server");
         let stopped = started.stop().await.expect("it should stop the server");
@@ -0,0 +1,42 @@
+//! API handlers for the [`audit_log`](crate::servers::apis::v1::context::audit_log)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use torrust_tracker_primitives::pagination::Pagination;
+
+use super::responses::{audit_log_response, failed_to_load_audit_log_response};
+use crate::core::Tracker;
+use crate::servers::apis::v1::context::torrent::handlers::empty_string_as_none;
+use crate::servers::apis::v1::extractors::Query;
+
+/// A container for the pagination URL query parameters: `offset` and `limit`.
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The number of entries to skip. Starts at 0.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub offset: Option<u32>,
+    /// The maximum number of entries to return.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub limit: Option<u32>,
+}
+
+/// It handles the request to get a page of the audit log.
+///
+/// It returns:
+///
+/// - `200` response with a json array of [`AuditLogEntry`](crate::servers::apis::v1::context::audit_log::resources::AuditLogEntry)
+///   resources, newest first.
+/// - `5xx` with a json error if the audit log can't be
+///   loaded from the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::audit_log#get-the-audit-log)
+/// for more information about this endpoint.
+pub async fn get_audit_log_handler(State(tracker): State<Arc<Tracker>>, pagination: Query<QueryParams>) -> Response {
+    match tracker.get_audit_log(&Pagination::new_with_options(pagination.0.offset, pagination.0.limit)) {
+        Ok(entries) => audit_log_response(entries).into_response(),
+        Err(e) => failed_to_load_audit_log_response(e),
+    }
+}
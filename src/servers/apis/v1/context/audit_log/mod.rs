@@ -0,0 +1,55 @@
+//! Audit log API context.
+//!
+//! This API context is responsible for handling the requests related to the
+//! [audit log](crate::core::audit) of the administrative actions.
+//!
+//! Every state-changing request to the API is recorded by the
+//! [`audit`](crate::servers::apis::v1::middlewares::audit) middleware.
+//!
+//! # Endpoints
+//!
+//! - [Get the audit log](#get-the-audit-log)
+//!
+//! # Get the audit log
+//!
+//! `GET /audit-log`
+//!
+//! It returns the recorded administrative actions, newest first. This
+//! endpoint requires one of the `admin_access_tokens`.
+//!
+//! **Query parameters**
+//!
+//! The endpoint supports pagination.
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `offset` | positive integer | The number of entries to skip, starting at 0 | No | `20`
+//! `limit` | positive integer | Page size. The number of results per page | No | `10`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/audit-log?token=MyAdminAccessToken&offset=0&limit=10"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "time": "2024-10-16T09:00:00Z",
+//!         "token_label": "admin",
+//!         "source_ip": "127.0.0.1",
+//!         "method": "POST",
+//!         "path": "/api/v1/whitelist/9c38422213e30bff212b30c360d26f9a02136422",
+//!         "status": 200
+//!     }
+//! ]
+//! ```
+//!
+//! Refer to the API [`AuditLogEntry`](crate::servers::apis::v1::context::audit_log::resources::AuditLogEntry)
+//! resource for more information about the response attributes.
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,70 @@
+//! API resources for the [`audit_log`](crate::servers::apis::v1::context::audit_log)
+//! API context.
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
+
+use crate::core::audit;
+
+/// A recorded administrative action.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    /// Time of the request, in RFC 3339 format.
+    pub time: String,
+    /// The label of the access token used in the request.
+    pub token_label: String,
+    /// The IP of the client, if it's known.
+    pub source_ip: Option<String>,
+    /// The HTTP method of the request.
+    pub method: String,
+    /// The request path, without the query.
+    pub path: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+}
+
+impl From<audit::Entry> for AuditLogEntry {
+    fn from(entry: audit::Entry) -> Self {
+        Self {
+            time: convert_from_timestamp_to_datetime_utc(entry.time).to_rfc3339_opts(SecondsFormat::Secs, true),
+            token_label: entry.token_label,
+            source_ip: entry.source_ip.map(|ip| ip.to_string()),
+            method: entry.method,
+            path: entry.path,
+            status: entry.status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    use super::AuditLogEntry;
+    use crate::core::audit;
+
+    #[test]
+    fn audit_log_entry_resource_should_be_converted_from_an_audit_log_entry() {
+        let entry = audit::Entry {
+            time: Duration::from_secs(1_729_069_200),
+            token_label: "admin".to_string(),
+            source_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            method: "POST".to_string(),
+            path: "/api/v1/keys".to_string(),
+            status: 200,
+        };
+
+        assert_eq!(
+            AuditLogEntry::from(entry),
+            AuditLogEntry {
+                time: "2024-10-16T09:00:00Z".to_string(),
+                token_label: "admin".to_string(),
+                source_ip: Some("127.0.0.1".to_string()),
+                method: "POST".to_string(),
+                path: "/api/v1/keys".to_string(),
+                status: 200,
+            }
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! API responses for the [`audit_log`](crate::servers::apis::v1::context::audit_log)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::AuditLogEntry;
+use crate::core::audit;
+use crate::servers::apis::v1::responses::failed_response;
+
+/// `200` response that contains an array of [`AuditLogEntry`] resources as
+/// json.
+pub fn audit_log_response(entries: Vec<audit::Entry>) -> Json<Vec<AuditLogEntry>> {
+    Json(entries.into_iter().map(AuditLogEntry::from).collect())
+}
+
+/// Error response when the audit log cannot be loaded from the
+/// database.
+#[must_use]
+pub fn failed_to_load_audit_log_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to load audit log", &e)
+}
@@ -0,0 +1,23 @@
+//! API routes for the [`audit_log`](crate::servers::apis::v1::context::audit_log) API context.
+//!
+//! - `GET /audit-log` (admin token required)
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::audit_log).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::{middleware, Router};
+
+use super::handlers::get_audit_log_handler;
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::admin;
+
+/// It adds the routes to the router for the [`audit_log`](crate::servers::apis::v1::context::audit_log) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router.route(
+        &format!("{prefix}/audit-log"),
+        get(get_audit_log_handler)
+            .route_layer(middleware::from_fn(admin))
+            .with_state(tracker),
+    )
+}
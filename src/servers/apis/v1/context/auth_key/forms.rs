@@ -19,4 +19,28 @@ pub struct AddKeyForm {
     #[serde_as(deserialize_as = "DefaultOnNull")]
     #[serde(rename = "seconds_valid")]
     pub opt_seconds_valid: Option<u64>,
+
+    /// The IP address or IP range in CIDR notation the key can only be used
+    /// from. Use `None` (null in json) for keys that can be used from any
+    /// address.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default, rename = "bound_to")]
+    pub opt_bound_to: Option<String>,
+}
+
+/// This type contains the daily quota for a tracker key. Use `None` (null in
+/// json) for the limits that do not apply.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KeyQuotaForm {
+    /// The maximum number of `announce` requests per day.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default)]
+    pub max_announces_per_day: Option<u32>,
+
+    /// The maximum number of peers returned in the `announce` responses per
+    /// day.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default)]
+    pub max_peers_per_day: Option<u32>,
 }
@@ -1,43 +1,60 @@
 //! API handlers for the [`auth_key`](crate::servers::apis::v1::context::auth_key) API context.
+use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use axum::extract::{self, Path, State};
+use axum::extract::{ConnectInfo, State};
 use axum::response::Response;
+use axum::Extension;
+use chrono::SecondsFormat;
 use serde::Deserialize;
+use torrust_tracker_clock::clock::Time;
+use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
+use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+use url::Url;
 
-use super::forms::AddKeyForm;
+use super::forms::{AddKeyForm, KeyQuotaForm};
 use super::responses::{
-    auth_key_response, failed_to_delete_key_response, failed_to_generate_key_response, failed_to_reload_keys_response,
-    invalid_auth_key_duration_response, invalid_auth_key_response,
+    auth_key_not_found_response, auth_key_response, expired_keys_response, failed_to_delete_key_response,
+    failed_to_expire_keys_response, failed_to_generate_key_response, failed_to_reload_keys_response,
+    failed_to_remove_key_quota_response, failed_to_set_key_quota_response, invalid_auth_key_binding_response,
+    invalid_auth_key_duration_response, invalid_auth_key_response, key_stats_response, key_validity_response,
 };
 use crate::core::auth::Key;
+use crate::core::key_quota::Quota;
 use crate::core::{AddKeyRequest, Tracker};
-use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
+use crate::servers::apis::v1::context::auth_key::resources::{
+    AuthKey, ExpiredKeys, KeyStats, KeyValidity, KeysExpiredNotification,
+};
+use crate::servers::apis::v1::extractors::{self, Path};
+use crate::servers::apis::v1::middlewares::auth::Authenticated;
 use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, ok_response};
+use crate::servers::apis::{API_LOG_TARGET, AUDIT_LOG_TARGET};
+use crate::CurrentClock;
 
 /// It handles the request to add a new authentication key.
 ///
 /// It returns these types of responses:
 ///
 /// - `200` with a json [`AuthKey`]
-///    resource. If the key was generated successfully.
-/// - `400` with an error if the key couldn't been added because of an invalid
-///    request.
-/// - `500` with serialized error in debug format. If the key couldn't be
-///    generated.
+///   resource. If the key was generated successfully.
+/// - `422` with an error if the key couldn't been added because of an invalid
+///   request.
+/// - `5xx` with a json error. If the key couldn't be
+///   generated.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#generate-a-new-authentication-key)
 /// for more information about this endpoint.
 pub async fn add_auth_key_handler(
     State(tracker): State<Arc<Tracker>>,
-    extract::Json(add_key_form): extract::Json<AddKeyForm>,
+    extractors::Json(add_key_form): extractors::Json<AddKeyForm>,
 ) -> Response {
     match tracker
         .add_peer_key(AddKeyRequest {
             opt_key: add_key_form.opt_key.clone(),
             opt_seconds_valid: add_key_form.opt_seconds_valid,
+            opt_bound_to: add_key_form.opt_bound_to.clone(),
         })
         .await
     {
@@ -47,6 +64,9 @@ pub async fn add_auth_key_handler(
                 invalid_auth_key_duration_response(seconds_valid)
             }
             crate::core::error::PeerKeyError::InvalidKey { key, source } => invalid_auth_key_response(&key, source),
+            crate::core::error::PeerKeyError::InvalidBinding { bound_to, source } => {
+                invalid_auth_key_binding_response(&bound_to, source)
+            }
             crate::core::error::PeerKeyError::DatabaseError { source } => failed_to_generate_key_response(source),
         },
     }
@@ -57,9 +77,9 @@ pub async fn add_auth_key_handler(
 /// It returns two types of responses:
 ///
 /// - `200` with an json [`AuthKey`]
-///    resource. If the key was generated successfully.
-/// - `500` with serialized error in debug format. If the key couldn't be
-///    generated.
+///   resource. If the key was generated successfully.
+/// - `5xx` with a json error. If the key couldn't be
+///   generated.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#generate-a-new-authentication-key)
 /// for more information about this endpoint.
@@ -96,9 +116,9 @@ pub struct KeyParam(String);
 /// It returns two types of responses:
 ///
 /// - `200` with an json [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok)
-///    response. If the key was deleted successfully.
-/// - `500` with serialized error in debug format. If the key couldn't be
-///    deleted.
+///   response. If the key was deleted successfully.
+/// - `5xx` with a json error. If the key couldn't be
+///   deleted.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#delete-an-authentication-key)
 /// for more information about this endpoint.
@@ -121,9 +141,9 @@ pub async fn delete_auth_key_handler(
 /// It returns two types of responses:
 ///
 /// - `200` with an json [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok)
-///    response. If the keys were successfully reloaded.
-/// - `500` with serialized error in debug format. If the they couldn't be
-///    reloaded.
+///   response. If the keys were successfully reloaded.
+/// - `5xx` with a json error. If the they couldn't be
+///   reloaded.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#reload-authentication-keys)
 /// for more information about this endpoint.
@@ -133,3 +153,189 @@ pub async fn reload_keys_handler(State(tracker): State<Arc<Tracker>>) -> Respons
         Err(e) => failed_to_reload_keys_response(e),
     }
 }
+
+/// It handles the request to get the daily quota and usage of an
+/// authentication key.
+///
+/// It returns two types of responses:
+///
+/// - `200` with a json [`KeyStats`] resource.
+/// - `400` if the key is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#get-the-stats-of-an-authentication-key)
+/// for more information about this endpoint.
+pub async fn get_key_stats_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<KeyParam>) -> Response {
+    match Key::from_str(&key.0) {
+        Err(_) => invalid_auth_key_param_response(&key.0),
+        Ok(key) => key_stats_response(&KeyStats::new(&key, tracker.get_key_quota(&key), tracker.get_key_usage(&key))),
+    }
+}
+
+/// It handles the request to check whether an authentication key can be used
+/// to announce. It's a read-only check: the key is not counted against its
+/// daily quota.
+///
+/// It returns three types of responses:
+///
+/// - `200` with a json [`KeyValidity`] resource.
+/// - `400` if the key is not valid.
+/// - `404` if the key is not registered.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#check-the-validity-of-an-authentication-key)
+/// for more information about this endpoint.
+pub async fn get_key_validity_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<KeyParam>) -> Response {
+    let Ok(key) = Key::from_str(&key.0) else {
+        return invalid_auth_key_param_response(&key.0);
+    };
+
+    match tracker.get_peer_key(&key).await {
+        None => auth_key_not_found_response(),
+        Some(peer_key) => key_validity_response(&KeyValidity::new(
+            &peer_key,
+            tracker.verify_auth_key(&key).await.is_ok(),
+            tracker.is_key_quota_exhausted(&key),
+        )),
+    }
+}
+
+/// It handles the request to set the daily quota of an authentication key.
+///
+/// It returns three types of responses:
+///
+/// - `200` with an json [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok)
+///   response. If the quota was set successfully.
+/// - `400` if the key is not valid.
+/// - `5xx` with a json error. If the quota couldn't be
+///   persisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#set-the-quota-of-an-authentication-key)
+/// for more information about this endpoint.
+pub async fn set_key_quota_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(key): Path<KeyParam>,
+    extractors::Json(quota_form): extractors::Json<KeyQuotaForm>,
+) -> Response {
+    match Key::from_str(&key.0) {
+        Err(_) => invalid_auth_key_param_response(&key.0),
+        Ok(key) => match tracker.set_key_quota(
+            &key,
+            Quota {
+                max_announces_per_day: quota_form.max_announces_per_day,
+                max_peers_per_day: quota_form.max_peers_per_day,
+            },
+        ) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_set_key_quota_response(e),
+        },
+    }
+}
+
+/// It handles the request to remove the daily quota of an authentication key.
+///
+/// It returns three types of responses:
+///
+/// - `200` with an json [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok)
+///   response. If the quota was removed successfully.
+/// - `400` if the key is not valid.
+/// - `5xx` with a json error. If the quota couldn't be
+///   removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#remove-the-quota-of-an-authentication-key)
+/// for more information about this endpoint.
+pub async fn remove_key_quota_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<KeyParam>) -> Response {
+    match Key::from_str(&key.0) {
+        Err(_) => invalid_auth_key_param_response(&key.0),
+        Ok(key) => match tracker.remove_key_quota(&key) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_key_quota_response(e),
+        },
+    }
+}
+
+/// It handles the request to force-expire all the authentication keys, in
+/// memory and in the database. It requires an admin token.
+///
+/// The action is written to the audit log and, if the `incident_webhook_url`
+/// is configured, the webhook is notified in the background.
+///
+/// It returns two types of responses:
+///
+/// - `200` with a json [`ExpiredKeys`] resource. If the keys were expired
+///   successfully.
+/// - `5xx` with a json error. If the keys couldn't be
+///   removed from the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#force-expire-all-authentication-keys)
+/// for more information about this endpoint.
+pub async fn expire_all_keys_handler(
+    State((tracker, incident_webhook_url)): State<(Arc<Tracker>, Option<Url>)>,
+    Extension(authenticated): Extension<Authenticated>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> Response {
+    let source = connect_info.map_or_else(|| "unknown".to_string(), |ConnectInfo(address)| address.ip().to_string());
+
+    match tracker.remove_all_auth_keys().await {
+        Ok(expired_keys) => {
+            tracing::warn!(
+                target: AUDIT_LOG_TARGET,
+                action = "expire_all_keys",
+                token_label = %authenticated.label,
+                %source,
+                expired_keys,
+                "All the authentication keys have been expired"
+            );
+
+            if let Some(url) = incident_webhook_url {
+                notify_incident_webhook(
+                    url,
+                    KeysExpiredNotification {
+                        action: "expire_all_keys".to_string(),
+                        expired_keys,
+                        token_label: authenticated.label,
+                        time: convert_from_timestamp_to_datetime_utc(CurrentClock::now())
+                            .to_rfc3339_opts(SecondsFormat::Millis, true),
+                    },
+                );
+            }
+
+            expired_keys_response(&ExpiredKeys { expired_keys })
+        }
+        Err(e) => {
+            tracing::warn!(
+                target: AUDIT_LOG_TARGET,
+                action = "expire_all_keys",
+                token_label = %authenticated.label,
+                %source,
+                error = %e,
+                "Failed to expire all the authentication keys"
+            );
+
+            failed_to_expire_keys_response(e)
+        }
+    }
+}
+
+/// It posts the notification to the incident webhook on a new task, so that
+/// a slow or unavailable webhook does not delay the response.
+fn notify_incident_webhook(url: Url, notification: KeysExpiredNotification) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(DEFAULT_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(err) => {
+                tracing::error!(target: API_LOG_TARGET, %err, "Failed to build the incident webhook client");
+                return;
+            }
+        };
+
+        match client
+            .post(url.clone())
+            .json(&notification)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+        {
+            Ok(_) => tracing::info!(target: API_LOG_TARGET, %url, "Incident webhook notified"),
+            Err(err) => tracing::error!(target: API_LOG_TARGET, %url, %err, "Failed to notify the incident webhook"),
+        }
+    });
+}
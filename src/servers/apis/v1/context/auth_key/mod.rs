@@ -19,6 +19,11 @@
 //! - [Generate a new authentication key](#generate-a-new-authentication-key)
 //! - [Delete an authentication key](#delete-an-authentication-key)
 //! - [Reload authentication keys](#reload-authentication-keys)
+//! - [Force-expire all authentication keys](#force-expire-all-authentication-keys)
+//! - [Get the stats of an authentication key](#get-the-stats-of-an-authentication-key)
+//! - [Check the validity of an authentication key](#check-the-validity-of-an-authentication-key)
+//! - [Set the quota of an authentication key](#set-the-quota-of-an-authentication-key)
+//! - [Remove the quota of an authentication key](#remove-the-quota-of-an-authentication-key)
 //!
 //! # Generate a new authentication key
 //!
@@ -32,10 +37,15 @@
 //! ---|---|---|---|---
 //! `key` | 32-char string (0-9, a-z, A-Z) or `null` | The optional pre-generated key. | Yes | `Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z7` or `null`
 //! `seconds_valid` | positive integer or `null` | The number of seconds the key will be valid. | Yes | `3600` or `null`
+//! `bound_to` | IP address, CIDR range or `null` | The address or range the key can only be used from. | No | `203.0.113.0/24` or `null`
 //!
 //! > **NOTICE**: the `key` and `seconds_valid` fields are optional. If `key` is not provided the tracker
 //! > will generated a random one. If `seconds_valid` field is not provided the key will be permanent. You can use the `null` value.
 //!
+//! > **NOTICE**: `announce` requests with a key bound to an address are
+//! > rejected when they come from any other address. It prevents leaked
+//! > announce URLs from being reused elsewhere.
+//!
 //! **Example request**
 //!
 //! ```bash
@@ -98,13 +108,16 @@
 //!
 //! **Example error response** `500`
 //!
-//! ```text
-//! Unhandled rejection: Err { reason: "failed to delete key: Failed to remove record from Sqlite3 database, error-code: 0, src/tracker/databases/sqlite.rs:267:27" }
+//! ```json
+//! {
+//!     "code": "internal_error",
+//!     "message": "failed to delete key: Failed to remove record from Sqlite3 database, error-code: 0, src/tracker/databases/sqlite.rs:267:27",
+//!     "details": []
+//! }
 //! ```
 //!
-//! > **NOTICE**: a `500` status code will be returned and the body is not a
-//! > valid JSON. It's a text body containing the serialized-to-display error
-//! > message.
+//! Refer to the [common responses](crate::servers::apis::v1::responses) for
+//! the list of error codes.
 //!
 //! # Reload authentication keys
 //!
@@ -126,6 +139,171 @@
 //!     "status": "ok"
 //! }
 //! ```
+//!
+//! # Force-expire all authentication keys
+//!
+//! `POST /keys/expire-all`
+//!
+//! It removes all the authentication keys, from memory and from the database.
+//! It's meant for incident response, when the keys are suspected to be
+//! compromised. Clients will have to get new keys.
+//!
+//! This endpoint requires one of the `admin_access_tokens`. Other tokens get
+//! a `403` response. The action is logged with the `AUDIT` target, including
+//! the token label and the source IP. If the `incident_webhook_url` is
+//! configured, the tracker also posts a JSON notification to it:
+//!
+//! ```toml
+//! [http_api]
+//! incident_webhook_url = "https://example.com/hooks/tracker-incident"
+//!
+//! [http_api.admin_access_tokens]
+//! incident-response = "MyAdminAccessToken"
+//! ```
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/keys/expire-all?token=MyAdminAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "expired_keys": 42
+//! }
+//! ```
+//!
+//! **Example webhook notification**
+//!
+//! ```json
+//! {
+//!     "action": "expire_all_keys",
+//!     "expired_keys": 42,
+//!     "token_label": "incident-response",
+//!     "time": "2024-09-20T10:00:00.000Z"
+//! }
+//! ```
+//!
+//! # Get the stats of an authentication key
+//!
+//! `GET /key/:key/stats`
+//!
+//! It returns the daily quota of the key and what it has consumed today. The
+//! counters are reset at midnight (UTC). Refer to the
+//! [`key_quota`](crate::core::key_quota) module for more information.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/key/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/stats?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "max_announces_per_day": 1000,
+//!     "max_peers_per_day": null,
+//!     "announces_today": 120,
+//!     "peers_today": 3400
+//! }
+//! ```
+//!
+//! # Check the validity of an authentication key
+//!
+//! `GET /key/:key/valid`
+//!
+//! It returns whether the key can be used to announce now, and why not. It's
+//! a cheap in-memory check for support tooling: the key is not counted
+//! against its daily quota. A key is not valid if it has expired (and the
+//! tracker checks the expiration of the keys) or if it has reached its daily
+//! quota. Keys bound to an address can only be used from the `bound_to`
+//! address or range.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/key/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/valid?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "valid": false,
+//!     "expired": false,
+//!     "quota_exhausted": true,
+//!     "expiry_time": "2024-10-17 09:00:00 UTC",
+//!     "bound_to": "203.0.113.0/24"
+//! }
+//! ```
+//!
+//! **Not found response** `404`
+//!
+//! ```json
+//! {
+//!     "code": "not_found",
+//!     "message": "key not registered"
+//! }
+//! ```
+//!
+//! # Set the quota of an authentication key
+//!
+//! `POST /key/:key/quota`
+//!
+//! It sets the daily quota of the key, replacing the previous one. When the
+//! key reaches any of the limits, its `announce` requests fail with a
+//! `retry in` the minutes left until the counters are reset.
+//!
+//! **POST parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `max_announces_per_day` | positive integer or `null` | The maximum number of `announce` requests per day. | No | `1000`
+//! `max_peers_per_day` | positive integer or `null` | The maximum number of peers returned per day. | No | `50000`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/key/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/quota?token=MyAccessToken \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "max_announces_per_day": 1000,
+//!            "max_peers_per_day": null
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove the quota of an authentication key
+//!
+//! `DELETE /key/:key/quota`
+//!
+//! It removes the daily quota of the key. Its usage is still counted.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/key/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/quota?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
 pub mod forms;
 pub mod handlers;
 pub mod resources;
@@ -3,7 +3,8 @@
 use serde::{Deserialize, Serialize};
 use torrust_tracker_clock::conv::convert_from_iso_8601_to_timestamp;
 
-use crate::core::auth::{self, Key};
+use crate::core::auth::{self, IpRange, Key};
+use crate::core::key_quota;
 
 /// A resource that represents an authentication key.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -15,6 +16,9 @@ pub struct AuthKey {
     pub valid_until: Option<u64>, // todo: remove when the torrust-index-backend starts using the `expiry_time` attribute.
     /// The ISO 8601 timestamp when the key will expire.
     pub expiry_time: Option<String>,
+    /// The IP address or IP range (CIDR) the key can only be used from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound_to: Option<String>,
 }
 
 impl From<AuthKey> for auth::PeerKey {
@@ -24,6 +28,9 @@ impl From<AuthKey> for auth::PeerKey {
             valid_until: auth_key_resource
                 .expiry_time
                 .map(|expiry_time| convert_from_iso_8601_to_timestamp(&expiry_time)),
+            bound_to: auth_key_resource
+                .bound_to
+                .map(|bound_to| bound_to.parse::<IpRange>().unwrap()),
         }
     }
 }
@@ -36,16 +43,102 @@ impl From<auth::PeerKey> for AuthKey {
                 key: auth_key.key.to_string(),
                 valid_until: Some(valid_until.as_secs()),
                 expiry_time: Some(expiry_time.to_string()),
+                bound_to: auth_key.bound_to.map(|bound_to| bound_to.to_string()),
             },
             _ => AuthKey {
                 key: auth_key.key.to_string(),
                 valid_until: None,
                 expiry_time: None,
+                bound_to: auth_key.bound_to.map(|bound_to| bound_to.to_string()),
             },
         }
     }
 }
 
+/// A resource with the result of force-expiring all the authentication keys.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ExpiredKeys {
+    /// The number of keys that were removed.
+    pub expired_keys: usize,
+}
+
+/// The notification sent to the incident webhook when all the authentication
+/// keys are force-expired.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct KeysExpiredNotification {
+    /// The incident response action, always `expire_all_keys`.
+    pub action: String,
+    /// The number of keys that were removed.
+    pub expired_keys: usize,
+    /// The label of the admin token used to run the action.
+    pub token_label: String,
+    /// The ISO 8601 timestamp of the action.
+    pub time: String,
+}
+
+/// A resource with the daily quota and usage of an authentication key.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct KeyStats {
+    /// The authentication key.
+    pub key: String,
+    /// The maximum number of `announce` requests per day, if any.
+    pub max_announces_per_day: Option<u32>,
+    /// The maximum number of peers returned per day, if any.
+    pub max_peers_per_day: Option<u32>,
+    /// The number of `announce` requests made with the key today.
+    pub announces_today: u32,
+    /// The number of peers returned for the key today.
+    pub peers_today: u32,
+}
+
+impl KeyStats {
+    #[must_use]
+    pub fn new(key: &Key, quota: Option<key_quota::Quota>, usage: key_quota::Usage) -> Self {
+        let quota = quota.unwrap_or_default();
+
+        Self {
+            key: key.to_string(),
+            max_announces_per_day: quota.max_announces_per_day,
+            max_peers_per_day: quota.max_peers_per_day,
+            announces_today: usage.announces,
+            peers_today: usage.peers,
+        }
+    }
+}
+
+/// Whether an authentication key can be used to announce, and why not.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct KeyValidity {
+    /// The authentication key.
+    pub key: String,
+    /// `true` if the key can be used to announce now.
+    pub valid: bool,
+    /// `true` if the key has expired. Expired keys are still accepted when
+    /// the tracker does not check the expiration of the keys.
+    pub expired: bool,
+    /// `true` if the key has reached its daily quota.
+    pub quota_exhausted: bool,
+    /// The ISO 8601 timestamp when the key will expire.
+    pub expiry_time: Option<String>,
+    /// The IP address or IP range (CIDR) the key can only be used from.
+    pub bound_to: Option<String>,
+}
+
+impl KeyValidity {
+    /// `accepted` is the result of verifying the key, ignoring the quota.
+    #[must_use]
+    pub fn new(peer_key: &auth::PeerKey, accepted: bool, quota_exhausted: bool) -> Self {
+        Self {
+            key: peer_key.key.to_string(),
+            valid: accepted && !quota_exhausted,
+            expired: !accepted,
+            quota_exhausted,
+            expiry_time: peer_key.expiry_time().map(|expiry_time| expiry_time.to_string()),
+            bound_to: peer_key.bound_to.map(|bound_to| bound_to.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -54,7 +147,7 @@ mod tests {
     use torrust_tracker_clock::clock::{self, Time};
 
     use super::AuthKey;
-    use crate::core::auth::{self, Key};
+    use crate::core::auth::{self, IpRange, Key};
     use crate::CurrentClock;
 
     struct TestTime {
@@ -83,13 +176,15 @@ mod tests {
             key: "IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM".to_string(), // cspell:disable-line
             valid_until: Some(one_hour_after_unix_epoch().timestamp),
             expiry_time: Some(one_hour_after_unix_epoch().iso_8601_v1),
+            bound_to: Some("203.0.113.0/24".to_string()),
         };
 
         assert_eq!(
             auth::PeerKey::from(auth_key_resource),
             auth::PeerKey {
                 key: "IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM".parse::<Key>().unwrap(), // cspell:disable-line
-                valid_until: Some(CurrentClock::now_add(&Duration::new(one_hour_after_unix_epoch().timestamp, 0)).unwrap())
+                valid_until: Some(CurrentClock::now_add(&Duration::new(one_hour_after_unix_epoch().timestamp, 0)).unwrap()),
+                bound_to: Some("203.0.113.0/24".parse::<IpRange>().unwrap()),
             }
         );
     }
@@ -102,6 +197,7 @@ mod tests {
         let auth_key = auth::PeerKey {
             key: "IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM".parse::<Key>().unwrap(), // cspell:disable-line
             valid_until: Some(CurrentClock::now_add(&Duration::new(one_hour_after_unix_epoch().timestamp, 0)).unwrap()),
+            bound_to: None,
         };
 
         assert_eq!(
@@ -110,6 +206,7 @@ mod tests {
                 key: "IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM".to_string(), // cspell:disable-line
                 valid_until: Some(one_hour_after_unix_epoch().timestamp),
                 expiry_time: Some(one_hour_after_unix_epoch().iso_8601_v2),
+                bound_to: None,
             }
         );
     }
@@ -122,6 +219,7 @@ mod tests {
                 key: "IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM".to_string(), // cspell:disable-line
                 valid_until: Some(one_hour_after_unix_epoch().timestamp),
                 expiry_time: Some(one_hour_after_unix_epoch().iso_8601_v1),
+                bound_to: None,
             })
             .unwrap(),
             "{\"key\":\"IaWDneuFNZi8IB4MPA3qW1CD0M30EZSM\",\"valid_until\":60,\"expiry_time\":\"1970-01-01T00:01:00.000Z\"}" // cspell:disable-line
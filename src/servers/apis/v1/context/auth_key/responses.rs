@@ -4,8 +4,8 @@ use std::error::Error;
 use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 
-use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
-use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+use crate::servers::apis::v1::context::auth_key::resources::{AuthKey, ExpiredKeys, KeyStats, KeyValidity};
+use crate::servers::apis::v1::responses::{failed_response, not_found_response, validation_failed_response};
 
 /// `200` response that contains the `AuthKey` resource as json.
 ///
@@ -22,39 +22,120 @@ pub fn auth_key_response(auth_key: &AuthKey) -> Response {
         .into_response()
 }
 
+/// `200` response that contains the `ExpiredKeys` resource as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `ExpiredKeys` resource to json
+#[must_use]
+pub fn expired_keys_response(expired_keys: &ExpiredKeys) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(expired_keys).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response that contains the `KeyStats` resource as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `KeyStats` resource to json
+#[must_use]
+pub fn key_stats_response(key_stats: &KeyStats) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(key_stats).unwrap(),
+    )
+        .into_response()
+}
+
+/// `200` response that contains the `KeyValidity` resource as json.
+///
+/// # Panics
+///
+/// Will panic if it can't convert the `KeyValidity` resource to json
+#[must_use]
+pub fn key_validity_response(key_validity: &KeyValidity) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        serde_json::to_string(key_validity).unwrap(),
+    )
+        .into_response()
+}
+
 // Error responses
 
-/// `500` error response when a new authentication key cannot be generated.
+/// `404` error response returned when a key is not registered.
+#[must_use]
+pub fn auth_key_not_found_response() -> Response {
+    not_found_response("key not registered")
+}
+
+/// Error response when a new authentication key cannot be generated.
+#[must_use]
+pub fn failed_to_generate_key_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to generate key", &e)
+}
+
+/// Error response when the provide key cannot be added.
+#[must_use]
+pub fn failed_to_add_key_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to add key", &e)
+}
+
+/// Error response when an authentication key cannot be deleted.
+#[must_use]
+pub fn failed_to_delete_key_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to delete key", &e)
+}
+
+/// Error response when the authentication keys cannot be expired.
 #[must_use]
-pub fn failed_to_generate_key_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to generate key: {e}"))
+pub fn failed_to_expire_keys_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to expire keys", &e)
 }
 
-/// `500` error response when the provide key cannot be added.
+/// Error response when the quota of a key cannot be set.
 #[must_use]
-pub fn failed_to_add_key_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to add key: {e}"))
+pub fn failed_to_set_key_quota_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to set key quota", &e)
 }
 
-/// `500` error response when an authentication key cannot be deleted.
+/// Error response when the quota of a key cannot be removed.
 #[must_use]
-pub fn failed_to_delete_key_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to delete key: {e}"))
+pub fn failed_to_remove_key_quota_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove key quota", &e)
 }
 
-/// `500` error response when the authentication keys cannot be reloaded from
+/// Error response when the authentication keys cannot be reloaded from
 /// the database into memory.
 #[must_use]
-pub fn failed_to_reload_keys_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to reload keys: {e}"))
+pub fn failed_to_reload_keys_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to reload keys", &e)
 }
 
+/// `422` error response when the provided key is not valid.
 #[must_use]
 pub fn invalid_auth_key_response<E: Error>(auth_key: &str, e: E) -> Response {
-    bad_request_response(&format!("Invalid URL: invalid auth key: string \"{auth_key}\", {e}"))
+    validation_failed_response("key", &format!("Invalid URL: invalid auth key: string \"{auth_key}\", {e}"))
+}
+
+/// `422` error response when the IP address or IP range the key is bound to
+/// is not valid.
+#[must_use]
+pub fn invalid_auth_key_binding_response<E: Error>(bound_to: &str, e: E) -> Response {
+    validation_failed_response("bound_to", &format!("Invalid key binding: \"{bound_to}\", {e}"))
 }
 
+/// `422` error response when the key duration is too long.
 #[must_use]
 pub fn invalid_auth_key_duration_response(duration: u64) -> Response {
-    bad_request_response(&format!("Invalid URL: invalid auth key duration: \"{duration}\""))
+    validation_failed_response(
+        "seconds_valid",
+        &format!("Invalid URL: invalid auth key duration: \"{duration}\""),
+    )
 }
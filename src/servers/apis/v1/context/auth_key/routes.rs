@@ -4,18 +4,29 @@
 //! - `POST /key/:seconds_valid`
 //! - `DELETE /key/:key`
 //! - `GET /keys/reload`
+//! - `POST /keys`
+//! - `POST /keys/expire-all` (admin token required)
+//! - `GET /key/:key/stats`
+//! - `GET /key/:key/valid`
+//! - `POST /key/:key/quota`
+//! - `DELETE /key/:key/quota`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key).
 use std::sync::Arc;
 
 use axum::routing::{get, post};
-use axum::Router;
+use axum::{middleware, Router};
+use url::Url;
 
-use super::handlers::{add_auth_key_handler, delete_auth_key_handler, generate_auth_key_handler, reload_keys_handler};
+use super::handlers::{
+    add_auth_key_handler, delete_auth_key_handler, expire_all_keys_handler, generate_auth_key_handler, get_key_stats_handler,
+    get_key_validity_handler, reload_keys_handler, remove_key_quota_handler, set_key_quota_handler,
+};
 use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::admin;
 
 /// It adds the routes to the router for the [`auth_key`](crate::servers::apis::v1::context::auth_key) API context.
-pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>, incident_webhook_url: Option<Url>) -> Router {
     // Keys
     router
         .route(
@@ -33,10 +44,32 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
                 .delete(delete_auth_key_handler)
                 .with_state(tracker.clone()),
         )
+        // Key quotas. The path variable must have the same name as in the
+        // route above.
+        .route(
+            &format!("{prefix}/key/:seconds_valid_or_key/stats"),
+            get(get_key_stats_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/key/:seconds_valid_or_key/valid"),
+            get(get_key_validity_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/key/:seconds_valid_or_key/quota"),
+            post(set_key_quota_handler)
+                .delete(remove_key_quota_handler)
+                .with_state(tracker.clone()),
+        )
         // Keys command
         .route(
             &format!("{prefix}/keys/reload"),
             get(reload_keys_handler).with_state(tracker.clone()),
         )
+        .route(
+            &format!("{prefix}/keys/expire-all"),
+            post(expire_all_keys_handler)
+                .route_layer(middleware::from_fn(admin))
+                .with_state((tracker.clone(), incident_webhook_url)),
+        )
         .route(&format!("{prefix}/keys"), post(add_auth_key_handler).with_state(tracker))
 }
@@ -0,0 +1,41 @@
+//! API handlers for the [`backup`](crate::servers::apis::v1::context::backup)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Json, Response};
+
+use super::resources::BackupStatus;
+use super::responses::{backup_status_response, backups_not_enabled_response, failed_to_back_up_the_database_response};
+use crate::core::Tracker;
+
+/// It handles the request to get the status of the database backups.
+///
+/// It returns a `200` response with a json [`BackupStatus`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::backup#get-the-backup-status)
+/// for more information about this endpoint.
+pub async fn get_backup_status_handler(State(tracker): State<Arc<Tracker>>) -> Json<BackupStatus> {
+    backup_status_response(&tracker)
+}
+
+/// It handles the request to back up the database.
+///
+/// It returns:
+///
+/// - `200` response with the updated json [`BackupStatus`].
+/// - `409` if the database backups are not enabled.
+/// - `5xx` with a json error if the backup failed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::backup#back-up-the-database)
+/// for more information about this endpoint.
+pub async fn back_up_database_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    if !tracker.is_database_backup_enabled() {
+        return backups_not_enabled_response();
+    }
+
+    match tracker.backup_database().await {
+        Ok(_) => backup_status_response(&tracker).into_response(),
+        Err(e) => failed_to_back_up_the_database_response(e),
+    }
+}
@@ -0,0 +1,95 @@
+//! Database backup API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the database backups.
+//!
+//! Backups are only available when they are enabled in the configuration,
+//! and only for the `sqlite3` database driver:
+//!
+//! ```toml
+//! [core.database_backup]
+//! directory = "./storage/tracker/lib/database/backups"
+//! interval = 86400
+//! retention = 7
+//! ```
+//!
+//! Refer to the [`backup`](crate::core::backup) module for more information
+//! about the backups.
+//!
+//! # Endpoints
+//!
+//! - [Get the backup status](#get-the-backup-status)
+//! - [Back up the database](#back-up-the-database)
+//!
+//! # Get the backup status
+//!
+//! `GET /database/backup`
+//!
+//! It returns whether the backups are enabled and the result of the last
+//! backup, if any.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/database/backup?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "enabled": true,
+//!     "last_backup": {
+//!         "finished_at": "2024-10-16T09:00:00.123456Z",
+//!         "file": "./storage/tracker/lib/database/backups/sqlite3-20241016T090000.120000Z.db",
+//!         "error": null
+//!     }
+//! }
+//! ```
+//!
+//! Refer to the API [`BackupStatus`](crate::servers::apis::v1::context::backup::resources::BackupStatus)
+//! resource for more information about the response attributes.
+//!
+//! # Back up the database
+//!
+//! `POST /database/backup`
+//!
+//! It writes a new backup and returns the updated backup status.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/database/backup?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! The same as the [backup status](#get-the-backup-status) response.
+//!
+//! **Example response** `409`
+//!
+//! When the backups are not enabled:
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "database backups are not enabled",
+//!     "details": []
+//! }
+//! ```
+//!
+//! **Example response** `501`
+//!
+//! When the database driver does not support online backups:
+//!
+//! ```json
+//! {
+//!     "code": "not_supported",
+//!     "message": "failed to back up the database: Backups are not supported for the mysql database, src/core/databases/mysql.rs:123:9",
+//!     "details": []
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
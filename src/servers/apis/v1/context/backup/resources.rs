@@ -0,0 +1,72 @@
+//! API resources for the [`backup`](crate::servers::apis::v1::context::backup)
+//! API context.
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{backup, Tracker};
+
+/// The status of the database backups.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct BackupStatus {
+    /// Whether the database backups are enabled.
+    pub enabled: bool,
+    /// The last backup since the tracker started, if any.
+    pub last_backup: Option<Backup>,
+}
+
+/// The result of a database backup.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Backup {
+    /// Time when the backup finished, in RFC 3339 format.
+    pub finished_at: String,
+    /// The backup file, when the backup succeeded.
+    pub file: Option<String>,
+    /// The error message, when the backup failed.
+    pub error: Option<String>,
+}
+
+impl From<&Tracker> for BackupStatus {
+    fn from(tracker: &Tracker) -> Self {
+        Self {
+            enabled: tracker.is_database_backup_enabled(),
+            last_backup: tracker.get_last_database_backup().map(Backup::from),
+        }
+    }
+}
+
+impl From<backup::Status> for Backup {
+    fn from(status: backup::Status) -> Self {
+        Self {
+            finished_at: status.finished_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            file: status.file.map(|file| file.to_string()),
+            error: status.error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use chrono::{TimeZone, Utc};
+
+    use super::Backup;
+    use crate::core::backup;
+
+    #[test]
+    fn backup_resource_should_be_converted_from_the_backup_status() {
+        let status = backup::Status {
+            finished_at: Utc.with_ymd_and_hms(2024, 10, 16, 9, 0, 0).unwrap(),
+            file: Some(Utf8PathBuf::from("backups/sqlite3-20241016T090000.000000Z.db")),
+            error: None,
+        };
+
+        assert_eq!(
+            Backup::from(status),
+            Backup {
+                finished_at: "2024-10-16T09:00:00.000000Z".to_string(),
+                file: Some("backups/sqlite3-20241016T090000.000000Z.db".to_string()),
+                error: None,
+            }
+        );
+    }
+}
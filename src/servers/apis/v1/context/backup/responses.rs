@@ -0,0 +1,26 @@
+//! API responses for the [`backup`](crate::servers::apis::v1::context::backup)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::BackupStatus;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::{failed_response, not_enabled_response};
+
+/// `200` response that contains the [`BackupStatus`] resource as json.
+pub fn backup_status_response(tracker: &Tracker) -> Json<BackupStatus> {
+    Json(BackupStatus::from(tracker))
+}
+
+/// `409` error response when the database backups are not enabled.
+#[must_use]
+pub fn backups_not_enabled_response() -> Response {
+    not_enabled_response("database backups are not enabled")
+}
+
+/// Error response when the database backup fails.
+#[must_use]
+pub fn failed_to_back_up_the_database_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to back up the database", &e)
+}
@@ -0,0 +1,23 @@
+//! API routes for the [`backup`](crate::servers::apis::v1::context::backup) API context.
+//!
+//! - `GET /database/backup`
+//! - `POST /database/backup`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::backup).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::Router;
+
+use super::handlers::{back_up_database_handler, get_backup_status_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`backup`](crate::servers::apis::v1::context::backup) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router.route(
+        &format!("{prefix}/database/backup"),
+        get(get_backup_status_handler)
+            .post(back_up_database_handler)
+            .with_state(tracker),
+    )
+}
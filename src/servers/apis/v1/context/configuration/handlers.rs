@@ -0,0 +1,15 @@
+//! API handlers for the [`configuration`](crate::servers::apis::v1::context::configuration)
+//! API context.
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use torrust_tracker_configuration::Configuration;
+
+/// It handles the request to get the configuration JSON Schema.
+///
+/// It returns a `200` response with the schema in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::configuration#get-the-configuration-schema)
+/// for more information about this endpoint.
+pub async fn get_configuration_schema_handler() -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], Configuration::json_schema()).into_response()
+}
@@ -0,0 +1,49 @@
+//! Configuration API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the tracker configuration.
+//!
+//! # Endpoints
+//!
+//! - [Get the configuration schema](#get-the-configuration-schema)
+//!
+//! # Get the configuration schema
+//!
+//! `GET /configuration/schema`
+//!
+//! It returns the [JSON Schema](https://json-schema.org/) of the configuration
+//! for the version of the running tracker. Editors and configuration
+//! management tools can use it to validate the configuration.
+//!
+//! The same schema can be printed with:
+//!
+//! ```text
+//! torrust-tracker config schema
+//! ```
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/configuration/schema?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!   "$schema": "https://json-schema.org/draft/2020-12/schema",
+//!   "title": "Configuration",
+//!   "description": "Core configuration for the tracker.",
+//!   "type": "object",
+//!   "properties": {
+//!     "metadata": {
+//!       "description": "Configuration metadata.",
+//!       "$ref": "#/$defs/Metadata"
+//!     },
+//!     ...
+//!   },
+//!   ...
+//! }
+//! ```
+pub mod handlers;
+pub mod routes;
@@ -0,0 +1,17 @@
+//! API routes for the [`configuration`](crate::servers::apis::v1::context::configuration) API context.
+//!
+//! - `GET /configuration/schema`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::configuration).
+use axum::routing::get;
+use axum::Router;
+
+use super::handlers::get_configuration_schema_handler;
+
+/// It adds the routes to the router for the [`configuration`](crate::servers::apis::v1::context::configuration) API context.
+pub fn add(prefix: &str, router: Router) -> Router {
+    router.route(
+        &format!("{prefix}/configuration/schema"),
+        get(get_configuration_schema_handler),
+    )
+}
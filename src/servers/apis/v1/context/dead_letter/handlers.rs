@@ -0,0 +1,54 @@
+//! API handlers for the [`dead_letter`](crate::servers::apis::v1::context::dead_letter)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Json, Response};
+
+use super::resources::DeadLetterQueue;
+use super::responses::{dead_letter_queue_not_enabled_response, dead_letter_queue_response};
+use crate::core::Tracker;
+
+/// It handles the request to get the dead-letter queue.
+///
+/// It returns a `200` response with a json [`DeadLetterQueue`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::dead_letter#get-the-dead-letter-queue)
+/// for more information about this endpoint.
+pub async fn get_dead_letters_handler(State(tracker): State<Arc<Tracker>>) -> Json<DeadLetterQueue> {
+    dead_letter_queue_response(&tracker)
+}
+
+/// It handles the request to retry the pending writes in the dead-letter
+/// queue.
+///
+/// It returns:
+///
+/// - `200` response with the updated json [`DeadLetterQueue`].
+/// - `409` if the dead-letter queue is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::dead_letter#replay-the-dead-letter-queue)
+/// for more information about this endpoint.
+pub async fn replay_dead_letters_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.replay_dead_letters().await {
+        Some(_) => dead_letter_queue_response(&tracker).into_response(),
+        None => dead_letter_queue_not_enabled_response(),
+    }
+}
+
+/// It handles the request to discard the pending writes in the dead-letter
+/// queue.
+///
+/// It returns:
+///
+/// - `200` response with the updated json [`DeadLetterQueue`].
+/// - `409` if the dead-letter queue is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::dead_letter#drain-the-dead-letter-queue)
+/// for more information about this endpoint.
+pub async fn drain_dead_letters_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.drain_dead_letters() {
+        Some(_) => dead_letter_queue_response(&tracker).into_response(),
+        None => dead_letter_queue_not_enabled_response(),
+    }
+}
@@ -0,0 +1,123 @@
+//! Dead-letter queue API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the failed database writes waiting to be retried.
+//!
+//! The queue is only available when it's enabled in the configuration:
+//!
+//! ```toml
+//! [core.dead_letter_queue]
+//! path = "./storage/tracker/lib/database/dead_letters.ndjson"
+//! capacity = 10000
+//! min_backoff_ms = 1000
+//! max_backoff_ms = 60000
+//! ```
+//!
+//! Refer to the [`dead_letter`](crate::core::dead_letter) module for more
+//! information about the queue.
+//!
+//! # Endpoints
+//!
+//! - [Get the dead-letter queue](#get-the-dead-letter-queue)
+//! - [Replay the dead-letter queue](#replay-the-dead-letter-queue)
+//! - [Drain the dead-letter queue](#drain-the-dead-letter-queue)
+//!
+//! # Get the dead-letter queue
+//!
+//! `GET /database/dead_letters`
+//!
+//! It returns the queue metrics and the pending writes, oldest first.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/database/dead_letters?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "enabled": true,
+//!     "pending": 1,
+//!     "queued": 3,
+//!     "replayed": 2,
+//!     "dropped": 0,
+//!     "letters": [
+//!         {
+//!             "write": {
+//!                 "kind": "torrent_completed",
+//!                 "info_hash": "9c38422213e30bff212b30c360d26f9a02136422",
+//!                 "completed": 12
+//!             },
+//!             "failed_at": "2024-10-16T09:00:00.123456Z",
+//!             "attempts": 3,
+//!             "last_error": "Unable to insert record into Sqlite3 database, src/core/databases/sqlite.rs:197:21"
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Refer to the API [`DeadLetterQueue`](crate::servers::apis::v1::context::dead_letter::resources::DeadLetterQueue)
+//! resource for more information about the response attributes.
+//!
+//! # Replay the dead-letter queue
+//!
+//! `POST /database/dead_letters/replay`
+//!
+//! It retries all the pending writes now, without waiting for the backoff,
+//! and returns the updated queue.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/database/dead_letters/replay?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! The same as the [dead-letter queue](#get-the-dead-letter-queue) response.
+//!
+//! **Example response** `409`
+//!
+//! When the dead-letter queue is not enabled:
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the dead-letter queue is not enabled",
+//!     "details": []
+//! }
+//! ```
+//!
+//! # Drain the dead-letter queue
+//!
+//! `DELETE /database/dead_letters`
+//!
+//! It discards all the pending writes and returns the updated queue.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/database/dead_letters?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! The same as the [dead-letter queue](#get-the-dead-letter-queue) response.
+//!
+//! **Example response** `409`
+//!
+//! When the dead-letter queue is not enabled:
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the dead-letter queue is not enabled",
+//!     "details": []
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,144 @@
+//! API resources for the [`dead_letter`](crate::servers::apis::v1::context::dead_letter)
+//! API context.
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
+
+use crate::core::{dead_letter, Tracker};
+
+/// The status of the dead-letter queue.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DeadLetterQueue {
+    /// Whether the dead-letter queue is enabled.
+    pub enabled: bool,
+    /// Number of writes waiting to be retried.
+    pub pending: usize,
+    /// Total number of writes queued since the tracker started.
+    pub queued: u64,
+    /// Total number of writes stored by a retry since the tracker started.
+    pub replayed: u64,
+    /// Total number of writes discarded because the queue was full or drained.
+    pub dropped: u64,
+    /// The writes waiting to be retried, oldest first.
+    pub letters: Vec<DeadLetter>,
+}
+
+/// A failed database write waiting to be retried.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DeadLetter {
+    /// The write to retry.
+    pub write: Write,
+    /// Time of the first failure, in RFC 3339 format.
+    pub failed_at: String,
+    /// Number of failed attempts.
+    pub attempts: u32,
+    /// The error message of the last failed attempt.
+    pub last_error: String,
+}
+
+/// A database write.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Write {
+    /// The number of completed downloads of a torrent.
+    TorrentCompleted { info_hash: String, completed: u32 },
+    /// A new authentication key. The expiration is a Unix timestamp in seconds.
+    AuthKey { key: String, valid_until: Option<u64> },
+}
+
+impl From<&Tracker> for DeadLetterQueue {
+    fn from(tracker: &Tracker) -> Self {
+        let metrics = tracker.get_dead_letter_metrics().unwrap_or_default();
+
+        Self {
+            enabled: tracker.is_dead_letter_queue_enabled(),
+            pending: metrics.pending,
+            queued: metrics.queued,
+            replayed: metrics.replayed,
+            dropped: metrics.dropped,
+            letters: tracker.get_dead_letters().into_iter().map(DeadLetter::from).collect(),
+        }
+    }
+}
+
+impl From<dead_letter::Letter> for DeadLetter {
+    fn from(letter: dead_letter::Letter) -> Self {
+        Self {
+            write: Write::from(letter.write),
+            failed_at: convert_from_timestamp_to_datetime_utc(letter.failed_at).to_rfc3339_opts(SecondsFormat::Micros, true),
+            attempts: letter.attempts,
+            last_error: letter.last_error,
+        }
+    }
+}
+
+impl From<dead_letter::Write> for Write {
+    fn from(write: dead_letter::Write) -> Self {
+        match write {
+            dead_letter::Write::TorrentCompleted { info_hash, completed } => Self::TorrentCompleted {
+                info_hash: info_hash.to_hex_string(),
+                completed,
+            },
+            dead_letter::Write::AuthKey { peer_key } => Self::AuthKey {
+                key: peer_key.key.to_string(),
+                valid_until: peer_key.valid_until.map(|valid_until| valid_until.as_secs()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::{DeadLetter, Write};
+    use crate::core::auth::PeerKey;
+    use crate::core::dead_letter;
+
+    #[test]
+    fn dead_letter_resource_should_be_converted_from_a_queued_write() {
+        let letter = dead_letter::Letter {
+            write: dead_letter::Write::TorrentCompleted {
+                info_hash: InfoHash::from([1; 20]),
+                completed: 12,
+            },
+            failed_at: Duration::new(1_729_069_200, 123_456_000),
+            attempts: 3,
+            last_error: "database is locked".to_string(),
+        };
+
+        assert_eq!(
+            DeadLetter::from(letter),
+            DeadLetter {
+                write: Write::TorrentCompleted {
+                    info_hash: "0101010101010101010101010101010101010101".to_string(),
+                    completed: 12,
+                },
+                failed_at: "2024-10-16T09:00:00.123456Z".to_string(),
+                attempts: 3,
+                last_error: "database is locked".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn write_resource_should_be_converted_from_a_queued_auth_key() {
+        let write = dead_letter::Write::AuthKey {
+            peer_key: PeerKey {
+                key: "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse().unwrap(),
+                valid_until: Some(Duration::from_secs(60)),
+                bound_to: None,
+            },
+        };
+
+        assert_eq!(
+            Write::from(write),
+            Write::AuthKey {
+                key: "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".to_string(),
+                valid_until: Some(60),
+            }
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! API responses for the [`dead_letter`](crate::servers::apis::v1::context::dead_letter)
+//! API context.
+use axum::response::{Json, Response};
+
+use super::resources::DeadLetterQueue;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::not_enabled_response;
+
+/// `200` response that contains the [`DeadLetterQueue`] resource as json.
+pub fn dead_letter_queue_response(tracker: &Tracker) -> Json<DeadLetterQueue> {
+    Json(DeadLetterQueue::from(tracker))
+}
+
+/// `409` error response when the dead-letter queue is not enabled.
+#[must_use]
+pub fn dead_letter_queue_not_enabled_response() -> Response {
+    not_enabled_response("the dead-letter queue is not enabled")
+}
@@ -0,0 +1,29 @@
+//! API routes for the [`dead_letter`](crate::servers::apis::v1::context::dead_letter) API context.
+//!
+//! - `GET /database/dead_letters`
+//! - `DELETE /database/dead_letters`
+//! - `POST /database/dead_letters/replay`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::dead_letter).
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use axum::Router;
+
+use super::handlers::{drain_dead_letters_handler, get_dead_letters_handler, replay_dead_letters_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`dead_letter`](crate::servers::apis::v1::context::dead_letter) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router
+        .route(
+            &format!("{prefix}/database/dead_letters"),
+            get(get_dead_letters_handler)
+                .delete(drain_dead_letters_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/database/dead_letters/replay"),
+            post(replay_dead_letters_handler).with_state(tracker),
+        )
+}
@@ -0,0 +1,40 @@
+//! API handlers for the [`events`](crate::servers::apis::v1::context::events)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::sse::{self, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures::stream;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::resources::Event;
+use crate::core::Tracker;
+
+/// It handles the request to follow the domain events of the tracker.
+///
+/// It returns a `200` response with a stream of server-sent events. Each
+/// event is an [`Event`] resource. The stream ends when the tracker stops.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::events#follow-the-events)
+/// for more information about this endpoint.
+pub async fn get_events_handler(State((tracker, peer_id_hash_salt)): State<(Arc<Tracker>, Option<String>)>) -> Response {
+    let receiver = tracker.subscribe();
+
+    let events = stream::unfold(
+        (receiver, peer_id_hash_salt),
+        |(mut receiver, peer_id_hash_salt)| async move {
+            let event = match receiver.recv().await {
+                Ok(event) => Event::new(event, peer_id_hash_salt.as_deref()),
+                Err(RecvError::Lagged(skipped)) => Event::Lagged { skipped },
+                Err(RecvError::Closed) => return None,
+            };
+
+            let sse_event = sse::Event::default().event(event.name()).json_data(&event);
+
+            Some((sse_event, (receiver, peer_id_hash_salt)))
+        },
+    );
+
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
@@ -0,0 +1,55 @@
+//! Events API context.
+//!
+//! This API context is responsible for streaming the
+//! [domain events](crate::core::events) of the tracker, as
+//! [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+//!
+//! # Endpoints
+//!
+//! - [Follow the events](#follow-the-events)
+//!
+//! # Follow the events
+//!
+//! `GET /events`
+//!
+//! It keeps the connection open and sends every event published after the
+//! request, like the peers announcing themselves, the torrents added to and
+//! removed from the tracker, and the keys created and revoked. Clients like
+//! dashboards or replicas can follow the tracker with it instead of polling
+//! the other endpoints. This endpoint requires one of the
+//! `admin_access_tokens`.
+//!
+//! The name of each event is its type, and the data is a json object with the
+//! same `type` and the event attributes. The peer IDs are hashed when the
+//! `peer_id_hash_salt` option is set, like in the torrent endpoint.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -N "http://127.0.0.1:1212/api/v1/events?token=MyAdminAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! event: torrent_created
+//! data: {"type":"torrent_created","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"}
+//!
+//! event: key_revoked
+//! data: {"type":"key_revoked","key":"YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"}
+//! ```
+//!
+//! When the client is too slow to receive the events, some of them are
+//! dropped, and the client gets a `lagged` event with the number of events
+//! it missed, so it can sync again with the other endpoints:
+//!
+//! ```text
+//! event: lagged
+//! data: {"type":"lagged","skipped":42}
+//! ```
+//!
+//! Refer to the API [`Event`](crate::servers::apis::v1::context::events::resources::Event)
+//! resource for more information about the event attributes.
+pub mod handlers;
+pub mod resources;
+pub mod routes;
@@ -0,0 +1,96 @@
+//! API resources for the [`events`](crate::servers::apis::v1::context::events)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::events;
+use crate::servers::apis::v1::context::torrent::resources::peer::Peer;
+
+/// A [domain event](crate::core::events::Event) of the tracker, or a notice
+/// about the events the client missed.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A peer announced itself for a torrent.
+    PeerAnnounced { info_hash: String, peer: Peer },
+    /// A torrent was added to the tracker.
+    TorrentCreated { info_hash: String },
+    /// A torrent was removed from the tracker.
+    TorrentRemoved { info_hash: String },
+    /// A torrent was removed from the whitelist.
+    TorrentUnwhitelisted { info_hash: String },
+    /// An authentication key was created.
+    KeyCreated { key: String },
+    /// An authentication key was revoked.
+    KeyRevoked { key: String },
+    /// The client was too slow and it missed `skipped` events.
+    Lagged { skipped: u64 },
+}
+
+impl Event {
+    /// It converts a domain event, hashing the peer ID with the salt if there
+    /// is one.
+    #[must_use]
+    pub fn new(event: events::Event, peer_id_hash_salt: Option<&str>) -> Self {
+        match event {
+            events::Event::PeerAnnounced { info_hash, peer } => Event::PeerAnnounced {
+                info_hash: info_hash.to_string(),
+                peer: match peer_id_hash_salt {
+                    Some(salt) => Peer::with_hashed_id(peer, salt),
+                    None => Peer::from(peer),
+                },
+            },
+            events::Event::TorrentCreated { info_hash } => Event::TorrentCreated {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::TorrentRemoved { info_hash } => Event::TorrentRemoved {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::TorrentUnwhitelisted { info_hash } => Event::TorrentUnwhitelisted {
+                info_hash: info_hash.to_string(),
+            },
+            events::Event::KeyCreated { key } => Event::KeyCreated { key: key.to_string() },
+            events::Event::KeyRevoked { key } => Event::KeyRevoked { key: key.to_string() },
+        }
+    }
+
+    /// The name of the server-sent event, which is the type of the event.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::PeerAnnounced { .. } => "peer_announced",
+            Event::TorrentCreated { .. } => "torrent_created",
+            Event::TorrentRemoved { .. } => "torrent_removed",
+            Event::TorrentUnwhitelisted { .. } => "torrent_unwhitelisted",
+            Event::KeyCreated { .. } => "key_created",
+            Event::KeyRevoked { .. } => "key_revoked",
+            Event::Lagged { .. } => "lagged",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::Event;
+    use crate::core::events;
+
+    #[test]
+    fn it_should_be_serialized_with_the_type_of_the_event() {
+        let event = Event::new(
+            events::Event::TorrentCreated {
+                info_hash: InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+            },
+            None,
+        );
+
+        assert_eq!(event.name(), "torrent_created");
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({ "type": "torrent_created", "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d" })
+        );
+    }
+}
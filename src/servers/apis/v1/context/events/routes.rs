@@ -0,0 +1,23 @@
+//! API routes for the [`events`](crate::servers::apis::v1::context::events) API context.
+//!
+//! - `GET /events` (admin token required)
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::events).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::{middleware, Router};
+
+use super::handlers::get_events_handler;
+use crate::core::Tracker;
+use crate::servers::apis::v1::middlewares::auth::admin;
+
+/// It adds the routes to the router for the [`events`](crate::servers::apis::v1::context::events) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>, peer_id_hash_salt: Option<String>) -> Router {
+    router.route(
+        &format!("{prefix}/events"),
+        get(get_events_handler)
+            .route_layer(middleware::from_fn(admin))
+            .with_state((tracker, peer_id_hash_salt)),
+    )
+}
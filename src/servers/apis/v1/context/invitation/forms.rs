@@ -0,0 +1,10 @@
+//! API forms for the [`invitation`](crate::servers::apis::v1::context::invitation)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+/// The invitation code a new user exchanges for an authentication key.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterForm {
+    /// The single-use invitation code.
+    pub code: String,
+}
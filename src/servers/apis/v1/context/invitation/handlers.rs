@@ -0,0 +1,119 @@
+//! API handlers for the [`invitation`](crate::servers::apis::v1::context::invitation)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use torrust_tracker_configuration::Registration;
+
+use super::forms::RegisterForm;
+use super::responses::{
+    failed_to_generate_invitation_code_response, failed_to_load_invitation_codes_response, failed_to_register_response,
+    failed_to_remove_invitation_code_response, invalid_invitation_code_field_response, invalid_invitation_code_param_response,
+    invitation_code_not_valid_response, invitation_code_response, invitation_codes_response, registration_not_enabled_response,
+};
+use crate::core::error::RegistrationError;
+use crate::core::invitation::Code;
+use crate::core::Tracker;
+use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
+use crate::servers::apis::v1::context::auth_key::responses::auth_key_response;
+use crate::servers::apis::v1::extractors::{self, Path};
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to mint a new invitation code.
+///
+/// It returns two types of responses:
+///
+/// - `200` with a json [`InvitationCode`](crate::servers::apis::v1::context::invitation::resources::InvitationCode)
+///   resource. If the code was generated successfully.
+/// - `5xx` with a json error. If the code couldn't be persisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::invitation#generate-a-new-invitation-code)
+/// for more information about this endpoint.
+pub async fn generate_invitation_code_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.generate_invitation_code() {
+        Ok(code) => invitation_code_response(code).into_response(),
+        Err(e) => failed_to_generate_invitation_code_response(e),
+    }
+}
+
+/// It handles the request to list the invitation codes that have not been
+/// used yet.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::invitation#list-the-invitation-codes)
+/// for more information about this endpoint.
+pub async fn get_invitation_codes_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_invitation_codes() {
+        Ok(codes) => invitation_codes_response(codes).into_response(),
+        Err(e) => failed_to_load_invitation_codes_response(e),
+    }
+}
+
+/// A container for the `code` parameter extracted from the URL PATH.
+///
+/// It does not perform any validation, it just stores the value.
+#[derive(Deserialize)]
+pub struct CodeParam(String);
+
+/// It handles the request to revoke an invitation code.
+///
+/// It returns three types of responses:
+///
+/// - `200` with an json [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok)
+///   response. If the code was revoked successfully.
+/// - `400` if the code is not valid.
+/// - `5xx` with a json error. If the code couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::invitation#revoke-an-invitation-code)
+/// for more information about this endpoint.
+pub async fn delete_invitation_code_handler(State(tracker): State<Arc<Tracker>>, Path(code): Path<CodeParam>) -> Response {
+    match Code::from_str(&code.0) {
+        Err(_) => invalid_invitation_code_param_response(&code.0),
+        Ok(code) => match tracker.remove_invitation_code(&code) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_invitation_code_response(e),
+        },
+    }
+}
+
+/// It handles the request to exchange an invitation code for a new
+/// authentication key.
+///
+/// It returns these types of responses:
+///
+/// - `200` with a json [`AuthKey`] resource. If the key was issued
+///   successfully.
+/// - `404` if the code is unknown or it was already used.
+/// - `409` if the self-registration is not enabled.
+/// - `422` if the code is not well-formed.
+/// - `5xx` with a json error. If the key couldn't be issued.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::invitation#register-with-an-invitation-code)
+/// for more information about this endpoint.
+pub async fn register_handler(
+    State(state): State<(Arc<Tracker>, Option<Registration>)>,
+    extractors::Json(register_form): extractors::Json<RegisterForm>,
+) -> Response {
+    let (tracker, registration) = state;
+
+    let Some(registration) = registration else {
+        return registration_not_enabled_response();
+    };
+
+    let code = match Code::from_str(&register_form.code) {
+        Ok(code) => code,
+        Err(e) => return invalid_invitation_code_field_response(e),
+    };
+
+    match tracker
+        .register_with_invitation_code(&code, registration.key_seconds_valid.map(Duration::from_secs))
+        .await
+    {
+        Ok(peer_key) => auth_key_response(&AuthKey::from(peer_key)),
+        Err(RegistrationError::InvitationCodeNotValid { .. }) => invitation_code_not_valid_response(),
+        Err(RegistrationError::DatabaseError { source }) => failed_to_register_response(source),
+    }
+}
@@ -0,0 +1,146 @@
+//! Invitation codes API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the self-registration of new users with invitation codes. The admins mint
+//! single-use invitation codes and share them with the new users, who exchange
+//! them for an authentication key without an access token.
+//!
+//! The codes are persisted in the database. The self-registration is disabled
+//! by default, but the codes can be minted anyway:
+//!
+//! ```toml
+//! [http_api.registration]
+//! key_seconds_valid = 2592000
+//! ```
+//!
+//! Refer to the [`invitation`](crate::core::invitation) module for more
+//! information.
+//!
+//! # Endpoints
+//!
+//! - [Generate a new invitation code](#generate-a-new-invitation-code)
+//! - [List the invitation codes](#list-the-invitation-codes)
+//! - [Revoke an invitation code](#revoke-an-invitation-code)
+//! - [Register with an invitation code](#register-with-an-invitation-code)
+//!
+//! # Generate a new invitation code
+//!
+//! `POST /invitations`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/invitations?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "code": "IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82"
+//! }
+//! ```
+//!
+//! Refer to the API [`InvitationCode`](crate::servers::apis::v1::context::invitation::resources::InvitationCode)
+//! resource for more information about the response attributes.
+//!
+//! # List the invitation codes
+//!
+//! `GET /invitations`
+//!
+//! It returns the codes that have not been used yet.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/invitations?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "code": "IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82"
+//!     }
+//! ]
+//! ```
+//!
+//! # Revoke an invitation code
+//!
+//! `DELETE /invitation/:code`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/invitation/IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Register with an invitation code
+//!
+//! `POST /register`
+//!
+//! It exchanges an invitation code for a new authentication key. It's a
+//! public endpoint: it does not require an access token. The code can only be
+//! used once.
+//!
+//! **POST params**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `code` | 32-char string | The invitation code | Yes | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/register \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "code": "IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82"
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "valid_until": 1682249460,
+//!     "expiry_time": "2023-04-23 11:31:00 UTC"
+//! }
+//! ```
+//!
+//! The key is permanent if the `key_seconds_valid` option is not set. Refer
+//! to the API [`AuthKey`](crate::servers::apis::v1::context::auth_key::resources::AuthKey)
+//! resource for more information about the response attributes.
+//!
+//! **Not Found response** `404`
+//!
+//! This response is returned when the code is not valid or it was already
+//! used.
+//!
+//! ```json
+//! {
+//!     "code": "not_found",
+//!     "message": "invitation code not valid",
+//!     "details": []
+//! }
+//! ```
+//!
+//! **Not Enabled response** `409`
+//!
+//! This response is returned when the self-registration is not enabled in the
+//! configuration.
+pub mod forms;
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
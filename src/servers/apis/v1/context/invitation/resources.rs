@@ -0,0 +1,38 @@
+//! API resources for the [`invitation`](crate::servers::apis::v1::context::invitation)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::invitation;
+
+/// A single-use invitation code.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct InvitationCode {
+    /// The invitation code.
+    pub code: String,
+}
+
+impl From<invitation::Code> for InvitationCode {
+    fn from(code: invitation::Code) -> Self {
+        Self { code: code.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::InvitationCode;
+    use crate::core::invitation;
+
+    #[test]
+    fn it_should_be_convertible_from_an_invitation_code() {
+        let code = invitation::Code::from_str("IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82").unwrap();
+
+        assert_eq!(
+            InvitationCode::from(code),
+            InvitationCode {
+                code: "IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82".to_string()
+            }
+        );
+    }
+}
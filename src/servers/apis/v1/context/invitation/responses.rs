@@ -0,0 +1,72 @@
+//! API responses for the [`invitation`](crate::servers::apis::v1::context::invitation)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::InvitationCode;
+use crate::core::invitation;
+use crate::servers::apis::v1::responses::{
+    failed_response, invalid_param_response, not_enabled_response, not_found_response, validation_failed_response,
+};
+
+/// `200` response that contains the [`InvitationCode`] resource as json.
+pub fn invitation_code_response(code: invitation::Code) -> Json<InvitationCode> {
+    Json(InvitationCode::from(code))
+}
+
+/// `200` response that contains an array of [`InvitationCode`] resources as
+/// json.
+pub fn invitation_codes_response(codes: Vec<invitation::Code>) -> Json<Vec<InvitationCode>> {
+    Json(codes.into_iter().map(InvitationCode::from).collect())
+}
+
+/// `400` error response when the invitation code in the URL path is not valid.
+#[must_use]
+pub fn invalid_invitation_code_param_response(code: &str) -> Response {
+    invalid_param_response("code", &format!("Invalid URL: invalid invitation code param \"{code}\""))
+}
+
+/// `422` error response when the invitation code in the request body is not
+/// valid.
+#[must_use]
+pub fn invalid_invitation_code_field_response<E: Error>(e: E) -> Response {
+    validation_failed_response("code", &format!("invalid invitation code: {e}"))
+}
+
+/// `404` error response when the invitation code is unknown or it was already
+/// used.
+#[must_use]
+pub fn invitation_code_not_valid_response() -> Response {
+    not_found_response("invitation code not valid")
+}
+
+/// `409` error response when the self-registration is not enabled.
+#[must_use]
+pub fn registration_not_enabled_response() -> Response {
+    not_enabled_response("self-registration is not enabled")
+}
+
+/// Error response when the invitation code cannot be generated.
+#[must_use]
+pub fn failed_to_generate_invitation_code_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to generate invitation code", &e)
+}
+
+/// Error response when the invitation codes cannot be loaded.
+#[must_use]
+pub fn failed_to_load_invitation_codes_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to load invitation codes", &e)
+}
+
+/// Error response when the invitation code cannot be revoked.
+#[must_use]
+pub fn failed_to_remove_invitation_code_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove invitation code", &e)
+}
+
+/// Error response when the new user cannot be registered.
+#[must_use]
+pub fn failed_to_register_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to register", &e)
+}
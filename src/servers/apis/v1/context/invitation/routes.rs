@@ -0,0 +1,45 @@
+//! API routes for the [`invitation`](crate::servers::apis::v1::context::invitation)
+//! API context.
+//!
+//! - `POST /invitations`
+//! - `GET /invitations`
+//! - `DELETE /invitation/:code`
+//! - `POST /register` (public)
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::invitation).
+use std::sync::Arc;
+
+use axum::routing::{delete, post};
+use axum::Router;
+use torrust_tracker_configuration::Registration;
+
+use super::handlers::{
+    delete_invitation_code_handler, generate_invitation_code_handler, get_invitation_codes_handler, register_handler,
+};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`invitation`](crate::servers::apis::v1::context::invitation) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router
+        .route(
+            &format!("{prefix}/invitations"),
+            post(generate_invitation_code_handler)
+                .get(get_invitation_codes_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/invitation/:code"),
+            delete(delete_invitation_code_handler).with_state(tracker),
+        )
+}
+
+/// It adds the public self-registration route to the router.
+///
+/// It must be added after the authentication layer, because new users don't
+/// have an access token.
+pub fn add_public(prefix: &str, router: Router, tracker: Arc<Tracker>, registration: Option<Registration>) -> Router {
+    router.route(
+        &format!("{prefix}/register"),
+        post(register_handler).with_state((tracker, registration)),
+    )
+}
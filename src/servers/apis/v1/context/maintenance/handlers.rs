@@ -0,0 +1,45 @@
+//! API handlers for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{Json, Response};
+
+use super::resources::Maintenance;
+use super::responses::maintenance_response;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get the maintenance mode.
+///
+/// It returns a `200` response with a json [`Maintenance`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#get-the-maintenance-mode)
+/// for more information about this endpoint.
+pub async fn get_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Json<Maintenance> {
+    maintenance_response(&tracker)
+}
+
+/// It handles the request to enable the maintenance mode.
+///
+/// It returns a `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#enable-the-maintenance-mode)
+/// for more information about this endpoint.
+pub async fn enable_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    tracker.set_maintenance(true);
+
+    ok_response()
+}
+
+/// It handles the request to disable the maintenance mode.
+///
+/// It returns a `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#disable-the-maintenance-mode)
+/// for more information about this endpoint.
+pub async fn disable_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    tracker.set_maintenance(false);
+
+    ok_response()
+}
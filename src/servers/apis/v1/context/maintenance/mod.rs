@@ -0,0 +1,94 @@
+//! Maintenance API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the tracker maintenance mode.
+//!
+//! While the tracker is in maintenance mode, `announce` requests are rejected:
+//!
+//! - The HTTP tracker returns a failure response with the `retry in` key
+//!   ([BEP 31](https://www.bittorrent.org/beps/bep_0031.html)).
+//! - The UDP tracker returns an error response with the same message.
+//!
+//! `scrape` requests and the API keep working, so operators can do database
+//! maintenance without clients hammering the tracker.
+//!
+//! The initial state and the retry interval can be set in the configuration:
+//!
+//! ```toml
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//! ```
+//!
+//! > **NOTICE**: the state set using the API is not persisted. The tracker
+//! > starts with the state in the configuration after restarting.
+//!
+//! # Endpoints
+//!
+//! - [Get the maintenance mode](#get-the-maintenance-mode)
+//! - [Enable the maintenance mode](#enable-the-maintenance-mode)
+//! - [Disable the maintenance mode](#disable-the-maintenance-mode)
+//!
+//! # Get the maintenance mode
+//!
+//! `GET /maintenance`
+//!
+//! It returns whether the tracker is in maintenance mode.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "enabled": false,
+//!     "retry_in": 10
+//! }
+//! ```
+//!
+//! Refer to the API [`Maintenance`](crate::servers::apis::v1::context::maintenance::resources::Maintenance)
+//! resource for more information about the response attributes.
+//!
+//! # Enable the maintenance mode
+//!
+//! `POST /maintenance`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Disable the maintenance mode
+//!
+//! `DELETE /maintenance`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
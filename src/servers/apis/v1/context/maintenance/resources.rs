@@ -0,0 +1,47 @@
+//! API resources for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::Tracker;
+
+/// The tracker maintenance mode.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Maintenance {
+    /// Whether the tracker is in maintenance mode.
+    pub enabled: bool,
+    /// Time in minutes clients are asked to wait before retrying an
+    /// `announce` while the tracker is in maintenance mode.
+    pub retry_in: u32,
+}
+
+impl From<&Tracker> for Maintenance {
+    fn from(tracker: &Tracker) -> Self {
+        Self {
+            enabled: tracker.is_in_maintenance(),
+            retry_in: tracker.get_maintenance_retry_in(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_test_helpers::configuration;
+
+    use super::Maintenance;
+    use crate::core::services::tracker_factory;
+
+    #[tokio::test]
+    async fn maintenance_resource_should_be_converted_from_the_tracker() {
+        let tracker = tracker_factory(&configuration::ephemeral());
+
+        tracker.set_maintenance(true);
+
+        assert_eq!(
+            Maintenance::from(&tracker),
+            Maintenance {
+                enabled: true,
+                retry_in: 10
+            }
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! API responses for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use axum::response::Json;
+
+use super::resources::Maintenance;
+use crate::core::Tracker;
+
+/// `200` response that contains the [`Maintenance`] resource as json.
+pub fn maintenance_response(tracker: &Tracker) -> Json<Maintenance> {
+    Json(Maintenance::from(tracker))
+}
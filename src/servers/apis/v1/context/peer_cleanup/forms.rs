@@ -0,0 +1,21 @@
+//! API forms for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup)
+//! API context.
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DefaultOnNull};
+
+/// This type contains the peer cleanup settings to change. Use `None` (null
+/// in json) for the settings that do not change.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PeerCleanupForm {
+    /// Interval in seconds the cleanup job runs at. `0` stops the cleanup.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default)]
+    pub inactive_peer_cleanup_interval: Option<u64>,
+
+    /// Maximum time in seconds that a peer can be inactive before being
+    /// removed.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default)]
+    pub max_peer_timeout: Option<u32>,
+}
@@ -0,0 +1,46 @@
+//! API handlers for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{Json, Response};
+
+use super::forms::PeerCleanupForm;
+use super::resources::PeerCleanup;
+use super::responses::peer_cleanup_response;
+use crate::core::peer_cleanup::Settings;
+use crate::core::Tracker;
+use crate::servers::apis::v1::extractors;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get the peer cleanup settings.
+///
+/// It returns a `200` response with a json [`PeerCleanup`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::peer_cleanup#get-the-peer-cleanup-settings)
+/// for more information about this endpoint.
+pub async fn get_peer_cleanup_handler(State(tracker): State<Arc<Tracker>>) -> Json<PeerCleanup> {
+    peer_cleanup_response(tracker.get_peer_cleanup())
+}
+
+/// It handles the request to change the peer cleanup settings.
+///
+/// It returns a `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::peer_cleanup#change-the-peer-cleanup-settings)
+/// for more information about this endpoint.
+pub async fn set_peer_cleanup_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extractors::Json(form): extractors::Json<PeerCleanupForm>,
+) -> Response {
+    let current = tracker.get_peer_cleanup();
+
+    tracker.set_peer_cleanup(Settings {
+        inactive_peer_cleanup_interval: form
+            .inactive_peer_cleanup_interval
+            .unwrap_or(current.inactive_peer_cleanup_interval),
+        max_peer_timeout: form.max_peer_timeout.unwrap_or(current.max_peer_timeout),
+    });
+
+    ok_response()
+}
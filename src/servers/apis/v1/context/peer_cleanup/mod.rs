@@ -0,0 +1,69 @@
+//! Peer cleanup API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the [inactive peer cleanup settings](crate::core::peer_cleanup).
+//!
+//! The settings can be changed without restarting the tracker, for example,
+//! to keep the peers for longer while the tracker is under heavy load. The
+//! cleanup job picks up the new interval immediately.
+//!
+//! > **NOTICE**: the settings changed using the API are not persisted. The
+//! > tracker starts with the settings in the configuration after restarting.
+//!
+//! # Endpoints
+//!
+//! - [Get the peer cleanup settings](#get-the-peer-cleanup-settings)
+//! - [Change the peer cleanup settings](#change-the-peer-cleanup-settings)
+//!
+//! # Get the peer cleanup settings
+//!
+//! `GET /peer-cleanup`
+//!
+//! It returns the current settings of the inactive peer cleanup, in seconds.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/peer-cleanup?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "inactive_peer_cleanup_interval": 600,
+//!     "max_peer_timeout": 900
+//! }
+//! ```
+//!
+//! Refer to the API [`PeerCleanup`](crate::servers::apis::v1::context::peer_cleanup::resources::PeerCleanup)
+//! resource for more information about the response attributes.
+//!
+//! # Change the peer cleanup settings
+//!
+//! `POST /peer-cleanup`
+//!
+//! It changes the settings in the json body. The missing (or `null`) settings
+//! are not changed. An `inactive_peer_cleanup_interval` of `0` stops the
+//! cleanup until it's changed again.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/peer-cleanup?token=MyAccessToken" \
+//!      -H "Content-Type: application/json" \
+//!      -d '{"max_peer_timeout": 3600}'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod forms;
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
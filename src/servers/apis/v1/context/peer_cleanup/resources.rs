@@ -0,0 +1,45 @@
+//! API resources for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::peer_cleanup::Settings;
+
+/// The settings of the inactive peer cleanup.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PeerCleanup {
+    /// Interval in seconds the cleanup job runs at. `0` when the cleanup is
+    /// stopped.
+    pub inactive_peer_cleanup_interval: u64,
+    /// Maximum time in seconds that a peer can be inactive before being
+    /// removed.
+    pub max_peer_timeout: u32,
+}
+
+impl From<Settings> for PeerCleanup {
+    fn from(settings: Settings) -> Self {
+        Self {
+            inactive_peer_cleanup_interval: settings.inactive_peer_cleanup_interval,
+            max_peer_timeout: settings.max_peer_timeout,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PeerCleanup;
+    use crate::core::peer_cleanup::Settings;
+
+    #[test]
+    fn peer_cleanup_resource_should_be_converted_from_the_settings() {
+        assert_eq!(
+            PeerCleanup::from(Settings {
+                inactive_peer_cleanup_interval: 600,
+                max_peer_timeout: 900,
+            }),
+            PeerCleanup {
+                inactive_peer_cleanup_interval: 600,
+                max_peer_timeout: 900,
+            }
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! API responses for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup)
+//! API context.
+use axum::response::Json;
+
+use super::resources::PeerCleanup;
+use crate::core::peer_cleanup::Settings;
+
+/// `200` response that contains the [`PeerCleanup`] resource as json.
+pub fn peer_cleanup_response(settings: Settings) -> Json<PeerCleanup> {
+    Json(PeerCleanup::from(settings))
+}
@@ -0,0 +1,23 @@
+//! API routes for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup) API context.
+//!
+//! - `GET /peer-cleanup`
+//! - `POST /peer-cleanup`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::peer_cleanup).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::Router;
+
+use super::handlers::{get_peer_cleanup_handler, set_peer_cleanup_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`peer_cleanup`](crate::servers::apis::v1::context::peer_cleanup) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router.route(
+        &format!("{prefix}/peer-cleanup"),
+        get(get_peer_cleanup_handler)
+            .post(set_peer_cleanup_handler)
+            .with_state(tracker),
+    )
+}
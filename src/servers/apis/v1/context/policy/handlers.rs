@@ -0,0 +1,79 @@
+//! API handlers for the [`policy`](crate::servers::apis::v1::context::policy)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{Json, Response};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::resources::TorrentPolicy;
+use super::responses::{
+    failed_to_remove_torrent_policy_response, failed_to_set_torrent_policy_response, invalid_torrent_policy_param_response,
+    torrent_policies_response,
+};
+use crate::core::torrent::policy;
+use crate::core::Tracker;
+use crate::servers::apis::v1::extractors::Path;
+use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
+use crate::servers::apis::InfoHashParam;
+
+/// It handles the request to list the torrent policies.
+///
+/// It returns a `200` response with a json array of [`TorrentPolicy`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::policy#list-the-torrent-policies)
+/// for more information about this endpoint.
+pub async fn get_torrent_policies_handler(State(tracker): State<Arc<Tracker>>) -> Json<Vec<TorrentPolicy>> {
+    torrent_policies_response(tracker.get_torrent_policies().await)
+}
+
+/// It handles the request to set the policy for a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` if the infohash or the policy are not valid.
+/// - `5xx` with a json error if the policy couldn't be persisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::policy#set-the-policy-for-a-torrent)
+/// for more information about this endpoint.
+pub async fn set_torrent_policy_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((info_hash, policy)): Path<(String, String)>,
+) -> Response {
+    let Ok(info_hash) = InfoHash::from_str(&info_hash) else {
+        return invalid_info_hash_param_response(&info_hash);
+    };
+
+    let Ok(policy) = policy::TorrentPolicy::from_str(&policy) else {
+        return invalid_torrent_policy_param_response(&policy);
+    };
+
+    match tracker.set_torrent_policy(&info_hash, policy).await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_set_torrent_policy_response(e),
+    }
+}
+
+/// It handles the request to remove the policy for a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `5xx` with a json error if the policy couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::policy#remove-the-policy-for-a-torrent)
+/// for more information about this endpoint.
+pub async fn remove_torrent_policy_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match InfoHash::from_str(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => match tracker.remove_torrent_policy(&info_hash).await {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_torrent_policy_response(e),
+        },
+    }
+}
@@ -0,0 +1,97 @@
+//! Torrent policies API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the per-torrent announce policies. They are useful to retire torrents
+//! gracefully:
+//!
+//! Policy | Description
+//! ---|---
+//! `seed-only` | Only seeders can announce. `announce` requests from leechers are rejected.
+//! `frozen` | No new peers are accepted. Only peers already in the swarm can keep announcing.
+//!
+//! The policies are persisted in the database and loaded when the tracker
+//! starts.
+//!
+//! Refer to the [`TorrentPolicy`](crate::core::torrent::policy::TorrentPolicy)
+//! for more information.
+//!
+//! # Endpoints
+//!
+//! - [List the torrent policies](#list-the-torrent-policies)
+//! - [Set the policy for a torrent](#set-the-policy-for-a-torrent)
+//! - [Remove the policy for a torrent](#remove-the-policy-for-a-torrent)
+//!
+//! # List the torrent policies
+//!
+//! `GET /policies`
+//!
+//! It returns all the torrents with an announce policy.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/policies?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "info_hash": "090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f",
+//!         "policy": "seed-only"
+//!     }
+//! ]
+//! ```
+//!
+//! Refer to the API [`TorrentPolicy`](crate::servers::apis::v1::context::policy::resources::TorrentPolicy)
+//! resource for more information about the response attributes.
+//!
+//! # Set the policy for a torrent
+//!
+//! `POST /policy/:info_hash/:policy`
+//!
+//! It sets the policy for a torrent, replacing the previous one.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f`
+//! `policy` | string | `seed-only` or `frozen` | Yes | `seed-only`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/policy/090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f/seed-only?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove the policy for a torrent
+//!
+//! `DELETE /policy/:info_hash`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/policy/090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
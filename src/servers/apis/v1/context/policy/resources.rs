@@ -0,0 +1,47 @@
+//! API resources for the [`policy`](crate::servers::apis::v1::context::policy)
+//! API context.
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use crate::core::torrent::policy;
+
+/// A torrent with an announce policy.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct TorrentPolicy {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+    /// The policy: `seed-only` or `frozen`.
+    pub policy: policy::TorrentPolicy,
+}
+
+impl From<(InfoHash, policy::TorrentPolicy)> for TorrentPolicy {
+    fn from((info_hash, policy): (InfoHash, policy::TorrentPolicy)) -> Self {
+        Self {
+            info_hash: info_hash.to_hex_string(),
+            policy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::TorrentPolicy;
+    use crate::core::torrent::policy;
+
+    #[test]
+    fn torrent_policy_resource_should_be_serialized_to_json() {
+        let resource = TorrentPolicy::from((
+            InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+            policy::TorrentPolicy::SeedOnly,
+        ));
+
+        assert_eq!(
+            serde_json::to_string(&resource).unwrap(),
+            r#"{"info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d","policy":"seed-only"}"#
+        );
+    }
+}
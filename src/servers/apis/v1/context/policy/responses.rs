@@ -0,0 +1,36 @@
+//! API responses for the [`policy`](crate::servers::apis::v1::context::policy)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::resources::TorrentPolicy;
+use crate::core::torrent::policy;
+use crate::servers::apis::v1::responses::{failed_response, invalid_param_response};
+
+/// `200` response that contains an array of [`TorrentPolicy`] resources as json.
+pub fn torrent_policies_response(policies: Vec<(InfoHash, policy::TorrentPolicy)>) -> Json<Vec<TorrentPolicy>> {
+    Json(policies.into_iter().map(TorrentPolicy::from).collect())
+}
+
+/// `400` error response when the policy in the URL path is not valid.
+#[must_use]
+pub fn invalid_torrent_policy_param_response(policy: &str) -> Response {
+    invalid_param_response(
+        "policy",
+        &format!("Invalid URL: invalid torrent policy param: \"{policy}\", expected \"seed-only\" or \"frozen\""),
+    )
+}
+
+/// Error response when the policy for a torrent cannot be set.
+#[must_use]
+pub fn failed_to_set_torrent_policy_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to set torrent policy", &e)
+}
+
+/// Error response when the policy for a torrent cannot be removed.
+#[must_use]
+pub fn failed_to_remove_torrent_policy_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove torrent policy", &e)
+}
@@ -0,0 +1,31 @@
+//! API routes for the [`policy`](crate::servers::apis::v1::context::policy) API context.
+//!
+//! - `GET /policies`
+//! - `POST /policy/:info_hash/:policy`
+//! - `DELETE /policy/:info_hash`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::policy).
+use std::sync::Arc;
+
+use axum::routing::{delete, get, post};
+use axum::Router;
+
+use super::handlers::{get_torrent_policies_handler, remove_torrent_policy_handler, set_torrent_policy_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`policy`](crate::servers::apis::v1::context::policy) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router
+        .route(
+            &format!("{prefix}/policies"),
+            get(get_torrent_policies_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/policy/:info_hash/:policy"),
+            post(set_torrent_policy_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/policy/:info_hash"),
+            delete(remove_torrent_policy_handler).with_state(tracker),
+        )
+}
@@ -0,0 +1,84 @@
+//! API handlers for the [`static_peer`](crate::servers::apis::v1::context::static_peer)
+//! API context.
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{Json, Response};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::resources::StaticPeers;
+use super::responses::{
+    failed_to_add_static_peer_response, failed_to_remove_static_peer_response, invalid_peer_addr_param_response,
+    static_peers_response,
+};
+use crate::core::Tracker;
+use crate::servers::apis::v1::extractors::Path;
+use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
+
+/// It handles the request to list the static peers.
+///
+/// It returns a `200` response with a json array of [`StaticPeers`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::static_peer#list-the-static-peers)
+/// for more information about this endpoint.
+pub async fn get_static_peers_handler(State(tracker): State<Arc<Tracker>>) -> Json<Vec<StaticPeers>> {
+    static_peers_response(tracker.get_static_peers().await)
+}
+
+/// It handles the request to add a static peer to a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` if the infohash or the peer address are not valid.
+/// - `5xx` with a json error if the static peer couldn't be persisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::static_peer#add-a-static-peer-to-a-torrent)
+/// for more information about this endpoint.
+pub async fn add_static_peer_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((info_hash, peer_addr)): Path<(String, String)>,
+) -> Response {
+    let Ok(info_hash) = InfoHash::from_str(&info_hash) else {
+        return invalid_info_hash_param_response(&info_hash);
+    };
+
+    let Ok(peer_addr) = SocketAddr::from_str(&peer_addr) else {
+        return invalid_peer_addr_param_response(&peer_addr);
+    };
+
+    match tracker.add_static_peer(&info_hash, &peer_addr).await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_add_static_peer_response(e),
+    }
+}
+
+/// It handles the request to remove a static peer from a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` if the infohash or the peer address are not valid.
+/// - `5xx` with a json error if the static peer couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::static_peer#remove-a-static-peer-from-a-torrent)
+/// for more information about this endpoint.
+pub async fn remove_static_peer_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((info_hash, peer_addr)): Path<(String, String)>,
+) -> Response {
+    let Ok(info_hash) = InfoHash::from_str(&info_hash) else {
+        return invalid_info_hash_param_response(&info_hash);
+    };
+
+    let Ok(peer_addr) = SocketAddr::from_str(&peer_addr) else {
+        return invalid_peer_addr_param_response(&peer_addr);
+    };
+
+    match tracker.remove_static_peer(&info_hash, &peer_addr).await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_remove_static_peer_response(e),
+    }
+}
@@ -0,0 +1,97 @@
+//! Static peers API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the static seed peers. They are permanent peers, like a web seed or an
+//! origin server, that bootstrap new swarms:
+//!
+//! - They are always included in the `announce` responses, before the swarm
+//!   peers.
+//! - They are counted as seeders.
+//! - They never expire.
+//!
+//! The static peers are persisted in the database and loaded when the tracker
+//! starts.
+//!
+//! Refer to the [`static_peers`](crate::core::torrent::static_peers) module
+//! for more information.
+//!
+//! # Endpoints
+//!
+//! - [List the static peers](#list-the-static-peers)
+//! - [Add a static peer to a torrent](#add-a-static-peer-to-a-torrent)
+//! - [Remove a static peer from a torrent](#remove-a-static-peer-from-a-torrent)
+//!
+//! # List the static peers
+//!
+//! `GET /static-peers`
+//!
+//! It returns all the torrents with static peers.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/static-peers?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "info_hash": "090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f",
+//!         "peers": ["126.0.0.1:8080"]
+//!     }
+//! ]
+//! ```
+//!
+//! Refer to the API [`StaticPeers`](crate::servers::apis::v1::context::static_peer::resources::StaticPeers)
+//! resource for more information about the response attributes.
+//!
+//! # Add a static peer to a torrent
+//!
+//! `POST /static-peer/:info_hash/:peer_addr`
+//!
+//! Adding a peer that was already added does nothing.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f`
+//! `peer_addr` | string | The IP and port of the peer. IPv6 addresses go in brackets | Yes | `126.0.0.1:8080`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/static-peer/090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f/126.0.0.1:8080?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a static peer from a torrent
+//!
+//! `DELETE /static-peer/:info_hash/:peer_addr`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/static-peer/090c6d4fb3a03191c4ef1fbb7e3c5d4d5c4a7b2f/126.0.0.1:8080?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
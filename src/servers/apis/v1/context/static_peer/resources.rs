@@ -0,0 +1,46 @@
+//! API resources for the [`static_peer`](crate::servers::apis::v1::context::static_peer)
+//! API context.
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+/// A torrent with static peers.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct StaticPeers {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+    /// The IP and port of the static peers.
+    pub peers: Vec<String>,
+}
+
+impl From<(InfoHash, Vec<SocketAddr>)> for StaticPeers {
+    fn from((info_hash, peer_addrs): (InfoHash, Vec<SocketAddr>)) -> Self {
+        Self {
+            info_hash: info_hash.to_hex_string(),
+            peers: peer_addrs.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::StaticPeers;
+
+    #[test]
+    fn static_peers_resource_should_be_serialized_to_json() {
+        let resource = StaticPeers::from((
+            InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+            vec!["126.0.0.1:8080".parse().unwrap()],
+        ));
+
+        assert_eq!(
+            serde_json::to_string(&resource).unwrap(),
+            r#"{"info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d","peers":["126.0.0.1:8080"]}"#
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! API responses for the [`static_peer`](crate::servers::apis::v1::context::static_peer)
+//! API context.
+use std::error::Error;
+use std::net::SocketAddr;
+
+use axum::response::{Json, Response};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+use super::resources::StaticPeers;
+use crate::servers::apis::v1::responses::{failed_response, invalid_param_response};
+
+/// `200` response that contains an array of [`StaticPeers`] resources as json.
+pub fn static_peers_response(static_peers: Vec<(InfoHash, Vec<SocketAddr>)>) -> Json<Vec<StaticPeers>> {
+    Json(static_peers.into_iter().map(StaticPeers::from).collect())
+}
+
+/// `400` error response when the peer address in the URL path is not valid.
+#[must_use]
+pub fn invalid_peer_addr_param_response(peer_addr: &str) -> Response {
+    invalid_param_response(
+        "peer_addr",
+        &format!("Invalid URL: invalid peer address param: \"{peer_addr}\", expected an IP and a port"),
+    )
+}
+
+/// Error response when the static peer cannot be added.
+#[must_use]
+pub fn failed_to_add_static_peer_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to add static peer", &e)
+}
+
+/// Error response when the static peer cannot be removed.
+#[must_use]
+pub fn failed_to_remove_static_peer_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove static peer", &e)
+}
@@ -0,0 +1,29 @@
+//! API routes for the [`static_peer`](crate::servers::apis::v1::context::static_peer) API context.
+//!
+//! - `GET /static-peers`
+//! - `POST /static-peer/:info_hash/:peer_addr`
+//! - `DELETE /static-peer/:info_hash/:peer_addr`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::static_peer).
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use axum::Router;
+
+use super::handlers::{add_static_peer_handler, get_static_peers_handler, remove_static_peer_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`static_peer`](crate::servers::apis::v1::context::static_peer) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router
+        .route(
+            &format!("{prefix}/static-peers"),
+            get(get_static_peers_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/static-peer/:info_hash/:peer_addr"),
+            post(add_static_peer_handler)
+                .delete(remove_static_peer_handler)
+                .with_state(tracker),
+        )
+}
@@ -3,19 +3,171 @@
 use std::sync::Arc;
 
 use axum::extract::State;
-use axum::response::Json;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 
-use super::resources::Stats;
-use super::responses::stats_response;
+use super::responses::{
+    announce_journal_not_enabled_response, announce_journal_stats_response, async_announce_not_enabled_response,
+    async_announce_stats_response, consistency_check_not_enabled_response, consistency_stats_response,
+    database_authorization_not_enabled_response, database_authorization_stats_response, flat_stats_response,
+    health_scores_response, prometheus_stats_response, runtimes_stats_response, stats_response, swarm_sizes_not_enabled_response,
+    swarm_sizes_response,
+};
+use crate::bootstrap::runtimes;
 use crate::core::services::statistics::get_metrics;
 use crate::core::Tracker;
+use crate::servers::apis::v1::extractors::Query;
+use crate::servers::apis::v1::fields::{fields_param, Fields};
+
+/// The format of the tracker statistics response.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A json [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
+    /// object.
+    #[default]
+    Json,
+    /// Plain text `key value` lines, one metric per line.
+    Flat,
+    /// The Prometheus text exposition format.
+    Prometheus,
+}
+
+/// Query parameters for the tracker statistics endpoint.
+#[derive(Deserialize, Debug, Default)]
+pub struct QueryParams {
+    /// The format of the response. It defaults to [`Format::Json`].
+    #[serde(default)]
+    pub format: Format,
+    /// The selected [`Fields`]. All the metrics are included when it's
+    /// missing.
+    #[serde(default, deserialize_with = "fields_param")]
+    pub fields: Option<Fields>,
+}
 
 /// It handles the request to get the tracker statistics.
 ///
-/// It returns a `200` response with a json [`Stats`]
+/// It returns a `200` response with the [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
+/// in the requested [`Format`], with only the selected [`Fields`] if any.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-tracker-statistics)
 /// for more information about this endpoint.
-pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>) -> Json<Stats> {
-    stats_response(get_metrics(tracker.clone()).await)
+pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>, Query(params): Query<QueryParams>) -> Response {
+    let metrics = get_metrics(tracker.clone()).await;
+
+    match params.format {
+        Format::Json => stats_response(metrics, params.fields.as_ref()),
+        Format::Flat => flat_stats_response(metrics, params.fields.as_ref()),
+        Format::Prometheus => prometheus_stats_response(metrics, params.fields.as_ref()),
+    }
+}
+
+/// It handles the request to get the scheduler metrics of the tokio runtimes.
+///
+/// It returns a `200` response with a list of [`RuntimeStats`](crate::servers::apis::v1::context::stats::resources::RuntimeStats).
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-runtimes-statistics)
+/// for more information about this endpoint.
+pub async fn get_runtimes_stats_handler() -> Response {
+    runtimes_stats_response(runtimes::metrics()).into_response()
+}
+
+/// It handles the request to get the distribution of the swarm sizes.
+///
+/// It returns:
+///
+/// - `200` response with a list of [`SwarmSizeBucket`](crate::servers::apis::v1::context::stats::resources::SwarmSizeBucket).
+/// - `409` if the read snapshot is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-swarm-sizes)
+/// for more information about this endpoint.
+pub async fn get_swarm_sizes_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_swarm_sizes() {
+        Some(swarm_sizes) => swarm_sizes_response(&swarm_sizes).into_response(),
+        None => swarm_sizes_not_enabled_response(),
+    }
+}
+
+/// It handles the request to get the distribution of the health scores of
+/// the torrents.
+///
+/// It returns:
+///
+/// - `200` response with a list of [`HealthScoreBucket`](crate::servers::apis::v1::context::stats::resources::HealthScoreBucket).
+/// - `409` if the read snapshot is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-health-scores)
+/// for more information about this endpoint.
+pub async fn get_health_scores_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_health_scores() {
+        Some(health_scores) => health_scores_response(&health_scores).into_response(),
+        None => swarm_sizes_not_enabled_response(),
+    }
+}
+
+/// It handles the request to get the state of the asynchronous `announce`
+/// queue.
+///
+/// It returns:
+///
+/// - `200` response with the [`AsyncAnnounceStats`](crate::servers::apis::v1::context::stats::resources::AsyncAnnounceStats).
+/// - `409` if the asynchronous `announce` queue is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-asynchronous-announce-statistics)
+/// for more information about this endpoint.
+pub async fn get_async_announce_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_async_announce_metrics() {
+        Some(metrics) => async_announce_stats_response(metrics).into_response(),
+        None => async_announce_not_enabled_response(),
+    }
+}
+
+/// It handles the request to get the counters of the `announce` journal.
+///
+/// It returns:
+///
+/// - `200` response with the [`AnnounceJournalStats`](crate::servers::apis::v1::context::stats::resources::AnnounceJournalStats).
+/// - `409` if the announce journal is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-announce-journal-statistics)
+/// for more information about this endpoint.
+pub async fn get_announce_journal_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_announce_journal_metrics() {
+        Some(metrics) => announce_journal_stats_response(metrics).into_response(),
+        None => announce_journal_not_enabled_response(),
+    }
+}
+
+/// It handles the request to get the last consistency check of the torrents
+/// repository.
+///
+/// It returns:
+///
+/// - `200` response with the [`ConsistencyStats`](crate::servers::apis::v1::context::stats::resources::ConsistencyStats).
+/// - `409` if the consistency check is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-consistency-check)
+/// for more information about this endpoint.
+pub async fn get_consistency_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_consistency_check_metrics() {
+        Some(metrics) => consistency_stats_response(metrics, tracker.get_last_consistency_report().as_deref()).into_response(),
+        None => consistency_check_not_enabled_response(),
+    }
+}
+
+/// It handles the request to get the state of the lookups in the database
+/// whitelist.
+///
+/// It returns:
+///
+/// - `200` response with the [`DatabaseAuthorizationStats`](crate::servers::apis::v1::context::stats::resources::DatabaseAuthorizationStats).
+/// - `409` if the database authorization is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-database-authorization-statistics)
+/// for more information about this endpoint.
+pub async fn get_database_authorization_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.get_database_authorization_metrics() {
+        Some(metrics) => database_authorization_stats_response(metrics).into_response(),
+        None => database_authorization_not_enabled_response(),
+    }
 }
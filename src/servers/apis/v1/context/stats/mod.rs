@@ -6,6 +6,13 @@
 //! # Endpoints
 //!
 //! - [Get tracker statistics](#get-tracker-statistics)
+//! - [Get runtimes statistics](#get-runtimes-statistics)
+//! - [Get swarm sizes](#get-swarm-sizes)
+//! - [Get health scores](#get-health-scores)
+//! - [Get asynchronous announce statistics](#get-asynchronous-announce-statistics)
+//! - [Get announce journal statistics](#get-announce-journal-statistics)
+//! - [Get consistency check](#get-consistency-check)
+//! - [Get database authorization statistics](#get-database-authorization-statistics)
 //!
 //! # Get tracker statistics
 //!
@@ -30,15 +37,48 @@
 //!     "tcp4_connections_handled": 0,
 //!     "tcp4_announces_handled": 0,
 //!     "tcp4_scrapes_handled": 0,
+//!     "tcp4_scrapes_exceeding_limit": 0,
 //!     "tcp6_connections_handled": 0,
 //!     "tcp6_announces_handled": 0,
 //!     "tcp6_scrapes_handled": 0,
+//!     "tcp6_scrapes_exceeding_limit": 0,
+//!     "tcp_announce_responses_under_128_bytes": 0,
+//!     "tcp_announce_responses_under_512_bytes": 0,
+//!     "tcp_announce_responses_under_2048_bytes": 0,
+//!     "tcp_announce_responses_over_2048_bytes": 0,
+//!     "tcp_announce_responses_padded": 0,
+//!     "tcp_announces_with_unknown_event": 0,
+//!     "tcp_scrape_duplicate_info_hashes": 0,
+//!     "tcp_requests_with_uri_too_long": 0,
+//!     "tcp_requests_with_headers_too_large": 0,
+//!     "tcp_announces_with_deadline_exceeded": 0,
+//!     "tcp_scrapes_with_deadline_exceeded": 0,
+//!     "tcp_announces_with_key_bound_to_another_address": 0,
 //!     "udp4_connections_handled": 0,
 //!     "udp4_announces_handled": 0,
 //!     "udp4_scrapes_handled": 0,
+//!     "udp4_scrapes_exceeding_limit": 0,
+//!     "udp4_connection_ids_used": 0,
+//!     "udp4_connection_ids_unused": 0,
+//!     "udp4_announces_with_expired_connection_id": 0,
+//!     "udp4_connect_to_announce_conversion_rate": 0.0,
+//!     "udp4_expired_connection_id_ratio": 0.0,
 //!     "udp6_connections_handled": 0,
 //!     "udp6_announces_handled": 0,
-//!     "udp6_scrapes_handled": 0
+//!     "udp6_scrapes_handled": 0,
+//!     "udp6_scrapes_exceeding_limit": 0,
+//!     "udp6_connection_ids_used": 0,
+//!     "udp6_connection_ids_unused": 0,
+//!     "udp6_announces_with_expired_connection_id": 0,
+//!     "udp6_connect_to_announce_conversion_rate": 0.0,
+//!     "udp6_expired_connection_id_ratio": 0.0,
+//!     "udp_announces_with_unknown_event": 0,
+//!     "udp_scrape_duplicate_info_hashes": 0,
+//!     "udp_negative_cache_hits": 0,
+//!     "udp_announces_with_deadline_exceeded": 0,
+//!     "udp_scrapes_with_deadline_exceeded": 0,
+//!     "torrents_created": 0,
+//!     "torrents_removed": 0
 //!   }
 //! ```
 //!
@@ -46,6 +86,345 @@
 //!
 //! Refer to the API [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
 //! resource for more information about the response attributes.
+//!
+//! **Other formats**
+//!
+//! Monitoring agents that can't parse json (Munin, Zabbix, etcetera) can
+//! request the statistics in other formats with the `format` query parameter:
+//!
+//! Parameter | Format
+//! ---|---
+//! `format=json` | The json object above (default).
+//! `format=flat` | Plain text `key value` lines, one metric per line.
+//! `format=prometheus` | The Prometheus text exposition format. Metrics are prefixed with `torrust_tracker_` and counters end in `_total`.
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&format=flat"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! torrents 0
+//! seeders 0
+//! completed 0
+//! leechers 0
+//! tcp4_connections_handled 0
+//! ...
+//! udp6_expired_connection_id_ratio 0.0
+//! udp_announces_with_unknown_event 0
+//! udp_scrape_duplicate_info_hashes 0
+//! udp_negative_cache_hits 0
+//! udp_announces_with_deadline_exceeded 0
+//! udp_scrapes_with_deadline_exceeded 0
+//! torrents_created 0
+//! torrents_removed 0
+//! ```
+//!
+//! **Selected metrics**
+//!
+//! Clients polling the statistics often can request only the metrics they
+//! need, in any format, with the comma-separated `fields` query parameter.
+//! See the [`fields`](crate::servers::apis::v1::fields) module.
+//!
+//! ```bash
+//! curl --compressed "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&fields=torrents,seeders,leechers"
+//! ```
+//!
+//! # Get runtimes statistics
+//!
+//! `GET /stats/runtimes`
+//!
+//! Returns the scheduler metrics of the main tokio runtime and the dedicated
+//! runtimes of the UDP trackers, if any.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/runtimes?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "name": "main",
+//!         "workers": 4,
+//!         "alive_tasks": 12
+//!     },
+//!     {
+//!         "name": "udp-0.0.0.0:6969",
+//!         "workers": 2,
+//!         "alive_tasks": 3
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`RuntimeStats`](crate::servers::apis::v1::context::stats::resources::RuntimeStats)
+//! resource for more information about the response attributes.
+//!
+//! # Get swarm sizes
+//!
+//! `GET /stats/swarm-sizes`
+//!
+//! Returns the number of torrents by swarm size (seeders plus leechers). The
+//! histogram is computed by the read snapshot job, so it's only available when
+//! the read snapshot is enabled, and it's as stale as the last snapshot.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/swarm-sizes?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     { "min": 0, "max": 0, "torrents": 120 },
+//!     { "min": 1, "max": 10, "torrents": 5421 },
+//!     { "min": 11, "max": 100, "torrents": 803 },
+//!     { "min": 101, "max": 1000, "torrents": 37 },
+//!     { "min": 1001, "max": 10000, "torrents": 2 },
+//!     { "min": 10001, "max": null, "torrents": 0 }
+//! ]
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the read snapshot is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`SwarmSizeBucket`](crate::servers::apis::v1::context::stats::resources::SwarmSizeBucket)
+//! resource for more information about the response attributes.
+//!
+//! # Get health scores
+//!
+//! `GET /stats/health-scores`
+//!
+//! Returns the number of torrents by health score, from `0` (dead) to `100`
+//! (healthy). Like the swarm sizes, the scores are computed by the read
+//! snapshot job, so they are only available when the read snapshot is
+//! enabled. Refer to the [`snapshot`](crate::core::torrent::snapshot) module
+//! for more information about how the score is computed.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/health-scores?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     { "min": 0, "max": 19, "torrents": 2310 },
+//!     { "min": 20, "max": 39, "torrents": 412 },
+//!     { "min": 40, "max": 59, "torrents": 1033 },
+//!     { "min": 60, "max": 79, "torrents": 897 },
+//!     { "min": 80, "max": 100, "torrents": 1731 }
+//! ]
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the read snapshot is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`HealthScoreBucket`](crate::servers::apis::v1::context::stats::resources::HealthScoreBucket)
+//! resource for more information about the response attributes.
+//!
+//! # Get asynchronous announce statistics
+//!
+//! `GET /stats/async-announce`
+//!
+//! Returns the state of the queue of the peer updates of the asynchronous
+//! `announce` requests. The staleness is the time the last applied update
+//! waited in the queue, that is, how long the swarms lagged behind the
+//! announces.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/async-announce?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "queue_depth": 12,
+//!     "queue_capacity": 10000,
+//!     "staleness_ms": 3,
+//!     "overflows": 0
+//! }
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the asynchronous announce queue is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`AsyncAnnounceStats`](crate::servers::apis::v1::context::stats::resources::AsyncAnnounceStats)
+//! resource for more information about the response attributes.
+//!
+//! # Get announce journal statistics
+//!
+//! `GET /stats/announce-journal`
+//!
+//! Returns the counters of the journal of the `announce` requests: the
+//! records written since the tracker started, and the result of replaying the
+//! journal on startup.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/announce-journal?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "capacity": 645277,
+//!     "appended": 10432,
+//!     "failed": 0,
+//!     "replayed": 52110,
+//!     "expired": 3021,
+//!     "corrupted": 1,
+//!     "replay_duration_ms": 84
+//! }
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the announce journal is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`AnnounceJournalStats`](crate::servers::apis::v1::context::stats::resources::AnnounceJournalStats)
+//! resource for more information about the response attributes.
+//!
+//! # Get consistency check
+//!
+//! `GET /stats/consistency`
+//!
+//! Returns the counters of the consistency checks of the torrents repository
+//! and the report of the last one. It's a debugging tool: the checks verify
+//! the internal invariants of the repository, like the aggregate metrics
+//! matching the ones recomputed from every torrent. The `last_report` is
+//! `null` until the first check runs.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/consistency?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "checks": 12,
+//!     "inconsistencies": 1,
+//!     "last_report": {
+//!         "checked_at": "2024-10-16T09:00:00Z",
+//!         "torrents": 3,
+//!         "inconsistencies": [
+//!             {
+//!                 "kind": "stale_peers",
+//!                 "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+//!                 "message": "the torrent has 1 peers that should have been removed by the cleanup"
+//!             }
+//!         ]
+//!     }
+//! }
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the consistency check is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`ConsistencyStats`](crate::servers::apis::v1::context::stats::resources::ConsistencyStats)
+//! resource for more information about the response attributes.
+//!
+//! # Get database authorization statistics
+//!
+//! `GET /stats/database-authorization`
+//!
+//! Returns the state of the lookups in the database whitelist. Listed
+//! trackers with the database authorization enabled look up the torrents
+//! that are not in the in-memory whitelist in the database. The
+//! `coalescing_hit_rate` is the ratio of lookups that reused the query of a
+//! concurrent lookup of the same torrent, and the `overflows` are the
+//! lookups rejected because the queue was full.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats/database-authorization?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "queue_depth": 3,
+//!     "queue_capacity": 1024,
+//!     "lookups": 200,
+//!     "coalesced": 50,
+//!     "queries": 147,
+//!     "overflows": 0,
+//!     "coalescing_hit_rate": 0.25
+//! }
+//! ```
+//!
+//! **Not enabled response** `409`
+//!
+//! ```json
+//! {
+//!     "code": "not_enabled",
+//!     "message": "the database authorization is not enabled"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`DatabaseAuthorizationStats`](crate::servers::apis::v1::context::stats::resources::DatabaseAuthorizationStats)
+//! resource for more information about the response attributes.
 pub mod handlers;
 pub mod resources;
 pub mod responses;
@@ -1,11 +1,17 @@
 //! API resources for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
+use chrono::SecondsFormat;
 use serde::{Deserialize, Serialize};
+use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
 
+use crate::bootstrap::runtimes::Metrics;
 use crate::core::services::statistics::TrackerMetrics;
+use crate::core::torrent::consistency;
+use crate::core::torrent::snapshot::Bucket;
+use crate::core::{announce_journal, async_announce, database_authorization};
 
 /// It contains all the statistics generated by the tracker.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Stats {
     // Torrent metrics
     /// Total number of torrents.
@@ -26,24 +32,127 @@ pub struct Stats {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub tcp4_scrapes_exceeding_limit: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub tcp6_scrapes_exceeding_limit: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses smaller than
+    /// 128 bytes, before padding.
+    pub tcp_announce_responses_under_128_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses from 128 to
+    /// 511 bytes, before padding.
+    pub tcp_announce_responses_under_512_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses from 512 to
+    /// 2047 bytes, before padding.
+    pub tcp_announce_responses_under_2048_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses of 2048 bytes
+    /// or more.
+    pub tcp_announce_responses_over_2048_bytes: u64,
+    /// Total number of TCP (HTTP tracker) `announce` responses that were
+    /// padded to the minimum size.
+    pub tcp_announce_responses_padded: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests with an unknown
+    /// `event` value. They are handled as regular announces.
+    pub tcp_announces_with_unknown_event: u64,
+    /// Total number of duplicate infohashes in TCP (HTTP tracker) `scrape`
+    /// requests. Each torrent is only looked up once per request.
+    pub tcp_scrape_duplicate_info_hashes: u64,
+    /// Total number of TCP (HTTP tracker) requests rejected because their
+    /// URI was too long.
+    pub tcp_requests_with_uri_too_long: u64,
+    /// Total number of TCP (HTTP tracker) requests rejected because their
+    /// headers were too large.
+    pub tcp_requests_with_headers_too_large: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests that exceeded
+    /// the request deadline.
+    pub tcp_announces_with_deadline_exceeded: u64,
+    /// Total number of TCP (HTTP tracker) `scrape` requests that exceeded
+    /// the request deadline.
+    pub tcp_scrapes_with_deadline_exceeded: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests rejected
+    /// because their key is bound to another address.
+    pub tcp_announces_with_key_bound_to_another_address: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
     pub udp4_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers.
     pub udp4_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub udp4_scrapes_exceeding_limit: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv4 peers
+    /// that were used in at least one `announce` request.
+    pub udp4_connection_ids_used: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv4 peers
+    /// that expired without being used in any `announce` request.
+    pub udp4_connection_ids_unused: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers
+    /// rejected because their connection ID had expired.
+    pub udp4_announces_with_expired_connection_id: u64,
+    /// Ratio of the used connection IDs to the used and unused connection IDs
+    /// issued to IPv4 peers. It's `0` when there are no connection IDs.
+    pub udp4_connect_to_announce_conversion_rate: f64,
+    /// Ratio of the UDP (UDP tracker) `announce` requests from IPv4 peers
+    /// that arrived with an expired connection ID. It's `0` when there are no
+    /// `announce` requests.
+    pub udp4_expired_connection_id_ratio: f64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
     pub udp6_announces_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers.
     pub udp6_scrapes_handled: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers
+    /// rejected because they exceeded the maximum number of torrents.
+    pub udp6_scrapes_exceeding_limit: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv6 peers
+    /// that were used in at least one `announce` request.
+    pub udp6_connection_ids_used: u64,
+    /// Total number of UDP (UDP tracker) connection IDs issued to IPv6 peers
+    /// that expired without being used in any `announce` request.
+    pub udp6_connection_ids_unused: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers
+    /// rejected because their connection ID had expired.
+    pub udp6_announces_with_expired_connection_id: u64,
+    /// Ratio of the used connection IDs to the used and unused connection IDs
+    /// issued to IPv6 peers. It's `0` when there are no connection IDs.
+    pub udp6_connect_to_announce_conversion_rate: f64,
+    /// Ratio of the UDP (UDP tracker) `announce` requests from IPv6 peers
+    /// that arrived with an expired connection ID. It's `0` when there are no
+    /// `announce` requests.
+    pub udp6_expired_connection_id_ratio: f64,
+    /// Total number of UDP (UDP tracker) `announce` requests with an unknown
+    /// `event` value. They are handled as regular announces.
+    pub udp_announces_with_unknown_event: u64,
+    /// Total number of duplicate infohashes in UDP (UDP tracker) `scrape`
+    /// requests. Each torrent is only looked up once per request.
+    pub udp_scrape_duplicate_info_hashes: u64,
+    /// Total number of infohashes in UDP (UDP tracker) `announce` and
+    /// `scrape` requests rejected from the negative cache, without looking
+    /// them up again.
+    pub udp_negative_cache_hits: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests that exceeded
+    /// the request deadline.
+    pub udp_announces_with_deadline_exceeded: u64,
+    /// Total number of UDP (UDP tracker) `scrape` requests that exceeded
+    /// the request deadline.
+    pub udp_scrapes_with_deadline_exceeded: u64,
+
+    // Torrents repository metrics
+    /// Total number of torrent entries added to the torrents repository.
+    pub torrents_created: u64,
+    /// Total number of torrent entries removed from the torrents repository,
+    /// by the cleanup or because they were removed from the whitelist.
+    pub torrents_removed: u64,
 }
 
 impl From<TrackerMetrics> for Stats {
@@ -56,28 +165,312 @@ impl From<TrackerMetrics> for Stats {
             tcp4_connections_handled: metrics.protocol_metrics.tcp4_connections_handled,
             tcp4_announces_handled: metrics.protocol_metrics.tcp4_announces_handled,
             tcp4_scrapes_handled: metrics.protocol_metrics.tcp4_scrapes_handled,
+            tcp4_scrapes_exceeding_limit: metrics.protocol_metrics.tcp4_scrapes_exceeding_limit,
             tcp6_connections_handled: metrics.protocol_metrics.tcp6_connections_handled,
             tcp6_announces_handled: metrics.protocol_metrics.tcp6_announces_handled,
             tcp6_scrapes_handled: metrics.protocol_metrics.tcp6_scrapes_handled,
+            tcp6_scrapes_exceeding_limit: metrics.protocol_metrics.tcp6_scrapes_exceeding_limit,
+            tcp_announce_responses_under_128_bytes: metrics.protocol_metrics.tcp_announce_responses_under_128_bytes,
+            tcp_announce_responses_under_512_bytes: metrics.protocol_metrics.tcp_announce_responses_under_512_bytes,
+            tcp_announce_responses_under_2048_bytes: metrics.protocol_metrics.tcp_announce_responses_under_2048_bytes,
+            tcp_announce_responses_over_2048_bytes: metrics.protocol_metrics.tcp_announce_responses_over_2048_bytes,
+            tcp_announce_responses_padded: metrics.protocol_metrics.tcp_announce_responses_padded,
+            tcp_announces_with_unknown_event: metrics.protocol_metrics.tcp_announces_with_unknown_event,
+            tcp_scrape_duplicate_info_hashes: metrics.protocol_metrics.tcp_scrape_duplicate_info_hashes,
+            tcp_requests_with_uri_too_long: metrics.protocol_metrics.tcp_requests_with_uri_too_long,
+            tcp_requests_with_headers_too_large: metrics.protocol_metrics.tcp_requests_with_headers_too_large,
+            tcp_announces_with_deadline_exceeded: metrics.protocol_metrics.tcp_announces_with_deadline_exceeded,
+            tcp_scrapes_with_deadline_exceeded: metrics.protocol_metrics.tcp_scrapes_with_deadline_exceeded,
+            tcp_announces_with_key_bound_to_another_address: metrics
+                .protocol_metrics
+                .tcp_announces_with_key_bound_to_another_address,
             udp4_connections_handled: metrics.protocol_metrics.udp4_connections_handled,
             udp4_announces_handled: metrics.protocol_metrics.udp4_announces_handled,
             udp4_scrapes_handled: metrics.protocol_metrics.udp4_scrapes_handled,
+            udp4_scrapes_exceeding_limit: metrics.protocol_metrics.udp4_scrapes_exceeding_limit,
+            udp4_connection_ids_used: metrics.protocol_metrics.udp4_connection_ids_used,
+            udp4_connection_ids_unused: metrics.protocol_metrics.udp4_connection_ids_unused,
+            udp4_announces_with_expired_connection_id: metrics.protocol_metrics.udp4_announces_with_expired_connection_id,
+            udp4_connect_to_announce_conversion_rate: ratio(
+                metrics.protocol_metrics.udp4_connection_ids_used,
+                metrics.protocol_metrics.udp4_connection_ids_used + metrics.protocol_metrics.udp4_connection_ids_unused,
+            ),
+            udp4_expired_connection_id_ratio: ratio(
+                metrics.protocol_metrics.udp4_announces_with_expired_connection_id,
+                metrics.protocol_metrics.udp4_announces_handled
+                    + metrics.protocol_metrics.udp4_announces_with_expired_connection_id,
+            ),
             udp6_connections_handled: metrics.protocol_metrics.udp6_connections_handled,
             udp6_announces_handled: metrics.protocol_metrics.udp6_announces_handled,
             udp6_scrapes_handled: metrics.protocol_metrics.udp6_scrapes_handled,
+            udp6_scrapes_exceeding_limit: metrics.protocol_metrics.udp6_scrapes_exceeding_limit,
+            udp6_connection_ids_used: metrics.protocol_metrics.udp6_connection_ids_used,
+            udp6_connection_ids_unused: metrics.protocol_metrics.udp6_connection_ids_unused,
+            udp6_announces_with_expired_connection_id: metrics.protocol_metrics.udp6_announces_with_expired_connection_id,
+            udp6_connect_to_announce_conversion_rate: ratio(
+                metrics.protocol_metrics.udp6_connection_ids_used,
+                metrics.protocol_metrics.udp6_connection_ids_used + metrics.protocol_metrics.udp6_connection_ids_unused,
+            ),
+            udp6_expired_connection_id_ratio: ratio(
+                metrics.protocol_metrics.udp6_announces_with_expired_connection_id,
+                metrics.protocol_metrics.udp6_announces_handled
+                    + metrics.protocol_metrics.udp6_announces_with_expired_connection_id,
+            ),
+            udp_announces_with_unknown_event: metrics.protocol_metrics.udp_announces_with_unknown_event,
+            udp_scrape_duplicate_info_hashes: metrics.protocol_metrics.udp_scrape_duplicate_info_hashes,
+            udp_negative_cache_hits: metrics.protocol_metrics.udp_negative_cache_hits,
+            udp_announces_with_deadline_exceeded: metrics.protocol_metrics.udp_announces_with_deadline_exceeded,
+            udp_scrapes_with_deadline_exceeded: metrics.protocol_metrics.udp_scrapes_with_deadline_exceeded,
+            torrents_created: metrics.protocol_metrics.torrents_created,
+            torrents_removed: metrics.protocol_metrics.torrents_removed,
+        }
+    }
+}
+
+/// Scheduler metrics of a tokio runtime.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// Name of the runtime. The main runtime is called `main`.
+    pub name: String,
+    /// Number of worker threads.
+    pub workers: usize,
+    /// Number of tasks currently alive.
+    pub alive_tasks: usize,
+}
+
+impl From<Metrics> for RuntimeStats {
+    fn from(metrics: Metrics) -> Self {
+        Self {
+            name: metrics.name,
+            workers: metrics.workers,
+            alive_tasks: metrics.alive_tasks,
+        }
+    }
+}
+
+/// A bucket of the swarm sizes histogram.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SwarmSizeBucket {
+    /// Minimum number of peers, inclusive.
+    pub min: u32,
+    /// Maximum number of peers, inclusive. `null` for the last bucket.
+    pub max: Option<u32>,
+    /// Number of torrents whose swarm size is in the bucket.
+    pub torrents: u64,
+}
+
+impl From<Bucket> for SwarmSizeBucket {
+    fn from(bucket: Bucket) -> Self {
+        Self {
+            min: bucket.min,
+            max: bucket.max,
+            torrents: bucket.torrents,
+        }
+    }
+}
+
+/// A bucket of the health scores histogram.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct HealthScoreBucket {
+    /// Minimum health score, inclusive.
+    pub min: u32,
+    /// Maximum health score, inclusive.
+    pub max: u32,
+    /// Number of torrents whose health score is in the bucket.
+    pub torrents: u64,
+}
+
+impl From<Bucket> for HealthScoreBucket {
+    fn from(bucket: Bucket) -> Self {
+        Self {
+            min: bucket.min,
+            max: bucket.max.unwrap_or(100),
+            torrents: bucket.torrents,
+        }
+    }
+}
+
+/// State of the queue of the asynchronous `announce` updates.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AsyncAnnounceStats {
+    /// Number of pending updates.
+    pub queue_depth: usize,
+    /// Maximum number of pending updates.
+    pub queue_capacity: usize,
+    /// Time in milliseconds the last applied update waited in the queue.
+    pub staleness_ms: u64,
+    /// Number of updates applied synchronously because the queue was full.
+    pub overflows: u64,
+}
+
+impl From<async_announce::Metrics> for AsyncAnnounceStats {
+    fn from(metrics: async_announce::Metrics) -> Self {
+        Self {
+            queue_depth: metrics.queue_depth,
+            queue_capacity: metrics.queue_capacity,
+            staleness_ms: metrics.staleness_ms,
+            overflows: metrics.overflows,
+        }
+    }
+}
+
+/// Counters of the journal of the `announce` requests.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AnnounceJournalStats {
+    /// Maximum number of records in the ring file.
+    pub capacity: u64,
+    /// Number of records written since the tracker started.
+    pub appended: u64,
+    /// Number of records that could not be written.
+    pub failed: u64,
+    /// Number of records replayed on startup.
+    pub replayed: u64,
+    /// Number of records not replayed because they were older than the
+    /// replay window.
+    pub expired: u64,
+    /// Number of records not replayed because they were corrupted.
+    pub corrupted: u64,
+    /// Time in milliseconds spent replaying the records on startup.
+    pub replay_duration_ms: u64,
+}
+
+impl From<announce_journal::Metrics> for AnnounceJournalStats {
+    fn from(metrics: announce_journal::Metrics) -> Self {
+        Self {
+            capacity: metrics.capacity,
+            appended: metrics.appended,
+            failed: metrics.failed,
+            replayed: metrics.replay.replayed,
+            expired: metrics.replay.expired,
+            corrupted: metrics.replay.corrupted,
+            replay_duration_ms: metrics.replay.duration_ms,
         }
     }
 }
 
+/// Counters and last report of the consistency check of the torrents
+/// repository.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ConsistencyStats {
+    /// Number of checks run since the tracker started.
+    pub checks: u64,
+    /// Number of inconsistencies found in all the checks.
+    pub inconsistencies: u64,
+    /// The last check, or `None` if no check has been run yet.
+    pub last_report: Option<ConsistencyReport>,
+}
+
+impl ConsistencyStats {
+    #[must_use]
+    pub fn new(metrics: consistency::Metrics, last_report: Option<&consistency::Report>) -> Self {
+        Self {
+            checks: metrics.checks,
+            inconsistencies: metrics.inconsistencies,
+            last_report: last_report.map(ConsistencyReport::from),
+        }
+    }
+}
+
+/// The result of a consistency check.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// When the check was run, in RFC 3339 format.
+    pub checked_at: String,
+    /// Number of torrents verified.
+    pub torrents: u64,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl From<&consistency::Report> for ConsistencyReport {
+    fn from(report: &consistency::Report) -> Self {
+        Self {
+            checked_at: convert_from_timestamp_to_datetime_utc(report.checked_at).to_rfc3339_opts(SecondsFormat::Secs, true),
+            torrents: report.torrents,
+            inconsistencies: report.inconsistencies.iter().map(Inconsistency::from).collect(),
+        }
+    }
+}
+
+/// An invariant of the torrents repository that is not satisfied.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// The invariant: `aggregate_metrics`, `entry_count`, `peer_count`,
+    /// `swarm_metadata` or `stale_peers`.
+    pub kind: String,
+    /// The torrent with the inconsistency, or `None` if it's about the whole
+    /// repository.
+    pub info_hash: Option<String>,
+    pub message: String,
+}
+
+impl From<&consistency::Inconsistency> for Inconsistency {
+    fn from(inconsistency: &consistency::Inconsistency) -> Self {
+        Self {
+            kind: inconsistency.kind.to_string(),
+            info_hash: inconsistency.info_hash.map(|info_hash| info_hash.to_hex_string()),
+            message: inconsistency.message.clone(),
+        }
+    }
+}
+
+/// State of the lookups in the database whitelist.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct DatabaseAuthorizationStats {
+    /// Number of pending lookups.
+    pub queue_depth: usize,
+    /// Maximum number of pending lookups.
+    pub queue_capacity: usize,
+    /// Total number of lookups.
+    pub lookups: u64,
+    /// Total number of lookups that waited for the query of a concurrent
+    /// lookup of the same torrent.
+    pub coalesced: u64,
+    /// Total number of queries to the database.
+    pub queries: u64,
+    /// Total number of lookups rejected because the queue was full.
+    pub overflows: u64,
+    /// Coalesced lookups / Total lookups.
+    pub coalescing_hit_rate: f64,
+}
+
+impl From<database_authorization::Metrics> for DatabaseAuthorizationStats {
+    fn from(metrics: database_authorization::Metrics) -> Self {
+        Self {
+            queue_depth: metrics.queue_depth,
+            queue_capacity: metrics.queue_capacity,
+            lookups: metrics.lookups,
+            coalesced: metrics.coalesced,
+            queries: metrics.queries,
+            overflows: metrics.overflows,
+            coalescing_hit_rate: ratio(metrics.coalesced, metrics.lookups),
+        }
+    }
+}
+
+/// It returns `part / total`, or `0` if the total is `0`.
+#[allow(clippy::cast_precision_loss)]
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
     use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 
-    use super::Stats;
+    use super::{ConsistencyReport, ConsistencyStats, Inconsistency, Stats};
     use crate::core::services::statistics::TrackerMetrics;
     use crate::core::statistics::Metrics;
+    use crate::core::torrent::consistency;
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn stats_resource_should_be_converted_from_tracker_metrics() {
         assert_eq!(
             Stats::from(TrackerMetrics {
@@ -91,15 +484,44 @@ mod tests {
                     tcp4_connections_handled: 5,
                     tcp4_announces_handled: 6,
                     tcp4_scrapes_handled: 7,
+                    tcp4_scrapes_exceeding_limit: 0,
                     tcp6_connections_handled: 8,
                     tcp6_announces_handled: 9,
                     tcp6_scrapes_handled: 10,
+                    tcp6_scrapes_exceeding_limit: 0,
+                    tcp_announce_responses_under_128_bytes: 0,
+                    tcp_announce_responses_under_512_bytes: 0,
+                    tcp_announce_responses_under_2048_bytes: 0,
+                    tcp_announce_responses_over_2048_bytes: 0,
+                    tcp_announce_responses_padded: 0,
+                    tcp_announces_with_unknown_event: 19,
+                    tcp_scrape_duplicate_info_hashes: 19,
+                    tcp_requests_with_uri_too_long: 22,
+                    tcp_requests_with_headers_too_large: 23,
+                    tcp_announces_with_deadline_exceeded: 24,
+                    tcp_scrapes_with_deadline_exceeded: 25,
+                    tcp_announces_with_key_bound_to_another_address: 26,
                     udp4_connections_handled: 11,
                     udp4_announces_handled: 12,
                     udp4_scrapes_handled: 13,
+                    udp4_scrapes_exceeding_limit: 17,
+                    udp4_connection_ids_used: 3,
+                    udp4_connection_ids_unused: 1,
+                    udp4_announces_with_expired_connection_id: 4,
                     udp6_connections_handled: 14,
                     udp6_announces_handled: 15,
-                    udp6_scrapes_handled: 16
+                    udp6_scrapes_handled: 16,
+                    udp6_scrapes_exceeding_limit: 18,
+                    udp6_connection_ids_used: 0,
+                    udp6_connection_ids_unused: 0,
+                    udp6_announces_with_expired_connection_id: 0,
+                    udp_announces_with_unknown_event: 20,
+                    udp_scrape_duplicate_info_hashes: 20,
+                    udp_negative_cache_hits: 21,
+                    udp_announces_with_deadline_exceeded: 26,
+                    udp_scrapes_with_deadline_exceeded: 27,
+                    torrents_created: 28,
+                    torrents_removed: 29
                 }
             }),
             Stats {
@@ -110,15 +532,84 @@ mod tests {
                 tcp4_connections_handled: 5,
                 tcp4_announces_handled: 6,
                 tcp4_scrapes_handled: 7,
+                tcp4_scrapes_exceeding_limit: 0,
                 tcp6_connections_handled: 8,
                 tcp6_announces_handled: 9,
                 tcp6_scrapes_handled: 10,
+                tcp6_scrapes_exceeding_limit: 0,
+                tcp_announce_responses_under_128_bytes: 0,
+                tcp_announce_responses_under_512_bytes: 0,
+                tcp_announce_responses_under_2048_bytes: 0,
+                tcp_announce_responses_over_2048_bytes: 0,
+                tcp_announce_responses_padded: 0,
+                tcp_announces_with_unknown_event: 19,
+                tcp_scrape_duplicate_info_hashes: 19,
+                tcp_requests_with_uri_too_long: 22,
+                tcp_requests_with_headers_too_large: 23,
+                tcp_announces_with_deadline_exceeded: 24,
+                tcp_scrapes_with_deadline_exceeded: 25,
+                tcp_announces_with_key_bound_to_another_address: 26,
                 udp4_connections_handled: 11,
                 udp4_announces_handled: 12,
                 udp4_scrapes_handled: 13,
+                udp4_scrapes_exceeding_limit: 17,
+                udp4_connection_ids_used: 3,
+                udp4_connection_ids_unused: 1,
+                udp4_announces_with_expired_connection_id: 4,
+                udp4_connect_to_announce_conversion_rate: 0.75,
+                udp4_expired_connection_id_ratio: 0.25,
                 udp6_connections_handled: 14,
                 udp6_announces_handled: 15,
-                udp6_scrapes_handled: 16
+                udp6_scrapes_handled: 16,
+                udp6_scrapes_exceeding_limit: 18,
+                udp6_connection_ids_used: 0,
+                udp6_connection_ids_unused: 0,
+                udp6_announces_with_expired_connection_id: 0,
+                udp6_connect_to_announce_conversion_rate: 0.0,
+                udp6_expired_connection_id_ratio: 0.0,
+                udp_announces_with_unknown_event: 20,
+                udp_scrape_duplicate_info_hashes: 20,
+                udp_negative_cache_hits: 21,
+                udp_announces_with_deadline_exceeded: 26,
+                udp_scrapes_with_deadline_exceeded: 27,
+                torrents_created: 28,
+                torrents_removed: 29
+            }
+        );
+    }
+
+    #[test]
+    fn consistency_stats_resource_should_be_converted_from_the_last_report() {
+        let report = consistency::Report {
+            checked_at: Duration::from_secs(1_729_069_200),
+            torrents: 2,
+            inconsistencies: vec![consistency::Inconsistency {
+                kind: consistency::Kind::StalePeers,
+                info_hash: Some(InfoHash::from([1; 20])),
+                message: "the torrent has 1 peers that should have been removed by the cleanup".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            ConsistencyStats::new(
+                consistency::Metrics {
+                    checks: 3,
+                    inconsistencies: 1
+                },
+                Some(&report)
+            ),
+            ConsistencyStats {
+                checks: 3,
+                inconsistencies: 1,
+                last_report: Some(ConsistencyReport {
+                    checked_at: "2024-10-16T09:00:00Z".to_string(),
+                    torrents: 2,
+                    inconsistencies: vec![Inconsistency {
+                        kind: "stale_peers".to_string(),
+                        info_hash: Some("0101010101010101010101010101010101010101".to_string()),
+                        message: "the torrent has 1 peers that should have been removed by the cleanup".to_string(),
+                    }],
+                }),
             }
         );
     }
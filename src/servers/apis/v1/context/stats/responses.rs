@@ -1,11 +1,310 @@
 //! API responses for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
-use axum::response::Json;
+use std::fmt::Write;
 
-use super::resources::Stats;
+use axum::http::header;
+use axum::response::{IntoResponse, Json, Response};
+
+use super::resources::{
+    AnnounceJournalStats, AsyncAnnounceStats, ConsistencyStats, DatabaseAuthorizationStats, HealthScoreBucket, RuntimeStats,
+    Stats, SwarmSizeBucket,
+};
+use crate::bootstrap::runtimes::Metrics;
 use crate::core::services::statistics::TrackerMetrics;
+use crate::core::torrent::consistency;
+use crate::core::torrent::snapshot::{HealthScores, SwarmSizes};
+use crate::core::{announce_journal, async_announce, database_authorization};
+use crate::servers::apis::v1::fields::Fields;
+use crate::servers::apis::v1::responses::not_enabled_response;
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prefix of the metric names in the Prometheus text exposition format.
+const PROMETHEUS_NAMESPACE: &str = "torrust_tracker_";
+
+/// Metrics that can go down. All the other metrics are counters.
+const GAUGES: [&str; 7] = [
+    "torrents",
+    "seeders",
+    "leechers",
+    "udp4_connect_to_announce_conversion_rate",
+    "udp4_expired_connection_id_ratio",
+    "udp6_connect_to_announce_conversion_rate",
+    "udp6_expired_connection_id_ratio",
+];
+
+/// `200` response that contains the [`Stats`] resource as json, with only
+/// the selected [`Fields`] if any.
+#[must_use]
+pub fn stats_response(tracker_metrics: TrackerMetrics, fields: Option<&Fields>) -> Response {
+    let stats = Stats::from(tracker_metrics);
+
+    match fields {
+        Some(fields) => Json(fields.select(&stats)).into_response(),
+        None => Json(stats).into_response(),
+    }
+}
+
+/// `200` response that contains the [`RuntimeStats`] of every runtime as
+/// json.
+pub fn runtimes_stats_response(metrics: Vec<Metrics>) -> Json<Vec<RuntimeStats>> {
+    Json(metrics.into_iter().map(RuntimeStats::from).collect())
+}
+
+/// `200` response that contains the [`SwarmSizeBucket`] list as json.
+pub fn swarm_sizes_response(swarm_sizes: &SwarmSizes) -> Json<Vec<SwarmSizeBucket>> {
+    Json(swarm_sizes.buckets().into_iter().map(SwarmSizeBucket::from).collect())
+}
+
+/// `409` error response when the swarm sizes are not available because the
+/// read snapshot is not enabled.
+#[must_use]
+pub fn swarm_sizes_not_enabled_response() -> Response {
+    not_enabled_response("the read snapshot is not enabled")
+}
+
+/// `200` response that contains the [`HealthScoreBucket`] list as json.
+pub fn health_scores_response(health_scores: &HealthScores) -> Json<Vec<HealthScoreBucket>> {
+    Json(health_scores.buckets().into_iter().map(HealthScoreBucket::from).collect())
+}
+
+/// `200` response that contains the [`AsyncAnnounceStats`] resource as json.
+pub fn async_announce_stats_response(metrics: async_announce::Metrics) -> Json<AsyncAnnounceStats> {
+    Json(AsyncAnnounceStats::from(metrics))
+}
+
+/// `409` error response when the asynchronous `announce` queue is not
+/// enabled.
+#[must_use]
+pub fn async_announce_not_enabled_response() -> Response {
+    not_enabled_response("the asynchronous announce queue is not enabled")
+}
+
+/// `200` response that contains the [`AnnounceJournalStats`] resource as json.
+pub fn announce_journal_stats_response(metrics: announce_journal::Metrics) -> Json<AnnounceJournalStats> {
+    Json(AnnounceJournalStats::from(metrics))
+}
+
+/// `409` error response when the announce journal is not enabled.
+#[must_use]
+pub fn announce_journal_not_enabled_response() -> Response {
+    not_enabled_response("the announce journal is not enabled")
+}
+
+/// `200` response that contains the [`ConsistencyStats`] resource as json.
+pub fn consistency_stats_response(
+    metrics: consistency::Metrics,
+    last_report: Option<&consistency::Report>,
+) -> Json<ConsistencyStats> {
+    Json(ConsistencyStats::new(metrics, last_report))
+}
+
+/// `409` error response when the consistency check is not enabled.
+#[must_use]
+pub fn consistency_check_not_enabled_response() -> Response {
+    not_enabled_response("the consistency check is not enabled")
+}
+
+/// `200` response that contains the [`DatabaseAuthorizationStats`] resource
+/// as json.
+pub fn database_authorization_stats_response(metrics: database_authorization::Metrics) -> Json<DatabaseAuthorizationStats> {
+    Json(DatabaseAuthorizationStats::from(metrics))
+}
+
+/// `409` error response when the database authorization is not enabled.
+#[must_use]
+pub fn database_authorization_not_enabled_response() -> Response {
+    not_enabled_response("the database authorization is not enabled")
+}
+
+/// `200` response that contains the [`Stats`] resource as plain text
+/// `key value` lines.
+///
+/// ```text
+/// torrents 0
+/// seeders 0
+/// ...
+/// ```
+#[must_use]
+pub fn flat_stats_response(tracker_metrics: TrackerMetrics, fields: Option<&Fields>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        to_flat(&Stats::from(tracker_metrics), fields),
+    )
+        .into_response()
+}
+
+/// `200` response that contains the [`Stats`] resource in the Prometheus text
+/// exposition format.
+///
+/// The metric names are prefixed with the `torrust_tracker_` namespace, and
+/// the counters are suffixed with `_total`, following the Prometheus naming
+/// conventions.
+///
+/// ```text
+/// # TYPE torrust_tracker_torrents gauge
+/// torrust_tracker_torrents 0
+/// ...
+/// # TYPE torrust_tracker_completed_total counter
+/// torrust_tracker_completed_total 0
+/// ...
+/// ```
+#[must_use]
+pub fn prometheus_stats_response(tracker_metrics: TrackerMetrics, fields: Option<&Fields>) -> Response {
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        to_prometheus(&Stats::from(tracker_metrics), fields),
+    )
+        .into_response()
+}
+
+/// It returns the [`Stats`] fields as `(name, value)` pairs in declaration
+/// order, only the selected [`Fields`] if any.
+///
+/// Non-finite ratios are serialized as `null`, so they are written as `NaN`.
+/// Any other non-numeric value is skipped.
+fn metrics(stats: &Stats, fields: Option<&Fields>) -> Vec<(String, String)> {
+    let value = match fields {
+        Some(fields) => fields.select(stats),
+        None => serde_json::to_value(stats).expect("it should serialize the stats"),
+    };
+
+    let serde_json::Value::Object(map) = value else {
+        return Vec::new();
+    };
+
+    map.into_iter()
+        .filter_map(|(name, value)| match value {
+            serde_json::Value::Number(value) => Some((name, value.to_string())),
+            serde_json::Value::Null => Some((name, "NaN".to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn to_flat(stats: &Stats, fields: Option<&Fields>) -> String {
+    let mut output = String::new();
+
+    for (name, value) in metrics(stats, fields) {
+        writeln!(output, "{name} {value}").expect("it should write to a string");
+    }
+
+    output
+}
+
+fn to_prometheus(stats: &Stats, fields: Option<&Fields>) -> String {
+    let mut output = String::new();
+
+    for (name, value) in metrics(stats, fields) {
+        let (name, metric_type) = if GAUGES.contains(&name.as_str()) {
+            (format!("{PROMETHEUS_NAMESPACE}{name}"), "gauge")
+        } else {
+            (format!("{PROMETHEUS_NAMESPACE}{name}_total"), "counter")
+        };
+
+        writeln!(output, "# TYPE {name} {metric_type}").expect("it should write to a string");
+        writeln!(output, "{name} {value}").expect("it should write to a string");
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_flat, to_prometheus};
+    use crate::servers::apis::v1::context::stats::resources::Stats;
+    use crate::servers::apis::v1::fields::Fields;
+
+    fn sample_stats() -> Stats {
+        Stats {
+            torrents: 1,
+            seeders: 2,
+            completed: 3,
+            leechers: 4,
+            tcp4_connections_handled: 5,
+            tcp4_announces_handled: 6,
+            tcp4_scrapes_handled: 7,
+            tcp4_scrapes_exceeding_limit: 0,
+            tcp6_connections_handled: 8,
+            tcp6_announces_handled: 9,
+            tcp6_scrapes_handled: 10,
+            tcp6_scrapes_exceeding_limit: 0,
+            tcp_announce_responses_under_128_bytes: 0,
+            tcp_announce_responses_under_512_bytes: 0,
+            tcp_announce_responses_under_2048_bytes: 0,
+            tcp_announce_responses_over_2048_bytes: 0,
+            tcp_announce_responses_padded: 0,
+            tcp_announces_with_unknown_event: 0,
+            tcp_scrape_duplicate_info_hashes: 0,
+            tcp_requests_with_uri_too_long: 0,
+            tcp_requests_with_headers_too_large: 0,
+            tcp_announces_with_deadline_exceeded: 0,
+            tcp_scrapes_with_deadline_exceeded: 0,
+            tcp_announces_with_key_bound_to_another_address: 0,
+            udp4_connections_handled: 11,
+            udp4_announces_handled: 12,
+            udp4_scrapes_handled: 13,
+            udp4_scrapes_exceeding_limit: 14,
+            udp4_connection_ids_used: 3,
+            udp4_connection_ids_unused: 1,
+            udp4_announces_with_expired_connection_id: 4,
+            udp4_connect_to_announce_conversion_rate: 0.75,
+            udp4_expired_connection_id_ratio: 0.25,
+            udp6_connections_handled: 15,
+            udp6_announces_handled: 16,
+            udp6_scrapes_handled: 17,
+            udp6_scrapes_exceeding_limit: 18,
+            udp6_connection_ids_used: 0,
+            udp6_connection_ids_unused: 0,
+            udp6_announces_with_expired_connection_id: 0,
+            udp6_connect_to_announce_conversion_rate: 0.0,
+            udp6_expired_connection_id_ratio: 0.0,
+            udp_announces_with_unknown_event: 0,
+            udp_scrape_duplicate_info_hashes: 0,
+            udp_negative_cache_hits: 0,
+            udp_announces_with_deadline_exceeded: 0,
+            udp_scrapes_with_deadline_exceeded: 0,
+            torrents_created: 0,
+            torrents_removed: 0,
+        }
+    }
+
+    #[test]
+    fn stats_should_be_serialized_as_flat_key_value_lines() {
+        let flat = to_flat(&sample_stats(), None);
+
+        let lines: Vec<&str> = flat.lines().collect();
+
+        assert_eq!(lines.len(), 49);
+        assert_eq!(lines[0], "torrents 1");
+        assert_eq!(lines[1], "seeders 2");
+        assert_eq!(lines[31], "udp4_connect_to_announce_conversion_rate 0.75");
+        assert_eq!(lines[41], "udp6_expired_connection_id_ratio 0.0");
+    }
+
+    #[test]
+    fn stats_should_be_serialized_in_the_prometheus_text_format() {
+        let prometheus = to_prometheus(&sample_stats(), None);
+
+        assert!(prometheus.starts_with("# TYPE torrust_tracker_torrents gauge\ntorrust_tracker_torrents 1\n"));
+        assert!(prometheus.contains("# TYPE torrust_tracker_completed_total counter\ntorrust_tracker_completed_total 3\n"));
+        assert!(prometheus.contains(
+            "# TYPE torrust_tracker_udp6_scrapes_exceeding_limit_total counter\ntorrust_tracker_udp6_scrapes_exceeding_limit_total 18\n"
+        ));
+        assert!(prometheus.contains(
+            "# TYPE torrust_tracker_udp4_expired_connection_id_ratio gauge\ntorrust_tracker_udp4_expired_connection_id_ratio 0.25\n"
+        ));
+    }
+
+    #[test]
+    fn only_the_selected_stats_should_be_serialized() {
+        let fields = Fields::parse("leechers,torrents").unwrap();
 
-/// `200` response that contains the [`Stats`] resource as json.
-pub fn stats_response(tracker_metrics: TrackerMetrics) -> Json<Stats> {
-    Json(Stats::from(tracker_metrics))
+        assert_eq!(to_flat(&sample_stats(), Some(&fields)), "torrents 1\nleechers 4\n");
+        assert_eq!(
+            to_prometheus(&sample_stats(), Some(&fields)),
+            "# TYPE torrust_tracker_torrents gauge\ntorrust_tracker_torrents 1\n# TYPE torrust_tracker_leechers gauge\ntorrust_tracker_leechers 4\n"
+        );
+    }
 }
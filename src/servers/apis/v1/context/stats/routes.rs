@@ -1,6 +1,13 @@
 //! API routes for the [`stats`](crate::servers::apis::v1::context::stats) API context.
 //!
 //! - `GET /stats`
+//! - `GET /stats/runtimes`
+//! - `GET /stats/swarm-sizes`
+//! - `GET /stats/health-scores`
+//! - `GET /stats/async-announce`
+//! - `GET /stats/announce-journal`
+//! - `GET /stats/consistency`
+//! - `GET /stats/database-authorization`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats).
 use std::sync::Arc;
@@ -8,10 +15,40 @@ use std::sync::Arc;
 use axum::routing::get;
 use axum::Router;
 
-use super::handlers::get_stats_handler;
+use super::handlers::{
+    get_announce_journal_stats_handler, get_async_announce_stats_handler, get_consistency_stats_handler,
+    get_database_authorization_stats_handler, get_health_scores_handler, get_runtimes_stats_handler, get_stats_handler,
+    get_swarm_sizes_handler,
+};
 use crate::core::Tracker;
 
 /// It adds the routes to the router for the [`stats`](crate::servers::apis::v1::context::stats) API context.
 pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
-    router.route(&format!("{prefix}/stats"), get(get_stats_handler).with_state(tracker))
+    router
+        .route(&format!("{prefix}/stats"), get(get_stats_handler).with_state(tracker.clone()))
+        .route(&format!("{prefix}/stats/runtimes"), get(get_runtimes_stats_handler))
+        .route(
+            &format!("{prefix}/stats/swarm-sizes"),
+            get(get_swarm_sizes_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/health-scores"),
+            get(get_health_scores_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/async-announce"),
+            get(get_async_announce_stats_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/announce-journal"),
+            get(get_announce_journal_stats_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/consistency"),
+            get(get_consistency_stats_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/stats/database-authorization"),
+            get(get_database_authorization_stats_handler).with_state(tracker),
+        )
 }
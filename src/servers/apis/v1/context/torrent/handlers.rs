@@ -3,35 +3,47 @@
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::extract::{Path, State};
+use axum::extract::State;
 use axum::response::{IntoResponse, Response};
-use axum_extra::extract::Query;
 use serde::{de, Deserialize, Deserializer};
 use thiserror::Error;
+use torrust_tracker_configuration::ANNOUNCE_URL_KEY_PLACEHOLDER;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::pagination::Pagination;
 
-use super::responses::{torrent_info_response, torrent_list_response, torrent_not_known_response};
-use crate::core::services::torrent::{get_torrent_info, get_torrents, get_torrents_page};
+use super::responses::{
+    magnet_links_not_enabled_response, magnet_response, stale_torrent_list_response, swarm_changes_not_enabled_response,
+    swarm_changes_response, torrent_info_response, torrent_list_response, torrent_not_known_response,
+};
+use crate::core::auth::Key;
+use crate::core::services::torrent::{get_stale_torrents, get_torrent_info, get_torrents, get_torrents_page};
 use crate::core::Tracker;
-use crate::servers::apis::v1::responses::invalid_info_hash_param_response;
+use crate::servers::apis::v1::extractors::{Path, Query};
+use crate::servers::apis::v1::fields::{fields_param, Fields};
+use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, invalid_info_hash_param_response};
+use crate::servers::apis::v1::streaming::Format;
 use crate::servers::apis::InfoHashParam;
+use crate::shared::bit_torrent::magnet::MagnetLink;
 
 /// It handles the request to get the torrent data.
 ///
 /// It returns:
 ///
 /// - `200` response with a json [`Torrent`](crate::servers::apis::v1::context::torrent::resources::torrent::Torrent).
-/// - `500` with serialized error in debug format if the torrent is not known.
+/// - `404` with a json error if the torrent is not known.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#get-a-torrent)
 /// for more information about this endpoint.
-pub async fn get_torrent_handler(State(tracker): State<Arc<Tracker>>, Path(info_hash): Path<InfoHashParam>) -> Response {
+pub async fn get_torrent_handler(
+    State((tracker, peer_id_hash_salt)): State<(Arc<Tracker>, Option<String>)>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
     match InfoHash::from_str(&info_hash.0) {
         Err(_) => invalid_info_hash_param_response(&info_hash.0),
         Ok(info_hash) => match get_torrent_info(tracker.clone(), &info_hash).await {
-            Some(info) => torrent_info_response(info).into_response(),
+            Some(info) => torrent_info_response(info, peer_id_hash_salt.as_deref()).into_response(),
             None => torrent_not_known_response(),
         },
     }
@@ -56,6 +68,12 @@ pub async fn get_torrent_handler(State(tracker): State<Arc<Tracker>>, Path(info_
 ///
 ///
 /// NOTICE: Pagination is ignored if array of infohashes is provided.
+///
+/// The response is streamed in the [`Format`] given in `format`: `json`
+/// (default) or `ndjson`. The `fields` of the torrents can be selected, for
+/// example:
+///
+/// <http://127.0.0.1:1212/api/v1/torrents?token=MyAccessToken&fields=info_hash,seeders>
 #[derive(Deserialize, Debug)]
 pub struct QueryParams {
     /// The offset of the first page to return. Starts at 0.
@@ -67,29 +85,41 @@ pub struct QueryParams {
     /// A list of infohashes to retrieve.
     #[serde(default, rename = "info_hash")]
     pub info_hashes: Vec<String>,
+    /// The format of the response.
+    #[serde(default)]
+    pub format: Format,
+    /// The selected [`Fields`] of the torrents. All the fields are included
+    /// when it's missing.
+    #[serde(default, deserialize_with = "fields_param")]
+    pub fields: Option<Fields>,
 }
 
 /// It handles the request to get a list of torrents.
 ///
 /// It returns a `200` response with a json array with [`crate::servers::apis::v1::context::torrent::resources::torrent::ListItem`] resources.
+/// The response is streamed.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#list-torrents)
 /// for more information about this endpoint.
 pub async fn get_torrents_handler(State(tracker): State<Arc<Tracker>>, pagination: Query<QueryParams>) -> Response {
     tracing::debug!("pagination: {:?}", pagination);
 
+    let format = pagination.0.format;
+    let fields = pagination.0.fields.clone();
+
     if pagination.0.info_hashes.is_empty() {
         torrent_list_response(
-            &get_torrents_page(
+            get_torrents_page(
                 tracker.clone(),
                 Some(&Pagination::new_with_options(pagination.0.offset, pagination.0.limit)),
             )
             .await,
+            format,
+            fields,
         )
-        .into_response()
     } else {
         match parse_info_hashes(pagination.0.info_hashes) {
-            Ok(info_hashes) => torrent_list_response(&get_torrents(tracker.clone(), &info_hashes).await).into_response(),
+            Ok(info_hashes) => torrent_list_response(get_torrents(tracker.clone(), &info_hashes).await, format, fields),
             Err(err) => match err {
                 QueryParamError::InvalidInfoHash { info_hash } => invalid_info_hash_param_response(&info_hash),
             },
@@ -97,6 +127,123 @@ pub async fn get_torrents_handler(State(tracker): State<Arc<Tracker>>, paginatio
     }
 }
 
+/// A container for the URL query parameters of the stale torrents endpoint.
+///
+/// For example, the torrents without announces for more than one day:
+///
+/// <http://127.0.0.1:1212/api/v1/torrents/stale?token=MyAccessToken&inactive_for=86400>
+#[derive(Deserialize, Debug)]
+pub struct StaleQueryParams {
+    /// The minimum number of seconds since the last announce.
+    pub inactive_for: u64,
+}
+
+/// It handles the request to get the list of stale torrents.
+///
+/// It returns a `200` response with a json array with [`crate::servers::apis::v1::context::torrent::resources::torrent::StaleListItem`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#list-stale-torrents)
+/// for more information about this endpoint.
+pub async fn get_stale_torrents_handler(State(tracker): State<Arc<Tracker>>, params: Query<StaleQueryParams>) -> Response {
+    stale_torrent_list_response(&get_stale_torrents(tracker.clone(), Duration::from_secs(params.0.inactive_for)).await)
+        .into_response()
+}
+
+/// A container for the URL query parameters of the swarm changes endpoint.
+///
+/// For example, the torrents that changed after the cursor `1500`:
+///
+/// <http://127.0.0.1:1212/api/v1/torrents/changes?token=MyAccessToken&since=1500>
+#[derive(Deserialize, Debug)]
+pub struct ChangesQueryParams {
+    /// The cursor returned by the previous request. Starts at 0.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub since: Option<u64>,
+    /// The maximum number of infohashes to return.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub limit: Option<u32>,
+}
+
+/// It handles the request to get the torrents whose swarm changed since a
+/// cursor.
+///
+/// It returns:
+///
+/// - `200` response with a json [`SwarmChanges`](crate::servers::apis::v1::context::torrent::resources::changes::SwarmChanges).
+/// - `409` with a json error if the swarm changes log is not enabled.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#list-swarm-changes)
+/// for more information about this endpoint.
+pub async fn get_swarm_changes_handler(State(tracker): State<Arc<Tracker>>, params: Query<ChangesQueryParams>) -> Response {
+    let since = params.0.since.unwrap_or_default();
+    let limit = params.0.limit.unwrap_or_else(Pagination::default_limit);
+
+    match tracker.get_swarm_changes(since, limit as usize) {
+        Some(page) => swarm_changes_response(page).into_response(),
+        None => swarm_changes_not_enabled_response(),
+    }
+}
+
+/// A container for the URL query parameters of the magnet link endpoint.
+///
+/// For example, the magnet link for the user with the authentication key
+/// `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`:
+///
+/// <http://127.0.0.1:1212/api/v1/torrent/9c38422213e30bff212b30c360d26f9a02136422/magnet?token=MyAccessToken&key=xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6>
+#[derive(Deserialize, Debug)]
+pub struct MagnetQueryParams {
+    /// The authentication key that replaces the `{key}` placeholder in the
+    /// announce URLs.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub key: Option<String>,
+    /// The display name of the torrent.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub name: Option<String>,
+}
+
+/// It handles the request to get the magnet link of a torrent with the
+/// tracker's own announce URLs.
+///
+/// It returns:
+///
+/// - `200` response with a json [`Magnet`](crate::servers::apis::v1::context::torrent::resources::magnet::Magnet).
+/// - `400` with a json error if the info-hash or the key are not valid.
+/// - `409` with a json error if there are no announce URLs configured.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#get-the-magnet-link-of-a-torrent)
+/// for more information about this endpoint.
+pub async fn get_torrent_magnet_handler(
+    State(announce_urls): State<Vec<String>>,
+    Path(info_hash): Path<InfoHashParam>,
+    params: Query<MagnetQueryParams>,
+) -> Response {
+    let Ok(info_hash) = InfoHash::from_str(&info_hash.0) else {
+        return invalid_info_hash_param_response(&info_hash.0);
+    };
+
+    if announce_urls.is_empty() {
+        return magnet_links_not_enabled_response();
+    }
+
+    let trackers = match &params.0.key {
+        Some(key) => match Key::from_str(key) {
+            Ok(key) => announce_urls
+                .iter()
+                .map(|url| url.replace(ANNOUNCE_URL_KEY_PLACEHOLDER, &key.to_string()))
+                .collect(),
+            Err(_) => return invalid_auth_key_param_response(key),
+        },
+        None => announce_urls,
+    };
+
+    magnet_response(MagnetLink {
+        info_hash,
+        display_name: params.0.name.clone(),
+        trackers,
+    })
+    .into_response()
+}
+
 #[derive(Error, Debug)]
 pub enum QueryParamError {
     #[error("invalid infohash {info_hash}")]
@@ -121,7 +268,7 @@ fn parse_info_hashes(info_hashes_str: Vec<String>) -> Result<Vec<InfoHash>, Quer
 }
 
 /// Serde deserialization decorator to map empty Strings to None,
-fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+pub(crate) fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
     T: FromStr,
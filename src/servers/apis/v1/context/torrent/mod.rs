@@ -6,7 +6,10 @@
 //! # Endpoints
 //!
 //! - [Get a torrent](#get-a-torrent)
+//! - [Get the magnet link of a torrent](#get-the-magnet-link-of-a-torrent)
 //! - [List torrents](#list-torrents)
+//! - [List stale torrents](#list-stale-torrents)
+//! - [List swarm changes](#list-swarm-changes)
 //!
 //! # Get a torrent
 //!
@@ -46,18 +49,63 @@
 //!         "uploaded": 0,
 //!         "downloaded": 0,
 //!         "left": 0,
-//!         "event": "None"
+//!         "event": "None",
+//!         "corrupt": 0,
+//!         "redundant": 0
 //!       }
-//!     ]
+//!     ],
+//!     "corrupt": 0,
+//!     "redundant": 0,
+//!     "peers_with_corrupt_data": 0
 //! }
 //! ```
 //!
-//! **Not Found response** `200`
+//! The `corrupt` and `redundant` attributes aggregate the bytes that failed
+//! the hash check and the bytes downloaded more than once, as reported by the
+//! peers with the non-standard `corrupt` and `redundant` announce params.
+//! `peers_with_corrupt_data` is the number of peers that reported corrupt
+//! bytes. Many of them could indicate a poisoned swarm. The peers that do not
+//! send these params count as zero.
+//!
+//! When the peer reachability verification is enabled, the response also
+//! includes the `reachability_ratio` attribute (from `0.0` to `1.0`) once at
+//! least one peer of the torrent has been probed.
+//!
+//! When the read snapshot is enabled, the response also includes the `health`
+//! of the swarm computed by the read snapshot job:
+//!
+//! ```json
+//! "health": {
+//!     "score": 92,
+//!     "availability": 1.0,
+//!     "churn": 0.0,
+//!     "trend": "stable"
+//! }
+//! ```
+//!
+//! The `score` goes from `0` (dead) to `100` (healthy). Refer to the
+//! [`snapshot`](crate::core::torrent::snapshot) module for more information
+//! about how it's computed.
+//!
+//! Peer IDs can identify users across torrents. When the `peer_id_hash_salt`
+//! option of the API is set, the `id` of the peers is the `SHA-1` hash of the
+//! salt and the peer ID instead of the raw peer ID. The `client` is kept.
+//!
+//! ```toml
+//! [http_api]
+//! peer_id_hash_salt = "MyPeerIdHashSalt"
+//! ```
+//!
+//! **Not Found response** `404`
 //!
 //! This response is returned when the tracker does not have the torrent.
 //!
 //! ```json
-//! "torrent not known"
+//! {
+//!     "code": "not_found",
+//!     "message": "torrent not known",
+//!     "details": []
+//! }
 //! ```
 //!
 //! **Resource**
@@ -79,6 +127,13 @@
 //! ---|---|---|---|---
 //! `offset` | positive integer | The page number, starting at 0 | No | `1`
 //! `limit` | positive integer | Page size. The number of results per page | No | `10`
+//! `format` | `json` or `ndjson` | The format of the response. Default is `json` | No | `ndjson`
+//! `fields` | comma-separated field names | The [`fields`](crate::servers::apis::v1::fields) of the torrents to include. Default is all | No | `info_hash,seeders`
+//!
+//! The response body is streamed, so big pages can be requested without
+//! buffering the whole serialized response. With the `ndjson` format, each torrent is a
+//! JSON object in its own line. See the [`streaming`](crate::servers::apis::v1::streaming)
+//! module.
 //!
 //! **Example request**
 //!
@@ -107,6 +162,144 @@
 //! response.
 //!
 //! > **NOTICE**: this endpoint does not include the `peers` list.
+//!
+//! # List stale torrents
+//!
+//! `GET /torrents/stale`
+//!
+//! Returns basic information (no peer list) for the torrents without announces
+//! for more than the given time. Stale torrents are not removed unless the
+//! core tracker option `max_torrent_inactivity` is set.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `inactive_for` | positive integer | Seconds since the last announce | Yes | `86400`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrents/stale?token=MyAccessToken&inactive_for=86400"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!       "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!       "seeders": 1,
+//!       "completed": 0,
+//!       "leechers": 0,
+//!       "last_announce_at": 1669397478
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`StaleListItem`](crate::servers::apis::v1::context::torrent::resources::torrent::StaleListItem)
+//! resource for more information about the attributes for a single item in the
+//! response.
+//!
+//! # List swarm changes
+//!
+//! `GET /torrents/changes`
+//!
+//! Returns the infohashes of the torrents whose swarm metadata changed after
+//! a cursor, so clients can sync the seeders and leechers counters
+//! incrementally instead of getting all the torrents again. Clients start
+//! with the cursor `0` and use the returned `cursor` in the next request.
+//!
+//! When `reset` is `true` the changes since the requested cursor are not
+//! available anymore (for example, after a tracker restart). The client has to
+//! sync all the torrents again and then continue from the returned `cursor`.
+//!
+//! It requires the core tracker option `swarm_changes`. Otherwise, it returns
+//! a `409` error.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `since` | positive integer | The cursor returned by the previous request | No | `1500`
+//! `limit` | positive integer | The maximum number of infohashes to return | No | `1000`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrents/changes?token=MyAccessToken&since=1500"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "cursor": 1502,
+//!     "reset": false,
+//!     "info_hashes": [
+//!       "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!       "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"
+//!     ]
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`SwarmChanges`](crate::servers::apis::v1::context::torrent::resources::changes::SwarmChanges)
+//! resource for more information about the attributes in the response.
+//!
+//! # Get the magnet link of a torrent
+//!
+//! `GET /torrent/:info_hash/magnet`
+//!
+//! Returns a magnet link for the torrent with the tracker's own announce
+//! URLs, so that index frontends don't have to build them. The torrent
+//! doesn't need to be known by the tracker.
+//!
+//! The announce URLs are the API `announce_urls` option:
+//!
+//! ```toml
+//! [http_api]
+//! announce_urls = [
+//!     "udp://tracker.example.com:6969/announce",
+//!     "https://tracker.example.com/announce/{key}",
+//! ]
+//! ```
+//!
+//! On private trackers, the `{key}` placeholder is replaced with the `key`
+//! query parameter. When the `key` is not provided, the placeholder is kept
+//! (percent-encoded as `%7Bkey%7D`), so the link can be used as a template.
+//!
+//! It returns a `409` error when there are no announce URLs configured.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `key` | 32-char string | The authentication key of the user | No | `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`
+//! `name` | string | The display name (`dn`) of the torrent | No | `ubuntu`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrent/9c38422213e30bff212b30c360d26f9a02136422/magnet?token=MyAccessToken&name=ubuntu"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "info_hash": "9c38422213e30bff212b30c360d26f9a02136422",
+//!     "magnet_uri": "magnet:?xt=urn:btih:9c38422213e30bff212b30c360d26f9a02136422&dn=ubuntu&tr=udp%3A%2F%2Ftracker%2Eexample%2Ecom%3A6969%2Fannounce"
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`Magnet`](crate::servers::apis::v1::context::torrent::resources::magnet::Magnet)
+//! resource for more information about the attributes in the response.
 pub mod handlers;
 pub mod resources;
 pub mod responses;
@@ -0,0 +1,52 @@
+//! `SwarmChanges` API resource.
+use serde::{Deserialize, Serialize};
+
+use crate::core::torrent::changes::Page;
+
+/// `SwarmChanges` API resource. The torrents whose swarm changed since a
+/// cursor.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SwarmChanges {
+    /// The cursor to use in the next request.
+    pub cursor: u64,
+    /// `true` if the changes since the requested cursor are not available
+    /// anymore. The client has to sync all the torrents again.
+    pub reset: bool,
+    /// The info hashes of the torrents that changed, in the order they
+    /// changed.
+    pub info_hashes: Vec<String>,
+}
+
+impl From<Page> for SwarmChanges {
+    fn from(page: Page) -> Self {
+        Self {
+            cursor: page.cursor,
+            reset: page.reset,
+            info_hashes: page.info_hashes.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::SwarmChanges;
+    use crate::core::torrent::changes::Page;
+
+    #[test]
+    fn swarm_changes_resource_should_be_converted_from_a_page_of_changes() {
+        assert_eq!(
+            SwarmChanges::from(Page {
+                cursor: 3,
+                info_hashes: vec!["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap()],
+                reset: false,
+            }),
+            SwarmChanges {
+                cursor: 3,
+                reset: false,
+                info_hashes: vec!["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string()],
+            }
+        );
+    }
+}
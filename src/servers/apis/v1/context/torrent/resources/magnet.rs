@@ -0,0 +1,52 @@
+//! `Magnet` API resource.
+use serde::{Deserialize, Serialize};
+
+use crate::shared::bit_torrent::magnet::MagnetLink;
+
+/// `Magnet` API resource. The magnet link of a torrent with the tracker's own
+/// announce URLs.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Magnet {
+    /// The torrent info-hash v1.
+    pub info_hash: String,
+    /// The magnet URI.
+    pub magnet_uri: String,
+}
+
+impl From<MagnetLink> for Magnet {
+    fn from(magnet_link: MagnetLink) -> Self {
+        Self {
+            info_hash: magnet_link.info_hash.to_hex_string(),
+            magnet_uri: magnet_link.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::Magnet;
+    use crate::shared::bit_torrent::magnet::MagnetLink;
+
+    #[test]
+    fn it_should_be_convertible_from_a_magnet_link() {
+        let magnet_link = MagnetLink {
+            info_hash: InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap(),
+            display_name: None,
+            trackers: vec!["udp://tracker.example.com:6969/announce".to_string()],
+        };
+
+        assert_eq!(
+            Magnet::from(magnet_link),
+            Magnet {
+                info_hash: "9c38422213e30bff212b30c360d26f9a02136422".to_string(),
+                magnet_uri: "magnet:?xt=urn:btih:9c38422213e30bff212b30c360d26f9a02136422\
+                             &tr=udp%3A%2F%2Ftracker%2Eexample%2Ecom%3A6969%2Fannounce"
+                    .to_string()
+            }
+        );
+    }
+}
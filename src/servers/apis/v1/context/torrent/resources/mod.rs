@@ -1,4 +1,6 @@
 //! API resources for the [`torrent`](crate::servers::apis::v1::context::torrent)
 //! API context.
+pub mod changes;
+pub mod magnet;
 pub mod peer;
 pub mod torrent;
@@ -2,6 +2,7 @@
 use aquatic_udp_protocol::PeerId;
 use derive_more::From;
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
 use torrust_tracker_primitives::peer;
 
 /// `Peer` API resource.
@@ -25,6 +26,12 @@ pub struct Peer {
     /// The peer's event: `started`, `stopped`, `completed`.
     /// See [`AnnounceEvent`](aquatic_udp_protocol::AnnounceEvent).
     pub event: String,
+    /// The peer's bytes that failed the hash check. Zero if the client does
+    /// not report them.
+    pub corrupt: i64,
+    /// The peer's bytes downloaded more than once. Zero if the client does not
+    /// report them.
+    pub redundant: i64,
 }
 
 /// Peer `Id` API resource.
@@ -46,6 +53,39 @@ impl From<PeerId> for Id {
     }
 }
 
+impl Id {
+    /// It replaces the peer ID with the `SHA-1` hash of the `salt` and the
+    /// peer ID, in the same hex format. The client name is kept because it
+    /// only depends on the peer ID prefix, which is shared by all the peers
+    /// using the same client.
+    #[must_use]
+    pub fn hashed(peer_id: PeerId, salt: &str) -> Self {
+        let mut hasher = Sha1::new();
+
+        hasher.update(salt.as_bytes());
+        hasher.update(peer_id.0);
+
+        let hash: [u8; 20] = hasher.finalize().into();
+
+        Id {
+            id: peer::Id::from(PeerId(hash)).to_hex_string(),
+            client: peer::Id::from(peer_id).get_client_name(),
+        }
+    }
+}
+
+impl Peer {
+    /// Like the `From<peer::Peer>` conversion, but
+    /// with the peer ID replaced by a salted hash. See [`Id::hashed`].
+    #[must_use]
+    pub fn with_hashed_id(peer: peer::Peer, salt: &str) -> Self {
+        Peer {
+            peer_id: Id::hashed(peer.peer_id, salt),
+            ..Peer::from(peer)
+        }
+    }
+}
+
 impl From<peer::Peer> for Peer {
     fn from(value: peer::Peer) -> Self {
         #[allow(deprecated)]
@@ -58,6 +98,8 @@ impl From<peer::Peer> for Peer {
             downloaded: value.downloaded.0.get(),
             left: value.left.0.get(),
             event: format!("{:?}", value.event),
+            corrupt: value.corrupt.0.get(),
+            redundant: value.redundant.0.get(),
         }
     }
 }
@@ -4,12 +4,14 @@
 //! - `ListItem` is a list item resource on a torrent list. `ListItem` does
 //!   include a `peers` field but it is always `None` in the struct and `null` in
 //!   the JSON response.
+//! - `StaleListItem` is a list item resource on a stale torrent list.
 use serde::{Deserialize, Serialize};
 
-use crate::core::services::torrent::{BasicInfo, Info};
+use crate::core::services::torrent::{BasicInfo, Info, StaleInfo};
+use crate::core::torrent::snapshot::{Health, Trend};
 
 /// `Torrent` API resource.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Torrent {
     /// The torrent's info hash v1.
     pub info_hash: String,
@@ -22,9 +24,61 @@ pub struct Torrent {
     /// The torrent's leechers counter. Active peers that are downloading the
     /// torrent.
     pub leechers: u64,
+    /// The torrent's partial seeds counter. Leechers that have all the
+    /// pieces they want. They are also counted as leechers.
+    pub partial_seeds: u64,
     /// The torrent's peers. See [`Peer`](crate::servers::apis::v1::context::torrent::resources::peer::Peer).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub peers: Option<Vec<super::peer::Peer>>,
+    /// The total number of bytes that failed the hash check, as reported by
+    /// the peers with the non-standard `corrupt` announce param.
+    pub corrupt: u64,
+    /// The total number of bytes downloaded more than once, as reported by
+    /// the peers with the non-standard `redundant` announce param.
+    pub redundant: u64,
+    /// The number of peers that reported corrupt bytes. A high number could
+    /// indicate a poisoned swarm.
+    pub peers_with_corrupt_data: u64,
+    /// The ratio (from `0.0` to `1.0`) of reachable peers over the probed
+    /// peers. It's only included when the peer reachability verification is
+    /// enabled and at least one peer has been probed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reachability_ratio: Option<f64>,
+    /// The health of the swarm. It's only included when the read snapshot is
+    /// enabled and the torrent was in the last published snapshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<TorrentHealth>,
+}
+
+/// The health of the swarm of a torrent, computed by the read snapshot job.
+/// See [`Health`].
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TorrentHealth {
+    /// The health score, from `0` (dead) to `100` (healthy).
+    pub score: u8,
+    /// The ratio of seeders to leechers, from `0.0` to `1.0`.
+    pub availability: f64,
+    /// The change in the number of peers since the previous snapshot, over
+    /// the size of the swarm, from `0.0` to `1.0`.
+    pub churn: f64,
+    /// The trend of the number of seeders: `rising`, `stable` or `falling`.
+    pub trend: String,
+}
+
+impl From<Health> for TorrentHealth {
+    fn from(health: Health) -> Self {
+        Self {
+            score: health.score,
+            availability: health.availability,
+            churn: health.churn,
+            trend: match health.trend {
+                Trend::Rising => "rising",
+                Trend::Stable => "stable",
+                Trend::Falling => "falling",
+            }
+            .to_string(),
+        }
+    }
 }
 
 /// `ListItem` API resource. A list item on a torrent list.
@@ -43,6 +97,9 @@ pub struct ListItem {
     /// The torrent's leechers counter. Active peers that are downloading the
     /// torrent.
     pub leechers: u64,
+    /// The torrent's partial seeds counter. Leechers that have all the
+    /// pieces they want. They are also counted as leechers.
+    pub partial_seeds: u64,
 }
 
 impl ListItem {
@@ -55,6 +112,34 @@ impl ListItem {
     }
 }
 
+/// `StaleListItem` API resource. A list item on a stale torrent list.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct StaleListItem {
+    /// The torrent's info hash v1.
+    pub info_hash: String,
+    /// The torrent's seeders counter. Active peers with a full copy of the
+    /// torrent.
+    pub seeders: u64,
+    /// The torrent's completed counter. Peers that have ever completed the
+    /// download.
+    pub completed: u64,
+    /// The torrent's leechers counter. Active peers that are downloading the
+    /// torrent.
+    pub leechers: u64,
+    /// The time of the last announce, in seconds since the Unix Epoch.
+    pub last_announce_at: u64,
+}
+
+impl StaleListItem {
+    #[must_use]
+    pub fn new_vec(stale_info_vec: &[StaleInfo]) -> Vec<Self> {
+        stale_info_vec
+            .iter()
+            .map(|stale_info| StaleListItem::from((*stale_info).clone()))
+            .collect()
+    }
+}
+
 /// Maps an array of the domain type [`BasicInfo`]
 /// to the API resource type [`ListItem`].
 #[must_use]
@@ -65,6 +150,25 @@ pub fn to_resource(basic_info_vec: &[BasicInfo]) -> Vec<ListItem> {
         .collect()
 }
 
+impl Torrent {
+    /// Like the `From<Info>` conversion, but
+    /// with the peer IDs replaced by a salted hash.
+    #[must_use]
+    pub fn with_hashed_peer_ids(mut info: Info, salt: &str) -> Self {
+        let peers = info.peers.take().map(|peers| {
+            peers
+                .into_iter()
+                .map(|peer| super::peer::Peer::with_hashed_id(peer, salt))
+                .collect()
+        });
+
+        Self {
+            peers,
+            ..Self::from(info)
+        }
+    }
+}
+
 impl From<Info> for Torrent {
     fn from(info: Info) -> Self {
         let peers: Option<super::peer::Vector> = info.peers.map(|peers| peers.into_iter().collect());
@@ -76,7 +180,13 @@ impl From<Info> for Torrent {
             seeders: info.seeders,
             completed: info.completed,
             leechers: info.leechers,
+            partial_seeds: info.partial_seeds,
             peers,
+            corrupt: info.corrupt,
+            redundant: info.redundant,
+            peers_with_corrupt_data: info.peers_with_corrupt_data,
+            reachability_ratio: info.reachability_ratio,
+            health: info.health.map(TorrentHealth::from),
         }
     }
 }
@@ -88,6 +198,19 @@ impl From<BasicInfo> for ListItem {
             seeders: basic_info.seeders,
             completed: basic_info.completed,
             leechers: basic_info.leechers,
+            partial_seeds: basic_info.partial_seeds,
+        }
+    }
+}
+
+impl From<StaleInfo> for StaleListItem {
+    fn from(stale_info: StaleInfo) -> Self {
+        Self {
+            info_hash: stale_info.info_hash.to_string(),
+            seeders: stale_info.seeders,
+            completed: stale_info.completed,
+            leechers: stale_info.leechers,
+            last_announce_at: stale_info.last_announce_at.as_secs(),
         }
     }
 }
@@ -101,10 +224,11 @@ mod tests {
     use torrust_tracker_primitives::info_hash::InfoHash;
     use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
-    use super::Torrent;
-    use crate::core::services::torrent::{BasicInfo, Info};
+    use super::{Torrent, TorrentHealth};
+    use crate::core::services::torrent::{BasicInfo, Info, StaleInfo};
+    use crate::core::torrent::snapshot::{Health, Trend};
     use crate::servers::apis::v1::context::torrent::resources::peer::Peer;
-    use crate::servers::apis::v1::context::torrent::resources::torrent::ListItem;
+    use crate::servers::apis::v1::context::torrent::resources::torrent::{ListItem, StaleListItem};
 
     fn sample_peer() -> peer::Peer {
         peer::Peer {
@@ -115,6 +239,9 @@ mod tests {
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            partial_seed: false,
+            corrupt: NumberOfBytes::new(0),
+            redundant: NumberOfBytes::new(0),
         }
     }
 
@@ -126,18 +253,73 @@ mod tests {
                 seeders: 1,
                 completed: 2,
                 leechers: 3,
+                partial_seeds: 0,
                 peers: Some(vec![sample_peer()]),
+                corrupt: 1024,
+                redundant: 2048,
+                peers_with_corrupt_data: 1,
+                reachability_ratio: Some(0.5),
+                health: Some(Health {
+                    score: 49,
+                    availability: 0.5,
+                    churn: 0.25,
+                    trend: Trend::Falling,
+                }),
             }),
             Torrent {
                 info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
                 seeders: 1,
                 completed: 2,
                 leechers: 3,
+                partial_seeds: 0,
                 peers: Some(vec![Peer::from(sample_peer())]),
+                corrupt: 1024,
+                redundant: 2048,
+                peers_with_corrupt_data: 1,
+                reachability_ratio: Some(0.5),
+                health: Some(TorrentHealth {
+                    score: 49,
+                    availability: 0.5,
+                    churn: 0.25,
+                    trend: "falling".to_string(),
+                }),
             }
         );
     }
 
+    #[test]
+    fn torrent_resource_should_hash_the_peer_ids_with_the_salt() {
+        let info = |peer| Info {
+            info_hash: InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+            seeders: 1,
+            completed: 2,
+            leechers: 3,
+            partial_seeds: 0,
+            peers: Some(vec![peer]),
+            corrupt: 0,
+            redundant: 0,
+            peers_with_corrupt_data: 0,
+            reachability_ratio: None,
+            health: None,
+        };
+
+        let peer_id = |salt| {
+            Torrent::with_hashed_peer_ids(info(sample_peer()), salt).peers.unwrap()[0]
+                .peer_id
+                .id
+                .clone()
+                .unwrap()
+        };
+
+        let raw = Peer::from(sample_peer()).peer_id;
+        let hashed = Torrent::with_hashed_peer_ids(info(sample_peer()), "salt");
+
+        assert_eq!(hashed.peers.unwrap()[0].peer_id.client, raw.client);
+        assert_ne!(peer_id("salt"), raw.id.unwrap());
+        assert_eq!(peer_id("salt"), peer_id("salt"));
+        assert_ne!(peer_id("salt"), peer_id("other salt"));
+    }
+
     #[test]
     fn torrent_resource_list_item_should_be_converted_from_the_basic_torrent_info() {
         assert_eq!(
@@ -146,12 +328,34 @@ mod tests {
                 seeders: 1,
                 completed: 2,
                 leechers: 3,
+                partial_seeds: 0,
             }),
             ListItem {
                 info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
                 seeders: 1,
                 completed: 2,
                 leechers: 3,
+                partial_seeds: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn torrent_resource_stale_list_item_should_be_converted_from_the_stale_torrent_info() {
+        assert_eq!(
+            StaleListItem::from(StaleInfo {
+                info_hash: InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+                seeders: 1,
+                completed: 2,
+                leechers: 3,
+                last_announce_at: DurationSinceUnixEpoch::new(1_669_397_478, 934),
+            }),
+            StaleListItem {
+                info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
+                seeders: 1,
+                completed: 2,
+                leechers: 3,
+                last_announce_at: 1_669_397_478,
             }
         );
     }
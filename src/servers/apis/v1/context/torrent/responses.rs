@@ -1,27 +1,77 @@
 //! API responses for the [`torrent`](crate::servers::apis::v1::context::torrent)
 //! API context.
-use axum::response::{IntoResponse, Json, Response};
-use serde_json::json;
+use axum::response::{Json, Response};
 
-use super::resources::torrent::{ListItem, Torrent};
-use crate::core::services::torrent::{BasicInfo, Info};
+use super::resources::changes::SwarmChanges;
+use super::resources::magnet::Magnet;
+use super::resources::torrent::{ListItem, StaleListItem, Torrent};
+use crate::core::services::torrent::{BasicInfo, Info, StaleInfo};
+use crate::core::torrent::changes::Page;
+use crate::servers::apis::v1::fields::Fields;
+use crate::servers::apis::v1::responses::{not_enabled_response, not_found_response};
+use crate::servers::apis::v1::streaming::{stream_response, Format};
+use crate::shared::bit_torrent::magnet::MagnetLink;
 
-/// `200` response that contains an array of
+/// `200` response that streams an array of
 /// [`ListItem`]
+/// resources in the requested format, with only the selected [`Fields`] if
+/// any.
+#[must_use]
+pub fn torrent_list_response(basic_infos: Vec<BasicInfo>, format: Format, fields: Option<Fields>) -> Response {
+    let items = basic_infos.into_iter().map(ListItem::from);
+
+    match fields {
+        Some(fields) => stream_response(items.map(move |item| fields.select(&item)), format),
+        None => stream_response(items, format),
+    }
+}
+
+/// `200` response that contains an array of
+/// [`StaleListItem`]
 /// resources as json.
-pub fn torrent_list_response(basic_infos: &[BasicInfo]) -> Json<Vec<ListItem>> {
-    Json(ListItem::new_vec(basic_infos))
+pub fn stale_torrent_list_response(stale_infos: &[StaleInfo]) -> Json<Vec<StaleListItem>> {
+    Json(StaleListItem::new_vec(stale_infos))
+}
+
+/// `200` response that contains the
+/// [`SwarmChanges`]
+/// resource as json.
+pub fn swarm_changes_response(page: Page) -> Json<SwarmChanges> {
+    Json(SwarmChanges::from(page))
 }
 
 /// `200` response that contains a
 /// [`Torrent`]
 /// resources as json.
-pub fn torrent_info_response(info: Info) -> Json<Torrent> {
-    Json(Torrent::from(info))
+pub fn torrent_info_response(info: Info, peer_id_hash_salt: Option<&str>) -> Json<Torrent> {
+    match peer_id_hash_salt {
+        Some(salt) => Json(Torrent::with_hashed_peer_ids(info, salt)),
+        None => Json(Torrent::from(info)),
+    }
 }
 
-/// `500` error response in plain text returned when a torrent is not found.
+/// `200` response that contains the
+/// [`Magnet`]
+/// resource as json.
+pub fn magnet_response(magnet_link: MagnetLink) -> Json<Magnet> {
+    Json(Magnet::from(magnet_link))
+}
+
+/// `404` error response returned when a torrent is not found.
 #[must_use]
 pub fn torrent_not_known_response() -> Response {
-    Json(json!("torrent not known")).into_response()
+    not_found_response("torrent not known")
+}
+
+/// `409` error response when the swarm changes log is not enabled.
+#[must_use]
+pub fn swarm_changes_not_enabled_response() -> Response {
+    not_enabled_response("swarm changes are not enabled")
+}
+
+/// `409` error response when there are no announce URLs to build the magnet
+/// links.
+#[must_use]
+pub fn magnet_links_not_enabled_response() -> Response {
+    not_enabled_response("magnet links are not enabled: there are no announce URLs")
 }
@@ -1,7 +1,10 @@
 //! API routes for the [`torrent`](crate::servers::apis::v1::context::torrent) API context.
 //!
 //! - `GET /torrent/:info_hash`
+//! - `GET /torrent/:info_hash/magnet`
 //! - `GET /torrents`
+//! - `GET /torrents/stale`
+//! - `GET /torrents/changes`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent).
 use std::sync::Arc;
@@ -9,16 +12,39 @@ use std::sync::Arc;
 use axum::routing::get;
 use axum::Router;
 
-use super::handlers::{get_torrent_handler, get_torrents_handler};
+use super::handlers::{
+    get_stale_torrents_handler, get_swarm_changes_handler, get_torrent_handler, get_torrent_magnet_handler, get_torrents_handler,
+};
 use crate::core::Tracker;
 
 /// It adds the routes to the router for the [`torrent`](crate::servers::apis::v1::context::torrent) API context.
-pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+pub fn add(
+    prefix: &str,
+    router: Router,
+    tracker: Arc<Tracker>,
+    peer_id_hash_salt: Option<String>,
+    announce_urls: Vec<String>,
+) -> Router {
     // Torrents
     router
         .route(
             &format!("{prefix}/torrent/:info_hash"),
-            get(get_torrent_handler).with_state(tracker.clone()),
+            get(get_torrent_handler).with_state((tracker.clone(), peer_id_hash_salt)),
+        )
+        .route(
+            &format!("{prefix}/torrent/:info_hash/magnet"),
+            get(get_torrent_magnet_handler).with_state(announce_urls),
+        )
+        .route(
+            &format!("{prefix}/torrents"),
+            get(get_torrents_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrents/stale"),
+            get(get_stale_torrents_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrents/changes"),
+            get(get_swarm_changes_handler).with_state(tracker),
         )
-        .route(&format!("{prefix}/torrents"), get(get_torrents_handler).with_state(tracker))
 }
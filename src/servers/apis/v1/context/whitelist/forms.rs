@@ -0,0 +1,31 @@
+//! API forms for the [`whitelist`](crate::servers::apis::v1::context::whitelist)
+//! API context.
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DefaultOnNull};
+
+/// This type contains the infohashes to check against the whitelist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WhitelistContainsForm {
+    /// The infohashes, in hexadecimal or base32.
+    pub info_hashes: Vec<String>,
+}
+
+/// This type contains the info needed to add a new whitelist rule.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddWhitelistRuleForm {
+    /// The pattern of the allowed torrents: `*` for all the torrents or an
+    /// infohash hexadecimal prefix followed by `*`.
+    pub pattern: String,
+
+    /// How long the rule will be valid in seconds. Use `None` (null in json)
+    /// for rules that do not expire.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default, rename = "seconds_valid")]
+    pub opt_seconds_valid: Option<u64>,
+
+    /// An optional description of the rule.
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    #[serde(default)]
+    pub comment: Option<String>,
+}
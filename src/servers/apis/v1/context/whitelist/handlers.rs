@@ -2,24 +2,121 @@
 //! API context.
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::extract::{Path, State};
-use axum::response::Response;
+use axum::extract::State;
+use axum::response::{IntoResponse, Json, Response};
+use serde::Deserialize;
+use torrust_tracker_clock::clock::Time;
 use torrust_tracker_primitives::info_hash::InfoHash;
 
+use super::forms::{AddWhitelistRuleForm, WhitelistContainsForm};
+use super::resources::{WhitelistMembership, WhitelistRule};
 use super::responses::{
-    failed_to_reload_whitelist_response, failed_to_remove_torrent_from_whitelist_response, failed_to_whitelist_torrent_response,
+    failed_to_add_whitelist_rule_response, failed_to_reload_whitelist_response, failed_to_remove_torrent_from_whitelist_response,
+    failed_to_remove_whitelist_rule_response, failed_to_whitelist_torrent_response,
+    invalid_whitelist_contains_info_hash_response, invalid_whitelist_rule_duration_response,
+    invalid_whitelist_rule_pattern_response, torrent_not_whitelisted_response, whitelist_membership_response,
+    whitelist_memberships_response, whitelist_response, whitelist_rule_response, whitelist_rules_response,
 };
+use crate::core::whitelist_rules::{Pattern, Rule};
 use crate::core::Tracker;
+use crate::servers::apis::v1::extractors::{self, Path, Query};
 use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
+use crate::servers::apis::v1::streaming::Format;
 use crate::servers::apis::InfoHashParam;
+use crate::CurrentClock;
+
+/// A container for the URL query parameters of the whitelist export.
+#[derive(Deserialize, Debug)]
+pub struct ExportQueryParams {
+    /// The format of the response: `json` (default) or `ndjson`.
+    #[serde(default)]
+    pub format: Format,
+}
+
+/// It handles the request to export the whitelisted torrents.
+///
+/// It returns a `200` response that streams the whitelisted infohashes.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#export-the-whitelist)
+/// for more information about this endpoint.
+pub async fn get_whitelist_handler(State(tracker): State<Arc<Tracker>>, params: Query<ExportQueryParams>) -> Response {
+    whitelist_response(tracker.get_whitelist().await, params.0.format)
+}
+
+/// It handles the request to check whether many torrents are in the
+/// whitelist at once.
+///
+/// It returns:
+///
+/// - `200` response with a json array of [`WhitelistMembership`] resources,
+///   in the same order as the infohashes in the request.
+/// - `422` if any of the infohashes is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#check-whether-torrents-are-whitelisted)
+/// for more information about this endpoint.
+pub async fn whitelist_contains_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extractors::Json(form): extractors::Json<WhitelistContainsForm>,
+) -> Response {
+    let mut info_hashes = Vec::with_capacity(form.info_hashes.len());
+
+    for info_hash in &form.info_hashes {
+        match InfoHash::from_str(info_hash) {
+            Ok(info_hash) => info_hashes.push(info_hash),
+            Err(_) => return invalid_whitelist_contains_info_hash_response(info_hash),
+        }
+    }
+
+    let whitelisted = tracker.are_info_hashes_whitelisted(&info_hashes).await;
+
+    whitelist_memberships_response(
+        form.info_hashes
+            .into_iter()
+            .zip(whitelisted)
+            .map(|(info_hash, whitelisted)| WhitelistMembership { info_hash, whitelisted })
+            .collect(),
+    )
+    .into_response()
+}
+
+/// It handles the request to check whether a torrent is in the whitelist.
+///
+/// It returns:
+///
+/// - `200` response with a [`WhitelistMembership`] resource if the torrent is
+///   whitelisted.
+/// - `400` if the infohash is not valid.
+/// - `404` if the torrent is not whitelisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#check-whether-a-torrent-is-whitelisted)
+/// for more information about this endpoint.
+pub async fn get_whitelisted_torrent_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    let Ok(parsed_info_hash) = InfoHash::from_str(&info_hash.0) else {
+        return invalid_info_hash_param_response(&info_hash.0);
+    };
+
+    if tracker.is_info_hash_whitelisted(&parsed_info_hash).await {
+        whitelist_membership_response(WhitelistMembership {
+            info_hash: info_hash.0,
+            whitelisted: true,
+        })
+        .into_response()
+    } else {
+        torrent_not_whitelisted_response()
+    }
+}
 
 /// It handles the request to add a torrent to the whitelist.
 ///
 /// It returns:
 ///
 /// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
-/// - `500` with serialized error in debug format if the torrent couldn't be whitelisted.
+/// - `5xx` with a json error if the torrent couldn't be whitelisted.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#add-a-torrent-to-the-whitelist)
 /// for more information about this endpoint.
@@ -41,7 +138,7 @@ pub async fn add_torrent_to_whitelist_handler(
 /// It returns:
 ///
 /// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
-/// - `500` with serialized error in debug format if the torrent couldn't be
+/// - `5xx` with a json error if the torrent couldn't be
 ///   removed from the whitelisted.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#remove-a-torrent-from-the-whitelist)
@@ -59,12 +156,77 @@ pub async fn remove_torrent_from_whitelist_handler(
     }
 }
 
+/// It handles the request to list the whitelist rules, including the expired
+/// ones.
+///
+/// It returns a `200` response with a json array of [`WhitelistRule`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#list-the-whitelist-rules)
+/// for more information about this endpoint.
+pub async fn get_whitelist_rules_handler(State(tracker): State<Arc<Tracker>>) -> Json<Vec<WhitelistRule>> {
+    whitelist_rules_response(tracker.get_whitelist_rules().await)
+}
+
+/// It handles the request to add a whitelist rule.
+///
+/// It returns:
+///
+/// - `200` response with the new [`WhitelistRule`] in json.
+/// - `422` if the pattern or the duration are not valid.
+/// - `5xx` with a json error if the rule couldn't be persisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#add-a-whitelist-rule)
+/// for more information about this endpoint.
+pub async fn add_whitelist_rule_handler(
+    State(tracker): State<Arc<Tracker>>,
+    extractors::Json(add_rule_form): extractors::Json<AddWhitelistRuleForm>,
+) -> Response {
+    let Ok(pattern) = Pattern::from_str(&add_rule_form.pattern) else {
+        return invalid_whitelist_rule_pattern_response(&add_rule_form.pattern);
+    };
+
+    let valid_until = match add_rule_form.opt_seconds_valid {
+        Some(seconds_valid) => match CurrentClock::now_add(&Duration::from_secs(seconds_valid)) {
+            Some(valid_until) => Some(valid_until),
+            None => return invalid_whitelist_rule_duration_response(seconds_valid),
+        },
+        None => None,
+    };
+
+    let rule = Rule {
+        pattern,
+        valid_until,
+        comment: add_rule_form.comment,
+    };
+
+    match tracker.add_whitelist_rule(rule.clone()).await {
+        Ok(id) => whitelist_rule_response(id, rule).into_response(),
+        Err(e) => failed_to_add_whitelist_rule_response(e),
+    }
+}
+
+/// It handles the request to remove a whitelist rule.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `5xx` with a json error if the rule couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#remove-a-whitelist-rule)
+/// for more information about this endpoint.
+pub async fn remove_whitelist_rule_handler(State(tracker): State<Arc<Tracker>>, Path(id): Path<u64>) -> Response {
+    match tracker.remove_whitelist_rule(id).await {
+        Ok(_) => ok_response(),
+        Err(e) => failed_to_remove_whitelist_rule_response(e),
+    }
+}
+
 /// It handles the request to reload the torrent whitelist from the database.
 ///
 /// It returns:
 ///
 /// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
-/// - `500` with serialized error in debug format if the torrent whitelist
+/// - `5xx` with a json error if the torrent whitelist
 ///   couldn't be reloaded from the database.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::whitelist#reload-the-whitelist)
@@ -10,6 +10,11 @@
 //! Common tracker requests like `announce` and `scrape` are limited to the
 //! torrents in the whitelist. The whitelist can be updated using the API.
 //!
+//! Torrents can also be allowed with [whitelist rules](crate::core::whitelist_rules):
+//! a temporary "allow all" window (`*`) or all the torrents whose infohash
+//! starts with a prefix (`c1277613*`). Rules can have an expiration date and
+//! a comment.
+//!
 //! > **NOTICE**: the whitelist is only used when the tracker is configured to
 //! > in `listed` or `private_listed` modes. Refer to the
 //! > [configuration crate documentation](https://docs.rs/torrust-tracker-configuration)
@@ -20,9 +25,127 @@
 //!
 //! # Endpoints
 //!
+//! - [Export the whitelist](#export-the-whitelist)
+//! - [Check whether torrents are whitelisted](#check-whether-torrents-are-whitelisted)
+//! - [Check whether a torrent is whitelisted](#check-whether-a-torrent-is-whitelisted)
 //! - [Add a torrent to the whitelist](#add-a-torrent-to-the-whitelist)
 //! - [Remove a torrent from the whitelist](#remove-a-torrent-from-the-whitelist)
 //! - [Reload the whitelist](#reload-the-whitelist)
+//! - [List the whitelist rules](#list-the-whitelist-rules)
+//! - [Add a whitelist rule](#add-a-whitelist-rule)
+//! - [Remove a whitelist rule](#remove-a-whitelist-rule)
+//!
+//! # Export the whitelist
+//!
+//! `GET /whitelist`
+//!
+//! It returns all the whitelisted infohashes, sorted. The response body is
+//! streamed, so the serialized whitelist is not buffered in memory. See the
+//! [`streaming`](crate::servers::apis::v1::streaming) module.
+//!
+//! **Query parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `format` | `json` or `ndjson` | The format of the response. Default is `json` | No | `ndjson`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/whitelist?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     "5452869be36f9f3350ccee6b4544e7e76caaadab"
+//! ]
+//! ```
+//!
+//! # Check whether torrents are whitelisted
+//!
+//! `POST /whitelist/contains`
+//!
+//! It checks whether each one of the infohashes is in the whitelist, in one
+//! request. For example, the Index can validate a bulk upload without
+//! requesting every torrent. Only the whitelisted infohashes are checked,
+//! not the [whitelist rules](crate::core::whitelist_rules). The results are
+//! in the same order as the infohashes in the request.
+//!
+//! **POST parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hashes` | array of strings | The Info Hashes v1, in hex or base32 | Yes | `["5452869be36f9f3350ccee6b4544e7e76caaadab"]`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/whitelist/contains?token=MyAccessToken \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "info_hashes": [
+//!              "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!              "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"
+//!            ]
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!         "whitelisted": true
+//!     },
+//!     {
+//!         "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+//!         "whitelisted": false
+//!     }
+//! ]
+//! ```
+//!
+//! It returns a `422` response if any of the infohashes is not valid.
+//!
+//! # Check whether a torrent is whitelisted
+//!
+//! `GET /whitelist/:info_hash`
+//!
+//! It checks whether one torrent is in the whitelist. It's a cheap in-memory
+//! check for support tooling. Like the bulk check, it does not take into
+//! account the [whitelist rules](crate::core::whitelist_rules).
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/whitelist/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!     "whitelisted": true
+//! }
+//! ```
+//!
+//! **Not found response** `404`
+//!
+//! ```json
+//! {
+//!     "code": "not_found",
+//!     "message": "torrent not whitelisted"
+//! }
+//! ```
 //!
 //! # Add a torrent to the whitelist
 //!
@@ -78,7 +201,7 @@
 //!
 //! # Reload the whitelist
 //!
-//! It reloads the whitelist from the database.
+//! It reloads the whitelist and the whitelist rules from the database.
 //!
 //! **Example request**
 //!
@@ -93,6 +216,96 @@
 //!     "status": "ok"
 //! }
 //! ```
+//!
+//! # List the whitelist rules
+//!
+//! `GET /whitelist/rules`
+//!
+//! It lists all the whitelist rules, including the expired ones.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/whitelist/rules?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "id": 1,
+//!         "pattern": "c1277613*",
+//!         "expiry_time": "2024-10-16T09:00:00Z",
+//!         "comment": "Community releases"
+//!     }
+//! ]
+//! ```
+//!
+//! # Add a whitelist rule
+//!
+//! `POST /whitelist/rules`
+//!
+//! It adds a rule to allow the torrents matching the pattern.
+//!
+//! **POST parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `pattern` | string | `*` or an infohash hexadecimal prefix followed by `*` | Yes | `*` or `c1277613*`
+//! `seconds_valid` | positive integer or `null` | The number of seconds the rule will be valid. | No | `3600` or `null`
+//! `comment` | string or `null` | A description of the rule. | No | `Community releases`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/whitelist/rules?token=MyAccessToken \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "pattern": "*",
+//!            "seconds_valid": 3600,
+//!            "comment": "Open weekend"
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "id": 2,
+//!     "pattern": "*",
+//!     "expiry_time": "2024-10-19T10:00:00Z",
+//!     "comment": "Open weekend"
+//! }
+//! ```
+//!
+//! # Remove a whitelist rule
+//!
+//! `DELETE /whitelist/rules/:id`
+//!
+//! It removes a whitelist rule.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `id` | positive integer | The rule id | Yes | `2`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/whitelist/rules/2?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod forms;
 pub mod handlers;
+pub mod resources;
 pub mod responses;
 pub mod routes;
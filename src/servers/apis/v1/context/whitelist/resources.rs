@@ -0,0 +1,70 @@
+//! API resources for the [`whitelist`](crate::servers::apis::v1::context::whitelist)
+//! API context.
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_clock::conv::convert_from_timestamp_to_datetime_utc;
+
+use crate::core::whitelist_rules::Rule;
+
+/// Whether a torrent is in the whitelist.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct WhitelistMembership {
+    /// The infohash, as it was sent in the request.
+    pub info_hash: String,
+    /// `true` if the torrent is in the whitelist.
+    pub whitelisted: bool,
+}
+
+/// A whitelist rule.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct WhitelistRule {
+    /// The rule id.
+    pub id: u64,
+    /// The pattern of the allowed torrents, for example `*` or `c1277613*`.
+    pub pattern: String,
+    /// The time when the rule expires, in RFC 3339 format. `None` for rules
+    /// that never expire.
+    pub expiry_time: Option<String>,
+    /// The description of the rule.
+    pub comment: Option<String>,
+}
+
+impl From<(u64, Rule)> for WhitelistRule {
+    fn from((id, rule): (u64, Rule)) -> Self {
+        Self {
+            id,
+            pattern: rule.pattern.to_string(),
+            expiry_time: rule.valid_until.map(|valid_until| {
+                convert_from_timestamp_to_datetime_utc(valid_until).to_rfc3339_opts(SecondsFormat::Secs, true)
+            }),
+            comment: rule.comment,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::WhitelistRule;
+    use crate::core::whitelist_rules::{Pattern, Rule};
+
+    #[test]
+    fn whitelist_rule_resource_should_be_converted_from_a_whitelist_rule() {
+        let rule = Rule {
+            pattern: Pattern::Prefix("c1277613".to_string()),
+            valid_until: Some(Duration::from_secs(1_729_069_200)),
+            comment: Some("Community releases".to_string()),
+        };
+
+        assert_eq!(
+            WhitelistRule::from((1, rule)),
+            WhitelistRule {
+                id: 1,
+                pattern: "c1277613*".to_string(),
+                expiry_time: Some("2024-10-16T09:00:00Z".to_string()),
+                comment: Some("Community releases".to_string()),
+            }
+        );
+    }
+}
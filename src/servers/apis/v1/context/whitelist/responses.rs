@@ -2,24 +2,100 @@
 //! API context.
 use std::error::Error;
 
-use axum::response::Response;
+use axum::response::{Json, Response};
 
-use crate::servers::apis::v1::responses::unhandled_rejection_response;
+use torrust_tracker_primitives::info_hash::InfoHash;
 
-/// `500` error response when a torrent cannot be removed from the whitelist.
+use super::resources::{WhitelistMembership, WhitelistRule};
+use crate::core::whitelist_rules::Rule;
+use crate::servers::apis::v1::responses::{failed_response, not_found_response, validation_failed_response};
+use crate::servers::apis::v1::streaming::{stream_response, Format};
+
+/// `200` response that streams the whitelisted infohashes in the requested
+/// format.
+#[must_use]
+pub fn whitelist_response(whitelist: Vec<InfoHash>, format: Format) -> Response {
+    stream_response(whitelist.into_iter().map(|info_hash| info_hash.to_hex_string()), format)
+}
+
+/// `200` response that contains an array of [`WhitelistMembership`]
+/// resources as json.
+pub fn whitelist_memberships_response(memberships: Vec<WhitelistMembership>) -> Json<Vec<WhitelistMembership>> {
+    Json(memberships)
+}
+
+/// `200` response that contains the [`WhitelistMembership`] resource of a
+/// whitelisted torrent as json.
+pub fn whitelist_membership_response(membership: WhitelistMembership) -> Json<WhitelistMembership> {
+    Json(membership)
+}
+
+/// `404` error response when the torrent is not in the whitelist.
+#[must_use]
+pub fn torrent_not_whitelisted_response() -> Response {
+    not_found_response("torrent not whitelisted")
+}
+
+/// `422` error response when one of the infohashes to check is not valid.
+#[must_use]
+pub fn invalid_whitelist_contains_info_hash_response(info_hash: &str) -> Response {
+    validation_failed_response(
+        "info_hashes",
+        &format!("Invalid infohash: \"{info_hash}\", expected a 40 character long hex or a 32 character long base32 string"),
+    )
+}
+
+/// `200` response that contains an array of [`WhitelistRule`] resources as json.
+pub fn whitelist_rules_response(rules: Vec<(u64, Rule)>) -> Json<Vec<WhitelistRule>> {
+    Json(rules.into_iter().map(WhitelistRule::from).collect())
+}
+
+/// `200` response that contains the [`WhitelistRule`] resource as json.
+pub fn whitelist_rule_response(id: u64, rule: Rule) -> Json<WhitelistRule> {
+    Json(WhitelistRule::from((id, rule)))
+}
+
+/// `422` error response when the whitelist rule pattern is not valid.
+#[must_use]
+pub fn invalid_whitelist_rule_pattern_response(pattern: &str) -> Response {
+    validation_failed_response(
+        "pattern",
+        &format!("Invalid whitelist rule pattern: \"{pattern}\", expected \"*\" or an infohash prefix followed by \"*\""),
+    )
+}
+
+/// `422` error response when the whitelist rule duration is too long.
+#[must_use]
+pub fn invalid_whitelist_rule_duration_response(duration: u64) -> Response {
+    validation_failed_response("seconds_valid", &format!("Invalid whitelist rule duration: \"{duration}\""))
+}
+
+/// Error response when a torrent cannot be removed from the whitelist.
+#[must_use]
+pub fn failed_to_remove_torrent_from_whitelist_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove torrent from whitelist", &e)
+}
+
+/// Error response when a torrent cannot be added to the whitelist.
+#[must_use]
+pub fn failed_to_whitelist_torrent_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to whitelist torrent", &e)
+}
+
+/// Error response when the whitelist cannot be reloaded from the database.
 #[must_use]
-pub fn failed_to_remove_torrent_from_whitelist_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to remove torrent from whitelist: {e}"))
+pub fn failed_to_reload_whitelist_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to reload whitelist", &e)
 }
 
-/// `500` error response when a torrent cannot be added to the whitelist.
+/// Error response when a whitelist rule cannot be added.
 #[must_use]
-pub fn failed_to_whitelist_torrent_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to whitelist torrent: {e}"))
+pub fn failed_to_add_whitelist_rule_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to add whitelist rule", &e)
 }
 
-/// `500` error response when the whitelist cannot be reloaded from the database.
+/// Error response when a whitelist rule cannot be removed.
 #[must_use]
-pub fn failed_to_reload_whitelist_response<E: Error>(e: E) -> Response {
-    unhandled_rejection_response(format!("failed to reload whitelist: {e}"))
+pub fn failed_to_remove_whitelist_rule_response<E: Error + 'static>(e: E) -> Response {
+    failed_response("failed to remove whitelist rule", &e)
 }
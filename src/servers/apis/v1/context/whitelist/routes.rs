@@ -1,8 +1,14 @@
 //! API routes for the [`whitelist`](crate::servers::apis::v1::context::whitelist) API context.
 //!
+//! - `GET /whitelist`
+//! - `POST /whitelist/contains`
+//! - `GET /whitelist/:info_hash`
 //! - `POST /whitelist/:info_hash`
 //! - `DELETE /whitelist/:info_hash`
 //! - `GET /whitelist/reload`
+//! - `GET /whitelist/rules`
+//! - `POST /whitelist/rules`
+//! - `DELETE /whitelist/rules/:id`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent).
 use std::sync::Arc;
@@ -10,7 +16,11 @@ use std::sync::Arc;
 use axum::routing::{delete, get, post};
 use axum::Router;
 
-use super::handlers::{add_torrent_to_whitelist_handler, reload_whitelist_handler, remove_torrent_from_whitelist_handler};
+use super::handlers::{
+    add_torrent_to_whitelist_handler, add_whitelist_rule_handler, get_whitelist_handler, get_whitelist_rules_handler,
+    get_whitelisted_torrent_handler, reload_whitelist_handler, remove_torrent_from_whitelist_handler,
+    remove_whitelist_rule_handler, whitelist_contains_handler,
+};
 use crate::core::Tracker;
 
 /// It adds the routes to the router for the [`whitelist`](crate::servers::apis::v1::context::whitelist) API context.
@@ -19,6 +29,15 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
 
     router
         // Whitelisted torrents
+        .route(&prefix, get(get_whitelist_handler).with_state(tracker.clone()))
+        .route(
+            &format!("{prefix}/contains"),
+            post(whitelist_contains_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/:info_hash"),
+            get(get_whitelisted_torrent_handler).with_state(tracker.clone()),
+        )
         .route(
             &format!("{prefix}/:info_hash"),
             post(add_torrent_to_whitelist_handler).with_state(tracker.clone()),
@@ -27,6 +46,17 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
             &format!("{prefix}/:info_hash"),
             delete(remove_torrent_from_whitelist_handler).with_state(tracker.clone()),
         )
+        // Whitelist rules
+        .route(
+            &format!("{prefix}/rules"),
+            get(get_whitelist_rules_handler)
+                .post(add_whitelist_rule_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/rules/:id"),
+            delete(remove_whitelist_rule_handler).with_state(tracker.clone()),
+        )
         // Whitelist commands
         .route(&format!("{prefix}/reload"), get(reload_whitelist_handler).with_state(tracker))
 }
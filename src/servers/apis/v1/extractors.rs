@@ -0,0 +1,151 @@
+//! Extractors for the API v1 handlers.
+//!
+//! They are wrappers over the axum extractors that return the common
+//! [`ErrorResponse`] when the request can't be parsed, instead of the plain
+//! text axum rejections. When the rejection is caused by a field, the field is
+//! included in the error details.
+use std::error::Error;
+
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{JsonRejection, PathRejection};
+use axum::extract::{FromRequest, FromRequestParts};
+use axum::http::StatusCode;
+
+use super::responses::{ErrorCode, ErrorResponse};
+
+/// Path params extractor. Refer to [`axum::extract::Path`].
+#[derive(FromRequestParts, Debug)]
+#[from_request(via(axum::extract::Path), rejection(ErrorResponse))]
+pub struct Path<T>(pub T);
+
+/// Query params extractor. It supports multiple values for the same param.
+/// Refer to [`axum_extra::extract::Query`].
+#[derive(FromRequestParts, Debug)]
+#[from_request(via(axum_extra::extract::Query), rejection(ErrorResponse))]
+pub struct Query<T>(pub T);
+
+/// Json request body extractor. Refer to [`axum::Json`].
+#[derive(FromRequest, Debug)]
+#[from_request(via(axum::Json), rejection(ErrorResponse))]
+pub struct Json<T>(pub T);
+
+impl From<PathRejection> for ErrorResponse {
+    fn from(rejection: PathRejection) -> Self {
+        let PathRejection::FailedToDeserializePathParams(e) = &rejection else {
+            return ErrorResponse::new(ErrorCode::InternalError, rejection.body_text());
+        };
+
+        let field = match e.kind() {
+            ErrorKind::ParseErrorAtKey { key, .. } | ErrorKind::InvalidUtf8InPathParam { key } => Some(key.clone()),
+            ErrorKind::ParseErrorAtIndex { index, .. } => Some(index.to_string()),
+            _ => None,
+        };
+
+        let code = if rejection.status() == StatusCode::BAD_REQUEST {
+            ErrorCode::InvalidParam
+        } else {
+            ErrorCode::InternalError
+        };
+
+        let response = ErrorResponse::new(code, rejection.body_text());
+
+        match field {
+            Some(field) => response.with_field_error(field, e.kind().to_string()),
+            None => response,
+        }
+    }
+}
+
+impl From<axum_extra::extract::QueryRejection> for ErrorResponse {
+    fn from(rejection: axum_extra::extract::QueryRejection) -> Self {
+        ErrorResponse::new(
+            ErrorCode::InvalidParam,
+            format!("Failed to deserialize query string: {rejection}"),
+        )
+    }
+}
+
+impl From<JsonRejection> for ErrorResponse {
+    fn from(rejection: JsonRejection) -> Self {
+        let code = match &rejection {
+            JsonRejection::JsonDataError(_) => ErrorCode::ValidationFailed,
+            JsonRejection::MissingJsonContentType(_) => ErrorCode::UnsupportedMediaType,
+            _ if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+            _ => ErrorCode::InvalidBody,
+        };
+
+        let response = ErrorResponse::new(code, rejection.body_text());
+
+        // The path of the invalid field is only known for the errors in the
+        // data, not for syntax errors.
+        match find_source::<serde_path_to_error::Error<serde_json::Error>>(&rejection) {
+            Some(e) if code == ErrorCode::ValidationFailed && e.path().iter().next().is_some() => {
+                response.with_field_error(e.path().to_string(), e.inner().to_string())
+            }
+            _ => response,
+        }
+    }
+}
+
+/// It returns the first error of type `T` in the chain of sources of the
+/// error.
+fn find_source<'a, T: Error + 'static>(e: &'a (dyn Error + 'static)) -> Option<&'a T> {
+    let mut current = Some(e);
+
+    while let Some(e) = current {
+        if let Some(e) = e.downcast_ref::<T>() {
+            return Some(e);
+        }
+
+        current = e.source();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::FromRequest;
+    use axum::http::{header, Request};
+    use serde::Deserialize;
+
+    use super::Json;
+    use crate::servers::apis::v1::responses::{ErrorCode, ErrorResponse, FieldError};
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Form {
+        seconds_valid: u64,
+    }
+
+    async fn extract_form(body: &'static str) -> Result<Json<Form>, ErrorResponse> {
+        let request = Request::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body))
+            .unwrap();
+
+        Json::<Form>::from_request(request, &()).await
+    }
+
+    #[tokio::test]
+    async fn it_should_include_the_path_of_the_invalid_field_in_the_error_details() {
+        let error = extract_form(r#"{"seconds_valid": "abc"}"#).await.unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(
+            error.details,
+            vec![FieldError {
+                field: "seconds_valid".to_string(),
+                message: "invalid type: string \"abc\", expected u64 at line 1 column 23".to_string()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_not_include_any_field_for_syntax_errors() {
+        let error = extract_form(r#"{"seconds_valid": "#).await.unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::InvalidBody);
+        assert!(error.details.is_empty());
+    }
+}
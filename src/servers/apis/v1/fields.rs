@@ -0,0 +1,122 @@
+//! Selection of the fields of the API resources.
+//!
+//! Clients polling the API every few seconds, like dashboards, usually need
+//! only some of the fields. The stats and torrents endpoints accept a
+//! comma-separated list of field names in the `fields` query parameter, and
+//! only those fields are included in the response. For example:
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&fields=torrents,seeders,leechers"
+//! ```
+//!
+//! ```json
+//! {
+//!   "torrents": 0,
+//!   "seeders": 0,
+//!   "leechers": 0
+//! }
+//! ```
+//!
+//! The unknown field names are ignored. All the fields are included when the
+//! parameter is missing or empty.
+//!
+//! > **NOTICE**: the API responses are compressed when the client accepts it,
+//! > with the `Accept-Encoding` request header (`gzip`, `deflate`, `br` or
+//! > `zstd`). For example: `curl --compressed ...`.
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The selected fields, from the comma-separated `fields` query parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fields(Vec<String>);
+
+impl Fields {
+    /// It parses the comma-separated list of field names. It returns `None`
+    /// when there is no field name, meaning all the fields are included.
+    #[must_use]
+    pub fn parse(fields: &str) -> Option<Self> {
+        let names: Vec<String> = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        if names.is_empty() {
+            None
+        } else {
+            Some(Self(names))
+        }
+    }
+
+    /// It returns `true` if the field is selected.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|field| field == name)
+    }
+
+    /// It returns the resource as json with only the selected fields. The
+    /// fields keep the resource declaration order.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the resource can't be serialized.
+    #[must_use]
+    pub fn select<T: Serialize>(&self, resource: &T) -> serde_json::Value {
+        let mut value = serde_json::to_value(resource).expect("it should serialize the API resource");
+
+        if let serde_json::Value::Object(map) = &mut value {
+            map.retain(|name, _| self.contains(name));
+        }
+
+        value
+    }
+}
+
+/// Serde deserialization decorator for the optional `fields` query parameter.
+pub(crate) fn fields_param<'de, D>(de: D) -> Result<Option<Fields>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let opt = Option::<String>::deserialize(de)?;
+    Ok(opt.as_deref().and_then(Fields::parse))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::Fields;
+
+    #[derive(Serialize)]
+    struct Resource {
+        a: u32,
+        b: u32,
+        c: u32,
+    }
+
+    #[test]
+    fn it_should_parse_a_comma_separated_list_of_field_names() {
+        let fields = Fields::parse("a, c,").unwrap();
+
+        assert!(fields.contains("a"));
+        assert!(!fields.contains("b"));
+        assert!(fields.contains("c"));
+    }
+
+    #[test]
+    fn it_should_select_all_the_fields_when_there_is_no_field_name() {
+        assert_eq!(Fields::parse(""), None);
+        assert_eq!(Fields::parse(" , "), None);
+    }
+
+    #[test]
+    fn it_should_keep_only_the_selected_fields_of_a_resource_in_declaration_order() {
+        let fields = Fields::parse("c,a,unknown").unwrap();
+
+        let selected = fields.select(&Resource { a: 1, b: 2, c: 3 });
+
+        assert_eq!(selected, json!({ "a": 1, "c": 3 }));
+        assert_eq!(serde_json::to_string(&selected).unwrap(), r#"{"a":1,"c":3}"#);
+    }
+}
@@ -0,0 +1,116 @@
+//! Audit middleware for the API.
+//!
+//! It records the state-changing requests in the [audit log](crate::core::audit),
+//! after they have been handled, so that the response status can be recorded
+//! too.
+//!
+//! A request changes the state of the tracker when its method is not `GET`,
+//! or when it reloads data from the database, for example,
+//! `GET /api/v1/whitelist/reload`.
+//!
+//! Requests rejected by the [`auth`](crate::servers::apis::v1::middlewares::auth)
+//! middleware are not recorded, because they don't have a valid token.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{self, ConnectInfo};
+use axum::http::{Method, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use camino::Utf8PathBuf;
+use torrust_tracker_clock::clock::Time;
+
+use super::auth::Authenticated;
+use crate::core::{audit, Tracker};
+use crate::servers::apis::AUDIT_LOG_TARGET;
+use crate::CurrentClock;
+
+#[derive(Clone)]
+pub struct State {
+    pub tracker: Arc<Tracker>,
+    /// Optional file where the entries are appended, in addition to the
+    /// database.
+    pub audit_log_path: Option<Utf8PathBuf>,
+}
+
+/// Middleware that records the state-changing requests in the audit log.
+pub async fn audit(
+    extract::State(state): extract::State<State>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    if !changes_state(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let Some(authenticated) = request.extensions().get::<Authenticated>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    let response = next.run(request).await;
+
+    let entry = audit::Entry {
+        time: CurrentClock::now(),
+        token_label: authenticated.label,
+        source_ip: connect_info.map(|ConnectInfo(address)| address.ip()),
+        method,
+        path,
+        status: response.status().as_u16(),
+    };
+
+    record(&state, &entry);
+
+    response
+}
+
+fn changes_state(method: &Method, path: &str) -> bool {
+    *method != Method::GET || path.ends_with("/reload")
+}
+
+/// It stores the entry in the database and in the audit log file, if it's
+/// enabled. Failures are logged but they don't change the response, because
+/// the action has already been executed.
+fn record(state: &State, entry: &audit::Entry) {
+    tracing::info!(
+        target: AUDIT_LOG_TARGET,
+        token_label = %entry.token_label,
+        source_ip = ?entry.source_ip,
+        method = %entry.method,
+        path = %entry.path,
+        status = entry.status,
+        "Administrative action"
+    );
+
+    if let Err(err) = state.tracker.add_audit_log_entry(entry) {
+        tracing::error!(target: AUDIT_LOG_TARGET, %err, "Failed to store the audit log entry in the database");
+    }
+
+    if let Some(path) = &state.audit_log_path {
+        if let Err(err) = audit::append_to_file(path, entry) {
+            tracing::error!(target: AUDIT_LOG_TARGET, %path, %err, "Failed to write the audit log entry to the file");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::changes_state;
+
+    #[test]
+    fn it_should_only_audit_the_requests_that_change_the_tracker_state() {
+        assert!(changes_state(
+            &Method::POST,
+            "/api/v1/whitelist/9c38422213e30bff212b30c360d26f9a02136422"
+        ));
+        assert!(changes_state(&Method::DELETE, "/api/v1/key/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"));
+        assert!(changes_state(&Method::GET, "/api/v1/keys/reload"));
+        assert!(!changes_state(&Method::GET, "/api/v1/stats"));
+        assert!(!changes_state(&Method::GET, "/api/v1/audit-log"));
+    }
+}
@@ -18,19 +18,36 @@
 //! admin = "MyAccessToken"
 //! ```
 //!
-//! All the tokes have the same permissions, so it is not possible to have
-//! different permissions for different tokens. The label is only used to
-//! identify the token.
+//! All the tokes have the same permissions, except for the administrative
+//! endpoints, which only accept the `admin_access_tokens`:
+//!
+//! ```toml
+//! [http_api.admin_access_tokens]
+//! incident-response = "MyAdminAccessToken"
+//! ```
+//!
+//! Admin tokens are valid for all the endpoints. The label is only used to
+//! identify the token, for example, in the audit log.
+//!
+//! When an external authentication provider is configured (`[http_api.auth]`),
+//! requests can also be authenticated with a bearer token issued by the
+//! provider, instead of the `token` param:
+//!
+//! `Authorization: Bearer <token>`
+//!
+//! Refer to the [`bearer`](crate::servers::apis::v1::middlewares::bearer)
+//! module for more information.
 use std::sync::Arc;
 
 use axum::extract::{self};
-use axum::http::Request;
+use axum::http::{header, HeaderMap, Request};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
-use torrust_tracker_configuration::AccessTokens;
+use torrust_tracker_configuration::{AccessTokens, ApiPermission};
 
-use crate::servers::apis::v1::responses::unhandled_rejection_response;
+use super::bearer::{self, Provider};
+use crate::servers::apis::v1::responses::{ErrorCode, ErrorResponse};
 
 /// Container for the `token` extracted from the query params.
 #[derive(Deserialize, Debug)]
@@ -41,32 +58,91 @@ pub struct QueryParams {
 #[derive(Clone, Debug)]
 pub struct State {
     pub access_tokens: Arc<AccessTokens>,
+    pub admin_access_tokens: Arc<AccessTokens>,
+    /// The external authentication provider for bearer tokens, if any.
+    pub provider: Option<Arc<Provider>>,
+}
+
+/// The client authenticated by the [`auth`] middleware. It's added to the
+/// request extensions so that handlers can know who made the request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Authenticated {
+    /// The label of the token in the configuration. For bearer tokens, it's
+    /// `bearer:` followed by the subject of the token.
+    pub label: String,
+    /// Whether the token is one of the `admin_access_tokens`.
+    pub admin: bool,
 }
 
 /// Middleware for authentication using a "token" GET param.
 /// The token must be one of the tokens in the tracker [HTTP API configuration](torrust_tracker_configuration::HttpApi).
+///
+/// Requests with a bearer token are authenticated with the external
+/// authentication provider instead.
 pub async fn auth(
     extract::State(state): extract::State<State>,
     extract::Query(params): extract::Query<QueryParams>,
-    request: Request<axum::body::Body>,
+    mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
+    if let Some(token) = bearer_token(request.headers()) {
+        let Some(provider) = &state.provider else {
+            return AuthError::TokenNotValid.into_response();
+        };
+
+        let authenticated = match provider.verify(&token).await {
+            Ok(grant) => Authenticated {
+                label: format!("bearer:{}", grant.subject.unwrap_or_default()),
+                admin: grant.permission == ApiPermission::Admin,
+            },
+            Err(bearer::Error::InsufficientScope) => return AuthError::InsufficientScope.into_response(),
+            Err(e) => {
+                tracing::debug!("Bearer token rejected: {e}");
+                return AuthError::TokenNotValid.into_response();
+            }
+        };
+
+        request.extensions_mut().insert(authenticated);
+
+        return next.run(request).await;
+    }
+
     let Some(token) = params.token else {
         return AuthError::Unauthorized.into_response();
     };
 
-    if !authenticate(&token, &state.access_tokens) {
-        return AuthError::TokenNotValid.into_response();
-    }
+    let authenticated = match authenticate(&token, &state.admin_access_tokens) {
+        Some(label) => Authenticated { label, admin: true },
+        None => match authenticate(&token, &state.access_tokens) {
+            Some(label) => Authenticated { label, admin: false },
+            None => return AuthError::TokenNotValid.into_response(),
+        },
+    };
+
+    request.extensions_mut().insert(authenticated);
 
     next.run(request).await
 }
 
+/// Middleware for the administrative endpoints. It must run after the
+/// [`auth`] middleware and it only lets through requests authenticated with
+/// one of the `admin_access_tokens`.
+pub async fn admin(request: Request<axum::body::Body>, next: Next) -> Response {
+    match request.extensions().get::<Authenticated>() {
+        Some(authenticated) if authenticated.admin => next.run(request).await,
+        _ => AuthError::Forbidden.into_response(),
+    }
+}
+
 enum AuthError {
     /// Missing token for authentication.
     Unauthorized,
     /// Token was provided but it is not valid.
     TokenNotValid,
+    /// Token is valid but it is not an admin token.
+    Forbidden,
+    /// Bearer token is valid but it does not have any of the configured scopes.
+    InsufficientScope,
 }
 
 impl IntoResponse for AuthError {
@@ -74,22 +150,52 @@ impl IntoResponse for AuthError {
         match self {
             AuthError::Unauthorized => unauthorized_response(),
             AuthError::TokenNotValid => token_not_valid_response(),
+            AuthError::Forbidden => forbidden_response(),
+            AuthError::InsufficientScope => insufficient_scope_response(),
         }
     }
 }
 
-fn authenticate(token: &str, tokens: &AccessTokens) -> bool {
-    tokens.values().any(|t| t == token)
+/// It returns the label of the token, if it's one of the `tokens`.
+fn authenticate(token: &str, tokens: &AccessTokens) -> Option<String> {
+    tokens
+        .iter()
+        .find(|(_label, t)| *t == token)
+        .map(|(label, _token)| label.clone())
+}
+
+/// It returns the token of the `Authorization: Bearer <token>` header, if any.
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
 }
 
-/// `500` error response returned when the token is missing.
+/// `401` error response returned when the token is missing.
 #[must_use]
 pub fn unauthorized_response() -> Response {
-    unhandled_rejection_response("unauthorized".to_string())
+    ErrorResponse::new(ErrorCode::Unauthorized, "unauthorized").into_response()
 }
 
-/// `500` error response when the provided token is not valid.
+/// `401` error response when the provided token is not valid.
 #[must_use]
 pub fn token_not_valid_response() -> Response {
-    unhandled_rejection_response("token not valid".to_string())
+    ErrorResponse::new(ErrorCode::InvalidToken, "token not valid").into_response()
+}
+
+/// `403` error response when the token is valid but the endpoint requires an
+/// admin token.
+#[must_use]
+pub fn forbidden_response() -> Response {
+    ErrorResponse::new(ErrorCode::Forbidden, "admin token required").into_response()
+}
+
+/// `403` error response when the bearer token is valid but it does not have
+/// any of the configured scopes.
+#[must_use]
+pub fn insufficient_scope_response() -> Response {
+    ErrorResponse::new(ErrorCode::Forbidden, "insufficient scope").into_response()
 }
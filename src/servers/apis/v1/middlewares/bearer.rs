@@ -0,0 +1,515 @@
+//! Bearer tokens issued by an external authentication provider.
+//!
+//! When the [`ApiAuth`] configuration is set, the API also accepts requests
+//! with an `Authorization: Bearer <token>` header. The token is verified with
+//! one of the providers:
+//!
+//! - [`Verifier::Jwks`]: the token is a JWT signed by one of the keys in a
+//!   static JWKS file. The signature, the expiration and, when they are
+//!   configured, the issuer and the audience are checked locally. The
+//!   signature algorithm is the `alg` of the key, never the one in the token
+//!   header, so the keys without an `alg` don't verify any token.
+//! - [`Verifier::Introspection`]: the token is sent to an OAuth 2.0 token
+//!   introspection endpoint (RFC 7662), which tells whether it's active and
+//!   which scopes it has. The result for an active token is reused for
+//!   `introspection_cache_ttl` seconds.
+//!
+//! The scopes of the token are mapped to an [`ApiPermission`] with the
+//! `scopes` table of the configuration.
+//!
+//! ```toml
+//! [http_api.auth]
+//! introspection_url = "https://auth.example.com/oauth2/introspect"
+//! client_id = "torrust-tracker"
+//! client_secret = "MyClientSecret"
+//!
+//! [http_api.auth.scopes]
+//! "tracker:read" = "access"
+//! "tracker:admin" = "admin"
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use thiserror::Error;
+use torrust_tracker_configuration::{ApiAuth, ApiPermission};
+use url::Url;
+
+/// Errors verifying a bearer token.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid token: {0}")]
+    InvalidToken(String),
+
+    #[error("the token does not have any of the configured scopes")]
+    InsufficientScope,
+
+    #[error("unable to reach the introspection endpoint: {0}")]
+    Introspection(#[from] reqwest::Error),
+}
+
+/// The identity and the permission of a verified bearer token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    /// The subject (`sub` claim) of the token, if any.
+    pub subject: Option<String>,
+    pub permission: ApiPermission,
+}
+
+/// The claims of a token, either decoded from the JWT or returned by the
+/// introspection endpoint.
+#[derive(Deserialize, Debug, Default, Clone)]
+struct Claims {
+    /// Only returned by the introspection endpoint.
+    #[serde(default)]
+    active: Option<bool>,
+    sub: Option<String>,
+    iss: Option<String>,
+    /// Expiration time, as seconds since the Unix epoch.
+    exp: Option<u64>,
+    #[serde(default)]
+    aud: Audience,
+    /// Space-separated scopes (RFC 8693).
+    scope: Option<String>,
+    /// Scopes as a list, used by some providers.
+    #[serde(default)]
+    scp: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(untagged)]
+enum Audience {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::None => false,
+            Audience::One(aud) => aud == audience,
+            Audience::Many(auds) => auds.iter().any(|aud| aud == audience),
+        }
+    }
+}
+
+/// The way bearer tokens are verified.
+pub enum Verifier {
+    Jwks(JwkSet),
+    Introspection {
+        client: reqwest::Client,
+        url: Url,
+        credentials: Option<(String, Option<String>)>,
+        cache: IntrospectionCache,
+    },
+}
+
+// The client credentials must not be logged.
+impl fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verifier::Jwks(jwks) => f.debug_tuple("Jwks").field(jwks).finish(),
+            Verifier::Introspection { url, .. } => f.debug_struct("Introspection").field("url", url).finish_non_exhaustive(),
+        }
+    }
+}
+
+/// The claims of the active tokens recently returned by the introspection
+/// endpoint, so the clients making several requests with the same token don't
+/// hit the endpoint on every request.
+pub struct IntrospectionCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Claims)>>,
+}
+
+impl IntrospectionCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    fn get(&self, token: &str) -> Option<Claims> {
+        let entries = self.entries.lock().expect("it should lock the introspection cache");
+
+        entries
+            .get(token)
+            .filter(|(expires_at, _)| *expires_at > Instant::now())
+            .map(|(_, claims)| claims.clone())
+    }
+
+    /// It keeps the claims for the cache TTL, or until the token expires if
+    /// it's sooner.
+    fn insert(&self, token: &str, claims: &Claims) {
+        let ttl = match claims.exp {
+            Some(exp) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+                self.ttl.min(Duration::from_secs(exp.saturating_sub(now)))
+            }
+            None => self.ttl,
+        };
+
+        if ttl.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+
+        let mut entries = self.entries.lock().expect("it should lock the introspection cache");
+
+        entries.retain(|_, (expires_at, _)| *expires_at > now);
+        entries.insert(token.to_string(), (now + ttl, claims.clone()));
+    }
+}
+
+/// It verifies bearer tokens and maps their scopes to permissions.
+#[derive(Debug)]
+pub struct Provider {
+    verifier: Verifier,
+    issuer: Option<String>,
+    audience: Option<String>,
+    scopes: HashMap<String, ApiPermission>,
+}
+
+impl Provider {
+    /// # Panics
+    ///
+    /// It will panic if the JWKS file can't be read or it's not a valid key
+    /// set.
+    #[must_use]
+    pub fn new(config: &ApiAuth) -> Self {
+        let verifier = match (&config.jwks_path, &config.introspection_url) {
+            (Some(path), _) => {
+                let jwks = std::fs::read_to_string(path).expect("it should be able to read the JWKS file");
+
+                Verifier::Jwks(serde_json::from_str(&jwks).expect("the JWKS file should be a valid key set"))
+            }
+            (None, Some(url)) => Verifier::Introspection {
+                client: reqwest::Client::new(),
+                url: url.clone(),
+                credentials: config
+                    .client_id
+                    .as_ref()
+                    .map(|client_id| (client_id.clone(), config.client_secret.clone())),
+                cache: IntrospectionCache::new(Duration::from_secs(u64::from(config.introspection_cache_ttl))),
+            },
+            (None, None) => panic!("the API auth should have a JWKS file or an introspection endpoint"),
+        };
+
+        Self {
+            verifier,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            scopes: config.scopes.clone(),
+        }
+    }
+
+    /// It verifies the token and returns what it grants.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the token is not valid, it doesn't have any of
+    /// the configured scopes, or the introspection endpoint can't be reached.
+    pub async fn verify(&self, token: &str) -> Result<Grant, Error> {
+        let claims = match &self.verifier {
+            Verifier::Jwks(jwks) => self.decode(jwks, token)?,
+            Verifier::Introspection {
+                client,
+                url,
+                credentials,
+                cache,
+            } => {
+                if let Some(claims) = cache.get(token) {
+                    claims
+                } else {
+                    let mut request = client
+                        .post(url.clone())
+                        .form(&[("token", token), ("token_type_hint", "access_token")]);
+
+                    if let Some((client_id, client_secret)) = credentials {
+                        request = request.basic_auth(client_id, client_secret.as_ref());
+                    }
+
+                    let claims: Claims = request.send().await?.error_for_status()?.json().await?;
+
+                    if claims.active != Some(true) {
+                        return Err(Error::InvalidToken("the token is not active".to_string()));
+                    }
+
+                    self.check_issuer_and_audience(&claims)?;
+
+                    cache.insert(token, &claims);
+
+                    claims
+                }
+            }
+        };
+
+        Ok(Grant {
+            permission: self.permission(&claims)?,
+            subject: claims.sub,
+        })
+    }
+
+    fn decode(&self, jwks: &JwkSet, token: &str) -> Result<Claims, Error> {
+        let header = decode_header(token).map_err(|e| Error::InvalidToken(e.to_string()))?;
+
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| Error::InvalidToken("unknown signing key".to_string()))?;
+
+        // The algorithm of the token header can't be trusted: a token could
+        // choose a weaker algorithm than the one the key is meant for.
+        let algorithm = jwk
+            .common
+            .key_algorithm
+            .and_then(|key_algorithm| Algorithm::from_str(&key_algorithm.to_string()).ok())
+            .ok_or_else(|| Error::InvalidToken("the signing key does not have a signature algorithm".to_string()))?;
+
+        let key = DecodingKey::from_jwk(jwk).map_err(|e| Error::InvalidToken(e.to_string()))?;
+
+        // It also rejects the tokens whose header has another algorithm.
+        let mut validation = Validation::new(algorithm);
+
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        decode::<Claims>(token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| Error::InvalidToken(e.to_string()))
+    }
+
+    fn check_issuer_and_audience(&self, claims: &Claims) -> Result<(), Error> {
+        if self.issuer.as_ref().is_some_and(|issuer| claims.iss.as_ref() != Some(issuer)) {
+            return Err(Error::InvalidToken("invalid issuer".to_string()));
+        }
+
+        if self.audience.as_ref().is_some_and(|audience| !claims.aud.contains(audience)) {
+            return Err(Error::InvalidToken("invalid audience".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// It returns the highest permission granted by the scopes of the token.
+    fn permission(&self, claims: &Claims) -> Result<ApiPermission, Error> {
+        let scopes = claims
+            .scope
+            .iter()
+            .flat_map(|scope| scope.split_whitespace())
+            .chain(claims.scp.iter().map(String::as_str));
+
+        let mut permission = None;
+
+        for scope in scopes {
+            match self.scopes.get(scope) {
+                Some(ApiPermission::Admin) => return Ok(ApiPermission::Admin),
+                Some(ApiPermission::Access) => permission = Some(ApiPermission::Access),
+                None => {}
+            }
+        }
+
+        permission.ok_or(Error::InsufficientScope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use jsonwebtoken::jwk::JwkSet;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde_json::json;
+    use torrust_tracker_configuration::ApiPermission;
+
+    use super::{Claims, Error, Grant, IntrospectionCache, Provider, Verifier};
+
+    const SECRET: &[u8] = b"MySigningSecret";
+
+    fn provider() -> Provider {
+        let jwks: JwkSet = serde_json::from_value(json!({
+            "keys": [{ "kty": "oct", "kid": "test", "alg": "HS256", "k": "TXlTaWduaW5nU2VjcmV0" }]
+        }))
+        .unwrap();
+
+        Provider {
+            verifier: Verifier::Jwks(jwks),
+            issuer: Some("https://auth.example.com".to_string()),
+            audience: Some("torrust-tracker".to_string()),
+            scopes: [
+                ("tracker:read".to_string(), ApiPermission::Access),
+                ("tracker:admin".to_string(), ApiPermission::Admin),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn token(claims: &serde_json::Value) -> String {
+        let header = Header {
+            kid: Some("test".to_string()),
+            ..Default::default()
+        };
+
+        encode(&header, claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    fn claims(scope: &str) -> serde_json::Value {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        json!({
+            "sub": "alice",
+            "iss": "https://auth.example.com",
+            "aud": "torrust-tracker",
+            "exp": now + 60,
+            "scope": scope,
+        })
+    }
+
+    #[tokio::test]
+    async fn it_should_grant_the_permission_mapped_to_the_scopes_of_the_token() {
+        let provider = provider();
+
+        assert_eq!(
+            provider.verify(&token(&claims("openid tracker:read"))).await.unwrap(),
+            Grant {
+                subject: Some("alice".to_string()),
+                permission: ApiPermission::Access
+            }
+        );
+
+        assert_eq!(
+            provider
+                .verify(&token(&claims("tracker:read tracker:admin")))
+                .await
+                .unwrap()
+                .permission,
+            ApiPermission::Admin
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_tokens_without_any_of_the_configured_scopes() {
+        assert!(matches!(
+            provider().verify(&token(&claims("openid profile"))).await,
+            Err(Error::InsufficientScope)
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_tokens_from_another_issuer() {
+        let mut claims = claims("tracker:read");
+        claims["iss"] = json!("https://evil.example.com");
+
+        assert!(matches!(
+            provider().verify(&token(&claims)).await,
+            Err(Error::InvalidToken(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_expired_tokens() {
+        let mut claims = claims("tracker:read");
+        claims["exp"] = json!(1);
+
+        assert!(matches!(
+            provider().verify(&token(&claims)).await,
+            Err(Error::InvalidToken(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_tokens_signed_with_another_key() {
+        let header = Header {
+            kid: Some("test".to_string()),
+            ..Default::default()
+        };
+
+        let token = encode(&header, &claims("tracker:read"), &EncodingKey::from_secret(b"AnotherSecret")).unwrap();
+
+        assert!(matches!(provider().verify(&token).await, Err(Error::InvalidToken(_))));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_tokens_signed_with_another_algorithm_than_the_one_of_the_key() {
+        let header = Header {
+            kid: Some("test".to_string()),
+            alg: Algorithm::HS384,
+            ..Default::default()
+        };
+
+        let token = encode(&header, &claims("tracker:read"), &EncodingKey::from_secret(SECRET)).unwrap();
+
+        assert!(matches!(provider().verify(&token).await, Err(Error::InvalidToken(_))));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_tokens_signed_with_a_key_without_an_algorithm() {
+        let mut provider = provider();
+        provider.verifier = Verifier::Jwks(
+            serde_json::from_value(json!({
+                "keys": [{ "kty": "oct", "kid": "test", "k": "TXlTaWduaW5nU2VjcmV0" }]
+            }))
+            .unwrap(),
+        );
+
+        assert!(matches!(
+            provider.verify(&token(&claims("tracker:read"))).await,
+            Err(Error::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn the_introspection_cache_should_keep_the_claims_until_the_token_expires() {
+        let cache = IntrospectionCache::new(Duration::from_secs(60));
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        let active = Claims {
+            sub: Some("alice".to_string()),
+            exp: Some(now + 60),
+            ..Default::default()
+        };
+
+        let expired = Claims {
+            sub: Some("bob".to_string()),
+            exp: Some(now - 1),
+            ..Default::default()
+        };
+
+        cache.insert("active", &active);
+        cache.insert("expired", &expired);
+
+        assert_eq!(cache.get("active").unwrap().sub, Some("alice".to_string()));
+        assert!(cache.get("expired").is_none());
+        assert!(cache.get("unknown").is_none());
+    }
+
+    #[test]
+    fn the_introspection_cache_should_be_disabled_with_a_zero_ttl() {
+        let cache = IntrospectionCache::new(Duration::ZERO);
+
+        cache.insert("token", &Claims::default());
+
+        assert!(cache.get("token").is_none());
+    }
+}
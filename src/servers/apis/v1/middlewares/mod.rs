@@ -1,2 +1,5 @@
 //! API middlewares. See [Axum middlewares](axum::middleware).
+pub mod audit;
 pub mod auth;
+pub mod bearer;
+pub mod slow_requests;
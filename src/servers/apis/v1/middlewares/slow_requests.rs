@@ -0,0 +1,38 @@
+//! Slow requests middleware for the API.
+//!
+//! It measures the total time of the requests and logs the ones exceeding the
+//! `api_threshold_ms` threshold in the [slow requests log](crate::core::slow_requests).
+//! The API requests are not split into phases.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{self, ConnectInfo};
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::slow_requests::{self, Kind, Timer};
+use crate::core::Tracker;
+
+/// Middleware that logs the slow API requests.
+pub async fn slow_requests(
+    extract::State(tracker): extract::State<Arc<Tracker>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    let timer = Timer::start();
+
+    let method = request.method().clone();
+    let uri = request.uri().clone();
+
+    let response = next.run(request).await;
+
+    tracker.log_slow_request(Kind::Api, &timer, || slow_requests::Request {
+        info_hashes: vec![],
+        client_ip: connect_info.map(|ConnectInfo(address)| address.ip()),
+        target: Some(format!("{method} {}", uri.path())),
+    });
+
+    response
+}
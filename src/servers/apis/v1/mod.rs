@@ -8,6 +8,8 @@
 //! `Torrents` | Torrents | [`v1`](crate::servers::apis::v1::context::torrent)
 //! `Whitelist` | Torrents whitelist | [`v1`](crate::servers::apis::v1::context::whitelist)
 //! `Authentication keys` | Authentication keys | [`v1`](crate::servers::apis::v1::context::auth_key)
+//! `Audit log` | Administrative actions | [`v1`](crate::servers::apis::v1::context::audit_log)
+//! `Configuration` | Configuration schema | [`v1`](crate::servers::apis::v1::context::configuration)
 //!
 //! > **NOTICE**:
 //! - The authentication keys are only used by the HTTP tracker.
@@ -16,7 +18,18 @@
 //!
 //! Refer to the [authentication middleware](crate::servers::apis::v1::middlewares::auth)
 //! for more information about the authentication process.
+//!
+//! All the endpoints return the same json body when the request fails. Refer
+//! to the [common responses](crate::servers::apis::v1::responses) for the list
+//! of error codes.
+//!
+//! The responses are compressed when the client sends the `Accept-Encoding`
+//! header, and the stats and torrents endpoints can return only the selected
+//! [`fields`] of the resources.
 pub mod context;
+pub mod extractors;
+pub mod fields;
 pub mod middlewares;
 pub mod responses;
 pub mod routes;
+pub mod streaming;
@@ -1,7 +1,50 @@
 //! Common responses for the API v1 shared by all the contexts.
+//!
+//! All the error responses have the same json body, an [`ErrorResponse`]:
+//!
+//! ```json
+//! {
+//!   "code": "validation_failed",
+//!   "message": "Failed to deserialize the JSON body into the target type: seconds_valid: invalid type: string \"abc\", expected u64 at line 1 column 24",
+//!   "details": [
+//!     {
+//!       "field": "seconds_valid",
+//!       "message": "invalid type: string \"abc\", expected u64 at line 1 column 24"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The `code` is a stable identifier for the kind of error, while the
+//! `message` is only meant for humans and it can change. The `details` list the
+//! invalid fields, when the error is caused by some of them. The HTTP status
+//! code depends on the [`ErrorCode`]:
+//!
+//! Code | Status | Description
+//! ---|---|---
+//! `invalid_param` | `400` | A path or query param is not valid.
+//! `invalid_body` | `400` | The request body is not valid json.
+//! `unauthorized` | `401` | The token is missing.
+//! `invalid_token` | `401` | The token is not valid.
+//! `forbidden` | `403` | The endpoint requires an admin token.
+//! `not_found` | `404` | The endpoint or the resource does not exist.
+//! `timeout` | `408` | The request took too long.
+//! `not_enabled` | `409` | The feature is not enabled in the tracker configuration.
+//! `persistence_disabled` | `409` | The tracker runs without a database.
+//! `payload_too_large` | `413` | The request body is too large.
+//! `unsupported_media_type` | `415` | The request body is not `application/json`.
+//! `validation_failed` | `422` | A field of the request body is not valid.
+//! `internal_error` | `500` | Unexpected tracker error.
+//! `not_supported` | `501` | The database driver does not support the operation.
+//! `database_unavailable` | `503` | The tracker can't connect to the database.
+use std::error::Error;
+
 use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
-use serde::Serialize;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::core::databases;
 
 /* code-review:
     When Axum cannot parse a path or query param it shows a message like this:
@@ -14,7 +57,7 @@ use serde::Serialize;
     We should show a message similar to the one we use when we parse the value in the handler.
     For example:
 
-    "Invalid URL: invalid infohash param: string \"INVALID VALUE\", expected a 40 character long string"
+    "Invalid URL: invalid infohash param: string \"INVALID VALUE\", expected a 40 character long hex or a 32 character long base32 string"
 
     We can customize the error message by using a custom type with custom serde deserialization.
     The same we are using for the "InfoHashVisitor".
@@ -49,36 +92,172 @@ pub fn ok_response() -> Response {
 
 // Error responses
 
+/// The kind of error in an [`ErrorResponse`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidParam,
+    InvalidBody,
+    Unauthorized,
+    InvalidToken,
+    Forbidden,
+    NotFound,
+    Timeout,
+    NotEnabled,
+    PersistenceDisabled,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    ValidationFailed,
+    InternalError,
+    NotSupported,
+    DatabaseUnavailable,
+}
+
+impl ErrorCode {
+    /// The HTTP status code of the responses with this error code.
+    #[must_use]
+    pub fn status_code(self) -> StatusCode {
+        match self {
+            ErrorCode::InvalidParam | ErrorCode::InvalidBody => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized | ErrorCode::InvalidToken => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Timeout => StatusCode::REQUEST_TIMEOUT,
+            ErrorCode::NotEnabled | ErrorCode::PersistenceDisabled => StatusCode::CONFLICT,
+            ErrorCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::ValidationFailed => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::NotSupported => StatusCode::NOT_IMPLEMENTED,
+            ErrorCode::DatabaseUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// The error code for an error returned by the tracker. It looks for
+    /// known errors in the chain of sources, and it defaults to
+    /// [`ErrorCode::InternalError`].
+    #[must_use]
+    pub fn of(e: &(dyn Error + 'static)) -> Self {
+        let mut current = Some(e);
+
+        while let Some(e) = current {
+            if let Some(e) = e.downcast_ref::<databases::error::Error>() {
+                return match e {
+                    #[cfg(feature = "mysql")]
+                    databases::error::Error::ConnectionError { .. } => ErrorCode::DatabaseUnavailable,
+                    databases::error::Error::ConnectionPool { .. } => ErrorCode::DatabaseUnavailable,
+                    databases::error::Error::PersistenceDisabled { .. } => ErrorCode::PersistenceDisabled,
+                    databases::error::Error::BackupNotSupported { .. } | databases::error::Error::DriverNotCompiled { .. } => {
+                        ErrorCode::NotSupported
+                    }
+                    _ => ErrorCode::InternalError,
+                };
+            }
+
+            current = e.source();
+        }
+
+        ErrorCode::InternalError
+    }
+}
+
+/// An invalid field in the request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// The path of the field, for example `seconds_valid` for a field in the
+    /// request body or `info_hash` for a path param.
+    pub field: String,
+    pub message: String,
+}
+
+/// The body of all the API error responses.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Vec<FieldError>,
+}
+
+impl ErrorResponse {
+    #[must_use]
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: vec![],
+        }
+    }
+
+    /// It adds an invalid field to the details of the error.
+    #[must_use]
+    pub fn with_field_error(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.details.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+        self
+    }
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> Response {
+        (self.code.status_code(), Json(self)).into_response()
+    }
+}
+
 #[must_use]
 pub fn invalid_info_hash_param_response(info_hash: &str) -> Response {
-    bad_request_response(&format!(
-        "Invalid URL: invalid infohash param: string \"{info_hash}\", expected a 40 character long string"
-    ))
+    invalid_param_response(
+        "info_hash",
+        &format!(
+            "Invalid URL: invalid infohash param: string \"{info_hash}\", expected a 40 character long hex or a 32 character long base32 string"
+        ),
+    )
 }
 
 #[must_use]
 pub fn invalid_auth_key_param_response(invalid_key: &str) -> Response {
-    bad_request_response(&format!("Invalid auth key id param \"{invalid_key}\""))
+    invalid_param_response("key", &format!("Invalid auth key id param \"{invalid_key}\""))
 }
 
+/// `400` error response for an invalid path or query param.
 #[must_use]
-pub fn bad_request_response(body: &str) -> Response {
-    (
-        StatusCode::BAD_REQUEST,
-        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
-        body.to_owned(),
-    )
+pub fn invalid_param_response(param: &str, message: &str) -> Response {
+    ErrorResponse::new(ErrorCode::InvalidParam, message)
+        .with_field_error(param, message)
         .into_response()
 }
 
-/// This error response is to keep backward compatibility with the old API.
-/// It should be a plain text or json.
+/// `422` error response for an invalid field in the request body.
 #[must_use]
-pub fn unhandled_rejection_response(reason: String) -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
-        format!("Unhandled rejection: {:?}", ActionStatus::Err { reason: reason.into() }),
-    )
+pub fn validation_failed_response(field: &str, message: &str) -> Response {
+    ErrorResponse::new(ErrorCode::ValidationFailed, message)
+        .with_field_error(field, message)
         .into_response()
 }
+
+/// `404` error response for a resource that does not exist.
+#[must_use]
+pub fn not_found_response(message: &str) -> Response {
+    ErrorResponse::new(ErrorCode::NotFound, message).into_response()
+}
+
+/// `409` error response for requests to features that are not enabled in the
+/// configuration.
+#[must_use]
+pub fn not_enabled_response(message: &str) -> Response {
+    ErrorResponse::new(ErrorCode::NotEnabled, message).into_response()
+}
+
+/// Error response for a request that failed because of an error in the
+/// tracker. The status code depends on the [`ErrorCode`] of the error.
+#[must_use]
+pub fn failed_response<E: Error + 'static>(reason: &str, e: &E) -> Response {
+    ErrorResponse::new(ErrorCode::of(e), format!("{reason}: {e}")).into_response()
+}
+
+/// `500` error response for unexpected errors.
+#[must_use]
+pub fn unhandled_rejection_response(reason: String) -> Response {
+    ErrorResponse::new(ErrorCode::InternalError, reason).into_response()
+}
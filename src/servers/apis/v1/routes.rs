@@ -2,17 +2,63 @@
 use std::sync::Arc;
 
 use axum::Router;
+use torrust_tracker_configuration::HttpApi;
 
-use super::context::{auth_key, stats, torrent, whitelist};
+use super::context::{
+    audit_log, auth_key, backup, configuration, dead_letter, events, invitation, maintenance, peer_cleanup, policy, static_peer,
+    stats, torrent, whitelist,
+};
 use crate::core::Tracker;
 
 /// Add the routes for the v1 API.
-pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>, config: &HttpApi) -> Router {
     let v1_prefix = format!("{prefix}/v1");
 
-    let router = auth_key::routes::add(&v1_prefix, router, tracker.clone());
+    let router = audit_log::routes::add(&v1_prefix, router, tracker.clone());
+    let router = auth_key::routes::add(&v1_prefix, router, tracker.clone(), config.incident_webhook_url.clone());
+    let router = backup::routes::add(&v1_prefix, router, tracker.clone());
+    let router = configuration::routes::add(&v1_prefix, router);
+    let router = dead_letter::routes::add(&v1_prefix, router, tracker.clone());
+    let router = events::routes::add(&v1_prefix, router, tracker.clone(), config.peer_id_hash_salt.clone());
+    let router = invitation::routes::add(&v1_prefix, router, tracker.clone());
+    let router = maintenance::routes::add(&v1_prefix, router, tracker.clone());
+    let router = peer_cleanup::routes::add(&v1_prefix, router, tracker.clone());
+    let router = policy::routes::add(&v1_prefix, router, tracker.clone());
+    let router = static_peer::routes::add(&v1_prefix, router, tracker.clone());
     let router = stats::routes::add(&v1_prefix, router, tracker.clone());
     let router = whitelist::routes::add(&v1_prefix, router, tracker.clone());
 
-    torrent::routes::add(&v1_prefix, router, tracker)
+    torrent::routes::add(
+        &v1_prefix,
+        router,
+        tracker,
+        config.peer_id_hash_salt.clone(),
+        config.announce_urls.clone(),
+    )
+}
+
+/// Add the routes for a [virtual tracker](crate::core::virtual_trackers) to
+/// the v1 API, under `{prefix}/v1/trackers/{name}`.
+///
+/// Only the contexts that belong to a single tracker are added. The
+/// process-wide contexts, like the configuration, backups or the audit log,
+/// are only served for the main tracker.
+pub fn add_virtual_tracker(prefix: &str, name: &str, router: Router, tracker: Arc<Tracker>, config: &HttpApi) -> Router {
+    let tracker_prefix = format!("{prefix}/v1/trackers/{name}");
+
+    let router = auth_key::routes::add(&tracker_prefix, router, tracker.clone(), config.incident_webhook_url.clone());
+    let router = events::routes::add(&tracker_prefix, router, tracker.clone(), config.peer_id_hash_salt.clone());
+    let router = peer_cleanup::routes::add(&tracker_prefix, router, tracker.clone());
+    let router = policy::routes::add(&tracker_prefix, router, tracker.clone());
+    let router = static_peer::routes::add(&tracker_prefix, router, tracker.clone());
+    let router = stats::routes::add(&tracker_prefix, router, tracker.clone());
+    let router = whitelist::routes::add(&tracker_prefix, router, tracker.clone());
+
+    torrent::routes::add(
+        &tracker_prefix,
+        router,
+        tracker,
+        config.peer_id_hash_salt.clone(),
+        config.announce_urls.clone(),
+    )
 }
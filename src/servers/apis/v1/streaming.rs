@@ -0,0 +1,172 @@
+//! Streaming of the big API responses.
+//!
+//! The listings that can be huge, like the torrents or the whitelist, are not
+//! serialized into one buffer. The items are serialized in chunks while the
+//! response is sent, with the chunked transfer encoding, so the serialized
+//! body is never fully in memory.
+//!
+//! > **NOTICE**: only the serialization is streamed. The items of the listing
+//! > are collected from the torrents repository or the whitelist before the
+//! > response starts, so they are fully in memory while it's sent.
+//!
+//! The listings support two formats, selected with the `format` query
+//! parameter:
+//!
+//! Format | Content type | Description
+//! ---|---|---
+//! `json` | `application/json` | A JSON array. It's the default.
+//! `ndjson` | `application/x-ndjson` | One JSON item per line ([Newline Delimited JSON](https://github.com/ndjson/ndjson-spec)).
+//!
+//! For example:
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrents?token=MyAccessToken&limit=1000000&format=ndjson"
+//! ```
+use axum::body::{Body, Bytes};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use futures::stream;
+use serde::{Deserialize, Serialize};
+
+/// Number of items serialized in each chunk of the response body.
+const ITEMS_PER_CHUNK: usize = 1000;
+
+/// The format of the streamed listings.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A JSON array.
+    #[default]
+    Json,
+    /// Newline Delimited JSON: one item per line.
+    Ndjson,
+}
+
+impl Format {
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+/// `200` response that streams the items in the given format.
+///
+/// The items are serialized lazily, a fixed number of items at a time.
+#[must_use]
+pub fn stream_response<T, I>(items: I, format: Format) -> Response
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: Send + 'static,
+{
+    let chunks = Chunks {
+        items: items.into_iter(),
+        format,
+        started: false,
+        finished: false,
+    };
+
+    let body = Body::from_stream(stream::iter(chunks));
+
+    ([(header::CONTENT_TYPE, format.content_type())], body).into_response()
+}
+
+/// Iterator over the serialized chunks of the response body.
+struct Chunks<I> {
+    items: I,
+    format: Format,
+    started: bool,
+    finished: bool,
+}
+
+impl<T, I> Iterator for Chunks<I>
+where
+    T: Serialize,
+    I: Iterator<Item = T>,
+{
+    type Item = Result<Bytes, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut chunk = vec![];
+
+        if !self.started && self.format == Format::Json {
+            chunk.push(b'[');
+        }
+
+        for _ in 0..ITEMS_PER_CHUNK {
+            let Some(item) = self.items.next() else {
+                self.finished = true;
+                break;
+            };
+
+            if self.format == Format::Json && self.started {
+                chunk.push(b',');
+            }
+
+            if let Err(err) = serde_json::to_writer(&mut chunk, &item) {
+                self.finished = true;
+                return Some(Err(err));
+            }
+
+            if self.format == Format::Ndjson {
+                chunk.push(b'\n');
+            }
+
+            self.started = true;
+        }
+
+        if self.finished && self.format == Format::Json {
+            chunk.push(b']');
+        }
+
+        Some(Ok(Bytes::from(chunk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use axum::http::header;
+
+    use super::{stream_response, Format, ITEMS_PER_CHUNK};
+
+    async fn body_of(items: Vec<u32>, format: Format) -> String {
+        let response = stream_response(items, format);
+
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_should_stream_the_items_as_a_json_array() {
+        assert_eq!(body_of(vec![], Format::Json).await, "[]");
+        assert_eq!(body_of(vec![1, 2, 3], Format::Json).await, "[1,2,3]");
+    }
+
+    #[tokio::test]
+    async fn it_should_stream_the_items_as_newline_delimited_json() {
+        assert_eq!(body_of(vec![], Format::Ndjson).await, "");
+        assert_eq!(body_of(vec![1, 2, 3], Format::Ndjson).await, "1\n2\n3\n");
+    }
+
+    #[tokio::test]
+    async fn it_should_stream_a_valid_json_array_with_more_than_one_chunk() {
+        let items: Vec<u32> = (0..u32::try_from(ITEMS_PER_CHUNK * 2 + 1).unwrap()).collect();
+
+        let body = body_of(items.clone(), Format::Json).await;
+
+        assert_eq!(serde_json::from_str::<Vec<u32>>(&body).unwrap(), items);
+    }
+
+    #[test]
+    fn it_should_set_the_content_type_of_the_format() {
+        let response = stream_response(vec![1], Format::Ndjson);
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/x-ndjson");
+    }
+}
@@ -0,0 +1,99 @@
+//! Logic to bind the services to a specific network interface.
+//!
+//! The UDP and HTTP trackers can be pinned to a network interface with the
+//! `SO_BINDTODEVICE` socket option, so multi-homed servers send and receive
+//! the tracker traffic only through that NIC, regardless of the routing
+//! tables:
+//!
+//! ```toml
+//! [[udp_trackers]]
+//! bind_address = "0.0.0.0:6969"
+//! bind_device = "eth1"
+//! ```
+//!
+//! The option is only available on Linux. The configuration is rejected on
+//! other platforms.
+use std::io;
+use std::net::SocketAddr;
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// The maximum number of pending connections of the TCP listeners. It's the
+/// same as the standard library uses.
+const TCP_BACKLOG: i32 = 128;
+
+/// It binds a TCP listener to the address. When the `bind_device` is set, the
+/// listener is also bound to that network interface.
+///
+/// # Errors
+///
+/// Will return an error if the listener can't be bound, for example, if the
+/// network interface doesn't exist.
+pub fn bind_tcp_listener(addr: SocketAddr, bind_device: Option<&str>) -> io::Result<std::net::TcpListener> {
+    let Some(bind_device) = bind_device else {
+        return std::net::TcpListener::bind(addr);
+    };
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+
+    socket.set_reuse_address(true)?;
+    set_bind_device(&socket, bind_device)?;
+    socket.bind(&addr.into())?;
+    socket.listen(TCP_BACKLOG)?;
+
+    Ok(socket.into())
+}
+
+/// It sets the `SO_BINDTODEVICE` socket option. It has to be set before
+/// binding the socket.
+///
+/// # Errors
+///
+/// Will return an error if the network interface doesn't exist, or if the
+/// process doesn't have permission to set the option.
+#[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+pub fn set_bind_device(socket: &Socket, bind_device: &str) -> io::Result<()> {
+    socket.bind_device(Some(bind_device.as_bytes())).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("unable to bind to network interface {bind_device}: {err}"),
+        )
+    })
+}
+
+/// It always fails: the `SO_BINDTODEVICE` socket option is only available on
+/// Linux.
+///
+/// # Errors
+///
+/// Will always return an [`io::ErrorKind::Unsupported`] error.
+#[cfg(not(any(target_os = "android", target_os = "fuchsia", target_os = "linux")))]
+pub fn set_bind_device(_socket: &Socket, bind_device: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("binding to a network interface ({bind_device}) is only supported on Linux"),
+    ))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    use super::bind_tcp_listener;
+
+    #[test]
+    fn it_should_bind_a_tcp_listener_to_a_network_interface() {
+        let listener = bind_tcp_listener(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), Some("lo")).unwrap();
+
+        let client = std::net::TcpStream::connect(listener.local_addr().unwrap());
+
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn it_should_fail_binding_a_tcp_listener_to_an_unknown_network_interface() {
+        let listener = bind_tcp_listener(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), Some("unknown0"));
+
+        assert!(listener.is_err());
+    }
+}
@@ -24,7 +24,10 @@ use std::task::{Context, Poll};
 use std::time::Duration;
 
 use axum_server::accept::Accept;
-use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+#[cfg(feature = "tls")]
+use axum_server::tls_rustls::RustlsAcceptor;
+#[cfg(feature = "tls")]
+pub use axum_server::tls_rustls::RustlsConfig;
 use axum_server::Server;
 use futures_util::{ready, Future};
 use http_body::{Body, Frame};
@@ -40,11 +43,19 @@ const HTTP1_HEADER_READ_TIMEOUT: Duration = Duration::from_secs(5);
 const HTTP2_KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(5);
 const HTTP2_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
 
+/// The TLS configuration when the tracker is built without the `tls` feature.
+///
+/// It has no values, so the HTTPS servers can't be started.
+#[cfg(not(feature = "tls"))]
+#[derive(Debug, Clone)]
+pub enum RustlsConfig {}
+
 #[must_use]
 pub fn from_tcp_with_timeouts(socket: TcpListener) -> Server {
     add_timeouts(axum_server::from_tcp(socket))
 }
 
+#[cfg(feature = "tls")]
 #[must_use]
 pub fn from_tcp_rustls_with_timeouts(socket: TcpListener, tls: RustlsConfig) -> Server<RustlsAcceptor> {
     add_timeouts(axum_server::from_tcp_rustls(socket, tls))
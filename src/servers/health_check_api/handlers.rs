@@ -1,22 +1,91 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 
 use axum::extract::State;
 use axum::Json;
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
 use tracing::{instrument, Level};
 
-use super::resources::{CheckReport, Report};
+use super::resources::{CheckReport, Report, WarmUpProgress};
 use super::responses;
-use crate::servers::registar::{ServiceHealthCheckJob, ServiceRegistration, ServiceRegistry};
+use crate::bootstrap::preflight;
+use crate::core::file_descriptors::Monitor;
+use crate::core::slo::{self, Evaluation};
+use crate::core::torrent::import::WarmUp;
+use crate::servers::registar::{Binding, ServiceHealthCheckJob, ServiceRegistration, ServiceRegistry};
+
+/// The time of the last passing check for each service, by its binding.
+pub(crate) type LastSuccesses = Arc<Mutex<HashMap<Binding, DateTime<Utc>>>>;
+
+/// The state shared with the health check handler.
+pub(crate) type HealthCheckState = (
+    ServiceRegistry,
+    LastSuccesses,
+    Arc<WarmUp>,
+    Option<Arc<Monitor>>,
+    Option<Arc<slo::Monitor>>,
+    Arc<preflight::Report>,
+);
 
 /// Endpoint for container health check.
 ///
 /// Creates a vector [`CheckReport`] from the input set of [`CheckJob`], and then builds a report from the results.
 ///
-#[instrument(skip(register), ret(level = Level::DEBUG))]
-pub(crate) async fn health_check_handler(State(register): State<ServiceRegistry>) -> Json<Report> {
+/// Each report includes the time the check took and the time of the last
+/// passing check for the service, since the health check API started.
+///
+/// While the tracker is importing the persisted torrents at startup the
+/// services are not running yet, so it returns a `WarmingUp` report with the
+/// number of torrents imported so far and the percentage of the persisted
+/// torrents they represent.
+///
+/// When all the checks pass but the `announce` requests are not meeting the
+/// service level objectives, it returns a `Degraded` report.
+///
+/// When all the checks pass but the usage of file descriptors is close to the
+/// limit, it returns a `Warning` report, since the HTTP trackers are rejecting
+/// new connections.
+///
+/// The warnings found by the preflight checks at startup are included in all
+/// the reports.
+///
+#[instrument(skip(register, last_successes, warm_up, file_descriptors, slo, preflight), ret(level = Level::DEBUG))]
+pub(crate) async fn health_check_handler(
+    State((register, last_successes, warm_up, file_descriptors, slo, preflight)): State<HealthCheckState>,
+) -> Json<Report> {
+    let Json(report) = health_check(register, last_successes, warm_up, file_descriptors, slo).await;
+
+    Json(report.with_preflight(&preflight))
+}
+
+async fn health_check(
+    register: ServiceRegistry,
+    last_successes: LastSuccesses,
+    warm_up: Arc<WarmUp>,
+    file_descriptors: Option<Arc<Monitor>>,
+    slo: Option<Arc<slo::Monitor>>,
+) -> Json<Report> {
+    if warm_up.is_in_progress() {
+        let progress = WarmUpProgress::from(warm_up.as_ref());
+
+        let message = match progress.progress {
+            Some(percentage) => format!(
+                "importing the persisted torrents, {} of {} imported ({percentage}%)",
+                progress.imported_torrents, progress.expected_torrents
+            ),
+            None => format!("importing the persisted torrents, {} imported", progress.imported_torrents),
+        };
+
+        return responses::warming_up(message, progress);
+    }
+
     #[allow(unused_assignments)]
     let mut checks: VecDeque<ServiceHealthCheckJob> = VecDeque::new();
 
+    let started = Instant::now();
+
     {
         let mutex = register.lock();
 
@@ -30,23 +99,59 @@ pub(crate) async fn health_check_handler(State(register): State<ServiceRegistry>
 
     let jobs = checks.drain(..).map(|c| {
         tokio::spawn(async move {
+            let result = c.job.await.expect("it should be able to join into the checking function");
+
             CheckReport {
                 binding: c.binding,
                 info: c.info.clone(),
-                result: c.job.await.expect("it should be able to join into the checking function"),
+                result,
+                latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+                last_success: None,
             }
         })
     });
 
-    let results: Vec<CheckReport> = futures::future::join_all(jobs)
+    let mut results: Vec<CheckReport> = futures::future::join_all(jobs)
         .await
         .drain(..)
         .map(|r| r.expect("it should be able to connect to the job"))
         .collect();
 
+    {
+        let mut last_successes = last_successes.lock().await;
+
+        for result in &mut results {
+            if result.pass() {
+                last_successes.insert(result.binding.clone(), Utc::now());
+            }
+
+            result.last_success = last_successes
+                .get(&result.binding)
+                .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true));
+        }
+    }
+
     if results.iter().any(CheckReport::fail) {
-        responses::error("health check failed".to_string(), results)
-    } else {
-        responses::ok(results)
+        return responses::error("health check failed".to_string(), results);
+    }
+
+    if let Some(evaluation) = slo.map(|slo| slo.evaluate()).filter(Evaluation::is_degraded) {
+        return responses::degraded(evaluation.to_string(), results);
     }
+
+    if let Some(monitor) = file_descriptors.filter(|monitor| monitor.is_near_the_limit()) {
+        let usage = monitor.usage();
+
+        return responses::warning(
+            format!(
+                "file descriptors usage is close to the limit ({} of {}), {} HTTP connections rejected",
+                usage.open,
+                usage.limit.unwrap_or_default(),
+                monitor.rejected_connections()
+            ),
+            results,
+        );
+    }
+
+    responses::ok(results)
 }
@@ -1,19 +1,32 @@
-use std::net::SocketAddr;
-
 use serde::{Deserialize, Serialize};
 
+use crate::bootstrap::preflight;
+use crate::core::torrent::import::WarmUp;
+use crate::servers::registar::Binding;
+
 #[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum Status {
     Ok,
     Error,
     None,
+    /// The tracker is still importing the persisted torrents.
+    WarmingUp,
+    /// The services are healthy, but the tracker is shedding load.
+    Warning,
+    /// The services are healthy, but the `announce` requests are not meeting
+    /// the service level objectives.
+    Degraded,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct CheckReport {
-    pub binding: SocketAddr,
+    pub binding: Binding,
     pub info: String,
     pub result: Result<String, String>,
+    /// Time in milliseconds the check took.
+    pub latency_ms: u64,
+    /// Time of the last passing check for the service, in RFC 3339 format.
+    pub last_success: Option<String>,
 }
 
 impl CheckReport {
@@ -27,11 +40,57 @@ impl CheckReport {
     }
 }
 
+/// A warning found by the [preflight checks](crate::bootstrap::preflight) at
+/// startup.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PreflightWarning {
+    pub check: String,
+    pub message: String,
+}
+
+impl From<&preflight::Warning> for PreflightWarning {
+    fn from(warning: &preflight::Warning) -> Self {
+        Self {
+            check: warning.check.to_string(),
+            message: warning.message.clone(),
+        }
+    }
+}
+
+/// The progress of the import of the persisted torrents while the tracker is
+/// warming up.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct WarmUpProgress {
+    pub imported_torrents: u64,
+    /// The number of persisted torrents, or `0` if they could not be counted.
+    pub expected_torrents: u64,
+    /// The percentage of the expected torrents imported so far.
+    pub progress: Option<u8>,
+}
+
+impl From<&WarmUp> for WarmUpProgress {
+    fn from(warm_up: &WarmUp) -> Self {
+        Self {
+            imported_torrents: warm_up.imported_torrents(),
+            expected_torrents: warm_up.expected_torrents(),
+            progress: warm_up.progress(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct Report {
     pub status: Status,
     pub message: String,
     pub details: Vec<CheckReport>,
+    /// The warnings found by the preflight checks at startup. They do not
+    /// change the status.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preflight: Vec<PreflightWarning>,
+    /// The progress of the import of the persisted torrents. It's only
+    /// included while the tracker is warming up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_up: Option<WarmUpProgress>,
 }
 
 impl Report {
@@ -41,6 +100,8 @@ impl Report {
             status: Status::None,
             message: String::new(),
             details: Vec::default(),
+            preflight: Vec::default(),
+            warm_up: None,
         }
     }
 
@@ -50,6 +111,41 @@ impl Report {
             status: Status::Ok,
             message: String::new(),
             details,
+            preflight: Vec::default(),
+            warm_up: None,
+        }
+    }
+
+    #[must_use]
+    pub fn warning(message: String, details: Vec<CheckReport>) -> Report {
+        Self {
+            status: Status::Warning,
+            message,
+            details,
+            preflight: Vec::default(),
+            warm_up: None,
+        }
+    }
+
+    #[must_use]
+    pub fn degraded(message: String, details: Vec<CheckReport>) -> Report {
+        Self {
+            status: Status::Degraded,
+            message,
+            details,
+            preflight: Vec::default(),
+            warm_up: None,
+        }
+    }
+
+    #[must_use]
+    pub fn warming_up(message: String, progress: WarmUpProgress) -> Report {
+        Self {
+            status: Status::WarmingUp,
+            message,
+            details: Vec::default(),
+            preflight: Vec::default(),
+            warm_up: Some(progress),
         }
     }
 
@@ -59,6 +155,14 @@ impl Report {
             status: Status::Error,
             message,
             details,
+            preflight: Vec::default(),
+            warm_up: None,
         }
     }
+
+    #[must_use]
+    pub fn with_preflight(mut self, report: &preflight::Report) -> Report {
+        self.preflight = report.warnings.iter().map(PreflightWarning::from).collect();
+        self
+    }
 }
@@ -1,6 +1,6 @@
 use axum::Json;
 
-use super::resources::{CheckReport, Report};
+use super::resources::{CheckReport, Report, WarmUpProgress};
 
 pub fn ok(details: Vec<CheckReport>) -> Json<Report> {
     Json(Report::ok(details))
@@ -10,6 +10,18 @@ pub fn error(message: String, details: Vec<CheckReport>) -> Json<Report> {
     Json(Report::error(message, details))
 }
 
+pub fn warning(message: String, details: Vec<CheckReport>) -> Json<Report> {
+    Json(Report::warning(message, details))
+}
+
+pub fn degraded(message: String, details: Vec<CheckReport>) -> Json<Report> {
+    Json(Report::degraded(message, details))
+}
+
+pub fn warming_up(message: String, progress: WarmUpProgress) -> Json<Report> {
+    Json(Report::warming_up(message, progress))
+}
+
 pub fn none() -> Json<Report> {
     Json(Report::none())
 }
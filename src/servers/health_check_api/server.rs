@@ -3,6 +3,7 @@
 //! This API is intended to be used by the container infrastructure to check if
 //! the whole application is healthy.
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use axum::http::HeaderName;
@@ -21,7 +22,11 @@ use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use tracing::{instrument, Level, Span};
 
 use crate::bootstrap::jobs::Started;
-use crate::servers::health_check_api::handlers::health_check_handler;
+use crate::bootstrap::preflight;
+use crate::core::file_descriptors::Monitor;
+use crate::core::slo;
+use crate::core::torrent::import::WarmUp;
+use crate::servers::health_check_api::handlers::{health_check_handler, LastSuccesses};
 use crate::servers::health_check_api::HEALTH_CHECK_API_LOG_TARGET;
 use crate::servers::registar::ServiceRegistry;
 use crate::servers::signals::{graceful_shutdown, Halted};
@@ -31,17 +36,22 @@ use crate::servers::signals::{graceful_shutdown, Halted};
 /// # Panics
 ///
 /// Will panic if binding to the socket address fails.
-#[instrument(skip(bind_to, tx, rx_halt, register))]
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip(bind_to, tx, rx_halt, register, warm_up, file_descriptors, slo, preflight))]
 pub fn start(
     bind_to: SocketAddr,
     tx: Sender<Started>,
     rx_halt: Receiver<Halted>,
     register: ServiceRegistry,
+    warm_up: Arc<WarmUp>,
+    file_descriptors: Option<Arc<Monitor>>,
+    slo: Option<Arc<slo::Monitor>>,
+    preflight: Arc<preflight::Report>,
 ) -> impl Future<Output = Result<(), std::io::Error>> {
     let router = Router::new()
         .route("/", get(|| async { Json(json!({})) }))
         .route("/health_check", get(health_check_handler))
-        .with_state(register)
+        .with_state((register, LastSuccesses::default(), warm_up, file_descriptors, slo, preflight))
         .layer(CompressionLayer::new())
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .layer(PropagateHeaderLayer::new(HeaderName::from_static("x-request-id")))
@@ -15,6 +15,8 @@
 //! - [Requests](#requests)
 //!     - [Announce](#announce)
 //!     - [Scrape](#scrape)
+//!     - [Request size limits](#request-size-limits)
+//! - [Health](#health)
 //! - [Versioning](#versioning)
 //! - [Links](#links)
 //!
@@ -193,6 +195,13 @@
 //! Refer to the [`Compact`](crate::servers::http::v1::responses::announce::Compact)
 //! response for more information about the response.
 //!
+//! **Padding**
+//!
+//! When the `announce_response_padding` option is enabled, the responses
+//! smaller than the configured minimum size get an extra `padding` key with
+//! blank spaces, for example `7:padding3:   `. Clients ignore unknown keys.
+//! It helps with middleboxes that drop tiny responses.
+//!
 //! **Protocol**
 //!
 //! Original specification in [BEP 03. The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html).
@@ -238,7 +247,15 @@
 //! `info_hash` parameters: `info_hash=%81%00%0...00%00%00&info_hash=%82%00%0...00%00%00`
 //!
 //! > **NOTICE**: the maximum number of torrents you can scrape at the same time
-//! > is `74`. Defined with a hardcoded const [`MAX_SCRAPE_TORRENTS`](crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS).
+//! > is `74` by default. It can be changed with the `max_scrape_torrents` option
+//! > in the [`core`](torrust_tracker_configuration::Core::max_scrape_torrents)
+//! > configuration section. Requests exceeding the limit get a failure response.
+//!
+//! Duplicate `info_hash` parameters are only looked up once, and the torrent
+//! is only included once in the response. They are counted in the
+//! `tcp_scrape_duplicate_info_hashes` metric. The files in the response are
+//! sorted by infohash, not in the request order, because bencoded
+//! dictionaries are sorted by key.
 //!
 //! **Sample response**
 //!
@@ -287,6 +304,44 @@
 //! - [BEP 48. Tracker Protocol Extension: Scrape](https://www.bittorrent.org/beps/bep_0048.html)
 //! - [Vuze scrape docs](https://wiki.vuze.com/w/Scrape)
 //!
+//! ### Request size limits
+//!
+//! Requests with a URI longer than `max_uri_bytes` (`8192` by default) get a
+//! `414 URI Too Long` response, and requests with headers larger than
+//! `max_header_bytes` (`16384` by default) get a
+//! `431 Request Header Fields Too Large` response. Both have a bencoded
+//! failure body and are counted in the `tcp_requests_with_uri_too_long` and
+//! `tcp_requests_with_headers_too_large` metrics.
+//!
+//! ```toml
+//! [[http_trackers]]
+//! bind_address = "0.0.0.0:7070"
+//!
+//! [http_trackers.request_limits]
+//! max_uri_bytes = 8192
+//! max_header_bytes = 16384
+//! ```
+//!
+//! > **NOTICE**: a `scrape` request with `74` infohashes is about 5 KiB long.
+//! > Raise `max_uri_bytes` if you raise `max_scrape_torrents`.
+//!
+//! Refer to the [`request_limits`](crate::servers::http::v1::middlewares::request_limits)
+//! middleware for more information.
+//!
+//! ## Health
+//!
+//! Each HTTP tracker serves two endpoints to check its health:
+//!
+//! - `/health_check`: it only returns `200` if the server is running. It's
+//!   used by the health check API.
+//! - `/health`: it returns `200` only if the tracker core, the torrents
+//!   repository and the database are responsive, so L7 load balancers can
+//!   check the actual service they route requests to. The path and the checks
+//!   are configurable.
+//!
+//! Refer to the [`health`](crate::servers::http::v1::handlers::health)
+//! handler for more information.
+//!
 //! ## Versioning
 //!
 //! Right not there is only version `v1`. The HTTP tracker implements BEPS:
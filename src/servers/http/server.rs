@@ -1,22 +1,31 @@
 //! Module to handle the HTTP server instances.
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
-use axum_server::tls_rustls::RustlsConfig;
+#[cfg(feature = "tls")]
+use axum_server::accept::DefaultAcceptor;
 use axum_server::Handle;
-use derive_more::Constructor;
+use camino::Utf8Path;
 use futures::future::BoxFuture;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tokio::sync::oneshot::{Receiver, Sender};
+use torrust_tracker_configuration::{Health, KeyPathFormat, RequestLimits};
 use tracing::instrument;
 
 use super::v1::routes::router;
 use crate::bootstrap::jobs::Started;
-use crate::core::Tracker;
-use crate::servers::custom_axum_server::{self, TimeoutAcceptor};
+use crate::core::virtual_trackers::VirtualHosts;
+use crate::core::{AnnounceMode, Tracker};
+use crate::servers::admission::AdmissionAcceptor;
+use crate::servers::bind_device::bind_tcp_listener;
+use crate::servers::custom_axum_server::{self, RustlsConfig, TimeoutAcceptor};
 use crate::servers::http::HTTP_TRACKER_LOG_TARGET;
 use crate::servers::logging::STARTED_ON;
-use crate::servers::registar::{ServiceHealthCheckJob, ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::proxy_protocol::{ProxyProtocolAcceptor, V2_LOCAL_HEADER};
+use crate::servers::registar::{Binding, ServiceHealthCheckJob, ServiceKind, ServiceRegistration, ServiceRegistrationForm};
 use crate::servers::signals::{graceful_shutdown, Halted};
+use crate::servers::unix_socket;
 
 /// Error that can occur when starting or stopping the HTTP server.
 ///
@@ -35,16 +44,50 @@ pub enum Error {
     Error(String),
 }
 
-#[derive(Constructor, Debug)]
+#[derive(Debug)]
 pub struct Launcher {
     pub bind_to: SocketAddr,
     pub tls: Option<RustlsConfig>,
+    pub proxy_protocol: bool,
+    pub key_path_format: KeyPathFormat,
+    pub health: Health,
+    pub announce_mode: AnnounceMode,
+    pub virtual_hosts: VirtualHosts,
+    pub request_limits: RequestLimits,
+    pub bind_device: Option<String>,
 }
 
 impl Launcher {
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        bind_to: SocketAddr,
+        tls: Option<RustlsConfig>,
+        proxy_protocol: bool,
+        key_path_format: KeyPathFormat,
+        health: Health,
+        announce_mode: AnnounceMode,
+        virtual_hosts: VirtualHosts,
+        request_limits: RequestLimits,
+        bind_device: Option<String>,
+    ) -> Self {
+        Self {
+            bind_to,
+            tls,
+            proxy_protocol,
+            key_path_format,
+            health,
+            announce_mode,
+            virtual_hosts,
+            request_limits,
+            bind_device,
+        }
+    }
+
     #[instrument(skip(self, tracker, tx_start, rx_halt))]
     fn start(&self, tracker: Arc<Tracker>, tx_start: Sender<Started>, rx_halt: Receiver<Halted>) -> BoxFuture<'static, ()> {
-        let socket = std::net::TcpListener::bind(self.bind_to).expect("Could not bind tcp_listener to address.");
+        let socket =
+            bind_tcp_listener(self.bind_to, self.bind_device.as_deref()).expect("Could not bind tcp_listener to address.");
         let address = socket.local_addr().expect("Could not get local_addr from tcp_listener.");
 
         let handle = Handle::new();
@@ -60,24 +103,62 @@ impl Launcher {
 
         tracing::info!(target: HTTP_TRACKER_LOG_TARGET, "Starting on: {protocol}://{}", address);
 
-        let app = router(tracker, address);
-
-        let running = Box::pin(async {
-            match tls {
-                Some(tls) => custom_axum_server::from_tcp_rustls_with_timeouts(socket, tls)
+        let file_descriptors = tracker.file_descriptors();
+        let trusted_proxies: Arc<[IpAddr]> = tracker.get_trusted_proxies().into();
+
+        let app = router(
+            tracker,
+            &self.virtual_hosts,
+            address,
+            self.key_path_format,
+            &self.health,
+            self.announce_mode,
+            self.request_limits,
+        );
+
+        let proxy_protocol = self.proxy_protocol;
+
+        let running = Box::pin(async move {
+            match (tls, proxy_protocol) {
+                #[cfg(feature = "tls")]
+                (Some(tls), false) => custom_axum_server::from_tcp_rustls_with_timeouts(socket, tls)
                     .handle(handle)
                     // The TimeoutAcceptor is commented because TSL does not work with it.
                     // See: https://github.com/torrust/torrust-index/issues/204#issuecomment-2115529214
                     //.acceptor(TimeoutAcceptor)
+                    .map(|acceptor| acceptor.acceptor(AdmissionAcceptor::closing(DefaultAcceptor, file_descriptors)))
                     .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
                     .await
                     .expect("Axum server crashed."),
-                None => custom_axum_server::from_tcp_with_timeouts(socket)
+                #[cfg(feature = "tls")]
+                (Some(tls), true) => custom_axum_server::from_tcp_rustls_with_timeouts(socket, tls)
+                    .map(|acceptor| {
+                        acceptor.acceptor(AdmissionAcceptor::closing(
+                            ProxyProtocolAcceptor::new(DefaultAcceptor, trusted_proxies),
+                            file_descriptors,
+                        ))
+                    })
                     .handle(handle)
-                    .acceptor(TimeoutAcceptor)
+                    .serve(app.into_make_service())
+                    .await
+                    .expect("Axum server crashed."),
+                #[cfg(not(feature = "tls"))]
+                (Some(tls), _) => match tls {},
+                (None, false) => custom_axum_server::from_tcp_with_timeouts(socket)
+                    .handle(handle)
+                    .acceptor(AdmissionAcceptor::new(TimeoutAcceptor, file_descriptors))
                     .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
                     .await
                     .expect("Axum server crashed."),
+                (None, true) => custom_axum_server::from_tcp_with_timeouts(socket)
+                    .handle(handle)
+                    .acceptor(AdmissionAcceptor::new(
+                        ProxyProtocolAcceptor::new(TimeoutAcceptor, trusted_proxies),
+                        file_descriptors,
+                    ))
+                    .serve(app.into_make_service())
+                    .await
+                    .expect("Axum server crashed."),
             }
         });
 
@@ -159,6 +240,14 @@ impl HttpServer<Stopped> {
 
         let launcher = self.state.launcher;
 
+        let tls = launcher.tls.is_some();
+
+        let check_fn = if launcher.proxy_protocol {
+            check_fn_behind_proxy
+        } else {
+            check_fn
+        };
+
         let task = tokio::spawn(async move {
             let server = launcher.start(tracker, tx_start, rx_halt);
 
@@ -169,7 +258,7 @@ impl HttpServer<Stopped> {
 
         let binding = rx_start.await.expect("it should be able to start the service").address;
 
-        form.send(ServiceRegistration::new(binding, check_fn))
+        form.send(ServiceRegistration::new(binding, ServiceKind::HttpTracker, tls, check_fn))
             .expect("it should be able to send service registration");
 
         Ok(HttpServer {
@@ -225,6 +314,64 @@ pub fn check_fn(binding: &SocketAddr) -> ServiceHealthCheckJob {
     ServiceHealthCheckJob::new(*binding, info, job)
 }
 
+/// Checks the Health by connecting to the HTTP tracker endpoint bound to a Unix
+/// domain socket.
+///
+/// # Errors
+///
+/// This function will return an error if unable to connect.
+/// Or if the request returns an error.
+#[must_use]
+pub fn check_unix_socket_fn(path: &Utf8Path) -> ServiceHealthCheckJob {
+    let path = path.to_path_buf();
+
+    let info = format!("checking http tracker health check at: unix:{path}/health_check");
+
+    let job = {
+        let path = path.clone();
+        tokio::spawn(async move { unix_socket::get_status(&path, "/health_check").await })
+    };
+
+    ServiceHealthCheckJob::new(Binding::Unix(path), info, job)
+}
+
+/// Checks the Health of a HTTP tracker expecting a PROXY protocol header. It
+/// sends a header with the `LOCAL` command, like load balancers do for their
+/// own health checks.
+///
+/// # Errors
+///
+/// This function will return an error if unable to connect.
+/// Or if the request returns an error.
+#[must_use]
+pub fn check_fn_behind_proxy(binding: &SocketAddr) -> ServiceHealthCheckJob {
+    let binding = *binding;
+
+    let info = format!("checking http tracker (behind a PROXY protocol proxy) health check at: http://{binding}/health_check"); // DevSkim: ignore DS137138
+
+    let job = tokio::spawn(async move {
+        let mut stream = TcpStream::connect(binding).await.map_err(|err| err.to_string())?;
+
+        stream.write_all(&V2_LOCAL_HEADER).await.map_err(|err| err.to_string())?;
+        stream
+            .write_all(format!("GET /health_check HTTP/1.1\r\nHost: {binding}\r\nConnection: close\r\n\r\n").as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.map_err(|err| err.to_string())?;
+
+        response
+            .lines()
+            .next()
+            .and_then(|status_line| status_line.split_once(' '))
+            .map(|(_version, status)| status.to_string())
+            .ok_or_else(|| format!("invalid HTTP response: {response}"))
+    });
+
+    ServiceHealthCheckJob::new(binding, info, job)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -233,6 +380,8 @@ mod tests {
 
     use crate::bootstrap::app::initialize_with_configuration;
     use crate::bootstrap::jobs::make_rust_tls;
+    use crate::core::virtual_trackers::VirtualHosts;
+    use crate::core::AnnounceMode;
     use crate::servers::http::server::{HttpServer, Launcher};
     use crate::servers::registar::Registar;
 
@@ -243,7 +392,11 @@ mod tests {
         let http_trackers = cfg.http_trackers.clone().expect("missing HTTP trackers configuration");
         let config = &http_trackers[0];
 
-        let bind_to = config.bind_address;
+        let bind_to = config
+            .bind_address
+            .first()
+            .socket_addr()
+            .expect("it should be bound to a TCP socket address");
 
         let tls = make_rust_tls(&config.tsl_config)
             .await
@@ -251,7 +404,17 @@ mod tests {
 
         let register = &Registar::default();
 
-        let stopped = HttpServer::new(Launcher::new(bind_to, tls));
+        let stopped = HttpServer::new(Launcher::new(
+            bind_to,
+            tls,
+            config.proxy_protocol,
+            config.key_path_format,
+            config.health.clone(),
+            AnnounceMode::new(config.async_announce),
+            VirtualHosts::default(),
+            config.request_limits,
+            config.bind_device.clone(),
+        ));
         let started = stopped
             .start(tracker, register.give_form())
             .await
@@ -35,13 +35,17 @@ use axum::response::{IntoResponse, Response};
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
+use crate::core::slow_requests::{Phase, Timer};
 use crate::servers::http::v1::query::Query;
 use crate::servers::http::v1::requests::announce::{Announce, ParseAnnounceQueryError};
 use crate::servers::http::v1::responses;
 
 /// Extractor for the [`Announce`]
 /// request.
-pub struct ExtractRequest(pub Announce);
+///
+/// It also starts the [`Timer`] of the slow requests logging, with the time
+/// spent parsing the request.
+pub struct ExtractRequest(pub Announce, pub Timer);
 
 impl<S> FromRequestParts<S> for ExtractRequest
 where
@@ -49,7 +53,6 @@ where
 {
     type Rejection = Response;
 
-    #[must_use]
     fn from_request_parts<'life0, 'life1, 'async_trait>(
         parts: &'life0 mut Parts,
         _state: &'life1 S,
@@ -60,8 +63,13 @@ where
         Self: 'async_trait,
     {
         async {
+            let mut timer = Timer::start();
+
             match extract_announce_from(parts.uri.query()) {
-                Ok(announce_request) => Ok(ExtractRequest(announce_request)),
+                Ok(announce_request) => {
+                    timer.lap(Phase::Parse);
+                    Ok(ExtractRequest(announce_request, timer))
+                }
                 Err(error) => Err(error.into_response()),
             }
         }
@@ -127,6 +135,8 @@ mod tests {
                 event: Some(Event::Completed),
                 compact: Some(Compact::NotAccepted),
                 numwant: Some(50),
+                corrupt: None,
+                redundant: None,
             }
         );
     }
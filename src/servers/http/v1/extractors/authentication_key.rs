@@ -75,7 +75,6 @@ where
 {
     type Rejection = Response;
 
-    #[must_use]
     fn from_request_parts<'life0, 'life1, 'async_trait>(
         parts: &'life0 mut Parts,
         state: &'life1 S,
@@ -8,50 +8,65 @@
 use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
 use std::sync::Arc;
+use std::time::Instant;
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
 use axum::extract::State;
+use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use torrust_tracker_clock::clock::Time;
+use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer;
 
 use crate::core::auth::Key;
-use crate::core::{AnnounceData, PeersWanted, Tracker};
+use crate::core::slow_requests::{self, Kind, Phase, Timer};
+use crate::core::{statistics, AnnounceData, AnnounceMode, PeersWanted, Requester, Tracker};
 use crate::servers::http::v1::extractors::announce_request::ExtractRequest;
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
 use crate::servers::http::v1::handlers::common::auth;
+use crate::servers::http::v1::handlers::tracker_error_response;
 use crate::servers::http::v1::requests::announce::{Announce, Compact, Event};
-use crate::servers::http::v1::responses::{self};
+use crate::servers::http::v1::responses::announce::{padding_for, Padding};
+use crate::servers::http::v1::responses::{self, Response as _};
 use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
 use crate::servers::http::v1::services::{self, peer_ip_resolver};
+use crate::servers::unix_socket::UNIX_SOCKET_CLIENT_ADDR;
 use crate::CurrentClock;
 
 /// It handles the `announce` request when the HTTP tracker does not require
 /// authentication (no PATH `key` parameter required).
 #[allow(clippy::unused_async)]
 pub async fn handle_without_key(
-    State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(announce_request): ExtractRequest,
+    State((tracker, announce_mode)): State<(Arc<Tracker>, AnnounceMode)>,
+    ExtractRequest(announce_request, timer): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
 ) -> Response {
     tracing::debug!("http announce request: {:#?}", announce_request);
 
-    handle(&tracker, &announce_request, &client_ip_sources, None).await
+    handle(&tracker, &announce_request, &client_ip_sources, None, announce_mode, timer).await
 }
 
 /// It handles the `announce` request when the HTTP tracker requires
 /// authentication (PATH `key` parameter required).
 #[allow(clippy::unused_async)]
 pub async fn handle_with_key(
-    State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(announce_request): ExtractRequest,
+    State((tracker, announce_mode)): State<(Arc<Tracker>, AnnounceMode)>,
+    ExtractRequest(announce_request, timer): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
     ExtractKey(key): ExtractKey,
 ) -> Response {
     tracing::debug!("http announce request: {:#?}", announce_request);
 
-    handle(&tracker, &announce_request, &client_ip_sources, Some(key)).await
+    handle(
+        &tracker,
+        &announce_request,
+        &client_ip_sources,
+        Some(key),
+        announce_mode,
+        timer,
+    )
+    .await
 }
 
 /// It handles the `announce` request.
@@ -63,12 +78,45 @@ async fn handle(
     announce_request: &Announce,
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
+    announce_mode: AnnounceMode,
+    mut timer: Timer,
 ) -> Response {
-    let announce_data = match handle_announce(tracker, announce_request, client_ip_sources, maybe_key).await {
-        Ok(announce_data) => announce_data,
-        Err(error) => return error.into_response(),
+    let start_time = Instant::now();
+
+    tracker.inject_request_latency().await;
+
+    let result = handle_announce(
+        tracker,
+        announce_request,
+        client_ip_sources,
+        maybe_key,
+        announce_mode,
+        &mut timer,
+    )
+    .await;
+
+    if let Some(slo) = tracker.slo() {
+        slo.record(start_time.elapsed(), result.is_err());
+    }
+
+    let response = match result {
+        Ok(announce_data) => build_response(tracker, announce_request, announce_data).await,
+        Err(error) => {
+            // See `deadline::Admission`.
+            timer.lap(Phase::Authorization);
+            error.into_response()
+        }
     };
-    build_response(announce_request, announce_data)
+
+    timer.lap(Phase::Serialize);
+
+    tracker.log_slow_request(Kind::Announce, &timer, || slow_requests::Request {
+        info_hashes: vec![announce_request.info_hash],
+        client_ip: peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources).ok(),
+        target: None,
+    });
+
+    response
 }
 
 /* code-review: authentication, authorization and peer IP resolution could be moved
@@ -82,51 +130,179 @@ async fn handle_announce(
     announce_request: &Announce,
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
+    announce_mode: AnnounceMode,
+    timer: &mut Timer,
 ) -> Result<AnnounceData, responses::error::Error> {
-    // Authentication
-    if tracker.requires_authentication() {
-        match maybe_key {
-            Some(key) => match tracker.authenticate(&key).await {
-                Ok(()) => (),
-                Err(error) => return Err(responses::error::Error::from(error)),
-            },
-            None => {
-                return Err(responses::error::Error::from(auth::Error::MissingAuthKey {
-                    location: Location::caller(),
-                }))
-            }
-        }
-    }
-
-    // Authorization
-    match tracker.authorize(&announce_request.info_hash).await {
+    // Maintenance
+    match tracker.check_maintenance() {
         Ok(()) => (),
-        Err(error) => return Err(responses::error::Error::from(error)),
+        Err(error) => return Err(tracker_error_response(tracker, error)),
     }
 
+    // Authentication and authorization, unless they were recently cached
+    let requester = match &maybe_key {
+        Some(key) if tracker.is_authorization_cached(key, &announce_request.info_hash) => Requester::Authenticated,
+        maybe_key => authenticate_and_authorize(tracker, &announce_request.info_hash, maybe_key.as_ref()).await?,
+    };
+
     let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
         Ok(peer_ip) => peer_ip,
         Err(error) => return Err(responses::error::Error::from(error)),
     };
 
+    let authenticated_key = match (requester, &maybe_key) {
+        (Requester::Authenticated, Some(key)) => Some(key),
+        _ => None,
+    };
+
+    // Key binding
+    if let Some(key) = authenticated_key {
+        if let Err(error) = tracker.verify_key_binding(key, &peer_ip).await {
+            tracker
+                .send_stats_event(statistics::Event::TcpAnnounceKeyBindingRejected)
+                .await;
+            return Err(responses::error::Error::from(error));
+        }
+    }
+
+    // Key quota
+    let remaining_peers = match authenticated_key.map(|key| tracker.consume_key_announce(key)) {
+        Some(Ok(remaining_peers)) => remaining_peers,
+        Some(Err(error)) => return Err(tracker_error_response(tracker, error)),
+        None => None,
+    };
+
     let mut peer = peer_from_request(announce_request, &peer_ip);
+
+    // Torrent policy
+    match tracker.authorize_peer(&announce_request.info_hash, &peer).await {
+        Ok(()) => (),
+        Err(error) => return Err(tracker_error_response(tracker, error)),
+    }
+
     let peers_wanted = match announce_request.numwant {
         Some(numwant) => PeersWanted::only(numwant),
         None => PeersWanted::All,
     };
 
-    let announce_data = services::announce::invoke(tracker.clone(), announce_request.info_hash, &mut peer, &peers_wanted).await;
+    let peers_wanted = match remaining_peers {
+        Some(remaining_peers) => peers_wanted.at_most(remaining_peers),
+        None => peers_wanted,
+    };
+
+    if let Some(Event::Unknown(_)) = announce_request.event {
+        tracker.send_stats_event(statistics::Event::TcpAnnounceWithUnknownEvent).await;
+    }
+
+    timer.lap(Phase::Authorization);
+
+    let announce_data = services::announce::invoke(
+        tracker.clone(),
+        &requester,
+        announce_request.info_hash,
+        &mut peer,
+        &peers_wanted,
+        announce_mode,
+    )
+    .await;
+
+    // Addresses taken from the `X-Forwarded-For` header are only probed when
+    // the header was set by a trusted proxy.
+    if !tracker.is_behind_reverse_proxy() || is_from_trusted_proxy(tracker, client_ip_sources) {
+        tracker.sample_peer_reachability(&announce_request.info_hash, &peer.peer_addr);
+    }
+
+    timer.lap(Phase::Repository);
+
+    if let Some(key) = authenticated_key {
+        tracker.consume_key_peers(key, announce_data.peers.len());
+    }
 
     Ok(announce_data)
 }
 
-fn build_response(announce_request: &Announce, announce_data: AnnounceData) -> Response {
+/// Requests received on a Unix domain socket always come from a local proxy.
+fn is_from_trusted_proxy(tracker: &Tracker, client_ip_sources: &ClientIpSources) -> bool {
+    client_ip_sources
+        .connection_info_ip
+        .is_some_and(|ip| ip == UNIX_SOCKET_CLIENT_ADDR.ip() || tracker.is_trusted_proxy(&ip))
+}
+
+async fn authenticate_and_authorize(
+    tracker: &Arc<Tracker>,
+    info_hash: &InfoHash,
+    maybe_key: Option<&Key>,
+) -> Result<Requester, responses::error::Error> {
+    // Authentication
+    let requester = match maybe_key {
+        Some(key) => match tracker.authenticate(key).await {
+            Ok(()) => Requester::Authenticated,
+            Err(error) if tracker.requires_authentication() => return Err(responses::error::Error::from(error)),
+            // The key is optional when the tracker is not private
+            Err(_) => Requester::Anonymous,
+        },
+        None if tracker.requires_authentication() => {
+            return Err(responses::error::Error::from(auth::Error::MissingAuthKey {
+                location: Location::caller(),
+            }))
+        }
+        None => Requester::Anonymous,
+    };
+
+    // Authorization
+    match tracker.authorize(info_hash).await {
+        Ok(()) => (),
+        Err(error) => return Err(tracker_error_response(tracker, error)),
+    }
+
+    if let (Requester::Authenticated, Some(key)) = (requester, maybe_key) {
+        tracker.cache_authorization(key, info_hash).await;
+    }
+
+    Ok(requester)
+}
+
+async fn build_response(tracker: &Tracker, announce_request: &Announce, announce_data: AnnounceData) -> Response {
     if announce_request.compact.as_ref().is_some_and(|f| *f == Compact::Accepted) {
-        let response: responses::Announce<responses::Compact> = announce_data.into();
-        response.into_response()
+        encode::<responses::Compact>(tracker, announce_data).await
     } else {
-        let response: responses::Announce<responses::Normal> = announce_data.into();
-        response.into_response()
+        encode::<responses::Normal>(tracker, announce_data).await
+    }
+}
+
+/// It encodes the response, padded to the minimum size when the
+/// `announce_response_padding` is enabled, and it sends the response size to
+/// the statistics.
+async fn encode<E>(tracker: &Tracker, announce_data: AnnounceData) -> Response
+where
+    E: From<AnnounceData> + Into<Vec<u8>> + Padding,
+{
+    let min_size = tracker.get_announce_response_min_size();
+
+    // The data is only needed again to encode the padded response.
+    let padded_data = min_size.map(|_| announce_data.clone());
+
+    let response: responses::Announce<E> = announce_data.into();
+
+    let bytes = match response.body() {
+        Ok(bytes) => bytes,
+        Err(error) => return error.into_response(),
+    };
+
+    let padding = min_size.and_then(|min_size| padding_for(bytes.len(), min_size));
+
+    tracker
+        .send_stats_event(statistics::Event::TcpAnnounceResponse {
+            size: bytes.len(),
+            padded: padding.is_some(),
+        })
+        .await;
+
+    match (padding, padded_data) {
+        (Some(padding), Some(announce_data)) => responses::Announce::<E>::from(announce_data)
+            .with_padding(padding)
+            .into_response(),
+        _ => (StatusCode::OK, bytes).into_response(),
     }
 }
 
@@ -143,6 +319,9 @@ fn peer_from_request(announce_request: &Announce, peer_ip: &IpAddr) -> peer::Pee
         downloaded: announce_request.downloaded.unwrap_or(NumberOfBytes::new(0)),
         left: announce_request.left.unwrap_or(NumberOfBytes::new(0)),
         event: map_to_torrust_event(&announce_request.event),
+        partial_seed: announce_request.event == Some(Event::Paused),
+        corrupt: announce_request.corrupt.unwrap_or(NumberOfBytes::new(0)),
+        redundant: announce_request.redundant.unwrap_or(NumberOfBytes::new(0)),
     }
 }
 
@@ -153,6 +332,7 @@ pub fn map_to_aquatic_event(event: &Option<Event>) -> aquatic_udp_protocol::Anno
             Event::Started => aquatic_udp_protocol::AnnounceEvent::Started,
             Event::Stopped => aquatic_udp_protocol::AnnounceEvent::Stopped,
             Event::Completed => aquatic_udp_protocol::AnnounceEvent::Completed,
+            Event::Paused | Event::None | Event::Unknown(_) => aquatic_udp_protocol::AnnounceEvent::None,
         },
         None => aquatic_udp_protocol::AnnounceEvent::None,
     }
@@ -165,6 +345,7 @@ pub fn map_to_torrust_event(event: &Option<Event>) -> AnnounceEvent {
             Event::Started => AnnounceEvent::Started,
             Event::Stopped => AnnounceEvent::Stopped,
             Event::Completed => AnnounceEvent::Completed,
+            Event::Paused | Event::None | Event::Unknown(_) => AnnounceEvent::None,
         },
         None => AnnounceEvent::None,
     }
@@ -210,6 +391,8 @@ mod tests {
             event: None,
             compact: None,
             numwant: None,
+            corrupt: None,
+            redundant: None,
         }
     }
 
@@ -227,13 +410,60 @@ mod tests {
         );
     }
 
+    mod building_the_peer_from_the_request {
+
+        use std::net::{IpAddr, Ipv4Addr};
+
+        use aquatic_udp_protocol::NumberOfBytes;
+
+        use super::sample_announce_request;
+        use crate::servers::http::v1::handlers::announce::peer_from_request;
+        use crate::servers::http::v1::requests::announce::{Announce, Event};
+
+        #[test]
+        fn it_should_mark_the_peer_as_a_partial_seed_when_the_event_is_paused() {
+            let announce_request = Announce {
+                event: Some(Event::Paused),
+                ..sample_announce_request()
+            };
+
+            let peer = peer_from_request(&announce_request, &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)));
+
+            assert!(peer.partial_seed);
+            assert_eq!(peer.event, aquatic_udp_protocol::AnnounceEvent::None);
+        }
+
+        #[test]
+        fn it_should_store_the_extended_client_stats_in_the_peer() {
+            let announce_request = Announce {
+                corrupt: Some(NumberOfBytes::new(1024)),
+                redundant: Some(NumberOfBytes::new(2048)),
+                ..sample_announce_request()
+            };
+
+            let peer = peer_from_request(&announce_request, &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)));
+
+            assert_eq!(peer.corrupt, NumberOfBytes::new(1024));
+            assert_eq!(peer.redundant, NumberOfBytes::new(2048));
+        }
+
+        #[test]
+        fn it_should_set_the_extended_client_stats_to_zero_when_the_client_does_not_report_them() {
+            let peer = peer_from_request(&sample_announce_request(), &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)));
+
+            assert_eq!(peer.corrupt, NumberOfBytes::new(0));
+            assert_eq!(peer.redundant, NumberOfBytes::new(0));
+        }
+    }
+
     mod with_tracker_in_private_mode {
 
         use std::str::FromStr;
         use std::sync::Arc;
 
         use super::{private_tracker, sample_announce_request, sample_client_ip_sources};
-        use crate::core::auth;
+        use crate::core::slow_requests::Timer;
+        use crate::core::{auth, AnnounceMode};
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
 
@@ -243,9 +473,16 @@ mod tests {
 
             let maybe_key = None;
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &sample_client_ip_sources(),
+                maybe_key,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -261,9 +498,16 @@ mod tests {
 
             let maybe_key = Some(unregistered_key);
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &sample_client_ip_sources(),
+                maybe_key,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(&response, "Authentication error: Failed to read key");
         }
@@ -274,6 +518,8 @@ mod tests {
         use std::sync::Arc;
 
         use super::{sample_announce_request, sample_client_ip_sources, whitelisted_tracker};
+        use crate::core::slow_requests::Timer;
+        use crate::core::AnnounceMode;
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
 
@@ -283,9 +529,16 @@ mod tests {
 
             let announce_request = sample_announce_request();
 
-            let response = handle_announce(&tracker, &announce_request, &sample_client_ip_sources(), None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &announce_request,
+                &sample_client_ip_sources(),
+                None,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -299,13 +552,70 @@ mod tests {
 
     mod with_tracker_on_reverse_proxy {
 
+        use std::net::{IpAddr, Ipv4Addr};
         use std::sync::Arc;
 
+        use torrust_tracker_configuration::PeerReachability;
+        use torrust_tracker_test_helpers::configuration;
+
         use super::{sample_announce_request, tracker_on_reverse_proxy};
+        use crate::core::services::tracker_factory;
+        use crate::core::slow_requests::Timer;
+        use crate::core::{AnnounceMode, Tracker};
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
 
+        fn trusted_proxy_ip() -> IpAddr {
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))
+        }
+
+        fn tracker_verifying_every_peer_behind_a_trusted_proxy() -> Tracker {
+            let mut configuration = configuration::ephemeral_with_reverse_proxy();
+            configuration.core.net.trusted_proxies = vec![trusted_proxy_ip()];
+            configuration.core.peer_reachability = Some(PeerReachability {
+                sample_one_in: 1,
+                ..Default::default()
+            });
+            tracker_factory(&configuration)
+        }
+
+        async fn announce_from(tracker: &Arc<Tracker>, proxy_ip: IpAddr) {
+            let client_ip_sources = ClientIpSources {
+                right_most_x_forwarded_for: Some(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1))),
+                connection_info_ip: Some(proxy_ip),
+            };
+
+            handle_announce(
+                tracker,
+                &sample_announce_request(),
+                &client_ip_sources,
+                None,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap();
+        }
+
+        #[tokio::test]
+        async fn it_should_probe_the_peer_address_forwarded_by_a_trusted_proxy() {
+            let tracker = Arc::new(tracker_verifying_every_peer_behind_a_trusted_proxy());
+
+            announce_from(&tracker, trusted_proxy_ip()).await;
+
+            assert!(tracker.next_reachability_probe().is_some());
+        }
+
+        #[tokio::test]
+        async fn it_should_not_probe_the_peer_address_forwarded_by_an_untrusted_proxy() {
+            let tracker = Arc::new(tracker_verifying_every_peer_behind_a_trusted_proxy());
+
+            announce_from(&tracker, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))).await;
+
+            assert!(tracker.next_reachability_probe().is_none());
+        }
+
         #[tokio::test]
         async fn it_should_fail_when_the_right_most_x_forwarded_for_header_ip_is_not_available() {
             let tracker = Arc::new(tracker_on_reverse_proxy());
@@ -315,9 +625,16 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &client_ip_sources,
+                None,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -331,6 +648,8 @@ mod tests {
         use std::sync::Arc;
 
         use super::{sample_announce_request, tracker_not_on_reverse_proxy};
+        use crate::core::slow_requests::Timer;
+        use crate::core::AnnounceMode;
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -344,9 +663,16 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &client_ip_sources,
+                None,
+                AnnounceMode::Synchronous,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
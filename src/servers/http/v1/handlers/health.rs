@@ -0,0 +1,127 @@
+//! Axum [`handlers`](axum#handlers) for the health endpoint.
+//!
+//! Unlike the `/health_check` endpoint, which only tells whether the server
+//! is running, the health endpoint checks the services the tracker depends
+//! on, so L7 load balancers can stop routing requests to a tracker instance
+//! that can't handle them.
+//!
+//! It's served on `/health` by default. The path and the checks can be
+//! changed in the configuration:
+//!
+//! ```toml
+//! [[http_trackers]]
+//! bind_address = "0.0.0.0:7070"
+//!
+//! [http_trackers.health]
+//! path = "/health"
+//! checks = ["core", "repository", "database"]
+//! ```
+//!
+//! It returns a `200` response if all the checks pass, and a `503` response
+//! otherwise. Each check fails if it does not finish in [`CHECK_TIMEOUT`].
+//!
+//! **Example response** `503`
+//!
+//! ```json
+//! {
+//!     "status": "Error",
+//!     "checks": [
+//!         { "check": "core", "status": "Error", "error": "the tracker is in maintenance mode" },
+//!         { "check": "repository", "status": "Ok", "error": null },
+//!         { "check": "database", "status": "Ok", "error": null }
+//!     ]
+//! }
+//! ```
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_configuration::HealthCheck;
+
+use super::health_check::Status;
+use crate::core::Tracker;
+
+/// Time to wait for each check to finish.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The report for all the checks.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// `Ok` only if all the checks passed.
+    pub status: Status,
+    pub checks: Vec<CheckReport>,
+}
+
+/// The report for one check.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct CheckReport {
+    pub check: HealthCheck,
+    pub status: Status,
+    /// Why the check failed.
+    pub error: Option<String>,
+}
+
+/// It handles the health requests.
+///
+/// It runs all the configured checks concurrently.
+pub async fn handler(State((tracker, checks)): State<(Arc<Tracker>, Arc<[HealthCheck]>)>) -> (StatusCode, Json<Report>) {
+    let checks = join_all(checks.iter().map(|check| run(tracker.clone(), *check))).await;
+
+    let (code, status) = if checks.iter().all(|check| check.status == Status::Ok) {
+        (StatusCode::OK, Status::Ok)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Status::Error)
+    };
+
+    (code, Json(Report { status, checks }))
+}
+
+async fn run(tracker: Arc<Tracker>, check: HealthCheck) -> CheckReport {
+    let result = match check {
+        HealthCheck::Core => {
+            if tracker.is_in_maintenance() {
+                Err("the tracker is in maintenance mode".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        HealthCheck::Repository => {
+            run_blocking(move || {
+                tracker.check_torrents_repository();
+                Ok(())
+            })
+            .await
+        }
+        HealthCheck::Database => run_blocking(move || tracker.ping_database().map_err(|err| err.to_string())).await,
+    };
+
+    match result {
+        Ok(()) => CheckReport {
+            check,
+            status: Status::Ok,
+            error: None,
+        },
+        Err(error) => CheckReport {
+            check,
+            status: Status::Error,
+            error: Some(error),
+        },
+    }
+}
+
+/// The repository and database checks can block (for example, waiting for a
+/// lock or a connection), so they run on the blocking threads.
+async fn run_blocking<F>(check: F) -> Result<(), String>
+where
+    F: FnOnce() -> Result<(), String> + Send + 'static,
+{
+    match tokio::time::timeout(CHECK_TIMEOUT, tokio::task::spawn_blocking(check)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(err)) => Err(format!("the check failed: {err}")),
+        Err(_) => Err(format!("the check did not finish in {} ms", CHECK_TIMEOUT.as_millis())),
+    }
+}
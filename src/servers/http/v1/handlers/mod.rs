@@ -4,16 +4,40 @@
 //! more information about the HTTP tracker.
 use super::responses;
 use crate::core::error::Error;
+use crate::core::Tracker;
 
 pub mod announce;
 pub mod common;
+pub mod health;
 pub mod health_check;
 pub mod scrape;
 
 impl From<Error> for responses::error::Error {
     fn from(err: Error) -> Self {
+        let retry_in = match err {
+            Error::TrackerInMaintenance { retry_in, .. } | Error::KeyQuotaExceeded { retry_in, .. } => Some(retry_in),
+            _ => None,
+        };
+
         responses::error::Error {
             failure_reason: format!("Tracker error: {err}"),
+            retry_in,
         }
     }
 }
+
+/// It builds the error response for an error returned by the domain tracker.
+///
+/// The failure reason is replaced with the template for the error code, when
+/// the operator has configured one. See [`failure_reasons`](crate::core::failure_reasons).
+pub(crate) fn tracker_error_response(tracker: &Tracker, err: Error) -> responses::error::Error {
+    let failure_reason = tracker.render_failure_reason(err.code(), &err.to_string());
+
+    let mut response = responses::error::Error::from(err);
+
+    if let Some(failure_reason) = failure_reason {
+        response.failure_reason = failure_reason;
+    }
+
+    response
+}
@@ -5,16 +5,19 @@
 //!
 //! The handlers perform the authentication and authorization of the request,
 //! and resolve the client IP address.
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use axum::extract::State;
 use axum::response::{IntoResponse, Response};
 
 use crate::core::auth::Key;
-use crate::core::{ScrapeData, Tracker};
+use crate::core::slow_requests::{self, Kind, Phase, Timer};
+use crate::core::{statistics, ScrapeData, Tracker};
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
 use crate::servers::http::v1::extractors::scrape_request::ExtractRequest;
+use crate::servers::http::v1::handlers::tracker_error_response;
 use crate::servers::http::v1::requests::scrape::Scrape;
 use crate::servers::http::v1::services::peer_ip_resolver::{self, ClientIpSources};
 use crate::servers::http::v1::{responses, services};
@@ -24,12 +27,12 @@ use crate::servers::http::v1::{responses, services};
 #[allow(clippy::unused_async)]
 pub async fn handle_without_key(
     State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(scrape_request): ExtractRequest,
+    ExtractRequest(scrape_request, timer): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
 ) -> Response {
     tracing::debug!("http scrape request: {:#?}", &scrape_request);
 
-    handle(&tracker, &scrape_request, &client_ip_sources, None).await
+    handle(&tracker, &scrape_request, &client_ip_sources, None, timer).await
 }
 
 /// It handles the `scrape` request when the HTTP tracker is configured
@@ -39,13 +42,13 @@ pub async fn handle_without_key(
 #[allow(clippy::unused_async)]
 pub async fn handle_with_key(
     State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(scrape_request): ExtractRequest,
+    ExtractRequest(scrape_request, timer): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
     ExtractKey(key): ExtractKey,
 ) -> Response {
     tracing::debug!("http scrape request: {:#?}", &scrape_request);
 
-    handle(&tracker, &scrape_request, &client_ip_sources, Some(key)).await
+    handle(&tracker, &scrape_request, &client_ip_sources, Some(key), timer).await
 }
 
 async fn handle(
@@ -53,12 +56,28 @@ async fn handle(
     scrape_request: &Scrape,
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
+    mut timer: Timer,
 ) -> Response {
-    let scrape_data = match handle_scrape(tracker, scrape_request, client_ip_sources, maybe_key).await {
-        Ok(scrape_data) => scrape_data,
-        Err(error) => return error.into_response(),
+    tracker.inject_request_latency().await;
+
+    let response = match handle_scrape(tracker, scrape_request, client_ip_sources, maybe_key, &mut timer).await {
+        Ok(scrape_data) => build_response(scrape_data),
+        Err(error) => {
+            // See `deadline::Admission`.
+            timer.lap(Phase::Authorization);
+            error.into_response()
+        }
     };
-    build_response(scrape_data)
+
+    timer.lap(Phase::Serialize);
+
+    tracker.log_slow_request(Kind::Scrape, &timer, || slow_requests::Request {
+        info_hashes: scrape_request.info_hashes.clone(),
+        client_ip: peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources).ok(),
+        target: None,
+    });
+
+    response
 }
 
 /* code-review: authentication, authorization and peer IP resolution could be moved
@@ -72,7 +91,33 @@ async fn handle_scrape(
     scrape_request: &Scrape,
     client_ip_sources: &ClientIpSources,
     maybe_key: Option<Key>,
+    timer: &mut Timer,
 ) -> Result<ScrapeData, responses::error::Error> {
+    // Limit the number of scraped torrents (shared with the UDP tracker)
+    let max_scrape_torrents = tracker.get_max_scrape_torrents();
+
+    if scrape_request.info_hashes.len() > usize::from(max_scrape_torrents) {
+        // The rejection is only counted when the client IP can be resolved
+        if let Ok(peer_ip) = peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
+            match peer_ip {
+                IpAddr::V4(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp4ScrapeLimitExceeded).await;
+                }
+                IpAddr::V6(_) => {
+                    tracker.send_stats_event(statistics::Event::Tcp6ScrapeLimitExceeded).await;
+                }
+            }
+        }
+
+        return Err(responses::error::Error {
+            failure_reason: format!(
+                "Too many info-hashes in scrape request: {}, max is {max_scrape_torrents}",
+                scrape_request.info_hashes.len()
+            ),
+            retry_in: None,
+        });
+    }
+
     // Authentication
     let return_real_scrape_data = if tracker.requires_authentication() {
         match maybe_key {
@@ -87,18 +132,28 @@ async fn handle_scrape(
     };
 
     // Authorization for scrape requests is handled at the `Tracker` level
-    // for each torrent.
+    // for each torrent. Only the torrents removed from the whitelist are
+    // rejected.
+    if let Err(error) = tracker.check_scrape_of_removed_torrents(&scrape_request.info_hashes).await {
+        return Err(tracker_error_response(tracker, error));
+    }
 
     let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
         Ok(peer_ip) => peer_ip,
         Err(error) => return Err(responses::error::Error::from(error)),
     };
 
-    if return_real_scrape_data {
-        Ok(services::scrape::invoke(tracker, &scrape_request.info_hashes, &peer_ip).await)
+    timer.lap(Phase::Authorization);
+
+    let scrape_data = if return_real_scrape_data {
+        services::scrape::invoke(tracker, &scrape_request.info_hashes, &peer_ip).await
     } else {
-        Ok(services::scrape::fake(tracker, &scrape_request.info_hashes, &peer_ip).await)
-    }
+        services::scrape::fake(tracker, &scrape_request.info_hashes, &peer_ip).await
+    };
+
+    timer.lap(Phase::Repository);
+
+    Ok(scrape_data)
 }
 
 fn build_response(scrape_data: ScrapeData) -> Response {
@@ -160,6 +215,7 @@ mod tests {
         use std::sync::Arc;
 
         use super::{private_tracker, sample_client_ip_sources, sample_scrape_request};
+        use crate::core::slow_requests::Timer;
         use crate::core::{auth, ScrapeData};
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
 
@@ -170,9 +226,15 @@ mod tests {
             let scrape_request = sample_scrape_request();
             let maybe_key = None;
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                maybe_key,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -187,9 +249,15 @@ mod tests {
             let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
             let maybe_key = Some(unregistered_key);
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                maybe_key,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -202,6 +270,7 @@ mod tests {
         use std::sync::Arc;
 
         use super::{sample_client_ip_sources, sample_scrape_request, whitelisted_tracker};
+        use crate::core::slow_requests::Timer;
         use crate::core::ScrapeData;
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
 
@@ -211,9 +280,15 @@ mod tests {
 
             let scrape_request = sample_scrape_request();
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), None)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                None,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -225,6 +300,7 @@ mod tests {
         use std::sync::Arc;
 
         use super::{sample_scrape_request, tracker_on_reverse_proxy};
+        use crate::core::slow_requests::Timer;
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
         use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -238,9 +314,15 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_scrape(&tracker, &sample_scrape_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_scrape(
+                &tracker,
+                &sample_scrape_request(),
+                &client_ip_sources,
+                None,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -253,6 +335,7 @@ mod tests {
         use std::sync::Arc;
 
         use super::{sample_scrape_request, tracker_not_on_reverse_proxy};
+        use crate::core::slow_requests::Timer;
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
         use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -266,9 +349,15 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_scrape(&tracker, &sample_scrape_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_scrape(
+                &tracker,
+                &sample_scrape_request(),
+                &client_ip_sources,
+                None,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -276,4 +365,73 @@ mod tests {
             );
         }
     }
+
+    mod with_any_tracker_mode {
+        use std::future;
+        use std::sync::Arc;
+
+        use mockall::predicate::eq;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_test_helpers::configuration;
+
+        use super::sample_client_ip_sources;
+        use crate::core::services::tracker_factory;
+        use crate::core::slow_requests::Timer;
+        use crate::core::{statistics, Tracker};
+        use crate::servers::http::v1::handlers::scrape::handle_scrape;
+        use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
+        use crate::servers::http::v1::requests::scrape::Scrape;
+
+        #[tokio::test]
+        async fn it_should_fail_when_the_request_contains_more_info_hashes_than_the_configured_limit() {
+            let mut config = configuration::ephemeral_public();
+            config.core.max_scrape_torrents = 1;
+            let tracker = Arc::new(tracker_factory(&config));
+
+            let scrape_request = Scrape {
+                info_hashes: vec![InfoHash::from_bytes(&[1u8; 20]), InfoHash::from_bytes(&[2u8; 20])],
+            };
+
+            let response = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                None,
+                &mut Timer::start(),
+            )
+            .await
+            .unwrap_err();
+
+            assert_error_response(&response, "Too many info-hashes in scrape request: 2, max is 1");
+        }
+
+        #[tokio::test]
+        async fn it_should_send_the_tcp4_scrape_limit_exceeded_event_when_the_request_exceeds_the_configured_limit() {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Tcp4ScrapeLimitExceeded))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+
+            let mut config = configuration::ephemeral_without_reverse_proxy();
+            config.core.max_scrape_torrents = 1;
+            let tracker =
+                Arc::new(Tracker::new(&config.core, Some(Box::new(stats_event_sender_mock)), statistics::Repo::new()).unwrap());
+
+            let scrape_request = Scrape {
+                info_hashes: vec![InfoHash::from_bytes(&[1u8; 20]), InfoHash::from_bytes(&[2u8; 20])],
+            };
+
+            assert!(handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                None,
+                &mut Timer::start()
+            )
+            .await
+            .is_err());
+        }
+    }
 }
@@ -0,0 +1,2 @@
+//! HTTP tracker middlewares. See [Axum middlewares](axum::middleware).
+pub mod request_limits;
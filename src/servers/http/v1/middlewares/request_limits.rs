@@ -0,0 +1,80 @@
+//! Middleware limiting the size of the HTTP tracker requests.
+//!
+//! An `announce` or `scrape` URL with hundreds of `info_hash` params is parsed
+//! into hundreds of values, so the requests exceeding the configured
+//! [`RequestLimits`] are rejected before they reach the handlers:
+//!
+//! - `414 URI Too Long` when the URI is longer than `max_uri_bytes`.
+//! - `431 Request Header Fields Too Large` when the headers, names and values,
+//!   are larger than `max_header_bytes`.
+//!
+//! Unlike the other error responses, they don't have a `200 OK` status code,
+//! but the body is still a bencoded [`Error`] so that `BitTorrent` clients can
+//! show the reason. The rejected requests are counted in the
+//! `tcp_requests_with_uri_too_long` and `tcp_requests_with_headers_too_large`
+//! metrics.
+//!
+//! > **NOTICE**: the server also has its own limit for the size of the request
+//! > head, which rejects larger requests before this middleware.
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use torrust_tracker_configuration::RequestLimits;
+
+use crate::core::{statistics, Tracker};
+use crate::servers::http::v1::responses::error::Error;
+
+/// It rejects the requests exceeding the [`RequestLimits`].
+pub async fn limit_request_size(
+    State((tracker, limits)): State<(Arc<Tracker>, RequestLimits)>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let uri_bytes = request.uri().to_string().len();
+
+    if uri_bytes > limits.max_uri_bytes {
+        tracker.send_stats_event(statistics::Event::TcpUriTooLong).await;
+
+        return rejection(
+            StatusCode::URI_TOO_LONG,
+            format!(
+                "request URI too long: {uri_bytes} bytes, the maximum is {}",
+                limits.max_uri_bytes
+            ),
+        );
+    }
+
+    let header_bytes: usize = request
+        .headers()
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum();
+
+    if header_bytes > limits.max_header_bytes {
+        tracker.send_stats_event(statistics::Event::TcpHeadersTooLarge).await;
+
+        return rejection(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            format!(
+                "request headers too large: {header_bytes} bytes, the maximum is {}",
+                limits.max_header_bytes
+            ),
+        );
+    }
+
+    next.run(request).await
+}
+
+fn rejection(status: StatusCode, failure_reason: String) -> Response {
+    let body = Error {
+        failure_reason,
+        retry_in: None,
+    }
+    .write();
+
+    (status, body).into_response()
+}
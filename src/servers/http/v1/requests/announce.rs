@@ -25,6 +25,8 @@ const LEFT: &str = "left";
 const EVENT: &str = "event";
 const COMPACT: &str = "compact";
 const NUMWANT: &str = "numwant";
+const CORRUPT: &str = "corrupt";
+const REDUNDANT: &str = "redundant";
 
 /// The `Announce` request. Fields use the domain types after parsing the
 /// query params of the request.
@@ -45,7 +47,9 @@ const NUMWANT: &str = "numwant";
 ///     left: Some(NumberOfBytes::new(1)),
 ///     event: Some(Event::Started),
 ///     compact: Some(Compact::NotAccepted),
-///     numwant: Some(50)
+///     numwant: Some(50),
+///     corrupt: Some(NumberOfBytes::new(0)),
+///     redundant: Some(NumberOfBytes::new(0)),
 /// };
 /// ```
 ///
@@ -56,6 +60,20 @@ const NUMWANT: &str = "numwant";
 /// > **NOTICE**: The struct does not contain the `IP` of the peer. It's not
 /// > mandatory and it's not used by the tracker. The `IP` is obtained from the
 /// > request itself.
+///
+/// Some real-world clients do not follow the specification strictly. The
+/// parser accepts these deviations with explicit mappings:
+///
+/// | Client behavior                                   | Parsed as                      |
+/// |---------------------------------------------------|--------------------------------|
+/// | `left` (or `uploaded`, `downloaded`) omitted      | `None`                         |
+/// | Optional param with an empty value (`event=`)     | `None`                         |
+/// | `event=paused` ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)) | [`Event::Paused`] |
+/// | `event=empty` ([BEP 3](https://www.bittorrent.org/beps/bep_0003.html)) | [`Event::None`] |
+/// | Unknown `event` value (`event=foo`)               | [`Event::Unknown`]             |
+/// | `info_hash` as a 40-char hex string (any case)    | The [`InfoHash`] it represents |
+/// | `info_hash` as a 32-char base32 string (any case) | The [`InfoHash`] it represents |
+/// | Invalid `corrupt` or `redundant` value            | `None`                         |
 #[derive(Debug, PartialEq)]
 pub struct Announce {
     // Mandatory params
@@ -88,6 +106,15 @@ pub struct Announce {
     /// Number of peers that the client would receive from the tracker. The
     /// value is permitted to be zero.
     pub numwant: Option<u32>,
+
+    /// The number of bytes downloaded by the peer that failed the hash check.
+    /// It's a non-standard param sent by some clients, like `qBittorrent` and
+    /// `uTorrent`.
+    pub corrupt: Option<NumberOfBytes>,
+
+    /// The number of bytes downloaded by the peer more than once. It's a
+    /// non-standard param sent by some clients, like `qBittorrent`.
+    pub redundant: Option<NumberOfBytes>,
 }
 
 /// Errors that can occur when parsing the `Announce` request.
@@ -135,12 +162,15 @@ pub enum ParseAnnounceQueryError {
     },
 }
 
-/// The event that the peer is reporting: `started`, `completed` or `stopped`.
+/// The event that the peer is reporting: `started`, `completed`, `stopped` or
+/// `paused`.
 ///
-/// If the event is not present or empty that means that the peer is just
-/// updating its status. It's one of the announcements done at regular intervals.
+/// If the event is not present, empty or `empty` that means that the peer is
+/// just updating its status. It's one of the announcements done at regular
+/// intervals. Unknown values are not rejected, they are handled as no event.
 ///
 /// Refer to [BEP 03. The `BitTorrent Protocol` Specification](https://www.bittorrent.org/beps/bep_0003.html)
+/// and [BEP 21. Extension for partial seeds](https://www.bittorrent.org/beps/bep_0021.html)
 /// for more information.
 #[derive(PartialEq, Debug)]
 pub enum Event {
@@ -151,6 +181,17 @@ pub enum Event {
     /// Event sent when the download is complete.
     /// No `completed` is sent if the file was complete when started
     Completed,
+    /// Event sent by partial seeds, peers that have all the pieces they want
+    /// but not the whole torrent. The tracker handles it as a regular
+    /// announce.
+    Paused,
+    /// The explicit `empty` event, which is the same as not sending the
+    /// event. It's a regular announce.
+    None,
+    /// An event value the tracker does not know. It's handled as a regular
+    /// announce, like the UDP tracker does with unknown event values, and
+    /// it's counted in the statistics.
+    Unknown(String),
 }
 
 impl FromStr for Event {
@@ -161,11 +202,9 @@ impl FromStr for Event {
             "started" => Ok(Self::Started),
             "stopped" => Ok(Self::Stopped),
             "completed" => Ok(Self::Completed),
-            _ => Err(ParseAnnounceQueryError::InvalidParam {
-                param_name: EVENT.to_owned(),
-                param_value: raw_param.to_owned(),
-                location: Location::caller(),
-            }),
+            "paused" => Ok(Self::Paused),
+            "empty" => Ok(Self::None),
+            _ => Ok(Self::Unknown(raw_param.to_owned())),
         }
     }
 }
@@ -176,6 +215,9 @@ impl fmt::Display for Event {
             Event::Started => write!(f, "started"),
             Event::Stopped => write!(f, "stopped"),
             Event::Completed => write!(f, "completed"),
+            Event::Paused => write!(f, "paused"),
+            Event::None => write!(f, "empty"),
+            Event::Unknown(raw_param) => write!(f, "{raw_param}"),
         }
     }
 }
@@ -227,6 +269,7 @@ impl From<ParseQueryError> for responses::error::Error {
     fn from(err: ParseQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Cannot parse query params: {err}"),
+            retry_in: None,
         }
     }
 }
@@ -235,6 +278,7 @@ impl From<ParseAnnounceQueryError> for responses::error::Error {
     fn from(err: ParseAnnounceQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Cannot parse query params for announce request: {err}"),
+            retry_in: None,
         }
     }
 }
@@ -253,6 +297,8 @@ impl TryFrom<Query> for Announce {
             event: extract_event(&query)?,
             compact: extract_compact(&query)?,
             numwant: extract_numwant(&query)?,
+            corrupt: extract_corrupt(&query),
+            redundant: extract_redundant(&query),
         })
     }
 }
@@ -261,15 +307,19 @@ impl TryFrom<Query> for Announce {
 
 fn extract_info_hash(query: &Query) -> Result<InfoHash, ParseAnnounceQueryError> {
     match query.get_param(INFO_HASH) {
-        Some(raw_param) => {
-            Ok(
+        Some(raw_param) => match InfoHash::from_str(&raw_param) {
+            // Some clients send the hex or base32 representation instead of
+            // the percent encoded bytes. They can't be confused with 20
+            // percent encoded bytes.
+            Ok(info_hash) => Ok(info_hash),
+            Err(_) => Ok(
                 percent_decode_info_hash(&raw_param).map_err(|err| ParseAnnounceQueryError::InvalidInfoHashParam {
                     param_name: INFO_HASH.to_owned(),
                     param_value: raw_param.clone(),
                     source: Located(err).into(),
                 })?,
-            )
-        }
+            ),
+        },
         None => Err(ParseAnnounceQueryError::MissingParam {
             location: Location::caller(),
             param_name: INFO_HASH.to_owned(),
@@ -308,6 +358,13 @@ fn extract_port(query: &Query) -> Result<u16, ParseAnnounceQueryError> {
 }
 
 // Optional params
+//
+// An optional param with an empty value is handled as if it was not present.
+
+/// It returns the value of an optional param, ignoring empty values.
+fn get_optional_param(query: &Query, param_name: &str) -> Option<String> {
+    query.get_param(param_name).filter(|raw_param| !raw_param.is_empty())
+}
 
 fn extract_downloaded(query: &Query) -> Result<Option<NumberOfBytes>, ParseAnnounceQueryError> {
     extract_number_of_bytes_from_param(DOWNLOADED, query)
@@ -322,7 +379,7 @@ fn extract_left(query: &Query) -> Result<Option<NumberOfBytes>, ParseAnnounceQue
 }
 
 fn extract_number_of_bytes_from_param(param_name: &str, query: &Query) -> Result<Option<NumberOfBytes>, ParseAnnounceQueryError> {
-    match query.get_param(param_name) {
+    match get_optional_param(query, param_name) {
         Some(raw_param) => {
             let number_of_bytes = u64::from_str(&raw_param).map_err(|_e| ParseAnnounceQueryError::InvalidParam {
                 param_name: param_name.to_owned(),
@@ -346,21 +403,21 @@ fn extract_number_of_bytes_from_param(param_name: &str, query: &Query) -> Result
 }
 
 fn extract_event(query: &Query) -> Result<Option<Event>, ParseAnnounceQueryError> {
-    match query.get_param(EVENT) {
+    match get_optional_param(query, EVENT) {
         Some(raw_param) => Ok(Some(Event::from_str(&raw_param)?)),
         None => Ok(None),
     }
 }
 
 fn extract_compact(query: &Query) -> Result<Option<Compact>, ParseAnnounceQueryError> {
-    match query.get_param(COMPACT) {
+    match get_optional_param(query, COMPACT) {
         Some(raw_param) => Ok(Some(Compact::from_str(&raw_param)?)),
         None => Ok(None),
     }
 }
 
 fn extract_numwant(query: &Query) -> Result<Option<u32>, ParseAnnounceQueryError> {
-    match query.get_param(NUMWANT) {
+    match get_optional_param(query, NUMWANT) {
         Some(raw_param) => match u32::from_str(&raw_param) {
             Ok(numwant) => Ok(Some(numwant)),
             Err(_) => Err(ParseAnnounceQueryError::InvalidParam {
@@ -373,6 +430,19 @@ fn extract_numwant(query: &Query) -> Result<Option<u32>, ParseAnnounceQueryError
     }
 }
 
+// Extended client stats
+//
+// They are not part of the protocol. An invalid value is ignored instead of
+// rejecting the announce request.
+
+fn extract_corrupt(query: &Query) -> Option<NumberOfBytes> {
+    extract_number_of_bytes_from_param(CORRUPT, query).ok().flatten()
+}
+
+fn extract_redundant(query: &Query) -> Option<NumberOfBytes> {
+    extract_number_of_bytes_from_param(REDUNDANT, query).ok().flatten()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -383,7 +453,8 @@ mod tests {
 
         use crate::servers::http::v1::query::Query;
         use crate::servers::http::v1::requests::announce::{
-            Announce, Compact, Event, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+            Announce, Compact, Event, COMPACT, CORRUPT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, REDUNDANT,
+            UPLOADED,
         };
 
         #[test]
@@ -411,6 +482,8 @@ mod tests {
                     event: None,
                     compact: None,
                     numwant: None,
+                    corrupt: None,
+                    redundant: None,
                 }
             );
         }
@@ -427,6 +500,8 @@ mod tests {
                 (EVENT, "started"),
                 (COMPACT, "0"),
                 (NUMWANT, "50"),
+                (CORRUPT, "4"),
+                (REDUNDANT, "5"),
             ])
             .to_string();
 
@@ -446,6 +521,8 @@ mod tests {
                     event: Some(Event::Started),
                     compact: Some(Compact::NotAccepted),
                     numwant: Some(50),
+                    corrupt: Some(NumberOfBytes::new(4)),
+                    redundant: Some(NumberOfBytes::new(5)),
                 }
             );
         }
@@ -454,7 +531,8 @@ mod tests {
 
             use crate::servers::http::v1::query::Query;
             use crate::servers::http::v1::requests::announce::{
-                Announce, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+                Announce, Event, COMPACT, CORRUPT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, REDUNDANT,
+                UPLOADED,
             };
 
             #[test]
@@ -549,16 +627,21 @@ mod tests {
             }
 
             #[test]
-            fn it_should_fail_if_the_event_param_is_invalid() {
+            fn it_should_not_fail_if_the_event_param_is_unknown() {
                 let raw_query = Query::from(vec![
                     (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
                     (PEER_ID, "-qB00000000000000001"),
                     (PORT, "17548"),
-                    (EVENT, "INVALID_EVENT_VALUE"),
+                    (EVENT, "UNKNOWN_EVENT_VALUE"),
                 ])
                 .to_string();
 
-                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+                let announce_request = Announce::try_from(raw_query.parse::<Query>().unwrap()).unwrap();
+
+                assert_eq!(
+                    announce_request.event,
+                    Some(Event::Unknown("UNKNOWN_EVENT_VALUE".to_string()))
+                );
             }
 
             #[test]
@@ -586,6 +669,166 @@ mod tests {
 
                 assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
             }
+
+            #[test]
+            fn it_should_ignore_the_invalid_extended_client_stats_params() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (CORRUPT, "INVALID_CORRUPT_VALUE"),
+                    (REDUNDANT, "-1"),
+                ])
+                .to_string();
+
+                let announce_request = Announce::try_from(raw_query.parse::<Query>().unwrap()).unwrap();
+
+                assert_eq!(announce_request.corrupt, None);
+                assert_eq!(announce_request.redundant, None);
+            }
+        }
+
+        mod when_it_is_sent_by_real_world_clients {
+
+            use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+            use torrust_tracker_primitives::info_hash::InfoHash;
+
+            use crate::servers::http::v1::query::Query;
+            use crate::servers::http::v1::requests::announce::{Announce, Compact, Event};
+
+            /// A row of the compatibility matrix: the query string sent by
+            /// the client and the values it should be parsed into.
+            struct Case {
+                client: &'static str,
+                raw_query: &'static str,
+                peer_id: PeerId,
+                left: Option<NumberOfBytes>,
+                event: Option<Event>,
+                compact: Option<Compact>,
+            }
+
+            fn sample_info_hash() -> InfoHash {
+                "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+            }
+
+            fn compatibility_matrix() -> Vec<Case> {
+                vec![
+                    Case {
+                        client: "qBittorrent 4.6.5",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-qB4650-k8hj0wgej6ch&port=6881&uploaded=0&downloaded=0&left=1000&corrupt=0&key=1A2B3C4D&event=started&numwant=200&compact=1&no_peer_id=1&supportcrypto=1&redundant=0",
+                        peer_id: PeerId(*b"-qB4650-k8hj0wgej6ch"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: Some(Event::Started),
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "Transmission 4.0.0",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=1000&numwant=80&key=5f3a9b2c&compact=1&supportcrypto=1&event=started",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: Some(Event::Started),
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "rTorrent 0.9.8",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-lt0D80-%F1%A3%9E%0E%B6%2A%E8%1C%C9%17%AB%05&key=6b5a4c3d&compact=1&port=6890&uploaded=0&downloaded=0&left=1000&event=started",
+                        peer_id: PeerId(*b"-lt0D80-\xF1\xA3\x9E\x0E\xB6\x2A\xE8\x1C\xC9\x17\xAB\x05"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: Some(Event::Started),
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "uTorrent 3.5.5",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-UT3550-%E2%BC%8D%07%AA%14%C3%B7%1D%F2%8E%5A&port=29418&uploaded=0&downloaded=0&left=0&corrupt=0&key=0F1E2D3C&numwant=200&compact=1&no_peer_id=1",
+                        peer_id: PeerId(*b"-UT3550-\xE2\xBC\x8D\x07\xAA\x14\xC3\xB7\x1D\xF2\x8E\x5A"),
+                        left: Some(NumberOfBytes::new(0)),
+                        event: None,
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "partial seed (BEP 21)",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-qB4650-k8hj0wgej6ch&port=6881&uploaded=0&downloaded=500&left=500&event=paused&compact=1",
+                        peer_id: PeerId(*b"-qB4650-k8hj0wgej6ch"),
+                        left: Some(NumberOfBytes::new(500)),
+                        event: Some(Event::Paused),
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "client omitting `left`",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&compact=1",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: None,
+                        event: None,
+                        compact: Some(Compact::Accepted),
+                    },
+                    Case {
+                        client: "client sending an uppercase hex `info_hash`",
+                        raw_query: "info_hash=3B245504CF5F11BBDBE1201CEA6A6BF45AEE1BC0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=1000",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: None,
+                        compact: None,
+                    },
+                    Case {
+                        client: "client sending a base32 `info_hash`",
+                        raw_query: "info_hash=HMSFKBGPL4I3XW7BEAOOU2TL6RNO4G6A&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=1000",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: None,
+                        compact: None,
+                    },
+                    Case {
+                        client: "client sending empty optional params",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=&event=&numwant=&compact=",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: None,
+                        event: None,
+                        compact: None,
+                    },
+                    Case {
+                        client: "client sending the explicit `empty` event (BEP 3)",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=1000&event=empty",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: Some(Event::None),
+                        compact: None,
+                    },
+                    Case {
+                        client: "client sending an unknown event",
+                        raw_query: "info_hash=%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0&peer_id=-TR4000-u7wnvd0h3tyy&port=51413&uploaded=0&downloaded=0&left=1000&event=update",
+                        peer_id: PeerId(*b"-TR4000-u7wnvd0h3tyy"),
+                        left: Some(NumberOfBytes::new(1000)),
+                        event: Some(Event::Unknown("update".to_string())),
+                        compact: None,
+                    },
+                ]
+            }
+
+            #[test]
+            fn it_should_parse_the_announce_requests_of_all_the_clients_in_the_compatibility_matrix() {
+                for case in compatibility_matrix() {
+                    let query = case
+                        .raw_query
+                        .parse::<Query>()
+                        .unwrap_or_else(|err| panic!("{}: invalid query: {err}", case.client));
+
+                    let announce_request = Announce::try_from(query)
+                        .unwrap_or_else(|err| panic!("{}: invalid announce request: {err}", case.client));
+
+                    assert_eq!(announce_request.info_hash, sample_info_hash(), "{}", case.client);
+                    assert_eq!(announce_request.peer_id, case.peer_id, "{}", case.client);
+                    assert_eq!(announce_request.left, case.left, "{}", case.client);
+                    assert_eq!(announce_request.event, case.event, "{}", case.client);
+                    assert_eq!(announce_request.compact, case.compact, "{}", case.client);
+                }
+            }
+
+            #[test]
+            fn it_should_still_fail_when_the_info_hash_is_neither_percent_encoded_bytes_nor_hex_nor_base32() {
+                let raw_query = "info_hash=3B245504CF5F11BBDBE1201CEA6A6BF45AEE1BCZ&peer_id=-TR4000-u7wnvd0h3tyy&port=51413";
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
         }
     }
 }
@@ -40,6 +40,7 @@ impl From<ParseScrapeQueryError> for responses::error::Error {
     fn from(err: ParseScrapeQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Cannot parse query params for scrape request: {err}"),
+            retry_in: None,
         }
     }
 }
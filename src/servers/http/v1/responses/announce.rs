@@ -55,6 +55,56 @@ where
     }
 }
 
+impl<E: From<AnnounceData> + Into<Vec<u8>> + Padding> Announce<E> {
+    /// It adds a `padding` key with `bytes` bytes to the response.
+    #[must_use]
+    pub fn with_padding(mut self, bytes: usize) -> Self {
+        self.data.set_padding(bytes);
+        self
+    }
+}
+
+/// A response that can be padded with an extra `padding` key. Clients ignore
+/// unknown keys, so it only makes the response bigger.
+pub trait Padding {
+    /// It sets the number of bytes of the `padding` value.
+    fn set_padding(&mut self, bytes: usize);
+}
+
+/// The number of bytes of the `padding` value needed to make a response of
+/// `size` bytes at least `min_size` bytes long. It's `None` when the response
+/// does not need any padding.
+///
+/// The `padding` key adds `7:padding` plus the value length prefix to the
+/// response, besides the value itself.
+#[must_use]
+pub fn padding_for(size: usize, min_size: usize) -> Option<usize> {
+    const KEY_LEN: usize = "7:padding".len();
+
+    if size >= min_size {
+        return None;
+    }
+
+    let mut bytes = 0;
+
+    while size + KEY_LEN + bytes.to_string().len() + 1 + bytes < min_size {
+        bytes += 1;
+    }
+
+    Some(bytes)
+}
+
+/// It adds the `padding` key to the bencoded response, if any.
+fn pad(mut response: BencodeMut<'_>, padding: Option<usize>) -> BencodeMut<'_> {
+    if let Some(bytes) = padding {
+        response
+            .dict_mut()
+            .expect("the announce response should be a dictionary")
+            .insert("padding".as_bytes().into(), ben_bytes!(vec![b' '; bytes]));
+    }
+    response
+}
+
 /// Implement the [`Response`] for the [`Announce`].
 ///
 impl<E: From<AnnounceData> + Into<Vec<u8>>> Response for Announce<E> {
@@ -64,12 +114,26 @@ impl<E: From<AnnounceData> + Into<Vec<u8>>> Response for Announce<E> {
 }
 
 /// Format of the [`Normal`] (Non-Compact) Encoding
+///
+/// Unlike the [`Compact`] form, which has a `peers6` key for the IPv6 peers,
+/// the `peers` list mixes both address families. Each peer is a dictionary
+/// with:
+///
+/// - `peer id`: the 20-byte peer ID.
+/// - `ip`: the address as a string, without brackets for IPv6 addresses (for
+///   example `2001:db8::1`). IPv4-mapped IPv6 addresses are written as IPv4
+///   addresses.
+/// - `port`: the port as an integer.
+///
+/// Clients announcing from an IPv4 address can be given only the IPv4 peers
+/// with the `exclude_ipv6_peers_for_ipv4_clients` network option.
 pub struct Normal {
     complete: i64,
     incomplete: i64,
     interval: i64,
     min_interval: i64,
     peers: Vec<NormalPeer>,
+    padding: Option<usize>,
 }
 
 impl From<AnnounceData> for Normal {
@@ -80,10 +144,17 @@ impl From<AnnounceData> for Normal {
             interval: data.policy.interval.into(),
             min_interval: data.policy.interval_min.into(),
             peers: data.peers.iter().map(AsRef::as_ref).copied().collect(),
+            padding: None,
         }
     }
 }
 
+impl Padding for Normal {
+    fn set_padding(&mut self, bytes: usize) {
+        self.padding = Some(bytes);
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for Normal {
     fn into(self) -> Vec<u8> {
@@ -93,13 +164,16 @@ impl Into<Vec<u8>> for Normal {
             peers_list_mut.push(peer.into());
         }
 
-        (ben_map! {
-            "complete" => ben_int!(self.complete),
-            "incomplete" => ben_int!(self.incomplete),
-            "interval" => ben_int!(self.interval),
-            "min interval" => ben_int!(self.min_interval),
-            "peers" => peers_list.clone()
-        })
+        pad(
+            ben_map! {
+                "complete" => ben_int!(self.complete),
+                "incomplete" => ben_int!(self.incomplete),
+                "interval" => ben_int!(self.interval),
+                "min interval" => ben_int!(self.min_interval),
+                "peers" => peers_list.clone()
+            },
+            self.padding,
+        )
         .encode()
     }
 }
@@ -112,6 +186,7 @@ pub struct Compact {
     min_interval: i64,
     peers: Vec<u8>,
     peers6: Vec<u8>,
+    padding: Option<usize>,
 }
 
 impl From<AnnounceData> for Compact {
@@ -131,21 +206,31 @@ impl From<AnnounceData> for Compact {
             min_interval: data.policy.interval_min.into(),
             peers: peers_encoded.0,
             peers6: peers_encoded_6.0,
+            padding: None,
         }
     }
 }
 
+impl Padding for Compact {
+    fn set_padding(&mut self, bytes: usize) {
+        self.padding = Some(bytes);
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for Compact {
     fn into(self) -> Vec<u8> {
-        (ben_map! {
-            "complete" => ben_int!(self.complete),
-            "incomplete" => ben_int!(self.incomplete),
-            "interval" => ben_int!(self.interval),
-            "min interval" => ben_int!(self.min_interval),
-            "peers" => ben_bytes!(self.peers),
-            "peers6" => ben_bytes!(self.peers6)
-        })
+        pad(
+            ben_map! {
+                "complete" => ben_int!(self.complete),
+                "incomplete" => ben_int!(self.incomplete),
+                "interval" => ben_int!(self.interval),
+                "min interval" => ben_int!(self.min_interval),
+                "peers" => ben_bytes!(self.peers),
+                "peers6" => ben_bytes!(self.peers6)
+            },
+            self.padding,
+        )
         .encode()
     }
 }
@@ -177,9 +262,14 @@ impl peer::Encoding for NormalPeer {}
 
 impl From<peer::Peer> for NormalPeer {
     fn from(peer: peer::Peer) -> Self {
+        let ip = match peer.peer_addr.ip() {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+            ip @ IpAddr::V4(_) => ip,
+        };
+
         NormalPeer {
             peer_id: peer.peer_id.0,
-            ip: peer.peer_addr.ip(),
+            ip,
             port: peer.peer_addr.port(),
         }
     }
@@ -306,7 +396,7 @@ mod tests {
     use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 
     use crate::core::AnnounceData;
-    use crate::servers::http::v1::responses::announce::{Announce, Compact, Normal, Response};
+    use crate::servers::http::v1::responses::announce::{padding_for, Announce, Compact, Normal, NormalPeer, Response};
 
     // Some ascii values used in tests:
     //
@@ -337,7 +427,7 @@ mod tests {
             .build();
 
         let peers = vec![Arc::new(peer_ipv4), Arc::new(peer_ipv6)];
-        let stats = SwarmMetadata::new(333, 333, 444);
+        let stats = SwarmMetadata::new(333, 333, 444, 0);
 
         AnnounceData::new(peers, stats, policy)
     }
@@ -356,6 +446,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_compact_announce_response_should_write_ipv6_addresses_in_their_shortest_form_without_brackets() {
+        let peer = PeerBuilder::default()
+            .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+            .with_peer_addr(&SocketAddr::new(
+                IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+                0x7070,
+            ))
+            .build();
+
+        let response: Announce<Normal> = AnnounceData::new(
+            vec![Arc::new(peer)],
+            SwarmMetadata::new(0, 1, 0, 0),
+            AnnouncePolicy::new(111, 222),
+        )
+        .into();
+        let bytes = response.body().expect("it should encode the response");
+
+        // cspell:disable-next-line
+        let expected_bytes = b"d8:completei1e10:incompletei0e8:intervali111e12:min intervali222e5:peersld2:ip11:2001:db8::17:peer id20:-qB000000000000000014:porti28784eeee";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn non_compact_announce_response_should_write_ipv4_mapped_ipv6_addresses_as_ipv4_addresses() {
+        let peer = PeerBuilder::default()
+            .with_peer_addr(&SocketAddr::new(
+                IpAddr::V6(Ipv4Addr::new(0x69, 0x69, 0x69, 0x69).to_ipv6_mapped()),
+                0x7070,
+            ))
+            .build();
+
+        let normal_peer = NormalPeer::from(peer);
+
+        assert_eq!(normal_peer.ip, IpAddr::V4(Ipv4Addr::new(0x69, 0x69, 0x69, 0x69)));
+        assert_eq!(normal_peer.port, 0x7070);
+    }
+
     #[test]
     fn compact_announce_response_can_be_bencoded() {
         let response: Announce<Compact> = setup_announce_data().into();
@@ -370,4 +502,40 @@ mod tests {
             String::from_utf8(expected_bytes.to_vec()).unwrap()
         );
     }
+
+    #[test]
+    fn compact_announce_response_can_be_padded_with_a_padding_key() {
+        let response: Announce<Compact> = setup_announce_data().into();
+        let bytes = response.with_padding(3).body().expect("it should encode the response");
+
+        let expected_bytes =
+            // cspell:disable-next-line
+            b"d8:completei333e10:incompletei444e8:intervali111e12:min intervali222e7:padding3:   5:peers6:iiiipp6:peers618:iiiiiiiiiiiiiiiippe";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn the_padding_should_make_the_response_at_least_as_long_as_the_minimum_size() {
+        let size = Announce::<Normal>::from(setup_announce_data()).body().unwrap().len();
+
+        for min_size in 0..1200 {
+            let response: Announce<Normal> = setup_announce_data().into();
+
+            match padding_for(size, min_size) {
+                Some(bytes) => {
+                    let padded_size = response.with_padding(bytes).body().unwrap().len();
+
+                    // The empty `padding` key alone adds 11 bytes, and the
+                    // length prefix can make it one byte longer than needed.
+                    assert!(padded_size >= min_size);
+                    assert!(padded_size <= (size + 11).max(min_size + 1));
+                }
+                None => assert!(size >= min_size),
+            }
+        }
+    }
 }
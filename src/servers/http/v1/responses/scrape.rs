@@ -25,6 +25,7 @@ use crate::core::ScrapeData;
 ///         complete: 1,
 ///         downloaded: 2,
 ///         incomplete: 3,
+///         partial_seeds: 0,
 ///     },
 /// );
 ///
@@ -107,6 +108,7 @@ mod tests {
                     complete: 1,
                     downloaded: 2,
                     incomplete: 3,
+                    partial_seeds: 0,
                 },
             );
             scrape_data
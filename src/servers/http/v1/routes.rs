@@ -3,42 +3,159 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use axum::body::Body;
 use axum::error_handling::HandleErrorLayer;
+use axum::http::header::HOST;
 use axum::http::HeaderName;
+use axum::middleware;
 use axum::response::Response;
 use axum::routing::get;
 use axum::{BoxError, Router};
 use axum_client_ip::SecureClientIpSource;
 use hyper::{Request, StatusCode};
-use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+use torrust_tracker_configuration::{Health, KeyPathFormat, RequestLimits, DEFAULT_TIMEOUT};
 use tower::timeout::TimeoutLayer;
-use tower::ServiceBuilder;
+use tower::{service_fn, ServiceBuilder, ServiceExt};
 use tower_http::compression::CompressionLayer;
 use tower_http::propagate_header::PropagateHeaderLayer;
 use tower_http::request_id::{MakeRequestUuid, SetRequestIdLayer};
 use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use tracing::{instrument, Level, Span};
 
-use super::handlers::{announce, health_check, scrape};
-use crate::core::Tracker;
+use super::handlers::{announce, health, health_check, scrape};
+use super::middlewares::request_limits;
+use crate::core::virtual_trackers::VirtualHosts;
+use crate::core::{AnnounceMode, Tracker};
 use crate::servers::http::HTTP_TRACKER_LOG_TARGET;
 
 /// It adds the routes to the router.
 ///
+/// The requests are served by the given tracker, except the ones whose `Host`
+/// is routed to a virtual tracker by the `virtual_hosts`, which are served by
+/// the same routes on the virtual tracker.
+///
+/// The routes with the authentication key depend on the `key_path_format`:
+/// `/announce/:key` and `/scrape/:key`, `/:key/announce` and `/:key/scrape`,
+/// or both.
+///
+/// The health endpoint is served on the configured `health` path.
+///
+/// The `announce` requests are handled in the given [`AnnounceMode`].
+///
+/// The requests exceeding the [`RequestLimits`] are rejected. See the
+/// [`request_limits`] middleware.
+///
 /// > **NOTICE**: it's added a layer to get the client IP from the connection
 /// > info. The tracker could use the connection info to get the client IP.
+#[instrument(skip(tracker, virtual_hosts, server_socket_addr, health))]
+pub fn router(
+    tracker: Arc<Tracker>,
+    virtual_hosts: &VirtualHosts,
+    server_socket_addr: SocketAddr,
+    key_path_format: KeyPathFormat,
+    health: &Health,
+    announce_mode: AnnounceMode,
+    request_limits: RequestLimits,
+) -> Router {
+    let default = tracker_router(
+        tracker,
+        server_socket_addr,
+        key_path_format,
+        health,
+        announce_mode,
+        request_limits,
+    );
+
+    if virtual_hosts.is_empty() {
+        return default;
+    }
+
+    let mut routers: Vec<(Arc<Tracker>, Router)> = Vec::new();
+
+    for (_host, virtual_tracker) in virtual_hosts.iter() {
+        if !routers.iter().any(|(tracker, _)| Arc::ptr_eq(tracker, virtual_tracker)) {
+            routers.push((
+                virtual_tracker.clone(),
+                tracker_router(
+                    virtual_tracker.clone(),
+                    server_socket_addr,
+                    key_path_format,
+                    health,
+                    announce_mode,
+                    request_limits,
+                ),
+            ));
+        }
+    }
+
+    let virtual_hosts = virtual_hosts.clone();
+    let routers = Arc::new(routers);
+
+    Router::new().fallback_service(service_fn(move |request: Request<Body>| {
+        let router = request_host(&request)
+            .and_then(|host| virtual_hosts.get(host))
+            .and_then(|virtual_tracker| routers.iter().find(|(tracker, _)| Arc::ptr_eq(tracker, virtual_tracker)))
+            .map_or_else(|| default.clone(), |(_, router)| router.clone());
+
+        router.oneshot(request)
+    }))
+}
+
+/// The `Host` of the request, from the URI (HTTP/2) or the `Host` header.
+fn request_host(request: &Request<Body>) -> Option<&str> {
+    request
+        .uri()
+        .host()
+        .or_else(|| request.headers().get(HOST).and_then(|host| host.to_str().ok()))
+}
+
 #[allow(clippy::needless_pass_by_value)]
-#[instrument(skip(tracker, server_socket_addr))]
-pub fn router(tracker: Arc<Tracker>, server_socket_addr: SocketAddr) -> Router {
-    Router::new()
+fn tracker_router(
+    tracker: Arc<Tracker>,
+    server_socket_addr: SocketAddr,
+    key_path_format: KeyPathFormat,
+    health: &Health,
+    announce_mode: AnnounceMode,
+    request_limits: RequestLimits,
+) -> Router {
+    let mut router = Router::new()
         // Health check
         .route("/health_check", get(health_check::handler))
+        .route(
+            &health.path,
+            get(health::handler).with_state((tracker.clone(), Arc::from(health.checks.as_slice()))),
+        )
         // Announce request
-        .route("/announce", get(announce::handle_without_key).with_state(tracker.clone()))
-        .route("/announce/:key", get(announce::handle_with_key).with_state(tracker.clone()))
+        .route(
+            "/announce",
+            get(announce::handle_without_key).with_state((tracker.clone(), announce_mode)),
+        )
         // Scrape request
-        .route("/scrape", get(scrape::handle_without_key).with_state(tracker.clone()))
-        .route("/scrape/:key", get(scrape::handle_with_key).with_state(tracker))
+        .route("/scrape", get(scrape::handle_without_key).with_state(tracker.clone()));
+
+    if key_path_format.accepts_suffix() {
+        router = router
+            .route(
+                "/announce/:key",
+                get(announce::handle_with_key).with_state((tracker.clone(), announce_mode)),
+            )
+            .route("/scrape/:key", get(scrape::handle_with_key).with_state(tracker.clone()));
+    }
+
+    if key_path_format.accepts_prefix() {
+        router = router
+            .route(
+                "/:key/announce",
+                get(announce::handle_with_key).with_state((tracker.clone(), announce_mode)),
+            )
+            .route("/:key/scrape", get(scrape::handle_with_key).with_state(tracker.clone()));
+    }
+
+    router
+        .layer(middleware::from_fn_with_state(
+            (tracker, request_limits),
+            request_limits::limit_request_size,
+        ))
         // Add extension to get the client IP from the connection info
         .layer(SecureClientIpSource::ConnectInfo.into_extension())
         .layer(CompressionLayer::new())
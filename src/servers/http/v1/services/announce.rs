@@ -8,13 +8,16 @@
 //!
 //! It also sends an [`statistics::Event`]
 //! because events are specific for the HTTP tracker.
+//!
+//! When the [request deadline](crate::core::deadline) is exceeded, it returns
+//! a best-effort response.
 use std::net::IpAddr;
 use std::sync::Arc;
 
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer;
 
-use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
+use crate::core::{statistics, AnnounceData, AnnounceMode, PeersWanted, Requester, Tracker};
 
 /// The HTTP tracker `announce` service.
 ///
@@ -23,19 +26,35 @@ use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
 /// - The number of TCP connections handled by the HTTP tracker.
 /// - The number of TCP `announce` requests handled by the HTTP tracker.
 ///
+/// And another one when the request exceeds the request deadline.
+///
 /// > **NOTICE**: as the HTTP tracker does not requires a connection request
 /// > like the UDP tracker, the number of TCP connections is incremented for
 /// > each `announce` request.
 pub async fn invoke(
     tracker: Arc<Tracker>,
+    requester: &Requester,
     info_hash: InfoHash,
     peer: &mut peer::Peer,
     peers_wanted: &PeersWanted,
+    announce_mode: AnnounceMode,
 ) -> AnnounceData {
     let original_peer_ip = peer.peer_addr.ip();
 
     // The tracker could change the original peer ip
-    let announce_data = tracker.announce(&info_hash, peer, &original_peer_ip, peers_wanted);
+    let announce_data = match tracker
+        .announce_within_deadline(requester, &info_hash, peer, &original_peer_ip, peers_wanted, announce_mode)
+        .await
+    {
+        Ok(announce_data) => announce_data,
+        Err(err) => {
+            tracing::warn!(%info_hash, "{err}, returning a best-effort announce response");
+
+            tracker.send_stats_event(statistics::Event::TcpAnnounceDeadlineExceeded).await;
+
+            tracker.best_effort_announce_data(&info_hash, requester)
+        }
+    };
 
     match original_peer_ip {
         IpAddr::V4(_) => {
@@ -91,6 +110,9 @@ mod tests {
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            partial_seed: false,
+            corrupt: NumberOfBytes::new(0),
+            redundant: NumberOfBytes::new(0),
         }
     }
 
@@ -105,7 +127,7 @@ mod tests {
         use torrust_tracker_test_helpers::configuration;
 
         use super::{sample_peer_using_ipv4, sample_peer_using_ipv6};
-        use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
+        use crate::core::{statistics, AnnounceData, AnnounceMode, PeersWanted, Requester, Tracker};
         use crate::servers::http::v1::services::announce::invoke;
         use crate::servers::http::v1::services::announce::tests::{public_tracker, sample_info_hash, sample_peer};
 
@@ -115,7 +137,15 @@ mod tests {
 
             let mut peer = sample_peer();
 
-            let announce_data = invoke(tracker.clone(), sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let announce_data = invoke(
+                tracker.clone(),
+                &Requester::Anonymous,
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                AnnounceMode::Synchronous,
+            )
+            .await;
 
             let expected_announce_data = AnnounceData {
                 peers: vec![],
@@ -123,6 +153,7 @@ mod tests {
                     downloaded: 0,
                     complete: 1,
                     incomplete: 0,
+                    partial_seeds: 0,
                 },
                 policy: tracker.get_announce_policy(),
             };
@@ -151,7 +182,15 @@ mod tests {
 
             let mut peer = sample_peer_using_ipv4();
 
-            let _announce_data = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let _announce_data = invoke(
+                tracker,
+                &Requester::Anonymous,
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                AnnounceMode::Synchronous,
+            )
+            .await;
         }
 
         fn tracker_with_an_ipv6_external_ip(stats_event_sender: Box<dyn statistics::EventSender>) -> Tracker {
@@ -164,7 +203,7 @@ mod tests {
         }
 
         fn peer_with_the_ipv4_loopback_ip() -> peer::Peer {
-            let loopback_ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+            let loopback_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
             let mut peer = sample_peer();
             peer.peer_addr = SocketAddr::new(loopback_ip, 8080);
             peer
@@ -188,9 +227,11 @@ mod tests {
 
             let _announce_data = invoke(
                 tracker_with_an_ipv6_external_ip(stats_event_sender).into(),
+                &Requester::Anonymous,
                 sample_info_hash(),
                 &mut peer,
                 &PeersWanted::All,
+                AnnounceMode::Synchronous,
             )
             .await;
         }
@@ -217,7 +258,15 @@ mod tests {
 
             let mut peer = sample_peer_using_ipv6();
 
-            let _announce_data = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let _announce_data = invoke(
+                tracker,
+                &Requester::Anonymous,
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                AnnounceMode::Synchronous,
+            )
+            .await;
         }
     }
 }
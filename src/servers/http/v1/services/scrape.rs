@@ -8,6 +8,9 @@
 //!
 //! It also sends an [`statistics::Event`]
 //! because events are specific for the HTTP tracker.
+//!
+//! When the [request deadline](crate::core::deadline) is exceeded, it returns
+//! a best-effort response.
 use std::net::IpAddr;
 use std::sync::Arc;
 
@@ -22,13 +25,27 @@ use crate::core::{statistics, ScrapeData, Tracker};
 /// - The number of TCP connections handled by the HTTP tracker.
 /// - The number of TCP `scrape` requests handled by the HTTP tracker.
 ///
+/// And another one with the number of duplicate infohashes, if there are any.
+/// Duplicate infohashes are only looked up once. And another one when the
+/// request exceeds the request deadline.
+///
 /// > **NOTICE**: as the HTTP tracker does not requires a connection request
 /// > like the UDP tracker, the number of TCP connections is incremented for
 /// > each `scrape` request.
 pub async fn invoke(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, original_peer_ip: &IpAddr) -> ScrapeData {
-    let scrape_data = tracker.scrape(info_hashes).await;
+    let scrape_data = match tracker.scrape_within_deadline(info_hashes).await {
+        Ok(scrape_data) => scrape_data,
+        Err(err) => {
+            tracing::warn!("{err}, returning a best-effort scrape response");
+
+            tracker.send_stats_event(statistics::Event::TcpScrapeDeadlineExceeded).await;
+
+            tracker.best_effort_scrape_data(info_hashes)
+        }
+    };
 
     send_scrape_event(original_peer_ip, tracker).await;
+    send_duplicates_event(scrape_data.duplicates(info_hashes), tracker).await;
 
     scrape_data
 }
@@ -40,9 +57,12 @@ pub async fn invoke(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, origina
 ///
 /// > **NOTICE**: tracker statistics are not updated in this case.
 pub async fn fake(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, original_peer_ip: &IpAddr) -> ScrapeData {
+    let scrape_data = ScrapeData::zeroed(info_hashes);
+
     send_scrape_event(original_peer_ip, tracker).await;
+    send_duplicates_event(scrape_data.duplicates(info_hashes), tracker).await;
 
-    ScrapeData::zeroed(info_hashes)
+    scrape_data
 }
 
 async fn send_scrape_event(original_peer_ip: &IpAddr, tracker: &Arc<Tracker>) {
@@ -56,6 +76,14 @@ async fn send_scrape_event(original_peer_ip: &IpAddr, tracker: &Arc<Tracker>) {
     }
 }
 
+async fn send_duplicates_event(duplicates: u64, tracker: &Arc<Tracker>) {
+    if duplicates > 0 {
+        tracker
+            .send_stats_event(statistics::Event::TcpScrapeDuplicateInfoHashes { count: duplicates })
+            .await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -90,6 +118,9 @@ mod tests {
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            partial_seed: false,
+            corrupt: NumberOfBytes::new(0),
+            redundant: NumberOfBytes::new(0),
         }
     }
 
@@ -130,6 +161,7 @@ mod tests {
                     complete: 1,
                     downloaded: 0,
                     incomplete: 0,
+                    partial_seeds: 0,
                 },
             );
 
@@ -183,6 +215,38 @@ mod tests {
 
             invoke(&tracker, &sample_info_hashes(), &peer_ip).await;
         }
+
+        #[tokio::test]
+        async fn it_should_send_the_number_of_duplicate_info_hashes() {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Tcp4Scrape))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::TcpScrapeDuplicateInfoHashes { count: 2 }))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            let stats_event_sender = Box::new(stats_event_sender_mock);
+
+            let tracker = Arc::new(
+                Tracker::new(
+                    &configuration::ephemeral().core,
+                    Some(stats_event_sender),
+                    statistics::Repo::new(),
+                )
+                .unwrap(),
+            );
+
+            let peer_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1));
+            let info_hashes = vec![sample_info_hash(), sample_info_hash(), sample_info_hash()];
+
+            let scrape_data = invoke(&tracker, &info_hashes, &peer_ip).await;
+
+            assert_eq!(scrape_data.files.len(), 1);
+        }
     }
 
     mod with_zeroed_data {
@@ -0,0 +1,250 @@
+//! Manifest of the running services.
+//!
+//! Once all the services have started, the application logs a single event
+//! listing every bound service, and optionally writes the same information to
+//! a json file. For example:
+//!
+//! ```json
+//! {
+//!   "pid": 4321,
+//!   "services": [
+//!     { "service": "udp_tracker", "protocol": "udp", "address": "0.0.0.0:6969", "tls": false },
+//!     { "service": "http_tracker", "protocol": "https", "address": "0.0.0.0:7070", "tls": true },
+//!     { "service": "http_api", "protocol": "http", "address": "127.0.0.1:1212", "tls": false },
+//!     { "service": "health_check_api", "protocol": "http", "address": "127.0.0.1:1313", "tls": false }
+//!   ]
+//! }
+//! ```
+//!
+//! The addresses are the actual bound ones, so they include the ports
+//! assigned by the OS when port `0` is configured. The services bound to a
+//! Unix domain socket are listed with the `unix:` path of the socket.
+//!
+//! In [development mode](crate::bootstrap::dev), the configured addresses
+//! that were taken are listed in `port_fallbacks`, next to the ephemeral
+//! addresses the services were bound to instead:
+//!
+//! ```json
+//! {
+//!   "port_fallbacks": [
+//!     { "service": "http_tracker", "configured": "0.0.0.0:7070" }
+//!   ]
+//! }
+//! ```
+use std::net::SocketAddr;
+
+use camino::Utf8Path;
+use derive_more::Constructor;
+use serde::Serialize;
+
+use crate::servers::registar::{Binding, ServiceKind, ServiceRegistry};
+
+/// The list of the services running in this process.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub pid: u32,
+    pub services: Vec<Service>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub port_fallbacks: Vec<PortFallback>,
+}
+
+/// A bound service. There is one for each address a service listens on.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Constructor)]
+pub struct Service {
+    pub service: ServiceKind,
+    pub protocol: &'static str,
+    pub address: Binding,
+    pub tls: bool,
+}
+
+/// A configured address that was already in use when the service started in
+/// development mode. The service was bound to an ephemeral port instead.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Constructor)]
+pub struct PortFallback {
+    pub service: ServiceKind,
+    pub configured: SocketAddr,
+}
+
+impl Manifest {
+    /// It builds the manifest from the registered services plus the Health
+    /// Check API, which is not registered. It's `None` when the tracker was
+    /// built without the Health Check API.
+    ///
+    /// Services are sorted by kind and address.
+    pub async fn new(registry: &ServiceRegistry, health_check_api: Option<SocketAddr>) -> Self {
+        let mut services: Vec<Service> = registry
+            .lock()
+            .await
+            .values()
+            .flat_map(|registration| {
+                registration.bindings().into_iter().map(|address| {
+                    Service::new(
+                        registration.kind(),
+                        registration.kind().protocol(registration.tls()),
+                        address,
+                        registration.tls(),
+                    )
+                })
+            })
+            .collect();
+
+        services.sort_by(|a, b| (a.service, &a.address).cmp(&(b.service, &b.address)));
+
+        services.extend(health_check_api.map(|address| {
+            Service::new(
+                ServiceKind::HealthCheckApi,
+                ServiceKind::HealthCheckApi.protocol(false),
+                address.into(),
+                false,
+            )
+        }));
+
+        Self {
+            pid: std::process::id(),
+            services,
+            port_fallbacks: Vec::new(),
+        }
+    }
+
+    /// It adds the configured addresses the services could not be bound to.
+    #[must_use]
+    pub fn with_port_fallbacks(mut self, port_fallbacks: Vec<PortFallback>) -> Self {
+        self.port_fallbacks = port_fallbacks;
+        self
+    }
+
+    /// It emits the manifest as a single log event.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the services or the port fallbacks can't be serialized
+    /// to json.
+    pub fn log(&self) {
+        let services = serde_json::to_string(&self.services).expect("it should serialize the services to json");
+
+        if self.port_fallbacks.is_empty() {
+            tracing::info!(pid = self.pid, services, "Services started");
+        } else {
+            let port_fallbacks =
+                serde_json::to_string(&self.port_fallbacks).expect("it should serialize the port fallbacks to json");
+
+            tracing::info!(pid = self.pid, services, port_fallbacks, "Services started");
+        }
+    }
+
+    /// It writes the manifest as json to the file in `path`, replacing it if
+    /// it exists. The parent directory is created if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file can't be written.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the manifest can't be serialized to json.
+    pub async fn write(&self, path: &Utf8Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("it should serialize the manifest to json");
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(path, json).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use serde_json::json;
+    use torrust_tracker_test_helpers::random;
+
+    use super::{Manifest, PortFallback};
+    use crate::servers::registar::{Binding, ServiceHealthCheckJob, ServiceKind, ServiceRegistration, ServiceRegistry};
+
+    fn check(binding: &SocketAddr) -> ServiceHealthCheckJob {
+        ServiceHealthCheckJob::new(*binding, String::new(), tokio::spawn(async { Ok(String::new()) }))
+    }
+
+    fn unix_socket_check(path: &Utf8Path) -> ServiceHealthCheckJob {
+        ServiceHealthCheckJob::new(
+            Binding::Unix(path.to_path_buf()),
+            String::new(),
+            tokio::spawn(async { Ok(String::new()) }),
+        )
+    }
+
+    fn address(address: &str) -> SocketAddr {
+        address.parse().unwrap()
+    }
+
+    async fn registry_with(registrations: Vec<ServiceRegistration>) -> ServiceRegistry {
+        let registry = ServiceRegistry::default();
+
+        for registration in registrations {
+            registry.lock().await.insert(registration.binding(), registration);
+        }
+
+        registry
+    }
+
+    #[tokio::test]
+    async fn it_should_list_every_address_of_the_registered_services_and_the_health_check_api() {
+        let registry = registry_with(vec![
+            ServiceRegistration::new(address("127.0.0.1:7070"), ServiceKind::HttpTracker, true, check),
+            ServiceRegistration::unix_socket("/var/run/torrust/api.sock".into(), ServiceKind::HttpApi, unix_socket_check),
+            ServiceRegistration::group(vec![
+                ServiceRegistration::new(address("0.0.0.0:6969"), ServiceKind::UdpTracker, false, check),
+                ServiceRegistration::new(address("[::]:6969"), ServiceKind::UdpTracker, false, check),
+            ]),
+        ])
+        .await;
+
+        let manifest = Manifest::new(&registry, Some(address("127.0.0.1:1313"))).await;
+
+        assert_eq!(manifest.pid, std::process::id());
+        assert_eq!(
+            serde_json::to_value(&manifest.services).unwrap(),
+            json!([
+                { "service": "udp_tracker", "protocol": "udp", "address": "0.0.0.0:6969", "tls": false },
+                { "service": "udp_tracker", "protocol": "udp", "address": "[::]:6969", "tls": false },
+                { "service": "http_tracker", "protocol": "https", "address": "127.0.0.1:7070", "tls": true },
+                { "service": "http_api", "protocol": "http", "address": "unix:/var/run/torrust/api.sock", "tls": false },
+                { "service": "health_check_api", "protocol": "http", "address": "127.0.0.1:1313", "tls": false },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_write_the_manifest_to_a_json_file() {
+        let registry = registry_with(vec![]).await;
+        let manifest = Manifest::new(&registry, Some(address("127.0.0.1:1313"))).await;
+
+        let directory = std::env::temp_dir().join(format!("manifest_{}", random::string(16)));
+        let path = Utf8PathBuf::from_path_buf(directory.join("lib").join("services.json")).unwrap();
+
+        manifest.write(&path).await.unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(written, serde_json::to_value(&manifest).unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_should_list_the_port_fallbacks_only_when_there_are_any() {
+        let registry = registry_with(vec![]).await;
+
+        let manifest = Manifest::new(&registry, Some(address("127.0.0.1:1313"))).await;
+
+        assert!(serde_json::to_value(&manifest).unwrap().get("port_fallbacks").is_none());
+
+        let manifest = manifest.with_port_fallbacks(vec![PortFallback::new(ServiceKind::HttpTracker, address("0.0.0.0:7070"))]);
+
+        assert_eq!(
+            serde_json::to_value(&manifest).unwrap()["port_fallbacks"],
+            json!([{ "service": "http_tracker", "configured": "0.0.0.0:7070" }])
+        );
+    }
+}
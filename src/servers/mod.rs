@@ -1,9 +1,21 @@
 //! Servers. Services that can be started and stopped.
+#[cfg(feature = "http")]
+pub mod admission;
+#[cfg(feature = "api")]
 pub mod apis;
+pub mod bind_device;
+#[cfg(feature = "web")]
 pub mod custom_axum_server;
+#[cfg(feature = "web")]
 pub mod health_check_api;
+#[cfg(feature = "http")]
 pub mod http;
 pub mod logging;
+pub mod manifest;
+pub mod proxy_protocol;
 pub mod registar;
 pub mod signals;
+#[cfg(feature = "udp")]
 pub mod udp;
+#[cfg(feature = "web")]
+pub mod unix_socket;
@@ -0,0 +1,525 @@
+//! `HAProxy` PROXY protocol support.
+//!
+//! When the tracker runs behind a TCP or UDP load balancer that cannot inject
+//! HTTP headers (like `X-Forwarded-For`), the only way to know the real client
+//! IP is the [PROXY protocol](https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt).
+//! The load balancer sends a small header with the original client address
+//! before the client data:
+//!
+//! - Version 1 is a human-readable line, for example:
+//!   `PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n`.
+//! - Version 2 is a binary header starting with a 12-byte signature. It's the
+//!   only version that can be used with UDP, where every datagram is prefixed
+//!   with the header.
+//!
+//! It can be enabled per service with the `proxy_protocol` option:
+//!
+//! ```toml
+//! [[http_trackers]]
+//! bind_address = "0.0.0.0:7070"
+//! proxy_protocol = true
+//!
+//! [[udp_trackers]]
+//! bind_address = "0.0.0.0:6969"
+//! proxy_protocol = true
+//! ```
+//!
+//! When enabled, the header is mandatory. HTTP connections without a valid
+//! header are closed and UDP datagrams without a valid header are discarded.
+//!
+//! The header is only trusted when it comes from a loopback address or from
+//! one of the [`trusted_proxies`](torrust_tracker_configuration::v2_0_0::network::Network::trusted_proxies)
+//! in the `core.net` section. Otherwise any client could forge its address.
+//! HTTP connections and UDP datagrams from other sources are rejected:
+//!
+//! ```toml
+//! [core.net]
+//! trusted_proxies = ["10.0.0.2"]
+//! ```
+//! Headers without a client address (the `LOCAL` command used by load
+//! balancers for their own health checks, or the `UNKNOWN` protocol) are
+//! accepted and the load balancer address is used instead.
+//!
+//! > **NOTICE**: when the tracker is also `on_reverse_proxy`, the HTTP tracker
+//! > keeps getting the peer IP from the `X-Forwarded-For` header.
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(feature = "web")]
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "web")]
+use axum::extract::ConnectInfo;
+#[cfg(feature = "web")]
+use axum::middleware::AddExtension;
+#[cfg(feature = "web")]
+use axum::Extension;
+#[cfg(feature = "web")]
+use axum_server::accept::Accept;
+#[cfg(feature = "web")]
+use futures::future::BoxFuture;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+#[cfg(feature = "web")]
+use tokio::net::TcpStream;
+#[cfg(feature = "web")]
+use tower::Layer;
+
+/// The prefix of a version 1 header.
+pub const V1_PREFIX: &[u8] = b"PROXY ";
+
+/// The maximum length of a version 1 header, including the final `CRLF`.
+pub const V1_MAX_LENGTH: usize = 107;
+
+/// The signature at the beginning of a version 2 header.
+pub const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The length of the fixed part of a version 2 header.
+const V2_FIXED_LENGTH: usize = 16;
+
+/// A version 2 header with the `LOCAL` command, used by load balancers to
+/// check the health of the service.
+pub const V2_LOCAL_HEADER: [u8; V2_FIXED_LENGTH] = [
+    b'\r', b'\n', b'\r', b'\n', 0x00, b'\r', b'\n', b'Q', b'U', b'I', b'T', b'\n', 0x20, 0x00, 0x00, 0x00,
+];
+
+/// The maximum time to wait for the header of a new connection.
+pub const HEADER_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors parsing a PROXY protocol header.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("the data does not start with a PROXY protocol header")]
+    MissingHeader,
+
+    #[error("the PROXY protocol header is incomplete")]
+    Incomplete,
+
+    #[error("invalid PROXY protocol header: {reason}")]
+    Invalid { reason: &'static str },
+}
+
+/// A parsed PROXY protocol header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// The address of the original client. It's `None` when the header does
+    /// not contain a client address (`LOCAL` command, `UNKNOWN` protocol or
+    /// address families other than IPv4 and IPv6).
+    pub source: Option<SocketAddr>,
+    /// The length of the header in bytes. The client data starts right after
+    /// the header.
+    pub length: usize,
+}
+
+/// It returns `true` if the headers sent from `ip` can be trusted. Loopback
+/// addresses, where the health checks and the local proxies connect from, are
+/// always trusted. IPv4-mapped IPv6 addresses are compared as IPv4 addresses.
+#[must_use]
+pub fn is_trusted_source(ip: IpAddr, trusted_proxies: &[IpAddr]) -> bool {
+    let ip = match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    };
+
+    ip.is_loopback() || trusted_proxies.contains(&ip)
+}
+
+/// It parses a version 1 or version 2 header at the beginning of `bytes`.
+///
+/// # Errors
+///
+/// Will return an error if `bytes` does not start with a complete and valid
+/// header.
+pub fn parse(bytes: &[u8]) -> Result<Header, Error> {
+    if bytes.starts_with(&V2_SIGNATURE) {
+        parse_v2(bytes)
+    } else if bytes.starts_with(V1_PREFIX) {
+        parse_v1(bytes)
+    } else if V2_SIGNATURE.starts_with(bytes) || V1_PREFIX.starts_with(bytes) {
+        Err(Error::Incomplete)
+    } else {
+        Err(Error::MissingHeader)
+    }
+}
+
+fn parse_v1(bytes: &[u8]) -> Result<Header, Error> {
+    let search_limit = bytes.len().min(V1_MAX_LENGTH);
+
+    let Some(end) = bytes[..search_limit].windows(2).position(|window| window == b"\r\n") else {
+        return if bytes.len() >= V1_MAX_LENGTH {
+            Err(Error::Invalid {
+                reason: "version 1 header is too long",
+            })
+        } else {
+            Err(Error::Incomplete)
+        };
+    };
+
+    let line = std::str::from_utf8(&bytes[..end]).map_err(|_| Error::Invalid {
+        reason: "version 1 header is not ASCII",
+    })?;
+
+    let length = end + 2;
+
+    let fields: Vec<&str> = line.split(' ').collect();
+
+    match fields.as_slice() {
+        ["PROXY", "UNKNOWN", ..] => Ok(Header { source: None, length }),
+        ["PROXY", protocol @ ("TCP4" | "TCP6"), source_ip, _destination_ip, source_port, _destination_port] => {
+            let ip: IpAddr = source_ip.parse().map_err(|_| Error::Invalid {
+                reason: "invalid source address",
+            })?;
+
+            let port: u16 = source_port.parse().map_err(|_| Error::Invalid {
+                reason: "invalid source port",
+            })?;
+
+            if (*protocol == "TCP4") != ip.is_ipv4() {
+                return Err(Error::Invalid {
+                    reason: "the source address does not match the protocol",
+                });
+            }
+
+            Ok(Header {
+                source: Some(SocketAddr::new(ip, port)),
+                length,
+            })
+        }
+        _ => Err(Error::Invalid {
+            reason: "malformed version 1 header",
+        }),
+    }
+}
+
+fn parse_v2(bytes: &[u8]) -> Result<Header, Error> {
+    if bytes.len() < V2_FIXED_LENGTH {
+        return Err(Error::Incomplete);
+    }
+
+    let version = bytes[12] >> 4;
+    let command = bytes[12] & 0x0F;
+    let family = bytes[13] >> 4;
+    let addresses_length = usize::from(u16::from_be_bytes([bytes[14], bytes[15]]));
+
+    if version != 2 {
+        return Err(Error::Invalid {
+            reason: "unsupported version",
+        });
+    }
+
+    let length = V2_FIXED_LENGTH + addresses_length;
+
+    if bytes.len() < length {
+        return Err(Error::Incomplete);
+    }
+
+    let addresses = &bytes[V2_FIXED_LENGTH..length];
+
+    let source = match (command, family) {
+        // PROXY over IPv4
+        (0x1, 0x1) => {
+            if addresses.len() < 12 {
+                return Err(Error::Invalid {
+                    reason: "truncated IPv4 addresses",
+                });
+            }
+
+            let ip = Ipv4Addr::from(<[u8; 4]>::try_from(&addresses[0..4]).expect("it should be a 4-byte slice"));
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+
+            Some(SocketAddr::new(IpAddr::V4(ip), port))
+        }
+        // PROXY over IPv6
+        (0x1, 0x2) => {
+            if addresses.len() < 36 {
+                return Err(Error::Invalid {
+                    reason: "truncated IPv6 addresses",
+                });
+            }
+
+            let ip = Ipv6Addr::from(<[u8; 16]>::try_from(&addresses[0..16]).expect("it should be a 16-byte slice"));
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+
+            Some(SocketAddr::new(IpAddr::V6(ip), port))
+        }
+        // LOCAL, or PROXY with unspecified or Unix addresses
+        (0x0 | 0x1, _) => None,
+        _ => {
+            return Err(Error::Invalid {
+                reason: "unsupported command",
+            })
+        }
+    };
+
+    Ok(Header { source, length })
+}
+
+/// It builds a version 2 header with the `PROXY` command. It's what a load
+/// balancer would send for a connection from `source` to `destination`.
+///
+/// IPv4 addresses are mapped to IPv6 when the families of the addresses are
+/// not the same.
+#[must_use]
+pub fn encode_v2(source: SocketAddr, destination: SocketAddr, datagram: bool) -> Vec<u8> {
+    let transport = if datagram { 0x2 } else { 0x1 };
+
+    let mut header = V2_SIGNATURE.to_vec();
+    header.push(0x21);
+
+    match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source_ip), IpAddr::V4(destination_ip)) => {
+            header.push(0x10 | transport);
+            header.extend_from_slice(&12u16.to_be_bytes());
+            header.extend_from_slice(&source_ip.octets());
+            header.extend_from_slice(&destination_ip.octets());
+        }
+        (source_ip, destination_ip) => {
+            header.push(0x20 | transport);
+            header.extend_from_slice(&36u16.to_be_bytes());
+            header.extend_from_slice(&to_ipv6(source_ip).octets());
+            header.extend_from_slice(&to_ipv6(destination_ip).octets());
+        }
+    }
+
+    header.extend_from_slice(&source.port().to_be_bytes());
+    header.extend_from_slice(&destination.port().to_be_bytes());
+
+    header
+}
+
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+/// It reads a header from the beginning of a stream. It does not read any
+/// byte after the header, so the stream can be used by the client protocol
+/// afterwards.
+///
+/// # Errors
+///
+/// Will return an error if it can't read from the stream or the stream does
+/// not start with a valid header.
+pub async fn read_header<S: AsyncRead + Unpin>(stream: &mut S) -> io::Result<Header> {
+    // Both the shortest version 1 header and the version 2 header are longer
+    // than this.
+    let mut buffer = vec![0u8; 8];
+
+    stream.read_exact(&mut buffer).await?;
+
+    if buffer.starts_with(V1_PREFIX) {
+        while !buffer.ends_with(b"\r\n") && buffer.len() < V1_MAX_LENGTH {
+            buffer.push(stream.read_u8().await?);
+        }
+    } else if V2_SIGNATURE.starts_with(&buffer) {
+        buffer.resize(V2_FIXED_LENGTH, 0);
+        stream.read_exact(&mut buffer[8..]).await?;
+
+        let addresses_length = usize::from(u16::from_be_bytes([buffer[14], buffer[15]]));
+
+        buffer.resize(V2_FIXED_LENGTH + addresses_length, 0);
+        stream.read_exact(&mut buffer[V2_FIXED_LENGTH..]).await?;
+    }
+
+    parse(&buffer).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The service with the [`ConnectInfo`] of the original client.
+#[cfg(feature = "web")]
+pub type WithConnectInfo<S> = AddExtension<S, ConnectInfo<SocketAddr>>;
+
+/// An [`Accept`] implementation for `axum-server` that reads the PROXY
+/// protocol header of every new connection before passing it to the `inner`
+/// acceptor. Connections from sources that are not trusted (see
+/// [`is_trusted_source`]) are rejected before reading the header.
+///
+/// The service gets the original client address as [`ConnectInfo`], so it has
+/// to be served without the `ConnectInfo` provided by `axum`
+/// (`into_make_service` instead of `into_make_service_with_connect_info`).
+#[cfg(feature = "web")]
+#[derive(Clone, Debug)]
+pub struct ProxyProtocolAcceptor<A> {
+    inner: A,
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+#[cfg(feature = "web")]
+impl<A> ProxyProtocolAcceptor<A> {
+    #[must_use]
+    pub fn new(inner: A, trusted_proxies: Arc<[IpAddr]>) -> Self {
+        Self { inner, trusted_proxies }
+    }
+}
+
+#[cfg(feature = "web")]
+impl<A, S> Accept<TcpStream, S> for ProxyProtocolAcceptor<A>
+where
+    A: Accept<TcpStream, WithConnectInfo<S>> + Clone + Send + Sync + 'static,
+    A::Future: Send,
+    S: Send + 'static,
+{
+    type Stream = A::Stream;
+    type Service = A::Service;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, mut stream: TcpStream, service: S) -> Self::Future {
+        let inner = self.inner.clone();
+        let trusted_proxies = self.trusted_proxies.clone();
+
+        Box::pin(async move {
+            let peer_addr = stream.peer_addr()?;
+
+            if !is_trusted_source(peer_addr.ip(), &trusted_proxies) {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("PROXY protocol connection from an untrusted source: {peer_addr}"),
+                ));
+            }
+
+            let header = tokio::time::timeout(HEADER_READ_TIMEOUT, read_header(&mut stream))
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "timeout reading the PROXY protocol header"))??;
+
+            let client_addr = header.source.unwrap_or(peer_addr);
+
+            let service = Extension(ConnectInfo(client_addr)).layer(service);
+
+            inner.accept(stream, service).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, SocketAddr};
+
+    use super::{encode_v2, is_trusted_source, parse, read_header, Error, Header, V2_LOCAL_HEADER};
+
+    fn source() -> SocketAddr {
+        "126.0.0.1:56324".parse().unwrap()
+    }
+
+    fn destination() -> SocketAddr {
+        "192.168.0.11:7070".parse().unwrap()
+    }
+
+    #[test]
+    fn it_should_parse_a_version_1_header() {
+        let bytes = b"PROXY TCP4 126.0.0.1 192.168.0.11 56324 7070\r\nGET / HTTP/1.1\r\n";
+
+        assert_eq!(
+            parse(bytes),
+            Ok(Header {
+                source: Some(source()),
+                length: 46
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_parse_a_version_1_header_with_an_ipv6_client() {
+        let bytes = b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 7070\r\n";
+
+        assert_eq!(parse(bytes).unwrap().source, Some("[2001:db8::1]:56324".parse().unwrap()));
+    }
+
+    #[test]
+    fn it_should_parse_a_version_1_header_without_a_client_address() {
+        assert_eq!(
+            parse(b"PROXY UNKNOWN\r\n"),
+            Ok(Header {
+                source: None,
+                length: 15
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_parsing_a_version_1_header_with_an_address_that_does_not_match_the_protocol() {
+        assert!(matches!(
+            parse(b"PROXY TCP4 2001:db8::1 2001:db8::2 56324 7070\r\n"),
+            Err(Error::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_parse_a_version_2_header() {
+        let mut bytes = encode_v2(source(), destination(), false);
+        bytes.extend_from_slice(b"GET / HTTP/1.1\r\n");
+
+        assert_eq!(
+            parse(&bytes),
+            Ok(Header {
+                source: Some(source()),
+                length: 28
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_parse_a_version_2_header_with_an_ipv6_client() {
+        let source: SocketAddr = "[2001:db8::1]:56324".parse().unwrap();
+
+        assert_eq!(parse(&encode_v2(source, destination(), true)).unwrap().source, Some(source));
+    }
+
+    #[test]
+    fn it_should_parse_a_version_2_header_with_the_local_command() {
+        assert_eq!(
+            parse(&V2_LOCAL_HEADER),
+            Ok(Header {
+                source: None,
+                length: 16
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_parsing_data_without_a_header() {
+        assert_eq!(parse(b"GET /announce HTTP/1.1\r\n"), Err(Error::MissingHeader));
+    }
+
+    #[test]
+    fn it_should_fail_parsing_an_incomplete_header() {
+        let bytes = encode_v2(source(), destination(), false);
+
+        assert_eq!(parse(&bytes[..20]), Err(Error::Incomplete));
+        assert_eq!(parse(b"PROXY TCP4 126.0.0.1"), Err(Error::Incomplete));
+    }
+
+    #[tokio::test]
+    async fn it_should_read_only_the_header_from_a_stream() {
+        for mut header in [
+            b"PROXY TCP4 126.0.0.1 192.168.0.11 56324 7070\r\n".to_vec(),
+            encode_v2(source(), destination(), false),
+        ] {
+            header.extend_from_slice(b"GET / HTTP/1.1\r\n");
+
+            let mut stream = header.as_slice();
+
+            assert_eq!(read_header(&mut stream).await.unwrap().source, Some(source()));
+            assert_eq!(stream, b"GET / HTTP/1.1\r\n");
+        }
+    }
+
+    #[test]
+    fn it_should_only_trust_the_loopback_addresses_and_the_trusted_proxies() {
+        let trusted_proxies: Vec<IpAddr> = vec!["10.0.0.2".parse().unwrap()];
+
+        assert!(is_trusted_source("127.0.0.1".parse().unwrap(), &trusted_proxies));
+        assert!(is_trusted_source("::1".parse().unwrap(), &trusted_proxies));
+        assert!(is_trusted_source("10.0.0.2".parse().unwrap(), &trusted_proxies));
+        assert!(is_trusted_source("::ffff:10.0.0.2".parse().unwrap(), &trusted_proxies));
+        assert!(!is_trusted_source("10.0.0.3".parse().unwrap(), &trusted_proxies));
+        assert!(!is_trusted_source("10.0.0.2".parse().unwrap(), &[]));
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_reading_a_stream_without_a_header() {
+        let mut stream = b"GET /announce HTTP/1.1\r\n".as_slice();
+
+        assert!(read_header(&mut stream).await.is_err());
+    }
+}
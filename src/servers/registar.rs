@@ -1,57 +1,293 @@
 //! Registar. Registers Services for Health Check.
 
 use std::collections::HashMap;
+use std::fmt;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use derive_more::Constructor;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
+use torrust_tracker_configuration::BindAddress;
 
 /// A [`ServiceHeathCheckResult`] is returned by a completed health check.
 pub type ServiceHeathCheckResult = Result<String, String>;
 
+/// The address a service is bound to: a socket address or the path of a
+/// Unix domain socket.
+///
+/// It's serialized like in the configuration, for example `127.0.0.1:1212`
+/// or `unix:/var/run/torrust/api.sock`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Binding {
+    Socket(SocketAddr),
+    Unix(Utf8PathBuf),
+}
+
+impl Binding {
+    /// It returns the socket address, if the service is not bound to a Unix
+    /// domain socket.
+    #[must_use]
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Binding::Socket(socket_addr) => Some(*socket_addr),
+            Binding::Unix(_) => None,
+        }
+    }
+}
+
+impl From<SocketAddr> for Binding {
+    fn from(socket_addr: SocketAddr) -> Self {
+        Binding::Socket(socket_addr)
+    }
+}
+
+impl PartialEq<SocketAddr> for Binding {
+    fn eq(&self, other: &SocketAddr) -> bool {
+        self.socket_addr().as_ref() == Some(other)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Socket(socket_addr) => write!(f, "{socket_addr}"),
+            Binding::Unix(path) => write!(f, "{}{path}", BindAddress::UNIX_PREFIX),
+        }
+    }
+}
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let binding = String::deserialize(deserializer)?;
+
+        match binding.strip_prefix(BindAddress::UNIX_PREFIX) {
+            Some(path) => Ok(Binding::Unix(path.into())),
+            None => binding.parse().map(Binding::Socket).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 /// The [`ServiceHealthCheckJob`] has a health check job with it's metadata
 ///
 /// The `job` awaits a [`ServiceHeathCheckResult`].
-#[derive(Debug, Constructor)]
+#[derive(Debug)]
 pub struct ServiceHealthCheckJob {
-    pub binding: SocketAddr,
+    pub binding: Binding,
     pub info: String,
     pub job: JoinHandle<ServiceHeathCheckResult>,
 }
 
+impl ServiceHealthCheckJob {
+    #[must_use]
+    pub fn new(binding: impl Into<Binding>, info: String, job: JoinHandle<ServiceHeathCheckResult>) -> Self {
+        Self {
+            binding: binding.into(),
+            info,
+            job,
+        }
+    }
+}
+
 /// The function specification [`FnSpawnServiceHeathCheck`].
 ///
 /// A function fulfilling this specification will spawn a new [`ServiceHealthCheckJob`].
 pub type FnSpawnServiceHeathCheck = fn(&SocketAddr) -> ServiceHealthCheckJob;
 
+/// The function specification [`FnSpawnUnixSocketHeathCheck`], for the
+/// services bound to a Unix domain socket.
+///
+/// A function fulfilling this specification will spawn a new [`ServiceHealthCheckJob`].
+pub type FnSpawnUnixSocketHeathCheck = fn(&Utf8Path) -> ServiceHealthCheckJob;
+
+/// The address of a listener of a service, with the function checking it.
+#[derive(Clone, Debug)]
+enum Listener {
+    Socket(SocketAddr, FnSpawnServiceHeathCheck),
+    Unix(Utf8PathBuf, FnSpawnUnixSocketHeathCheck),
+}
+
+impl Listener {
+    fn binding(&self) -> Binding {
+        match self {
+            Listener::Socket(socket_addr, _) => Binding::Socket(*socket_addr),
+            Listener::Unix(path, _) => Binding::Unix(path.clone()),
+        }
+    }
+
+    fn spawn_check(&self) -> ServiceHealthCheckJob {
+        match self {
+            Listener::Socket(socket_addr, check_fn) => check_fn(socket_addr),
+            Listener::Unix(path, check_fn) => check_fn(path),
+        }
+    }
+}
+
+/// The kind of service a [`ServiceRegistration`] belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceKind {
+    UdpTracker,
+    HttpTracker,
+    HttpApi,
+    HealthCheckApi,
+}
+
+impl ServiceKind {
+    /// The protocol used to reach the service.
+    #[must_use]
+    pub fn protocol(self, tls: bool) -> &'static str {
+        match (self, tls) {
+            (ServiceKind::UdpTracker, _) => "udp",
+            (_, false) => "http",
+            (_, true) => "https",
+        }
+    }
+}
+
 /// A [`ServiceRegistration`] is provided to the [`Registar`] for registration.
 ///
 /// Each registration includes a function that fulfils the [`FnSpawnServiceHeathCheck`] specification.
-#[derive(Clone, Debug, Constructor)]
+///
+/// A service bound to more than one address is registered once, with the
+/// registrations of the other listeners grouped under the main one. See
+/// [`ServiceRegistration::group`].
+#[derive(Clone, Debug)]
 pub struct ServiceRegistration {
-    binding: SocketAddr,
-    check_fn: FnSpawnServiceHeathCheck,
+    listener: Listener,
+    kind: ServiceKind,
+    tls: bool,
+    listeners: Vec<ServiceRegistration>,
 }
 
 impl ServiceRegistration {
+    #[must_use]
+    pub fn new(binding: SocketAddr, kind: ServiceKind, tls: bool, check_fn: FnSpawnServiceHeathCheck) -> Self {
+        Self {
+            listener: Listener::Socket(binding, check_fn),
+            kind,
+            tls,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// It registers a service bound to a Unix domain socket. These services
+    /// don't support TLS.
+    #[must_use]
+    pub fn unix_socket(path: Utf8PathBuf, kind: ServiceKind, check_fn: FnSpawnUnixSocketHeathCheck) -> Self {
+        Self {
+            listener: Listener::Unix(path, check_fn),
+            kind,
+            tls: false,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// It groups the registrations of all the listeners of one logical
+    /// service into a single registration. The first one is the main
+    /// registration.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if there are no registrations.
+    #[must_use]
+    pub fn group(mut registrations: Vec<ServiceRegistration>) -> Self {
+        assert!(!registrations.is_empty(), "it should have at least one registration");
+
+        let mut main = registrations.remove(0);
+
+        main.listeners.extend(registrations);
+
+        main
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> ServiceKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn tls(&self) -> bool {
+        self.tls
+    }
+
+    /// It returns the address of the main listener of the service.
+    #[must_use]
+    pub fn binding(&self) -> Binding {
+        self.listener.binding()
+    }
+
+    /// It returns the addresses of all the listeners of the service, starting
+    /// with the main one.
+    #[must_use]
+    pub fn bindings(&self) -> Vec<Binding> {
+        std::iter::once(self.binding())
+            .chain(self.listeners.iter().flat_map(ServiceRegistration::bindings))
+            .collect()
+    }
+
+    /// It spawns the health check for the service.
+    ///
+    /// When the service has more than one listener, all of them are checked.
+    /// The check only passes when all the listeners pass.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if any of the listener checks can't be joined.
     #[must_use]
     pub fn spawn_check(&self) -> ServiceHealthCheckJob {
-        (self.check_fn)(&self.binding)
+        let check = self.listener.spawn_check();
+
+        if self.listeners.is_empty() {
+            return check;
+        }
+
+        let checks: Vec<ServiceHealthCheckJob> = std::iter::once(check)
+            .chain(self.listeners.iter().map(ServiceRegistration::spawn_check))
+            .collect();
+
+        let info = checks.iter().map(|check| check.info.as_str()).collect::<Vec<_>>().join(", ");
+
+        let job = tokio::spawn(async move {
+            let mut passed = Vec::new();
+            let mut failed = Vec::new();
+
+            for check in checks {
+                match check.job.await.expect("it should be able to join into the checking function") {
+                    Ok(message) => passed.push(message),
+                    Err(error) => failed.push(format!("{}: {error}", check.binding)),
+                }
+            }
+
+            if failed.is_empty() {
+                Ok(passed.join(", "))
+            } else {
+                Err(failed.join(", "))
+            }
+        });
+
+        ServiceHealthCheckJob::new(self.binding(), info, job)
     }
 }
 
 /// A [`ServiceRegistrationForm`] will return a completed [`ServiceRegistration`] to the [`Registar`].
 pub type ServiceRegistrationForm = tokio::sync::oneshot::Sender<ServiceRegistration>;
 
-/// The [`ServiceRegistry`] contains each unique [`ServiceRegistration`] by it's [`SocketAddr`].
-pub type ServiceRegistry = Arc<Mutex<HashMap<SocketAddr, ServiceRegistration>>>;
+/// The [`ServiceRegistry`] contains each unique [`ServiceRegistration`] by it's [`Binding`].
+pub type ServiceRegistry = Arc<Mutex<HashMap<Binding, ServiceRegistration>>>;
 
 /// The [`Registar`] manages the [`ServiceRegistry`].
 #[derive(Clone, Debug)]
 pub struct Registar {
     registry: ServiceRegistry,
+    pending: Arc<std::sync::Mutex<Vec<JoinHandle<()>>>>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -59,26 +295,56 @@ impl Default for Registar {
     fn default() -> Self {
         Self {
             registry: ServiceRegistry::default(),
+            pending: Arc::default(),
         }
     }
 }
 
 impl Registar {
     pub fn new(register: ServiceRegistry) -> Self {
-        Self { registry: register }
+        Self {
+            registry: register,
+            pending: Arc::default(),
+        }
     }
 
     /// Registers a Service
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the list of pending registrations is poisoned.
     #[must_use]
     pub fn give_form(&self) -> ServiceRegistrationForm {
         let (tx, rx) = tokio::sync::oneshot::channel::<ServiceRegistration>();
         let register = self.clone();
-        tokio::spawn(async move {
+        let insertion = tokio::spawn(async move {
             register.insert(rx).await;
         });
+        self.pending
+            .lock()
+            .expect("it should lock the pending registrations")
+            .push(insertion);
         tx
     }
 
+    /// Waits until the registrations of all the forms given so far are
+    /// inserted into the registry.
+    ///
+    /// Forms dropped without a registration are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the list of pending registrations is poisoned.
+    pub async fn wait_for_registrations(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().expect("it should lock the pending registrations"));
+
+        for insertion in pending {
+            if let Err(err) = insertion.await {
+                tracing::debug!("A service was not registered: {err}");
+            }
+        }
+    }
+
     /// Inserts a listing into the registry.
     async fn insert(&self, rx: tokio::sync::oneshot::Receiver<ServiceRegistration>) {
         tracing::debug!("Waiting for the started service to send registration data ...");
@@ -89,7 +355,7 @@ impl Registar {
 
         let mut mutex = self.registry.lock().await;
 
-        mutex.insert(service_registration.binding, service_registration);
+        mutex.insert(service_registration.binding(), service_registration);
     }
 
     /// Returns the [`ServiceRegistry`] of services
@@ -98,3 +364,86 @@ impl Registar {
         self.registry.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::{Binding, ServiceHealthCheckJob, ServiceKind, ServiceRegistration};
+
+    fn passing_check(binding: &SocketAddr) -> ServiceHealthCheckJob {
+        ServiceHealthCheckJob::new(
+            *binding,
+            format!("checking {binding}"),
+            tokio::spawn(async { Ok("ok".to_string()) }),
+        )
+    }
+
+    fn failing_check(binding: &SocketAddr) -> ServiceHealthCheckJob {
+        ServiceHealthCheckJob::new(
+            *binding,
+            format!("checking {binding}"),
+            tokio::spawn(async { Err("unreachable".to_string()) }),
+        )
+    }
+
+    fn ipv4_binding() -> SocketAddr {
+        "127.0.0.1:6969".parse().unwrap()
+    }
+
+    fn ipv6_binding() -> SocketAddr {
+        "[::1]:6969".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_grouped_registration_should_be_reported_with_the_main_binding() {
+        let registration = ServiceRegistration::group(vec![
+            ServiceRegistration::new(ipv4_binding(), ServiceKind::UdpTracker, false, passing_check),
+            ServiceRegistration::new(ipv6_binding(), ServiceKind::UdpTracker, false, passing_check),
+        ]);
+
+        let check = registration.spawn_check();
+
+        assert_eq!(check.binding, ipv4_binding());
+        assert_eq!(check.info, "checking 127.0.0.1:6969, checking [::1]:6969");
+        assert_eq!(check.job.await.unwrap(), Ok("ok, ok".to_string()));
+    }
+
+    #[test]
+    fn a_grouped_registration_should_list_the_bindings_of_all_the_listeners() {
+        let registration = ServiceRegistration::group(vec![
+            ServiceRegistration::new(ipv4_binding(), ServiceKind::UdpTracker, false, passing_check),
+            ServiceRegistration::new(ipv6_binding(), ServiceKind::UdpTracker, false, passing_check),
+        ]);
+
+        assert_eq!(registration.bindings(), vec![ipv4_binding(), ipv6_binding()]);
+    }
+
+    #[tokio::test]
+    async fn a_grouped_registration_should_fail_when_any_of_the_listeners_fails() {
+        let registration = ServiceRegistration::group(vec![
+            ServiceRegistration::new(ipv4_binding(), ServiceKind::UdpTracker, false, passing_check),
+            ServiceRegistration::new(ipv6_binding(), ServiceKind::UdpTracker, false, failing_check),
+        ]);
+
+        assert_eq!(
+            registration.spawn_check().job.await.unwrap(),
+            Err("[::1]:6969: unreachable".to_string())
+        );
+    }
+
+    #[test]
+    fn a_binding_should_be_serialized_as_the_socket_address_or_the_unix_socket_path() {
+        let socket = Binding::from(ipv4_binding());
+        let unix = Binding::Unix("/var/run/torrust/api.sock".into());
+
+        assert_eq!(serde_json::to_string(&socket).unwrap(), r#""127.0.0.1:6969""#);
+        assert_eq!(serde_json::to_string(&unix).unwrap(), r#""unix:/var/run/torrust/api.sock""#);
+
+        assert_eq!(serde_json::from_str::<Binding>(r#""127.0.0.1:6969""#).unwrap(), socket);
+        assert_eq!(
+            serde_json::from_str::<Binding>(r#""unix:/var/run/torrust/api.sock""#).unwrap(),
+            unix
+        );
+    }
+}
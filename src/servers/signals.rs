@@ -1,7 +1,9 @@
 //! This module contains functions to handle signals.
+#[cfg(feature = "web")]
 use std::time::Duration;
 
 use derive_more::Display;
+#[cfg(feature = "web")]
 use tokio::time::sleep;
 use tracing::instrument;
 
@@ -69,6 +71,7 @@ pub async fn shutdown_signal_with_message(rx_halt: tokio::sync::oneshot::Receive
     tracing::info!("{message}");
 }
 
+#[cfg(feature = "web")]
 #[instrument(skip(handle, rx_halt, message))]
 pub async fn graceful_shutdown(handle: axum_server::Handle, rx_halt: tokio::sync::oneshot::Receiver<Halted>, message: String) {
     shutdown_signal_with_message(rx_halt, message).await;
@@ -69,6 +69,7 @@ use std::panic::Location;
 
 use aquatic_udp_protocol::ConnectionId;
 use torrust_tracker_clock::time_extent::{Extent, TimeExtent};
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
 use zerocopy::network_endian::I64;
 use zerocopy::AsBytes;
 
@@ -103,6 +104,16 @@ pub fn make(remote_address: &SocketAddr) -> Cookie {
     cookie_builder::build(remote_address, &time_extent)
 }
 
+/// Returns the time until the connection cookies generated now are valid.
+///
+/// # Panics
+///
+/// It would panic if the `COOKIE_LIFETIME` constant would be an unreasonably large number.
+#[must_use]
+pub fn valid_until() -> DurationSinceUnixEpoch {
+    cookie_builder::get_last_time_extent().total_next().unwrap().unwrap()
+}
+
 /// Checks if the supplied `connection_cookie` is valid.
 ///
 /// # Panics
@@ -165,13 +176,14 @@ mod cookie_builder {
 #[cfg(test)]
 mod tests {
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::time::Duration;
 
     use torrust_tracker_clock::clock::stopped::Stopped as _;
     use torrust_tracker_clock::clock::{self};
     use torrust_tracker_clock::time_extent::{self, Extent};
 
     use super::cookie_builder::{self};
-    use crate::servers::udp::connection_cookie::{check, make, Cookie, COOKIE_LIFETIME};
+    use crate::servers::udp::connection_cookie::{check, make, valid_until, Cookie, COOKIE_LIFETIME};
 
     // #![feature(const_socketaddr)]
     // const REMOTE_ADDRESS_IPV4_ZERO: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
@@ -336,4 +348,22 @@ mod tests {
 
         check(&remote_address, &cookie).unwrap();
     }
+
+    #[test]
+    fn it_should_be_valid_until_the_end_of_their_last_time_extent() {
+        let remote_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+
+        clock::Stopped::local_set_to_unix_epoch();
+
+        let cookie = make(&remote_address);
+        let valid_until = valid_until();
+
+        clock::Stopped::local_set(&valid_until.checked_sub(Duration::from_nanos(1)).unwrap());
+
+        check(&remote_address, &cookie).unwrap();
+
+        clock::Stopped::local_set(&valid_until);
+
+        assert!(check(&remote_address, &cookie).is_err());
+    }
 }
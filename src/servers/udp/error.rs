@@ -1,17 +1,37 @@
 //! Error types for the UDP server.
+//!
+//! The error responses sent to the clients start with the short
+//! machine-readable code of the error, followed by the human-readable
+//! message. For example:
+//!
+//! ```text
+//! E03: tracker server error: The torrent: 9c38422213e30bff212b30c360d26f9a02136422, is not whitelisted, src/servers/udp/handlers.rs:302:17
+//! ```
+//!
+//! Errors returned by the domain tracker keep their own
+//! [code](crate::core::error::Error::code). The codes for the UDP server
+//! errors are:
+//!
+//! Error | Code
+//! ---|---
+//! `InternalServer` | `E20`
+//! `InvalidConnectionId` | `E21`
+//! `BadRequest` | `E22`
+//! `TooManyInfoHashes` | `E23`
+//! `TrackerAuthenticationRequired` | `E24`
 use std::panic::Location;
 
 use thiserror::Error;
 use torrust_tracker_located_error::LocatedError;
 
+use crate::core;
+
 /// Error returned by the UDP server.
 #[derive(Error, Debug)]
 pub enum Error {
     /// Error returned when the domain tracker returns an error.
     #[error("tracker server error: {source}")]
-    TrackerError {
-        source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
-    },
+    TrackerError { source: core::error::Error },
 
     /// Error returned from a third-party library (`aquatic_udp_protocol`).
     #[error("internal server error: {message}, {location}")]
@@ -30,7 +50,32 @@ pub enum Error {
         source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
     },
 
+    /// Error returned when a `scrape` request contains more info-hashes than
+    /// the configured limit.
+    #[error("too many info-hashes in scrape request: {requested}, max is {max}")]
+    TooManyInfoHashes {
+        requested: usize,
+        max: u16,
+        location: &'static Location<'static>,
+    },
+
     /// Error returned when tracker requires authentication.
     #[error("domain tracker requires authentication but is not supported in current UDP implementation. Location: {location}")]
     TrackerAuthenticationRequired { location: &'static Location<'static> },
 }
+
+impl Error {
+    /// The short machine-readable code of the error in the unified error
+    /// taxonomy. See the [module](self) documentation.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::TrackerError { source } => source.code(),
+            Error::InternalServer { .. } => "E20",
+            Error::InvalidConnectionId { .. } => "E21",
+            Error::BadRequest { .. } => "E22",
+            Error::TooManyInfoHashes { .. } => "E23",
+            Error::TrackerAuthenticationRequired { .. } => "E24",
+        }
+    }
+}
@@ -1,4 +1,5 @@
 //! Handlers for the UDP server.
+use std::borrow::Cow;
 use std::fmt;
 use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
@@ -12,17 +13,19 @@ use aquatic_udp_protocol::{
 };
 use torrust_tracker_located_error::DynError;
 use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use tracing::{instrument, Level};
 use uuid::Uuid;
 use zerocopy::network_endian::I32;
 
-use super::connection_cookie::{check, from_connection_id, into_connection_id, make};
+use super::connection_cookie::{check, from_connection_id, into_connection_id, make, valid_until};
 use super::RawRequest;
-use crate::core::{statistics, PeersWanted, ScrapeData, Tracker};
+use crate::core::negative_cache::Rejection;
+use crate::core::slow_requests::{self, Kind, Phase, Timer};
+use crate::core::{self, statistics, AnnounceMode, PeersWanted, Requester, ScrapeData, Tracker};
 use crate::servers::udp::error::Error;
 use crate::servers::udp::logging::{log_bad_request, log_error_response, log_request, log_response};
 use crate::servers::udp::peer_builder;
-use crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS;
 
 /// It handles the incoming UDP packets.
 ///
@@ -33,20 +36,38 @@ use crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS;
 ///
 /// It will return an `Error` response if the request is invalid.
 #[instrument(skip(udp_request, tracker, local_addr), ret(level = Level::TRACE))]
-pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, local_addr: SocketAddr) -> Response {
+pub(crate) async fn handle_packet(
+    udp_request: RawRequest,
+    tracker: &Arc<Tracker>,
+    local_addr: SocketAddr,
+    announce_mode: AnnounceMode,
+) -> Response {
     tracing::debug!("Handling Packets: {udp_request:?}");
 
     let start_time = Instant::now();
+    let mut timer = Timer::start();
+
+    tracker.inject_request_latency().await;
 
     let request_id = RequestId::make(&udp_request);
 
-    match Request::parse_bytes(&udp_request.payload[..udp_request.payload.len()], MAX_SCRAPE_TORRENTS).map_err(|e| {
-        Error::InternalServer {
-            message: format!("{e:?}"),
-            location: Location::caller(),
-        }
+    // The `paused` event from partial seeds and unknown events are not valid
+    // BEP 15 events, so they are replaced before parsing the request.
+    let (payload, replaced_event) = match replace_non_standard_event(&udp_request.payload) {
+        Some((payload, replaced_event)) => (Cow::Owned(payload), Some(replaced_event)),
+        None => (Cow::Borrowed(&udp_request.payload), None),
+    };
+
+    // The scrape limit is enforced in the `scrape` handler. The parser returns
+    // up to `u8::MAX` info-hashes, so the scrape requests with more
+    // info-hashes are rejected below instead of silently truncating the list.
+    match Request::parse_bytes(&payload[..payload.len()], u8::MAX).map_err(|e| Error::InternalServer {
+        message: format!("{e:?}"),
+        location: Location::caller(),
     }) {
         Ok(request) => {
+            timer.lap(Phase::Parse);
+
             log_request(&request, &request_id, &local_addr);
 
             let transaction_id = match &request {
@@ -55,13 +76,38 @@ pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, lo
                 Request::Scrape(scrape_request) => scrape_request.transaction_id,
             };
 
-            let response = match handle_request(request, udp_request.from, tracker).await {
+            let is_announce = matches!(request, Request::Announce(_));
+
+            let result = match scrape_info_hashes_not_parsed(&request, &payload) {
+                Some(requested) => too_many_info_hashes(requested, udp_request.from, tracker).await,
+                None => handle_request(&request, udp_request.from, replaced_event, tracker, announce_mode, &mut timer).await,
+            };
+
+            let response = match result {
                 Ok(response) => response,
-                Err(e) => handle_error(&e, transaction_id),
+                Err(e) => {
+                    // See `deadline::Admission`.
+                    timer.lap(Phase::Authorization);
+                    handle_error(&e, transaction_id, tracker)
+                }
             };
 
+            timer.lap(Phase::Serialize);
+
+            if let Some(kind) = slow_request_kind(&request) {
+                tracker.log_slow_request(kind, &timer, || slow_requests::Request {
+                    info_hashes: requested_info_hashes(&request),
+                    client_ip: Some(peer_ip(&udp_request.from)),
+                    target: None,
+                });
+            }
+
             let latency = start_time.elapsed();
 
+            if let Some(slo) = tracker.slo().filter(|_| is_announce) {
+                slo.record(latency, matches!(response, Response::Error(_)));
+            }
+
             log_response(&response, &transaction_id, &request_id, &local_addr, latency);
 
             response
@@ -73,7 +119,8 @@ pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, lo
                 &Error::BadRequest {
                     source: (Arc::new(e) as DynError).into(),
                 },
-                TransactionId(I32::new(0)),
+                raw_transaction_id(&payload),
+                tracker,
             );
 
             log_error_response(&request_id);
@@ -83,19 +130,166 @@ pub(crate) async fn handle_packet(udp_request: RawRequest, tracker: &Tracker, lo
     }
 }
 
+/// Offset of the `transaction_id` field, which is the same in all the
+/// requests. See [BEP 15](https://www.bittorrent.org/beps/bep_0015.html).
+const TRANSACTION_ID_OFFSET: usize = 12;
+
+/// It returns the transaction ID of a request that could not be parsed, so
+/// that clients with several outstanding transactions can match the error
+/// response. It's `0` when the payload is too short to contain it.
+fn raw_transaction_id(payload: &[u8]) -> TransactionId {
+    let transaction_id = payload
+        .get(TRANSACTION_ID_OFFSET..TRANSACTION_ID_OFFSET + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map_or(0, i32::from_be_bytes);
+
+    TransactionId(I32::new(transaction_id))
+}
+
+/// Length of the fixed part of the scrape request, before the info-hashes.
+/// See [BEP 15](https://www.bittorrent.org/beps/bep_0015.html).
+const SCRAPE_HEADER_LENGTH: usize = 16;
+
+/// Length of an info-hash in the scrape request.
+const INFO_HASH_LENGTH: usize = 20;
+
+/// It returns the number of info-hashes in a scrape request when the parser
+/// didn't return all of them, because there are more than `u8::MAX`.
+fn scrape_info_hashes_not_parsed(request: &Request, payload: &[u8]) -> Option<usize> {
+    let Request::Scrape(scrape_request) = request else {
+        return None;
+    };
+
+    let requested = payload.len().saturating_sub(SCRAPE_HEADER_LENGTH) / INFO_HASH_LENGTH;
+
+    (requested > scrape_request.info_hashes.len()).then_some(requested)
+}
+
+/// It rejects a scrape request with more info-hashes than the parser returns.
+/// No UDP scrape request can contain more than `u8::MAX` torrents, even if the
+/// `max_scrape_torrents` limit is higher.
+async fn too_many_info_hashes(requested: usize, remote_addr: SocketAddr, tracker: &Tracker) -> Result<Response, Error> {
+    send_scrape_limit_exceeded_event(remote_addr, tracker).await;
+
+    Err(Error::TooManyInfoHashes {
+        requested,
+        max: tracker.get_max_scrape_torrents().min(u16::from(u8::MAX)),
+        location: Location::caller(),
+    })
+}
+
+async fn send_scrape_limit_exceeded_event(remote_addr: SocketAddr, tracker: &Tracker) {
+    match peer_ip(&remote_addr) {
+        IpAddr::V4(_) => {
+            tracker.send_stats_event(statistics::Event::Udp4ScrapeLimitExceeded).await;
+        }
+        IpAddr::V6(_) => {
+            tracker.send_stats_event(statistics::Event::Udp6ScrapeLimitExceeded).await;
+        }
+    }
+}
+
+/// Offset of the `event` field in the announce request. See
+/// [BEP 15](https://www.bittorrent.org/beps/bep_0015.html).
+const ANNOUNCE_EVENT_OFFSET: usize = 80;
+
+/// The `event` value sent by partial seeds. See
+/// [BEP 21](https://www.bittorrent.org/beps/bep_0021.html).
+const PAUSED_EVENT: i32 = 4;
+
+/// The `event` value for regular announces.
+const NONE_EVENT: i32 = 0;
+
+/// The highest `event` value defined in
+/// [BEP 15](https://www.bittorrent.org/beps/bep_0015.html): `stopped`.
+const STOPPED_EVENT: i32 = 3;
+
+/// An announce `event` value that is not a BEP 15 event. It's replaced by the
+/// `none` event before parsing the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacedEvent {
+    /// The `paused` event sent by partial seeds.
+    Paused,
+    /// Any other value. The announce is handled as a regular announce.
+    Unknown,
+}
+
+/// It returns a copy of the payload with the event replaced by the `none`
+/// event, if the payload is an announce request with the `paused` event or an
+/// unknown event.
+fn replace_non_standard_event(payload: &[u8]) -> Option<(Vec<u8>, ReplacedEvent)> {
+    const ANNOUNCE_ACTION: i32 = 1;
+
+    let action = payload.get(8..12)?;
+    let event = payload.get(ANNOUNCE_EVENT_OFFSET..ANNOUNCE_EVENT_OFFSET + 4)?;
+
+    if i32::from_be_bytes(action.try_into().ok()?) != ANNOUNCE_ACTION {
+        return None;
+    }
+
+    let replaced_event = match i32::from_be_bytes(event.try_into().ok()?) {
+        NONE_EVENT..=STOPPED_EVENT => return None,
+        PAUSED_EVENT => ReplacedEvent::Paused,
+        _ => ReplacedEvent::Unknown,
+    };
+
+    let mut payload = payload.to_vec();
+
+    payload[ANNOUNCE_EVENT_OFFSET..ANNOUNCE_EVENT_OFFSET + 4].copy_from_slice(&NONE_EVENT.to_be_bytes());
+
+    Some((payload, replaced_event))
+}
+
+/// The kind of the requests logged when they are slow. The `connect`
+/// requests are not logged.
+fn slow_request_kind(request: &Request) -> Option<Kind> {
+    match request {
+        Request::Connect(_) => None,
+        Request::Announce(_) => Some(Kind::Announce),
+        Request::Scrape(_) => Some(Kind::Scrape),
+    }
+}
+
+/// The torrents requested in an `announce` or `scrape` request.
+fn requested_info_hashes(request: &Request) -> Vec<InfoHash> {
+    match request {
+        Request::Connect(_) => vec![],
+        Request::Announce(announce_request) => vec![announce_request.info_hash.into()],
+        Request::Scrape(scrape_request) => scrape_request
+            .info_hashes
+            .iter()
+            .map(|info_hash| (*info_hash).into())
+            .collect(),
+    }
+}
+
 /// It dispatches the request to the correct handler.
 ///
+/// The `replaced_event` is only used for announce requests. See [`handle_packet`].
+///
+/// The `timer` measures the phases of the `announce` and `scrape` requests
+/// for the slow requests log.
+///
 /// # Errors
 ///
 /// If a error happens in the `handle_request` function, it will just return the  `ServerError`.
-#[instrument(skip(request, remote_addr, tracker))]
-pub async fn handle_request(request: Request, remote_addr: SocketAddr, tracker: &Tracker) -> Result<Response, Error> {
+#[instrument(skip(request, remote_addr, tracker, timer))]
+pub async fn handle_request(
+    request: &Request,
+    remote_addr: SocketAddr,
+    replaced_event: Option<ReplacedEvent>,
+    tracker: &Arc<Tracker>,
+    announce_mode: AnnounceMode,
+    timer: &mut Timer,
+) -> Result<Response, Error> {
     tracing::trace!("handle request");
 
     match request {
-        Request::Connect(connect_request) => handle_connect(remote_addr, &connect_request, tracker).await,
-        Request::Announce(announce_request) => handle_announce(remote_addr, &announce_request, tracker).await,
-        Request::Scrape(scrape_request) => handle_scrape(remote_addr, &scrape_request, tracker).await,
+        Request::Connect(connect_request) => handle_connect(remote_addr, connect_request, tracker).await,
+        Request::Announce(announce_request) => {
+            announce(remote_addr, announce_request, replaced_event, tracker, announce_mode, timer).await
+        }
+        Request::Scrape(scrape_request) => scrape_request_with_timer(remote_addr, scrape_request, tracker, timer).await,
     }
 }
 
@@ -118,12 +312,24 @@ pub async fn handle_connect(remote_addr: SocketAddr, request: &ConnectRequest, t
     };
 
     // send stats event
-    match remote_addr {
-        SocketAddr::V4(_) => {
+    match peer_ip(&remote_addr) {
+        IpAddr::V4(_) => {
             tracker.send_stats_event(statistics::Event::Udp4Connect).await;
+            tracker
+                .send_stats_event(statistics::Event::Udp4ConnectionIdIssued {
+                    connection_id: connection_id.0.get(),
+                    valid_until: valid_until(),
+                })
+                .await;
         }
-        SocketAddr::V6(_) => {
+        IpAddr::V6(_) => {
             tracker.send_stats_event(statistics::Event::Udp6Connect).await;
+            tracker
+                .send_stats_event(statistics::Event::Udp6ConnectionIdIssued {
+                    connection_id: connection_id.0.get(),
+                    valid_until: valid_until(),
+                })
+                .await;
         }
     }
 
@@ -136,14 +342,36 @@ pub async fn handle_connect(remote_addr: SocketAddr, request: &ConnectRequest, t
 /// # Errors
 ///
 /// If a error happens in the `handle_announce` function, it will just return the  `ServerError`.
-#[instrument(skip(tracker), err, ret(level = Level::TRACE))]
 pub async fn handle_announce(
     remote_addr: SocketAddr,
     announce_request: &AnnounceRequest,
-    tracker: &Tracker,
+    tracker: &Arc<Tracker>,
+) -> Result<Response, Error> {
+    announce(
+        remote_addr,
+        announce_request,
+        None,
+        tracker,
+        AnnounceMode::Synchronous,
+        &mut Timer::start(),
+    )
+    .await
+}
+
+#[instrument(skip(tracker, timer), err, ret(level = Level::TRACE))]
+async fn announce(
+    remote_addr: SocketAddr,
+    announce_request: &AnnounceRequest,
+    replaced_event: Option<ReplacedEvent>,
+    tracker: &Arc<Tracker>,
+    announce_mode: AnnounceMode,
+    timer: &mut Timer,
 ) -> Result<Response, Error> {
     tracing::trace!("handle announce");
 
+    // Maintenance
+    tracker.check_maintenance().map_err(|e| Error::TrackerError { source: e })?;
+
     // Authentication
     if tracker.requires_authentication() {
         return Err(Error::TrackerAuthenticationRequired {
@@ -151,20 +379,83 @@ pub async fn handle_announce(
         });
     }
 
-    check(&remote_addr, &from_connection_id(&announce_request.connection_id))?;
+    let connection_id = announce_request.connection_id.0.get();
+
+    if let Err(e) = check(&remote_addr, &from_connection_id(&announce_request.connection_id)) {
+        tracker
+            .send_stats_event(statistics::Event::UdpConnectionIdRejected { connection_id })
+            .await;
+
+        return Err(e);
+    }
+
+    tracker
+        .send_stats_event(statistics::Event::UdpConnectionIdUsed { connection_id })
+        .await;
 
     let info_hash = announce_request.info_hash.into();
-    let remote_client_ip = remote_addr.ip();
+    let remote_client_ip = peer_ip(&remote_addr);
 
     // Authorization
-    tracker.authorize(&info_hash).await.map_err(|e| Error::TrackerError {
-        source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+    if tracker.get_cached_rejection(&info_hash) == Some(Rejection::NotAuthorized) {
+        tracker.send_stats_event(statistics::Event::UdpNegativeCacheHit).await;
+
+        let e = core::error::Error::TorrentNotWhitelisted {
+            info_hash,
+            location: Location::caller(),
+        };
+
+        return Err(Error::TrackerError { source: e });
+    }
+
+    tracker.authorize(&info_hash).await.map_err(|e| {
+        tracker.cache_rejection(&info_hash, Rejection::NotAuthorized);
+
+        Error::TrackerError { source: e }
     })?;
 
-    let mut peer = peer_builder::from_request(announce_request, &remote_client_ip);
+    let partial_seed = replaced_event == Some(ReplacedEvent::Paused);
+
+    let mut peer = peer_builder::from_request(announce_request, &remote_client_ip, partial_seed);
+
+    // Torrent policy
+    tracker
+        .authorize_peer(&info_hash, &peer)
+        .await
+        .map_err(|e| Error::TrackerError { source: e })?;
+
+    if replaced_event == Some(ReplacedEvent::Unknown) {
+        tracker.send_stats_event(statistics::Event::UdpAnnounceWithUnknownEvent).await;
+    }
+
     let peers_wanted: PeersWanted = i32::from(announce_request.peers_wanted.0).into();
 
-    let response = tracker.announce(&info_hash, &mut peer, &remote_client_ip, &peers_wanted);
+    timer.lap(Phase::Authorization);
+
+    let response = match tracker
+        .announce_within_deadline(
+            &Requester::Anonymous,
+            &info_hash,
+            &mut peer,
+            &remote_client_ip,
+            &peers_wanted,
+            announce_mode,
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::warn!(%info_hash, "{err}, returning a best-effort announce response");
+
+            tracker.send_stats_event(statistics::Event::UdpAnnounceDeadlineExceeded).await;
+
+            tracker.best_effort_announce_data(&info_hash, &Requester::Anonymous)
+        }
+    };
+
+    tracker.sample_peer_reachability(&info_hash, &peer.peer_addr);
+
+    timer.lap(Phase::Repository);
 
     match remote_client_ip {
         IpAddr::V4(_) => {
@@ -176,7 +467,7 @@ pub async fn handle_announce(
     }
 
     #[allow(clippy::cast_possible_truncation)]
-    if remote_addr.is_ipv4() {
+    if remote_client_ip.is_ipv4() {
         let announce_response = AnnounceResponse {
             fixed: AnnounceResponseFixedData {
                 transaction_id: announce_request.transaction_id,
@@ -234,27 +525,77 @@ pub async fn handle_announce(
 ///
 /// # Errors
 ///
-/// This function does not ever return an error.
-#[instrument(skip(tracker), err, ret(level = Level::TRACE))]
-pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tracker: &Tracker) -> Result<Response, Error> {
+/// If the request contains more info-hashes than the configured
+/// `max_scrape_torrents` limit, it will return a `TooManyInfoHashes` error.
+///
+/// If the request contains a torrent removed from the whitelist, it will
+/// return a `TrackerError` error.
+pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tracker: &Arc<Tracker>) -> Result<Response, Error> {
+    scrape_request_with_timer(remote_addr, request, tracker, &mut Timer::start()).await
+}
+
+#[instrument(skip(tracker, timer), err, ret(level = Level::TRACE))]
+async fn scrape_request_with_timer(
+    remote_addr: SocketAddr,
+    request: &ScrapeRequest,
+    tracker: &Arc<Tracker>,
+    timer: &mut Timer,
+) -> Result<Response, Error> {
     tracing::trace!("handle scrape");
 
+    let max_scrape_torrents = tracker.get_max_scrape_torrents();
+
+    if request.info_hashes.len() > usize::from(max_scrape_torrents) {
+        send_scrape_limit_exceeded_event(remote_addr, tracker).await;
+
+        return Err(Error::TooManyInfoHashes {
+            requested: request.info_hashes.len(),
+            max: max_scrape_torrents,
+            location: Location::caller(),
+        });
+    }
+
     // Convert from aquatic infohashes
     let mut info_hashes: Vec<InfoHash> = vec![];
     for info_hash in &request.info_hashes {
         info_hashes.push((*info_hash).into());
     }
 
+    // Torrents removed from the whitelist
+    tracker
+        .check_scrape_of_removed_torrents(&info_hashes)
+        .await
+        .map_err(|e| Error::TrackerError { source: e })?;
+
+    timer.lap(Phase::Authorization);
+
     let scrape_data = if tracker.requires_authentication() {
         ScrapeData::zeroed(&info_hashes)
     } else {
-        tracker.scrape(&info_hashes).await
+        match tracker.start_request_deadline().run(scrape(tracker, &info_hashes)).await {
+            Ok(scrape_data) => scrape_data,
+            Err(err) => {
+                tracing::warn!("{err}, returning a best-effort scrape response");
+
+                tracker.send_stats_event(statistics::Event::UdpScrapeDeadlineExceeded).await;
+
+                tracker.best_effort_scrape_data(&info_hashes)
+            }
+        }
     };
 
+    timer.lap(Phase::Repository);
+
+    // One entry for each requested infohash, in the request order, including
+    // the duplicates. Clients match the entries to the infohashes by position.
     let mut torrent_stats: Vec<TorrentScrapeStatistics> = Vec::new();
 
-    for file in &scrape_data.files {
-        let swarm_metadata = file.1;
+    for info_hash in &info_hashes {
+        let swarm_metadata = scrape_data
+            .files
+            .get(info_hash)
+            .copied()
+            .unwrap_or_else(SwarmMetadata::zeroed);
 
         #[allow(clippy::cast_possible_truncation)]
         let scrape_entry = {
@@ -269,15 +610,23 @@ pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tra
     }
 
     // send stats event
-    match remote_addr {
-        SocketAddr::V4(_) => {
+    match peer_ip(&remote_addr) {
+        IpAddr::V4(_) => {
             tracker.send_stats_event(statistics::Event::Udp4Scrape).await;
         }
-        SocketAddr::V6(_) => {
+        IpAddr::V6(_) => {
             tracker.send_stats_event(statistics::Event::Udp6Scrape).await;
         }
     }
 
+    let duplicates = scrape_data.duplicates(&info_hashes);
+
+    if duplicates > 0 {
+        tracker
+            .send_stats_event(statistics::Event::UdpScrapeDuplicateInfoHashes { count: duplicates })
+            .await;
+    }
+
     let response = ScrapeResponse {
         transaction_id: request.transaction_id,
         torrent_stats,
@@ -286,8 +635,57 @@ pub async fn handle_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tra
     Ok(Response::from(response))
 }
 
-fn handle_error(e: &Error, transaction_id: TransactionId) -> Response {
-    let message = e.to_string();
+/// It returns the swarm metadata of the requested torrents. The torrents
+/// rejected recently, because they are not whitelisted or do not exist, are
+/// returned with zeroed metadata without looking them up again, when the
+/// negative cache is enabled. Duplicate infohashes are only looked up once.
+async fn scrape(tracker: &Tracker, info_hashes: &Vec<InfoHash>) -> ScrapeData {
+    let mut scrape_data = ScrapeData::empty();
+
+    for info_hash in info_hashes {
+        if scrape_data.contains(info_hash) {
+            continue;
+        }
+
+        if tracker.get_cached_rejection(info_hash).is_some() {
+            tracker.send_stats_event(statistics::Event::UdpNegativeCacheHit).await;
+            scrape_data.add_file_with_zeroed_metadata(info_hash);
+            continue;
+        }
+
+        match tracker.scrape_torrent(info_hash).await {
+            Ok(swarm_metadata) => scrape_data.add_file(info_hash, swarm_metadata),
+            Err(rejection) => {
+                tracker.cache_rejection(info_hash, rejection);
+                scrape_data.add_file_with_zeroed_metadata(info_hash);
+            }
+        }
+    }
+
+    scrape_data
+}
+
+/// The IP address of the peer. IPv4 peers reaching a dual-stack socket (see
+/// the `ipv6_only` option) have IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`),
+/// which are converted back to IPv4 so that they are handled as IPv4 peers.
+fn peer_ip(remote_addr: &SocketAddr) -> IpAddr {
+    match remote_addr.ip() {
+        IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+        ip @ IpAddr::V4(_) => ip,
+    }
+}
+
+/// It builds the error response. The message starts with the code of the
+/// error, followed by the failure reason template for the code, when the
+/// operator has configured one, or the default message.
+fn handle_error(e: &Error, transaction_id: TransactionId, tracker: &Tracker) -> Response {
+    let default_message = e.to_string();
+
+    let failure_reason = tracker
+        .render_failure_reason(e.code(), &default_message)
+        .unwrap_or(default_message);
+
+    let message = format!("{}: {failure_reason}", e.code());
     Response::from(ErrorResponse {
         transaction_id,
         message: message.into(),
@@ -359,11 +757,11 @@ mod tests {
     }
 
     fn sample_ipv4_socket_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080)
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080)
     }
 
     fn sample_ipv6_socket_address() -> SocketAddr {
-        SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), 8080)
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080)
     }
 
     #[derive(Debug, Default)]
@@ -428,13 +826,35 @@ mod tests {
         }
     }
 
+    mod bad_request {
+        use aquatic_udp_protocol::TransactionId;
+        use zerocopy::network_endian::I32;
+
+        use crate::servers::udp::handlers::raw_transaction_id;
+
+        #[test]
+        fn the_transaction_id_of_an_unparsable_request_should_be_taken_from_its_bytes() {
+            let mut payload = vec![0u8; 16];
+            payload[8..12].copy_from_slice(&99i32.to_be_bytes()); // unknown action
+            payload[12..16].copy_from_slice(&123i32.to_be_bytes());
+
+            assert_eq!(raw_transaction_id(&payload), TransactionId(I32::new(123)));
+        }
+
+        #[test]
+        fn the_transaction_id_of_an_unparsable_request_should_be_zero_when_the_request_is_too_short() {
+            assert_eq!(raw_transaction_id(&[0u8; 15]), TransactionId(I32::new(0)));
+        }
+    }
+
     mod connect_request {
 
         use std::future;
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
         use std::sync::Arc;
 
         use aquatic_udp_protocol::{ConnectRequest, ConnectResponse, Response, TransactionId};
-        use mockall::predicate::eq;
+        use mockall::predicate::{eq, function};
 
         use super::{sample_ipv4_socket_address, sample_ipv6_remote_addr, tracker_configuration};
         use crate::core::{self, statistics};
@@ -494,6 +914,13 @@ mod tests {
                 .with(eq(statistics::Event::Udp4Connect))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(function(|event| {
+                    matches!(event, statistics::Event::Udp4ConnectionIdIssued { .. })
+                }))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
 
             let client_socket_address = sample_ipv4_socket_address();
@@ -511,6 +938,39 @@ mod tests {
                 .unwrap();
         }
 
+        #[tokio::test]
+        async fn it_should_send_the_upd4_connect_event_when_a_client_tries_to_connect_using_an_ipv4_mapped_ip6_socket_address() {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::Udp4Connect))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(function(|event| {
+                    matches!(event, statistics::Event::Udp4ConnectionIdIssued { .. })
+                }))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            let stats_event_sender = Box::new(stats_event_sender_mock);
+
+            // As received from an IPv4 client on a dual-stack socket
+            let client_socket_address = SocketAddr::new(IpAddr::V6(Ipv4Addr::new(126, 0, 0, 1).to_ipv6_mapped()), 8080);
+
+            let torrent_tracker = Arc::new(
+                core::Tracker::new(
+                    &tracker_configuration().core,
+                    Some(stats_event_sender),
+                    statistics::Repo::new(),
+                )
+                .unwrap(),
+            );
+            handle_connect(client_socket_address, &sample_connect_request(), &torrent_tracker)
+                .await
+                .unwrap();
+        }
+
         #[tokio::test]
         async fn it_should_send_the_upd6_connect_event_when_a_client_tries_to_connect_using_a_ip6_socket_address() {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
@@ -519,6 +979,13 @@ mod tests {
                 .with(eq(statistics::Event::Udp6Connect))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(function(|event| {
+                    matches!(event, statistics::Event::Udp6ConnectionIdIssued { .. })
+                }))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
 
             let torrent_tracker = Arc::new(
@@ -603,6 +1070,11 @@ mod tests {
                 self
             }
 
+            pub fn with_bytes_left(mut self, bytes_left: i64) -> Self {
+                self.request.bytes_left = NumberOfBytes(bytes_left.into());
+                self
+            }
+
             pub fn into(self) -> AnnounceRequest {
                 self.request
             }
@@ -618,15 +1090,16 @@ mod tests {
                 AnnounceInterval, AnnounceResponse, InfoHash as AquaticInfoHash, Ipv4AddrBytes, Ipv6AddrBytes, NumberOfPeers,
                 PeerId as AquaticPeerId, Response, ResponsePeer,
             };
-            use mockall::predicate::eq;
+            use mockall::predicate::{eq, function};
 
-            use crate::core::{self, statistics};
+            use crate::core::{self, statistics, AnnounceMode};
             use crate::servers::udp::connection_cookie::{into_connection_id, make};
             use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
             use crate::servers::udp::handlers::tests::{
                 public_tracker, sample_ipv4_socket_address, tracker_configuration, TorrentPeerBuilder,
             };
-            use crate::servers::udp::handlers::{handle_announce, AnnounceResponseFixedData};
+            use crate::servers::udp::handlers::{handle_announce, handle_packet, AnnounceResponseFixedData};
+            use crate::servers::udp::RawRequest;
 
             #[tokio::test]
             async fn an_announced_peer_should_be_added_to_the_tracker() {
@@ -659,6 +1132,69 @@ mod tests {
                 assert_eq!(peers[0], Arc::new(expected_peer));
             }
 
+            #[tokio::test]
+            async fn a_peer_announcing_from_an_ipv4_mapped_ipv6_address_should_be_added_with_its_ipv4_address() {
+                let tracker = public_tracker();
+
+                let client_ip = Ipv4Addr::new(126, 0, 0, 1);
+                let client_port = 8080;
+                let info_hash = AquaticInfoHash([0u8; 20]);
+                let peer_id = AquaticPeerId([255u8; 20]);
+
+                // As received on a dual-stack socket
+                let remote_addr = SocketAddr::new(IpAddr::V6(client_ip.to_ipv6_mapped()), client_port);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .with_info_hash(info_hash)
+                    .with_peer_id(peer_id)
+                    .with_port(client_port)
+                    .into();
+
+                let response = handle_announce(remote_addr, &request, &tracker).await.unwrap();
+
+                let peers = tracker.get_torrent_peers(&info_hash.0.into());
+
+                assert_eq!(peers[0].peer_addr, SocketAddr::new(IpAddr::V4(client_ip), client_port));
+                assert!(matches!(response, Response::AnnounceIpv4(_)));
+            }
+
+            #[tokio::test]
+            async fn an_announce_with_the_paused_event_should_be_handled_as_an_announce_from_a_partial_seed() {
+                let tracker = public_tracker();
+
+                let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+                let info_hash = AquaticInfoHash([0u8; 20]);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&make(&remote_addr)))
+                    .with_info_hash(info_hash)
+                    .with_bytes_left(1000)
+                    .into();
+
+                let mut payload = Vec::new();
+                request.write_bytes(&mut payload).unwrap();
+                // BEP 21 `paused` event
+                payload[80..84].copy_from_slice(&4i32.to_be_bytes());
+
+                let response = handle_packet(
+                    RawRequest {
+                        payload,
+                        from: remote_addr,
+                    },
+                    &tracker,
+                    sample_ipv4_socket_address(),
+                    AnnounceMode::Synchronous,
+                )
+                .await;
+
+                assert!(matches!(response, Response::AnnounceIpv4(_)));
+
+                let peers = tracker.get_torrent_peers(&info_hash.0.into());
+
+                assert!(peers[0].is_partial_seed());
+            }
+
             #[tokio::test]
             async fn the_announced_peer_should_not_be_included_in_the_response() {
                 let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
@@ -767,6 +1303,13 @@ mod tests {
                     .with(eq(statistics::Event::Udp4Announce))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(function(|event| {
+                        matches!(event, statistics::Event::UdpConnectionIdUsed { .. })
+                    }))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
 
                 let tracker = Arc::new(
@@ -787,6 +1330,82 @@ mod tests {
                 .unwrap();
             }
 
+            #[tokio::test]
+            async fn should_send_the_udp_announce_with_unknown_event_event_when_the_event_is_not_a_bep_15_event() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::UdpAnnounceWithUnknownEvent))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(function(|event| {
+                        matches!(
+                            event,
+                            statistics::Event::Udp4Announce | statistics::Event::UdpConnectionIdUsed { .. }
+                        )
+                    }))
+                    .times(2)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let tracker = Arc::new(
+                    core::Tracker::new(
+                        &tracker_configuration().core,
+                        Some(stats_event_sender),
+                        statistics::Repo::new(),
+                    )
+                    .unwrap(),
+                );
+
+                let mut payload = Vec::new();
+                AnnounceRequestBuilder::default().into().write_bytes(&mut payload).unwrap();
+                // Not a BEP 15 event
+                payload[80..84].copy_from_slice(&42i32.to_be_bytes());
+
+                let response = handle_packet(
+                    RawRequest {
+                        payload,
+                        from: sample_ipv4_socket_address(),
+                    },
+                    &tracker,
+                    sample_ipv4_socket_address(),
+                    AnnounceMode::Synchronous,
+                )
+                .await;
+
+                assert!(matches!(response, Response::AnnounceIpv4(_)));
+            }
+
+            #[tokio::test]
+            async fn should_send_the_udp_connection_id_rejected_event_when_the_connection_id_is_not_valid() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::UdpConnectionIdRejected { connection_id: 0 }))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let tracker = Arc::new(
+                    core::Tracker::new(
+                        &tracker_configuration().core,
+                        Some(stats_event_sender),
+                        statistics::Repo::new(),
+                    )
+                    .unwrap(),
+                );
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(into_connection_id(&[0u8; 8]))
+                    .into();
+
+                assert!(handle_announce(sample_ipv4_socket_address(), &request, &tracker)
+                    .await
+                    .is_err());
+            }
+
             mod from_a_loopback_ip {
                 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
                 use std::sync::Arc;
@@ -802,7 +1421,7 @@ mod tests {
                 async fn the_peer_ip_should_be_changed_to_the_external_ip_in_the_tracker_configuration_if_defined() {
                     let tracker = public_tracker();
 
-                    let client_ip = Ipv4Addr::new(127, 0, 0, 1);
+                    let client_ip = Ipv4Addr::LOCALHOST;
                     let client_port = 8080;
                     let info_hash = AquaticInfoHash([0u8; 20]);
                     let peer_id = AquaticPeerId([255u8; 20]);
@@ -843,7 +1462,7 @@ mod tests {
                 AnnounceInterval, AnnounceResponse, InfoHash as AquaticInfoHash, Ipv4AddrBytes, Ipv6AddrBytes, NumberOfPeers,
                 PeerId as AquaticPeerId, Response, ResponsePeer,
             };
-            use mockall::predicate::eq;
+            use mockall::predicate::{eq, function};
 
             use crate::core::{self, statistics};
             use crate::servers::udp::connection_cookie::{into_connection_id, make};
@@ -999,6 +1618,13 @@ mod tests {
                     .with(eq(statistics::Event::Udp6Announce))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(function(|event| {
+                        matches!(event, statistics::Event::UdpConnectionIdUsed { .. })
+                    }))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
 
                 let tracker = Arc::new(
@@ -1039,8 +1665,8 @@ mod tests {
                     let tracker =
                         Arc::new(core::Tracker::new(&configuration.core, Some(stats_event_sender), stats_repository).unwrap());
 
-                    let loopback_ipv4 = Ipv4Addr::new(127, 0, 0, 1);
-                    let loopback_ipv6 = Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1);
+                    let loopback_ipv4 = Ipv4Addr::LOCALHOST;
+                    let loopback_ipv6 = Ipv6Addr::LOCALHOST;
 
                     let client_ip_v4 = loopback_ipv4;
                     let client_ip_v6 = loopback_ipv6;
@@ -1168,10 +1794,16 @@ mod tests {
         }
 
         mod with_a_public_tracker {
-            use aquatic_udp_protocol::{NumberOfDownloads, NumberOfPeers, TorrentScrapeStatistics};
+            use aquatic_udp_protocol::{
+                InfoHash, NumberOfDownloads, NumberOfPeers, ScrapeRequest, TorrentScrapeStatistics, TransactionId,
+            };
 
-            use crate::servers::udp::handlers::tests::public_tracker;
-            use crate::servers::udp::handlers::tests::scrape_request::{add_a_sample_seeder_and_scrape, match_scrape_response};
+            use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::handlers::handle_scrape;
+            use crate::servers::udp::handlers::tests::scrape_request::{
+                add_a_sample_seeder_and_scrape, add_a_seeder, match_scrape_response, zeroed_torrent_statistics,
+            };
+            use crate::servers::udp::handlers::tests::{public_tracker, sample_ipv4_remote_addr};
 
             #[tokio::test]
             async fn should_return_torrent_statistics_when_the_tracker_has_the_requested_torrent() {
@@ -1187,6 +1819,41 @@ mod tests {
 
                 assert_eq!(torrent_stats.unwrap().torrent_stats, expected_torrent_stats);
             }
+
+            #[tokio::test]
+            async fn should_return_the_torrent_statistics_in_the_request_order_including_the_duplicate_info_hashes() {
+                let tracker = public_tracker();
+                let remote_addr = sample_ipv4_remote_addr();
+
+                let seeded = InfoHash([1u8; 20]);
+                let unknown = InfoHash([2u8; 20]);
+
+                add_a_seeder(tracker.clone(), &remote_addr, &seeded).await;
+
+                let request = ScrapeRequest {
+                    connection_id: into_connection_id(&make(&remote_addr)),
+                    transaction_id: TransactionId::new(0i32),
+                    info_hashes: vec![unknown, seeded, unknown, seeded],
+                };
+
+                let torrent_stats = match_scrape_response(handle_scrape(remote_addr, &request, &tracker).await.unwrap()).unwrap();
+
+                let seeded_stats = TorrentScrapeStatistics {
+                    seeders: NumberOfPeers(1.into()),
+                    completed: NumberOfDownloads(0.into()),
+                    leechers: NumberOfPeers(0.into()),
+                };
+
+                assert_eq!(
+                    torrent_stats.torrent_stats,
+                    vec![
+                        zeroed_torrent_statistics(),
+                        seeded_stats,
+                        zeroed_torrent_statistics(),
+                        seeded_stats
+                    ]
+                );
+            }
         }
 
         mod with_a_private_tracker {
@@ -1280,6 +1947,124 @@ mod tests {
             }
         }
 
+        mod with_the_negative_cache_enabled {
+            use std::sync::Arc;
+
+            use aquatic_udp_protocol::{InfoHash, NumberOfDownloads, NumberOfPeers, TorrentScrapeStatistics};
+            use torrust_tracker_configuration::NegativeCache;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::negative_cache::Rejection;
+            use crate::core::Tracker;
+            use crate::servers::udp::handlers::handle_scrape;
+            use crate::servers::udp::handlers::tests::scrape_request::{
+                add_a_seeder, build_scrape_request, match_scrape_response,
+            };
+            use crate::servers::udp::handlers::tests::{initialized_tracker, sample_ipv4_remote_addr};
+
+            fn whitelisted_tracker_with_negative_cache() -> Arc<Tracker> {
+                let mut configuration = configuration::ephemeral_listed();
+                configuration.core.negative_cache = Some(NegativeCache::default());
+                initialized_tracker(&configuration)
+            }
+
+            #[tokio::test]
+            async fn should_cache_the_torrents_that_are_not_whitelisted() {
+                let tracker = whitelisted_tracker_with_negative_cache();
+
+                let remote_addr = sample_ipv4_remote_addr();
+                let info_hash = InfoHash([0u8; 20]);
+
+                handle_scrape(remote_addr, &build_scrape_request(&remote_addr, &info_hash), &tracker)
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    tracker.get_cached_rejection(&info_hash.0.into()),
+                    Some(Rejection::NotAuthorized)
+                );
+            }
+
+            #[tokio::test]
+            async fn should_cache_the_torrents_that_do_not_exist() {
+                let tracker = whitelisted_tracker_with_negative_cache();
+
+                let remote_addr = sample_ipv4_remote_addr();
+                let info_hash = InfoHash([0u8; 20]);
+
+                tracker.add_torrent_to_memory_whitelist(&info_hash.0.into()).await;
+
+                handle_scrape(remote_addr, &build_scrape_request(&remote_addr, &info_hash), &tracker)
+                    .await
+                    .unwrap();
+
+                assert_eq!(tracker.get_cached_rejection(&info_hash.0.into()), Some(Rejection::NotFound));
+            }
+
+            #[tokio::test]
+            async fn should_forget_the_rejection_when_the_torrent_is_whitelisted_and_announced() {
+                let tracker = whitelisted_tracker_with_negative_cache();
+
+                let remote_addr = sample_ipv4_remote_addr();
+                let info_hash = InfoHash([0u8; 20]);
+                let request = build_scrape_request(&remote_addr, &info_hash);
+
+                handle_scrape(remote_addr, &request, &tracker).await.unwrap();
+
+                tracker.add_torrent_to_memory_whitelist(&info_hash.0.into()).await;
+                add_a_seeder(tracker.clone(), &remote_addr, &info_hash).await;
+
+                let torrent_stats = match_scrape_response(handle_scrape(remote_addr, &request, &tracker).await.unwrap()).unwrap();
+
+                let expected_torrent_stats = vec![TorrentScrapeStatistics {
+                    seeders: NumberOfPeers(1.into()),
+                    completed: NumberOfDownloads(0.into()),
+                    leechers: NumberOfPeers(0.into()),
+                }];
+
+                assert_eq!(torrent_stats.torrent_stats, expected_torrent_stats);
+                assert_eq!(tracker.get_cached_rejection(&info_hash.0.into()), None);
+            }
+        }
+
+        mod when_the_request_exceeds_the_max_number_of_torrents {
+            use aquatic_udp_protocol::{InfoHash, ScrapeRequest, TransactionId};
+
+            use crate::servers::udp::connection_cookie::{into_connection_id, make};
+            use crate::servers::udp::error::Error;
+            use crate::servers::udp::handlers::handle_scrape;
+            use crate::servers::udp::handlers::tests::{public_tracker, sample_ipv4_remote_addr};
+
+            #[tokio::test]
+            async fn should_return_an_error_instead_of_truncating_the_list_of_torrents() {
+                let tracker = public_tracker();
+
+                let remote_addr = sample_ipv4_remote_addr();
+                let max_scrape_torrents = tracker.get_max_scrape_torrents();
+
+                let info_hashes = (0..=max_scrape_torrents)
+                    .map(|i| {
+                        let mut bytes = [0u8; 20];
+                        bytes[..2].copy_from_slice(&i.to_be_bytes());
+                        InfoHash(bytes)
+                    })
+                    .collect();
+
+                let request = ScrapeRequest {
+                    connection_id: into_connection_id(&make(&remote_addr)),
+                    transaction_id: TransactionId(0i32.into()),
+                    info_hashes,
+                };
+
+                let error = handle_scrape(remote_addr, &request, &tracker).await.unwrap_err();
+
+                assert!(matches!(
+                    error,
+                    Error::TooManyInfoHashes { requested, max, .. } if requested == usize::from(max_scrape_torrents) + 1 && max == max_scrape_torrents
+                ));
+            }
+        }
+
         fn sample_scrape_request(remote_addr: &SocketAddr) -> ScrapeRequest {
             let info_hash = InfoHash([0u8; 20]);
             let info_hashes = vec![info_hash];
@@ -1295,6 +2080,7 @@ mod tests {
             use std::future;
             use std::sync::Arc;
 
+            use aquatic_udp_protocol::InfoHash;
             use mockall::predicate::eq;
 
             use super::sample_scrape_request;
@@ -1326,12 +2112,37 @@ mod tests {
                     .await
                     .unwrap();
             }
+
+            #[tokio::test]
+            async fn should_send_the_udp4_scrape_limit_exceeded_event_when_the_request_exceeds_the_max_number_of_torrents() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::Udp4ScrapeLimitExceeded))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let remote_addr = sample_ipv4_remote_addr();
+
+                let mut configuration = tracker_configuration();
+                configuration.core.max_scrape_torrents = 1;
+
+                let tracker =
+                    Arc::new(core::Tracker::new(&configuration.core, Some(stats_event_sender), statistics::Repo::new()).unwrap());
+
+                let mut request = sample_scrape_request(&remote_addr);
+                request.info_hashes.push(InfoHash([1u8; 20]));
+
+                assert!(handle_scrape(remote_addr, &request, &tracker).await.is_err());
+            }
         }
 
         mod using_ipv6 {
             use std::future;
             use std::sync::Arc;
 
+            use aquatic_udp_protocol::InfoHash;
             use mockall::predicate::eq;
 
             use super::sample_scrape_request;
@@ -1363,6 +2174,107 @@ mod tests {
                     .await
                     .unwrap();
             }
+
+            #[tokio::test]
+            async fn should_send_the_udp6_scrape_limit_exceeded_event_when_the_request_exceeds_the_max_number_of_torrents() {
+                let mut stats_event_sender_mock = statistics::MockEventSender::new();
+                stats_event_sender_mock
+                    .expect_send_event()
+                    .with(eq(statistics::Event::Udp6ScrapeLimitExceeded))
+                    .times(1)
+                    .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+                let stats_event_sender = Box::new(stats_event_sender_mock);
+
+                let remote_addr = sample_ipv6_remote_addr();
+
+                let mut configuration = tracker_configuration();
+                configuration.core.max_scrape_torrents = 1;
+
+                let tracker =
+                    Arc::new(core::Tracker::new(&configuration.core, Some(stats_event_sender), statistics::Repo::new()).unwrap());
+
+                let mut request = sample_scrape_request(&remote_addr);
+                request.info_hashes.push(InfoHash([1u8; 20]));
+
+                assert!(handle_scrape(remote_addr, &request, &tracker).await.is_err());
+            }
+        }
+    }
+
+    mod error_responses {
+        use std::collections::BTreeMap;
+        use std::panic::Location;
+
+        use aquatic_udp_protocol::{Response, TransactionId};
+        use torrust_tracker_configuration::FailureReasons;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core;
+        use crate::servers::udp::error::Error;
+        use crate::servers::udp::handlers::handle_error;
+        use crate::servers::udp::handlers::tests::{initialized_tracker, public_tracker};
+
+        fn message_of(response: &Response) -> String {
+            match response {
+                Response::Error(error_response) => error_response.message.to_string(),
+                _ => panic!("expected an error response"),
+            }
+        }
+
+        #[tokio::test]
+        async fn should_start_with_the_code_of_the_domain_tracker_error() {
+            let error = Error::TrackerError {
+                source: core::error::Error::TorrentNotWhitelisted {
+                    info_hash: "9c38422213e30bff212b30c360d26f9a02136422".parse::<InfoHash>().unwrap(), // # DevSkim: ignore DS173237
+                    location: Location::caller(),
+                },
+            };
+
+            let message = message_of(&handle_error(&error, TransactionId(0i32.into()), &public_tracker()));
+
+            assert!(message.starts_with("E03: tracker server error: The torrent: "));
+        }
+
+        #[tokio::test]
+        async fn should_use_the_failure_reason_template_for_the_error_code_when_there_is_one() {
+            let mut configuration = configuration::ephemeral();
+            configuration.core.failure_reasons = Some(FailureReasons {
+                language: "en".to_string(),
+                templates: BTreeMap::from([(
+                    "en".to_string(),
+                    BTreeMap::from([(
+                        "E03".to_string(),
+                        "torrent not registered, visit https://example.com/rules".to_string(),
+                    )]),
+                )]),
+            });
+
+            let error = Error::TrackerError {
+                source: core::error::Error::TorrentNotWhitelisted {
+                    info_hash: "9c38422213e30bff212b30c360d26f9a02136422".parse::<InfoHash>().unwrap(), // # DevSkim: ignore DS173237
+                    location: Location::caller(),
+                },
+            };
+
+            let message = message_of(&handle_error(
+                &error,
+                TransactionId(0i32.into()),
+                &initialized_tracker(&configuration),
+            ));
+
+            assert_eq!(message, "E03: torrent not registered, visit https://example.com/rules");
+        }
+
+        #[tokio::test]
+        async fn should_start_with_the_code_of_the_udp_server_error() {
+            let error = Error::InvalidConnectionId {
+                location: Location::caller(),
+            };
+
+            let message = message_of(&handle_error(&error, TransactionId(0i32.into()), &public_tracker()));
+
+            assert_eq!(message, "E21: connection id could not be verified");
         }
     }
 }
@@ -46,7 +46,7 @@ pub fn log_request(request: &Request, request_id: &RequestId, server_socket_addr
                 request_id = %request_id,
                 connection_id = %connection_id_str);
         }
-    };
+    }
 }
 
 fn map_action_name(udp_request: &Request) -> String {
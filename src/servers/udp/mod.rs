@@ -61,6 +61,11 @@
 //! UDP packet -> Aquatic Struct Request -> [Torrust Struct Request] ->  Tracker -> Aquatic Struct Response -> UDP packet
 //! ```
 //!
+//! Each request is handled in its own task, so a client can have several
+//! outstanding transactions on the same socket. They are answered
+//! independently, and possibly out of order, with the `transaction_id` of
+//! each request.
+//!
 //! ### Connect
 //!
 //! `Connect` requests are used to get a connection ID which must be provided on
@@ -226,6 +231,14 @@
 //! 92     | [`i32`](std::i32) | `num_want`       | The maximum number of peers the peer wants in the response.  | `0x00_00_00_C8`                                                 | `200`
 //! 96     | [`i16`](std::i16) | `port`           | The port the peer is listening on.                           | `0x44_8C`                                                       | `17548`
 //!
+//! **Event**
+//!
+//! Unknown `event` values are not rejected. They are handled as `0` (none),
+//! that is, as a regular announce, and they are counted in the
+//! `udp_announces_with_unknown_event` metric. The `paused` event (`4`) sent
+//! by partial seeds ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html))
+//! is handled as a regular announce too, but it's not counted as unknown.
+//!
 //! **Peer IP address**
 //!
 //! The peer IP address is always ignored by the tracker. It uses the sender's
@@ -398,7 +411,9 @@
 //! dynamically generated data with the list of peers in the swarm. The list may
 //! include `IPv4` or `IPv6` peers, depending on the address family of the
 //! underlying UDP packet. I.e. packets from a v4 address use the v4 format,
-//! those from a v6 address use the v6 format.
+//! those from a v6 address use the v6 format. Packets from IPv4-mapped IPv6
+//! addresses, received on a dual-stack socket (`ipv6_only = false`), are
+//! handled as packets from the v4 address.
 //!
 //! UDP packet bytes (`IPv4` peer list):
 //!
@@ -476,7 +491,9 @@
 //!
 //! > **NOTICE**: up to about 74 torrents can be scraped at once. A full scrape
 //! > can't be done with this protocol. This is a limitation of the UDP protocol.
-//! > Defined with a hardcoded const [`MAX_SCRAPE_TORRENTS`](crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS).
+//! > The limit can be changed with the `max_scrape_torrents` option in the
+//! > [`core`](torrust_tracker_configuration::Core::max_scrape_torrents)
+//! > configuration section. Requests exceeding the limit get an error response.
 //! > Refer to [issue 262](https://github.com/torrust/torrust-tracker/issues/262)
 //! > for more information about this limitation.
 //!
@@ -600,10 +617,24 @@
 //! `transaction_id` | [`TransactionId`](aquatic_udp_protocol::common::TransactionId)                                  | `-1560718264`
 //! `torrent_stats`  | Vector of [`TorrentScrapeStatistics`](aquatic_udp_protocol::response::TorrentScrapeStatistics)  | `[]`
 //!
+//! There is one `torrent_stats` entry for each requested infohash, in the
+//! request order, so clients can match them by position. Duplicate infohashes
+//! get one entry for each occurrence, but the torrent is only looked up once.
+//! The duplicates are counted in the `udp_scrape_duplicate_info_hashes`
+//! metric.
+//!
 //! **Scrape specification**
 //!
 //! Original specification in [BEP 15. UDP Tracker Protocol for `BitTorrent`](https://www.bittorrent.org/beps/bep_0015.html).
 //!
+//! **Negative cache**
+//!
+//! When the core tracker option `negative_cache` is enabled, the infohashes
+//! that are not whitelisted or do not exist are remembered for a while. The
+//! following `announce` (not whitelisted) and `scrape` requests for them are
+//! answered without looking them up again, and they are counted in the
+//! `udp_negative_cache_hits` metric.
+//!
 //! ## Errors
 //!
 //! ### Error Response
@@ -616,6 +647,16 @@
 //! 4      | [`i32`](std::i32) | `transaction_id` | Must match the `transaction_id` sent from the client. | `0xCB_05_5E_07`             | `-888840697`
 //! 8      | N Bytes           | `error_string`   | Error description.                                    |                             |
 //!
+//! When the request can't be parsed, the `transaction_id` is taken from the
+//! bytes at offset `12` of the request, which is where all the requests have
+//! it. It's `0` when the request is shorter.
+//!
+//! The `error_string` starts with a short machine-readable code followed by
+//! the human-readable description, for example `E07: tracker server error: The
+//! tracker is in maintenance mode, retry in 10 minutes, ...`. Refer to the
+//! [`error`] module for the list of codes. Operators can replace the
+//! description with their own [templates](crate::core::failure_reasons).
+//!
 //! ## Extensions
 //!
 //! Extensions described in [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html)
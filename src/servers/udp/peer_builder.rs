@@ -1,6 +1,7 @@
 //! Logic to extract the peer info from the announce request.
 use std::net::{IpAddr, SocketAddr};
 
+use aquatic_udp_protocol::NumberOfBytes;
 use torrust_tracker_clock::clock::Time;
 use torrust_tracker_primitives::peer;
 
@@ -12,8 +13,13 @@ use crate::CurrentClock;
 /// # Arguments
 ///
 /// * `peer_ip` - The real IP address of the peer, not the one in the announce request.
+/// * `partial_seed` - Whether the request came with the `paused` event. See [BEP 21](https://www.bittorrent.org/beps/bep_0021.html).
 #[must_use]
-pub fn from_request(announce_request: &aquatic_udp_protocol::AnnounceRequest, peer_ip: &IpAddr) -> peer::Peer {
+pub fn from_request(
+    announce_request: &aquatic_udp_protocol::AnnounceRequest,
+    peer_ip: &IpAddr,
+    partial_seed: bool,
+) -> peer::Peer {
     peer::Peer {
         peer_id: announce_request.peer_id,
         peer_addr: SocketAddr::new(*peer_ip, announce_request.port.0.into()),
@@ -22,5 +28,9 @@ pub fn from_request(announce_request: &aquatic_udp_protocol::AnnounceRequest, pe
         downloaded: announce_request.bytes_downloaded,
         left: announce_request.bytes_left,
         event: announce_request.event.into(),
+        partial_seed,
+        // The UDP protocol does not have the extended client stats.
+        corrupt: NumberOfBytes::new(0),
+        redundant: NumberOfBytes::new(0),
     }
 }
@@ -2,30 +2,60 @@ use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::ops::Deref;
 
+use derive_more::Constructor;
+use socket2::{Domain, Protocol, Socket, Type};
 use url::Url;
 
+use crate::servers::bind_device::set_bind_device;
 use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
 
+/// The options of the UDP socket set by the [`BoundSocket`].
+#[derive(Constructor, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SocketOptions {
+    /// The `IPV6_V6ONLY` socket option for IPv6 sockets. With `Some(false)` a
+    /// socket bound to `[::]` also receives datagrams from IPv4 peers, as
+    /// IPv4-mapped IPv6 addresses. With `None` the operating system default
+    /// is kept.
+    pub ipv6_only: Option<bool>,
+    /// The DSCP value the outgoing datagrams are marked with. It's written in
+    /// the upper six bits of the `IP_TOS` socket option.
+    pub dscp: Option<u8>,
+    /// The network interface the socket is bound to with the
+    /// `SO_BINDTODEVICE` socket option.
+    pub bind_device: Option<String>,
+}
+
 /// Wrapper for Tokio [`UdpSocket`][`tokio::net::UdpSocket`] that is bound to a particular socket.
 pub struct BoundSocket {
     socket: tokio::net::UdpSocket,
 }
 
 impl BoundSocket {
+    /// It binds the socket to the address setting the [`SocketOptions`].
+    ///
     /// # Errors
     ///
-    /// Will return an error if the socket can't be bound the the provided address.
-    pub async fn new(addr: SocketAddr) -> Result<Self, Box<std::io::Error>> {
+    /// Will return an error if the socket can't be bound the the provided
+    /// address, or if the socket options can't be set.
+    pub async fn new(addr: SocketAddr, options: SocketOptions) -> Result<Self, Box<std::io::Error>> {
         let bind_addr = format!("udp://{addr}");
-        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, bind_addr, "UdpSocket::new (binding)");
+        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, bind_addr, ?options, "UdpSocket::new (binding)");
 
-        let socket = tokio::net::UdpSocket::bind(addr).await;
+        let socket = if (options.ipv6_only.is_some() && addr.is_ipv6()) || options.bind_device.is_some() {
+            bind_with_options(addr, &options)
+        } else {
+            tokio::net::UdpSocket::bind(addr).await
+        };
 
         let socket = match socket {
             Ok(socket) => socket,
             Err(e) => Err(e)?,
         };
 
+        if let Some(dscp) = options.dscp {
+            socket.set_tos(u32::from(dscp) << 2)?;
+        }
+
         let local_addr = format!("udp://{}", socket.local_addr()?);
         tracing::debug!(target: UDP_TRACKER_LOG_TARGET, local_addr, "UdpSocket::new (bound)");
 
@@ -50,6 +80,25 @@ impl BoundSocket {
     }
 }
 
+/// It binds a UDP socket setting the `IPV6_V6ONLY` and `SO_BINDTODEVICE`
+/// options, which has to be done before binding.
+fn bind_with_options(addr: SocketAddr, options: &SocketOptions) -> std::io::Result<tokio::net::UdpSocket> {
+    let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+
+    if let Some(ipv6_only) = options.ipv6_only.filter(|_| addr.is_ipv6()) {
+        socket.set_only_v6(ipv6_only)?;
+    }
+
+    if let Some(bind_device) = &options.bind_device {
+        set_bind_device(&socket, bind_device)?;
+    }
+
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+
+    tokio::net::UdpSocket::from_std(socket.into())
+}
+
 impl Deref for BoundSocket {
     type Target = tokio::net::UdpSocket;
 
@@ -68,3 +117,109 @@ impl Debug for BoundSocket {
         f.debug_struct("UdpSocket").field("addr", &local_addr).finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    use super::{BoundSocket, SocketOptions};
+
+    #[tokio::test]
+    async fn a_dual_stack_socket_should_receive_datagrams_from_ipv4_peers_as_ipv4_mapped_addresses() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+            SocketOptions::new(Some(false), None, None),
+        )
+        .await
+        .unwrap();
+
+        let client = tokio::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .await
+            .unwrap();
+
+        client
+            .send_to(b"ping", SocketAddr::from((Ipv4Addr::LOCALHOST, socket.address().port())))
+            .await
+            .unwrap();
+
+        let mut buffer = [0u8; 4];
+        let (_, from) = socket.recv_from(&mut buffer).await.unwrap();
+
+        assert_eq!(from.ip(), Ipv4Addr::LOCALHOST.to_ipv6_mapped());
+    }
+
+    #[tokio::test]
+    async fn an_ipv6_only_socket_should_not_take_the_port_for_ipv4() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+            SocketOptions::new(Some(true), None, None),
+        )
+        .await
+        .unwrap();
+
+        let ipv4_socket = BoundSocket::new(
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, socket.address().port())),
+            SocketOptions::default(),
+        )
+        .await;
+
+        assert!(ipv4_socket.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_mark_the_outgoing_datagrams_with_the_dscp_value() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+            SocketOptions::new(None, Some(46), None),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(socket.tos().unwrap(), 184);
+    }
+
+    #[tokio::test]
+    async fn it_should_mark_the_outgoing_datagrams_to_ipv4_peers_on_a_dual_stack_socket() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+            SocketOptions::new(Some(false), Some(46), None),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(socket.tos().unwrap(), 184);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn it_should_bind_the_socket_to_a_network_interface() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+            SocketOptions::new(None, None, Some("lo".to_string())),
+        )
+        .await
+        .unwrap();
+
+        let client = tokio::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .await
+            .unwrap();
+
+        client.send_to(b"ping", socket.address()).await.unwrap();
+
+        let mut buffer = [0u8; 4];
+        let (_, from) = socket.recv_from(&mut buffer).await.unwrap();
+
+        assert_eq!(from, client.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_should_fail_binding_the_socket_to_an_unknown_network_interface() {
+        let socket = BoundSocket::new(
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+            SocketOptions::new(None, None, Some("unknown0".to_string())),
+        )
+        .await;
+
+        assert!(socket.is_err());
+    }
+}
@@ -10,15 +10,16 @@ use tracing::instrument;
 
 use super::request_buffer::ActiveRequests;
 use crate::bootstrap::jobs::Started;
-use crate::core::Tracker;
+use crate::core::{AnnounceMode, Tracker};
 use crate::servers::logging::STARTED_ON;
+use crate::servers::proxy_protocol::V2_LOCAL_HEADER;
 use crate::servers::registar::ServiceHealthCheckJob;
 use crate::servers::signals::{shutdown_signal_with_message, Halted};
-use crate::servers::udp::server::bound_socket::BoundSocket;
+use crate::servers::udp::server::bound_socket::{BoundSocket, SocketOptions};
 use crate::servers::udp::server::processor::Processor;
 use crate::servers::udp::server::receiver::Receiver;
 use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
-use crate::shared::bit_torrent::tracker::udp::client::check;
+use crate::shared::bit_torrent::tracker::udp::client::{check, check_with_prefix};
 
 /// A UDP server instance launcher.
 #[derive(Constructor)]
@@ -35,12 +36,15 @@ impl Launcher {
     pub async fn run_with_graceful_shutdown(
         tracker: Arc<Tracker>,
         bind_to: SocketAddr,
+        proxy_protocol: bool,
+        socket_options: SocketOptions,
+        announce_mode: AnnounceMode,
         tx_start: oneshot::Sender<Started>,
         rx_halt: oneshot::Receiver<Halted>,
     ) {
         tracing::info!(target: UDP_TRACKER_LOG_TARGET, "Starting on: {bind_to}");
 
-        let socket = tokio::time::timeout(Duration::from_millis(5000), BoundSocket::new(bind_to))
+        let socket = tokio::time::timeout(Duration::from_secs(5), BoundSocket::new(bind_to, socket_options))
             .await
             .expect("it should bind to the socket within five seconds");
 
@@ -65,7 +69,7 @@ impl Launcher {
             let local_addr = local_udp_url.clone();
             tokio::task::spawn(async move {
                 tracing::debug!(target: UDP_TRACKER_LOG_TARGET, local_addr, "Udp::run_with_graceful_shutdown::task (listening...)");
-                let () = Self::run_udp_server_main(receiver, tracker.clone()).await;
+                let () = Self::run_udp_server_main(receiver, tracker.clone(), proxy_protocol, announce_mode).await;
             })
         };
 
@@ -102,15 +106,34 @@ impl Launcher {
         ServiceHealthCheckJob::new(binding, info, job)
     }
 
+    /// It checks the health of a UDP tracker expecting a PROXY protocol
+    /// header in every datagram. It sends a header with the `LOCAL` command,
+    /// like load balancers do for their own health checks.
+    #[must_use]
+    #[instrument(skip(binding))]
+    pub fn check_behind_proxy(binding: &SocketAddr) -> ServiceHealthCheckJob {
+        let binding = *binding;
+        let info = format!("checking the udp tracker (behind a PROXY protocol proxy) health check at: {binding}");
+
+        let job = tokio::spawn(async move { check_with_prefix(&binding, &V2_LOCAL_HEADER).await });
+
+        ServiceHealthCheckJob::new(binding, info, job)
+    }
+
     #[instrument(skip(receiver, tracker))]
-    async fn run_udp_server_main(mut receiver: Receiver, tracker: Arc<Tracker>) {
+    async fn run_udp_server_main(
+        mut receiver: Receiver,
+        tracker: Arc<Tracker>,
+        proxy_protocol: bool,
+        announce_mode: AnnounceMode,
+    ) {
         let active_requests = &mut ActiveRequests::default();
 
         let addr = receiver.bound_socket_address();
         let local_addr = format!("udp://{addr}");
 
         loop {
-            let processor = Processor::new(receiver.socket.clone(), tracker.clone());
+            let processor = Processor::new(receiver.socket.clone(), tracker.clone(), proxy_protocol, announce_mode);
 
             if let Some(req) = {
                 tracing::trace!(target: UDP_TRACKER_LOG_TARGET, local_addr, "Udp::run_udp_server (wait for request)");
@@ -59,9 +59,11 @@ mod tests {
 
     use torrust_tracker_test_helpers::configuration::ephemeral_public;
 
+    use super::bound_socket::SocketOptions;
     use super::spawner::Spawner;
     use super::Server;
     use crate::bootstrap::app::initialize_with_configuration;
+    use crate::core::AnnounceMode;
     use crate::servers::registar::Registar;
 
     #[tokio::test]
@@ -70,10 +72,19 @@ mod tests {
         let tracker = initialize_with_configuration(&cfg);
         let udp_trackers = cfg.udp_trackers.clone().expect("missing UDP trackers configuration");
         let config = &udp_trackers[0];
-        let bind_to = config.bind_address;
+        let bind_to = config
+            .bind_address
+            .first()
+            .socket_addr()
+            .expect("it should be bound to a socket address");
         let register = &Registar::default();
 
-        let stopped = Server::new(Spawner::new(bind_to));
+        let stopped = Server::new(Spawner::new(
+            bind_to,
+            config.proxy_protocol,
+            SocketOptions::new(config.ipv6_only, config.dscp, config.bind_device.clone()),
+            AnnounceMode::new(config.async_announce),
+        ));
 
         let started = stopped
             .start(tracker, register.give_form())
@@ -92,10 +103,19 @@ mod tests {
         let cfg = Arc::new(ephemeral_public());
         let tracker = initialize_with_configuration(&cfg);
         let config = &cfg.udp_trackers.as_ref().unwrap().first().unwrap();
-        let bind_to = config.bind_address;
+        let bind_to = config
+            .bind_address
+            .first()
+            .socket_addr()
+            .expect("it should be bound to a socket address");
         let register = &Registar::default();
 
-        let stopped = Server::new(Spawner::new(bind_to));
+        let stopped = Server::new(Spawner::new(
+            bind_to,
+            config.proxy_protocol,
+            SocketOptions::new(config.ipv6_only, config.dscp, config.bind_device.clone()),
+            AnnounceMode::new(config.async_announce),
+        ));
 
         let started = stopped
             .start(tracker, register.give_form())
@@ -6,23 +6,53 @@ use aquatic_udp_protocol::Response;
 use tracing::{instrument, Level};
 
 use super::bound_socket::BoundSocket;
-use crate::core::Tracker;
+use crate::core::{AnnounceMode, Tracker};
+use crate::servers::proxy_protocol;
 use crate::servers::udp::{handlers, RawRequest};
 
 pub struct Processor {
     socket: Arc<BoundSocket>,
     tracker: Arc<Tracker>,
+    proxy_protocol: bool,
+    announce_mode: AnnounceMode,
 }
 
 impl Processor {
-    pub fn new(socket: Arc<BoundSocket>, tracker: Arc<Tracker>) -> Self {
-        Self { socket, tracker }
+    pub fn new(socket: Arc<BoundSocket>, tracker: Arc<Tracker>, proxy_protocol: bool, announce_mode: AnnounceMode) -> Self {
+        Self {
+            socket,
+            tracker,
+            proxy_protocol,
+            announce_mode,
+        }
     }
 
     #[instrument(skip(self, request))]
     pub async fn process_request(self, request: RawRequest) {
         let from = request.from;
-        let response = handlers::handle_packet(request, &self.tracker, self.socket.address()).await;
+
+        // Behind a proxy, the response is sent back to the proxy, but the
+        // request is handled as if it came from the original client.
+        let request = if self.proxy_protocol {
+            if !proxy_protocol::is_trusted_source(from.ip(), self.tracker.get_trusted_proxies()) {
+                tracing::debug!(%from, "discarding datagram from an untrusted PROXY protocol source");
+                return;
+            }
+
+            match proxy_protocol::parse(&request.payload) {
+                Ok(header) => RawRequest {
+                    payload: request.payload[header.length..].to_vec(),
+                    from: header.source.unwrap_or(from),
+                },
+                Err(err) => {
+                    tracing::debug!(%from, %err, "discarding datagram without a valid PROXY protocol header");
+                    return;
+                }
+            }
+        } else {
+            request
+        };
+        let response = handlers::handle_packet(request, &self.tracker, self.socket.address(), self.announce_mode).await;
         self.send_response(from, response).await;
     }
 
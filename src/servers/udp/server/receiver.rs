@@ -8,11 +8,14 @@ use futures::Stream;
 
 use super::bound_socket::BoundSocket;
 use super::RawRequest;
-use crate::shared::bit_torrent::tracker::udp::MAX_PACKET_SIZE;
+
+/// The size of the receive buffer. It's bigger than the biggest UDP datagram,
+/// so the requests are never truncated by the socket.
+const RECEIVE_BUFFER_SIZE: usize = 65_536;
 
 pub struct Receiver {
     pub socket: Arc<BoundSocket>,
-    data: RefCell<[u8; MAX_PACKET_SIZE]>,
+    data: RefCell<Vec<u8>>,
 }
 
 impl Receiver {
@@ -20,7 +23,7 @@ impl Receiver {
     pub fn new(bound_socket: Arc<BoundSocket>) -> Self {
         Receiver {
             socket: bound_socket,
-            data: RefCell::new([0; MAX_PACKET_SIZE]),
+            data: RefCell::new(vec![0; RECEIVE_BUFFER_SIZE]),
         }
     }
 
@@ -33,8 +36,8 @@ impl Stream for Receiver {
     type Item = std::io::Result<RawRequest>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut buf = *self.data.borrow_mut();
-        let mut buf = tokio::io::ReadBuf::new(&mut buf);
+        let mut data = self.data.borrow_mut();
+        let mut buf = tokio::io::ReadBuf::new(&mut data);
 
         let Poll::Ready(ready) = self.socket.poll_recv_from(cx, &mut buf) else {
             return Poll::Pending;
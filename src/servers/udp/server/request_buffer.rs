@@ -135,6 +135,6 @@ impl ActiveRequests {
                     self.rb.try_push(new_task).expect("it should have space for this new task.");
                 }
             }
-        };
+        }
     }
 }
@@ -7,15 +7,19 @@ use derive_more::Constructor;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
+use super::bound_socket::SocketOptions;
 use super::launcher::Launcher;
 use crate::bootstrap::jobs::Started;
-use crate::core::Tracker;
+use crate::core::{AnnounceMode, Tracker};
 use crate::servers::signals::Halted;
 
-#[derive(Constructor, Copy, Clone, Debug, Display)]
+#[derive(Constructor, Clone, Debug, Display)]
 #[display("(with socket): {bind_to}")]
 pub struct Spawner {
     pub bind_to: SocketAddr,
+    pub proxy_protocol: bool,
+    pub socket_options: SocketOptions,
+    pub announce_mode: AnnounceMode,
 }
 
 impl Spawner {
@@ -30,10 +34,19 @@ impl Spawner {
         tx_start: oneshot::Sender<Started>,
         rx_halt: oneshot::Receiver<Halted>,
     ) -> JoinHandle<Spawner> {
-        let spawner = Self::new(self.bind_to);
+        let spawner = self.clone();
 
         tokio::spawn(async move {
-            Launcher::run_with_graceful_shutdown(tracker, spawner.bind_to, tx_start, rx_halt).await;
+            Launcher::run_with_graceful_shutdown(
+                tracker,
+                spawner.bind_to,
+                spawner.proxy_protocol,
+                spawner.socket_options.clone(),
+                spawner.announce_mode,
+                tx_start,
+                rx_halt,
+            )
+            .await;
             spawner
         })
     }
@@ -11,7 +11,7 @@ use super::spawner::Spawner;
 use super::{Server, UdpError};
 use crate::bootstrap::jobs::Started;
 use crate::core::Tracker;
-use crate::servers::registar::{ServiceRegistration, ServiceRegistrationForm};
+use crate::servers::registar::{ServiceKind, ServiceRegistration, ServiceRegistrationForm};
 use crate::servers::signals::Halted;
 use crate::servers::udp::server::launcher::Launcher;
 use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
@@ -73,7 +73,13 @@ impl Server<Stopped> {
 
         let local_addr = rx_start.await.expect("it should be able to start the service").address;
 
-        form.send(ServiceRegistration::new(local_addr, Launcher::check))
+        let check_fn = if self.state.spawner.proxy_protocol {
+            Launcher::check_behind_proxy
+        } else {
+            Launcher::check
+        };
+
+        form.send(ServiceRegistration::new(local_addr, ServiceKind::UdpTracker, false, check_fn))
             .expect("it should be able to send service registration");
 
         let running_udp_server: Server<Running> = Server {
@@ -0,0 +1,309 @@
+//! Logic to run the HTTP services on a Unix domain socket.
+//!
+//! The tracker REST API and the HTTP trackers can be bound to a Unix domain
+//! socket instead of a TCP socket address, for setups where a local reverse
+//! proxy handles all the network exposure:
+//!
+//! ```toml
+//! [http_api]
+//! bind_address = "unix:/var/run/torrust/api.sock"
+//! ```
+//!
+//! Some limitations apply to services bound to a Unix domain socket:
+//!
+//! - TLS is not supported. The reverse proxy should terminate TLS.
+//! - The socket path must not exist, unless it's a socket left by a previous
+//!   run. Any other kind of file is never replaced.
+//! - Clients do not have an IP address. The connection info contains the
+//!   [`UNIX_SOCKET_CLIENT_ADDR`] placeholder, so HTTP trackers get the peer IP
+//!   from the proxy headers (the tracker must be `on_reverse_proxy`).
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use axum::extract::ConnectInfo;
+use axum::{Extension, Router};
+use camino::Utf8Path;
+#[cfg(unix)]
+use camino::Utf8PathBuf;
+use tokio::sync::oneshot::Receiver;
+
+use crate::servers::logging::STARTED_ON;
+use crate::servers::signals::{shutdown_signal_with_message, Halted};
+
+/// The client address used as connection info for requests received on a
+/// Unix domain socket.
+pub const UNIX_SOCKET_CLIENT_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+
+/// A server bound to a Unix domain socket.
+///
+/// Binding is done before spawning the server task, so errors like a path
+/// that is already in use are returned to the job starting the service.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct Server {
+    path: Utf8PathBuf,
+    listener: tokio::net::UnixListener,
+}
+
+#[cfg(unix)]
+impl Server {
+    /// It binds to the socket path.
+    ///
+    /// A socket file left by a previous run is replaced, but any other kind
+    /// of file at the path is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the path exists and it's not a socket, or if it
+    /// can't bind to the socket path.
+    pub fn bind(path: &Utf8Path) -> std::io::Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+            Ok(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("the path exists and it is not a Unix domain socket: {path}"),
+                ))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        let listener = tokio::net::UnixListener::bind(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            listener,
+        })
+    }
+
+    /// The path of the socket file.
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// It serves the `router` until the halt signal (or the global shutdown
+    /// signal) is received.
+    ///
+    /// The `log_target` of the service is included in the log records as the
+    /// `service` field.
+    ///
+    /// The socket file is removed when the server stops.
+    pub async fn serve(self, router: Router, rx_halt: Receiver<Halted>, log_target: &'static str) {
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+        use hyper_util::server::conn::auto::Builder;
+        use hyper_util::service::TowerToHyperService;
+
+        let Self { path, listener } = self;
+
+        let router = router.layer(Extension(ConnectInfo(UNIX_SOCKET_CLIENT_ADDR)));
+
+        tracing::info!(service = log_target, "{STARTED_ON} unix:{path}");
+
+        let halt = shutdown_signal_with_message(rx_halt, format!("Shutting down server on Unix domain socket: {path}"));
+        tokio::pin!(halt);
+
+        loop {
+            tokio::select! {
+                () = &mut halt => break,
+                accepted = listener.accept() => {
+                    let stream = match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            tracing::warn!(service = log_target, "Failed to accept connection on unix:{path}: {err}");
+                            continue;
+                        }
+                    };
+
+                    let service = TowerToHyperService::new(router.clone());
+
+                    tokio::spawn(async move {
+                        if let Err(err) = Builder::new(TokioExecutor::new())
+                            .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                            .await
+                        {
+                            tracing::debug!(service = log_target, "Connection error: {err}");
+                        }
+                    });
+                }
+            }
+        }
+
+        drop(std::fs::remove_file(&path));
+    }
+}
+
+/// Unix domain sockets are only supported on Unix platforms.
+#[cfg(not(unix))]
+#[derive(Debug)]
+pub enum Server {}
+
+#[cfg(not(unix))]
+impl Server {
+    /// Unix domain sockets are only supported on Unix platforms.
+    ///
+    /// # Errors
+    ///
+    /// It always returns an [`Unsupported`](std::io::ErrorKind::Unsupported) error.
+    pub fn bind(path: &Utf8Path) -> std::io::Result<Self> {
+        Err(unsupported(path))
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        match *self {}
+    }
+
+    pub async fn serve(self, _router: Router, _rx_halt: Receiver<Halted>, _log_target: &'static str) {
+        match self {}
+    }
+}
+
+/// It sends a `GET` request for the `uri` to the server on the Unix domain
+/// socket and returns the status of the response, for example `200 OK`.
+///
+/// It's used to check the health of the services bound to a Unix domain
+/// socket.
+///
+/// # Errors
+///
+/// Will return an error if unable to connect, or if the response is not a
+/// valid HTTP response.
+#[cfg(unix)]
+pub async fn get_status(path: &Utf8Path, uri: &str) -> Result<String, String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::UnixStream::connect(path).await.map_err(|err| err.to_string())?;
+
+    stream
+        .write_all(format!("GET {uri} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").as_bytes())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.map_err(|err| err.to_string())?;
+
+    response
+        .lines()
+        .next()
+        .and_then(|status_line| status_line.split_once(' '))
+        .map(|(_version, status)| status.to_string())
+        .ok_or_else(|| format!("invalid HTTP response: {response}"))
+}
+
+/// Unix domain sockets are only supported on Unix platforms.
+///
+/// # Errors
+///
+/// It always returns an error.
+#[cfg(not(unix))]
+pub async fn get_status(path: &Utf8Path, _uri: &str) -> Result<String, String> {
+    Err(unsupported(path).to_string())
+}
+
+#[cfg(not(unix))]
+fn unsupported(path: &Utf8Path) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("Unix domain sockets are not supported on this platform: {path}"),
+    )
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use axum::extract::ConnectInfo;
+    use axum::routing::get;
+    use axum::Router;
+    use camino::Utf8PathBuf;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    use super::{get_status, Server, UNIX_SOCKET_CLIENT_ADDR};
+    use crate::servers::signals::Halted;
+
+    fn temp_socket_path() -> Utf8PathBuf {
+        Utf8PathBuf::from_path_buf(std::env::temp_dir().join(format!("torrust_{}.sock", uuid::Uuid::new_v4())))
+            .expect("it should be a UTF-8 path")
+    }
+
+    #[tokio::test]
+    async fn it_should_serve_http_requests_on_a_unix_domain_socket() {
+        let path = temp_socket_path();
+
+        let router = Router::new().route(
+            "/client",
+            get(|ConnectInfo(client): ConnectInfo<std::net::SocketAddr>| async move { client.to_string() }),
+        );
+
+        let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
+
+        let server = Server::bind(&path).unwrap();
+
+        let server = tokio::spawn(server.serve(router, rx_halt, "TEST"));
+
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+
+        stream
+            .write_all(b"GET /client HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(&UNIX_SOCKET_CLIENT_ADDR.to_string()));
+
+        tx_halt.send(Halted::Normal).unwrap();
+        server.await.unwrap();
+
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn it_should_replace_a_socket_file_left_by_a_previous_run() {
+        let path = temp_socket_path();
+
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+        let server = Server::bind(&path).unwrap();
+
+        assert_eq!(server.path(), path);
+
+        drop(server);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_not_replace_a_file_that_is_not_a_socket() {
+        let path = temp_socket_path();
+
+        std::fs::write(&path, "not a socket").unwrap();
+
+        let err = Server::bind(&path).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "not a socket");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_get_the_status_of_a_request_to_a_unix_domain_socket() {
+        let path = temp_socket_path();
+
+        let router = Router::new().route("/health_check", get(|| async { "ok" }));
+
+        let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
+
+        let server = tokio::spawn(Server::bind(&path).unwrap().serve(router, rx_halt, "TEST"));
+
+        assert_eq!(get_status(&path, "/health_check").await, Ok("200 OK".to_string()));
+        assert_eq!(get_status(&path, "/missing").await, Ok("404 Not Found".to_string()));
+
+        tx_halt.send(Halted::Normal).unwrap();
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,87 @@
+//! `BitTorrent` magnet links.
+//!
+//! A magnet link identifies a torrent by its info-hash, so that clients can
+//! download the torrent metadata from the peers instead of from a torrent
+//! file. For example:
+//!
+//! ```text
+//! magnet:?xt=urn:btih:9c38422213e30bff212b30c360d26f9a02136422&dn=ubuntu&tr=udp%3A%2F%2Ftracker%2Eexample%2Ecom%3A6969%2Fannounce
+//! ```
+//!
+//! - `xt`: the exact topic, the hex info-hash v1.
+//! - `dn`: the optional display name.
+//! - `tr`: the announce URLs of the trackers. It can be repeated.
+//!
+//! See [BEP 9. Extension for Peers to Send Metadata Files](https://www.bittorrent.org/beps/bep_0009.html)
+//! for the official specification.
+use std::fmt;
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use torrust_tracker_primitives::info_hash::InfoHash;
+
+/// A magnet link for a torrent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagnetLink {
+    pub info_hash: InfoHash,
+    pub display_name: Option<String>,
+    pub trackers: Vec<String>,
+}
+
+impl fmt::Display for MagnetLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "magnet:?xt=urn:btih:{}", self.info_hash.to_hex_string())?;
+
+        if let Some(display_name) = &self.display_name {
+            write!(f, "&dn={}", utf8_percent_encode(display_name, NON_ALPHANUMERIC))?;
+        }
+
+        for tracker in &self.trackers {
+            write!(f, "&tr={}", utf8_percent_encode(tracker, NON_ALPHANUMERIC))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use torrust_tracker_primitives::info_hash::InfoHash;
+
+    use super::MagnetLink;
+
+    #[test]
+    fn it_should_only_contain_the_info_hash_when_there_is_no_name_and_no_trackers() {
+        let magnet_link = MagnetLink {
+            info_hash: InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap(),
+            display_name: None,
+            trackers: vec![],
+        };
+
+        assert_eq!(
+            magnet_link.to_string(),
+            "magnet:?xt=urn:btih:9c38422213e30bff212b30c360d26f9a02136422"
+        );
+    }
+
+    #[test]
+    fn it_should_percent_encode_the_display_name_and_the_trackers() {
+        let magnet_link = MagnetLink {
+            info_hash: InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap(),
+            display_name: Some("ubuntu 24.04".to_string()),
+            trackers: vec![
+                "udp://tracker.example.com:6969/announce".to_string(),
+                "https://tracker.example.com/announce".to_string(),
+            ],
+        };
+
+        assert_eq!(
+            magnet_link.to_string(),
+            "magnet:?xt=urn:btih:9c38422213e30bff212b30c360d26f9a02136422\
+             &dn=ubuntu%2024%2E04\
+             &tr=udp%3A%2F%2Ftracker%2Eexample%2Ecom%3A6969%2Fannounce\
+             &tr=https%3A%2F%2Ftracker%2Eexample%2Ecom%2Fannounce"
+        );
+    }
+}
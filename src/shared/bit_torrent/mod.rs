@@ -69,4 +69,5 @@
 //!Bencode & bdecode in your browser | <https://github.com/Chocobo1/bencode_online>
 pub mod common;
 pub mod info_hash;
+pub mod magnet;
 pub mod tracker;
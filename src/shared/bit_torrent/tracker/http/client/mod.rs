@@ -5,9 +5,8 @@ use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use hyper::StatusCode;
 use requests::{announce, scrape};
-use reqwest::{Response, Url};
+use reqwest::{Response, StatusCode, Url};
 use thiserror::Error;
 
 use crate::core::auth::Key;
@@ -23,6 +22,7 @@ pub enum Error {
 }
 
 /// HTTP Tracker Client
+#[allow(clippy::struct_field_names)]
 pub struct Client {
     client: reqwest::Client,
     base_url: Url,
@@ -73,6 +73,29 @@ impl Client {
         })
     }
 
+    /// Creates the new client sending the requests through a `proxy`. The
+    /// proxy URL scheme can be `http`, `https`, `socks5` or `socks5h`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the proxy URL is not valid or the client builder
+    /// fails.
+    pub fn proxied(base_url: Url, timeout: Duration, proxy: &Url) -> Result<Self, Error> {
+        let proxy = reqwest::Proxy::all(proxy.clone()).map_err(|e| Error::ClientBuildingError { err: e.into() })?;
+
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .proxy(proxy)
+            .build()
+            .map_err(|e| Error::ClientBuildingError { err: e.into() })?;
+
+        Ok(Self {
+            base_url,
+            client,
+            key: None,
+        })
+    }
+
     /// # Errors
     ///
     /// This method fails if the client builder fails.
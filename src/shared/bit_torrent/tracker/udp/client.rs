@@ -4,12 +4,17 @@ use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
 
-use aquatic_udp_protocol::{ConnectRequest, Request, Response, TransactionId};
+use aquatic_udp_protocol::{
+    AnnounceActionPlaceholder, AnnounceEvent, AnnounceRequest, ConnectRequest, InfoHash, NumberOfBytes, NumberOfPeers, PeerId,
+    PeerKey, Port, Request, Response, TransactionId,
+};
 use tokio::net::UdpSocket;
 use tokio::time;
 use torrust_tracker_configuration::DEFAULT_TIMEOUT;
-use zerocopy::network_endian::I32;
+use url::Url;
+use zerocopy::network_endian::{I32, I64, U16};
 
+use super::socks5::{self, Association};
 use super::Error;
 use crate::shared::bit_torrent::tracker::udp::MAX_PACKET_SIZE;
 
@@ -180,6 +185,9 @@ impl UdpClient {
 #[derive(Debug)]
 pub struct UdpTrackerClient {
     pub client: UdpClient,
+
+    /// The association with the SOCKS5 proxy relaying the datagrams, if any.
+    socks5: Option<Association>,
 }
 
 impl UdpTrackerClient {
@@ -191,18 +199,48 @@ impl UdpTrackerClient {
     ///
     pub async fn new(remote_addr: SocketAddr, timeout: Duration) -> Result<UdpTrackerClient, Error> {
         let client = UdpClient::connected(remote_addr, timeout).await?;
-        Ok(UdpTrackerClient { client })
+        Ok(UdpTrackerClient { client, socks5: None })
+    }
+
+    /// Creates a new `UdpTrackerClient` that sends the requests to the Udp
+    /// Tracker server through a SOCKS5 `proxy`.
+    ///
+    /// # Errors
+    ///
+    /// If the proxy is not a SOCKS5 proxy, or if unable to open the UDP
+    /// association with the proxy or to connect to its relay.
+    pub async fn proxied(remote_addr: SocketAddr, proxy: &Url, timeout: Duration) -> Result<UdpTrackerClient, Error> {
+        let association = Association::open(proxy, remote_addr, timeout).await?;
+        let client = UdpClient::connected(association.relay, timeout).await?;
+        Ok(UdpTrackerClient {
+            client,
+            socks5: Some(association),
+        })
     }
 
     /// # Errors
     ///
     /// Will return error if can't write request to bytes.
     pub async fn send(&self, request: Request) -> Result<usize, Error> {
+        self.send_with_prefix(&[], request).await
+    }
+
+    /// It sends the request after some arbitrary bytes, for example, a PROXY
+    /// protocol header.
+    ///
+    /// # Errors
+    ///
+    /// Will return error if can't write request to bytes.
+    pub async fn send_with_prefix(&self, prefix: &[u8], request: Request) -> Result<usize, Error> {
         tracing::trace!(target: UDP_CLIENT_LOG_TARGET, "sending request {request:?} ...");
 
         // Write request into a buffer
         // todo: optimize the pre-allocated amount based upon request type.
-        let mut writer = Cursor::new(Vec::with_capacity(200));
+        let header = self.socks5.as_ref().map_or(&[][..], Association::header);
+        let mut writer = Cursor::new(Vec::with_capacity(200 + header.len() + prefix.len()));
+        writer.get_mut().extend_from_slice(header);
+        writer.get_mut().extend_from_slice(prefix);
+        writer.set_position((header.len() + prefix.len()) as u64);
         let () = request
             .write_bytes(&mut writer)
             .map_err(|e| Error::UnableToWriteDataFromRequest { err: e.into(), request })?;
@@ -214,57 +252,123 @@ impl UdpTrackerClient {
     ///
     /// Will return error if can't create response from the received payload (bytes buffer).
     pub async fn receive(&self) -> Result<Response, Error> {
-        let response = self.client.receive().await?;
+        let mut response = self.client.receive().await?;
+
+        if self.socks5.is_some() {
+            response = socks5::strip_header(&response)?.to_vec();
+        }
 
         tracing::debug!(target: UDP_CLIENT_LOG_TARGET, "received {} bytes: {response:?}", response.len());
 
         Response::parse_bytes(&response, true).map_err(|e| Error::UnableToParseResponse { err: e.into(), response })
     }
+
+    /// It sends all the requests before receiving any response, like a
+    /// client with several outstanding transactions.
+    ///
+    /// The responses are returned in the order they are received, which can
+    /// be different from the order of the requests. They have to be matched
+    /// with the requests by transaction ID.
+    ///
+    /// # Errors
+    ///
+    /// Will return error if it can't send a request or receive a response for
+    /// each request.
+    pub async fn send_pipelined(&self, requests: Vec<Request>) -> Result<Vec<Response>, Error> {
+        let number_of_requests = requests.len();
+
+        for request in requests {
+            self.send(request).await?;
+        }
+
+        let mut responses = Vec::with_capacity(number_of_requests);
+
+        for _ in 0..number_of_requests {
+            responses.push(self.receive().await?);
+        }
+
+        Ok(responses)
+    }
 }
 
 /// Helper Function to Check if a UDP Service is Connectable
 ///
-/// # Panics
+/// It does a full round trip: a `connect` request followed by an `announce`
+/// request with the `stopped` event, so the checker is never added to a swarm.
 ///
-/// It will return an error if unable to connect to the UDP service.
+/// Any valid response to the `announce` request passes the check, including
+/// an error response (for example, when the tracker is private or the torrent
+/// is not whitelisted), because it means the tracker handled the request.
 ///
 /// # Errors
 ///
+/// It will return an error if unable to connect to the UDP service or if the
+/// service does not answer both requests.
 pub async fn check(remote_addr: &SocketAddr) -> Result<String, String> {
+    check_with_prefix(remote_addr, &[]).await
+}
+
+/// Same as [`check`], but the request datagrams start with the `prefix` bytes.
+///
+/// # Errors
+///
+/// It will return an error if unable to connect to the UDP service or if the
+/// service does not answer both requests.
+pub async fn check_with_prefix(remote_addr: &SocketAddr, prefix: &[u8]) -> Result<String, String> {
     tracing::debug!("Checking Service (detail): {remote_addr:?}.");
 
-    match UdpTrackerClient::new(*remote_addr, DEFAULT_TIMEOUT).await {
-        Ok(client) => {
-            let connect_request = ConnectRequest {
-                transaction_id: TransactionId(I32::new(123)),
-            };
-
-            // client.send() return usize, but doesn't use here
-            match client.send(connect_request.into()).await {
-                Ok(_) => (),
-                Err(e) => tracing::debug!("Error: {e:?}."),
-            };
-
-            let process = move |response| {
-                if matches!(response, Response::Connect(_connect_response)) {
-                    Ok("Connected".to_string())
-                } else {
-                    Err("Did not Connect".to_string())
-                }
-            };
-
-            let sleep = time::sleep(Duration::from_millis(2000));
-            tokio::pin!(sleep);
-
-            tokio::select! {
-                () = &mut sleep => {
-                      Err("Timed Out".to_string())
-                }
-                response = client.receive() => {
-                      process(response.unwrap())
-                }
-            }
+    let client = UdpTrackerClient::new(*remote_addr, DEFAULT_TIMEOUT)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+    let connect_request = ConnectRequest {
+        transaction_id: TransactionId(I32::new(123)),
+    };
+
+    let Response::Connect(connect_response) = round_trip(&client, prefix, connect_request.into()).await? else {
+        return Err("Did not Connect".to_string());
+    };
+
+    let announce_request = AnnounceRequest {
+        connection_id: connect_response.connection_id,
+        action_placeholder: AnnounceActionPlaceholder::default(),
+        transaction_id: TransactionId(I32::new(124)),
+        info_hash: InfoHash([0u8; 20]),
+        peer_id: PeerId(*b"-TT0000-healthcheck0"),
+        bytes_downloaded: NumberOfBytes(I64::new(0)),
+        bytes_uploaded: NumberOfBytes(I64::new(0)),
+        bytes_left: NumberOfBytes(I64::new(0)),
+        event: AnnounceEvent::Stopped.into(),
+        ip_address: Ipv4Addr::UNSPECIFIED.into(),
+        key: PeerKey::new(0i32),
+        peers_wanted: NumberOfPeers(I32::new(1)),
+        port: Port(U16::new(client.client.socket.local_addr().map_or(0, |addr| addr.port()))),
+    };
+
+    match round_trip(&client, prefix, announce_request.into()).await? {
+        Response::AnnounceIpv4(_) | Response::AnnounceIpv6(_) => Ok("Connected and announced".to_string()),
+        Response::Error(error) => Ok(format!("Connected and announced (rejected: {})", error.message)),
+        _ => Err("Did not Announce".to_string()),
+    }
+}
+
+/// It sends the request and waits for the response.
+async fn round_trip(client: &UdpTrackerClient, prefix: &[u8], request: Request) -> Result<Response, String> {
+    // client.send() return usize, but doesn't use here
+    match client.send_with_prefix(prefix, request).await {
+        Ok(_) => (),
+        Err(e) => tracing::debug!("Error: {e:?}."),
+    }
+
+    let sleep = time::sleep(Duration::from_secs(2));
+    tokio::pin!(sleep);
+
+    tokio::select! {
+        () = &mut sleep => {
+              Err("Timed Out".to_string())
+        }
+        response = client.receive() => {
+              response.map_err(|e| format!("{e:?}"))
         }
-        Err(e) => Err(format!("{e:?}")),
     }
 }
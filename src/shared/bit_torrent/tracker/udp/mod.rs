@@ -6,8 +6,10 @@ use thiserror::Error;
 use torrust_tracker_located_error::DynError;
 
 pub mod client;
+pub mod socks5;
 
-/// The maximum number of bytes in a UDP packet.
+/// The maximum number of bytes in the UDP packets received by the client. The
+/// server receive buffer holds any UDP datagram.
 pub const MAX_PACKET_SIZE: usize = 1496;
 /// A magic 64-bit integer constant defined in the protocol that is used to
 /// identify the protocol.
@@ -59,6 +61,21 @@ pub enum Error {
 
     #[error("Failed to parse response: {response:?}, with error: {err:?}")]
     UnableToParseResponse { err: Arc<std::io::Error>, response: Vec<u8> },
+
+    #[error("Unsupported proxy: {proxy}, only SOCKS5 proxies can relay UDP datagrams")]
+    UnsupportedProxy { proxy: String },
+
+    #[error("Timeout while waiting for the association with the proxy: {proxy}")]
+    TimeoutWhileConnectingToProxy { proxy: String },
+
+    #[error("Failed to connect to proxy: {proxy}, with error: {err:?}")]
+    UnableToConnectToProxy { err: Arc<std::io::Error>, proxy: String },
+
+    #[error("Proxy handshake failed: {reason}")]
+    ProxyHandshakeFailed { reason: String },
+
+    #[error("Invalid datagram received from the proxy relay: {data:?}")]
+    InvalidProxyDatagram { data: Vec<u8> },
 }
 
 impl From<Error> for DynError {
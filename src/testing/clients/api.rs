@@ -0,0 +1,412 @@
+//! Client for the tracker REST API.
+//!
+//! > **NOTICE**: the client panics if the request can't be sent. It's only
+//! > intended to be used in tests.
+use reqwest::Response;
+use serde::Serialize;
+
+use super::query::{Query, QueryParam, ReqwestQuery};
+
+#[must_use]
+pub fn connection_with_invalid_token(bind_address: &str) -> ConnectionInfo {
+    ConnectionInfo::authenticated(bind_address, "invalid token")
+}
+
+#[must_use]
+pub fn connection_with_no_token(bind_address: &str) -> ConnectionInfo {
+    ConnectionInfo::anonymous(bind_address)
+}
+
+/// The API address and the (optional) access token used by the client.
+#[derive(Clone, Debug)]
+pub struct ConnectionInfo {
+    pub bind_address: String,
+    pub api_token: Option<String>,
+}
+
+impl ConnectionInfo {
+    #[must_use]
+    pub fn authenticated(bind_address: &str, api_token: &str) -> Self {
+        Self {
+            bind_address: bind_address.to_string(),
+            api_token: Some(api_token.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn anonymous(bind_address: &str) -> Self {
+        Self {
+            bind_address: bind_address.to_string(),
+            api_token: None,
+        }
+    }
+}
+
+/// API Client
+pub struct Client {
+    connection_info: ConnectionInfo,
+    base_path: String,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(connection_info: ConnectionInfo) -> Self {
+        Self {
+            connection_info,
+            base_path: "/api/v1/".to_string(),
+        }
+    }
+
+    pub async fn generate_auth_key(&self, seconds_valid: i32) -> Response {
+        self.post_empty(&format!("key/{}", &seconds_valid)).await
+    }
+
+    pub async fn add_auth_key(&self, add_key_form: AddKeyForm) -> Response {
+        self.post_form("keys", &add_key_form).await
+    }
+
+    pub async fn delete_auth_key(&self, key: &str) -> Response {
+        self.delete(&format!("key/{}", &key)).await
+    }
+
+    pub async fn reload_keys(&self) -> Response {
+        self.get("keys/reload", Query::default()).await
+    }
+
+    pub async fn expire_all_keys(&self) -> Response {
+        self.post_empty("keys/expire-all").await
+    }
+
+    pub async fn get_key_stats(&self, key: &str) -> Response {
+        self.get(&format!("key/{key}/stats"), Query::default()).await
+    }
+
+    pub async fn get_key_validity(&self, key: &str) -> Response {
+        self.get(&format!("key/{key}/valid"), Query::default()).await
+    }
+
+    pub async fn set_key_quota(&self, key: &str, key_quota_form: KeyQuotaForm) -> Response {
+        self.post_form(&format!("key/{key}/quota"), &key_quota_form).await
+    }
+
+    pub async fn remove_key_quota(&self, key: &str) -> Response {
+        self.delete(&format!("key/{key}/quota")).await
+    }
+
+    pub async fn generate_invitation_code(&self) -> Response {
+        self.post_empty("invitations").await
+    }
+
+    pub async fn get_invitation_codes(&self) -> Response {
+        self.get("invitations", Query::default()).await
+    }
+
+    pub async fn delete_invitation_code(&self, code: &str) -> Response {
+        self.delete(&format!("invitation/{code}")).await
+    }
+
+    pub async fn register(&self, register_form: RegisterForm) -> Response {
+        self.post_form("register", &register_form).await
+    }
+
+    pub async fn whitelist_a_torrent(&self, info_hash: &str) -> Response {
+        self.post_empty(&format!("whitelist/{}", &info_hash)).await
+    }
+
+    pub async fn get_whitelisted_torrent(&self, info_hash: &str) -> Response {
+        self.get(&format!("whitelist/{}", &info_hash), Query::default()).await
+    }
+
+    pub async fn remove_torrent_from_whitelist(&self, info_hash: &str) -> Response {
+        self.delete(&format!("whitelist/{}", &info_hash)).await
+    }
+
+    pub async fn reload_whitelist(&self) -> Response {
+        self.get("whitelist/reload", Query::default()).await
+    }
+
+    pub async fn get_whitelist(&self, params: Query) -> Response {
+        self.get("whitelist", params).await
+    }
+
+    pub async fn whitelist_contains(&self, form: WhitelistContainsForm) -> Response {
+        self.post_form("whitelist/contains", &form).await
+    }
+
+    pub async fn get_whitelist_rules(&self) -> Response {
+        self.get("whitelist/rules", Query::default()).await
+    }
+
+    pub async fn add_whitelist_rule(&self, add_rule_form: AddWhitelistRuleForm) -> Response {
+        self.post_form("whitelist/rules", &add_rule_form).await
+    }
+
+    pub async fn remove_whitelist_rule(&self, id: u64) -> Response {
+        self.delete(&format!("whitelist/rules/{id}")).await
+    }
+
+    pub async fn get_torrent_policies(&self) -> Response {
+        self.get("policies", Query::default()).await
+    }
+
+    pub async fn set_torrent_policy(&self, info_hash: &str, policy: &str) -> Response {
+        self.post_empty(&format!("policy/{}/{}", &info_hash, &policy)).await
+    }
+
+    pub async fn remove_torrent_policy(&self, info_hash: &str) -> Response {
+        self.delete(&format!("policy/{}", &info_hash)).await
+    }
+
+    pub async fn get_static_peers(&self) -> Response {
+        self.get("static-peers", Query::default()).await
+    }
+
+    pub async fn add_static_peer(&self, info_hash: &str, peer_addr: &str) -> Response {
+        self.post_empty(&format!("static-peer/{}/{}", &info_hash, &peer_addr)).await
+    }
+
+    pub async fn remove_static_peer(&self, info_hash: &str, peer_addr: &str) -> Response {
+        self.delete(&format!("static-peer/{}/{}", &info_hash, &peer_addr)).await
+    }
+
+    pub async fn get_torrent(&self, info_hash: &str) -> Response {
+        self.get(&format!("torrent/{}", &info_hash), Query::default()).await
+    }
+
+    pub async fn get_torrent_magnet(&self, info_hash: &str, params: Query) -> Response {
+        self.get(&format!("torrent/{info_hash}/magnet"), params).await
+    }
+
+    pub async fn get_torrents(&self, params: Query) -> Response {
+        self.get("torrents", params).await
+    }
+
+    pub async fn get_stale_torrents(&self, params: Query) -> Response {
+        self.get("torrents/stale", params).await
+    }
+
+    pub async fn get_swarm_changes(&self, params: Query) -> Response {
+        self.get("torrents/changes", params).await
+    }
+
+    pub async fn get_audit_log(&self, params: Query) -> Response {
+        self.get("audit-log", params).await
+    }
+
+    pub async fn get_tracker_statistics(&self) -> Response {
+        self.get("stats", Query::default()).await
+    }
+
+    pub async fn get_tracker_statistics_in_format(&self, format: &str) -> Response {
+        self.get("stats", Query::params([QueryParam::new("format", format)].to_vec()))
+            .await
+    }
+
+    pub async fn get_tracker_statistics_with_fields(&self, fields: &str) -> Response {
+        self.get("stats", Query::params([QueryParam::new("fields", fields)].to_vec()))
+            .await
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the request can't be sent.
+    pub async fn get_tracker_statistics_accepting_encoding(&self, encoding: &str) -> Response {
+        reqwest::Client::new()
+            .get(self.base_url("stats").clone())
+            .query(&ReqwestQuery::from(self.query_with_token()))
+            .header(reqwest::header::ACCEPT_ENCODING, encoding)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_runtimes_statistics(&self) -> Response {
+        self.get("stats/runtimes", Query::default()).await
+    }
+
+    pub async fn get_swarm_sizes(&self) -> Response {
+        self.get("stats/swarm-sizes", Query::default()).await
+    }
+
+    pub async fn get_health_scores(&self) -> Response {
+        self.get("stats/health-scores", Query::default()).await
+    }
+
+    pub async fn get_async_announce_statistics(&self) -> Response {
+        self.get("stats/async-announce", Query::default()).await
+    }
+
+    pub async fn get_announce_journal_statistics(&self) -> Response {
+        self.get("stats/announce-journal", Query::default()).await
+    }
+
+    pub async fn get_consistency_check(&self) -> Response {
+        self.get("stats/consistency", Query::default()).await
+    }
+
+    pub async fn get_database_authorization_statistics(&self) -> Response {
+        self.get("stats/database-authorization", Query::default()).await
+    }
+
+    pub async fn get_configuration_schema(&self) -> Response {
+        self.get("configuration/schema", Query::default()).await
+    }
+
+    pub async fn get_maintenance(&self) -> Response {
+        self.get("maintenance", Query::default()).await
+    }
+
+    pub async fn enable_maintenance(&self) -> Response {
+        self.post_empty("maintenance").await
+    }
+
+    pub async fn disable_maintenance(&self) -> Response {
+        self.delete("maintenance").await
+    }
+
+    pub async fn get_peer_cleanup(&self) -> Response {
+        self.get("peer-cleanup", Query::default()).await
+    }
+
+    pub async fn set_peer_cleanup(&self, peer_cleanup_form: PeerCleanupForm) -> Response {
+        self.post_form("peer-cleanup", &peer_cleanup_form).await
+    }
+
+    pub async fn get_database_backup_status(&self) -> Response {
+        self.get("database/backup", Query::default()).await
+    }
+
+    pub async fn back_up_database(&self) -> Response {
+        self.post_empty("database/backup").await
+    }
+
+    pub async fn get_dead_letters(&self) -> Response {
+        self.get("database/dead_letters", Query::default()).await
+    }
+
+    pub async fn replay_dead_letters(&self) -> Response {
+        self.post_empty("database/dead_letters/replay").await
+    }
+
+    pub async fn drain_dead_letters(&self) -> Response {
+        self.delete("database/dead_letters").await
+    }
+
+    pub async fn get(&self, path: &str, params: Query) -> Response {
+        let mut query: Query = params;
+
+        if let Some(token) = &self.connection_info.api_token {
+            query.add_param(QueryParam::new("token", token));
+        }
+
+        self.get_request_with_query(path, query).await
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the request can't be sent.
+    pub async fn post_empty(&self, path: &str) -> Response {
+        reqwest::Client::new()
+            .post(self.base_url(path).clone())
+            .query(&ReqwestQuery::from(self.query_with_token()))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the request can't be sent.
+    pub async fn post_form<T: Serialize + ?Sized>(&self, path: &str, form: &T) -> Response {
+        reqwest::Client::new()
+            .post(self.base_url(path).clone())
+            .query(&ReqwestQuery::from(self.query_with_token()))
+            .json(&form)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn delete(&self, path: &str) -> Response {
+        reqwest::Client::new()
+            .delete(self.base_url(path).clone())
+            .query(&ReqwestQuery::from(self.query_with_token()))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_request_with_query(&self, path: &str, params: Query) -> Response {
+        get(&self.base_url(path), Some(params)).await
+    }
+
+    pub async fn get_request(&self, path: &str) -> Response {
+        get(&self.base_url(path), None).await
+    }
+
+    fn query_with_token(&self) -> Query {
+        match &self.connection_info.api_token {
+            Some(token) => Query::params([QueryParam::new("token", token)].to_vec()),
+            None => Query::default(),
+        }
+    }
+
+    fn base_url(&self, path: &str) -> String {
+        format!("http://{}{}{path}", &self.connection_info.bind_address, &self.base_path)
+    }
+}
+
+/// # Panics
+///
+/// Will panic if the request can't be sent.
+pub async fn get(path: &str, query: Option<Query>) -> Response {
+    match query {
+        Some(params) => reqwest::Client::builder()
+            .build()
+            .unwrap()
+            .get(path)
+            .query(&ReqwestQuery::from(params))
+            .send()
+            .await
+            .unwrap(),
+        None => reqwest::Client::builder().build().unwrap().get(path).send().await.unwrap(),
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct AddKeyForm {
+    #[serde(rename = "key")]
+    pub opt_key: Option<String>,
+    pub seconds_valid: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bound_to: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AddWhitelistRuleForm {
+    pub pattern: String,
+    pub seconds_valid: Option<u64>,
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WhitelistContainsForm {
+    pub info_hashes: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyQuotaForm {
+    pub max_announces_per_day: Option<u32>,
+    pub max_peers_per_day: Option<u32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PeerCleanupForm {
+    pub inactive_peer_cleanup_interval: Option<u64>,
+    pub max_peer_timeout: Option<u32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RegisterForm {
+    pub code: String,
+}
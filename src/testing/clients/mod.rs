@@ -0,0 +1,10 @@
+//! Typed clients for the tracker servers.
+//!
+//! - [`api`]: client for the tracker REST API.
+//! - [`http`]: client for the HTTP tracker.
+//! - [`udp`]: client for the UDP tracker.
+pub mod api;
+pub mod query;
+
+pub use crate::shared::bit_torrent::tracker::http::client as http;
+pub use crate::shared::bit_torrent::tracker::udp::client as udp;
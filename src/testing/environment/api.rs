@@ -0,0 +1,129 @@
+//! Environment for the tracker REST API.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures::executor::block_on;
+use torrust_tracker_configuration::{Configuration, HttpApi};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+use crate::bootstrap::app::initialize_with_configuration;
+use crate::bootstrap::jobs::make_rust_tls;
+use crate::core::virtual_trackers::VirtualTrackers;
+use crate::core::Tracker;
+use crate::servers::apis::server::{ApiServer, Launcher, Running, Stopped};
+use crate::servers::registar::Registar;
+use crate::testing::clients::api::ConnectionInfo;
+
+/// A running REST API environment.
+pub type Started = Environment<Running>;
+
+pub struct Environment<S>
+where
+    S: std::fmt::Debug + std::fmt::Display,
+{
+    pub config: Arc<HttpApi>,
+    pub tracker: Arc<Tracker>,
+    pub registar: Registar,
+    pub server: ApiServer<S>,
+}
+
+impl<S> Environment<S>
+where
+    S: std::fmt::Debug + std::fmt::Display,
+{
+    /// Add a torrent to the tracker
+    pub fn add_torrent_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+        self.tracker.upsert_peer_and_get_stats(info_hash, peer);
+    }
+}
+
+impl Environment<Stopped> {
+    /// # Panics
+    ///
+    /// Will panic if the configuration does not contain the API section or
+    /// the TLS configuration is not valid.
+    #[must_use]
+    pub fn new(configuration: &Arc<Configuration>) -> Self {
+        let tracker = initialize_with_configuration(configuration);
+
+        let config = Arc::new(configuration.http_api.clone().expect("missing API configuration"));
+
+        let bind_to = config
+            .bind_address
+            .socket_addr()
+            .expect("it should be bound to a TCP socket address");
+
+        let tls = block_on(make_rust_tls(&config.tsl_config)).map(|tls| tls.expect("tls config failed"));
+
+        let virtual_trackers = VirtualTrackers::new(configuration.virtual_trackers.as_deref().unwrap_or_default());
+
+        let server = ApiServer::new(Launcher::new(bind_to, tls, virtual_trackers));
+
+        Self {
+            config,
+            tracker,
+            registar: Registar::default(),
+            server,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server can't be started.
+    pub async fn start(self) -> Environment<Running> {
+        Environment {
+            config: self.config.clone(),
+            tracker: self.tracker.clone(),
+            registar: self.registar.clone(),
+            server: self
+                .server
+                .start(self.tracker, self.registar.give_form(), self.config.clone())
+                .await
+                .unwrap(),
+        }
+    }
+}
+
+impl Environment<Running> {
+    pub async fn new(configuration: &Arc<Configuration>) -> Self {
+        Environment::<Stopped>::new(configuration).start().await
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server can't be stopped.
+    pub async fn stop(self) -> Environment<Stopped> {
+        Environment {
+            config: self.config,
+            tracker: self.tracker,
+            registar: Registar::default(),
+            server: self.server.stop().await.unwrap(),
+        }
+    }
+
+    /// It returns the connection info for the API client, using the `admin`
+    /// access token.
+    #[must_use]
+    pub fn get_connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            bind_address: self.server.state.local_addr.to_string(),
+            api_token: self.config.access_tokens.get("admin").cloned(),
+        }
+    }
+
+    /// It returns the connection info for the API client, using the
+    /// `incident-response` admin access token.
+    #[must_use]
+    pub fn get_admin_connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            bind_address: self.server.state.local_addr.to_string(),
+            api_token: self.config.admin_access_tokens.get("incident-response").cloned(),
+        }
+    }
+
+    #[must_use]
+    pub fn bind_address(&self) -> SocketAddr {
+        self.server.state.local_addr
+    }
+}
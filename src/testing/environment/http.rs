@@ -0,0 +1,115 @@
+//! Environment for the HTTP tracker.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures::executor::block_on;
+use torrust_tracker_configuration::{Configuration, HttpTracker};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+use crate::bootstrap::app::initialize_with_configuration;
+use crate::bootstrap::jobs::make_rust_tls;
+use crate::core::virtual_trackers::VirtualTrackers;
+use crate::core::{AnnounceMode, Tracker};
+use crate::servers::http::server::{HttpServer, Launcher, Running, Stopped};
+use crate::servers::registar::Registar;
+
+/// A running HTTP tracker environment.
+pub type Started = Environment<Running>;
+
+pub struct Environment<S> {
+    pub config: Arc<HttpTracker>,
+    pub tracker: Arc<Tracker>,
+    pub registar: Registar,
+    pub server: HttpServer<S>,
+}
+
+impl<S> Environment<S> {
+    /// Add a torrent to the tracker
+    pub fn add_torrent_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+        self.tracker.upsert_peer_and_get_stats(info_hash, peer);
+    }
+}
+
+impl Environment<Stopped> {
+    /// It uses the first HTTP tracker in the configuration.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the configuration does not contain any HTTP tracker or
+    /// the TLS configuration is not valid.
+    #[must_use]
+    pub fn new(configuration: &Arc<Configuration>) -> Self {
+        let tracker = initialize_with_configuration(configuration);
+
+        let http_tracker = configuration
+            .http_trackers
+            .clone()
+            .expect("missing HTTP tracker configuration");
+
+        let config = Arc::new(http_tracker[0].clone());
+
+        let bind_to = config
+            .bind_address
+            .first()
+            .socket_addr()
+            .expect("it should be bound to a TCP socket address");
+
+        let tls = block_on(make_rust_tls(&config.tsl_config)).map(|tls| tls.expect("tls config failed"));
+
+        let server = HttpServer::new(Launcher::new(
+            bind_to,
+            tls,
+            config.proxy_protocol,
+            config.key_path_format,
+            config.health.clone(),
+            AnnounceMode::new(config.async_announce),
+            VirtualTrackers::new(configuration.virtual_trackers.as_deref().unwrap_or_default()).hosts(),
+            config.request_limits,
+            config.bind_device.clone(),
+        ));
+
+        Self {
+            config,
+            tracker,
+            registar: Registar::default(),
+            server,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server can't be started.
+    pub async fn start(self) -> Environment<Running> {
+        Environment {
+            config: self.config,
+            tracker: self.tracker.clone(),
+            registar: self.registar.clone(),
+            server: self.server.start(self.tracker, self.registar.give_form()).await.unwrap(),
+        }
+    }
+}
+
+impl Environment<Running> {
+    pub async fn new(configuration: &Arc<Configuration>) -> Self {
+        Environment::<Stopped>::new(configuration).start().await
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server can't be stopped.
+    pub async fn stop(self) -> Environment<Stopped> {
+        Environment {
+            config: self.config,
+            tracker: self.tracker,
+            registar: Registar::default(),
+
+            server: self.server.stop().await.unwrap(),
+        }
+    }
+
+    #[must_use]
+    pub fn bind_address(&self) -> &SocketAddr {
+        &self.server.state.binding
+    }
+}
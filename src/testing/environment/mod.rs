@@ -0,0 +1,9 @@
+//! Running tracker servers for integration tests.
+//!
+//! Every environment contains a core tracker and one server. It can be in two
+//! states: `Stopped` or `Running`. Servers are bound to the address in the
+//! configuration, use an [ephemeral](super::configuration) configuration to
+//! bind them to a random free port.
+pub mod api;
+pub mod http;
+pub mod udp;
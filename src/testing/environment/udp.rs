@@ -0,0 +1,118 @@
+//! Environment for the UDP tracker.
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use torrust_tracker_configuration::{Configuration, UdpTracker, DEFAULT_TIMEOUT};
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer;
+
+use crate::bootstrap::app::initialize_with_configuration;
+use crate::core::{AnnounceMode, Tracker};
+use crate::servers::registar::Registar;
+use crate::servers::udp::server::bound_socket::SocketOptions;
+use crate::servers::udp::server::spawner::Spawner;
+use crate::servers::udp::server::states::{Running, Stopped};
+use crate::servers::udp::server::Server;
+
+/// A running UDP tracker environment.
+pub type Started = Environment<Running>;
+
+pub struct Environment<S>
+where
+    S: std::fmt::Debug + std::fmt::Display,
+{
+    pub config: Arc<UdpTracker>,
+    pub tracker: Arc<Tracker>,
+    pub registar: Registar,
+    pub server: Server<S>,
+}
+
+impl<S> Environment<S>
+where
+    S: std::fmt::Debug + std::fmt::Display,
+{
+    /// Add a torrent to the tracker
+    pub fn add_torrent(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+        self.tracker.upsert_peer_and_get_stats(info_hash, peer);
+    }
+}
+
+impl Environment<Stopped> {
+    /// It uses the first UDP tracker in the configuration.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the configuration does not contain any UDP tracker.
+    #[must_use]
+    pub fn new(configuration: &Arc<Configuration>) -> Self {
+        let tracker = initialize_with_configuration(configuration);
+
+        let udp_tracker = configuration.udp_trackers.clone().expect("missing UDP tracker configuration");
+
+        let config = Arc::new(udp_tracker[0].clone());
+
+        let bind_to = config
+            .bind_address
+            .first()
+            .socket_addr()
+            .expect("it should be bound to a socket address");
+
+        let server = Server::new(Spawner::new(
+            bind_to,
+            config.proxy_protocol,
+            SocketOptions::new(config.ipv6_only, config.dscp, config.bind_device.clone()),
+            AnnounceMode::new(config.async_announce),
+        ));
+
+        Self {
+            config,
+            tracker,
+            registar: Registar::default(),
+            server,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server can't be started.
+    pub async fn start(self) -> Environment<Running> {
+        Environment {
+            config: self.config,
+            tracker: self.tracker.clone(),
+            registar: self.registar.clone(),
+            server: self.server.start(self.tracker, self.registar.give_form()).await.unwrap(),
+        }
+    }
+}
+
+impl Environment<Running> {
+    /// # Panics
+    ///
+    /// Will panic if the server is not started within the [`DEFAULT_TIMEOUT`].
+    pub async fn new(configuration: &Arc<Configuration>) -> Self {
+        tokio::time::timeout(DEFAULT_TIMEOUT, Environment::<Stopped>::new(configuration).start())
+            .await
+            .expect("it should create an environment within the timeout")
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the server is not stopped within the [`DEFAULT_TIMEOUT`].
+    pub async fn stop(self) -> Environment<Stopped> {
+        let stopped = tokio::time::timeout(DEFAULT_TIMEOUT, self.server.stop())
+            .await
+            .expect("it should stop the environment within the timeout");
+
+        Environment {
+            config: self.config,
+            tracker: self.tracker,
+            registar: Registar::default(),
+            server: stopped.expect("it stop the udp tracker service"),
+        }
+    }
+
+    #[must_use]
+    pub fn bind_address(&self) -> SocketAddr {
+        self.server.state.local_addr
+    }
+}
@@ -0,0 +1,38 @@
+//! Helpers to run a real tracker in integration test suites.
+//!
+//! > **NOTICE**: this module is only available when the `testing` feature is
+//! > enabled.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! torrust-tracker = { version = "3.0.0-develop", features = ["testing"] }
+//! ```
+//!
+//! It contains the same helpers the tracker integration tests use, so that
+//! other projects (like the [Torrust Index](https://github.com/torrust/torrust-index))
+//! can spin up a tracker in their own test suites:
+//!
+//! - [`configuration`]: ephemeral configurations. All services are bound to
+//!   random free ports on `127.0.0.1` and use a temporary `SQLite` database.
+//! - [`tracker::Builder`]: a builder for core trackers using those
+//!   configurations.
+//! - [`environment`]: running servers (REST API, HTTP and UDP trackers) that
+//!   can be started and stopped.
+//! - [`clients`]: typed clients for the running servers.
+//!
+//! ```text
+//! use torrust_tracker::testing::configuration;
+//! use torrust_tracker::testing::environment::api;
+//! use torrust_tracker::testing::clients::api::Client;
+//!
+//! let env = api::Started::new(&configuration::ephemeral_public().into()).await;
+//!
+//! let response = Client::new(env.get_connection_info()).get_tracker_statistics().await;
+//!
+//! env.stop().await;
+//! ```
+pub mod clients;
+pub mod environment;
+pub mod tracker;
+
+pub use torrust_tracker_test_helpers::configuration;
@@ -0,0 +1,101 @@
+//! Builder for core trackers with ephemeral configurations.
+use std::sync::Arc;
+
+use torrust_tracker_configuration::Configuration;
+
+use super::configuration;
+use crate::bootstrap::app::initialize_with_configuration;
+use crate::core::Tracker;
+
+/// It builds a core [`Tracker`] using an ephemeral configuration.
+///
+/// ```text
+/// let tracker = Builder::private()
+///     .with(|configuration| configuration.core.tracker_policy.max_peer_timeout = 60)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    configuration: Configuration,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            configuration: configuration::ephemeral(),
+        }
+    }
+}
+
+impl Builder {
+    /// A tracker in `public` mode.
+    #[must_use]
+    pub fn public() -> Self {
+        Self {
+            configuration: configuration::ephemeral_public(),
+        }
+    }
+
+    /// A tracker in `private` mode.
+    #[must_use]
+    pub fn private() -> Self {
+        Self {
+            configuration: configuration::ephemeral_private(),
+        }
+    }
+
+    /// A tracker in `listed` mode.
+    #[must_use]
+    pub fn listed() -> Self {
+        Self {
+            configuration: configuration::ephemeral_listed(),
+        }
+    }
+
+    /// A tracker in `private` and `listed` mode.
+    #[must_use]
+    pub fn private_and_listed() -> Self {
+        Self {
+            configuration: configuration::ephemeral_private_and_listed(),
+        }
+    }
+
+    /// It changes the configuration before building the tracker.
+    #[must_use]
+    pub fn with(mut self, change: impl FnOnce(&mut Configuration)) -> Self {
+        change(&mut self.configuration);
+        self
+    }
+
+    /// It returns the configuration the tracker will be built with.
+    #[must_use]
+    pub fn configuration(&self) -> Arc<Configuration> {
+        Arc::new(self.configuration.clone())
+    }
+
+    /// It builds the core tracker.
+    #[must_use]
+    pub fn build(self) -> Arc<Tracker> {
+        initialize_with_configuration(&self.configuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+
+    #[tokio::test]
+    async fn it_should_build_a_tracker_with_the_selected_mode() {
+        assert!(Builder::public().build().is_public());
+        assert!(Builder::private().build().is_private());
+        assert!(Builder::listed().build().is_listed());
+    }
+
+    #[tokio::test]
+    async fn it_should_allow_changing_the_configuration_before_building_the_tracker() {
+        let builder = Builder::public().with(|configuration| configuration.core.max_scrape_torrents = 10);
+
+        assert_eq!(builder.configuration().core.max_scrape_torrents, 10);
+        assert_eq!(builder.build().get_max_scrape_torrents(), 10);
+    }
+}
@@ -1,9 +1,18 @@
 // code-review: should we use macros to return the exact line where the assert fails?
 
 use reqwest::Response;
-use torrust_tracker::servers::apis::v1::context::auth_key::resources::AuthKey;
+use torrust_tracker::servers::apis::v1::context::audit_log::resources::AuditLogEntry;
+use torrust_tracker::servers::apis::v1::context::auth_key::resources::{AuthKey, ExpiredKeys, KeyStats, KeyValidity};
+use torrust_tracker::servers::apis::v1::context::backup::resources::BackupStatus;
+use torrust_tracker::servers::apis::v1::context::dead_letter::resources::DeadLetterQueue;
+use torrust_tracker::servers::apis::v1::context::invitation::resources::InvitationCode;
+use torrust_tracker::servers::apis::v1::context::maintenance::resources::Maintenance;
+use torrust_tracker::servers::apis::v1::context::peer_cleanup::resources::PeerCleanup;
 use torrust_tracker::servers::apis::v1::context::stats::resources::Stats;
+use torrust_tracker::servers::apis::v1::context::torrent::resources::magnet::Magnet;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::torrent::{ListItem, Torrent};
+use torrust_tracker::servers::apis::v1::context::whitelist::resources::WhitelistRule;
+use torrust_tracker::servers::apis::v1::responses::{ErrorCode, ErrorResponse};
 
 // Resource responses
 
@@ -13,6 +22,60 @@ pub async fn assert_stats(response: Response, stats: Stats) {
     assert_eq!(response.json::<Stats>().await.unwrap(), stats);
 }
 
+pub async fn assert_maintenance(response: Response, maintenance: Maintenance) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<Maintenance>().await.unwrap(), maintenance);
+}
+
+pub async fn assert_peer_cleanup(response: Response, peer_cleanup: PeerCleanup) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<PeerCleanup>().await.unwrap(), peer_cleanup);
+}
+
+pub async fn assert_backup_status(response: Response) -> BackupStatus {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<BackupStatus>().await.unwrap()
+}
+
+pub async fn assert_dead_letter_queue(response: Response) -> DeadLetterQueue {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<DeadLetterQueue>().await.unwrap()
+}
+
+pub async fn assert_audit_log(response: Response) -> Vec<AuditLogEntry> {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<Vec<AuditLogEntry>>().await.unwrap()
+}
+
+pub async fn assert_invitation_code(response: Response) -> InvitationCode {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<InvitationCode>().await.unwrap()
+}
+
+pub async fn assert_invitation_codes(response: Response) -> Vec<InvitationCode> {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<Vec<InvitationCode>>().await.unwrap()
+}
+
+pub async fn assert_whitelist_rule(response: Response) -> WhitelistRule {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<WhitelistRule>().await.unwrap()
+}
+
+pub async fn assert_whitelist_rules(response: Response) -> Vec<WhitelistRule> {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<Vec<WhitelistRule>>().await.unwrap()
+}
+
 pub async fn assert_torrent_list(response: Response, torrents: Vec<ListItem>) {
     assert_eq!(response.status(), 200);
     assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
@@ -25,6 +88,12 @@ pub async fn assert_torrent_info(response: Response, torrent: Torrent) {
     assert_eq!(response.json::<Torrent>().await.unwrap(), torrent);
 }
 
+pub async fn assert_magnet(response: Response) -> Magnet {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    response.json::<Magnet>().await.unwrap()
+}
+
 pub async fn assert_auth_key_utf8(response: Response) -> AuthKey {
     assert_eq!(response.status(), 200);
     assert_eq!(
@@ -34,6 +103,33 @@ pub async fn assert_auth_key_utf8(response: Response) -> AuthKey {
     response.json::<AuthKey>().await.unwrap()
 }
 
+pub async fn assert_expired_keys(response: Response) -> ExpiredKeys {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    response.json::<ExpiredKeys>().await.unwrap()
+}
+
+pub async fn assert_key_stats(response: Response) -> KeyStats {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    response.json::<KeyStats>().await.unwrap()
+}
+
+pub async fn assert_key_validity(response: Response) -> KeyValidity {
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    response.json::<KeyValidity>().await.unwrap()
+}
+
 // OK response
 
 pub async fn assert_ok(response: Response) {
@@ -55,41 +151,46 @@ pub async fn assert_ok(response: Response) {
 
 // Error responses
 
-pub async fn assert_bad_request(response: Response, body: &str) {
-    assert_eq!(response.status(), 400);
-    assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
-    assert_eq!(response.text().await.unwrap(), body);
+/// It asserts the response is a json error response with the expected code,
+/// and it returns the error.
+pub async fn assert_error(response: Response, code: ErrorCode) -> ErrorResponse {
+    assert_eq!(response.status(), code.status_code().as_u16());
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+
+    let error = response.json::<ErrorResponse>().await.unwrap();
+    assert_eq!(error.code, code);
+    error
 }
 
-pub async fn assert_bad_request_with_text(response: Response, text: &str) {
-    assert_eq!(response.status(), 400);
-    assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
-    assert!(response.text().await.unwrap().contains(text));
+pub async fn assert_bad_request(response: Response, message: &str) {
+    assert_eq!(assert_error(response, ErrorCode::InvalidParam).await.message, message);
 }
 
 pub async fn assert_unprocessable_content(response: Response, text: &str) {
-    assert_eq!(response.status(), 422);
-    assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
-    assert!(response.text().await.unwrap().contains(text));
+    let error = assert_error(response, ErrorCode::ValidationFailed).await;
+    assert!(error.message.contains(text), "{error:?}");
+}
+
+pub async fn assert_not_enabled(response: Response, message: &str) {
+    assert_eq!(assert_error(response, ErrorCode::NotEnabled).await.message, message);
+}
+
+pub async fn assert_persistence_disabled(response: Response) {
+    assert_error(response, ErrorCode::PersistenceDisabled).await;
 }
 
 pub async fn assert_not_found(response: Response) {
-    assert_eq!(response.status(), 404);
-    // todo: missing header in the response
-    //assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
-    assert_eq!(response.text().await.unwrap(), "");
+    assert_error(response, ErrorCode::NotFound).await;
 }
 
 pub async fn assert_torrent_not_known(response: Response) {
-    assert_eq!(response.status(), 200);
-    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
-    assert_eq!(response.text().await.unwrap(), "\"torrent not known\"");
+    assert_eq!(assert_error(response, ErrorCode::NotFound).await.message, "torrent not known");
 }
 
 pub async fn assert_invalid_infohash_param(response: Response, invalid_infohash: &str) {
     assert_bad_request(
         response,
-        &format!("Invalid URL: invalid infohash param: string \"{invalid_infohash}\", expected a 40 character long string"),
+        &format!("Invalid URL: invalid infohash param: string \"{invalid_infohash}\", expected a 40 character long hex or a 32 character long base32 string"),
     )
     .await;
 }
@@ -99,35 +200,55 @@ pub async fn assert_invalid_auth_key_get_param(response: Response, invalid_auth_
 }
 
 pub async fn assert_invalid_auth_key_post_param(response: Response, invalid_auth_key: &str) {
-    assert_bad_request_with_text(
-        response,
-        &format!("Invalid URL: invalid auth key: string \"{}\"", &invalid_auth_key),
-    )
-    .await;
+    let error = assert_error(response, ErrorCode::ValidationFailed).await;
+
+    assert!(error
+        .message
+        .contains(&format!("Invalid URL: invalid auth key: string \"{}\"", &invalid_auth_key)));
+    assert_eq!(error.details[0].field, "key");
 }
 
 pub async fn assert_unprocessable_auth_key_duration_param(response: Response, _invalid_value: &str) {
-    assert_unprocessable_content(
-        response,
-        "Failed to deserialize the JSON body into the target type: seconds_valid: invalid type",
-    )
-    .await;
+    let error = assert_error(response, ErrorCode::ValidationFailed).await;
+
+    assert!(error
+        .message
+        .contains("Failed to deserialize the JSON body into the target type: seconds_valid: invalid type"));
+    assert_eq!(error.details[0].field, "seconds_valid");
 }
 
 pub async fn assert_invalid_key_duration_param(response: Response, invalid_key_duration: &str) {
-    assert_bad_request(
-        response,
-        &format!("Invalid URL: Cannot parse `\"{invalid_key_duration}\"` to a `u64`"),
-    )
-    .await;
+    let error = assert_error(response, ErrorCode::InvalidParam).await;
+
+    assert_eq!(
+        error.message,
+        format!("Invalid URL: Cannot parse `\"{invalid_key_duration}\"` to a `u64`")
+    );
 }
 
 pub async fn assert_token_not_valid(response: Response) {
-    assert_unhandled_rejection(response, "token not valid").await;
+    assert_eq!(
+        assert_error(response, ErrorCode::InvalidToken).await.message,
+        "token not valid"
+    );
 }
 
 pub async fn assert_unauthorized(response: Response) {
-    assert_unhandled_rejection(response, "unauthorized").await;
+    assert_eq!(assert_error(response, ErrorCode::Unauthorized).await.message, "unauthorized");
+}
+
+pub async fn assert_forbidden(response: Response) {
+    assert_eq!(
+        assert_error(response, ErrorCode::Forbidden).await.message,
+        "admin token required"
+    );
+}
+
+pub async fn assert_insufficient_scope(response: Response) {
+    assert_eq!(
+        assert_error(response, ErrorCode::Forbidden).await.message,
+        "insufficient scope"
+    );
 }
 
 pub async fn assert_failed_to_remove_torrent_from_whitelist(response: Response) {
@@ -142,26 +263,44 @@ pub async fn assert_failed_to_reload_whitelist(response: Response) {
     assert_unhandled_rejection(response, "failed to reload whitelist").await;
 }
 
+pub async fn assert_failed_to_add_whitelist_rule(response: Response) {
+    assert_unhandled_rejection(response, "failed to add whitelist rule").await;
+}
+
 pub async fn assert_failed_to_generate_key(response: Response) {
     assert_unhandled_rejection(response, "failed to generate key").await;
 }
 
+pub async fn assert_failed_to_generate_invitation_code(response: Response) {
+    assert_unhandled_rejection(response, "failed to generate invitation code").await;
+}
+
 pub async fn assert_failed_to_delete_key(response: Response) {
     assert_unhandled_rejection(response, "failed to delete key").await;
 }
 
+pub async fn assert_failed_to_set_key_quota(response: Response) {
+    assert_unhandled_rejection(response, "failed to set key quota").await;
+}
+
 pub async fn assert_failed_to_reload_keys(response: Response) {
     assert_unhandled_rejection(response, "failed to reload keys").await;
 }
 
+pub async fn assert_failed_to_expire_keys(response: Response) {
+    assert_unhandled_rejection(response, "failed to expire keys").await;
+}
+
+pub async fn assert_failed_to_load_audit_log(response: Response) {
+    assert_unhandled_rejection(response, "failed to load audit log").await;
+}
+
 async fn assert_unhandled_rejection(response: Response, reason: &str) {
-    assert_eq!(response.status(), 500);
-    assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
+    let error = assert_error(response, ErrorCode::InternalError).await;
 
-    let reason_text = format!("Unhandled rejection: Err {{ reason: \"{reason}");
-    let response_text = response.text().await.unwrap();
     assert!(
-        response_text.contains(&reason_text),
-        ":\n  response: `\"{response_text}\"`\n  does not contain: `\"{reason_text}\"`."
+        error.message.starts_with(reason),
+        ":\n  message: `\"{}\"`\n  does not start with: `\"{reason}\"`.",
+        error.message
     );
 }
@@ -1,9 +1,16 @@
-use torrust_tracker_test_helpers::configuration;
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use camino::Utf8PathBuf;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::json;
+use torrust_tracker_configuration::{ApiAuth, ApiPermission};
+use torrust_tracker_test_helpers::{configuration, random};
 use tracing::level_filters::LevelFilter;
 
 use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
-use crate::servers::api::v1::asserts::{assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::asserts::{assert_insufficient_scope, assert_token_not_valid, assert_unauthorized};
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::Started;
 
@@ -103,3 +110,109 @@ async fn should_allow_the_token_query_param_to_be_at_any_position_in_the_url_que
 
     env.stop().await;
 }
+
+/// The secret of the key in the static JWKS, base64url encoded in the file.
+const JWKS_SECRET: &[u8] = b"MySigningSecret";
+
+fn configuration_with_jwks() -> torrust_tracker_configuration::Configuration {
+    let path = env::temp_dir().join(format!("jwks_{}.json", random::string(16)));
+
+    std::fs::write(
+        &path,
+        json!({ "keys": [{ "kty": "oct", "kid": "test", "alg": "HS256", "k": "TXlTaWduaW5nU2VjcmV0" }] }).to_string(),
+    )
+    .unwrap();
+
+    let mut configuration = configuration::ephemeral();
+    configuration.http_api.as_mut().unwrap().auth = Some(ApiAuth {
+        jwks_path: Some(Utf8PathBuf::from_path_buf(path).unwrap()),
+        scopes: [
+            ("tracker:read".to_string(), ApiPermission::Access),
+            ("tracker:admin".to_string(), ApiPermission::Admin),
+        ]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    });
+    configuration
+}
+
+fn bearer_token(scope: &str) -> String {
+    let header = Header {
+        kid: Some("test".to_string()),
+        ..Default::default()
+    };
+
+    let exp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 60;
+
+    encode(
+        &header,
+        &json!({ "sub": "alice", "exp": exp, "scope": scope }),
+        &EncodingKey::from_secret(JWKS_SECRET),
+    )
+    .unwrap()
+}
+
+async fn get_with_bearer_token(bind_address: &str, path: &str, token: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!("http://{bind_address}/api/v1/{path}"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn should_authenticate_requests_by_using_a_bearer_token_from_the_external_provider() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_jwks().into()).await;
+
+    let response = get_with_bearer_token(
+        &env.get_connection_info().bind_address,
+        "stats",
+        &bearer_token("openid tracker:read"),
+    )
+    .await;
+
+    assert_eq!(response.status(), 200);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_authenticate_requests_with_a_bearer_token_without_the_configured_scopes() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_jwks().into()).await;
+
+    let response = get_with_bearer_token(&env.get_connection_info().bind_address, "stats", &bearer_token("openid")).await;
+
+    assert_insufficient_scope(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_authenticate_requests_with_a_bearer_token_when_there_is_no_external_provider() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = get_with_bearer_token(
+        &env.get_connection_info().bind_address,
+        "stats",
+        &bearer_token("tracker:read"),
+    )
+    .await;
+
+    assert_token_not_valid(response).await;
+
+    env.stop().await;
+}
@@ -12,7 +12,7 @@ use tracing::level_filters::LevelFilter;
 use crate::common::logging::{tracing_stderr_init, INIT};
 
 #[tokio::test]
-#[ignore]
+#[ignore = "the SSL configuration is not validated yet"]
 #[should_panic = "Could not receive bind_address."]
 async fn should_fail_with_ssl_enabled_and_bad_ssl_config() {
     INIT.call_once(|| {
@@ -0,0 +1,158 @@
+use std::env;
+
+use camino::Utf8PathBuf;
+use torrust_tracker::core::audit;
+use torrust_tracker_test_helpers::{configuration, random};
+use tracing::level_filters::LevelFilter;
+
+use crate::common::http::{Query, QueryParam};
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_audit_log, assert_failed_to_load_audit_log, assert_forbidden, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::{force_database_error, Started};
+
+#[tokio::test]
+async fn should_record_the_requests_that_change_the_tracker_state() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d";
+
+    let client = Client::new(env.get_connection_info());
+
+    client.whitelist_a_torrent(info_hash).await;
+    client.reload_whitelist().await;
+    client.get_tracker_statistics().await;
+
+    let response = Client::new(env.get_admin_connection_info())
+        .get_audit_log(Query::empty())
+        .await;
+
+    let entries = assert_audit_log(response).await;
+
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].method, "GET");
+    assert_eq!(entries[0].path, "/api/v1/whitelist/reload");
+
+    assert_eq!(entries[1].token_label, "admin");
+    assert_eq!(entries[1].source_ip, Some("127.0.0.1".to_string()));
+    assert_eq!(entries[1].method, "POST");
+    assert_eq!(entries[1].path, format!("/api/v1/whitelist/{info_hash}"));
+    assert_eq!(entries[1].status, 200);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_paginating_the_audit_log() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let client = Client::new(env.get_connection_info());
+
+    client.reload_keys().await;
+    client.reload_whitelist().await;
+
+    let response = Client::new(env.get_admin_connection_info())
+        .get_audit_log(Query::params(
+            [QueryParam::new("offset", "1"), QueryParam::new("limit", "1")].to_vec(),
+        ))
+        .await;
+
+    let entries = assert_audit_log(response).await;
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path, "/api/v1/keys/reload");
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_append_the_audit_log_entries_to_the_file_when_it_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let path = Utf8PathBuf::from_path_buf(
+        env::temp_dir()
+            .join(format!("audit_log_{}", random::string(16)))
+            .join("audit.ndjson"),
+    )
+    .unwrap();
+
+    let mut configuration = configuration::ephemeral();
+    if let Some(ref mut http_api) = configuration.http_api {
+        http_api.audit_log_path = Some(path.clone());
+    }
+
+    let env = Started::new(&configuration.into()).await;
+
+    Client::new(env.get_connection_info()).reload_keys().await;
+
+    let entries: Vec<audit::Entry> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path, "/api/v1/keys/reload");
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_audit_log_cannot_be_loaded() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_admin_connection_info())
+        .get_audit_log(Query::empty())
+        .await;
+
+    assert_failed_to_load_audit_log(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_getting_the_audit_log_without_an_admin_token() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_audit_log(Query::empty()).await;
+
+    assert_forbidden(response).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .get_audit_log(Query::empty())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_audit_log(Query::empty())
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
@@ -1,18 +1,24 @@
 use std::time::Duration;
 
 use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 use torrust_tracker::core::auth::Key;
+use torrust_tracker::core::key_quota::Quota;
+use torrust_tracker::servers::apis::v1::context::auth_key::resources::{ExpiredKeys, KeyStats, KeyValidity};
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_auth_key_utf8, assert_failed_to_delete_key, assert_failed_to_generate_key, assert_failed_to_reload_keys,
-    assert_invalid_auth_key_get_param, assert_invalid_auth_key_post_param, assert_ok, assert_token_not_valid,
-    assert_unauthorized, assert_unprocessable_auth_key_duration_param,
+    assert_auth_key_utf8, assert_expired_keys, assert_failed_to_delete_key, assert_failed_to_expire_keys,
+    assert_failed_to_generate_key, assert_failed_to_reload_keys, assert_failed_to_set_key_quota, assert_forbidden,
+    assert_invalid_auth_key_get_param, assert_invalid_auth_key_post_param, assert_key_stats, assert_key_validity,
+    assert_not_found, assert_ok, assert_token_not_valid, assert_unauthorized, assert_unprocessable_auth_key_duration_param,
+    assert_unprocessable_content,
 };
-use crate::servers::api::v1::client::{AddKeyForm, Client};
+use crate::servers::api::v1::client::{AddKeyForm, Client, KeyQuotaForm};
 use crate::servers::api::{force_database_error, Started};
 
 #[tokio::test]
@@ -27,6 +33,7 @@ async fn should_allow_generating_a_new_random_auth_key() {
         .add_auth_key(AddKeyForm {
             opt_key: None,
             seconds_valid: Some(60),
+            bound_to: None,
         })
         .await;
 
@@ -53,6 +60,7 @@ async fn should_allow_uploading_a_preexisting_auth_key() {
         .add_auth_key(AddKeyForm {
             opt_key: Some("Xc1L4PbQJSFGlrgSRZl8wxSFAuMa21z5".to_string()),
             seconds_valid: Some(60),
+            bound_to: None,
         })
         .await;
 
@@ -67,6 +75,50 @@ async fn should_allow_uploading_a_preexisting_auth_key() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_generating_a_new_auth_key_bound_to_an_ip_range() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_auth_key(AddKeyForm {
+            opt_key: None,
+            seconds_valid: Some(60),
+            bound_to: Some("203.0.113.7/24".to_string()),
+        })
+        .await;
+
+    let auth_key_resource = assert_auth_key_utf8(response).await;
+
+    assert_eq!(auth_key_resource.bound_to, Some("203.0.113.0/24".to_string()));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_generating_a_new_auth_key_when_the_ip_range_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_auth_key(AddKeyForm {
+            opt_key: None,
+            seconds_valid: Some(60),
+            bound_to: Some("203.0.113.0/33".to_string()),
+        })
+        .await;
+
+    assert_unprocessable_content(response, "Invalid key binding: \"203.0.113.0/33\"").await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_not_allow_generating_a_new_auth_key_for_unauthenticated_users() {
     INIT.call_once(|| {
@@ -79,6 +131,7 @@ async fn should_not_allow_generating_a_new_auth_key_for_unauthenticated_users()
         .add_auth_key(AddKeyForm {
             opt_key: None,
             seconds_valid: Some(60),
+            bound_to: None,
         })
         .await;
 
@@ -88,6 +141,7 @@ async fn should_not_allow_generating_a_new_auth_key_for_unauthenticated_users()
         .add_auth_key(AddKeyForm {
             opt_key: None,
             seconds_valid: Some(60),
+            bound_to: None,
         })
         .await;
 
@@ -110,6 +164,7 @@ async fn should_fail_when_the_auth_key_cannot_be_generated() {
         .add_auth_key(AddKeyForm {
             opt_key: None,
             seconds_valid: Some(60),
+            bound_to: None,
         })
         .await;
 
@@ -386,6 +441,124 @@ async fn should_not_allow_reloading_keys_for_unauthenticated_users() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_expiring_all_keys_with_an_admin_token() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+    let permanent_key = env.tracker.generate_auth_key(None).await.unwrap();
+
+    let response = Client::new(env.get_admin_connection_info()).expire_all_keys().await;
+
+    assert_eq!(assert_expired_keys(response).await, ExpiredKeys { expired_keys: 2 });
+
+    assert!(env.tracker.authenticate(&expiring_key.key()).await.is_err());
+    assert!(env.tracker.authenticate(&permanent_key.key()).await.is_err());
+
+    // The keys are not restored from the database
+    env.tracker.load_keys_from_database().await.unwrap();
+
+    assert!(env.tracker.authenticate(&expiring_key.key()).await.is_err());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_notify_the_incident_webhook_when_all_keys_are_expired() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let webhook = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+    let mut configuration = configuration::ephemeral();
+    if let Some(ref mut http_api) = configuration.http_api {
+        http_api.incident_webhook_url = Some(format!("http://{}/incident", webhook.local_addr().unwrap()).parse().unwrap());
+    }
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.tracker.generate_auth_key(None).await.unwrap();
+
+    let response = Client::new(env.get_admin_connection_info()).expire_all_keys().await;
+
+    assert_expired_keys(response).await;
+
+    let (mut stream, _) = webhook.accept().await.unwrap();
+
+    let mut request = String::new();
+    let mut buffer = [0; 1024];
+    while !request.ends_with('}') {
+        let read = stream.read(&mut buffer).await.unwrap();
+        assert!(read > 0, "the webhook request should contain a JSON body");
+        request.push_str(&String::from_utf8_lossy(&buffer[..read]));
+    }
+
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+        .await
+        .unwrap();
+
+    assert!(request.starts_with("POST /incident "));
+    assert!(request.contains(r#""action":"expire_all_keys","expired_keys":1,"token_label":"incident-response""#));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_keys_cannot_be_expired() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.generate_auth_key(None).await.unwrap();
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_admin_connection_info()).expire_all_keys().await;
+
+    assert_failed_to_expire_keys(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_expiring_all_keys_without_an_admin_token() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let permanent_key = env.tracker.generate_auth_key(None).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).expire_all_keys().await;
+
+    assert_forbidden(response).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .expire_all_keys()
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .expire_all_keys()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert!(env.tracker.authenticate(&permanent_key.key()).await.is_ok());
+
+    env.stop().await;
+}
+
 mod deprecated_generate_key_endpoint {
 
     use torrust_tracker::core::auth::Key;
@@ -492,3 +665,241 @@ mod deprecated_generate_key_endpoint {
         env.stop().await;
     }
 }
+
+#[tokio::test]
+async fn should_allow_setting_the_quota_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .set_key_quota(
+            &auth_key.key.to_string(),
+            KeyQuotaForm {
+                max_announces_per_day: Some(1000),
+                max_peers_per_day: None,
+            },
+        )
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_key_quota(&auth_key.key),
+        Some(Quota {
+            max_announces_per_day: Some(1000),
+            max_peers_per_day: None,
+        })
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_quota_of_a_key_cannot_be_set() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .set_key_quota(
+            &auth_key.key.to_string(),
+            KeyQuotaForm {
+                max_announces_per_day: Some(1000),
+                max_peers_per_day: None,
+            },
+        )
+        .await;
+
+    assert_failed_to_set_key_quota(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_the_quota_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker
+        .set_key_quota(
+            &auth_key.key,
+            Quota {
+                max_announces_per_day: Some(1000),
+                max_peers_per_day: None,
+            },
+        )
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_key_quota(&auth_key.key.to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_key_quota(&auth_key.key), None);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_quota_consumption_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker
+        .set_key_quota(
+            &auth_key.key,
+            Quota {
+                max_announces_per_day: Some(1000),
+                max_peers_per_day: Some(50_000),
+            },
+        )
+        .unwrap();
+    env.tracker.consume_key_announce(&auth_key.key).unwrap();
+    env.tracker.consume_key_peers(&auth_key.key, 25);
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_stats(&auth_key.key.to_string())
+        .await;
+
+    assert_eq!(
+        assert_key_stats(response).await,
+        KeyStats {
+            key: auth_key.key.to_string(),
+            max_announces_per_day: Some(1000),
+            max_peers_per_day: Some(50_000),
+            announces_today: 1,
+            peers_today: 25,
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_stats_of_a_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_key_stats("INVALID_KEY").await;
+
+    assert_invalid_auth_key_get_param(response, "INVALID_KEY").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_checking_the_validity_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_validity(&auth_key.key.to_string())
+        .await;
+
+    assert_eq!(
+        assert_key_validity(response).await,
+        KeyValidity {
+            key: auth_key.key.to_string(),
+            valid: true,
+            expired: false,
+            quota_exhausted: false,
+            expiry_time: None,
+            bound_to: None,
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_report_a_key_that_has_reached_its_quota_as_not_valid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+    env.tracker
+        .set_key_quota(
+            &auth_key.key,
+            Quota {
+                max_announces_per_day: Some(1),
+                max_peers_per_day: None,
+            },
+        )
+        .unwrap();
+    env.tracker.consume_key_announce(&auth_key.key).unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_validity(&auth_key.key.to_string())
+        .await;
+
+    let key_validity = assert_key_validity(response).await;
+
+    assert!(!key_validity.valid);
+    assert!(key_validity.quota_exhausted);
+
+    // The check does not count against the quota
+    assert_eq!(env.tracker.get_key_usage(&auth_key.key).announces, 1);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_checking_the_validity_of_a_key_that_is_not_registered() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_validity("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ")
+        .await;
+
+    assert_not_found(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_checking_the_validity_of_a_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_key_validity("INVALID_KEY").await;
+
+    assert_invalid_auth_key_get_param(response, "INVALID_KEY").await;
+
+    env.stop().await;
+}
@@ -0,0 +1,97 @@
+use std::env;
+
+use camino::Utf8PathBuf;
+use torrust_tracker_configuration::DatabaseBackup;
+use torrust_tracker_test_helpers::{configuration, random};
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::connection_with_no_token;
+use crate::servers::api::v1::asserts::{assert_backup_status, assert_not_enabled, assert_unauthorized};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+fn configuration_with_database_backups() -> torrust_tracker_configuration::Configuration {
+    let directory = env::temp_dir().join(format!("backups_{}", random::string(16)));
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.database_backup = Some(DatabaseBackup {
+        directory: Utf8PathBuf::from_path_buf(directory).unwrap(),
+        interval: 86_400,
+        retention: 7,
+    });
+    configuration
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_database_backup_status() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_database_backup_status().await;
+
+    let status = assert_backup_status(response).await;
+
+    assert!(!status.enabled);
+    assert_eq!(status.last_backup, None);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_backing_up_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_database_backups().into()).await;
+
+    let response = Client::new(env.get_connection_info()).back_up_database().await;
+
+    let status = assert_backup_status(response).await;
+
+    assert!(status.enabled);
+
+    let last_backup = status.last_backup.unwrap();
+    assert_eq!(last_backup.error, None);
+    assert!(Utf8PathBuf::from(last_backup.file.unwrap()).exists());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_backing_up_the_database_when_the_backups_are_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).back_up_database().await;
+
+    assert_not_enabled(response, "database backups are not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_backing_up_the_database_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_database_backups().into()).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .back_up_database()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert_eq!(env.tracker.get_last_database_backup(), None);
+
+    env.stop().await;
+}
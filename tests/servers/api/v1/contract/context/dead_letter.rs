@@ -0,0 +1,166 @@
+use std::env;
+
+use camino::Utf8PathBuf;
+use torrust_tracker::servers::apis::v1::context::dead_letter::resources::Write;
+use torrust_tracker_configuration::DeadLetterQueue;
+use torrust_tracker_test_helpers::{configuration, random};
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::connection_with_no_token;
+use crate::servers::api::force_database_error;
+use crate::servers::api::v1::asserts::{assert_dead_letter_queue, assert_not_enabled, assert_unauthorized};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+fn configuration_with_dead_letter_queue() -> torrust_tracker_configuration::Configuration {
+    let path = env::temp_dir().join(format!("dead_letters_{}.ndjson", random::string(16)));
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.dead_letter_queue = Some(DeadLetterQueue {
+        path: Utf8PathBuf::from_path_buf(path).unwrap(),
+        // The replay job should not retry the writes during the tests.
+        min_backoff_ms: 3_600_000,
+        max_backoff_ms: 3_600_000,
+        ..Default::default()
+    });
+    configuration
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_dead_letter_queue() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_dead_letters().await;
+
+    let queue = assert_dead_letter_queue(response).await;
+
+    assert!(!queue.enabled);
+    assert!(queue.letters.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_list_the_writes_that_failed_when_the_dead_letter_queue_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_dead_letter_queue().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let auth_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_dead_letters().await;
+
+    let queue = assert_dead_letter_queue(response).await;
+
+    assert!(queue.enabled);
+    assert_eq!(queue.pending, 1);
+    assert_eq!(queue.queued, 1);
+    assert_eq!(
+        queue.letters[0].write,
+        Write::AuthKey {
+            key: auth_key.key.to_string(),
+            valid_until: None,
+        }
+    );
+    assert_eq!(queue.letters[0].attempts, 1);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_replaying_the_dead_letter_queue() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_dead_letter_queue().into()).await;
+
+    force_database_error(&env.tracker);
+
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).replay_dead_letters().await;
+
+    let queue = assert_dead_letter_queue(response).await;
+
+    // The tables are still missing, so the write fails again.
+    assert_eq!(queue.pending, 1);
+    assert_eq!(queue.replayed, 0);
+    assert_eq!(queue.letters[0].attempts, 2);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_draining_the_dead_letter_queue() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_dead_letter_queue().into()).await;
+
+    force_database_error(&env.tracker);
+
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).drain_dead_letters().await;
+
+    let queue = assert_dead_letter_queue(response).await;
+
+    assert_eq!(queue.pending, 0);
+    assert_eq!(queue.dropped, 1);
+    assert!(queue.letters.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_replaying_or_draining_the_dead_letter_queue_when_it_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).replay_dead_letters().await;
+
+    assert_not_enabled(response, "the dead-letter queue is not enabled").await;
+
+    let response = Client::new(env.get_connection_info()).drain_dead_letters().await;
+
+    assert_not_enabled(response, "the dead-letter queue is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_draining_the_dead_letter_queue_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_dead_letter_queue().into()).await;
+
+    force_database_error(&env.tracker);
+
+    env.tracker.generate_permanent_auth_key().await.unwrap();
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .drain_dead_letters()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert_eq!(env.tracker.get_dead_letter_metrics().unwrap().pending, 1);
+
+    env.stop().await;
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::http::Query;
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::v1::asserts::assert_forbidden;
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_stream_the_tracker_events() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let mut response = Client::new(env.get_admin_connection_info())
+        .get("events", Query::empty())
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "text/event-stream");
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let mut body = String::new();
+
+    while !body.contains("event: torrent_created") {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .expect("it should receive the event")
+            .unwrap()
+            .expect("the stream should not end");
+
+        body.push_str(&String::from_utf8_lossy(&chunk));
+    }
+
+    assert!(body.contains(r#"data: {"type":"torrent_created","info_hash":"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"}"#));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_following_the_events_with_a_non_admin_token() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get("events", Query::empty()).await;
+
+    assert_forbidden(response).await;
+
+    env.stop().await;
+}
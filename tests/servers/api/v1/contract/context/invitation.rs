@@ -0,0 +1,267 @@
+use torrust_tracker::core::auth::Key;
+use torrust_tracker_configuration::Registration;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_auth_key_utf8, assert_failed_to_generate_invitation_code, assert_invitation_code, assert_invitation_codes,
+    assert_not_enabled, assert_not_found, assert_ok, assert_token_not_valid, assert_unauthorized, assert_unprocessable_content,
+};
+use crate::servers::api::v1::client::{Client, RegisterForm};
+use crate::servers::api::{force_database_error, Started};
+
+fn configuration_with_registration(key_seconds_valid: Option<u64>) -> torrust_tracker_configuration::Configuration {
+    let mut configuration = configuration::ephemeral_private();
+    if let Some(ref mut http_api) = configuration.http_api {
+        http_api.registration = Some(Registration { key_seconds_valid });
+    }
+    configuration
+}
+
+#[tokio::test]
+async fn should_allow_generating_a_new_invitation_code() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).generate_invitation_code().await;
+
+    let invitation_code = assert_invitation_code(response).await;
+
+    let codes = env.tracker.get_invitation_codes().unwrap();
+
+    assert_eq!(codes.len(), 1);
+    assert_eq!(codes[0].to_string(), invitation_code.code);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_unused_invitation_codes() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_invitation_codes().await;
+
+    let codes = assert_invitation_codes(response).await;
+
+    assert_eq!(codes.len(), 1);
+    assert_eq!(codes[0].code, code.to_string());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_revoking_an_invitation_code() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .delete_invitation_code(&code.to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_invitation_codes().unwrap().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_revoking_an_invitation_code_when_the_code_is_not_valid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .delete_invitation_code("INVALID-CODE")
+        .await;
+
+    assert_eq!(response.status(), 400);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_invitation_code_cannot_be_persisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).generate_invitation_code().await;
+
+    assert_failed_to_generate_invitation_code(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_managing_the_invitation_codes_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .generate_invitation_code()
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_invitation_codes()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_new_users_to_register_with_an_invitation_code_without_an_access_token() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_registration(Some(3600)).into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .register(RegisterForm { code: code.to_string() })
+        .await;
+
+    let auth_key_resource = assert_auth_key_utf8(response).await;
+
+    assert!(auth_key_resource.expiry_time.is_some());
+    assert!(env
+        .tracker
+        .authenticate(&auth_key_resource.key.parse::<Key>().unwrap())
+        .await
+        .is_ok());
+    assert!(env.tracker.get_invitation_codes().unwrap().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_issue_permanent_keys_when_the_key_lifetime_is_not_configured() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_registration(None).into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .register(RegisterForm { code: code.to_string() })
+        .await;
+
+    let auth_key_resource = assert_auth_key_utf8(response).await;
+
+    assert!(auth_key_resource.expiry_time.is_none());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_using_an_invitation_code_twice() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_registration(None).into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let client = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()));
+
+    assert_auth_key_utf8(client.register(RegisterForm { code: code.to_string() }).await).await;
+
+    let response = client.register(RegisterForm { code: code.to_string() }).await;
+
+    assert_not_found(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_registering_with_an_unknown_invitation_code() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_registration(None).into()).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .register(RegisterForm {
+            code: "IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82".to_string(),
+        })
+        .await;
+
+    assert_not_found(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_registering_with_a_malformed_invitation_code() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_registration(None).into()).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .register(RegisterForm {
+            code: "INVALID-CODE".to_string(),
+        })
+        .await;
+
+    assert_unprocessable_content(response, "invalid invitation code").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_registering_when_the_self_registration_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let code = env.tracker.generate_invitation_code().unwrap();
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .register(RegisterForm { code: code.to_string() })
+        .await;
+
+    assert_not_enabled(response, "self-registration is not enabled").await;
+
+    // The code is not consumed
+    assert_eq!(env.tracker.get_invitation_codes().unwrap().len(), 1);
+
+    env.stop().await;
+}
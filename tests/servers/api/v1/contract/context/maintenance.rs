@@ -0,0 +1,90 @@
+use torrust_tracker::servers::apis::v1::context::maintenance::resources::Maintenance;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{assert_maintenance, assert_ok, assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_allow_getting_the_maintenance_mode() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_maintenance().await;
+
+    assert_maintenance(
+        response,
+        Maintenance {
+            enabled: false,
+            retry_in: 10,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_enabling_the_maintenance_mode() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).enable_maintenance().await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.is_in_maintenance());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_disabling_the_maintenance_mode() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.set_maintenance(true);
+
+    let response = Client::new(env.get_connection_info()).disable_maintenance().await;
+
+    assert_ok(response).await;
+    assert!(!env.tracker.is_in_maintenance());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_changing_the_maintenance_mode_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .enable_maintenance()
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .enable_maintenance()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert!(!env.tracker.is_in_maintenance());
+
+    env.stop().await;
+}
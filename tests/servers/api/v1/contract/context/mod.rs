@@ -1,5 +1,15 @@
+pub mod audit_log;
 pub mod auth_key;
+pub mod backup;
+pub mod configuration;
+pub mod dead_letter;
+pub mod events;
 pub mod health_check;
+pub mod invitation;
+pub mod maintenance;
+pub mod peer_cleanup;
+pub mod policy;
+pub mod static_peer;
 pub mod stats;
 pub mod torrent;
 pub mod whitelist;
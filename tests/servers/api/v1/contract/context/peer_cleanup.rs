@@ -0,0 +1,99 @@
+use torrust_tracker::core::peer_cleanup::Settings;
+use torrust_tracker::servers::apis::v1::context::peer_cleanup::resources::PeerCleanup;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{assert_ok, assert_peer_cleanup, assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::client::{Client, PeerCleanupForm};
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_allow_getting_the_peer_cleanup_settings() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.inactive_peer_cleanup_interval = 60;
+    configuration.core.tracker_policy.max_peer_timeout = 120;
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_peer_cleanup().await;
+
+    assert_peer_cleanup(
+        response,
+        PeerCleanup {
+            inactive_peer_cleanup_interval: 60,
+            max_peer_timeout: 120,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_changing_the_peer_cleanup_settings() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.inactive_peer_cleanup_interval = 60;
+    configuration.core.tracker_policy.max_peer_timeout = 120;
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .set_peer_cleanup(PeerCleanupForm {
+            inactive_peer_cleanup_interval: None,
+            max_peer_timeout: Some(3600),
+        })
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_peer_cleanup(),
+        Settings {
+            inactive_peer_cleanup_interval: 60,
+            max_peer_timeout: 3600,
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_changing_the_peer_cleanup_settings_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let settings = env.tracker.get_peer_cleanup();
+
+    let form = || PeerCleanupForm {
+        inactive_peer_cleanup_interval: Some(1),
+        max_peer_timeout: Some(1),
+    };
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .set_peer_cleanup(form())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .set_peer_cleanup(form())
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert_eq!(env.tracker.get_peer_cleanup(), settings);
+
+    env.stop().await;
+}
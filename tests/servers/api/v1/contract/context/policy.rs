@@ -0,0 +1,157 @@
+use std::str::FromStr;
+
+use torrust_tracker::core::torrent::policy::TorrentPolicy;
+use torrust_tracker::servers::apis::v1::context::policy::resources;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_bad_request, assert_invalid_infohash_param, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_allow_setting_the_policy_for_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(env.get_connection_info())
+        .set_torrent_policy(&info_hash, "seed-only")
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_torrent_policy(&InfoHash::from_str(&info_hash).unwrap()).await,
+        Some(TorrentPolicy::SeedOnly)
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_torrent_policies() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker
+        .set_torrent_policy(&info_hash, TorrentPolicy::Frozen)
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_torrent_policies().await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Vec<resources::TorrentPolicy>>().await.unwrap(),
+        vec![resources::TorrentPolicy {
+            info_hash: info_hash.to_hex_string(),
+            policy: TorrentPolicy::Frozen,
+        }]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_the_policy_for_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker
+        .set_torrent_policy(&info_hash, TorrentPolicy::Frozen)
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrent_policy(&info_hash.to_hex_string())
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_torrent_policy(&info_hash).await, None);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_setting_the_policy_for_a_torrent_when_the_provided_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .set_torrent_policy("INVALID", "seed-only")
+        .await;
+
+    assert_invalid_infohash_param(response, "INVALID").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_setting_an_unknown_policy_for_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .set_torrent_policy("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", "leech-only")
+        .await;
+
+    assert_bad_request(
+        response,
+        "Invalid URL: invalid torrent policy param: \"leech-only\", expected \"seed-only\" or \"frozen\"",
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_setting_the_policy_for_a_torrent_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .set_torrent_policy(&info_hash, "frozen")
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .set_torrent_policy(&info_hash, "frozen")
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert!(env.tracker.get_torrent_policies().await.is_empty());
+
+    env.stop().await;
+}
@@ -0,0 +1,157 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use torrust_tracker::servers::apis::v1::context::static_peer::resources;
+use torrust_tracker_primitives::info_hash::InfoHash;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_bad_request, assert_invalid_infohash_param, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::Started;
+
+#[tokio::test]
+async fn should_allow_adding_a_static_peer_to_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .add_static_peer(&info_hash.to_hex_string(), "126.0.0.1:8080")
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_static_peers().await,
+        vec![(info_hash, vec![SocketAddr::from_str("126.0.0.1:8080").unwrap()])]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_static_peers() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker
+        .add_static_peer(&info_hash, &SocketAddr::from_str("126.0.0.1:8080").unwrap())
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_static_peers().await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Vec<resources::StaticPeers>>().await.unwrap(),
+        vec![resources::StaticPeers {
+            info_hash: info_hash.to_hex_string(),
+            peers: vec!["126.0.0.1:8080".to_owned()],
+        }]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_static_peer_from_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.tracker
+        .add_static_peer(&info_hash, &SocketAddr::from_str("126.0.0.1:8080").unwrap())
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_static_peer(&info_hash.to_hex_string(), "126.0.0.1:8080")
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_static_peers().await.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_static_peer_when_the_provided_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_static_peer("INVALID", "126.0.0.1:8080")
+        .await;
+
+    assert_invalid_infohash_param(response, "INVALID").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_static_peer_when_the_provided_peer_address_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_static_peer("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", "126.0.0.1")
+        .await;
+
+    assert_bad_request(
+        response,
+        "Invalid URL: invalid peer address param: \"126.0.0.1\", expected an IP and a port",
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_adding_a_static_peer_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_static_peer(&info_hash, "126.0.0.1:8080")
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .add_static_peer(&info_hash, "126.0.0.1:8080")
+        .await;
+
+    assert_unauthorized(response).await;
+
+    assert!(env.tracker.get_static_peers().await.is_empty());
+
+    env.stop().await;
+}
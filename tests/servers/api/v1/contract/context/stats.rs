@@ -1,14 +1,27 @@
+use std::env;
 use std::str::FromStr;
 
-use torrust_tracker::servers::apis::v1::context::stats::resources::Stats;
+use camino::Utf8PathBuf;
+use torrust_tracker::core::announce_journal::RECORD_SIZE;
+use torrust_tracker::servers::apis::v1::context::stats::resources::{
+    AnnounceJournalStats, AsyncAnnounceStats, ConsistencyStats, DatabaseAuthorizationStats, HealthScoreBucket, RuntimeStats,
+    Stats, SwarmSizeBucket,
+};
+use torrust_tracker::servers::apis::v1::responses::ErrorCode;
+use torrust_tracker_configuration::{
+    AnnounceJournal, AsyncAnnounce, ConsistencyCheck, DatabaseAuthorization, ReadSnapshot, VirtualTracker,
+};
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer::fixture::PeerBuilder;
-use torrust_tracker_test_helpers::configuration;
+use torrust_tracker_test_helpers::{configuration, random};
 use tracing::level_filters::LevelFilter;
 
+use crate::common::http::Query;
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
-use crate::servers::api::v1::asserts::{assert_stats, assert_token_not_valid, assert_unauthorized};
+use crate::servers::api::v1::asserts::{
+    assert_error, assert_not_enabled, assert_stats, assert_token_not_valid, assert_unauthorized,
+};
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::Started;
 
@@ -37,15 +50,48 @@ async fn should_allow_getting_tracker_statistics() {
             tcp4_connections_handled: 0,
             tcp4_announces_handled: 0,
             tcp4_scrapes_handled: 0,
+            tcp4_scrapes_exceeding_limit: 0,
             tcp6_connections_handled: 0,
             tcp6_announces_handled: 0,
             tcp6_scrapes_handled: 0,
+            tcp6_scrapes_exceeding_limit: 0,
+            tcp_announce_responses_under_128_bytes: 0,
+            tcp_announce_responses_under_512_bytes: 0,
+            tcp_announce_responses_under_2048_bytes: 0,
+            tcp_announce_responses_over_2048_bytes: 0,
+            tcp_announce_responses_padded: 0,
+            tcp_announces_with_unknown_event: 0,
+            tcp_scrape_duplicate_info_hashes: 0,
+            tcp_requests_with_uri_too_long: 0,
+            tcp_requests_with_headers_too_large: 0,
+            tcp_announces_with_deadline_exceeded: 0,
+            tcp_scrapes_with_deadline_exceeded: 0,
+            tcp_announces_with_key_bound_to_another_address: 0,
             udp4_connections_handled: 0,
             udp4_announces_handled: 0,
             udp4_scrapes_handled: 0,
+            udp4_scrapes_exceeding_limit: 0,
+            udp4_connection_ids_used: 0,
+            udp4_connection_ids_unused: 0,
+            udp4_announces_with_expired_connection_id: 0,
+            udp4_connect_to_announce_conversion_rate: 0.0,
+            udp4_expired_connection_id_ratio: 0.0,
             udp6_connections_handled: 0,
             udp6_announces_handled: 0,
             udp6_scrapes_handled: 0,
+            udp6_scrapes_exceeding_limit: 0,
+            udp6_connection_ids_used: 0,
+            udp6_connection_ids_unused: 0,
+            udp6_announces_with_expired_connection_id: 0,
+            udp6_connect_to_announce_conversion_rate: 0.0,
+            udp6_expired_connection_id_ratio: 0.0,
+            udp_announces_with_unknown_event: 0,
+            udp_scrape_duplicate_info_hashes: 0,
+            udp_negative_cache_hits: 0,
+            udp_announces_with_deadline_exceeded: 0,
+            udp_scrapes_with_deadline_exceeded: 0,
+            torrents_created: 1,
+            torrents_removed: 0,
         },
     )
     .await;
@@ -53,6 +99,120 @@ async fn should_allow_getting_tracker_statistics() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_tracker_statistics_as_flat_key_value_lines() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_in_format("flat")
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
+
+    let body = response.text().await.unwrap();
+
+    assert!(body.starts_with("torrents 1\nseeders 1\ncompleted 0\nleechers 0\n"));
+    assert_eq!(body.lines().count(), 49);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_tracker_statistics_in_the_prometheus_text_format() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_in_format("prometheus")
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4; charset=utf-8"
+    );
+
+    let body = response.text().await.unwrap();
+
+    assert!(body.starts_with("# TYPE torrust_tracker_torrents gauge\ntorrust_tracker_torrents 0\n"));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_only_the_selected_tracker_statistics() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_with_fields("torrents,seeders")
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<serde_json::Value>().await.unwrap(),
+        serde_json::json!({ "torrents": 1, "seeders": 1 })
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_compress_the_tracker_statistics_when_the_client_accepts_gzip() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_accepting_encoding("gzip")
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_tracker_statistics_in_an_unknown_format() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_in_format("xml")
+        .await;
+
+    assert_error(response, ErrorCode::InvalidParam).await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_not_allow_getting_tracker_statistics_for_unauthenticated_users() {
     INIT.call_once(|| {
@@ -75,3 +235,351 @@ async fn should_not_allow_getting_tracker_statistics_for_unauthenticated_users()
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_getting_the_runtimes_statistics() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_runtimes_statistics().await;
+
+    assert_eq!(response.status(), 200);
+
+    let runtimes: Vec<RuntimeStats> = response.json().await.unwrap();
+
+    assert_eq!(runtimes.first().map(|runtime| runtime.name.as_str()), Some("main"));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_swarm_sizes_when_the_read_snapshot_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.read_snapshot = Some(ReadSnapshot::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    env.tracker.refresh_read_snapshot();
+
+    let response = Client::new(env.get_connection_info()).get_swarm_sizes().await;
+
+    assert_eq!(response.status(), 200);
+
+    let buckets: Vec<SwarmSizeBucket> = response.json().await.unwrap();
+
+    assert_eq!(
+        buckets[1],
+        SwarmSizeBucket {
+            min: 1,
+            max: Some(10),
+            torrents: 1
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_swarm_sizes_when_the_read_snapshot_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_swarm_sizes().await;
+
+    assert_not_enabled(response, "the read snapshot is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_health_scores_when_the_read_snapshot_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.read_snapshot = Some(ReadSnapshot::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::seeder().into(),
+    );
+
+    env.tracker.refresh_read_snapshot();
+
+    let response = Client::new(env.get_connection_info()).get_health_scores().await;
+
+    assert_eq!(response.status(), 200);
+
+    let buckets: Vec<HealthScoreBucket> = response.json().await.unwrap();
+
+    assert_eq!(
+        buckets[4],
+        HealthScoreBucket {
+            min: 80,
+            max: 100,
+            torrents: 1
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_health_scores_when_the_read_snapshot_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_health_scores().await;
+
+    assert_not_enabled(response, "the read snapshot is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_asynchronous_announce_statistics_when_the_queue_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.async_announce = Some(AsyncAnnounce { queue_capacity: 100 });
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_async_announce_statistics().await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<AsyncAnnounceStats>().await.unwrap(),
+        AsyncAnnounceStats {
+            queue_depth: 0,
+            queue_capacity: 100,
+            staleness_ms: 0,
+            overflows: 0
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_asynchronous_announce_statistics_when_the_queue_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_async_announce_statistics().await;
+
+    assert_not_enabled(response, "the asynchronous announce queue is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_announce_journal_statistics_when_the_journal_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.announce_journal = Some(AnnounceJournal {
+        path: Utf8PathBuf::from_path_buf(env::temp_dir().join(format!("announce_journal_{}.bin", random::string(16)))).unwrap(),
+        max_size_bytes: 100 * RECORD_SIZE as u64,
+        ..Default::default()
+    });
+
+    let env = Started::new(&configuration.into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_announce_journal_statistics().await;
+
+    assert_eq!(response.status(), 200);
+
+    let stats = response.json::<AnnounceJournalStats>().await.unwrap();
+
+    assert_eq!(stats.capacity, 100);
+    assert_eq!(stats.appended, 0);
+    assert_eq!(stats.replayed, 0);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_announce_journal_statistics_when_the_journal_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_announce_journal_statistics().await;
+
+    assert_not_enabled(response, "the announce journal is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_last_consistency_check_when_it_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.consistency_check = Some(ConsistencyCheck::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    env.tracker.check_consistency();
+
+    let response = Client::new(env.get_connection_info()).get_consistency_check().await;
+
+    assert_eq!(response.status(), 200);
+
+    let stats = response.json::<ConsistencyStats>().await.unwrap();
+
+    assert_eq!(stats.checks, 1);
+    assert_eq!(stats.inconsistencies, 0);
+
+    let last_report = stats.last_report.unwrap();
+
+    assert_eq!(last_report.torrents, 1);
+    assert!(last_report.inconsistencies.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_last_consistency_check_when_it_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_consistency_check().await;
+
+    assert_not_enabled(response, "the consistency check is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_database_authorization_statistics_when_it_is_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral_listed();
+    configuration.core.database_authorization = Some(DatabaseAuthorization::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    // The torrent is not whitelisted, so it's looked up in the database
+    assert!(env
+        .tracker
+        .authorize(&InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap())
+        .await
+        .is_err());
+
+    let response = Client::new(env.get_connection_info())
+        .get_database_authorization_statistics()
+        .await;
+
+    assert_eq!(response.status(), 200);
+
+    let stats = response.json::<DatabaseAuthorizationStats>().await.unwrap();
+
+    assert_eq!(stats.queue_depth, 0);
+    assert_eq!(stats.queue_capacity, 1024);
+    assert_eq!(stats.lookups, 1);
+    assert_eq!(stats.queries, 1);
+    assert_eq!(stats.overflows, 0);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_database_authorization_statistics_when_it_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_database_authorization_statistics()
+        .await;
+
+    assert_not_enabled(response, "the database authorization is not enabled").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_statistics_of_a_virtual_tracker() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.virtual_trackers = Some(vec![VirtualTracker {
+        name: "private".to_string(),
+        hosts: vec![],
+        core: configuration::ephemeral_private().core,
+    }]);
+
+    let env = Started::new(&configuration.into()).await;
+
+    // The peer is added to the main tracker, not to the virtual tracker
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get("trackers/private/stats", Query::empty())
+        .await;
+
+    assert_eq!(response.status(), 200);
+
+    let stats = response.json::<Stats>().await.unwrap();
+
+    assert_eq!(stats.torrents, 0);
+
+    let response = Client::new(env.get_connection_info())
+        .get("trackers/unknown/stats", Query::empty())
+        .await;
+
+    assert_eq!(response.status(), 404);
+
+    env.stop().await;
+}
@@ -1,9 +1,12 @@
 use std::str::FromStr;
 
+use torrust_tracker::servers::apis::v1::context::torrent::resources::changes;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::peer::Peer;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::torrent::{self, Torrent};
+use torrust_tracker_configuration::SwarmChanges;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
@@ -11,8 +14,8 @@ use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_bad_request, assert_invalid_infohash_param, assert_not_found, assert_token_not_valid, assert_torrent_info,
-    assert_torrent_list, assert_torrent_not_known, assert_unauthorized,
+    assert_bad_request, assert_invalid_infohash_param, assert_magnet, assert_not_enabled, assert_not_found,
+    assert_token_not_valid, assert_torrent_info, assert_torrent_list, assert_torrent_not_known, assert_unauthorized,
 };
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::v1::contract::fixtures::{
@@ -41,6 +44,7 @@ async fn should_allow_getting_all_torrents() {
             seeders: 1,
             completed: 0,
             leechers: 0,
+            partial_seeds: 0,
         }],
     )
     .await;
@@ -48,6 +52,71 @@ async fn should_allow_getting_all_torrents() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_the_torrents_as_newline_delimited_json() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash_1 = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+    let info_hash_2 = InfoHash::from_str("0b3aea4adc213ce32295be85d3883a63bca25446").unwrap();
+
+    env.add_torrent_peer(&info_hash_1, &PeerBuilder::default().into());
+    env.add_torrent_peer(&info_hash_2, &PeerBuilder::default().into());
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrents(Query::params([QueryParam::new("format", "ndjson")].to_vec()))
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/x-ndjson");
+
+    let torrents: Vec<torrent::ListItem> = response
+        .text()
+        .await
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(
+        torrents.iter().map(|torrent| torrent.info_hash.as_str()).collect::<Vec<_>>(),
+        vec![
+            "0b3aea4adc213ce32295be85d3883a63bca25446",
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"
+        ]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_only_the_selected_fields_of_the_torrents() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.add_torrent_peer(&info_hash, &PeerBuilder::default().into());
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrents(Query::params([QueryParam::new("fields", "info_hash,seeders")].to_vec()))
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<serde_json::Value>().await.unwrap(),
+        serde_json::json!([{ "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", "seeders": 1 }])
+    );
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_limiting_the_torrents_in_the_result() {
     INIT.call_once(|| {
@@ -74,6 +143,7 @@ async fn should_allow_limiting_the_torrents_in_the_result() {
             seeders: 1,
             completed: 0,
             leechers: 0,
+            partial_seeds: 0,
         }],
     )
     .await;
@@ -107,6 +177,7 @@ async fn should_allow_the_torrents_result_pagination() {
             seeders: 1,
             completed: 0,
             leechers: 0,
+            partial_seeds: 0,
         }],
     )
     .await;
@@ -146,12 +217,14 @@ async fn should_allow_getting_a_list_of_torrents_providing_infohashes() {
                 seeders: 1,
                 completed: 0,
                 leechers: 0,
+                partial_seeds: 0,
             },
             torrent::ListItem {
                 info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(), // DevSkim: ignore DS173237
                 seeders: 1,
                 completed: 0,
                 leechers: 0,
+                partial_seeds: 0,
             },
         ],
     )
@@ -219,7 +292,7 @@ async fn should_fail_getting_torrents_when_the_info_hash_parameter_is_invalid()
 
         assert_bad_request(
             response,
-            &format!("Invalid URL: invalid infohash param: string \"{invalid_info_hash}\", expected a 40 character long string"),
+            &format!("Invalid URL: invalid infohash param: string \"{invalid_info_hash}\", expected a 40 character long hex or a 32 character long base32 string"),
         )
         .await;
     }
@@ -250,6 +323,113 @@ async fn should_not_allow_getting_torrents_for_unauthenticated_users() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_the_stale_torrents() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let stale_info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+    let active_info_hash = InfoHash::from_str("0b3aea4adc213ce32295be85d3883a63bca25446").unwrap();
+
+    env.add_torrent_peer(
+        &stale_info_hash,
+        &PeerBuilder::default()
+            .last_updated_on(DurationSinceUnixEpoch::new(1_669_397_478, 0))
+            .into(),
+    );
+    env.add_torrent_peer(&active_info_hash, &PeerBuilder::default().into());
+
+    let response = Client::new(env.get_connection_info())
+        .get_stale_torrents(Query::params([QueryParam::new("inactive_for", "86400")].to_vec()))
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Vec<torrent::StaleListItem>>().await.unwrap(),
+        vec![torrent::StaleListItem {
+            info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
+            seeders: 1,
+            completed: 0,
+            leechers: 0,
+            last_announce_at: 1_669_397_478,
+        }]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_stale_torrents_when_the_inactive_for_query_parameter_cannot_be_parsed() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let invalid_values = [" ", "-1", "1.1", "INVALID"];
+
+    for invalid_value in &invalid_values {
+        let response = Client::new(env.get_connection_info())
+            .get_stale_torrents(Query::params([QueryParam::new("inactive_for", invalid_value)].to_vec()))
+            .await;
+
+        assert_bad_request(response, "Failed to deserialize query string: invalid digit found in string").await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_torrents_that_changed_since_a_cursor() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.swarm_changes = Some(SwarmChanges::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.tracker
+        .record_swarm_change(&InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap());
+    env.tracker
+        .record_swarm_change(&InfoHash::from_str("0b3aea4adc213ce32295be85d3883a63bca25446").unwrap());
+
+    let response = Client::new(env.get_connection_info())
+        .get_swarm_changes(Query::params([QueryParam::new("since", "1")].to_vec()))
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<changes::SwarmChanges>().await.unwrap(),
+        changes::SwarmChanges {
+            cursor: 2,
+            reset: false,
+            info_hashes: vec!["0b3aea4adc213ce32295be85d3883a63bca25446".to_string()],
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_swarm_changes_when_they_are_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_swarm_changes(Query::empty()).await;
+
+    assert_not_enabled(response, "swarm changes are not enabled").await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_getting_a_torrent_info() {
     INIT.call_once(|| {
@@ -275,7 +455,241 @@ async fn should_allow_getting_a_torrent_info() {
             seeders: 1,
             completed: 0,
             leechers: 0,
+            partial_seeds: 0,
+            peers: Some(vec![Peer::from(peer)]),
+            corrupt: 0,
+            redundant: 0,
+            peers_with_corrupt_data: 0,
+            reachability_ratio: None,
+            health: None,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_include_the_corrupt_and_redundant_bytes_reported_by_the_peers_in_the_torrent_info() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    let peer = PeerBuilder::default()
+        .with_corrupt_bytes(1024)
+        .with_redundant_bytes(2048)
+        .into();
+
+    env.add_torrent_peer(&info_hash, &peer);
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent(&info_hash.to_string())
+        .await;
+
+    assert_torrent_info(
+        response,
+        Torrent {
+            info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
+            seeders: 1,
+            completed: 0,
+            leechers: 0,
+            partial_seeds: 0,
+            peers: Some(vec![Peer::from(peer)]),
+            corrupt: 1024,
+            redundant: 2048,
+            peers_with_corrupt_data: 1,
+            reachability_ratio: None,
+            health: None,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_hash_the_peer_ids_of_the_torrent_info_when_the_peer_id_hash_salt_is_set() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+
+    if let Some(ref mut http_api) = configuration.http_api {
+        http_api.peer_id_hash_salt = Some("MyPeerIdHashSalt".to_string());
+    }
+
+    let env = Started::new(&configuration.into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    let peer = PeerBuilder::default().into();
+
+    env.add_torrent_peer(&info_hash, &peer);
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent(&info_hash.to_string())
+        .await;
+
+    assert_torrent_info(
+        response,
+        Torrent {
+            info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
+            seeders: 1,
+            completed: 0,
+            leechers: 0,
+            partial_seeds: 0,
+            peers: Some(vec![Peer::with_hashed_id(peer, "MyPeerIdHashSalt")]),
+            corrupt: 0,
+            redundant: 0,
+            peers_with_corrupt_data: 0,
+            reachability_ratio: None,
+            health: None,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+fn configuration_with_announce_urls() -> torrust_tracker_configuration::Configuration {
+    let mut configuration = configuration::ephemeral();
+
+    if let Some(ref mut http_api) = configuration.http_api {
+        http_api.announce_urls = vec![
+            "udp://tracker.example.com:6969/announce".to_string(),
+            "https://tracker.example.com/announce/{key}".to_string(),
+        ];
+    }
+
+    configuration
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_magnet_link_of_a_torrent_with_the_tracker_announce_urls() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_announce_urls().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent_magnet(
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+            Query::params([QueryParam::new("name", "ubuntu")].to_vec()),
+        )
+        .await;
+
+    let magnet = assert_magnet(response).await;
+
+    assert_eq!(magnet.info_hash, "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d");
+    assert_eq!(
+        magnet.magnet_uri,
+        "magnet:?xt=urn:btih:9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d&dn=ubuntu\
+         &tr=udp%3A%2F%2Ftracker%2Eexample%2Ecom%3A6969%2Fannounce\
+         &tr=https%3A%2F%2Ftracker%2Eexample%2Ecom%2Fannounce%2F%7Bkey%7D"
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_replace_the_key_placeholder_in_the_magnet_link_announce_urls() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_announce_urls().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent_magnet(
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+            Query::params([QueryParam::new("key", "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6")].to_vec()),
+        )
+        .await;
+
+    let magnet = assert_magnet(response).await;
+
+    assert!(magnet
+        .magnet_uri
+        .ends_with("&tr=https%3A%2F%2Ftracker%2Eexample%2Ecom%2Fannounce%2FxqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6"));
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_magnet_link_of_a_torrent_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration_with_announce_urls().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent_magnet(
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+            Query::params([QueryParam::new("key", "INVALID-KEY")].to_vec()),
+        )
+        .await;
+
+    assert_bad_request(response, "Invalid auth key id param \"INVALID-KEY\"").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_magnet_link_of_a_torrent_when_there_are_no_announce_urls() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent_magnet("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d", Query::default())
+        .await;
+
+    assert_not_enabled(response, "magnet links are not enabled: there are no announce URLs").await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_a_torrent_info_using_the_base32_info_hash() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    let peer = PeerBuilder::default().into();
+
+    env.add_torrent_peer(&info_hash, &peer);
+
+    let response = Client::new(env.get_connection_info())
+        .get_torrent("TYBBPUH2OHEHGMWNRP45X2V4WLBM6PCN")
+        .await;
+
+    assert_torrent_info(
+        response,
+        Torrent {
+            info_hash: "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(),
+            seeders: 1,
+            completed: 0,
+            leechers: 0,
+            partial_seeds: 0,
             peers: Some(vec![Peer::from(peer)]),
+            corrupt: 0,
+            redundant: 0,
+            peers_with_corrupt_data: 0,
+            reachability_ratio: None,
+            health: None,
         },
     )
     .await;
@@ -1,21 +1,196 @@
 use std::str::FromStr;
 
+use torrust_tracker::servers::apis::v1::context::whitelist::resources::WhitelistMembership;
+use torrust_tracker_configuration::Driver;
 use torrust_tracker_primitives::info_hash::InfoHash;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
+use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_failed_to_reload_whitelist, assert_failed_to_remove_torrent_from_whitelist, assert_failed_to_whitelist_torrent,
-    assert_invalid_infohash_param, assert_not_found, assert_ok, assert_token_not_valid, assert_unauthorized,
+    assert_failed_to_add_whitelist_rule, assert_failed_to_reload_whitelist, assert_failed_to_remove_torrent_from_whitelist,
+    assert_failed_to_whitelist_torrent, assert_invalid_infohash_param, assert_not_found, assert_ok, assert_persistence_disabled,
+    assert_token_not_valid, assert_unauthorized, assert_unprocessable_content, assert_whitelist_rule, assert_whitelist_rules,
 };
-use crate::servers::api::v1::client::Client;
+use crate::servers::api::v1::client::{AddWhitelistRuleForm, Client, WhitelistContainsForm};
 use crate::servers::api::v1::contract::fixtures::{
     invalid_infohashes_returning_bad_request, invalid_infohashes_returning_not_found,
 };
 use crate::servers::api::{force_database_error, Started};
 
+#[tokio::test]
+async fn should_allow_exporting_the_whitelist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    for info_hash in [
+        "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+        "0b3aea4adc213ce32295be85d3883a63bca25446",
+    ] {
+        env.tracker
+            .add_torrent_to_whitelist(&InfoHash::from_str(info_hash).unwrap())
+            .await
+            .unwrap();
+    }
+
+    let response = Client::new(env.get_connection_info()).get_whitelist(Query::empty()).await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Vec<String>>().await.unwrap(),
+        vec![
+            "0b3aea4adc213ce32295be85d3883a63bca25446".to_owned(),
+            "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned()
+        ]
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get_whitelist(Query::params([QueryParam::new("format", "ndjson")].to_vec()))
+        .await;
+
+    assert_eq!(
+        response.text().await.unwrap(),
+        "\"0b3aea4adc213ce32295be85d3883a63bca25446\"\n\"9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d\"\n"
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_checking_whether_many_torrents_are_whitelisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    env.tracker
+        .add_torrent_to_whitelist(&InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap())
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .whitelist_contains(WhitelistContainsForm {
+            info_hashes: vec![
+                "0b3aea4adc213ce32295be85d3883a63bca25446".to_string(),
+                "9E0217D0FA71C87332CD8BF9DBEABCB2C2CF3C4D".to_string(),
+            ],
+        })
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<Vec<WhitelistMembership>>().await.unwrap(),
+        vec![
+            WhitelistMembership {
+                info_hash: "0b3aea4adc213ce32295be85d3883a63bca25446".to_string(),
+                whitelisted: false
+            },
+            WhitelistMembership {
+                info_hash: "9E0217D0FA71C87332CD8BF9DBEABCB2C2CF3C4D".to_string(),
+                whitelisted: true
+            },
+        ]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_checking_whether_many_torrents_are_whitelisted_when_an_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .whitelist_contains(WhitelistContainsForm {
+            info_hashes: vec!["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_string(), "INVALID".to_string()],
+        })
+        .await;
+
+    assert_unprocessable_content(
+        response,
+        "Invalid infohash: \"INVALID\", expected a 40 character long hex or a 32 character long base32 string",
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_checking_whether_a_torrent_is_whitelisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    env.tracker
+        .add_torrent_to_whitelist(&InfoHash::from_str(&info_hash).unwrap())
+        .await
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .get_whitelisted_torrent(&info_hash)
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json::<WhitelistMembership>().await.unwrap(),
+        WhitelistMembership {
+            info_hash,
+            whitelisted: true
+        }
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_not_found_when_checking_a_torrent_that_is_not_whitelisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_whitelisted_torrent("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d")
+        .await;
+
+    assert_not_found(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_checking_whether_a_torrent_is_whitelisted_when_the_provided_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    for invalid_infohash in &invalid_infohashes_returning_bad_request() {
+        let response = Client::new(env.get_connection_info())
+            .get_whitelisted_torrent(invalid_infohash)
+            .await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+    }
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_whitelisting_a_torrent() {
     INIT.call_once(|| {
@@ -38,6 +213,30 @@ async fn should_allow_whitelisting_a_torrent() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_whitelisting_a_torrent_using_the_uppercase_hex_or_base32_info_hash() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    for encoded_info_hash in ["9E0217D0FA71C87332CD8BF9DBEABCB2C2CF3C4D", "TYBBPUH2OHEHGMWNRP45X2V4WLBM6PCN"] {
+        let response = Client::new(env.get_connection_info())
+            .whitelist_a_torrent(encoded_info_hash)
+            .await;
+
+        assert_ok(response).await;
+        assert!(env.tracker.is_info_hash_whitelisted(&info_hash).await);
+
+        env.tracker.remove_torrent_from_memory_whitelist(&info_hash).await;
+    }
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_whitelisting_a_torrent_that_has_been_already_whitelisted() {
     INIT.call_once(|| {
@@ -103,6 +302,31 @@ async fn should_fail_when_the_torrent_cannot_be_whitelisted() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_fail_whitelisting_a_torrent_when_the_tracker_runs_without_a_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.database.driver = Driver::None;
+
+    let env = Started::new(&configuration.into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(env.get_connection_info()).whitelist_a_torrent(&info_hash).await;
+
+    assert_persistence_disabled(response).await;
+    assert!(
+        !env.tracker
+            .is_info_hash_whitelisted(&InfoHash::from_str(&info_hash).unwrap())
+            .await
+    );
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_fail_whitelisting_a_torrent_when_the_provided_infohash_is_invalid() {
     INIT.call_once(|| {
@@ -298,3 +522,165 @@ async fn should_fail_when_the_whitelist_cannot_be_reloaded_from_the_database() {
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_adding_a_whitelist_rule() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_whitelist_rule(AddWhitelistRuleForm {
+            pattern: "9E0217*".to_string(),
+            seconds_valid: Some(3600),
+            comment: Some("Community releases".to_string()),
+        })
+        .await;
+
+    let rule = assert_whitelist_rule(response).await;
+
+    assert_eq!(rule.pattern, "9e0217*");
+    assert!(rule.expiry_time.is_some());
+    assert_eq!(rule.comment, Some("Community releases".to_string()));
+    assert!(env
+        .tracker
+        .authorize(&InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap())
+        .await
+        .is_ok());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_whitelist_rule_when_the_pattern_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_whitelist_rule(AddWhitelistRuleForm {
+            pattern: "9e0217".to_string(),
+            seconds_valid: None,
+            comment: None,
+        })
+        .await;
+
+    assert_unprocessable_content(
+        response,
+        "Invalid whitelist rule pattern: \"9e0217\", expected \"*\" or an infohash prefix followed by \"*\"",
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_whitelist_rule_cannot_be_added() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .add_whitelist_rule(AddWhitelistRuleForm {
+            pattern: "*".to_string(),
+            seconds_valid: None,
+            comment: None,
+        })
+        .await;
+
+    assert_failed_to_add_whitelist_rule(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_listing_the_whitelist_rules() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let added_rule = assert_whitelist_rule(
+        api_client
+            .add_whitelist_rule(AddWhitelistRuleForm {
+                pattern: "*".to_string(),
+                seconds_valid: None,
+                comment: Some("Open weekend".to_string()),
+            })
+            .await,
+    )
+    .await;
+
+    let rules = assert_whitelist_rules(api_client.get_whitelist_rules().await).await;
+
+    assert_eq!(rules, vec![added_rule]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_whitelist_rule() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let rule = assert_whitelist_rule(
+        api_client
+            .add_whitelist_rule(AddWhitelistRuleForm {
+                pattern: "*".to_string(),
+                seconds_valid: None,
+                comment: None,
+            })
+            .await,
+    )
+    .await;
+
+    let response = api_client.remove_whitelist_rule(rule.id).await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_whitelist_rules().await.is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_managing_the_whitelist_rules_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_whitelist_rule(AddWhitelistRuleForm {
+            pattern: "*".to_string(),
+            seconds_valid: None,
+            comment: None,
+        })
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .get_whitelist_rules()
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
@@ -305,7 +305,8 @@ mod udp {
             let details = report.details.first().expect("it should have some details");
 
             assert_eq!(details.binding, service.bind_address());
-            assert_eq!(details.result, Ok("Connected".to_string()));
+            assert_eq!(details.result, Ok("Connected and announced".to_string()));
+            assert!(details.last_success.is_some());
 
             assert_eq!(
                 details.info,
@@ -356,8 +357,85 @@ mod udp {
             assert_eq!(details.binding, binding);
             assert_eq!(details.result, Err("Timed Out".to_string()));
             assert_eq!(details.info, format!("checking the udp tracker health check at: {binding}"));
+            assert_eq!(details.last_success, None);
 
             env.stop().await.expect("it should stop the service");
         }
     }
+
+    #[tokio::test]
+    pub(crate) async fn it_should_return_good_health_for_udp_service_bound_to_an_ipv6_address() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.udp_trackers.as_mut().unwrap()[0].bind_address = "[::1]:0".parse().unwrap();
+        let configuration = Arc::new(configuration);
+
+        let service = udp::Started::new(&configuration).await;
+
+        let registar = service.registar.clone();
+
+        {
+            let config = configuration.health_check_api.clone();
+            let env = Started::new(&config.into(), registar).await;
+
+            let response = get(&format!("http://{}/health_check", env.state.binding)).await;
+
+            let report: Report = response
+                .json()
+                .await
+                .expect("it should be able to get the report from the json");
+
+            assert_eq!(report.status, Status::Ok);
+
+            let details = report.details.first().expect("it should have some details");
+
+            assert!(details.binding.socket_addr().is_some_and(|binding| binding.is_ipv6()));
+            assert_eq!(details.result, Ok("Connected and announced".to_string()));
+
+            env.stop().await.expect("it should stop the service");
+        }
+
+        service.stop().await;
+    }
+
+    #[tokio::test]
+    pub(crate) async fn it_should_return_good_health_for_a_private_udp_service_rejecting_the_announce() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let configuration = Arc::new(configuration::ephemeral_private());
+
+        let service = udp::Started::new(&configuration).await;
+
+        let registar = service.registar.clone();
+
+        {
+            let config = configuration.health_check_api.clone();
+            let env = Started::new(&config.into(), registar).await;
+
+            let response = get(&format!("http://{}/health_check", env.state.binding)).await;
+
+            let report: Report = response
+                .json()
+                .await
+                .expect("it should be able to get the report from the json");
+
+            assert_eq!(report.status, Status::Ok);
+
+            let details = report.details.first().expect("it should have some details");
+
+            assert!(details
+                .result
+                .as_ref()
+                .is_ok_and(|message| message.starts_with("Connected and announced (rejected:")));
+
+            env.stop().await.expect("it should stop the service");
+        }
+
+        service.stop().await;
+    }
 }
@@ -32,7 +32,10 @@ pub struct Environment<S> {
 
 impl Environment<Stopped> {
     pub fn new(config: &Arc<HealthCheckApi>, registar: Registar) -> Self {
-        let bind_to = config.bind_address;
+        let bind_to = config
+            .bind_address
+            .socket_addr()
+            .expect("it should be bound to a socket address");
 
         Self {
             registar,
@@ -53,9 +56,18 @@ impl Environment<Stopped> {
         let server = tokio::spawn(async move {
             tracing::debug!(target: HEALTH_CHECK_API_LOG_TARGET, "Starting the server in a spawned task ...");
 
-            server::start(self.state.bind_to, tx_start, rx_halt, register)
-                .await
-                .expect("it should start the health check service");
+            server::start(
+                self.state.bind_to,
+                tx_start,
+                rx_halt,
+                register,
+                Arc::default(),
+                None,
+                None,
+                Arc::default(),
+            )
+            .await
+            .expect("it should start the health check service");
 
             tracing::debug!(target: HEALTH_CHECK_API_LOG_TARGET, "Server started. Sending the binding {} ...", self.state.bind_to);
 
@@ -106,6 +106,34 @@ pub async fn assert_missing_query_params_for_scrape_request_error_response(respo
     );
 }
 
+pub async fn assert_too_many_info_hashes_for_scrape_request_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "Too many info-hashes in scrape request",
+        Location::caller(),
+    );
+}
+
+// Request size errors
+
+pub async fn assert_uri_too_long_error_response(response: Response) {
+    assert_eq!(response.status(), 414);
+
+    assert_bencoded_error(&response.text().await.unwrap(), "request URI too long", Location::caller());
+}
+
+pub async fn assert_headers_too_large_error_response(response: Response) {
+    assert_eq!(response.status(), 431);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "request headers too large",
+        Location::caller(),
+    );
+}
+
 // Other errors
 
 pub async fn assert_torrent_not_in_whitelist_error_response(response: Response) {
@@ -114,6 +142,50 @@ pub async fn assert_torrent_not_in_whitelist_error_response(response: Response)
     assert_bencoded_error(&response.text().await.unwrap(), "is not whitelisted", Location::caller());
 }
 
+pub async fn assert_torrent_removed_from_whitelist_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "was removed from the whitelist",
+        Location::caller(),
+    );
+}
+
+pub async fn assert_torrent_frozen_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "does not accept new peers",
+        Location::caller(),
+    );
+}
+
+pub async fn assert_tracker_in_maintenance_error_response(response: Response, retry_in: u32) {
+    assert_eq!(response.status(), 200);
+
+    let response_text = response.text().await.unwrap();
+
+    assert_bencoded_error(&response_text, "The tracker is in maintenance mode", Location::caller());
+
+    let error = serde_bencode::from_str::<Error>(&response_text).unwrap();
+
+    assert_eq!(error.retry_in, Some(retry_in));
+}
+
+pub async fn assert_key_quota_exceeded_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    let response_text = response.text().await.unwrap();
+
+    assert_bencoded_error(&response_text, "has reached its daily quota", Location::caller());
+
+    let error = serde_bencode::from_str::<Error>(&response_text).unwrap();
+
+    assert!(error.retry_in.is_some_and(|retry_in| retry_in > 0 && retry_in <= 24 * 60));
+}
+
 pub async fn assert_could_not_find_remote_address_on_x_forwarded_for_header_error_response(response: Response) {
     assert_eq!(response.status(), 200);
 
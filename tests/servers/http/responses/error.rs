@@ -4,4 +4,6 @@ use serde::{Deserialize, Serialize};
 pub struct Error {
     #[serde(rename = "failure reason")]
     pub failure_reason: String,
+    #[serde(rename = "retry in", default, skip_serializing_if = "Option::is_none")]
+    pub retry_in: Option<u32>,
 }
@@ -36,6 +36,152 @@ mod for_all_config_modes {
         env.stop().await;
     }
 
+    mod the_health_endpoint {
+        use torrust_tracker::servers::http::v1::handlers::health::Report;
+        use torrust_tracker::servers::http::v1::handlers::health_check::Status;
+        use torrust_tracker_configuration::HealthCheck;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::client::Client;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_return_ok_when_all_the_checks_pass() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral().into()).await;
+
+            let response = Client::new(*env.bind_address()).get("health").await;
+
+            assert_eq!(response.status(), 200);
+
+            let report = response.json::<Report>().await.unwrap();
+            assert_eq!(report.status, Status::Ok);
+            assert_eq!(
+                report.checks.iter().map(|check| check.check).collect::<Vec<_>>(),
+                vec![HealthCheck::Core, HealthCheck::Repository, HealthCheck::Database]
+            );
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_return_service_unavailable_when_the_tracker_is_in_maintenance_mode() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral().into()).await;
+
+            env.tracker.set_maintenance(true);
+
+            let response = Client::new(*env.bind_address()).get("health").await;
+
+            assert_eq!(response.status(), 503);
+
+            let report = response.json::<Report>().await.unwrap();
+            assert_eq!(report.status, Status::Error);
+            assert_eq!(report.checks[0].status, Status::Error);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_be_served_on_the_configured_path_with_the_configured_checks() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            let http_tracker = &mut configuration.http_trackers.as_mut().unwrap()[0];
+            http_tracker.health.path = "/lb/status".to_string();
+            http_tracker.health.checks = vec![HealthCheck::Database];
+
+            let env = Started::new(&configuration.into()).await;
+
+            // The tracker is in maintenance mode, but the core check is not configured
+            env.tracker.set_maintenance(true);
+
+            let response = Client::new(*env.bind_address()).get("lb/status").await;
+
+            assert_eq!(response.status(), 200);
+            assert_eq!(response.json::<Report>().await.unwrap().checks.len(), 1);
+
+            env.stop().await;
+        }
+    }
+
+    mod the_request_size_limits {
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::{assert_headers_too_large_error_response, assert_uri_too_long_error_response};
+        use crate::servers::http::client::Client;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_reject_the_requests_with_a_uri_longer_than_the_maximum() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.http_trackers.as_mut().unwrap()[0].request_limits.max_uri_bytes = 256;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!(
+                    "scrape?{}",
+                    "info_hash=%81%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00&".repeat(4)
+                ))
+                .await;
+
+            assert_uri_too_long_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp_requests_with_uri_too_long, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_reject_the_requests_with_headers_larger_than_the_maximum() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.http_trackers.as_mut().unwrap()[0]
+                .request_limits
+                .max_header_bytes = 256;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .get_with_header("health_check", "x-padding", &"a".repeat(512))
+                .await;
+
+            assert_headers_too_large_error_response(response).await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp_requests_with_headers_too_large, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+    }
+
     mod and_running_on_reverse_proxy {
         use torrust_tracker_test_helpers::configuration;
         use tracing::level_filters::LevelFilter;
@@ -86,6 +232,109 @@ mod for_all_config_modes {
         }
     }
 
+    mod and_running_behind_a_proxy_protocol_load_balancer {
+        use std::net::{IpAddr, SocketAddr};
+        use std::str::FromStr;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpStream;
+        use torrust_tracker::servers::proxy_protocol::encode_v2;
+        use torrust_tracker_primitives::info_hash::InfoHash;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        async fn send_raw_announce(bind_address: SocketAddr, header: &[u8], info_hash: &InfoHash) -> std::io::Result<String> {
+            let announce_query = QueryBuilder::default().with_info_hash(info_hash).query();
+
+            let mut stream = TcpStream::connect(bind_address).await?;
+
+            stream.write_all(header).await?;
+            stream
+                .write_all(
+                    format!("GET /announce?{announce_query} HTTP/1.1\r\nHost: {bind_address}\r\nConnection: close\r\n\r\n")
+                        .as_bytes(),
+                )
+                .await?;
+
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await?;
+
+            Ok(response)
+        }
+
+        #[tokio::test]
+        async fn it_should_assign_to_the_peer_ip_the_client_ip_in_the_proxy_protocol_v1_header() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_with_proxy_protocol().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let response = send_raw_announce(
+                *env.bind_address(),
+                b"PROXY TCP4 150.172.238.178 127.0.0.1 56324 7070\r\n",
+                &info_hash,
+            )
+            .await
+            .unwrap();
+
+            assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+            let peers = env.tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers[0].peer_addr.ip(), IpAddr::from_str("150.172.238.178").unwrap());
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn it_should_assign_to_the_peer_ip_the_client_ip_in_the_proxy_protocol_v2_header() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_with_proxy_protocol().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let header = encode_v2("150.172.238.178:56324".parse().unwrap(), *env.bind_address(), false);
+
+            let response = send_raw_announce(*env.bind_address(), &header, &info_hash).await.unwrap();
+
+            assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+            let peers = env.tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers[0].peer_addr.ip(), IpAddr::from_str("150.172.238.178").unwrap());
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn it_should_close_connections_without_a_proxy_protocol_header() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_with_proxy_protocol().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let response = send_raw_announce(*env.bind_address(), b"", &info_hash).await;
+
+            assert!(response.map_or(true, |response| response.is_empty()));
+            assert!(env.tracker.get_torrent_peers(&info_hash).is_empty());
+
+            env.stop().await;
+        }
+    }
+
     mod receiving_an_announce_request {
 
         // Announce request documentation:
@@ -106,6 +355,8 @@ mod for_all_config_modes {
         use local_ip_address::local_ip;
         use reqwest::{Response, StatusCode};
         use tokio::net::TcpListener;
+        use torrust_tracker::core::torrent::policy::TorrentPolicy;
+        use torrust_tracker_configuration::AnnounceResponsePadding;
         use torrust_tracker_primitives::info_hash::InfoHash;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
@@ -117,6 +368,7 @@ mod for_all_config_modes {
             assert_announce_response, assert_bad_announce_request_error_response, assert_cannot_parse_query_param_error_response,
             assert_cannot_parse_query_params_error_response, assert_compact_announce_response, assert_empty_announce_response,
             assert_is_announce_response, assert_missing_query_params_for_announce_request_error_response,
+            assert_torrent_frozen_error_response, assert_tracker_in_maintenance_error_response,
         };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::announce::{Compact, QueryBuilder};
@@ -152,6 +404,49 @@ mod for_all_config_modes {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_ask_the_client_to_retry_later_when_the_tracker_is_in_maintenance_mode() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral().into()).await;
+
+            env.tracker.set_maintenance(true);
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_tracker_in_maintenance_error_response(response, 10).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_not_accept_new_peers_for_a_frozen_torrent() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            env.tracker
+                .set_torrent_policy(&info_hash, TorrentPolicy::Frozen)
+                .await
+                .unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_info_hash(&info_hash).query())
+                .await;
+
+            assert_torrent_frozen_error_response(response).await;
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_fail_when_the_url_query_component_is_empty() {
             INIT.call_once(|| {
@@ -395,33 +690,39 @@ mod for_all_config_modes {
         }
 
         #[tokio::test]
-        async fn should_fail_when_the_event_param_is_invalid() {
+        async fn should_handle_unknown_event_values_as_regular_announces() {
             INIT.call_once(|| {
                 tracing_stderr_init(LevelFilter::ERROR);
             });
 
-            let env = Started::new(&configuration::ephemeral().into()).await;
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
 
             let mut params = QueryBuilder::default().query().params();
 
-            let invalid_values = [
+            let unknown_values = [
                 "0",
                 "-1",
                 "1.1",
                 "a",
-                "Started",   // It should be lowercase to be valid: `started`
-                "Stopped",   // It should be lowercase to be valid: `stopped`
-                "Completed", // It should be lowercase to be valid: `completed`
+                "Started",   // Events are lowercase: `started`
+                "Stopped",   // Events are lowercase: `stopped`
+                "Completed", // Events are lowercase: `completed`
             ];
 
-            for invalid_value in invalid_values {
-                params.set("event", invalid_value);
+            for unknown_value in unknown_values {
+                params.set("event", unknown_value);
 
                 let response = Client::new(*env.bind_address()).get(&format!("announce?{params}")).await;
 
-                assert_bad_announce_request_error_response(response, "invalid param value").await;
+                assert_eq!(response.status(), 200);
             }
 
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp_announces_with_unknown_event, 7);
+
+            drop(stats);
+
             env.stop().await;
         }
 
@@ -718,6 +1019,36 @@ mod for_all_config_modes {
             compact_announce.is_ok()
         }
 
+        #[tokio::test]
+        async fn should_pad_the_response_to_the_minimum_size_when_the_response_padding_is_enabled() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.announce_response_padding = Some(AnnounceResponsePadding::new(512));
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_compact(Compact::Accepted).query())
+                .await;
+
+            let bytes = response.bytes().await.unwrap();
+
+            assert!(bytes.len() >= 512);
+            assert!(serde_bencode::from_bytes::<responses::announce::DeserializedCompact>(&bytes).is_ok());
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp_announce_responses_under_128_bytes, 1);
+            assert_eq!(stats.tcp_announce_responses_padded, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_increase_the_number_of_tcp4_connections_handled_in_statistics() {
             INIT.call_once(|| {
@@ -780,7 +1111,7 @@ mod for_all_config_modes {
             Client::new(*env.bind_address())
                 .announce(
                     &QueryBuilder::default()
-                        .with_peer_addr(&IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)))
+                        .with_peer_addr(&IpAddr::V6(Ipv6Addr::LOCALHOST))
                         .query(),
                 )
                 .await;
@@ -856,7 +1187,7 @@ mod for_all_config_modes {
             Client::new(*env.bind_address())
                 .announce(
                     &QueryBuilder::default()
-                        .with_peer_addr(&IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)))
+                        .with_peer_addr(&IpAddr::V6(Ipv6Addr::LOCALHOST))
                         .query(),
                 )
                 .await;
@@ -1052,7 +1383,7 @@ mod for_all_config_modes {
         use crate::common::logging::{tracing_stderr_init, INIT};
         use crate::servers::http::asserts::{
             assert_cannot_parse_query_params_error_response, assert_missing_query_params_for_scrape_request_error_response,
-            assert_scrape_response,
+            assert_scrape_response, assert_too_many_info_hashes_for_scrape_request_error_response,
         };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::scrape::QueryBuilder;
@@ -1233,7 +1564,7 @@ mod for_all_config_modes {
         }
 
         #[tokio::test]
-        async fn should_increase_the_number_ot_tcp4_scrape_requests_handled_in_statistics() {
+        async fn should_return_only_one_file_for_duplicate_infohashes() {
             INIT.call_once(|| {
                 tracing_stderr_init(LevelFilter::ERROR);
             });
@@ -1242,17 +1573,20 @@ mod for_all_config_modes {
 
             let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
 
-            Client::new(*env.bind_address())
+            let response = Client::new(*env.bind_address())
                 .scrape(
                     &requests::scrape::QueryBuilder::default()
                         .with_one_info_hash(&info_hash)
+                        .add_info_hash(&info_hash)
                         .query(),
                 )
                 .await;
 
+            assert_scrape_response(response, &scrape::Response::with_one_file(info_hash.bytes(), File::zeroed())).await;
+
             let stats = env.tracker.get_stats().await;
 
-            assert_eq!(stats.tcp4_scrapes_handled, 1);
+            assert_eq!(stats.tcp_scrape_duplicate_info_hashes, 1);
 
             drop(stats);
 
@@ -1260,33 +1594,88 @@ mod for_all_config_modes {
         }
 
         #[tokio::test]
-        async fn should_increase_the_number_ot_tcp6_scrape_requests_handled_in_statistics() {
+        async fn should_fail_when_the_request_exceeds_the_max_number_of_infohashes() {
             INIT.call_once(|| {
                 tracing_stderr_init(LevelFilter::ERROR);
             });
 
-            if TcpListener::bind(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0, 0, 0))
-                .await
-                .is_err()
-            {
-                return; // we cannot bind to a ipv6 socket, so we will skip this test
-            }
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.max_scrape_torrents = 1;
 
-            let env = Started::new(&configuration::ephemeral_ipv6().into()).await;
+            let env = Started::new(&configuration.into()).await;
 
-            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+            let info_hash1 = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+            let info_hash2 = InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap();
 
-            Client::bind(*env.bind_address(), IpAddr::from_str("::1").unwrap())
+            let response = Client::new(*env.bind_address())
                 .scrape(
                     &requests::scrape::QueryBuilder::default()
-                        .with_one_info_hash(&info_hash)
+                        .add_info_hash(&info_hash1)
+                        .add_info_hash(&info_hash2)
                         .query(),
                 )
                 .await;
 
-            let stats = env.tracker.get_stats().await;
+            assert_too_many_info_hashes_for_scrape_request_error_response(response).await;
 
-            assert_eq!(stats.tcp6_scrapes_handled, 1);
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_number_ot_tcp4_scrape_requests_handled_in_statistics() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            Client::new(*env.bind_address())
+                .scrape(
+                    &requests::scrape::QueryBuilder::default()
+                        .with_one_info_hash(&info_hash)
+                        .query(),
+                )
+                .await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp4_scrapes_handled, 1);
+
+            drop(stats);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_number_ot_tcp6_scrape_requests_handled_in_statistics() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            if TcpListener::bind(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 0, 0, 0))
+                .await
+                .is_err()
+            {
+                return; // we cannot bind to a ipv6 socket, so we will skip this test
+            }
+
+            let env = Started::new(&configuration::ephemeral_ipv6().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            Client::bind(*env.bind_address(), IpAddr::from_str("::1").unwrap())
+                .scrape(
+                    &requests::scrape::QueryBuilder::default()
+                        .with_one_info_hash(&info_hash)
+                        .query(),
+                )
+                .await;
+
+            let stats = env.tracker.get_stats().await;
+
+            assert_eq!(stats.tcp6_scrapes_handled, 1);
 
             drop(stats);
 
@@ -1298,14 +1687,19 @@ mod for_all_config_modes {
 mod configured_as_whitelisted {
 
     mod and_receiving_an_announce_request {
+        use std::collections::BTreeMap;
+        use std::panic::Location;
         use std::str::FromStr;
 
+        use torrust_tracker_configuration::FailureReasons;
         use torrust_tracker_primitives::info_hash::InfoHash;
         use torrust_tracker_test_helpers::configuration;
         use tracing::level_filters::LevelFilter;
 
         use crate::common::logging::{tracing_stderr_init, INIT};
-        use crate::servers::http::asserts::{assert_is_announce_response, assert_torrent_not_in_whitelist_error_response};
+        use crate::servers::http::asserts::{
+            assert_bencoded_error, assert_is_announce_response, assert_torrent_not_in_whitelist_error_response,
+        };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::announce::QueryBuilder;
         use crate::servers::http::Started;
@@ -1329,6 +1723,43 @@ mod configured_as_whitelisted {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_use_the_configured_failure_reason_template_when_the_torrent_is_not_in_the_whitelist() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_listed();
+            configuration.core.failure_reasons = Some(FailureReasons {
+                language: "en".to_string(),
+                templates: BTreeMap::from([(
+                    "en".to_string(),
+                    BTreeMap::from([(
+                        "E03".to_string(),
+                        "torrent not registered, visit https://example.com/rules".to_string(),
+                    )]),
+                )]),
+            });
+
+            let env = Started::new(&configuration.into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_info_hash(&info_hash).query())
+                .await;
+
+            assert_eq!(response.status(), 200);
+
+            assert_bencoded_error(
+                &response.text().await.unwrap(),
+                "torrent not registered, visit https://example.com/rules",
+                Location::caller(),
+            );
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_allow_announcing_a_whitelisted_torrent() {
             INIT.call_once(|| {
@@ -1364,7 +1795,7 @@ mod configured_as_whitelisted {
         use tracing::level_filters::LevelFilter;
 
         use crate::common::logging::{tracing_stderr_init, INIT};
-        use crate::servers::http::asserts::assert_scrape_response;
+        use crate::servers::http::asserts::{assert_scrape_response, assert_torrent_removed_from_whitelist_error_response};
         use crate::servers::http::client::Client;
         use crate::servers::http::responses::scrape::{File, ResponseBuilder};
         use crate::servers::http::{requests, Started};
@@ -1402,6 +1833,38 @@ mod configured_as_whitelisted {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_reject_the_request_when_the_requested_file_was_removed_from_the_whitelist() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_listed().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            env.tracker
+                .add_torrent_to_whitelist(&info_hash)
+                .await
+                .expect("should add the torrent to the whitelist");
+            env.tracker
+                .remove_torrent_from_whitelist(&info_hash)
+                .await
+                .expect("should remove the torrent from the whitelist");
+
+            let response = Client::new(*env.bind_address())
+                .scrape(
+                    &requests::scrape::QueryBuilder::default()
+                        .with_one_info_hash(&info_hash)
+                        .query(),
+                )
+                .await;
+
+            assert_torrent_removed_from_whitelist_error_response(response).await;
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_return_the_file_stats_when_the_requested_file_is_whitelisted() {
             INIT.call_once(|| {
@@ -1457,13 +1920,19 @@ mod configured_as_private {
         use std::str::FromStr;
         use std::time::Duration;
 
+        use reqwest::StatusCode;
         use torrust_tracker::core::auth::Key;
+        use torrust_tracker::core::key_quota::Quota;
+        use torrust_tracker::core::AddKeyRequest;
+        use torrust_tracker_configuration::KeyPathFormat;
         use torrust_tracker_primitives::info_hash::InfoHash;
         use torrust_tracker_test_helpers::configuration;
         use tracing::level_filters::LevelFilter;
 
         use crate::common::logging::{tracing_stderr_init, INIT};
-        use crate::servers::http::asserts::{assert_authentication_error_response, assert_is_announce_response};
+        use crate::servers::http::asserts::{
+            assert_authentication_error_response, assert_is_announce_response, assert_key_quota_exceeded_error_response,
+        };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::announce::QueryBuilder;
         use crate::servers::http::Started;
@@ -1487,6 +1956,55 @@ mod configured_as_private {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_respond_to_authenticated_peers_using_the_key_prefix_path_format() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_private();
+            configuration.http_trackers.as_mut().unwrap()[0].key_path_format = KeyPathFormat::Prefix;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!(
+                    "{}/announce?{}",
+                    expiring_key.key(),
+                    QueryBuilder::default().query()
+                ))
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_not_accept_the_key_prefix_path_format_by_default() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!(
+                    "{}/announce?{}",
+                    expiring_key.key(),
+                    QueryBuilder::default().query()
+                ))
+                .await;
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_fail_if_the_peer_has_not_provided_the_authentication_key() {
             INIT.call_once(|| {
@@ -1544,6 +2062,99 @@ mod configured_as_private {
 
             env.stop().await;
         }
+
+        #[tokio::test]
+        async fn should_fail_if_the_key_is_bound_to_another_address() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let bound_key = env
+                .tracker
+                .add_peer_key(AddKeyRequest {
+                    opt_key: None,
+                    opt_seconds_valid: None,
+                    opt_bound_to: Some("203.0.113.0/24".to_string()),
+                })
+                .await
+                .unwrap();
+
+            let response = Client::authenticated(*env.bind_address(), bound_key.key())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_authentication_error_response(response).await;
+
+            let announces_with_key_bound_to_another_address =
+                env.tracker.get_stats().await.tcp_announces_with_key_bound_to_another_address;
+
+            assert_eq!(announces_with_key_bound_to_another_address, 1);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_respond_to_peers_using_a_key_bound_to_their_address() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let bound_key = env
+                .tracker
+                .add_peer_key(AddKeyRequest {
+                    opt_key: None,
+                    opt_seconds_valid: None,
+                    opt_bound_to: Some(env.bind_address().ip().to_string()),
+                })
+                .await
+                .unwrap();
+
+            let response = Client::authenticated(*env.bind_address(), bound_key.key())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_ask_the_client_to_retry_later_when_the_key_has_reached_its_daily_quota() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            env.tracker
+                .set_key_quota(
+                    &expiring_key.key(),
+                    Quota {
+                        max_announces_per_day: Some(1),
+                        max_peers_per_day: None,
+                    },
+                )
+                .unwrap();
+
+            let client = Client::authenticated(*env.bind_address(), expiring_key.key());
+
+            let response = client.announce(&QueryBuilder::default().query()).await;
+
+            assert_is_announce_response(response).await;
+
+            let response = client.announce(&QueryBuilder::default().query()).await;
+
+            assert_key_quota_exceeded_error_response(response).await;
+            assert_eq!(env.tracker.get_key_usage(&expiring_key.key()).announces, 1);
+
+            env.stop().await;
+        }
     }
 
     mod receiving_an_scrape_request {
@@ -1700,9 +2311,134 @@ mod configured_as_private {
     }
 }
 
+mod configured_as_hybrid {
+
+    mod and_receiving_an_announce_request {
+        use std::str::FromStr;
+
+        use torrust_tracker::core::auth::Key;
+        use torrust_tracker::core::key_quota::Usage;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::assert_is_announce_response;
+        use crate::servers::http::client::Client;
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_respond_to_peers_without_a_key() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_hybrid().into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_respond_to_authenticated_peers_and_count_the_key_usage() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_hybrid().into()).await;
+
+            let peer_key = env.tracker.generate_permanent_auth_key().await.unwrap();
+
+            let response = Client::authenticated(*env.bind_address(), peer_key.key())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+            assert_eq!(env.tracker.get_key_usage(&peer_key.key()), Usage { announces: 1, peers: 0 });
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_handle_peers_with_an_unregistered_key_as_anonymous_peers() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_hybrid().into()).await;
+
+            // The tracker does not have this key
+            let unregistered_key = Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
+
+            let response = Client::authenticated(*env.bind_address(), unregistered_key.clone())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_is_announce_response(response).await;
+            assert_eq!(env.tracker.get_key_usage(&unregistered_key), Usage::default());
+
+            env.stop().await;
+        }
+    }
+}
+
 mod configured_as_private_and_whitelisted {
 
     mod and_receiving_an_announce_request {}
 
     mod receiving_an_scrape_request {}
 }
+
+mod configured_with_virtual_trackers {
+
+    mod and_receiving_an_announce_request {
+        use torrust_tracker_configuration::VirtualTracker;
+        use torrust_tracker_test_helpers::configuration;
+        use tracing::level_filters::LevelFilter;
+
+        use crate::common::logging::{tracing_stderr_init, INIT};
+        use crate::servers::http::asserts::{assert_authentication_error_response, assert_is_announce_response};
+        use crate::servers::http::client::Client;
+        use crate::servers::http::requests::announce::QueryBuilder;
+        use crate::servers::http::Started;
+
+        #[tokio::test]
+        async fn should_route_the_requests_to_the_virtual_tracker_serving_the_host() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.virtual_trackers = Some(vec![VirtualTracker {
+                name: "private".to_string(),
+                hosts: vec!["private.tracker.test".to_string()],
+                core: configuration::ephemeral_private().core,
+            }]);
+
+            let env = Started::new(&configuration.into()).await;
+
+            let query = QueryBuilder::default().query();
+
+            // The main tracker is public
+            let response = Client::new(*env.bind_address())
+                .announce_with_header(&query, "Host", "public.tracker.test")
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            // The virtual tracker is private
+            let response = Client::new(*env.bind_address())
+                .announce_with_header(&query, "Host", "private.tracker.test:7070")
+                .await;
+
+            assert_authentication_error_response(response).await;
+
+            env.stop().await;
+        }
+    }
+}
@@ -21,3 +21,13 @@ pub fn is_ipv4_announce_response(response: &Response) -> bool {
 pub fn is_scrape_response(response: &Response) -> bool {
     matches!(response, Response::Scrape(_))
 }
+
+pub fn transaction_id_of(response: &Response) -> TransactionId {
+    match response {
+        Response::Connect(response) => response.transaction_id,
+        Response::AnnounceIpv4(response) => response.fixed.transaction_id,
+        Response::AnnounceIpv6(response) => response.fixed.transaction_id,
+        Response::Scrape(response) => response.transaction_id,
+        Response::Error(response) => response.transaction_id,
+    }
+}
@@ -26,7 +26,7 @@ async fn send_connection_request(transaction_id: TransactionId, client: &UdpTrac
     match client.send(connect_request.into()).await {
         Ok(_) => (),
         Err(err) => panic!("{err}"),
-    };
+    }
 
     let response = match client.receive().await {
         Ok(response) => response,
@@ -55,7 +55,7 @@ async fn should_return_a_bad_request_response_when_the_client_sends_an_empty_req
     match client.client.send(&empty_udp_request()).await {
         Ok(_) => (),
         Err(err) => panic!("{err}"),
-    };
+    }
 
     let response = match client.client.receive().await {
         Ok(response) => response,
@@ -64,7 +64,7 @@ async fn should_return_a_bad_request_response_when_the_client_sends_an_empty_req
 
     let response = Response::parse_bytes(&response, true).unwrap();
 
-    assert!(is_error_response(&response, "bad request"));
+    assert!(is_error_response(&response, "E22: bad request"));
 
     env.stop().await;
 }
@@ -100,7 +100,7 @@ mod receiving_a_connection_request {
         match client.send(connect_request.into()).await {
             Ok(_) => (),
             Err(err) => panic!("{err}"),
-        };
+        }
 
         let response = match client.receive().await {
             Ok(response) => response,
@@ -126,14 +126,12 @@ mod receiving_an_announce_request {
     use tracing::level_filters::LevelFilter;
 
     use crate::common::logging::{tracing_stderr_init, INIT};
-    use crate::servers::udp::asserts::is_ipv4_announce_response;
+    use crate::servers::udp::asserts::{is_error_response, is_ipv4_announce_response};
     use crate::servers::udp::contract::send_connection_request;
     use crate::servers::udp::Started;
 
-    pub async fn send_and_get_announce(tx_id: TransactionId, c_id: ConnectionId, client: &UdpTrackerClient) {
-        // Send announce request
-
-        let announce_request = AnnounceRequest {
+    fn announce_request(tx_id: TransactionId, c_id: ConnectionId, client: &UdpTrackerClient) -> AnnounceRequest {
+        AnnounceRequest {
             connection_id: ConnectionId(c_id.0),
             action_placeholder: AnnounceActionPlaceholder::default(),
             transaction_id: tx_id,
@@ -143,16 +141,22 @@ mod receiving_an_announce_request {
             bytes_uploaded: NumberOfBytes(0i64.into()),
             bytes_left: NumberOfBytes(0i64.into()),
             event: AnnounceEvent::Started.into(),
-            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            ip_address: Ipv4Addr::UNSPECIFIED.into(),
             key: PeerKey::new(0i32),
             peers_wanted: NumberOfPeers(1i32.into()),
             port: Port(client.client.socket.local_addr().unwrap().port().into()),
-        };
+        }
+    }
+
+    pub async fn send_and_get_announce(tx_id: TransactionId, c_id: ConnectionId, client: &UdpTrackerClient) {
+        // Send announce request
+
+        let announce_request = announce_request(tx_id, c_id, client);
 
         match client.send(announce_request.into()).await {
             Ok(_) => (),
             Err(err) => panic!("{err}"),
-        };
+        }
 
         let response = match client.receive().await {
             Ok(response) => response,
@@ -210,17 +214,54 @@ mod receiving_an_announce_request {
 
         env.stop().await;
     }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_tracker_is_in_maintenance_mode() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        env.tracker.set_maintenance(true);
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        match client.send(announce_request(tx_id, c_id, &client).into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        }
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(is_error_response(
+            &response,
+            "E07: tracker server error: The tracker is in maintenance mode, retry in 10 minutes"
+        ));
+
+        env.stop().await;
+    }
 }
 
 mod receiving_an_scrape_request {
     use aquatic_udp_protocol::{ConnectionId, InfoHash, ScrapeRequest, TransactionId};
     use torrust_tracker::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;
-    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_configuration::{DEFAULT_TIMEOUT, MAX_SCRAPE_TORRENTS};
     use torrust_tracker_test_helpers::configuration;
     use tracing::level_filters::LevelFilter;
 
     use crate::common::logging::{tracing_stderr_init, INIT};
-    use crate::servers::udp::asserts::is_scrape_response;
+    use crate::servers::udp::asserts::{is_error_response, is_scrape_response};
     use crate::servers::udp::contract::send_connection_request;
     use crate::servers::udp::Started;
 
@@ -255,7 +296,7 @@ mod receiving_an_scrape_request {
         match client.send(scrape_request.into()).await {
             Ok(_) => (),
             Err(err) => panic!("{err}"),
-        };
+        }
 
         let response = match client.receive().await {
             Ok(response) => response,
@@ -266,4 +307,271 @@ mod receiving_an_scrape_request {
 
         env.stop().await;
     }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_request_exceeds_the_max_number_of_torrents() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.core.max_scrape_torrents = 2;
+
+        let env = Started::new(&configuration.into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let connection_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let scrape_request = ScrapeRequest {
+            connection_id: ConnectionId(connection_id.0),
+            transaction_id: TransactionId::new(123i32),
+            info_hashes: vec![InfoHash([1u8; 20]), InfoHash([2u8; 20]), InfoHash([3u8; 20])],
+        };
+
+        match client.send(scrape_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        }
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(is_error_response(&response, "E23: too many info-hashes in scrape request"));
+
+        env.stop().await;
+    }
+
+    fn info_hashes(number: u16) -> Vec<InfoHash> {
+        (0..number)
+            .map(|i| {
+                let mut info_hash = [0u8; 20];
+                info_hash[..2].copy_from_slice(&i.to_be_bytes());
+                InfoHash(info_hash)
+            })
+            .collect()
+    }
+
+    async fn send_scrape_request_with(number_of_torrents: u16, env: &Started) -> aquatic_udp_protocol::Response {
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let connection_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let scrape_request = ScrapeRequest {
+            connection_id: ConnectionId(connection_id.0),
+            transaction_id: TransactionId::new(123i32),
+            info_hashes: info_hashes(number_of_torrents),
+        };
+
+        match client.send(scrape_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        }
+
+        match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_a_scrape_response_when_the_request_has_the_default_max_number_of_torrents() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let response = send_scrape_request_with(MAX_SCRAPE_TORRENTS, &env).await;
+
+        match response {
+            aquatic_udp_protocol::Response::Scrape(response) => {
+                assert_eq!(response.torrent_stats.len(), usize::from(MAX_SCRAPE_TORRENTS));
+            }
+            response => panic!("expected a scrape response, got {response:?}"),
+        }
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_request_exceeds_the_default_max_number_of_torrents() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let response = send_scrape_request_with(MAX_SCRAPE_TORRENTS + 1, &env).await;
+
+        assert!(is_error_response(&response, "E23: too many info-hashes in scrape request"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_request_has_more_torrents_than_the_parser_returns() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.core.max_scrape_torrents = 300;
+
+        let env = Started::new(&configuration.into()).await;
+
+        let response = send_scrape_request_with(u16::from(u8::MAX) + 1, &env).await;
+
+        assert!(is_error_response(&response, "E23: too many info-hashes in scrape request"));
+
+        env.stop().await;
+    }
+}
+
+mod receiving_pipelined_requests {
+    use aquatic_udp_protocol::{ConnectionId, InfoHash, Request, Response, ScrapeRequest, TransactionId};
+    use torrust_tracker::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;
+    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::asserts::{is_error_response, is_scrape_response, transaction_id_of};
+    use crate::servers::udp::contract::send_connection_request;
+    use crate::servers::udp::Started;
+
+    #[tokio::test]
+    async fn should_answer_each_scrape_request_from_the_same_client_with_its_own_transaction_id() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let connection_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let transaction_ids: Vec<i32> = (1..=10).collect();
+
+        let requests: Vec<Request> = transaction_ids
+            .iter()
+            .map(|transaction_id| {
+                ScrapeRequest {
+                    connection_id: ConnectionId(connection_id.0),
+                    transaction_id: TransactionId::new(*transaction_id),
+                    info_hashes: vec![InfoHash([0u8; 20])],
+                }
+                .into()
+            })
+            .collect();
+
+        let responses = match client.send_pipelined(requests).await {
+            Ok(responses) => responses,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(responses.iter().all(is_scrape_response));
+
+        let mut answered_transaction_ids: Vec<i32> =
+            responses.iter().map(|response| transaction_id_of(response).0.get()).collect();
+        answered_transaction_ids.sort_unstable();
+
+        assert_eq!(answered_transaction_ids, transaction_ids);
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_answer_a_bad_request_with_its_transaction_id() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        // A request with an unknown action
+        let mut bad_request = [0u8; 16];
+        bad_request[8..12].copy_from_slice(&99i32.to_be_bytes());
+        bad_request[12..16].copy_from_slice(&456i32.to_be_bytes());
+
+        match client.client.send(&bad_request).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        }
+
+        let response = match client.client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        let response = Response::parse_bytes(&response, true).unwrap();
+
+        assert!(is_error_response(&response, "E22: bad request"));
+        assert_eq!(transaction_id_of(&response), TransactionId::new(456));
+
+        env.stop().await;
+    }
+}
+
+mod behind_a_proxy_protocol_load_balancer {
+    use aquatic_udp_protocol::{ConnectRequest, Response, TransactionId};
+    use torrust_tracker::servers::proxy_protocol::encode_v2;
+    use torrust_tracker::shared::bit_torrent::tracker::udp::client::UdpTrackerClient;
+    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::Started;
+
+    #[tokio::test]
+    async fn should_handle_requests_prefixed_with_a_proxy_protocol_v2_header() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_with_proxy_protocol().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let header = encode_v2("150.172.238.178:56324".parse().unwrap(), env.bind_address(), true);
+
+        let connect_request = ConnectRequest {
+            transaction_id: TransactionId::new(123),
+        };
+
+        match client.send_with_prefix(&header, connect_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        }
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(matches!(response, Response::Connect(_)));
+
+        env.stop().await;
+    }
 }